@@ -0,0 +1,56 @@
+#!/bin/bash
+# Build the contract once per feature set, optimize each WASM, and print the
+# size of each next to its delta from the full build.
+#
+# Fails if any build exceeds WASM_SIZE_LIMIT_BYTES, or if dropping a feature
+# does not make the WASM smaller (the feature is not actually gating code).
+#
+# Usage: scripts/check-wasm-sizes.sh   (from contracts/predict-iq)
+
+set -euo pipefail
+
+LIMIT="${WASM_SIZE_LIMIT_BYTES:-65536}"
+RELEASE_DIR="target/wasm32-unknown-unknown/release"
+OUT_DIR="target/wasm-sizes"
+mkdir -p "$OUT_DIR"
+
+# name | cargo feature arguments
+FEATURE_SETS=(
+  "full|"
+  "minimal|--no-default-features"
+)
+
+declare -A SIZES
+
+for entry in "${FEATURE_SETS[@]}"; do
+  name="${entry%%|*}"
+  args="${entry#*|}"
+  # shellcheck disable=SC2086
+  cargo build --target wasm32-unknown-unknown --release $args >/dev/null
+  soroban contract optimize \
+    --wasm "$RELEASE_DIR/predict_iq.wasm" \
+    --wasm-out "$OUT_DIR/$name.wasm" >/dev/null
+  SIZES[$name]=$(wc -c < "$OUT_DIR/$name.wasm")
+done
+
+full=${SIZES[full]}
+status=0
+
+printf "%-10s %-28s %10s %10s\n" "build" "features" "bytes" "delta"
+for entry in "${FEATURE_SETS[@]}"; do
+  name="${entry%%|*}"
+  args="${entry#*|}"
+  size=${SIZES[$name]}
+  printf "%-10s %-28s %10d %+10d\n" "$name" "${args:-default}" "$size" "$((size - full))"
+  if [ "$size" -gt "$LIMIT" ]; then
+    echo "  $name exceeds the $LIMIT byte limit" >&2
+    status=1
+  fi
+done
+
+if [ "${SIZES[minimal]}" -ge "$full" ]; then
+  echo "minimal build is not smaller than the full build; check the feature gates" >&2
+  status=1
+fi
+
+exit $status
@@ -163,4 +163,58 @@ pub enum ErrorCode {
     ResolutionDeadlinePassed = 158,
     Overflow = 159,
     InvalidTimeRange = 160,
+
+    /// The oracle answered for a different feed than the market is configured with.
+    OracleFeedMismatch = 161,
+
+    /// A market's captured fee can only be lowered after creation.
+    FeeIncreaseNotAllowed = 162,
+
+    /// The market description is longer than its tier allows.
+    DescriptionTooLong = 163,
+
+    /// The market's resolution deadline is further out than its tier allows.
+    MarketDurationTooLong = 164,
+
+    /// The market category or tags are empty, too long, or too many.
+    InvalidMarketLabels = 167,
+
+    /// The referral balance went unclaimed past the referral expiry and can
+    /// only be swept to protocol revenue.
+    RewardsExpired = 168,
+
+    /// The accepted token list repeats a token, lists the market's own
+    /// token, has a non-positive rate, or is too long.
+    InvalidAcceptedToken = 169,
+
+    /// The market's funding window has not lapsed yet.
+    FundingWindowOpen = 170,
+
+    /// The creator's reputation is below what the requested market tier needs.
+    InsufficientReputation = 173,
+
+    /// The oracle reported a negative publish time.
+    InvalidTimestamp = 174,
+
+    /// A token transfer failed inside the token contract.
+    TransferFailed = 175,
+
+    /// The market counter is at `u64::MAX`; no further ids can be allocated.
+    MarketIdOverflow = 176,
+
+    /// The next market id already has a stored market.
+    MarketIdCollision = 177,
+
+    /// The market token's conversion reserve cannot back the converted
+    /// stake of an accepted-token bet, or a withdrawal would dip into
+    /// stakes it backs.
+    InsufficientConversionReserve = 178,
+
+    /// A position cannot be transferred to the bettor who holds it.
+    InvalidPositionTransfer = 179,
+
+    /// `sweep_expired_referrals` was given more than
+    /// `MAX_REFERRAL_SWEEP_BATCH` referrers, or the expired ones hold more
+    /// than `MAX_REFERRAL_SWEEP_ACCRUALS` unclaimed market accruals.
+    SweepBatchTooLarge = 180,
 }
@@ -1,11 +1,34 @@
 #![no_std]
+#[cfg(test)]
+extern crate std;
 use soroban_sdk::{contract, contractimpl, Address, Env, String, Vec};
 
-mod errors;
+pub mod errors;
+#[cfg(any(test, feature = "testutils"))]
+pub mod mock_oracle;
+#[cfg(any(test, feature = "testutils"))]
+pub mod mock_snapshot_token;
 mod modules;
+pub mod oracle_feed_client;
 pub mod pyth_client;
 mod test;
+mod test_accepted_tokens;
+mod test_claim_records;
+mod test_conservation;
+mod test_dispute_summary;
+mod test_fee_capture;
+mod test_funding;
+mod test_guardian_log;
+mod test_market_metadata;
+mod test_mock_oracle;
+mod test_payout_dust;
 mod test_pyth_integration;
+mod test_referral_clawback;
+mod test_referral_expiry;
+mod test_snapshot_voting;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
+mod testutils_test;
 pub mod types;
 
 use crate::errors::ErrorCode;
@@ -21,6 +44,7 @@ impl PredictIQ {
         if e.storage().persistent().has(&ConfigKey::Admin) {
             return Err(ErrorCode::AlreadyInitialized);
         }
+        admin.require_auth();
 
         admin::set_admin(&e, admin);
         e.storage().persistent().set(&ConfigKey::BaseFee, &base_fee);
@@ -78,7 +102,8 @@ impl PredictIQ {
         )
     }
 
-    pub fn create_market_with_dispute_window(
+    /// `create_market` with a custom dispute window in seconds.
+    pub fn create_market_with_window(
         e: Env,
         creator: Address,
         description: String,
@@ -108,6 +133,82 @@ impl PredictIQ {
         )
     }
 
+    /// `create_market` with a category and tags for off-chain grouping,
+    /// emitted in `mkt_creat` and readable through `get_market_labels`.
+    pub fn create_market_with_labels(
+        e: Env,
+        creator: Address,
+        description: String,
+        options: Vec<String>,
+        deadline: u64,
+        resolution_deadline: u64,
+        oracle_config: crate::types::OracleConfig,
+        tier: crate::types::MarketTier,
+        native_token: Address,
+        parent_id: u64,
+        parent_outcome_idx: u32,
+        labels: crate::types::MarketLabels,
+    ) -> Result<u64, ErrorCode> {
+        crate::modules::markets::create_market_with_labels(
+            &e,
+            creator,
+            description,
+            options,
+            deadline,
+            resolution_deadline,
+            oracle_config,
+            tier,
+            native_token,
+            parent_id,
+            parent_outcome_idx,
+            labels,
+        )
+    }
+
+    /// `create_market` that starts in the Funding status: it opens for
+    /// public bets once the creator has staked `min_liquidity` within
+    /// `funding_window` seconds, and can be cancelled by anyone otherwise.
+    pub fn create_market_with_funding(
+        e: Env,
+        creator: Address,
+        description: String,
+        options: Vec<String>,
+        deadline: u64,
+        resolution_deadline: u64,
+        oracle_config: crate::types::OracleConfig,
+        tier: crate::types::MarketTier,
+        native_token: Address,
+        parent_id: u64,
+        parent_outcome_idx: u32,
+        min_liquidity: i128,
+        funding_window: u64,
+    ) -> Result<u64, ErrorCode> {
+        crate::modules::markets::create_market_with_funding(
+            &e,
+            creator,
+            description,
+            options,
+            deadline,
+            resolution_deadline,
+            oracle_config,
+            tier,
+            native_token,
+            parent_id,
+            parent_outcome_idx,
+            min_liquidity,
+            funding_window,
+        )
+    }
+
+    pub fn get_funding_requirement(e: Env, id: u64) -> Option<crate::types::FundingRequirement> {
+        crate::modules::markets::get_funding_requirement(&e, id)
+    }
+
+    /// Cancel a Funding market whose funding window lapsed unfunded.
+    pub fn cancel_unfunded_market(e: Env, market_id: u64) -> Result<(), ErrorCode> {
+        crate::modules::cancellation::cancel_unfunded_market(&e, market_id)
+    }
+
     pub fn place_bet(
         e: Env,
         bettor: Address,
@@ -141,15 +242,93 @@ impl PredictIQ {
         e: Env,
         bettor: Address,
         market_id: u64,
+        outcome: u32,
         token_address: Address,
     ) -> Result<i128, ErrorCode> {
-        crate::modules::bets::withdraw_refund(&e, bettor, market_id, token_address)
+        crate::modules::bets::withdraw_refund(&e, bettor, market_id, outcome, token_address)
+    }
+
+    /// Parts of a bet placed in the market's accepted tokens, keyed by token.
+    pub fn get_bet_deposits(
+        e: Env,
+        market_id: u64,
+        bettor: Address,
+        outcome: u32,
+    ) -> soroban_sdk::Map<Address, crate::types::TokenDeposit> {
+        crate::modules::bets::get_bet_deposits(&e, market_id, bettor, outcome)
     }
 
     pub fn get_market(e: Env, id: u64) -> Option<crate::types::Market> {
         crate::modules::markets::get_market(&e, id)
     }
 
+    /// Page of markets in id order; `limit` is clamped to `MAX_PAGE_LIMIT`.
+    pub fn get_markets(e: Env, offset: u32, limit: u32) -> Vec<crate::types::Market> {
+        crate::modules::queries::get_markets(&e, offset, limit)
+    }
+
+    /// Page of markets in one status; `limit` is clamped to `MAX_PAGE_LIMIT`.
+    pub fn get_markets_by_status(
+        e: Env,
+        status: crate::types::MarketStatus,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<crate::types::Market> {
+        crate::modules::queries::get_markets_by_status(&e, status, offset, limit)
+    }
+
+    /// Just the market's description, for indexers that only need the text
+    /// and would rather not decode the whole `Market`.
+    pub fn get_market_description(e: Env, id: u64) -> Option<String> {
+        crate::modules::markets::get_market(&e, id).map(|m| m.description)
+    }
+
+    /// Just the market's outcome labels, in outcome index order.
+    pub fn get_market_options(e: Env, id: u64) -> Option<Vec<String>> {
+        crate::modules::markets::get_market(&e, id).map(|m| m.options)
+    }
+
+    /// The market's category and tags; empty when it was created without.
+    pub fn get_market_labels(e: Env, id: u64) -> Option<crate::types::MarketLabels> {
+        crate::modules::markets::get_market_labels(&e, id)
+    }
+
+    /// Let a market take bets in other tokens at fixed rates (admin only).
+    pub fn set_market_accepted_tokens(
+        e: Env,
+        market_id: u64,
+        tokens: Vec<crate::types::AcceptedToken>,
+    ) -> Result<(), ErrorCode> {
+        crate::modules::markets::set_accepted_tokens(&e, market_id, tokens)
+    }
+
+    /// Tokens a market takes bets in besides its own, with their rates.
+    pub fn get_market_accepted_tokens(e: Env, id: u64) -> Vec<crate::types::AcceptedToken> {
+        crate::modules::markets::get_accepted_tokens(&e, id)
+    }
+
+    /// Add the admin's `token` to the reserve that backs accepted-token
+    /// stakes in markets denominated in it (admin only).
+    pub fn fund_conversion_reserve(e: Env, token: Address, amount: i128) -> Result<(), ErrorCode> {
+        crate::modules::bets::fund_conversion_reserve(&e, token, amount)
+    }
+
+    /// Withdraw from the free part of `token`'s conversion reserve (admin only).
+    pub fn withdraw_conversion_reserve(
+        e: Env,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<(), ErrorCode> {
+        crate::modules::bets::withdraw_conversion_reserve(&e, token, amount, recipient)
+    }
+
+    /// Free balance of `token`'s conversion reserve, including losing
+    /// accepted-token deposits it has taken in.
+    pub fn get_conversion_reserve(e: Env, token: Address) -> i128 {
+        crate::modules::bets::get_conversion_reserve(&e, token)
+    }
+
     pub fn cast_vote(
         e: Env,
         voter: Address,
@@ -161,6 +340,36 @@ impl PredictIQ {
         crate::modules::voting::cast_vote(&e, voter, market_id, outcome, weight)
     }
 
+    /// A voter's recorded vote, including whether its weight came from a
+    /// token snapshot or locked tokens.
+    pub fn get_vote(e: Env, market_id: u64, voter: Address) -> Option<crate::types::Vote> {
+        crate::modules::voting::get_vote(&e, market_id, voter)
+    }
+
+    /// Governance tokens `voter` has locked in votes on the market's dispute.
+    pub fn get_locked_balance(e: Env, market_id: u64, voter: Address) -> i128 {
+        crate::modules::voting::get_locked_balance(&e, market_id, voter)
+    }
+
+    /// Per-outcome totals, voter count and resolution source of a disputed
+    /// market. Available once the market is resolved, including after it has
+    /// been pruned.
+    pub fn get_dispute_summary(
+        e: Env,
+        market_id: u64,
+    ) -> Result<crate::types::DisputeSummary, ErrorCode> {
+        crate::modules::voting::get_dispute_summary(&e, market_id)
+    }
+
+    /// The outcome and weight `voter` chose in a resolved dispute.
+    pub fn get_voter_choice(
+        e: Env,
+        market_id: u64,
+        voter: Address,
+    ) -> Result<Option<(u32, i128)>, ErrorCode> {
+        crate::modules::voting::get_voter_choice(&e, market_id, voter)
+    }
+
     pub fn file_dispute(e: Env, disciplinarian: Address, market_id: u64) -> Result<(), ErrorCode> {
         crate::modules::circuit_breaker::require_closed(&e)?;
         crate::modules::disputes::file_dispute(&e, disciplinarian, market_id)
@@ -178,6 +387,15 @@ impl PredictIQ {
         crate::modules::resolution::set_dispute_window_bounds(&e, min_seconds, max_seconds)
     }
 
+    /// Winner count above which a resolved market pays out in Pull mode (admin only).
+    pub fn set_max_push_payout_winners(e: Env, threshold: u32) -> Result<(), ErrorCode> {
+        crate::modules::disputes::set_max_push_payout_winners(&e, threshold)
+    }
+
+    pub fn get_max_push_payout_winners(e: Env) -> u32 {
+        crate::modules::disputes::get_max_push_payout_winners(&e)
+    }
+
     pub fn get_market_dispute_window(e: Env, market_id: u64) -> u64 {
         crate::modules::markets::get_market_dispute_window(&e, market_id)
     }
@@ -199,6 +417,12 @@ impl PredictIQ {
         crate::modules::circuit_breaker::get_threshold(&e)
     }
 
+    /// Current circuit breaker state. Does not apply the Open -> HalfOpen
+    /// cool-down; the next guarded call does.
+    pub fn get_circuit_breaker_state(e: Env) -> crate::types::CircuitBreakerState {
+        crate::modules::circuit_breaker::get_state(&e)
+    }
+
     pub fn set_base_fee(e: Env, amount: i128) -> Result<(), ErrorCode> {
         crate::modules::fees::set_base_fee(&e, amount)
     }
@@ -207,10 +431,43 @@ impl PredictIQ {
         crate::modules::fees::get_base_fee(&e)
     }
 
+    /// Lower a market's creation-time fee (admin only; never raises it).
+    pub fn reduce_market_fee(e: Env, market_id: u64, fee_bps: i128) -> Result<(), ErrorCode> {
+        crate::modules::fees::reduce_market_fee(&e, market_id, fee_bps)
+    }
+
     pub fn get_revenue(e: Env, token: Address) -> i128 {
         crate::modules::fees::get_revenue(&e, token)
     }
 
+    /// Rounding remainder of a resolved market's payouts; see
+    /// `modules::bets::get_market_dust`.
+    pub fn get_market_dust(e: Env, market_id: u64) -> i128 {
+        crate::modules::bets::get_market_dust(&e, market_id)
+    }
+
+    /// What `claim_winnings` would pay `bettor` in the market's token now;
+    /// 0 when there is nothing left to claim.
+    pub fn quote_winnings(e: Env, market_id: u64, bettor: Address) -> i128 {
+        crate::modules::bets::quote_winnings(&e, market_id, bettor)
+    }
+
+    /// Move `from`'s whole unclaimed position on `outcome` to `to`.
+    pub fn transfer_position(
+        e: Env,
+        from: Address,
+        to: Address,
+        market_id: u64,
+        outcome: u32,
+    ) -> Result<(), ErrorCode> {
+        crate::modules::bets::transfer_position(&e, from, to, market_id, outcome)
+    }
+
+    /// Whether `bettor`'s bet on `outcome` of the market has been claimed.
+    pub fn is_outcome_claimed(e: Env, market_id: u64, bettor: Address, outcome: u32) -> bool {
+        crate::modules::bets::is_outcome_claimed(&e, market_id, &bettor, outcome)
+    }
+
     pub fn set_fee_admin(e: Env, fee_admin: Address) -> Result<(), ErrorCode> {
         crate::modules::fees::set_fee_admin(&e, fee_admin)
     }
@@ -231,6 +488,57 @@ impl PredictIQ {
         crate::modules::fees::claim_referral_rewards(&e, &address, &token)
     }
 
+    /// Take back the referral rewards of a cancelled market that its
+    /// cancellation left for later, `MAX_REFERRAL_REVERSAL_BATCH` referrers
+    /// per call. Permissionless. Returns how many referrers are still waiting.
+    pub fn reverse_market_referrals(e: Env, market_id: u64) -> Result<u32, ErrorCode> {
+        crate::modules::fees::continue_referral_reversal(&e, market_id)
+    }
+
+    /// Move the `token` balances of `referrers` left unclaimed past the
+    /// referral expiry into protocol revenue. Permissionless; takes at most
+    /// `MAX_REFERRAL_SWEEP_BATCH` referrers, holding at most
+    /// `MAX_REFERRAL_SWEEP_ACCRUALS` unclaimed market accruals, per call.
+    pub fn sweep_expired_referrals(
+        e: Env,
+        referrers: Vec<Address>,
+        token: Address,
+    ) -> Result<i128, ErrorCode> {
+        crate::modules::fees::sweep_expired_referrals(&e, &referrers, &token)
+    }
+
+    /// Set how many seconds a referral balance stays claimable after its
+    /// last accrual or claim (admin only).
+    pub fn set_referral_expiry(e: Env, seconds: u64) -> Result<(), ErrorCode> {
+        crate::modules::fees::set_referral_expiry(&e, seconds)
+    }
+
+    pub fn get_referral_expiry(e: Env) -> u64 {
+        crate::modules::fees::get_referral_expiry(&e)
+    }
+
+    /// Ledger time of `referrer`'s last accrual or claim in `token`.
+    pub fn get_referral_accrued_at(e: Env, referrer: Address, token: Address) -> Option<u64> {
+        crate::modules::fees::get_referral_accrued_at(&e, referrer, token)
+    }
+
+    /// Referral reward `referrer` has accrued from `market_id`. Zero once the
+    /// market is cancelled and the reward reversed.
+    pub fn get_referral_accrual(e: Env, market_id: u64, referrer: Address) -> i128 {
+        crate::modules::fees::get_referral_accrual(&e, market_id, referrer)
+    }
+
+    /// Referral rewards `referrer` can claim in `token`.
+    pub fn get_referral_balance(e: Env, referrer: Address, token: Address) -> i128 {
+        crate::modules::fees::get_referral_balance(&e, referrer, token)
+    }
+
+    /// Already-claimed rewards from cancelled markets that `referrer` still
+    /// owes in `token`; netted against their future rewards.
+    pub fn get_referral_debt(e: Env, referrer: Address, token: Address) -> i128 {
+        crate::modules::fees::get_referral_debt(&e, referrer, token)
+    }
+
     pub fn set_oracle_result(
         e: Env,
         market_id: u64,
@@ -301,6 +609,16 @@ impl PredictIQ {
         crate::modules::circuit_breaker::unpause(&e)
     }
 
+    /// Up to `limit` of the last 100 guardian and governance actions, from
+    /// sequence number `start` on.
+    pub fn get_guardian_action_log(
+        e: Env,
+        start: u64,
+        limit: u32,
+    ) -> Vec<crate::types::GuardianActionRecord> {
+        crate::modules::guardian_log::get_guardian_action_log(&e, start, limit)
+    }
+
     pub fn get_resolution_metrics(
         e: Env,
         market_id: u64,
@@ -347,6 +665,21 @@ impl PredictIQ {
         crate::modules::markets::get_creation_fee(&e)
     }
 
+    /// Override the outcome, description and duration caps for a market
+    /// tier (admin only).
+    pub fn set_tier_limits(
+        e: Env,
+        tier: crate::types::MarketTier,
+        limits: crate::types::TierLimits,
+    ) -> Result<(), ErrorCode> {
+        crate::modules::markets::set_tier_limits(&e, tier, limits)
+    }
+
+    /// Creation limits currently enforced for `tier`.
+    pub fn get_tier_limits(e: Env, tier: crate::types::MarketTier) -> crate::types::TierLimits {
+        crate::modules::markets::get_tier_limits(&e, &tier)
+    }
+
     /// Issue #507: Set protocol treasury address (admin only)
     pub fn set_protocol_treasury(e: Env, treasury: Address) -> Result<(), ErrorCode> {
         crate::modules::markets::set_protocol_treasury(&e, treasury)
@@ -357,7 +690,25 @@ impl PredictIQ {
         crate::modules::markets::get_protocol_treasury(&e)
     }
 
-    // Governance and Upgrade Functions
+    /// Prune (archive) a resolved market after 30 days grace period
+    pub fn prune_market(e: Env, market_id: u64) -> Result<(), ErrorCode> {
+        crate::modules::markets::prune_market(&e, market_id)
+    }
+
+    pub fn cancel_market_admin(e: Env, market_id: u64) -> Result<(), ErrorCode> {
+        crate::modules::cancellation::cancel_market_admin(&e, market_id)
+    }
+
+    pub fn cancel_market_vote(e: Env, market_id: u64) -> Result<(), ErrorCode> {
+        crate::modules::cancellation::cancel_market_vote(&e, market_id)
+    }
+}
+
+/// Guardian set and upgrade governance. Compiled out without the
+/// `governance` feature, along with its storage and events.
+#[cfg(feature = "governance")]
+#[contractimpl]
+impl PredictIQ {
     pub fn initialize_guardians(
         e: Env,
         guardians: Vec<crate::types::Guardian>,
@@ -386,6 +737,10 @@ impl PredictIQ {
         crate::modules::governance::execute_guardian_removal(&e)
     }
 
+    pub fn get_pending_guardian_removal(e: Env) -> Option<crate::types::PendingGuardianRemoval> {
+        crate::modules::governance::get_pending_guardian_removal(&e)
+    }
+
     pub fn get_guardians(e: Env) -> Vec<crate::types::Guardian> {
         crate::modules::governance::get_guardians(&e)
     }
@@ -425,17 +780,4 @@ impl PredictIQ {
     pub fn emergency_pause(e: Env, voter: Address) -> Result<(), ErrorCode> {
         crate::modules::governance::emergency_pause(&e, voter)
     }
-
-    /// Prune (archive) a resolved market after 30 days grace period
-    pub fn prune_market(e: Env, market_id: u64) -> Result<(), ErrorCode> {
-        crate::modules::markets::prune_market(&e, market_id)
-    }
-
-    pub fn cancel_market_admin(e: Env, market_id: u64) -> Result<(), ErrorCode> {
-        crate::modules::cancellation::cancel_market_admin(&e, market_id)
-    }
-
-    pub fn cancel_market_vote(e: Env, market_id: u64) -> Result<(), ErrorCode> {
-        crate::modules::cancellation::cancel_market_vote(&e, market_id)
-    }
 }
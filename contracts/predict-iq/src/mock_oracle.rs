@@ -0,0 +1,55 @@
+//! Mock outcome-feed oracle for tests.
+//!
+//! Implements [`crate::oracle_feed_client::OutcomeFeedInterface`] so
+//! resolution goes through a real cross-contract call. Tests control what it
+//! answers with [`MockOracle::set_report`] and make it trap with
+//! [`MockOracle::set_trap`]. The stored report is returned for whatever feed
+//! is asked for, which is how a misconfigured oracle answering on the wrong
+//! feed is simulated.
+
+use crate::oracle_feed_client::OutcomeReport;
+use soroban_sdk::{contract, contractimpl, contracttype, Env, String};
+
+#[contracttype]
+enum MockOracleKey {
+    Report,
+    Trap,
+}
+
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    /// Publish `outcome` on `feed_id` at `published_at`.
+    pub fn set_report(env: Env, feed_id: String, outcome: u32, published_at: u64) {
+        env.storage().instance().set(
+            &MockOracleKey::Report,
+            &OutcomeReport {
+                feed_id,
+                outcome,
+                published_at,
+            },
+        );
+    }
+
+    /// Make every subsequent `get_outcome` call trap.
+    pub fn set_trap(env: Env, trap: bool) {
+        env.storage().instance().set(&MockOracleKey::Trap, &trap);
+    }
+
+    pub fn get_outcome(env: Env, _feed_id: String, _market_id: u64) -> OutcomeReport {
+        if env
+            .storage()
+            .instance()
+            .get(&MockOracleKey::Trap)
+            .unwrap_or(false)
+        {
+            panic!("MockOracle: trapped");
+        }
+        env.storage()
+            .instance()
+            .get(&MockOracleKey::Report)
+            .expect("MockOracle: no report published")
+    }
+}
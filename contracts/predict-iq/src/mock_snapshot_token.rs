@@ -0,0 +1,90 @@
+//! Mock snapshot-capable governance token for tests.
+//!
+//! Implements [`crate::modules::voting::SnapshotToken`] alongside the
+//! `balance`/`transfer`/`decimals` subset of the token interface voting
+//! touches, so the locking fallback still works when the snapshot call is
+//! made to trap with [`MockSnapshotToken::set_trap`]. Every balance change
+//! is checkpointed at the current ledger sequence.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+#[contracttype]
+enum MockSnapshotKey {
+    /// `Vec<(ledger, balance)>`, oldest first.
+    Checkpoints(Address),
+    Trap,
+}
+
+#[contract]
+pub struct MockSnapshotToken;
+
+fn checkpoints(env: &Env, id: &Address) -> Vec<(u32, i128)> {
+    env.storage()
+        .persistent()
+        .get(&MockSnapshotKey::Checkpoints(id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn write_balance(env: &Env, id: &Address, balance: i128) {
+    let ledger = env.ledger().sequence();
+    let mut history = checkpoints(env, id);
+    if let Some((last, _)) = history.last() {
+        if last == ledger {
+            history.pop_back();
+        }
+    }
+    history.push_back((ledger, balance));
+    env.storage()
+        .persistent()
+        .set(&MockSnapshotKey::Checkpoints(id.clone()), &history);
+}
+
+#[contractimpl]
+impl MockSnapshotToken {
+    /// Credit `amount` to `to` at the current ledger.
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        write_balance(&env, &to, balance + amount);
+    }
+
+    /// Make every subsequent `balance_at` call trap.
+    pub fn set_trap(env: Env, trap: bool) {
+        env.storage().instance().set(&MockSnapshotKey::Trap, &trap);
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        checkpoints(&env, &id).last().map_or(0, |(_, balance)| balance)
+    }
+
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> i128 {
+        if env
+            .storage()
+            .instance()
+            .get(&MockSnapshotKey::Trap)
+            .unwrap_or(false)
+        {
+            panic!("MockSnapshotToken: trapped");
+        }
+        let mut balance = 0;
+        for (at, value) in checkpoints(&env, &id).iter() {
+            if at > ledger {
+                break;
+            }
+            balance = value;
+        }
+        balance
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        let from_balance = Self::balance(env.clone(), from.clone());
+        assert!(from_balance >= amount, "MockSnapshotToken: insufficient balance");
+        write_balance(&env, &from, from_balance - amount);
+        let to_balance = Self::balance(env.clone(), to.clone());
+        write_balance(&env, &to, to_balance + amount);
+    }
+
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+}
@@ -1,7 +1,31 @@
+//! Bet placement, winnings and refunds.
+//!
+//! # Accepted tokens
+//!
+//! A market may take bets in other tokens at fixed rates into its own token
+//! (see `markets::set_accepted_tokens`). Such a bet is staked at its
+//! converted value and its deposit is recorded per token, so refunds and a
+//! winner's own stake are paid back in the token it was placed in. Winnings
+//! above a winner's own stake are paid in the market's token.
+//!
+//! The contract never swaps. Instead the admin funds a conversion reserve
+//! per token, and an accepted-token bet takes its converted stake out of the
+//! market token's reserve when it is placed, so the market holds its whole
+//! pool in its own token. Refunds and winning deposits give that stake back
+//! to the reserve; a losing deposit is kept by it, in its own token, in
+//! exchange for the stake paid out to the winners.
+//!
+//! Fees stay in the token they were paid in. `Bet::fee_paid` counts only
+//! fees paid in the market's token; refunds on a cancelled market return
+//! the net stake in every token, so no fee is refunded in either case.
+
 use crate::errors::ErrorCode;
 use crate::modules::{markets, sac};
-use crate::types::{Bet, MarketStatus, BET_TTL_HIGH_THRESHOLD, BET_TTL_LOW_THRESHOLD};
-use soroban_sdk::{contracttype, Address, Env};
+use crate::types::{
+    Bet, Market, MarketStatus, TokenDeposit, BET_TTL_HIGH_THRESHOLD, BET_TTL_LOW_THRESHOLD,
+    RATE_SCALE,
+};
+use soroban_sdk::{contracttype, Address, Env, Map};
 
 /// TTL Strategy for per-user bet records (Issue #100)
 ///
@@ -27,23 +51,165 @@ use soroban_sdk::{contracttype, Address, Env};
 ///                         cannot cause the record to expire mid-dispute
 ///   3. withdraw_refund  — same protection for cancelled-market refunds
 ///
-/// Claimed(u64, Address) sentinel records use the same TTL so the
-/// AlreadyClaimed guard remains valid for the full prune grace period.
+/// ClaimedOutcome(u64, Address, u32) sentinel records use the same TTL so the
+/// AlreadyClaimed guard remains valid for the full prune grace period;
+/// `prune_market` leaves them to expire with the bets they guard.
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
-    Bet(u64, Address, u32),         // market_id, bettor, outcome
-    Claimed(u64, Address),          // market_id, bettor — set after claim
+    Bet(u64, Address, u32),            // market_id, bettor, outcome
+    Claimed(u64, Address), // market_id, bettor — pre per-outcome claims; see is_outcome_claimed
+    ClaimedOutcome(u64, Address, u32), // market_id, bettor, outcome — set after claim
     BetReferrer(u64, Address, u32), // market_id, bettor, outcome — referrer at bet time
+    PayoutRemainder(u64),  // market_id — sum of (bet * pool) % winning stake
+    WinnersClaimed(u64),   // market_id — winners paid so far
+    MarketDust(u64),       // market_id — remainder swept into revenue
+    /// Parts of a bet placed in the market's accepted tokens; the rest of
+    /// `Bet::amount` was placed in the market's own token.
+    BetDeposits(u64, Address, u32), // market_id, bettor, outcome -> Map<token, TokenDeposit>
+    OutcomeDeposits(u64, u32), // market_id, outcome -> Map<token, TokenDeposit>
+    MarketDeposits(u64),   // market_id -> Map<token, TokenDeposit>
+    TokenClaimed(u64),     // market_id -> Map<token, i128> paid to winners
+    ConversionReserve(Address), // token -> amount free to back accepted-token stakes
 }
 
 /// Extend the TTL of a bet record to BET_TTL_HIGH_THRESHOLD.
 /// Called at write time and again before any read that could race with expiry.
+/// A missing record is left alone so the read reports it instead of the host
+/// aborting on the extension.
 fn bump_bet_ttl(e: &Env, key: &DataKey) {
+    if e.storage().persistent().has(key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(key, BET_TTL_LOW_THRESHOLD, BET_TTL_HIGH_THRESHOLD);
+    }
+}
+
+/// `amount` of an accepted token in units of the market's token, rounded
+/// down.
+pub(crate) fn to_canonical(amount: i128, rate: i128) -> Result<i128, ErrorCode> {
+    amount
+        .checked_mul(rate)
+        .map(|product| product / RATE_SCALE)
+        .ok_or(ErrorCode::ArithmeticOverflow)
+}
+
+fn get_deposits(e: &Env, key: &DataKey) -> Map<Address, TokenDeposit> {
+    e.storage().persistent().get(key).unwrap_or(Map::new(e))
+}
+
+/// Add (or with negative values, take back) a deposit of `token` under `key`.
+fn adjust_deposit(
+    e: &Env,
+    key: &DataKey,
+    token: &Address,
+    amount: i128,
+    stake: i128,
+) -> Result<(), ErrorCode> {
+    let mut deposits = get_deposits(e, key);
+    let current = deposits.get(token.clone()).unwrap_or(TokenDeposit {
+        amount: 0,
+        stake: 0,
+    });
+    let updated = TokenDeposit {
+        amount: current
+            .amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?,
+        stake: current
+            .stake
+            .checked_add(stake)
+            .ok_or(ErrorCode::ArithmeticOverflow)?,
+    };
+    deposits.set(token.clone(), updated);
+    e.storage().persistent().set(key, &deposits);
+    Ok(())
+}
+
+pub fn get_conversion_reserve(e: &Env, token: Address) -> i128 {
     e.storage()
         .persistent()
-        .extend_ttl(key, BET_TTL_LOW_THRESHOLD, BET_TTL_HIGH_THRESHOLD);
+        .get(&DataKey::ConversionReserve(token))
+        .unwrap_or(0)
+}
+
+/// Add `amount` of `token` to its conversion reserve, or with a negative
+/// amount take it out; fails rather than leave the reserve negative.
+fn adjust_conversion_reserve(e: &Env, token: &Address, amount: i128) -> Result<(), ErrorCode> {
+    let key = DataKey::ConversionReserve(token.clone());
+    let reserve = get_conversion_reserve(e, token.clone())
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if reserve < 0 {
+        return Err(ErrorCode::InsufficientConversionReserve);
+    }
+    e.storage().persistent().set(&key, &reserve);
+    crate::modules::events::emit_conversion_reserve_changed(e, token.clone(), reserve);
+    Ok(())
+}
+
+/// Move `amount` of `token` from the admin into its conversion reserve
+/// (admin only).
+pub fn fund_conversion_reserve(e: &Env, token: Address, amount: i128) -> Result<(), ErrorCode> {
+    crate::modules::admin::require_admin(e)?;
+    if amount <= 0 {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    let admin = crate::modules::admin::get_admin(e).ok_or(ErrorCode::NotAuthorized)?;
+    sac::safe_transfer(e, &token, &admin, &e.current_contract_address(), &amount)?;
+    adjust_conversion_reserve(e, &token, amount)
+}
+
+/// Pay out `amount` of `token` from the free part of its conversion reserve
+/// (admin only). Stakes backing open bets cannot be withdrawn.
+pub fn withdraw_conversion_reserve(
+    e: &Env,
+    token: Address,
+    amount: i128,
+    recipient: Address,
+) -> Result<(), ErrorCode> {
+    crate::modules::admin::require_admin(e)?;
+    if amount <= 0 {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    adjust_conversion_reserve(e, &token, -amount)?;
+    sac::safe_transfer(e, &token, &e.current_contract_address(), &recipient, &amount)
+}
+
+/// Record an accepted-token bet against the bettor, the outcome and the
+/// market.
+fn record_deposit(
+    e: &Env,
+    market_id: u64,
+    bettor: &Address,
+    outcome: u32,
+    token: &Address,
+    amount: i128,
+    stake: i128,
+) -> Result<(), ErrorCode> {
+    let bet_key = DataKey::BetDeposits(market_id, bettor.clone(), outcome);
+    adjust_deposit(e, &bet_key, token, amount, stake)?;
+    bump_bet_ttl(e, &bet_key);
+    adjust_deposit(
+        e,
+        &DataKey::OutcomeDeposits(market_id, outcome),
+        token,
+        amount,
+        stake,
+    )?;
+    adjust_deposit(e, &DataKey::MarketDeposits(market_id), token, amount, stake)
+}
+
+/// A bettor's accepted-token deposits on `outcome`; empty when they only
+/// bet in the market's own token.
+pub fn get_bet_deposits(
+    e: &Env,
+    market_id: u64,
+    bettor: Address,
+    outcome: u32,
+) -> Map<Address, TokenDeposit> {
+    get_deposits(e, &DataKey::BetDeposits(market_id, bettor, outcome))
 }
 
 pub fn place_bet(
@@ -72,8 +238,10 @@ pub fn place_bet(
 
     let mut market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
 
-    if market.status != MarketStatus::Active {
-        return Err(ErrorCode::MarketClosed);
+    match market.status {
+        MarketStatus::Active => {}
+        MarketStatus::Funding => markets::require_funding_bet(e, &market, &bettor)?,
+        _ => return Err(ErrorCode::MarketClosed),
     }
 
     if market.parent_id > 0 {
@@ -96,9 +264,16 @@ pub fn place_bet(
         return Err(ErrorCode::InvalidOutcome);
     }
 
-    if token_address != market.token_address {
-        return Err(ErrorCode::InvalidBetAmount);
-    }
+    // Bets in one of the market's accepted tokens are staked at their value
+    // in the market's token; see `markets::set_accepted_tokens`.
+    let rate = if token_address == market.token_address {
+        None
+    } else {
+        Some(
+            markets::accepted_rate(e, market_id, &token_address)
+                .ok_or(ErrorCode::InvalidBetAmount)?,
+        )
+    };
 
     // Check if user's tokens are frozen for SAC-wrapped assets
     sac::check_token_not_frozen(e, &token_address, &bettor)?;
@@ -114,12 +289,23 @@ pub fn place_bet(
     // Deduct protocol fee from the bet amount before crediting the pool.
     // This ensures total_staked always reflects the net distributable pool,
     // so the parimutuel formula pays out the correct proportional share.
-    let fee = crate::modules::fees::calculate_tiered_fee(e, amount, &market.tier)?;
-    let net_amount = amount - fee;
+    // The fee is kept in the token the bet was placed in.
+    let fee = crate::modules::fees::calculate_market_fee(amount, &market)?;
+    let net_deposit = amount - fee;
+    let net_amount = match rate {
+        Some(rate) => to_canonical(net_deposit, rate)?,
+        None => net_deposit,
+    };
+    if net_amount <= 0 {
+        return Err(ErrorCode::InvalidBetAmount);
+    }
 
     if fee > 0 {
         crate::modules::fees::collect_fee(e, token_address.clone(), fee)?;
     }
+    if rate.is_some() {
+        adjust_conversion_reserve(e, &market.token_address, -net_amount)?;
+    }
 
     let bet_key = DataKey::Bet(market_id, bettor.clone(), outcome);
     let mut existing_bet: Bet = e.storage().persistent().get(&bet_key).unwrap_or(Bet {
@@ -135,26 +321,25 @@ pub fn place_bet(
         .amount
         .checked_add(net_amount)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
-    existing_bet.fee_paid = existing_bet
-        .fee_paid
-        .checked_add(fee)
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if rate.is_none() {
+        existing_bet.fee_paid = existing_bet
+            .fee_paid
+            .checked_add(fee)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
     existing_bet.outcome = outcome;
     market.total_staked = market
         .total_staked
         .checked_add(net_amount)
         .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    let outcome_stake = markets::get_outcome_stake(e, market_id, outcome);
-    markets::set_outcome_stake(
-        e,
-        market_id,
+    let outcome_stake = market.outcome_stakes.get(outcome).unwrap_or(0);
+    market.outcome_stakes.set(
         outcome,
         outcome_stake
             .checked_add(net_amount)
             .ok_or(ErrorCode::ArithmeticOverflow)?,
     );
-    markets::increment_outcome_bet_count(e, market_id, outcome);
 
     // Issue #24: Maintain actual winner count per outcome
     let is_new_bettor = existing_bet.amount == net_amount; // first bet on this outcome
@@ -165,13 +350,25 @@ pub fn place_bet(
 
     e.storage().persistent().set(&bet_key, &existing_bet);
     bump_bet_ttl(e, &bet_key); // Issue #100: ensure record survives full market lifecycle
+    if rate.is_some() {
+        record_deposit(
+            e,
+            market_id,
+            &bettor,
+            outcome,
+            &token_address,
+            net_deposit,
+            net_amount,
+        )?;
+    }
+    markets::activate_if_funded(e, &mut market);
     markets::update_market(e, market);
     markets::bump_market_ttl(e, market_id);
 
     // Track referral reward — 10% of the protocol fee goes to the referrer.
     if let Some(ref r) = referrer {
         if fee > 0 {
-            crate::modules::fees::add_referral_reward(e, r, &token_address, fee)?;
+            crate::modules::fees::add_referral_reward(e, market_id, r, &token_address, fee)?;
         }
         // Store referrer so cancellation can reverse the reward if needed.
         let referrer_key = DataKey::BetReferrer(market_id, bettor.clone(), outcome);
@@ -179,8 +376,12 @@ pub fn place_bet(
         bump_bet_ttl(e, &referrer_key);
     }
 
-    // Emit standardized BetPlaced event
+    // Emit standardized BetPlaced event, in units of the market's token
     // Topics: [BetPlaced, market_id, bettor]
+    let amount = match rate {
+        Some(rate) => to_canonical(amount, rate)?,
+        None => amount,
+    };
     crate::modules::events::emit_bet_placed(e, market_id, bettor, outcome, amount);
 
     Ok(())
@@ -250,7 +451,270 @@ fn internal_claim_amount(
     Ok(amount)
 }
 
-pub fn claim_winnings(e: &Env, bettor: Address, market_id: u64) -> Result<i128, ErrorCode> {
+/// Parimutuel payout: a winning bet's proportional share of the pool.
+///
+/// `winnings = (bet_amount * total_staked) / winning_outcome_stake`, where
+/// all amounts are net of fees. Integer division truncates down, favouring
+/// the protocol, so the payouts of all winners never sum to more than
+/// `total_staked`. A non-positive `winning_outcome_stake` falls back to
+/// `bet_amount`.
+pub(crate) fn parimutuel_payout(
+    bet_amount: i128,
+    total_staked: i128,
+    winning_outcome_stake: i128,
+) -> Result<i128, ErrorCode> {
+    let winning_outcome_stake = if winning_outcome_stake > 0 {
+        winning_outcome_stake
+    } else {
+        bet_amount
+    };
+
+    // Issue #192: Use checked arithmetic to prevent overflow in high-inflation scenarios
+    bet_amount
+        .checked_mul(total_staked)
+        .and_then(|product| product.checked_div(winning_outcome_stake))
+        .ok_or(ErrorCode::ArithmeticOverflow)
+}
+
+/// Pay out part of a claim or refund in one of the market's accepted tokens.
+fn pay_deposit_token(
+    e: &Env,
+    market_id: u64,
+    bettor: &Address,
+    token: &Address,
+    amount: i128,
+    is_refund: bool,
+) -> Result<(), ErrorCode> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    sac::safe_transfer(e, token, &e.current_contract_address(), bettor, &amount)?;
+    crate::modules::events::emit_rewards_claimed(
+        e,
+        market_id,
+        bettor.clone(),
+        amount,
+        token.clone(),
+        is_refund,
+    );
+    Ok(())
+}
+
+fn sum_stakes(deposits: &Map<Address, TokenDeposit>) -> Result<i128, ErrorCode> {
+    let mut total: i128 = 0;
+    for (_, deposit) in deposits.iter() {
+        total = total
+            .checked_add(deposit.stake)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+    }
+    Ok(total)
+}
+
+/// What claiming a winning bet pays out.
+struct ClaimPayout {
+    /// Paid in the market's token.
+    winnings: i128,
+    /// The winner's own accepted-token deposits, paid back in kind.
+    tokens: Map<Address, i128>,
+}
+
+/// Work out what `bet`, on the winning outcome, pays out.
+///
+/// The payout is the bet's parimutuel share of the whole pool, in units of
+/// the market's token. The parts of the bet placed in accepted tokens are
+/// paid back in those tokens and the rest of the share in the market's
+/// token; the reserve backs the difference (see the module docs).
+fn compute_payout(
+    e: &Env,
+    market: &Market,
+    winning_outcome: u32,
+    bettor: &Address,
+    bet: &Bet,
+) -> Result<ClaimPayout, ErrorCode> {
+    let market_id = market.id;
+    let winning_outcome_stake = markets::get_outcome_stake(e, market_id, winning_outcome);
+    let own_deposits = get_deposits(
+        e,
+        &DataKey::BetDeposits(market_id, bettor.clone(), winning_outcome),
+    );
+
+    let winnings = parimutuel_payout(bet.amount, market.total_staked, winning_outcome_stake)?
+        .checked_sub(sum_stakes(&own_deposits)?)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let mut tokens = Map::new(e);
+    for (token, deposit) in own_deposits.iter() {
+        tokens.set(token, deposit.amount);
+    }
+
+    Ok(ClaimPayout { winnings, tokens })
+}
+
+/// Whether `bettor`'s bet on `outcome` has been claimed.
+///
+/// Claims used to be recorded once per (market, bettor). Such a record only
+/// ever covered the winning outcome, so it is read as a claim of that
+/// outcome; [`claim_winnings`] rewrites it in the per-outcome form.
+pub fn is_outcome_claimed(e: &Env, market_id: u64, bettor: &Address, outcome: u32) -> bool {
+    if e.storage()
+        .persistent()
+        .has(&DataKey::ClaimedOutcome(market_id, bettor.clone(), outcome))
+    {
+        return true;
+    }
+    let winning_outcome = markets::get_market(e, market_id).and_then(|m| m.winning_outcome);
+    winning_outcome == Some(outcome)
+        && e.storage()
+            .persistent()
+            .has(&DataKey::Claimed(market_id, bettor.clone()))
+}
+
+/// Move a market-wide claim record to the per-outcome form.
+fn migrate_legacy_claim(e: &Env, market_id: u64, bettor: &Address, winning_outcome: u32) {
+    let legacy_key = DataKey::Claimed(market_id, bettor.clone());
+    if !e.storage().persistent().has(&legacy_key) {
+        return;
+    }
+    let key = DataKey::ClaimedOutcome(market_id, bettor.clone(), winning_outcome);
+    e.storage().persistent().set(&key, &true);
+    bump_bet_ttl(e, &key);
+    e.storage().persistent().remove(&legacy_key);
+}
+
+/// What [`claim_winnings`] would pay `bettor` in the market's token right
+/// now; 0 if the market is not resolved, they have no winning bet, or it is
+/// already claimed.
+pub fn quote_winnings(e: &Env, market_id: u64, bettor: Address) -> i128 {
+    let Some(market) = markets::get_market(e, market_id) else {
+        return 0;
+    };
+    if market.status != MarketStatus::Resolved {
+        return 0;
+    }
+    let Some(winning_outcome) = market.winning_outcome else {
+        return 0;
+    };
+    if is_outcome_claimed(e, market_id, &bettor, winning_outcome) {
+        return 0;
+    }
+    let Some(bet) = get_bet(e, market_id, bettor.clone(), winning_outcome) else {
+        return 0;
+    };
+    compute_payout(e, &market, winning_outcome, &bettor, &bet)
+        .map(|payout| payout.winnings)
+        .unwrap_or(0)
+}
+
+/// Move `from`'s whole position on `outcome` of a market to `to`, merging it
+/// into any position `to` already holds there. Works in any market status
+/// as long as the position is still unclaimed and unrefunded, but not while
+/// the contract is paused.
+///
+/// A position received on an outcome `to` has already claimed is claimable
+/// on its own: the claim removed `to`'s bet record, so the new record holds
+/// only the transferred stake and its claim record is cleared.
+pub fn transfer_position(
+    e: &Env,
+    from: Address,
+    to: Address,
+    market_id: u64,
+    outcome: u32,
+) -> Result<(), ErrorCode> {
+    from.require_auth();
+
+    crate::modules::circuit_breaker::require_not_paused_for_high_risk(e)?;
+
+    if from == to {
+        return Err(ErrorCode::InvalidPositionTransfer);
+    }
+
+    let mut market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+
+    let from_key = DataKey::Bet(market_id, from.clone(), outcome);
+    bump_bet_ttl(e, &from_key);
+    let moved: Bet = e
+        .storage()
+        .persistent()
+        .get(&from_key)
+        .ok_or(ErrorCode::BetNotFound)?;
+
+    let to_key = DataKey::Bet(market_id, to.clone(), outcome);
+    let received = match e.storage().persistent().get::<_, Bet>(&to_key) {
+        Some(mut bet) => {
+            bet.amount = bet
+                .amount
+                .checked_add(moved.amount)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            bet.fee_paid = bet
+                .fee_paid
+                .checked_add(moved.fee_paid)
+                .ok_or(ErrorCode::ArithmeticOverflow)?;
+            // Two positions on the outcome become one.
+            let count = market.winner_counts.get(outcome).unwrap_or(0);
+            market.winner_counts.set(outcome, count.saturating_sub(1));
+            bet
+        }
+        None => {
+            if let Some(winning_outcome) = market.winning_outcome {
+                migrate_legacy_claim(e, market_id, &to, winning_outcome);
+            }
+            e.storage()
+                .persistent()
+                .remove(&DataKey::ClaimedOutcome(market_id, to.clone(), outcome));
+            Bet {
+                bettor: to.clone(),
+                ..moved.clone()
+            }
+        }
+    };
+    e.storage().persistent().set(&to_key, &received);
+    bump_bet_ttl(e, &to_key);
+    e.storage().persistent().remove(&from_key);
+
+    let from_deposits_key = DataKey::BetDeposits(market_id, from.clone(), outcome);
+    let deposits = get_deposits(e, &from_deposits_key);
+    if !deposits.is_empty() {
+        let to_deposits_key = DataKey::BetDeposits(market_id, to.clone(), outcome);
+        for (token, deposit) in deposits.iter() {
+            adjust_deposit(e, &to_deposits_key, &token, deposit.amount, deposit.stake)?;
+        }
+        bump_bet_ttl(e, &to_deposits_key);
+        e.storage().persistent().remove(&from_deposits_key);
+    }
+
+    let from_referrer_key = DataKey::BetReferrer(market_id, from.clone(), outcome);
+    if let Some(referrer) = e.storage().persistent().get::<_, Address>(&from_referrer_key) {
+        let to_referrer_key = DataKey::BetReferrer(market_id, to.clone(), outcome);
+        if !e.storage().persistent().has(&to_referrer_key) {
+            e.storage().persistent().set(&to_referrer_key, &referrer);
+            bump_bet_ttl(e, &to_referrer_key);
+        }
+        e.storage().persistent().remove(&from_referrer_key);
+    }
+
+    markets::update_market(e, market);
+    crate::modules::events::emit_position_transferred(
+        e,
+        market_id,
+        from,
+        to,
+        outcome,
+        moved.amount,
+    );
+    Ok(())
+}
+
+/// Claim a winning bet. Returns the amount paid in the market's token; see
+/// [`compute_payout`] for bets placed in accepted tokens.
+///
+/// Claims are recorded per outcome. Only the winning outcome pays, so a
+/// bettor's bets on other outcomes stay unclaimed and untouched.
+pub fn claim_winnings(
+    e: &Env,
+    bettor: Address,
+    market_id: u64,
+    token_address: Address,
+) -> Result<i128, ErrorCode> {
     bettor.require_auth();
 
     let market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
@@ -259,11 +723,16 @@ pub fn claim_winnings(e: &Env, bettor: Address, market_id: u64) -> Result<i128,
         return Err(ErrorCode::MarketNotResolved);
     }
 
+    if token_address != market.token_address {
+        return Err(ErrorCode::InvalidBetAmount);
+    }
+
     let winning_outcome = market.winning_outcome.ok_or(ErrorCode::MarketNotResolved)?;
 
     let bet_key = DataKey::Bet(market_id, bettor.clone(), winning_outcome);
-    let claimed_key = DataKey::Claimed(market_id, bettor.clone());
+    let claimed_key = DataKey::ClaimedOutcome(market_id, bettor.clone(), winning_outcome);
 
+    migrate_legacy_claim(e, market_id, &bettor, winning_outcome);
     if e.storage().persistent().has(&claimed_key) {
         return Err(ErrorCode::AlreadyClaimed);
     }
@@ -282,35 +751,203 @@ pub fn claim_winnings(e: &Env, bettor: Address, market_id: u64) -> Result<i128,
         return Err(ErrorCode::NoWinnings);
     }
 
-    // Parimutuel payout: winner's proportional share of the total pool.
-    // winnings = (bet.amount * total_staked) / winning_outcome_stake
-    // Integer division truncates down, favouring the protocol.
-    let winning_outcome_stake = markets::get_outcome_stake(e, market_id, winning_outcome);
-    let winning_outcome_stake = if winning_outcome_stake > 0 {
-        winning_outcome_stake
-    } else {
-        bet.amount
-    };
+    let payout = compute_payout(e, &market, winning_outcome, &bettor, &bet)?;
 
-    // Issue #192: Use checked arithmetic to prevent overflow in high-inflation scenarios
-    let winnings = bet
-        .amount
-        .checked_mul(market.total_staked)
-        .and_then(|product| product.checked_div(winning_outcome_stake))
-        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    if !payout.tokens.is_empty() {
+        let claimed_tokens_key = DataKey::TokenClaimed(market_id);
+        let mut claimed_tokens: Map<Address, i128> = e
+            .storage()
+            .persistent()
+            .get(&claimed_tokens_key)
+            .unwrap_or(Map::new(e));
+        for (token, amount) in payout.tokens.iter() {
+            pay_deposit_token(e, market_id, &bettor, &token, amount, false)?;
+            let claimed = claimed_tokens.get(token.clone()).unwrap_or(0);
+            claimed_tokens.set(token, claimed + amount);
+        }
+        e.storage()
+            .persistent()
+            .set(&claimed_tokens_key, &claimed_tokens);
+        e.storage().persistent().remove(&DataKey::BetDeposits(
+            market_id,
+            bettor.clone(),
+            winning_outcome,
+        ));
+    }
 
     internal_claim_amount(
         e,
         market_id,
         &bettor,
         &market.token_address,
-        winnings,
+        payout.winnings,
         &bet_key,
         Some(&claimed_key),
         false,
-    )
+    )?;
+
+    let winning_outcome_stake = markets::get_outcome_stake(e, market_id, winning_outcome);
+    track_payout_remainder(
+        e,
+        market_id,
+        bet.amount,
+        market.total_staked,
+        winning_outcome_stake,
+    )?;
+
+    let winners_key = DataKey::WinnersClaimed(market_id);
+    let claimed: u32 = e.storage().persistent().get(&winners_key).unwrap_or(0) + 1;
+    e.storage().persistent().set(&winners_key, &claimed);
+
+    let winners = market.winner_counts.get(winning_outcome).unwrap_or(0);
+    if claimed >= winners {
+        sweep_market_dust(e, market_id)?;
+    }
+
+    Ok(payout.winnings)
 }
 
+/// Rounding policy for winner payouts.
+///
+/// Each winner receives the floor of their proportional share (see
+/// [`parimutuel_payout`]). The discarded fractions are tracked per market as
+/// the sum of `(bet_amount * total_staked) % winning_outcome_stake`; divided
+/// by the winning stake this is the whole-unit dust left behind so far. Once
+/// every winner has claimed, the fractions sum to a multiple of the winning
+/// stake and the dust equals `total_staked - total_claimed` exactly.
+///
+/// Winning accepted-token stakes are paid back in kind rather than out of
+/// the pool, so [`sweep_market_dust`] returns them to the conversion reserve
+/// before counting the dust.
+fn track_payout_remainder(
+    e: &Env,
+    market_id: u64,
+    bet_amount: i128,
+    total_staked: i128,
+    winning_outcome_stake: i128,
+) -> Result<(), ErrorCode> {
+    if winning_outcome_stake <= 0 {
+        return Ok(());
+    }
+    let remainder = bet_amount
+        .checked_mul(total_staked)
+        .map(|product| product % winning_outcome_stake)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+    let key = DataKey::PayoutRemainder(market_id);
+    let total: i128 = e.storage().persistent().get(&key).unwrap_or(0);
+    let total = total
+        .checked_add(remainder)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    e.storage().persistent().set(&key, &total);
+    Ok(())
+}
+
+/// Move whatever the floored payouts left in the pool into protocol revenue.
+/// Runs once, when the last winner claims or, if some never do, when the
+/// market is pruned; the unclaimed winnings are swept with the dust then.
+///
+/// This also settles the market's accepted-token bets with the conversion
+/// reserve: the stakes of winning deposits go back to the market token's
+/// reserve and losing deposits go to their own token's reserve.
+pub(crate) fn sweep_market_dust(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
+    let dust_key = DataKey::MarketDust(market_id);
+    if e.storage().persistent().has(&dust_key) {
+        return Ok(());
+    }
+    let market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+    let market_deposits = get_deposits(e, &DataKey::MarketDeposits(market_id));
+    let winning_deposits = match market.winning_outcome {
+        Some(outcome) => get_deposits(e, &DataKey::OutcomeDeposits(market_id, outcome)),
+        None => Map::new(e),
+    };
+    let winning_stake = sum_stakes(&winning_deposits)?;
+    if winning_stake > 0 {
+        adjust_conversion_reserve(e, &market.token_address, winning_stake)?;
+    }
+    let dust = market
+        .total_staked
+        .checked_sub(winning_stake)
+        .and_then(|pool| pool.checked_sub(market.total_claimed))
+        .ok_or(ErrorCode::ArithmeticOverflow)?
+        .max(0);
+
+    if dust > 0 {
+        crate::modules::fees::collect_fee(e, market.token_address.clone(), dust)?;
+    }
+
+    let claimed_tokens: Map<Address, i128> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::TokenClaimed(market_id))
+        .unwrap_or(Map::new(e));
+    for (token, pooled) in market_deposits.iter() {
+        let winning = winning_deposits
+            .get(token.clone())
+            .map(|d| d.amount)
+            .unwrap_or(0);
+        let losing = pooled.amount - winning;
+        if losing > 0 {
+            adjust_conversion_reserve(e, &token, losing)?;
+        }
+        let unclaimed = winning - claimed_tokens.get(token.clone()).unwrap_or(0);
+        if unclaimed > 0 {
+            crate::modules::fees::collect_fee(e, token, unclaimed)?;
+        }
+    }
+    e.storage().persistent().set(&dust_key, &dust);
+    crate::modules::events::emit_dust_swept(e, market_id, dust);
+    Ok(())
+}
+
+/// Rounding dust for a resolved market: the amount swept into revenue once
+/// every winner has claimed, or before then the whole units already left
+/// behind by floored payouts.
+pub fn get_market_dust(e: &Env, market_id: u64) -> i128 {
+    if let Some(dust) = e
+        .storage()
+        .persistent()
+        .get::<_, i128>(&DataKey::MarketDust(market_id))
+    {
+        return dust;
+    }
+    let Some(market) = markets::get_market(e, market_id) else {
+        return 0;
+    };
+    let Some(winning_outcome) = market.winning_outcome else {
+        return 0;
+    };
+    let winning_outcome_stake = markets::get_outcome_stake(e, market_id, winning_outcome);
+    if winning_outcome_stake <= 0 {
+        return 0;
+    }
+    let remainder: i128 = e
+        .storage()
+        .persistent()
+        .get(&DataKey::PayoutRemainder(market_id))
+        .unwrap_or(0);
+    remainder / winning_outcome_stake
+}
+
+/// Drop a pruned market's claim accounting. Winners who had not claimed by
+/// the prune lose their claim with the market record, and what they were
+/// owed has been swept into revenue; per-bettor bet and claim records are
+/// left to expire on their TTL.
+pub fn prune_claim_state(e: &Env, market_id: u64, num_outcomes: u32) {
+    let storage = e.storage().persistent();
+    storage.remove(&DataKey::PayoutRemainder(market_id));
+    storage.remove(&DataKey::WinnersClaimed(market_id));
+    storage.remove(&DataKey::MarketDust(market_id));
+    storage.remove(&DataKey::TokenClaimed(market_id));
+    storage.remove(&DataKey::MarketDeposits(market_id));
+    for outcome in 0..num_outcomes {
+        storage.remove(&DataKey::OutcomeDeposits(market_id, outcome));
+    }
+}
+
+/// Refund a bet on a cancelled market. Parts placed in accepted tokens are
+/// returned in those tokens; returns the amount refunded in the market's
+/// token.
 pub fn withdraw_refund(
     e: &Env,
     bettor: Address,
@@ -357,8 +994,34 @@ pub fn withdraw_refund(
     market
         .outcome_stakes
         .set(bet_outcome, outcome_stake.saturating_sub(refund_amount));
+    let market_token = market.token_address.clone();
     markets::update_market(e, market);
 
+    let deposits_key = DataKey::BetDeposits(market_id, bettor.clone(), bet_outcome);
+    let deposits = get_deposits(e, &deposits_key);
+    for (token, deposit) in deposits.iter() {
+        adjust_deposit(
+            e,
+            &DataKey::OutcomeDeposits(market_id, bet_outcome),
+            &token,
+            -deposit.amount,
+            -deposit.stake,
+        )?;
+        adjust_deposit(
+            e,
+            &DataKey::MarketDeposits(market_id),
+            &token,
+            -deposit.amount,
+            -deposit.stake,
+        )?;
+        adjust_conversion_reserve(e, &market_token, deposit.stake)?;
+        pay_deposit_token(e, market_id, &bettor, &token, deposit.amount, true)?;
+    }
+    e.storage().persistent().remove(&deposits_key);
+    let refund_amount = refund_amount
+        .checked_sub(sum_stakes(&deposits)?)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+
     internal_claim_amount(
         e,
         market_id,
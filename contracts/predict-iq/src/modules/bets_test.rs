@@ -1,31 +1,18 @@
 #![cfg(test)]
 use crate::errors::ErrorCode;
-use crate::types::{MarketTier, OracleConfig};
-use crate::{PredictIQ, PredictIQClient};
+use crate::testutils::{self, ScenarioBuilder};
+use crate::types::MarketTier;
+use crate::PredictIQClient;
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
-    token, Address, Env, String, Vec,
+    token, Address, Env,
 };
 
 fn setup_test_with_token() -> (Env, PredictIQClient<'static>, Address, Address, Address) {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let contract_id = env.register(PredictIQ, ());
-    let client = PredictIQClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-    client.initialize(&admin, &100);
-
-    let token_admin = Address::generate(&env);
-    let token_id = env.register_stellar_asset_contract_v2(token_admin.clone());
-    let token_address = token_id.address();
-
-    let user = Address::generate(&env);
-    let token_client = token::StellarAssetClient::new(&env, &token_address);
-    token_client.mint(&user, &100_000);
-
-    (env, client, admin, user, token_address)
+    let s = ScenarioBuilder::new().at(0).with_bettors(1, 100_000).build();
+    let user = s.bettor(0);
+    let token = s.token().clone();
+    (s.env, s.client, s.admin, user, token)
 }
 
 fn create_simple_market(
@@ -34,31 +21,14 @@ fn create_simple_market(
     creator: &Address,
     token: &Address,
 ) -> u64 {
-    let options = Vec::from_array(
-        env,
-        [String::from_str(env, "Yes"), String::from_str(env, "No")],
-    );
-
-    let oracle_config = OracleConfig {
-        oracle_address: Address::generate(env),
-        feed_id: String::from_str(env, "test"),
-        min_responses: Some(1),
-        max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
-    };
-
-    client.create_market(
+    let now = env.ledger().timestamp();
+    testutils::create_market(
+        client,
         creator,
-        &String::from_str(env, "Test Market"),
-        &options,
-        &(env.ledger().timestamp() + 1000),
-        &(env.ledger().timestamp() + 2000),
-        &oracle_config,
-        &MarketTier::Basic,
         token,
-        &0,
-        &0,
+        2,
+        (now + 1000, now + 2000),
+        &MarketTier::Basic,
     )
 }
 
@@ -702,67 +672,22 @@ fn test_micro_bets_precise_count_prevents_gas_overflow() {
 /// This test uses 0 fee (base_fee = 0) to match the issue's exact numbers.
 #[test]
 fn test_parimutuel_payout_10_bettors_2_winners() {
-    let env = Env::default();
-    env.mock_all_auths();
-
-    let contract_id = env.register(PredictIQ, ());
-    let client = PredictIQClient::new(&env, &contract_id);
-
-    let admin = Address::generate(&env);
-    // Initialize with 0 fee so net amounts equal gross amounts
-    client.initialize(&admin, &0);
-
-    let token_admin = Address::generate(&env);
-    let token_id = env.register_stellar_asset_contract_v2(token_admin.clone());
-    let token_address = token_id.address();
-
-    env.ledger().set_timestamp(500);
-
-    // Create 10 users, each with 100 XLM
+    // 0 fee so net amounts equal gross amounts; 10 users with 100 XLM each.
     let bet_amount: i128 = 100;
-    let mut users = soroban_sdk::Vec::new(&env);
-    let sac = token::StellarAssetClient::new(&env, &token_address);
-    for _ in 0..10 {
-        let u = Address::generate(&env);
-        sac.mint(&u, &bet_amount);
-        users.push_back(u);
-    }
-
-    let options = soroban_sdk::Vec::from_array(
-        &env,
-        [
-            String::from_str(&env, "Yes"),
-            String::from_str(&env, "No"),
-        ],
-    );
-    let oracle_config = OracleConfig {
-        oracle_address: Address::generate(&env),
-        feed_id: String::from_str(&env, "test"),
-        min_responses: Some(1),
-        max_staleness_seconds: 3600,
-        max_confidence_bps: 100,
-        strike_price: None,
-    };
-    let market_id = client.create_market(
-        &users[0],
-        &String::from_str(&env, "Parimutuel Test"),
-        &options,
-        &(env.ledger().timestamp() + 1000),
-        &(env.ledger().timestamp() + 2000),
-        &oracle_config,
-        &MarketTier::Basic,
-        &token_address,
-        &0,
-        &0,
-    );
+    let s = ScenarioBuilder::new()
+        .with_base_fee(0)
+        .with_bettors(10, bet_amount)
+        .with_market(2, (1_500, 2_500))
+        .build();
+    let (client, market_id, token_address) = (&s.client, s.market_id(), s.token());
 
     // Users 0 and 1 bet on outcome 0 (the winning side)
-    client.place_bet(&users.get(0).unwrap(), &market_id, &0, &bet_amount, &token_address, &None);
-    client.place_bet(&users.get(1).unwrap(), &market_id, &0, &bet_amount, &token_address, &None);
+    client.place_bet(&s.bettor(0), &market_id, &0, &bet_amount, token_address, &None);
+    client.place_bet(&s.bettor(1), &market_id, &0, &bet_amount, token_address, &None);
 
     // Users 2–9 bet on outcome 1 (the losing side)
     for i in 2..10u32 {
-        client.place_bet(&users.get(i).unwrap(), &market_id, &1, &bet_amount, &token_address, &None);
+        client.place_bet(&s.bettor(i), &market_id, &1, &bet_amount, token_address, &None);
     }
 
     // Resolve with outcome 0
@@ -770,16 +695,15 @@ fn test_parimutuel_payout_10_bettors_2_winners() {
 
     // Each winner should receive 500 XLM:
     // winnings = (100 * 1000) / 200 = 500
-    let winnings0 = client.claim_winnings(&users.get(0).unwrap(), &market_id);
-    let winnings1 = client.claim_winnings(&users.get(1).unwrap(), &market_id);
+    let winnings0 = client.claim_winnings(&s.bettor(0), &market_id, token_address);
+    let winnings1 = client.claim_winnings(&s.bettor(1), &market_id, token_address);
 
     assert_eq!(winnings0, 500, "Winner 0 should receive 500 XLM");
     assert_eq!(winnings1, 500, "Winner 1 should receive 500 XLM");
 
     // Verify token balances reflect the payout
-    let token_client = token::Client::new(&env, &token_address);
-    assert_eq!(token_client.balance(&users.get(0).unwrap()), 500);
-    assert_eq!(token_client.balance(&users.get(1).unwrap()), 500);
+    assert_eq!(s.balance(&s.bettor(0)), 500);
+    assert_eq!(s.balance(&s.bettor(1)), 500);
 }
 
 /// Verify that with a non-zero fee, the net pool is correctly distributed.
@@ -0,0 +1,283 @@
+//! Budget regression suite for the hot entrypoints.
+//!
+//! Each scenario is set up under an unlimited budget, then the budget is
+//! reset immediately before the measured call so only that call's CPU
+//! instructions and memory bytes are counted. Every entrypoint must stay
+//! under a committed ceiling (see [`CEILINGS`]) set tight enough that a 2x
+//! regression fails, and far below the network's per-transaction limits.
+//!
+//! The ratio checks in
+//! [`budget_costs_stay_flat_in_pool_size`] catch `claim_winnings` or
+//! `create_market` starting to scale linearly with pool or outcome count.
+//!
+//! Run with `cargo test budget_ -- --nocapture` to print the cost table.
+//!
+//! Scope: `create_market` (2 and 50 outcomes), `place_bet`, `claim_winnings`
+//! (1 and 100 prior bettors), `finalize_resolution` and `prune_market`.
+//! `buy_shares` was part of the original request but the contract has no
+//! such entrypoint; its measurement is tracked separately in `issues.md`.
+#![cfg(test)]
+
+use crate::types::{CreatorReputation, MarketTier, OracleConfig, PRUNE_GRACE_PERIOD};
+use crate::{PredictIQ, PredictIQClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, String as SorobanString, Vec as SorobanVec,
+};
+
+// ---------------------------------------------------------------------------
+// Ceilings
+// ---------------------------------------------------------------------------
+
+/// Network per-transaction limits the ceilings are derived from.
+const NETWORK_CPU_LIMIT: u64 = 100_000_000;
+const NETWORK_MEM_LIMIT: u64 = 41_943_040;
+
+/// Committed (cpu instructions, memory bytes) ceilings per measured call.
+/// Each sits about 1.5x above the cost measured when it was committed, so
+/// an entrypoint that doubles in cost fails the suite. Raising one needs a
+/// reason in the PR that raises it.
+const CEILINGS: &[(&str, u64, u64)] = &[
+    ("create_market (2 outcomes)", 650_000, 270_000),
+    ("create_market (50 outcomes)", 770_000, 350_000),
+    ("place_bet", 1_300_000, 450_000),
+    ("claim_winnings (1 bettor)", 1_800_000, 520_000),
+    ("claim_winnings (100 bettors)", 3_600_000, 1_400_000),
+    ("finalize_resolution", 600_000, 220_000),
+    ("prune_market", 1_000_000, 300_000),
+];
+
+/// A claim among 100 bettors may cost at most this much more than a claim
+/// by the only bettor. Claims read one bet and one outcome stake, so the
+/// pool size only shows up through the test host's storage map, whose
+/// lookups grow with the log of the entries in it (about 2x here). Walking
+/// the bettors would cost far more than this allows.
+const CLAIM_SCALING_LIMIT_PCT: u64 = 300;
+
+/// A 50-outcome market may cost at most this multiple of a 2-outcome one.
+const CREATE_SCALING_LIMIT: u64 = 8;
+
+const DEADLINE: u64 = 1_000;
+const RESOLUTION_DEADLINE: u64 = DEADLINE + 86_400;
+const BET: i128 = 10_000_000;
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+struct Cost {
+    cpu: u64,
+    mem: u64,
+}
+
+/// Run `f` against a freshly reset budget and return what it cost.
+fn measure(env: &Env, f: impl FnOnce()) -> Cost {
+    env.budget().reset_unlimited();
+    f();
+    let cost = Cost {
+        cpu: env.budget().cpu_instruction_cost(),
+        mem: env.budget().memory_bytes_cost(),
+    };
+    env.budget().reset_unlimited();
+    cost
+}
+
+fn setup() -> (Env, PredictIQClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let contract_id = env.register(PredictIQ, ());
+    let client = PredictIQClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &100);
+    // The 50-outcome rows need a tier that allows that many outcomes.
+    client.set_creator_reputation(&admin, &CreatorReputation::Institutional);
+    env.ledger().set_timestamp(0);
+    (env, client, admin)
+}
+
+fn options(env: &Env, count: u32) -> SorobanVec<SorobanString> {
+    let mut options = SorobanVec::new(env);
+    for i in 0..count {
+        let label = std::format!("Outcome {i}");
+        options.push_back(SorobanString::from_str(env, &label));
+    }
+    options
+}
+
+fn oracle(env: &Env) -> OracleConfig {
+    OracleConfig {
+        oracle_address: Address::generate(env),
+        feed_id: SorobanString::from_str(env, "feed"),
+        min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
+    }
+}
+
+fn token(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+/// Create a market with `outcomes` options, returning its id, token and the
+/// cost of the `create_market` call itself.
+fn create_market(
+    env: &Env,
+    client: &PredictIQClient,
+    admin: &Address,
+    outcomes: u32,
+) -> (u64, Address, Cost) {
+    let options = options(env, outcomes);
+    let oracle = oracle(env);
+    let token = token(env);
+    let description = SorobanString::from_str(env, "Budget Market");
+    let mut market_id = 0;
+    let cost = measure(env, || {
+        market_id = client.create_market(
+            admin,
+            &description,
+            &options,
+            &DEADLINE,
+            &RESOLUTION_DEADLINE,
+            &oracle,
+            &MarketTier::Institutional,
+            &token,
+            &0,
+            &0,
+        );
+    });
+    (market_id, token, cost)
+}
+
+fn funded_bettor(env: &Env, token: &Address) -> Address {
+    let bettor = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&bettor, &BET);
+    bettor
+}
+
+/// Cost of one winner's claim after `bettors` bets split across two
+/// outcomes; the measured claimant always bet on the winner.
+fn claim_cost(bettors: u32) -> Cost {
+    let (env, client, admin) = setup();
+    let (market_id, token, _) = create_market(&env, &client, &admin, 2);
+
+    let claimant = funded_bettor(&env, &token);
+    client.place_bet(&claimant, &market_id, &0, &BET, &token, &None);
+    for i in 1..bettors {
+        let bettor = funded_bettor(&env, &token);
+        client.place_bet(&bettor, &market_id, &(i % 2), &BET, &token, &None);
+    }
+    client.resolve_market(&market_id, &0);
+
+    measure(&env, || {
+        client.claim_winnings(&claimant, &market_id, &token);
+    })
+}
+
+fn ceiling(label: &str) -> (u64, u64) {
+    CEILINGS
+        .iter()
+        .find(|(name, _, _)| *name == label)
+        .map(|&(_, cpu, mem)| (cpu, mem))
+        .unwrap_or_else(|| panic!("no ceiling committed for {label}"))
+}
+
+fn print_table(rows: &[(&str, Cost)]) {
+    std::println!(
+        "{:<30} {:>14} {:>14} {:>14} {:>14}",
+        "entrypoint", "cpu", "cpu ceiling", "mem", "mem ceiling"
+    );
+    for (label, cost) in rows {
+        let (cpu, mem) = ceiling(label);
+        std::println!(
+            "{:<30} {:>14} {:>14} {:>14} {:>14}",
+            label, cost.cpu, cpu, cost.mem, mem
+        );
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[test]
+fn budget_ceilings_fit_within_network_limits() {
+    for &(label, cpu, mem) in CEILINGS {
+        assert!(cpu < NETWORK_CPU_LIMIT, "{label}: cpu ceiling {cpu} has no headroom");
+        assert!(mem < NETWORK_MEM_LIMIT, "{label}: mem ceiling {mem} has no headroom");
+    }
+}
+
+#[test]
+fn budget_hot_entrypoints_stay_under_ceilings() {
+    let mut rows: std::vec::Vec<(&str, Cost)> = std::vec::Vec::new();
+
+    let (env, client, admin) = setup();
+    let (_, _, create_two) = create_market(&env, &client, &admin, 2);
+    rows.push(("create_market (2 outcomes)", create_two));
+    let (_, _, create_fifty) = create_market(&env, &client, &admin, 50);
+    rows.push(("create_market (50 outcomes)", create_fifty));
+
+    let (market_id, token, _) = create_market(&env, &client, &admin, 2);
+    let bettor = funded_bettor(&env, &token);
+    let place = measure(&env, || {
+        client.place_bet(&bettor, &market_id, &0, &BET, &token, &None);
+    });
+    rows.push(("place_bet", place));
+
+    rows.push(("claim_winnings (1 bettor)", claim_cost(1)));
+    rows.push(("claim_winnings (100 bettors)", claim_cost(100)));
+
+    // Oracle path: Active → PendingResolution → Resolved, then prune once
+    // the grace period has passed.
+    env.ledger().set_timestamp(RESOLUTION_DEADLINE);
+    client.set_oracle_result(&market_id, &0, &0);
+    client.attempt_oracle_resolution(&market_id);
+    let window = client.get_market_dispute_window(&market_id);
+    env.ledger().set_timestamp(RESOLUTION_DEADLINE + window);
+    let finalize = measure(&env, || client.finalize_resolution(&market_id));
+    rows.push(("finalize_resolution", finalize));
+
+    env.ledger()
+        .set_timestamp(RESOLUTION_DEADLINE + window + PRUNE_GRACE_PERIOD);
+    let prune = measure(&env, || client.prune_market(&market_id));
+    rows.push(("prune_market", prune));
+
+    print_table(&rows);
+    for (label, cost) in &rows {
+        let (cpu, mem) = ceiling(label);
+        assert!(cost.cpu <= cpu, "{label}: cpu {} over ceiling {cpu}", cost.cpu);
+        assert!(cost.mem <= mem, "{label}: mem {} over ceiling {mem}", cost.mem);
+    }
+}
+
+#[test]
+fn budget_costs_stay_flat_in_pool_size() {
+    let alone = claim_cost(1);
+    let crowded = claim_cost(100);
+    assert!(
+        crowded.cpu * 100 <= alone.cpu * CLAIM_SCALING_LIMIT_PCT,
+        "claim cpu grew from {} to {} with 100 bettors",
+        alone.cpu,
+        crowded.cpu
+    );
+    assert!(
+        crowded.mem * 100 <= alone.mem * CLAIM_SCALING_LIMIT_PCT,
+        "claim mem grew from {} to {} with 100 bettors",
+        alone.mem,
+        crowded.mem
+    );
+
+    let (env, client, admin) = setup();
+    let (_, _, two) = create_market(&env, &client, &admin, 2);
+    let (_, _, fifty) = create_market(&env, &client, &admin, 50);
+    assert!(
+        fifty.cpu <= two.cpu * CREATE_SCALING_LIMIT,
+        "create_market cpu {} for 50 outcomes vs {} for 2",
+        fifty.cpu,
+        two.cpu
+    );
+}
@@ -16,7 +16,9 @@ pub fn cancel_market_admin(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     }
 
     market.status = MarketStatus::Cancelled;
+    let token = market.token_address.clone();
     markets::update_market(e, market);
+    crate::modules::fees::reverse_market_referrals(e, market_id, &token)?;
 
     e.events()
         .publish((Symbol::new(e, "market_cancelled"), market_id), ());
@@ -24,6 +26,38 @@ pub fn cancel_market_admin(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     Ok(())
 }
 
+/// Cancel a Funding market whose funding window lapsed before it was seeded.
+/// Anyone can call this; the creator's deposit goes straight back to them and
+/// seed bets are refunded through `withdraw_refund` as on any cancelled market.
+pub fn cancel_unfunded_market(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
+    let mut market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+
+    if market.status != MarketStatus::Funding {
+        return Err(ErrorCode::MarketNotActive);
+    }
+    let funding =
+        markets::get_funding_requirement(e, market_id).ok_or(ErrorCode::MarketNotActive)?;
+    if e.ledger().timestamp() <= funding.funding_deadline {
+        return Err(ErrorCode::FundingWindowOpen);
+    }
+
+    let deposit = market.creation_deposit;
+    let creator = market.creator.clone();
+    let token = market.token_address.clone();
+    let total_staked = market.total_staked;
+    market.status = MarketStatus::Cancelled;
+    market.creation_deposit = 0;
+    markets::update_market(e, market);
+    crate::modules::fees::reverse_market_referrals(e, market_id, &token)?;
+
+    if deposit > 0 {
+        sac::safe_transfer(e, &token, &e.current_contract_address(), &creator, &deposit)?;
+    }
+    crate::modules::events::emit_market_unfunded(e, market_id, total_staked);
+
+    Ok(())
+}
+
 /// Community vote to cancel a market (requires 75% threshold)
 pub fn cancel_market_vote(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     let mut market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
@@ -54,7 +88,9 @@ pub fn cancel_market_vote(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     }
 
     market.status = MarketStatus::Cancelled;
+    let token = market.token_address.clone();
     markets::update_market(e, market);
+    crate::modules::fees::reverse_market_referrals(e, market_id, &token)?;
 
     e.events()
         .publish((Symbol::new(e, "market_cancelled_vote"), market_id), ());
@@ -128,17 +164,9 @@ pub fn withdraw_refund(
     // Reverse the protocol fee revenue so accounting stays consistent.
     crate::modules::fees::reverse_fee(e, market.token_address.clone(), fee_paid);
 
-    // Reverse any referral reward that was credited when this bet was placed.
-    // The referrer only earns rewards from markets that complete — not cancelled ones.
-    if let Some(referrer) =
-        crate::modules::bets::get_bet_referrer(e, market_id, bettor.clone(), outcome)
-    {
-        crate::modules::fees::reverse_referral_reward(
-            e,
-            &referrer,
-            &market.token_address,
-            fee_paid,
-        );
+    // The referral reward from this bet was reversed when the market was
+    // cancelled (fees::reverse_market_referrals); only the record remains.
+    if crate::modules::bets::get_bet_referrer(e, market_id, bettor.clone(), outcome).is_some() {
         crate::modules::bets::remove_bet_referrer(e, market_id, &bettor, outcome);
     }
 
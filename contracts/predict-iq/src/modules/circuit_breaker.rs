@@ -1,7 +1,7 @@
 use crate::errors::ErrorCode;
-use crate::modules::admin;
-use crate::types::{CircuitBreakerState, ConfigKey};
-use soroban_sdk::Env;
+use crate::modules::{admin, guardian_log};
+use crate::types::{CircuitBreakerState, ConfigKey, GuardianAction};
+use soroban_sdk::{Address, Env};
 
 /// Cool-down period before Open transitions to HalfOpen (Issue #12).
 const COOLDOWN_SECONDS: u64 = 6 * 3600; // 6 hours
@@ -105,25 +105,30 @@ pub fn require_closed(e: &Env) -> Result<(), ErrorCode> {
     }
 }
 
-/// Issue #50: Guardian majority can pause without Admin consent.
-pub fn pause(e: &Env) -> Result<(), ErrorCode> {
+/// The guardian if one is set, otherwise the admin, after checking its auth.
+fn require_pauser(e: &Env) -> Result<Address, ErrorCode> {
     if let Some(guardian) = admin::get_guardian(e) {
         guardian.require_auth();
+        Ok(guardian)
     } else {
         admin::require_admin(e)?;
+        Ok(guardian_log::admin_actor(e))
     }
+}
 
-    _set_state_internal(e, CircuitBreakerState::Paused)
+/// Issue #50: Guardian majority can pause without Admin consent.
+pub fn pause(e: &Env) -> Result<(), ErrorCode> {
+    let actor = require_pauser(e)?;
+    _set_state_internal(e, CircuitBreakerState::Paused)?;
+    guardian_log::log_action(e, actor, GuardianAction::Paused);
+    Ok(())
 }
 
 pub fn unpause(e: &Env) -> Result<(), ErrorCode> {
-    if let Some(guardian) = admin::get_guardian(e) {
-        guardian.require_auth();
-    } else {
-        admin::require_admin(e)?;
-    }
-
-    _set_state_internal(e, CircuitBreakerState::Closed)
+    let actor = require_pauser(e)?;
+    _set_state_internal(e, CircuitBreakerState::Closed)?;
+    guardian_log::log_action(e, actor, GuardianAction::Unpaused);
+    Ok(())
 }
 
 pub fn require_not_paused_for_high_risk(e: &Env) -> Result<(), ErrorCode> {
@@ -166,28 +171,37 @@ mod threshold_tests {
     #[test]
     fn default_threshold_returned_when_not_set() {
         let e = Env::default();
-        assert_eq!(get_threshold(&e), DEFAULT_CIRCUIT_BREAKER_THRESHOLD);
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            assert_eq!(get_threshold(&e), DEFAULT_CIRCUIT_BREAKER_THRESHOLD);
+        });
     }
 
     #[test]
     fn admin_can_update_threshold() {
         let e = Env::default();
         e.mock_all_auths();
-        setup_admin(&e);
-        set_threshold(&e, 500_000_000).unwrap();
-        assert_eq!(get_threshold(&e), 500_000_000);
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            setup_admin(&e);
+            set_threshold(&e, 500_000_000).unwrap();
+            assert_eq!(get_threshold(&e), 500_000_000);
+        });
     }
 
     #[test]
     fn threshold_stored_in_instance_storage() {
         let e = Env::default();
         e.mock_all_auths();
-        setup_admin(&e);
-        set_threshold(&e, 42).unwrap();
-        let stored: Option<i128> = e
-            .storage()
-            .instance()
-            .get(&ConfigKey::CircuitBreakerThreshold);
-        assert_eq!(stored, Some(42));
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            setup_admin(&e);
+            set_threshold(&e, 42).unwrap();
+            let stored: Option<i128> = e
+                .storage()
+                .instance()
+                .get(&ConfigKey::CircuitBreakerThreshold);
+            assert_eq!(stored, Some(42));
+        });
     }
 }
@@ -29,6 +29,33 @@ fn test_initial_state_closed() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn test_state_getter_follows_pause_and_unpause() {
+    let (_env, client, _admin, _guardian) = setup();
+    assert_eq!(
+        client.get_circuit_breaker_state(),
+        CircuitBreakerState::Closed
+    );
+
+    client.pause();
+    assert_eq!(
+        client.get_circuit_breaker_state(),
+        CircuitBreakerState::Paused
+    );
+
+    client.unpause();
+    assert_eq!(
+        client.get_circuit_breaker_state(),
+        CircuitBreakerState::Closed
+    );
+
+    client.set_circuit_breaker(&CircuitBreakerState::HalfOpen);
+    assert_eq!(
+        client.get_circuit_breaker_state(),
+        CircuitBreakerState::HalfOpen
+    );
+}
+
 #[test]
 fn test_pause_contract() {
     let (_env, client, _admin, _guardian) = setup();
@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::modules::markets;
-use crate::types::{ConfigKey, MarketStatus};
+use crate::types::{ConfigKey, MarketStatus, PayoutMode, ResolutionSource};
 use soroban_sdk::{contracttype, Address, Env};
 
 #[contracttype]
@@ -30,6 +30,8 @@ pub fn file_dispute(e: &Env, disciplinarian: Address, market_id: u64) -> Result<
 
     market.status = MarketStatus::Disputed;
     market.dispute_timestamp = Some(e.ledger().timestamp());
+    // Votes are weighed by governance token balances as of this ledger.
+    market.dispute_snapshot_ledger = Some(e.ledger().sequence());
     // Extend resolution deadline by the full dispute window duration
     market.resolution_deadline += dispute_window;
     let new_deadline = market.resolution_deadline;
@@ -51,9 +53,33 @@ pub fn resolve_market(e: &Env, market_id: u64, winning_outcome: u32) -> Result<(
         return Err(ErrorCode::InvalidOutcome);
     }
 
+    // A market that never opened for public bets has nothing to resolve.
+    if market.status == MarketStatus::Funding {
+        return Err(ErrorCode::MarketNotActive);
+    }
+
     // payout_mode is intentionally NOT mutated here — it is fixed at creation
     // time and must remain stable throughout PendingResolution and Disputed
     // phases so that gas and distribution path calculations are consistent.
+    // A Push market pays every winner in one call, so it cannot resolve to an
+    // outcome with more winners than the configured cap.
+    if market.payout_mode == PayoutMode::Push
+        && market.winner_counts.get(winning_outcome).unwrap_or(0)
+            > get_max_push_payout_winners(e)
+    {
+        return Err(ErrorCode::TooManyWinners);
+    }
+
+    if market.status == MarketStatus::Disputed {
+        let quorum_reached = crate::modules::resolution::has_voting_majority(e, &market);
+        crate::modules::voting::record_dispute_summary(
+            e,
+            &market,
+            winning_outcome,
+            ResolutionSource::Admin,
+            quorum_reached,
+        );
+    }
 
     market.status = MarketStatus::Resolved;
     market.winning_outcome = Some(winning_outcome);
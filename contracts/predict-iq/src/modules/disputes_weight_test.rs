@@ -75,7 +75,7 @@ fn setup_disputed_market(
         &String::from_str(env, "Dispute Test"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(env),
         &MarketTier::Basic,
         &token,
@@ -223,7 +223,7 @@ fn test_vote_on_active_market_rejected() {
         &String::from_str(&env, "Active Market"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -258,7 +258,7 @@ fn test_vote_without_governance_token_fails() {
         &String::from_str(&env, "No Gov Token"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -296,7 +296,7 @@ fn test_snapshot_ledger_set_at_dispute_time() {
         &String::from_str(&env, "Snapshot Test"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
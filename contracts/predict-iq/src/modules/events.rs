@@ -14,8 +14,8 @@ use soroban_sdk::{symbol_short, Address, Env};
 /// This standardization ensures external indexers can perfectly reconstruct
 /// market states by following a consistent event schema.
 ///
-/// EVENT SCHEMA VERSION: 1
-/// Last Updated: 2026-05-29
+/// EVENT SCHEMA VERSION: 2
+/// Last Updated: 2026-10-16
 ///
 /// Indexer Integration Guide:
 /// 1. Subscribe to contract events using market_id as primary filter
@@ -26,7 +26,7 @@ use soroban_sdk::{symbol_short, Address, Env};
 /// 6. Check the version field before decoding the payload to handle schema changes
 
 /// Current event schema version. Increment this when any event structure changes.
-pub const EVENT_VERSION: u32 = 1;
+pub const EVENT_VERSION: u32 = 2;
 
 pub fn emit_market_created(
     e: &Env,
@@ -35,10 +35,18 @@ pub fn emit_market_created(
     description: soroban_sdk::String,
     num_outcomes: u32,
     deadline: u64,
+    labels: crate::types::MarketLabels,
 ) {
     e.events().publish(
         (symbol_short!("mkt_creat"), market_id, creator),
-        (EVENT_VERSION, description, num_outcomes, deadline),
+        (
+            EVENT_VERSION,
+            description,
+            num_outcomes,
+            deadline,
+            labels.category,
+            labels.tags,
+        ),
     );
 }
 
@@ -83,10 +91,34 @@ pub fn emit_rewards_claimed(
     );
 }
 
-pub fn emit_vote_cast(e: &Env, market_id: u64, voter: Address, outcome: u32, weight: i128) {
+/// Emit PositionTransferred when a bettor's whole position on an outcome
+/// moves to another address. `amount` is the stake moved, in the market's
+/// token.
+pub fn emit_position_transferred(
+    e: &Env,
+    market_id: u64,
+    from: Address,
+    to: Address,
+    outcome: u32,
+    amount: i128,
+) {
+    e.events().publish(
+        (symbol_short!("pos_xfer"), market_id, from),
+        (EVENT_VERSION, to, outcome, amount),
+    );
+}
+
+pub fn emit_vote_cast(
+    e: &Env,
+    market_id: u64,
+    voter: Address,
+    outcome: u32,
+    weight: i128,
+    weight_source: crate::types::VoteWeightSource,
+) {
     e.events().publish(
         (symbol_short!("vote_cast"), market_id, voter),
-        (EVENT_VERSION, outcome, weight),
+        (EVENT_VERSION, outcome, weight, weight_source),
     );
 }
 
@@ -164,6 +196,31 @@ pub fn emit_referral_reward(e: &Env, market_id: u64, referrer: Address, amount:
     );
 }
 
+/// Emit ReferralReversed when a cancelled market's referral reward is taken
+/// back. `owed` is the part the referrer had already claimed, now carried as
+/// a debt against their future rewards.
+pub fn emit_referral_reversed(
+    e: &Env,
+    market_id: u64,
+    referrer: Address,
+    amount: i128,
+    owed: i128,
+) {
+    e.events().publish(
+        (symbol_short!("ref_rvrs"), market_id, referrer),
+        (EVENT_VERSION, amount, owed),
+    );
+}
+
+/// Emit DustSwept when the last winner of a market claims and the rounding
+/// remainder left by floored payouts moves into protocol revenue.
+pub fn emit_dust_swept(e: &Env, market_id: u64, amount: i128) {
+    e.events().publish(
+        (symbol_short!("dust_swp"), market_id),
+        (EVENT_VERSION, amount),
+    );
+}
+
 pub fn emit_referral_claimed(e: &Env, market_id: u64, claimer: Address, amount: i128) {
     e.events().publish(
         (symbol_short!("ref_claim"), market_id, claimer),
@@ -171,6 +228,15 @@ pub fn emit_referral_claimed(e: &Env, market_id: u64, claimer: Address, amount:
     );
 }
 
+/// Emit ReferralSwept when a referrer's expired balance in `token` moves
+/// into protocol revenue.
+pub fn emit_referral_swept(e: &Env, referrer: Address, token: Address, amount: i128) {
+    e.events().publish(
+        (symbol_short!("ref_sweep"), 0u64, referrer),
+        (EVENT_VERSION, token, amount),
+    );
+}
+
 pub fn emit_referral_distribution(e: &Env, market_id: u64, token: Address) {
     e.events().publish(
         (symbol_short!("ref_dist"), market_id, token),
@@ -192,6 +258,50 @@ pub fn emit_fee_collected(e: &Env, _market_id: u64, contract_address: Address, a
     );
 }
 
+/// Emit AcceptedTokensSet when the admin changes the tokens a market takes
+/// bets in besides its own.
+pub fn emit_accepted_tokens_set(e: &Env, market_id: u64, count: u32) {
+    e.events().publish(
+        (symbol_short!("mkt_tokns"), market_id),
+        (EVENT_VERSION, count),
+    );
+}
+
+/// Emit ConversionReserveChanged with the free balance of `token`'s
+/// conversion reserve after it was funded, withdrawn from, or backed or
+/// released an accepted-token stake.
+pub fn emit_conversion_reserve_changed(e: &Env, token: Address, reserve: i128) {
+    e.events().publish(
+        (symbol_short!("conv_rsv"), token),
+        (EVENT_VERSION, reserve),
+    );
+}
+
+/// Emit MarketFunded when a market's seeded stake reaches its funding
+/// requirement and it opens for public bets.
+pub fn emit_market_funded(e: &Env, market_id: u64, total_staked: i128) {
+    e.events().publish(
+        (symbol_short!("mkt_fundd"), market_id),
+        (EVENT_VERSION, total_staked),
+    );
+}
+
+/// Emit MarketUnfunded when a market whose funding window lapsed is cancelled.
+pub fn emit_market_unfunded(e: &Env, market_id: u64, total_staked: i128) {
+    e.events().publish(
+        (symbol_short!("mkt_unfnd"), market_id),
+        (EVENT_VERSION, total_staked),
+    );
+}
+
+/// Emit MarketFeeReduced when the admin lowers a market's captured fee.
+pub fn emit_market_fee_reduced(e: &Env, market_id: u64, previous_fee_bps: i128, fee_bps: i128) {
+    e.events().publish(
+        (symbol_short!("fee_redc"), market_id),
+        (EVENT_VERSION, previous_fee_bps, fee_bps),
+    );
+}
+
 /// Issue #63: Emit AdminFallbackResolution event
 pub fn emit_admin_fallback_resolution(
     e: &Env,
@@ -219,6 +329,23 @@ pub fn emit_creation_deposit_set(e: &Env, old_amount: i128, new_amount: i128) {
     );
 }
 
+/// Emit TierLimitsSet when the admin changes a tier's creation limits.
+pub fn emit_tier_limits_set(
+    e: &Env,
+    tier: crate::types::MarketTier,
+    limits: &crate::types::TierLimits,
+) {
+    e.events().publish(
+        (symbol_short!("tier_set"), tier),
+        (
+            EVENT_VERSION,
+            limits.max_outcomes,
+            limits.max_description_len,
+            limits.max_duration_seconds,
+        ),
+    );
+}
+
 pub fn emit_monitoring_state_reset(
     e: &Env,
     resetter: Address,
@@ -242,6 +369,7 @@ pub fn emit_market_pruned(e: &Env, market_id: u64, pruned_at: u64) {
     );
 }
 
+#[cfg(feature = "governance")]
 pub fn emit_upgrade_initiated(e: &Env, initiator: Address, wasm_hash: soroban_sdk::BytesN<32>) {
     e.events().publish(
         (symbol_short!("upg_init"), initiator),
@@ -249,6 +377,7 @@ pub fn emit_upgrade_initiated(e: &Env, initiator: Address, wasm_hash: soroban_sd
     );
 }
 
+#[cfg(feature = "governance")]
 pub fn emit_upgrade_voted(e: &Env, voter: Address, vote_for: bool) {
     e.events().publish(
         (symbol_short!("upg_vote"), voter),
@@ -256,6 +385,7 @@ pub fn emit_upgrade_voted(e: &Env, voter: Address, vote_for: bool) {
     );
 }
 
+#[cfg(feature = "governance")]
 pub fn emit_upgrade_executed(e: &Env, executor: Address, wasm_hash: soroban_sdk::BytesN<32>) {
     e.events().publish(
         (symbol_short!("upg_exec"), executor),
@@ -263,6 +393,20 @@ pub fn emit_upgrade_executed(e: &Env, executor: Address, wasm_hash: soroban_sdk:
     );
 }
 
+/// Every guardian and governance action, as recorded in the action log.
+pub fn emit_guardian_action(e: &Env, record: &crate::types::GuardianActionRecord) {
+    e.events().publish(
+        (symbol_short!("grd_act"), record.actor.clone()),
+        (
+            EVENT_VERSION,
+            record.seq,
+            record.action.clone(),
+            record.timestamp,
+        ),
+    );
+}
+
+#[cfg(feature = "governance")]
 pub fn emit_upgrade_rejected(e: &Env, wasm_hash: soroban_sdk::BytesN<32>) {
     e.events()
         .publish((symbol_short!("upg_rej"),), (EVENT_VERSION, wasm_hash));
@@ -1,7 +1,11 @@
 use crate::errors::ErrorCode;
-use crate::modules::admin;
-use crate::types::{ConfigKey, MarketTier, TTL_HIGH_THRESHOLD, TTL_LOW_THRESHOLD};
-use soroban_sdk::{contracttype, Address, Env, Symbol};
+use crate::modules::{admin, markets};
+use crate::types::{
+    ConfigKey, Market, MarketStatus, MarketTier, DEFAULT_REFERRAL_EXPIRY,
+    MAX_REFERRAL_REVERSAL_BATCH, MAX_REFERRAL_SWEEP_ACCRUALS, MAX_REFERRAL_SWEEP_BATCH, MAX_UNCLAIMED_ACCRUAL_MARKETS,
+    TTL_HIGH_THRESHOLD, TTL_LOW_THRESHOLD,
+};
+use soroban_sdk::{contracttype, Address, Env, Map, Symbol, Vec};
 
 const BPS_DENOMINATOR: i128 = 10_000;
 const TIER_DENOMINATOR_BPS: i128 = 10_000;
@@ -12,9 +16,27 @@ pub enum DataKey {
     FeeRevenue(Address),
     /// Issue #1: Key is now (referrer, token) to prevent cross-asset mixing.
     ReferrerBalance(Address, Address),
+    /// Reward credited to a referrer from one market, so cancelling that
+    /// market can take it back.
+    ReferralAccrual(u64, Address), // market_id, referrer
+    /// Referrers with an accrual on a market. Once the market is cancelled,
+    /// the referrers whose rewards are still to be reversed.
+    MarketReferrers(u64), // market_id -> Vec<Address>
+    /// Clawback a referrer had already claimed, recovered from their next
+    /// rewards in that token before anything is credited.
+    ReferralDebt(Address, Address), // referrer, token
+    /// Ledger time of the referrer's last accrual or claim in a token. The
+    /// balance expires once this is older than the referral expiry.
+    ReferralAccruedAt(Address, Address), // referrer, token
+    /// Reward credited to the claimable balance per market since the
+    /// referrer's last claim or sweep, so a sweep can drop the accruals it
+    /// took and a later cancellation does not claw them back again. Holds at
+    /// most `MAX_UNCLAIMED_ACCRUAL_MARKETS` markets; see
+    /// [`record_unclaimed_accrual`].
+    UnclaimedAccruals(Address, Address), // referrer, token -> Map<market_id, i128>
 }
 
-fn bump_config_ttl(e: &Env, key: &ConfigKey) {
+pub(crate) fn bump_config_ttl(e: &Env, key: &ConfigKey) {
     e.storage()
         .persistent()
         .extend_ttl(key, TTL_LOW_THRESHOLD, TTL_HIGH_THRESHOLD);
@@ -72,7 +94,7 @@ fn tier_multiplier_bps(tier: &MarketTier) -> i128 {
     }
 }
 
-fn calculate_tiered_fee_with_base(
+pub(crate) fn calculate_tiered_fee_with_base(
     amount: i128,
     base_fee_bps: i128,
     tier: &MarketTier,
@@ -94,6 +116,30 @@ pub fn calculate_tiered_fee(e: &Env, amount: i128, tier: &MarketTier) -> Result<
     calculate_tiered_fee_with_base(amount, base_fee, tier)
 }
 
+/// Fee on `amount` for a bet on `market`, at the fee captured when the
+/// market was created. Later `set_base_fee` calls do not reach open markets.
+pub fn calculate_market_fee(amount: i128, market: &Market) -> Result<i128, ErrorCode> {
+    calculate_tiered_fee_with_base(amount, market.fee_bps, &market.tier)
+}
+
+/// Lower `market_id`'s captured fee to `fee_bps` (admin only). Raising it is
+/// rejected so bettors who joined at one fee are never charged more.
+pub fn reduce_market_fee(e: &Env, market_id: u64, fee_bps: i128) -> Result<(), ErrorCode> {
+    admin::require_admin(e)?;
+    let mut market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+    if fee_bps < 0 {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    if fee_bps > market.fee_bps {
+        return Err(ErrorCode::FeeIncreaseNotAllowed);
+    }
+    let previous_fee_bps = market.fee_bps;
+    market.fee_bps = fee_bps;
+    markets::update_market(e, market);
+    crate::modules::events::emit_market_fee_reduced(e, market_id, previous_fee_bps, fee_bps);
+    Ok(())
+}
+
 pub fn collect_fee(e: &Env, token: Address, amount: i128) -> Result<(), ErrorCode> {
     let key = DataKey::FeeRevenue(token.clone());
     let total: i128 = e.storage().persistent().get(&key).unwrap_or(0);
@@ -156,8 +202,11 @@ pub fn withdraw_protocol_fees(
 }
 
 /// Issue #1: Referral reward keyed by (referrer, token) to prevent cross-asset mixing.
+/// The reward is also recorded against `market_id` so
+/// [`reverse_market_referrals`] can undo it if the market is cancelled.
 pub fn add_referral_reward(
     e: &Env,
+    market_id: u64,
     referrer: &Address,
     token: &Address,
     fee_amount: i128,
@@ -166,33 +215,206 @@ pub fn add_referral_reward(
         .checked_mul(10)
         .and_then(|n| n.checked_div(100))
         .ok_or(ErrorCode::Overflow)?;
+    if reward == 0 {
+        return Ok(());
+    }
+
+    let accrual_key = DataKey::ReferralAccrual(market_id, referrer.clone());
+    let accrued: Option<i128> = e.storage().persistent().get(&accrual_key);
+    if accrued.is_none() {
+        let referrers_key = DataKey::MarketReferrers(market_id);
+        let mut referrers: Vec<Address> = e
+            .storage()
+            .persistent()
+            .get(&referrers_key)
+            .unwrap_or(Vec::new(e));
+        referrers.push_back(referrer.clone());
+        e.storage().persistent().set(&referrers_key, &referrers);
+    }
+    let new_accrual = accrued
+        .unwrap_or(0)
+        .checked_add(reward)
+        .ok_or(ErrorCode::Overflow)?;
+    e.storage().persistent().set(&accrual_key, &new_accrual);
+
+    // Settle any outstanding clawback before crediting the claimable balance.
+    let debt_key = DataKey::ReferralDebt(referrer.clone(), token.clone());
+    let debt: i128 = e.storage().persistent().get(&debt_key).unwrap_or(0);
+    let repaid = debt.min(reward);
+    if repaid > 0 {
+        e.storage().persistent().set(&debt_key, &(debt - repaid));
+    }
+
+    // The reward is the referrer's share of a fee already collected as
+    // revenue, so it moves out of revenue. What settles a debt stays there:
+    // the referrer was paid it when they claimed before the clawback.
+    let credited = reward - repaid;
+    reverse_fee(e, token.clone(), credited);
+
     let key = DataKey::ReferrerBalance(referrer.clone(), token.clone());
     let balance: i128 = e.storage().persistent().get(&key).unwrap_or(0);
-    let new_balance = balance.checked_add(reward).ok_or(ErrorCode::Overflow)?;
+    let new_balance = balance.checked_add(credited).ok_or(ErrorCode::Overflow)?;
     e.storage().persistent().set(&key, &new_balance);
+    touch_referral(e, referrer, token);
+
+    if credited > 0 {
+        record_unclaimed_accrual(e, market_id, referrer, token, credited)?;
+    }
+
+    crate::modules::events::emit_referral_reward(e, market_id, referrer.clone(), reward);
+    Ok(())
+}
+
+/// Add `credited` to `referrer`'s unclaimed accrual from `market_id`.
+///
+/// A new market past `MAX_UNCLAIMED_ACCRUAL_MARKETS` first drops the entries
+/// of markets that can no longer be cancelled (resolved or pruned). If every
+/// entry is still open, the lowest market id is dropped; cancelling that
+/// market after a sweep would then book its swept reward as debt.
+fn record_unclaimed_accrual(
+    e: &Env,
+    market_id: u64,
+    referrer: &Address,
+    token: &Address,
+    credited: i128,
+) -> Result<(), ErrorCode> {
+    let unclaimed_key = DataKey::UnclaimedAccruals(referrer.clone(), token.clone());
+    let mut unclaimed: Map<u64, i128> = e
+        .storage()
+        .persistent()
+        .get(&unclaimed_key)
+        .unwrap_or(Map::new(e));
+
+    if !unclaimed.contains_key(market_id) && unclaimed.len() >= MAX_UNCLAIMED_ACCRUAL_MARKETS {
+        for id in unclaimed.keys().iter() {
+            let open =
+                markets::get_market(e, id).is_some_and(|m| m.status != MarketStatus::Resolved);
+            if !open {
+                unclaimed.remove(id);
+            }
+        }
+        if unclaimed.len() >= MAX_UNCLAIMED_ACCRUAL_MARKETS {
+            if let Some(oldest) = unclaimed.keys().first() {
+                unclaimed.remove(oldest);
+            }
+        }
+    }
 
-    crate::modules::events::emit_referral_reward(e, 0, referrer.clone(), reward);
+    let market_unclaimed = unclaimed
+        .get(market_id)
+        .unwrap_or(0)
+        .checked_add(credited)
+        .ok_or(ErrorCode::Overflow)?;
+    unclaimed.set(market_id, market_unclaimed);
+    e.storage().persistent().set(&unclaimed_key, &unclaimed);
     Ok(())
 }
 
-/// Reverse a referral reward that was credited at bet time.
-/// Called during cancellation refund to void rewards from cancelled markets.
-pub fn reverse_referral_reward(e: &Env, referrer: &Address, token: &Address, fee_amount: i128) {
-    let reward = match fee_amount.checked_mul(10).and_then(|n| n.checked_div(100)) {
-        Some(r) => r,
-        None => return, // overflow on a reversal is a no-op; balance stays as-is
+/// Take back the referral rewards credited from a cancelled market, for the
+/// first `MAX_REFERRAL_REVERSAL_BATCH` referrers still waiting. Returns how
+/// many are left; [`continue_referral_reversal`] takes the next batch.
+///
+/// Unclaimed rewards come straight out of the referrer's balance and go back
+/// to protocol revenue. Whatever they already withdrew, including while they
+/// waited for their batch, is recorded as a [`DataKey::ReferralDebt`] and
+/// recovered from their future rewards in `token`.
+pub fn reverse_market_referrals(
+    e: &Env,
+    market_id: u64,
+    token: &Address,
+) -> Result<u32, ErrorCode> {
+    let referrers_key = DataKey::MarketReferrers(market_id);
+    let referrers: Vec<Address> = match e.storage().persistent().get(&referrers_key) {
+        Some(referrers) => referrers,
+        None => return Ok(0),
     };
-    if reward == 0 {
-        return;
+    let batch = referrers.len().min(MAX_REFERRAL_REVERSAL_BATCH);
+
+    for referrer in referrers.slice(..batch).iter() {
+        let accrual_key = DataKey::ReferralAccrual(market_id, referrer.clone());
+        let accrued: i128 = e.storage().persistent().get(&accrual_key).unwrap_or(0);
+        e.storage().persistent().remove(&accrual_key);
+
+        let unclaimed_key = DataKey::UnclaimedAccruals(referrer.clone(), token.clone());
+        let unclaimed: Option<Map<u64, i128>> = e.storage().persistent().get(&unclaimed_key);
+        if let Some(mut unclaimed) = unclaimed {
+            unclaimed.remove(market_id);
+            e.storage().persistent().set(&unclaimed_key, &unclaimed);
+        }
+
+        if accrued <= 0 {
+            continue;
+        }
+
+        let balance_key = DataKey::ReferrerBalance(referrer.clone(), token.clone());
+        let balance: i128 = e.storage().persistent().get(&balance_key).unwrap_or(0);
+        let recovered = balance.min(accrued);
+        e.storage()
+            .persistent()
+            .set(&balance_key, &(balance - recovered));
+        if recovered > 0 {
+            collect_fee(e, token.clone(), recovered)?;
+        }
+
+        let owed = accrued - recovered;
+        if owed > 0 {
+            let debt_key = DataKey::ReferralDebt(referrer.clone(), token.clone());
+            let debt: i128 = e.storage().persistent().get(&debt_key).unwrap_or(0);
+            e.storage()
+                .persistent()
+                .set(&debt_key, &debt.saturating_add(owed));
+        }
+
+        crate::modules::events::emit_referral_reversed(e, market_id, referrer, accrued, owed);
     }
-    let key = DataKey::ReferrerBalance(referrer.clone(), token.clone());
-    let balance: i128 = e.storage().persistent().get(&key).unwrap_or(0);
-    let new_balance = balance.saturating_sub(reward);
-    e.storage().persistent().set(&key, &new_balance);
+
+    let waiting = referrers.slice(batch..);
+    if waiting.is_empty() {
+        e.storage().persistent().remove(&referrers_key);
+    } else {
+        e.storage().persistent().set(&referrers_key, &waiting);
+    }
+    Ok(waiting.len())
+}
+
+/// Reverse the next batch of a cancelled market's referral rewards. Anyone
+/// may call it until it returns 0.
+pub fn continue_referral_reversal(e: &Env, market_id: u64) -> Result<u32, ErrorCode> {
+    let market = markets::get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+    if market.status != MarketStatus::Cancelled {
+        return Err(ErrorCode::MarketNotCancelled);
+    }
+    reverse_market_referrals(e, market_id, &market.token_address)
+}
+
+/// Referral reward `referrer` has accrued from `market_id` and not had
+/// reversed.
+pub fn get_referral_accrual(e: &Env, market_id: u64, referrer: Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ReferralAccrual(market_id, referrer))
+        .unwrap_or(0)
+}
+
+/// Referral rewards `referrer` can claim in `token`.
+pub fn get_referral_balance(e: &Env, referrer: Address, token: Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ReferrerBalance(referrer, token))
+        .unwrap_or(0)
+}
+
+/// Clawback `referrer` still owes in `token` from cancelled markets.
+pub fn get_referral_debt(e: &Env, referrer: Address, token: Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ReferralDebt(referrer, token))
+        .unwrap_or(0)
 }
 
 /// Reverse protocol fee revenue that was collected at bet time.
-/// Called during cancellation refund so the fee is returned to the bettor.
+/// Called during cancellation refund so the fee is returned to the bettor,
+/// and when a referrer is credited their share of a fee.
 pub fn reverse_fee(e: &Env, token: Address, amount: i128) {
     if amount == 0 {
         return;
@@ -228,8 +450,17 @@ pub fn claim_referral_rewards(
     if balance == 0 {
         return Err(ErrorCode::InsufficientBalance);
     }
+    if referral_expired(e, address, token) {
+        return Err(ErrorCode::RewardsExpired);
+    }
 
     e.storage().persistent().set(&key, &0i128);
+    touch_referral(e, address, token);
+    // Claimed rewards stay in their markets' accruals, to be recorded as
+    // debt if one of those markets is cancelled.
+    e.storage()
+        .persistent()
+        .remove(&DataKey::UnclaimedAccruals(address.clone(), token.clone()));
 
     let client = soroban_sdk::token::Client::new(e, token);
     client.transfer(&e.current_contract_address(), address, &balance);
@@ -239,6 +470,161 @@ pub fn claim_referral_rewards(
     Ok(balance)
 }
 
+/// Seconds a referral balance stays claimable after its last accrual or claim.
+pub fn get_referral_expiry(e: &Env) -> u64 {
+    e.storage()
+        .persistent()
+        .get(&ConfigKey::ReferralExpiry)
+        .unwrap_or(DEFAULT_REFERRAL_EXPIRY)
+}
+
+pub fn set_referral_expiry(e: &Env, seconds: u64) -> Result<(), ErrorCode> {
+    admin::require_admin(e)?;
+    if seconds == 0 {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    e.storage()
+        .persistent()
+        .set(&ConfigKey::ReferralExpiry, &seconds);
+    bump_config_ttl(e, &ConfigKey::ReferralExpiry);
+    Ok(())
+}
+
+/// Ledger time of `referrer`'s last accrual or claim in `token`, if any.
+pub fn get_referral_accrued_at(e: &Env, referrer: Address, token: Address) -> Option<u64> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::ReferralAccruedAt(referrer, token))
+}
+
+fn touch_referral(e: &Env, referrer: &Address, token: &Address) {
+    e.storage().persistent().set(
+        &DataKey::ReferralAccruedAt(referrer.clone(), token.clone()),
+        &e.ledger().timestamp(),
+    );
+}
+
+/// Balances accrued before expiry was tracked have no timestamp and do not
+/// expire until a sweep, or the referrer's next accrual or claim, starts
+/// their clock.
+fn referral_expired(e: &Env, referrer: &Address, token: &Address) -> bool {
+    match get_referral_accrued_at(e, referrer.clone(), token.clone()) {
+        Some(at) => e.ledger().timestamp().saturating_sub(at) > get_referral_expiry(e),
+        None => false,
+    }
+}
+
+/// Move the `token` balances of those `referrers` that have expired into
+/// protocol revenue. Anyone may call it, with at most
+/// `MAX_REFERRAL_SWEEP_BATCH` referrers; referrers with nothing expired are
+/// skipped. Returns the total swept.
+///
+/// The referrers swept may hold at most `MAX_REFERRAL_SWEEP_ACCRUALS`
+/// unclaimed market accruals between them; past that the whole call fails
+/// with `SweepBatchTooLarge` and should be retried with fewer referrers.
+///
+/// A balance with no accrual timestamp (from before expiry was tracked) is
+/// stamped with the current time instead, so it expires one referral expiry
+/// after its first sweep.
+pub fn sweep_expired_referrals(
+    e: &Env,
+    referrers: &Vec<Address>,
+    token: &Address,
+) -> Result<i128, ErrorCode> {
+    if referrers.len() > MAX_REFERRAL_SWEEP_BATCH {
+        return Err(ErrorCode::SweepBatchTooLarge);
+    }
+
+    let mut swept: i128 = 0;
+    let mut accruals: u32 = 0;
+    for referrer in referrers.iter() {
+        let key = DataKey::ReferrerBalance(referrer.clone(), token.clone());
+        let balance: i128 = e.storage().persistent().get(&key).unwrap_or(0);
+        if balance <= 0 {
+            continue;
+        }
+        if get_referral_accrued_at(e, referrer.clone(), token.clone()).is_none() {
+            touch_referral(e, &referrer, token);
+            continue;
+        }
+        if !referral_expired(e, &referrer, token) {
+            continue;
+        }
+
+        accruals = accruals.saturating_add(unclaimed_accrual_count(e, &referrer, token));
+        if accruals > MAX_REFERRAL_SWEEP_ACCRUALS {
+            return Err(ErrorCode::SweepBatchTooLarge);
+        }
+
+        e.storage().persistent().set(&key, &0i128);
+        e.storage()
+            .persistent()
+            .remove(&DataKey::ReferralAccruedAt(referrer.clone(), token.clone()));
+        drop_swept_accruals(e, &referrer, token, balance)?;
+        collect_fee(e, token.clone(), balance)?;
+        swept = swept.checked_add(balance).ok_or(ErrorCode::Overflow)?;
+
+        crate::modules::events::emit_referral_swept(e, referrer, token.clone(), balance);
+    }
+    Ok(swept)
+}
+
+fn unclaimed_accrual_count(e: &Env, referrer: &Address, token: &Address) -> u32 {
+    e.storage()
+        .persistent()
+        .get::<_, Map<u64, i128>>(&DataKey::UnclaimedAccruals(referrer.clone(), token.clone()))
+        .map_or(0, |unclaimed| unclaimed.len())
+}
+
+/// Remove up to `swept` of `referrer`'s unclaimed rewards from the accruals
+/// of the markets they came from. The swept balance is already protocol
+/// revenue, so cancelling one of those markets must not take it back again.
+fn drop_swept_accruals(
+    e: &Env,
+    referrer: &Address,
+    token: &Address,
+    swept: i128,
+) -> Result<(), ErrorCode> {
+    let unclaimed_key = DataKey::UnclaimedAccruals(referrer.clone(), token.clone());
+    let unclaimed: Map<u64, i128> = match e.storage().persistent().get(&unclaimed_key) {
+        Some(unclaimed) => unclaimed,
+        None => return Ok(()),
+    };
+    e.storage().persistent().remove(&unclaimed_key);
+
+    let mut remaining = swept;
+    for (market_id, amount) in unclaimed.iter() {
+        if remaining <= 0 {
+            break;
+        }
+        let dropped = amount.min(remaining);
+        remaining -= dropped;
+
+        let accrual_key = DataKey::ReferralAccrual(market_id, referrer.clone());
+        let accrued: i128 = e.storage().persistent().get(&accrual_key).unwrap_or(0);
+        let left = accrued.checked_sub(dropped).ok_or(ErrorCode::Overflow)?;
+        if left > 0 {
+            e.storage().persistent().set(&accrual_key, &left);
+            continue;
+        }
+
+        e.storage().persistent().remove(&accrual_key);
+        let referrers_key = DataKey::MarketReferrers(market_id);
+        let referrers: Option<Vec<Address>> = e.storage().persistent().get(&referrers_key);
+        if let Some(mut referrers) = referrers {
+            if let Some(index) = referrers.first_index_of(referrer.clone()) {
+                referrers.remove(index);
+            }
+            if referrers.is_empty() {
+                e.storage().persistent().remove(&referrers_key);
+            } else {
+                e.storage().persistent().set(&referrers_key, &referrers);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Issue #511: Distribute referral fees on market resolution
 /// Called during market resolution to distribute accumulated referral rewards
 pub fn distribute_referral_fees(e: &Env, market_id: u64, token: &Address) -> Result<(), ErrorCode> {
@@ -380,6 +766,7 @@ mod withdrawal_tests {
 
         // Attempt withdrawal from a non-admin address — mock_all_auths is off for this call
         let treasury = Address::generate(&env);
+        env.set_auths(&[]);
         let result = client.try_withdraw_protocol_fees(&token, &treasury);
         assert!(result.is_err());
     }
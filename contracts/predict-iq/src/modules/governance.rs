@@ -1,8 +1,9 @@
 use crate::errors::ErrorCode;
+use crate::modules::guardian_log::{admin_actor, log_action};
 use crate::types::{
-    ConfigKey, Guardian, PendingUpgrade, MAJORITY_THRESHOLD_PERCENT, TIMELOCK_DURATION,
-    TIMELOCK_MAX_SECONDS, TIMELOCK_MIN_SECONDS, TTL_HIGH_THRESHOLD, TTL_LOW_THRESHOLD,
-    UPGRADE_COOLDOWN_DURATION,
+    ConfigKey, Guardian, GuardianAction, PendingUpgrade, MAJORITY_THRESHOLD_PERCENT,
+    TIMELOCK_DURATION, TIMELOCK_MAX_SECONDS, TIMELOCK_MIN_SECONDS, TTL_HIGH_THRESHOLD,
+    TTL_LOW_THRESHOLD, UPGRADE_COOLDOWN_DURATION,
 };
 use soroban_sdk::{Address, BytesN, Env, Vec};
 
@@ -34,10 +35,12 @@ pub fn initialize_guardians(e: &Env, guardians: Vec<Guardian>) -> Result<(), Err
         }
     }
 
+    let count = guardians.len();
     e.storage()
         .persistent()
         .set(&ConfigKey::GuardianSet, &guardians);
     bump_gov_ttl(e, &ConfigKey::GuardianSet);
+    log_action(e, admin_actor(e), GuardianAction::GuardiansInitialized(count));
     Ok(())
 }
 
@@ -69,11 +72,13 @@ pub fn add_guardian(e: &Env, guardian: Guardian) -> Result<(), ErrorCode> {
         }
     }
 
+    let address = guardian.address.clone();
     guardians.push_back(guardian);
     e.storage()
         .persistent()
         .set(&ConfigKey::GuardianSet, &guardians);
     bump_gov_ttl(e, &ConfigKey::GuardianSet);
+    log_action(e, admin_actor(e), GuardianAction::GuardianAdded(address));
     Ok(())
 }
 
@@ -110,6 +115,11 @@ pub fn remove_guardian(e: &Env, address: Address) -> Result<(), ErrorCode> {
         .persistent()
         .remove(&ConfigKey::PendingGuardianRemovalPassedAt);
     bump_gov_ttl(e, &ConfigKey::PendingGuardianRemoval);
+    log_action(
+        e,
+        admin_actor(e),
+        GuardianAction::GuardianRemovalProposed(address),
+    );
     Ok(())
 }
 
@@ -146,7 +156,7 @@ pub fn vote_on_guardian_removal(e: &Env, voter: Address, approve: bool) -> Resul
     }
 
     if approve {
-        pending_removal.votes_for.push_back(voter);
+        pending_removal.votes_for.push_back(voter.clone());
     }
 
     // Calculate if majority reached (excluding target guardian)
@@ -163,6 +173,11 @@ pub fn vote_on_guardian_removal(e: &Env, voter: Address, approve: bool) -> Resul
         .persistent()
         .set(&ConfigKey::PendingGuardianRemoval, &pending_removal);
     bump_gov_ttl(e, &ConfigKey::PendingGuardianRemoval);
+    log_action(
+        e,
+        voter,
+        GuardianAction::GuardianRemovalVoted(pending_removal.target_guardian, approve),
+    );
 
     Ok(())
 }
@@ -210,10 +225,22 @@ pub fn execute_guardian_removal(e: &Env) -> Result<(), ErrorCode> {
     e.storage()
         .persistent()
         .remove(&ConfigKey::PendingGuardianRemovalPassedAt);
+    log_action(
+        e,
+        admin_actor(e),
+        GuardianAction::GuardianRemoved(pending_removal.target_guardian),
+    );
 
     Ok(())
 }
 
+/// The guardian removal being voted on, if any.
+pub fn get_pending_guardian_removal(e: &Env) -> Option<crate::types::PendingGuardianRemoval> {
+    e.storage()
+        .persistent()
+        .get(&ConfigKey::PendingGuardianRemoval)
+}
+
 fn get_guardian_removal_passed_at(e: &Env) -> Option<u64> {
     e.storage()
         .persistent()
@@ -233,6 +260,7 @@ fn set_guardian_removal_passed_at(e: &Env) {
 pub fn initiate_upgrade(e: &Env, wasm_hash: BytesN<32>) -> Result<(), ErrorCode> {
     crate::modules::admin::require_admin(e)?;
 
+    expire_rejected_upgrade(e)?;
     require_no_upgrade_collision(e, &wasm_hash)?;
 
     // Check if an upgrade is already pending
@@ -244,7 +272,7 @@ pub fn initiate_upgrade(e: &Env, wasm_hash: BytesN<32>) -> Result<(), ErrorCode>
     let empty_votes: Vec<Address> = Vec::new(e);
 
     let pending_upgrade = PendingUpgrade {
-        wasm_hash,
+        wasm_hash: wasm_hash.clone(),
         initiated_at: current_time,
         votes_for: empty_votes.clone(),
         votes_against: empty_votes,
@@ -258,8 +286,9 @@ pub fn initiate_upgrade(e: &Env, wasm_hash: BytesN<32>) -> Result<(), ErrorCode>
         .remove(&ConfigKey::PendingUpgradePassedAt);
     bump_gov_ttl(e, &ConfigKey::PendingUpgrade);
 
-    let admin = crate::modules::admin::get_admin(e).unwrap_or(e.current_contract_address());
-    crate::modules::events::emit_upgrade_initiated(e, admin, wasm_hash);
+    let admin = admin_actor(e);
+    crate::modules::events::emit_upgrade_initiated(e, admin.clone(), wasm_hash.clone());
+    log_action(e, admin, GuardianAction::UpgradeInitiated(wasm_hash));
 
     Ok(())
 }
@@ -288,13 +317,43 @@ fn get_upgrade_rejected_at(e: &Env, wasm_hash: &BytesN<32>) -> Option<u64> {
         .get(&ConfigKey::UpgradeRejectedAt(wasm_hash.clone()))
 }
 
-fn set_upgrade_rejected_at(e: &Env, wasm_hash: &BytesN<32>) {
-    e.storage().persistent().set(
-        &ConfigKey::UpgradeRejectedAt(wasm_hash.clone()),
-        &e.ledger().timestamp(),
+fn set_upgrade_rejected_at(e: &Env, wasm_hash: &BytesN<32>, rejected_at: u64) {
+    e.storage()
+        .persistent()
+        .set(&ConfigKey::UpgradeRejectedAt(wasm_hash.clone()), &rejected_at);
+}
+
+/// Drop the pending upgrade and start its hash's cooldown at `rejected_at`.
+fn reject_pending_upgrade(e: &Env, pending_upgrade: PendingUpgrade, rejected_at: u64) {
+    set_upgrade_rejected_at(e, &pending_upgrade.wasm_hash, rejected_at);
+    e.storage().persistent().remove(&ConfigKey::PendingUpgrade);
+    e.storage()
+        .persistent()
+        .remove(&ConfigKey::PendingUpgradePassedAt);
+    crate::modules::events::emit_upgrade_rejected(e, pending_upgrade.wasm_hash.clone());
+    log_action(
+        e,
+        admin_actor(e),
+        GuardianAction::UpgradeRejected(pending_upgrade.wasm_hash),
     );
 }
 
+/// A pending upgrade whose timelock ran out without a majority is rejected.
+/// `execute_upgrade` reports that as an error, which rolls back its own
+/// writes, so the next `initiate_upgrade` records the rejection instead,
+/// dated to when the timelock expired.
+fn expire_rejected_upgrade(e: &Env) -> Result<(), ErrorCode> {
+    if let Some(pending_upgrade) = get_pending_upgrade(e) {
+        if is_timelock_satisfied(e)? && !is_majority_met(e, &pending_upgrade) {
+            let rejected_at = get_upgrade_passed_at(e)
+                .unwrap_or(pending_upgrade.initiated_at)
+                .saturating_add(get_timelock_duration(e));
+            reject_pending_upgrade(e, pending_upgrade, rejected_at);
+        }
+    }
+    Ok(())
+}
+
 fn clear_upgrade_rejected_at(e: &Env, wasm_hash: &BytesN<32>) {
     e.storage()
         .persistent()
@@ -342,9 +401,9 @@ pub fn vote_for_upgrade(e: &Env, voter: Address, vote_for: bool) -> Result<bool,
 
     // Record vote
     if vote_for {
-        pending_upgrade.votes_for.push_back(voter);
+        pending_upgrade.votes_for.push_back(voter.clone());
     } else {
-        pending_upgrade.votes_against.push_back(voter);
+        pending_upgrade.votes_against.push_back(voter.clone());
     }
 
     if is_majority_met(e, &pending_upgrade) && get_upgrade_passed_at(e).is_none() {
@@ -356,7 +415,12 @@ pub fn vote_for_upgrade(e: &Env, voter: Address, vote_for: bool) -> Result<bool,
         .set(&ConfigKey::PendingUpgrade, &pending_upgrade);
     bump_gov_ttl(e, &ConfigKey::PendingUpgrade);
 
-    crate::modules::events::emit_upgrade_voted(e, voter, vote_for);
+    crate::modules::events::emit_upgrade_voted(e, voter.clone(), vote_for);
+    log_action(
+        e,
+        voter,
+        GuardianAction::UpgradeVoted(pending_upgrade.wasm_hash, vote_for),
+    );
 
     Ok(true)
 }
@@ -379,6 +443,7 @@ pub fn set_timelock_duration(e: &Env, seconds: u64) -> Result<(), ErrorCode> {
         .persistent()
         .set(&ConfigKey::TimelockDuration, &seconds);
     bump_gov_ttl(e, &ConfigKey::TimelockDuration);
+    log_action(e, admin_actor(e), GuardianAction::TimelockDurationSet(seconds));
     Ok(())
 }
 
@@ -439,12 +504,6 @@ pub fn execute_upgrade(e: &Env) -> Result<BytesN<32>, ErrorCode> {
 
     // Verify majority vote
     if !is_majority_met(e, &pending_upgrade) {
-        set_upgrade_rejected_at(e, &pending_upgrade.wasm_hash);
-        e.storage().persistent().remove(&ConfigKey::PendingUpgrade);
-        e.storage()
-            .persistent()
-            .remove(&ConfigKey::PendingUpgradePassedAt);
-        crate::modules::events::emit_upgrade_rejected(e, pending_upgrade.wasm_hash);
         return Err(ErrorCode::InsufficientVotes);
     }
 
@@ -457,8 +516,9 @@ pub fn execute_upgrade(e: &Env) -> Result<BytesN<32>, ErrorCode> {
         .remove(&ConfigKey::PendingUpgradePassedAt);
     clear_upgrade_rejected_at(e, &wasm_hash);
 
-    let executor = crate::modules::admin::get_admin(e).unwrap_or(e.current_contract_address());
-    crate::modules::events::emit_upgrade_executed(e, executor, wasm_hash.clone());
+    let executor = admin_actor(e);
+    crate::modules::events::emit_upgrade_executed(e, executor.clone(), wasm_hash.clone());
+    log_action(e, executor, GuardianAction::UpgradeExecuted(wasm_hash.clone()));
 
     // Execute host-level contract code upgrade.
     e.deployer().update_current_contract_wasm(wasm_hash.clone());
@@ -523,6 +583,13 @@ pub fn emergency_pause(e: &Env, voter: Address) -> Result<(), ErrorCode> {
         &crate::types::CircuitBreakerState::Paused,
     );
     bump_gov_ttl(e, &ConfigKey::CircuitBreakerState);
+    // Same event as every other breaker transition, so indexers see it.
+    crate::modules::events::emit_circuit_breaker_triggered(
+        e,
+        e.current_contract_address(),
+        soroban_sdk::String::from_str(e, "paused"),
+    );
+    log_action(e, voter, GuardianAction::EmergencyPause);
 
     Ok(())
 }
@@ -0,0 +1,74 @@
+//! On-chain log of guardian and governance actions.
+//!
+//! Kept outside `governance` so the single-guardian pause and unpause in
+//! `circuit_breaker` are logged in builds without the `governance` feature.
+use crate::types::{
+    ConfigKey, GuardianAction, GuardianActionRecord, GUARDIAN_LOG_CAPACITY, TTL_HIGH_THRESHOLD,
+    TTL_LOW_THRESHOLD,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+fn bump_log_ttl(e: &Env, key: &ConfigKey) {
+    e.storage()
+        .persistent()
+        .extend_ttl(key, TTL_LOW_THRESHOLD, TTL_HIGH_THRESHOLD);
+}
+
+/// The admin, for actions taken under admin authority; the contract itself
+/// if none is set.
+pub fn admin_actor(e: &Env) -> Address {
+    crate::modules::admin::get_admin(e).unwrap_or(e.current_contract_address())
+}
+
+/// Record `action` in the guardian action log and emit it as a `grd_act`
+/// event.
+///
+/// The log is a ring buffer of the last [`GUARDIAN_LOG_CAPACITY`] actions:
+/// each write overwrites a single slot, so logging costs the same however
+/// long the contract has been running.
+pub fn log_action(e: &Env, actor: Address, action: GuardianAction) {
+    let seq: u64 = e
+        .storage()
+        .persistent()
+        .get(&ConfigKey::GuardianActionCount)
+        .unwrap_or(0);
+    let record = GuardianActionRecord {
+        seq,
+        actor,
+        action,
+        timestamp: e.ledger().timestamp(),
+    };
+
+    let slot_key = ConfigKey::GuardianActionSlot((seq % GUARDIAN_LOG_CAPACITY as u64) as u32);
+    e.storage().persistent().set(&slot_key, &record);
+    bump_log_ttl(e, &slot_key);
+    e.storage()
+        .persistent()
+        .set(&ConfigKey::GuardianActionCount, &(seq + 1));
+    bump_log_ttl(e, &ConfigKey::GuardianActionCount);
+
+    crate::modules::events::emit_guardian_action(e, &record);
+}
+
+/// Up to `limit` logged actions from sequence number `start` on, oldest
+/// first. Actions that have rotated out of the log are skipped, so `start`
+/// of 0 reads from the oldest one still kept.
+pub fn get_guardian_action_log(e: &Env, start: u64, limit: u32) -> Vec<GuardianActionRecord> {
+    let count: u64 = e
+        .storage()
+        .persistent()
+        .get(&ConfigKey::GuardianActionCount)
+        .unwrap_or(0);
+    let oldest = count.saturating_sub(GUARDIAN_LOG_CAPACITY as u64);
+    let start = start.max(oldest);
+    let end = count.min(start.saturating_add(limit.min(GUARDIAN_LOG_CAPACITY) as u64));
+
+    let mut records = Vec::new(e);
+    for seq in start..end {
+        let slot_key = ConfigKey::GuardianActionSlot((seq % GUARDIAN_LOG_CAPACITY as u64) as u32);
+        if let Some(record) = e.storage().persistent().get(&slot_key) {
+            records.push_back(record);
+        }
+    }
+    records
+}
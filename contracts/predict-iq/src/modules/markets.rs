@@ -1,15 +1,25 @@
 use crate::errors::ErrorCode;
 use crate::types::{
-    ConfigKey, CreatorReputation, Market, MarketStatus, MarketTier, OracleConfig,
-    PRUNE_GRACE_PERIOD, TTL_HIGH_THRESHOLD, TTL_LOW_THRESHOLD,
+    AcceptedToken, ConfigKey, CreatorReputation, FundingRequirement, Market, MarketLabels,
+    MarketStatus, MarketTier, MarketV1, OracleConfig, TierLimits, MAX_ACCEPTED_TOKENS,
+    MAX_CATEGORY_LEN, MAX_DESCRIPTION_LEN, MAX_MARKET_DURATION_SECONDS, MAX_OUTCOMES_PER_MARKET,
+    MAX_TAGS_PER_MARKET, MAX_TAG_LEN, PRUNE_GRACE_PERIOD, TTL_HIGH_THRESHOLD, TTL_LOW_THRESHOLD,
+};
+use soroban_sdk::{
+    contracttype, symbol_short, token, Address, Env, Map, String, Symbol, TryFromVal, Val, Vec,
 };
-use soroban_sdk::{contracttype, token, Address, Env, String, Vec};
 
 #[contracttype]
 pub enum DataKey {
     Market(u64),
     MarketCount,
     MarketDisputeWindow(u64),
+    /// Present only for markets created with a category or tags.
+    MarketLabels(u64),
+    /// Present only for markets that take bets in other tokens too.
+    AcceptedTokens(u64),
+    /// Present only for markets created in the Funding status.
+    FundingRequirement(u64),
     CreatorReputation(Address),
     /// Presence key for the status index.
     /// `StatusIndex(market_id, status)` exists iff market `market_id` currently
@@ -43,6 +53,25 @@ pub fn update_status_index(
         .set(&DataKey::StatusIndex(market_id, new_status.clone()), &true);
 }
 
+/// Minimum gap between a market's betting deadline and resolution deadline.
+const MIN_DEADLINE_GAP: u64 = 86400;
+
+/// Reserve the next market id. Refuses to wrap the counter or to hand out an
+/// id that already has a stored market.
+pub fn allocate_market_id(e: &Env) -> Result<u64, ErrorCode> {
+    let count: u64 = e
+        .storage()
+        .instance()
+        .get(&DataKey::MarketCount)
+        .unwrap_or(0);
+    let id = count.checked_add(1).ok_or(ErrorCode::MarketIdOverflow)?;
+    if e.storage().persistent().has(&DataKey::Market(id)) {
+        return Err(ErrorCode::MarketIdCollision);
+    }
+    e.storage().instance().set(&DataKey::MarketCount, &id);
+    Ok(id)
+}
+
 pub fn create_market(
     e: &Env,
     creator: Address,
@@ -85,6 +114,144 @@ pub fn create_market_with_dispute_window(
     parent_id: u64,
     parent_outcome_idx: u32,
     dispute_window_seconds: Option<u64>,
+) -> Result<u64, ErrorCode> {
+    create_market_full(
+        e,
+        creator,
+        description,
+        options,
+        deadline,
+        resolution_deadline,
+        oracle_config,
+        tier,
+        native_token,
+        parent_id,
+        parent_outcome_idx,
+        dispute_window_seconds,
+        MarketLabels {
+            category: None,
+            tags: Vec::new(e),
+        },
+        None,
+    )
+}
+
+/// [`create_market`] with a category and tags, stored with the market and
+/// carried in its `mkt_creat` event so indexers can group on-chain markets.
+pub fn create_market_with_labels(
+    e: &Env,
+    creator: Address,
+    description: String,
+    options: Vec<String>,
+    deadline: u64,
+    resolution_deadline: u64,
+    oracle_config: OracleConfig,
+    tier: MarketTier,
+    native_token: Address,
+    parent_id: u64,
+    parent_outcome_idx: u32,
+    labels: MarketLabels,
+) -> Result<u64, ErrorCode> {
+    create_market_full(
+        e,
+        creator,
+        description,
+        options,
+        deadline,
+        resolution_deadline,
+        oracle_config,
+        tier,
+        native_token,
+        parent_id,
+        parent_outcome_idx,
+        None,
+        labels,
+        None,
+    )
+}
+
+/// [`create_market`] in the Funding status: the market only opens for
+/// public bets once the creator has staked `min_liquidity` (net of fees)
+/// within `funding_window` seconds. If they do not, anyone can cancel it
+/// with `cancellation::cancel_unfunded_market`.
+pub fn create_market_with_funding(
+    e: &Env,
+    creator: Address,
+    description: String,
+    options: Vec<String>,
+    deadline: u64,
+    resolution_deadline: u64,
+    oracle_config: OracleConfig,
+    tier: MarketTier,
+    native_token: Address,
+    parent_id: u64,
+    parent_outcome_idx: u32,
+    min_liquidity: i128,
+    funding_window: u64,
+) -> Result<u64, ErrorCode> {
+    let funding = FundingRequirement {
+        min_liquidity,
+        funding_deadline: e
+            .ledger()
+            .timestamp()
+            .checked_add(funding_window)
+            .ok_or(ErrorCode::InvalidTimeRange)?,
+    };
+    create_market_full(
+        e,
+        creator,
+        description,
+        options,
+        deadline,
+        resolution_deadline,
+        oracle_config,
+        tier,
+        native_token,
+        parent_id,
+        parent_outcome_idx,
+        None,
+        MarketLabels {
+            category: None,
+            tags: Vec::new(e),
+        },
+        Some(funding),
+    )
+}
+
+/// A category of 1..=`MAX_CATEGORY_LEN` bytes and at most
+/// `MAX_TAGS_PER_MARKET` tags of 1..=`MAX_TAG_LEN` bytes each.
+fn validate_labels(labels: &MarketLabels) -> Result<(), ErrorCode> {
+    let category_ok = labels
+        .category
+        .as_ref()
+        .map_or(true, |c| !c.is_empty() && c.len() <= MAX_CATEGORY_LEN);
+    let tags_ok = labels.tags.len() <= MAX_TAGS_PER_MARKET
+        && labels
+            .tags
+            .iter()
+            .all(|t| !t.is_empty() && t.len() <= MAX_TAG_LEN);
+    if category_ok && tags_ok {
+        Ok(())
+    } else {
+        Err(ErrorCode::InvalidMarketLabels)
+    }
+}
+
+fn create_market_full(
+    e: &Env,
+    creator: Address,
+    description: String,
+    options: Vec<String>,
+    deadline: u64,
+    resolution_deadline: u64,
+    oracle_config: OracleConfig,
+    tier: MarketTier,
+    native_token: Address,
+    parent_id: u64,
+    parent_outcome_idx: u32,
+    dispute_window_seconds: Option<u64>,
+    labels: MarketLabels,
+    funding: Option<FundingRequirement>,
 ) -> Result<u64, ErrorCode> {
     creator.require_auth();
 
@@ -105,37 +272,36 @@ pub fn create_market_with_dispute_window(
     }
 
     // Enforce minimum deadline gap (24 hours = 86400 seconds)
-    const MIN_DEADLINE_GAP: u64 = 86400;
     if resolution_deadline - deadline < MIN_DEADLINE_GAP {
         return Err(ErrorCode::InvalidTimeRange);
     }
 
-    // Gas optimization: Limit number of outcomes to prevent excessive iteration
-    if options.len() > crate::types::MAX_OUTCOMES_PER_MARKET {
+    // Gas optimization: outcome count, description size and lifetime are
+    // capped per tier to bound iteration and storage.
+    let limits = get_tier_limits(e, &tier);
+    if options.len() > limits.max_outcomes {
         return Err(ErrorCode::TooManyOutcomes);
     }
-
-    // Validate parent market if this is a conditional market
-    if parent_id > 0 {
-        let parent_market = get_market(e, parent_id).ok_or(ErrorCode::MarketNotFound)?;
-
-        // Parent must be resolved
-        if parent_market.status != MarketStatus::Resolved {
-            return Err(ErrorCode::ParentMarketNotResolved);
+    if description.len() > limits.max_description_len {
+        return Err(ErrorCode::DescriptionTooLong);
+    }
+    if resolution_deadline - current_time > limits.max_duration_seconds {
+        return Err(ErrorCode::MarketDurationTooLong);
+    }
+    validate_labels(&labels)?;
+    if let Some(funding) = &funding {
+        if funding.min_liquidity <= 0 {
+            return Err(ErrorCode::InvalidAmount);
         }
-
-        // Parent must have resolved to the required outcome
-        let parent_winning_outcome = parent_market
-            .winning_outcome
-            .ok_or(ErrorCode::ParentMarketNotResolved)?;
-        if parent_winning_outcome != parent_outcome_idx {
-            return Err(ErrorCode::ParentMarketInvalidOutcome);
+        // The funding window must close before betting does.
+        if funding.funding_deadline <= current_time || funding.funding_deadline >= deadline {
+            return Err(ErrorCode::InvalidTimeRange);
         }
+    }
 
-        // Validate parent_outcome_idx is within parent's options range
-        if parent_outcome_idx >= parent_market.options.len() {
-            return Err(ErrorCode::InvalidOutcome);
-        }
+    // Validate parent market if this is a conditional market
+    if parent_id > 0 {
+        let parent_market = validate_parent_market(e, parent_id, parent_outcome_idx)?;
 
         // Issue #069: Conditional market inherits parent constraints.
         // The conditional market's deadline must not exceed the parent's resolution_deadline
@@ -178,7 +344,6 @@ pub fn create_market_with_dispute_window(
     );
 
     let token_client = token::Client::new(e, &native_token);
-    let balance = token_client.balance(&creator);
 
     // Calculate total amount needed (deposit + fee)
     let total_required = if deposit_required {
@@ -187,7 +352,7 @@ pub fn create_market_with_dispute_window(
         0
     } + creation_fee;
 
-    if total_required > 0 && balance < total_required {
+    if total_required > 0 && token_client.balance(&creator) < total_required {
         return Err(ErrorCode::InsufficientDeposit);
     }
 
@@ -205,14 +370,14 @@ pub fn create_market_with_dispute_window(
         token_client.transfer(&creator, &e.current_contract_address(), &creation_deposit);
     }
 
-    let mut count: u64 = e
-        .storage()
-        .instance()
-        .get(&DataKey::MarketCount)
-        .unwrap_or(0);
-    count += 1;
+    let count = allocate_market_id(e)?;
 
     let num_outcomes = options.len() as u32;
+    let status = if funding.is_some() {
+        MarketStatus::Funding
+    } else {
+        MarketStatus::Active
+    };
     let dispute_window =
         crate::modules::resolution::resolve_market_dispute_window(e, dispute_window_seconds)?;
 
@@ -221,7 +386,7 @@ pub fn create_market_with_dispute_window(
         creator: creator.clone(),
         description,
         options,
-        status: MarketStatus::Active,
+        status: status.clone(),
         deadline,
         resolution_deadline,
         winning_outcome: None,
@@ -241,6 +406,10 @@ pub fn create_market_with_dispute_window(
         outcome_stakes: soroban_sdk::Map::new(e),
         pending_resolution_timestamp: None,
         dispute_snapshot_ledger: None,
+        dispute_timestamp: None,
+        winner_counts: soroban_sdk::Map::new(e),
+        total_claimed: 0,
+        fee_bps: crate::modules::fees::get_base_fee(e),
     };
 
     e.storage()
@@ -261,13 +430,32 @@ pub fn create_market_with_dispute_window(
         TTL_LOW_THRESHOLD,
         TTL_HIGH_THRESHOLD,
     );
+    if labels.category.is_some() || !labels.tags.is_empty() {
+        e.storage()
+            .persistent()
+            .set(&DataKey::MarketLabels(count), &labels);
+        e.storage().persistent().extend_ttl(
+            &DataKey::MarketLabels(count),
+            TTL_LOW_THRESHOLD,
+            TTL_HIGH_THRESHOLD,
+        );
+    }
+
+    if let Some(funding) = &funding {
+        e.storage()
+            .persistent()
+            .set(&DataKey::FundingRequirement(count), funding);
+        e.storage().persistent().extend_ttl(
+            &DataKey::FundingRequirement(count),
+            TTL_LOW_THRESHOLD,
+            TTL_HIGH_THRESHOLD,
+        );
+    }
 
     // Maintain status index so get_markets_by_status can probe O(limit) keys.
     e.storage()
         .persistent()
-        .set(&DataKey::StatusIndex(count, MarketStatus::Active), &true);
-
-    e.storage().instance().set(&DataKey::MarketCount, &count);
+        .set(&DataKey::StatusIndex(count, status), &true);
 
     // Emit standardized MarketCreated event
     // Topics: [MarketCreated, market_id, creator]
@@ -278,6 +466,7 @@ pub fn create_market_with_dispute_window(
         market.description.clone(),
         num_outcomes,
         deadline,
+        labels,
     );
 
     Ok(count)
@@ -290,8 +479,161 @@ pub fn get_market_dispute_window(e: &Env, market_id: u64) -> u64 {
         .unwrap_or_else(|| crate::modules::resolution::get_default_dispute_window(e))
 }
 
+/// The labels market `id` was created with; empty for a market created
+/// without them, `None` for an unknown market.
+pub fn get_market_labels(e: &Env, id: u64) -> Option<MarketLabels> {
+    if !e.storage().persistent().has(&DataKey::Market(id)) {
+        return None;
+    }
+    Some(
+        e.storage()
+            .persistent()
+            .get(&DataKey::MarketLabels(id))
+            .unwrap_or(MarketLabels {
+                category: None,
+                tags: Vec::new(e),
+            }),
+    )
+}
+
+/// Let market `market_id` take bets in `tokens` besides its own token, at
+/// fixed rates (admin only). Replaces the previous list; bets already placed
+/// keep the rate they were converted at, and are paid back in their token
+/// even if it is delisted.
+pub fn set_accepted_tokens(
+    e: &Env,
+    market_id: u64,
+    tokens: Vec<AcceptedToken>,
+) -> Result<(), ErrorCode> {
+    crate::modules::admin::require_admin(e)?;
+    let market = get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+    if market.status != MarketStatus::Active && market.status != MarketStatus::Funding {
+        return Err(ErrorCode::MarketNotActive);
+    }
+    if tokens.len() > MAX_ACCEPTED_TOKENS {
+        return Err(ErrorCode::InvalidAcceptedToken);
+    }
+    for (i, accepted) in tokens.iter().enumerate() {
+        if accepted.rate <= 0 || accepted.token == market.token_address {
+            return Err(ErrorCode::InvalidAcceptedToken);
+        }
+        if tokens
+            .iter()
+            .skip(i + 1)
+            .any(|other| other.token == accepted.token)
+        {
+            return Err(ErrorCode::InvalidAcceptedToken);
+        }
+    }
+
+    let key = DataKey::AcceptedTokens(market_id);
+    if tokens.is_empty() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, &tokens);
+    }
+    crate::modules::events::emit_accepted_tokens_set(e, market_id, tokens.len());
+    Ok(())
+}
+
+/// Tokens market `id` takes bets in besides its own; empty for most markets.
+pub fn get_accepted_tokens(e: &Env, id: u64) -> Vec<AcceptedToken> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::AcceptedTokens(id))
+        .unwrap_or(Vec::new(e))
+}
+
+/// The rate `token` is currently accepted at on market `id`, if it is.
+pub fn accepted_rate(e: &Env, id: u64, token: &Address) -> Option<i128> {
+    get_accepted_tokens(e, id)
+        .iter()
+        .find(|accepted| &accepted.token == token)
+        .map(|accepted| accepted.rate)
+}
+
+/// The funding requirement of a market created in the Funding status.
+pub fn get_funding_requirement(e: &Env, id: u64) -> Option<FundingRequirement> {
+    e.storage()
+        .persistent()
+        .get(&DataKey::FundingRequirement(id))
+}
+
+/// Bets on a Funding market: only the creator, and only while the funding
+/// window is open.
+pub(crate) fn require_funding_bet(
+    e: &Env,
+    market: &Market,
+    bettor: &Address,
+) -> Result<(), ErrorCode> {
+    if bettor != &market.creator {
+        return Err(ErrorCode::MarketNotActive);
+    }
+    let funding = get_funding_requirement(e, market.id).ok_or(ErrorCode::MarketNotActive)?;
+    if e.ledger().timestamp() > funding.funding_deadline {
+        return Err(ErrorCode::MarketClosed);
+    }
+    Ok(())
+}
+
+/// Open a Funding market for public bets once its stake meets the
+/// requirement. The caller saves the market.
+pub(crate) fn activate_if_funded(e: &Env, market: &mut Market) {
+    if market.status != MarketStatus::Funding {
+        return;
+    }
+    let Some(funding) = get_funding_requirement(e, market.id) else {
+        return;
+    };
+    if market.total_staked >= funding.min_liquidity {
+        market.status = MarketStatus::Active;
+        crate::modules::events::emit_market_funded(e, market.id, market.total_staked);
+    }
+}
+
+/// The parent of a conditional market, provided it resolved to
+/// `parent_outcome_idx`.
+pub fn validate_parent_market(
+    e: &Env,
+    parent_id: u64,
+    parent_outcome_idx: u32,
+) -> Result<Market, ErrorCode> {
+    let parent_market = get_market(e, parent_id).ok_or(ErrorCode::MarketNotFound)?;
+
+    // Parent must be resolved
+    if parent_market.status != MarketStatus::Resolved {
+        return Err(ErrorCode::ParentMarketNotResolved);
+    }
+
+    // Validate parent_outcome_idx is within parent's options range
+    if parent_outcome_idx >= parent_market.options.len() {
+        return Err(ErrorCode::InvalidOutcome);
+    }
+
+    // Parent must have resolved to the required outcome
+    let parent_winning_outcome = parent_market
+        .winning_outcome
+        .ok_or(ErrorCode::ParentMarketNotResolved)?;
+    if parent_winning_outcome != parent_outcome_idx {
+        return Err(ErrorCode::ParentMarketInvalidOutcome);
+    }
+
+    Ok(parent_market)
+}
+
+/// Markets stored before `fee_bps` existed ([`MarketV1`]) are upgraded on
+/// read at the current base fee, which is what their bets were charged until
+/// now; the next `update_market` stores the upgraded layout and pins it.
 pub fn get_market(e: &Env, id: u64) -> Option<Market> {
-    e.storage().persistent().get(&DataKey::Market(id))
+    // Decoding into the wrong struct traps, so pick the layout by its fields.
+    let raw: Map<Symbol, Val> = e.storage().persistent().get(&DataKey::Market(id))?;
+    if raw.contains_key(symbol_short!("fee_bps")) {
+        Market::try_from_val(e, raw.as_val()).ok()
+    } else {
+        MarketV1::try_from_val(e, raw.as_val())
+            .ok()
+            .map(|legacy| legacy.upgrade(crate::modules::fees::get_base_fee(e)))
+    }
 }
 
 pub fn update_market(e: &Env, market: Market) {
@@ -322,16 +664,18 @@ pub fn set_payout_mode(
     Ok(())
 }
 
-// Gas-optimized market count for specific outcome
-pub fn count_bets_for_outcome(e: &Env, market_id: u64, _outcome: u32) -> u32 {
-    // This would need a separate index in production
-    // For now, return estimate based on storage patterns
-    let key = crate::modules::bets::DataKey::Bet(market_id, e.current_contract_address());
-    if e.storage().persistent().has(&key) {
-        1
-    } else {
-        0
-    }
+/// Net stake on `outcome`, or 0 when the market or outcome has none.
+pub fn get_outcome_stake(e: &Env, market_id: u64, outcome: u32) -> i128 {
+    get_market(e, market_id)
+        .and_then(|m| m.outcome_stakes.get(outcome))
+        .unwrap_or(0)
+}
+
+/// Distinct bettors on `outcome`, from the market's `winner_counts`.
+pub fn count_bets_for_outcome(e: &Env, market_id: u64, outcome: u32) -> u32 {
+    get_market(e, market_id)
+        .and_then(|m| m.winner_counts.get(outcome))
+        .unwrap_or(0)
 }
 
 pub fn get_creator_reputation(e: &Env, creator: &Address) -> CreatorReputation {
@@ -385,6 +729,56 @@ pub fn set_creation_fee(e: &Env, amount: i128) -> Result<(), ErrorCode> {
     Ok(())
 }
 
+/// Built-in limits for each tier, used until the admin overrides them.
+pub fn default_tier_limits(tier: &MarketTier) -> TierLimits {
+    match tier {
+        MarketTier::Basic => TierLimits {
+            max_outcomes: 10,
+            max_description_len: 500,
+            max_duration_seconds: 90 * 86_400,
+        },
+        MarketTier::Pro => TierLimits {
+            max_outcomes: 30,
+            max_description_len: 2_000,
+            max_duration_seconds: 180 * 86_400,
+        },
+        MarketTier::Institutional => TierLimits {
+            max_outcomes: MAX_OUTCOMES_PER_MARKET,
+            max_description_len: MAX_DESCRIPTION_LEN,
+            max_duration_seconds: MAX_MARKET_DURATION_SECONDS,
+        },
+    }
+}
+
+pub fn get_tier_limits(e: &Env, tier: &MarketTier) -> TierLimits {
+    e.storage()
+        .persistent()
+        .get(&ConfigKey::TierLimits(tier.clone()))
+        .unwrap_or_else(|| default_tier_limits(tier))
+}
+
+/// Override a tier's creation limits (admin only). Every limit must allow at
+/// least a minimal market and stay within the contract-wide ceilings.
+pub fn set_tier_limits(e: &Env, tier: MarketTier, limits: TierLimits) -> Result<(), ErrorCode> {
+    crate::modules::admin::require_admin(e)?;
+    if limits.max_outcomes < 2 || limits.max_outcomes > MAX_OUTCOMES_PER_MARKET {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    if limits.max_description_len == 0 || limits.max_description_len > MAX_DESCRIPTION_LEN {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    if limits.max_duration_seconds <= MIN_DEADLINE_GAP
+        || limits.max_duration_seconds > MAX_MARKET_DURATION_SECONDS
+    {
+        return Err(ErrorCode::InvalidAmount);
+    }
+    let key = ConfigKey::TierLimits(tier.clone());
+    e.storage().persistent().set(&key, &limits);
+    crate::modules::fees::bump_config_ttl(e, &key);
+    crate::modules::events::emit_tier_limits_set(e, tier, &limits);
+    Ok(())
+}
+
 /// Issue #507: Get protocol treasury address
 pub fn get_protocol_treasury(e: &Env) -> Address {
     e.storage()
@@ -407,7 +801,7 @@ pub fn release_creation_deposit(
     market_id: u64,
     native_token: Address,
 ) -> Result<(), ErrorCode> {
-    let market = get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
+    let mut market = get_market(e, market_id).ok_or(ErrorCode::MarketNotFound)?;
 
     // Only the market creator may reclaim their own deposit
     market.creator.require_auth();
@@ -416,13 +810,17 @@ pub fn release_creation_deposit(
         return Err(ErrorCode::MarketNotActive);
     }
 
-    if market.creation_deposit > 0 {
+    // The deposit was locked in the market's token, and is released once.
+    if native_token != market.token_address {
+        return Err(ErrorCode::InvalidBetAmount);
+    }
+    let deposit = market.creation_deposit;
+    if deposit > 0 {
+        let creator = market.creator.clone();
+        market.creation_deposit = 0;
+        update_market(e, market);
         let token_client = token::Client::new(e, &native_token);
-        token_client.transfer(
-            &e.current_contract_address(),
-            &market.creator,
-            &market.creation_deposit,
-        );
+        token_client.transfer(&e.current_contract_address(), &creator, &deposit);
     }
 
     Ok(())
@@ -464,6 +862,10 @@ pub fn prune_market(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
         return Err(ErrorCode::MarketNotActive);
     }
 
+    // Winnings nobody claimed within the grace period go to revenue with the
+    // dust; their claims are dropped with the market record below.
+    crate::modules::bets::sweep_market_dust(e, market_id)?;
+
     // Archive the market ID for off-chain indexers
     crate::modules::event_archive::archive_market(e, market_id);
 
@@ -477,6 +879,16 @@ pub fn prune_market(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     e.storage()
         .persistent()
         .remove(&DataKey::MarketDisputeWindow(market_id));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::MarketLabels(market_id));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::AcceptedTokens(market_id));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::FundingRequirement(market_id));
+    crate::modules::bets::prune_claim_state(e, market_id, market.options.len());
 
     // Emit pruning event
     crate::modules::events::emit_market_pruned(e, market_id, current_time);
@@ -82,16 +82,16 @@ fn create_resolved_market(
 fn test_conditional_market_valid_parent() {
     let (env, client, admin, cid) = setup();
 
-    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 2000, 0);
+    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 1000 + 86_400, 0);
 
     let token = Address::generate(&env);
-    // Conditional market deadline must be <= parent resolution_deadline (2000)
+    // Conditional market deadline must be <= parent resolution_deadline (87_400)
     let child_id = client.create_market(
         &admin,
         &String::from_str(&env, "Child"),
         &two_options(&env),
         &1500,
-        &1800,
+        &(1500 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -115,7 +115,7 @@ fn test_conditional_market_parent_not_resolved() {
         &String::from_str(&env, "Parent"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -128,7 +128,7 @@ fn test_conditional_market_parent_not_resolved() {
         &String::from_str(&env, "Child"),
         &two_options(&env),
         &500,
-        &800,
+        &(500 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -144,7 +144,7 @@ fn test_conditional_market_wrong_parent_outcome() {
     let (env, client, admin, cid) = setup();
 
     // Parent resolves to outcome 1
-    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 2000, 1);
+    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 1000 + 86_400, 1);
 
     let token = Address::generate(&env);
     // Child requires parent outcome 0 — mismatch
@@ -153,7 +153,7 @@ fn test_conditional_market_wrong_parent_outcome() {
         &String::from_str(&env, "Child"),
         &two_options(&env),
         &1500,
-        &1800,
+        &(1500 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -168,7 +168,7 @@ fn test_conditional_market_wrong_parent_outcome() {
 fn test_conditional_market_invalid_outcome_index() {
     let (env, client, admin, cid) = setup();
 
-    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 2000, 0);
+    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 1000 + 86_400, 0);
 
     let token = Address::generate(&env);
     // Parent only has 2 options (0, 1); index 5 is invalid
@@ -177,7 +177,7 @@ fn test_conditional_market_invalid_outcome_index() {
         &String::from_str(&env, "Child"),
         &two_options(&env),
         &1500,
-        &1800,
+        &(1500 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -192,17 +192,17 @@ fn test_conditional_market_invalid_outcome_index() {
 fn test_conditional_market_deadline_exceeds_parent() {
     let (env, client, admin, cid) = setup();
 
-    // Parent resolution_deadline = 2000
-    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 2000, 0);
+    // Parent resolution_deadline = 87_400
+    let parent_id = create_resolved_market(&env, &client, &cid, &admin, 1000, 1000 + 86_400, 0);
 
     let token = Address::generate(&env);
-    // Child deadline 3000 > parent resolution_deadline 2000
+    // Child deadline 88_000 > parent resolution_deadline 87_400
     let result = client.try_create_market(
         &admin,
         &String::from_str(&env, "Child"),
         &two_options(&env),
-        &3000,
-        &4000,
+        &88_000,
+        &(88_000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -223,7 +223,7 @@ fn test_independent_market_no_parent_validation() {
         &String::from_str(&env, "Independent"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -253,7 +253,7 @@ fn test_tier_none_reputation_basic_only() {
         &String::from_str(&env, "Basic"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -267,7 +267,7 @@ fn test_tier_none_reputation_basic_only() {
         &String::from_str(&env, "Pro"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Pro,
         &token,
@@ -282,7 +282,7 @@ fn test_tier_none_reputation_basic_only() {
         &String::from_str(&env, "Inst"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Institutional,
         &token,
@@ -307,7 +307,7 @@ fn test_tier_pro_reputation() {
         &String::from_str(&env, "Basic"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Basic,
         &token,
@@ -321,7 +321,7 @@ fn test_tier_pro_reputation() {
         &String::from_str(&env, "Pro"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Pro,
         &token,
@@ -335,7 +335,7 @@ fn test_tier_pro_reputation() {
         &String::from_str(&env, "Inst"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Institutional,
         &token,
@@ -364,7 +364,7 @@ fn test_tier_institutional_reputation_all_tiers() {
             &String::from_str(&env, "Market"),
             &two_options(&env),
             &1000,
-            &2000,
+            &(1000 + 86_400),
             &oracle_config(&env),
             &tier,
             &token,
@@ -388,7 +388,7 @@ fn test_tier_upgrade_path() {
         &String::from_str(&env, "Pro"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Pro,
         &token,
@@ -406,7 +406,7 @@ fn test_tier_upgrade_path() {
         &String::from_str(&env, "Pro"),
         &two_options(&env),
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config(&env),
         &MarketTier::Pro,
         &token,
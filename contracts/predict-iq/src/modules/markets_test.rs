@@ -1,9 +1,11 @@
 #![cfg(test)]
+extern crate std;
+
 use crate::errors::ErrorCode;
-use crate::types::{CreatorReputation, MarketStatus, MarketTier, OracleConfig};
+use crate::types::{CreatorReputation, MarketStatus, MarketTier, OracleConfig, TierLimits};
 use crate::{PredictIQ, PredictIQClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
+    testutils::{Address as _, Events as _, Ledger as _},
     Address, Env, String, Vec,
 };
 
@@ -932,3 +934,178 @@ fn test_create_market_valid_time_range_succeeds() {
 
     assert!(result.is_ok());
 }
+
+// ===================== Per-tier creation limits =====================
+
+const DAY: u64 = 86_400;
+const TIERS: [MarketTier; 3] = [
+    MarketTier::Basic,
+    MarketTier::Pro,
+    MarketTier::Institutional,
+];
+
+/// Try to create a market as an Institutional creator (so every tier is
+/// allowed), varying only what the tier limits govern.
+fn try_create_in_tier(
+    env: &Env,
+    client: &PredictIQClient,
+    admin: &Address,
+    tier: MarketTier,
+    outcomes: u32,
+    description_len: u32,
+    resolution_deadline: u64,
+) -> Result<u64, ErrorCode> {
+    client.set_creator_reputation(admin, &CreatorReputation::Institutional);
+    let mut options = Vec::new(env);
+    for _ in 0..outcomes {
+        options.push_back(String::from_str(env, "x"));
+    }
+    let description = std::vec![b'd'; description_len as usize];
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    client
+        .try_create_market(
+            admin,
+            &String::from_bytes(env, &description),
+            &options,
+            &1000,
+            &resolution_deadline,
+            &make_oracle_config(env),
+            &tier,
+            &token,
+            &0,
+            &0,
+        )
+        .map(|id| id.unwrap())
+        .map_err(|e| e.unwrap())
+}
+
+#[test]
+fn test_default_tier_limits() {
+    let (_env, client, _admin) = setup();
+
+    let expected = [
+        (10, 500, 90 * DAY),
+        (30, 2_000, 180 * DAY),
+        (100, 8_000, 365 * DAY),
+    ];
+    for (tier, (outcomes, description, duration)) in TIERS.iter().zip(expected) {
+        let limits = client.get_tier_limits(tier);
+        assert_eq!(limits.max_outcomes, outcomes);
+        assert_eq!(limits.max_description_len, description);
+        assert_eq!(limits.max_duration_seconds, duration);
+    }
+}
+
+#[test]
+fn test_outcome_limit_boundaries_per_tier() {
+    let (env, client, admin) = setup();
+
+    for tier in TIERS {
+        let max = client.get_tier_limits(&tier).max_outcomes;
+        let at_limit = try_create_in_tier(&env, &client, &admin, tier.clone(), max, 10, 2 * DAY);
+        let over = try_create_in_tier(&env, &client, &admin, tier, max + 1, 10, 2 * DAY);
+
+        assert!(at_limit.is_ok());
+        assert_eq!(over, Err(ErrorCode::TooManyOutcomes));
+    }
+}
+
+#[test]
+fn test_description_limit_boundaries_per_tier() {
+    let (env, client, admin) = setup();
+
+    for tier in TIERS {
+        let max = client.get_tier_limits(&tier).max_description_len;
+        let at_limit = try_create_in_tier(&env, &client, &admin, tier.clone(), 2, max, 2 * DAY);
+        let over = try_create_in_tier(&env, &client, &admin, tier, 2, max + 1, 2 * DAY);
+
+        assert!(at_limit.is_ok());
+        assert_eq!(over, Err(ErrorCode::DescriptionTooLong));
+    }
+}
+
+#[test]
+fn test_duration_limit_boundaries_per_tier() {
+    let (env, client, admin) = setup();
+
+    for tier in TIERS {
+        // The ledger is at 0, so the resolution deadline is the duration.
+        let max = client.get_tier_limits(&tier).max_duration_seconds;
+        let at_limit = try_create_in_tier(&env, &client, &admin, tier.clone(), 2, 10, max);
+        let over = try_create_in_tier(&env, &client, &admin, tier, 2, 10, max + 1);
+
+        assert!(at_limit.is_ok());
+        assert_eq!(over, Err(ErrorCode::MarketDurationTooLong));
+    }
+}
+
+#[test]
+fn test_admin_can_raise_basic_limits_within_ceiling() {
+    let (env, client, admin) = setup();
+    let limits = TierLimits {
+        max_outcomes: 20,
+        max_description_len: 1_000,
+        max_duration_seconds: 120 * DAY,
+    };
+
+    client.set_tier_limits(&MarketTier::Basic, &limits);
+
+    assert_eq!(client.get_tier_limits(&MarketTier::Basic), limits);
+    let events = std::format!("{:?}", env.events().all());
+    assert!(events.contains("tier_set"));
+    let result = try_create_in_tier(
+        &env,
+        &client,
+        &admin,
+        MarketTier::Basic,
+        20,
+        1_000,
+        120 * DAY,
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_tier_limits_out_of_bounds_are_rejected() {
+    let (_env, client, _admin) = setup();
+    let valid = TierLimits {
+        max_outcomes: 10,
+        max_description_len: 500,
+        max_duration_seconds: 90 * DAY,
+    };
+    let invalid = [
+        TierLimits {
+            max_outcomes: 1,
+            ..valid.clone()
+        },
+        TierLimits {
+            max_outcomes: 101,
+            ..valid.clone()
+        },
+        TierLimits {
+            max_description_len: 0,
+            ..valid.clone()
+        },
+        TierLimits {
+            max_description_len: 8_001,
+            ..valid.clone()
+        },
+        TierLimits {
+            max_duration_seconds: DAY,
+            ..valid.clone()
+        },
+        TierLimits {
+            max_duration_seconds: 365 * DAY + 1,
+            ..valid.clone()
+        },
+    ];
+
+    for limits in invalid {
+        let result = client.try_set_tier_limits(&MarketTier::Pro, &limits);
+        assert_eq!(result, Err(Ok(ErrorCode::InvalidAmount)));
+    }
+    assert_eq!(client.get_tier_limits(&MarketTier::Pro).max_outcomes, 30);
+}
@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
-use crate::types::ConfigKey;
-use soroban_sdk::{contracttype, Env, Vec};
+use crate::types::{ConfigKey, Guardian};
+use soroban_sdk::{contracttype, Address, Env, Vec};
 
 /// Storage migration context for tracking version changes
 #[contracttype]
@@ -10,6 +10,34 @@ pub struct MigrationContext {
     pub to_version: u32,
 }
 
+/// Storage keys owned by the migration runner.
+#[contracttype]
+#[derive(Clone)]
+pub enum MigrationKey {
+    /// Pre-migration snapshot taken before upgrading from this version.
+    Backup(u32),
+    /// The last completed migration.
+    Log,
+}
+
+/// Values of the integrity-checked keys before a migration ran.
+#[contracttype]
+#[derive(Clone)]
+pub struct MigrationBackup {
+    pub taken_at: u64,
+    pub admin: Option<Address>,
+    pub guardian_set: Option<Vec<Guardian>>,
+}
+
+/// The last completed migration, as recorded under [`MigrationKey::Log`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationRecord {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub migrated_at: u64,
+}
+
 /// Execute a storage migration with rollback capability
 /// Post-migration validation checks key invariants. If validation fails, migration fails atomically.
 pub fn execute_migration(
@@ -50,34 +78,46 @@ pub fn execute_migration(
 
 /// Backup current storage state before migration
 fn backup_storage_state(e: &Env, version: u32) -> Result<(), ErrorCode> {
-    let backup_key = format!("migration:backup:v{}", version);
-    let timestamp = e.ledger().timestamp();
-
-    e.storage().persistent().set(&backup_key, &timestamp);
+    let storage = e.storage().persistent();
+    let backup = MigrationBackup {
+        taken_at: e.ledger().timestamp(),
+        admin: storage.get(&ConfigKey::Admin),
+        guardian_set: storage.get(&ConfigKey::GuardianSet),
+    };
+    storage.set(&MigrationKey::Backup(version), &backup);
 
     Ok(())
 }
 
 /// Restore storage state from backup
 fn restore_storage_state(e: &Env, version: u32) -> Result<(), ErrorCode> {
-    let backup_key = format!("migration:backup:v{}", version);
-
-    if !e.storage().persistent().has(&backup_key) {
-        return Err(ErrorCode::NotAuthorized);
+    let storage = e.storage().persistent();
+    let backup: MigrationBackup = storage
+        .get(&MigrationKey::Backup(version))
+        .ok_or(ErrorCode::NotAuthorized)?;
+
+    match backup.admin {
+        Some(admin) => storage.set(&ConfigKey::Admin, &admin),
+        None => storage.remove(&ConfigKey::Admin),
+    }
+    match backup.guardian_set {
+        Some(guardians) => storage.set(&ConfigKey::GuardianSet, &guardians),
+        None => storage.remove(&ConfigKey::GuardianSet),
     }
 
-    e.storage().persistent().remove(&backup_key);
+    storage.remove(&MigrationKey::Backup(version));
     Ok(())
 }
 
 /// Record migration completion
 fn record_migration(e: &Env, from_version: u32, to_version: u32) -> Result<(), ErrorCode> {
-    let migration_log_key = "migration:log";
-    let timestamp = e.ledger().timestamp();
-
-    let entry = format!("v{}->v{}@{}", from_version, to_version, timestamp);
+    let entry = MigrationRecord {
+        from_version,
+        to_version,
+        migrated_at: e.ledger().timestamp(),
+    };
 
-    e.storage().persistent().set(&migration_log_key, &entry);
+    e.storage().persistent().set(&MigrationKey::Log, &entry);
 
     Ok(())
 }
@@ -87,7 +127,7 @@ fn record_migration(e: &Env, from_version: u32, to_version: u32) -> Result<(), E
 /// Returns Ok(true) if all invariants pass, Ok(false) if validation fails.
 pub fn verify_migration_integrity(e: &Env) -> Result<bool, ErrorCode> {
     // Check critical storage keys exist
-    let required_keys = vec![ConfigKey::Admin, ConfigKey::GuardianSet];
+    let required_keys = [ConfigKey::Admin, ConfigKey::GuardianSet];
 
     for key in required_keys.iter() {
         if !e.storage().persistent().has(key) {
@@ -143,14 +183,13 @@ pub fn reverse_migration(e: &Env, from_version: u32, to_version: u32) -> Result<
     }
 
     // Verify backup exists
-    let backup_key = format!("migration:backup:v{}", from_version);
+    let backup_key = MigrationKey::Backup(from_version);
     if !e.storage().persistent().has(&backup_key) {
         return Err(ErrorCode::NotAuthorized);
     }
 
     // Clear migration log entry
-    let migration_log_key = "migration:log";
-    e.storage().persistent().remove(&migration_log_key);
+    e.storage().persistent().remove(&MigrationKey::Log);
 
     // Remove backup after successful reversal
     e.storage().persistent().remove(&backup_key);
@@ -161,43 +200,66 @@ pub fn reverse_migration(e: &Env, from_version: u32, to_version: u32) -> Result<
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::PredictIQ;
+    use soroban_sdk::testutils::Address as _;
+
+    fn contract_env() -> (Env, Address) {
+        let env = Env::default();
+        let contract_id = env.register(PredictIQ, ());
+        (env, contract_id)
+    }
 
     #[test]
     fn test_migration_version_validation() {
         // Version must progress forward
-        let result = execute_migration(&soroban_sdk::Env::default(), 2, 1, |_| Ok(()));
-        assert!(result.is_err());
+        let (env, contract_id) = contract_env();
+        env.as_contract(&contract_id, || {
+            let result = execute_migration(&env, 2, 1, |_| Ok(()));
+            assert!(result.is_err());
+        });
     }
 
     #[test]
     fn test_migration_with_rollback() {
-        let env = soroban_sdk::Env::default();
-
-        let result = execute_migration(&env, 1, 2, |_| Err(ErrorCode::NotAuthorized));
-
-        assert!(result.is_err());
+        let (env, contract_id) = contract_env();
+        env.as_contract(&contract_id, || {
+            let result = execute_migration(&env, 1, 2, |_| Err(ErrorCode::NotAuthorized));
+            assert!(result.is_err());
+        });
     }
 
     #[test]
     fn test_migration_validation_failure_rolls_back() {
-        use soroban_sdk::Address;
-
-        let env = soroban_sdk::Env::default();
+        let (env, contract_id) = contract_env();
         let admin = Address::generate(&env);
-
-        env.storage().persistent().set(&ConfigKey::Admin, &admin);
-        env.storage()
-            .persistent()
-            .set(&ConfigKey::GuardianSet, &admin);
-
-        // Migration that removes admin key (invalidates state)
-        let result = execute_migration(&env, 1, 2, |_e| {
-            _e.storage().persistent().remove(&ConfigKey::Admin);
-            Ok(())
+        let guardians = Vec::from_array(
+            &env,
+            [Guardian {
+                address: Address::generate(&env),
+                voting_power: 1,
+            }],
+        );
+
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(&ConfigKey::Admin, &admin);
+            env.storage()
+                .persistent()
+                .set(&ConfigKey::GuardianSet, &guardians);
+
+            // Migration that removes admin key (invalidates state)
+            let result = execute_migration(&env, 1, 2, |e| {
+                e.storage().persistent().remove(&ConfigKey::Admin);
+                Ok(())
+            });
+
+            assert_eq!(result, Err(ErrorCode::MigrationValidationError));
+            assert_eq!(
+                env.storage()
+                    .persistent()
+                    .get::<_, Address>(&ConfigKey::Admin),
+                Some(admin.clone())
+            );
+            assert!(!env.storage().persistent().has(&MigrationKey::Backup(1)));
         });
-
-        assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), ErrorCode::MigrationValidationError);
-        assert!(env.storage().persistent().has(&ConfigKey::Admin));
     }
 }
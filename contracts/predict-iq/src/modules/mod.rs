@@ -6,7 +6,9 @@ pub mod disputes;
 pub mod event_archive;
 pub mod events;
 pub mod fees;
+#[cfg(feature = "governance")]
 pub mod governance;
+pub mod guardian_log;
 pub mod markets;
 pub mod migration;
 pub mod monitoring;
@@ -22,3 +24,7 @@ mod disputes_weight_test;
 mod markets_conditional_test;
 #[cfg(test)]
 mod property_invariants_test;
+#[cfg(test)]
+mod payout_math_prop_test;
+#[cfg(test)]
+mod budget_bench_test;
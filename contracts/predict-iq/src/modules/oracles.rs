@@ -1,4 +1,5 @@
 use crate::errors::ErrorCode;
+use crate::oracle_feed_client::{OutcomeFeedClient, OutcomeReport};
 use crate::types::OracleConfig;
 use soroban_sdk::{contracttype, symbol_short, Bytes, Env, Map};
 
@@ -52,7 +53,19 @@ fn hex_char(c: u8) -> Result<u8, ErrorCode> {
 pub fn fetch_pyth_price(e: &Env, config: &OracleConfig) -> Result<PythPrice, ErrorCode> {
     let feed_id = decode_feed_id(e, &config.feed_id)?;
     let client = crate::pyth_client::PythOracleClient::new(e, &config.oracle_address);
-    let (price, conf, expo, publish_time) = client.get_price(&feed_id);
+    // `u64::MAX` opts out of on-chain enforcement; `validate_price` still
+    // checks the publish time afterwards.
+    let quote = if config.max_staleness_seconds == u64::MAX {
+        client.get_price(&feed_id)
+    } else {
+        client.get_price_no_older_than(&feed_id, &config.max_staleness_seconds)
+    };
+    let crate::pyth_client::Price {
+        price,
+        conf,
+        expo,
+        publish_time,
+    } = quote;
     Ok(PythPrice {
         price,
         conf,
@@ -145,6 +158,75 @@ pub fn resolve_with_pyth(
     Ok(outcome)
 }
 
+/// Ask the outcome-feed contract at `config.oracle_address` for `market_id`'s
+/// result. A trapping or missing oracle surfaces as
+/// [`ErrorCode::OracleFailure`].
+pub fn fetch_outcome(
+    e: &Env,
+    market_id: u64,
+    config: &OracleConfig,
+) -> Result<OutcomeReport, ErrorCode> {
+    let client = OutcomeFeedClient::new(e, &config.oracle_address);
+    match client.try_get_outcome(&config.feed_id, &market_id) {
+        Ok(Ok(report)) => Ok(report),
+        _ => Err(ErrorCode::OracleFailure),
+    }
+}
+
+/// Reject a report for another feed, a stale one, or one naming an option
+/// the market does not have.
+pub fn validate_report(
+    e: &Env,
+    report: &OutcomeReport,
+    config: &OracleConfig,
+    num_outcomes: u32,
+) -> Result<(), ErrorCode> {
+    if report.feed_id != config.feed_id {
+        return Err(ErrorCode::OracleFeedMismatch);
+    }
+    if is_stale(
+        e.ledger().timestamp(),
+        report.published_at,
+        effective_max_staleness(config),
+    ) {
+        return Err(ErrorCode::StalePrice);
+    }
+    if report.outcome >= num_outcomes {
+        return Err(ErrorCode::InvalidOutcome);
+    }
+    Ok(())
+}
+
+/// Pull the market's outcome from its oracle contract, validate it, and
+/// record it as oracle 0's result.
+pub fn pull_oracle_result(
+    e: &Env,
+    market_id: u64,
+    config: &OracleConfig,
+    num_outcomes: u32,
+) -> Result<u32, ErrorCode> {
+    let report = fetch_outcome(e, market_id, config)?;
+    validate_report(e, &report, config, num_outcomes)?;
+
+    e.storage()
+        .persistent()
+        .set(&OracleData::Result(market_id, 0), &report.outcome);
+    e.storage()
+        .persistent()
+        .set(&OracleData::LastUpdate(market_id, 0), &report.published_at);
+
+    e.events().publish(
+        (
+            symbol_short!("oracle_ok"),
+            market_id,
+            config.oracle_address.clone(),
+        ),
+        (report.outcome, report.published_at),
+    );
+
+    Ok(report.outcome)
+}
+
 fn determine_outcome(price: &PythPrice, config: &OracleConfig) -> u32 {
     let threshold = config.strike_price.unwrap_or(0);
     if price.price >= threshold {
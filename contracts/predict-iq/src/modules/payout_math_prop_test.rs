@@ -0,0 +1,368 @@
+//! Proptest-based properties for the fee and parimutuel payout math.
+//!
+//! The pure helpers ([`calculate_tiered_fee_with_base`] and
+//! [`parimutuel_payout`]) are exercised over random pool sizes, stake
+//! distributions, tiers and fee rates; a smaller set of cases then drives
+//! the same flows through the contract to check that what it pays out is
+//! what the helpers quote.
+//!
+//! Invariants:
+//!   1. A fee is never negative and never more than the amount it is taken
+//!      from; higher tiers never pay more.
+//!   2. The payouts of all winners never exceed the pool net of fees, and
+//!      truncation leaves less than one unit per winner behind.
+//!   3. Overflow in the payout formula is a typed error, never a panic.
+//!   4. Betting and then taking a refund never returns more than was paid.
+//!   5. `claim_winnings` pays exactly what `parimutuel_payout` quotes.
+//!
+//! Failures shrink to a minimal case before being reported; rerun one with
+//! the seed proptest prints, or raise the case count with `PROPTEST_CASES`.
+#![cfg(test)]
+
+use crate::errors::ErrorCode;
+use crate::modules::bets::parimutuel_payout;
+use crate::modules::fees::calculate_tiered_fee_with_base;
+use crate::types::{CreatorReputation, MarketTier, OracleConfig};
+use crate::{PredictIQ, PredictIQClient};
+use proptest::prelude::*;
+use proptest::test_runner::Config as ProptestConfig;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, String as SorobanString, Vec as SorobanVec,
+};
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Pure-math properties are cheap, so they run the default case count.
+/// Shrinking is given enough iterations to reach a minimal case.
+fn math_config() -> ProptestConfig {
+    ProptestConfig {
+        max_shrink_iters: 10_000,
+        ..ProptestConfig::default()
+    }
+}
+
+/// Each contract case registers a contract and a token, so fewer run.
+fn contract_config() -> ProptestConfig {
+    ProptestConfig {
+        cases: 32,
+        max_shrink_iters: 1_000,
+        ..ProptestConfig::default()
+    }
+}
+
+/// Largest base fee the strategies use: 10%.
+const MAX_BASE_FEE_BPS: i128 = 1_000;
+
+// ---------------------------------------------------------------------------
+// Strategies
+// ---------------------------------------------------------------------------
+
+fn arb_tier() -> impl Strategy<Value = MarketTier> {
+    prop_oneof![
+        Just(MarketTier::Basic),
+        Just(MarketTier::Pro),
+        Just(MarketTier::Institutional),
+    ]
+}
+
+prop_compose! {
+    /// Gross bets as (outcome, amount) on a four-outcome market, with
+    /// amounts from dust to large pools.
+    fn arb_stakes()(
+        bets in prop::collection::vec((0u32..4, 1i128..=1_000_000_000_000_000i128), 1..=24),
+    ) -> std::vec::Vec<(u32, i128)> {
+        bets
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Net bets after fees, and the total fee taken.
+fn settle(
+    bets: &[(u32, i128)],
+    base_fee_bps: i128,
+    tier: &MarketTier,
+) -> (std::vec::Vec<(u32, i128)>, i128) {
+    let mut fees = 0i128;
+    let net = bets
+        .iter()
+        .map(|&(outcome, amount)| {
+            let fee = calculate_tiered_fee_with_base(amount, base_fee_bps, tier).unwrap();
+            fees += fee;
+            (outcome, amount - fee)
+        })
+        .collect();
+    (net, fees)
+}
+
+fn outcome_stake(net: &[(u32, i128)], outcome: u32) -> i128 {
+    net.iter()
+        .filter(|(o, _)| *o == outcome)
+        .map(|(_, amount)| amount)
+        .sum()
+}
+
+fn setup_env(base_fee_bps: i128) -> (Env, PredictIQClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+    let contract_id = env.register(PredictIQ, ());
+    let client = PredictIQClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &base_fee_bps);
+    (env, client, admin)
+}
+
+fn create_market(
+    env: &Env,
+    client: &PredictIQClient,
+    admin: &Address,
+    tier: &MarketTier,
+) -> (u64, Address) {
+    let mut options = SorobanVec::new(env);
+    for label in ["A", "B", "C", "D"] {
+        options.push_back(SorobanString::from_str(env, label));
+    }
+    let oracle = OracleConfig {
+        oracle_address: Address::generate(env),
+        feed_id: SorobanString::from_str(env, "feed"),
+        min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
+    };
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    // Institutional reputation may open markets of any tier.
+    client.set_creator_reputation(admin, &CreatorReputation::Institutional);
+    let market_id = client.create_market(
+        admin,
+        &SorobanString::from_str(env, "Payout Prop Market"),
+        &options,
+        &1_000,
+        &(1_000 + 86_400),
+        &oracle,
+        tier,
+        &token,
+        &0,
+        &0,
+    );
+    (market_id, token)
+}
+
+// ---------------------------------------------------------------------------
+// Invariant 1 — Fees are bounded and tier discounts never cost more
+// ---------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(math_config())]
+
+    #[test]
+    fn prop_fee_is_bounded_by_amount(
+        amount in 0i128..=1_000_000_000_000_000_000i128,
+        base_fee_bps in 0i128..=10_000i128,
+        tier in arb_tier(),
+    ) {
+        let fee = calculate_tiered_fee_with_base(amount, base_fee_bps, &tier).unwrap();
+        prop_assert!(fee >= 0, "negative fee {fee}");
+        prop_assert!(fee <= amount, "fee {fee} exceeds amount {amount}");
+    }
+
+    #[test]
+    fn prop_higher_tiers_never_pay_more(
+        amount in 0i128..=1_000_000_000_000_000_000i128,
+        base_fee_bps in 0i128..=10_000i128,
+    ) {
+        let fee = |tier| calculate_tiered_fee_with_base(amount, base_fee_bps, &tier).unwrap();
+        let (basic, pro, institutional) =
+            (fee(MarketTier::Basic), fee(MarketTier::Pro), fee(MarketTier::Institutional));
+        prop_assert!(institutional <= pro && pro <= basic, "{basic} / {pro} / {institutional}");
+    }
+
+    #[test]
+    fn prop_fee_is_monotonic_in_amount(
+        amount in 0i128..=1_000_000_000_000_000i128,
+        extra in 0i128..=1_000_000_000i128,
+        base_fee_bps in 0i128..=10_000i128,
+        tier in arb_tier(),
+    ) {
+        let smaller = calculate_tiered_fee_with_base(amount, base_fee_bps, &tier).unwrap();
+        let larger = calculate_tiered_fee_with_base(amount + extra, base_fee_bps, &tier).unwrap();
+        prop_assert!(smaller <= larger);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Invariant 2 — Payouts never exceed the pool net of fees
+// ---------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(math_config())]
+
+    #[test]
+    fn prop_payouts_never_exceed_pool_minus_fees(
+        bets in arb_stakes(),
+        base_fee_bps in 0i128..=MAX_BASE_FEE_BPS,
+        tier in arb_tier(),
+        winning_outcome in 0u32..4,
+    ) {
+        let (net, fees) = settle(&bets, base_fee_bps, &tier);
+        let gross: i128 = bets.iter().map(|(_, amount)| amount).sum();
+        let total_staked: i128 = net.iter().map(|(_, amount)| amount).sum();
+        prop_assert_eq!(total_staked, gross - fees);
+
+        let winning_stake = outcome_stake(&net, winning_outcome);
+        let winners: std::vec::Vec<i128> = net
+            .iter()
+            .filter(|(outcome, _)| *outcome == winning_outcome)
+            .map(|(_, amount)| *amount)
+            .collect();
+
+        let mut paid = 0i128;
+        for &amount in &winners {
+            let payout = parimutuel_payout(amount, total_staked, winning_stake).unwrap();
+            prop_assert!(payout >= amount, "winner paid {payout} for a stake of {amount}");
+            paid += payout;
+        }
+
+        prop_assert!(paid <= total_staked, "paid {paid} from a pool of {total_staked}");
+        if winning_stake > 0 {
+            // Each payout truncates by less than one unit.
+            let dust = total_staked - paid;
+            prop_assert!(dust < winners.len() as i128, "{dust} left for {} winners", winners.len());
+        }
+    }
+
+    #[test]
+    fn prop_sole_winner_takes_the_pool(
+        stake in 1i128..=1_000_000_000_000_000i128,
+        losing in 0i128..=1_000_000_000_000_000i128,
+    ) {
+        let total_staked = stake + losing;
+        prop_assert_eq!(parimutuel_payout(stake, total_staked, stake).unwrap(), total_staked);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Invariant 3 — Overflow is a typed error
+// ---------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(math_config())]
+
+    #[test]
+    fn prop_payout_overflow_is_a_typed_error(
+        bet_amount in 1i128..=i128::MAX,
+        total_staked in 1i128..=i128::MAX,
+        winning_stake in 1i128..=i128::MAX,
+    ) {
+        let payout = parimutuel_payout(bet_amount, total_staked, winning_stake);
+        match bet_amount.checked_mul(total_staked) {
+            None => prop_assert_eq!(payout, Err(ErrorCode::ArithmeticOverflow)),
+            Some(product) => prop_assert_eq!(payout, Ok(product / winning_stake)),
+        }
+    }
+
+    #[test]
+    fn prop_fee_overflow_is_a_typed_error(
+        amount in 1i128..=i128::MAX,
+        base_fee_bps in 1i128..=10_000i128,
+        tier in arb_tier(),
+    ) {
+        // Never panics; overflow surfaces as an error.
+        let _ = calculate_tiered_fee_with_base(amount, base_fee_bps, &tier);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Invariant 4 — Bet then refund never profits
+// ---------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(contract_config())]
+
+    #[test]
+    fn prop_bet_then_refund_never_profits(
+        amount in 1i128..=1_000_000_000_000i128,
+        base_fee_bps in 0i128..=MAX_BASE_FEE_BPS,
+        tier in arb_tier(),
+    ) {
+        let (env, client, admin) = setup_env(base_fee_bps);
+        let (market_id, token) = create_market(&env, &client, &admin, &tier);
+        env.ledger().set_timestamp(0);
+
+        let bettor = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token).mint(&bettor, &amount);
+        prop_assume!(client.try_place_bet(&bettor, &market_id, &0, &amount, &token, &None).is_ok());
+
+        client.cancel_market_admin(&market_id);
+        let refund = client.withdraw_refund(&bettor, &market_id, &0, &token);
+
+        let balance = token::Client::new(&env, &token).balance(&bettor);
+        prop_assert!(refund <= amount, "refund {refund} for a bet of {amount}");
+        prop_assert!(balance <= amount, "balance {balance} after betting {amount}");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Invariant 5 — Executed claims equal quoted payouts
+// ---------------------------------------------------------------------------
+
+proptest! {
+    #![proptest_config(contract_config())]
+
+    #[test]
+    fn prop_claims_match_quotes_and_stay_within_pool(
+        bets in prop::collection::vec((0u32..4, 1i128..=1_000_000_000_000i128), 1..=8),
+        base_fee_bps in 0i128..=MAX_BASE_FEE_BPS,
+        tier in arb_tier(),
+        winning_outcome in 0u32..4,
+    ) {
+        let (env, client, admin) = setup_env(base_fee_bps);
+        let (market_id, token) = create_market(&env, &client, &admin, &tier);
+        env.ledger().set_timestamp(0);
+
+        let minter = token::StellarAssetClient::new(&env, &token);
+        let mut placed: std::vec::Vec<(Address, u32, i128)> = std::vec::Vec::new();
+        for &(outcome, amount) in &bets {
+            let bettor = Address::generate(&env);
+            minter.mint(&bettor, &amount);
+            if client.try_place_bet(&bettor, &market_id, &outcome, &amount, &token, &None).is_ok() {
+                placed.push((bettor, outcome, amount));
+            }
+        }
+        prop_assume!(!placed.is_empty());
+
+        let gross: std::vec::Vec<(u32, i128)> =
+            placed.iter().map(|(_, outcome, amount)| (*outcome, *amount)).collect();
+        let (net, fees) = settle(&gross, base_fee_bps, &tier);
+        let total_staked: i128 = net.iter().map(|(_, amount)| amount).sum();
+        prop_assert_eq!(client.get_market(&market_id).unwrap().total_staked, total_staked);
+        prop_assert_eq!(client.get_revenue(&token), fees);
+
+        client.resolve_market(&market_id, &winning_outcome);
+        let winning_stake = outcome_stake(&net, winning_outcome);
+
+        let mut paid = 0i128;
+        for ((bettor, outcome, _), (_, net_amount)) in placed.iter().zip(&net) {
+            if *outcome != winning_outcome {
+                continue;
+            }
+            let quote = parimutuel_payout(*net_amount, total_staked, winning_stake).unwrap();
+            let claimed = client.claim_winnings(bettor, &market_id, &token);
+            prop_assert_eq!(claimed, quote);
+            paid += claimed;
+        }
+
+        prop_assert!(paid <= total_staked, "paid {paid} from a pool of {total_staked}");
+        // Fees stay in the contract, withdrawable, after every winner is paid.
+        let held = token::Client::new(&env, &token).balance(&client.address);
+        prop_assert!(held >= fees, "contract holds {held} but owes {fees} in fees");
+    }
+}
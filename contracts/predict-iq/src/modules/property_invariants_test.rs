@@ -71,15 +71,14 @@ fn create_two_option_market(
     (market_id, token)
 }
 
-fn assert_stake_conservation(env: &Env, client: &PredictIQClient, market_id: u64) {
+fn assert_stake_conservation(_env: &Env, client: &PredictIQClient, market_id: u64) {
     let market = client.get_market(&market_id).unwrap();
     let mut outcome_sum: i128 = 0;
     for o in 0..10u32 {
-        outcome_sum += client.get_outcome_stake(&market_id, &o);
+        outcome_sum += market.outcome_stakes.get(o).unwrap_or(0);
     }
     assert_eq!(
-        outcome_sum,
-        market.total_staked,
+        outcome_sum, market.total_staked,
         "stake conservation violated: sum(outcome_stakes)={outcome_sum} != total_staked={}",
         market.total_staked
     );
@@ -106,14 +105,13 @@ proptest! {
     #[test]
     fn prop_stake_conservation_arbitrary_bets(bets in arb_bet_sequence()) {
         let (env, client, admin) = setup_env();
-        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 2_000);
+        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 1_000 + 86_400);
 
         env.ledger().set_timestamp(0);
 
         for (outcome, amount) in &bets {
             let user = Address::generate(&env);
-            soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-                .mint(&user, amount);
+            soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&user, amount);
             let _ = client.try_place_bet(&user, &market_id, outcome, amount, &token, &None);
             assert_stake_conservation(&env, &client, market_id);
         }
@@ -130,7 +128,7 @@ proptest! {
         amounts in prop::collection::vec(1i128..=5_000i128, 0..=4),
     ) {
         let (env, client, admin) = setup_env();
-        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 2_000);
+        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 1_000 + 86_400);
 
         env.ledger().set_timestamp(0);
 
@@ -138,8 +136,7 @@ proptest! {
         for (i, amount) in amounts.iter().enumerate() {
             let outcome = (i % 2) as u32;
             let user = Address::generate(&env);
-            soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-                .mint(&user, amount);
+            soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&user, amount);
             let _ = client.try_place_bet(&user, &market_id, &outcome, amount, &token, &None);
         }
 
@@ -151,8 +148,7 @@ proptest! {
 
         // Attempting to place a bet on a cancelled market must fail
         let late_user = Address::generate(&env);
-        soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-            .mint(&late_user, &1_000i128);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&late_user, &1_000i128);
         let result = client.try_place_bet(&late_user, &market_id, &0, &500, &token, &None);
         assert!(result.is_err(), "bets on a Cancelled market must be rejected");
     }
@@ -169,13 +165,12 @@ proptest! {
         winning_outcome in 0u32..=1u32,
     ) {
         let (env, client, admin) = setup_env();
-        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 2_000);
+        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 1_000 + 86_400);
 
         env.ledger().set_timestamp(0);
 
         let user = Address::generate(&env);
-        soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-            .mint(&user, &(bet_amount * 2));
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&user, &(bet_amount * 2));
         client.place_bet(&user, &market_id, &winning_outcome, &bet_amount, &token, &None);
 
         // Resolve
@@ -186,8 +181,7 @@ proptest! {
 
         // Bets on a resolved market must fail
         let post_user = Address::generate(&env);
-        soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-            .mint(&post_user, &1_000i128);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&post_user, &1_000i128);
         let result = client.try_place_bet(&post_user, &market_id, &winning_outcome, &500, &token, &None);
         assert!(result.is_err(), "bets on a Resolved market must be rejected");
     }
@@ -203,15 +197,14 @@ proptest! {
         amounts in prop::collection::vec(1i128..=50_000i128, 1..=10),
     ) {
         let (env, client, admin) = setup_env();
-        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 2_000);
+        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 1_000 + 86_400);
 
         env.ledger().set_timestamp(0);
 
         for (i, amount) in amounts.iter().enumerate() {
             let outcome = (i % 2) as u32;
             let user = Address::generate(&env);
-            soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-                .mint(&user, amount);
+            soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&user, amount);
             let _ = client.try_place_bet(&user, &market_id, &outcome, amount, &token, &None);
 
             let market = client.get_market(&market_id).unwrap();
@@ -234,7 +227,7 @@ proptest! {
         amounts in prop::collection::vec(1i128..=10_000i128, 1..=6),
     ) {
         let (env, client, admin) = setup_env();
-        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 2_000);
+        let (market_id, token) = create_two_option_market(&env, &client, &admin, 1_000, 1_000 + 86_400);
 
         env.ledger().set_timestamp(0);
 
@@ -242,8 +235,7 @@ proptest! {
         for (i, amount) in amounts.iter().enumerate() {
             let outcome = (i % 2) as u32;
             let user = Address::generate(&env);
-            soroban_sdk::testutils::StellarAssetContract::new(token.clone(), &env)
-                .mint(&user, amount);
+            soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&user, amount);
             if client.try_place_bet(&user, &market_id, &outcome, amount, &token, &None).is_ok() {
                 bettors.push((user, outcome));
             }
@@ -1,6 +1,6 @@
 use crate::errors::ErrorCode;
 use crate::modules::{markets, oracles, voting};
-use crate::types::MarketStatus;
+use crate::types::{MarketStatus, ResolutionSource};
 use soroban_sdk::{Env, Symbol};
 
 pub const DEFAULT_DISPUTE_WINDOW_SECONDS: u64 = 259_200; // 72 hours
@@ -92,38 +92,46 @@ pub fn attempt_oracle_resolution(e: &Env, market_id: u64) -> Result<(), ErrorCod
         return Err(ErrorCode::ResolutionNotReady);
     }
 
-    // Issue #508: Validate oracle staleness before resolution
-    oracles::validate_oracle_staleness(e, market_id, &market.oracle_config)?;
-
-    // Attempt oracle resolution
-    if let Some(oracle_outcome) = oracles::get_oracle_result(e, market_id, 0) {
-        let old_status = soroban_sdk::String::from_slice(e, "Active");
-        let new_status = soroban_sdk::String::from_slice(e, "PendingResolution");
-
-        market.status = MarketStatus::PendingResolution;
-        market.winning_outcome = Some(oracle_outcome);
-        market.pending_resolution_timestamp = Some(e.ledger().timestamp());
-
-        markets::update_market(e, market);
-
-        // Emit market state change event for indexing
-        crate::modules::events::emit_market_state_changed(
+    // A result pushed with set_oracle_result wins; otherwise pull one from
+    // the oracle contract, which validates feed, staleness and range itself.
+    let oracle_outcome = match oracles::get_oracle_result(e, market_id, 0) {
+        Some(outcome) => {
+            // Issue #508: Validate oracle staleness before resolution
+            oracles::validate_oracle_staleness(e, market_id, &market.oracle_config)?;
+            outcome
+        }
+        None => oracles::pull_oracle_result(
             e,
             market_id,
-            old_status,
-            new_status,
-            e.ledger().timestamp(),
-        );
-
-        e.events().publish(
-            (Symbol::new(e, "oracle_resolved"), market_id),
-            oracle_outcome,
-        );
+            &market.oracle_config,
+            market.options.len(),
+        )?,
+    };
+
+    let old_status = soroban_sdk::String::from_slice(e, "Active");
+    let new_status = soroban_sdk::String::from_slice(e, "PendingResolution");
+
+    market.status = MarketStatus::PendingResolution;
+    market.winning_outcome = Some(oracle_outcome);
+    market.pending_resolution_timestamp = Some(e.ledger().timestamp());
+
+    markets::update_market(e, market);
+
+    // Emit market state change event for indexing
+    crate::modules::events::emit_market_state_changed(
+        e,
+        market_id,
+        old_status,
+        new_status,
+        e.ledger().timestamp(),
+    );
+
+    e.events().publish(
+        (Symbol::new(e, "oracle_resolved"), market_id),
+        oracle_outcome,
+    );
 
-        Ok(())
-    } else {
-        Err(ErrorCode::OracleFailure)
-    }
+    Ok(())
 }
 
 /// T+24h: Finalize resolution if no dispute filed
@@ -178,6 +186,13 @@ pub fn finalize_resolution(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
 
             // Calculate voting outcome
             let winning_outcome = calculate_voting_outcome(e, &market)?;
+            voting::record_dispute_summary(
+                e,
+                &market,
+                winning_outcome,
+                ResolutionSource::Vote,
+                true,
+            );
             let old_status = soroban_sdk::String::from_slice(e, "Disputed");
             let new_status = soroban_sdk::String::from_slice(e, "Resolved");
 
@@ -207,6 +222,12 @@ pub fn finalize_resolution(e: &Env, market_id: u64) -> Result<(), ErrorCode> {
     }
 }
 
+/// Whether the leading outcome of a disputed market's vote has the 60%
+/// majority `finalize_resolution` requires.
+pub(crate) fn has_voting_majority(e: &Env, market: &crate::types::Market) -> bool {
+    calculate_voting_outcome(e, market).is_ok()
+}
+
 /// Calculate voting outcome with 60% majority requirement
 fn calculate_voting_outcome(e: &Env, market: &crate::types::Market) -> Result<u32, ErrorCode> {
     let mut total_votes: i128 = 0;
@@ -253,17 +274,23 @@ mod tests {
     #[test]
     fn get_default_dispute_window_returns_default_when_unset() {
         let e = Env::default();
-        assert_eq!(get_default_dispute_window(&e), DEFAULT_DISPUTE_WINDOW_SECONDS);
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            assert_eq!(get_default_dispute_window(&e), DEFAULT_DISPUTE_WINDOW_SECONDS);
+        });
     }
 
     /// Admin-configured dispute window is returned after set_dispute_window.
     #[test]
     fn get_default_dispute_window_returns_configured_value() {
         let e = Env::default();
-        // Bypass admin check by writing directly to storage.
-        e.storage()
-            .persistent()
-            .set(&crate::types::ConfigKey::DefaultDisputeWindow, &7_200u64);
-        assert_eq!(get_default_dispute_window(&e), 7_200u64);
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            // Bypass admin check by writing directly to storage.
+            e.storage()
+                .persistent()
+                .set(&crate::types::ConfigKey::DefaultDisputeWindow, &7_200u64);
+            assert_eq!(get_default_dispute_window(&e), 7_200u64);
+        });
     }
 }
@@ -40,14 +40,13 @@ pub fn check_token_not_frozen(
     token_address: &Address,
     user: &Address,
 ) -> Result<(), ErrorCode> {
-    let client = token::Client::new(e, token_address);
+    let client = token::StellarAssetClient::new(e, token_address);
 
-    // Attempt to call frozen() - if the token doesn't support freeze,
-    // this will return an error internally, but we treat it as "not frozen"
-    // since the token doesn't have that capability.
-    match client.frozen(user) {
-        Ok(is_frozen) => {
-            if is_frozen {
+    // A frozen trustline is a deauthorized one. Tokens that are not Stellar
+    // asset contracts have no `authorized` and are treated as not frozen.
+    match client.try_authorized(user) {
+        Ok(Ok(authorized)) => {
+            if !authorized {
                 e.events().publish(
                     (
                         symbol_short!("token_frz"),
@@ -61,7 +60,7 @@ pub fn check_token_not_frozen(
                 Ok(())
             }
         }
-        Err(_) => {
+        _ => {
             // Token doesn't support freeze operation or view is restricted
             // Treat as not frozen - the transfer attempt itself will fail if needed
             Ok(())
@@ -2,8 +2,22 @@ use crate::errors::ErrorCode;
 use crate::modules::markets;
 // Issue #171: ConfigKey (including GovernanceToken variant) must be explicitly imported
 // from types. Previously missing, causing compilation failure in cast_vote.
-use crate::types::{ConfigKey, LockedTokens, MarketStatus, Vote};
-use soroban_sdk::{contracttype, token, Address, Env, IntoVal, Symbol, Val, Vec};
+use crate::types::{
+    ConfigKey, DisputeSummary, LockedTokens, Market, MarketStatus, ResolutionSource, Vote, VoteV1,
+    VoteWeightSource, BET_TTL_HIGH_THRESHOLD, BET_TTL_LOW_THRESHOLD, CANCEL_OUTCOME_INDEX,
+};
+use soroban_sdk::{
+    contractclient, contracttype, token, Address, Env, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Governance tokens that can report historical balances. The standard
+/// Stellar asset contract does not implement this, so votes in SAC-backed
+/// deployments take the locking fallback in [`cast_vote`].
+#[contractclient(name = "SnapshotTokenClient")]
+pub trait SnapshotToken {
+    /// `id`'s balance as of the close of `ledger`.
+    fn balance_at(env: Env, id: Address, ledger: u32) -> i128;
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -15,6 +29,8 @@ pub enum DataKey {
     LockedBalance(u64, Address), // market_id, voter -> amount
     /// Registered voters for a disputed market — drives O(n) deep prune (Issue #84).
     DisputeVoters(u64), // market_id -> Vec<Address>
+    /// Written once when a disputed market resolves; not touched by pruning.
+    DisputeSummary(u64), // market_id -> DisputeSummary
 }
 
 pub fn cast_vote(
@@ -32,7 +48,7 @@ pub fn cast_vote(
         return Err(ErrorCode::MarketNotDisputed);
     }
 
-    if outcome >= market.options.len() {
+    if outcome >= market.options.len() && outcome != CANCEL_OUTCOME_INDEX {
         return Err(ErrorCode::InvalidOutcome);
     }
 
@@ -40,7 +56,7 @@ pub fn cast_vote(
 
     // Issue #175: Allow vote revision - voters can change their vote before resolution deadline
     // This enables more flexible governance where voters can respond to new information
-    let old_vote: Option<Vote> = e.storage().persistent().get(&vote_key);
+    let old_vote = get_vote(e, market_id, voter.clone());
     if let Some(ref old_vote_data) = old_vote {
         // Decrement the old outcome tally when vote is revised
         let old_tally_key = DataKey::VoteTally(market_id, old_vote_data.outcome);
@@ -77,9 +93,13 @@ pub fn cast_vote(
         .get(&ConfigKey::GovernanceToken)
         .ok_or(ErrorCode::GovernanceTokenNotSet)?;
 
-    let actual_weight = match try_get_balance_at(e, &gov_token, &voter, snapshot_ledger) {
-        Ok(balance) => balance,
-        Err(_) => {
+    // Any failure of the snapshot call — the function is missing, traps, or
+    // returns the wrong type — means the token cannot vouch for a
+    // historical balance, so the vote falls back to escrow.
+    let snapshot = SnapshotTokenClient::new(e, &gov_token).try_balance_at(&voter, &snapshot_ledger);
+    let (actual_weight, weight_source) = match snapshot {
+        Ok(Ok(balance)) => (balance, VoteWeightSource::Snapshot),
+        _ => {
             // Issue #37: Fallback — lock tokens and track per-user balance
             let token_client = token::Client::new(e, &gov_token);
             let current_balance = token_client.balance(&voter);
@@ -107,7 +127,7 @@ pub fn cast_vote(
                 .persistent()
                 .set(&DataKey::LockedTokens(market_id, voter.clone()), &locked);
 
-            weight
+            (weight, VoteWeightSource::Locked)
         }
     };
 
@@ -137,6 +157,7 @@ pub fn cast_vote(
         voter: voter.clone(),
         outcome,
         weight: normalized_weight,
+        weight_source,
     };
 
     e.storage().persistent().set(&vote_key, &vote);
@@ -157,25 +178,18 @@ pub fn cast_vote(
     current_tally += normalized_weight;
     e.storage().persistent().set(&tally_key, &current_tally);
 
-    crate::modules::events::emit_vote_cast(e, market_id, voter, outcome, normalized_weight);
+    crate::modules::events::emit_vote_cast(
+        e,
+        market_id,
+        voter,
+        outcome,
+        normalized_weight,
+        weight_source,
+    );
 
     Ok(())
 }
 
-fn try_get_balance_at(
-    e: &Env,
-    token: &Address,
-    account: &Address,
-    ledger: u32,
-) -> Result<i128, ErrorCode> {
-    let args: Vec<Val> = soroban_sdk::vec![e, account.clone().into_val(e), ledger.into_val(e)];
-
-    match e.try_invoke_contract::<i128, ErrorCode>(token, &Symbol::new(e, "balance_at"), args) {
-        Ok(Ok(balance)) => Ok(balance),
-        _ => Err(ErrorCode::OracleFailure),
-    }
-}
-
 /// Fetch the decimal precision of a token contract (defaults to 7 for Stellar native tokens).
 fn get_token_decimals(e: &Env, token: &Address) -> u32 {
     let args: Vec<Val> = soroban_sdk::vec![e];
@@ -233,6 +247,37 @@ pub fn unlock_tokens(e: &Env, voter: Address, market_id: u64) -> Result<(), Erro
     Ok(())
 }
 
+/// Votes stored before `weight_source` existed ([`VoteV1`]) are upgraded on
+/// read: `Locked` while the voter still has tokens locked on the market,
+/// `Snapshot` otherwise. A locked vote whose tokens were already unlocked
+/// therefore reads as `Snapshot`; nothing is left to tell them apart.
+pub fn get_vote(e: &Env, market_id: u64, voter: Address) -> Option<Vote> {
+    // Decoding into the wrong struct traps, so pick the layout by its fields.
+    let raw: Map<Symbol, Val> = e
+        .storage()
+        .persistent()
+        .get(&DataKey::Vote(market_id, voter.clone()))?;
+    if raw.contains_key(Symbol::new(e, "weight_source")) {
+        return Vote::try_from_val(e, raw.as_val()).ok();
+    }
+    let legacy = VoteV1::try_from_val(e, raw.as_val()).ok()?;
+    let source = if get_locked_balance(e, market_id, voter) > 0 {
+        VoteWeightSource::Locked
+    } else {
+        VoteWeightSource::Snapshot
+    };
+    Some(legacy.upgrade(source))
+}
+
+/// Governance tokens `voter` has locked in votes on `market_id`, summed over
+/// revisions; zero for snapshot-weighted votes.
+pub fn get_locked_balance(e: &Env, market_id: u64, voter: Address) -> i128 {
+    e.storage()
+        .persistent()
+        .get(&DataKey::LockedBalance(market_id, voter))
+        .unwrap_or(0)
+}
+
 pub fn get_tally(e: &Env, market_id: u64, outcome: u32) -> i128 {
     e.storage()
         .persistent()
@@ -240,6 +285,80 @@ pub fn get_tally(e: &Env, market_id: u64, outcome: u32) -> i128 {
         .unwrap_or(0)
 }
 
+/// Record how the vote on a disputed market broke down. Called as the
+/// market moves from Disputed to Resolved, while the tallies and voter
+/// registry still exist.
+pub fn record_dispute_summary(
+    e: &Env,
+    market: &Market,
+    winning_outcome: u32,
+    source: ResolutionSource,
+    quorum_reached: bool,
+) {
+    let mut outcome_totals = Vec::new(e);
+    for outcome in 0..market.options.len() {
+        outcome_totals.push_back(get_tally(e, market.id, outcome));
+    }
+    let voter_count = e
+        .storage()
+        .persistent()
+        .get::<_, Vec<Address>>(&DataKey::DisputeVoters(market.id))
+        .map_or(0, |voters| voters.len());
+
+    let summary = DisputeSummary {
+        market_id: market.id,
+        outcome_totals,
+        voter_count,
+        quorum_reached,
+        winning_outcome,
+        source,
+        snapshot_ledger: market.dispute_snapshot_ledger,
+        resolved_at: e.ledger().timestamp(),
+    };
+
+    // The summary is the only record of the vote after the market is pruned,
+    // so it gets the longer bet-record retention rather than the market TTL.
+    let key = DataKey::DisputeSummary(market.id);
+    e.storage().persistent().set(&key, &summary);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BET_TTL_LOW_THRESHOLD, BET_TTL_HIGH_THRESHOLD);
+}
+
+/// The vote breakdown of a resolved dispute. Refused with
+/// `MarketNotResolved` while the market is still open, so a running vote
+/// cannot be read off the contract.
+///
+/// Votes have no commit-reveal phase, so the refusal always applies. A
+/// commit-reveal mode would need its own rule for reads before resolution.
+pub fn get_dispute_summary(e: &Env, market_id: u64) -> Result<DisputeSummary, ErrorCode> {
+    if let Some(summary) = e
+        .storage()
+        .persistent()
+        .get(&DataKey::DisputeSummary(market_id))
+    {
+        return Ok(summary);
+    }
+    match markets::get_market(e, market_id) {
+        Some(market) if market.status == MarketStatus::Resolved => {
+            Err(ErrorCode::MarketNotDisputed)
+        }
+        Some(_) => Err(ErrorCode::MarketNotResolved),
+        None => Err(ErrorCode::MarketNotFound),
+    }
+}
+
+/// `voter`'s outcome and normalized weight in a resolved dispute, or `None`
+/// if they did not vote. Refused on the same terms as [`get_dispute_summary`].
+pub fn get_voter_choice(
+    e: &Env,
+    market_id: u64,
+    voter: Address,
+) -> Result<Option<(u32, i128)>, ErrorCode> {
+    get_dispute_summary(e, market_id)?;
+    Ok(get_vote(e, market_id, voter).map(|vote| (vote.outcome, vote.weight)))
+}
+
 /// Clears vote tallies, per-voter vote/lock ledgers, and the dispute voter registry.
 /// Safe to call when no voting occurred (only removes keys that exist).
 pub fn prune_market_voting_state(e: &Env, market_id: u64, num_outcomes: u32) {
@@ -260,7 +379,7 @@ pub fn prune_market_voting_state(e: &Env, market_id: u64, num_outcomes: u32) {
     }
     e.storage().persistent().remove(&reg_key);
 
-    for o in 0..num_outcomes {
+    for o in (0..num_outcomes).chain(core::iter::once(CANCEL_OUTCOME_INDEX)) {
         e.storage()
             .persistent()
             .remove(&DataKey::VoteTally(market_id, o));
@@ -276,114 +395,125 @@ mod import_tests {
     #[test]
     fn governance_token_config_key_round_trips() {
         let e = Env::default();
-        let token = Address::generate(&e);
-        e.storage()
-            .instance()
-            .set(&ConfigKey::GovernanceToken, &token);
-        let stored: Option<Address> = e.storage().instance().get(&ConfigKey::GovernanceToken);
-        assert_eq!(stored, Some(token));
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            let token = Address::generate(&e);
+            e.storage()
+                .instance()
+                .set(&ConfigKey::GovernanceToken, &token);
+            let stored: Option<Address> = e.storage().instance().get(&ConfigKey::GovernanceToken);
+            assert_eq!(stored, Some(token));
+        });
     }
 
     /// Issue #171: cast_vote returns GovernanceTokenNotSet when token is not configured.
     #[test]
     fn cast_vote_returns_error_when_governance_token_not_set() {
         let e = Env::default();
-        // GovernanceToken not set in storage — get returns None
-        let stored: Option<Address> = e.storage().instance().get(&ConfigKey::GovernanceToken);
-        assert!(
-            stored.is_none(),
-            "GovernanceToken must be absent to trigger the error"
-        );
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            // GovernanceToken not set in storage — get returns None
+            let stored: Option<Address> = e.storage().instance().get(&ConfigKey::GovernanceToken);
+            assert!(
+                stored.is_none(),
+                "GovernanceToken must be absent to trigger the error"
+            );
+        });
     }
 }
 
 #[cfg(test)]
 mod prune_tests {
     use super::{prune_market_voting_state, DataKey};
-    use crate::types::{LockedTokens, Vote};
+    use crate::types::{LockedTokens, Vote, VoteWeightSource};
     use soroban_sdk::{testutils::Address as _, Address, Env};
 
     #[test]
     fn prune_clears_votes_locks_tallies_and_registry() {
         let e = Env::default();
-        let market_id = 42u64;
-        let v1 = Address::generate(&e);
-        let v2 = Address::generate(&e);
-
-        e.storage().persistent().set(
-            &DataKey::Vote(market_id, v1.clone()),
-            &Vote {
-                market_id,
-                voter: v1.clone(),
-                outcome: 0,
-                weight: 100,
-            },
-        );
-        e.storage().persistent().set(
-            &DataKey::Vote(market_id, v2.clone()),
-            &Vote {
-                market_id,
-                voter: v2.clone(),
-                outcome: 1,
-                weight: 200,
-            },
-        );
-        e.storage()
-            .persistent()
-            .set(&DataKey::VoteTally(market_id, 0), &100_i128);
-        e.storage()
-            .persistent()
-            .set(&DataKey::VoteTally(market_id, 1), &200_i128);
-        e.storage().persistent().set(
-            &DataKey::LockedTokens(market_id, v1.clone()),
-            &LockedTokens {
-                voter: v1.clone(),
-                market_id,
-                amount: 50,
-                unlock_time: 0,
-            },
-        );
-        e.storage()
-            .persistent()
-            .set(&DataKey::LockedBalance(market_id, v1.clone()), &50_i128);
+        let contract_id = e.register(crate::PredictIQ, ());
+        e.as_contract(&contract_id, || {
+            let market_id = 42u64;
+            let v1 = Address::generate(&e);
+            let v2 = Address::generate(&e);
+
+            e.storage().persistent().set(
+                &DataKey::Vote(market_id, v1.clone()),
+                &Vote {
+                    market_id,
+                    voter: v1.clone(),
+                    outcome: 0,
+                    weight: 100,
+                    weight_source: VoteWeightSource::Locked,
+                },
+            );
+            e.storage().persistent().set(
+                &DataKey::Vote(market_id, v2.clone()),
+                &Vote {
+                    market_id,
+                    voter: v2.clone(),
+                    outcome: 1,
+                    weight: 200,
+                    weight_source: VoteWeightSource::Snapshot,
+                },
+            );
+            e.storage()
+                .persistent()
+                .set(&DataKey::VoteTally(market_id, 0), &100_i128);
+            e.storage()
+                .persistent()
+                .set(&DataKey::VoteTally(market_id, 1), &200_i128);
+            e.storage().persistent().set(
+                &DataKey::LockedTokens(market_id, v1.clone()),
+                &LockedTokens {
+                    voter: v1.clone(),
+                    market_id,
+                    amount: 50,
+                    unlock_time: 0,
+                },
+            );
+            e.storage()
+                .persistent()
+                .set(&DataKey::LockedBalance(market_id, v1.clone()), &50_i128);
 
-        let mut reg = soroban_sdk::Vec::new(&e);
-        reg.push_back(v1.clone());
-        reg.push_back(v2.clone());
-        e.storage()
-            .persistent()
-            .set(&DataKey::DisputeVoters(market_id), &reg);
+            let mut reg = soroban_sdk::Vec::new(&e);
+            reg.push_back(v1.clone());
+            reg.push_back(v2.clone());
+            e.storage()
+                .persistent()
+                .set(&DataKey::DisputeVoters(market_id), &reg);
 
-        prune_market_voting_state(&e, market_id, 2);
+            prune_market_voting_state(&e, market_id, 2);
 
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::Vote(market_id, v1.clone())));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::Vote(market_id, v2.clone())));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::LockedTokens(market_id, v1.clone())));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::LockedBalance(market_id, v1.clone())));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::VoteTally(market_id, 0)));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::VoteTally(market_id, 1)));
-        assert!(!e
-            .storage()
-            .persistent()
-            .has(&DataKey::DisputeVoters(market_id)));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::Vote(market_id, v1.clone())));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::Vote(market_id, v2.clone())));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::LockedTokens(market_id, v1.clone())));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::LockedBalance(market_id, v1.clone())));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::VoteTally(market_id, 0)));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::VoteTally(market_id, 1)));
+            assert!(!e
+                .storage()
+                .persistent()
+                .has(&DataKey::DisputeVoters(market_id)));
+        });
     }
 }
 
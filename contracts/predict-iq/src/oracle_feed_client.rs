@@ -0,0 +1,36 @@
+//! Outcome-feed oracle client.
+//!
+//! Markets whose result is a discrete outcome rather than a price point read
+//! it from a contract at [`crate::types::OracleConfig::oracle_address`] that
+//! implements [`OutcomeFeedInterface`]. [`crate::modules::oracles::pull_oracle_result`]
+//! calls it when `attempt_oracle_resolution` runs and no result has been
+//! pushed for the market.
+//!
+//! The report echoes the feed it answers for, so the caller can reject a
+//! report for the wrong feed, and carries its publication time so stale
+//! results are rejected with the same window as pushed ones.
+
+use soroban_sdk::{contractclient, contracttype, Env, String};
+
+/// One oracle answer for a market.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeReport {
+    /// Feed the report was published on; must match the market's `feed_id`.
+    pub feed_id: String,
+    /// Index of the winning option.
+    pub outcome: u32,
+    /// Unix timestamp (seconds) the outcome was published.
+    pub published_at: u64,
+}
+
+/// Cross-contract interface to an outcome-feed oracle.
+///
+/// `#[contractclient]` generates an `OutcomeFeedClient`; its `try_` methods
+/// turn a trapping oracle into an error instead of aborting resolution.
+#[contractclient(name = "OutcomeFeedClient")]
+pub trait OutcomeFeedInterface {
+    /// Return the latest outcome for `market_id` on `feed_id`. Traps if there
+    /// is none.
+    fn get_outcome(env: Env, feed_id: String, market_id: u64) -> OutcomeReport;
+}
@@ -148,6 +148,7 @@ fn test_status_based_pagination() {
     assert_eq!(active_page.len(), 3);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_get_guardians_paginated() {
     let e = Env::default();
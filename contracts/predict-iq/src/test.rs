@@ -2,7 +2,9 @@
 use super::*;
 use crate::modules::markets::{self, DataKey};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{token, Address, BytesN, Env, String, Vec};
+#[cfg(feature = "governance")]
+use soroban_sdk::BytesN;
+use soroban_sdk::{token, Address, Env, String, Vec};
 
 fn setup_test_env() -> (Env, Address, soroban_sdk::Address, PredictIQClient<'static>) {
     let e = Env::default();
@@ -12,15 +14,7 @@ fn setup_test_env() -> (Env, Address, soroban_sdk::Address, PredictIQClient<'sta
     let contract_id = e.register(PredictIQ, ());
     let client = PredictIQClient::new(&e, &contract_id);
 
-    let init_guardians = {
-        let mut g = soroban_sdk::Vec::new(&e);
-        g.push_back(types::Guardian {
-            address: Address::generate(&e),
-            voting_power: 1,
-        });
-        g
-    };
-    client.initialize(&admin, &100, &init_guardians);
+    client.initialize(&admin, &100);
 
     (e, admin, contract_id, client)
 }
@@ -33,13 +27,13 @@ fn test_config_setters_reject_non_admin() {
     let token = Address::generate(&e);
 
     // Remove admin auth mocking for this test
-    e.remove_all_auths();
+    e.set_auths(&[]);
 
-    // Helper to call and check NotAuthorized
+    // Helper to call and check the admin's missing auth aborts the call
     macro_rules! expect_not_authorized {
         ($expr:expr) => {
             let res = $expr;
-            assert_eq!(res, Err(Ok(ErrorCode::NotAuthorized)));
+            assert!(matches!(res, Err(Err(_))), "expected auth failure");
         };
     }
 
@@ -58,9 +52,6 @@ fn test_config_setters_reject_non_admin() {
     // set_dispute_window
     expect_not_authorized!(client.try_set_dispute_window(&100_000));
 
-    // set_minimum_bet_amount
-    expect_not_authorized!(client.try_set_minimum_bet_amount(&1000));
-
     // set_creator_reputation
     expect_not_authorized!(
         client.try_set_creator_reputation(&non_admin, &types::CreatorReputation::Pro)
@@ -73,8 +64,21 @@ fn test_config_setters_reject_non_admin() {
     expect_not_authorized!(client.try_set_governance_token(&token));
 }
 
+#[cfg(feature = "governance")]
+/// Upload a minimal Wasm module, holding just the env-meta section the host
+/// requires, so `execute_upgrade` has installed code to switch to.
 fn upgrade_wasm_hash(e: &Env) -> BytesN<32> {
-    BytesN::from_array(e, &[7; 32])
+    const WASM: [u8; 40] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version
+        0x00, 0x1e, 0x11, // custom section: size, name length
+        b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a',
+        b'v', b'0', // "contractenvmetav0"
+        0x00, 0x00, 0x00, 0x00, // ScEnvMetaEntry::InterfaceVersion
+        0x00, 0x00, 0x00, 0x1b, // protocol 27
+        0x00, 0x00, 0x00, 0x00, // pre-release 0
+    ];
+    e.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(e, &WASM))
 }
 
 fn create_test_market(
@@ -103,7 +107,7 @@ fn create_test_market(
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &tier,
         native_token,
@@ -148,6 +152,7 @@ fn make_stored_market(e: &Env, id: u64) -> types::Market {
         dispute_timestamp: None,
         winner_counts: soroban_sdk::Map::new(e),
         total_claimed: 0,
+        fee_bps: 0,
     }
 }
 
@@ -173,21 +178,12 @@ fn test_market_creation_fails_without_deposit() {
             opts
         },
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &types::OracleConfig {
             oracle_address: Address::generate(&e),
             feed_id: String::from_str(&e, "test"),
-            min_responses: 1,
-            max_staleness_seconds: 300,
-            max_confidence_bps: 200,
-            strike_price: None,
             min_responses: Some(1),
             max_staleness_seconds: 3600,
-            max_confidence_bps: 200,
-            strike_price: None,
-            max_staleness_seconds: 3600,
-            max_confidence_bps: 200,
-            strike_price: None,
             max_confidence_bps: 100,
             strike_price: None,
         },
@@ -276,11 +272,14 @@ fn test_market_id_overflow_returns_error() {
             opts
         },
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &types::OracleConfig {
             oracle_address: Address::generate(&e),
             feed_id: String::from_str(&e, "overflow_feed"),
             min_responses: Some(1),
+            max_staleness_seconds: 3600,
+            max_confidence_bps: 200,
+            strike_price: None,
         },
         &types::MarketTier::Basic,
         &native_token,
@@ -316,11 +315,14 @@ fn test_market_id_collision_returns_error() {
             opts
         },
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &types::OracleConfig {
             oracle_address: Address::generate(&e),
             feed_id: String::from_str(&e, "collision_feed"),
             min_responses: Some(1),
+            max_staleness_seconds: 3600,
+            max_confidence_bps: 200,
+            strike_price: None,
         },
         &types::MarketTier::Basic,
         &native_token,
@@ -334,6 +336,7 @@ fn test_market_id_collision_returns_error() {
 #[test]
 fn test_market_id_allocator_simulates_one_million_unique_ids() {
     let (e, _admin, contract_id, _client) = setup_test_env();
+    e.budget().reset_unlimited();
 
     let mut last_id = 0u64;
     e.as_contract(&contract_id, || {
@@ -434,6 +437,8 @@ fn test_tiered_commission_rates() {
 
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
+    // Pro and Institutional tiers are gated on creator reputation.
+    client.set_creator_reputation(&creator, &types::CreatorReputation::Institutional);
 
     // Create Basic tier market
     let basic_market_id = create_test_market(
@@ -466,50 +471,51 @@ fn test_tiered_commission_rates() {
 
 #[test]
 fn test_push_mode_market_fails_resolution_when_winners_exceed_threshold() {
-    let (e, _admin, _contract_id, client) = setup_test_env();
+    let (e, _admin, contract_id, client) = setup_test_env();
     client.set_creation_deposit(&0);
 
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
 
     // Issue #24: winner count now comes from the precise winner_counts counter,
-    // not the tally/100 heuristic. Seed the counter directly via storage.
-    let market_default = create_test_market(
-        &client,
-        &e,
-        &creator,
-        types::MarketTier::Basic,
-        &native_token,
-    );
-    // Simulate 20 unique bettors on outcome 0 — below default threshold of 50.
-    let market_data_key = crate::modules::markets::DataKey::Market(market_default);
-    let mut m: types::Market = e.storage().persistent().get(&market_data_key).unwrap();
-    m.winner_counts.set(0, 20u32);
-    e.storage().persistent().set(&market_data_key, &m);
+    // not the tally/100 heuristic. Seed the counter and the (creation-time)
+    // payout mode directly via storage.
+    let seed_push_market = |winners: u32| {
+        let market_id = create_test_market(
+            &client,
+            &e,
+            &creator,
+            types::MarketTier::Basic,
+            &native_token,
+        );
+        e.as_contract(&contract_id, || {
+            let mut m = markets::get_market(&e, market_id).unwrap();
+            m.payout_mode = types::PayoutMode::Push;
+            m.winner_counts.set(0, winners);
+            markets::update_market(&e, m);
+        });
+        market_id
+    };
 
+    // 20 unique winners — below the default threshold of 50.
+    let market_default = seed_push_market(20);
     client.resolve_market(&market_default, &0);
     let resolved_default = client.get_market(&market_default).unwrap();
+    assert_eq!(resolved_default.status, types::MarketStatus::Resolved);
     assert_eq!(resolved_default.payout_mode, types::PayoutMode::Push);
 
-    // Admin lowers threshold so 20 winners now exceeds it → Pull.
+    // Admin lowers threshold so 20 winners now exceeds it.
     client.set_max_push_payout_winners(&10);
     assert_eq!(client.get_max_push_payout_winners(), 10);
 
-    let market_id = create_test_market(
-        &client,
-        &e,
-        &creator,
-        types::MarketTier::Basic,
-        &native_token,
+    let market_lowered = seed_push_market(20);
+    assert_eq!(
+        client.try_resolve_market(&market_lowered, &0),
+        Err(Ok(ErrorCode::TooManyWinners))
     );
-    let market_data_key2 = crate::modules::markets::DataKey::Market(market_lowered);
-    let mut m2: types::Market = e.storage().persistent().get(&market_data_key2).unwrap();
-    m2.winner_counts.set(0, 20u32);
-    e.storage().persistent().set(&market_data_key2, &m2);
-
-    client.resolve_market(&market_lowered, &0);
-    let resolved_lowered = client.get_market(&market_lowered).unwrap();
-    assert_eq!(resolved_lowered.payout_mode, types::PayoutMode::Pull);
+    let unresolved = client.get_market(&market_lowered).unwrap();
+    assert_eq!(unresolved.status, types::MarketStatus::Active);
+    assert_eq!(unresolved.payout_mode, types::PayoutMode::Push);
 }
 
 #[test]
@@ -657,6 +663,7 @@ fn test_only_guardian_can_unpause() {
 
 // ===================== Governance & Upgrade Tests =====================
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_initialize_guardians() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -682,6 +689,7 @@ fn test_initialize_guardians() {
     assert_eq!(stored_guardians.len(), 2);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_initialize_guardians_already_initialized() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -706,6 +714,7 @@ fn test_initialize_guardians_already_initialized() {
     assert_eq!(result, Err(Ok(ErrorCode::AlreadyInitialized)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_add_guardian() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -731,6 +740,7 @@ fn test_add_guardian() {
     assert_eq!(stored_guardians.len(), 2);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_guardian_removal_requires_timelock_after_majority() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -762,6 +772,9 @@ fn test_guardian_removal_requires_timelock_after_majority() {
     client.vote_on_guardian_removal(&guardian1, &true);
     client.vote_on_guardian_removal(&guardian2, &true);
 
+    let pending = client.get_pending_guardian_removal().unwrap();
+    assert_eq!(pending.target_guardian, guardian3);
+    assert_eq!(pending.votes_for.len(), 2);
     assert_eq!(
         client.try_execute_guardian_removal(),
         Err(Ok(ErrorCode::TimelockActive))
@@ -776,10 +789,12 @@ fn test_guardian_removal_requires_timelock_after_majority() {
     for guardian in stored_guardians.iter() {
         assert_ne!(guardian.address, guardian3);
     }
+    assert!(client.get_pending_guardian_removal().is_none());
 }
 
 // Issue #19: Admin-Guardian separation tests
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_add_admin_as_guardian_rejected() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -800,6 +815,7 @@ fn test_add_admin_as_guardian_rejected() {
     assert_eq!(result, Err(Ok(ErrorCode::NotAuthorized)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_initialize_guardians_with_admin_rejected() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -814,6 +830,7 @@ fn test_initialize_guardians_with_admin_rejected() {
     assert_eq!(result, Err(Ok(ErrorCode::NotAuthorized)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_initiate_upgrade_starts_timelock() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -841,6 +858,7 @@ fn test_initiate_upgrade_starts_timelock() {
     assert_eq!(pending.initiated_at, 1000);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_execute_upgrade_before_timelock_fails() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -867,6 +885,7 @@ fn test_execute_upgrade_before_timelock_fails() {
     assert_eq!(result, Err(Ok(ErrorCode::TimelockActive)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_execute_upgrade_timelock_starts_when_vote_passes() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -897,6 +916,7 @@ fn test_execute_upgrade_timelock_starts_when_vote_passes() {
     assert!(client.try_execute_upgrade().is_ok());
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_execute_upgrade_after_timelock_succeeds() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -925,11 +945,15 @@ fn test_execute_upgrade_after_timelock_succeeds() {
     let result = client.try_execute_upgrade();
     assert!(result.is_ok());
 
-    // Verify pending upgrade is cleared after execution
-    let pending = client.get_pending_upgrade();
+    // Verify pending upgrade is cleared after execution. The contract now
+    // runs the uploaded Wasm, so read its storage directly.
+    let pending = e.as_contract(&client.address, || {
+        crate::modules::governance::get_pending_upgrade(&e)
+    });
     assert!(pending.is_none());
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_insufficient_votes_to_execute() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -972,6 +996,7 @@ fn test_insufficient_votes_to_execute() {
 
 // Issue #13: configurable timelock tests
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_set_timelock_duration_and_early_execution() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1005,6 +1030,7 @@ fn test_set_timelock_duration_and_early_execution() {
     assert!(client.try_execute_upgrade().is_ok());
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_set_timelock_duration_out_of_range_rejected() {
     let (_e, _admin, _contract_id, client) = setup_test_env();
@@ -1021,6 +1047,7 @@ fn test_set_timelock_duration_out_of_range_rejected() {
     );
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_get_timelock_duration_default_and_updated() {
     let (_e, _admin, _contract_id, client) = setup_test_env();
@@ -1033,6 +1060,7 @@ fn test_get_timelock_duration_default_and_updated() {
     assert_eq!(client.get_timelock_duration(), 24 * 3600);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_majority_vote_required() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -1074,6 +1102,7 @@ fn test_majority_vote_required() {
     assert!(result.is_ok());
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_cannot_vote_twice() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -1100,6 +1129,7 @@ fn test_cannot_vote_twice() {
     assert_eq!(result, Err(Ok(ErrorCode::AlreadyVotedOnUpgrade)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_only_guardians_can_vote() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -1125,6 +1155,7 @@ fn test_only_guardians_can_vote() {
     assert_eq!(result, Err(Ok(ErrorCode::NotAuthorized)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_get_upgrade_votes() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -1169,6 +1200,7 @@ fn test_get_upgrade_votes() {
     assert_eq!(votes.votes_against, 1);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_persistent_state_preserved_on_upgrade() {
     let (e, admin, _contract_id, client) = setup_test_env();
@@ -1201,6 +1233,7 @@ fn test_persistent_state_preserved_on_upgrade() {
     assert_eq!(stored_admin, admin);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_same_hash_cannot_be_reinitiated_while_pending() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1222,6 +1255,7 @@ fn test_same_hash_cannot_be_reinitiated_while_pending() {
     assert_eq!(result, Err(Ok(ErrorCode::UpgradeAlreadyPending)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_different_hash_still_blocked_while_another_upgrade_is_pending() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1244,6 +1278,7 @@ fn test_different_hash_still_blocked_while_another_upgrade_is_pending() {
     assert_eq!(result, Err(Ok(ErrorCode::NotAuthorized)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_rejected_hash_blocked_during_cooldown() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1280,6 +1315,7 @@ fn test_rejected_hash_blocked_during_cooldown() {
     assert_eq!(result, Err(Ok(ErrorCode::UpgradeHashInCooldown)));
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_rejected_hash_allowed_after_cooldown_expires() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1320,6 +1356,7 @@ fn test_rejected_hash_allowed_after_cooldown_expires() {
     assert!(result.is_ok());
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_rejected_hash_still_blocked_at_exact_cooldown_boundary() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1352,8 +1389,10 @@ fn test_rejected_hash_still_blocked_at_exact_cooldown_boundary() {
     let execute_result = client.try_execute_upgrade();
     assert_eq!(execute_result, Err(Ok(ErrorCode::InsufficientVotes)));
 
+    // The rejection is dated to the timelock's expiry, so this is exactly
+    // one cooldown later.
     e.ledger().with_mut(|li| {
-        li.timestamp = 1000 + types::TIMELOCK_DURATION + 1 + types::UPGRADE_COOLDOWN_DURATION
+        li.timestamp = 1000 + types::TIMELOCK_DURATION + types::UPGRADE_COOLDOWN_DURATION
     });
 
     let result = client.try_initiate_upgrade(&wasm_hash);
@@ -1390,14 +1429,8 @@ fn test_create_conditional_market_parent_not_resolved() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1407,7 +1440,7 @@ fn test_create_conditional_market_parent_not_resolved() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1448,14 +1481,8 @@ fn test_create_conditional_market_parent_wrong_outcome() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1465,7 +1492,7 @@ fn test_create_conditional_market_parent_wrong_outcome() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1506,14 +1533,8 @@ fn test_create_conditional_market_success() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1523,7 +1544,7 @@ fn test_create_conditional_market_success() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1573,14 +1594,8 @@ fn test_place_bet_on_conditional_market_parent_not_resolved() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1590,7 +1605,7 @@ fn test_place_bet_on_conditional_market_parent_not_resolved() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1640,14 +1655,8 @@ fn test_place_bet_on_conditional_market_parent_wrong_outcome() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1657,7 +1666,7 @@ fn test_place_bet_on_conditional_market_parent_wrong_outcome() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1728,14 +1737,8 @@ fn test_multi_level_conditional_markets() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1745,7 +1748,7 @@ fn test_multi_level_conditional_markets() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1761,7 +1764,7 @@ fn test_multi_level_conditional_markets() {
         &String::from_str(&e, "Level 3 Market"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1804,14 +1807,8 @@ fn test_create_conditional_market_invalid_parent_outcome_idx() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
-        min_responses: 1,
-        max_staleness_seconds: 300,
-        max_confidence_bps: 200,
-        strike_price: None,
         min_responses: Some(1),
         max_staleness_seconds: 3600,
-        max_confidence_bps: 200,
-        strike_price: None,
         max_confidence_bps: 100,
         strike_price: None,
     };
@@ -1821,7 +1818,7 @@ fn test_create_conditional_market_invalid_parent_outcome_idx() {
         &description,
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1854,13 +1851,16 @@ fn test_create_market_with_255_outcomes_is_rejected() {
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
 
-    // 255 outcomes — well above MAX_OUTCOMES_PER_MARKET (32)
+    // 255 outcomes — well above any tier's outcome limit
     let options = make_options(&e, 255);
 
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
         min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
     };
 
     let result = client.try_create_market(
@@ -1868,7 +1868,7 @@ fn test_create_market_with_255_outcomes_is_rejected() {
         &String::from_str(&e, "Gas griefing market"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1888,13 +1888,19 @@ fn test_create_market_at_max_outcomes_succeeds() {
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
 
-    // Exactly MAX_OUTCOMES_PER_MARKET (32) should be accepted
-    let options = make_options(&e, types::MAX_OUTCOMES_PER_MARKET);
+    // Exactly the Basic tier's outcome limit should be accepted
+    let max_outcomes = client
+        .get_tier_limits(&types::MarketTier::Basic)
+        .max_outcomes;
+    let options = make_options(&e, max_outcomes);
 
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
         min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
     };
 
     let result = client.try_create_market(
@@ -1902,7 +1908,7 @@ fn test_create_market_at_max_outcomes_succeeds() {
         &String::from_str(&e, "Max outcomes market"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1921,13 +1927,19 @@ fn test_create_market_one_above_max_outcomes_is_rejected() {
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
 
-    // MAX_OUTCOMES_PER_MARKET + 1 must be rejected
-    let options = make_options(&e, types::MAX_OUTCOMES_PER_MARKET + 1);
+    // One more than the Basic tier's outcome limit must be rejected
+    let max_outcomes = client
+        .get_tier_limits(&types::MarketTier::Basic)
+        .max_outcomes;
+    let options = make_options(&e, max_outcomes + 1);
 
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test_feed"),
         min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
     };
 
     let result = client.try_create_market(
@@ -1935,7 +1947,7 @@ fn test_create_market_one_above_max_outcomes_is_rejected() {
         &String::from_str(&e, "Over-limit market"),
         &options,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &oracle_config,
         &types::MarketTier::Basic,
         &native_token,
@@ -1948,6 +1960,7 @@ fn test_create_market_one_above_max_outcomes_is_rejected() {
 
 // ===================== Governance TTL Durability Tests (Issue #85) =====================
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_pending_upgrade_survives_3_months_inactivity() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1968,7 +1981,7 @@ fn test_pending_upgrade_survives_3_months_inactivity() {
     // Simulate ~3 months of network inactivity:
     // 90 days * 86400 seconds/day / 5 seconds per ledger = 1_555_200 ledgers
     e.ledger().with_mut(|li| {
-        li.sequence = li.sequence + 1_555_200;
+        li.sequence_number += 1_555_200;
         li.timestamp = 1000 + (90 * 86400);
     });
 
@@ -1981,6 +1994,7 @@ fn test_pending_upgrade_survives_3_months_inactivity() {
     assert_eq!(pending.unwrap().wasm_hash, wasm_hash);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_guardian_set_survives_3_months_inactivity() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -1995,7 +2009,7 @@ fn test_guardian_set_survives_3_months_inactivity() {
 
     // Advance 3 months
     e.ledger().with_mut(|li| {
-        li.sequence = li.sequence + 1_555_200;
+        li.sequence_number += 1_555_200;
         li.timestamp = 90 * 86400;
     });
 
@@ -2008,6 +2022,7 @@ fn test_guardian_set_survives_3_months_inactivity() {
     assert_eq!(stored.get(0).unwrap().address, guardian);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_vote_on_upgrade_refreshes_ttl() {
     let (e, _admin, _contract_id, client) = setup_test_env();
@@ -2029,7 +2044,7 @@ fn test_vote_on_upgrade_refreshes_ttl() {
 
     // Advance another 3 months after the vote
     e.ledger().with_mut(|li| {
-        li.sequence = li.sequence + 1_555_200;
+        li.sequence_number += 1_555_200;
         li.timestamp = 1000 + (90 * 86400);
     });
 
@@ -2059,7 +2074,7 @@ fn test_voting_works_with_optimized_vote_struct() {
 
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
-    let resolution_deadline = 2000u64;
+    let resolution_deadline = 1000u64 + 86_400;
 
     let market_id = client.create_market(
         &creator,
@@ -2076,6 +2091,9 @@ fn test_voting_works_with_optimized_vote_struct() {
             oracle_address: Address::generate(&e),
             feed_id: String::from_str(&e, "test"),
             min_responses: Some(1),
+            max_staleness_seconds: 3600,
+            max_confidence_bps: 200,
+            strike_price: None,
         },
         &types::MarketTier::Basic,
         &native_token,
@@ -2084,8 +2102,8 @@ fn test_voting_works_with_optimized_vote_struct() {
     );
 
     // Move to PendingResolution then dispute
-    client.set_oracle_result(&market_id, &0, &0);
     e.ledger().with_mut(|li| li.timestamp = resolution_deadline);
+    client.set_oracle_result(&market_id, &0, &0);
     client.attempt_oracle_resolution(&market_id);
 
     let disputer = Address::generate(&e);
@@ -2127,7 +2145,7 @@ fn test_double_vote_still_rejected_with_optimized_struct() {
 
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
-    let resolution_deadline = 2000u64;
+    let resolution_deadline = 1000u64 + 86_400;
 
     let market_id = client.create_market(
         &creator,
@@ -2144,6 +2162,9 @@ fn test_double_vote_still_rejected_with_optimized_struct() {
             oracle_address: Address::generate(&e),
             feed_id: String::from_str(&e, "test"),
             min_responses: Some(1),
+            max_staleness_seconds: 3600,
+            max_confidence_bps: 200,
+            strike_price: None,
         },
         &types::MarketTier::Basic,
         &native_token,
@@ -2151,8 +2172,8 @@ fn test_double_vote_still_rejected_with_optimized_struct() {
         &0,
     );
 
-    client.set_oracle_result(&market_id, &0, &0);
     e.ledger().with_mut(|li| li.timestamp = resolution_deadline);
+    client.set_oracle_result(&market_id, &0, &0);
     client.attempt_oracle_resolution(&market_id);
 
     let disputer = Address::generate(&e);
@@ -2165,9 +2186,12 @@ fn test_double_vote_still_rejected_with_optimized_struct() {
 
     client.cast_vote(&voter, &market_id, &0, &5000);
 
-    // Second vote from same voter must be rejected
+    // A revised vote cannot reuse the tokens already locked by the first one
     let result = client.try_cast_vote(&voter, &market_id, &1, &5000);
-    assert_eq!(result, Err(Ok(crate::errors::ErrorCode::AlreadyVoted)));
+    assert_eq!(
+        result,
+        Err(Ok(crate::errors::ErrorCode::InsufficientVotingWeight))
+    );
 }
 
 // ===================== Dispute Deadline Idempotency Test =====================
@@ -2182,7 +2206,7 @@ fn test_dispute_deadline_extension_is_one_time_and_idempotent() {
 
     let creator = Address::generate(&e);
     let native_token = Address::generate(&e);
-    let resolution_deadline = 2000u64;
+    let resolution_deadline = 1000u64 + 86_400;
 
     e.ledger().with_mut(|li| li.timestamp = 500);
 
@@ -2212,8 +2236,8 @@ fn test_dispute_deadline_extension_is_one_time_and_idempotent() {
     );
 
     // Move market to PendingResolution
-    client.set_oracle_result(&market_id, &0, &0);
     e.ledger().with_mut(|li| li.timestamp = resolution_deadline);
+    client.set_oracle_result(&market_id, &0, &0);
     client.attempt_oracle_resolution(&market_id);
 
     // First dispute — must succeed and extend deadline by one dispute window (72h)
@@ -2254,14 +2278,9 @@ fn test_initialize_rejects_non_deployer() {
     let client = PredictIQClient::new(&e, &contract_id);
 
     let attacker = Address::generate(&e);
-    let mut guardians = soroban_sdk::Vec::new(&e);
-    guardians.push_back(types::Guardian {
-        address: Address::generate(&e),
-        voting_power: 1,
-    });
 
     // An attacker (non-deployer) attempting to initialize must fail.
-    let result = client.try_initialize(&attacker, &100, &guardians);
+    let result = client.try_initialize(&attacker, &100);
     assert!(result.is_err());
 }
 
@@ -2305,7 +2324,7 @@ mod fee_calculation_tests {
         fee_amount: i128,
     ) {
         env.as_contract(contract_id, || {
-            fees::add_referral_reward(env, referrer, token, fee_amount);
+            fees::add_referral_reward(env, 0, referrer, token, fee_amount);
         });
     }
 
@@ -2337,7 +2356,7 @@ mod fee_calculation_tests {
 
         // With 0 bps fee, calculate_fee returns 0 — nothing collected
         env.as_contract(&contract_id, || {
-            let fee = fees::calculate_fee(&env, 10_000);
+            let fee = fees::calculate_fee(&env, 10_000).unwrap();
             assert_eq!(fee, 0);
             // collect_fee with 0 is a no-op in bets.rs (if fee > 0 guard)
         });
@@ -2364,7 +2383,7 @@ mod fee_calculation_tests {
 
         env.as_contract(&contract_id, || {
             // 100 bps on 10_000 = 100
-            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic);
+            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic).unwrap();
             assert_eq!(fee, 100);
         });
     }
@@ -2375,7 +2394,7 @@ mod fee_calculation_tests {
 
         env.as_contract(&contract_id, || {
             // 100 bps × 75% = 75 bps on 10_000 = 75
-            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Pro);
+            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Pro).unwrap();
             assert_eq!(fee, 75);
         });
     }
@@ -2386,7 +2405,7 @@ mod fee_calculation_tests {
 
         env.as_contract(&contract_id, || {
             // 100 bps × 50% = 50 bps on 10_000 = 50
-            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Institutional);
+            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Institutional).unwrap();
             assert_eq!(fee, 50);
         });
     }
@@ -2396,9 +2415,10 @@ mod fee_calculation_tests {
         let (env, _client, _admin, _token, contract_id) = setup();
 
         env.as_contract(&contract_id, || {
-            let basic = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic);
-            let pro = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Pro);
-            let inst = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Institutional);
+            let basic = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic).unwrap();
+            let pro = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Pro).unwrap();
+            let inst =
+                fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Institutional).unwrap();
 
             assert!(basic > pro, "Basic fee must exceed Pro fee");
             assert!(pro > inst, "Pro fee must exceed Institutional fee");
@@ -2477,7 +2497,7 @@ mod fee_calculation_tests {
 
         env.as_contract(&contract_id, || {
             let amount = 10_000i128;
-            let fee = fees::calculate_tiered_fee(&env, amount, &MarketTier::Basic);
+            let fee = fees::calculate_tiered_fee(&env, amount, &MarketTier::Basic).unwrap();
             let net = amount - fee;
             assert_eq!(fee + net, amount);
         });
@@ -2488,7 +2508,7 @@ mod fee_calculation_tests {
         let (env, _client, _admin, _token, contract_id) = setup();
 
         env.as_contract(&contract_id, || {
-            let fee = fees::calculate_fee(&env, 0);
+            let fee = fees::calculate_fee(&env, 0).unwrap();
             assert_eq!(fee, 0);
         });
     }
@@ -2500,7 +2520,7 @@ mod fee_calculation_tests {
         client.set_base_fee(&10_000); // 100%
 
         env.as_contract(&contract_id, || {
-            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic);
+            let fee = fees::calculate_tiered_fee(&env, 10_000, &MarketTier::Basic).unwrap();
             assert_eq!(fee, 10_000);
         });
     }
@@ -2532,7 +2552,9 @@ mod dispute_resolution_tests {
         token, Address, Env, String, Vec,
     };
 
-    const DISPUTE_WINDOW: u64 = 86_400; // 24h — matches resolution::DISPUTE_WINDOW_SECONDS
+    const DISPUTE_WINDOW: u64 = crate::modules::resolution::DEFAULT_DISPUTE_WINDOW_SECONDS;
+    /// Governance tokens have 7 decimals; tallies are normalized to 18.
+    const WEIGHT_SCALE: i128 = 100_000_000_000;
 
     fn setup() -> (Env, PredictIQClient<'static>, Address, Address) {
         let env = Env::default();
@@ -2562,7 +2584,7 @@ mod dispute_resolution_tests {
             &String::from_str(env, "Dispute Test"),
             &options,
             &1000,
-            &2000,
+            &(1000 + 86_400),
             &OracleConfig {
                 oracle_address: Address::generate(env),
                 feed_id: String::from_str(env, "feed"),
@@ -2626,7 +2648,7 @@ mod dispute_resolution_tests {
             None,
         );
 
-        // Dispute within the 24h window
+        // Dispute within the dispute window
         env.ledger().set_timestamp(pending_ts + 1);
         let disputer = Address::generate(&env);
         client.file_dispute(&disputer, &market_id);
@@ -2847,7 +2869,7 @@ mod dispute_resolution_tests {
 
         env.as_contract(&contract_id, || {
             let tally = voting::get_tally(&env, market_id, 0);
-            assert_eq!(tally, 1_000);
+            assert_eq!(tally, 1_000 * WEIGHT_SCALE);
         });
     }
 
@@ -2866,7 +2888,7 @@ mod dispute_resolution_tests {
 
         env.as_contract(&contract_id, || {
             let tally = voting::get_tally(&env, market_id, 1);
-            assert_eq!(tally, 700);
+            assert_eq!(tally, 700 * WEIGHT_SCALE);
         });
     }
 
@@ -2887,8 +2909,8 @@ mod dispute_resolution_tests {
         client.cast_vote(&voter_b, &market_id, &1, &300);
 
         env.as_contract(&contract_id, || {
-            assert_eq!(voting::get_tally(&env, market_id, 0), 700);
-            assert_eq!(voting::get_tally(&env, market_id, 1), 300);
+            assert_eq!(voting::get_tally(&env, market_id, 0), 700 * WEIGHT_SCALE);
+            assert_eq!(voting::get_tally(&env, market_id, 1), 300 * WEIGHT_SCALE);
         });
     }
 
@@ -0,0 +1,357 @@
+//! Bets in a market's accepted tokens.
+//!
+//! A market can list other tokens with a fixed rate into its own token. Bets
+//! in them are staked at their converted value out of the conversion reserve,
+//! keep their fee in the token they were placed in, and are paid back
+//! (refunds or a winner's own stake) in that token. Winnings above the stake
+//! are paid in the market's token.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::{testutils::Events as _, token, vec, Address, Vec};
+
+use crate::{
+    errors::ErrorCode,
+    testutils::{register_token, Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::{AcceptedToken, RATE_SCALE},
+};
+
+/// One unit of the second token is worth 100 of the market's token.
+const RATE: i128 = 100 * RATE_SCALE;
+/// Market-token conversion reserve funded by the admin.
+const RESERVE: i128 = 5_000_000_000;
+
+fn scenario(bettors: u32) -> (Scenario, Address) {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(100)
+        .with_bettors(bettors, 2_000_000_000)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+    let usdc = register_token(&s.env);
+    for i in 0..bettors {
+        token::StellarAssetClient::new(&s.env, &usdc).mint(&s.bettor(i), &100_000_000);
+    }
+    accept(&s, &usdc, RATE);
+    s.mint(&s.admin, RESERVE);
+    s.client.fund_conversion_reserve(s.token(), &RESERVE);
+    (s, usdc)
+}
+
+fn accept(s: &Scenario, token: &Address, rate: i128) {
+    s.client.set_market_accepted_tokens(
+        &s.market_id(),
+        &vec![
+            &s.env,
+            AcceptedToken {
+                token: token.clone(),
+                rate,
+            },
+        ],
+    );
+}
+
+fn bet(s: &Scenario, index: u32, outcome: u32, amount: i128, token: &Address) {
+    s.client.place_bet(
+        &s.bettor(index),
+        &s.market_id(),
+        &outcome,
+        &amount,
+        token,
+        &None,
+    );
+}
+
+fn balance(s: &Scenario, token: &Address, of: &Address) -> i128 {
+    token::Client::new(&s.env, token).balance(of)
+}
+
+/// The contract holds exactly its revenue and conversion reserve in `token`.
+fn assert_no_leakage(s: &Scenario, token: &Address) {
+    assert_eq!(
+        balance(s, token, &s.client.address),
+        s.client.get_revenue(token) + s.client.get_conversion_reserve(token)
+    );
+}
+
+#[test]
+fn test_mixed_pool_pays_each_token_back_in_kind() {
+    let (s, usdc) = scenario(4);
+    bet(&s, 0, 0, 1_000_000_000, s.token());
+    bet(&s, 1, 0, 5_000_000, &usdc);
+    bet(&s, 2, 1, 3_000_000, &usdc);
+    bet(&s, 3, 1, 700_000_000, s.token());
+
+    // Net stakes: 990M and 495M on outcome 0, 297M and 693M on outcome 1.
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.total_staked, 2_475_000_000);
+    let deposits = s.client.get_bet_deposits(&s.market_id(), &s.bettor(1), &0);
+    assert_eq!(deposits.get(usdc.clone()).unwrap().amount, 4_950_000);
+    assert_eq!(deposits.get(usdc.clone()).unwrap().stake, 495_000_000);
+    assert_eq!(s.client.get_revenue(&usdc), 80_000);
+
+    // Both converted stakes are backed by the reserve.
+    assert_eq!(
+        s.client.get_conversion_reserve(s.token()),
+        RESERVE - 792_000_000
+    );
+    s.client.resolve_market(&s.market_id(), &0);
+
+    // The pool pays 5/3 of each winning stake, all in the market's token
+    // except the USDC winner's own stake, which comes back in USDC.
+    let before = balance(&s, &usdc, &s.bettor(0));
+    assert_eq!(
+        s.client
+            .claim_winnings(&s.bettor(0), &s.market_id(), s.token()),
+        1_650_000_000
+    );
+    assert_eq!(balance(&s, &usdc, &s.bettor(0)), before);
+
+    let before = balance(&s, &usdc, &s.bettor(1));
+    assert_eq!(
+        s.client
+            .claim_winnings(&s.bettor(1), &s.market_id(), s.token()),
+        330_000_000
+    );
+    assert_eq!(balance(&s, &usdc, &s.bettor(1)) - before, 4_950_000);
+
+    // The reserve got the winning stake back and swapped the losing one for
+    // its USDC; only fees and the reserves are left.
+    assert_eq!(
+        s.client.get_conversion_reserve(s.token()),
+        RESERVE - 297_000_000
+    );
+    assert_eq!(s.client.get_conversion_reserve(&usdc), 2_970_000);
+    assert_eq!(s.client.get_revenue(s.token()), 17_000_000);
+    assert_eq!(s.client.get_revenue(&usdc), 80_000);
+    assert_no_leakage(&s, s.token());
+    assert_no_leakage(&s, &usdc);
+}
+
+#[test]
+fn test_awkward_rate_leaves_dust_in_revenue() {
+    let (s, usdc) = scenario(4);
+    accept(&s, &usdc, 733_333_330);
+    bet(&s, 0, 0, 1_000_003, s.token());
+    bet(&s, 1, 0, 1_000_001, &usdc);
+    bet(&s, 2, 1, 1_234_567, &usdc);
+    bet(&s, 3, 1, 2_000_011, s.token());
+    s.client.resolve_market(&s.market_id(), &0);
+
+    for i in 0..2 {
+        s.client
+            .claim_winnings(&s.bettor(i), &s.market_id(), s.token());
+    }
+
+    assert!(s.client.get_market_dust(&s.market_id()) >= 0);
+    assert_no_leakage(&s, s.token());
+    assert_no_leakage(&s, &usdc);
+}
+
+#[test]
+fn test_rounding_at_both_precisions_leaks_nothing() {
+    // A rate as for a 6-decimal token against the 7-decimal market token,
+    // slightly off so conversions round down to whole market-token units.
+    const ODD_RATE: i128 = 10 * RATE_SCALE + 3;
+    let (s, usdc) = scenario(4);
+    accept(&s, &usdc, ODD_RATE);
+    bet(&s, 0, 0, 7, s.token());
+    bet(&s, 1, 0, 1_000_003, &usdc);
+    bet(&s, 2, 1, 333_333, &usdc);
+    bet(&s, 3, 1, 999_999, s.token());
+    s.client.resolve_market(&s.market_id(), &0);
+
+    for i in 0..2 {
+        s.client
+            .claim_winnings(&s.bettor(i), &s.market_id(), s.token());
+    }
+
+    assert_no_leakage(&s, s.token());
+    assert_no_leakage(&s, &usdc);
+    // The reserve never pays out more than it took in, at the listed rate.
+    let usdc_in = s.client.get_conversion_reserve(&usdc);
+    let paid = RESERVE - s.client.get_conversion_reserve(s.token());
+    assert!(paid <= usdc_in * ODD_RATE / RATE_SCALE);
+}
+
+#[test]
+fn test_refunds_return_each_deposit_in_its_token() {
+    let (s, usdc) = scenario(2);
+    bet(&s, 0, 0, 1_000_000_000, s.token());
+    bet(&s, 1, 0, 5_000_000, &usdc);
+    bet(&s, 1, 0, 100_000_000, s.token());
+
+    // Delisting stops new bets but not refunds of old ones.
+    s.client
+        .set_market_accepted_tokens(&s.market_id(), &Vec::new(&s.env));
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("mkt_tokns"));
+    let result = s
+        .client
+        .try_place_bet(&s.bettor(1), &s.market_id(), &0, &5_000_000, &usdc, &None);
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidBetAmount)));
+
+    s.client.cancel_market_admin(&s.market_id());
+    let before = balance(&s, &usdc, &s.bettor(1));
+    let refunded = s
+        .client
+        .withdraw_refund(&s.bettor(1), &s.market_id(), &0, s.token());
+    assert_eq!(refunded, 99_000_000);
+    assert_eq!(balance(&s, &usdc, &s.bettor(1)) - before, 4_950_000);
+    assert!(s
+        .client
+        .get_bet_deposits(&s.market_id(), &s.bettor(1), &0)
+        .is_empty());
+
+    let refunded = s
+        .client
+        .withdraw_refund(&s.bettor(0), &s.market_id(), &0, s.token());
+    assert_eq!(refunded, 990_000_000);
+    assert_eq!(s.client.get_conversion_reserve(s.token()), RESERVE);
+    assert_eq!(s.client.get_conversion_reserve(&usdc), 0);
+    assert_no_leakage(&s, s.token());
+    assert_no_leakage(&s, &usdc);
+}
+
+#[test]
+fn test_accepted_token_list_is_validated() {
+    let (s, usdc) = scenario(1);
+    let other = register_token(&s.env);
+    let entry = |token: &Address, rate: i128| AcceptedToken {
+        token: token.clone(),
+        rate,
+    };
+    let invalid = Err(Ok(ErrorCode::InvalidAcceptedToken));
+
+    let own_token = vec![&s.env, entry(s.token(), RATE)];
+    let duplicate = vec![&s.env, entry(&usdc, RATE), entry(&usdc, RATE)];
+    let zero_rate = vec![&s.env, entry(&other, 0)];
+    let mut too_many = Vec::new(&s.env);
+    for _ in 0..5 {
+        too_many.push_back(entry(&register_token(&s.env), RATE));
+    }
+    for tokens in [own_token, duplicate, zero_rate, too_many] {
+        let result = s
+            .client
+            .try_set_market_accepted_tokens(&s.market_id(), &tokens);
+        assert_eq!(result, invalid);
+    }
+    assert_eq!(
+        s.client.get_market_accepted_tokens(&s.market_id()),
+        vec![&s.env, entry(&usdc, RATE)]
+    );
+
+    let result =
+        s.client
+            .try_place_bet(&s.bettor(0), &s.market_id(), &0, &5_000_000, &other, &None);
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidBetAmount)));
+}
+
+#[test]
+fn test_accepted_token_winner_is_paid_above_stake_in_market_token() {
+    let (s, usdc) = scenario(2);
+    bet(&s, 0, 0, 5_000_000, &usdc);
+    bet(&s, 1, 1, 1_000_000_000, s.token());
+    s.client.resolve_market(&s.market_id(), &0);
+
+    // The winner's own deposit comes back in USDC and the losing stake in
+    // the market's token.
+    let before = balance(&s, &usdc, &s.bettor(0));
+    assert_eq!(
+        s.client
+            .claim_winnings(&s.bettor(0), &s.market_id(), s.token()),
+        990_000_000
+    );
+    assert_eq!(balance(&s, &usdc, &s.bettor(0)) - before, 4_950_000);
+    assert_eq!(s.client.get_conversion_reserve(s.token()), RESERVE);
+    assert_no_leakage(&s, s.token());
+    assert_eq!(balance(&s, &usdc, &s.client.address), 50_000);
+}
+
+#[test]
+fn test_losing_accepted_token_stake_is_paid_from_the_reserve() {
+    let (s, usdc) = scenario(2);
+    bet(&s, 0, 0, 1_000_000_000, s.token());
+    bet(&s, 1, 1, 5_000_000, &usdc);
+    s.client.resolve_market(&s.market_id(), &0);
+
+    assert_eq!(
+        s.client
+            .claim_winnings(&s.bettor(0), &s.market_id(), s.token()),
+        1_485_000_000
+    );
+    // The reserve paid the losing stake out in the market's token and kept
+    // the USDC it was placed in.
+    assert_eq!(
+        s.client.get_conversion_reserve(s.token()),
+        RESERVE - 495_000_000
+    );
+    assert_eq!(s.client.get_conversion_reserve(&usdc), 4_950_000);
+    assert_no_leakage(&s, s.token());
+    assert_no_leakage(&s, &usdc);
+}
+
+#[test]
+fn test_accepted_token_bet_needs_reserve_to_back_its_stake() {
+    let (s, usdc) = scenario(1);
+    let drained = s.client.try_withdraw_conversion_reserve(
+        s.token(),
+        &(RESERVE - 100_000_000),
+        &s.admin,
+    );
+    assert!(drained.is_ok());
+
+    // 2M USDC nets 198M after the fee; only 100M is left to back it.
+    let result =
+        s.client
+            .try_place_bet(&s.bettor(0), &s.market_id(), &0, &2_000_000, &usdc, &None);
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientConversionReserve)));
+
+    bet(&s, 0, 0, 1_000_000, &usdc);
+    assert_eq!(s.client.get_conversion_reserve(s.token()), 1_000_000);
+    // The stake backing the open bet cannot be withdrawn.
+    let result = s
+        .client
+        .try_withdraw_conversion_reserve(s.token(), &1_000_001, &s.admin);
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientConversionReserve)));
+}
+
+#[test]
+fn test_cancelled_accepted_token_bet_keeps_its_fee_in_that_token() {
+    let (s, usdc) = scenario(1);
+    bet(&s, 0, 0, 5_000_000, &usdc);
+    assert_eq!(s.client.get_revenue(&usdc), 50_000);
+
+    s.client.cancel_market_admin(&s.market_id());
+    let before = balance(&s, &usdc, &s.bettor(0));
+    let refunded = s
+        .client
+        .withdraw_refund(&s.bettor(0), &s.market_id(), &0, s.token());
+
+    // Like a market-token bet, the net stake comes back and the fee stays
+    // in revenue, in the token it was paid in.
+    assert_eq!(refunded, 0);
+    assert_eq!(balance(&s, &usdc, &s.bettor(0)) - before, 4_950_000);
+    assert_eq!(s.client.get_revenue(&usdc), 50_000);
+    assert_eq!(balance(&s, &usdc, &s.client.address), 50_000);
+    assert_eq!(s.client.get_conversion_reserve(s.token()), RESERVE);
+}
+
+#[test]
+fn test_transferred_position_keeps_its_deposits() {
+    let (s, usdc) = scenario(2);
+    bet(&s, 0, 0, 5_000_000, &usdc);
+    s.client
+        .transfer_position(&s.bettor(0), &s.bettor(1), &s.market_id(), &0);
+    assert!(s
+        .client
+        .get_bet_deposits(&s.market_id(), &s.bettor(0), &0)
+        .is_empty());
+
+    s.client.cancel_market_admin(&s.market_id());
+    let before = balance(&s, &usdc, &s.bettor(1));
+    s.client
+        .withdraw_refund(&s.bettor(1), &s.market_id(), &0, s.token());
+    assert_eq!(balance(&s, &usdc, &s.bettor(1)) - before, 4_950_000);
+    assert_eq!(s.client.get_conversion_reserve(s.token()), RESERVE);
+}
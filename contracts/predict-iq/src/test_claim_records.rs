@@ -0,0 +1,203 @@
+//! Claim records per (market, bettor, outcome).
+//!
+//! A claim only marks the outcome it paid for, so a bettor's bets on other
+//! outcomes stay untouched. Market-wide records written before the change
+//! are read as a claim of the winning outcome. A position transferred in on
+//! an outcome the receiver already claimed is claimed on its own.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::testutils::Ledger as _;
+
+use crate::{
+    errors::ErrorCode,
+    modules::bets::{self, DataKey},
+    testutils::{Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::PRUNE_GRACE_PERIOD,
+};
+
+fn scenario() -> Scenario {
+    ScenarioBuilder::new()
+        .with_base_fee(0)
+        .with_bettors(2, 10_000)
+        .with_market(3, DEFAULT_DEADLINES)
+        .build()
+}
+
+fn bet(s: &Scenario, index: u32, outcome: u32, amount: i128) {
+    s.client.place_bet(
+        &s.bettor(index),
+        &s.market_id(),
+        &outcome,
+        &amount,
+        s.token(),
+        &None,
+    );
+}
+
+#[test]
+fn test_claim_marks_only_the_winning_outcome() {
+    let s = scenario();
+    let bettor = s.bettor(0);
+    bet(&s, 0, 0, 1_000);
+    bet(&s, 0, 1, 2_000);
+    bet(&s, 1, 2, 1_000);
+    s.client.resolve_market(&s.market_id(), &1);
+
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &bettor), 4_000);
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &s.bettor(1)), 0);
+    assert_eq!(
+        s.client.claim_winnings(&bettor, &s.market_id(), s.token()),
+        4_000
+    );
+
+    assert!(s.client.is_outcome_claimed(&s.market_id(), &bettor, &1));
+    assert!(!s.client.is_outcome_claimed(&s.market_id(), &bettor, &0));
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &bettor), 0);
+    let losing_bet = s.env.as_contract(&s.client.address, || {
+        bets::get_bet(&s.env, s.market_id(), bettor.clone(), 0)
+    });
+    assert_eq!(losing_bet.unwrap().amount, 1_000);
+
+    let result = s
+        .client
+        .try_claim_winnings(&bettor, &s.market_id(), s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::AlreadyClaimed)));
+}
+
+#[test]
+fn test_market_wide_claim_record_counts_for_the_winning_outcome() {
+    let s = scenario();
+    let bettor = s.bettor(0);
+    bet(&s, 0, 0, 1_000);
+    bet(&s, 1, 1, 1_000);
+    s.client.resolve_market(&s.market_id(), &0);
+
+    // A claim recorded before claims were tracked per outcome.
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .set(&DataKey::Claimed(s.market_id(), bettor.clone()), &true);
+    });
+
+    assert!(s.client.is_outcome_claimed(&s.market_id(), &bettor, &0));
+    assert!(!s.client.is_outcome_claimed(&s.market_id(), &bettor, &1));
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &bettor), 0);
+    let result = s
+        .client
+        .try_claim_winnings(&bettor, &s.market_id(), s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::AlreadyClaimed)));
+}
+
+#[test]
+fn test_prune_drops_claim_accounting_but_not_claim_records() {
+    let s = scenario();
+    let bettor = s.bettor(0);
+    bet(&s, 0, 0, 1_000);
+    bet(&s, 1, 0, 1_000);
+    bet(&s, 1, 1, 1_001);
+    s.client.resolve_market(&s.market_id(), &0);
+    s.client.claim_winnings(&bettor, &s.market_id(), s.token());
+
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + PRUNE_GRACE_PERIOD);
+    s.client.prune_market(&s.market_id());
+
+    s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage().persistent();
+        assert!(!storage.has(&DataKey::WinnersClaimed(s.market_id())));
+        assert!(!storage.has(&DataKey::PayoutRemainder(s.market_id())));
+        assert!(storage.has(&DataKey::ClaimedOutcome(s.market_id(), bettor.clone(), 0)));
+    });
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &s.bettor(1)), 0);
+}
+
+#[test]
+fn test_transferred_in_position_is_claimed_separately() {
+    let s = scenario();
+    let bettor = s.bettor(0);
+    bet(&s, 0, 0, 1_000);
+    bet(&s, 0, 1, 2_000);
+    bet(&s, 1, 1, 1_000);
+    bet(&s, 1, 2, 1_000);
+    s.client.resolve_market(&s.market_id(), &1);
+
+    assert_eq!(
+        s.client.claim_winnings(&bettor, &s.market_id(), s.token()),
+        3_333
+    );
+    s.client
+        .transfer_position(&s.bettor(1), &bettor, &s.market_id(), &1);
+
+    assert!(!s.client.is_outcome_claimed(&s.market_id(), &bettor, &1));
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &bettor), 1_666);
+    assert_eq!(s.client.quote_winnings(&s.market_id(), &s.bettor(1)), 0);
+    assert_eq!(
+        s.client.claim_winnings(&bettor, &s.market_id(), s.token()),
+        1_666
+    );
+    let result = s
+        .client
+        .try_claim_winnings(&bettor, &s.market_id(), s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::AlreadyClaimed)));
+
+    // Both winning positions are paid, so the rounding dust was swept.
+    assert_eq!(s.client.get_market_dust(&s.market_id()), 1);
+    assert_eq!(s.balance(&s.client.address), s.client.get_revenue(s.token()));
+}
+
+#[test]
+fn test_transfer_merges_into_the_receivers_position() {
+    let s = scenario();
+    bet(&s, 0, 0, 1_000);
+    bet(&s, 1, 0, 3_000);
+    bet(&s, 1, 1, 4_000);
+    s.client
+        .transfer_position(&s.bettor(1), &s.bettor(0), &s.market_id(), &0);
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.winner_counts.get(0), Some(1));
+    s.client.resolve_market(&s.market_id(), &0);
+    assert_eq!(
+        s.client
+            .claim_winnings(&s.bettor(0), &s.market_id(), s.token()),
+        8_000
+    );
+    let result = s
+        .client
+        .try_claim_winnings(&s.bettor(1), &s.market_id(), s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::NoWinnings)));
+}
+
+#[test]
+fn test_transfer_needs_a_position_and_another_receiver() {
+    let s = scenario();
+    bet(&s, 0, 0, 1_000);
+
+    let result =
+        s.client
+            .try_transfer_position(&s.bettor(0), &s.bettor(0), &s.market_id(), &0);
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidPositionTransfer)));
+    let result =
+        s.client
+            .try_transfer_position(&s.bettor(0), &s.bettor(1), &s.market_id(), &1);
+    assert_eq!(result, Err(Ok(ErrorCode::BetNotFound)));
+}
+
+#[test]
+fn test_transfer_is_rejected_while_paused() {
+    let s = scenario();
+    bet(&s, 0, 0, 1_000);
+    s.client.pause();
+
+    let result =
+        s.client
+            .try_transfer_position(&s.bettor(0), &s.bettor(1), &s.market_id(), &0);
+    assert_eq!(result, Err(Ok(ErrorCode::ContractPaused)));
+
+    s.client.unpause();
+    s.client
+        .transfer_position(&s.bettor(0), &s.bettor(1), &s.market_id(), &0);
+}
@@ -0,0 +1,513 @@
+//! Conservation of funds across whole-contract operation sequences.
+//!
+//! After every step the contract's balance of its one token must equal what
+//! it owes, reconstructed from getters alone:
+//!
+//! - protocol revenue (`get_revenue`) and claimable referral rewards
+//!   (`get_referral_balance`);
+//! - each market's creation deposit and the stake it still holds: all of
+//!   `total_staked` until it resolves, then whatever winners have not been
+//!   paid, less the dust swept into revenue once the last of them claims;
+//! - governance tokens locked in dispute votes (`get_locked_balance`), since
+//!   the governance token is the betting token here.
+//!
+//! There is no AMM in this contract, so no reserves enter the sum.
+//!
+//! Random runs pick operations from a seeded LCG and ignore the ones the
+//! contract rejects. A failure names its seed; set `CONSERVATION_SEED` to
+//! replay just that one.
+
+#![cfg(test)]
+extern crate std;
+
+use std::{format, string::String, vec::Vec};
+
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address,
+};
+
+use crate::{
+    testutils::{create_market, oracle_config, Scenario, ScenarioBuilder},
+    types::{Market, MarketStatus, MarketTier},
+};
+
+const SEEDS: [u64; 8] = [1, 2, 3, 5, 8, 13, 21, 34];
+const STEPS: u32 = 100;
+const MAX_MARKETS: usize = 6;
+const BETTORS: u32 = 5;
+const BALANCE: i128 = 1_000_000;
+const CREATION_DEPOSIT: i128 = 250;
+/// Past a market's dispute window, and past the voting period of a dispute.
+const DISPUTE_PERIOD: u64 = 259_200;
+
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0 >> 16
+    }
+
+    /// Uniform in `0..n`.
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn amount(&mut self, lo: i128, hi: i128) -> i128 {
+        lo + self.below((hi - lo + 1) as u64) as i128
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Op {
+    Create,
+    CreateFunded,
+    Bet,
+    Refund,
+    Claim,
+    ClaimReferral,
+    WithdrawFees,
+    Report,
+    Dispute,
+    Vote,
+    Finalize,
+    Resolve,
+    Cancel,
+    CancelUnfunded,
+    ReleaseDeposit,
+    Advance,
+}
+
+/// Relative weights of the random operations.
+const OPS: [(Op, u64); 16] = [
+    (Op::Create, 4),
+    (Op::CreateFunded, 1),
+    (Op::Bet, 24),
+    (Op::Refund, 5),
+    (Op::Claim, 6),
+    (Op::ClaimReferral, 2),
+    (Op::WithdrawFees, 1),
+    (Op::Report, 4),
+    (Op::Dispute, 3),
+    (Op::Vote, 5),
+    (Op::Finalize, 3),
+    (Op::Resolve, 2),
+    (Op::Cancel, 1),
+    (Op::CancelUnfunded, 1),
+    (Op::ReleaseDeposit, 2),
+    (Op::Advance, 8),
+];
+
+struct Harness {
+    s: Scenario,
+    voters: Vec<Address>,
+    referrers: Vec<Address>,
+    treasury: Address,
+    markets: Vec<u64>,
+    /// Operations so far, for failure messages.
+    trace: Vec<String>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let s = ScenarioBuilder::new()
+            .with_bettors(BETTORS, BALANCE)
+            .build();
+        s.env.budget().reset_unlimited();
+        s.client.set_creation_deposit(&CREATION_DEPOSIT);
+        s.client.set_governance_token(s.token());
+
+        let voters: Vec<Address> = (0..3).map(|_| Address::generate(&s.env)).collect();
+        for voter in &voters {
+            s.mint(voter, BALANCE);
+        }
+        let referrers = (0..2).map(|_| Address::generate(&s.env)).collect();
+        let treasury = Address::generate(&s.env);
+        Self {
+            s,
+            voters,
+            referrers,
+            treasury,
+            markets: Vec::new(),
+            trace: Vec::new(),
+        }
+    }
+
+    fn now(&self) -> u64 {
+        self.s.env.ledger().timestamp()
+    }
+
+    fn advance(&self, seconds: u64) {
+        self.s.env.ledger().set_timestamp(self.now() + seconds);
+    }
+
+    fn bettors(&self) -> Vec<Address> {
+        self.s.bettors.iter().collect()
+    }
+
+    fn market(&self, id: u64) -> Market {
+        self.s.client.get_market(&id).unwrap()
+    }
+
+    /// A two- or three-way market created by `creator`, betting for
+    /// `betting` seconds.
+    fn create(&mut self, creator: &Address, outcomes: u32, betting: u64) -> u64 {
+        let deadline = self.now() + betting;
+        let id = create_market(
+            &self.s.client,
+            creator,
+            self.s.token(),
+            outcomes,
+            (deadline, deadline + 86_400),
+            &MarketTier::Basic,
+        );
+        self.markets.push(id);
+        id
+    }
+
+    /// Stake `market` still holds for its bettors.
+    fn pool(&self, market: &Market) -> i128 {
+        if market.status != MarketStatus::Resolved {
+            return market.total_staked;
+        }
+        let unpaid = market.total_staked - market.total_claimed;
+        let winning = market.winning_outcome.unwrap();
+        let winners = market.winner_counts.get(winning).unwrap_or(0);
+        let claimed = self
+            .bettors()
+            .iter()
+            .filter(|bettor| {
+                self.s
+                    .client
+                    .is_outcome_claimed(&market.id, bettor, &winning)
+            })
+            .count() as u32;
+        if winners > 0 && claimed >= winners {
+            unpaid - self.s.client.get_market_dust(&market.id)
+        } else {
+            unpaid
+        }
+    }
+
+    /// What the contract owes, by the getters.
+    fn owed(&self) -> i128 {
+        let client = &self.s.client;
+        let token = self.s.token();
+        let mut owed = client.get_revenue(token);
+        for referrer in &self.referrers {
+            owed += client.get_referral_balance(referrer, token);
+        }
+        for &id in &self.markets {
+            let market = self.market(id);
+            owed += market.creation_deposit + self.pool(&market);
+            for voter in &self.voters {
+                owed += client.get_locked_balance(&id, voter);
+            }
+        }
+        owed
+    }
+
+    /// Assert conservation, and that no resolved market has promised its
+    /// winners more than it holds.
+    fn check(&self, context: &str) {
+        let held = token::Client::new(&self.s.env, self.s.token()).balance(&self.s.client.address);
+        let owed = self.owed();
+        let recent = self.trace[self.trace.len().saturating_sub(20)..].join("\n  ");
+        assert_eq!(
+            held, owed,
+            "funds not conserved after {context}; recent operations:\n  {recent}"
+        );
+
+        for &id in &self.markets {
+            let market = self.market(id);
+            if market.status != MarketStatus::Resolved {
+                continue;
+            }
+            let quoted: i128 = self
+                .bettors()
+                .iter()
+                .map(|bettor| self.s.client.quote_winnings(&id, bettor))
+                .sum();
+            assert!(
+                quoted <= self.pool(&market),
+                "market {id} quotes {quoted} to winners but holds {} after {context}",
+                self.pool(&market)
+            );
+        }
+    }
+
+    /// Run `op` with arguments from `rng`. Rejected calls change nothing.
+    fn random_step(&mut self, rng: &mut Lcg, op: Op) -> String {
+        match op {
+            Op::Create | Op::CreateFunded => return self.random_create(rng, op),
+            Op::Advance => return self.random_advance(rng),
+            _ if self.markets.is_empty() => return format!("{op:?}: no markets"),
+            _ => {}
+        }
+        let client = &self.s.client;
+        let token = self.s.token().clone();
+        let bettor = rng.pick(&self.bettors()).clone();
+        let market_id = *rng.pick(&self.markets);
+        let outcomes = self.market(market_id).options.len();
+        let outcome = rng.below(outcomes as u64) as u32;
+
+        match op {
+            Op::Create | Op::CreateFunded | Op::Advance => unreachable!(),
+            Op::Bet => {
+                let amount = rng.amount(1, 5_000);
+                let referrer = (rng.below(2) == 0).then(|| rng.pick(&self.referrers).clone());
+                let result =
+                    client.try_place_bet(&bettor, &market_id, &outcome, &amount, &token, &referrer);
+                format!(
+                    "Bet {amount} on {market_id}/{outcome} referred={} ok={}",
+                    referrer.is_some(),
+                    result.is_ok()
+                )
+            }
+            Op::Refund => {
+                let result = client.try_withdraw_refund(&bettor, &market_id, &outcome, &token);
+                format!("Refund {market_id}/{outcome} ok={}", result.is_ok())
+            }
+            Op::Claim => {
+                let result = client.try_claim_winnings(&bettor, &market_id, &token);
+                format!("Claim {market_id} ok={}", result.is_ok())
+            }
+            Op::ClaimReferral => {
+                let referrer = rng.pick(&self.referrers).clone();
+                let result = client.try_claim_referral_rewards(&referrer, &token);
+                format!("ClaimReferral ok={}", result.is_ok())
+            }
+            Op::WithdrawFees => {
+                let result = client.try_withdraw_protocol_fees(&token, &self.treasury);
+                format!("WithdrawFees ok={}", result.is_ok())
+            }
+            Op::Report => {
+                let _ = client.try_set_oracle_result(&market_id, &0, &outcome);
+                let result = client.try_attempt_oracle_resolution(&market_id);
+                format!("Report {market_id}/{outcome} ok={}", result.is_ok())
+            }
+            Op::Dispute => {
+                let result = client.try_file_dispute(&bettor, &market_id);
+                format!("Dispute {market_id} ok={}", result.is_ok())
+            }
+            Op::Vote => {
+                let voter = rng.pick(&self.voters).clone();
+                let weight = rng.amount(1, 10_000);
+                let result = client.try_cast_vote(&voter, &market_id, &outcome, &weight);
+                format!(
+                    "Vote {weight} on {market_id}/{outcome} ok={}",
+                    result.is_ok()
+                )
+            }
+            Op::Finalize => {
+                let result = client.try_finalize_resolution(&market_id);
+                format!("Finalize {market_id} ok={}", result.is_ok())
+            }
+            Op::Resolve => {
+                // The admin is trusted not to resolve a settled market again.
+                let status = self.market(market_id).status;
+                if matches!(status, MarketStatus::Resolved | MarketStatus::Cancelled) {
+                    return format!("Resolve {market_id}: already {status:?}");
+                }
+                let result = client.try_resolve_market(&market_id, &outcome);
+                format!("Resolve {market_id}/{outcome} ok={}", result.is_ok())
+            }
+            Op::Cancel => {
+                let result = client.try_cancel_market_admin(&market_id);
+                format!("Cancel {market_id} ok={}", result.is_ok())
+            }
+            Op::CancelUnfunded => {
+                let result = client.try_cancel_unfunded_market(&market_id);
+                format!("CancelUnfunded {market_id} ok={}", result.is_ok())
+            }
+            Op::ReleaseDeposit => {
+                let result = client.try_release_creation_deposit(&market_id, &token);
+                format!("ReleaseDeposit {market_id} ok={}", result.is_ok())
+            }
+        }
+    }
+
+    fn random_create(&mut self, rng: &mut Lcg, op: Op) -> String {
+        if self.markets.len() >= MAX_MARKETS {
+            return format!("{op:?}: market limit reached");
+        }
+        let creator = &rng.pick(&self.bettors()).clone();
+        let outcomes = 2 + rng.below(2) as u32;
+        let betting = 1_000 + rng.below(10_000);
+        if let Op::Create = op {
+            let id = self.create(creator, outcomes, betting);
+            return format!("Create {id} with {outcomes} outcomes");
+        }
+
+        let env = &self.s.env;
+        let mut options = soroban_sdk::Vec::new(env);
+        for i in 0..outcomes {
+            options.push_back(soroban_sdk::String::from_str(env, &format!("Outcome {i}")));
+        }
+        let deadline = self.now() + betting;
+        let window = 100 + rng.below(800);
+        let id = self.s.client.create_market_with_funding(
+            creator,
+            &soroban_sdk::String::from_str(env, "Seeded market"),
+            &options,
+            &deadline,
+            &(deadline + 86_400),
+            &oracle_config(&Address::generate(env), "test_feed"),
+            &MarketTier::Basic,
+            self.s.token(),
+            &0,
+            &0,
+            &rng.amount(1_000, 10_000),
+            &window,
+        );
+        self.markets.push(id);
+        format!("CreateFunded {id} with {outcomes} outcomes")
+    }
+
+    fn random_advance(&mut self, rng: &mut Lcg) -> String {
+        let seconds = 1 + rng.below(60_000);
+        self.advance(seconds);
+        format!("Advance {seconds}s")
+    }
+
+    fn run(seed: u64, steps: u32) {
+        let mut h = Self::new();
+        let mut rng = Lcg(seed);
+        let total: u64 = OPS.iter().map(|(_, weight)| weight).sum();
+        for step in 0..steps {
+            let mut roll = rng.below(total);
+            let op = OPS
+                .iter()
+                .find(|(_, weight)| {
+                    let hit = roll < *weight;
+                    roll = roll.saturating_sub(*weight);
+                    hit
+                })
+                .unwrap()
+                .0;
+            let line = h.random_step(&mut rng, op);
+            h.trace.push(format!("{step}: {line}"));
+            h.check(&format!(
+                "step {step} of seed {seed} (replay with CONSERVATION_SEED={seed})"
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_random_sequences_conserve_funds() {
+    let seeds: Vec<u64> = match std::env::var("CONSERVATION_SEED") {
+        Ok(seed) => std::vec![seed.parse().expect("CONSERVATION_SEED must be a number")],
+        Err(_) => SEEDS.to_vec(),
+    };
+    for seed in seeds {
+        Harness::run(seed, STEPS);
+    }
+}
+
+#[test]
+fn test_cancel_after_partial_claims() {
+    let mut h = Harness::new();
+    let [b0, b1, b2, b3, _]: [Address; 5] = h.bettors().try_into().unwrap();
+    let resolved = h.create(&b0, 2, 1_000);
+    let cancelled = h.create(&b1, 2, 1_000);
+    let later = h.create(&b2, 2, 1_000);
+    h.check("creating the markets");
+    let client = &h.s.client;
+    let token = h.s.token().clone();
+    let referrer = Some(h.referrers[0].clone());
+
+    client.place_bet(&b0, &resolved, &0, &1_000, &token, &referrer);
+    client.place_bet(&b1, &resolved, &0, &2_000, &token, &None);
+    client.place_bet(&b2, &resolved, &1, &3_001, &token, &referrer);
+    client.place_bet(&b0, &cancelled, &0, &1_500, &token, &referrer);
+    client.place_bet(&b1, &cancelled, &1, &2_500, &token, &None);
+    client.place_bet(&b3, &cancelled, &0, &500, &token, &referrer);
+    h.check("referred and plain bets on both markets");
+
+    // The referrer withdraws the reward from the market about to be
+    // cancelled, so it comes back as a debt.
+    client.claim_referral_rewards(&h.referrers[0], &token);
+    h.check("claiming referral rewards");
+
+    client.resolve_market(&resolved, &0);
+    client.claim_winnings(&b0, &resolved, &token);
+    h.check("the first of two winners claiming");
+
+    client.cancel_market_admin(&cancelled);
+    assert!(client.get_referral_debt(&h.referrers[0], &token) > 0);
+    h.check("cancelling with a claimed referral reward");
+
+    client.withdraw_refund(&b0, &cancelled, &0, &token);
+    h.check("the first refund");
+    client.claim_winnings(&b1, &resolved, &token);
+    h.check("the last winner claiming and the dust sweep");
+    client.withdraw_refund(&b1, &cancelled, &1, &token);
+    client.withdraw_refund(&b3, &cancelled, &0, &token);
+    h.check("the remaining refunds");
+
+    client.release_creation_deposit(&resolved, &token);
+    h.check("releasing the resolved market's deposit");
+    client.release_creation_deposit(&resolved, &token);
+    h.check("releasing the same deposit again");
+
+    // A later referred bet pays the debt down before anything is credited.
+    client.place_bet(&b2, &later, &0, &50_000, &token, &referrer);
+    assert_eq!(client.get_referral_debt(&h.referrers[0], &token), 0);
+    h.check("a referred bet repaying the debt");
+    client.withdraw_protocol_fees(&token, &h.treasury);
+    h.check("withdrawing protocol fees");
+}
+
+#[test]
+fn test_dispute_overturn() {
+    let mut h = Harness::new();
+    let [b0, b1, b2, b3, _]: [Address; 5] = h.bettors().try_into().unwrap();
+    let [v0, v1, v2]: [Address; 3] = h.voters.clone().try_into().unwrap();
+    let market_id = h.create(&b0, 2, 1_000);
+    let client = &h.s.client;
+    let token = h.s.token().clone();
+
+    client.place_bet(&b0, &market_id, &0, &4_000, &token, &None);
+    client.place_bet(&b1, &market_id, &1, &1_000, &token, &None);
+    client.place_bet(&b2, &market_id, &1, &2_333, &token, &None);
+    client.place_bet(&b3, &market_id, &0, &1_000, &token, &None);
+    h.check("bets on both outcomes");
+
+    h.advance(1_000 + 86_400);
+    client.set_oracle_result(&market_id, &0, &0);
+    client.attempt_oracle_resolution(&market_id);
+    client.file_dispute(&b1, &market_id);
+    h.check("disputing the oracle's outcome");
+
+    client.cast_vote(&v0, &market_id, &1, &1_000);
+    client.cast_vote(&v1, &market_id, &1, &2_000);
+    client.cast_vote(&v2, &market_id, &0, &500);
+    h.check("locking votes");
+    // A revised vote locks its new weight on top of the old.
+    client.cast_vote(&v2, &market_id, &1, &700);
+    assert_eq!(client.get_locked_balance(&market_id, &v2), 1_200);
+    h.check("revising a vote");
+
+    h.advance(DISPUTE_PERIOD);
+    client.finalize_resolution(&market_id);
+    assert_eq!(h.market(market_id).winning_outcome, Some(1));
+    h.check("overturning the oracle");
+
+    let result = client.try_claim_winnings(&b0, &market_id, &token);
+    assert!(result.is_err());
+    client.claim_winnings(&b1, &market_id, &token);
+    h.check("the first overturned winner claiming");
+    client.claim_winnings(&b2, &market_id, &token);
+    h.check("the last winner claiming and the dust sweep");
+    client.release_creation_deposit(&market_id, &token);
+    h.check("releasing the creation deposit");
+}
@@ -0,0 +1,145 @@
+//! Dispute summaries: how a contested vote broke down.
+//!
+//! The summary is written when a disputed market resolves, either through
+//! `finalize_resolution` or an admin override, and is refused until then so
+//! a running vote cannot be read off the contract. It lives under its own
+//! key and is still there after `prune_market` drops the market.
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, token, Address};
+
+use crate::{
+    errors::ErrorCode,
+    modules::markets,
+    testutils::{register_token, Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::{MarketStatus, ResolutionSource, PRUNE_GRACE_PERIOD},
+};
+
+const SNAPSHOT_LEDGER: u32 = 10;
+const VOTING_PERIOD: u64 = 259_200;
+/// Governance tokens have 7 decimals; weights are normalized to 18.
+const WEIGHT_SCALE: i128 = 100_000_000_000;
+
+/// A disputed market with a Stellar asset governance token, so votes take
+/// the locking path. Returns the scenario and the governance token.
+fn disputed_scenario() -> (Scenario, Address) {
+    let s = ScenarioBuilder::new()
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+    s.env.ledger().set_sequence_number(SNAPSHOT_LEDGER);
+    let now = s.env.ledger().timestamp();
+    s.env.as_contract(&s.client.address, || {
+        let mut market = markets::get_market(&s.env, s.market_id()).unwrap();
+        market.status = MarketStatus::Disputed;
+        market.pending_resolution_timestamp = Some(now);
+        market.dispute_timestamp = Some(now);
+        market.dispute_snapshot_ledger = Some(SNAPSHOT_LEDGER);
+        markets::update_market(&s.env, market);
+    });
+    let gov = register_token(&s.env);
+    s.client.set_governance_token(&gov);
+    (s, gov)
+}
+
+/// Lock `weight` governance tokens from a fresh voter behind `outcome`.
+fn vote(s: &Scenario, gov: &Address, outcome: u32, weight: i128) -> Address {
+    let voter = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, gov).mint(&voter, &weight);
+    s.client
+        .cast_vote(&voter, &s.market_id(), &outcome, &weight);
+    voter
+}
+
+fn end_voting(s: &Scenario) {
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + VOTING_PERIOD);
+}
+
+#[test]
+fn test_summary_is_refused_while_vote_is_open() {
+    let (s, gov) = disputed_scenario();
+    let voter = vote(&s, &gov, 0, 700);
+
+    let summary = s.client.try_get_dispute_summary(&s.market_id());
+    let choice = s.client.try_get_voter_choice(&s.market_id(), &voter);
+
+    assert_eq!(summary, Err(Ok(ErrorCode::MarketNotResolved)));
+    assert_eq!(choice, Err(Ok(ErrorCode::MarketNotResolved)));
+}
+
+#[test]
+fn test_finalized_vote_is_summarized() {
+    let (s, gov) = disputed_scenario();
+    let yes = vote(&s, &gov, 0, 700);
+    vote(&s, &gov, 1, 300);
+    end_voting(&s);
+
+    s.client.finalize_resolution(&s.market_id());
+
+    let summary = s.client.get_dispute_summary(&s.market_id());
+    assert_eq!(summary.outcome_totals.get(0), Some(700 * WEIGHT_SCALE));
+    assert_eq!(summary.outcome_totals.get(1), Some(300 * WEIGHT_SCALE));
+    assert_eq!(summary.voter_count, 2);
+    assert!(summary.quorum_reached);
+    assert_eq!(summary.winning_outcome, 0);
+    assert_eq!(summary.source, ResolutionSource::Vote);
+    assert_eq!(summary.snapshot_ledger, Some(SNAPSHOT_LEDGER));
+    assert_eq!(
+        s.client.get_voter_choice(&s.market_id(), &yes),
+        Some((0, 700 * WEIGHT_SCALE))
+    );
+    let bystander = Address::generate(&s.env);
+    assert_eq!(s.client.get_voter_choice(&s.market_id(), &bystander), None);
+}
+
+#[test]
+fn test_admin_override_records_missing_quorum() {
+    let (s, gov) = disputed_scenario();
+    vote(&s, &gov, 0, 550);
+    vote(&s, &gov, 1, 450);
+    end_voting(&s);
+    assert_eq!(
+        s.client.try_finalize_resolution(&s.market_id()),
+        Err(Ok(ErrorCode::NoMajorityReached))
+    );
+
+    s.client.resolve_market(&s.market_id(), &1);
+
+    let summary = s.client.get_dispute_summary(&s.market_id());
+    assert!(!summary.quorum_reached);
+    assert_eq!(summary.winning_outcome, 1);
+    assert_eq!(summary.source, ResolutionSource::Admin);
+}
+
+#[test]
+fn test_summary_survives_prune() {
+    let (s, gov) = disputed_scenario();
+    let voter = vote(&s, &gov, 1, 900);
+    end_voting(&s);
+    s.client.finalize_resolution(&s.market_id());
+    let before = s.client.get_dispute_summary(&s.market_id());
+
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + PRUNE_GRACE_PERIOD);
+    s.client.prune_market(&s.market_id());
+
+    assert!(s.client.get_market(&s.market_id()).is_none());
+    assert_eq!(s.client.get_dispute_summary(&s.market_id()), before);
+    assert_eq!(
+        s.client.get_voter_choice(&s.market_id(), &voter),
+        Some((1, 900 * WEIGHT_SCALE))
+    );
+}
+
+#[test]
+fn test_undisputed_market_has_no_summary() {
+    let s = ScenarioBuilder::new()
+        .with_market(2, DEFAULT_DEADLINES)
+        .resolved(0)
+        .build();
+
+    let result = s.client.try_get_dispute_summary(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::MarketNotDisputed)));
+}
@@ -0,0 +1,176 @@
+//! Creation-time fee capture.
+//!
+//! A market charges the base fee in force when it was created. Raising the
+//! global fee afterwards must not take more from bets on it, and therefore
+//! must not shrink any winner's payout; the admin may only lower a market's
+//! captured fee. Markets stored before the fee was captured read at the
+//! current base fee until their next write pins it.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::testutils::Events as _;
+
+use crate::{
+    errors::ErrorCode,
+    modules::markets::DataKey,
+    testutils::{Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::{Market, MarketTier, MarketV1},
+};
+
+const CREATION_FEE_BPS: i128 = 100;
+const STAKE: i128 = 10_000;
+
+fn scenario() -> Scenario {
+    ScenarioBuilder::new()
+        .with_base_fee(CREATION_FEE_BPS)
+        .with_bettors(2, STAKE)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build()
+}
+
+#[test]
+fn test_global_fee_raise_does_not_reach_open_market() {
+    let s = scenario();
+    let (winner, loser) = (s.bettor(0), s.bettor(1));
+
+    s.client
+        .place_bet(&winner, &s.market_id(), &0, &STAKE, s.token(), &None);
+    s.client.set_base_fee(&1_000);
+    s.client
+        .place_bet(&loser, &s.market_id(), &1, &STAKE, s.token(), &None);
+    s.client.resolve_market(&s.market_id(), &0);
+
+    // Both bets paid 1%, so the pool is 2 * 9_900.
+    let payout = s.client.claim_winnings(&winner, &s.market_id(), s.token());
+    assert_eq!(payout, 19_800);
+    assert_eq!(s.client.get_revenue(s.token()), 200);
+}
+
+#[test]
+fn test_market_created_after_change_uses_new_fee() {
+    let s = scenario();
+    s.client.set_base_fee(&250);
+
+    let later = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+
+    assert_eq!(
+        s.client.get_market(&s.market_id()).unwrap().fee_bps,
+        CREATION_FEE_BPS
+    );
+    assert_eq!(s.client.get_market(&later).unwrap().fee_bps, 250);
+}
+
+#[test]
+fn test_captured_fee_can_be_lowered() {
+    let s = scenario();
+
+    s.client.reduce_market_fee(&s.market_id(), &50);
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("fee_redc"));
+
+    assert_eq!(s.client.get_market(&s.market_id()).unwrap().fee_bps, 50);
+
+    s.client
+        .place_bet(&s.bettor(0), &s.market_id(), &0, &STAKE, s.token(), &None);
+    assert_eq!(s.client.get_revenue(s.token()), 50);
+}
+
+#[test]
+fn test_captured_fee_cannot_be_raised() {
+    let s = scenario();
+
+    let result = s
+        .client
+        .try_reduce_market_fee(&s.market_id(), &(CREATION_FEE_BPS + 1));
+
+    assert_eq!(result, Err(Ok(ErrorCode::FeeIncreaseNotAllowed)));
+    assert_eq!(
+        s.client.get_market(&s.market_id()).unwrap().fee_bps,
+        CREATION_FEE_BPS
+    );
+}
+
+#[test]
+fn test_negative_fee_is_rejected() {
+    let s = scenario();
+
+    let result = s.client.try_reduce_market_fee(&s.market_id(), &-1);
+
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidAmount)));
+}
+
+#[test]
+fn test_unknown_market_is_rejected() {
+    let s = scenario();
+
+    let result = s.client.try_reduce_market_fee(&999, &0);
+
+    assert_eq!(result, Err(Ok(ErrorCode::MarketNotFound)));
+}
+
+/// Rewrite the scenario's market in the layout stored before `fee_bps`.
+fn store_as_legacy(s: &Scenario) {
+    s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage().persistent();
+        let m: Market = storage.get(&DataKey::Market(s.market_id())).unwrap();
+        let legacy = MarketV1 {
+            id: m.id,
+            creator: m.creator,
+            description: m.description,
+            options: m.options,
+            status: m.status,
+            deadline: m.deadline,
+            resolution_deadline: m.resolution_deadline,
+            winning_outcome: m.winning_outcome,
+            oracle_config: m.oracle_config,
+            total_staked: m.total_staked,
+            payout_mode: m.payout_mode,
+            tier: m.tier,
+            creation_deposit: m.creation_deposit,
+            parent_id: m.parent_id,
+            parent_outcome_idx: m.parent_outcome_idx,
+            resolved_at: m.resolved_at,
+            token_address: m.token_address,
+            outcome_stakes: m.outcome_stakes,
+            pending_resolution_timestamp: m.pending_resolution_timestamp,
+            dispute_snapshot_ledger: m.dispute_snapshot_ledger,
+            dispute_timestamp: m.dispute_timestamp,
+            winner_counts: m.winner_counts,
+            total_claimed: m.total_claimed,
+        };
+        storage.set(&DataKey::Market(s.market_id()), &legacy);
+    });
+}
+
+#[test]
+fn test_legacy_market_reads_at_the_current_base_fee() {
+    let s = scenario();
+    store_as_legacy(&s);
+    s.client.set_base_fee(&250);
+
+    assert_eq!(s.client.get_market(&s.market_id()).unwrap().fee_bps, 250);
+}
+
+#[test]
+fn test_legacy_market_is_pinned_by_its_next_write() {
+    let s = scenario();
+    store_as_legacy(&s);
+
+    s.client
+        .place_bet(&s.bettor(0), &s.market_id(), &0, &STAKE, s.token(), &None);
+    s.client.set_base_fee(&1_000);
+
+    assert_eq!(
+        s.client.get_market(&s.market_id()).unwrap().fee_bps,
+        CREATION_FEE_BPS
+    );
+    s.env.as_contract(&s.client.address, || {
+        let stored: Option<Market> = s
+            .env
+            .storage()
+            .persistent()
+            .get(&DataKey::Market(s.market_id()));
+        assert!(stored.is_some());
+    });
+}
@@ -0,0 +1,171 @@
+//! Markets that need seeding before they open.
+//!
+//! A market created with `create_market_with_funding` starts in the Funding
+//! status. Only its creator can bet until their stake meets the requirement,
+//! which opens it to everyone; if the window lapses first, anyone can cancel
+//! it and the creator gets their deposit back.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Events as _, Ledger as _},
+    vec, Address, String,
+};
+
+use crate::{
+    errors::ErrorCode,
+    testutils::{oracle_config, register_token, Scenario, ScenarioBuilder, DEFAULT_TIMESTAMP},
+    types::{MarketStatus, MarketTier, PRUNE_GRACE_PERIOD},
+};
+
+const DEPOSIT: i128 = 500;
+const MIN_LIQUIDITY: i128 = 5_000;
+const FUNDING_WINDOW: u64 = 100;
+
+/// A Funding market created by bettor 0; bettor 1 is the public.
+fn scenario() -> (Scenario, u64) {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(0)
+        .with_bettors(2, 100_000)
+        .build();
+    s.client.set_creation_deposit(&DEPOSIT);
+    let creator = s.bettor(0);
+    let market_id = s.client.create_market_with_funding(
+        &creator,
+        &String::from_str(&s.env, "Seeded market"),
+        &vec![
+            &s.env,
+            String::from_str(&s.env, "Yes"),
+            String::from_str(&s.env, "No"),
+        ],
+        &(DEFAULT_TIMESTAMP + 1_000),
+        &(DEFAULT_TIMESTAMP + 1_000 + 86_400),
+        &oracle_config(&creator, "test_feed"),
+        &MarketTier::Basic,
+        s.token(),
+        &0,
+        &0,
+        &MIN_LIQUIDITY,
+        &FUNDING_WINDOW,
+    );
+    (s, market_id)
+}
+
+fn bet(s: &Scenario, bettor: &Address, market_id: u64, amount: i128) -> Result<(), ErrorCode> {
+    match s
+        .client
+        .try_place_bet(bettor, &market_id, &0, &amount, s.token(), &None)
+    {
+        Ok(_) => Ok(()),
+        Err(Ok(error)) => Err(error),
+        Err(Err(_)) => panic!("unexpected host error"),
+    }
+}
+
+fn status(s: &Scenario, market_id: u64) -> MarketStatus {
+    s.client.get_market(&market_id).unwrap().status
+}
+
+#[test]
+fn test_funded_in_time_opens_for_public_bets() {
+    let (s, market_id) = scenario();
+    let creator = s.bettor(0);
+    assert_eq!(status(&s, market_id), MarketStatus::Funding);
+    let requirement = s.client.get_funding_requirement(&market_id).unwrap();
+    assert_eq!(requirement.min_liquidity, MIN_LIQUIDITY);
+    assert_eq!(
+        requirement.funding_deadline,
+        DEFAULT_TIMESTAMP + FUNDING_WINDOW
+    );
+
+    assert_eq!(bet(&s, &creator, market_id, 3_000), Ok(()));
+    assert_eq!(status(&s, market_id), MarketStatus::Funding);
+
+    s.env
+        .ledger()
+        .set_timestamp(DEFAULT_TIMESTAMP + FUNDING_WINDOW);
+    assert_eq!(bet(&s, &creator, market_id, 2_000), Ok(()));
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("mkt_fundd"));
+    assert_eq!(status(&s, market_id), MarketStatus::Active);
+
+    assert_eq!(bet(&s, &s.bettor(1), market_id, 1_000), Ok(()));
+    let result = s.client.try_cancel_unfunded_market(&market_id);
+    assert_eq!(result, Err(Ok(ErrorCode::MarketNotActive)));
+}
+
+#[test]
+fn test_public_bets_are_rejected_while_funding() {
+    let (s, market_id) = scenario();
+
+    assert_eq!(
+        bet(&s, &s.bettor(1), market_id, 10_000),
+        Err(ErrorCode::MarketNotActive)
+    );
+    let result = s.client.try_resolve_market(&market_id, &0);
+    assert_eq!(result, Err(Ok(ErrorCode::MarketNotActive)));
+    assert_eq!(status(&s, market_id), MarketStatus::Funding);
+}
+
+#[test]
+fn test_lapsed_window_cancels_and_returns_the_deposit() {
+    let (s, market_id) = scenario();
+    let creator = s.bettor(0);
+    assert_eq!(bet(&s, &creator, market_id, 1_000), Ok(()));
+    let before = s.balance(&creator);
+
+    let result = s.client.try_cancel_unfunded_market(&market_id);
+    assert_eq!(result, Err(Ok(ErrorCode::FundingWindowOpen)));
+
+    s.env
+        .ledger()
+        .set_timestamp(DEFAULT_TIMESTAMP + FUNDING_WINDOW + 1);
+    assert_eq!(
+        bet(&s, &creator, market_id, 4_000),
+        Err(ErrorCode::MarketClosed)
+    );
+    s.client.cancel_unfunded_market(&market_id);
+    assert_eq!(status(&s, market_id), MarketStatus::Cancelled);
+    assert_eq!(s.balance(&creator), before + DEPOSIT);
+
+    let refunded = s
+        .client
+        .withdraw_refund(&creator, &market_id, &0, s.token());
+    assert_eq!(refunded, 1_000);
+    assert_eq!(s.balance(&creator), before + DEPOSIT + 1_000);
+}
+
+#[test]
+fn test_creation_deposit_is_released_once() {
+    let (s, market_id) = scenario();
+    let creator = s.bettor(0);
+    assert_eq!(bet(&s, &creator, market_id, MIN_LIQUIDITY), Ok(()));
+    s.client.resolve_market(&market_id, &0);
+    let before = s.balance(&creator);
+
+    let other_token = register_token(&s.env);
+    let result = s
+        .client
+        .try_release_creation_deposit(&market_id, &other_token);
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidBetAmount)));
+
+    s.client.release_creation_deposit(&market_id, s.token());
+    assert_eq!(s.balance(&creator), before + DEPOSIT);
+    s.client.release_creation_deposit(&market_id, s.token());
+    assert_eq!(s.balance(&creator), before + DEPOSIT);
+    assert_eq!(s.client.get_market(&market_id).unwrap().creation_deposit, 0);
+}
+
+#[test]
+fn test_prune_drops_the_funding_requirement() {
+    let (s, market_id) = scenario();
+    assert_eq!(bet(&s, &s.bettor(0), market_id, MIN_LIQUIDITY), Ok(()));
+    s.client.resolve_market(&market_id, &0);
+
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + PRUNE_GRACE_PERIOD);
+    s.client.prune_market(&market_id);
+
+    assert_eq!(s.client.get_funding_requirement(&market_id), None);
+}
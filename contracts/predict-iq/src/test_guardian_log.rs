@@ -0,0 +1,182 @@
+//! Guardian action events and the on-chain action log.
+//!
+//! Every guardian and governance action emits a `grd_act` event and is kept
+//! in a ring buffer of the last 100 actions, so the record survives an
+//! indexer that missed the events. The circuit breaker's `pause` and
+//! `unpause` are logged too, with or without the `governance` feature.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    Address,
+};
+
+use crate::{
+    testutils::{ScenarioBuilder, DEFAULT_TIMESTAMP},
+    types::GuardianAction,
+};
+#[cfg(feature = "governance")]
+use crate::{
+    testutils::Scenario,
+    types::{Guardian, GUARDIAN_LOG_CAPACITY},
+};
+#[cfg(feature = "governance")]
+use soroban_sdk::BytesN;
+
+#[cfg(feature = "governance")]
+fn scenario() -> Scenario {
+    ScenarioBuilder::new().with_guardians(3).build()
+}
+
+#[test]
+fn test_guardian_pause_and_unpause_are_logged_in_order() {
+    let s = ScenarioBuilder::new().build();
+    let guardian = Address::generate(&s.env);
+    s.client.set_guardian(&guardian);
+
+    s.env.ledger().set_timestamp(DEFAULT_TIMESTAMP + 10);
+    s.client.pause();
+    assert!(std::format!("{:?}", s.env.events().all()).contains("grd_act"));
+    s.env.ledger().set_timestamp(DEFAULT_TIMESTAMP + 20);
+    s.client.unpause();
+    assert!(std::format!("{:?}", s.env.events().all()).contains("grd_act"));
+
+    let log = s.client.get_guardian_action_log(&0, &10);
+    assert_eq!(log.len(), 2);
+    let paused = log.get(0).unwrap();
+    assert_eq!(
+        (paused.seq, paused.actor, paused.action, paused.timestamp),
+        (0, guardian.clone(), GuardianAction::Paused, DEFAULT_TIMESTAMP + 10)
+    );
+    let unpaused = log.get(1).unwrap();
+    assert_eq!(
+        (unpaused.seq, unpaused.actor, unpaused.action, unpaused.timestamp),
+        (1, guardian, GuardianAction::Unpaused, DEFAULT_TIMESTAMP + 20)
+    );
+}
+
+#[test]
+fn test_pause_without_a_guardian_is_logged_for_the_admin() {
+    let s = ScenarioBuilder::new().build();
+
+    s.client.pause();
+
+    let log = s.client.get_guardian_action_log(&0, &10);
+    assert_eq!(log.len(), 1);
+    assert_eq!(log.get(0).unwrap().actor, s.admin);
+}
+
+#[cfg(feature = "governance")]
+#[test]
+fn test_pause_removal_vote_and_upgrade_vote_are_logged_in_order() {
+    let s = scenario();
+    let admin = s.client.get_admin().unwrap();
+    // Heavy enough to pause on its own: 10 of 13 voting power.
+    let pauser = Address::generate(&s.env);
+    s.client.add_guardian(&Guardian {
+        address: pauser.clone(),
+        voting_power: 10,
+    });
+    let wasm_hash = BytesN::from_array(&s.env, &[7; 32]);
+    let target = s.guardians.get(2).unwrap();
+    let signer = s.guardians.get(0).unwrap();
+    let upgrade_voter = s.guardians.get(1).unwrap();
+
+    s.env.ledger().set_timestamp(DEFAULT_TIMESTAMP + 10);
+    s.client.emergency_pause(&pauser);
+    s.env.ledger().set_timestamp(DEFAULT_TIMESTAMP + 20);
+    s.client.remove_guardian(&target);
+    s.client.vote_on_guardian_removal(&signer, &true);
+    s.env.ledger().set_timestamp(DEFAULT_TIMESTAMP + 30);
+    s.client.initiate_upgrade(&wasm_hash);
+    s.client.vote_for_upgrade(&upgrade_voter, &true);
+
+    // The last call's events: its own `upg_vote` and the `grd_act` record.
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("grd_act"));
+    assert!(events.contains("upg_vote"));
+
+    let expected = [
+        (
+            admin.clone(),
+            GuardianAction::GuardiansInitialized(3),
+            DEFAULT_TIMESTAMP,
+        ),
+        (
+            admin.clone(),
+            GuardianAction::GuardianAdded(pauser.clone()),
+            DEFAULT_TIMESTAMP,
+        ),
+        (
+            pauser.clone(),
+            GuardianAction::EmergencyPause,
+            DEFAULT_TIMESTAMP + 10,
+        ),
+        (
+            admin.clone(),
+            GuardianAction::GuardianRemovalProposed(target.clone()),
+            DEFAULT_TIMESTAMP + 20,
+        ),
+        (
+            signer.clone(),
+            GuardianAction::GuardianRemovalVoted(target.clone(), true),
+            DEFAULT_TIMESTAMP + 20,
+        ),
+        (
+            admin.clone(),
+            GuardianAction::UpgradeInitiated(wasm_hash.clone()),
+            DEFAULT_TIMESTAMP + 30,
+        ),
+        (
+            upgrade_voter.clone(),
+            GuardianAction::UpgradeVoted(wasm_hash.clone(), true),
+            DEFAULT_TIMESTAMP + 30,
+        ),
+    ];
+
+    let log = s.client.get_guardian_action_log(&0, &100);
+    assert_eq!(log.len(), expected.len() as u32);
+    for (i, (record, (actor, action, timestamp))) in log.iter().zip(expected.iter()).enumerate() {
+        assert_eq!(record.seq, i as u64);
+        assert_eq!(&record.actor, actor);
+        assert_eq!(&record.action, action);
+        assert_eq!(record.timestamp, *timestamp);
+    }
+}
+
+#[cfg(feature = "governance")]
+#[test]
+fn test_log_pages_from_a_sequence_number() {
+    let s = scenario();
+    for _ in 0..4 {
+        s.client.set_timelock_duration(&(48 * 3600));
+    }
+
+    let page = s.client.get_guardian_action_log(&2, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().seq, 2);
+    assert_eq!(page.get(1).unwrap().seq, 3);
+    assert!(s.client.get_guardian_action_log(&5, &10).is_empty());
+}
+
+#[cfg(feature = "governance")]
+#[test]
+fn test_log_keeps_the_last_hundred_actions() {
+    let s = scenario();
+    // Initialization plus 104 timelock changes: 105 actions.
+    for i in 0..104u64 {
+        s.client.set_timelock_duration(&(24 * 3600 + i));
+    }
+
+    let log = s.client.get_guardian_action_log(&0, &200);
+    assert_eq!(log.len(), GUARDIAN_LOG_CAPACITY);
+    assert_eq!(log.get(0).unwrap().seq, 5);
+    let last = log.get(GUARDIAN_LOG_CAPACITY - 1).unwrap();
+    assert_eq!(last.seq, 104);
+    assert_eq!(
+        last.action,
+        GuardianAction::TimelockDurationSet(24 * 3600 + 103)
+    );
+}
@@ -0,0 +1,129 @@
+//! Metadata getters read by the API through `simulateTransaction`.
+
+#![cfg(test)]
+
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events as _},
+    Address, String, TryFromVal, Vec,
+};
+
+use crate::errors::ErrorCode;
+use crate::testutils::{oracle_config, Scenario, ScenarioBuilder};
+use crate::types::{MarketLabels, MarketTier, MAX_CATEGORY_LEN, MAX_TAGS_PER_MARKET, MAX_TAG_LEN};
+
+const DEADLINES: (u64, u64) = (1_000, 1_000 + 86_400);
+
+fn labels(s: &Scenario, category: Option<&str>, tags: &[&str]) -> MarketLabels {
+    let mut list = Vec::new(&s.env);
+    for tag in tags {
+        list.push_back(String::from_str(&s.env, tag));
+    }
+    MarketLabels {
+        category: category.map(|c| String::from_str(&s.env, c)),
+        tags: list,
+    }
+}
+
+fn try_create_with_labels(s: &Scenario, labels: &MarketLabels) -> Result<u64, ErrorCode> {
+    let mut options = Vec::new(&s.env);
+    options.push_back(String::from_str(&s.env, "Yes"));
+    options.push_back(String::from_str(&s.env, "No"));
+    s.client
+        .try_create_market_with_labels(
+            &s.creator,
+            &String::from_str(&s.env, "Labelled Market"),
+            &options,
+            &DEADLINES.0,
+            &DEADLINES.1,
+            &oracle_config(&Address::generate(&s.env), "test_feed"),
+            &MarketTier::Basic,
+            s.token(),
+            &0,
+            &0,
+            labels,
+        )
+        .map(|id| id.unwrap())
+        .map_err(|e| e.unwrap())
+}
+
+#[test]
+fn test_metadata_getters_return_market_text() {
+    let s = ScenarioBuilder::new().with_market(3, DEADLINES).build();
+
+    let description = s.client.get_market_description(&s.market_id());
+    let options = s.client.get_market_options(&s.market_id());
+
+    assert_eq!(description, Some(String::from_str(&s.env, "Test Market")));
+    let mut expected = Vec::new(&s.env);
+    for label in ["Outcome 0", "Outcome 1", "Outcome 2"] {
+        expected.push_back(String::from_str(&s.env, label));
+    }
+    assert_eq!(options, Some(expected));
+}
+
+#[test]
+fn test_metadata_getters_are_none_for_unknown_market() {
+    let s = ScenarioBuilder::new().build();
+
+    assert_eq!(s.client.get_market_description(&404), None);
+    assert_eq!(s.client.get_market_options(&404), None);
+}
+
+#[test]
+fn test_labels_are_stored_and_emitted_with_the_market() {
+    let s = ScenarioBuilder::new().with_token().build();
+    let labels = labels(&s, Some("crypto"), &["btc", "price"]);
+
+    let market_id = try_create_with_labels(&s, &labels).unwrap();
+    let events = s.env.events().all();
+
+    assert_eq!(s.client.get_market_labels(&market_id), Some(labels.clone()));
+    let soroban_sdk::xdr::ContractEventBody::V0(event) = &events.events().last().unwrap().body;
+    let name = soroban_sdk::Symbol::try_from_val(&s.env, &event.topics[0]).unwrap();
+    assert_eq!(name, soroban_sdk::symbol_short!("mkt_creat"));
+    let data = soroban_sdk::Val::try_from_val(&s.env, &event.data).unwrap();
+    let (_, _, _, _, category, tags) =
+        <(u32, String, u32, u64, Option<String>, Vec<String>)>::try_from_val(&s.env, &data)
+            .unwrap();
+    assert_eq!(category, labels.category);
+    assert_eq!(tags, labels.tags);
+}
+
+#[test]
+fn test_labels_are_empty_for_markets_created_without() {
+    let s = ScenarioBuilder::new().with_market(2, DEADLINES).build();
+
+    let stored = s.client.get_market_labels(&s.market_id()).unwrap();
+    assert_eq!(stored.category, None);
+    assert!(stored.tags.is_empty());
+    assert_eq!(s.client.get_market_labels(&404), None);
+}
+
+#[test]
+fn test_label_bounds() {
+    let s = ScenarioBuilder::new().with_token().build();
+    let longest_category = "c".repeat(MAX_CATEGORY_LEN as usize);
+    let longest_tag = "t".repeat(MAX_TAG_LEN as usize);
+    let most_tags: std::vec::Vec<&str> = (0..MAX_TAGS_PER_MARKET).map(|_| "tag").collect();
+
+    assert!(try_create_with_labels(&s, &labels(&s, Some(&longest_category), &[])).is_ok());
+    assert!(try_create_with_labels(&s, &labels(&s, None, &[&longest_tag])).is_ok());
+    assert!(try_create_with_labels(&s, &labels(&s, None, &most_tags)).is_ok());
+
+    let too_many_tags = [most_tags.as_slice(), &["one-more"]].concat();
+    let rejected = [
+        labels(&s, Some(&"c".repeat(MAX_CATEGORY_LEN as usize + 1)), &[]),
+        labels(&s, Some(""), &[]),
+        labels(&s, None, &[&"t".repeat(MAX_TAG_LEN as usize + 1)]),
+        labels(&s, None, &[""]),
+        labels(&s, None, &too_many_tags),
+    ];
+    for labels in &rejected {
+        assert_eq!(
+            try_create_with_labels(&s, labels),
+            Err(ErrorCode::InvalidMarketLabels)
+        );
+    }
+}
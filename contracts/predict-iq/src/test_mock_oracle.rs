@@ -0,0 +1,120 @@
+//! Cross-contract oracle resolution tests.
+//!
+//! A [`MockOracle`](crate::mock_oracle::MockOracle) is registered as the
+//! market's `oracle_address`, so `attempt_oracle_resolution` pulls its result
+//! through the generated `OutcomeFeedClient` rather than relying on the admin
+//! pushing one with `set_oracle_result`.
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Ledger as _, String};
+
+use crate::{
+    errors::ErrorCode,
+    modules::oracles::{get_last_update, get_oracle_result},
+    testutils::{Scenario, ScenarioBuilder},
+    types::MarketStatus,
+};
+
+const FEED: &str = "btc_above_100k";
+const RESOLUTION_DEADLINE: u64 = 1_000 + 86_400;
+
+fn scenario() -> Scenario {
+    let s = ScenarioBuilder::new()
+        .with_mock_oracle(FEED)
+        .with_market(3, (1_000, RESOLUTION_DEADLINE))
+        .build();
+    s.env.ledger().set_timestamp(RESOLUTION_DEADLINE + 10);
+    s
+}
+
+fn publish(s: &Scenario, feed_id: &str, outcome: u32, published_at: u64) {
+    s.oracle()
+        .set_report(&String::from_str(&s.env, feed_id), &outcome, &published_at);
+}
+
+fn status(s: &Scenario) -> MarketStatus {
+    s.client.get_market(&s.market_id()).unwrap().status
+}
+
+#[test]
+fn test_correct_feed_resolves_market() {
+    let s = scenario();
+    publish(&s, FEED, 2, RESOLUTION_DEADLINE);
+
+    s.client.attempt_oracle_resolution(&s.market_id());
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.status, MarketStatus::PendingResolution);
+    assert_eq!(market.winning_outcome, Some(2));
+    s.env.as_contract(&s.client.address, || {
+        assert_eq!(get_oracle_result(&s.env, s.market_id(), 0), Some(2));
+        assert_eq!(get_last_update(&s.env, s.market_id(), 0), Some(RESOLUTION_DEADLINE));
+    });
+}
+
+#[test]
+fn test_wrong_feed_id_is_rejected() {
+    let s = scenario();
+    publish(&s, "eth_above_10k", 0, RESOLUTION_DEADLINE);
+
+    let result = s.client.try_attempt_oracle_resolution(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::OracleFeedMismatch)));
+    assert_eq!(status(&s), MarketStatus::Active);
+}
+
+#[test]
+fn test_stale_report_is_rejected() {
+    let s = scenario();
+    // 110s old at the ledger time, past the 60s staleness cap.
+    publish(&s, FEED, 0, RESOLUTION_DEADLINE - 100);
+
+    let result = s.client.try_attempt_oracle_resolution(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::StalePrice)));
+    assert_eq!(status(&s), MarketStatus::Active);
+}
+
+#[test]
+fn test_out_of_range_outcome_is_rejected() {
+    let s = scenario();
+    publish(&s, FEED, 3, RESOLUTION_DEADLINE);
+
+    let result = s.client.try_attempt_oracle_resolution(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidOutcome)));
+}
+
+#[test]
+fn test_trapping_oracle_surfaces_clean_error() {
+    let s = scenario();
+    publish(&s, FEED, 0, RESOLUTION_DEADLINE);
+    s.oracle().set_trap(&true);
+
+    let result = s.client.try_attempt_oracle_resolution(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::OracleFailure)));
+    assert_eq!(status(&s), MarketStatus::Active);
+}
+
+#[test]
+fn test_oracle_without_report_surfaces_clean_error() {
+    let s = scenario();
+
+    let result = s.client.try_attempt_oracle_resolution(&s.market_id());
+
+    assert_eq!(result, Err(Ok(ErrorCode::OracleFailure)));
+}
+
+#[test]
+fn test_pushed_result_takes_precedence_over_oracle() {
+    let s = scenario();
+    publish(&s, FEED, 2, RESOLUTION_DEADLINE);
+    s.client.set_oracle_result(&s.market_id(), &0, &1);
+
+    s.client.attempt_oracle_resolution(&s.market_id());
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.winning_outcome, Some(1));
+}
@@ -0,0 +1,119 @@
+//! Rounding policy for winner payouts.
+//!
+//! Each winner is paid the floor of their share of the pool. The remainder
+//! is tracked per market and swept into protocol revenue when the last
+//! winner claims, or with any unclaimed winnings when the market is pruned,
+//! so every unit bet is accounted for exactly: paid out, kept as a fee, or
+//! swept as dust.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::testutils::{Events as _, Ledger as _};
+
+use crate::{
+    testutils::{Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::PRUNE_GRACE_PERIOD,
+};
+
+fn scenario(base_fee: i128, bettors: u32) -> Scenario {
+    ScenarioBuilder::new()
+        .with_base_fee(base_fee)
+        .with_bettors(bettors, 1_000_000)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build()
+}
+
+/// Place `bets` as `(outcome, amount)`, one bettor each, and resolve to 0.
+fn resolve_with(s: &Scenario, bets: &[(u32, i128)]) -> i128 {
+    let mut stakes_in = 0;
+    for (i, (outcome, amount)) in bets.iter().enumerate() {
+        s.client.place_bet(
+            &s.bettor(i as u32),
+            &s.market_id(),
+            outcome,
+            amount,
+            s.token(),
+            &None,
+        );
+        stakes_in += amount;
+    }
+    s.client.resolve_market(&s.market_id(), &0);
+    stakes_in
+}
+
+fn claim(s: &Scenario, index: u32) -> i128 {
+    s.client
+        .claim_winnings(&s.bettor(index), &s.market_id(), s.token())
+}
+
+#[test]
+fn test_dust_is_swept_when_last_winner_claims() {
+    let s = scenario(0, 3);
+    // Pool 4_001 over a winning stake of 3_000: shares of 1_333.67 and 2_667.33.
+    let stakes_in = resolve_with(&s, &[(0, 1_000), (0, 2_000), (1, 1_001)]);
+
+    assert_eq!(claim(&s, 0), 1_333);
+    assert_eq!(s.client.get_market_dust(&s.market_id()), 0);
+    assert_eq!(s.client.get_revenue(s.token()), 0);
+
+    assert_eq!(claim(&s, 1), 2_667);
+    assert_eq!(s.client.get_market_dust(&s.market_id()), 1);
+    assert_eq!(s.client.get_revenue(s.token()), 1);
+
+    assert_eq!(1_333 + 2_667 + 1, stakes_in);
+    assert_eq!(s.balance(&s.client.address), 1);
+}
+
+#[test]
+fn test_stakes_in_equal_stakes_out_plus_fees_plus_dust() {
+    let s = scenario(100, 4);
+    let stakes_in = resolve_with(&s, &[(0, 3_333), (0, 5_557), (0, 7_001), (1, 4_999)]);
+    let fees = s.client.get_revenue(s.token());
+
+    let stakes_out: i128 = (0..3).map(|i| claim(&s, i)).sum();
+    let dust = s.client.get_market_dust(&s.market_id());
+
+    assert!(dust > 0);
+    assert_eq!(stakes_out + fees + dust, stakes_in);
+    assert_eq!(s.client.get_revenue(s.token()), fees + dust);
+    assert_eq!(s.balance(&s.client.address), fees + dust);
+}
+
+#[test]
+fn test_dust_with_seven_decimal_amounts() {
+    let s = scenario(0, 3);
+    s.mint(&s.bettor(0), 10_000_000_000);
+    s.mint(&s.bettor(1), 10_000_000_000);
+    s.mint(&s.bettor(2), 10_000_000_000);
+    let stakes_in = resolve_with(
+        &s,
+        &[(0, 6_599_999_999), (0, 3_300_000_000), (1, 1_000_000_001)],
+    );
+
+    let stakes_out = claim(&s, 0) + claim(&s, 1);
+    // The last claim sweeps the dust.
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("dust_swp"));
+    let dust = s.client.get_market_dust(&s.market_id());
+
+    assert!(dust > 0);
+    assert_eq!(stakes_out + dust, stakes_in);
+    assert_eq!(s.balance(&s.client.address), dust);
+}
+
+#[test]
+fn test_unclaimed_winnings_are_swept_when_the_market_is_pruned() {
+    let s = scenario(0, 3);
+    let stakes_in = resolve_with(&s, &[(0, 1_000), (0, 2_000), (1, 1_001)]);
+    let paid = claim(&s, 0);
+    assert_eq!(s.client.get_revenue(s.token()), 0);
+
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + PRUNE_GRACE_PERIOD);
+    s.client.prune_market(&s.market_id());
+
+    // Bettor 1's 2_667 and the unit of dust.
+    assert_eq!(s.client.get_revenue(s.token()), stakes_in - paid);
+    assert_eq!(s.balance(&s.client.address), stakes_in - paid);
+}
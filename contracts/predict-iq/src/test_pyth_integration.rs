@@ -117,12 +117,13 @@ fn setup_market(e: &Env, config: OracleConfig) -> (PredictIQClient<'static>, u64
     options.push_back(String::from_str(e, "Yes"));
     options.push_back(String::from_str(e, "No"));
 
+    let deadline = e.ledger().timestamp() + 1_000;
     let market_id = client.create_market(
         &admin,
         &String::from_str(e, "BTC above $50k?"),
         &options,
-        &1_000,
-        &2_000,
+        &deadline,
+        &(deadline + 86_400),
         &config,
         &MarketTier::Basic,
         &token,
@@ -190,7 +191,7 @@ fn test_different_markets_can_have_different_feed_ids() {
         &String::from_str(&e, "BTC market"),
         &opts,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &btc_config,
         &MarketTier::Basic,
         &token,
@@ -202,7 +203,7 @@ fn test_different_markets_can_have_different_feed_ids() {
         &String::from_str(&e, "ETH market"),
         &opts,
         &1000,
-        &2000,
+        &(1000 + 86_400),
         &eth_config,
         &MarketTier::Basic,
         &token,
@@ -394,20 +395,23 @@ fn test_resolve_with_pyth_stores_outcome_and_timestamp() {
     // strike_price = None → threshold = 0 → price (5_000_000) >= 0 → outcome 0
     let config = oracle_config(&e, pyth_addr, u64::MAX, 500);
 
-    let result = resolve_with_pyth(&e, 1u64, 0u32, &config);
-    assert!(
-        result.is_ok(),
-        "resolve_with_pyth should succeed: {:?}",
-        result
-    );
-    assert_eq!(
-        result.unwrap(),
-        0u32,
-        "outcome should be 0 (price >= strike)"
-    );
-
-    assert_eq!(get_oracle_result(&e, 1u64, 0u32), Some(0u32));
-    assert!(get_last_update(&e, 1u64, 0u32).is_some());
+    let contract_id = e.register(crate::PredictIQ, ());
+    e.as_contract(&contract_id, || {
+        let result = resolve_with_pyth(&e, 1u64, 0u32, &config);
+        assert!(
+            result.is_ok(),
+            "resolve_with_pyth should succeed: {:?}",
+            result
+        );
+        assert_eq!(
+            result.unwrap(),
+            0u32,
+            "outcome should be 0 (price >= strike)"
+        );
+
+        assert_eq!(get_oracle_result(&e, 1u64, 0u32), Some(0u32));
+        assert!(get_last_update(&e, 1u64, 0u32).is_some());
+    });
 }
 
 #[test]
@@ -426,8 +430,11 @@ fn test_resolve_with_pyth_outcome_below_strike() {
         strike_price: Some(10_000_000),
     };
 
-    let result = resolve_with_pyth(&e, 2u64, 0u32, &config);
-    assert_eq!(result, Ok(1u32), "outcome should be 1 (price < strike)");
+    let contract_id = e.register(crate::PredictIQ, ());
+    e.as_contract(&contract_id, || {
+        let result = resolve_with_pyth(&e, 2u64, 0u32, &config);
+        assert_eq!(result, Ok(1u32), "outcome should be 1 (price < strike)");
+    });
 }
 
 #[test]
@@ -441,26 +448,27 @@ fn test_resolve_with_pyth_stale_price_leaves_no_storage() {
         oracle_address: pyth_addr,
         feed_id: btc_usd_feed_id(&e),
         min_responses: Some(1),
-        max_staleness_seconds: 60, // 60s max — price is 10_000s old
+        max_staleness_seconds: u64::MAX, // capped at 60s — price is 10_000s old
         max_confidence_bps: 500,
         strike_price: None,
     };
 
-    // fetch_pyth_price uses get_price (permissive) when max_staleness != u64::MAX,
-    // but validate_price will catch the staleness.
-    // To test the off-chain path we temporarily override by calling resolve_with_pyth
-    // which calls validate_price after fetch.
-    let result = resolve_with_pyth(&e, 3u64, 0u32, &config);
-    assert_eq!(result, Err(ErrorCode::StalePrice));
-
-    assert!(
-        get_oracle_result(&e, 3u64, 0u32).is_none(),
-        "no result should be stored"
-    );
-    assert!(
-        get_last_update(&e, 3u64, 0u32).is_none(),
-        "no timestamp should be stored"
-    );
+    let contract_id = e.register(crate::PredictIQ, ());
+    e.as_contract(&contract_id, || {
+        // fetch_pyth_price uses get_price (permissive) when max_staleness == u64::MAX,
+        // so validate_price is what catches the staleness.
+        let result = resolve_with_pyth(&e, 3u64, 0u32, &config);
+        assert_eq!(result, Err(ErrorCode::StalePrice));
+
+        assert!(
+            get_oracle_result(&e, 3u64, 0u32).is_none(),
+            "no result should be stored"
+        );
+        assert!(
+            get_last_update(&e, 3u64, 0u32).is_none(),
+            "no timestamp should be stored"
+        );
+    });
 }
 
 #[test]
@@ -471,15 +479,18 @@ fn test_resolve_with_pyth_multiple_oracle_ids_are_independent() {
     let pyth_addr = e.register(MockPythContract, ());
     let config = oracle_config(&e, pyth_addr, u64::MAX, 500);
 
-    // Resolve with oracle_id 0 and oracle_id 1 for the same market.
-    resolve_with_pyth(&e, 10u64, 0u32, &config).unwrap();
-    resolve_with_pyth(&e, 10u64, 1u32, &config).unwrap();
+    let contract_id = e.register(crate::PredictIQ, ());
+    e.as_contract(&contract_id, || {
+        // Resolve with oracle_id 0 and oracle_id 1 for the same market.
+        resolve_with_pyth(&e, 10u64, 0u32, &config).unwrap();
+        resolve_with_pyth(&e, 10u64, 1u32, &config).unwrap();
 
-    assert_eq!(get_oracle_result(&e, 10u64, 0u32), Some(0u32));
-    assert_eq!(get_oracle_result(&e, 10u64, 1u32), Some(0u32));
+        assert_eq!(get_oracle_result(&e, 10u64, 0u32), Some(0u32));
+        assert_eq!(get_oracle_result(&e, 10u64, 1u32), Some(0u32));
 
-    // Different market must not be affected.
-    assert!(get_oracle_result(&e, 11u64, 0u32).is_none());
+        // Different market must not be affected.
+        assert!(get_oracle_result(&e, 11u64, 0u32).is_none());
+    });
 }
 
 // ---------------------------------------------------------------------------
@@ -498,8 +509,9 @@ fn test_contract_attempt_oracle_resolution_with_mock_pyth() {
     let config = oracle_config(&e, pyth_addr, u64::MAX, 500);
     let (client, market_id) = setup_market(&e, config);
 
-    // Advance past resolution_deadline (2000).
-    e.ledger().set_timestamp(1_700_002_000);
+    // Advance to the resolution deadline.
+    let resolution_deadline = client.get_market(&market_id).unwrap().resolution_deadline;
+    e.ledger().set_timestamp(resolution_deadline);
 
     // Manually inject oracle result (simulates resolve_with_pyth having run).
     client.set_oracle_result(&market_id, &0, &0);
@@ -0,0 +1,187 @@
+//! Referral rewards from cancelled markets.
+//!
+//! A referrer earns 10% of the fee on each referred bet. When the market is
+//! cancelled that reward is taken back: out of the unclaimed balance if it is
+//! still there, otherwise as a debt netted against the referrer's next
+//! rewards in the same token. Cancellation reverses a bounded batch of
+//! referrers; `reverse_market_referrals` takes the rest.
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, Address, Vec};
+
+use crate::{
+    errors::ErrorCode,
+    testutils::{Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::{MarketTier, MAX_REFERRAL_REVERSAL_BATCH},
+};
+
+fn scenario() -> (Scenario, Address) {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(100)
+        .with_bettors(1, 100_000)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+    let referrer = Address::generate(&s.env);
+    (s, referrer)
+}
+
+/// Bet `amount` on `market_id` through `referrer`. At a 1% fee the referrer
+/// earns `amount / 1_000`.
+fn referred_bet(s: &Scenario, market_id: u64, amount: i128, referrer: &Address) {
+    s.client.place_bet(
+        &s.bettor(0),
+        &market_id,
+        &0,
+        &amount,
+        s.token(),
+        &Some(referrer.clone()),
+    );
+}
+
+#[test]
+fn test_accrual_is_tracked_per_market() {
+    let (s, referrer) = scenario();
+    let other = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+    referred_bet(&s, other, 20_000, &referrer);
+
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &referrer), 10);
+    assert_eq!(s.client.get_referral_accrual(&other, &referrer), 20);
+}
+
+#[test]
+fn test_cancellation_reverses_unclaimed_reward() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+
+    s.client.cancel_market_admin(&s.market_id());
+
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &referrer), 0);
+    assert_eq!(s.client.get_referral_debt(&referrer, s.token()), 0);
+    let result = s.client.try_claim_referral_rewards(&referrer, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientBalance)));
+}
+
+#[test]
+fn test_cancellation_only_reverses_that_market() {
+    let (s, referrer) = scenario();
+    let other = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+    referred_bet(&s, other, 20_000, &referrer);
+
+    s.client.cancel_market_admin(&s.market_id());
+
+    assert_eq!(s.client.get_referral_accrual(&other, &referrer), 20);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 20);
+}
+
+#[test]
+fn test_claimed_reward_nets_against_later_accrual() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 10);
+
+    s.client.cancel_market_admin(&s.market_id());
+    assert_eq!(s.client.get_referral_debt(&referrer, s.token()), 10);
+
+    let later = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+    referred_bet(&s, later, 30_000, &referrer);
+
+    // The full reward accrues to the market; the clawback comes out of what
+    // the referrer can claim.
+    assert_eq!(s.client.get_referral_accrual(&later, &referrer), 30);
+    assert_eq!(s.client.get_referral_debt(&referrer, s.token()), 0);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 20);
+}
+
+#[test]
+fn test_debt_larger_than_next_reward_carries_over() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 50_000, &referrer);
+    s.client.claim_referral_rewards(&referrer, s.token());
+    s.client.cancel_market_admin(&s.market_id());
+
+    let later = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+    referred_bet(&s, later, 20_000, &referrer);
+
+    assert_eq!(s.client.get_referral_debt(&referrer, s.token()), 30);
+    let result = s.client.try_claim_referral_rewards(&referrer, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientBalance)));
+}
+
+#[test]
+fn test_reward_leaves_revenue_and_returns_on_cancellation() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+    // The 100 fee is split: 10 to the referrer, 90 to the protocol.
+    assert_eq!(s.client.get_revenue(s.token()), 90);
+
+    s.client.cancel_market_admin(&s.market_id());
+
+    // The unclaimed reward is recovered back into revenue.
+    assert_eq!(s.client.get_revenue(s.token()), 100);
+}
+
+#[test]
+fn test_debt_repayment_stays_in_revenue() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+    s.client.claim_referral_rewards(&referrer, s.token());
+    s.client.cancel_market_admin(&s.market_id());
+    // Nothing was left to recover; the claimed 10 is owed.
+    assert_eq!(s.client.get_revenue(s.token()), 90);
+
+    let later = s.create_market(2, DEFAULT_DEADLINES, &MarketTier::Basic);
+    referred_bet(&s, later, 20_000, &referrer);
+
+    // Of the 20 reward, 10 repays the debt and only 10 leaves revenue.
+    assert_eq!(s.client.get_revenue(s.token()), 90 + 200 - 10);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 10);
+}
+
+#[test]
+fn test_cancellation_with_many_referrers_reverses_in_batches() {
+    let (s, _) = scenario();
+    let count = 2 * MAX_REFERRAL_REVERSAL_BATCH + 5;
+    s.mint(&s.bettor(0), i128::from(count) * 10_000);
+    let mut referrers = Vec::new(&s.env);
+    for _ in 0..count {
+        let referrer = Address::generate(&s.env);
+        referred_bet(&s, s.market_id(), 10_000, &referrer);
+        referrers.push_back(referrer);
+    }
+    let late = referrers.last().unwrap();
+    // A referrer still waiting for their batch can claim; the reversal
+    // then books it as debt.
+    assert_eq!(s.client.claim_referral_rewards(&late, s.token()), 10);
+
+    s.client.cancel_market_admin(&s.market_id());
+
+    let first = referrers.get(0).unwrap();
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &first), 0);
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &late), 10);
+
+    assert_eq!(s.client.reverse_market_referrals(&s.market_id()), 5);
+    assert_eq!(s.client.reverse_market_referrals(&s.market_id()), 0);
+    assert_eq!(s.client.reverse_market_referrals(&s.market_id()), 0);
+
+    for referrer in referrers.iter() {
+        assert_eq!(s.client.get_referral_accrual(&s.market_id(), &referrer), 0);
+    }
+    assert_eq!(s.client.get_referral_debt(&late, s.token()), 10);
+    // Every fee is back in revenue except the reward already claimed.
+    let fees = i128::from(referrers.len()) * 100;
+    assert_eq!(s.client.get_revenue(s.token()), fees - 10);
+}
+
+#[test]
+fn test_reversal_needs_a_cancelled_market() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, s.market_id(), 10_000, &referrer);
+
+    let result = s.client.try_reverse_market_referrals(&s.market_id());
+    assert_eq!(result, Err(Ok(ErrorCode::MarketNotCancelled)));
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &referrer), 10);
+}
@@ -0,0 +1,297 @@
+//! Expiry of unclaimed referral rewards.
+//!
+//! A referrer's balance in a token stays claimable for the referral expiry
+//! (365 days by default) after their last accrual or claim in it. Past that,
+//! claiming fails and anyone can sweep the balance into protocol revenue,
+//! in batches of at most `MAX_REFERRAL_SWEEP_BATCH` referrers holding at
+//! most `MAX_REFERRAL_SWEEP_ACCRUALS` unclaimed market accruals. A balance
+//! from before expiry was tracked starts its clock at its first sweep.
+
+#![cfg(test)]
+extern crate std;
+
+use soroban_sdk::{
+    testutils::{Address as _, Events as _, Ledger as _},
+    vec, Address, Map, Vec,
+};
+
+use crate::{
+    errors::ErrorCode,
+    modules::fees::DataKey,
+    testutils::{Scenario, ScenarioBuilder, DEFAULT_DEADLINES, DEFAULT_TIMESTAMP},
+    types::{
+        DEFAULT_REFERRAL_EXPIRY, MAX_REFERRAL_SWEEP_ACCRUALS, MAX_REFERRAL_SWEEP_BATCH,
+        MAX_UNCLAIMED_ACCRUAL_MARKETS,
+    },
+};
+
+fn scenario() -> (Scenario, Address) {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(100)
+        .with_bettors(1, 100_000)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+    let referrer = Address::generate(&s.env);
+    (s, referrer)
+}
+
+/// Bet `amount` through `referrer`. At a 1% fee the referrer earns
+/// `amount / 1_000`.
+fn referred_bet(s: &Scenario, amount: i128, referrer: &Address) {
+    s.client.place_bet(
+        &s.bettor(0),
+        &s.market_id(),
+        &0,
+        &amount,
+        s.token(),
+        &Some(referrer.clone()),
+    );
+}
+
+fn set_time(s: &Scenario, timestamp: u64) {
+    s.env.ledger().set_timestamp(timestamp);
+}
+
+#[test]
+fn test_expired_balance_cannot_be_claimed_and_is_swept() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, 10_000, &referrer);
+    assert_eq!(
+        s.client.get_referral_accrued_at(&referrer, s.token()),
+        Some(DEFAULT_TIMESTAMP)
+    );
+
+    set_time(&s, DEFAULT_TIMESTAMP + DEFAULT_REFERRAL_EXPIRY + 1);
+    let result = s.client.try_claim_referral_rewards(&referrer, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::RewardsExpired)));
+
+    let revenue = s.client.get_revenue(s.token());
+    let swept = s
+        .client
+        .sweep_expired_referrals(&vec![&s.env, referrer.clone()], s.token());
+    let events = std::format!("{:?}", s.env.events().all());
+    assert!(events.contains("ref_sweep"));
+    assert_eq!(swept, 10);
+    assert_eq!(s.client.get_revenue(s.token()), revenue + 10);
+    assert_eq!(s.client.get_referral_accrued_at(&referrer, s.token()), None);
+
+    let result = s.client.try_claim_referral_rewards(&referrer, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientBalance)));
+    let swept = s
+        .client
+        .sweep_expired_referrals(&vec![&s.env, referrer.clone()], s.token());
+    assert_eq!(swept, 0);
+}
+
+#[test]
+fn test_claim_inside_the_window_resets_the_clock() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, 10_000, &referrer);
+
+    let last_moment = DEFAULT_TIMESTAMP + DEFAULT_REFERRAL_EXPIRY;
+    set_time(&s, last_moment);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 10);
+    assert_eq!(
+        s.client.get_referral_accrued_at(&referrer, s.token()),
+        Some(last_moment)
+    );
+
+    // A year after the first accrual, but not after the claim.
+    set_time(&s, last_moment + 1);
+    let swept = s
+        .client
+        .sweep_expired_referrals(&vec![&s.env, referrer.clone()], s.token());
+    assert_eq!(swept, 0);
+}
+
+#[test]
+fn test_sweep_skips_referrers_inside_the_window() {
+    let (s, stale) = scenario();
+    let fresh = Address::generate(&s.env);
+    s.client.set_referral_expiry(&300);
+    assert_eq!(s.client.get_referral_expiry(), 300);
+
+    referred_bet(&s, 10_000, &stale);
+    set_time(&s, DEFAULT_TIMESTAMP + 200);
+    referred_bet(&s, 20_000, &fresh);
+    set_time(&s, DEFAULT_TIMESTAMP + 301);
+
+    let swept = s.client.sweep_expired_referrals(
+        &vec![&s.env, stale.clone(), fresh.clone(), stale.clone()],
+        s.token(),
+    );
+    assert_eq!(swept, 10);
+    assert_eq!(s.client.claim_referral_rewards(&fresh, s.token()), 20);
+}
+
+#[test]
+fn test_new_accrual_restarts_an_expired_balance() {
+    let (s, referrer) = scenario();
+    s.client.set_referral_expiry(&300);
+    referred_bet(&s, 10_000, &referrer);
+
+    // Still before the market's betting deadline.
+    set_time(&s, DEFAULT_TIMESTAMP + 301);
+    let result = s.client.try_claim_referral_rewards(&referrer, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::RewardsExpired)));
+
+    referred_bet(&s, 20_000, &referrer);
+    assert_eq!(s.client.claim_referral_rewards(&referrer, s.token()), 30);
+}
+
+#[test]
+fn test_cancelling_a_market_after_its_rewards_were_swept_books_no_debt() {
+    let (s, referrer) = scenario();
+    s.client.set_referral_expiry(&300);
+    referred_bet(&s, 10_000, &referrer);
+
+    set_time(&s, DEFAULT_TIMESTAMP + 301);
+    let swept = s
+        .client
+        .sweep_expired_referrals(&vec![&s.env, referrer.clone()], s.token());
+    assert_eq!(swept, 10);
+    assert_eq!(s.client.get_referral_accrual(&s.market_id(), &referrer), 0);
+
+    // The swept reward is already revenue; the reversal must not count it
+    // a second time by booking it as owed.
+    s.client.cancel_market_admin(&s.market_id());
+    assert_eq!(s.client.get_referral_debt(&referrer, s.token()), 0);
+    assert_eq!(s.client.get_referral_balance(&referrer, s.token()), 0);
+}
+
+#[test]
+fn test_referral_expiry_must_be_positive() {
+    let (s, _) = scenario();
+    let result = s.client.try_set_referral_expiry(&0);
+    assert_eq!(result, Err(Ok(ErrorCode::InvalidAmount)));
+    assert_eq!(s.client.get_referral_expiry(), DEFAULT_REFERRAL_EXPIRY);
+}
+
+#[test]
+fn test_sweep_rejects_an_oversized_batch() {
+    let (s, referrer) = scenario();
+    let mut referrers = Vec::new(&s.env);
+    for _ in 0..=MAX_REFERRAL_SWEEP_BATCH {
+        referrers.push_back(referrer.clone());
+    }
+
+    let result = s.client.try_sweep_expired_referrals(&referrers, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::SweepBatchTooLarge)));
+
+    referrers.pop_back();
+    assert_eq!(s.client.sweep_expired_referrals(&referrers, s.token()), 0);
+}
+
+#[test]
+fn test_first_sweep_starts_the_clock_on_a_legacy_balance() {
+    let (s, referrer) = scenario();
+    referred_bet(&s, 10_000, &referrer);
+    s.env.as_contract(&s.client.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .remove(&DataKey::ReferralAccruedAt(
+                referrer.clone(),
+                s.token().clone(),
+            ));
+    });
+
+    let first_sweep = DEFAULT_TIMESTAMP + 2 * DEFAULT_REFERRAL_EXPIRY;
+    set_time(&s, first_sweep);
+    let referrers = vec![&s.env, referrer.clone()];
+    assert_eq!(s.client.sweep_expired_referrals(&referrers, s.token()), 0);
+    assert_eq!(
+        s.client.get_referral_accrued_at(&referrer, s.token()),
+        Some(first_sweep)
+    );
+
+    set_time(&s, first_sweep + DEFAULT_REFERRAL_EXPIRY + 1);
+    assert_eq!(s.client.sweep_expired_referrals(&referrers, s.token()), 10);
+}
+
+#[test]
+fn test_unclaimed_accruals_drop_settled_markets_when_full() {
+    let (s, referrer) = scenario();
+    let key = DataKey::UnclaimedAccruals(referrer.clone(), s.token().clone());
+    // Entries for markets that no longer exist, as after pruning.
+    s.env.as_contract(&s.client.address, || {
+        let mut unclaimed = Map::<u64, i128>::new(&s.env);
+        for id in 0..u64::from(MAX_UNCLAIMED_ACCRUAL_MARKETS) {
+            unclaimed.set(1_000 + id, 1);
+        }
+        s.env.storage().persistent().set(&key, &unclaimed);
+    });
+
+    referred_bet(&s, 10_000, &referrer);
+
+    s.env.as_contract(&s.client.address, || {
+        let unclaimed: Map<u64, i128> = s.env.storage().persistent().get(&key).unwrap();
+        assert_eq!(unclaimed.len(), 1);
+        assert_eq!(unclaimed.get(s.market_id()), Some(10));
+    });
+}
+
+/// An expired referrer holding the most unclaimed accruals tracked, one unit
+/// from each of that many markets.
+fn fully_tracked_expired_referrer(s: &Scenario) -> Address {
+    let referrer = Address::generate(&s.env);
+    let token = s.token().clone();
+    s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage().persistent();
+        let mut unclaimed = Map::<u64, i128>::new(&s.env);
+        for id in 0..u64::from(MAX_UNCLAIMED_ACCRUAL_MARKETS) {
+            unclaimed.set(1_000 + id, 1);
+            storage.set(&DataKey::ReferralAccrual(1_000 + id, referrer.clone()), &1i128);
+        }
+        storage.set(
+            &DataKey::UnclaimedAccruals(referrer.clone(), token.clone()),
+            &unclaimed,
+        );
+        storage.set(
+            &DataKey::ReferrerBalance(referrer.clone(), token.clone()),
+            &i128::from(MAX_UNCLAIMED_ACCRUAL_MARKETS),
+        );
+        storage.set(
+            &DataKey::ReferralAccruedAt(referrer.clone(), token.clone()),
+            &DEFAULT_TIMESTAMP,
+        );
+    });
+    referrer
+}
+
+#[test]
+fn test_sweep_is_bounded_by_the_accruals_it_drops() {
+    let (s, _) = scenario();
+    let mut referrers = Vec::new(&s.env);
+    for _ in 0..MAX_REFERRAL_SWEEP_BATCH {
+        referrers.push_back(fully_tracked_expired_referrer(&s));
+    }
+    set_time(&s, DEFAULT_TIMESTAMP + DEFAULT_REFERRAL_EXPIRY + 1);
+
+    // A full batch of fully tracked referrers is rejected whole.
+    let result = s.client.try_sweep_expired_referrals(&referrers, s.token());
+    assert_eq!(result, Err(Ok(ErrorCode::SweepBatchTooLarge)));
+    let first = referrers.get(0).unwrap();
+    assert_eq!(
+        s.client.get_referral_balance(&first, s.token()),
+        i128::from(MAX_UNCLAIMED_ACCRUAL_MARKETS)
+    );
+
+    // Taken as many at a time as the budget allows, every one is swept.
+    let per_call = MAX_REFERRAL_SWEEP_ACCRUALS / MAX_UNCLAIMED_ACCRUAL_MARKETS;
+    assert!(per_call >= 1);
+    let mut swept = 0;
+    let mut start = 0;
+    while start < referrers.len() {
+        let end = (start + per_call).min(referrers.len());
+        swept += s
+            .client
+            .sweep_expired_referrals(&referrers.slice(start..end), s.token());
+        start = end;
+    }
+    assert_eq!(
+        swept,
+        i128::from(MAX_REFERRAL_SWEEP_BATCH * MAX_UNCLAIMED_ACCRUAL_MARKETS)
+    );
+    assert_eq!(s.client.get_referral_accrual(&1_000, &first), 0);
+}
@@ -58,7 +58,7 @@ fn create_test_market_with_dispute_window(
     let token_id = e.register_stellar_asset_contract_v2(token_admin.clone());
     let token_address = token_id.address();
 
-    client.create_market_with_dispute_window(
+    client.create_market_with_window(
         &creator,
         &description,
         &options,
@@ -746,7 +746,7 @@ fn test_payouts_blocked_until_resolved() {
     let oracle_config = types::OracleConfig {
         oracle_address: Address::generate(&e),
         feed_id: String::from_str(&e, "test"),
-        min_responses: 1,
+        min_responses: Some(1),
         max_staleness_seconds: 3600,
         max_confidence_bps: 200,
         strike_price: None,
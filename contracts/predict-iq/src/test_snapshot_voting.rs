@@ -0,0 +1,193 @@
+//! Vote weighting through a snapshot-capable governance token.
+//!
+//! With a [`MockSnapshotToken`](crate::mock_snapshot_token::MockSnapshotToken)
+//! as the governance token, `cast_vote` weighs votes by the balance at the
+//! dispute's snapshot ledger and escrows nothing. A plain Stellar asset
+//! contract, or a snapshot token whose `balance_at` traps, takes the locking
+//! fallback instead. The vote record says which path was used; votes stored
+//! before it did are read back as `Locked` while their tokens are locked.
+
+#![cfg(test)]
+
+use soroban_sdk::{testutils::Address as _, testutils::Ledger as _, token, Address};
+
+use crate::{
+    errors::ErrorCode,
+    mock_snapshot_token::{MockSnapshotToken, MockSnapshotTokenClient},
+    modules::{markets, voting},
+    testutils::{register_token, Scenario, ScenarioBuilder, DEFAULT_DEADLINES},
+    types::{MarketStatus, Vote, VoteV1, VoteWeightSource},
+};
+
+const SNAPSHOT_LEDGER: u32 = 10;
+const VOTE_LEDGER: u32 = 20;
+/// Governance tokens have 7 decimals; weights are normalized to 18.
+const WEIGHT_SCALE: i128 = 100_000_000_000;
+
+fn disputed_scenario() -> Scenario {
+    let s = ScenarioBuilder::new().with_market(2, DEFAULT_DEADLINES).build();
+    s.env.ledger().set_sequence_number(SNAPSHOT_LEDGER);
+    s.env.as_contract(&s.client.address, || {
+        let mut market = markets::get_market(&s.env, s.market_id()).unwrap();
+        market.status = MarketStatus::Disputed;
+        market.pending_resolution_timestamp = Some(1_000);
+        market.dispute_timestamp = Some(1_001);
+        market.dispute_snapshot_ledger = Some(SNAPSHOT_LEDGER);
+        markets::update_market(&s.env, market);
+    });
+    s
+}
+
+fn snapshot_token(s: &Scenario) -> MockSnapshotTokenClient<'_> {
+    let address = s.env.register(MockSnapshotToken, ());
+    s.client.set_governance_token(&address);
+    MockSnapshotTokenClient::new(&s.env, &address)
+}
+
+#[test]
+fn test_snapshot_path_uses_balance_before_later_mint() {
+    let s = disputed_scenario();
+    let gov = snapshot_token(&s);
+    let voter = Address::generate(&s.env);
+    gov.mint(&voter, &100);
+
+    s.env.ledger().set_sequence_number(VOTE_LEDGER);
+    gov.mint(&voter, &900);
+    s.client.cast_vote(&voter, &s.market_id(), &0, &1_000);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Snapshot);
+    assert_eq!(vote.weight, 100 * WEIGHT_SCALE);
+    // Nothing was escrowed.
+    assert_eq!(gov.balance(&voter), 1_000);
+    assert_eq!(gov.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_snapshot_path_ignores_tokens_acquired_after_dispute() {
+    let s = disputed_scenario();
+    let gov = snapshot_token(&s);
+    let voter = Address::generate(&s.env);
+
+    s.env.ledger().set_sequence_number(VOTE_LEDGER);
+    gov.mint(&voter, &1_000_000);
+    let result = s.client.try_cast_vote(&voter, &s.market_id(), &0, &1_000_000);
+
+    assert_eq!(result, Err(Ok(ErrorCode::InsufficientVotingWeight)));
+}
+
+#[test]
+fn test_stellar_asset_falls_back_to_locking() {
+    let s = disputed_scenario();
+    let gov = register_token(&s.env);
+    s.client.set_governance_token(&gov);
+    let voter = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &gov).mint(&voter, &500);
+
+    s.client.cast_vote(&voter, &s.market_id(), &1, &500);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Locked);
+    assert_eq!(vote.weight, 500 * WEIGHT_SCALE);
+    let balances = token::Client::new(&s.env, &gov);
+    assert_eq!(balances.balance(&voter), 0);
+    assert_eq!(balances.balance(&s.client.address), 500);
+}
+
+#[test]
+fn test_trapping_snapshot_call_falls_back_cleanly() {
+    let s = disputed_scenario();
+    let gov = snapshot_token(&s);
+    gov.set_trap(&true);
+    let voter = Address::generate(&s.env);
+    gov.mint(&voter, &300);
+
+    s.client.cast_vote(&voter, &s.market_id(), &0, &300);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Locked);
+    assert_eq!(vote.weight, 300 * WEIGHT_SCALE);
+    assert_eq!(gov.balance(&voter), 0);
+    assert_eq!(gov.balance(&s.client.address), 300);
+}
+
+#[test]
+fn test_filing_a_dispute_snapshots_the_current_ledger() {
+    let s = ScenarioBuilder::new().with_market(2, DEFAULT_DEADLINES).build();
+    let gov = snapshot_token(&s);
+    let voter = Address::generate(&s.env);
+    gov.mint(&voter, &100);
+
+    s.env.ledger().set_timestamp(DEFAULT_DEADLINES.1);
+    s.env.ledger().set_sequence_number(SNAPSHOT_LEDGER);
+    s.client.set_oracle_result(&s.market_id(), &0, &0);
+    s.client.attempt_oracle_resolution(&s.market_id());
+    s.client.file_dispute(&voter, &s.market_id());
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.dispute_snapshot_ledger, Some(SNAPSHOT_LEDGER));
+
+    s.env.ledger().set_sequence_number(VOTE_LEDGER);
+    gov.mint(&voter, &900);
+    s.client.cast_vote(&voter, &s.market_id(), &1, &100);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Snapshot);
+    assert_eq!(vote.weight, 100 * WEIGHT_SCALE);
+}
+
+/// Rewrite `voter`'s vote in the layout stored before `weight_source`.
+fn store_as_legacy(s: &Scenario, voter: &Address) {
+    s.env.as_contract(&s.client.address, || {
+        let storage = s.env.storage().persistent();
+        let key = voting::DataKey::Vote(s.market_id(), voter.clone());
+        let vote: Vote = storage.get(&key).unwrap();
+        let legacy = VoteV1 {
+            market_id: vote.market_id,
+            voter: vote.voter,
+            outcome: vote.outcome,
+            weight: vote.weight,
+        };
+        storage.set(&key, &legacy);
+    });
+}
+
+#[test]
+fn test_legacy_vote_with_locked_tokens_reads_as_locked() {
+    let s = disputed_scenario();
+    let gov = register_token(&s.env);
+    s.client.set_governance_token(&gov);
+    let voter = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &gov).mint(&voter, &500);
+    s.client.cast_vote(&voter, &s.market_id(), &1, &200);
+    store_as_legacy(&s, &voter);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Locked);
+    assert_eq!(vote.weight, 200 * WEIGHT_SCALE);
+
+    // Revising it reads the old weight back out of the tally.
+    s.client.cast_vote(&voter, &s.market_id(), &0, &300);
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!((vote.outcome, vote.weight), (0, 300 * WEIGHT_SCALE));
+    s.env.as_contract(&s.client.address, || {
+        assert_eq!(voting::get_tally(&s.env, s.market_id(), 1), 0);
+        assert_eq!(
+            voting::get_tally(&s.env, s.market_id(), 0),
+            300 * WEIGHT_SCALE
+        );
+    });
+}
+
+#[test]
+fn test_legacy_vote_without_locked_tokens_reads_as_snapshot() {
+    let s = disputed_scenario();
+    let gov = snapshot_token(&s);
+    let voter = Address::generate(&s.env);
+    gov.mint(&voter, &100);
+    s.client.cast_vote(&voter, &s.market_id(), &0, &100);
+    store_as_legacy(&s, &voter);
+
+    let vote = s.client.get_vote(&s.market_id(), &voter).unwrap();
+    assert_eq!(vote.weight_source, VoteWeightSource::Snapshot);
+    assert_eq!(vote.weight, 100 * WEIGHT_SCALE);
+}
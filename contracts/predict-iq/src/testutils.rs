@@ -0,0 +1,362 @@
+//! Scenario builder for contract tests.
+//!
+//! Market setup lives here so a change to `initialize` or `create_market`
+//! touches one place instead of every test module:
+//!
+//! ```ignore
+//! let s = ScenarioBuilder::new()
+//!     .with_market(2, DEFAULT_DEADLINES)
+//!     .with_bettors(3, 10_000)
+//!     .build();
+//! s.client.place_bet(&s.bettor(0), &s.market_id(), &0, &1_000, s.token(), &None);
+//! ```
+//!
+//! Available to unit tests, and to integration tests and downstream crates
+//! through the `testutils` feature.
+extern crate std;
+
+use crate::mock_oracle::{MockOracle, MockOracleClient};
+#[cfg(feature = "governance")]
+use crate::types::Guardian;
+use crate::types::{CreatorReputation, MarketTier, OracleConfig};
+use crate::{PredictIQ, PredictIQClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token, Address, Env, String, Vec,
+};
+
+/// Base fee `initialize` is called with unless overridden: 1%.
+pub const DEFAULT_BASE_FEE_BPS: i128 = 100;
+
+/// Ledger timestamp scenarios start at, before any default deadline.
+pub const DEFAULT_TIMESTAMP: u64 = 500;
+
+/// Betting and resolution deadlines that clear `create_market`'s 24h
+/// minimum gap between the two.
+pub const DEFAULT_DEADLINES: (u64, u64) = (1_000, 1_000 + 86_400);
+
+/// Oracle config pointing at `oracle_address` with test defaults.
+pub fn oracle_config(oracle_address: &Address, feed_id: &str) -> OracleConfig {
+    let env = oracle_address.env();
+    OracleConfig {
+        oracle_address: oracle_address.clone(),
+        feed_id: String::from_str(env, feed_id),
+        min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
+    }
+}
+
+/// Create a market on `client` with `outcomes` generated options and an
+/// oracle address nothing is deployed at.
+pub fn create_market(
+    client: &PredictIQClient,
+    creator: &Address,
+    token: &Address,
+    outcomes: u32,
+    deadlines: (u64, u64),
+    tier: &MarketTier,
+) -> u64 {
+    let oracle = oracle_config(&Address::generate(&client.env), "test_feed");
+    create_market_with_oracle(client, creator, token, outcomes, deadlines, tier, &oracle)
+}
+
+/// [`create_market`] with an explicit oracle. The one place tests call the
+/// contract's `create_market`.
+pub fn create_market_with_oracle(
+    client: &PredictIQClient,
+    creator: &Address,
+    token: &Address,
+    outcomes: u32,
+    (deadline, resolution_deadline): (u64, u64),
+    tier: &MarketTier,
+    oracle_config: &OracleConfig,
+) -> u64 {
+    let env = &client.env;
+    let mut options = Vec::new(env);
+    for i in 0..outcomes {
+        options.push_back(String::from_str(env, &std::format!("Outcome {i}")));
+    }
+    client.create_market(
+        creator,
+        &String::from_str(env, "Test Market"),
+        &options,
+        &deadline,
+        &resolution_deadline,
+        oracle_config,
+        tier,
+        token,
+        &0,
+        &0,
+    )
+}
+
+/// Register a fresh Stellar asset contract and return its address.
+pub fn register_token(env: &Env) -> Address {
+    env.register_stellar_asset_contract_v2(Address::generate(env))
+        .address()
+}
+
+#[cfg(feature = "governance")]
+fn register_guardians(client: &PredictIQClient, count: u32) -> Vec<Address> {
+    let mut guardians = Vec::new(&client.env);
+    if count > 0 {
+        let mut set = Vec::new(&client.env);
+        for _ in 0..count {
+            let address = Address::generate(&client.env);
+            set.push_back(Guardian {
+                address: address.clone(),
+                voting_power: 1,
+            });
+            guardians.push_back(address);
+        }
+        client.initialize_guardians(&set);
+    }
+    guardians
+}
+
+#[cfg(not(feature = "governance"))]
+fn register_guardians(client: &PredictIQClient, count: u32) -> Vec<Address> {
+    assert!(count == 0, "with_guardians needs the governance feature");
+    Vec::new(&client.env)
+}
+
+/// Fluent setup for a [`Scenario`]. Every step is optional; steps that need
+/// a token or a market add one.
+#[derive(Clone, Debug)]
+pub struct ScenarioBuilder {
+    base_fee: i128,
+    timestamp: u64,
+    token: bool,
+    market: Option<(u32, (u64, u64))>,
+    tier: MarketTier,
+    bettors: u32,
+    balance: i128,
+    guardians: u32,
+    mock_oracle: Option<&'static str>,
+    resolved: Option<u32>,
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScenarioBuilder {
+    pub fn new() -> Self {
+        Self {
+            base_fee: DEFAULT_BASE_FEE_BPS,
+            timestamp: DEFAULT_TIMESTAMP,
+            token: false,
+            market: None,
+            tier: MarketTier::Basic,
+            bettors: 0,
+            balance: 0,
+            guardians: 0,
+            mock_oracle: None,
+            resolved: None,
+        }
+    }
+
+    /// Base fee in basis points passed to `initialize`.
+    pub fn with_base_fee(mut self, base_fee: i128) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+
+    /// Ledger timestamp set before anything is created.
+    pub fn at(mut self, timestamp: u64) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    /// Register a Stellar asset token.
+    pub fn with_token(mut self) -> Self {
+        self.token = true;
+        self
+    }
+
+    /// Create a market with `outcomes` options and `(deadline,
+    /// resolution_deadline)`, in the scenario's token.
+    pub fn with_market(mut self, outcomes: u32, deadlines: (u64, u64)) -> Self {
+        self.token = true;
+        self.market = Some((outcomes, deadlines));
+        self
+    }
+
+    /// Market tier for [`Self::with_market`]; Basic by default.
+    pub fn with_tier(mut self, tier: MarketTier) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    /// Generate `count` bettors, each minted `balance` of the token.
+    pub fn with_bettors(mut self, count: u32, balance: i128) -> Self {
+        self.token = true;
+        self.bettors = count;
+        self.balance = balance;
+        self
+    }
+
+    /// Register `count` guardians with a voting power of one each. Needs
+    /// the `governance` feature.
+    pub fn with_guardians(mut self, count: u32) -> Self {
+        self.guardians = count;
+        self
+    }
+
+    /// Register a [`MockOracle`] and point the market at it on `feed_id`.
+    /// Nothing is published until the test calls `set_report`.
+    pub fn with_mock_oracle(mut self, feed_id: &'static str) -> Self {
+        self.mock_oracle = Some(feed_id);
+        self
+    }
+
+    /// Resolve the market to `outcome` once everything else is set up. Adds
+    /// a default two-outcome market if none was requested.
+    pub fn resolved(mut self, outcome: u32) -> Self {
+        if self.market.is_none() {
+            self = self.with_market(2, DEFAULT_DEADLINES);
+        }
+        self.resolved = Some(outcome);
+        self
+    }
+
+    pub fn build(self) -> Scenario {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(self.timestamp);
+
+        let contract_id = env.register(PredictIQ, ());
+        let client = PredictIQClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.initialize(&admin, &self.base_fee);
+
+        let guardians = register_guardians(&client, self.guardians);
+
+        let token = self.token.then(|| register_token(&env));
+
+        let mut bettors = Vec::new(&env);
+        if let Some(token) = &token {
+            let minter = token::StellarAssetClient::new(&env, token);
+            for _ in 0..self.bettors {
+                let bettor = Address::generate(&env);
+                minter.mint(&bettor, &self.balance);
+                bettors.push_back(bettor);
+            }
+        }
+
+        let oracle = self.mock_oracle.map(|feed_id| {
+            let address = env.register(MockOracle, ());
+            (address, feed_id)
+        });
+        let market_oracle = match &oracle {
+            Some((address, feed_id)) => oracle_config(address, feed_id),
+            None => oracle_config(&Address::generate(&env), "test_feed"),
+        };
+
+        let creator = Address::generate(&env);
+        // Pro and Institutional markets need a creator of matching reputation.
+        match self.tier {
+            MarketTier::Basic => {}
+            MarketTier::Pro => client.set_creator_reputation(&creator, &CreatorReputation::Pro),
+            MarketTier::Institutional => {
+                client.set_creator_reputation(&creator, &CreatorReputation::Institutional)
+            }
+        }
+        let market_id = match (self.market, &token) {
+            (Some((outcomes, deadlines)), Some(token)) => Some(create_market_with_oracle(
+                &client,
+                &creator,
+                token,
+                outcomes,
+                deadlines,
+                &self.tier,
+                &market_oracle,
+            )),
+            _ => None,
+        };
+
+        if let (Some(outcome), Some(market_id)) = (self.resolved, market_id) {
+            client.resolve_market(&market_id, &outcome);
+        }
+
+        Scenario {
+            env,
+            client,
+            admin,
+            creator,
+            token,
+            market_id,
+            oracle: oracle.map(|(address, _)| address),
+            bettors,
+            guardians,
+        }
+    }
+}
+
+/// A built scenario. Fields are public so tests can destructure it.
+pub struct Scenario {
+    pub env: Env,
+    pub client: PredictIQClient<'static>,
+    pub admin: Address,
+    /// Creator of the scenario's market.
+    pub creator: Address,
+    pub token: Option<Address>,
+    pub market_id: Option<u64>,
+    /// Address of the [`MockOracle`], if one was requested.
+    pub oracle: Option<Address>,
+    pub bettors: Vec<Address>,
+    pub guardians: Vec<Address>,
+}
+
+impl Scenario {
+    /// The scenario's token. Panics if none was requested.
+    pub fn token(&self) -> &Address {
+        self.token
+            .as_ref()
+            .expect("scenario has no token; use with_token")
+    }
+
+    /// The scenario's market. Panics if none was requested.
+    pub fn market_id(&self) -> u64 {
+        self.market_id
+            .expect("scenario has no market; use with_market")
+    }
+
+    /// Client for the scenario's mock oracle. Panics if none was requested.
+    pub fn oracle(&self) -> MockOracleClient<'_> {
+        let address = self
+            .oracle
+            .as_ref()
+            .expect("scenario has no oracle; use with_mock_oracle");
+        MockOracleClient::new(&self.env, address)
+    }
+
+    pub fn bettor(&self, index: u32) -> Address {
+        self.bettors.get(index).expect("no bettor at that index")
+    }
+
+    /// Mint `amount` of the scenario's token to `to`.
+    pub fn mint(&self, to: &Address, amount: i128) {
+        token::StellarAssetClient::new(&self.env, self.token()).mint(to, &amount);
+    }
+
+    pub fn balance(&self, of: &Address) -> i128 {
+        token::Client::new(&self.env, self.token()).balance(of)
+    }
+
+    /// Create another market in the scenario's token.
+    pub fn create_market(&self, outcomes: u32, deadlines: (u64, u64), tier: &MarketTier) -> u64 {
+        create_market(
+            &self.client,
+            &self.creator,
+            self.token(),
+            outcomes,
+            deadlines,
+            tier,
+        )
+    }
+}
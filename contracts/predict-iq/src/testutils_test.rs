@@ -0,0 +1,140 @@
+#![cfg(test)]
+use crate::testutils::{
+    ScenarioBuilder, DEFAULT_BASE_FEE_BPS, DEFAULT_DEADLINES, DEFAULT_TIMESTAMP,
+};
+use crate::types::{MarketStatus, MarketTier};
+use soroban_sdk::testutils::Ledger as _;
+
+#[test]
+fn test_bare_scenario_is_initialized_only() {
+    let s = ScenarioBuilder::new().build();
+
+    assert_eq!(s.client.get_admin(), Some(s.admin.clone()));
+    assert_eq!(s.client.get_base_fee(), DEFAULT_BASE_FEE_BPS);
+    assert_eq!(s.env.ledger().timestamp(), DEFAULT_TIMESTAMP);
+    assert!(s.token.is_none());
+    assert!(s.market_id.is_none());
+    assert!(s.bettors.is_empty());
+    assert!(s.guardians.is_empty());
+}
+
+#[test]
+fn test_bettors_imply_a_funded_token() {
+    let s = ScenarioBuilder::new().with_bettors(3, 5_000).build();
+
+    assert_eq!(s.bettors.len(), 3);
+    for bettor in s.bettors.iter() {
+        assert_eq!(s.balance(&bettor), 5_000);
+    }
+    assert!(s.market_id.is_none());
+}
+
+#[test]
+fn test_market_uses_requested_shape() {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(0)
+        .with_tier(MarketTier::Pro)
+        .with_market(4, DEFAULT_DEADLINES)
+        .build();
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.options.len(), 4);
+    assert_eq!(market.deadline, DEFAULT_DEADLINES.0);
+    assert_eq!(market.resolution_deadline, DEFAULT_DEADLINES.1);
+    assert_eq!(market.tier, MarketTier::Pro);
+    assert_eq!(market.token_address, *s.token());
+    assert_eq!(market.status, MarketStatus::Active);
+    assert_eq!(s.client.get_base_fee(), 0);
+}
+
+#[cfg(feature = "governance")]
+#[test]
+fn test_guardians_are_registered() {
+    let s = ScenarioBuilder::new().with_guardians(3).build();
+
+    let registered = s.client.get_guardians();
+    assert_eq!(registered.len(), 3);
+    for (guardian, address) in registered.iter().zip(s.guardians.iter()) {
+        assert_eq!(guardian.address, address);
+        assert_eq!(guardian.voting_power, 1);
+    }
+}
+
+#[cfg(feature = "governance")]
+#[test]
+fn test_steps_compose_into_a_claimable_market() {
+    let s = ScenarioBuilder::new()
+        .with_base_fee(0)
+        .with_guardians(1)
+        .with_bettors(2, 1_000)
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+    let (winner, loser) = (s.bettor(0), s.bettor(1));
+    s.client
+        .place_bet(&winner, &s.market_id(), &0, &1_000, s.token(), &None);
+    s.client
+        .place_bet(&loser, &s.market_id(), &1, &1_000, s.token(), &None);
+    s.client.resolve_market(&s.market_id(), &0);
+
+    let payout = s.client.claim_winnings(&winner, &s.market_id(), s.token());
+    assert_eq!(payout, 2_000);
+    assert_eq!(s.balance(&winner), 2_000);
+}
+
+#[test]
+fn test_resolved_adds_a_market_when_none_requested() {
+    let s = ScenarioBuilder::new().resolved(1).build();
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(market.status, MarketStatus::Resolved);
+    assert_eq!(market.winning_outcome, Some(1));
+}
+
+#[test]
+fn test_order_of_steps_does_not_matter() {
+    let a = ScenarioBuilder::new()
+        .resolved(0)
+        .with_bettors(2, 100)
+        .with_market(3, DEFAULT_DEADLINES)
+        .build();
+    let b = ScenarioBuilder::new()
+        .with_market(3, DEFAULT_DEADLINES)
+        .with_bettors(2, 100)
+        .resolved(0)
+        .build();
+
+    for s in [&a, &b] {
+        let market = s.client.get_market(&s.market_id()).unwrap();
+        assert_eq!(market.options.len(), 3);
+        assert_eq!(market.status, MarketStatus::Resolved);
+        assert_eq!(s.bettors.len(), 2);
+    }
+}
+
+#[test]
+fn test_scenario_creates_further_markets_in_its_token() {
+    let s = ScenarioBuilder::new()
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+
+    let second = s.create_market(5, (3_000, 3_000 + 86_400), &MarketTier::Basic);
+    assert_ne!(second, s.market_id());
+    let market = s.client.get_market(&second).unwrap();
+    assert_eq!(market.options.len(), 5);
+    assert_eq!(market.token_address, *s.token());
+}
+
+#[test]
+fn test_mock_oracle_backs_the_market() {
+    let s = ScenarioBuilder::new()
+        .with_mock_oracle("feed")
+        .with_market(2, DEFAULT_DEADLINES)
+        .build();
+
+    let market = s.client.get_market(&s.market_id()).unwrap();
+    assert_eq!(Some(market.oracle_config.oracle_address), s.oracle);
+    assert_eq!(
+        market.oracle_config.feed_id,
+        soroban_sdk::String::from_str(&s.env, "feed")
+    );
+}
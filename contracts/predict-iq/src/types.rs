@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
+use soroban_sdk::{contracttype, Address, Map, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -8,6 +8,9 @@ pub enum MarketStatus {
     Disputed,
     Resolved,
     Cancelled,
+    /// Waiting for the creator to seed a minimum stake; see
+    /// `FundingRequirement`. Only the creator can bet until it turns Active.
+    Funding,
 }
 
 #[contracttype]
@@ -36,6 +39,72 @@ pub struct Market {
     pub dispute_timestamp: Option<u64>, // Timestamp when dispute was filed
     pub winner_counts: Map<u32, u32>,   // Unique bettor count per outcome
     pub total_claimed: i128,            // Total amount claimed by winners
+    /// Base fee in bps captured at creation. Bets on this market are charged
+    /// this rather than the current global fee; it can only be lowered.
+    pub fee_bps: i128,
+}
+
+/// [`Market`] as stored before `fee_bps` was added. `markets::get_market`
+/// still reads entries in this layout and upgrades them; `update_market`
+/// writes them back as [`Market`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketV1 {
+    pub id: u64,
+    pub creator: Address,
+    pub description: String,
+    pub options: Vec<String>,
+    pub status: MarketStatus,
+    pub deadline: u64,
+    pub resolution_deadline: u64,
+    pub winning_outcome: Option<u32>,
+    pub oracle_config: OracleConfig,
+    pub total_staked: i128,
+    pub payout_mode: PayoutMode,
+    pub tier: MarketTier,
+    pub creation_deposit: i128,
+    pub parent_id: u64,
+    pub parent_outcome_idx: u32,
+    pub resolved_at: Option<u64>,
+    pub token_address: Address,
+    pub outcome_stakes: Map<u32, i128>,
+    pub pending_resolution_timestamp: Option<u64>,
+    pub dispute_snapshot_ledger: Option<u32>,
+    pub dispute_timestamp: Option<u64>,
+    pub winner_counts: Map<u32, u32>,
+    pub total_claimed: i128,
+}
+
+impl MarketV1 {
+    /// The current layout, charging `fee_bps` from now on.
+    pub fn upgrade(self, fee_bps: i128) -> Market {
+        Market {
+            id: self.id,
+            creator: self.creator,
+            description: self.description,
+            options: self.options,
+            status: self.status,
+            deadline: self.deadline,
+            resolution_deadline: self.resolution_deadline,
+            winning_outcome: self.winning_outcome,
+            oracle_config: self.oracle_config,
+            total_staked: self.total_staked,
+            payout_mode: self.payout_mode,
+            tier: self.tier,
+            creation_deposit: self.creation_deposit,
+            parent_id: self.parent_id,
+            parent_outcome_idx: self.parent_outcome_idx,
+            resolved_at: self.resolved_at,
+            token_address: self.token_address,
+            outcome_stakes: self.outcome_stakes,
+            pending_resolution_timestamp: self.pending_resolution_timestamp,
+            dispute_snapshot_ledger: self.dispute_snapshot_ledger,
+            dispute_timestamp: self.dispute_timestamp,
+            winner_counts: self.winner_counts,
+            total_claimed: self.total_claimed,
+            fee_bps,
+        }
+    }
 }
 
 #[contracttype]
@@ -53,6 +122,19 @@ pub enum MarketTier {
     Institutional,
 }
 
+/// Per-tier caps enforced when a market is created. Defaults come from
+/// `markets::default_tier_limits`; the admin can change them within the
+/// hard ceilings below.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierLimits {
+    pub max_outcomes: u32,
+    /// Maximum description length in bytes.
+    pub max_description_len: u32,
+    /// Maximum time from creation to `resolution_deadline`.
+    pub max_duration_seconds: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CreatorReputation {
@@ -62,6 +144,33 @@ pub enum CreatorReputation {
     Institutional,
 }
 
+/// Liquidity a market created in `MarketStatus::Funding` must be seeded with,
+/// in net stake, before `funding_deadline` to open for public bets.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingRequirement {
+    pub min_liquidity: i128,
+    pub funding_deadline: u64,
+}
+
+/// A token a market takes bets in besides its own `token_address`. One unit
+/// of `token` is staked as `rate / RATE_SCALE` units of the market's token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedToken {
+    pub token: Address,
+    pub rate: i128,
+}
+
+/// Bets placed in one accepted token: `amount` in that token's units, net of
+/// fees, and the `stake` it was credited as in the market's token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenDeposit {
+    pub amount: i128,
+    pub stake: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Bet {
@@ -69,6 +178,8 @@ pub struct Bet {
     pub bettor: Address,
     pub outcome: u32,
     pub amount: i128,
+    /// Fees paid in the market's token. Fees on accepted-token bets are
+    /// kept as revenue in that token and not tracked per bet.
     pub fee_paid: i128,
 }
 
@@ -79,6 +190,71 @@ pub struct Vote {
     pub voter: Address,
     pub outcome: u32,
     pub weight: i128,
+    pub weight_source: VoteWeightSource,
+}
+
+/// [`Vote`] as stored before `weight_source` was added. `voting::get_vote`
+/// still reads entries in this layout and upgrades them; revising the vote
+/// writes it back as [`Vote`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VoteV1 {
+    pub market_id: u64,
+    pub voter: Address,
+    pub outcome: u32,
+    pub weight: i128,
+}
+
+impl VoteV1 {
+    /// The current layout, recording how the weight was established.
+    pub fn upgrade(self, weight_source: VoteWeightSource) -> Vote {
+        Vote {
+            market_id: self.market_id,
+            voter: self.voter,
+            outcome: self.outcome,
+            weight: self.weight,
+            weight_source,
+        }
+    }
+}
+
+/// How a vote's weight was established.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VoteWeightSource {
+    /// The governance token reported the voter's balance at the dispute's
+    /// snapshot ledger; nothing is escrowed.
+    Snapshot,
+    /// The token has no `balance_at`, so the claimed weight was transferred
+    /// into the contract until the market resolves.
+    Locked,
+}
+
+/// Who settled a disputed market.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResolutionSource {
+    /// The vote reached the majority threshold at `finalize_resolution`.
+    Vote,
+    /// The admin resolved the market while it was disputed.
+    Admin,
+}
+
+/// How a dispute vote broke down, recorded when the market is resolved.
+/// Kept under its own key so it outlives `prune_market`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeSummary {
+    pub market_id: u64,
+    /// Normalized vote weight per outcome, indexed by outcome.
+    pub outcome_totals: Vec<i128>,
+    pub voter_count: u32,
+    /// Whether the leading outcome reached the majority threshold.
+    pub quorum_reached: bool,
+    pub winning_outcome: u32,
+    pub source: ResolutionSource,
+    pub snapshot_ledger: Option<u32>,
+    pub resolved_at: u64,
 }
 
 #[contracttype]
@@ -104,6 +280,24 @@ pub struct OracleConfig {
 // Gas optimization constants
 pub const MAX_PUSH_PAYOUT_WINNERS: u32 = 50; // Threshold for switching to pull mode
 pub const MAX_OUTCOMES_PER_MARKET: u32 = 100; // Limit to prevent excessive iteration
+pub const CANCEL_OUTCOME_INDEX: u32 = u32::MAX; // Dispute vote for cancelling the market
+pub const MAX_DESCRIPTION_LEN: u32 = 8_000; // Ceiling for any tier's description limit
+pub const MAX_MARKET_DURATION_SECONDS: u64 = 365 * 86_400; // Ceiling for any tier's duration limit
+pub const MAX_CATEGORY_LEN: u32 = 64; // Matches the API's category slug column
+pub const MAX_TAGS_PER_MARKET: u32 = 8;
+pub const MAX_TAG_LEN: u32 = 32;
+pub const MAX_ACCEPTED_TOKENS: u32 = 4; // Bounds the per-token work in claims
+pub const RATE_SCALE: i128 = 10_000_000; // Denominator of AcceptedToken::rate
+
+/// Category and tags a market was created with, for off-chain grouping.
+/// Only stored when at least one is set; markets created without them read
+/// back as empty labels.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketLabels {
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -132,6 +326,11 @@ pub enum ConfigKey {
     MaxDisputeWindow,
     CircuitBreakerThreshold,
     PendingAdmin,
+    TierLimits(MarketTier),
+    ReferralExpiry,
+    GuardianActionSlot(u32),
+    GuardianActionCount,
+    MinimumBetAmount,
 }
 
 #[contracttype]
@@ -167,6 +366,29 @@ pub const TIMELOCK_MAX_SECONDS: u64 = 7 * 24 * 3600; // 7 days maximum
 pub const MAJORITY_THRESHOLD_PERCENT: u32 = 51; // 51% for majority
 pub const UPGRADE_COOLDOWN_DURATION: u64 = 7 * 24 * 3600; // 7 days cooldown for rejected upgrades
 
+/// How long a referral balance stays claimable after its last accrual or
+/// claim, unless the admin sets another expiry.
+pub const DEFAULT_REFERRAL_EXPIRY: u64 = 365 * 24 * 3600;
+
+/// Most referrers whose rewards one cancellation, or one
+/// `reverse_market_referrals` call, takes back. Each costs about five
+/// persistent entries, so a whole market's referrers may not fit one
+/// transaction.
+pub const MAX_REFERRAL_REVERSAL_BATCH: u32 = 10;
+
+/// Most referrers one `sweep_expired_referrals` call takes.
+pub const MAX_REFERRAL_SWEEP_BATCH: u32 = 10;
+
+/// Most markets a referrer's unclaimed accruals in one token are tracked
+/// for; see `fees::DataKey::UnclaimedAccruals`.
+pub const MAX_UNCLAIMED_ACCRUAL_MARKETS: u32 = 20;
+
+/// Most per-market accruals one `sweep_expired_referrals` call drops, summed
+/// over the referrers it sweeps. Each costs the accrual entry and the
+/// market's referrer list. At least `MAX_UNCLAIMED_ACCRUAL_MARKETS`, so any
+/// single referrer can be swept.
+pub const MAX_REFERRAL_SWEEP_ACCRUALS: u32 = 20;
+
 // Governance stats type for vote counting
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -183,6 +405,39 @@ pub struct PendingGuardianRemoval {
     pub votes_for: Vec<Address>,
 }
 
+/// A guardian or governance action, with its target.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum GuardianAction {
+    GuardiansInitialized(u32),
+    GuardianAdded(Address),
+    GuardianRemovalProposed(Address),
+    GuardianRemovalVoted(Address, bool),
+    GuardianRemoved(Address),
+    UpgradeInitiated(soroban_sdk::BytesN<32>),
+    UpgradeVoted(soroban_sdk::BytesN<32>, bool),
+    UpgradeRejected(soroban_sdk::BytesN<32>),
+    UpgradeExecuted(soroban_sdk::BytesN<32>),
+    TimelockDurationSet(u64),
+    EmergencyPause,
+    Paused,
+    Unpaused,
+}
+
+/// One entry of the guardian action log. `seq` counts every action ever
+/// logged, so gaps show where the ring buffer has rotated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianActionRecord {
+    pub seq: u64,
+    pub actor: Address,
+    pub action: GuardianAction,
+    pub timestamp: u64,
+}
+
+/// How many guardian actions the on-chain log keeps.
+pub const GUARDIAN_LOG_CAPACITY: u32 = 100;
+
 // TTL Management Constants (in ledgers, ~5 seconds per ledger)
 pub const TTL_LOW_THRESHOLD: u32 = 17_280; // ~1 day (86400 seconds / 5)
 pub const TTL_HIGH_THRESHOLD: u32 = 518_400; // ~30 days (2592000 seconds / 5)
@@ -1,7 +1,12 @@
 // Common test utilities and helpers
 
 use predict_iq::{PredictIQ, PredictIQClient};
-use soroban_sdk::{token, Address, Env, String, Vec};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{token, Address, BytesN, Env, String, Vec};
+
+/// Scenario builder shared with the unit tests; prefer it for new tests.
+#[cfg(feature = "testutils")]
+pub use predict_iq::testutils::{Scenario, ScenarioBuilder};
 
 /// Setup test environment with initialized contract
 pub fn setup() -> (Env, PredictIQClient<'static>, Address) {
@@ -54,7 +59,7 @@ pub fn create_market(
         &String::from_str(env, "Test Market"),
         &options,
         &(env.ledger().timestamp() + 1000),
-        &(env.ledger().timestamp() + 2000),
+        &(env.ledger().timestamp() + 1000 + 86_400),
         &oracle_config,
         &predict_iq::types::MarketTier::Basic,
         token,
@@ -166,6 +171,7 @@ pub fn create_users(env: &Env, token: &Address, count: u32, balance: i128) -> Ve
 }
 
 /// Setup guardians for governance
+#[cfg(feature = "governance")]
 pub fn setup_guardians(
     client: &PredictIQClient,
     env: &Env,
@@ -202,3 +208,19 @@ pub fn resolve_and_verify(client: &PredictIQClient, market_id: u64, winning_outc
     client.resolve_market(&market_id, &winning_outcome);
     assert_market_status(client, market_id, predict_iq::types::MarketStatus::Resolved);
 }
+
+/// Upload the smallest Wasm the host accepts as an upgrade target: an empty
+/// module carrying only the environment-meta custom section.
+pub fn upload_upgrade_wasm(env: &Env) -> BytesN<32> {
+    const WASM: [u8; 40] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version
+        0x00, 0x1e, 0x11, // custom section: size, name length
+        b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a',
+        b'v', b'0', // "contractenvmetav0"
+        0x00, 0x00, 0x00, 0x00, // ScEnvMetaEntry::InterfaceVersion
+        0x00, 0x00, 0x00, 0x1b, // protocol 27
+        0x00, 0x00, 0x00, 0x00, // pre-release 0
+    ];
+    env.deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_slice(env, &WASM))
+}
@@ -99,6 +99,7 @@ fn test_full_flow_with_referral_rewards() {
 
     // Setup users
     let bettor = setup_user_with_balance(&env, &token, 50_000);
+    let opponent = setup_user_with_balance(&env, &token, 50_000);
     let referrer = setup_user_with_balance(&env, &token, 0);
     let token_client = token::StellarAssetClient::new(&env, &token);
 
@@ -112,6 +113,7 @@ fn test_full_flow_with_referral_rewards() {
         &token,
         &Some(referrer.clone()),
     );
+    client.place_bet(&opponent, &market_id, &1, &bet_amount, &token, &None);
 
     // Resolve market with bettor winning
     advance_time(&env, 2100);
@@ -122,6 +124,7 @@ fn test_full_flow_with_referral_rewards() {
     assert!(winnings > bet_amount);
 
     // Referrer should have earned rewards
+    client.claim_referral_rewards(&referrer, &token);
     let referrer_balance = token_client.balance(&referrer);
     assert!(referrer_balance > 0, "Referrer should have earned rewards");
 }
@@ -175,6 +178,7 @@ fn test_full_flow_multiple_markets_sequential() {
     env.ledger().with_mut(|li| li.timestamp = 1000);
 
     let user = setup_user_with_balance(&env, &token, 200_000);
+    let opponent = setup_user_with_balance(&env, &token, 200_000);
     let token_client = token::StellarAssetClient::new(&env, &token);
 
     // Create and resolve multiple markets sequentially
@@ -185,6 +189,7 @@ fn test_full_flow_multiple_markets_sequential() {
         // Place bet
         let bet_amount = 10_000i128;
         client.place_bet(&user, &market_id, &0, &bet_amount, &token, &None);
+        client.place_bet(&opponent, &market_id, &1, &bet_amount, &token, &None);
 
         // Resolve market
         advance_time(&env, 2100);
@@ -132,7 +132,7 @@ fn test_referral_system_integration() {
 
     // Referrer should have pending rewards
     let rewards_before = token_client.balance(&referrer);
-    let claimed = client.claim_referral_rewards(&referrer, &token);
+    let claimed = client.try_claim_referral_rewards(&referrer, &token);
 
     if claimed.is_ok() {
         let rewards_after = token_client.balance(&referrer);
@@ -162,6 +162,9 @@ fn test_conditional_market_chain() {
         oracle_address: Address::generate(&env),
         feed_id: String::from_str(&env, "test"),
         min_responses: Some(1),
+        max_staleness_seconds: 3600,
+        max_confidence_bps: 200,
+        strike_price: None,
     };
 
     let child_id = client.create_market(
@@ -169,7 +172,7 @@ fn test_conditional_market_chain() {
         &String::from_str(&env, "Child Market"),
         &options,
         &2000,
-        &3000,
+        &(2000 + 86_400),
         &oracle_config,
         &predict_iq::types::MarketTier::Basic,
         &token,
@@ -189,7 +192,7 @@ fn test_emergency_pause_and_recovery() {
     let (env, client, admin, token) = setup_with_token();
 
     let guardian = Address::generate(&env);
-    client.set_guardian(&guardian).unwrap();
+    client.set_guardian(&guardian);
 
     env.ledger().with_mut(|li| li.timestamp = 1000);
 
@@ -227,6 +230,7 @@ fn test_emergency_pause_and_recovery() {
     assert_eq!(new_market, 2);
 }
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_governance_upgrade_workflow() {
     let (env, client, admin, _token) = setup_with_token();
@@ -254,14 +258,7 @@ fn test_governance_upgrade_workflow() {
     // Initiate upgrade
     env.ledger().with_mut(|li| li.timestamp = 1000);
 
-    let wasm_hash = soroban_sdk::BytesN::from_array(
-        &env,
-        &[
-            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
-            0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c,
-            0x1d, 0x1e, 0x1f, 0x20,
-        ],
-    );
+    let wasm_hash = upload_upgrade_wasm(&env);
     client.initiate_upgrade(&wasm_hash);
 
     // Guardians vote
@@ -281,10 +278,8 @@ fn test_governance_upgrade_workflow() {
     // Execute upgrade
     let result = client.try_execute_upgrade();
     assert!(result.is_ok());
-
-    // Verify upgrade cleared
-    let pending = client.get_pending_upgrade();
-    assert!(pending.is_none());
+    // The contract now runs the uploaded stub, so there is nothing further
+    // to query through the client.
 }
 
 #[test]
@@ -345,12 +340,13 @@ fn test_fee_collection_and_distribution() {
     token_client.mint(&user1, &10_000);
     token_client.mint(&user2, &10_000);
 
+    // Fees are taken when bets are placed.
+    let revenue_before = client.get_revenue(&token);
+
     // Place bets
     client.place_bet(&user1, &market_id, &0, &1_000, &token, &None);
     client.place_bet(&user2, &market_id, &1, &1_000, &token, &None);
 
-    let revenue_before = client.get_revenue(&token);
-
     // Resolve market
     client.resolve_market(&market_id, &0);
 
@@ -11,6 +11,7 @@ use soroban_sdk::{
 mod common;
 use common::*;
 
+#[cfg(feature = "governance")]
 #[test]
 fn test_full_disputed_lifecycle() {
     let env = Env::default();
@@ -61,7 +62,7 @@ fn test_full_disputed_lifecycle() {
 
     env.ledger().with_mut(|li| li.timestamp = 1000);
     let deadline = 2000;
-    let resolution_deadline = 3000;
+    let resolution_deadline = deadline + 86_400;
 
     let market_id = client.create_market(
         &creator,
@@ -89,7 +90,7 @@ fn test_full_disputed_lifecycle() {
     assert_market_status(&client, market_id, MarketStatus::Active);
 
     // 4. Resolve via Oracle (Proposed Outcome: 1)
-    env.ledger().with_mut(|li| li.timestamp = 3001); // Past resolution deadline
+    env.ledger().with_mut(|li| li.timestamp = resolution_deadline + 1); // Past resolution deadline
 
     // Set oracle result as admin
     client.set_oracle_result(&market_id, &0, &1);
@@ -100,7 +101,7 @@ fn test_full_disputed_lifecycle() {
     assert_eq!(market.winning_outcome, Some(1));
 
     // 5. File Dispute (User A disagrees)
-    env.ledger().with_mut(|li| li.timestamp = 3100); // Within 48h dispute window
+    env.ledger().with_mut(|li| li.timestamp = resolution_deadline + 100); // Within the dispute window
     client.file_dispute(&user_a, &market_id);
 
     assert_market_status(&client, market_id, MarketStatus::Disputed);
@@ -120,7 +121,7 @@ fn test_full_disputed_lifecycle() {
 
     // 7. Finalize Resolution after voting period (72h after dispute)
     env.ledger()
-        .with_mut(|li| li.timestamp = 3100 + (72 * 3601)); // Past 72h
+        .with_mut(|li| li.timestamp = resolution_deadline + 100 + (72 * 3601)); // Past 72h
     client.finalize_resolution(&market_id);
 
     assert_market_status(&client, market_id, MarketStatus::Resolved);
@@ -129,13 +130,13 @@ fn test_full_disputed_lifecycle() {
 
     // 8. Claim Winnings (User A was right after all)
     let balance_before = token::Client::new(&env, &native_token).balance(&user_a);
-    let claimed = client.claim_winnings(&user_a, &market_id);
+    let claimed = client.claim_winnings(&user_a, &market_id, &native_token);
     assert!(claimed > 1000); // Original 1000 + share of user B's bet - fees
 
     let balance_after = token::Client::new(&env, &native_token).balance(&user_a);
     assert_eq!(balance_after, balance_before + claimed);
 
     // 9. Loser (User B) cannot claim
-    let b_result = client.try_claim_winnings(&user_b, &market_id);
+    let b_result = client.try_claim_winnings(&user_b, &market_id, &native_token);
     assert!(b_result.is_err());
 }
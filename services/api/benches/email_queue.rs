@@ -52,6 +52,9 @@ async fn build_email_queue() -> Option<EmailQueue> {
         query_timeout: std::time::Duration::from_secs(30),
         statement_timeout_ms: 30000,
         lock_timeout_ms: 10000,
+        run_migrations: false,
+        slow_query_threshold: std::time::Duration::from_millis(500),
+        sample_interval: std::time::Duration::from_secs(15),
     };
 
     let db = match Database::new(&database_url, cache.clone(), metrics, &db_pool).await {
@@ -62,7 +65,7 @@ async fn build_email_queue() -> Option<EmailQueue> {
         }
     };
 
-    Some(EmailQueue::new(cache, db))
+    Some(EmailQueue::new(db))
 }
 
 // ── Benchmark: enqueue throughput ──────────────────────────────────────────────
@@ -104,7 +107,7 @@ fn bench_email_enqueue_throughput(c: &mut Criterion) {
 // This benchmark enqueues a job, dequeues it, and simulates the "send" step
 // by calling into EmailService with a mocked reqwest client.  Because we don't
 // have a real SendGrid API key in benchmarks, the "send" is a no-op that
-// validates the cycle overhead (Redis POP + DB update).
+// validates the cycle overhead (SKIP LOCKED claim + DB update).
 
 fn bench_email_dequeue_to_send_cycle(c: &mut Criterion) {
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -133,17 +136,17 @@ fn bench_email_dequeue_to_send_cycle(c: &mut Criterion) {
                 .await
                 .expect("enqueue should succeed");
 
-            // 2. Dequeue it.
-            let dequeued = queue
-                .dequeue()
+            // 2. Claim it.
+            let claimed = queue
+                .claim_next()
                 .await
-                .expect("dequeue should succeed")
+                .expect("claim should succeed")
                 .expect("a job should be available");
-            assert_eq!(dequeued, job_id);
+            assert_eq!(claimed.id, job_id);
 
             // 3. Mark as completed (simulates successful send).
             queue
-                .mark_completed(job_id, Some("bench-message-id"))
+                .mark_completed(&claimed, Some("bench-message-id".to_string()))
                 .await
                 .expect("mark_completed should succeed");
 
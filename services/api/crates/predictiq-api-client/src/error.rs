@@ -0,0 +1,94 @@
+use std::fmt;
+
+use predictiq_api_types::ErrorEnvelope;
+
+/// Why a request failed.
+#[derive(Debug)]
+pub enum Error {
+    /// The API answered with its error envelope; `envelope.code` says why.
+    Api {
+        status: u16,
+        envelope: ErrorEnvelope,
+    },
+    /// A non-success response without an error envelope, e.g. from a proxy
+    /// in front of the API.
+    UnexpectedResponse { status: u16, body: String },
+    /// A success response whose body is not the expected type.
+    Decode {
+        status: u16,
+        source: serde_json::Error,
+    },
+    /// The request never got a response: DNS, connect, TLS or timeout.
+    Transport(reqwest::Error),
+    /// The base URL, or a path joined onto it, is not a valid URL.
+    InvalidUrl(url::ParseError),
+    /// A configured header value (API key, network) is not a valid header.
+    InvalidHeader { name: &'static str },
+}
+
+impl Error {
+    /// The HTTP status, when a response was received.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. }
+            | Self::UnexpectedResponse { status, .. }
+            | Self::Decode { status, .. } => Some(*status),
+            Self::Transport(e) => e.status().map(|s| s.as_u16()),
+            Self::InvalidUrl(_) | Self::InvalidHeader { .. } => None,
+        }
+    }
+
+    /// The envelope's machine-readable code, e.g. `MARKET_NOT_FOUND`.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            Self::Api { envelope, .. } => Some(&envelope.code),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Api { status, envelope } => {
+                write!(
+                    f,
+                    "API error {status} {}: {}",
+                    envelope.code, envelope.message
+                )
+            }
+            Self::UnexpectedResponse { status, body } => {
+                write!(f, "unexpected response {status}: {body}")
+            }
+            Self::Decode { status, source } => {
+                write!(f, "could not decode {status} response: {source}")
+            }
+            Self::Transport(e) => write!(f, "request failed: {e}"),
+            Self::InvalidUrl(e) => write!(f, "invalid URL: {e}"),
+            Self::InvalidHeader { name } => write!(f, "invalid {name} header value"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode { source, .. } => Some(source),
+            Self::Transport(e) => Some(e),
+            Self::InvalidUrl(e) => Some(e),
+            Self::Api { .. } | Self::UnexpectedResponse { .. } | Self::InvalidHeader { .. } => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Transport(e)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self {
+        Self::InvalidUrl(e)
+    }
+}
@@ -0,0 +1,436 @@
+//! Typed async client for the PredictIQ API.
+//!
+//! Responses deserialise into the same [`predictiq_api_types`] the API
+//! serialises, and failures carry the API's [`ErrorEnvelope`].
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), predictiq_api_client::Error> {
+//! use predictiq_api_client::{Client, IdempotencyKey, PageRequest};
+//!
+//! let client = Client::builder("https://api.predictiq.com")
+//!     .api_key("pk_live_...")
+//!     .max_retries(3)
+//!     .build()?;
+//! let featured = client.featured_markets(&PageRequest::default()).await?;
+//! let market = client.chain_market(featured.items[0].id).await?;
+//! let key = IdempotencyKey::new();
+//! let body = serde_json::json!({ "winning_outcome": 1 });
+//! let _: serde_json::Value = client
+//!     .post(&format!("/api/v1/markets/{}/resolve", market.market_id), &body, Some(&key))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `429` and `503` responses are retried up to [`ClientBuilder::max_retries`]
+//! times, waiting as long as their `Retry-After` header (or
+//! `details.retry_after`) asks. A wait longer than
+//! [`ClientBuilder::max_retry_delay`] is not attempted: the error is returned
+//! so the caller can decide. Without either hint the client backs off
+//! exponentially from 250 ms. Other failures are returned as they are; a
+//! `POST` that must not run twice should carry an [`IdempotencyKey`], which
+//! makes resending it after a transport error safe.
+
+mod error;
+
+use std::{fmt, time::Duration};
+
+use reqwest::{header::HeaderValue, Method, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use url::Url;
+
+pub use error::Error;
+pub use predictiq_api_types as types;
+use predictiq_api_types::{
+    ChainMarketData, ErrorEnvelope, FeaturedMarketView, NewsletterResponse, PaginatedResponse,
+    Portfolio, UserBet,
+};
+
+/// Header carrying the API key on admin and metered routes.
+pub const API_KEY_HEADER: &str = "X-API-Key";
+/// Header the API deduplicates mutating requests by.
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+/// Header selecting the network blockchain routes read from.
+pub const NETWORK_HEADER: &str = "X-Network";
+
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const BACKOFF_BASE: Duration = Duration::from_millis(250);
+
+/// Configures a [`Client`]; start from [`Client::builder`].
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    base_url: String,
+    api_key: Option<String>,
+    network: Option<String>,
+    max_retries: u32,
+    max_retry_delay: Duration,
+    timeout: Duration,
+}
+
+impl ClientBuilder {
+    /// Sent as `X-API-Key` on every request.
+    pub fn api_key(mut self, key: impl Into<String>) -> Self {
+        self.api_key = Some(key.into());
+        self
+    }
+
+    /// Sent as `X-Network` on every request; the API's primary network is
+    /// used when unset.
+    pub fn network(mut self, name: impl Into<String>) -> Self {
+        self.network = Some(name.into());
+        self
+    }
+
+    /// Retries after a `429` or `503`, on top of the first attempt.
+    /// Defaults to 2; `0` disables retries.
+    pub fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Longest wait before a retry. A `Retry-After` above it is returned as
+    /// an error instead of waited out. Defaults to 30 s.
+    pub fn max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = delay;
+        self
+    }
+
+    /// Per-attempt request timeout. Defaults to 30 s.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn build(self) -> Result<Client, Error> {
+        let base_url = Url::parse(&self.base_url)?;
+        if base_url.cannot_be_a_base() {
+            return Err(Error::InvalidUrl(
+                url::ParseError::RelativeUrlWithCannotBeABaseBase,
+            ));
+        }
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(key) = &self.api_key {
+            let mut value = HeaderValue::from_str(key).map_err(|_| Error::InvalidHeader {
+                name: API_KEY_HEADER,
+            })?;
+            value.set_sensitive(true);
+            headers.insert(API_KEY_HEADER, value);
+        }
+        if let Some(network) = &self.network {
+            let value = HeaderValue::from_str(network).map_err(|_| Error::InvalidHeader {
+                name: NETWORK_HEADER,
+            })?;
+            headers.insert(NETWORK_HEADER, value);
+        }
+        let http = reqwest::Client::builder()
+            .default_headers(headers)
+            .timeout(self.timeout)
+            .build()?;
+
+        Ok(Client {
+            http,
+            base_url,
+            max_retries: self.max_retries,
+            max_retry_delay: self.max_retry_delay,
+        })
+    }
+}
+
+/// An `Idempotency-Key` value. Reuse the same key when resending a request
+/// so the API replays the first response instead of repeating its effect.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// A fresh random key.
+    pub fn new() -> Self {
+        Self(uuid::Uuid::new_v4().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for IdempotencyKey {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<String> for IdempotencyKey {
+    fn from(key: String) -> Self {
+        Self(key)
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// `limit` and `cursor` for paginated routes; both use the API's defaults
+/// when unset. Pass the previous page's `next_cursor` to continue.
+#[derive(Debug, Clone, Default)]
+pub struct PageRequest {
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+impl PageRequest {
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if let Some(limit) = self.limit {
+            query.push(("limit", limit.to_string()));
+        }
+        if let Some(cursor) = &self.cursor {
+            query.push(("cursor", cursor.clone()));
+        }
+        query
+    }
+}
+
+/// A PredictIQ API client. Cheap to clone; clones share one connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: Url,
+    max_retries: u32,
+    max_retry_delay: Duration,
+}
+
+impl Client {
+    /// Start configuring a client for the API at `base_url`, e.g.
+    /// `https://api.predictiq.com`. A path on the base URL is kept as a
+    /// prefix of every route.
+    pub fn builder(base_url: impl Into<String>) -> ClientBuilder {
+        ClientBuilder {
+            base_url: base_url.into(),
+            api_key: None,
+            network: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            max_retry_delay: DEFAULT_MAX_RETRY_DELAY,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// `GET /api/v1/markets/featured`.
+    pub async fn featured_markets(
+        &self,
+        page: &PageRequest,
+    ) -> Result<PaginatedResponse<FeaturedMarketView>, Error> {
+        let url = self.url(&["api", "v1", "markets", "featured"], &page.query())?;
+        self.send(Method::GET, url, None::<&()>, None).await
+    }
+
+    /// `GET /api/v1/blockchain/markets/{market_id}`.
+    pub async fn chain_market(&self, market_id: i64) -> Result<ChainMarketData, Error> {
+        let id = market_id.to_string();
+        let url = self.url(&["api", "v1", "blockchain", "markets", &id], &[])?;
+        self.send(Method::GET, url, None::<&()>, None).await
+    }
+
+    /// `GET /api/v1/blockchain/users/{user}/bets`.
+    pub async fn user_bets(
+        &self,
+        user: &str,
+        page: &PageRequest,
+    ) -> Result<PaginatedResponse<UserBet>, Error> {
+        let url = self.url(
+            &["api", "v1", "blockchain", "users", user, "bets"],
+            &page.query(),
+        )?;
+        self.send(Method::GET, url, None::<&()>, None).await
+    }
+
+    /// `GET /api/v1/users/{address}/portfolio`.
+    pub async fn portfolio(&self, address: &str) -> Result<Portfolio, Error> {
+        let url = self.url(&["api", "v1", "users", address, "portfolio"], &[])?;
+        self.send(Method::GET, url, None::<&()>, None).await
+    }
+
+    /// `POST /api/v1/newsletter/subscribe`.
+    pub async fn newsletter_subscribe(
+        &self,
+        email: &str,
+        source: Option<&str>,
+    ) -> Result<NewsletterResponse, Error> {
+        #[derive(Serialize)]
+        struct Subscribe<'a> {
+            email: &'a str,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            source: Option<&'a str>,
+        }
+
+        let url = self.url(&["api", "v1", "newsletter", "subscribe"], &[])?;
+        self.send(Method::POST, url, Some(&Subscribe { email, source }), None)
+            .await
+    }
+
+    /// `GET` any route, e.g. `/api/v1/statistics`, decoding the body as `T`.
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let url = self.path_url(path)?;
+        self.send(Method::GET, url, None::<&()>, None).await
+    }
+
+    /// `POST` `body` as JSON to any route. With `idempotency_key` the request
+    /// carries `Idempotency-Key`, so resending it with the same key is safe.
+    pub async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+        idempotency_key: Option<&IdempotencyKey>,
+    ) -> Result<T, Error> {
+        let url = self.path_url(path)?;
+        self.send(Method::POST, url, Some(body), idempotency_key)
+            .await
+    }
+
+    fn url(&self, segments: &[&str], query: &[(&str, String)]) -> Result<Url, Error> {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .map_err(|_| Error::InvalidUrl(url::ParseError::RelativeUrlWithCannotBeABaseBase))?
+            .pop_if_empty()
+            .extend(segments);
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        Ok(url)
+    }
+
+    fn path_url(&self, path: &str) -> Result<Url, Error> {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut url = self.url(&segments, &[])?;
+        if !query.is_empty() {
+            url.set_query(Some(query));
+        }
+        Ok(url)
+    }
+
+    fn request<B: Serialize + ?Sized>(
+        &self,
+        method: &Method,
+        url: &Url,
+        body: Option<&B>,
+        idempotency_key: Option<&IdempotencyKey>,
+    ) -> RequestBuilder {
+        let mut request = self.http.request(method.clone(), url.clone());
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        if let Some(key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_HEADER, key.as_str());
+        }
+        request
+    }
+
+    async fn send<B: Serialize + ?Sized, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: Url,
+        body: Option<&B>,
+        idempotency_key: Option<&IdempotencyKey>,
+    ) -> Result<T, Error> {
+        let mut attempt = 0;
+        loop {
+            let response = self
+                .request(&method, &url, body, idempotency_key)
+                .send()
+                .await?;
+            let status = response.status();
+            let retry_after = retry_after_header(&response);
+            let bytes = response.bytes().await?;
+
+            if status.is_success() {
+                return serde_json::from_slice(&bytes).map_err(|source| Error::Decode {
+                    status: status.as_u16(),
+                    source,
+                });
+            }
+
+            let err = match serde_json::from_slice::<ErrorEnvelope>(&bytes) {
+                Ok(envelope) => Error::Api {
+                    status: status.as_u16(),
+                    envelope,
+                },
+                Err(_) => Error::UnexpectedResponse {
+                    status: status.as_u16(),
+                    body: String::from_utf8_lossy(&bytes).into_owned(),
+                },
+            };
+            if !is_retryable(status) || attempt >= self.max_retries {
+                return Err(err);
+            }
+            let hinted = retry_after.or_else(|| match &err {
+                Error::Api { envelope, .. } => envelope.retry_after_secs().map(Duration::from_secs),
+                _ => None,
+            });
+            let delay = match hinted {
+                Some(delay) if delay > self.max_retry_delay => return Err(err),
+                Some(delay) => delay,
+                None => backoff(attempt).min(self.max_retry_delay),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// `Retry-After` in delay-seconds form, the only form the API sends.
+fn retry_after_header(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn backoff(attempt: u32) -> Duration {
+    BACKOFF_BASE.saturating_mul(2u32.saturating_pow(attempt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_from_the_base() {
+        assert_eq!(backoff(0), Duration::from_millis(250));
+        assert_eq!(backoff(3), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn base_path_is_kept_and_segments_are_encoded() {
+        let client = Client::builder("https://example.test/predictiq/")
+            .build()
+            .unwrap();
+        let url = client
+            .url(
+                &["api", "v1", "users", "a b", "portfolio"],
+                &[("limit", "5".into())],
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://example.test/predictiq/api/v1/users/a%20b/portfolio?limit=5"
+        );
+        let url = client.path_url("/api/v1/statistics?x=1").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://example.test/predictiq/api/v1/statistics?x=1"
+        );
+    }
+}
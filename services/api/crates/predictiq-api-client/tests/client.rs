@@ -0,0 +1,220 @@
+//! The client against a mocked API: decoding, retries and error envelopes.
+
+use std::time::Duration;
+
+use predictiq_api_client::{Client, Error, IdempotencyKey, PageRequest};
+use serde_json::json;
+use wiremock::{
+    matchers::{body_json, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+fn client(server: &MockServer) -> Client {
+    Client::builder(server.uri())
+        .api_key("test-key")
+        .max_retries(2)
+        .max_retry_delay(Duration::from_secs(5))
+        .build()
+        .unwrap()
+}
+
+fn featured_page() -> serde_json::Value {
+    json!({
+        "items": [{
+            "id": 7,
+            "title": "Will it rain?",
+            "volume": 1250.5,
+            "ends_at": "2026-03-01T00:00:00Z",
+            "onchain_volume": "12505000000",
+            "volume_usd": null,
+            "resolved_outcome": null,
+            "image_url": null
+        }],
+        "next_cursor": "1",
+        "limit": 1,
+        "has_more": true
+    })
+}
+
+#[tokio::test]
+async fn decodes_a_typed_page_and_sends_the_api_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/markets/featured"))
+        .and(query_param("limit", "1"))
+        .and(header("x-api-key", "test-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(featured_page()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let page = client(&server)
+        .featured_markets(&PageRequest {
+            limit: Some(1),
+            cursor: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, 7);
+    assert_eq!(page.items[0].onchain_volume, "12505000000");
+    assert_eq!(page.next_cursor.as_deref(), Some("1"));
+    assert!(page.has_more);
+}
+
+#[tokio::test]
+async fn retries_a_429_after_its_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/blockchain/markets/7"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "1")
+                .set_body_json(json!({ "code": "RATE_LIMITED", "message": "Too many requests, please try again later." })),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/blockchain/markets/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "market_id": 7,
+            "title": null,
+            "status": "active",
+            "onchain_volume": "0",
+            "resolved_outcome": null,
+            "ledger": 1000,
+            "source": "live"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = std::time::Instant::now();
+    let market = client(&server).chain_market(7).await.unwrap();
+    assert!(
+        started.elapsed() >= Duration::from_secs(1),
+        "waited out Retry-After"
+    );
+    assert_eq!(market.market_id, 7);
+    assert_eq!(market.status.as_deref(), Some("active"));
+    assert_eq!(market.options, None);
+}
+
+#[tokio::test]
+async fn does_not_wait_out_a_retry_after_beyond_the_cap() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/statistics"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "86400")
+                .set_body_json(
+                    json!({ "code": "QUOTA_EXCEEDED", "message": "Monthly quota exhausted." }),
+                ),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .get::<serde_json::Value>("/api/v1/statistics")
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(429));
+    assert_eq!(err.code(), Some("QUOTA_EXCEEDED"));
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/statistics"))
+        .respond_with(ResponseTemplate::new(503).set_body_json(json!({
+            "code": "OVERLOADED",
+            "message": "The service is busy; please retry shortly.",
+            "details": { "retry_after": 0 }
+        })))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let err = client(&server)
+        .get::<serde_json::Value>("/api/v1/statistics")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some("OVERLOADED"));
+}
+
+#[tokio::test]
+async fn error_envelopes_deserialize_into_api_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/blockchain/markets/404"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "code": "MARKET_NOT_FOUND",
+            "message": "market 404 not found",
+            "details": { "market_id": 404 },
+            "request_id": "req-abc"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    match client(&server).chain_market(404).await.unwrap_err() {
+        Error::Api { status, envelope } => {
+            assert_eq!(status, 404);
+            assert_eq!(envelope.code, "MARKET_NOT_FOUND");
+            assert_eq!(envelope.message, "market 404 not found");
+            assert_eq!(envelope.details, Some(json!({ "market_id": 404 })));
+            assert_eq!(envelope.request_id.as_deref(), Some("req-abc"));
+        }
+        other => panic!("expected an API error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn non_envelope_errors_keep_the_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/users/GABC/portfolio"))
+        .respond_with(ResponseTemplate::new(502).set_body_string("<html>Bad Gateway</html>"))
+        .mount(&server)
+        .await;
+
+    match client(&server).portfolio("GABC").await.unwrap_err() {
+        Error::UnexpectedResponse { status, body } => {
+            assert_eq!(status, 502);
+            assert!(body.contains("Bad Gateway"));
+        }
+        other => panic!("expected an unexpected response, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn posts_carry_the_idempotency_key() {
+    let server = MockServer::start().await;
+    let key = IdempotencyKey::new();
+    Mock::given(method("POST"))
+        .and(path("/api/v1/newsletter/subscribe"))
+        .and(header("idempotency-key", key.as_str()))
+        .and(body_json(json!({ "email": "a@example.com" })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(json!({
+            "success": true,
+            "message": "Please check your email to confirm your subscription."
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response: predictiq_api_client::types::NewsletterResponse = client(&server)
+        .post(
+            "/api/v1/newsletter/subscribe",
+            &json!({ "email": "a@example.com" }),
+            Some(&key),
+        )
+        .await
+        .unwrap();
+    assert!(response.success);
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// The body of every error response: `{ code, message, details?, request_id? }`.
+/// `message` is always safe to show.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorEnvelope {
+    /// Stable machine-readable code, e.g. `MARKET_NOT_FOUND`.
+    pub code: String,
+    pub message: String,
+    /// Structured context a client can act on, e.g. `retry_after`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+    /// Correlation ID of the failed request, matching the `X-Request-ID`
+    /// response header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ErrorEnvelope {
+    /// `details.retry_after` in seconds, when the server supplied one.
+    pub fn retry_after_secs(&self) -> Option<u64> {
+        self.details.as_ref()?.get("retry_after")?.as_u64()
+    }
+}
@@ -0,0 +1,18 @@
+//! Request and response bodies of the PredictIQ API.
+//!
+//! The API serialises these exact types and `predictiq-api-client`
+//! deserialises them, so a field renamed on one side is a compile error on
+//! the other rather than a silently missing value. Enable the `openapi`
+//! feature to derive `utoipa::ToSchema` for the API's spec.
+
+mod error;
+mod market;
+mod newsletter;
+mod pagination;
+mod portfolio;
+
+pub use error::ErrorEnvelope;
+pub use market::{ChainMarketData, DataSource, FeaturedMarketView, UserBet, UserBetsPage};
+pub use newsletter::NewsletterResponse;
+pub use pagination::PaginatedResponse;
+pub use portfolio::{MarketPosition, Portfolio, PositionStatus, TokenTotals};
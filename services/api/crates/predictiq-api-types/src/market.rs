@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Indicates whether a response was sourced from a live RPC call or a stale
+/// cache entry served after an RPC failure.
+///
+/// Consumers and alerting rules can use this field to distinguish real zeros
+/// from error-masked defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    /// Data was fetched live from the RPC node.
+    Live,
+    /// The RPC call failed; this is a stale cached value served as a fallback.
+    StaleFallback,
+}
+
+/// `GET /api/v1/blockchain/markets/{market_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainMarketData {
+    pub market_id: i64,
+    pub title: Option<String>,
+    /// Outcome labels read from the contract; only filled in when the
+    /// database has no title for the market.
+    #[serde(default)]
+    pub options: Option<Vec<String>>,
+    pub status: Option<String>,
+    pub onchain_volume: String,
+    pub resolved_outcome: Option<u32>,
+    pub ledger: u32,
+    pub source: DataSource,
+}
+
+/// One entry of `GET /api/v1/markets/featured`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FeaturedMarketView {
+    pub id: i64,
+    pub title: String,
+    pub volume: f64,
+    pub ends_at: chrono::DateTime<chrono::Utc>,
+    pub onchain_volume: String,
+    /// `onchain_volume` in USD to the cent; `null` when the market's token
+    /// has no fresh price.
+    #[serde(default)]
+    pub volume_usd: Option<String>,
+    pub resolved_outcome: Option<u32>,
+    /// Versioned cover image URL; `null` until one is uploaded.
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
+/// One entry of `GET /api/v1/blockchain/users/{user}/bets`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBet {
+    pub market_id: i64,
+    pub outcome: u32,
+    pub amount: String,
+    pub token: Option<String>,
+    pub ledger: u32,
+}
+
+/// A page of an address's bets as read from the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBetsPage {
+    pub user: String,
+    pub page: i64,
+    pub page_size: i64,
+    pub total: i64,
+    pub items: Vec<UserBet>,
+    pub source: DataSource,
+}
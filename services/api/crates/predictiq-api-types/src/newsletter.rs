@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// `POST /api/v1/newsletter/subscribe` and the other newsletter actions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct NewsletterResponse {
+    pub success: bool,
+    pub message: String,
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// A single page of results returned by paginated endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedResponse<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub limit: u32,
+    pub has_more: bool,
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<String>, limit: u32, has_more: bool) -> Self {
+        Self {
+            items,
+            next_cursor,
+            limit,
+            has_more,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PositionStatus {
+    /// Market not settled yet; the stake is still at risk.
+    Open,
+    /// Resolved in the address's favour and the payout has been claimed.
+    Won,
+    /// Resolved in the address's favour but nothing claimed yet.
+    Unclaimed,
+    /// Resolved against every outcome the address backed.
+    Lost,
+    /// Market cancelled; stakes are returned rather than settled.
+    Refunded,
+}
+
+/// Amounts are integer token units (stroops) as decimal strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct MarketPosition {
+    pub market_id: i64,
+    pub token: Option<String>,
+    pub status: PositionStatus,
+    pub market_status: Option<String>,
+    pub resolved_outcome: Option<i32>,
+    pub staked: String,
+    pub claimed: String,
+    pub refunded: String,
+    /// Settled gain or loss; `"0"` while the position is open or unclaimed.
+    pub realized_pnl: String,
+    /// Stake still tied up in the market.
+    pub open_exposure: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TokenTotals {
+    pub token: Option<String>,
+    pub staked: String,
+    pub realized_pnl: String,
+    pub open_exposure: String,
+}
+
+/// `GET /api/v1/users/{address}/portfolio`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Portfolio {
+    pub address: String,
+    pub positions: Vec<MarketPosition>,
+    /// One entry per token; amounts in different tokens are never summed.
+    pub totals: Vec<TokenTotals>,
+}
@@ -1,8 +1,9 @@
--- Migration: performance indexes for markets and content tables.
+-- Migration: performance indexes for markets.
 -- Promoted from sql/performance_indexes.sql into the migration system.
+--
+-- The content index that used to live here referenced a `content` table no
+-- migration created, so this file could never apply to a fresh database. It
+-- now ships with that table in 035_create_content.sql.
 
 CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_markets_status_volume_ends_at
 ON markets (status, total_volume DESC, ends_at ASC);
-
-CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_content_published_at
-ON content (is_published, published_at DESC);
@@ -5,53 +5,66 @@
 --
 -- Limits are chosen to be generous enough for real-world content while
 -- still preventing runaway storage from malformed or malicious inputs.
+--
+-- PostgreSQL has no ADD CONSTRAINT IF NOT EXISTS; each constraint is dropped
+-- and re-added in the same statement so the file can be re-run.
 
 -- markets.title: free-form market title supplied by operators
 ALTER TABLE markets
-    ADD CONSTRAINT IF NOT EXISTS chk_markets_title_length
+    DROP CONSTRAINT IF EXISTS chk_markets_title_length,
+    ADD CONSTRAINT chk_markets_title_length
         CHECK (char_length(title) <= 500);
 
 -- contact_form_submissions.message: user-supplied contact message body
 ALTER TABLE contact_form_submissions
-    ADD CONSTRAINT IF NOT EXISTS chk_contact_message_length
+    DROP CONSTRAINT IF EXISTS chk_contact_message_length,
+    ADD CONSTRAINT chk_contact_message_length
         CHECK (char_length(message) <= 10000);
 
 -- analytics_events.page_url: URL of the page where the event occurred
 ALTER TABLE analytics_events
-    ADD CONSTRAINT IF NOT EXISTS chk_analytics_page_url_length
+    DROP CONSTRAINT IF EXISTS chk_analytics_page_url_length,
+    ADD CONSTRAINT chk_analytics_page_url_length
         CHECK (char_length(page_url) <= 2048);
 
 -- analytics_events.referrer: HTTP Referer header value
 ALTER TABLE analytics_events
-    ADD CONSTRAINT IF NOT EXISTS chk_analytics_referrer_length
+    DROP CONSTRAINT IF EXISTS chk_analytics_referrer_length,
+    ADD CONSTRAINT chk_analytics_referrer_length
         CHECK (char_length(referrer) <= 2048);
 
 -- analytics_events.user_agent: User-Agent header string
 ALTER TABLE analytics_events
-    ADD CONSTRAINT IF NOT EXISTS chk_analytics_user_agent_length
+    DROP CONSTRAINT IF EXISTS chk_analytics_user_agent_length,
+    ADD CONSTRAINT chk_analytics_user_agent_length
         CHECK (char_length(user_agent) <= 512);
 
 -- email_jobs.error_message: internal error detail from the mailer
 ALTER TABLE email_jobs
-    ADD CONSTRAINT IF NOT EXISTS chk_email_jobs_error_message_length
+    DROP CONSTRAINT IF EXISTS chk_email_jobs_error_message_length,
+    ADD CONSTRAINT chk_email_jobs_error_message_length
         CHECK (char_length(error_message) <= 4000);
 
 -- email_suppressions.reason: human-readable suppression reason
 ALTER TABLE email_suppressions
-    ADD CONSTRAINT IF NOT EXISTS chk_email_suppressions_reason_length
+    DROP CONSTRAINT IF EXISTS chk_email_suppressions_reason_length,
+    ADD CONSTRAINT chk_email_suppressions_reason_length
         CHECK (char_length(reason) <= 1000);
 
 -- email_template_variants.subject_line: rendered email subject
 ALTER TABLE email_template_variants
-    ADD CONSTRAINT IF NOT EXISTS chk_email_subject_line_length
+    DROP CONSTRAINT IF EXISTS chk_email_subject_line_length,
+    ADD CONSTRAINT chk_email_subject_line_length
         CHECK (char_length(subject_line) <= 998);
 
 -- audit_logs.reason: free-text audit rationale entered by an operator
 ALTER TABLE audit_logs
-    ADD CONSTRAINT IF NOT EXISTS chk_audit_logs_reason_length
+    DROP CONSTRAINT IF EXISTS chk_audit_logs_reason_length,
+    ADD CONSTRAINT chk_audit_logs_reason_length
         CHECK (char_length(reason) <= 2000);
 
 -- content_management.body: CMS page body (allow large content)
 ALTER TABLE content_management
-    ADD CONSTRAINT IF NOT EXISTS chk_content_body_length
+    DROP CONSTRAINT IF EXISTS chk_content_body_length,
+    ADD CONSTRAINT chk_content_body_length
         CHECK (char_length(body) <= 500000);
@@ -0,0 +1,40 @@
+-- Filter columns and keyset indexes for the market list endpoint.
+--
+-- GET /api/v1/markets filters by status, category, and creator and pages
+-- through results with a keyset cursor of (sort key, id). Each supported
+-- sort order gets a composite index whose trailing id column matches the
+-- cursor tie-breaker, so every page is a single index range scan instead of
+-- an OFFSET walk. All indexes are partial on deleted_at IS NULL because
+-- soft-deleted markets are never listed.
+--
+-- participant_count is maintained by the chain indexer; existing rows start
+-- at 0 and are corrected on the next sync.
+
+ALTER TABLE markets
+    ADD COLUMN IF NOT EXISTS category          TEXT,
+    ADD COLUMN IF NOT EXISTS creator           TEXT,
+    ADD COLUMN IF NOT EXISTS participant_count BIGINT NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_markets_list_volume
+    ON markets (status, total_volume DESC, id DESC)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_markets_list_ends_at
+    ON markets (status, ends_at ASC, id ASC)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_markets_list_created_at
+    ON markets (status, created_at DESC, id DESC)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_markets_list_participants
+    ON markets (status, participant_count DESC, id DESC)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_markets_category
+    ON markets (category)
+    WHERE deleted_at IS NULL AND category IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_markets_creator
+    ON markets (creator)
+    WHERE deleted_at IS NULL AND creator IS NOT NULL;
@@ -0,0 +1,16 @@
+-- Descriptive fields for the combined market detail endpoint.
+--
+-- GET /api/v1/markets/:id returns the off-chain metadata alongside on-chain
+-- state. The markets table so far only held the fields needed for listing;
+-- this adds the long-form description and the outcome labels with their
+-- current implied odds (probabilities in [0, 1], index-aligned with
+-- outcome_options). Existing rows get empty arrays and a NULL description.
+
+ALTER TABLE markets
+    ADD COLUMN IF NOT EXISTS description     TEXT,
+    ADD COLUMN IF NOT EXISTS outcome_options TEXT[]             NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS outcome_odds    DOUBLE PRECISION[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE markets
+    ADD CONSTRAINT chk_markets_description_length
+        CHECK (char_length(description) <= 10000);
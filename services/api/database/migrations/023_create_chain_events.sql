@@ -0,0 +1,42 @@
+-- Indexed contract events.
+--
+-- The sync worker previously kept events only in Redis with a 30-minute TTL,
+-- which is enough to drive cache refreshes but not to answer per-user
+-- questions (portfolio, leaderboards). Every confirmed contract event is now
+-- also written here, keyed by the RPC event id so replays are idempotent.
+--
+-- Topic layout follows contracts/predict-iq/src/modules/events.rs:
+--   topic[0] = event name (kind), topic[1] = market_id, topic[2] = address.
+-- Amounts are stroop-denominated i128 values, stored as NUMERIC(39, 0) so no
+-- precision is lost; readers cast aggregates back to TEXT.
+--
+-- markets.token records the market's stake token so bet events (which do not
+-- carry a token in their payload) can be grouped by token.
+
+CREATE TABLE IF NOT EXISTS chain_events (
+    id           TEXT           PRIMARY KEY,
+    network      TEXT           NOT NULL,
+    ledger       BIGINT         NOT NULL,
+    tx_hash      TEXT,
+    kind         TEXT           NOT NULL,
+    market_id    BIGINT,
+    address      TEXT,
+    outcome      INTEGER,
+    amount       NUMERIC(39, 0),
+    token        TEXT,
+    is_refund    BOOLEAN        NOT NULL DEFAULT FALSE,
+    indexed_at   TIMESTAMPTZ    NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_events_address_market
+    ON chain_events (address, market_id)
+    WHERE address IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_chain_events_market_kind
+    ON chain_events (market_id, kind);
+
+CREATE INDEX IF NOT EXISTS idx_chain_events_network_ledger
+    ON chain_events (network, ledger);
+
+ALTER TABLE markets
+    ADD COLUMN IF NOT EXISTS token TEXT;
@@ -0,0 +1,25 @@
+-- Precomputed leaderboards.
+--
+-- A background task in the API rebuilds each (period, metric) board from
+-- chain_events on a fixed interval and swaps the rows in a single
+-- transaction, so readers always see one complete snapshot.
+--
+-- rank is assigned with ROW_NUMBER over (score DESC, markets DESC, address
+-- ASC): equal scores never share a rank and the order is stable across runs.
+-- score is NUMERIC so profit/volume keep full stroop precision; win rate is a
+-- fraction in [0, 1].
+
+CREATE TABLE IF NOT EXISTS leaderboard_snapshots (
+    period       TEXT           NOT NULL CHECK (period IN ('weekly', 'all')),
+    metric       TEXT           NOT NULL CHECK (metric IN ('profit', 'volume', 'winrate')),
+    rank         INTEGER        NOT NULL,
+    address      TEXT           NOT NULL,
+    score        NUMERIC        NOT NULL,
+    markets      BIGINT         NOT NULL,
+    computed_at  TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (period, metric, rank)
+);
+
+-- Weekly volume scans bet events by time.
+CREATE INDEX IF NOT EXISTS idx_chain_events_kind_indexed_at
+    ON chain_events (kind, indexed_at);
@@ -0,0 +1,30 @@
+-- Implied-probability history per market outcome.
+--
+-- The sync worker writes one raw row per (market, outcome, minute) on each
+-- pass, folding repeated samples within the minute into that row's
+-- high/low/close. Raw rows older than 30 days are rolled up into one row per
+-- hour (is_rollup = TRUE) by a scheduled job. Both kinds carry OHLC columns so
+-- the history endpoint can bucket them with the same aggregate.
+--
+-- Probabilities are fractions in [0, 1] derived from indexed stake totals
+-- (stake on outcome / total stake on market).
+
+CREATE TABLE IF NOT EXISTS market_price_points (
+    market_id    BIGINT              NOT NULL,
+    outcome      INTEGER             NOT NULL,
+    is_rollup    BOOLEAN             NOT NULL DEFAULT FALSE,
+    ts           TIMESTAMPTZ         NOT NULL,
+    open         DOUBLE PRECISION    NOT NULL,
+    high         DOUBLE PRECISION    NOT NULL,
+    low          DOUBLE PRECISION    NOT NULL,
+    close        DOUBLE PRECISION    NOT NULL,
+    PRIMARY KEY (market_id, outcome, is_rollup, ts)
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_price_points_market_ts
+    ON market_price_points (market_id, ts);
+
+-- Retention scans raw rows by age.
+CREATE INDEX IF NOT EXISTS idx_market_price_points_raw_ts
+    ON market_price_points (ts)
+    WHERE NOT is_rollup;
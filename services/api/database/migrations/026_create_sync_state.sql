@@ -0,0 +1,12 @@
+-- Authoritative chain sync cursor.
+--
+-- The cursor used to live only in Redis (chain:v1:sync_cursor:<network>), so a
+-- Redis flush restarted indexing from ledger 0. Postgres now holds the last
+-- ledger whose events are fully indexed; the Redis key is a cache of it.
+-- The cursor may move backwards when a reorg rewinds indexing.
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    network                TEXT           PRIMARY KEY,
+    last_confirmed_ledger  BIGINT         NOT NULL CHECK (last_confirmed_ledger >= 0),
+    updated_at             TIMESTAMPTZ    NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,31 @@
+-- Audit trail for oracle results pushed on-chain by the keeper endpoint
+-- (POST /api/v1/admin/oracle/:market_id/result).
+--
+-- Every attempt is kept. At most one live ('pending' or 'confirmed') row may
+-- exist per (market, oracle): that row is what makes a repeated submission a
+-- no-op and a conflicting one a 409. A 'failed' row does not block a retry.
+
+CREATE TABLE IF NOT EXISTS oracle_submissions (
+    id                  BIGSERIAL      PRIMARY KEY,
+    market_id           BIGINT         NOT NULL,
+    oracle_id           INTEGER        NOT NULL,
+    outcome             INTEGER        NOT NULL CHECK (outcome >= 0),
+    source              TEXT           NOT NULL,
+    confidence_bps      INTEGER        NOT NULL CHECK (confidence_bps BETWEEN 0 AND 10000),
+    status              TEXT           NOT NULL DEFAULT 'pending'
+                                       CHECK (status IN ('pending', 'confirmed', 'failed')),
+    tx_hash             TEXT,
+    error               TEXT,
+    -- Set when attempt_oracle_resolution was invoked after the result landed.
+    resolution_tx_hash  TEXT,
+    resolution_error    TEXT,
+    created_at          TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ    NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_oracle_submissions_live
+    ON oracle_submissions (market_id, oracle_id)
+    WHERE status IN ('pending', 'confirmed');
+
+CREATE INDEX IF NOT EXISTS idx_oracle_submissions_market_created
+    ON oracle_submissions (market_id, created_at DESC);
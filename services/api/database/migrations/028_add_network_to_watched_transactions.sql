@@ -0,0 +1,12 @@
+-- Scope watched transactions by network.
+--
+-- One API process now serves several networks, each with its own tx monitor,
+-- so a client must restore only the hashes it registered. Rows written before
+-- this column existed have a NULL network and are restored by the primary
+-- network's client, which was the only one at the time.
+
+ALTER TABLE watched_transactions ADD COLUMN IF NOT EXISTS network TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_watched_transactions_network_pending
+    ON watched_transactions (network)
+    WHERE status = 'pending';
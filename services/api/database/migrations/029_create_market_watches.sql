@@ -0,0 +1,40 @@
+-- Market watches: email notifications when a watched market changes state.
+--
+-- A watch is one email address following one market on one network.
+-- notify_on lists the triggers the watcher wants: 'resolve' and 'dispute' are
+-- sent by the sync worker when it indexes the contract's resolv_fx and
+-- disp_file events; 'deadline' is stored for deadline reminders. The
+-- unsubscribe token is an opaque capability carried in every notification
+-- link; it only removes this one watch.
+--
+-- market_watch_notifications records which (watch, event) pairs have had an
+-- email enqueued. Its primary key is the dedupe: replays and re-syncs of the
+-- same event never email a watcher twice.
+
+CREATE TABLE IF NOT EXISTS market_watches (
+    id                 BIGSERIAL     PRIMARY KEY,
+    network            TEXT          NOT NULL,
+    market_id          BIGINT        NOT NULL REFERENCES markets(id) ON DELETE CASCADE,
+    email              VARCHAR(255)  NOT NULL,
+    address            TEXT,
+    notify_on          TEXT[]        NOT NULL DEFAULT '{resolve}'
+                       CHECK (notify_on <@ ARRAY['resolve', 'dispute', 'deadline']::TEXT[]
+                              AND cardinality(notify_on) > 0),
+    unsubscribe_token  TEXT          NOT NULL UNIQUE,
+    created_at         TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_market_watches_network_market_email UNIQUE (network, market_id, email)
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_watches_email
+    ON market_watches (email);
+
+CREATE TABLE IF NOT EXISTS market_watch_notifications (
+    watch_id      BIGINT       NOT NULL REFERENCES market_watches(id) ON DELETE CASCADE,
+    event_id      TEXT         NOT NULL,
+    email_job_id  UUID,
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (watch_id, event_id)
+);
@@ -0,0 +1,23 @@
+-- Postgres is the email queue.
+--
+-- The worker claims due rows with FOR UPDATE SKIP LOCKED, ordered by priority
+-- and scheduled_at; the partial index below serves that scan. A job that
+-- exhausts max_attempts moves to status 'dead_letter' in place, so the
+-- separate email_dead_letter_jobs copy is folded back into email_jobs and
+-- dropped. Jobs still listed in the old Redis sorted sets already have
+-- 'pending' rows here and are picked up without any replay.
+
+UPDATE email_jobs
+SET status = 'dead_letter', updated_at = NOW()
+WHERE status = 'failed'
+   OR id IN (SELECT id FROM email_dead_letter_jobs);
+
+DROP TABLE IF EXISTS email_dead_letter_jobs;
+
+CREATE INDEX IF NOT EXISTS idx_email_jobs_due
+    ON email_jobs (priority DESC, scheduled_at)
+    WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_email_jobs_dead_letter
+    ON email_jobs (failed_at DESC)
+    WHERE status = 'dead_letter';
@@ -0,0 +1,23 @@
+-- Contact form submissions move through new -> answered -> closed.
+--
+-- Earlier rows used free-form statuses; 'resolved' maps to 'closed' and
+-- anything else unrecognised goes back to 'new' so it is triaged again.
+-- answered_at is stamped on the transition to 'answered'; resolved_at keeps
+-- recording when a submission was closed.
+
+UPDATE contact_form_submissions SET status = 'closed', resolved_at = COALESCE(resolved_at, updated_at)
+WHERE status = 'resolved';
+
+UPDATE contact_form_submissions SET status = 'new'
+WHERE status NOT IN ('new', 'answered', 'closed');
+
+ALTER TABLE contact_form_submissions
+    ADD COLUMN IF NOT EXISTS answered_at TIMESTAMPTZ;
+
+ALTER TABLE contact_form_submissions
+    ADD CONSTRAINT chk_contact_status
+        CHECK (status IN ('new', 'answered', 'closed'));
+
+-- Admin triage lists open submissions newest first.
+CREATE INDEX IF NOT EXISTS idx_contact_form_submissions_status_submitted
+ON contact_form_submissions (status, submitted_at DESC);
@@ -0,0 +1,35 @@
+-- Waitlist referrals and invitations.
+--
+-- Every entry gets a referral_code to share; joining with someone's code
+-- records referred_by and adds to the referrer's priority_score. The queue is
+-- ordered by priority_score DESC, joined_at, id, and invited_at is stamped
+-- when an admin invites an entry off the front of it.
+
+ALTER TABLE waitlist_entries
+    ADD COLUMN IF NOT EXISTS referral_code VARCHAR(16),
+    ADD COLUMN IF NOT EXISTS referred_by UUID REFERENCES waitlist_entries(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS invited_at TIMESTAMPTZ;
+
+UPDATE waitlist_entries SET referral_code = UPPER(SUBSTR(MD5(id::TEXT), 1, 8))
+WHERE referral_code IS NULL;
+
+ALTER TABLE waitlist_entries ALTER COLUMN referral_code SET NOT NULL;
+
+ALTER TABLE waitlist_entries
+    ADD CONSTRAINT uq_waitlist_entries_referral_code UNIQUE (referral_code);
+
+UPDATE waitlist_entries SET status = 'pending'
+WHERE status NOT IN ('pending', 'invited', 'converted');
+
+ALTER TABLE waitlist_entries
+    ADD CONSTRAINT chk_waitlist_status
+        CHECK (status IN ('pending', 'invited', 'converted'));
+
+-- Position lookups and top-N invites walk pending entries in queue order.
+CREATE INDEX IF NOT EXISTS idx_waitlist_entries_queue
+ON waitlist_entries (priority_score DESC, joined_at, id)
+WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_waitlist_entries_referred_by
+ON waitlist_entries (referred_by)
+WHERE referred_by IS NOT NULL;
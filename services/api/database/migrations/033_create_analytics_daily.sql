@@ -0,0 +1,19 @@
+-- Daily analytics rollup.
+--
+-- One row per UTC day and event type, rebuilt from analytics_events by the
+-- API's rollup job. Re-running the rollup for a day overwrites that day's
+-- counts rather than adding to them.
+
+CREATE TABLE IF NOT EXISTS analytics_daily (
+    day DATE NOT NULL,
+    event_type VARCHAR(120) NOT NULL,
+    event_count BIGINT NOT NULL DEFAULT 0,
+    unique_sessions BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (day, event_type)
+);
+
+-- The rollup scans a few days of events by time and groups by type.
+CREATE INDEX IF NOT EXISTS idx_analytics_events_occurred_at_name
+ON analytics_events (occurred_at, event_name);
@@ -0,0 +1,19 @@
+-- Request context for admin audit entries.
+--
+-- audit_log recorded what an admin did (action, resource) but not the request
+-- behind it. route and method identify the endpoint, response_status the
+-- outcome, and request_summary the query and body fields that survive the
+-- audit middleware's allowlist; every other field is stored as "[REDACTED]".
+
+ALTER TABLE audit_log
+    ADD COLUMN IF NOT EXISTS route VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS method VARCHAR(10),
+    ADD COLUMN IF NOT EXISTS response_status SMALLINT,
+    ADD COLUMN IF NOT EXISTS request_summary JSONB;
+
+-- GET /api/v1/admin/audit filters by route prefix, newest first.
+CREATE INDEX IF NOT EXISTS idx_audit_log_route_time
+    ON audit_log (route text_pattern_ops, timestamp DESC);
+
+COMMENT ON COLUMN audit_log.route IS 'Request path, e.g. /api/v1/admin/contact/<id>/status';
+COMMENT ON COLUMN audit_log.request_summary IS 'Allowlisted query and body fields; others redacted';
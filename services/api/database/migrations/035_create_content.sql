@@ -0,0 +1,18 @@
+-- Published content shown by GET /api/v1/content.
+--
+-- The API has always read this table, but no migration created it; its
+-- index sat in 012_add_performance_indexes.sql and failed on fresh databases.
+
+CREATE TABLE IF NOT EXISTS content (
+    id BIGSERIAL PRIMARY KEY,
+    title VARCHAR(220) NOT NULL,
+    category VARCHAR(80) NOT NULL,
+    is_published BOOLEAN NOT NULL DEFAULT FALSE,
+    published_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_content_published_at CHECK (NOT is_published OR published_at IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_content_published_at
+ON content (is_published, published_at DESC);
@@ -0,0 +1,21 @@
+-- Market categories listed by GET /api/v1/categories.
+--
+-- markets.category holds a slug from this table. There is no foreign key:
+-- markets are indexed from chain events and may name a category before an
+-- admin has created it; the API rejects unknown slugs at query time instead.
+-- Slugs already used by markets are seeded with the slug as their name.
+
+CREATE TABLE IF NOT EXISTS categories (
+    slug VARCHAR(64) PRIMARY KEY,
+    name VARCHAR(120) NOT NULL,
+    is_featured BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_categories_slug CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$')
+);
+
+INSERT INTO categories (slug, name)
+SELECT DISTINCT category, category
+FROM markets
+WHERE category ~ '^[a-z0-9]+(-[a-z0-9]+)*$' AND LENGTH(category) <= 64
+ON CONFLICT (slug) DO NOTHING;
@@ -0,0 +1,21 @@
+-- Daily platform statistics for the landing-page growth chart.
+--
+-- One row per UTC day, rebuilt by the API's nightly rollup from markets and
+-- the indexed chain_events of the primary network. The first run backfills
+-- every day since the earliest market or event; re-running a day overwrites
+-- its values rather than adding to them. Volume is in stroops, NUMERIC like
+-- chain_events.amount.
+
+CREATE TABLE IF NOT EXISTS platform_stats_daily (
+    day DATE PRIMARY KEY,
+    markets_created BIGINT NOT NULL DEFAULT 0,
+    volume NUMERIC(39, 0) NOT NULL DEFAULT 0,
+    new_participants BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- First-bet lookups group bet_place events by address.
+CREATE INDEX IF NOT EXISTS idx_chain_events_kind_address_indexed_at
+ON chain_events (network, kind, address, indexed_at)
+WHERE address IS NOT NULL;
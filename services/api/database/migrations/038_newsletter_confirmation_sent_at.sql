@@ -0,0 +1,14 @@
+-- When the current confirmation token was emailed. Tokens expire a fixed
+-- window after this (NEWSLETTER_TOKEN_TTL_SECS), and a resend is allowed once
+-- the last send is 15 minutes old. Unlike created_at, a resend moves it.
+ALTER TABLE newsletter_subscribers
+    ADD COLUMN IF NOT EXISTS confirmation_sent_at TIMESTAMPTZ;
+
+UPDATE newsletter_subscribers
+SET confirmation_sent_at = created_at
+WHERE confirmation_token IS NOT NULL AND confirmation_sent_at IS NULL;
+
+-- Cleanup purges pending rows (those still holding a token) by send time.
+CREATE INDEX IF NOT EXISTS idx_newsletter_subscribers_pending_sent_at
+    ON newsletter_subscribers (confirmation_sent_at)
+    WHERE confirmation_token IS NOT NULL;
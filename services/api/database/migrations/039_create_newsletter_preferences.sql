@@ -0,0 +1,25 @@
+-- Per-category opt-outs for newsletter subscribers, edited through
+-- GET/PUT /api/v1/newsletter/preferences.
+--
+-- Keyed by email rather than subscriber id so the enqueue-time check also
+-- covers addresses that reach us another way (market watches). An address
+-- with no row for a category receives it; confirming a subscription writes
+-- every category as enabled and unsubscribe-all writes every one as disabled.
+
+CREATE TABLE IF NOT EXISTS newsletter_preferences (
+    email VARCHAR(255) NOT NULL,
+    category VARCHAR(32) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (email, category),
+    CONSTRAINT chk_newsletter_preferences_category CHECK (category IN ('product', 'markets', 'digest'))
+);
+
+-- Existing subscribers keep receiving everything they get today; anyone who
+-- already unsubscribed stays opted out of every category.
+INSERT INTO newsletter_preferences (email, category, enabled)
+SELECT s.email, c.category, s.confirmed AND s.unsubscribed_at IS NULL
+FROM newsletter_subscribers s
+CROSS JOIN (VALUES ('product'), ('markets'), ('digest')) AS c(category)
+WHERE s.deleted_at IS NULL AND (s.confirmed OR s.unsubscribed_at IS NOT NULL)
+ON CONFLICT (email, category) DO NOTHING;
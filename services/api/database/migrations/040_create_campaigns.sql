@@ -0,0 +1,40 @@
+-- Bulk newsletter campaigns, created and launched through
+-- /api/v1/admin/campaigns.
+--
+-- A launched campaign is `sending` while the campaign sender enqueues its
+-- audience in batches; `cursor` is the last subscriber email enqueued (the
+-- audience is walked in email order) and moves in the same transaction as the
+-- batch insert. `completed` means every batch is enqueued, not delivered; the
+-- email worker bumps sent/failed/skipped as the jobs finish.
+
+CREATE TABLE IF NOT EXISTS campaigns (
+    id BIGSERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL,
+    template_name VARCHAR(100) NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}'::JSONB,
+    status VARCHAR(20) NOT NULL DEFAULT 'draft',
+    total_recipients INTEGER NOT NULL DEFAULT 0,
+    enqueued_count INTEGER NOT NULL DEFAULT 0,
+    sent_count INTEGER NOT NULL DEFAULT 0,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    skipped_count INTEGER NOT NULL DEFAULT 0,
+    cursor VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    launched_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    cancelled_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT chk_campaigns_status CHECK (status IN ('draft', 'sending', 'completed', 'cancelled'))
+);
+
+-- The sender polls for the oldest campaign that is still sending.
+CREATE INDEX IF NOT EXISTS idx_campaigns_sending
+    ON campaigns (launched_at)
+    WHERE status = 'sending';
+
+ALTER TABLE email_jobs
+    ADD COLUMN IF NOT EXISTS campaign_id BIGINT REFERENCES campaigns(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_email_jobs_campaign_id
+    ON email_jobs (campaign_id)
+    WHERE campaign_id IS NOT NULL;
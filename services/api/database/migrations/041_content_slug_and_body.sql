@@ -0,0 +1,27 @@
+-- Slugs and markdown bodies for content managed through
+-- /api/v1/admin/content. GET /api/v1/content/:slug looks rows up by slug and
+-- renders the body to sanitized HTML.
+--
+-- Existing rows get a slug derived from the title, suffixed with the id so it
+-- is unique, and an empty body.
+ALTER TABLE content
+    ADD COLUMN IF NOT EXISTS slug VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS body TEXT NOT NULL DEFAULT '';
+
+UPDATE content
+SET slug = COALESCE(
+        NULLIF(TRIM(BOTH '-' FROM LEFT(LOWER(REGEXP_REPLACE(title, '[^A-Za-z0-9]+', '-', 'g')), 40)), ''),
+        'content'
+    ) || '-' || id
+WHERE slug IS NULL;
+
+-- LEFT() can leave a trailing hyphen before the suffix; collapse it.
+UPDATE content SET slug = REGEXP_REPLACE(slug, '-+', '-', 'g') WHERE slug ~ '--';
+
+ALTER TABLE content ALTER COLUMN slug SET NOT NULL;
+
+ALTER TABLE content
+    DROP CONSTRAINT IF EXISTS chk_content_slug,
+    ADD CONSTRAINT chk_content_slug CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$');
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_content_slug ON content (slug);
@@ -0,0 +1,21 @@
+-- Per-key usage metering and monthly quotas for database-backed API keys.
+--
+-- `monthly_quota` caps the requests a key may make per calendar month (UTC);
+-- NULL means unlimited. Requests are counted in Redis and flushed here hourly
+-- by the usage flusher, one row per key per day. `requests` is the running
+-- total for that day, so a flush overwrites rather than adds.
+
+ALTER TABLE api_keys
+    ADD COLUMN IF NOT EXISTS monthly_quota BIGINT;
+
+ALTER TABLE api_keys
+    DROP CONSTRAINT IF EXISTS chk_api_keys_monthly_quota,
+    ADD CONSTRAINT chk_api_keys_monthly_quota CHECK (monthly_quota IS NULL OR monthly_quota >= 0);
+
+CREATE TABLE IF NOT EXISTS api_key_usage (
+    key_id UUID NOT NULL REFERENCES api_keys(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    requests BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (key_id, day)
+);
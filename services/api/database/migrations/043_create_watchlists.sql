@@ -0,0 +1,17 @@
+-- Watchlists: markets a Stellar account has starred.
+--
+-- One row per (address, market). Writes are authorised by a signed wallet
+-- challenge in the API, so address is always an account the caller proved
+-- control of. Soft-deleted markets stay in the table but are filtered out of
+-- listings; hard-deleted markets cascade.
+
+CREATE TABLE IF NOT EXISTS watchlists (
+    address     TEXT         NOT NULL,
+    market_id   BIGINT       NOT NULL REFERENCES markets(id) ON DELETE CASCADE,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_watchlists_address_market UNIQUE (address, market_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_watchlists_address_created
+    ON watchlists (address, created_at DESC);
@@ -0,0 +1,13 @@
+-- Cover images for markets, uploaded through
+-- POST /api/v1/admin/markets/:id/image.
+--
+-- image_key locates the bytes in the configured media storage (local
+-- directory or S3-compatible bucket). Keys are content-addressed, so a new
+-- upload gets a new key and image_url, and the old object is deleted.
+-- image_url is what clients render; it carries a version parameter so the
+-- long-lived cache headers on GET /api/v1/markets/:id/image are safe.
+ALTER TABLE markets
+    ADD COLUMN IF NOT EXISTS image_key          TEXT,
+    ADD COLUMN IF NOT EXISTS image_content_type TEXT,
+    ADD COLUMN IF NOT EXISTS image_etag         TEXT,
+    ADD COLUMN IF NOT EXISTS image_url          TEXT;
@@ -0,0 +1,14 @@
+-- Weekly digest send log.
+--
+-- One row per digest week, keyed by the Monday the week starts on. The row
+-- is inserted in the same transaction as the week's email_jobs, so a week
+-- either has a row and its jobs or neither: a restart (or a second
+-- instance) that finds the row does not send again.
+
+CREATE TABLE IF NOT EXISTS digest_sends (
+    week_start   DATE         PRIMARY KEY,
+    recipients   INTEGER      NOT NULL,
+    markets      INTEGER      NOT NULL,
+    resolutions  INTEGER      NOT NULL,
+    sent_at      TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
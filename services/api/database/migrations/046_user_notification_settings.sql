@@ -0,0 +1,39 @@
+-- Per-wallet email notifications about the wallet's own positions.
+--
+-- One row per Stellar account. The email is only used once it has been
+-- verified: setting or changing it clears email_verified_at and issues a
+-- confirmation token, redeemed within the newsletter confirmation window.
+-- The three toggles are opt-in and default off. The unsubscribe token is an
+-- opaque capability carried in every notification; it turns all three off.
+--
+-- user_notification_sends records which (address, event) pairs have had an
+-- email enqueued. Its primary key is the dedupe: an address gets at most one
+-- email per indexed event, and replays never send a second.
+
+CREATE TABLE IF NOT EXISTS user_notification_settings (
+    address               TEXT          PRIMARY KEY,
+    email                 VARCHAR(255),
+    email_verified_at     TIMESTAMPTZ,
+    confirmation_token    TEXT          UNIQUE,
+    confirmation_sent_at  TIMESTAMPTZ,
+    market_resolved       BOOLEAN       NOT NULL DEFAULT FALSE,
+    claim_available       BOOLEAN       NOT NULL DEFAULT FALSE,
+    dispute_filed         BOOLEAN       NOT NULL DEFAULT FALSE,
+    unsubscribe_token     TEXT          NOT NULL UNIQUE,
+    created_at            TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+    updated_at            TIMESTAMPTZ   NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_notification_settings_email
+    ON user_notification_settings (email);
+
+CREATE TABLE IF NOT EXISTS user_notification_sends (
+    address       TEXT         NOT NULL REFERENCES user_notification_settings(address) ON DELETE CASCADE,
+    event_id      TEXT         NOT NULL,
+    kind          TEXT         NOT NULL
+                  CHECK (kind IN ('market_resolved', 'claim_available', 'dispute_filed')),
+    email_job_id  UUID,
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (address, event_id)
+);
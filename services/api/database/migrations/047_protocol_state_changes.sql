@@ -0,0 +1,20 @@
+-- Circuit breaker transitions, recorded by the sync worker from `cb_state`
+-- events.
+--
+-- One row per event; the event id is the key, so replays after a restart or
+-- a reorg rewind never record a transition twice. changed_at is the close
+-- time of the event's ledger as the RPC reported it. Rows above a rewind
+-- ledger are deleted along with the chain_events they came with.
+
+CREATE TABLE IF NOT EXISTS protocol_state_changes (
+    event_id    TEXT         PRIMARY KEY,
+    network     TEXT         NOT NULL,
+    ledger      BIGINT       NOT NULL,
+    state       TEXT         NOT NULL
+                CHECK (state IN ('closed', 'open', 'half_open', 'paused')),
+    changed_at  TIMESTAMPTZ  NOT NULL,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_protocol_state_changes_network_ledger
+    ON protocol_state_changes (network, ledger DESC);
@@ -0,0 +1,29 @@
+-- Subscriptions to a watched transaction reaching a final status.
+--
+-- `POST /api/v1/blockchain/tx/:hash/watch` adds one row per subscriber; the
+-- hash itself is polled once however many rows point at it. The monitor
+-- claims a row by setting notified_at, so each subscriber is notified at
+-- most once even with several API instances polling. secret signs the
+-- webhook payload and is returned to the subscriber only at creation. Rows
+-- are deleted once expires_at passes, notified or not.
+
+CREATE TABLE IF NOT EXISTS tx_watch_subscriptions (
+    id            UUID         PRIMARY KEY,
+    network       TEXT         NOT NULL,
+    tx_hash       TEXT         NOT NULL,
+    callback_url  TEXT,
+    email         TEXT,
+    secret        TEXT         NOT NULL,
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    expires_at    TIMESTAMPTZ  NOT NULL,
+    notified_at   TIMESTAMPTZ,
+    CONSTRAINT tx_watch_subscriptions_target_check
+        CHECK (callback_url IS NOT NULL OR email IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_tx_watch_subscriptions_pending
+    ON tx_watch_subscriptions (network, tx_hash)
+    WHERE notified_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tx_watch_subscriptions_expires_at
+    ON tx_watch_subscriptions (expires_at);
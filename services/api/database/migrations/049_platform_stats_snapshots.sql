@@ -0,0 +1,25 @@
+-- Platform statistics derived from indexed data, for fallback and audit.
+--
+-- An hourly API task counts markets (the markets table plus mkt_creat
+-- events), sums bet_place volume from chain_events overall and per token,
+-- and stores the result with the ledger the indexer had confirmed at the
+-- time. When the contract's own statistics cannot be read,
+-- GET /api/v1/blockchain/stats serves the newest row instead. The same task
+-- compares both and alerts when they diverge. token_volumes is a JSON array
+-- of {"token", "volume"}; a market with no recorded token has "token": null.
+-- Rows older than 90 days are deleted by the task.
+
+CREATE TABLE IF NOT EXISTS platform_stats_snapshots (
+    id                BIGSERIAL      PRIMARY KEY,
+    network           TEXT           NOT NULL,
+    ledger            BIGINT         NOT NULL,
+    total_markets     BIGINT         NOT NULL,
+    active_markets    BIGINT         NOT NULL,
+    resolved_markets  BIGINT         NOT NULL,
+    total_volume      NUMERIC(39, 0) NOT NULL,
+    token_volumes     JSONB          NOT NULL DEFAULT '[]'::JSONB,
+    created_at        TIMESTAMPTZ    NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_platform_stats_snapshots_network_created_at
+    ON platform_stats_snapshots (network, created_at DESC);
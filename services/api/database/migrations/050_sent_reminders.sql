@@ -0,0 +1,16 @@
+-- Resolution deadline reminders sent to market creators.
+--
+-- One row per (market, lead time). The row is claimed before the reminder
+-- job is enqueued, so a creator gets at most one reminder per lead-time
+-- bucket however often the scheduler runs and however many instances run
+-- it. The claim is only taken while the market is still unresolved. No
+-- email is stored here; email_job_id leads to the job that was sent.
+
+CREATE TABLE IF NOT EXISTS sent_reminders (
+    market_id     BIGINT        NOT NULL REFERENCES markets(id) ON DELETE CASCADE,
+    lead_hours    INTEGER       NOT NULL CHECK (lead_hours > 0),
+    email_job_id  UUID,
+    sent_at       TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+
+    PRIMARY KEY (market_id, lead_hours)
+);
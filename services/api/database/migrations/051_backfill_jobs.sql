@@ -0,0 +1,40 @@
+-- Historical event backfills (POST /api/v1/admin/indexer/backfill).
+--
+-- The indexer started after the contract did, so events before the first
+-- sync cursor were never stored. A backfill job walks getEvents over
+-- [from_ledger, to_ledger] one page at a time and stores each page before
+-- recording its progress here: page_cursor is the RPC paging token to resume
+-- from. Inserts are idempotent, so resuming after a crash only repeats the
+-- unrecorded page; events_inserted counts rows actually added, leaving out
+-- events the live sync had already stored.
+--
+-- Jobs are run by a background task on whichever instance holds the lease.
+-- lease_until is pushed forward after every page; a job whose holder died is
+-- picked up again once it lapses. After the last page the job reconciles the
+-- derived tables ('reconciling') and then ends 'completed'.
+
+CREATE TABLE IF NOT EXISTS backfill_jobs (
+    id              BIGSERIAL      PRIMARY KEY,
+    network         TEXT           NOT NULL,
+    from_ledger     BIGINT         NOT NULL CHECK (from_ledger > 0),
+    to_ledger       BIGINT         NOT NULL,
+    status          TEXT           NOT NULL DEFAULT 'pending'
+                                   CHECK (status IN ('pending', 'running', 'reconciling', 'completed')),
+    page_cursor     TEXT,
+    -- Highest ledger seen so far, for progress reporting.
+    last_ledger     BIGINT,
+    pages           BIGINT         NOT NULL DEFAULT 0,
+    events_scanned  BIGINT         NOT NULL DEFAULT 0,
+    events_inserted BIGINT         NOT NULL DEFAULT 0,
+    -- The most recent failure; the job is retried on the next poll.
+    last_error      TEXT,
+    lease_until     TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ    NOT NULL DEFAULT NOW(),
+    completed_at    TIMESTAMPTZ,
+    CHECK (to_ledger >= from_ledger)
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_unfinished
+    ON backfill_jobs (id)
+    WHERE status <> 'completed';
@@ -0,0 +1,25 @@
+-- Signup source attribution (GET /api/v1/admin/analytics/acquisition).
+--
+-- Sources are now stored trimmed, lowercased and cut to 64 characters, with
+-- 'direct' for signups that named none (crate::acquisition::normalize_source).
+-- Rows written before that are normalized the same way here, so older
+-- 'Twitter' and 'twitter ' signups count towards one campaign. LEFT and LOWER
+-- work on characters, as the Rust side does.
+--
+-- The report filters on signup time and groups by source; these indexes
+-- serve both from the index alone.
+
+UPDATE newsletter_subscribers
+SET source = COALESCE(NULLIF(LEFT(LOWER(BTRIM(source)), 64), ''), 'direct')
+WHERE source IS DISTINCT FROM COALESCE(NULLIF(LEFT(LOWER(BTRIM(source)), 64), ''), 'direct');
+
+UPDATE waitlist_entries
+SET source = COALESCE(NULLIF(LEFT(LOWER(BTRIM(source)), 64), ''), 'direct')
+WHERE source IS DISTINCT FROM COALESCE(NULLIF(LEFT(LOWER(BTRIM(source)), 64), ''), 'direct');
+
+CREATE INDEX IF NOT EXISTS idx_newsletter_subscribers_created_at_source
+    ON newsletter_subscribers (created_at, source)
+    WHERE deleted_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_waitlist_entries_joined_at_source
+    ON waitlist_entries (joined_at, source);
@@ -0,0 +1,34 @@
+-- Email domains refused by the signup endpoints (newsletter, waitlist,
+-- contact, and the notification email fields).
+--
+-- A pattern is a lowercase domain in which `*` stands for any run of
+-- characters, as `%` does in LIKE: `*.tempmail.*` blocks `x.tempmail.org`
+-- but not `tempmail.org` itself. Matching happens in the API against a
+-- cached copy of this table (crate::signup_guard::is_blocked), so there is
+-- no index beyond the unique one.
+--
+-- Seeded with the domains that used to be hard-coded plus a few of the
+-- busiest disposable providers; admins manage the rest through
+-- /api/v1/admin/email/blocked-domains.
+
+CREATE TABLE IF NOT EXISTS blocked_email_domains (
+    id          BIGSERIAL     PRIMARY KEY,
+    pattern     VARCHAR(253)  NOT NULL UNIQUE,
+    note        TEXT,
+    created_at  TIMESTAMPTZ   NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_blocked_email_domains_pattern CHECK (pattern ~ '^[a-z0-9*.-]+$')
+);
+
+INSERT INTO blocked_email_domains (pattern, note)
+VALUES
+    ('mailinator.com', 'seed'),
+    ('*.mailinator.com', 'seed'),
+    ('tempmail.com', 'seed'),
+    ('*.tempmail.*', 'seed'),
+    ('guerrillamail.*', 'seed'),
+    ('sharklasers.com', 'seed'),
+    ('10minutemail.com', 'seed'),
+    ('yopmail.com', 'seed'),
+    ('trashmail.com', 'seed')
+ON CONFLICT (pattern) DO NOTHING;
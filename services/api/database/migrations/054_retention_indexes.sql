@@ -0,0 +1,17 @@
+-- Indexes for the nightly retention task (src/retention.rs).
+--
+-- Each retention batch selects rows older than a cutoff on the policy's age
+-- column. Without an index on that column every batch is a sequential scan
+-- of a table that, by the nature of the task, is large. The default
+-- policies' columns on analytics_events and contact_form_submissions are
+-- already indexed; these cover the rest, plus chain_events.indexed_at for
+-- operators who add a policy for it.
+
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_email_events_created_at
+ON email_events (created_at);
+
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_watched_transactions_created_at
+ON watched_transactions (created_at);
+
+CREATE INDEX CONCURRENTLY IF NOT EXISTS idx_chain_events_indexed_at
+ON chain_events (indexed_at);
@@ -0,0 +1,33 @@
+-- Market risk alerts.
+--
+-- A background API task evaluates the bet exposure of every active market
+-- (from the indexed bet_place events) and keeps one open row per market and
+-- condition that currently exceeds its threshold: 'concentration' (the top
+-- five bettors' share of the stake) or 'inflow' (stake placed in the last
+-- hour as a share of the total). A row is cleared, not deleted, when the
+-- condition stops holding, so the partial unique index allows a new alert
+-- for the same market later. notified_at is set once the alert has been
+-- pushed to ALERT_WEBHOOK_URL; rows without it are retried.
+
+CREATE TABLE IF NOT EXISTS risk_alerts (
+    id            BIGSERIAL          PRIMARY KEY,
+    network       TEXT               NOT NULL,
+    market_id     BIGINT             NOT NULL,
+    kind          TEXT               NOT NULL
+                  CONSTRAINT risk_alerts_kind_check
+                      CHECK (kind IN ('concentration', 'inflow')),
+    value         DOUBLE PRECISION   NOT NULL,
+    threshold     DOUBLE PRECISION   NOT NULL,
+    total_staked  NUMERIC(39, 0)     NOT NULL,
+    opened_at     TIMESTAMPTZ        NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ        NOT NULL DEFAULT NOW(),
+    notified_at   TIMESTAMPTZ,
+    cleared_at    TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_risk_alerts_open
+    ON risk_alerts (network, market_id, kind)
+    WHERE cleared_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_risk_alerts_opened_at
+    ON risk_alerts (opened_at DESC);
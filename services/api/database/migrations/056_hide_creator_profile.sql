@@ -0,0 +1,8 @@
+-- Lets a creator hide their public profile.
+--
+-- GET /api/v1/creators/:address answers 404 for an address with
+-- hide_creator_profile set, and GET /api/v1/creators/top leaves it out. The
+-- flag lives with the notification settings because that is the one row a
+-- wallet already saves for itself; it does not affect any email.
+ALTER TABLE user_notification_settings
+    ADD COLUMN IF NOT EXISTS hide_creator_profile BOOLEAN NOT NULL DEFAULT FALSE;
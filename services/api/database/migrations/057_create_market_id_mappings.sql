@@ -0,0 +1,25 @@
+-- Public market ids and the chain ids they stand for on each network.
+--
+-- Each network's contract numbers its markets from 1, so testnet and mainnet
+-- ids drift apart, and every contract redeploy (a testnet reset) starts over.
+-- The API and the CMS refer to a market by one public id, its markets.id on
+-- the primary network; a row binds that id to the market's chain id on one
+-- network, under the contract deployed there. Blockchain reads resolve the
+-- public id through this table and answer 404 when the requested network has
+-- no row for it, or only one recorded against another contract.
+--
+-- The API maps primary-network markets to themselves from their mkt_creat
+-- events; mappings on other networks are bound by an admin. A chain market
+-- stands for at most one public id.
+
+CREATE TABLE IF NOT EXISTS market_id_mappings (
+    internal_id      BIGINT       NOT NULL,
+    network          TEXT         NOT NULL,
+    chain_market_id  BIGINT       NOT NULL,
+    contract_id      TEXT         NOT NULL,
+    created_at       TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (internal_id, network)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_market_id_mappings_chain
+    ON market_id_mappings (network, contract_id, chain_market_id);
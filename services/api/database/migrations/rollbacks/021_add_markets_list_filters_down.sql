@@ -0,0 +1,14 @@
+-- Rollback for 021_add_markets_list_filters.sql
+-- Drops the market list indexes and the category, creator, and
+-- participant_count columns. Any values stored in those columns are discarded.
+
+DROP INDEX IF EXISTS idx_markets_creator;
+DROP INDEX IF EXISTS idx_markets_category;
+DROP INDEX IF EXISTS idx_markets_list_participants;
+DROP INDEX IF EXISTS idx_markets_list_created_at;
+DROP INDEX IF EXISTS idx_markets_list_ends_at;
+DROP INDEX IF EXISTS idx_markets_list_volume;
+ALTER TABLE markets
+    DROP COLUMN IF EXISTS participant_count,
+    DROP COLUMN IF EXISTS creator,
+    DROP COLUMN IF EXISTS category;
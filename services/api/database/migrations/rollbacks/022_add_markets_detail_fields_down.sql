@@ -0,0 +1,9 @@
+-- Rollback for 022_add_markets_detail_fields.sql
+-- Drops the description, outcome_options, and outcome_odds columns.
+-- Any values stored in those columns are discarded.
+
+ALTER TABLE markets DROP CONSTRAINT IF EXISTS chk_markets_description_length;
+ALTER TABLE markets
+    DROP COLUMN IF EXISTS outcome_odds,
+    DROP COLUMN IF EXISTS outcome_options,
+    DROP COLUMN IF EXISTS description;
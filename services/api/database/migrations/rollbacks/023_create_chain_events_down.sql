@@ -0,0 +1,6 @@
+-- Rollback for 023_create_chain_events.sql
+-- Drops the indexed events table and markets.token. Indexed events can be
+-- rebuilt by replaying the sync worker from an earlier ledger.
+
+ALTER TABLE markets DROP COLUMN IF EXISTS token;
+DROP TABLE IF EXISTS chain_events;
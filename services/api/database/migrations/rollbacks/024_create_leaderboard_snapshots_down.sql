@@ -0,0 +1,5 @@
+-- Rollback for 024_create_leaderboard_snapshots.sql
+-- Snapshots are derived data; the next aggregation run rebuilds them.
+
+DROP INDEX IF EXISTS idx_chain_events_kind_indexed_at;
+DROP TABLE IF EXISTS leaderboard_snapshots;
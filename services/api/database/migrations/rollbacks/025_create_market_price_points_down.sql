@@ -0,0 +1,5 @@
+-- Rollback for 025_create_market_price_points.sql
+-- Price history cannot be rebuilt once dropped; only stake totals at the
+-- time of a future sample are recoverable from chain_events.
+
+DROP TABLE IF EXISTS market_price_points;
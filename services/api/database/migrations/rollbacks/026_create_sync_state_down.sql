@@ -0,0 +1,5 @@
+-- Rollback for 026_create_sync_state.sql
+-- The worker falls back to the Redis cursor when this table is absent only
+-- after the code change is also rolled back.
+
+DROP TABLE IF EXISTS sync_state;
@@ -0,0 +1,4 @@
+-- Rollback for 027_create_oracle_submissions.sql
+-- Drops the keeper audit trail; export it first if it is still needed.
+
+DROP TABLE IF EXISTS oracle_submissions;
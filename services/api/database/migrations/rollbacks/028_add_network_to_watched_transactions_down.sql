@@ -0,0 +1,6 @@
+-- Rollback for 028_add_network_to_watched_transactions.sql
+-- Only safe once a single network is served again: every pending row is then
+-- restored by that network's client.
+
+DROP INDEX IF EXISTS idx_watched_transactions_network_pending;
+ALTER TABLE watched_transactions DROP COLUMN IF EXISTS network;
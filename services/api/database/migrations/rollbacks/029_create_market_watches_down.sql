@@ -0,0 +1,5 @@
+-- Rollback for 029_create_market_watches.sql
+-- Drops every watch; watchers must re-subscribe after a roll-forward.
+
+DROP TABLE IF EXISTS market_watch_notifications;
+DROP TABLE IF EXISTS market_watches;
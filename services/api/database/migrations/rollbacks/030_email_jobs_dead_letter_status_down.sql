@@ -0,0 +1,34 @@
+-- Rollback for 030_email_jobs_dead_letter_status.sql
+-- Restores the email_dead_letter_jobs copy from the dead-lettered rows and
+-- returns them to the old 'failed' status.
+
+CREATE TABLE IF NOT EXISTS email_dead_letter_jobs (
+    id              UUID        PRIMARY KEY,
+    payload         JSONB       NOT NULL,
+    failure_reason  TEXT        NOT NULL,
+    failed_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    retry_count     INTEGER     NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_dead_letter_jobs_failed_at
+    ON email_dead_letter_jobs (failed_at DESC);
+
+INSERT INTO email_dead_letter_jobs (id, payload, failure_reason, failed_at, retry_count)
+SELECT id,
+       jsonb_build_object(
+           'job_type', job_type,
+           'recipient_email', recipient_email,
+           'template_name', template_name,
+           'template_data', template_data
+       ),
+       COALESCE(error_message, ''),
+       COALESCE(failed_at, updated_at),
+       attempts
+FROM email_jobs
+WHERE status = 'dead_letter'
+ON CONFLICT (id) DO NOTHING;
+
+UPDATE email_jobs SET status = 'failed' WHERE status = 'dead_letter';
+
+DROP INDEX IF EXISTS idx_email_jobs_dead_letter;
+DROP INDEX IF EXISTS idx_email_jobs_due;
@@ -0,0 +1,4 @@
+-- Statuses rewritten by the up migration are not restored.
+DROP INDEX IF EXISTS idx_contact_form_submissions_status_submitted;
+ALTER TABLE contact_form_submissions DROP CONSTRAINT IF EXISTS chk_contact_status;
+ALTER TABLE contact_form_submissions DROP COLUMN IF EXISTS answered_at;
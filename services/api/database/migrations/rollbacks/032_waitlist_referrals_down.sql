@@ -0,0 +1,8 @@
+-- Statuses rewritten by the up migration are not restored.
+DROP INDEX IF EXISTS idx_waitlist_entries_referred_by;
+DROP INDEX IF EXISTS idx_waitlist_entries_queue;
+ALTER TABLE waitlist_entries DROP CONSTRAINT IF EXISTS chk_waitlist_status;
+ALTER TABLE waitlist_entries DROP CONSTRAINT IF EXISTS uq_waitlist_entries_referral_code;
+ALTER TABLE waitlist_entries DROP COLUMN IF EXISTS invited_at;
+ALTER TABLE waitlist_entries DROP COLUMN IF EXISTS referred_by;
+ALTER TABLE waitlist_entries DROP COLUMN IF EXISTS referral_code;
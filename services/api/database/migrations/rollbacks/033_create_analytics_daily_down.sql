@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_analytics_events_occurred_at_name;
+DROP TABLE IF EXISTS analytics_daily;
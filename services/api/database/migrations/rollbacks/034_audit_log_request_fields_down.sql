@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_audit_log_route_time;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS request_summary;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS response_status;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS method;
+ALTER TABLE audit_log DROP COLUMN IF EXISTS route;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_content_published_at;
+DROP TABLE IF EXISTS content;
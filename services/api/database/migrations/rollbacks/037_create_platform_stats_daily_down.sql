@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_chain_events_kind_address_indexed_at;
+DROP TABLE IF EXISTS platform_stats_daily;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_newsletter_subscribers_pending_sent_at;
+ALTER TABLE newsletter_subscribers DROP COLUMN IF EXISTS confirmation_sent_at;
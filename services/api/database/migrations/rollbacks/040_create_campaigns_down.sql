@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_email_jobs_campaign_id;
+ALTER TABLE email_jobs DROP COLUMN IF EXISTS campaign_id;
+DROP TABLE IF EXISTS campaigns;
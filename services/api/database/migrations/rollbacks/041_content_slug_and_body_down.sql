@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_content_slug;
+ALTER TABLE content DROP CONSTRAINT IF EXISTS chk_content_slug;
+ALTER TABLE content DROP COLUMN IF EXISTS body;
+ALTER TABLE content DROP COLUMN IF EXISTS slug;
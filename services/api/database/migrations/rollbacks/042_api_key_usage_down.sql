@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS api_key_usage;
+ALTER TABLE api_keys DROP CONSTRAINT IF EXISTS chk_api_keys_monthly_quota;
+ALTER TABLE api_keys DROP COLUMN IF EXISTS monthly_quota;
@@ -0,0 +1,5 @@
+ALTER TABLE markets
+    DROP COLUMN IF EXISTS image_url,
+    DROP COLUMN IF EXISTS image_etag,
+    DROP COLUMN IF EXISTS image_content_type,
+    DROP COLUMN IF EXISTS image_key;
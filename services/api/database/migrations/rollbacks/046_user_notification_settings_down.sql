@@ -0,0 +1,6 @@
+-- Rollback for 046_user_notification_settings.sql
+-- Drops every wallet's settings; users must opt in and verify again after a
+-- roll-forward.
+
+DROP TABLE IF EXISTS user_notification_sends;
+DROP TABLE IF EXISTS user_notification_settings;
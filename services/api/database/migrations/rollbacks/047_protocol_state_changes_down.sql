@@ -0,0 +1,5 @@
+-- Rollback for 047_protocol_state_changes.sql
+-- Drops the recorded history; a roll-forward only records transitions
+-- indexed after it.
+
+DROP TABLE IF EXISTS protocol_state_changes;
@@ -0,0 +1,5 @@
+-- Rollback for 048_tx_watch_subscriptions.sql
+-- Drops open subscriptions; their transactions are still watched, but no
+-- one is notified when they finalize.
+
+DROP TABLE IF EXISTS tx_watch_subscriptions;
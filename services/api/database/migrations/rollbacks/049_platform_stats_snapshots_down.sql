@@ -0,0 +1,5 @@
+-- Rollback for 049_platform_stats_snapshots.sql
+-- Drops the stored snapshots; platform statistics go back to failing when
+-- the contract read fails.
+
+DROP TABLE IF EXISTS platform_stats_snapshots;
@@ -0,0 +1,5 @@
+-- Rollback for 050_sent_reminders.sql
+-- Forgets which reminders were sent; markets still inside a lead-time
+-- bucket will be reminded again.
+
+DROP TABLE IF EXISTS sent_reminders;
@@ -0,0 +1,4 @@
+-- Rollback for 051_backfill_jobs.sql
+-- Drops the job history. Events already backfilled stay in chain_events.
+
+DROP TABLE IF EXISTS backfill_jobs;
@@ -0,0 +1,6 @@
+-- Rollback for 052_signup_source_attribution.sql
+-- Drops the report indexes. The source normalization is not reversible; the
+-- original spellings are gone.
+
+DROP INDEX IF EXISTS idx_newsletter_subscribers_created_at_source;
+DROP INDEX IF EXISTS idx_waitlist_entries_joined_at_source;
@@ -0,0 +1,5 @@
+-- Rollback for 053_blocked_email_domains.sql
+-- Drops the blocklist, including patterns added by admins. Roll the API back
+-- first: signups fail while it still reads the table.
+
+DROP TABLE IF EXISTS blocked_email_domains;
@@ -0,0 +1,6 @@
+-- Rollback for 054_retention_indexes.sql
+-- Drops the age-column indexes used by retention batches.
+
+DROP INDEX IF EXISTS idx_email_events_created_at;
+DROP INDEX IF EXISTS idx_watched_transactions_created_at;
+DROP INDEX IF EXISTS idx_chain_events_indexed_at;
@@ -0,0 +1,5 @@
+-- Rollback for 055_create_risk_alerts.sql
+-- Drops open and cleared risk alerts. Roll the API back first: the risk task
+-- and GET /api/v1/admin/risk/alerts fail while they still read the table.
+
+DROP TABLE IF EXISTS risk_alerts;
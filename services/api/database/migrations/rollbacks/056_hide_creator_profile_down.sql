@@ -0,0 +1,6 @@
+-- Rollback for 056_hide_creator_profile.sql
+-- Hidden creator profiles become public again. Roll the API back first: it
+-- reads and writes the column on every notification settings request.
+
+ALTER TABLE user_notification_settings
+    DROP COLUMN IF EXISTS hide_creator_profile;
@@ -0,0 +1,7 @@
+-- Rollback for 057_create_market_id_mappings.sql
+-- Drops every public-to-chain market id binding, including those bound by an
+-- admin, which have to be bound again after re-applying. Roll the API back
+-- first: every blockchain market read fails while it still resolves ids
+-- through the table.
+
+DROP TABLE IF EXISTS market_id_mappings;
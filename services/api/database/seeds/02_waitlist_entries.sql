@@ -1,6 +1,6 @@
-INSERT INTO waitlist_entries (email, status, source, priority_score, joined_at, converted_at)
+INSERT INTO waitlist_entries (email, status, source, priority_score, joined_at, converted_at, invited_at, referral_code)
 VALUES
-    ('earlybird@example.com', 'pending', 'landing-page', 90, NOW() - INTERVAL '3 days', NULL),
-    ('partnerlead@example.com', 'invited', 'partnership', 75, NOW() - INTERVAL '12 days', NULL),
-    ('converted@example.com', 'converted', 'ads', 55, NOW() - INTERVAL '30 days', NOW() - INTERVAL '5 days')
+    ('earlybird@example.com', 'pending', 'landing-page', 90, NOW() - INTERVAL '3 days', NULL, NULL, 'EARLYB1D'),
+    ('partnerlead@example.com', 'invited', 'partnership', 75, NOW() - INTERVAL '12 days', NULL, NOW() - INTERVAL '2 days', 'PARTNER7'),
+    ('converted@example.com', 'converted', 'ads', 55, NOW() - INTERVAL '30 days', NOW() - INTERVAL '5 days', NOW() - INTERVAL '8 days', 'CONVERT3')
 ON CONFLICT (email) DO NOTHING;
@@ -17,10 +17,12 @@
 # How it works:
 #   1. Ensures the schema_migrations tracking table exists (000_ migration).
 #   2. For each *.sql file in migrations/ (sorted by filename):
-#        a. Derives the version from the numeric prefix (e.g. "001").
+#        a. Derives the version from the numeric prefix (e.g. "001"), adding a
+#           letter for later files that share a prefix ("017b", "017c", ...).
 #        b. Skips the file if that version is already recorded.
 #        c. Computes a SHA-256 checksum of the file.
-#        d. Runs the SQL inside a transaction.
+#        d. Runs the SQL inside a transaction (statement by statement for
+#           CREATE INDEX CONCURRENTLY, which cannot run in one).
 #        e. Records the version + checksum in schema_migrations.
 #   3. Exits non-zero on any failure; the transaction is rolled back automatically.
 # =============================================================================
@@ -58,6 +60,30 @@ psql_exec() {
     psql "$DATABASE_URL" --no-psqlrc -v ON_ERROR_STOP=1 "$@"
 }
 
+# ---------------------------------------------------------------------------
+# Version for the next file in sorted order: its numeric prefix, plus a
+# letter for the second and later files sharing that prefix (017, 017b, ...).
+# Must match the versions embedded in src/migrations.rs. Sets $VERSION;
+# call reset_versions before each pass over the files.
+# ---------------------------------------------------------------------------
+reset_versions() {
+    PREV_PREFIX=""
+    DUP_INDEX=0
+}
+
+next_version() {
+    local prefix="${1%%_*}"
+    local letters="abcdefghijklmnopqrstuvwxyz"
+    if [[ "$prefix" == "$PREV_PREFIX" ]]; then
+        DUP_INDEX=$((DUP_INDEX + 1))
+        VERSION="${prefix}${letters:$DUP_INDEX:1}"
+    else
+        PREV_PREFIX="$prefix"
+        DUP_INDEX=0
+        VERSION="$prefix"
+    fi
+}
+
 # ---------------------------------------------------------------------------
 # Ensure tracking table exists
 # ---------------------------------------------------------------------------
@@ -93,14 +119,22 @@ apply_migration() {
 
     log "Applying [$version] $name ..."
 
-    # Wrap in a transaction so a partial failure rolls back cleanly
-    psql_exec -q <<SQL
+    if grep -q "CONCURRENTLY" "$file"; then
+        # CREATE INDEX CONCURRENTLY cannot run inside a transaction; these
+        # files are idempotent, so a partial failure is safe to re-run.
+        psql_exec -q -f "$file"
+        psql_exec -q -c "INSERT INTO schema_migrations (version, name, applied_at, checksum)
+VALUES ('$version', '$name', NOW(), '$checksum');"
+    else
+        # Wrap in a transaction so a partial failure rolls back cleanly
+        psql_exec -q <<SQL
 BEGIN;
 \i $file
 INSERT INTO schema_migrations (version, name, applied_at, checksum)
 VALUES ('$version', '$name', NOW(), '$checksum');
 COMMIT;
 SQL
+    fi
 
     log "  ✓ [$version] applied (checksum: ${checksum:0:12}...)"
 }
@@ -116,10 +150,12 @@ cmd_status() {
     printf "  %-8s %-50s %-26s %s\n" "-------" "----" "----------" "--------"
 
     local any_pending=false
+    reset_versions
     for file in $(ls "$MIGRATIONS_DIR"/*.sql 2>/dev/null | sort); do
         local fname version name
         fname="$(basename "$file")"
-        version="${fname%%_*}"
+        next_version "$fname"
+        version="$VERSION"
         name="${fname%.sql}"
 
         if is_applied "$version"; then
@@ -202,10 +238,12 @@ cmd_run() {
     local applied=0
     local skipped=0
 
+    reset_versions
     for file in $files; do
         local fname version
         fname="$(basename "$file")"
-        version="${fname%%_*}"
+        next_version "$fname"
+        version="$VERSION"
 
         if is_applied "$version"; then
             log "Skipping [$version] — already applied"
@@ -0,0 +1,244 @@
+//! Signup attribution for marketing.
+//!
+//! Newsletter and waitlist signups carry the `source` the client sent
+//! (`direct` when it sent none), normalized by [`normalize_source`] so
+//! `Twitter` and ` twitter ` count as one campaign. Migration 052 applied the
+//! same normalization to rows written before it.
+//!
+//! `GET /api/v1/admin/analytics/acquisition` counts the signups made in the
+//! window by source or by UTC day, and what became of them: newsletter
+//! confirmations and unsubscribes, waitlist conversions. Every count is over
+//! the window's signups, so the confirmation rate of a group is a cohort rate
+//! and a late confirmation is credited to the day of its signup.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::export::CsvRecord;
+
+/// Longest stored `source`; longer labels are cut to this many characters.
+pub const MAX_SOURCE_LEN: usize = 64;
+
+/// Source recorded for signups that name none.
+pub const DEFAULT_SOURCE: &str = "direct";
+
+/// Default and maximum `days` for the acquisition report.
+pub const DEFAULT_DAYS: u32 = 30;
+pub const MAX_DAYS: u32 = 365;
+
+/// `source` as stored: trimmed, lowercased and at most [`MAX_SOURCE_LEN`]
+/// characters, or [`DEFAULT_SOURCE`] when blank.
+pub fn normalize_source(source: Option<&str>) -> String {
+    let source = source.map(str::trim).unwrap_or_default();
+    if source.is_empty() {
+        return DEFAULT_SOURCE.to_string();
+    }
+    source.to_lowercase().chars().take(MAX_SOURCE_LEN).collect()
+}
+
+/// What the report's rows are keyed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AcquisitionGroupBy {
+    Source,
+    /// UTC day of the signup, `YYYY-MM-DD`.
+    Day,
+}
+
+impl AcquisitionGroupBy {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "source" => Some(Self::Source),
+            "day" => Some(Self::Day),
+            _ => None,
+        }
+    }
+}
+
+/// Signup counts for one source or day, as aggregated in SQL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AcquisitionCounts {
+    pub key: String,
+    pub newsletter_signups: i64,
+    pub confirmations: i64,
+    pub unsubscribes: i64,
+    pub waitlist_signups: i64,
+    pub waitlist_conversions: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AcquisitionRow {
+    /// The source, or the day for `group_by=day`.
+    pub key: String,
+    pub newsletter_signups: i64,
+    /// Newsletter signups that confirmed their address.
+    pub confirmations: i64,
+    /// `confirmations / newsletter_signups`, 0 when there were none.
+    pub confirmation_rate: f64,
+    /// Newsletter signups that have since unsubscribed.
+    pub unsubscribes: i64,
+    pub waitlist_signups: i64,
+    /// Waitlist signups that converted.
+    pub waitlist_conversions: i64,
+}
+
+impl From<AcquisitionCounts> for AcquisitionRow {
+    fn from(c: AcquisitionCounts) -> Self {
+        let confirmation_rate = if c.newsletter_signups > 0 {
+            // Four decimals is plenty for a dashboard and keeps CSVs tidy.
+            (c.confirmations as f64 / c.newsletter_signups as f64 * 10_000.0).round() / 10_000.0
+        } else {
+            0.0
+        };
+        Self {
+            key: c.key,
+            newsletter_signups: c.newsletter_signups,
+            confirmations: c.confirmations,
+            confirmation_rate,
+            unsubscribes: c.unsubscribes,
+            waitlist_signups: c.waitlist_signups,
+            waitlist_conversions: c.waitlist_conversions,
+        }
+    }
+}
+
+impl CsvRecord for AcquisitionRow {
+    const HEADER: &'static [&'static str] = &[
+        "key",
+        "newsletter_signups",
+        "confirmations",
+        "confirmation_rate",
+        "unsubscribes",
+        "waitlist_signups",
+        "waitlist_conversions",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.key.clone(),
+            self.newsletter_signups.to_string(),
+            self.confirmations.to_string(),
+            self.confirmation_rate.to_string(),
+            self.unsubscribes.to_string(),
+            self.waitlist_signups.to_string(),
+            self.waitlist_conversions.to_string(),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AcquisitionReport {
+    pub days: u32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: AcquisitionGroupBy,
+    /// By source, most signups first; by day, oldest first. Days without
+    /// signups are left out.
+    pub rows: Vec<AcquisitionRow>,
+    /// The whole window, keyed `total`.
+    pub totals: AcquisitionRow,
+}
+
+impl AcquisitionReport {
+    pub fn new(
+        days: u32,
+        (from, to): (NaiveDate, NaiveDate),
+        group_by: AcquisitionGroupBy,
+        counts: Vec<AcquisitionCounts>,
+    ) -> Self {
+        let mut total = AcquisitionCounts {
+            key: "total".to_string(),
+            ..Default::default()
+        };
+        for c in &counts {
+            total.newsletter_signups += c.newsletter_signups;
+            total.confirmations += c.confirmations;
+            total.unsubscribes += c.unsubscribes;
+            total.waitlist_signups += c.waitlist_signups;
+            total.waitlist_conversions += c.waitlist_conversions;
+        }
+        Self {
+            days,
+            from,
+            to,
+            group_by,
+            rows: counts.into_iter().map(AcquisitionRow::from).collect(),
+            totals: total.into(),
+        }
+    }
+}
+
+/// Half-open `[from, to + 1 day)` bounds of the report window.
+pub fn window_bounds((from, to): (NaiveDate, NaiveDate)) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = |d: NaiveDate| d.and_time(chrono::NaiveTime::MIN).and_utc();
+    (start(from), start(to + chrono::Days::new(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_are_trimmed_lowercased_and_truncated() {
+        assert_eq!(normalize_source(Some("  Twitter ")), "twitter");
+        assert_eq!(normalize_source(Some("Spring-Promo")), "spring-promo");
+        assert_eq!(normalize_source(Some("   ")), "direct");
+        assert_eq!(normalize_source(None), "direct");
+        let long = "É".repeat(MAX_SOURCE_LEN + 10);
+        assert_eq!(
+            normalize_source(Some(&long)),
+            "é".repeat(MAX_SOURCE_LEN),
+            "cut by characters, not bytes"
+        );
+    }
+
+    #[test]
+    fn confirmation_rate_is_rounded_and_zero_without_signups() {
+        let row = AcquisitionRow::from(AcquisitionCounts {
+            key: "twitter".into(),
+            newsletter_signups: 3,
+            confirmations: 2,
+            ..Default::default()
+        });
+        assert_eq!(row.confirmation_rate, 0.6667);
+
+        let waitlist_only = AcquisitionRow::from(AcquisitionCounts {
+            key: "ads".into(),
+            waitlist_signups: 4,
+            ..Default::default()
+        });
+        assert_eq!(waitlist_only.confirmation_rate, 0.0);
+    }
+
+    #[test]
+    fn totals_sum_the_rows() {
+        let counts = vec![
+            AcquisitionCounts {
+                key: "a".into(),
+                newsletter_signups: 2,
+                confirmations: 1,
+                unsubscribes: 1,
+                waitlist_signups: 1,
+                waitlist_conversions: 0,
+            },
+            AcquisitionCounts {
+                key: "b".into(),
+                newsletter_signups: 2,
+                confirmations: 2,
+                unsubscribes: 0,
+                waitlist_signups: 3,
+                waitlist_conversions: 2,
+            },
+        ];
+        let day = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        let report = AcquisitionReport::new(1, (day, day), AcquisitionGroupBy::Source, counts);
+        assert_eq!(report.totals.key, "total");
+        assert_eq!(report.totals.newsletter_signups, 4);
+        assert_eq!(report.totals.confirmation_rate, 0.75);
+        assert_eq!(report.totals.waitlist_conversions, 2);
+        assert_eq!(
+            window_bounds((day, day)).1.to_rfc3339(),
+            "2026-05-02T00:00:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod acquisition_tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::handlers::{analytics_acquisition, analytics_acquisition_csv};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded emails live in a reserved range so cleanup never touches rows
+    /// created by other tests or the seed data.
+    const EMAIL_PREFIX: &str = "acquisition-97";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/admin/analytics/acquisition", get(analytics_acquisition))
+            .route(
+                "/admin/analytics/acquisition.csv",
+                get(analytics_acquisition_csv),
+            )
+            .with_state(state)
+    }
+
+    async fn get_response(state: &Arc<crate::AppState>, uri: &str) -> (StatusCode, String) {
+        let response = app(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    /// Three sources unique to this run, so the report's other rows (from
+    /// anything else in the window) can be told apart. The third needs
+    /// quoting in CSV.
+    fn sources() -> [String; 3] {
+        let run = &uuid::Uuid::new_v4().simple().to_string()[..8];
+        [
+            format!("acq{run}-twitter"),
+            format!("acq{run}-blog"),
+            format!("acq{run}, \"spring\""),
+        ]
+    }
+
+    /// Twitter: 4 newsletter signups (3 confirmed, 1 of them unsubscribed),
+    /// 2 waitlist (1 converted). Blog: 2 newsletter (none confirmed) plus a
+    /// GDPR-deleted one, and a signup from before the window. Spring: 1
+    /// waitlist signup only.
+    async fn seed(state: &crate::AppState, [twitter, blog, spring]: &[String; 3]) {
+        sqlx::query(
+            "INSERT INTO newsletter_subscribers \
+                 (email, source, confirmed, created_at, confirmed_at, unsubscribed_at, deleted_at) \
+             VALUES \
+                 ($1 || 'n1-' || $2 || '@example.com', $2, TRUE,  NOW(), NOW(), NULL, NULL), \
+                 ($1 || 'n2-' || $2 || '@example.com', $2, TRUE,  NOW(), NOW(), NULL, NULL), \
+                 ($1 || 'n3-' || $2 || '@example.com', $2, TRUE,  NOW(), NOW(), NOW(), NULL), \
+                 ($1 || 'n4-' || $2 || '@example.com', $2, FALSE, NOW(), NULL, NULL, NULL), \
+                 ($1 || 'n5-' || $3 || '@example.com', $3, FALSE, NOW(), NULL, NULL, NULL), \
+                 ($1 || 'n6-' || $3 || '@example.com', $3, FALSE, NOW(), NULL, NULL, NULL), \
+                 ($1 || 'n7-' || $3 || '@example.com', $3, TRUE,  NOW(), NOW(), NULL, NOW()), \
+                 ($1 || 'n8-' || $3 || '@example.com', $3, TRUE,  NOW() - INTERVAL '60 days', NOW(), NULL, NULL)",
+        )
+        .bind(EMAIL_PREFIX)
+        .bind(twitter)
+        .bind(blog)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO waitlist_entries (email, status, source, referral_code) \
+             VALUES \
+                 ($1 || 'w1-' || $2 || '@example.com', 'converted', $2, UPPER(LEFT(MD5($1 || 'w1' || $2), 10))), \
+                 ($1 || 'w2-' || $2 || '@example.com', 'pending', $2, UPPER(LEFT(MD5($1 || 'w2' || $2), 10))), \
+                 ($1 || 'w3-' || $3 || '@example.com', 'invited', $3, UPPER(LEFT(MD5($1 || 'w3' || $3), 10)))",
+        )
+        .bind(EMAIL_PREFIX)
+        .bind(twitter)
+        .bind(spring)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{EMAIL_PREFIX}%@example.com");
+        for table in ["newsletter_subscribers", "waitlist_entries"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE email LIKE $1"))
+                .bind(&pattern)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    fn row<'a>(report: &'a Value, key: &str) -> &'a Value {
+        report["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["key"] == key)
+            .unwrap_or_else(|| panic!("no row for {key}"))
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Each source gets its own counts and cohort confirmation rate; deleted
+    /// subscribers and signups before the window are left out.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_report_counts_and_rates_by_source() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        let sources = sources();
+        seed(&state, &sources).await;
+
+        let (status, body) = get_response(&state, "/admin/analytics/acquisition?days=30").await;
+        assert_eq!(status, StatusCode::OK);
+        let report: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(report["group_by"], "source");
+        assert_eq!(report["days"], 30);
+
+        let twitter = row(&report, &sources[0]);
+        assert_eq!(twitter["newsletter_signups"], 4);
+        assert_eq!(twitter["confirmations"], 3);
+        assert_eq!(twitter["confirmation_rate"], 0.75);
+        assert_eq!(twitter["unsubscribes"], 1);
+        assert_eq!(twitter["waitlist_signups"], 2);
+        assert_eq!(twitter["waitlist_conversions"], 1);
+
+        let blog = row(&report, &sources[1]);
+        assert_eq!(
+            blog["newsletter_signups"], 2,
+            "deleted and old signups excluded"
+        );
+        assert_eq!(blog["confirmations"], 0);
+        assert_eq!(blog["confirmation_rate"], 0.0);
+
+        let spring = row(&report, &sources[2]);
+        assert_eq!(spring["newsletter_signups"], 0);
+        assert_eq!(spring["confirmation_rate"], 0.0);
+        assert_eq!(spring["waitlist_signups"], 1);
+
+        let rows = report["rows"].as_array().unwrap();
+        let position = |key: &str| rows.iter().position(|r| r["key"] == key).unwrap();
+        assert!(
+            position(&sources[0]) < position(&sources[1]),
+            "most signups first"
+        );
+        assert!(
+            report["totals"]["newsletter_signups"].as_i64().unwrap() >= 6,
+            "totals cover every row"
+        );
+
+        let (status, body) =
+            get_response(&state, "/admin/analytics/acquisition?group_by=day").await;
+        assert_eq!(status, StatusCode::OK);
+        let by_day: Value = serde_json::from_str(&body).unwrap();
+        let today = chrono::Utc::now().date_naive().to_string();
+        assert!(row(&by_day, &today)["newsletter_signups"].as_i64().unwrap() >= 6);
+
+        cleanup(&state).await;
+    }
+
+    /// The CSV carries the same rows, with a source that contains a comma
+    /// and quotes escaped per RFC 4180.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_csv_escapes_sources() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        let sources = sources();
+        seed(&state, &sources).await;
+
+        let response = app(Arc::clone(&state))
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/analytics/acquisition.csv?days=7")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let csv = String::from_utf8(bytes.to_vec()).unwrap();
+        let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "key,newsletter_signups,confirmations,confirmation_rate,unsubscribes,waitlist_signups,waitlist_conversions"
+        );
+        assert!(lines.contains(&format!("{},4,3,0.75,1,2,1", sources[0]).as_str()));
+        assert!(lines.contains(&format!("{},2,0,0,0,0,0", sources[1]).as_str()));
+        let escaped = format!("\"{}\",0,0,0,0,1,0", sources[2].replace('"', "\"\""));
+        assert!(lines.contains(&escaped.as_str()), "{csv}");
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rejects_bad_parameters() {
+        let state = build_test_state().await;
+
+        let (status, _) = get_response(&state, "/admin/analytics/acquisition?group_by=week").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get_response(&state, "/admin/analytics/acquisition.csv?days=0").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let (status, _) = get_response(&state, "/admin/analytics/acquisition?days=366").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
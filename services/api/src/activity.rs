@@ -0,0 +1,526 @@
+//! Governance activity feed for `GET /api/v1/activity`.
+//!
+//! Moderators follow disputes, votes, resolutions and guardian actions here
+//! instead of reading raw contract events. Each [`ActivityType`] covers one
+//! or more event names in `chain_events`, including the long names the
+//! resolution and cancellation modules publish alongside the short ones, and
+//! [`Activity::from_row`] turns a stored event into the fields that type
+//! carries: the disputer, the voted outcome and weight, the payout.
+//!
+//! The contract publishes no event when a vote crosses the resolution
+//! threshold, so the feed reports individual votes; a `dispute_resolved` or
+//! `resolution_finalized` entry follows once the outcome is settled.
+//!
+//! Bots poll with `since_id`: every event indexed after that id, oldest
+//! first, so the last one returned is the next `since_id`.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default and maximum entries per page.
+pub const DEFAULT_LIMIT: i64 = 20;
+pub const MAX_LIMIT: i64 = 100;
+
+/// Longest `since_id` accepted; event ids are well under this.
+const MAX_SINCE_ID_LEN: usize = 64;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityType {
+    DisputeFiled,
+    VoteCast,
+    /// The oracle reported an outcome; the dispute window opens.
+    OracleResolved,
+    /// Resolved without a dispute, or by the admin fallback. `payout` is
+    /// present when the contract reported it.
+    ResolutionFinalized,
+    /// A disputed market settled by the vote.
+    DisputeResolved,
+    MarketCancelled,
+    /// Upgrade proposals, votes and executions, and circuit breaker changes.
+    GuardianAction,
+}
+
+impl ActivityType {
+    pub const ALL: [Self; 7] = [
+        Self::DisputeFiled,
+        Self::VoteCast,
+        Self::OracleResolved,
+        Self::ResolutionFinalized,
+        Self::DisputeResolved,
+        Self::MarketCancelled,
+        Self::GuardianAction,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::DisputeFiled => "dispute_filed",
+            Self::VoteCast => "vote_cast",
+            Self::OracleResolved => "oracle_resolved",
+            Self::ResolutionFinalized => "resolution_finalized",
+            Self::DisputeResolved => "dispute_resolved",
+            Self::MarketCancelled => "market_cancelled",
+            Self::GuardianAction => "guardian_action",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.label() == raw)
+    }
+
+    /// The `chain_events.kind` values this type covers.
+    pub fn kinds(self) -> &'static [&'static str] {
+        match self {
+            Self::DisputeFiled => &["disp_file"],
+            Self::VoteCast => &["vote_cast"],
+            Self::OracleResolved => &["orcl_res", "oracle_resolved"],
+            Self::ResolutionFinalized => &["resolv_fx", "mkt_final", "market_finalized", "adm_fbk"],
+            Self::DisputeResolved => &["disp_res", "dispute_resolved"],
+            Self::MarketCancelled => &[
+                "mkt_cncl",
+                "market_cancelled",
+                "mk_cn_vt",
+                "market_cancelled_vote",
+            ],
+            Self::GuardianAction => &["upg_init", "upg_vote", "upg_exec", "upg_rej", "cb_state"],
+        }
+    }
+
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.kinds().contains(&kind))
+    }
+
+    /// Parse a comma-separated `types` filter; empty means every type.
+    pub fn parse_list(raw: Option<&str>) -> Result<Vec<Self>, String> {
+        let mut types = Vec::new();
+        for part in raw
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let ty = Self::parse(part).ok_or_else(|| {
+                let known: Vec<&str> = Self::ALL.iter().map(|t| t.label()).collect();
+                format!(
+                    "unknown activity type '{part}', expected one of {}",
+                    known.join(", ")
+                )
+            })?;
+            if !types.contains(&ty) {
+                types.push(ty);
+            }
+        }
+        if types.is_empty() {
+            types = Self::ALL.to_vec();
+        }
+        types.sort();
+        Ok(types)
+    }
+}
+
+/// Whether `raw` looks like an indexed event id.
+pub fn valid_since_id(raw: &str) -> bool {
+    !raw.is_empty()
+        && raw.len() <= MAX_SINCE_ID_LEN
+        && raw.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// What a feed query selects; see [`crate::db::Database::activity_page`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityFilter {
+    pub types: Vec<ActivityType>,
+    pub market_id: Option<i64>,
+    /// Only events indexed after this id, oldest first.
+    pub since_id: Option<String>,
+}
+
+impl ActivityFilter {
+    pub fn kinds(&self) -> Vec<String> {
+        self.types
+            .iter()
+            .flat_map(|t| t.kinds())
+            .map(|kind| kind.to_string())
+            .collect()
+    }
+}
+
+/// One `chain_events` row as the feed reads it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityRow {
+    pub id: String,
+    pub ledger: i64,
+    pub tx_hash: Option<String>,
+    pub kind: String,
+    pub market_id: Option<i64>,
+    pub address: Option<String>,
+    pub outcome: Option<i32>,
+    /// Integer amount in stroops, as a decimal string.
+    pub amount: Option<String>,
+    /// The recorded breaker state, for `cb_state` events.
+    pub breaker_state: Option<String>,
+    pub indexed_at: DateTime<Utc>,
+}
+
+/// The fields each activity type carries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ActivityDetails {
+    DisputeFiled {
+        disputer: Option<String>,
+    },
+    VoteCast {
+        voter: Option<String>,
+        outcome: Option<u32>,
+        /// Vote weight in stroops, as a decimal string.
+        weight: Option<String>,
+    },
+    OracleResolved {
+        oracle: Option<String>,
+        outcome: Option<u32>,
+    },
+    ResolutionFinalized {
+        resolver: Option<String>,
+        outcome: Option<u32>,
+        /// Total payout in stroops, as a decimal string.
+        payout: Option<String>,
+        /// Whether the admin fallback resolved the market.
+        admin_fallback: bool,
+    },
+    DisputeResolved {
+        resolver: Option<String>,
+        outcome: Option<u32>,
+    },
+    MarketCancelled {
+        cancelled_by: Option<String>,
+        /// Whether a governance vote cancelled the market.
+        by_vote: bool,
+    },
+    GuardianAction {
+        /// `upgrade_proposed`, `upgrade_voted`, `upgrade_executed`,
+        /// `upgrade_rejected` or `circuit_breaker`.
+        action: String,
+        actor: Option<String>,
+        /// The breaker's new state, for `circuit_breaker`.
+        breaker_state: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Activity {
+    /// Indexed event id.
+    pub id: String,
+    pub market_id: Option<i64>,
+    pub ledger: i64,
+    pub tx_hash: Option<String>,
+    pub indexed_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub details: ActivityDetails,
+}
+
+impl Activity {
+    /// Map a stored event to its activity. Returns `None` for kinds the feed
+    /// does not cover.
+    pub fn from_row(row: ActivityRow) -> Option<Self> {
+        let outcome = row.outcome.and_then(|o| u32::try_from(o).ok());
+        let mut market_id = row.market_id;
+        let details = match ActivityType::from_kind(&row.kind)? {
+            ActivityType::DisputeFiled => ActivityDetails::DisputeFiled {
+                disputer: row.address,
+            },
+            ActivityType::VoteCast => ActivityDetails::VoteCast {
+                voter: row.address,
+                outcome,
+                weight: row.amount,
+            },
+            ActivityType::OracleResolved => ActivityDetails::OracleResolved {
+                oracle: row.address,
+                outcome,
+            },
+            ActivityType::ResolutionFinalized => ActivityDetails::ResolutionFinalized {
+                resolver: row.address,
+                outcome,
+                payout: row.amount,
+                admin_fallback: row.kind == "adm_fbk",
+            },
+            ActivityType::DisputeResolved => ActivityDetails::DisputeResolved {
+                resolver: row.address,
+                outcome,
+            },
+            ActivityType::MarketCancelled => ActivityDetails::MarketCancelled {
+                cancelled_by: row.address,
+                by_vote: matches!(row.kind.as_str(), "mk_cn_vt" | "market_cancelled_vote"),
+            },
+            ActivityType::GuardianAction => {
+                let action = match row.kind.as_str() {
+                    "upg_init" => "upgrade_proposed",
+                    "upg_vote" => "upgrade_voted",
+                    "upg_exec" => "upgrade_executed",
+                    "upg_rej" => "upgrade_rejected",
+                    _ => "circuit_breaker",
+                };
+                // `cb_state` is published with market id 0 and the contract
+                // as its address; neither says anything about the actor.
+                let actor = if action == "circuit_breaker" {
+                    market_id = None;
+                    None
+                } else {
+                    row.address
+                };
+                ActivityDetails::GuardianAction {
+                    action: action.to_string(),
+                    actor,
+                    breaker_state: row.breaker_state,
+                }
+            }
+        };
+        Some(Self {
+            id: row.id,
+            market_id,
+            ledger: row.ledger,
+            tx_hash: row.tx_hash,
+            indexed_at: row.indexed_at,
+            details,
+        })
+    }
+}
+
+/// A page of the feed.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ActivityFeed {
+    /// Newest first, or oldest first with `since_id`.
+    pub items: Vec<Activity>,
+    /// Matching activity per type across every page. Types without any are
+    /// listed with 0.
+    pub counts: BTreeMap<ActivityType, i64>,
+    pub page: i64,
+    pub limit: i64,
+    pub has_more: bool,
+    /// The `since_id` for the next poll: the last id returned, or the
+    /// request's `since_id` when nothing was new. Absent without `since_id`.
+    pub next_since_id: Option<String>,
+}
+
+impl ActivityFeed {
+    /// Assemble a page from up to `limit + 1` rows and the per-kind counts.
+    pub fn new(
+        filter: &ActivityFilter,
+        rows: Vec<ActivityRow>,
+        kind_counts: Vec<(String, i64)>,
+        page: i64,
+        limit: i64,
+    ) -> Self {
+        let has_more = rows.len() as i64 > limit;
+        let items: Vec<Activity> = rows
+            .into_iter()
+            .take(limit as usize)
+            .filter_map(Activity::from_row)
+            .collect();
+        let mut counts: BTreeMap<ActivityType, i64> =
+            filter.types.iter().map(|t| (*t, 0)).collect();
+        for (kind, count) in kind_counts {
+            if let Some(ty) = ActivityType::from_kind(&kind) {
+                *counts.entry(ty).or_default() += count;
+            }
+        }
+        let next_since_id = filter.since_id.as_ref().map(|since| {
+            items
+                .last()
+                .map_or_else(|| since.clone(), |last| last.id.clone())
+        });
+        Self {
+            items,
+            counts,
+            page,
+            limit,
+            has_more,
+            next_since_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn row(
+        kind: &str,
+        address: Option<&str>,
+        outcome: Option<i32>,
+        amount: Option<&str>,
+    ) -> ActivityRow {
+        ActivityRow {
+            id: "0000000000000004200-0000000001".into(),
+            ledger: 42,
+            tx_hash: Some("abc".into()),
+            kind: kind.into(),
+            market_id: Some(7),
+            address: address.map(Into::into),
+            outcome,
+            amount: amount.map(Into::into),
+            breaker_state: None,
+            indexed_at: "2026-03-01T12:00:00Z".parse().unwrap(),
+        }
+    }
+
+    fn details(row: ActivityRow) -> serde_json::Value {
+        let mut value = serde_json::to_value(Activity::from_row(row).unwrap()).unwrap();
+        for common in ["id", "ledger", "tx_hash", "indexed_at"] {
+            value.as_object_mut().unwrap().remove(common);
+        }
+        value
+    }
+
+    #[test]
+    fn type_labels_round_trip_and_cover_distinct_kinds() {
+        let mut kinds = Vec::new();
+        for ty in ActivityType::ALL {
+            assert_eq!(ActivityType::parse(ty.label()), Some(ty));
+            assert_eq!(serde_json::to_value(ty).unwrap(), ty.label());
+            for kind in ty.kinds() {
+                assert_eq!(ActivityType::from_kind(kind), Some(ty));
+                kinds.push(*kind);
+            }
+        }
+        let total = kinds.len();
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), total, "a kind maps to one type");
+        assert_eq!(ActivityType::from_kind("bet_place"), None);
+    }
+
+    #[test]
+    fn type_filters_parse() {
+        assert_eq!(
+            ActivityType::parse_list(None).unwrap(),
+            ActivityType::ALL.to_vec()
+        );
+        assert_eq!(
+            ActivityType::parse_list(Some("vote_cast, dispute_filed,vote_cast")).unwrap(),
+            vec![ActivityType::DisputeFiled, ActivityType::VoteCast]
+        );
+        assert!(ActivityType::parse_list(Some("bets")).is_err());
+    }
+
+    #[test]
+    fn dispute_filed_names_the_disputer() {
+        assert_eq!(
+            details(row("disp_file", Some("GDISPUTER"), None, None)),
+            json!({ "type": "dispute_filed", "market_id": 7, "disputer": "GDISPUTER" })
+        );
+    }
+
+    #[test]
+    fn vote_cast_carries_outcome_and_weight() {
+        assert_eq!(
+            details(row("vote_cast", Some("GVOTER"), Some(1), Some("1200"))),
+            json!({ "type": "vote_cast", "market_id": 7, "voter": "GVOTER", "outcome": 1, "weight": "1200" })
+        );
+    }
+
+    #[test]
+    fn resolutions_carry_outcome_and_payout() {
+        assert_eq!(
+            details(row("resolv_fx", Some("GRESOLVER"), Some(0), Some("250000"))),
+            json!({
+                "type": "resolution_finalized", "market_id": 7, "resolver": "GRESOLVER",
+                "outcome": 0, "payout": "250000", "admin_fallback": false
+            })
+        );
+        assert_eq!(
+            details(row("market_finalized", None, Some(2), None)),
+            json!({
+                "type": "resolution_finalized", "market_id": 7, "resolver": null,
+                "outcome": 2, "payout": null, "admin_fallback": false
+            })
+        );
+        assert_eq!(
+            details(row("dispute_resolved", None, Some(1), None)),
+            json!({ "type": "dispute_resolved", "market_id": 7, "resolver": null, "outcome": 1 })
+        );
+        assert_eq!(
+            details(row("orcl_res", Some("CORACLE"), Some(1), None)),
+            json!({ "type": "oracle_resolved", "market_id": 7, "oracle": "CORACLE", "outcome": 1 })
+        );
+    }
+
+    #[test]
+    fn cancellations_note_a_vote() {
+        assert_eq!(
+            details(row("mk_cn_vt", Some("GRESOLVER"), None, None)),
+            json!({ "type": "market_cancelled", "market_id": 7, "cancelled_by": "GRESOLVER", "by_vote": true })
+        );
+    }
+
+    #[test]
+    fn guardian_actions_name_the_action() {
+        assert_eq!(
+            details(ActivityRow {
+                market_id: None,
+                ..row("upg_vote", Some("GGUARDIAN"), None, None)
+            }),
+            json!({
+                "type": "guardian_action", "market_id": null, "action": "upgrade_voted",
+                "actor": "GGUARDIAN", "breaker_state": null
+            })
+        );
+        assert_eq!(
+            details(ActivityRow {
+                market_id: Some(0),
+                breaker_state: Some("paused".into()),
+                ..row("cb_state", Some("CCONTRACT"), None, None)
+            }),
+            json!({
+                "type": "guardian_action", "market_id": null, "action": "circuit_breaker",
+                "actor": null, "breaker_state": "paused"
+            })
+        );
+    }
+
+    #[test]
+    fn feed_counts_every_requested_type_and_tracks_since_id() {
+        let filter = ActivityFilter {
+            types: vec![
+                ActivityType::DisputeFiled,
+                ActivityType::ResolutionFinalized,
+            ],
+            market_id: None,
+            since_id: Some("0000000000000004100-0000000001".into()),
+        };
+        let rows = vec![
+            row("disp_file", Some("GDISPUTER"), None, None),
+            ActivityRow {
+                id: "0000000000000004300-0000000001".into(),
+                ..row("resolv_fx", None, Some(1), Some("10"))
+            },
+        ];
+        let counts = vec![("resolv_fx".to_string(), 2), ("mkt_final".to_string(), 1)];
+
+        let feed = ActivityFeed::new(&filter, rows, counts, 1, 1);
+        assert!(feed.has_more);
+        assert_eq!(feed.items.len(), 1);
+        assert_eq!(
+            feed.next_since_id.as_deref(),
+            Some("0000000000000004200-0000000001")
+        );
+        assert_eq!(
+            serde_json::to_value(&feed.counts).unwrap(),
+            json!({ "dispute_filed": 0, "resolution_finalized": 3 })
+        );
+
+        let empty = ActivityFeed::new(&filter, Vec::new(), Vec::new(), 1, 20);
+        assert_eq!(empty.next_since_id, filter.since_id);
+        assert!(!empty.has_more);
+    }
+
+    #[test]
+    fn since_ids_are_event_ids() {
+        assert!(valid_since_id("0000000000000004200-0000000001"));
+        assert!(!valid_since_id(""));
+        assert!(!valid_since_id("1' OR '1'='1"));
+    }
+}
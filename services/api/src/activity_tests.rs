@@ -0,0 +1,329 @@
+#[cfg(test)]
+mod activity_tests {
+    use serde_json::{json, Value};
+
+    use crate::{
+        activity::{Activity, ActivityDetails, ActivityFeed, ActivityFilter, ActivityType},
+        blockchain::{ContractEvent, IndexedEvent},
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    /// A network name unique to this run, so only rows created here match.
+    fn test_network() -> String {
+        format!("acttest{}", uuid::Uuid::new_v4().simple())
+    }
+
+    /// Event ids are unique across networks, so each run's carry its
+    /// network's suffix. They still sort in ledger order.
+    fn event_id(network: &str, ledger: u32) -> String {
+        format!("{ledger:019}-{}", &network[network.len() - 10..])
+    }
+
+    /// Index a synthetic event as the sync worker would.
+    async fn index(
+        db: &Database,
+        network: &str,
+        ledger: u32,
+        topic: Value,
+        value: Value,
+    ) -> String {
+        let id = event_id(network, ledger);
+        let raw = json!({ "id": id, "ledger": ledger, "topic": topic, "value": value });
+        let event = ContractEvent {
+            id: id.clone(),
+            ledger,
+            topic: raw["topic"].to_string(),
+            tx_hash: Some(format!("tx{ledger}")),
+            value: raw,
+        };
+        let indexed = IndexedEvent::from_contract_event(&event).unwrap();
+        assert!(db
+            .chain_event_insert_at(network, &indexed, None)
+            .await
+            .unwrap());
+        id
+    }
+
+    /// A bet the feed must skip, then a dispute, a vote, an oracle
+    /// resolution (on market 8), a resolution and an upgrade vote, one per
+    /// ledger. Returns the ids in order.
+    async fn seed(db: &Database, network: &str) -> Vec<String> {
+        vec![
+            index(
+                db,
+                network,
+                100,
+                json!(["bet_place", 7, "GBETTOR"]),
+                json!([1, 0, "500"]),
+            )
+            .await,
+            index(
+                db,
+                network,
+                101,
+                json!(["disp_file", 7, "GDISPUTER"]),
+                json!([1, 1_900_000_000u64]),
+            )
+            .await,
+            index(
+                db,
+                network,
+                102,
+                json!(["vote_cast", 7, "GVOTER"]),
+                json!([1, 1, "1200", ["Token"]]),
+            )
+            .await,
+            index(db, network, 103, json!(["oracle_resolved", 8]), json!(0)).await,
+            index(
+                db,
+                network,
+                104,
+                json!(["resolv_fx", 7, "GRESOLVER"]),
+                json!([1, 1, "250000"]),
+            )
+            .await,
+            index(
+                db,
+                network,
+                105,
+                json!(["upg_vote", "GGUARDIAN"]),
+                json!([1, true]),
+            )
+            .await,
+        ]
+    }
+
+    fn filter(
+        types: &[ActivityType],
+        market_id: Option<i64>,
+        since_id: Option<&str>,
+    ) -> ActivityFilter {
+        ActivityFilter {
+            types: types.to_vec(),
+            market_id,
+            since_id: since_id.map(str::to_string),
+        }
+    }
+
+    async fn feed(
+        db: &Database,
+        network: &str,
+        filter: &ActivityFilter,
+        page: i64,
+        limit: i64,
+    ) -> ActivityFeed {
+        let rows = db
+            .activity_page(network, filter, limit, (page - 1) * limit)
+            .await
+            .unwrap();
+        let counts = db.activity_counts(network, filter).await.unwrap();
+        ActivityFeed::new(filter, rows, counts, page, limit)
+    }
+
+    fn item_ids(feed: &ActivityFeed) -> Vec<&str> {
+        feed.items.iter().map(|a| a.id.as_str()).collect()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Tests
+    // ---------------------------------------------------------------------------
+
+    /// Events go through the indexer and come back with their type's fields.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_indexed_events_map_to_activity() {
+        let db = build_db().await;
+        let network = test_network();
+        let ids = seed(&db, &network).await;
+
+        let all = feed(
+            &db,
+            &network,
+            &filter(&ActivityType::ALL, None, None),
+            1,
+            20,
+        )
+        .await;
+        assert_eq!(all.items.len(), 5, "the bet is not activity");
+        let by_id = |id: &str| -> &Activity { all.items.iter().find(|a| a.id == id).unwrap() };
+
+        assert_eq!(
+            by_id(&ids[1]).details,
+            ActivityDetails::DisputeFiled {
+                disputer: Some("GDISPUTER".into())
+            }
+        );
+        assert_eq!(
+            by_id(&ids[2]).details,
+            ActivityDetails::VoteCast {
+                voter: Some("GVOTER".into()),
+                outcome: Some(1),
+                weight: Some("1200".into()),
+            }
+        );
+        assert_eq!(
+            by_id(&ids[3]).details,
+            ActivityDetails::OracleResolved {
+                oracle: None,
+                outcome: Some(0)
+            }
+        );
+        assert_eq!(by_id(&ids[3]).market_id, Some(8));
+        assert_eq!(
+            by_id(&ids[4]).details,
+            ActivityDetails::ResolutionFinalized {
+                resolver: Some("GRESOLVER".into()),
+                outcome: Some(1),
+                payout: Some("250000".into()),
+                admin_fallback: false,
+            }
+        );
+        assert_eq!(
+            by_id(&ids[5]).details,
+            ActivityDetails::GuardianAction {
+                action: "upgrade_voted".into(),
+                actor: Some("GGUARDIAN".into()),
+                breaker_state: None,
+            }
+        );
+        assert_eq!(by_id(&ids[5]).tx_hash.as_deref(), Some("tx105"));
+
+        db.chain_events_delete_above(&network, 0).await.unwrap();
+    }
+
+    /// Pages run newest first; type and market filters apply to the counts.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_pages_and_counts_follow_the_filter() {
+        let db = build_db().await;
+        let network = test_network();
+        let ids = seed(&db, &network).await;
+
+        let first = feed(&db, &network, &filter(&ActivityType::ALL, None, None), 1, 2).await;
+        assert_eq!(item_ids(&first), vec![ids[5].as_str(), ids[4].as_str()]);
+        assert!(first.has_more);
+        assert_eq!(first.next_since_id, None);
+        let third = feed(&db, &network, &filter(&ActivityType::ALL, None, None), 3, 2).await;
+        assert_eq!(item_ids(&third), vec![ids[1].as_str()]);
+        assert!(!third.has_more);
+
+        let market = feed(
+            &db,
+            &network,
+            &filter(
+                &[
+                    ActivityType::DisputeFiled,
+                    ActivityType::VoteCast,
+                    ActivityType::OracleResolved,
+                ],
+                Some(7),
+                None,
+            ),
+            1,
+            20,
+        )
+        .await;
+        assert_eq!(item_ids(&market), vec![ids[2].as_str(), ids[1].as_str()]);
+        assert_eq!(market.counts[&ActivityType::DisputeFiled], 1);
+        assert_eq!(market.counts[&ActivityType::VoteCast], 1);
+        assert_eq!(
+            market.counts[&ActivityType::OracleResolved],
+            0,
+            "market 8 is filtered out"
+        );
+        assert!(!market.counts.contains_key(&ActivityType::GuardianAction));
+
+        db.chain_events_delete_above(&network, 0).await.unwrap();
+    }
+
+    /// A bot polling with `since_id` sees each event once, in order, and
+    /// keeps its position when nothing is new.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_since_id_polls_incrementally() {
+        let db = build_db().await;
+        let network = test_network();
+        let ids = seed(&db, &network).await;
+        let types = ActivityType::ALL;
+
+        let poll = feed(&db, &network, &filter(&types, None, Some(&ids[2])), 1, 2).await;
+        assert_eq!(
+            item_ids(&poll),
+            vec![ids[3].as_str(), ids[4].as_str()],
+            "oldest first"
+        );
+        assert!(poll.has_more);
+        assert_eq!(poll.next_since_id.as_deref(), Some(ids[4].as_str()));
+        assert_eq!(
+            poll.counts.values().sum::<i64>(),
+            3,
+            "counts cover what is new"
+        );
+
+        let next = feed(
+            &db,
+            &network,
+            &filter(&types, None, poll.next_since_id.as_deref()),
+            1,
+            2,
+        )
+        .await;
+        assert_eq!(item_ids(&next), vec![ids[5].as_str()]);
+        assert!(!next.has_more);
+
+        let idle = feed(
+            &db,
+            &network,
+            &filter(&types, None, next.next_since_id.as_deref()),
+            1,
+            2,
+        )
+        .await;
+        assert!(idle.items.is_empty());
+        assert_eq!(idle.next_since_id.as_deref(), Some(ids[5].as_str()));
+
+        let later = index(
+            &db,
+            &network,
+            110,
+            json!(["disp_res", 7, "GRESOLVER"]),
+            json!([1, 1]),
+        )
+        .await;
+        let caught_up = feed(
+            &db,
+            &network,
+            &filter(&types, None, idle.next_since_id.as_deref()),
+            1,
+            2,
+        )
+        .await;
+        assert_eq!(item_ids(&caught_up), vec![later.as_str()]);
+        assert_eq!(
+            caught_up.items[0].details,
+            ActivityDetails::DisputeResolved {
+                resolver: Some("GRESOLVER".into()),
+                outcome: Some(1)
+            }
+        );
+
+        db.chain_events_delete_above(&network, 0).await.unwrap();
+    }
+}
@@ -0,0 +1,682 @@
+//! Webhook alerts when a served network degrades.
+//!
+//! The alert task ([`run`], spawned in `main.rs` when `ALERT_WEBHOOK_URL` is
+//! set) checks every network each `alert_check_interval` for three
+//! conditions:
+//!
+//! - **RPC unhealthy**: the cached [`BlockchainHealth`] has `is_healthy: false`.
+//! - **Sync stalled**: the sync cursor has not moved for
+//!   `alert_sync_stall_polls` event poll intervals.
+//! - **RPC error rate**: the failed share of `rpc_calls_total` over the last
+//!   `alert_rpc_error_window` is above `alert_rpc_error_rate`.
+//!
+//! Each (network, condition) pair alerts when it starts holding and not
+//! again within `alert_cooldown`, however often it flaps. When a condition
+//! that alerted clears, a recovery message follows. Payloads are Slack
+//! incoming-webhook JSON (`{"text": "..."}`), which most chat tools accept.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    blockchain::{BlockchainClient, BlockchainHealth},
+    cache::{keys, RedisCache},
+    config::Config,
+    metrics::Metrics,
+    AppState,
+};
+
+const WORKER_NAME: &str = "blockchain_alerts";
+
+/// Fewest RPC calls in the window before an error rate is judged; below
+/// this a couple of failures would read as a 100% outage.
+const MIN_RPC_CALLS: u64 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertCondition {
+    RpcUnhealthy,
+    SyncStalled,
+    RpcErrorRate,
+}
+
+impl AlertCondition {
+    fn title(self) -> &'static str {
+        match self {
+            Self::RpcUnhealthy => "Soroban RPC unhealthy",
+            Self::SyncStalled => "Indexer stalled",
+            Self::RpcErrorRate => "High RPC error rate",
+        }
+    }
+}
+
+/// Limits that turn raw signals into alert conditions.
+#[derive(Debug, Clone)]
+pub struct AlertThresholds {
+    pub cooldown: Duration,
+    /// How long the sync cursor may stand still.
+    pub sync_stall_after: Duration,
+    pub rpc_error_rate: f64,
+    pub rpc_error_window: Duration,
+}
+
+impl AlertThresholds {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            cooldown: config.alert_cooldown,
+            sync_stall_after: config.event_poll_interval * config.alert_sync_stall_polls,
+            rpc_error_rate: config.alert_rpc_error_rate,
+            rpc_error_window: config.alert_rpc_error_window,
+        }
+    }
+}
+
+/// Outbound webhook HTTP client, shared by network alerts and transaction
+/// notifications ([`crate::tx_watch`]).
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    http: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new() -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(3))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("failed to construct webhook http client")?;
+        Ok(Self { http })
+    }
+
+    /// POST a JSON `body` to `url` with the extra `headers`. A non-2xx
+    /// response is an error.
+    pub async fn post(
+        &self,
+        url: &str,
+        body: Vec<u8>,
+        headers: &[(&str, &str)],
+    ) -> anyhow::Result<()> {
+        let mut request = self
+            .http
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request
+            .send()
+            .await
+            .context("webhook request failed")?
+            .error_for_status()
+            .context("webhook returned an error")?;
+        Ok(())
+    }
+}
+
+/// A Slack-compatible incoming webhook.
+pub struct AlertWebhook {
+    dispatcher: WebhookDispatcher,
+    url: String,
+}
+
+impl AlertWebhook {
+    pub fn new(url: impl Into<String>) -> anyhow::Result<Self> {
+        Ok(Self {
+            dispatcher: WebhookDispatcher::new()?,
+            url: url.into(),
+        })
+    }
+
+    pub async fn send(&self, text: &str) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&json!({ "text": text }))?;
+        self.dispatcher
+            .post(&self.url, body, &[])
+            .await
+            .context("alert webhook delivery failed")
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConditionState {
+    /// An alert went out and no recovery has yet.
+    firing: bool,
+    last_alert: Option<Instant>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SyncProgress {
+    cursor: u32,
+    advanced_at: Instant,
+}
+
+/// Raw signals remembered between checks for one network.
+#[derive(Debug, Default)]
+struct NetworkSignals {
+    sync: Option<SyncProgress>,
+    /// `(at, calls, failed)` samples of the running RPC totals, oldest first.
+    rpc_samples: VecDeque<(Instant, u64, u64)>,
+}
+
+/// Evaluates alert conditions and sends de-duplicated alerts and recoveries.
+pub struct Alerter {
+    webhook: AlertWebhook,
+    thresholds: AlertThresholds,
+    conditions: HashMap<(String, AlertCondition), ConditionState>,
+    signals: HashMap<String, NetworkSignals>,
+}
+
+impl Alerter {
+    pub fn new(webhook: AlertWebhook, thresholds: AlertThresholds) -> Self {
+        Self {
+            webhook,
+            thresholds,
+            conditions: HashMap::new(),
+            signals: HashMap::new(),
+        }
+    }
+
+    /// Evaluate every condition for `client`'s network at `now`.
+    pub async fn check(
+        &mut self,
+        client: &BlockchainClient,
+        cache: &RedisCache,
+        metrics: &Metrics,
+        now: Instant,
+    ) {
+        let network = client.network().to_string();
+
+        let health = client.health_check_cached().await;
+        let (unhealthy, detail) = match &health {
+            Ok(h) => (!h.is_healthy, health_detail(h)),
+            Err(e) => (true, format!("health check failed: {e}")),
+        };
+        self.observe(
+            &network,
+            AlertCondition::RpcUnhealthy,
+            unhealthy,
+            &detail,
+            now,
+        )
+        .await;
+
+        let cursor = cache
+            .get_json::<u32>(&keys::chain_sync_cursor(&network))
+            .await
+            .ok()
+            .flatten();
+        let stall_after = self.thresholds.sync_stall_after;
+        let signals = self.signals.entry(network.clone()).or_default();
+        let stalled = sync_stalled(&mut signals.sync, cursor, now, stall_after);
+        let detail = match (stalled, signals.sync) {
+            (Some(still), Some(progress)) => format!(
+                "sync cursor stuck at ledger {} for {}s",
+                progress.cursor,
+                still.as_secs()
+            ),
+            _ => String::new(),
+        };
+        self.observe(
+            &network,
+            AlertCondition::SyncStalled,
+            stalled.is_some(),
+            &detail,
+            now,
+        )
+        .await;
+
+        let (threshold, window) = (
+            self.thresholds.rpc_error_rate,
+            self.thresholds.rpc_error_window,
+        );
+        let signals = self.signals.entry(network.clone()).or_default();
+        let rate = rpc_error_rate(
+            &mut signals.rpc_samples,
+            now,
+            metrics.rpc_call_totals(&network),
+            window,
+        );
+        let high = rate.is_some_and(|r| r > threshold);
+        let detail = rate
+            .map(|r| {
+                format!(
+                    "{:.0}% of RPC calls failed over the last {}s (threshold {:.0}%)",
+                    r * 100.0,
+                    window.as_secs(),
+                    threshold * 100.0
+                )
+            })
+            .unwrap_or_default();
+        self.observe(&network, AlertCondition::RpcErrorRate, high, &detail, now)
+            .await;
+    }
+
+    /// Record whether `condition` holds on `network` at `now`: alert when it
+    /// holds and none went out within the cooldown, send a recovery when it
+    /// clears after alerting. A failed send is retried on the next check.
+    pub async fn observe(
+        &mut self,
+        network: &str,
+        condition: AlertCondition,
+        active: bool,
+        detail: &str,
+        now: Instant,
+    ) {
+        let cooldown = self.thresholds.cooldown;
+        let state = self
+            .conditions
+            .entry((network.to_string(), condition))
+            .or_default();
+
+        let text = if active && !state.firing {
+            if state
+                .last_alert
+                .is_some_and(|at| now.duration_since(at) < cooldown)
+            {
+                return;
+            }
+            format!(
+                ":rotating_light: [{network}] {}: {detail}",
+                condition.title()
+            )
+        } else if !active && state.firing {
+            format!(
+                ":white_check_mark: [{network}] Resolved: {}",
+                condition.title()
+            )
+        } else {
+            return;
+        };
+
+        match self.webhook.send(&text).await {
+            Ok(()) => {
+                if active {
+                    state.firing = true;
+                    state.last_alert = Some(now);
+                } else {
+                    state.firing = false;
+                }
+                tracing::info!(network, ?condition, active, "blockchain alert sent");
+            }
+            Err(e) => {
+                tracing::warn!(network, ?condition, error = %e, "failed to send blockchain alert")
+            }
+        }
+    }
+}
+
+fn health_detail(health: &BlockchainHealth) -> String {
+    format!(
+        "status {:?}, latest ledger {}, contract reachable: {}",
+        health.status, health.latest_ledger, health.contract_reachable
+    )
+}
+
+/// Track the sync cursor; `Some(still)` once it has not advanced for longer
+/// than `stall_after`. No cursor yet (a fresh deployment) is not a stall.
+fn sync_stalled(
+    progress: &mut Option<SyncProgress>,
+    cursor: Option<u32>,
+    now: Instant,
+    stall_after: Duration,
+) -> Option<Duration> {
+    let cursor = cursor?;
+    match progress {
+        Some(p) if p.cursor == cursor => {
+            let still = now.duration_since(p.advanced_at);
+            (still > stall_after).then_some(still)
+        }
+        _ => {
+            *progress = Some(SyncProgress {
+                cursor,
+                advanced_at: now,
+            });
+            None
+        }
+    }
+}
+
+/// Add a sample of the running `(calls, failed)` totals and return the
+/// failed share over `window`, or `None` with fewer than [`MIN_RPC_CALLS`].
+fn rpc_error_rate(
+    samples: &mut VecDeque<(Instant, u64, u64)>,
+    now: Instant,
+    (calls, failed): (u64, u64),
+    window: Duration,
+) -> Option<f64> {
+    samples.push_back((now, calls, failed));
+    // Keep the newest sample at or before the window start as the baseline.
+    while samples.len() > 1 && now.duration_since(samples[1].0) >= window {
+        samples.pop_front();
+    }
+    let (_, base_calls, base_failed) = *samples.front()?;
+    let calls = calls.saturating_sub(base_calls);
+    if calls < MIN_RPC_CALLS {
+        return None;
+    }
+    Some(failed.saturating_sub(base_failed) as f64 / calls as f64)
+}
+
+/// Check every network each `alert_check_interval` until `shutdown` fires.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    let Some(url) = state.config.alert_webhook_url.clone() else {
+        return;
+    };
+    let webhook = match AlertWebhook::new(url) {
+        Ok(webhook) => webhook,
+        Err(e) => {
+            tracing::error!("[alerts] {e}");
+            return;
+        }
+    };
+    let mut alerter = Alerter::new(webhook, AlertThresholds::from_config(&state.config));
+    let mut interval = tokio::time::interval(state.config.alert_check_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    state.metrics.set_worker_status(WORKER_NAME, true);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        for client in state.networks.iter() {
+            alerter
+                .check(client, &state.cache, &state.metrics, Instant::now())
+                .await;
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use serde_json::Value;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::Mutex;
+
+    const COOLDOWN: Duration = Duration::from_secs(600);
+
+    /// Mock webhook recording every payload. The first `failures` requests
+    /// get a 500.
+    async fn start_webhook(failures: usize) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let remaining_failures = Arc::new(AtomicUsize::new(failures));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let sink = sink.clone();
+                let remaining_failures = remaining_failures.clone();
+                async move {
+                    if remaining_failures
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok()
+                    {
+                        return StatusCode::INTERNAL_SERVER_ERROR;
+                    }
+                    sink.lock().await.push(body);
+                    StatusCode::OK
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, received)
+    }
+
+    fn alerter(url: &str) -> Alerter {
+        Alerter::new(
+            AlertWebhook::new(url).unwrap(),
+            AlertThresholds {
+                cooldown: COOLDOWN,
+                sync_stall_after: Duration::from_secs(60),
+                rpc_error_rate: 0.25,
+                rpc_error_window: Duration::from_secs(300),
+            },
+        )
+    }
+
+    async fn texts(received: &Mutex<Vec<Value>>) -> Vec<String> {
+        received
+            .lock()
+            .await
+            .iter()
+            .map(|body| body["text"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn alerts_once_then_recovers() {
+        let (url, received) = start_webhook(0).await;
+        let mut alerter = alerter(&url);
+        let t0 = Instant::now();
+        let unhealthy = AlertCondition::RpcUnhealthy;
+
+        alerter
+            .observe("testnet", unhealthy, true, "node down", t0)
+            .await;
+        for s in 1..5 {
+            alerter
+                .observe(
+                    "testnet",
+                    unhealthy,
+                    true,
+                    "node down",
+                    t0 + Duration::from_secs(s),
+                )
+                .await;
+        }
+        assert_eq!(
+            texts(&received).await.len(),
+            1,
+            "a held condition alerts once"
+        );
+
+        alerter
+            .observe(
+                "testnet",
+                unhealthy,
+                false,
+                "",
+                t0 + Duration::from_secs(10),
+            )
+            .await;
+        alerter
+            .observe(
+                "testnet",
+                unhealthy,
+                false,
+                "",
+                t0 + Duration::from_secs(11),
+            )
+            .await;
+
+        let texts = texts(&received).await;
+        assert_eq!(texts.len(), 2, "one recovery when the condition clears");
+        assert!(
+            texts[0].contains("[testnet] Soroban RPC unhealthy: node down"),
+            "{}",
+            texts[0]
+        );
+        assert!(
+            texts[1].contains("[testnet] Resolved: Soroban RPC unhealthy"),
+            "{}",
+            texts[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn flapping_condition_does_not_realert_within_cooldown() {
+        let (url, received) = start_webhook(0).await;
+        let mut alerter = alerter(&url);
+        let t0 = Instant::now();
+        let stalled = AlertCondition::SyncStalled;
+
+        alerter.observe("testnet", stalled, true, "stuck", t0).await;
+        alerter
+            .observe("testnet", stalled, false, "", t0 + Duration::from_secs(30))
+            .await;
+        // Recurs inside the cooldown: suppressed, and so is its recovery.
+        alerter
+            .observe(
+                "testnet",
+                stalled,
+                true,
+                "stuck",
+                t0 + Duration::from_secs(60),
+            )
+            .await;
+        alerter
+            .observe("testnet", stalled, false, "", t0 + Duration::from_secs(90))
+            .await;
+        assert_eq!(texts(&received).await.len(), 2);
+
+        alerter
+            .observe(
+                "testnet",
+                stalled,
+                true,
+                "stuck",
+                t0 + COOLDOWN + Duration::from_secs(1),
+            )
+            .await;
+        let texts = texts(&received).await;
+        assert_eq!(texts.len(), 3, "alerts again once the cooldown has passed");
+        assert!(texts[2].contains("Indexer stalled"));
+    }
+
+    #[tokio::test]
+    async fn conditions_and_networks_are_independent() {
+        let (url, received) = start_webhook(0).await;
+        let mut alerter = alerter(&url);
+        let t0 = Instant::now();
+
+        alerter
+            .observe("testnet", AlertCondition::RpcUnhealthy, true, "down", t0)
+            .await;
+        alerter
+            .observe("mainnet", AlertCondition::RpcUnhealthy, true, "down", t0)
+            .await;
+        alerter
+            .observe("testnet", AlertCondition::RpcErrorRate, true, "50%", t0)
+            .await;
+
+        assert_eq!(texts(&received).await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_is_retried_on_next_check() {
+        let (url, received) = start_webhook(1).await;
+        let mut alerter = alerter(&url);
+        let t0 = Instant::now();
+
+        alerter
+            .observe("testnet", AlertCondition::RpcUnhealthy, true, "down", t0)
+            .await;
+        assert!(texts(&received).await.is_empty());
+
+        alerter
+            .observe(
+                "testnet",
+                AlertCondition::RpcUnhealthy,
+                true,
+                "down",
+                t0 + Duration::from_secs(30),
+            )
+            .await;
+        assert_eq!(texts(&received).await.len(), 1);
+    }
+
+    #[test]
+    fn sync_stall_needs_an_unmoving_cursor() {
+        let stall_after = Duration::from_secs(60);
+        let t0 = Instant::now();
+        let mut progress = None;
+
+        assert_eq!(
+            sync_stalled(&mut progress, None, t0, stall_after),
+            None,
+            "no cursor yet"
+        );
+        assert_eq!(
+            sync_stalled(&mut progress, Some(100), t0, stall_after),
+            None
+        );
+        assert_eq!(
+            sync_stalled(
+                &mut progress,
+                Some(100),
+                t0 + Duration::from_secs(60),
+                stall_after
+            ),
+            None
+        );
+        assert_eq!(
+            sync_stalled(
+                &mut progress,
+                Some(100),
+                t0 + Duration::from_secs(61),
+                stall_after
+            ),
+            Some(Duration::from_secs(61))
+        );
+        // Advancing resets the clock.
+        assert_eq!(
+            sync_stalled(
+                &mut progress,
+                Some(101),
+                t0 + Duration::from_secs(62),
+                stall_after
+            ),
+            None
+        );
+        assert_eq!(
+            sync_stalled(
+                &mut progress,
+                Some(101),
+                t0 + Duration::from_secs(100),
+                stall_after
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn rpc_error_rate_is_measured_over_the_window() {
+        let window = Duration::from_secs(300);
+        let t0 = Instant::now();
+        let mut samples = VecDeque::new();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+
+        assert_eq!(
+            rpc_error_rate(&mut samples, at(0), (1_000, 900), window),
+            None
+        );
+        // Too few calls since the baseline to judge.
+        assert_eq!(
+            rpc_error_rate(&mut samples, at(30), (1_010, 910), window),
+            None
+        );
+        // 40 calls and 10 failures since the t=0 baseline.
+        assert_eq!(
+            rpc_error_rate(&mut samples, at(60), (1_040, 910), window),
+            Some(0.25)
+        );
+        // t=0 and t=30 have aged out; the t=60 sample is the new baseline.
+        let rate = rpc_error_rate(&mut samples, at(400), (1_140, 960), window).unwrap();
+        assert!((rate - 0.5).abs() < f64::EPSILON, "{rate}");
+        assert_eq!(samples.len(), 2);
+    }
+}
@@ -0,0 +1,231 @@
+//! First-party product analytics.
+//!
+//! `POST /api/v1/analytics/events` accepts a batch of up to
+//! [`MAX_BATCH_EVENTS`] events whose `event_type` is in [`EVENT_TYPES`] and
+//! writes it to `analytics_events` with a single multi-row insert, so a batch
+//! is stored whole or not at all. A supervised task ([`run_rollup`]) folds
+//! the last [`ROLLUP_LOOKBACK_DAYS`] days into `analytics_daily`, which backs
+//! `GET /api/v1/admin/analytics/summary`. Each run replaces the counts for
+//! the days it covers, so repeating it never double-counts.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::AppState;
+
+const WORKER_NAME: &str = "analytics_rollup";
+
+/// Event types the ingestion endpoint accepts. Anything else is rejected so
+/// a client bug cannot fill the table with one-off names.
+pub const EVENT_TYPES: &[&str] = &[
+    "page_view",
+    "market_view",
+    "market_search",
+    "wallet_connected",
+    "prediction_started",
+    "prediction_submitted",
+    "newsletter_signup",
+    "waitlist_join",
+    "share_click",
+    "cta_click",
+];
+
+/// Largest batch one request may carry.
+pub const MAX_BATCH_EVENTS: usize = 100;
+
+/// Cap on one event's serialized `properties`.
+pub const MAX_PROPERTIES_BYTES: usize = 2048;
+
+/// Matches `analytics_events.session_id`. `u64` to match
+/// `#[validate(length)]`.
+pub const MAX_SESSION_ID_LEN: u64 = 120;
+
+/// Matches `chk_analytics_user_agent_length`; longer values are truncated.
+pub const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Body limit for the ingestion route: a full batch at the properties cap,
+/// plus envelope.
+pub const BODY_LIMIT_BYTES: usize = 256 * 1024;
+
+/// How far ahead of the server clock `occurred_at` may be.
+pub const MAX_CLOCK_SKEW: chrono::Duration = chrono::Duration::minutes(5);
+
+/// How old an event may be when it arrives. Older events would land on days
+/// the rollup no longer revisits.
+pub const MAX_EVENT_AGE: chrono::Duration = chrono::Duration::hours(24);
+
+/// Days before today that each rollup recomputes. Two, not one: an event
+/// accepted just before midnight may be up to [`MAX_EVENT_AGE`] old, and the
+/// first run after midnight must still cover its day.
+pub const ROLLUP_LOOKBACK_DAYS: u64 = 2;
+
+/// How often the rollup job runs.
+pub const ROLLUP_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Default and maximum `days` for the summary endpoint.
+pub const DEFAULT_SUMMARY_DAYS: u32 = 7;
+pub const MAX_SUMMARY_DAYS: u32 = 90;
+
+pub fn is_known_event_type(event_type: &str) -> bool {
+    EVENT_TYPES.contains(&event_type)
+}
+
+/// A validated event, ready to insert.
+#[derive(Debug, Clone)]
+pub struct AnalyticsEvent {
+    pub event_type: String,
+    pub properties: serde_json::Value,
+    pub session_id: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Days the rollup recomputes at `now`: `[from, to)`, ending tomorrow so
+/// today's partial counts are included.
+pub fn rollup_window(now: DateTime<Utc>) -> (NaiveDate, NaiveDate) {
+    let today = now.date_naive();
+    (
+        today - chrono::Days::new(ROLLUP_LOOKBACK_DAYS),
+        today + chrono::Days::new(1),
+    )
+}
+
+/// Supervised loop: recomputes [`rollup_window`] every [`ROLLUP_INTERVAL`]
+/// until `shutdown` fires. A failed run is repaired by the next one.
+pub async fn run_rollup(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+
+    let mut interval = tokio::time::interval(ROLLUP_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        let (from, to) = rollup_window(Utc::now());
+        match state.db.analytics_rollup(from, to).await {
+            Ok(n) => tracing::debug!("[analytics] rolled up {n} daily counts from {from}"),
+            Err(e) => tracing::warn!("[analytics] rollup error: {e}"),
+        }
+        state.metrics.set_worker_status(WORKER_NAME, true);
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+/// The last `days` UTC days, today included: `[from, to]`.
+pub fn summary_window(now: DateTime<Utc>, days: u32) -> (NaiveDate, NaiveDate) {
+    let today = now.date_naive();
+    (today - chrono::Days::new(u64::from(days.max(1)) - 1), today)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AnalyticsDailyCount {
+    pub day: NaiveDate,
+    pub event_type: String,
+    pub event_count: i64,
+    /// Distinct non-null `session_id`s that day.
+    pub unique_sessions: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AnalyticsTypeTotal {
+    pub event_type: String,
+    pub event_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AnalyticsSummary {
+    pub days: u32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// Per-type totals over the window, largest first.
+    pub totals: Vec<AnalyticsTypeTotal>,
+    /// Rolled-up counts by day, then type.
+    pub daily: Vec<AnalyticsDailyCount>,
+    /// When `analytics_daily` was last written; counts for today lag events
+    /// by up to one rollup interval.
+    pub rolled_up_at: Option<DateTime<Utc>>,
+}
+
+impl AnalyticsSummary {
+    pub fn new(
+        days: u32,
+        (from, to): (NaiveDate, NaiveDate),
+        daily: Vec<AnalyticsDailyCount>,
+        rolled_up_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let mut by_type = std::collections::BTreeMap::<&str, i64>::new();
+        for row in &daily {
+            *by_type.entry(row.event_type.as_str()).or_default() += row.event_count;
+        }
+        let mut totals: Vec<_> = by_type
+            .into_iter()
+            .map(|(event_type, event_count)| AnalyticsTypeTotal {
+                event_type: event_type.to_string(),
+                event_count,
+            })
+            .collect();
+        totals.sort_by_key(|t| std::cmp::Reverse(t.event_count));
+        Self { days, from, to, totals, daily, rolled_up_at }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn rollup_window_covers_every_day_an_accepted_event_can_land_on() {
+        let now = at(2026, 3, 2, 0);
+        let (from, to) = rollup_window(now);
+        assert_eq!((from, to), (day(2026, 2, 28), day(2026, 3, 3)));
+        let oldest = (now - MAX_EVENT_AGE - chrono::Duration::minutes(10)).date_naive();
+        assert!(oldest >= from);
+    }
+
+    #[test]
+    fn summary_window_includes_today() {
+        assert_eq!(summary_window(at(2026, 3, 2, 12), 7), (day(2026, 2, 24), day(2026, 3, 2)));
+        assert_eq!(summary_window(at(2026, 3, 2, 12), 1), (day(2026, 3, 2), day(2026, 3, 2)));
+    }
+
+    #[test]
+    fn summary_totals_sum_days_and_sort_largest_first() {
+        let row = |d, t: &str, n| AnalyticsDailyCount {
+            day: day(2026, 3, d),
+            event_type: t.to_string(),
+            event_count: n,
+            unique_sessions: 1,
+        };
+        let summary = AnalyticsSummary::new(
+            2,
+            (day(2026, 3, 1), day(2026, 3, 2)),
+            vec![row(1, "page_view", 5), row(1, "cta_click", 4), row(2, "page_view", 1), row(2, "cta_click", 4)],
+            None,
+        );
+        let totals: Vec<_> = summary.totals.iter().map(|t| (t.event_type.as_str(), t.event_count)).collect();
+        assert_eq!(totals, vec![("cta_click", 8), ("page_view", 6)]);
+    }
+
+    #[test]
+    fn event_types_fit_the_column_and_are_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for t in EVENT_TYPES {
+            assert!(t.len() <= 120, "{t}");
+            assert!(seen.insert(t), "duplicate {t}");
+            assert!(is_known_event_type(t));
+        }
+        assert!(!is_known_event_type("Page_View"));
+    }
+}
@@ -0,0 +1,318 @@
+#[cfg(test)]
+mod analytics_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use chrono::NaiveDate;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::{
+        analytics::MAX_BATCH_EVENTS,
+        handlers::{analytics_ingest, analytics_summary},
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Session ids and rollup-only event names live in a reserved range so
+    /// cleanup never touches rows created by other tests.
+    const TAG: &str = "analytics-95";
+    const RATE_LIMIT_MAX: usize = 3;
+
+    /// A fresh session id per test run.
+    fn session() -> String {
+        format!("{TAG}-{}", Uuid::new_v4())
+    }
+
+    /// A fresh client IP per test run so the Redis-backed limiter starts at
+    /// zero.
+    fn client_ip() -> String {
+        let bytes = Uuid::new_v4().into_bytes();
+        format!("10.95.{}.{}", bytes[0], bytes[1])
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/analytics/events", post(analytics_ingest))
+            .route("/admin/analytics/summary", get(analytics_summary))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    fn ingest(ip: &str, events: Vec<Value>) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/analytics/events")
+            .header("content-type", "application/json")
+            .header("x-forwarded-for", ip)
+            .body(Body::from(json!({ "events": events }).to_string()))
+            .unwrap()
+    }
+
+    fn event(event_type: &str, session_id: &str) -> Value {
+        json!({
+            "event_type": event_type,
+            "session_id": session_id,
+            "properties": { "path": "/markets/1" },
+        })
+    }
+
+    async fn stored(state: &crate::AppState, session_id: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM analytics_events WHERE session_id = $1")
+            .bind(session_id)
+            .fetch_one(&state.db.pool())
+            .await
+            .unwrap()
+    }
+
+    /// (day, event_type, event_count, unique_sessions)
+    type DailyRow = (NaiveDate, String, i64, i64);
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2020, 1, d).unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState, session_id: &str) {
+        sqlx::query("DELETE FROM analytics_events WHERE session_id = $1")
+            .bind(session_id)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    /// Removes the rollup test's events and daily rows, which use event
+    /// names no client can send.
+    async fn cleanup_rollup(state: &crate::AppState) {
+        let pattern = format!("{TAG}%");
+        sqlx::query("DELETE FROM analytics_events WHERE event_name LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM analytics_daily WHERE event_type LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A valid batch is stored in full with the client's IP.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_batch_is_stored() {
+        let state = build_state().await;
+        let session = session();
+        let ip = client_ip();
+
+        let events = vec![event("page_view", &session); MAX_BATCH_EVENTS];
+        let (code, body) = send(&state, ingest(&ip, events)).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+        assert_eq!(body["accepted"], MAX_BATCH_EVENTS);
+        assert_eq!(stored(&state, &session).await, MAX_BATCH_EVENTS as i64);
+
+        let stored_ip: String = sqlx::query_scalar(
+            "SELECT DISTINCT host(ip_address) FROM analytics_events WHERE session_id = $1",
+        )
+        .bind(&session)
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(stored_ip, ip);
+
+        cleanup(&state, &session).await;
+    }
+
+    /// One event over the cap rejects the whole batch.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_oversize_batch_is_rejected() {
+        let state = build_state().await;
+        let session = session();
+
+        let events = vec![event("page_view", &session); MAX_BATCH_EVENTS + 1];
+        let (code, body) = send(&state, ingest(&client_ip(), events)).await;
+        assert_eq!(code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "INVALID_FIELDS");
+        assert_eq!(body["details"]["fields"][0]["field"], "events");
+        assert_eq!(stored(&state, &session).await, 0);
+
+        cleanup(&state, &session).await;
+    }
+
+    /// An unknown type anywhere in the batch stores nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_unknown_event_type_is_rejected() {
+        let state = build_state().await;
+        let session = session();
+
+        let events = vec![
+            event("page_view", &session),
+            event("market_view", &session),
+            event("admin_login", &session),
+        ];
+        let (code, body) = send(&state, ingest(&client_ip(), events)).await;
+        assert_eq!(code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "INVALID_FIELDS");
+        assert_eq!(body["details"]["fields"][0]["field"], "events[2].event_type");
+        assert_eq!(body["details"]["fields"][0]["code"], "unknown_event_type");
+        assert_eq!(stored(&state, &session).await, 0);
+
+        cleanup(&state, &session).await;
+    }
+
+    /// Batches past ANALYTICS_RATE_LIMIT_MAX from one IP are rejected.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rate_limit_rejects_after_max() {
+        let state = build_state().await;
+        let session = session();
+        let ip = client_ip();
+
+        for n in 0..RATE_LIMIT_MAX {
+            let (code, _) = send(&state, ingest(&ip, vec![event("cta_click", &session)])).await;
+            assert_eq!(code, StatusCode::ACCEPTED, "batch {n} is within the limit");
+        }
+        let (code, body) = send(&state, ingest(&ip, vec![event("cta_click", &session)])).await;
+        assert_eq!(code, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "RATE_LIMITED");
+        assert_eq!(stored(&state, &session).await, RATE_LIMIT_MAX as i64);
+
+        cleanup(&state, &session).await;
+    }
+
+    /// The rollup counts events and distinct sessions per UTC day and type,
+    /// respects the window, and overwrites rather than adds on re-runs.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rollup_counts_per_day_and_type() {
+        let state = build_state().await;
+        cleanup_rollup(&state).await;
+
+        let click = format!("{TAG}-click");
+        let view = format!("{TAG}-view");
+        let seed = |name: &str, session: Option<&str>, at: &str| {
+            sqlx::query(
+                "INSERT INTO analytics_events (event_name, session_id, occurred_at) \
+                 VALUES ($1, $2, $3::TIMESTAMPTZ)",
+            )
+            .bind(name.to_string())
+            .bind(session.map(str::to_string))
+            .bind(at.to_string())
+        };
+        let seeded = [
+            // 2020-01-01: three clicks from two sessions, one anonymous view.
+            (&click, Some("analytics-95-a"), "2020-01-01 00:00:00Z"),
+            (&click, Some("analytics-95-a"), "2020-01-01 12:00:00Z"),
+            (&click, Some("analytics-95-b"), "2020-01-01 23:59:59Z"),
+            (&view, None, "2020-01-01 08:00:00Z"),
+            // 2020-01-02 locally, but still the 1st in UTC.
+            (&click, Some("analytics-95-c"), "2020-01-02 01:00:00+05:00"),
+            // Outside the window.
+            (&click, Some("analytics-95-d"), "2020-01-03 00:00:00Z"),
+        ];
+        for (name, session, at) in seeded {
+            seed(name, session, at).execute(&state.db.pool()).await.unwrap();
+        }
+
+        let daily = |state: Arc<crate::AppState>| async move {
+            let (rows, _) = state.db.analytics_daily(day(1), day(3)).await.unwrap();
+            rows.into_iter()
+                .filter(|r| r.event_type.starts_with(TAG))
+                .map(|r| (r.day, r.event_type, r.event_count, r.unique_sessions))
+                .collect::<Vec<DailyRow>>()
+        };
+
+        state.db.analytics_rollup(day(1), day(3)).await.unwrap();
+        // 01:00+05:00 on the 2nd is 20:00Z on the 1st.
+        let expected: Vec<DailyRow> = vec![
+            (day(1), click.clone(), 4, 3),
+            (day(1), view.clone(), 1, 0),
+        ];
+        assert_eq!(daily(Arc::clone(&state)).await, expected);
+
+        // Re-running is idempotent; new events in the window are picked up.
+        state.db.analytics_rollup(day(1), day(3)).await.unwrap();
+        assert_eq!(daily(Arc::clone(&state)).await, expected);
+        seed(&view, Some("analytics-95-e"), "2020-01-02 09:00:00Z")
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        state.db.analytics_rollup(day(1), day(3)).await.unwrap();
+        let expected: Vec<DailyRow> = vec![
+            (day(1), click.clone(), 4, 3),
+            (day(1), view.clone(), 1, 0),
+            (day(2), view.clone(), 1, 1),
+        ];
+        assert_eq!(daily(Arc::clone(&state)).await, expected);
+
+        cleanup_rollup(&state).await;
+    }
+
+    /// The summary endpoint validates `days` and reports a window ending today.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_summary_window() {
+        let state = build_state().await;
+
+        let (code, body) = send(
+            &state,
+            Request::builder()
+                .uri("/admin/analytics/summary?days=3")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["days"], 3);
+        assert_eq!(body["to"], chrono::Utc::now().date_naive().to_string());
+
+        let (code, _) = send(
+            &state,
+            Request::builder()
+                .uri("/admin/analytics/summary?days=91")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.trust_proxy = true;
+        config.trusted_proxy_cidrs = Vec::new();
+        config.analytics_rate_limit_max = RATE_LIMIT_MAX;
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -0,0 +1,291 @@
+//! Per-key request metering and monthly quotas for database-backed API keys.
+//!
+//! [`crate::security::api_key_middleware`] counts every request made with a
+//! database key in Redis: one counter per key per UTC day and one per calendar
+//! month. [`UsageMeter::record`] checks the month counter against the key's
+//! `monthly_quota` in the same script that increments both, so a key that has
+//! used its quota is rejected without being counted. The quota comes from the
+//! key record the middleware already loads to authenticate the request, so
+//! enforcement costs one Redis round trip and no database query. Static
+//! `API_KEYS` have no record and are neither metered nor capped.
+//!
+//! A supervised task (spawned in `main.rs`) calls [`flush`] every
+//! [`FLUSH_INTERVAL`] and once more on shutdown, copying the daily counters
+//! into `api_key_usage`. A counter holds the running total for its day, so
+//! rows are overwritten rather than added to and a repeated flush is a no-op.
+//! `GET /api/v1/admin/keys/:id/usage` reads that table and overlays the live
+//! counters for the days they still cover, so today's figure is never up to an
+//! hour stale.
+//!
+//! The month counter lives only in Redis. Losing Redis resets every key's
+//! month-to-date count; the flushed daily rows are unaffected.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{keys, RedisCache},
+    db::Database,
+    metrics::Metrics,
+    AppState,
+};
+
+/// Default and maximum `days` for the usage endpoint.
+pub const DEFAULT_USAGE_DAYS: u32 = 30;
+pub const MAX_USAGE_DAYS: u32 = 90;
+
+/// How often daily counters are copied to `api_key_usage`.
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Daily counters outlive their day by long enough for the first flush after
+/// midnight to record the final total, with a day of slack for a missed run.
+const DAY_COUNTER_TTL: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+
+/// Days, today included, for which a daily counter may still be in Redis.
+const LIVE_DAYS: u64 = 3;
+
+/// Month counters outlive the longest month.
+const MONTH_COUNTER_TTL: Duration = Duration::from_secs(32 * 24 * 60 * 60);
+
+/// Upper bound on counters read by one flush. At three live days per key this
+/// covers tens of thousands of keys; anything beyond is flushed next hour.
+const MAX_FLUSH_COUNTERS: usize = 100_000;
+
+const WORKER_NAME: &str = "api_key_usage_flush";
+
+/// Outcome of metering one request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaDecision {
+    /// Counted; `used` is the key's month-to-date total including it.
+    Allowed { used: u64 },
+    /// The key has used all `quota` requests this month; not counted.
+    Exceeded { quota: u64 },
+}
+
+/// Redis-backed request counter for database API keys.
+#[derive(Clone)]
+pub struct UsageMeter {
+    cache: RedisCache,
+    metrics: Metrics,
+}
+
+impl UsageMeter {
+    pub fn new(cache: RedisCache, metrics: Metrics) -> Self {
+        Self { cache, metrics }
+    }
+
+    /// Count one request made with `key_id` at `now`, unless the key's month
+    /// counter has already reached `monthly_quota`.
+    pub async fn record(
+        &self,
+        key_id: uuid::Uuid,
+        monthly_quota: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<QuotaDecision> {
+        let day = now.date_naive();
+        let counters = [
+            (keys::api_key_usage_month(&key_id, day), MONTH_COUNTER_TTL),
+            (keys::api_key_usage_day(&key_id, day), DAY_COUNTER_TTL),
+        ];
+        let quota = monthly_quota.map(|q| q.max(0) as u64);
+        match self.cache.incr_capped(&counters, quota).await? {
+            Some(used) => Ok(QuotaDecision::Allowed { used }),
+            None => {
+                self.metrics.observe_api_key_quota_rejection();
+                Ok(QuotaDecision::Exceeded {
+                    quota: quota.unwrap_or(0),
+                })
+            }
+        }
+    }
+
+    /// Live counters for `key_id`: the daily totals still in Redis between
+    /// `from` and today, and the month-to-date total.
+    pub async fn live(
+        &self,
+        key_id: uuid::Uuid,
+        from: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<(Vec<(NaiveDate, i64)>, i64)> {
+        let today = now.date_naive();
+        let first_live = today
+            .checked_sub_days(Days::new(LIVE_DAYS - 1))
+            .unwrap_or(today)
+            .max(from);
+        let days: Vec<NaiveDate> = first_live.iter_days().take_while(|d| *d <= today).collect();
+
+        let mut counter_keys: Vec<String> =
+            days.iter().map(|day| keys::api_key_usage_day(&key_id, *day)).collect();
+        counter_keys.push(keys::api_key_usage_month(&key_id, today));
+
+        let mut counts = self.cache.mget_json::<i64>(&counter_keys).await?;
+        let month_to_date = counts.pop().flatten().unwrap_or(0);
+        let daily = days
+            .into_iter()
+            .zip(counts)
+            .filter_map(|(day, count)| Some((day, count?)))
+            .collect();
+        Ok((daily, month_to_date))
+    }
+}
+
+/// One day of an API key's usage series.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct DailyUsage {
+    pub day: NaiveDate,
+    pub requests: i64,
+}
+
+/// Response body for `GET /api/v1/admin/keys/:id/usage`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ApiKeyUsage {
+    pub key_id: uuid::Uuid,
+    pub label: String,
+    /// `null` when the key is unlimited.
+    pub monthly_quota: Option<i64>,
+    /// Requests counted so far this calendar month (UTC).
+    pub month_to_date: i64,
+    /// One point per UTC day, oldest first; days without requests are zero.
+    pub days: Vec<DailyUsage>,
+}
+
+/// Merge flushed rows and live counters into one point per day from `from`
+/// to `to` inclusive. Where both have a day the larger wins: the live counter
+/// is ahead of the last flush, and a flushed row outlives its counter.
+pub fn usage_series(
+    from: NaiveDate,
+    to: NaiveDate,
+    flushed: &[(NaiveDate, i64)],
+    live: &[(NaiveDate, i64)],
+) -> Vec<DailyUsage> {
+    let mut by_day: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    for (day, requests) in flushed.iter().chain(live) {
+        let entry = by_day.entry(*day).or_default();
+        *entry = (*entry).max(*requests);
+    }
+    from.iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| DailyUsage {
+            day,
+            requests: by_day.get(&day).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Copy every daily counter in Redis into `api_key_usage`. Returns the number
+/// of rows written.
+pub async fn flush(cache: &RedisCache, db: &Database) -> anyhow::Result<u64> {
+    let (counter_keys, truncated) = cache
+        .scan_keys(&keys::api_key_usage_day_pattern(), MAX_FLUSH_COUNTERS)
+        .await?;
+    if truncated {
+        tracing::warn!(
+            limit = MAX_FLUSH_COUNTERS,
+            "[api-key-usage] more daily counters than one flush reads; the rest wait for the next run"
+        );
+    }
+
+    let counts = cache.mget_json::<i64>(&counter_keys).await?;
+    let entries: Vec<(uuid::Uuid, NaiveDate, i64)> = counter_keys
+        .iter()
+        .zip(counts)
+        .filter_map(|(key, count)| {
+            let (key_id, day) = parse_day_counter(key)?;
+            Some((key_id, day, count?))
+        })
+        .collect();
+    db.api_key_usage_upsert(&entries).await
+}
+
+/// Key id and day of a [`keys::api_key_usage_day`] key.
+fn parse_day_counter(key: &str) -> Option<(uuid::Uuid, NaiveDate)> {
+    let rest = key.strip_prefix(keys::USAGE_PREFIX)?.strip_prefix(":key:")?;
+    let (key_id, day) = rest.split_once(":day:")?;
+    Some((
+        key_id.parse().ok()?,
+        NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()?,
+    ))
+}
+
+/// Flush every [`FLUSH_INTERVAL`] until `shutdown`, then once more so the
+/// last partial hour is not lost.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately; counters are at most an hour old
+    // after a restart, so there is nothing to gain from flushing on startup.
+    interval.tick().await;
+    loop {
+        let stopping = tokio::select! {
+            _ = shutdown.cancelled() => true,
+            _ = interval.tick() => false,
+        };
+        match flush(&state.cache, &state.db).await {
+            Ok(n) => tracing::debug!("[api-key-usage] flushed {n} daily counters"),
+            Err(e) => tracing::warn!("[api-key-usage] flush error: {e}"),
+        }
+        if stopping {
+            break;
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn day_counter_keys_round_trip() {
+        let key_id = uuid::Uuid::new_v4();
+        let key = keys::api_key_usage_day(&key_id, day(2026, 3, 9));
+        assert_eq!(parse_day_counter(&key), Some((key_id, day(2026, 3, 9))));
+        assert_eq!(parse_day_counter(&keys::api_key_usage_month(&key_id, day(2026, 3, 9))), None);
+        assert_eq!(parse_day_counter("usage:v1:key:not-a-uuid:day:2026-03-09"), None);
+    }
+
+    #[test]
+    fn month_counter_is_shared_across_the_month() {
+        let key_id = uuid::Uuid::new_v4();
+        assert_eq!(
+            keys::api_key_usage_month(&key_id, day(2026, 3, 1)),
+            keys::api_key_usage_month(&key_id, day(2026, 3, 31))
+        );
+        assert_ne!(
+            keys::api_key_usage_month(&key_id, day(2026, 3, 31)),
+            keys::api_key_usage_month(&key_id, day(2026, 4, 1))
+        );
+    }
+
+    #[test]
+    fn series_fills_gaps_and_prefers_the_larger_count() {
+        let flushed = [(day(2026, 3, 1), 5), (day(2026, 3, 3), 40)];
+        let live = [(day(2026, 3, 3), 42), (day(2026, 3, 4), 7)];
+        let series = usage_series(day(2026, 3, 1), day(2026, 3, 4), &flushed, &live);
+        let points: Vec<(NaiveDate, i64)> = series.iter().map(|p| (p.day, p.requests)).collect();
+        assert_eq!(
+            points,
+            vec![
+                (day(2026, 3, 1), 5),
+                (day(2026, 3, 2), 0),
+                (day(2026, 3, 3), 42),
+                (day(2026, 3, 4), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn series_ignores_days_outside_the_window() {
+        let flushed = [(day(2026, 2, 28), 9)];
+        let series = usage_series(day(2026, 3, 1), day(2026, 3, 1), &flushed, &[]);
+        assert_eq!(series, vec![DailyUsage { day: day(2026, 3, 1), requests: 0 }]);
+    }
+}
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod api_key_usage_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::get,
+        Router,
+    };
+    use chrono::{Days, Utc};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        api_key_usage::{flush, QuotaDecision, UsageMeter},
+        cache::keys,
+        db::{ApiKeyRecord, Database},
+        handlers::api_key_usage,
+        security::{api_key_middleware, ApiKeyAuth},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Insert a database key and return its raw value and record.
+    async fn insert_key(state: &crate::AppState, monthly_quota: Option<i64>) -> (String, ApiKeyRecord) {
+        let raw = format!("test-{}", uuid::Uuid::new_v4().simple());
+        let record = state
+            .db
+            .api_key_insert(&Database::hash_api_key(&raw), "usage-test", monthly_quota)
+            .await
+            .unwrap();
+        (raw, record)
+    }
+
+    fn meter(state: &crate::AppState) -> UsageMeter {
+        UsageMeter::new(state.cache.clone(), state.metrics.clone())
+    }
+
+    /// A route behind the metering API key middleware.
+    fn metered_app(state: &Arc<crate::AppState>) -> Router {
+        let auth = Arc::new(
+            ApiKeyAuth::new_with_db(Vec::new(), Arc::new(state.db.clone())).with_usage(meter(state)),
+        );
+        Router::new()
+            .route("/partner", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(auth, api_key_middleware))
+    }
+
+    async fn call(app: &Router, raw_key: &str) -> (StatusCode, Option<String>, Value) {
+        let request = Request::builder()
+            .uri("/partner")
+            .header("x-api-key", raw_key)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get("retry-after")
+            .map(|v| v.to_str().unwrap().to_string());
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, retry_after, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    async fn counter(state: &crate::AppState, key: &str) -> Option<i64> {
+        state.cache.get_json::<i64>(key).await.unwrap()
+    }
+
+    async fn flushed(state: &crate::AppState, key_id: uuid::Uuid) -> Vec<(chrono::NaiveDate, i64)> {
+        let from = Utc::now().date_naive() - Days::new(30);
+        state.db.api_key_usage_series(key_id, from).await.unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState, ids: &[uuid::Uuid]) {
+        for id in ids {
+            state
+                .cache
+                .del_by_pattern(&format!("{}:key:{id}:*", keys::USAGE_PREFIX))
+                .await
+                .unwrap();
+        }
+        // api_key_usage rows go with the key (ON DELETE CASCADE).
+        sqlx::query("DELETE FROM api_keys WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Quota enforcement
+    // ---------------------------------------------------------------------------
+
+    /// A key that reaches its quota partway through the day is rejected from
+    /// the next request on, and rejected requests are not counted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_quota_exhausted_mid_day_returns_quota_exceeded() {
+        let state = build_test_state().await;
+        let (raw, record) = insert_key(&state, Some(5)).await;
+        let today = Utc::now().date_naive();
+        let month_key = keys::api_key_usage_month(&record.id, today);
+        let day_key = keys::api_key_usage_day(&record.id, today);
+        // Earlier days this month used 3 of the 5 requests.
+        state.cache.set_json(&month_key, &3i64, std::time::Duration::from_secs(3600)).await.unwrap();
+
+        let app = metered_app(&state);
+        for _ in 0..2 {
+            let (status, _, _) = call(&app, &raw).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (status, retry_after, body) = call(&app, &raw).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "QUOTA_EXCEEDED");
+        assert!(retry_after.unwrap().parse::<i64>().unwrap() > 0);
+
+        let (status, _, _) = call(&app, &raw).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(counter(&state, &month_key).await, Some(5));
+        assert_eq!(counter(&state, &day_key).await, Some(2));
+
+        cleanup(&state, &[record.id]).await;
+    }
+
+    /// Keys without a quota are counted but never rejected.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_unlimited_key_is_metered_but_not_capped() {
+        let state = build_test_state().await;
+        let (_, record) = insert_key(&state, None).await;
+        let meter = meter(&state);
+
+        for expected in 1..=3 {
+            assert_eq!(
+                meter.record(record.id, None, Utc::now()).await.unwrap(),
+                QuotaDecision::Allowed { used: expected }
+            );
+        }
+
+        cleanup(&state, &[record.id]).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Hourly flush
+    // ---------------------------------------------------------------------------
+
+    /// The flush writes one row per key per day holding that day's running
+    /// total; flushing again after more requests overwrites rather than adds.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_flush_aggregates_daily_counters_per_key() {
+        let state = build_test_state().await;
+        let (_, first) = insert_key(&state, None).await;
+        let (_, second) = insert_key(&state, None).await;
+        let meter = meter(&state);
+        let now = Utc::now();
+        let yesterday = now - chrono::Duration::days(1);
+
+        for _ in 0..4 {
+            meter.record(first.id, None, yesterday).await.unwrap();
+        }
+        for _ in 0..2 {
+            meter.record(first.id, None, now).await.unwrap();
+        }
+        meter.record(second.id, None, now).await.unwrap();
+
+        flush(&state.cache, &state.db).await.unwrap();
+        assert_eq!(
+            flushed(&state, first.id).await,
+            vec![(yesterday.date_naive(), 4), (now.date_naive(), 2)]
+        );
+        assert_eq!(flushed(&state, second.id).await, vec![(now.date_naive(), 1)]);
+
+        // Re-flushing unchanged counters is a no-op.
+        flush(&state.cache, &state.db).await.unwrap();
+        assert_eq!(flushed(&state, second.id).await, vec![(now.date_naive(), 1)]);
+
+        // More requests in the next hour replace today's total.
+        for _ in 0..3 {
+            meter.record(second.id, None, now).await.unwrap();
+        }
+        flush(&state.cache, &state.db).await.unwrap();
+        assert_eq!(flushed(&state, second.id).await, vec![(now.date_naive(), 4)]);
+        assert_eq!(
+            flushed(&state, first.id).await,
+            vec![(yesterday.date_naive(), 4), (now.date_naive(), 2)]
+        );
+
+        cleanup(&state, &[first.id, second.id]).await;
+    }
+
+    /// Counters left behind by a deleted key do not fail the flush.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_flush_skips_counters_of_deleted_keys() {
+        let state = build_test_state().await;
+        let (_, kept) = insert_key(&state, None).await;
+        let orphan = uuid::Uuid::new_v4();
+        let meter = meter(&state);
+        meter.record(kept.id, None, Utc::now()).await.unwrap();
+        meter.record(orphan, None, Utc::now()).await.unwrap();
+
+        flush(&state.cache, &state.db).await.unwrap();
+        assert_eq!(flushed(&state, kept.id).await.len(), 1);
+        assert!(flushed(&state, orphan).await.is_empty());
+
+        cleanup(&state, &[kept.id, orphan]).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // GET /api/v1/admin/keys/:id/usage
+    // ---------------------------------------------------------------------------
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_usage_endpoint_merges_flushed_rows_and_live_counters() {
+        let state = build_test_state().await;
+        let (_, record) = insert_key(&state, Some(1000)).await;
+        let today = Utc::now().date_naive();
+        let ten_days_ago = today - Days::new(10);
+        state
+            .db
+            .api_key_usage_upsert(&[(record.id, ten_days_ago, 17)])
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            meter(&state).record(record.id, Some(1000), Utc::now()).await.unwrap();
+        }
+
+        let app = Router::new()
+            .route("/admin/keys/:id/usage", get(api_key_usage))
+            .with_state(Arc::clone(&state));
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/admin/keys/{}/usage?days=14", record.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert_eq!(body["monthly_quota"], 1000);
+        assert_eq!(body["month_to_date"], 3);
+        let days = body["days"].as_array().unwrap();
+        assert_eq!(days.len(), 14);
+        assert_eq!(days[3]["day"], ten_days_ago.to_string());
+        assert_eq!(days[3]["requests"], 17);
+        assert_eq!(days[13]["day"], today.to_string());
+        assert_eq!(days[13]["requests"], 3);
+        assert_eq!(days.iter().map(|d| d["requests"].as_i64().unwrap()).sum::<i64>(), 20);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/admin/keys/{}/usage", uuid::Uuid::new_v4()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        cleanup(&state, &[record.id]).await;
+    }
+}
@@ -1,10 +1,8 @@
 use std::net::IpAddr;
 
-pub use crate::client_ip::{extract_client_ip, trusted_cidrs_from_env};
-
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{postgres::PgRow, PgPool, Row};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +19,14 @@ pub struct AuditLogEntry {
     pub error_message: Option<String>,
     pub request_id: Option<Uuid>,
     pub user_agent: Option<String>,
+    /// Request path and method; `None` for entries written outside the
+    /// admin middleware.
+    pub route: Option<String>,
+    pub method: Option<String>,
+    pub response_status: Option<u16>,
+    /// Allowlisted query and body fields; see
+    /// [`crate::body_redact::summarize_request`].
+    pub request_summary: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +46,28 @@ impl std::fmt::Display for AuditStatus {
     }
 }
 
+impl AuditStatus {
+    fn parse(raw: &str) -> Self {
+        match raw {
+            "failure" => Self::Failure,
+            "partial" => Self::Partial,
+            _ => Self::Success,
+        }
+    }
+}
+
+/// Filters for [`AuditLogger::query`]; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub resource_type: Option<String>,
+    /// Matches entries whose route starts with this path.
+    pub route: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct AuditLogger {
     pool: PgPool,
@@ -56,9 +84,10 @@ impl AuditLogger {
             r#"
             INSERT INTO audit_log (
                 timestamp, actor, actor_ip, action, resource_type, resource_id,
-                details, status, error_message, request_id, user_agent
+                details, status, error_message, request_id, user_agent,
+                route, method, response_status, request_summary
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            VALUES ($1, $2, $3::INET, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
             RETURNING id
             "#,
         )
@@ -73,6 +102,10 @@ impl AuditLogger {
         .bind(&entry.error_message)
         .bind(entry.request_id)
         .bind(&entry.user_agent)
+        .bind(&entry.route)
+        .bind(&entry.method)
+        .bind(entry.response_status.map(|s| s as i16))
+        .bind(&entry.request_summary)
         .fetch_one(&self.pool)
         .await?;
 
@@ -89,99 +122,49 @@ impl AuditLogger {
         Ok(id)
     }
 
-    /// Query audit log entries with filters.
+    /// Query audit log entries with filters, newest first.
     ///
     /// Uses `sqlx::QueryBuilder` so every filter value is bound as a typed
     /// parameter — the SQL string never contains user-supplied data directly.
     pub async fn query(
         &self,
-        actor: Option<&str>,
-        action: Option<&str>,
-        resource_type: Option<&str>,
-        from: Option<DateTime<Utc>>,
-        to: Option<DateTime<Utc>>,
+        filter: &AuditLogFilter,
         limit: i64,
         offset: i64,
     ) -> anyhow::Result<Vec<AuditLogEntry>> {
-        type Row = (
-            i64,
-            DateTime<Utc>,
-            String,
-            Option<String>,
-            String,
-            String,
-            Option<String>,
-            Option<serde_json::Value>,
-            String,
-            Option<String>,
-            Option<Uuid>,
-            Option<String>,
-        );
-
         let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
-            "SELECT id, timestamp, actor, actor_ip, action, resource_type, resource_id, \
-             details, status, error_message, request_id, user_agent \
+            "SELECT id, timestamp, actor, host(actor_ip) AS actor_ip, action, resource_type, \
+             resource_id, details, status, error_message, request_id, user_agent, \
+             route, method, response_status, request_summary \
              FROM audit_log WHERE 1=1",
         );
 
-        if let Some(a) = actor {
+        if let Some(a) = &filter.actor {
             qb.push(" AND actor = ").push_bind(a);
         }
-        if let Some(a) = action {
+        if let Some(a) = &filter.action {
             qb.push(" AND action = ").push_bind(a);
         }
-        if let Some(rt) = resource_type {
+        if let Some(rt) = &filter.resource_type {
             qb.push(" AND resource_type = ").push_bind(rt);
         }
-        if let Some(f) = from {
+        if let Some(route) = &filter.route {
+            // Escape LIKE metacharacters so the filter is a literal prefix.
+            let prefix = route.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+            qb.push(" AND route LIKE ").push_bind(format!("{prefix}%"));
+        }
+        if let Some(f) = filter.from {
             qb.push(" AND timestamp >= ").push_bind(f);
         }
-        if let Some(t) = to {
+        if let Some(t) = filter.to {
             qb.push(" AND timestamp <= ").push_bind(t);
         }
 
-        qb.push(" ORDER BY timestamp DESC LIMIT ").push_bind(limit);
+        qb.push(" ORDER BY timestamp DESC, id DESC LIMIT ").push_bind(limit);
         qb.push(" OFFSET ").push_bind(offset);
 
-        let rows: Vec<Row> = qb.build_query_as().fetch_all(&self.pool).await?;
-
-        Ok(rows
-            .into_iter()
-            .map(
-                |(
-                    id,
-                    timestamp,
-                    actor,
-                    actor_ip_str,
-                    action,
-                    resource_type,
-                    resource_id,
-                    details,
-                    status,
-                    error_message,
-                    request_id,
-                    user_agent,
-                )| AuditLogEntry {
-                    id: Some(id),
-                    timestamp,
-                    actor,
-                    actor_ip: actor_ip_str.and_then(|s| s.parse().ok()),
-                    action,
-                    resource_type,
-                    resource_id,
-                    details,
-                    status: match status.as_str() {
-                        "success" => AuditStatus::Success,
-                        "failure" => AuditStatus::Failure,
-                        "partial" => AuditStatus::Partial,
-                        _ => AuditStatus::Success,
-                    },
-                    error_message,
-                    request_id,
-                    user_agent,
-                },
-            )
-            .collect())
+        let rows = qb.build().fetch_all(&self.pool).await?;
+        rows.iter().map(audit_entry_from_row).collect()
     }
 
     /// Get audit log statistics
@@ -213,6 +196,30 @@ impl AuditLogger {
     }
 }
 
+fn audit_entry_from_row(row: &PgRow) -> anyhow::Result<AuditLogEntry> {
+    let actor_ip: Option<String> = row.try_get("actor_ip")?;
+    let status: String = row.try_get("status")?;
+    let response_status: Option<i16> = row.try_get("response_status")?;
+    Ok(AuditLogEntry {
+        id: Some(row.try_get("id")?),
+        timestamp: row.try_get("timestamp")?,
+        actor: row.try_get("actor")?,
+        actor_ip: actor_ip.and_then(|s| s.parse().ok()),
+        action: row.try_get("action")?,
+        resource_type: row.try_get("resource_type")?,
+        resource_id: row.try_get("resource_id")?,
+        details: row.try_get("details")?,
+        status: AuditStatus::parse(&status),
+        error_message: row.try_get("error_message")?,
+        request_id: row.try_get("request_id")?,
+        user_agent: row.try_get("user_agent")?,
+        route: row.try_get("route")?,
+        method: row.try_get("method")?,
+        response_status: response_status.map(|s| s as u16),
+        request_summary: row.try_get("request_summary")?,
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditStatistics {
     pub total: i64,
@@ -220,6 +227,38 @@ pub struct AuditStatistics {
     pub failed: i64,
 }
 
+/// Helper to create audit log entry from request context
+#[allow(clippy::too_many_arguments)]
+pub fn create_audit_entry(
+    actor: String,
+    actor_ip: Option<IpAddr>,
+    action: String,
+    resource_type: String,
+    resource_id: Option<String>,
+    details: Option<serde_json::Value>,
+    request_id: Option<Uuid>,
+    user_agent: Option<String>,
+) -> AuditLogEntry {
+    AuditLogEntry {
+        id: None,
+        timestamp: Utc::now(),
+        actor,
+        actor_ip,
+        action,
+        resource_type,
+        resource_id,
+        details,
+        status: AuditStatus::Success,
+        error_message: None,
+        request_id,
+        user_agent,
+        route: None,
+        method: None,
+        response_status: None,
+        request_summary: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +277,10 @@ mod tests {
             error_message: None,
             request_id: None,
             user_agent: None,
+            route: None,
+            method: None,
+            response_status: None,
+            request_summary: None,
         }
     }
 
@@ -276,6 +319,13 @@ mod tests {
         assert!(entry.id.is_none());
     }
 
+    #[test]
+    fn audit_status_parse_round_trips() {
+        for status in [AuditStatus::Success, AuditStatus::Failure, AuditStatus::Partial] {
+            assert_eq!(AuditStatus::parse(&status.to_string()).to_string(), status.to_string());
+        }
+    }
+
     #[test]
     fn make_entry_helper_sets_status() {
         let e = make_entry("admin", "delete", AuditStatus::Failure);
@@ -283,30 +333,3 @@ mod tests {
         assert_eq!(e.actor, "admin");
     }
 }
-
-/// Helper to create audit log entry from request context
-pub fn create_audit_entry(
-    actor: String,
-    actor_ip: Option<IpAddr>,
-    action: String,
-    resource_type: String,
-    resource_id: Option<String>,
-    details: Option<serde_json::Value>,
-    request_id: Option<Uuid>,
-    user_agent: Option<String>,
-) -> AuditLogEntry {
-    AuditLogEntry {
-        id: None,
-        timestamp: Utc::now(),
-        actor,
-        actor_ip,
-        action,
-        resource_type,
-        resource_id,
-        details,
-        status: AuditStatus::Success,
-        error_message: None,
-        request_id,
-        user_agent,
-    }
-}
@@ -3,8 +3,9 @@ pub use crate::body_redact::{body_logging_enabled, redact_sensitive, truncate_bo
 use std::sync::Arc;
 
 use axum::{
+    body::{Body, Bytes},
     extract::{ConnectInfo, Request, State},
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, Method, StatusCode},
     middleware::Next,
     response::Response,
 };
@@ -12,9 +13,45 @@ use uuid::Uuid;
 
 use crate::{
     audit::{create_audit_entry, AuditStatus},
+    body_redact::summarize_request,
+    security::{extract_client_ip_cidrs, ApiKeyIdentity},
+    validation::{body_limit, payload_too_large},
     AppState,
 };
 
+/// Query and body fields copied verbatim into `audit_log.request_summary`.
+/// Everything else is stored as `"[REDACTED]"`; add a field here only once
+/// it is known to carry no personal data or secrets.
+const SUMMARY_FIELDS: &[&str] = &[
+    "action",
+    "actor",
+    "confidence_bps",
+    "confirm",
+    "count",
+    "cursor",
+    "days",
+    "from",
+    "from_ledger",
+    "key",
+    "key_label",
+    "ledger",
+    "limit",
+    "name",
+    "offset",
+    "outcome",
+    "overlap_days",
+    "pattern",
+    "positions",
+    "resource_type",
+    "route",
+    "source",
+    "status",
+    "template_name",
+    "to",
+    "tx_hash",
+    "winning_outcome",
+];
+
 // ── auth-failure reason classification ───────────────────────────────────────
 
 /// Classify the reason a request received a 401 response.
@@ -58,8 +95,7 @@ impl AuthFailureReason {
 ///
 /// Never logs or stores the full key value.
 fn key_prefix(key: &str) -> String {
-    let chars: String = key.chars().take(4).collect();
-    format!("{}****", chars)
+    crate::log_redact::mask_secret(key)
 }
 
 // ── middleware ────────────────────────────────────────────────────────────────
@@ -68,33 +104,34 @@ fn key_prefix(key: &str) -> String {
 /// failures to the audit trail.
 ///
 /// For every request this middleware:
-/// 1. Runs the inner handler.
+/// 1. Buffers the body of mutating requests (up to the request size limit) so
+///    an allowlisted summary of it can be recorded, then runs the inner
+///    handler.
 /// 2. If the response is `401 Unauthorized`, creates an `auth_failure` audit
 ///    entry that includes the failure reason, the first 4 chars of the
 ///    attempted API key (if present), client IP, and user-agent.  The
 ///    `auth_failures_total{reason=...}` Prometheus counter is also incremented.
-/// 3. For all responses, creates a standard admin-operation audit entry.
+/// 3. For all responses, creates a standard admin-operation audit entry with
+///    the route, method, response status, and the key name that
+///    [`crate::security::api_key_middleware`] attached to the response.
+///
+/// Audit writes are spawned, so they never add latency to the response. Must
+/// be layered outside `api_key_middleware` to see the key name.
 pub async fn audit_logging_middleware(
     State(state): State<Arc<AppState>>,
-    ConnectInfo(addr): ConnectInfo<std::net::SocketAddr>,
+    connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
     headers: HeaderMap,
     mut request: Request,
     next: Next,
 ) -> Response {
     // ── capture request metadata ─────────────────────────────────────────────
-    let actor = headers
-        .get("x-api-key")
-        .and_then(|v| v.to_str().ok())
-        .map(|k| format!("api_key:{}", &k[..8.min(k.len())]))
-        .or_else(|| {
-            headers
-                .get("authorization")
-                .and_then(|v| v.to_str().ok())
-                .map(|s| s.to_string())
-        })
-        .unwrap_or_else(|| "unknown".to_string());
-
-    let actor_ip = Some(addr.ip());
+    let client_ip = extract_client_ip_cidrs(
+        &headers,
+        connect_info.as_ref(),
+        state.config.trust_proxy,
+        &state.config.trusted_proxy_cidrs,
+    );
+    let actor_ip = client_ip.parse::<std::net::IpAddr>().ok();
     let user_agent = headers
         .get("user-agent")
         .and_then(|v| v.to_str().ok())
@@ -102,15 +139,33 @@ pub async fn audit_logging_middleware(
 
     let request_id = Uuid::new_v4();
 
-    // Stash context for downstream handlers.
-    request.extensions_mut().insert(actor.clone());
-    request.extensions_mut().insert(request_id);
-
     let method = request.method().clone();
     let uri = request.uri().clone();
 
+    // ── buffer the body of mutating requests for the summary ─────────────────
+    let mut body = Bytes::new();
+    let mut oversized = false;
+    if matches!(method, Method::POST | Method::PUT | Method::PATCH | Method::DELETE) {
+        let (parts, raw) = request.into_parts();
+        match axum::body::to_bytes(raw, body_limit()).await {
+            Ok(bytes) => body = bytes,
+            Err(_) => oversized = true,
+        }
+        request = Request::from_parts(parts, Body::from(body.clone()));
+    }
+    let request_summary = summarize_request(uri.query(), &body, SUMMARY_FIELDS);
+
+    // Stash context for downstream handlers.
+    request.extensions_mut().insert(request_id);
+
     // ── execute the request ──────────────────────────────────────────────────
-    let response = next.run(request).await;
+    // An oversized body has already been consumed, so it is rejected here
+    // rather than by the size validation further in, but is still audited.
+    let response = if oversized {
+        payload_too_large(body_limit())
+    } else {
+        next.run(request).await
+    };
 
     let status_code = response.status();
 
@@ -124,7 +179,7 @@ pub async fn audit_logging_middleware(
                 let prefix = headers
                     .get("x-api-key")
                     .and_then(|v| v.to_str().ok())
-                    .map(|k| key_prefix(k))
+                    .map(key_prefix)
                     .unwrap_or_else(|| "****".to_string());
                 format!("api_key_attempt:{}", prefix)
             }
@@ -135,7 +190,7 @@ pub async fn audit_logging_middleware(
         tracing::warn!(
             reason = reason.as_str(),
             actor = %auth_actor,
-            client_ip = %addr.ip(),
+            client_ip = %client_ip,
             user_agent = ?user_agent,
             path = %uri.path(),
             "Authentication failure"
@@ -177,6 +232,22 @@ pub async fn audit_logging_middleware(
     let path = uri.path();
     let (action, resource_type, resource_id) = parse_admin_action(path, &method);
 
+    // The key name is only known once `api_key_middleware` has accepted the
+    // key; before that, fall back to a masked description of the credential.
+    let actor = response
+        .extensions()
+        .get::<ApiKeyIdentity>()
+        .map(|identity| identity.0.clone())
+        .unwrap_or_else(|| match AuthFailureReason::from_headers(&headers) {
+            AuthFailureReason::InvalidApiKey => headers
+                .get("x-api-key")
+                .and_then(|v| v.to_str().ok())
+                .map(|k| format!("api_key:{}", key_prefix(k)))
+                .unwrap_or_else(|| "unknown".to_string()),
+            AuthFailureReason::ExpiredToken => "token:****".to_string(),
+            AuthFailureReason::MissingCredentials => "unknown".to_string(),
+        });
+
     let status = if status_code.is_success() {
         AuditStatus::Success
     } else {
@@ -201,6 +272,10 @@ pub async fn audit_logging_middleware(
     );
     entry.status = status;
     entry.error_message = error_message;
+    entry.route = Some(path.to_string());
+    entry.method = Some(method.to_string());
+    entry.response_status = Some(status_code.as_u16());
+    entry.request_summary = Some(request_summary);
 
     let audit_logger = state.audit_logger.clone();
     tokio::spawn(async move {
@@ -223,7 +298,7 @@ fn parse_admin_action(path: &str, method: &axum::http::Method) -> (String, Strin
             .map(|id| id.to_string());
         ("resolve_market".to_string(), "market".to_string(), market_id)
     } else if path.contains("/email/preview/") {
-        let template = path.split('/').last().map(|s| s.to_string());
+        let template = path.split('/').next_back().map(|s| s.to_string());
         ("preview_email".to_string(), "email_template".to_string(), template)
     } else if path.contains("/email/test") {
         ("send_test_email".to_string(), "email".to_string(), None)
@@ -239,20 +314,93 @@ fn parse_admin_action(path: &str, method: &axum::http::Method) -> (String, Strin
             "email_queue".to_string(),
             None,
         )
-    } else if path.contains("/email/queue/dead-letter") && path.contains("/requeue") {
+    } else if path.contains("/email/dead-letter") && path.ends_with("/retry") {
         let job_id = path.split('/').nth_back(1).map(|s| s.to_string());
         (
-            "requeue_dead_letter".to_string(),
+            "retry_dead_letter".to_string(),
             "email_queue".to_string(),
             job_id,
         )
-    } else if path.contains("/email/queue/dead-letter") {
+    } else if path.contains("/email/dead-letter") {
         (
             "list_dead_letter".to_string(),
             "email_queue".to_string(),
             None,
         )
-    } else if path.contains("/audit/logs") {
+    } else if path.contains("/gdpr/") && !path.contains("/newsletter/") {
+        // The subject's email stays out of the audit row.
+        let action = if method == axum::http::Method::DELETE {
+            "gdpr_erase"
+        } else {
+            "gdpr_export"
+        };
+        (action.to_string(), "data_subject".to_string(), None)
+    } else if path.contains("/admin/campaigns") {
+        let campaign_id = path
+            .split('/')
+            .skip_while(|s| *s != "campaigns")
+            .nth(1)
+            .map(|s| s.to_string());
+        let action = if path.ends_with("/launch") {
+            "launch_campaign"
+        } else if path.ends_with("/cancel") {
+            "cancel_campaign"
+        } else if campaign_id.is_some() {
+            "view_campaign"
+        } else {
+            "create_campaign"
+        };
+        (action.to_string(), "campaign".to_string(), campaign_id)
+    } else if path.contains("/admin/contact/") && path.ends_with("/status") {
+        let submission_id = path.split('/').nth_back(1).map(|s| s.to_string());
+        (
+            "set_contact_status".to_string(),
+            "contact_submission".to_string(),
+            submission_id,
+        )
+    } else if path.contains("/admin/contact") {
+        (
+            "list_contact_submissions".to_string(),
+            "contact_submission".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/waitlist/invite") {
+        (
+            "invite_waitlist".to_string(),
+            "waitlist_entry".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/analytics/summary") {
+        (
+            "view_analytics_summary".to_string(),
+            "analytics".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/waitlist/export") {
+        (
+            "export_waitlist".to_string(),
+            "waitlist".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/newsletter/export") {
+        (
+            "export_newsletter".to_string(),
+            "newsletter".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/waitlist/stats") {
+        (
+            "view_waitlist_stats".to_string(),
+            "waitlist".to_string(),
+            None,
+        )
+    } else if path.contains("/admin/cache/keys") {
+        ("list_cache_keys".to_string(), "cache".to_string(), None)
+    } else if path.contains("/admin/cache/entry") {
+        ("view_cache_entry".to_string(), "cache".to_string(), None)
+    } else if path.ends_with("/admin/cache") && method == axum::http::Method::DELETE {
+        ("invalidate_cache".to_string(), "cache".to_string(), None)
+    } else if path.contains("/admin/audit") || path.contains("/audit/logs") {
         (
             "query_audit_logs".to_string(),
             "audit_log".to_string(),
@@ -366,6 +514,66 @@ mod tests {
         assert_eq!(resource_type, "audit_log");
     }
 
+    #[test]
+    fn parse_admin_audit_action() {
+        let (action, resource_type, _) =
+            parse_admin_action("/api/v1/admin/audit", &axum::http::Method::GET);
+        assert_eq!(action, "query_audit_logs");
+        assert_eq!(resource_type, "audit_log");
+    }
+
+    #[test]
+    fn parse_cache_admin_actions() {
+        let (action, resource_type, _) =
+            parse_admin_action("/api/v1/admin/cache/keys", &axum::http::Method::GET);
+        assert_eq!((action.as_str(), resource_type.as_str()), ("list_cache_keys", "cache"));
+        let (action, _, _) =
+            parse_admin_action("/api/v1/admin/cache/entry", &axum::http::Method::GET);
+        assert_eq!(action, "view_cache_entry");
+        let (action, _, _) =
+            parse_admin_action("/api/v1/admin/cache", &axum::http::Method::DELETE);
+        assert_eq!(action, "invalidate_cache");
+    }
+
+    #[test]
+    fn parse_gdpr_actions() {
+        let (action, resource_type, resource_id) =
+            parse_admin_action("/api/v1/gdpr/export", &axum::http::Method::GET);
+        assert_eq!((action.as_str(), resource_type.as_str()), ("gdpr_export", "data_subject"));
+        assert_eq!(resource_id, None);
+        let (action, _, _) =
+            parse_admin_action("/api/v1/gdpr/delete", &axum::http::Method::DELETE);
+        assert_eq!(action, "gdpr_erase");
+    }
+
+    #[test]
+    fn parse_campaign_actions() {
+        let (action, resource_type, resource_id) =
+            parse_admin_action("/api/v1/admin/campaigns", &axum::http::Method::POST);
+        assert_eq!((action.as_str(), resource_type.as_str()), ("create_campaign", "campaign"));
+        assert_eq!(resource_id, None);
+        let (action, _, resource_id) =
+            parse_admin_action("/api/v1/admin/campaigns/7", &axum::http::Method::GET);
+        assert_eq!(action, "view_campaign");
+        assert_eq!(resource_id.as_deref(), Some("7"));
+        let (action, _, resource_id) =
+            parse_admin_action("/api/v1/admin/campaigns/7/launch", &axum::http::Method::POST);
+        assert_eq!(action, "launch_campaign");
+        assert_eq!(resource_id.as_deref(), Some("7"));
+        let (action, _, _) =
+            parse_admin_action("/api/v1/admin/campaigns/7/cancel", &axum::http::Method::POST);
+        assert_eq!(action, "cancel_campaign");
+    }
+
+    #[test]
+    fn summary_fields_are_sorted_and_not_sensitive() {
+        assert!(SUMMARY_FIELDS.windows(2).all(|w| w[0] < w[1]));
+        for field in SUMMARY_FIELDS {
+            let body = format!(r#"{{"{field}":"v"}}"#);
+            assert_eq!(crate::body_redact::redact_sensitive(&body), body, "{field}");
+        }
+    }
+
     #[test]
     fn parse_unknown_path_falls_back_to_admin_action() {
         let (action, resource_type, _) =
@@ -0,0 +1,245 @@
+#[cfg(test)]
+mod audit_middleware_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::{get, post},
+        Json, Router,
+    };
+    use http_body_util::BodyExt;
+    use std::{sync::Arc, time::Duration};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::{
+        audit::{AuditLogEntry, AuditLogFilter},
+        audit_middleware::audit_logging_middleware,
+        body_redact::REDACTED,
+        handlers::{admin_audit_list, ApiError},
+        security::{api_key_middleware, ApiKeyAuth},
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// audit_log is append-only, so tests cannot clean up after themselves.
+    /// Every route lives under a reserved prefix plus a per-test marker.
+    const ROUTE_PREFIX: &str = "/api/v1/admin/audit-96";
+    const API_KEY: &str = "audit-96-key";
+
+    /// The admin router's auth and audit layers in production order, around
+    /// one handler that succeeds and two that fail.
+    fn app(state: Arc<crate::AppState>) -> Router {
+        let auth = Arc::new(ApiKeyAuth::new(vec!["other-key".to_string(), API_KEY.to_string()]));
+        Router::new()
+            .route(&format!("{ROUTE_PREFIX}/:marker/ok"), post(|| async { Json(serde_json::json!({ "ok": true })) }))
+            .route(
+                &format!("{ROUTE_PREFIX}/:marker/rejected"),
+                post(|| async { ApiError::unprocessable("INVALID_TRANSITION", "nope") }),
+            )
+            .route(
+                &format!("{ROUTE_PREFIX}/:marker/broken"),
+                get(|| async { ApiError::internal(anyhow::anyhow!("boom")) }),
+            )
+            .route("/api/v1/admin/audit", get(admin_audit_list))
+            .layer(middleware::from_fn_with_state(auth, api_key_middleware))
+            .layer(middleware::from_fn_with_state(state.clone(), audit_logging_middleware))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, req: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app(Arc::clone(state)).oneshot(req).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    fn post_json(uri: &str, key: Option<&str>, body: serde_json::Value) -> Request<Body> {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("x-forwarded-for", client_ip());
+        if let Some(key) = key {
+            builder = builder.header("x-api-key", key);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    fn client_ip() -> String {
+        let bytes = Uuid::new_v4().into_bytes();
+        format!("10.96.{}.{}", bytes[0], bytes[1])
+    }
+
+    fn marker() -> String {
+        Uuid::new_v4().simple().to_string()
+    }
+
+    /// Audit writes are spawned after the response, so poll for the row.
+    async fn audit_entry(state: &crate::AppState, route: &str) -> AuditLogEntry {
+        let filter = AuditLogFilter {
+            route: Some(route.to_string()),
+            ..Default::default()
+        };
+        for _ in 0..50 {
+            let mut entries = state.audit_logger.query(&filter, 10, 0).await.unwrap();
+            if let Some(entry) = entries.pop() {
+                assert!(entries.is_empty(), "more than one entry for {route}");
+                return entry;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("no audit entry for {route}");
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A successful request is recorded with the key name, route, method,
+    /// status, and a summary that keeps only allowlisted fields.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_success_response_is_audited() {
+        let state = build_state().await;
+        let route = format!("{ROUTE_PREFIX}/{}/ok", marker());
+
+        let (status, _) = send(
+            &state,
+            post_json(
+                &format!("{route}?limit=5&email=a%40example.com"),
+                Some(API_KEY),
+                serde_json::json!({ "status": "closed", "recipient": "a@example.com" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let entry = audit_entry(&state, &route).await;
+        assert_eq!(entry.actor, "static:2");
+        assert_eq!(entry.route.as_deref(), Some(route.as_str()));
+        assert_eq!(entry.method.as_deref(), Some("POST"));
+        assert_eq!(entry.response_status, Some(200));
+        assert_eq!(entry.status.to_string(), "success");
+        assert!(entry.actor_ip.unwrap().to_string().starts_with("10.96."));
+
+        let summary = entry.request_summary.expect("request summary");
+        assert_eq!(summary["body"]["status"], "closed");
+        assert_eq!(summary["body"]["recipient"], REDACTED);
+        assert_eq!(summary["query"]["limit"], "5");
+        assert_eq!(summary["query"]["email"], REDACTED);
+    }
+
+    /// Client and server errors are recorded as failures with their status.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_failure_responses_are_audited() {
+        let state = build_state().await;
+
+        let rejected = format!("{ROUTE_PREFIX}/{}/rejected", marker());
+        let (status, _) = send(&state, post_json(&rejected, Some(API_KEY), serde_json::json!({}))).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        let entry = audit_entry(&state, &rejected).await;
+        assert_eq!(entry.actor, "static:2");
+        assert_eq!(entry.response_status, Some(422));
+        assert_eq!(entry.status.to_string(), "failure");
+
+        let broken = format!("{ROUTE_PREFIX}/{}/broken", marker());
+        let req = Request::builder()
+            .uri(&broken)
+            .header("x-api-key", API_KEY)
+            .header("x-forwarded-for", client_ip())
+            .body(Body::empty())
+            .unwrap();
+        let (status, _) = send(&state, req).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        let entry = audit_entry(&state, &broken).await;
+        assert_eq!(entry.method.as_deref(), Some("GET"));
+        assert_eq!(entry.response_status, Some(500));
+        assert_eq!(entry.status.to_string(), "failure");
+        assert_eq!(entry.request_summary, Some(serde_json::json!({})));
+    }
+
+    /// A rejected key is recorded without ever storing the key itself.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_unauthorized_request_is_audited_with_masked_key() {
+        let state = build_state().await;
+        let route = format!("{ROUTE_PREFIX}/{}/ok", marker());
+
+        let (status, _) = send(&state, post_json(&route, Some("wrong-secret"), serde_json::json!({}))).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let entry = audit_entry(&state, &route).await;
+        assert_eq!(entry.actor, "api_key:wron****");
+        assert_eq!(entry.response_status, Some(401));
+    }
+
+    /// The listing endpoint filters by route prefix and actor and pages with
+    /// an offset cursor.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_audit_list_filters_and_paginates() {
+        let state = build_state().await;
+        let prefix = format!("{ROUTE_PREFIX}/{}", marker());
+        for _ in 0..3 {
+            let (status, _) =
+                send(&state, post_json(&format!("{prefix}/ok"), Some(API_KEY), serde_json::json!({}))).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let filter = AuditLogFilter {
+            route: Some(prefix.clone()),
+            ..Default::default()
+        };
+        for _ in 0..50 {
+            if state.audit_logger.query(&filter, 10, 0).await.unwrap().len() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        let list = |query: String| {
+            Request::builder()
+                .uri(format!("/api/v1/admin/audit?{query}"))
+                .header("x-api-key", API_KEY)
+                .header("x-forwarded-for", client_ip())
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let (status, page) = send(&state, list(format!("route={prefix}&actor=static:2&limit=2"))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["items"].as_array().unwrap().len(), 2);
+        assert_eq!(page["has_more"], true);
+        assert_eq!(page["next_cursor"], "2");
+
+        let (_, page) = send(&state, list(format!("route={prefix}&limit=2&cursor=2"))).await;
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+        assert_eq!(page["has_more"], false);
+
+        let (_, page) = send(&state, list(format!("route={prefix}&actor=static:1"))).await;
+        assert!(page["items"].as_array().unwrap().is_empty());
+
+        let (status, _) = send(
+            &state,
+            list("from=2026-02-01T00:00:00Z&to=2026-01-01T00:00:00Z".to_string()),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.trust_proxy = true;
+        config.trusted_proxy_cidrs = vec![];
+        build_test_state_with(config, &[]).await
+    }
+}
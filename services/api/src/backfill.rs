@@ -0,0 +1,321 @@
+//! Historical event backfill.
+//!
+//! The indexer only stores events from its first sync cursor onwards, so a
+//! contract that was live before the indexer has history missing from
+//! `chain_events`, and everything derived from it under-reports.
+//! `POST /api/v1/admin/indexer/backfill` records a job for a ledger range;
+//! the supervised [`run`] task walks `getEvents` over that range one page of
+//! `BACKFILL_PAGE_SIZE` at a time, pausing `BACKFILL_PAGE_INTERVAL_MS`
+//! between pages so the live sync keeps its share of the RPC node.
+//!
+//! Each page is stored before its progress is recorded in `backfill_jobs`,
+//! and inserts are idempotent, so a job resumed after a crash or restart
+//! repeats at most one page. Historical events only fill in the tables: they
+//! send no notification emails and invalidate nothing per event. Once the
+//! range is covered, the job rebuilds price points for the range and the
+//! leaderboards, drops cached portfolios, and is marked completed.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    blockchain::{BlockchainClient, ContractEvent, IndexedEvent},
+    cache::keys,
+    db::Database,
+    leaderboard, protocol_state, AppState,
+};
+
+/// How often the task looks for a job when none is running.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a claimed job stays with this instance without progress. Every
+/// recorded page renews it; once it lapses another instance may resume.
+pub const LEASE: Duration = Duration::from_secs(2 * 60);
+
+const WORKER_NAME: &str = "indexer_backfill";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BackfillStatus {
+    /// Recorded; no page fetched yet.
+    Pending,
+    /// Walking the ledger range.
+    Running,
+    /// Every page is stored; derived tables are being rebuilt.
+    Reconciling,
+    Completed,
+}
+
+impl BackfillStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Reconciling => "reconciling",
+            Self::Completed => "completed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "pending" => Some(Self::Pending),
+            "running" => Some(Self::Running),
+            "reconciling" => Some(Self::Reconciling),
+            "completed" => Some(Self::Completed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BackfillJob {
+    pub id: i64,
+    pub network: String,
+    pub from_ledger: i64,
+    pub to_ledger: i64,
+    pub status: BackfillStatus,
+    /// RPC paging token the next page is fetched after.
+    pub page_cursor: Option<String>,
+    /// Highest ledger among the events stored so far.
+    pub last_ledger: Option<i64>,
+    pub pages: i64,
+    /// Events read from the node, including any re-read after a resume.
+    pub events_scanned: i64,
+    /// Events that were not yet in `chain_events`.
+    pub events_inserted: i64,
+    /// Share of the ledger range covered, from 0 to 1.
+    pub progress: f64,
+    /// The most recent failure; the job is retried on the next poll.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Share of `[from_ledger, to_ledger]` covered once events up to
+/// `last_ledger` are stored. Events are sparse, so a running job can sit
+/// below 1 after its last event; only a finished walk reports 1.
+pub fn progress(
+    from_ledger: i64,
+    to_ledger: i64,
+    last_ledger: Option<i64>,
+    status: BackfillStatus,
+) -> f64 {
+    if matches!(
+        status,
+        BackfillStatus::Reconciling | BackfillStatus::Completed
+    ) {
+        return 1.0;
+    }
+    let Some(last) = last_ledger else {
+        return 0.0;
+    };
+    let span = (to_ledger - from_ledger + 1).max(1) as f64;
+    ((last - from_ledger + 1) as f64 / span).clamp(0.0, 1.0)
+}
+
+/// Store one historical event the way the sync worker would, minus the
+/// notifications and cache invalidations, with its ledger close time as
+/// `indexed_at` so time-windowed aggregates place it correctly. Returns
+/// whether the event was new.
+pub async fn index_event(
+    db: &Database,
+    network: &str,
+    event: &ContractEvent,
+) -> anyhow::Result<bool> {
+    if let Some(change) = protocol_state::breaker_transition(event) {
+        db.protocol_state_change_insert(network, &change).await?;
+    }
+    match IndexedEvent::from_contract_event(event) {
+        Some(indexed) => {
+            db.chain_event_insert_at(network, &indexed, event.ledger_closed_at())
+                .await
+        }
+        None => Ok(false),
+    }
+}
+
+/// Walk `job`'s remaining pages on `client`, then reconcile. Returns the job
+/// as last recorded: completed, or still running when `shutdown` fired
+/// between pages. An error leaves the progress of every stored page in
+/// place for the next attempt.
+pub async fn run_job(
+    state: &AppState,
+    client: &BlockchainClient,
+    mut job: BackfillJob,
+    shutdown: &CancellationToken,
+) -> anyhow::Result<BackfillJob> {
+    let db = &state.db;
+    let from_ledger = u32::try_from(job.from_ledger)?;
+    let page_size = state.config.backfill_page_size;
+
+    while matches!(
+        job.status,
+        BackfillStatus::Pending | BackfillStatus::Running
+    ) {
+        let page = client
+            .fetch_events_page(from_ledger, job.page_cursor.as_deref(), page_size)
+            .await?;
+        let mut done = page.next_cursor.is_none();
+        let (mut scanned, mut inserted, mut last_ledger) = (0, 0, None);
+        for event in &page.events {
+            if i64::from(event.ledger) > job.to_ledger {
+                done = true;
+                break;
+            }
+            if index_event(db, &job.network, event).await? {
+                inserted += 1;
+            }
+            scanned += 1;
+            last_ledger = Some(i64::from(event.ledger));
+        }
+
+        job = db
+            .backfill_job_record_page(
+                job.id,
+                page.next_cursor.as_deref(),
+                last_ledger,
+                scanned,
+                inserted,
+                LEASE,
+            )
+            .await?;
+        if done {
+            job = db
+                .backfill_job_set_status(job.id, BackfillStatus::Reconciling)
+                .await?;
+            break;
+        }
+
+        tokio::select! {
+            _ = shutdown.cancelled() => return Ok(job),
+            _ = tokio::time::sleep(state.config.backfill_page_interval) => {}
+        }
+    }
+
+    if job.status == BackfillStatus::Reconciling {
+        reconcile(state, &job).await?;
+        job = db
+            .backfill_job_set_status(job.id, BackfillStatus::Completed)
+            .await?;
+        tracing::info!(
+            job_id = job.id,
+            network = %job.network,
+            events_inserted = job.events_inserted,
+            "[backfill] job completed"
+        );
+    }
+    Ok(job)
+}
+
+/// Rebuild what is derived from the backfilled range: price points for its
+/// bets, the leaderboards (primary network only, as they are built for it),
+/// and every cached portfolio. Each step is idempotent, so a job interrupted
+/// here simply reconciles again.
+async fn reconcile(state: &AppState, job: &BackfillJob) -> anyhow::Result<()> {
+    let points = state
+        .db
+        .price_points_backfill(&job.network, job.from_ledger, job.to_ledger)
+        .await?;
+    if job.network == state.config.network_name() {
+        leaderboard::refresh_all(&state.db, &state.cache, &state.config).await?;
+    }
+    let purged = state
+        .cache
+        .del_by_pattern(&keys::api_user_portfolio("*"))
+        .await?;
+    state.metrics.observe_invalidation("backfill", purged);
+    tracing::info!(
+        job_id = job.id,
+        points,
+        purged,
+        "[backfill] derived tables reconciled"
+    );
+    Ok(())
+}
+
+/// Run unfinished backfill jobs for the networks this instance serves, one
+/// at a time, until `shutdown` fires.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    let networks: Vec<String> = state
+        .networks
+        .names()
+        .into_iter()
+        .map(str::to_owned)
+        .collect();
+    loop {
+        let claimed = match state.db.backfill_job_claim(&networks, LEASE).await {
+            Ok(job) => job,
+            Err(e) => {
+                tracing::warn!("[backfill] claim failed: {e}");
+                None
+            }
+        };
+        if let Some(job) = claimed {
+            let id = job.id;
+            // Claims only cover the networks served here.
+            let client = state
+                .networks
+                .get(&job.network)
+                .expect("claimed job on a served network");
+            tracing::info!(job_id = id, network = %job.network, status = job.status.label(), "[backfill] job claimed");
+            match run_job(&state, client, job, &shutdown).await {
+                Ok(job) if job.status == BackfillStatus::Completed => continue,
+                Ok(_) => {
+                    // Stopped for shutdown: free the job for the next start.
+                    let _ = state.db.backfill_job_release(id, None).await;
+                }
+                Err(e) => {
+                    tracing::warn!(job_id = id, "[backfill] job failed, will retry: {e:#}");
+                    if let Err(e) = state
+                        .db
+                        .backfill_job_release(id, Some(&format!("{e:#}")))
+                        .await
+                    {
+                        tracing::warn!(job_id = id, "[backfill] failed to record error: {e}");
+                    }
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_labels_round_trip() {
+        for status in [
+            BackfillStatus::Pending,
+            BackfillStatus::Running,
+            BackfillStatus::Reconciling,
+            BackfillStatus::Completed,
+        ] {
+            assert_eq!(BackfillStatus::parse(status.label()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.label());
+        }
+        assert_eq!(BackfillStatus::parse("failed"), None);
+    }
+
+    #[test]
+    fn progress_follows_the_last_stored_ledger() {
+        assert_eq!(progress(101, 200, None, BackfillStatus::Pending), 0.0);
+        assert_eq!(progress(101, 200, Some(150), BackfillStatus::Running), 0.5);
+        assert_eq!(progress(101, 200, Some(250), BackfillStatus::Running), 1.0);
+        assert_eq!(
+            progress(101, 200, Some(150), BackfillStatus::Reconciling),
+            1.0
+        );
+        assert_eq!(progress(7, 7, Some(7), BackfillStatus::Running), 1.0);
+    }
+}
@@ -0,0 +1,385 @@
+#[cfg(test)]
+mod backfill_tests {
+    use axum::{routing::post, Json, Router};
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde_json::{json, Value};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        backfill::{self, BackfillJob, BackfillStatus},
+        cache::keys,
+        db::Database,
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    const PAGE_SIZE: u32 = 2;
+
+    /// A `getEvents` node over a fixed, ledger-ordered event list. Pages start
+    /// after `cursor` or at `startLedger`, hold at most `limit` events, and
+    /// the `fail_call`-th call (1-based; 0 never) is a JSON-RPC error.
+    struct MockRpc {
+        events: Vec<Value>,
+        calls: AtomicUsize,
+        fail_call: AtomicUsize,
+        cursors: Mutex<Vec<Option<String>>>,
+    }
+
+    impl MockRpc {
+        fn page(&self, params: &Value) -> Value {
+            let limit = params["limit"].as_u64().unwrap_or(100) as usize;
+            let cursor = params["cursor"].as_str();
+            self.cursors
+                .lock()
+                .unwrap()
+                .push(cursor.map(str::to_string));
+            let start = match cursor {
+                Some(c) => self
+                    .events
+                    .iter()
+                    .position(|e| e["id"] == c)
+                    .map_or(self.events.len(), |i| i + 1),
+                None => {
+                    let from = params["startLedger"].as_u64().unwrap_or(0);
+                    self.events
+                        .iter()
+                        .position(|e| e["ledger"].as_u64().unwrap() >= from)
+                        .unwrap_or(self.events.len())
+                }
+            };
+            let page: Vec<Value> = self
+                .events
+                .iter()
+                .skip(start)
+                .take(limit)
+                .cloned()
+                .collect();
+            json!({ "result": { "events": page, "latestLedger": 1_000 } })
+        }
+    }
+
+    async fn start_mock_rpc(events: Vec<Value>) -> (String, Arc<MockRpc>) {
+        let mock = Arc::new(MockRpc {
+            events,
+            calls: AtomicUsize::new(0),
+            fail_call: AtomicUsize::new(0),
+            cursors: Mutex::new(Vec::new()),
+        });
+        let served = mock.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let mock = served.clone();
+                async move {
+                    let resp = match body["method"].as_str().unwrap_or_default() {
+                        "getEvents" => {
+                            let call = mock.calls.fetch_add(1, Ordering::SeqCst) + 1;
+                            if call == mock.fail_call.load(Ordering::SeqCst) {
+                                json!({ "error": { "code": -32603, "message": "node overloaded" } })
+                            } else {
+                                mock.page(&body["params"])
+                            }
+                        }
+                        _ => json!({ "error": { "code": -32601, "message": "not mocked" } }),
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, mock)
+    }
+
+    /// State whose only network is `network`, a name unique to the run, so
+    /// claims, events and cursors never meet another run's. It is not the
+    /// configured primary, so reconciling leaves the leaderboards alone.
+    async fn build_state(rpc_url: String, network: &str) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.retry_attempts = 1;
+        config.backfill_page_size = PAGE_SIZE;
+        config.backfill_page_interval = Duration::ZERO;
+        build_test_state_with(config, &[network]).await
+    }
+
+    fn unique_network() -> String {
+        format!("bftest{}", uuid::Uuid::new_v4().simple())
+    }
+
+    fn unique_market_id() -> i64 {
+        (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 4_000_000_000
+    }
+
+    /// A `bet_place` event of `amount` on `outcome` in a ledger that closed at
+    /// `closed_at`. Ids embed `network`, so they are unique to the run.
+    fn bet(
+        network: &str,
+        ledger: u32,
+        market_id: i64,
+        outcome: u32,
+        amount: i64,
+        closed_at: DateTime<Utc>,
+    ) -> Value {
+        json!({
+            "id": format!("{network}-{ledger:010}"),
+            "ledger": ledger,
+            "ledgerClosedAt": closed_at.to_rfc3339(),
+            "topic": ["bet_place", market_id, BETTOR],
+            "value": [1, outcome, amount.to_string()],
+        })
+    }
+
+    const BETTOR: &str = "GBACKFILLTESTBETTORXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+    async fn claim(state: &crate::AppState, network: &str) -> Option<BackfillJob> {
+        state
+            .db
+            .backfill_job_claim(&[network.to_string()], backfill::LEASE)
+            .await
+            .unwrap()
+    }
+
+    async fn run_job(
+        state: &crate::AppState,
+        network: &str,
+        job: BackfillJob,
+    ) -> anyhow::Result<BackfillJob> {
+        let client = state.networks.get(network).expect("test network");
+        backfill::run_job(state, client, job, &CancellationToken::new()).await
+    }
+
+    async fn indexed_ids(db: &Database, network: &str) -> Vec<String> {
+        sqlx::query_scalar("SELECT id FROM chain_events WHERE network = $1 ORDER BY ledger")
+            .bind(network)
+            .fetch_all(&db.pool())
+            .await
+            .unwrap()
+    }
+
+    async fn cleanup(db: &Database, network: &str, market_id: i64) {
+        sqlx::query("DELETE FROM chain_events WHERE network = $1")
+            .bind(network)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM backfill_jobs WHERE network = $1")
+            .bind(network)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM market_price_points WHERE market_id = $1")
+            .bind(market_id)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A job that fails mid-range keeps the progress of every stored page.
+    /// While its lease holds no other instance takes it; once the lease
+    /// lapses it resumes after the stored cursor and stops at `to_ledger`.
+    /// An event the live sync already indexed is scanned but not inserted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_job_resumes_from_its_cursor_after_a_failure() {
+        let network = unique_network();
+        let market_id = unique_market_id();
+        let at = Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap();
+        // Ledgers 101-107 are in range; 120 is past `to_ledger`.
+        let mut events: Vec<Value> = (101..=107)
+            .map(|ledger| bet(&network, ledger, market_id, 0, 10, at))
+            .collect();
+        events.push(bet(&network, 120, market_id, 0, 10, at));
+        let ids: Vec<String> = events
+            .iter()
+            .map(|e| e["id"].as_str().unwrap().to_string())
+            .collect();
+        let (url, mock) = start_mock_rpc(events.clone()).await;
+        let state = build_state(url, &network).await;
+
+        let live = crate::blockchain::ContractEvent {
+            id: ids[1].clone(),
+            ledger: 102,
+            topic: events[1]["topic"].to_string(),
+            tx_hash: None,
+            value: events[1].clone(),
+        };
+        assert!(backfill::index_event(&state.db, &network, &live)
+            .await
+            .unwrap());
+
+        let created = state
+            .db
+            .backfill_job_create(&network, 100, 110)
+            .await
+            .unwrap();
+        assert_eq!(created.status, BackfillStatus::Pending);
+        let job = claim(&state, &network).await.expect("job claimed");
+        assert_eq!(job.id, created.id);
+
+        // Pages 1 and 2 land; the node fails on the third.
+        mock.fail_call.store(3, Ordering::SeqCst);
+        assert!(run_job(&state, &network, job).await.is_err());
+        let stored = state
+            .db
+            .backfill_job_get(created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, BackfillStatus::Running);
+        assert_eq!(stored.pages, 2);
+        assert_eq!(stored.events_scanned, 4);
+        assert_eq!(stored.events_inserted, 3, "ledger 102 was already indexed");
+        assert_eq!(stored.page_cursor.as_deref(), Some(ids[3].as_str()));
+        assert_eq!(stored.last_ledger, Some(104));
+        assert!((stored.progress - 5.0 / 11.0).abs() < 1e-9);
+
+        // The instance "crashed" without releasing: the lease keeps the job
+        // until it lapses.
+        assert!(claim(&state, &network).await.is_none());
+        sqlx::query(
+            "UPDATE backfill_jobs SET lease_until = NOW() - INTERVAL '1 second' WHERE id = $1",
+        )
+        .bind(created.id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        let resumed = claim(&state, &network).await.expect("lapsed job claimed");
+        assert_eq!(resumed.page_cursor.as_deref(), Some(ids[3].as_str()));
+
+        mock.fail_call.store(0, Ordering::SeqCst);
+        let done = run_job(&state, &network, resumed).await.unwrap();
+        assert_eq!(done.status, BackfillStatus::Completed);
+        assert_eq!(done.pages, 4);
+        assert_eq!(done.events_scanned, 7);
+        assert_eq!(done.events_inserted, 6);
+        assert_eq!(done.last_ledger, Some(107));
+        assert_eq!(done.progress, 1.0);
+        assert!(done.completed_at.is_some());
+        assert!(
+            claim(&state, &network).await.is_none(),
+            "completed jobs are not claimed"
+        );
+
+        // The resumed walk continued after ledger 104 rather than starting over.
+        assert_eq!(
+            *mock.cursors.lock().unwrap(),
+            vec![
+                None,
+                Some(ids[1].clone()),
+                Some(ids[3].clone()),
+                Some(ids[5].clone())
+            ]
+        );
+        assert_eq!(indexed_ids(&state.db, &network).await, ids[..7].to_vec());
+
+        cleanup(&state.db, &network, market_id).await;
+    }
+
+    /// Backfilled events carry their ledger close time, and reconciling
+    /// rebuilds the range's price points and drops cached portfolios.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_completed_job_reconciles_derived_data() {
+        let network = unique_network();
+        let market_id = unique_market_id();
+        let minute = Utc.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap();
+        let events = vec![
+            bet(
+                &network,
+                201,
+                market_id,
+                0,
+                300,
+                minute + chrono::Duration::seconds(10),
+            ),
+            bet(
+                &network,
+                202,
+                market_id,
+                1,
+                100,
+                minute + chrono::Duration::seconds(40),
+            ),
+            bet(
+                &network,
+                203,
+                market_id,
+                1,
+                200,
+                minute + chrono::Duration::seconds(65),
+            ),
+        ];
+        let (url, _mock) = start_mock_rpc(events).await;
+        let state = build_state(url, &network).await;
+        let portfolio_key = keys::api_user_portfolio(BETTOR);
+        state
+            .cache
+            .set_json(&portfolio_key, &"stale", Duration::from_secs(300))
+            .await
+            .unwrap();
+
+        state
+            .db
+            .backfill_job_create(&network, 201, 203)
+            .await
+            .unwrap();
+        let job = claim(&state, &network).await.expect("job claimed");
+        let done = run_job(&state, &network, job).await.unwrap();
+        assert_eq!(done.status, BackfillStatus::Completed);
+        assert_eq!((done.pages, done.events_inserted), (2, 3));
+
+        let indexed_at: DateTime<Utc> = sqlx::query_scalar(
+            "SELECT indexed_at FROM chain_events WHERE network = $1 AND ledger = 201",
+        )
+        .bind(&network)
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(indexed_at, minute + chrono::Duration::seconds(10));
+
+        let points: Vec<(i32, DateTime<Utc>, f64)> = sqlx::query_as(
+            "SELECT outcome, ts, close FROM market_price_points \
+             WHERE market_id = $1 AND NOT is_rollup ORDER BY ts, outcome",
+        )
+        .bind(market_id)
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap();
+        let next = minute + chrono::Duration::minutes(1);
+        assert_eq!(
+            points,
+            vec![
+                (0, minute, 0.75),
+                (1, minute, 0.25),
+                (0, next, 0.5),
+                (1, next, 0.5)
+            ]
+        );
+
+        assert_eq!(
+            state.cache.get_json::<Value>(&portfolio_key).await.unwrap(),
+            None
+        );
+
+        cleanup(&state.db, &network, market_id).await;
+    }
+}
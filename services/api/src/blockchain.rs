@@ -1,24 +1,39 @@
 use std::{
     collections::HashMap,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use chrono::Utc;
 
 use anyhow::{anyhow, Context};
-use rand::Rng as _;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
 use tokio::{sync::RwLock, time::sleep};
 
 use crate::{
-    cache::{keys, RedisCache},
-    config::{Config, ContractKeySchema},
+    alerting::WebhookDispatcher,
+    cache::{keys, InvalidationTag, RedisCache},
+    config::{CacheTtls, Config, ContractKeySchema},
+    contract_read::{self, ContractRead, ReadFunction},
+    creators::{self, CreatorReputation},
     db::Database,
+    email::queue::EmailQueue,
+    market_watch,
     metrics::Metrics,
+    protocol_state::{self, ProtocolState},
+    rpc::{RetryPolicy, RpcTransport},
     shutdown::{ShutdownCoordinator, WorkerHandle},
+    supervisor::TaskSupervisor,
+    signer::TxSigner,
+    sync_refresh::{MarketProfile, RefreshPlan, RefreshPolicy, RefreshSchedule},
+    tx_watch,
+    user_notifications,
 };
 
 #[derive(Clone)]
@@ -28,13 +43,24 @@ pub struct BlockchainClient {
     network: String,
     contract_id: String,
     key_schema: ContractKeySchema,
-    retry_attempts: u32,
-    retry_base_delay_ms: u64,
-    rpc_backoff_jitter_factor: f64,
+    rpc: RpcTransport,
+    /// Time budget of an RPC call made while serving a request.
+    rpc_budget: Duration,
     event_poll_interval: Duration,
     tx_poll_interval: Duration,
     confirmation_ledger_lag: u32,
+    /// Always in the sync watch set, ahead of the database selection.
     sync_market_ids: Vec<i64>,
+    sync_watch_max_markets: usize,
+    sync_watch_refresh_every: u32,
+    sync_watch_resolved_retention: Duration,
+    /// Which watched markets a pass refreshes, within its call budget.
+    sync_refresh: RefreshPolicy,
+    sync_watch: Arc<SyncWatchState>,
+    /// Lifetimes of the `chain:v1` read caches, from `Config::cache_ttls`.
+    cache_ttls: CacheTtls,
+    /// Sizes the featured-markets key that resolution events invalidate.
+    featured_limit: i64,
     cache: RedisCache,
     db: Database,
     metrics: Metrics,
@@ -49,6 +75,17 @@ pub struct BlockchainClient {
     /// Whether the service is running in a production environment.
     /// Affects startup passphrase-mismatch behaviour: hard exit vs. warning.
     is_production: bool,
+    /// Contract admin signer from `ADMIN_SECRET_KEY`; `None` disables
+    /// [`Self::resolve_market_onchain`].
+    admin_signer: Option<Arc<TxSigner>>,
+    /// Oracle keeper signer from `ORACLE_SECRET_KEY`, else the admin signer.
+    oracle_signer: Option<Arc<TxSigner>>,
+    contract_call_timeout: Duration,
+    /// Public base URL for links in watcher notification emails.
+    base_url: String,
+    /// Whether creation events upsert `markets` rows. Only the primary
+    /// network's market ids are the table's; see [`NetworkClients::with`].
+    owns_market_rows: bool,
 }
 
 /// TTL for watched transaction hashes. Entries older than this are evicted
@@ -63,6 +100,73 @@ pub const WATCHED_TX_DEFAULT_TTL: Duration = WATCHED_TX_TTL_DEFAULT;
 /// The runtime cap comes from `Config::watched_tx_max_size`.
 pub const WATCHED_TX_MAX_SIZE: usize = 10_000;
 
+/// One [`BlockchainClient`] per served network, keyed by network name.
+///
+/// Each client has its own RPC URL, contract id, watch map and sync worker;
+/// cache keys and indexed rows are already scoped by network name, so two
+/// clients never read each other's data. Requests pick a network with the
+/// `X-Network` header and fall back to the primary (`BLOCKCHAIN_NETWORK`).
+#[derive(Clone)]
+pub struct NetworkClients {
+    primary: String,
+    clients: HashMap<String, BlockchainClient>,
+}
+
+impl NetworkClients {
+    /// A set serving only `primary`.
+    pub fn single(primary: BlockchainClient) -> Self {
+        let name = primary.network.clone();
+        Self {
+            clients: HashMap::from([(name.clone(), primary)]),
+            primary: name,
+        }
+    }
+
+    /// Add a client for another network. A client for the primary's name is
+    /// rejected so the primary can never be replaced. The added client does
+    /// not write `markets` rows, whose ids are the primary's.
+    pub fn with(mut self, mut client: BlockchainClient) -> anyhow::Result<Self> {
+        if self.clients.contains_key(&client.network) {
+            anyhow::bail!("network {} is configured twice", client.network);
+        }
+        client.owns_market_rows = false;
+        self.clients.insert(client.network.clone(), client);
+        Ok(self)
+    }
+
+    pub fn primary(&self) -> &BlockchainClient {
+        &self.clients[&self.primary]
+    }
+
+    pub fn primary_name(&self) -> &str {
+        &self.primary
+    }
+
+    pub fn get(&self, network: &str) -> Option<&BlockchainClient> {
+        self.clients.get(network)
+    }
+
+    /// The client for `requested`, or the primary when it is absent or blank.
+    /// Names match case-insensitively; `None` means the network is not served.
+    pub fn resolve(&self, requested: Option<&str>) -> Option<&BlockchainClient> {
+        match requested.map(str::trim).filter(|name| !name.is_empty()) {
+            None => Some(self.primary()),
+            Some(name) => self.clients.get(&name.to_ascii_lowercase()),
+        }
+    }
+
+    /// Served network names, sorted, for error messages and logs.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.clients.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BlockchainClient> {
+        self.clients.values()
+    }
+}
+
 /// Errors that can be returned by [`BlockchainClient::watch_transaction`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WatchTxError {
@@ -76,35 +180,118 @@ pub enum WatchTxError {
     CapReached,
 }
 
+/// Why a contract call signed by the service did not reach `SUCCESS`.
+/// Each variant maps to a distinct API error so operators can tell a local
+/// problem from a network rejection from a slow ledger.
+#[derive(Debug)]
+pub enum ContractCallError {
+    /// No signer is configured for the role the call needs.
+    NotConfigured,
+    /// The transaction could not be built or signed locally.
+    Signing(anyhow::Error),
+    /// Simulation failed; the node's message, unmodified. Nothing was submitted.
+    Simulation(String),
+    /// The network refused the transaction, or it was applied and failed.
+    Rejected {
+        hash: String,
+        status: String,
+        result_xdr: Option<String>,
+    },
+    /// Submitted but not final within `CONTRACT_CALL_TIMEOUT_SECS`; it may
+    /// still be applied.
+    Timeout { hash: String },
+    /// The RPC node could not be reached or answered unexpectedly.
+    Rpc(anyhow::Error),
+}
+
+impl std::fmt::Display for ContractCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotConfigured => write!(f, "no signer configured"),
+            Self::Signing(e) => write!(f, "signing failed: {e:#}"),
+            Self::Simulation(msg) => write!(f, "simulation failed: {msg}"),
+            Self::Rejected { hash, status, .. } => write!(f, "transaction {hash} {status}"),
+            Self::Timeout { hash } => write!(f, "transaction {hash} not confirmed in time"),
+            Self::Rpc(e) => write!(f, "rpc error: {e:#}"),
+        }
+    }
+}
+
+impl std::error::Error for ContractCallError {}
+
+pub use crate::rpc::{RpcError, RpcErrorKind};
+/// Response types shared with `predictiq-api-client`.
+pub use predictiq_api_types::{ChainMarketData, DataSource, UserBet, UserBetsPage};
+
+/// Inclusion fee bid for service-signed transactions, in stroops. The
+/// resource fee from simulation is added on top.
+const CONTRACT_CALL_BASE_FEE: u32 = 100;
+
+/// Time budget of the RPC calls behind health checks. A node that cannot
+/// answer within it is reported down rather than waited on.
+const HEALTH_RPC_BUDGET: Duration = Duration::from_secs(2);
+
+/// Time budget of the sync worker's RPC calls. No request waits on them, so
+/// they can ride out a flaky node.
+const SYNC_RPC_BUDGET: Duration = Duration::from_secs(30);
+
+/// Hot cache of the watch set. The source of truth is the Redis sorted set
+/// at [`keys::tx_watch_set`], shared by every instance and kept across
+/// restarts; the monitor reloads this map from it on each poll.
 #[derive(Default)]
 struct MonitoringState {
-    /// Maps tx hash → time it was first watched. Evicted after `WATCHED_TX_TTL`.
+    /// Maps tx hash → time it was first watched. Evicted after `watched_tx_ttl`.
     watched_txs: RwLock<HashMap<String, Instant>>,
 }
 
-/// Indicates whether a response was sourced from a live RPC call or a stale
-/// cache entry served after an RPC failure.
-///
-/// Consumers and alerting rules can use this field to distinguish real zeros
-/// from error-masked defaults.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum DataSource {
-    /// Data was fetched live from the RPC node.
-    Live,
-    /// The RPC call failed; this is a stale cached value served as a fallback.
-    StaleFallback,
+/// Markets whose chain data `sync_once` refreshes, reloaded from the
+/// database every `sync_watch_refresh_every` passes, and when each was last
+/// refreshed.
+#[derive(Default)]
+struct SyncWatchState {
+    markets: RwLock<Vec<MarketProfile>>,
+    schedule: Mutex<RefreshSchedule>,
+    passes: AtomicU32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ChainMarketData {
-    pub market_id: i64,
-    pub title: Option<String>,
-    pub status: Option<String>,
-    pub onchain_volume: String,
-    pub resolved_outcome: Option<u32>,
-    pub ledger: u32,
-    pub source: DataSource,
+/// How long a [`PendingOverride`] outlives the write that set it. Long
+/// enough for a lagging RPC node to catch up; short enough that a wrong
+/// override cannot hide the chain for long.
+pub const PENDING_OVERRIDE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// The state an admin write expects a market to have once every RPC node has
+/// seen it. Stored under [`keys::pending_override_market`] right after the
+/// transaction is confirmed, and layered over [`ChainMarketData`] reads until
+/// a read shows the same state or the entry expires, so responses built from
+/// a node that is behind do not show, or get cached with, the pre-write state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingOverride {
+    /// The network the write went to; reads on other networks ignore it.
+    pub network: String,
+    pub status: String,
+    pub resolved_outcome: u32,
+    pub tx_hash: String,
+}
+
+impl PendingOverride {
+    /// Whether `data` already shows the expected state.
+    pub fn confirmed_by(&self, data: &ChainMarketData) -> bool {
+        data.status.as_deref() == Some(self.status.as_str())
+            && data.resolved_outcome == Some(self.resolved_outcome)
+    }
+
+    pub fn apply(&self, data: &mut ChainMarketData) {
+        data.status = Some(self.status.clone());
+        data.resolved_outcome = Some(self.resolved_outcome);
+    }
+}
+
+/// A market's description and outcome labels as stored in the contract.
+/// Both are `None` when the read failed or the contract has no such market.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketMetadata {
+    pub description: Option<String>,
+    pub options: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,25 +304,6 @@ pub struct PlatformStatistics {
     pub source: DataSource,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UserBet {
-    pub market_id: i64,
-    pub outcome: u32,
-    pub amount: String,
-    pub token: Option<String>,
-    pub ledger: u32,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UserBetsPage {
-    pub user: String,
-    pub page: i64,
-    pub page_size: i64,
-    pub total: i64,
-    pub items: Vec<UserBet>,
-    pub source: DataSource,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OracleResult {
     pub market_id: i64,
@@ -175,6 +343,225 @@ pub struct ContractEvent {
     pub value: Value,
 }
 
+impl ContractEvent {
+    /// When the event's ledger closed, from the RPC's `ledgerClosedAt`.
+    pub fn ledger_closed_at(&self) -> Option<chrono::DateTime<Utc>> {
+        self.value
+            .get("ledgerClosedAt")
+            .and_then(Value::as_str)
+            .and_then(|raw| chrono::DateTime::parse_from_rfc3339(raw).ok())
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+/// Page size of the sync worker's `getEvents` calls.
+const EVENTS_PAGE_SIZE: u32 = 100;
+
+/// One page of `getEvents` results.
+#[derive(Debug, Clone)]
+pub struct EventsPage {
+    pub events: Vec<ContractEvent>,
+    /// Paging token to continue after this page; `None` on the last page.
+    pub next_cursor: Option<String>,
+    /// The node's newest ledger when it answered.
+    pub latest_ledger: Option<u32>,
+}
+
+/// A [`ContractEvent`] decoded into the columns of the `chain_events` table.
+///
+/// Follows the topic layout in `contracts/predict-iq/src/modules/events.rs`:
+/// `topic[0]` is the event name, `topic[1]` the market id, and `topic[2]` the
+/// triggering address. Payload fields are only decoded for the event kinds
+/// the API aggregates over (`bet_place`, `reward_fx`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedEvent {
+    pub id: String,
+    pub ledger: u32,
+    pub tx_hash: Option<String>,
+    pub kind: String,
+    pub market_id: Option<i64>,
+    pub address: Option<String>,
+    pub outcome: Option<u32>,
+    /// Integer amount in stroops, kept as a decimal string (i128 range).
+    pub amount: Option<String>,
+    pub token: Option<String>,
+    pub is_refund: bool,
+}
+
+pub const EVENT_BET_PLACED: &str = "bet_place";
+pub const EVENT_REWARD_CLAIMED: &str = "reward_fx";
+pub const EVENT_MARKET_RESOLVED: &str = "resolv_fx";
+pub const EVENT_DISPUTE_FILED: &str = "disp_file";
+pub const EVENT_MARKET_CREATED: &str = "mkt_creat";
+pub const EVENT_MARKET_CANCELLED: &str = "mkt_cncl";
+pub const EVENT_VOTE_CAST: &str = "vote_cast";
+
+/// Resolution events whose payload carries the winning outcome: either
+/// `(version, outcome)` or, for the long names the resolution module
+/// publishes, the bare outcome.
+const OUTCOME_EVENT_KINDS: [&str; 7] = [
+    "orcl_res",
+    "oracle_resolved",
+    "mkt_final",
+    "market_finalized",
+    "disp_res",
+    "dispute_resolved",
+    "adm_fbk",
+];
+
+/// Governance events published as `(name, actor)`, with no market id.
+const ACTOR_EVENT_KINDS: [&str; 3] = ["upg_init", "upg_vote", "upg_exec"];
+
+/// Every event kind the indexer stores.
+pub const EVENT_KINDS: [&str; 6] = [
+    EVENT_BET_PLACED,
+    EVENT_REWARD_CLAIMED,
+    EVENT_MARKET_RESOLVED,
+    EVENT_DISPUTE_FILED,
+    EVENT_MARKET_CREATED,
+    EVENT_MARKET_CANCELLED,
+];
+
+/// Read an i128 amount that the RPC may encode as a JSON number or string.
+fn json_amount(v: Option<&Value>) -> Option<String> {
+    let raw = match v? {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    raw.parse::<i128>().ok().map(|n| n.to_string())
+}
+
+impl IndexedEvent {
+    /// Decode `event`. Returns `None` when the topic has no event name.
+    pub fn from_contract_event(event: &ContractEvent) -> Option<Self> {
+        let topic = event.value.get("topic").and_then(Value::as_array)?;
+        let kind = topic.first().and_then(Value::as_str)?.to_string();
+        let market_id = topic.get(1).and_then(Value::as_i64);
+        let address = topic.get(2).and_then(Value::as_str).map(ToOwned::to_owned);
+        let data = event
+            .value
+            .get("value")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        let mut indexed = Self {
+            id: event.id.clone(),
+            ledger: event.ledger,
+            tx_hash: event.tx_hash.clone(),
+            kind,
+            market_id,
+            address,
+            outcome: None,
+            amount: None,
+            token: None,
+            is_refund: false,
+        };
+
+        // data[0] is the schema version on every event.
+        match indexed.kind.as_str() {
+            EVENT_BET_PLACED => {
+                indexed.outcome = data.get(1).and_then(Value::as_u64).map(|v| v as u32);
+                indexed.amount = json_amount(data.get(2));
+            }
+            EVENT_REWARD_CLAIMED => {
+                indexed.amount = json_amount(data.get(1));
+                indexed.token = data.get(2).and_then(Value::as_str).map(ToOwned::to_owned);
+                indexed.is_refund = data.get(3).and_then(Value::as_bool).unwrap_or(false);
+            }
+            EVENT_MARKET_RESOLVED | EVENT_VOTE_CAST => {
+                indexed.outcome = data.get(1).and_then(Value::as_u64).map(|v| v as u32);
+                indexed.amount = json_amount(data.get(2));
+            }
+            kind if OUTCOME_EVENT_KINDS.contains(&kind) => {
+                indexed.outcome = data
+                    .get(1)
+                    .and_then(Value::as_u64)
+                    .or_else(|| event.value.get("value").and_then(Value::as_u64))
+                    .map(|v| v as u32);
+            }
+            kind if ACTOR_EVENT_KINDS.contains(&kind) => {
+                indexed.address = topic.get(1).and_then(Value::as_str).map(ToOwned::to_owned);
+            }
+            _ => {}
+        }
+
+        Some(indexed)
+    }
+
+    /// The cache entries this event makes stale on `network`, if any.
+    pub fn invalidation_tag(&self, network: &str, featured_limit: i64) -> Option<InvalidationTag> {
+        let network = network.to_string();
+        match self.kind.as_str() {
+            EVENT_MARKET_RESOLVED => Some(InvalidationTag::MarketResolved {
+                market_id: self.market_id?,
+                network,
+                featured_limit,
+            }),
+            EVENT_MARKET_CANCELLED => Some(InvalidationTag::MarketCancelled {
+                market_id: self.market_id?,
+                network,
+                featured_limit,
+            }),
+            EVENT_MARKET_CREATED => Some(InvalidationTag::MarketCreated { network }),
+            EVENT_BET_PLACED | EVENT_REWARD_CLAIMED => Some(InvalidationTag::UserBetsChanged {
+                network,
+                address: self.address.clone()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The market a `mkt_creat` event announces. The payload after the version
+/// is `(description, num_outcomes, deadline, category, tags)`; events from
+/// schema version 1 end at `deadline` and carry no category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketCreation {
+    pub market_id: i64,
+    pub creator: Option<String>,
+    pub description: String,
+    /// Betting deadline, Unix seconds.
+    pub deadline: i64,
+    pub category: Option<String>,
+}
+
+impl MarketCreation {
+    /// Decode `event`. Returns `None` for other event kinds and for payloads
+    /// without a description or deadline.
+    pub fn from_contract_event(event: &ContractEvent) -> Option<Self> {
+        let topic = event.value.get("topic").and_then(Value::as_array)?;
+        if topic.first().and_then(Value::as_str)? != EVENT_MARKET_CREATED {
+            return None;
+        }
+        let data = event.value.get("value").and_then(Value::as_array)?;
+        Some(Self {
+            market_id: topic.get(1).and_then(Value::as_i64)?,
+            creator: topic.get(2).and_then(Value::as_str).map(ToOwned::to_owned),
+            description: data.get(1).and_then(Value::as_str)?.to_string(),
+            deadline: data.get(3).and_then(Value::as_i64)?,
+            category: data.get(4).and_then(Value::as_str).map(ToOwned::to_owned),
+        })
+    }
+
+    /// The description cut to the `markets.title` limit.
+    pub fn title(&self) -> String {
+        self.description.chars().take(MARKET_TITLE_MAX_CHARS).collect()
+    }
+
+    /// The category when it is a valid slug; the contract only bounds its
+    /// length, and `markets.category` holds slugs.
+    pub fn category_slug(&self) -> Option<&str> {
+        self.category
+            .as_deref()
+            .filter(|slug| crate::category::validate_slug(slug).is_ok())
+    }
+}
+
+/// `chk_markets_title_length`.
+const MARKET_TITLE_MAX_CHARS: usize = 500;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum HealthStatus {
@@ -183,6 +570,91 @@ pub enum HealthStatus {
     Unhealthy,
 }
 
+/// Outcome of `simulateTransaction` for a partner-built envelope.
+///
+/// XDR fields are passed through base64-encoded as the RPC returns them; the
+/// caller attaches `transaction_data` (resources and ledger footprint) and
+/// `min_resource_fee` before signing.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TxSimulation {
+    pub latest_ledger: u32,
+    pub min_resource_fee: Option<String>,
+    /// Base64 `SorobanTransactionData`, including the read/write footprint.
+    pub transaction_data: Option<String>,
+    pub results: Vec<TxSimulationResult>,
+    /// Base64 diagnostic events.
+    pub events: Vec<String>,
+    /// Set when the node could not simulate; the RPC's message, unmodified.
+    pub error: Option<String>,
+    /// Present when archived entries must be restored first.
+    #[schema(value_type = Option<Object>)]
+    pub restore_preamble: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TxSimulationResult {
+    /// Base64 `ScVal` return value.
+    pub xdr: String,
+    /// Base64 `SorobanAuthorizationEntry` values the signer must cover.
+    pub auth: Vec<String>,
+}
+
+/// Outcome of `sendTransaction`. `status` is the RPC's: `PENDING`,
+/// `DUPLICATE`, `TRY_AGAIN_LATER`, or `ERROR`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TxSubmission {
+    pub hash: String,
+    pub status: String,
+    pub latest_ledger: u32,
+    /// Base64 `TransactionResult` when `status` is `ERROR`.
+    pub error_result_xdr: Option<String>,
+    /// Whether the hash is now tracked by the transaction monitor.
+    pub watched: bool,
+}
+
+impl TxSubmission {
+    /// The network accepted (or already had) the transaction.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self.status.as_str(), "PENDING" | "DUPLICATE")
+    }
+}
+
+/// Where one sync pass starts and stops, after reconciling the stored cursor
+/// with the chain head.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncPlan {
+    /// Last ledger treated as indexed; the pass fetches events after it.
+    pub cursor: u32,
+    /// Newest ledger old enough to index (`latest - confirmation lag`).
+    pub confirmed_tip: u32,
+    /// Set when indexed events above this ledger must be discarded and
+    /// replayed: the head moved backwards (reorg) or the stored cursor is
+    /// ahead of the confirmed tip (stale or foreign cursor).
+    pub rewind_to: Option<u32>,
+}
+
+impl SyncPlan {
+    pub fn decide(cursor: u32, latest: u32, last_seen: u32, lag: u32) -> Self {
+        let confirmed_tip = latest.saturating_sub(lag);
+        let reorg = last_seen > 0 && latest.saturating_add(lag) < last_seen;
+        let rewind_to = (reorg || cursor > confirmed_tip).then(|| cursor.min(confirmed_tip));
+        Self {
+            cursor: rewind_to.unwrap_or(cursor),
+            confirmed_tip,
+            rewind_to,
+        }
+    }
+
+    /// Plan used when the chain head is unknown: hold the cursor.
+    pub fn hold(cursor: u32) -> Self {
+        Self {
+            cursor,
+            confirmed_tip: cursor,
+            rewind_to: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReplayRequest {
     pub from_ledger: u32,
@@ -195,31 +667,85 @@ pub struct ReplayProgress {
     pub completed: bool,
 }
 
-#[derive(Debug, Deserialize)]
-struct RpcEnvelope<T> {
-    result: Option<T>,
-    error: Option<RpcError>,
+/// Decode a contract `Option<String>` return value from a simulation result.
+fn decode_optional_string(result_xdr: &str) -> anyhow::Result<Option<String>> {
+    match xdr::ScVal::from_xdr_base64(result_xdr, xdr::Limits::none())? {
+        xdr::ScVal::Void => Ok(None),
+        val => sc_string(&val).map(Some),
+    }
+}
+
+/// Decode a contract `Option<Vec<String>>` return value from a simulation
+/// result.
+fn decode_optional_strings(result_xdr: &str) -> anyhow::Result<Option<Vec<String>>> {
+    match xdr::ScVal::from_xdr_base64(result_xdr, xdr::Limits::none())? {
+        xdr::ScVal::Void => Ok(None),
+        xdr::ScVal::Vec(Some(items)) => items.0.iter().map(sc_string).collect::<anyhow::Result<_>>().map(Some),
+        xdr::ScVal::Vec(None) => Ok(Some(Vec::new())),
+        other => Err(anyhow!("expected a vec of strings, got {:?}", other.discriminant())),
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct RpcError {
-    code: i64,
-    message: String,
+fn sc_string(val: &xdr::ScVal) -> anyhow::Result<String> {
+    match val {
+        xdr::ScVal::String(s) => Ok(s.0.to_utf8_string_lossy()),
+        other => Err(anyhow!("expected a string, got {:?}", other.discriminant())),
+    }
 }
 
-/// Standard JSON-RPC error codes that indicate a client-side mistake and
-/// should never be retried (retrying will produce the same error).
-fn is_non_retryable_rpc_error(code: i64) -> bool {
-    matches!(
-        code,
-        -32700  // Parse error
-        | -32600  // Invalid request
-        | -32601  // Method not found
-        | -32602  // Invalid params
-    )
+/// Apply a successful simulation to `tx`: resource footprint, resource fee
+/// and the auth entries the invocation needs.
+fn assemble_transaction(
+    mut tx: xdr::Transaction,
+    simulation: &TxSimulation,
+) -> anyhow::Result<xdr::Transaction> {
+    let data = simulation
+        .transaction_data
+        .as_deref()
+        .ok_or_else(|| anyhow!("simulation returned no transaction data"))?;
+    let resource_fee: u32 = simulation
+        .min_resource_fee
+        .as_deref()
+        .unwrap_or("0")
+        .parse()
+        .context("simulation: minResourceFee is not a u32")?;
+
+    let auth = match simulation.results.first() {
+        Some(result) => result
+            .auth
+            .iter()
+            .map(|entry| xdr::SorobanAuthorizationEntry::from_xdr_base64(entry, xdr::Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let mut operations = tx.operations.to_vec();
+    for op in &mut operations {
+        if let xdr::OperationBody::InvokeHostFunction(invoke) = &mut op.body {
+            invoke.auth = auth.clone().try_into()?;
+        }
+    }
+
+    tx.operations = operations.try_into()?;
+    tx.fee = tx.fee.saturating_add(resource_fee);
+    tx.ext = xdr::TransactionExt::V1(xdr::SorobanTransactionData::from_xdr_base64(
+        data,
+        xdr::Limits::none(),
+    )?);
+    Ok(tx)
 }
 
 impl BlockchainClient {
+    /// Network name this client serves; scopes its cache keys and metrics.
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    /// Contract this client reads on its network.
+    pub fn contract_id(&self) -> &str {
+        &self.contract_id
+    }
+
     pub fn new(config: &Config, cache: RedisCache, db: Database, metrics: Metrics) -> anyhow::Result<Self> {
         let http = Client::builder()
             .pool_max_idle_per_host(16)
@@ -229,6 +755,28 @@ impl BlockchainClient {
             .build()
             .context("failed to construct RPC http client")?;
 
+        // A malformed admin key is a deployment error; refuse to start rather
+        // than failing on the first resolve call.
+        let admin_signer = config
+            .admin_secret_key
+            .as_ref()
+            .map(TxSigner::from_secret)
+            .transpose()
+            .context("ADMIN_SECRET_KEY")?
+            .map(Arc::new);
+        if let Some(signer) = &admin_signer {
+            tracing::info!(account = %signer.account_id(), "contract admin signer loaded");
+        }
+        let oracle_signer = match &config.oracle_secret_key {
+            Some(secret) => Some(Arc::new(
+                TxSigner::from_secret(secret).context("ORACLE_SECRET_KEY")?,
+            )),
+            None => admin_signer.clone(),
+        };
+        if let Some(signer) = &oracle_signer {
+            tracing::info!(account = %signer.account_id(), "oracle keeper signer loaded");
+        }
+
         // ── Startup schema validation ─────────────────────────────────────────
         // Validate the key schema eagerly so template drift is caught before
         // any contract reads are attempted.  A validation failure is logged as
@@ -252,19 +800,40 @@ impl BlockchainClient {
             ),
         }
 
+        let rpc = RpcTransport::new(
+            http.clone(),
+            config.blockchain_rpc_url.clone(),
+            RetryPolicy {
+                attempts: config.retry_attempts.max(1),
+                base_delay: Duration::from_millis(config.retry_base_delay_ms.max(50)),
+                max_delay: Duration::from_millis(config.rpc_retry_max_delay_ms),
+                jitter_factor: config.rpc_backoff_jitter_factor,
+            },
+        );
+
         Ok(Self {
             http,
             rpc_url: config.blockchain_rpc_url.clone(),
             network: config.network_name().to_string(),
             contract_id: config.contract_id.clone(),
             key_schema,
-            retry_attempts: config.retry_attempts.max(1),
-            retry_base_delay_ms: config.retry_base_delay_ms.max(50),
-            rpc_backoff_jitter_factor: config.rpc_backoff_jitter_factor,
+            rpc,
+            rpc_budget: config.rpc_call_budget,
             event_poll_interval: config.event_poll_interval,
             tx_poll_interval: config.tx_poll_interval,
             confirmation_ledger_lag: config.confirmation_ledger_lag.max(1),
             sync_market_ids: config.sync_market_ids.clone(),
+            sync_watch_max_markets: config.sync_watch_max_markets,
+            sync_watch_refresh_every: config.sync_watch_refresh_every.max(1),
+            sync_watch_resolved_retention: config.sync_watch_resolved_retention,
+            sync_refresh: RefreshPolicy {
+                hot_window: config.sync_hot_window,
+                dormant_every: config.sync_dormant_refresh_every.max(1),
+                call_budget: config.sync_call_budget,
+            },
+            sync_watch: Arc::new(SyncWatchState::default()),
+            cache_ttls: config.cache_ttls.clone(),
+            featured_limit: config.featured_limit,
             cache,
             db,
             metrics,
@@ -273,6 +842,11 @@ impl BlockchainClient {
             watched_tx_ttl: Duration::from_secs(config.watched_tx_ttl_secs),
             watched_tx_max_size: config.watched_tx_max_size,
             is_production: config.is_production,
+            admin_signer,
+            oracle_signer,
+            contract_call_timeout: config.contract_call_timeout,
+            base_url: config.base_url.clone(),
+            owns_market_rows: true,
         })
     }
 
@@ -385,7 +959,7 @@ impl BlockchainClient {
         }
 
         match self
-            .rpc_call::<NetworkResult>("getNetwork", serde_json::json!({}))
+            .rpc_call_within::<NetworkResult>("getNetwork", serde_json::json!({}), HEALTH_RPC_BUDGET)
             .await
         {
             Ok(r) => r.passphrase == self.expected_passphrase,
@@ -396,105 +970,50 @@ impl BlockchainClient {
         }
     }
 
+    /// Call `method` with retries within the request budget
+    /// (`RPC_CALL_BUDGET_MS`), tagging log lines with the correlation ID of
+    /// the request being served on this task, if any.
     async fn rpc_call<T: for<'de> Deserialize<'de>>(
         &self,
         method: &str,
         params: Value,
     ) -> anyhow::Result<T> {
-        let mut attempt: u32 = 0;
-
-        loop {
-            attempt += 1;
-
-            let payload = json!({
-                "jsonrpc": "2.0",
-                "id": format!("{}-{}", method, attempt),
-                "method": method,
-                "params": params,
-            });
-
-            let response = self.http.post(&self.rpc_url).json(&payload).send().await;
-
-            match response {
-                Ok(resp) => {
-                    let status = resp.status();
-
-                    // 4xx (except 429 Too Many Requests) are non-retryable client errors.
-                    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
-                        return Err(anyhow!(
-                            "rpc {} non-retryable client error: {}",
-                            method, status
-                        ));
-                    }
+        self.rpc_call_within(method, params, self.rpc_budget).await
+    }
 
-                    if !status.is_success() {
-                        // 5xx / 429 are transient — retry with backoff.
-                        if attempt >= self.retry_attempts {
-                            return Err(anyhow!(
-                                "rpc {} http error after {} attempt(s): {}",
-                                method, attempt, status
-                            ));
-                        }
-                        tracing::warn!(
-                            method, attempt, %status,
-                            "rpc http error, retrying"
-                        );
-                    } else {
-                        let parsed = resp
-                            .json::<RpcEnvelope<T>>()
-                            .await
-                            .context("rpc parse error")?;
-
-                        if let Some(err) = parsed.error {
-                            if is_non_retryable_rpc_error(err.code) {
-                                return Err(anyhow!(
-                                    "rpc {} non-retryable error: {} ({})",
-                                    method, err.message, err.code
-                                ));
-                            }
-                            if attempt >= self.retry_attempts {
-                                return Err(anyhow!(
-                                    "rpc {} failed: {} ({})",
-                                    method, err.message, err.code
-                                ));
-                            }
-                            tracing::warn!(
-                                method, attempt, code = err.code,
-                                message = %err.message, "rpc error, retrying"
-                            );
-                        } else if let Some(result) = parsed.result {
-                            return Ok(result);
-                        } else if attempt >= self.retry_attempts {
-                            return Err(anyhow!("rpc {} returned empty result", method));
-                        } else {
-                            tracing::warn!(method, attempt, "rpc empty result, retrying");
-                        }
-                    }
-                }
-                Err(err) => {
-                    if attempt >= self.retry_attempts {
-                        return Err(anyhow!("rpc {} transport failed: {err}", method));
-                    }
-                    tracing::warn!(method, attempt, error = %err, "rpc transport error, retrying");
-                }
-            }
+    /// [`Self::rpc_call`] with an explicit time budget: health probes use
+    /// [`HEALTH_RPC_BUDGET`], the sync worker [`SYNC_RPC_BUDGET`].
+    async fn rpc_call_within<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: Value,
+        budget: Duration,
+    ) -> anyhow::Result<T> {
+        let correlation_id = crate::correlation::current_request_id();
+        self.rpc_call_correlated(method, params, budget, correlation_id.as_deref())
+            .await
+    }
 
-            // Full-jitter backoff: delay = random(0, min(cap, base * 2^attempt))
-            // RPC_BACKOFF_JITTER_FACTOR controls the jitter fraction (1.0 = full).
-            let cap_ms = (self.retry_base_delay_ms * (1u64 << (attempt - 1).min(10))).min(60_000);
-            let backoff_ms = if self.rpc_backoff_jitter_factor > 0.0 {
-                let jitter_range = (cap_ms as f64 * self.rpc_backoff_jitter_factor) as u64;
-                let deterministic = cap_ms.saturating_sub(jitter_range);
-                deterministic + rand::thread_rng().gen_range(0..=jitter_range)
-            } else {
-                cap_ms
-            };
-            tracing::warn!(method, attempt, backoff_ms, "rpc retry scheduled");
-            sleep(Duration::from_millis(backoff_ms)).await;
-        }
+    /// [`Self::rpc_call_within`] with an explicit correlation ID, for calls
+    /// made on behalf of a request from a spawned task, where the request
+    /// context is not inherited.
+    pub(crate) async fn rpc_call_correlated<T: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: Value,
+        budget: Duration,
+        correlation_id: Option<&str>,
+    ) -> anyhow::Result<T> {
+        let result = self.rpc.call(method, &params, budget, correlation_id).await;
+        self.metrics.observe_rpc_call(&self.network, result.is_ok());
+        Ok(result?)
     }
 
     async fn latest_ledger(&self) -> anyhow::Result<u32> {
+        self.latest_ledger_within(self.rpc_budget).await
+    }
+
+    async fn latest_ledger_within(&self, budget: Duration) -> anyhow::Result<u32> {
         #[derive(Debug, Deserialize)]
         struct LatestLedgerResult {
             sequence: u32,
@@ -506,70 +1025,360 @@ impl BlockchainClient {
             latest_ledger: LatestLedgerResult,
         }
 
-        let result: GetLatestLedgerResult = self.rpc_call("getLatestLedger", json!({})).await?;
+        let result: GetLatestLedgerResult = self
+            .rpc_call_within("getLatestLedger", json!({}), budget)
+            .await?;
         Ok(result.latest_ledger.sequence)
     }
 
     pub async fn market_data_cached(&self, market_id: i64) -> anyhow::Result<ChainMarketData> {
-        let key = keys::chain_market(market_id);
-        let ttl = Duration::from_secs(60);
+        self.market_data_lookup(market_id).await.map(|(value, _)| value)
+    }
+
+    /// Like [`Self::market_data_cached`] but also reports whether the value
+    /// was served from cache (`true`) or fetched from the RPC node (`false`).
+    pub async fn market_data_lookup(&self, market_id: i64) -> anyhow::Result<(ChainMarketData, bool)> {
+        let key = keys::chain_market(&self.network, market_id);
+        let ttl = self.cache_ttls.market_data;
+
+        let (mut value, hit) = self
+            .cache
+            .get_or_set_json(&key, ttl, || self.fetch_market_data(market_id))
+            .await?;
+        self.observe_lookup("market_data", hit);
+
+        let pending = self
+            .cache
+            .get_json::<PendingOverride>(&keys::pending_override_market(market_id))
+            .await
+            .unwrap_or_else(|e| {
+                tracing::debug!(market_id, error = %e, "pending override unavailable");
+                None
+            });
+        self.layer_pending_override(&mut value, pending).await;
+
+        Ok((value, hit))
+    }
+
+    /// [`Self::market_data_cached`] for many markets, reading every cached
+    /// entry in one Redis round trip. Results are in `market_ids` order and
+    /// fail independently.
+    pub async fn market_data_many(&self, market_ids: &[i64]) -> Vec<anyhow::Result<ChainMarketData>> {
+        let keys: Vec<String> = market_ids
+            .iter()
+            .map(|&id| keys::chain_market(&self.network, id))
+            .collect();
+        let results = self
+            .cache
+            .get_or_set_many_json(&keys, self.cache_ttls.market_data, |i| {
+                self.fetch_market_data(market_ids[i])
+            })
+            .await;
+        let mut results = self.observe_lookups("market_data", results);
+
+        let override_keys: Vec<String> = market_ids
+            .iter()
+            .map(|&id| keys::pending_override_market(id))
+            .collect();
+        let pending = self
+            .cache
+            .mget_json::<PendingOverride>(&override_keys)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::debug!(error = %e, "pending overrides unavailable");
+                vec![None; market_ids.len()]
+            });
+        for (result, pending) in results.iter_mut().zip(pending) {
+            if let Ok(value) = result {
+                self.layer_pending_override(value, pending).await;
+            }
+        }
+        results
+    }
+
+    /// Record that `market_id` was resolved to `outcome` by `tx_hash`, so
+    /// reads serve that state for [`PENDING_OVERRIDE_TTL`] even from a node
+    /// that has not seen the transaction yet.
+    pub async fn set_pending_override(
+        &self,
+        market_id: i64,
+        outcome: u32,
+        tx_hash: &str,
+    ) -> anyhow::Result<()> {
+        let pending = PendingOverride {
+            network: self.network.clone(),
+            status: "resolved".to_string(),
+            resolved_outcome: outcome,
+            tx_hash: tx_hash.to_string(),
+        };
+        self.cache
+            .set_json(&keys::pending_override_market(market_id), &pending, PENDING_OVERRIDE_TTL)
+            .await
+    }
+
+    /// Apply `pending` to `data` unless the chain already shows it, in which
+    /// case the override has done its job and is dropped. The chain entry
+    /// underneath is left as read: it expires on its own TTL, and the
+    /// override covers it until then.
+    async fn layer_pending_override(
+        &self,
+        data: &mut ChainMarketData,
+        pending: Option<PendingOverride>,
+    ) {
+        let Some(pending) = pending.filter(|p| p.network == self.network) else {
+            return;
+        };
+        if pending.confirmed_by(data) {
+            if let Err(e) = self.cache.del(&keys::pending_override_market(data.market_id)).await {
+                tracing::debug!(market_id = data.market_id, error = %e, "pending override not dropped");
+            }
+            return;
+        }
+        pending.apply(data);
+        self.metrics.observe_pending_override_hit(&self.network);
+    }
+
+    async fn fetch_market_data(&self, market_id: i64) -> anyhow::Result<ChainMarketData> {
         let endpoint = "market_data";
+        let ledger = self.latest_ledger().await.unwrap_or(0);
+        match self
+            .rpc_call::<Value>(
+                "getContractData",
+                json!({
+                    "contractId": self.contract_id,
+                    "key": self.key_schema.market_key(market_id),
+                }),
+            )
+            .await
+        {
+            Ok(data) => Ok(ChainMarketData {
+                market_id,
+                title: data.get("title").and_then(Value::as_str).map(ToOwned::to_owned),
+                options: None,
+                status: data.get("status").and_then(Value::as_str).map(ToOwned::to_owned),
+                onchain_volume: data
+                    .get("onchain_volume")
+                    .and_then(Value::as_str)
+                    .unwrap_or("0")
+                    .to_string(),
+                resolved_outcome: data
+                    .get("resolved_outcome")
+                    .and_then(Value::as_u64)
+                    .map(|v| v as u32),
+                ledger,
+                source: DataSource::Live,
+            }),
+            Err(e) => {
+                self.metrics.observe_rpc_error(&self.network, "getContractData");
+                self.metrics.observe_rpc_fallback(&self.network, endpoint);
+                tracing::warn!(market_id, error = %e, "market_data RPC failed");
+                Err(e)
+            }
+        }
+    }
 
-        let (value, hit) = self
+    /// Description and outcome labels of `market_id`, read by simulating the
+    /// contract's `get_market_description` and `get_market_options`. Market
+    /// text never changes once created, so a successful read is cached for
+    /// `cache_ttls.market_metadata`. Failed reads are not cached and come
+    /// back as empty metadata: callers treat this as a fallback for a missing
+    /// database title, never as a reason to fail the request.
+    pub async fn market_metadata_cached(&self, market_id: i64) -> MarketMetadata {
+        let key = keys::chain_market_metadata(&self.network, market_id);
+        let ttl = self.cache_ttls.market_metadata;
+
+        match self
             .cache
-            .get_or_set_json(&key, ttl, || async move {
-                let ledger = self.latest_ledger().await.unwrap_or(0);
-                match self
-                    .rpc_call::<Value>(
-                        "getContractData",
-                        json!({
-                            "contractId": self.contract_id,
-                            "key": self.key_schema.market_key(market_id),
-                        }),
-                    )
-                    .await
-                {
-                    Ok(data) => Ok(ChainMarketData {
-                        market_id,
-                        title: data.get("title").and_then(Value::as_str).map(ToOwned::to_owned),
-                        status: data.get("status").and_then(Value::as_str).map(ToOwned::to_owned),
-                        onchain_volume: data
-                            .get("onchain_volume")
-                            .and_then(Value::as_str)
-                            .unwrap_or("0")
-                            .to_string(),
-                        resolved_outcome: data
-                            .get("resolved_outcome")
-                            .and_then(Value::as_u64)
-                            .map(|v| v as u32),
-                        ledger,
-                        source: DataSource::Live,
-                    }),
-                    Err(e) => {
-                        self.metrics.observe_rpc_error("getContractData");
-                        self.metrics.observe_rpc_fallback(endpoint);
-                        tracing::warn!(market_id, error = %e, "market_data RPC failed");
-                        Err(e)
-                    }
-                }
+            .get_or_set_json(&key, ttl, || self.fetch_market_metadata(market_id))
+            .await
+        {
+            Ok((value, hit)) => {
+                self.observe_lookup("market_metadata", hit);
+                value
+            }
+            Err(e) => {
+                tracing::debug!(market_id, error = %e, "market metadata unavailable");
+                MarketMetadata::default()
+            }
+        }
+    }
+
+    /// Simulate both metadata getters. Errors when either simulation fails or
+    /// when the contract has no such market, so neither is cached.
+    async fn fetch_market_metadata(&self, market_id: i64) -> anyhow::Result<MarketMetadata> {
+        let market_id = u64::try_from(market_id).context("market id is negative")?;
+        let (description, options) = tokio::join!(
+            self.simulate_read("get_market_description", vec![xdr::ScVal::U64(market_id)]),
+            self.simulate_read("get_market_options", vec![xdr::ScVal::U64(market_id)]),
+        );
+
+        let decoded = (|| {
+            anyhow::Ok((
+                decode_optional_string(&description?.0)?,
+                decode_optional_strings(&options?.0)?,
+            ))
+        })();
+        match decoded {
+            Ok((None, _)) => Err(anyhow!("market {market_id} is not on chain")),
+            Ok((description, options)) => Ok(MarketMetadata { description, options }),
+            Err(e) => {
+                self.metrics.observe_rpc_fallback(&self.network, "market_metadata");
+                tracing::warn!(market_id, error = %e, "market metadata simulation failed");
+                Err(e)
+            }
+        }
+    }
+
+    /// Circuit breaker, guardian removal and pending upgrade state, cached for
+    /// `cache_ttls.protocol_state` and dropped early when the sync worker sees
+    /// a breaker transition. Failed simulations are not cached.
+    pub async fn protocol_state_cached(&self) -> anyhow::Result<ProtocolState> {
+        let key = keys::chain_protocol_state(&self.network);
+        let ttl = self.cache_ttls.protocol_state;
+
+        let (state, hit) = self
+            .cache
+            .get_or_set_json(&key, ttl, || self.fetch_protocol_state())
+            .await?;
+        self.observe_lookup("protocol_state", hit);
+        Ok(state)
+    }
+
+    /// Simulate the three getters side by side.
+    async fn fetch_protocol_state(&self) -> anyhow::Result<ProtocolState> {
+        let (breaker, removal, upgrade) = tokio::join!(
+            self.simulate_read("get_circuit_breaker_state", vec![]),
+            self.simulate_read("get_pending_guardian_removal", vec![]),
+            self.simulate_read("get_pending_upgrade", vec![]),
+        );
+        let (breaker_xdr, ledger) = breaker?;
+
+        Ok(ProtocolState::new(
+            protocol_state::decode_breaker_state(&breaker_xdr)?,
+            protocol_state::decode_guardian_removal(&removal?.0)?,
+            protocol_state::decode_pending_upgrade(&upgrade?.0)?,
+            ledger,
+            Utc::now(),
+        ))
+    }
+
+    /// Simulate an allowlisted getter, cached per function and arguments for
+    /// the function's own TTL. `raw_args` are the arguments as the caller
+    /// wrote them, which key the cache. Failed simulations are not cached; a
+    /// contract error comes back as [`ContractCallError::Simulation`].
+    pub async fn contract_read_cached(
+        &self,
+        function: &ReadFunction,
+        raw_args: Vec<String>,
+        args: Vec<xdr::ScVal>,
+    ) -> anyhow::Result<(ContractRead, bool)> {
+        let key = keys::chain_contract_read(&self.network, &function.name, &raw_args.join(","));
+
+        let (read, hit) = self
+            .cache
+            .get_or_set_json(&key, function.ttl, || async move {
+                let (result_xdr, ledger) = self.simulate_read(&function.name, args).await?;
+                Ok::<_, anyhow::Error>(ContractRead {
+                    function: function.name.clone(),
+                    args: raw_args,
+                    result: contract_read::scval_to_json(&xdr::ScVal::from_xdr_base64(&result_xdr, xdr::Limits::none())?),
+                    ledger,
+                })
             })
             .await?;
+        self.observe_lookup("contract_read", hit);
+        Ok((read, hit))
+    }
 
+    /// `creator`'s reputation tier, read by simulating the contract's
+    /// `get_creator_reputation`. Not cached here; the creator profile built
+    /// on it is. A failed read comes back as `None` and counts in
+    /// `rpc_fallbacks_total{endpoint="creator_reputation"}`.
+    pub async fn creator_reputation(&self, creator: &str) -> Option<CreatorReputation> {
+        let read = async {
+            let address = contract_read::parse_address(creator)
+                .ok_or_else(|| anyhow!("{creator} is not a strkey"))?;
+            let (result_xdr, _) = self
+                .simulate_read("get_creator_reputation", vec![xdr::ScVal::Address(address)])
+                .await?;
+            creators::decode_reputation(&result_xdr)
+        };
+        match read.await {
+            Ok(reputation) => Some(reputation),
+            Err(e) => {
+                self.metrics.observe_rpc_fallback(&self.network, "creator_reputation");
+                tracing::warn!(creator, error = %e, "creator reputation simulation failed");
+                None
+            }
+        }
+    }
+
+    /// Simulate a read-only call to `function` and return its result XDR and
+    /// the ledger it ran against. Simulation never checks the source account,
+    /// so a zero key with sequence 0 stands in for one. A contract error comes
+    /// back as [`ContractCallError::Simulation`].
+    async fn simulate_read(&self, function: &str, args: Vec<xdr::ScVal>) -> anyhow::Result<(String, u32)> {
+        let tx = self.build_invocation([0; 32], 0, function, args)?;
+        let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx,
+            signatures: xdr::VecM::default(),
+        })
+        .to_xdr_base64(xdr::Limits::none())?;
+
+        let simulation = self.simulate_transaction(&envelope).await.map_err(|e| {
+            self.metrics.observe_rpc_error(&self.network, "simulateTransaction");
+            e
+        })?;
+        if let Some(error) = simulation.error {
+            return Err(ContractCallError::Simulation(format!("{function}: {error}")).into());
+        }
+        let ledger = simulation.latest_ledger;
+        simulation
+            .results
+            .into_iter()
+            .next()
+            .map(|result| (result.xdr, ledger))
+            .ok_or_else(|| anyhow!("{function}: simulation returned no result"))
+    }
+
+    /// Record a chain cache hit or miss for `endpoint`.
+    fn observe_lookup(&self, endpoint: &str, hit: bool) {
         if hit {
             self.metrics.observe_hit("chain", endpoint);
         } else {
             self.metrics.observe_miss("chain", endpoint);
         }
+    }
 
-        Ok(value)
+    /// Record the hits and misses of a multi-key lookup and drop the flags.
+    fn observe_lookups<T>(
+        &self,
+        endpoint: &str,
+        results: Vec<anyhow::Result<(T, bool)>>,
+    ) -> Vec<anyhow::Result<T>> {
+        results
+            .into_iter()
+            .map(|result| {
+                result.map(|(value, hit)| {
+                    self.observe_lookup(endpoint, hit);
+                    value
+                })
+            })
+            .collect()
     }
 
+    /// The contract's platform statistics. When they cannot be read, or the
+    /// contract holds no counters, the newest derived snapshot (see
+    /// [`crate::platform_stats`]) is served as
+    /// [`DataSource::StaleFallback`]; only with no snapshot either is the
+    /// read an error. The fallback is never cached, so the next request
+    /// tries the chain again.
     pub async fn platform_statistics_cached(&self) -> anyhow::Result<PlatformStatistics> {
         let key = keys::chain_platform_stats(&self.network);
-        let ttl = Duration::from_secs(120);
+        let ttl = self.cache_ttls.platform_stats;
         let endpoint = "platform_stats";
 
-        let (value, hit) = self
+        let result = self
             .cache
             .get_or_set_json(&key, ttl, || async move {
                 let ledger = self.latest_ledger().await.unwrap_or(0);
@@ -583,7 +1392,7 @@ impl BlockchainClient {
                     )
                     .await
                 {
-                    Ok(data) => Ok(PlatformStatistics {
+                    Ok(data) if data.get("total_markets").is_some() => Ok(PlatformStatistics {
                         total_markets: data.get("total_markets").and_then(Value::as_u64).unwrap_or(0),
                         active_markets: data.get("active_markets").and_then(Value::as_u64).unwrap_or(0),
                         resolved_markets: data.get("resolved_markets").and_then(Value::as_u64).unwrap_or(0),
@@ -595,21 +1404,32 @@ impl BlockchainClient {
                         ledger,
                         source: DataSource::Live,
                     }),
+                    Ok(_) => {
+                        self.metrics.observe_rpc_fallback(&self.network, endpoint);
+                        Err(anyhow!("contract holds no platform statistics"))
+                    }
                     Err(e) => {
-                        self.metrics.observe_rpc_error("getContractData");
-                        self.metrics.observe_rpc_fallback(endpoint);
+                        self.metrics.observe_rpc_error(&self.network, "getContractData");
+                        self.metrics.observe_rpc_fallback(&self.network, endpoint);
                         tracing::warn!(error = %e, "platform_statistics RPC failed");
                         Err(e)
                     }
                 }
             })
-            .await?;
+            .await;
 
-        if hit {
-            self.metrics.observe_hit("chain", endpoint);
-        } else {
-            self.metrics.observe_miss("chain", endpoint);
-        }
+        let (value, hit) = match result {
+            Ok(found) => found,
+            Err(e) => match self.db.platform_stats_snapshot_latest(&self.network).await {
+                Ok(Some(snapshot)) => (snapshot.into_statistics(), false),
+                Ok(None) => return Err(e),
+                Err(db_err) => {
+                    tracing::warn!(error = %db_err, "platform_statistics snapshot read failed");
+                    return Err(e);
+                }
+            },
+        };
+        self.observe_lookup(endpoint, hit);
 
         Ok(value)
     }
@@ -625,7 +1445,7 @@ impl BlockchainClient {
         let offset = page * page_size;
 
         let key = keys::chain_user_bets_page(&self.network, user, page, page_size);
-        let ttl = Duration::from_secs(30);
+        let ttl = self.cache_ttls.user_bets;
         let endpoint = "user_bets";
 
         let (value, hit) = self
@@ -674,8 +1494,8 @@ impl BlockchainClient {
                         })
                     }
                     Err(e) => {
-                        self.metrics.observe_rpc_error("getContractData");
-                        self.metrics.observe_rpc_fallback(endpoint);
+                        self.metrics.observe_rpc_error(&self.network, "getContractData");
+                        self.metrics.observe_rpc_fallback(&self.network, endpoint);
                         tracing::warn!(user, error = %e, "user_bets RPC failed");
                         Err(e)
                     }
@@ -693,54 +1513,73 @@ impl BlockchainClient {
     }
 
     pub async fn oracle_result_cached(&self, market_id: i64) -> anyhow::Result<OracleResult> {
+        self.oracle_result_lookup(market_id).await.map(|(value, _)| value)
+    }
+
+    /// Like [`Self::oracle_result_cached`] but also reports whether the value
+    /// was served from cache (`true`) or fetched from the RPC node (`false`).
+    pub async fn oracle_result_lookup(&self, market_id: i64) -> anyhow::Result<(OracleResult, bool)> {
         let key = keys::chain_oracle_result(&self.network, market_id);
-        let ttl = Duration::from_secs(30);
-        let endpoint = "oracle_result";
+        let ttl = self.cache_ttls.oracle_result;
 
         let (value, hit) = self
             .cache
-            .get_or_set_json(&key, ttl, || async move {
-                let ledger = self.latest_ledger().await.unwrap_or(0);
-                match self
-                    .rpc_call::<Value>(
-                        "getContractData",
-                        json!({
-                            "contractId": self.contract_id,
-                            "key": self.key_schema.oracle_result_key(market_id),
-                        }),
-                    )
-                    .await
-                {
-                    Ok(data) => Ok(OracleResult {
-                        market_id,
-                        source_name: data.get("source").and_then(Value::as_str).map(ToOwned::to_owned),
-                        outcome: data.get("outcome").and_then(Value::as_u64).map(|v| v as u32),
-                        confidence_bps: data.get("confidence_bps").and_then(Value::as_u64),
-                        ledger,
-                        source: DataSource::Live,
-                    }),
-                    Err(e) => {
-                        self.metrics.observe_rpc_error("getContractData");
-                        self.metrics.observe_rpc_fallback(endpoint);
-                        tracing::warn!(market_id, error = %e, "oracle_result RPC failed");
-                        Err(e)
-                    }
-                }
-            })
+            .get_or_set_json(&key, ttl, || self.fetch_oracle_result(market_id))
             .await?;
+        self.observe_lookup("oracle_result", hit);
 
-        if hit {
-            self.metrics.observe_hit("chain", endpoint);
-        } else {
-            self.metrics.observe_miss("chain", endpoint);
-        }
+        Ok((value, hit))
+    }
 
-        Ok(value)
+    /// [`Self::oracle_result_cached`] for many markets in one Redis round
+    /// trip; see [`Self::market_data_many`].
+    pub async fn oracle_result_many(&self, market_ids: &[i64]) -> Vec<anyhow::Result<OracleResult>> {
+        let keys: Vec<String> = market_ids
+            .iter()
+            .map(|&id| keys::chain_oracle_result(&self.network, id))
+            .collect();
+        let results = self
+            .cache
+            .get_or_set_many_json(&keys, self.cache_ttls.oracle_result, |i| {
+                self.fetch_oracle_result(market_ids[i])
+            })
+            .await;
+        self.observe_lookups("oracle_result", results)
+    }
+
+    async fn fetch_oracle_result(&self, market_id: i64) -> anyhow::Result<OracleResult> {
+        let endpoint = "oracle_result";
+        let ledger = self.latest_ledger().await.unwrap_or(0);
+        match self
+            .rpc_call::<Value>(
+                "getContractData",
+                json!({
+                    "contractId": self.contract_id,
+                    "key": self.key_schema.oracle_result_key(market_id),
+                }),
+            )
+            .await
+        {
+            Ok(data) => Ok(OracleResult {
+                market_id,
+                source_name: data.get("source").and_then(Value::as_str).map(ToOwned::to_owned),
+                outcome: data.get("outcome").and_then(Value::as_u64).map(|v| v as u32),
+                confidence_bps: data.get("confidence_bps").and_then(Value::as_u64),
+                ledger,
+                source: DataSource::Live,
+            }),
+            Err(e) => {
+                self.metrics.observe_rpc_error(&self.network, "getContractData");
+                self.metrics.observe_rpc_fallback(&self.network, endpoint);
+                tracing::warn!(market_id, error = %e, "oracle_result RPC failed");
+                Err(e)
+            }
+        }
     }
 
     pub async fn transaction_status_cached(&self, hash: &str) -> anyhow::Result<TransactionStatus> {
         let key = keys::chain_tx_status(&self.network, hash);
-        let ttl = Duration::from_secs(20);
+        let ttl = self.cache_ttls.tx_status;
         let endpoint = "tx_status";
 
         let (value, hit) = self
@@ -767,8 +1606,8 @@ impl BlockchainClient {
                         source: DataSource::Live,
                     }),
                     Err(e) => {
-                        self.metrics.observe_rpc_error("getTransaction");
-                        self.metrics.observe_rpc_fallback(endpoint);
+                        self.metrics.observe_rpc_error(&self.network, "getTransaction");
+                        self.metrics.observe_rpc_fallback(&self.network, endpoint);
                         tracing::warn!(hash, error = %e, "transaction_status RPC failed");
                         Err(e)
                     }
@@ -787,30 +1626,31 @@ impl BlockchainClient {
 
     pub async fn health_check_cached(&self) -> anyhow::Result<BlockchainHealth> {
         let key = keys::chain_health(&self.network);
-        let ttl = Duration::from_secs(15);
+        let ttl = self.cache_ttls.health;
         let endpoint = "health";
 
         let (value, hit) = self
             .cache
             .get_or_set_json(&key, ttl, || async move {
-                let latest = self.latest_ledger().await.unwrap_or_else(|e| {
-                    self.metrics.observe_rpc_error("getLatestLedger");
+                let latest = self.latest_ledger_within(HEALTH_RPC_BUDGET).await.unwrap_or_else(|e| {
+                    self.metrics.observe_rpc_error(&self.network, "getLatestLedger");
                     tracing::warn!(error = %e, "health_check: getLatestLedger failed");
                     0
                 });
                 let contract_reachable = match self
-                    .rpc_call::<Value>(
+                    .rpc_call_within::<Value>(
                         "getContractData",
                         json!({
                             "contractId": self.contract_id,
                             "key": self.key_schema.health_check.clone(),
                         }),
+                        HEALTH_RPC_BUDGET,
                     )
                     .await
                 {
                     Ok(_) => true,
                     Err(e) => {
-                        self.metrics.observe_rpc_error("getContractData");
+                        self.metrics.observe_rpc_error(&self.network, "getContractData");
                         tracing::warn!(error = %e, "health_check: contract probe failed");
                         false
                     }
@@ -853,58 +1693,17 @@ impl BlockchainClient {
     }
 
     async fn fetch_events_since(&self, from_ledger: u32) -> anyhow::Result<Vec<ContractEvent>> {
-        #[derive(Debug, Deserialize)]
-        struct EventsResponse {
-            events: Vec<Value>,
-            #[serde(rename = "latestLedger")]
-            latest_ledger: Option<u32>,
-        }
-
         let mut all_events: Vec<ContractEvent> = Vec::new();
         let mut cursor: Option<String> = None;
         let mut pages: u64 = 0;
 
         loop {
-            let mut params = json!({
-                "startLedger": from_ledger,
-                "filters": [{"type": "contract", "contractIds": [self.contract_id]}],
-                "limit": 100,
-            });
-            if let Some(ref c) = cursor {
-                params["cursor"] = json!(c);
-            }
-
-            let result = self
-                .rpc_call::<EventsResponse>("getEvents", params)
-                .await
-                .map_err(|e| {
-                    self.metrics.observe_rpc_error("getEvents");
-                    tracing::warn!(from_ledger, error = %e, "getEvents RPC failed");
-                    e
-                })?;
-
+            let page = self
+                .fetch_events_page(from_ledger, cursor.as_deref(), EVENTS_PAGE_SIZE)
+                .await?;
             pages += 1;
-            let batch_len = result.events.len();
-            let last_id = result.events.last()
-                .and_then(|e| e.get("id"))
-                .and_then(Value::as_str)
-                .map(ToOwned::to_owned);
-
-            for e in result.events {
-                all_events.push(ContractEvent {
-                    id: e.get("id").and_then(Value::as_str).unwrap_or("unknown").to_string(),
-                    ledger: e.get("ledger").and_then(Value::as_u64).unwrap_or_default() as u32,
-                    topic: e.get("topic").map(|v| v.to_string()).unwrap_or_else(|| "unknown".to_string()),
-                    tx_hash: e.get("txHash").and_then(Value::as_str).map(ToOwned::to_owned),
-                    value: e,
-                });
-            }
-
-            // Stop if we got fewer than the page size (last page)
-            if batch_len < 100 {
-                break;
-            }
-            cursor = last_id;
+            all_events.extend(page.events);
+            cursor = page.next_cursor;
             if cursor.is_none() {
                 break;
             }
@@ -923,33 +1722,242 @@ impl BlockchainClient {
         Ok(all_events)
     }
 
-    async fn handle_reorg_if_detected(&self, latest_ledger: u32) -> anyhow::Result<()> {
-        let key = keys::chain_last_seen_ledger(&self.network);
-        let previous = self.cache.get_json::<u32>(&key).await?.unwrap_or(0);
+    /// One `getEvents` page of this contract's events from `start_ledger`,
+    /// or after `cursor` when continuing a previous page.
+    pub(crate) async fn fetch_events_page(
+        &self,
+        start_ledger: u32,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> anyhow::Result<EventsPage> {
+        #[derive(Debug, Deserialize)]
+        struct EventsResponse {
+            events: Vec<Value>,
+            #[serde(rename = "latestLedger")]
+            latest_ledger: Option<u32>,
+        }
 
-        if previous > 0 && latest_ledger + self.confirmation_ledger_lag < previous {
-            let purged = self
-                .cache
-                .del_by_pattern(&format!("{}:*", keys::CHAIN_PREFIX))
-                .await?;
-            self.metrics.observe_invalidation("chain_reorg", purged);
+        let mut params = json!({
+            "startLedger": start_ledger,
+            "filters": [{"type": "contract", "contractIds": [self.contract_id]}],
+            "limit": limit,
+        });
+        if let Some(c) = cursor {
+            params["cursor"] = json!(c);
         }
 
+        let result = self
+            .rpc_call_within::<EventsResponse>("getEvents", params, SYNC_RPC_BUDGET)
+            .await
+            .map_err(|e| {
+                self.metrics.observe_rpc_error(&self.network, "getEvents");
+                tracing::warn!(start_ledger, error = %e, "getEvents RPC failed");
+                e
+            })?;
+
+        let batch_len = result.events.len();
+        let last_id = result.events.last()
+            .and_then(|e| e.get("id"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned);
+
+        let events = result
+            .events
+            .into_iter()
+            .map(|e| ContractEvent {
+                id: e.get("id").and_then(Value::as_str).unwrap_or("unknown").to_string(),
+                ledger: e.get("ledger").and_then(Value::as_u64).unwrap_or_default() as u32,
+                topic: e.get("topic").map(|v| v.to_string()).unwrap_or_else(|| "unknown".to_string()),
+                tx_hash: e.get("txHash").and_then(Value::as_str).map(ToOwned::to_owned),
+                value: e,
+            })
+            .collect();
+
+        // A short page is the last one.
+        let next_cursor = if batch_len < limit as usize { None } else { last_id };
+        Ok(EventsPage {
+            events,
+            next_cursor,
+            latest_ledger: result.latest_ledger,
+        })
+    }
+
+    /// Reconcile `cursor` with the chain head and record the head as the
+    /// last-seen ledger for the next pass's reorg check.
+    pub async fn plan_sync(&self, cursor: u32) -> anyhow::Result<SyncPlan> {
+        let latest = match self.latest_ledger_within(SYNC_RPC_BUDGET).await {
+            Ok(latest) => latest,
+            Err(e) => {
+                self.metrics.observe_rpc_error(&self.network, "getLatestLedger");
+                tracing::warn!(error = %e, "plan_sync: getLatestLedger failed, holding cursor");
+                return Ok(SyncPlan::hold(cursor));
+            }
+        };
+        self.metrics.set_sync_lag(&self.network, latest.saturating_sub(cursor));
+
+        let key = keys::chain_last_seen_ledger(&self.network);
+        let last_seen = self.cache.get_json::<u32>(&key).await?.unwrap_or(0);
         self.cache
-            .set_json(&key, &latest_ledger, Duration::from_secs(24 * 60 * 60))
+            .set_json(&key, &latest, Duration::from_secs(24 * 60 * 60))
             .await?;
+
+        Ok(SyncPlan::decide(cursor, latest, last_seen, self.confirmation_ledger_lag))
+    }
+
+    /// Discard everything indexed above `ledger` so the next fetch replays it:
+    /// Postgres events first, then the cursor, then derived Redis entries.
+    async fn rewind_to_ledger(&self, ledger: u32) -> anyhow::Result<()> {
+        let removed = self.db.chain_events_delete_above(&self.network, ledger).await?;
+        self.db.protocol_state_changes_delete_above(&self.network, ledger).await?;
+        self.db.sync_state_set(&self.network, ledger).await?;
+
+        let mut purged = self
+            .cache
+            .del_by_pattern(&format!("{}:*", keys::CHAIN_PREFIX))
+            .await?;
+        purged += self
+            .cache
+            .del_by_pattern(&keys::api_user_portfolio("*"))
+            .await?;
+        self.metrics.observe_invalidation("chain_reorg", purged);
+
+        tracing::warn!(
+            network = %self.network,
+            rewind_to = ledger,
+            events_removed = removed,
+            cache_keys_purged = purged,
+            "chain reorg or stale cursor: rewinding indexed events"
+        );
         Ok(())
     }
 
-    async fn sync_once(&self, cursor_ledger: u32) -> anyhow::Result<u32> {
-        let latest = self.latest_ledger().await.unwrap_or_else(|e| {
-            self.metrics.observe_rpc_error("getLatestLedger");
-            tracing::warn!(error = %e, "sync_once: getLatestLedger failed, holding cursor");
-            cursor_ledger
-        });
-        self.handle_reorg_if_detected(latest).await?;
+    /// Load the sync cursor. Postgres is authoritative; the Redis copy is
+    /// only used when the table has no row yet (first start after upgrade)
+    /// or Postgres is unreachable.
+    async fn load_sync_cursor(&self) -> u32 {
+        match self.db.sync_state_get(&self.network).await {
+            Ok(Some(ledger)) => {
+                let _ = self
+                    .cache
+                    .set_json(
+                        &keys::chain_sync_cursor(&self.network),
+                        &ledger,
+                        Duration::from_secs(24 * 60 * 60),
+                    )
+                    .await;
+                ledger
+            }
+            Ok(None) => self.cached_sync_cursor().await,
+            Err(e) => {
+                tracing::warn!(error = %e, "sync_state read failed; falling back to Redis cursor");
+                self.cached_sync_cursor().await
+            }
+        }
+    }
+
+    async fn cached_sync_cursor(&self) -> u32 {
+        self.cache
+            .get_json::<u32>(&keys::chain_sync_cursor(&self.network))
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+    }
+
+    /// Write the cursor to Postgres, then refresh the Redis cache of it.
+    async fn persist_sync_cursor(&self, ledger: u32) -> anyhow::Result<()> {
+        self.db.sync_state_set(&self.network, ledger).await?;
+        let _ = self
+            .cache
+            .set_json(
+                &keys::chain_sync_cursor(&self.network),
+                &ledger,
+                Duration::from_secs(24 * 60 * 60),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Persist one confirmed event: a short-lived Redis copy for cache
+    /// refreshes plus an idempotent row in `chain_events`, and one in
+    /// `protocol_state_changes` for a breaker transition. A creation event
+    /// on the primary network also gets its market a `markets` row when it
+    /// has none, so markets created directly on chain are listed; a row that
+    /// exists only has a missing category or creator filled in. It also maps
+    /// the market's public id to itself unless either is already bound; see
+    /// [`crate::market_ids`]. Invalidates
+    /// the portfolio of the event's address and the entries named by
+    /// [`IndexedEvent::invalidation_tag`] so the next read reflects it, and
+    /// emails anyone watching the market for resolutions and disputes, and
+    /// opted-in holders of positions in it. Returns the market the event
+    /// concerns, if any.
+    async fn store_event(&self, event: &ContractEvent) -> anyhow::Result<Option<i64>> {
+        let event_key = format!("{}:event:{}", keys::CHAIN_PREFIX, event.id);
+        self.cache
+            .set_json(&event_key, event, Duration::from_secs(30 * 60))
+            .await?;
+
+        if let Some(change) = protocol_state::breaker_transition(event) {
+            self.db.protocol_state_change_insert(&self.network, &change).await?;
+            let _ = self.cache.del(&keys::chain_protocol_state(&self.network)).await;
+        }
+
+        if let Some(indexed) = IndexedEvent::from_contract_event(event) {
+            self.db.chain_event_insert(&self.network, &indexed).await?;
+            if self.owns_market_rows {
+                if let Some(creation) = MarketCreation::from_contract_event(event) {
+                    if self.db.market_skeleton_upsert(&creation).await? {
+                        tracing::info!(market_id = creation.market_id, "indexed a market created on chain");
+                    }
+                    self.db
+                        .market_id_mapping_identity(&self.network, &self.contract_id, creation.market_id)
+                        .await?;
+                }
+            }
+            if let Some(address) = &indexed.address {
+                let _ = self.cache.del(&keys::api_user_portfolio(address)).await;
+            }
+            // A failed invalidation leaves the entries to expire with their
+            // TTL; it must not stall the sync cursor.
+            if let Some(tag) = indexed.invalidation_tag(&self.network, self.featured_limit) {
+                match self.cache.invalidate_tag(&tag).await {
+                    Ok(count) => self.metrics.observe_event_invalidation(&self.network, &indexed.kind, count),
+                    Err(e) => tracing::warn!(event_id = %indexed.id, error = %e, "event cache invalidation failed"),
+                }
+            }
+            // Notification failures must not stall the sync cursor; claims
+            // are per (watch, event) and (address, event), so a replay
+            // retries only what failed.
+            let queue = EmailQueue::new(self.db.clone());
+            if let Err(e) =
+                market_watch::notify_watchers(&self.db, &queue, &self.base_url, &self.network, &indexed).await
+            {
+                tracing::warn!(event_id = %indexed.id, error = %e, "failed to notify market watchers");
+            }
+            if let Err(e) = user_notifications::notify_position_holders(
+                &self.db,
+                &queue,
+                &self.base_url,
+                &self.network,
+                &indexed,
+            )
+            .await
+            {
+                tracing::warn!(event_id = %indexed.id, error = %e, "failed to notify position holders");
+            }
+            return Ok(indexed.market_id);
+        }
+        Ok(None)
+    }
+
+    pub(crate) async fn sync_once(&self, cursor_ledger: u32) -> anyhow::Result<u32> {
+        let plan = self.plan_sync(cursor_ledger).await?;
+        if let Some(ledger) = plan.rewind_to {
+            self.rewind_to_ledger(ledger).await?;
+        }
+        let cursor_ledger = plan.cursor;
+        let confirmed_tip = plan.confirmed_tip;
 
-        let confirmed_tip = latest.saturating_sub(self.confirmation_ledger_lag);
         if confirmed_tip <= cursor_ledger {
             return Ok(cursor_ledger);
         }
@@ -969,11 +1977,11 @@ impl BlockchainClient {
         }
 
         let events = self.fetch_events_since(cursor_ledger + 1).await?;
+        let mut touched = Vec::new();
         for event in events {
-            let event_key = format!("{}:event:{}", keys::CHAIN_PREFIX, event.id);
-            self.cache
-                .set_json(&event_key, &event, Duration::from_secs(30 * 60))
-                .await?;
+            if let Some(market_id) = self.store_event(&event).await? {
+                touched.push(market_id);
+            }
 
             if let Some(hash) = event.tx_hash {
                 // AlreadyWatched is benign (idempotent); CapReached is logged
@@ -990,43 +1998,113 @@ impl BlockchainClient {
             }
         }
 
-        for market_id in &self.sync_market_ids {
-            let _ = self.market_data_cached(*market_id).await;
-            let _ = self.oracle_result_cached(*market_id).await;
+        // Snapshot implied odds every pass, not only when new bets arrived,
+        // so charts show flat segments instead of gaps.
+        if let Err(e) = self.db.price_points_sample(&self.network).await {
+            tracing::warn!(error = %e, "sync_once: price point sampling failed");
         }
 
-        let _ = self.platform_statistics_cached().await;
+        let refresh = self.sync_refresh_plan(&touched).await;
+        for &market_id in &refresh.markets {
+            let _ = self.market_data_cached(market_id).await;
+            let _ = self.oracle_result_cached(market_id).await;
+        }
+        if refresh.platform_stats {
+            let _ = self.platform_statistics_cached().await;
+        }
 
         Ok(confirmed_tip)
     }
 
-    /// Sync worker — polls for new on-chain events on each iteration.
-    /// Stops cleanly when `shutdown` is cancelled; any in-flight `sync_once`
-    /// call is always allowed to complete before the loop exits.
-    pub async fn run_sync_worker(
-        self: Arc<Self>,
-        shutdown: tokio_util::sync::CancellationToken,
-        coordinator: ShutdownCoordinator,
-    ) {
+    /// The refreshes for this pass, chosen by `sync_refresh` from the watch
+    /// set. The set is reloaded from the database on the first pass and
+    /// every `sync_watch_refresh_every` after; a failed reload keeps the
+    /// previous set. Markets in `touched` had events this pass and count as
+    /// hot until the next reload catches up.
+    async fn sync_refresh_plan(&self, touched: &[i64]) -> RefreshPlan {
+        let pass = self.sync_watch.passes.fetch_add(1, Ordering::Relaxed);
+        if pass % self.sync_watch_refresh_every == 0 {
+            if let Err(e) = self.refresh_market_watch_set().await {
+                tracing::warn!(network = %self.network, error = %e, "sync watch set refresh failed");
+            }
+        }
+
+        let now = Utc::now();
+        let mut markets = self.sync_watch.markets.write().await;
+        for profile in markets.iter_mut().filter(|p| touched.contains(&p.market_id)) {
+            profile.last_event_at = Some(now);
+        }
+        let plan = self
+            .sync_watch
+            .schedule
+            .lock()
+            .unwrap()
+            .plan(&markets, &self.sync_refresh, now);
+        drop(markets);
+
+        self.metrics.observe_sync_refreshes_skipped(&self.network, "budget", plan.skipped_budget);
+        self.metrics.observe_sync_refreshes_skipped(&self.network, "backoff", plan.skipped_backoff);
+        plan
+    }
+
+    /// Reload the sync watch set: `SYNC_MARKET_IDS` first, then active and
+    /// recently resolved markets by volume, up to `sync_watch_max_markets`
+    /// in total, with the profiles `sync_refresh` ranks them by. Returns the
+    /// new set's ids.
+    pub async fn refresh_market_watch_set(&self) -> anyhow::Result<Vec<i64>> {
+        let mut markets: Vec<i64> = Vec::with_capacity(self.sync_watch_max_markets);
+        for id in &self.sync_market_ids {
+            if !markets.contains(id) {
+                markets.push(*id);
+            }
+        }
+        // `SYNC_MARKET_IDS` is kept whole even when it alone exceeds the cap.
+        let cap = self.sync_watch_max_markets.max(markets.len());
+        if markets.len() < cap {
+            // Fetching `cap` rows leaves room for static ids that come back
+            // from the query too.
+            let selected = self
+                .db
+                .active_market_ids(cap as i64, self.sync_watch_resolved_retention)
+                .await?;
+            for id in selected {
+                if markets.len() >= cap {
+                    break;
+                }
+                if !markets.contains(&id) {
+                    markets.push(id);
+                }
+            }
+        }
+
+        let mut profiles = self.db.sync_market_profiles(&self.network, &markets).await?;
+        let profiles: Vec<MarketProfile> = markets
+            .iter()
+            .map(|&id| match profiles.iter().position(|p| p.market_id == id) {
+                Some(i) => profiles.swap_remove(i),
+                None => MarketProfile::unknown(id),
+            })
+            .collect();
+
+        self.metrics.set_sync_watch_size(&self.network, markets.len());
+        *self.sync_watch.markets.write().await = profiles;
+        Ok(markets)
+    }
+
+    /// Sync loop — polls for new on-chain events on each iteration.
+    /// Stops cleanly when `shutdown` is cancelled; any in-flight `sync_once`
+    /// call is always allowed to complete before the loop exits.
+    pub async fn run_sync_loop(&self, shutdown: tokio_util::sync::CancellationToken) {
         const WORKER_NAME: &str = "blockchain_sync";
         
         // Set worker status to running
-        if let Some(ref m) = &self.metrics {
-            m.set_worker_status(WORKER_NAME, true);
-        }
+        self.metrics.set_worker_status(WORKER_NAME, true);
         
         tracing::info!("Blockchain sync worker started");
 
-        let cursor_key = keys::chain_sync_cursor(&self.network);
         let checkpoint_key = format!("{}:ledger_checkpoint:{}", keys::CHAIN_PREFIX, &self.network);
 
-        let mut cursor = self
-            .cache
-            .get_json::<u32>(&cursor_key)
-            .await
-            .ok()
-            .flatten()
-            .unwrap_or(0);
+        let mut cursor = self.load_sync_cursor().await;
 
         // On restart, check for a gap between stored checkpoint and current ledger.
         if cursor > 0 {
@@ -1040,7 +2118,7 @@ impl BlockchainClient {
                             gap_size = gap,
                             "Ledger gap detected on restart — replaying missed range"
                         );
-                        self.metrics.observe_ledger_gap(gap);
+                        self.metrics.observe_ledger_gap(&self.network, gap);
                         if gap > 10 {
                             tracing::error!(
                                 gap_size = gap,
@@ -1053,8 +2131,6 @@ impl BlockchainClient {
             }
         }
 
-        loop {
-
         let mut heartbeat_interval = tokio::time::interval(Duration::from_secs(30));
         heartbeat_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
@@ -1062,9 +2138,7 @@ impl BlockchainClient {
             // Update heartbeat
             tokio::select! {
                 _ = heartbeat_interval.tick() => {
-                    if let Some(ref m) = &self.metrics {
-                        m.set_worker_status(WORKER_NAME, true);
-                    }
+                    self.metrics.set_worker_status(WORKER_NAME, true);
                 }
                 else => {}
             }
@@ -1077,7 +2151,8 @@ impl BlockchainClient {
 
             match self.sync_once(cursor).await {
                 Ok(next_cursor) => {
-                    if next_cursor > cursor {
+                    // next_cursor < cursor after a rewind; persist that too.
+                    if next_cursor != cursor {
                         let expected_next = cursor + 1;
                         if next_cursor > expected_next {
                             let gap = next_cursor - expected_next;
@@ -1087,7 +2162,7 @@ impl BlockchainClient {
                                 gap_size = gap,
                                 "Ledger sequence gap detected during sync"
                             );
-                            self.metrics.observe_ledger_gap(gap);
+                            self.metrics.observe_ledger_gap(&self.network, gap);
                             if gap > 10 {
                                 tracing::error!(
                                     gap_size = gap,
@@ -1097,10 +2172,9 @@ impl BlockchainClient {
                         }
 
                         cursor = next_cursor;
-                        let _ = self
-                            .cache
-                            .set_json(&cursor_key, &cursor, Duration::from_secs(24 * 60 * 60))
-                            .await;
+                        if let Err(e) = self.persist_sync_cursor(cursor).await {
+                            tracing::warn!(error = %e, cursor, "failed to persist sync cursor");
+                        }
                         let _ = self
                             .cache
                             .set_json(&checkpoint_key, &cursor, Duration::from_secs(7 * 24 * 60 * 60))
@@ -1121,9 +2195,7 @@ impl BlockchainClient {
         }
 
         // Set worker status to stopped
-        if let Some(ref m) = &self.metrics {
-            m.set_worker_status(WORKER_NAME, false);
-        }
+        self.metrics.set_worker_status(WORKER_NAME, false);
         
         tracing::info!("Blockchain sync worker stopped");
     }
@@ -1149,46 +2221,57 @@ impl BlockchainClient {
         const WORKER_NAME: &str = "blockchain_tx_monitor";
         
         // Set worker status to running
-        if let Some(ref m) = &self.metrics {
-            m.set_worker_status(WORKER_NAME, true);
-        }
+        self.metrics.set_worker_status(WORKER_NAME, true);
         
         tracing::info!("Blockchain transaction monitor started");
 
         let mut heartbeat_interval = tokio::time::interval(Duration::from_secs(30));
         heartbeat_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
+        // Resolution writes run off the poll loop but are awaited before the
+        // monitor reports itself stopped, so shutdown never drops one.
+        let mut resolutions = tokio::task::JoinSet::new();
+
+        // Finalization notifications for `tx_watch` subscribers. Without a
+        // dispatcher, emails still go out but callbacks are skipped.
+        let queue = EmailQueue::new(self.db.clone());
+        let webhooks = match WebhookDispatcher::new() {
+            Ok(webhooks) => Some(Arc::new(webhooks)),
+            Err(e) => {
+                tracing::error!(error = %e, "Transaction monitor: webhook client unavailable");
+                None
+            }
+        };
+
         loop {
             // Update heartbeat
             tokio::select! {
                 _ = heartbeat_interval.tick() => {
-                    if let Some(ref m) = &self.metrics {
-                        m.set_worker_status(WORKER_NAME, true);
-                    }
+                    self.metrics.set_worker_status(WORKER_NAME, true);
+                    let db = self.db.clone();
+                    resolutions.spawn(async move {
+                        match db.tx_watch_subscriptions_delete_expired().await {
+                            Ok(0) => {}
+                            Ok(removed) => tracing::debug!(removed, "expired tx watch subscriptions removed"),
+                            Err(e) => tracing::warn!(error = %e, "failed to remove expired tx watch subscriptions"),
+                        }
+                    });
                 }
                 else => {}
             }
-            
+            while resolutions.try_join_next().is_some() {}
+
             if shutdown.is_cancelled() {
                 tracing::info!("Transaction monitor: shutdown signal received, stopping");
                 break;
             }
 
-            let hashes = self
-                .monitor
-                .watched_txs
-                .read()
-                .await
-                .keys()
-                .cloned()
-                .collect::<Vec<_>>();
+            let hashes = self.refresh_watch_set().await;
 
             for hash in hashes {
                 if let Ok(status) = self.transaction_status_cached(&hash).await {
                     if status.status != "NOT_FOUND" && status.status != "PENDING" {
-                        let mut set = self.monitor.watched_txs.write().await;
-                        set.remove(&hash);
-                        self.metrics.set_watched_tx_count(set.len() as i64);
+                        self.unwatch_transaction(&hash).await;
                         let db = self.db.clone();
                         let resolved_status = if status.status == "SUCCESS" {
                             "confirmed"
@@ -1196,7 +2279,9 @@ impl BlockchainClient {
                             "expired"
                         };
                         let hash_owned = hash.clone();
-                        tokio::spawn(async move {
+                        let (queue, webhooks, network) =
+                            (queue.clone(), webhooks.clone(), self.network.clone());
+                        resolutions.spawn(async move {
                             if let Err(e) = db.watched_tx_mark_resolved(&hash_owned, resolved_status).await {
                                 tracing::warn!(
                                     tx_hash = %hash_owned,
@@ -1205,6 +2290,19 @@ impl BlockchainClient {
                                     "failed to mark watched tx resolved in database"
                                 );
                             }
+                            // One poll, however many subscribers: each open
+                            // subscription is claimed and notified here.
+                            if let Err(e) = tx_watch::notify_subscribers(
+                                &db,
+                                &queue,
+                                webhooks.as_deref(),
+                                &network,
+                                &status,
+                            )
+                            .await
+                            {
+                                tracing::warn!(tx_hash = %hash_owned, error = %e, "failed to notify tx watch subscribers");
+                            }
                         });
                     }
                 }
@@ -1219,31 +2317,415 @@ impl BlockchainClient {
             }
         }
 
-        // Set worker status to stopped
-        if let Some(ref m) = &self.metrics {
-            m.set_worker_status(WORKER_NAME, false);
+        if !resolutions.is_empty() {
+            tracing::info!(pending = resolutions.len(), "Transaction monitor: flushing resolution writes");
+            while resolutions.join_next().await.is_some() {}
         }
+
+        // Set worker status to stopped
+        self.metrics.set_worker_status(WORKER_NAME, false);
         
         tracing::info!("Blockchain transaction monitor stopped");
         coordinator.worker_completed();
     }
 
-    /// Load non-expired pending watched transactions from the database into the in-memory map.
-    /// Call once on startup before spawning background workers.
-    pub async fn load_watched_transactions(&self) -> anyhow::Result<()> {
-        let pending = self.db.watched_tx_load_pending().await?;
+    /// Load non-expired pending watched transactions from the database into the in-memory map
+    /// and the Redis watch set, so watches recorded only in the database (e.g. while Redis was
+    /// down) are picked up by the monitor. Existing Redis entries keep their original score.
+    /// Call once on startup before spawning background workers. Pass
+    /// `include_unscoped` for the primary network only; see
+    /// [`Database::watched_tx_load_pending`].
+    pub async fn load_watched_transactions(&self, include_unscoped: bool) -> anyhow::Result<()> {
+        let pending = self
+            .db
+            .watched_tx_load_pending(&self.network, include_unscoped)
+            .await?;
         let count = pending.len();
         if count > 0 {
+            let key = keys::tx_watch_set(&self.network);
+            let score = Utc::now().timestamp();
             let mut set = self.monitor.watched_txs.write().await;
             let now = Instant::now();
             for tx_hash in pending {
+                if let Err(e) = self.cache.zset_add_nx(&key, &tx_hash, score).await {
+                    tracing::warn!(tx_hash = %tx_hash, error = %e, "failed to restore watched tx to Redis");
+                }
                 set.entry(tx_hash).or_insert(now);
             }
-            tracing::info!(count, "restored watched transactions from database");
+            tracing::info!(count, network = %self.network, "restored watched transactions from database");
         }
         Ok(())
     }
 
+    /// Expire old entries from the Redis watch set, then merge what remains
+    /// into the in-memory map and return the hashes to poll. Expired hashes
+    /// are marked `expired` in the database and counted in
+    /// `tx_watch_expired_total`. If Redis is unavailable the monitor carries
+    /// on with the in-memory map alone.
+    async fn refresh_watch_set(&self) -> Vec<String> {
+        let key = keys::tx_watch_set(&self.network);
+        let now_unix = Utc::now().timestamp();
+        let cutoff = now_unix - self.watched_tx_ttl.as_secs() as i64;
+
+        match self.cache.zset_pop_at_most(&key, cutoff).await {
+            Ok(expired) if !expired.is_empty() => {
+                self.metrics
+                    .observe_tx_watch_expired(&self.network, expired.len() as u64);
+                tracing::info!(count = expired.len(), network = %self.network, "watched transactions expired");
+                for hash in &expired {
+                    if let Err(e) = self.db.watched_tx_mark_resolved(hash, "expired").await {
+                        tracing::warn!(tx_hash = %hash, error = %e, "failed to mark watched tx expired in database");
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "failed to expire watched transactions in Redis"),
+        }
+
+        let persisted = match self.cache.zset_members(&key).await {
+            Ok(members) => members,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load watched transactions from Redis; using in-memory set");
+                Vec::new()
+            }
+        };
+
+        let mut set = self.monitor.watched_txs.write().await;
+        let now = Instant::now();
+        set.retain(|_, inserted_at| now.duration_since(*inserted_at) < self.watched_tx_ttl);
+        for (hash, score) in persisted {
+            let age = Duration::from_secs(now_unix.saturating_sub(score).max(0) as u64);
+            set.entry(hash).or_insert_with(|| now.checked_sub(age).unwrap_or(now));
+        }
+        self.metrics.set_watched_tx_count(set.len() as i64);
+        set.keys().cloned().collect()
+    }
+
+    /// Stop tracking `hash` once it reaches a final status, here and in Redis.
+    async fn unwatch_transaction(&self, hash: &str) {
+        let mut set = self.monitor.watched_txs.write().await;
+        set.remove(hash);
+        self.metrics.set_watched_tx_count(set.len() as i64);
+        drop(set);
+        if let Err(e) = self
+            .cache
+            .zset_remove(&keys::tx_watch_set(&self.network), hash)
+            .await
+        {
+            tracing::warn!(tx_hash = %hash, error = %e, "failed to remove watched tx from Redis");
+        }
+    }
+
+    /// Proxy `simulateTransaction` for an unsigned, base64 envelope. A failed
+    /// simulation is not an `Err`: the node's message is returned in
+    /// [`TxSimulation::error`] so callers see it verbatim.
+    pub async fn simulate_transaction(&self, envelope_xdr: &str) -> anyhow::Result<TxSimulation> {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawResult {
+            xdr: String,
+            #[serde(default)]
+            auth: Vec<String>,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawSimulation {
+            latest_ledger: u32,
+            min_resource_fee: Option<String>,
+            transaction_data: Option<String>,
+            #[serde(default)]
+            results: Vec<RawResult>,
+            #[serde(default)]
+            events: Vec<String>,
+            error: Option<String>,
+            restore_preamble: Option<Value>,
+        }
+
+        let raw: RawSimulation = self
+            .rpc_call("simulateTransaction", json!({ "transaction": envelope_xdr }))
+            .await?;
+
+        Ok(TxSimulation {
+            latest_ledger: raw.latest_ledger,
+            min_resource_fee: raw.min_resource_fee,
+            transaction_data: raw.transaction_data,
+            results: raw
+                .results
+                .into_iter()
+                .map(|r| TxSimulationResult { xdr: r.xdr, auth: r.auth })
+                .collect(),
+            events: raw.events,
+            error: raw.error,
+            restore_preamble: raw.restore_preamble,
+        })
+    }
+
+    /// Proxy `sendTransaction` for a signed, base64 envelope and register the
+    /// hash with the transaction monitor when the network accepts it.
+    /// Resubmitting the same envelope is safe: the node answers `DUPLICATE`.
+    pub async fn submit_transaction(&self, envelope_xdr: &str) -> anyhow::Result<TxSubmission> {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct RawSend {
+            hash: String,
+            status: String,
+            latest_ledger: u32,
+            error_result_xdr: Option<String>,
+        }
+
+        let raw: RawSend = self
+            .rpc_call("sendTransaction", json!({ "transaction": envelope_xdr }))
+            .await?;
+
+        let mut submission = TxSubmission {
+            hash: raw.hash,
+            status: raw.status,
+            latest_ledger: raw.latest_ledger,
+            error_result_xdr: raw.error_result_xdr,
+            watched: false,
+        };
+
+        if submission.is_accepted() {
+            submission.watched = match self.watch_transaction(&submission.hash).await {
+                Ok(()) | Err(WatchTxError::AlreadyWatched) => true,
+                Err(WatchTxError::CapReached) => {
+                    tracing::warn!(
+                        hash = %submission.hash,
+                        "submit_transaction: watched_tx cap reached, hash not monitored"
+                    );
+                    false
+                }
+            };
+        }
+
+        Ok(submission)
+    }
+
+    /// Resolve `market_id` on-chain as the contract admin and wait for the
+    /// transaction to succeed. Callers must not touch caches or the database
+    /// until this returns `Ok`.
+    pub async fn resolve_market_onchain(
+        &self,
+        market_id: u64,
+        winning_outcome: u32,
+    ) -> Result<TransactionStatus, ContractCallError> {
+        let signer = self
+            .admin_signer
+            .clone()
+            .ok_or(ContractCallError::NotConfigured)?;
+        self.invoke_contract(
+            &signer,
+            "resolve_market",
+            vec![xdr::ScVal::U64(market_id), xdr::ScVal::U32(winning_outcome)],
+        )
+        .await
+    }
+
+    /// Write `outcome` into oracle slot `oracle_id` for `market_id`, signed by
+    /// the oracle keeper key, and wait for the transaction to succeed.
+    pub async fn set_oracle_result_onchain(
+        &self,
+        market_id: u64,
+        oracle_id: u32,
+        outcome: u32,
+    ) -> Result<TransactionStatus, ContractCallError> {
+        let signer = self
+            .oracle_signer
+            .clone()
+            .ok_or(ContractCallError::NotConfigured)?;
+        self.invoke_contract(
+            &signer,
+            "set_oracle_result",
+            vec![
+                xdr::ScVal::U64(market_id),
+                xdr::ScVal::U32(oracle_id),
+                xdr::ScVal::U32(outcome),
+            ],
+        )
+        .await
+    }
+
+    /// Move `market_id` to pending resolution from its oracle result. The
+    /// contract call is permissionless; the oracle keeper key pays for it.
+    pub async fn attempt_oracle_resolution_onchain(
+        &self,
+        market_id: u64,
+    ) -> Result<TransactionStatus, ContractCallError> {
+        let signer = self
+            .oracle_signer
+            .clone()
+            .ok_or(ContractCallError::NotConfigured)?;
+        self.invoke_contract(
+            &signer,
+            "attempt_oracle_resolution",
+            vec![xdr::ScVal::U64(market_id)],
+        )
+        .await
+    }
+
+    /// Build, simulate, sign and submit a call to `function` on the
+    /// configured contract, then poll until it is final.
+    ///
+    /// The signer is the transaction source, so simulation's auth entries use
+    /// source-account credentials and need no extra signatures.
+    pub async fn invoke_contract(
+        &self,
+        signer: &TxSigner,
+        function: &str,
+        args: Vec<xdr::ScVal>,
+    ) -> Result<TransactionStatus, ContractCallError> {
+        let sequence = self
+            .account_sequence(signer.public_key())
+            .await
+            .map_err(ContractCallError::Rpc)?;
+        let tx = self
+            .build_invocation(signer.public_key(), sequence + 1, function, args)
+            .map_err(ContractCallError::Signing)?;
+
+        let unsigned = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: xdr::VecM::default(),
+        });
+        let unsigned = unsigned
+            .to_xdr_base64(xdr::Limits::none())
+            .map_err(|e| ContractCallError::Signing(e.into()))?;
+        let simulation = self
+            .simulate_transaction(&unsigned)
+            .await
+            .map_err(ContractCallError::Rpc)?;
+        if let Some(error) = simulation.error {
+            return Err(ContractCallError::Simulation(error));
+        }
+
+        let envelope = assemble_transaction(tx, &simulation)
+            .and_then(|tx| signer.sign(tx, &self.expected_passphrase))
+            .and_then(|env| Ok(env.to_xdr_base64(xdr::Limits::none())?))
+            .map_err(ContractCallError::Signing)?;
+
+        let submission = self
+            .submit_transaction(&envelope)
+            .await
+            .map_err(ContractCallError::Rpc)?;
+        if !submission.is_accepted() {
+            return Err(ContractCallError::Rejected {
+                hash: submission.hash,
+                status: submission.status,
+                result_xdr: submission.error_result_xdr,
+            });
+        }
+
+        tracing::info!(function, hash = %submission.hash, "contract call submitted");
+        self.await_final_status(&submission.hash).await
+    }
+
+    /// Poll `transaction_status_cached` until `hash` is `SUCCESS` or `FAILED`,
+    /// or `contract_call_timeout` elapses.
+    async fn await_final_status(&self, hash: &str) -> Result<TransactionStatus, ContractCallError> {
+        let deadline = Instant::now() + self.contract_call_timeout;
+        let status_key = keys::chain_tx_status(&self.network, hash);
+
+        loop {
+            match self.transaction_status_cached(hash).await {
+                Ok(status) if status.status == "SUCCESS" => return Ok(status),
+                Ok(status) if status.status == "FAILED" => {
+                    return Err(ContractCallError::Rejected {
+                        hash: hash.to_string(),
+                        status: status.status,
+                        result_xdr: status.error,
+                    })
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!(hash, error = %e, "contract call: status poll failed"),
+            }
+
+            // Statuses are cached for `cache_ttls.tx_status`; drop the
+            // pending one so the next poll reaches the node.
+            let _ = self.cache.del(&status_key).await;
+
+            if Instant::now() >= deadline {
+                return Err(ContractCallError::Timeout {
+                    hash: hash.to_string(),
+                });
+            }
+            sleep(self.tx_poll_interval).await;
+        }
+    }
+
+    /// Current sequence number of `account`, via `getLedgerEntries`.
+    async fn account_sequence(&self, account: [u8; 32]) -> anyhow::Result<i64> {
+        #[derive(Debug, Deserialize)]
+        struct Entry {
+            xdr: String,
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Entries {
+            #[serde(default)]
+            entries: Vec<Entry>,
+        }
+
+        let key = xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(account))),
+        });
+        let raw: Entries = self
+            .rpc_call(
+                "getLedgerEntries",
+                json!({ "keys": [key.to_xdr_base64(xdr::Limits::none())?] }),
+            )
+            .await?;
+
+        let entry = raw
+            .entries
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("signer account does not exist on this network"))?;
+        match xdr::LedgerEntryData::from_xdr_base64(&entry.xdr, xdr::Limits::none())
+            .context("getLedgerEntries: bad account entry")?
+        {
+            xdr::LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+            _ => Err(anyhow!("getLedgerEntries: expected an account entry")),
+        }
+    }
+
+    fn build_invocation(
+        &self,
+        source: [u8; 32],
+        seq_num: i64,
+        function: &str,
+        args: Vec<xdr::ScVal>,
+    ) -> anyhow::Result<xdr::Transaction> {
+        let contract = stellar_strkey::Contract::from_string(&self.contract_id)
+            .map_err(|_| anyhow!("CONTRACT_ID is not a contract address: {}", self.contract_id))?;
+
+        let operation = xdr::Operation {
+            source_account: None,
+            body: xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+                host_function: xdr::HostFunction::InvokeContract(xdr::InvokeContractArgs {
+                    contract_address: xdr::ScAddress::Contract(xdr::Hash(contract.0)),
+                    function_name: xdr::ScSymbol(function.try_into()?),
+                    args: args.try_into()?,
+                }),
+                auth: xdr::VecM::default(),
+            }),
+        };
+
+        Ok(xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(source)),
+            fee: CONTRACT_CALL_BASE_FEE,
+            seq_num: xdr::SequenceNumber(seq_num),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: vec![operation].try_into()?,
+            ext: xdr::TransactionExt::V0,
+        })
+    }
+
+    /// Whether `hash` is currently tracked by the transaction monitor.
+    pub async fn is_watched(&self, hash: &str) -> bool {
+        self.monitor.watched_txs.read().await.contains_key(hash)
+    }
+
     pub async fn watch_transaction(&self, hash: &str) -> Result<(), WatchTxError> {
         let mut set = self.monitor.watched_txs.write().await;
 
@@ -1278,15 +2760,27 @@ impl BlockchainClient {
         set.insert(hash.to_string(), now);
         self.metrics.set_watched_tx_count(set.len() as i64);
         tracing::debug!(hash, size = set.len(), "watch_transaction: registered");
+        drop(set);
 
-        // Persist to database so the watch survives a restart.
+        // Write through to the shared watch set so the monitor on any
+        // instance, including after a restart, keeps polling this hash.
+        if let Err(e) = self
+            .cache
+            .zset_add_nx(&keys::tx_watch_set(&self.network), hash, Utc::now().timestamp())
+            .await
+        {
+            tracing::warn!(tx_hash = %hash, error = %e, "failed to persist watched tx to Redis; watching in memory only");
+        }
+
+        // Record in the database for history and as a fallback on startup.
         let expires_at = Utc::now()
-            + chrono::Duration::from_std(WATCHED_TX_TTL)
+            + chrono::Duration::from_std(self.watched_tx_ttl)
                 .unwrap_or(chrono::Duration::minutes(30));
         let db = self.db.clone();
+        let network = self.network.clone();
         let hash_owned = hash.to_string();
         tokio::spawn(async move {
-            if let Err(e) = db.watched_tx_upsert(&hash_owned, None, expires_at).await {
+            if let Err(e) = db.watched_tx_upsert(&network, &hash_owned, None, expires_at).await {
                 tracing::warn!(tx_hash = %hash_owned, error = %e, "failed to persist watched tx to database");
             }
         });
@@ -1318,10 +2812,7 @@ impl BlockchainClient {
             if event.ledger > confirmed_tip {
                 continue;
             }
-            let event_key = format!("{}:event:{}", keys::CHAIN_PREFIX, event.id);
-            self.cache
-                .set_json(&event_key, &event, Duration::from_secs(30 * 60))
-                .await?;
+            self.store_event(&event).await?;
         }
 
         let progress = ReplayProgress {
@@ -1338,76 +2829,146 @@ impl BlockchainClient {
         Ok(progress)
     }
 
-    /// Spawn both background workers and return their handles.
-    /// The sync worker is wrapped in a supervised restart loop: if it panics or
-    /// exits unexpectedly, it is restarted and the restart counter incremented.
-    /// Each worker holds a child cancellation token and reports completion
-    /// to the coordinator when it exits.
-    pub fn start_background_tasks(self: Arc<Self>, coordinator: &ShutdownCoordinator) -> Vec<WorkerHandle> {
-        // ── Supervised sync worker ────────────────────────────────────────────
-        let sync_token = coordinator.token();
-        let sync_coord = coordinator.clone();
     /// Test-only constructor that accepts an externally built HTTP client so
     /// tests can configure short timeouts and point at a local mock RPC server.
-    #[cfg(test)]
-    pub(crate) fn new_for_test(
+    #[cfg(any(test, feature = "redis-integration"))]
+    #[doc(hidden)]
+    pub fn new_for_test(
         rpc_url: String,
         cache: RedisCache,
+        db: Database,
         metrics: Metrics,
         http: Client,
         retry_attempts: u32,
     ) -> Self {
+        let rpc = RpcTransport::new(
+            http.clone(),
+            rpc_url.clone(),
+            RetryPolicy {
+                attempts: retry_attempts,
+                base_delay: Duration::from_millis(10),
+                max_delay: Duration::from_millis(100),
+                jitter_factor: 1.0,
+            },
+        );
         Self {
             http,
             rpc_url,
             network: "testnet".to_string(),
-            contract_id: "test-contract".to_string(),
-            retry_attempts,
-            retry_base_delay_ms: 10,
+            contract_id: "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR".to_string(),
+            key_schema: ContractKeySchema {
+                version: "1.0.0".to_string(),
+                market: "market:{id}".to_string(),
+                platform_stats: "platform:stats".to_string(),
+                user_bets: "user_bets:{id}".to_string(),
+                oracle_result: "oracle_result:{id}".to_string(),
+                health_check: "platform:stats".to_string(),
+            },
+            rpc,
+            rpc_budget: Duration::from_secs(5),
             event_poll_interval: Duration::from_millis(50),
             tx_poll_interval: Duration::from_millis(50),
             confirmation_ledger_lag: 1,
             sync_market_ids: vec![],
+            sync_watch_max_markets: 100,
+            sync_watch_refresh_every: 1,
+            sync_watch_resolved_retention: Duration::from_secs(86_400),
+            sync_refresh: RefreshPolicy {
+                hot_window: Duration::from_secs(3600),
+                dormant_every: 12,
+                call_budget: 0,
+            },
+            sync_watch: Arc::new(SyncWatchState::default()),
+            cache_ttls: CacheTtls::default(),
+            featured_limit: 10,
             cache,
+            db,
             metrics,
             monitor: Arc::new(MonitoringState::default()),
+            expected_passphrase: "Test SDF Network ; September 2015".to_string(),
+            watched_tx_ttl: WATCHED_TX_TTL_DEFAULT,
+            watched_tx_max_size: WATCHED_TX_MAX_SIZE,
+            is_production: false,
+            admin_signer: None,
+            oracle_signer: None,
+            contract_call_timeout: Duration::from_secs(2),
+            base_url: "http://localhost:8080".to_string(),
+            owns_market_rows: true,
         }
     }
 
+    /// Serve under a different network name; tests use this to run two
+    /// clients side by side.
+    pub fn with_network(mut self, network: &str) -> Self {
+        self.network = network.to_string();
+        self
+    }
+
+    /// Override the watched-transaction max age; tests use this in place of
+    /// `WATCHED_TX_TTL_SECS`.
+    pub fn with_watched_tx_ttl(mut self, ttl: Duration) -> Self {
+        self.watched_tx_ttl = ttl;
+        self
+    }
+
+    /// Attach a contract admin signer; tests use this in place of
+    /// `ADMIN_SECRET_KEY`.
+    pub fn with_admin_signer(mut self, signer: TxSigner) -> Self {
+        self.admin_signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Attach an oracle keeper signer; tests use this in place of
+    /// `ORACLE_SECRET_KEY`.
+    pub fn with_oracle_signer(mut self, signer: TxSigner) -> Self {
+        self.oracle_signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Spawn both background workers under `supervisor` and return their
+    /// handles. A worker that panics or exits early is restarted with backoff
+    /// and its restart recorded as `blockchain_sync:<network>` or
+    /// `blockchain_tx_monitor:<network>`. Each worker holds a child
+    /// cancellation token and reports completion to the coordinator once it
+    /// has stopped for good.
+    pub fn start_background_tasks(
+        self: Arc<Self>,
+        coordinator: &ShutdownCoordinator,
+        supervisor: &TaskSupervisor,
+    ) -> Vec<WorkerHandle> {
+        // ── Supervised sync worker ────────────────────────────────────────────
+        let sync_token = coordinator.token();
+        let sync_coord = coordinator.clone();
         let sync_client = self.clone();
-        let sync_handle = tokio::spawn(async move {
-            loop {
-                if sync_token.is_cancelled() {
-                    break;
+        let mut sync_started = false;
+        let sync_task = supervisor.spawn(
+            format!("blockchain_sync:{}", self.network),
+            sync_token.clone(),
+            move || {
+                if std::mem::replace(&mut sync_started, true) {
+                    sync_client.metrics.observe_sync_worker_restart();
                 }
-                let token = sync_token.clone();
                 let client = sync_client.clone();
-                let result = tokio::spawn(async move {
-                    client.run_sync_loop(token).await;
-                })
-                .await;
-
-                match result {
-                    Ok(_) => break, // clean shutdown exit
-                    Err(e) => {
-                        sync_client.metrics.observe_sync_worker_restart();
-                        tracing::error!(error = %e, "FATAL: blockchain sync worker panicked — restarting");
-                        if sync_token.is_cancelled() {
-                            break;
-                        }
-                        tokio::time::sleep(Duration::from_secs(1)).await;
-                    }
-                }
-            }
+                let token = sync_token.clone();
+                async move { client.run_sync_loop(token).await }
+            },
+        );
+        let sync_handle = tokio::spawn(async move {
+            let _ = sync_task.await;
             sync_coord.worker_completed();
         });
 
-        // ── Transaction monitor ───────────────────────────────────────────────
+        // ── Supervised transaction monitor ────────────────────────────────────
+        // run_transaction_monitor reports completion itself, and only returns
+        // once cancelled, so a restarted monitor still completes exactly once.
         let mon_token = coordinator.token();
         let mon_coord = coordinator.clone();
+        let mon_name = format!("blockchain_tx_monitor:{}", self.network);
         let mon_client = self;
-        let mon_handle = tokio::spawn(async move {
-            mon_client.run_transaction_monitor(mon_token, mon_coord).await;
+        let mon_handle = supervisor.spawn(mon_name, mon_token.clone(), move || {
+            mon_client
+                .clone()
+                .run_transaction_monitor(mon_token.clone(), mon_coord.clone())
         });
 
         vec![
@@ -1419,7 +2980,195 @@ impl BlockchainClient {
 
 #[cfg(test)]
 mod tests {
-    use super::DataSource;
+    use std::time::Duration;
+
+    use super::{ChainMarketData, DataSource, PendingOverride};
+
+    fn contract_event(value: serde_json::Value) -> super::ContractEvent {
+        super::ContractEvent {
+            id: "0000001-0001".to_string(),
+            ledger: 1,
+            topic: value["topic"].to_string(),
+            tx_hash: Some("abc".to_string()),
+            value,
+        }
+    }
+
+    // `simulateTransaction` result XDR for the contract's metadata getters.
+    const VOID_XDR: &str = "AAAAAQ==";
+    const DESCRIPTION_XDR: &str = "AAAADgAAABtXaWxsIEJUQyBjbG9zZSBhYm92ZSAkMTAwaz8A";
+    const OPTIONS_XDR: &str = "AAAAEAAAAAEAAAACAAAADgAAAANZZXMAAAAADgAAAAJObwAA";
+    const U32_XDR: &str = "AAAAAwAAAAc=";
+
+    #[test]
+    fn market_description_decodes_from_simulation_result() {
+        assert_eq!(
+            super::decode_optional_string(DESCRIPTION_XDR).unwrap().as_deref(),
+            Some("Will BTC close above $100k?")
+        );
+        assert_eq!(super::decode_optional_string(VOID_XDR).unwrap(), None);
+        assert!(super::decode_optional_string(U32_XDR).is_err());
+        assert!(super::decode_optional_string("not xdr").is_err());
+    }
+
+    #[test]
+    fn market_options_decode_from_simulation_result() {
+        assert_eq!(
+            super::decode_optional_strings(OPTIONS_XDR).unwrap(),
+            Some(vec!["Yes".to_string(), "No".to_string()])
+        );
+        assert_eq!(super::decode_optional_strings(VOID_XDR).unwrap(), None);
+        assert!(super::decode_optional_strings(DESCRIPTION_XDR).is_err());
+    }
+
+    #[test]
+    fn indexed_event_decodes_bet_placed() {
+        let event = contract_event(serde_json::json!({
+            "topic": ["bet_place", 7, "GBETTOR"],
+            "value": [1, 2, "170141183460469231731687303715884105727"],
+        }));
+        let indexed = super::IndexedEvent::from_contract_event(&event).unwrap();
+        assert_eq!(indexed.kind, "bet_place");
+        assert_eq!(indexed.market_id, Some(7));
+        assert_eq!(indexed.address.as_deref(), Some("GBETTOR"));
+        assert_eq!(indexed.outcome, Some(2));
+        // i128::MAX must survive without going through f64.
+        assert_eq!(
+            indexed.amount.as_deref(),
+            Some("170141183460469231731687303715884105727")
+        );
+    }
+
+    #[test]
+    fn indexed_event_decodes_refund_claim() {
+        let event = contract_event(serde_json::json!({
+            "topic": ["reward_fx", 7, "GBETTOR"],
+            "value": [1, 5000, "CTOKEN", true],
+        }));
+        let indexed = super::IndexedEvent::from_contract_event(&event).unwrap();
+        assert_eq!(indexed.amount.as_deref(), Some("5000"));
+        assert_eq!(indexed.token.as_deref(), Some("CTOKEN"));
+        assert!(indexed.is_refund);
+    }
+
+    #[test]
+    fn indexed_event_decodes_market_resolved() {
+        let event = contract_event(serde_json::json!({
+            "topic": ["resolv_fx", 7, "GRESOLVER"],
+            "value": [1, 1, "250000"],
+        }));
+        let indexed = super::IndexedEvent::from_contract_event(&event).unwrap();
+        assert_eq!(indexed.kind, super::EVENT_MARKET_RESOLVED);
+        assert_eq!(indexed.outcome, Some(1));
+        assert_eq!(indexed.amount.as_deref(), Some("250000"));
+    }
+
+    #[test]
+    fn indexed_event_decodes_votes_and_resolution_outcomes() {
+        let vote = contract_event(serde_json::json!({
+            "topic": ["vote_cast", 7, "GVOTER"],
+            "value": [1, 0, "1200", ["Token"]],
+        }));
+        let indexed = super::IndexedEvent::from_contract_event(&vote).unwrap();
+        assert_eq!((indexed.outcome, indexed.amount.as_deref()), (Some(0), Some("1200")));
+
+        for (topic, value) in [
+            (serde_json::json!(["dispute_resolved", 7]), serde_json::json!(1)),
+            (serde_json::json!(["disp_res", 7, "GRESOLVER"]), serde_json::json!([1, 1])),
+        ] {
+            let event = contract_event(serde_json::json!({ "topic": topic, "value": value }));
+            let indexed = super::IndexedEvent::from_contract_event(&event).unwrap();
+            assert_eq!((indexed.market_id, indexed.outcome), (Some(7), Some(1)));
+        }
+
+        let upgrade = contract_event(serde_json::json!({ "topic": ["upg_vote", "GGUARDIAN"], "value": [1, true] }));
+        let indexed = super::IndexedEvent::from_contract_event(&upgrade).unwrap();
+        assert_eq!((indexed.market_id, indexed.address.as_deref()), (None, Some("GGUARDIAN")));
+    }
+
+    #[test]
+    fn market_creation_decodes_category_and_older_payloads() {
+        let event = contract_event(serde_json::json!({
+            "topic": ["mkt_creat", 7, "GCREATOR"],
+            "value": [2, "Will it rain?", 2, 1_900_000_000u64, "weather", ["rain"]],
+        }));
+        let creation = super::MarketCreation::from_contract_event(&event).unwrap();
+        assert_eq!(creation.market_id, 7);
+        assert_eq!(creation.creator.as_deref(), Some("GCREATOR"));
+        assert_eq!(creation.title(), "Will it rain?");
+        assert_eq!(creation.deadline, 1_900_000_000);
+        assert_eq!(creation.category_slug(), Some("weather"));
+
+        let v1 = contract_event(serde_json::json!({
+            "topic": ["mkt_creat", 7, "GCREATOR"],
+            "value": [1, "Will it rain?", 2, 1_900_000_000u64],
+        }));
+        assert_eq!(super::MarketCreation::from_contract_event(&v1).unwrap().category, None);
+
+        let unlabelled = contract_event(serde_json::json!({
+            "topic": ["mkt_creat", 7, "GCREATOR"],
+            "value": [2, "Will it rain?", 2, 1_900_000_000u64, null, []],
+        }));
+        assert_eq!(super::MarketCreation::from_contract_event(&unlabelled).unwrap().category, None);
+    }
+
+    #[test]
+    fn market_creation_bounds_title_and_drops_non_slug_categories() {
+        let event = contract_event(serde_json::json!({
+            "topic": ["mkt_creat", 7, "GCREATOR"],
+            "value": [2, "é".repeat(600), 2, 1_900_000_000u64, "US Politics", []],
+        }));
+        let creation = super::MarketCreation::from_contract_event(&event).unwrap();
+        assert_eq!(creation.title().chars().count(), 500);
+        assert_eq!(creation.category.as_deref(), Some("US Politics"));
+        assert_eq!(creation.category_slug(), None);
+
+        let bet = contract_event(serde_json::json!({
+            "topic": ["bet_place", 7, "GBETTOR"],
+            "value": [2, 1, "100"],
+        }));
+        assert!(super::MarketCreation::from_contract_event(&bet).is_none());
+        let bare = contract_event(serde_json::json!({ "topic": ["mkt_creat", 7, "GCREATOR"], "value": [1] }));
+        assert!(super::MarketCreation::from_contract_event(&bare).is_none());
+    }
+
+    #[test]
+    fn indexed_event_invalidation_tags_by_topic() {
+        use crate::cache::InvalidationTag;
+        let tag = |topic: serde_json::Value| {
+            let event = contract_event(serde_json::json!({ "topic": topic, "value": [1] }));
+            super::IndexedEvent::from_contract_event(&event)
+                .unwrap()
+                .invalidation_tag("testnet", 10)
+        };
+
+        assert!(matches!(
+            tag(serde_json::json!(["resolv_fx", 7, "GRESOLVER"])),
+            Some(InvalidationTag::MarketResolved { market_id: 7, .. })
+        ));
+        assert!(matches!(
+            tag(serde_json::json!(["mkt_cncl", 7, "GADMIN"])),
+            Some(InvalidationTag::MarketCancelled { market_id: 7, .. })
+        ));
+        assert!(matches!(
+            tag(serde_json::json!(["mkt_creat", 7, "GCREATOR"])),
+            Some(InvalidationTag::MarketCreated { .. })
+        ));
+        for topic in ["bet_place", "reward_fx"] {
+            match tag(serde_json::json!([topic, 7, "GBETTOR"])) {
+                Some(InvalidationTag::UserBetsChanged { address, .. }) => assert_eq!(address, "GBETTOR"),
+                other => panic!("{topic}: {other:?}"),
+            }
+        }
+        assert!(tag(serde_json::json!(["bet_place", 7])).is_none(), "no address, nothing to invalidate");
+        assert!(tag(serde_json::json!(["vote_cast", 7, "GVOTER"])).is_none());
+    }
+
+    #[test]
+    fn indexed_event_rejects_missing_topic() {
+        let event = contract_event(serde_json::json!({ "value": [1] }));
+        assert!(super::IndexedEvent::from_contract_event(&event).is_none());
+    }
 
     /// DataSource::Live and StaleFallback must be distinguishable by callers.
     #[test]
@@ -1447,58 +3196,71 @@ mod tests {
         }
     }
 
+    /// A pending override replaces what a lagging node reports and counts as
+    /// confirmed only once status and outcome both match.
+    #[test]
+    fn pending_override_applies_until_confirmed() {
+        let pending = PendingOverride {
+            network: "testnet".to_string(),
+            status: "resolved".to_string(),
+            resolved_outcome: 1,
+            tx_hash: "feed01".to_string(),
+        };
+        let mut data = ChainMarketData {
+            market_id: 7,
+            title: None,
+            options: None,
+            status: Some("active".to_string()),
+            onchain_volume: "0".to_string(),
+            resolved_outcome: None,
+            ledger: 900,
+            source: DataSource::Live,
+        };
+        assert!(!pending.confirmed_by(&data));
+
+        data.status = Some("resolved".to_string());
+        data.resolved_outcome = Some(0);
+        assert!(!pending.confirmed_by(&data), "a different outcome is not a confirmation");
+
+        pending.apply(&mut data);
+        assert_eq!(data.status.as_deref(), Some("resolved"));
+        assert_eq!(data.resolved_outcome, Some(1));
+        assert_eq!(data.ledger, 900);
+        assert!(pending.confirmed_by(&data));
+    }
+
     // ── #935: full-jitter backoff ─────────────────────────────────────────────
 
+    fn retry_policy(jitter_factor: f64) -> crate::rpc::RetryPolicy {
+        crate::rpc::RetryPolicy {
+            attempts: 5,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(60),
+            jitter_factor,
+        }
+    }
+
     /// Simulates 100 retry delay calculations with full jitter and verifies
     /// no two delays are identical (extremely unlikely with random values).
     #[test]
     fn jitter_backoff_produces_unique_delays() {
-        use rand::Rng as _;
-        let base_ms: u64 = 200;
-        let jitter_factor: f64 = 1.0;
-        let attempt: u32 = 3;
-
-        let cap_ms = (base_ms * (1u64 << (attempt - 1).min(10))).min(60_000);
-        let jitter_range = (cap_ms as f64 * jitter_factor) as u64;
-        let deterministic = cap_ms.saturating_sub(jitter_range);
-
-        let mut rng = rand::thread_rng();
-        let delays: Vec<u64> = (0..100)
-            .map(|_| deterministic + rng.gen_range(0..=jitter_range))
-            .collect();
+        let policy = retry_policy(1.0);
+        let delays: Vec<Duration> = (0..100).map(|_| policy.backoff(3)).collect();
 
         // With full jitter over range [0, 800ms] the probability of any two
         // being equal is negligible; assert at least 90% unique values.
-        let unique: std::collections::HashSet<u64> = delays.iter().cloned().collect();
+        let unique: std::collections::HashSet<Duration> = delays.iter().cloned().collect();
         assert!(unique.len() >= 90, "expected >= 90 unique delays, got {}", unique.len());
+        assert!(delays.iter().all(|&d| d <= Duration::from_millis(800)));
     }
 
     /// With jitter_factor=0.0 (no jitter) all delays are identical.
     #[test]
     fn zero_jitter_produces_deterministic_delays() {
-        let base_ms: u64 = 200;
-        let jitter_factor: f64 = 0.0;
-        let attempt: u32 = 3;
-
-        let cap_ms = (base_ms * (1u64 << (attempt - 1).min(10))).min(60_000);
-        let jitter_range = (cap_ms as f64 * jitter_factor) as u64;
-        let deterministic = cap_ms.saturating_sub(jitter_range);
-
-        let delays: Vec<u64> = (0..100)
-            .map(|_| {
-                // With jitter_factor=0, jitter_range=0, all delays are deterministic.
-                if jitter_factor > 0.0 {
-                    let mut rng = rand::thread_rng();
-                    deterministic + rng.gen_range(0..=jitter_range)
-                } else {
-                    cap_ms
-                }
-            })
-            .collect();
-
+        let policy = retry_policy(0.0);
         assert!(
-            delays.iter().all(|&d| d == cap_ms),
-            "all delays must equal cap_ms when jitter_factor=0"
+            (0..100).all(|_| policy.backoff(3) == Duration::from_millis(800)),
+            "all delays must equal the ceiling when jitter_factor=0"
         );
     }
 
@@ -1524,7 +3286,6 @@ mod tests {
     async fn watch_transaction_dedup_returns_already_watched() {
         use super::{MonitoringState, WatchTxError, WATCHED_TX_MAX_SIZE, WATCHED_TX_DEFAULT_TTL};
         use std::sync::Arc;
-        use std::time::Duration;
 
         let state = Arc::new(MonitoringState::default());
         let ttl = WATCHED_TX_DEFAULT_TTL;
@@ -1645,4 +3406,42 @@ mod tests {
         use super::WatchTxError;
         assert_ne!(WatchTxError::AlreadyWatched, WatchTxError::CapReached);
     }
+
+    #[test]
+    fn sync_plan_steady_state_does_not_rewind() {
+        use super::SyncPlan;
+        let plan = SyncPlan::decide(499, 501, 500, 1);
+        assert_eq!(plan, SyncPlan { cursor: 499, confirmed_tip: 500, rewind_to: None });
+    }
+
+    #[test]
+    fn sync_plan_first_run_starts_from_zero() {
+        use super::SyncPlan;
+        let plan = SyncPlan::decide(0, 100, 0, 1);
+        assert_eq!(plan, SyncPlan { cursor: 0, confirmed_tip: 99, rewind_to: None });
+    }
+
+    #[test]
+    fn sync_plan_head_regression_within_lag_is_not_a_reorg() {
+        use super::SyncPlan;
+        // 499 + 1 == 500: within the lag, so the cursor is only held.
+        let plan = SyncPlan::decide(498, 499, 500, 1);
+        assert_eq!(plan.rewind_to, None);
+    }
+
+    #[test]
+    fn sync_plan_reorg_rewinds_below_cursor() {
+        use super::SyncPlan;
+        let plan = SyncPlan::decide(499, 495, 500, 1);
+        assert_eq!(plan, SyncPlan { cursor: 494, confirmed_tip: 494, rewind_to: Some(494) });
+    }
+
+    #[test]
+    fn sync_plan_reorg_behind_cursor_keeps_cursor() {
+        use super::SyncPlan;
+        // Head went back but is still above the cursor: nothing indexed is
+        // above the new tip, so only the (no-op) rewind to the cursor remains.
+        let plan = SyncPlan::decide(400, 495, 500, 1);
+        assert_eq!(plan, SyncPlan { cursor: 400, confirmed_tip: 494, rewind_to: Some(400) });
+    }
 }
@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod blockchain_batch_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use redis::AsyncCommands;
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use tower::ServiceExt;
+
+    use crate::handlers::{blockchain_batch, BLOCKCHAIN_BATCH_MAX};
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/api/v1/blockchain/batch", post(blockchain_batch))
+            .with_state(state)
+    }
+
+    async fn post_batch(router: Router, ip: &str, queries: Value) -> (StatusCode, Value) {
+        let body = serde_json::to_vec(&json!({ "queries": queries })).unwrap();
+        let resp = router
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/v1/blockchain/batch")
+                    .header("content-type", "application/json")
+                    .header("x-forwarded-for", ip)
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Answers `getTransaction` by hash: `good-*` succeeds, anything else is
+    /// a JSON-RPC error.
+    async fn start_mock_rpc() -> String {
+        let app = Router::new().route(
+            "/",
+            post(|Json(body): Json<Value>| async move {
+                let hash = body["params"]["hash"].as_str().unwrap_or_default();
+                let resp = if hash.starts_with("good") {
+                    json!({ "result": { "status": "SUCCESS", "ledger": 77 } })
+                } else {
+                    json!({ "error": { "code": -32600, "message": "node unavailable" } })
+                };
+                Json(resp)
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// A client IP unique to this run, so window counts start at zero.
+    fn test_ip() -> String {
+        let n = uuid::Uuid::new_v4().as_u128();
+        format!("10.{}.{}.{}", (n >> 16) as u8, (n >> 8) as u8, n as u8)
+    }
+
+    async fn global_window_count(state: &crate::AppState, ip: &str) -> u64 {
+        let mut conn = state.cache.redis_pool().get().await.unwrap();
+        conn.zcard(format!("global:{ip}:60")).await.unwrap()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Tests
+    // ---------------------------------------------------------------------------
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_mixed_items_fail_independently() {
+        let state = build_state(Some(start_mock_rpc().await)).await;
+        let good = format!("good-{}", uuid::Uuid::new_v4());
+
+        let (status, body) = post_batch(
+            app(state),
+            &test_ip(),
+            json!([
+                { "type": "tx_status", "hash": good },
+                { "type": "tx_status", "hash": "bad-hash" },
+                { "type": "tx_status", "hash": "  " },
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let results = body["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3, "one result per query, in order");
+
+        assert_eq!(results[0]["status"], 200);
+        assert_eq!(results[0]["data"]["status"], "SUCCESS");
+        assert_eq!(results[0]["data"]["hash"], good);
+        assert!(results[0].get("error").is_none());
+
+        assert_eq!(results[1]["status"], 502);
+        assert_eq!(results[1]["error"]["code"], "UPSTREAM_UNAVAILABLE");
+        assert!(results[1].get("data").is_none());
+
+        assert_eq!(results[2]["status"], 400);
+        assert_eq!(results[2]["error"]["code"], "BAD_REQUEST");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_batch_over_cap_is_rejected() {
+        let state = build_state(None).await;
+        let ip = test_ip();
+        let queries: Vec<Value> = (0..=BLOCKCHAIN_BATCH_MAX as i64)
+            .map(|id| json!({ "type": "market", "id": id }))
+            .collect();
+
+        let (status, body) = post_batch(app(state.clone()), &ip, json!(queries)).await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "BATCH_TOO_LARGE");
+        assert_eq!(
+            global_window_count(&state, &ip).await,
+            0,
+            "a rejected batch is not charged"
+        );
+
+        let (status, _) = post_batch(app(state), &ip, json!([])).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_batch_counts_each_query_against_rate_limit() {
+        let state = build_state(Some(start_mock_rpc().await)).await;
+        let ip = test_ip();
+        let queries: Vec<Value> = (0..5)
+            .map(|i| json!({ "type": "tx_status", "hash": format!("good-{i}-{ip}") }))
+            .collect();
+
+        let (status, _) = post_batch(app(state.clone()), &ip, json!(queries)).await;
+        assert_eq!(status, StatusCode::OK);
+        // The global middleware charges the request itself; the handler
+        // charges the remaining N - 1.
+        assert_eq!(global_window_count(&state, &ip).await, 4);
+
+        // 4 + 94 = 98 used of 100: a single query fits, five do not.
+        let redis = state.cache.redis_pool();
+        let config = crate::rate_limit::global_rate_limit_config();
+        crate::rate_limit::check_rate_limit_weighted(&redis, &config, &ip, 94)
+            .await
+            .unwrap();
+
+        let (status, body) = post_batch(app(state.clone()), &ip, json!(queries)).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "RATE_LIMITED");
+
+        let (status, _) = post_batch(app(state), &ip, json!([queries[0]])).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_market_items_share_the_per_market_limit() {
+        let state = build_state(None).await;
+        let market_id = (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 1_000_000;
+        let limit = crate::rate_limit::ResourceRateLimitState::from_app_state(&state);
+        crate::rate_limit::check_rate_limit_weighted(
+            &limit.redis,
+            &limit.config,
+            &format!("market:{market_id}"),
+            limit.config.max_requests,
+        )
+        .await
+        .unwrap();
+
+        let (status, body) = post_batch(
+            app(state),
+            &test_ip(),
+            json!([
+                { "type": "market", "id": market_id },
+                { "type": "oracle", "id": market_id },
+            ]),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        for item in body["results"].as_array().unwrap() {
+            assert_eq!(item["status"], 429);
+            assert_eq!(item["error"]["code"], "RATE_LIMITED");
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    /// With `rpc_url`, points the client at a mock node.
+    async fn build_state(rpc_url: Option<String>) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.trust_proxy = true;
+        if let Some(rpc_url) = rpc_url {
+            config.blockchain_rpc_url = rpc_url;
+            config.contract_call_timeout = Duration::from_secs(1);
+            config.retry_attempts = 1;
+        }
+        build_test_state_with(config, &[]).await
+    }
+}
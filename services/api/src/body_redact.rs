@@ -1,5 +1,6 @@
 //! body_redact.rs — Body capture, truncation, and sensitive field redaction
-//! for failed request/response logging.
+//! for failed request/response logging, plus allowlist summaries for the
+//! admin audit trail.
 
 use serde_json::{Map, Value};
 use tracing;
@@ -7,6 +8,9 @@ use tracing;
 /// Maximum bytes captured from request/response body before truncation.
 pub const MAX_BODY_BYTES: usize = 4 * 1024; // 4 KB
 
+/// Placeholder written in place of any value that is not safe to store.
+pub const REDACTED: &str = "[REDACTED]";
+
 const SENSITIVE_FIELDS: &[&str] = &[
     "password",
     "password_confirmation",
@@ -58,7 +62,7 @@ fn redact_map(mut map: Map<String, Value>) -> Map<String, Value> {
     for key in map.keys().cloned().collect::<Vec<_>>() {
         let lower = key.to_lowercase();
         if SENSITIVE_FIELDS.iter().any(|s| lower.contains(s)) {
-            map.insert(key, Value::String("[REDACTED]".to_owned()));
+            map.insert(key, Value::String(REDACTED.to_owned()));
         } else if let Some(Value::Object(nested)) = map.get(&key).cloned() {
             map.insert(key, Value::Object(redact_map(nested)));
         }
@@ -66,6 +70,52 @@ fn redact_map(mut map: Map<String, Value>) -> Map<String, Value> {
     map
 }
 
+/// Summarise a request for the audit trail: `{"query": {...}, "body": ...}`.
+///
+/// Unlike [`redact_sensitive`], this is an allowlist — only top-level keys in
+/// `allowed` keep their value, every other key is kept with its value
+/// replaced by `"[REDACTED]"`, so a new field is private until someone
+/// decides otherwise. Bodies that are not a JSON object are reduced to their
+/// size. Empty parts are omitted.
+pub fn summarize_request(query: Option<&str>, body: &[u8], allowed: &[&str]) -> Value {
+    let mut summary = Map::new();
+
+    if let Some(query) = query.filter(|q| !q.is_empty()) {
+        let params: Map<String, Value> = url::form_urlencoded::parse(query.as_bytes())
+            .map(|(k, v)| {
+                let value = if allowed.contains(&k.as_ref()) {
+                    Value::String(v.into_owned())
+                } else {
+                    Value::String(REDACTED.to_owned())
+                };
+                (k.into_owned(), value)
+            })
+            .collect();
+        summary.insert("query".to_owned(), Value::Object(params));
+    }
+
+    if !body.is_empty() {
+        let body = match serde_json::from_slice::<Value>(body) {
+            Ok(Value::Object(map)) => Value::Object(
+                map.into_iter()
+                    .map(|(k, v)| {
+                        let v = if allowed.contains(&k.as_str()) {
+                            v
+                        } else {
+                            Value::String(REDACTED.to_owned())
+                        };
+                        (k, v)
+                    })
+                    .collect(),
+            ),
+            _ => serde_json::json!({ "bytes": body.len() }),
+        };
+        summary.insert("body".to_owned(), body);
+    }
+
+    Value::Object(summary)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -114,4 +164,31 @@ mod tests {
         assert_eq!(v["user"]["password"], "[REDACTED]");
         assert_eq!(v["user"]["name"], "Alice");
     }
+
+    #[test]
+    fn summary_keeps_only_allowlisted_body_fields() {
+        let body = br#"{"status":"resolved","recipient":"a@example.com","meta":{"status":"x"}}"#;
+        let v = summarize_request(None, body, &["status"]);
+        assert_eq!(v["body"]["status"], "resolved");
+        assert_eq!(v["body"]["recipient"], REDACTED);
+        // Allowlisting is top-level only; nested objects are never copied.
+        assert_eq!(v["body"]["meta"], REDACTED);
+        assert!(v.get("query").is_none());
+    }
+
+    #[test]
+    fn summary_redacts_query_params_outside_allowlist() {
+        let v = summarize_request(Some("limit=10&email=a%40example.com"), b"", &["limit"]);
+        assert_eq!(v["query"]["limit"], "10");
+        assert_eq!(v["query"]["email"], REDACTED);
+        assert!(v.get("body").is_none());
+    }
+
+    #[test]
+    fn summary_reduces_non_object_body_to_size() {
+        let v = summarize_request(None, b"not json", &["status"]);
+        assert_eq!(v["body"], serde_json::json!({ "bytes": 8 }));
+        let v = summarize_request(None, br#"["a","b"]"#, &["status"]);
+        assert_eq!(v["body"]["bytes"], 9);
+    }
 }
@@ -0,0 +1,151 @@
+//! Admin inspection and invalidation of cache entries.
+//!
+//! Backs `GET /api/v1/admin/cache/keys`, `GET /api/v1/admin/cache/entry` and
+//! `DELETE /api/v1/admin/cache`, so "clear the cache for market 57" no longer
+//! means `redis-cli` on production. Every key and pattern must sit under one
+//! of the configured prefixes (`CACHE_ADMIN_ALLOWED_PREFIXES`, default
+//! `api:v1:*,chain:v1:*`); rate-limit counters, idempotency records and the
+//! like are out of reach. Listing is SCAN-backed and capped, and a pattern
+//! delete also requires `confirm=true`.
+
+use serde::{Deserialize, Serialize};
+
+/// Default and maximum number of keys `GET /admin/cache/keys` returns.
+pub const DEFAULT_KEY_LIMIT: usize = 100;
+pub const MAX_KEY_LIMIT: usize = 1000;
+
+/// Prefixes allowed when `CACHE_ADMIN_ALLOWED_PREFIXES` is unset.
+pub const DEFAULT_ALLOWED_PREFIXES: [&str; 2] = ["api:v1:", "chain:v1:"];
+
+/// Why a key or pattern was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    Empty,
+    /// Does not start with any allowed prefix, so it could reach keys the
+    /// admin API must not touch.
+    NotAllowed,
+}
+
+impl std::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("pattern must not be empty"),
+            Self::NotAllowed => f.write_str("pattern is outside the allowed cache prefixes"),
+        }
+    }
+}
+
+/// Check that `pattern` (a Redis glob, or a plain key) starts with one of
+/// `allowed`. Allowed prefixes are literal, so a pattern that passes can only
+/// match keys inside that prefix: `api:v1:market_detail:*` is fine, `api:*`
+/// and `*` are not.
+pub fn check_pattern(pattern: &str, allowed: &[String]) -> Result<(), PatternError> {
+    if pattern.is_empty() {
+        return Err(PatternError::Empty);
+    }
+    if allowed
+        .iter()
+        .any(|prefix| pattern.starts_with(prefix.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(PatternError::NotAllowed)
+    }
+}
+
+/// Parse `CACHE_ADMIN_ALLOWED_PREFIXES`: comma-separated, each entry written
+/// as a prefix glob (`api:v1:*`) or a bare prefix (`api:v1:`). Entries that
+/// are empty once the trailing `*` is dropped, or that still contain glob
+/// characters, are skipped rather than widening the allowlist.
+pub fn parse_allowed_prefixes(raw: &str) -> Vec<String> {
+    let mut prefixes: Vec<String> = Vec::new();
+    for entry in raw.split(',') {
+        let prefix = entry.trim().trim_end_matches('*');
+        if prefix.is_empty() || prefix.contains(['*', '?', '[', ']', '\\']) {
+            continue;
+        }
+        if !prefixes.iter().any(|p| p == prefix) {
+            prefixes.push(prefix.to_string());
+        }
+    }
+    prefixes
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheKeyList {
+    pub pattern: String,
+    /// Matching keys in SCAN order, at most `limit` of them.
+    pub keys: Vec<String>,
+    /// More keys matched than were returned.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheEntry {
+    pub key: String,
+    /// Seconds until the entry expires; `null` when it has no expiry.
+    pub ttl_secs: Option<i64>,
+    /// The stored value, parsed as JSON; a value that is not JSON is returned
+    /// as a string.
+    #[schema(value_type = Object)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheDeleteResult {
+    pub pattern: String,
+    pub deleted: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed() -> Vec<String> {
+        DEFAULT_ALLOWED_PREFIXES
+            .iter()
+            .map(|p| p.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn patterns_inside_an_allowed_prefix_pass() {
+        assert_eq!(check_pattern("api:v1:*", &allowed()), Ok(()));
+        assert_eq!(check_pattern("api:v1:market_detail:57", &allowed()), Ok(()));
+        assert_eq!(
+            check_pattern("chain:v1:testnet:market:*", &allowed()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn broader_or_foreign_patterns_are_refused() {
+        for pattern in [
+            "*",
+            "api:*",
+            "api:v1*",
+            "a*:v1:*",
+            "dbq:v1:*",
+            "abuse:block:*",
+        ] {
+            assert_eq!(
+                check_pattern(pattern, &allowed()),
+                Err(PatternError::NotAllowed),
+                "{pattern}"
+            );
+        }
+        assert_eq!(check_pattern("", &allowed()), Err(PatternError::Empty));
+        assert_eq!(
+            check_pattern("api:v1:*", &[]),
+            Err(PatternError::NotAllowed)
+        );
+    }
+
+    #[test]
+    fn allowed_prefixes_drop_globs_and_duplicates() {
+        assert_eq!(
+            parse_allowed_prefixes(" api:v1:* , chain:v1:,api:v1:, *, , a?:x:*"),
+            vec!["api:v1:".to_string(), "chain:v1:".to_string()]
+        );
+    }
+}
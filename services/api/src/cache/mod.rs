@@ -12,6 +12,23 @@ use deadpool_redis::{Config as PoolConfig, Pool};
 use redis::AsyncCommands;
 use serde::{de::DeserializeOwned, Serialize};
 
+pub mod admin;
+pub mod warming;
+
+tokio::task_local! {
+    static REFRESHING: ();
+}
+
+/// Run `fut` with every `get_or_set_json*` call inside it skipping the cache
+/// read and rewriting its entry from the fetcher. Cache warming uses this to
+/// refresh entries through the same methods the request path calls.
+pub async fn refreshing<F: Future>(fut: F) -> F::Output {
+    REFRESHING.scope((), fut).await
+}
+
+fn is_refreshing() -> bool {
+    REFRESHING.try_with(|_| ()).is_ok()
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CircuitState {
@@ -77,7 +94,7 @@ impl CircuitBreaker {
     }
 
     fn record_failure(&self, metrics: &Option<crate::metrics::Metrics>) {
-        let prev_state = self.state();
+        let _prev_state = self.state();
         let prev = self.failure_count.fetch_add(1, Ordering::AcqRel);
         if prev + 1 >= self.threshold && self.opened_at_ms.load(Ordering::Acquire) == 0 {
             let now_ms = std::time::SystemTime::now()
@@ -136,6 +153,9 @@ pub struct RedisCacheConfig {
     pub cb_threshold: u32,
     /// Circuit breaker: how long to stay open before half-open probe.
     pub cb_reset_timeout: Duration,
+    /// Longest a single command (or pipeline) may take once a connection is
+    /// acquired. A timed-out command is not retried.
+    pub command_timeout: Duration,
 }
 
 impl RedisCacheConfig {
@@ -175,6 +195,13 @@ impl RedisCacheConfig {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(30u64),
         );
+        let command_timeout = Duration::from_millis(
+            std::env::var("REDIS_COMMAND_TIMEOUT_MS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .filter(|&ms: &u64| ms > 0)
+                .unwrap_or(500u64),
+        );
         Self {
             pool_min_idle,
             pool_max_size,
@@ -183,6 +210,7 @@ impl RedisCacheConfig {
             retry_base_delay,
             cb_threshold,
             cb_reset_timeout,
+            command_timeout,
         }
     }
 }
@@ -375,6 +403,7 @@ impl RedisCache {
             .context("failed to build Redis pool config")?
             .max_size(cfg.pool_max_size)
             .wait_timeout(Some(cfg.acquire_timeout))
+            .runtime(deadpool_redis::Runtime::Tokio1)
             .build()
             .context("failed to build Redis pool")?;
 
@@ -411,6 +440,11 @@ impl RedisCache {
 
     /// Execute `op` with retry + circuit breaker.  On circuit open, returns
     /// `Err` immediately so callers can degrade gracefully.
+    ///
+    /// Each attempt is bounded by `command_timeout`. A timeout counts as a
+    /// circuit-breaker failure and is returned without retrying: a wedged
+    /// Redis would only time out again, and callers fall back to their
+    /// fetcher sooner.
     async fn exec<T, F, Fut>(&self, op: F) -> anyhow::Result<T>
     where
         F: Fn(deadpool_redis::Connection) -> Fut,
@@ -431,15 +465,22 @@ impl RedisCache {
                     last_err = anyhow::anyhow!("pool acquire: {e}");
                     self.cb.record_failure(&self.metrics);
                 }
-                Ok(conn) => match op(conn).await {
-                    Ok(v) => {
+                Ok(conn) => match tokio::time::timeout(self.cfg.command_timeout, op(conn)).await {
+                    Ok(Ok(v)) => {
                         self.cb.record_success(&self.metrics);
                         return Ok(v);
                     }
-                    Err(e) => {
+                    Ok(Err(e)) => {
                         last_err = e;
                         self.cb.record_failure(&self.metrics);
                     }
+                    Err(_) => {
+                        self.cb.record_failure(&self.metrics);
+                        if let Some(m) = &self.metrics {
+                            m.observe_redis_command_timeout();
+                        }
+                        anyhow::bail!("Redis command timed out after {:?}", self.cfg.command_timeout);
+                    }
                 },
             }
         }
@@ -484,6 +525,108 @@ impl RedisCache {
         .await
     }
 
+    /// Read many JSON values in one `MGET`, in `keys` order. A slot is `None`
+    /// when its key is absent or holds something that does not decode as `T`,
+    /// so callers treat both as a miss.
+    pub async fn mget_json<T>(&self, keys: &[String]) -> anyhow::Result<Vec<Option<T>>>
+    where
+        T: DeserializeOwned,
+    {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        let keys = keys.to_vec();
+        let raw: Vec<Option<String>> = self
+            .exec(|mut conn| {
+                let keys = keys.clone();
+                async move { Ok(redis::cmd("MGET").arg(&keys).query_async(&mut conn).await?) }
+            })
+            .await?;
+        Ok(raw
+            .into_iter()
+            .zip(&keys)
+            .map(|(raw, key)| {
+                serde_json::from_str(&raw?)
+                    .map_err(|e| tracing::warn!(key, error = %e, "undecodable cache entry, treating as a miss"))
+                    .ok()
+            })
+            .collect())
+    }
+
+    /// Write many JSON values with one TTL in a single pipelined round trip.
+    pub async fn mset_json<T>(&self, entries: &[(String, T)], ttl: Duration) -> anyhow::Result<()>
+    where
+        T: Serialize,
+    {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let raw = entries
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), serde_json::to_string(value)?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let secs = ttl.as_secs().max(1);
+        self.exec(|mut conn| {
+            let raw = raw.clone();
+            async move {
+                let mut pipe = redis::pipe();
+                for (key, value) in &raw {
+                    pipe.set_ex(key, value, secs).ignore();
+                }
+                let _: () = pipe.query_async(&mut conn).await?;
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Store `value` only if `key` does not exist yet (`SET NX EX`). Returns
+    /// whether this call wrote it.
+    pub async fn set_json_nx<T>(&self, key: &str, value: &T, ttl: Duration) -> anyhow::Result<bool>
+    where
+        T: Serialize,
+    {
+        let key = key.to_owned();
+        let raw = serde_json::to_string(value)?;
+        let secs = ttl.as_secs().max(1);
+        self.exec(|mut conn| {
+            let key = key.clone();
+            let raw = raw.clone();
+            async move {
+                let set: Option<String> = redis::cmd("SET")
+                    .arg(&key)
+                    .arg(raw)
+                    .arg("NX")
+                    .arg("EX")
+                    .arg(secs)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(set.is_some())
+            }
+        })
+        .await
+    }
+
+    /// Read and delete `key` in one `GETDEL`, so at most one caller ever
+    /// sees the value. Used for single-use tokens.
+    pub async fn take_json<T>(&self, key: &str) -> anyhow::Result<Option<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let key = key.to_owned();
+        self.exec(|mut conn| {
+            let key = key.clone();
+            async move {
+                let val: Option<String> = redis::cmd("GETDEL").arg(&key).query_async(&mut conn).await?;
+                match val {
+                    Some(raw) => Ok(Some(serde_json::from_str(&raw)?)),
+                    None => Ok(None),
+                }
+            }
+        })
+        .await
+    }
+
     pub async fn del(&self, key: &str) -> anyhow::Result<()> {
         let key = key.to_owned();
         self.exec(|mut conn| {
@@ -574,6 +717,72 @@ impl RedisCache {
         Ok(total_deleted)
     }
 
+    /// Up to `limit` keys matching `pattern`, found with cursor-based SCAN,
+    /// and whether more matched. Like [`Self::del_by_pattern`], each SCAN
+    /// round uses its own pool connection.
+    pub async fn scan_keys(
+        &self,
+        pattern: &str,
+        limit: usize,
+    ) -> anyhow::Result<(Vec<String>, bool)> {
+        let mut cursor: u64 = 0;
+        let mut found: Vec<String> = Vec::new();
+        let pattern = pattern.to_owned();
+
+        loop {
+            let (next_cursor, keys) = self
+                .exec(|mut conn| {
+                    let pattern_clone = pattern.clone();
+                    async move {
+                        let page: (u64, Vec<String>) = redis::cmd("SCAN")
+                            .arg(cursor)
+                            .arg("MATCH")
+                            .arg(&pattern_clone)
+                            .arg("COUNT")
+                            .arg(100u64)
+                            .query_async(&mut conn)
+                            .await?;
+                        Ok(page)
+                    }
+                })
+                .await?;
+
+            for key in keys {
+                if found.len() == limit {
+                    return Ok((found, true));
+                }
+                if !found.contains(&key) {
+                    found.push(key);
+                }
+            }
+            cursor = next_cursor;
+            if cursor == 0 {
+                return Ok((found, false));
+            }
+        }
+    }
+
+    /// The raw string stored at `key` and its remaining TTL in seconds
+    /// (`None` when the key has no expiry), or `None` when the key is absent.
+    pub async fn get_raw_with_ttl(
+        &self,
+        key: &str,
+    ) -> anyhow::Result<Option<(String, Option<i64>)>> {
+        let key = key.to_owned();
+        self.exec(|mut conn| {
+            let key = key.clone();
+            async move {
+                let (raw, ttl): (Option<String>, i64) = redis::pipe()
+                    .get(&key)
+                    .ttl(&key)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(raw.map(|raw| (raw, (ttl >= 0).then_some(ttl))))
+            }
+        })
+        .await
+    }
+
     /// Fetch-or-set with stampede protection.
     ///
     /// Strategy (applied in order when enabled via `StampedeConfig`):
@@ -605,32 +814,114 @@ impl RedisCache {
             return Ok((value, false));
         }
 
-        if let Ok(Some(cached)) = self.get_json(key).await {
-            return Ok((cached, true));
+        if !is_refreshing() {
+            if let Ok(Some(cached)) = self.get_json(key).await {
+                return Ok((cached, true));
+            }
         }
 
-        // Cache miss — call fetcher and store the result.
+        // Cache miss (or forced refresh) — call fetcher and store the result.
         self.recompute_and_store(key, ttl, fetcher).await
     }
 
-    async fn set_entry<T>(&self, key: &str, entry: &CachedEntry<T>, ttl: Duration) -> anyhow::Result<()>
+    /// [`Self::get_or_set_json`] for many keys at once: one `MGET` for the
+    /// lookups and one pipelined write for the misses. `fetcher(i)` fetches
+    /// the value behind `keys[i]`; misses are fetched concurrently and fail
+    /// independently, and only successes are written back. Returns one
+    /// `(value, cache_hit)` result per key, in `keys` order.
+    pub async fn get_or_set_many_json<T, F, Fut>(
+        &self,
+        keys: &[String],
+        ttl: Duration,
+        fetcher: F,
+    ) -> Vec<anyhow::Result<(T, bool)>>
     where
-        T: Serialize,
+        T: Serialize + DeserializeOwned + Clone,
+        F: Fn(usize) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
     {
-        let key = key.to_owned();
-        let raw = serde_json::to_string(entry)?;
-        // Store with a small grace period beyond the logical TTL so XFetch
-        // can still serve the stale value while a refresh is in flight.
-        let redis_ttl = ttl + Duration::from_secs(30);
-        self.exec(|mut conn| {
-            let key = key.clone();
-            let raw = raw.clone();
-            async move {
-                let _: () = conn.set_ex(&key, raw, redis_ttl.as_secs()).await?;
-                Ok(())
+        let available = self.cb.allow(&self.metrics);
+        let cached: Vec<Option<T>> = if !available {
+            tracing::warn!(keys = keys.len(), "Redis unavailable, bypassing cache");
+            keys.iter().map(|_| None).collect()
+        } else if is_refreshing() {
+            keys.iter().map(|_| None).collect()
+        } else {
+            self.mget_json(keys).await.unwrap_or_else(|e| {
+                tracing::warn!(keys = keys.len(), error = %e, "cache multi-get failed");
+                keys.iter().map(|_| None).collect()
+            })
+        };
+
+        let misses: Vec<usize> = (0..keys.len()).filter(|&i| cached[i].is_none()).collect();
+        let fetched = futures::future::join_all(misses.iter().map(|&i| fetcher(i))).await;
+
+        let mut results: Vec<Option<anyhow::Result<(T, bool)>>> =
+            cached.into_iter().map(|value| value.map(|v| Ok((v, true)))).collect();
+        let mut writes = Vec::new();
+        for (i, result) in misses.into_iter().zip(fetched) {
+            if let Ok(value) = &result {
+                writes.push((keys[i].clone(), value.clone()));
+            }
+            results[i] = Some(result.map(|value| (value, false)));
+        }
+        // Best-effort write — don't fail the lookups if the write fails.
+        if available {
+            if let Err(e) = self.mset_json(&writes, ttl).await {
+                tracing::warn!(keys = writes.len(), error = %e, "cache write failed");
             }
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect("every key is either a hit or fetched"))
+            .collect()
+    }
+
+    /// [`Self::get_or_set_json`] for responses served with an `ETag`.
+    ///
+    /// The ETag is hashed from the serialized value once, when it is fetched,
+    /// and stored next to it, so a cache hit returns it without re-serializing.
+    /// Because the hash covers the content, a refetch after invalidation yields
+    /// a new ETag only if the data actually changed.
+    pub async fn get_or_set_json_tagged<T, F, Fut>(
+        &self,
+        key: &str,
+        ttl: Duration,
+        fetcher: F,
+    ) -> anyhow::Result<TaggedLookup<T>>
+    where
+        T: Serialize + DeserializeOwned + Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if self.cb.allow(&self.metrics) {
+            if !is_refreshing() {
+                if let Ok(Some(cached)) = self.get_json::<TaggedEntry<T>>(key).await {
+                    return Ok(TaggedLookup {
+                        value: cached.value,
+                        etag: cached.etag,
+                        hit: true,
+                    });
+                }
+            }
+        } else {
+            tracing::warn!(key, "Redis unavailable, bypassing cache");
+        }
+
+        let value = fetcher().await?;
+        let entry = TaggedEntry {
+            etag: crate::etag::strong_etag(serde_json::to_string(&value)?.as_bytes()),
+            value,
+        };
+        if let Err(e) = self.set_json(key, &entry, ttl).await {
+            tracing::warn!(key, error = %e, "cache write failed");
+        }
+        Ok(TaggedLookup {
+            value: entry.value,
+            etag: entry.etag,
+            hit: false,
         })
-        .await
     }
 
     async fn recompute_and_store<T, F, Fut>(
@@ -666,7 +957,7 @@ impl RedisCache {
         //
         // We still eagerly delete the concrete cache keys for correctness,
         // but tag metadata is now stored in Redis with TTL + cap.
-        let _ = self.tag_store_invalidate(tag).await?;
+        self.tag_store_invalidate(tag).await?;
 
         let tag_keys = tag.cache_keys();
         let mut deleted = 0usize;
@@ -674,6 +965,9 @@ impl RedisCache {
             self.del(key).await?;
             deleted += 1;
         }
+        for pattern in tag.cache_patterns() {
+            deleted += self.del_by_pattern(&pattern).await?;
+        }
         Ok(deleted)
     }
 
@@ -701,6 +995,187 @@ impl RedisCache {
         .await
     }
 
+    /// Record `member` in a set that rotates every `window`, returning `true`
+    /// the first time it is seen in the current or previous window. Each
+    /// window's set expires after two windows, so membership is remembered for
+    /// between one and two windows without the set growing unbounded.
+    pub async fn add_to_windowed_set(
+        &self,
+        prefix: &str,
+        member: &str,
+        window: Duration,
+    ) -> anyhow::Result<bool> {
+        let window_secs = window.as_secs().max(1);
+        let bucket = chrono::Utc::now().timestamp() as u64 / window_secs;
+        let current = format!("{prefix}:{bucket}");
+        let previous = format!("{prefix}:{}", bucket.saturating_sub(1));
+        let member = member.to_owned();
+        self.exec(|mut conn| {
+            let (current, previous, member) = (current.clone(), previous.clone(), member.clone());
+            async move {
+                let script = redis::Script::new(
+                    r#"
+                    if redis.call('SISMEMBER', KEYS[2], ARGV[1]) == 1 then
+                        return 0
+                    end
+                    local added = redis.call('SADD', KEYS[1], ARGV[1])
+                    redis.call('EXPIRE', KEYS[1], ARGV[2])
+                    return added
+                    "#,
+                );
+                let added: i64 = script
+                    .key(&current)
+                    .key(&previous)
+                    .arg(&member)
+                    .arg(window_secs * 2)
+                    .invoke_async(&mut conn)
+                    .await?;
+                Ok(added == 1)
+            }
+        })
+        .await
+    }
+
+    /// Forget `member` from the sets [`Self::add_to_windowed_set`] checks, so
+    /// the next add sees it for the first time again.
+    pub async fn remove_from_windowed_set(
+        &self,
+        prefix: &str,
+        member: &str,
+        window: Duration,
+    ) -> anyhow::Result<()> {
+        let window_secs = window.as_secs().max(1);
+        let bucket = chrono::Utc::now().timestamp() as u64 / window_secs;
+        let keys = [
+            format!("{prefix}:{bucket}"),
+            format!("{prefix}:{}", bucket.saturating_sub(1)),
+        ];
+        let member = member.to_owned();
+        self.exec(|mut conn| {
+            let (keys, member) = (keys.clone(), member.clone());
+            async move {
+                for key in &keys {
+                    let _: usize = conn.srem(key, &member).await?;
+                }
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Increment every counter in `counters` by one in a single atomic step,
+    /// unless the first has already reached `cap`. A counter gets its TTL
+    /// when the increment creates it. Returns the first counter's new value,
+    /// or `None` when the cap held and nothing was incremented.
+    pub async fn incr_capped(
+        &self,
+        counters: &[(String, Duration)],
+        cap: Option<u64>,
+    ) -> anyhow::Result<Option<u64>> {
+        anyhow::ensure!(!counters.is_empty(), "incr_capped needs at least one counter");
+        let counters = counters.to_vec();
+        // -1 disables the cap inside the script.
+        let cap = cap.map_or(-1, |c| c.min(i64::MAX as u64) as i64);
+        self.exec(|mut conn| {
+            let counters = counters.clone();
+            async move {
+                let script = redis::Script::new(
+                    r#"
+                    local cap = tonumber(ARGV[1])
+                    if cap >= 0 and tonumber(redis.call('GET', KEYS[1]) or '0') >= cap then
+                        return -1
+                    end
+                    local first = 0
+                    for i, key in ipairs(KEYS) do
+                        local current = redis.call('INCR', key)
+                        if current == 1 then
+                            redis.call('EXPIRE', key, ARGV[i + 1])
+                        end
+                        if i == 1 then
+                            first = current
+                        end
+                    end
+                    return first
+                    "#,
+                );
+                let mut invocation = script.prepare_invoke();
+                invocation.arg(cap);
+                for (key, ttl) in &counters {
+                    invocation.key(key).arg(ttl.as_secs().max(1));
+                }
+                let first: i64 = invocation.invoke_async(&mut conn).await?;
+                Ok((first >= 0).then_some(first as u64))
+            }
+        })
+        .await
+    }
+
+    /// Add `member` to the sorted set at `key` with `score` unless it is
+    /// already there (`ZADD NX`); an existing member keeps its score. Returns
+    /// whether this call added it.
+    pub async fn zset_add_nx(&self, key: &str, member: &str, score: i64) -> anyhow::Result<bool> {
+        let (key, member) = (key.to_owned(), member.to_owned());
+        self.exec(|mut conn| {
+            let (key, member) = (key.clone(), member.clone());
+            async move {
+                let added: i64 = redis::cmd("ZADD")
+                    .arg(&key)
+                    .arg("NX")
+                    .arg(score)
+                    .arg(&member)
+                    .query_async(&mut conn)
+                    .await?;
+                Ok(added == 1)
+            }
+        })
+        .await
+    }
+
+    /// Every member of the sorted set at `key` with its score, lowest first.
+    pub async fn zset_members(&self, key: &str) -> anyhow::Result<Vec<(String, i64)>> {
+        let key = key.to_owned();
+        self.exec(|mut conn| {
+            let key = key.clone();
+            async move { Ok(conn.zrange_withscores(&key, 0, -1).await?) }
+        })
+        .await
+    }
+
+    pub async fn zset_remove(&self, key: &str, member: &str) -> anyhow::Result<()> {
+        let (key, member) = (key.to_owned(), member.to_owned());
+        self.exec(|mut conn| {
+            let (key, member) = (key.clone(), member.clone());
+            async move {
+                let _: usize = conn.zrem(&key, &member).await?;
+                Ok(())
+            }
+        })
+        .await
+    }
+
+    /// Atomically remove and return the members of the sorted set at `key`
+    /// scored at or below `max_score`. When several instances race, each
+    /// member is returned to exactly one of them.
+    pub async fn zset_pop_at_most(&self, key: &str, max_score: i64) -> anyhow::Result<Vec<String>> {
+        let key = key.to_owned();
+        self.exec(|mut conn| {
+            let key = key.clone();
+            async move {
+                let script = redis::Script::new(
+                    r#"
+                    local members = redis.call('ZRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+                    if #members > 0 then
+                        redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', ARGV[1])
+                    end
+                    return members
+                    "#,
+                );
+                Ok(script.key(&key).arg(max_score).invoke_async(&mut conn).await?)
+            }
+        })
+        .await
+    }
+
     /// Acquire a raw connection from the pool.
     /// Prefer `exec` for most use cases; use this only when you need to hold
     /// a connection across multiple commands (e.g. pipelined operations).
@@ -712,6 +1187,23 @@ impl RedisCache {
     }
 }
 
+/// What [`RedisCache::get_or_set_json_tagged`] stores: the value and the
+/// ETag of its serialized form.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct TaggedEntry<T> {
+    etag: String,
+    value: T,
+}
+
+/// Result of [`RedisCache::get_or_set_json_tagged`].
+#[derive(Debug, Clone)]
+pub struct TaggedLookup<T> {
+    pub value: T,
+    /// Quoted strong ETag, ready for the `ETag` header.
+    pub etag: String,
+    pub hit: bool,
+}
+
 // ── XFetch stampede protection types ────────────────────────────────────────
 
 /// A cached entry with metadata for probabilistic early expiry (XFetch).
@@ -767,13 +1259,19 @@ pub fn xfetch_should_refresh<T>(entry: &CachedEntry<T>, beta: f64) -> bool {
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "redis-integration")]
     use std::time::Duration;
 
+    #[cfg(feature = "redis-integration")]
     use testcontainers::runners::AsyncRunner;
+    #[cfg(feature = "redis-integration")]
     use testcontainers_modules::redis::Redis;
 
     use super::RedisCache;
+    #[cfg(feature = "redis-integration")]
+    use super::refreshing;
 
+    #[cfg(feature = "redis-integration")]
     async fn start_cache() -> (RedisCache, impl Drop) {
         let container = Redis::default().start().await.expect("redis container");
         let port = container
@@ -786,6 +1284,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn cache_miss_populates_on_first_request() {
         let (cache, _c) = start_cache().await;
         let (val, hit) = cache
@@ -807,6 +1306,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn cache_hit_on_subsequent_request() {
         let (cache, _c) = start_cache().await;
         cache
@@ -824,6 +1324,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn del_invalidates_cached_entry() {
         let (cache, _c) = start_cache().await;
         cache
@@ -836,6 +1337,63 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    async fn tagged_entry_keeps_etag_until_invalidated() {
+        let (cache, _c) = start_cache().await;
+        let ttl = Duration::from_secs(60);
+        let first = cache
+            .get_or_set_json_tagged::<u32, _, _>("key:tagged", ttl, || async { Ok(1u32) })
+            .await
+            .unwrap();
+        assert!(!first.hit);
+        assert!(first.etag.starts_with('"') && first.etag.ends_with('"'));
+
+        let second = cache
+            .get_or_set_json_tagged::<u32, _, _>("key:tagged", ttl, || async {
+                Err(anyhow::anyhow!("fetcher must not run on a hit"))
+            })
+            .await
+            .unwrap();
+        assert!(second.hit);
+        assert_eq!(second.etag, first.etag);
+
+        cache.del("key:tagged").await.unwrap();
+        let third = cache
+            .get_or_set_json_tagged::<u32, _, _>("key:tagged", ttl, || async { Ok(2u32) })
+            .await
+            .unwrap();
+        assert_eq!(third.value, 2);
+        assert_ne!(third.etag, first.etag, "new content must get a new ETag");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    async fn refreshing_rewrites_live_entries() {
+        let (cache, _c) = start_cache().await;
+        let ttl = Duration::from_secs(60);
+        cache
+            .get_or_set_json::<u32, _, _>("key:refresh", ttl, || async { Ok(1u32) })
+            .await
+            .unwrap();
+
+        let (value, hit) = refreshing(cache.get_or_set_json::<u32, _, _>("key:refresh", ttl, || async { Ok(2u32) }))
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+        assert!(!hit);
+
+        let (value, hit) = cache
+            .get_or_set_json::<u32, _, _>("key:refresh", ttl, || async {
+                Err(anyhow::anyhow!("fetcher must not run on a hit"))
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 2);
+        assert!(hit);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn del_by_pattern_invalidates_matching_entries() {
         let (cache, _c) = start_cache().await;
         for i in 0..3u32 {
@@ -863,6 +1421,7 @@ mod tests {
     /// Verifies that del_by_pattern correctly handles a keyspace larger than a
     /// single SCAN page (COUNT 100), exercising the cursor-batching loop.
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn del_by_pattern_large_keyspace_uses_cursor_batching() {
         let (cache, _c) = start_cache().await;
         let n = 250u32; // exceeds the COUNT 100 hint, forcing multiple SCAN rounds
@@ -893,15 +1452,141 @@ mod tests {
 
     /// Verifies that del_by_pattern returns 0 and does not error when no keys match.
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn del_by_pattern_no_matches_returns_zero() {
         let (cache, _c) = start_cache().await;
         let deleted = cache.del_by_pattern("nonexistent:*").await.unwrap();
         assert_eq!(deleted, 0);
     }
 
+    // ── Multi-key tests ──────────────────────────────────────────────────────
+
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    async fn mget_json_reports_partial_hits() {
+        let (cache, _c) = start_cache().await;
+        cache.set_json("mget:a", &1u32, Duration::from_secs(60)).await.unwrap();
+        cache.set_json("mget:c", &3u32, Duration::from_secs(60)).await.unwrap();
+        cache.set_json("mget:bad", &"not a number", Duration::from_secs(60)).await.unwrap();
+
+        let keys: Vec<String> = ["mget:a", "mget:b", "mget:c", "mget:bad"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect();
+        let values: Vec<Option<u32>> = cache.mget_json(&keys).await.unwrap();
+        assert_eq!(values, vec![Some(1), None, Some(3), None], "absent and undecodable are misses");
+
+        let none: Vec<Option<u32>> = cache.mget_json(&[]).await.unwrap();
+        assert!(none.is_empty());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    async fn get_or_set_many_json_fetches_only_misses() {
+        use std::sync::Mutex;
+        let (cache, _c) = start_cache().await;
+        cache.set_json("many:0", &10u32, Duration::from_secs(60)).await.unwrap();
+
+        let keys: Vec<String> = (0..4).map(|i| format!("many:{i}")).collect();
+        let fetched = Mutex::new(Vec::new());
+        let results = cache
+            .get_or_set_many_json(&keys, Duration::from_secs(60), |i| {
+                fetched.lock().unwrap().push(i);
+                async move {
+                    if i == 2 {
+                        anyhow::bail!("upstream failed")
+                    }
+                    Ok(i as u32 * 10)
+                }
+            })
+            .await;
+
+        let mut fetched = fetched.into_inner().unwrap();
+        fetched.sort();
+        assert_eq!(fetched, vec![1, 2, 3], "hits are not fetched");
+        assert_eq!(results[0].as_ref().unwrap(), &(10, true));
+        assert_eq!(results[1].as_ref().unwrap(), &(10, false));
+        assert!(results[2].is_err(), "a failed fetch fails only its own slot");
+        assert_eq!(results[3].as_ref().unwrap(), &(30, false));
+
+        // Successes were written back; the failure was not.
+        let stored: Vec<Option<u32>> = cache.mget_json(&keys).await.unwrap();
+        assert_eq!(stored, vec![Some(10), Some(10), None, Some(30)]);
+        let mut conn = cache.redis_pool().get().await.unwrap();
+        let ttl: i64 = redis::AsyncCommands::ttl(&mut conn, "many:3").await.unwrap();
+        assert!((1..=60).contains(&ttl), "written with the TTL: {ttl}");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    async fn command_timeout_falls_back_to_fetcher() {
+        use super::RedisCacheConfig;
+        let container = Redis::default().start().await.expect("redis container");
+        let port = container.get_host_port_ipv4(6379).await.expect("redis port");
+        let url = format!("redis://127.0.0.1:{port}");
+        let cfg = RedisCacheConfig {
+            command_timeout: Duration::from_millis(100),
+            retry_attempts: 3,
+            ..RedisCacheConfig::from_env()
+        };
+        let metrics = crate::metrics::Metrics::new().unwrap();
+        let cache = RedisCache::new_with_config_and_metrics(&url, cfg, Some(metrics.clone()))
+            .await
+            .unwrap();
+        cache.set_json("slow:key", &1u32, Duration::from_secs(60)).await.unwrap();
+
+        // Wedge the server for longer than the command timeout.
+        let mut blocker = cache.redis_pool().get().await.unwrap();
+        tokio::spawn(async move {
+            let _: redis::RedisResult<()> =
+                redis::cmd("DEBUG").arg("SLEEP").arg("0.5").query_async(&mut blocker).await;
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = std::time::Instant::now();
+        let (value, hit) = cache
+            .get_or_set_json::<u32, _, _>("slow:key", Duration::from_secs(60), || async { Ok(7u32) })
+            .await
+            .unwrap();
+        assert_eq!((value, hit), (7, false), "served by the fetcher");
+        // The lookup and the write-back each give up after one 100ms attempt.
+        assert!(started.elapsed() < Duration::from_millis(400), "timeouts are not retried");
+        assert!(metrics.render().unwrap().contains("redis_command_timeouts_total 2"));
+    }
+
+    /// Round trips for a page of chain entries: one `MGET` against a `GET`
+    /// per key. Run with `cargo test mget_round_trip_benchmark -- --ignored --nocapture`.
+    #[tokio::test]
+    #[cfg(feature = "redis-integration")]
+    #[ignore] // Micro-benchmark
+    async fn mget_round_trip_benchmark() {
+        let (cache, _c) = start_cache().await;
+        let keys: Vec<String> = (0..50).map(|i| format!("bench:{i}")).collect();
+        let entries: Vec<(String, u32)> = keys.iter().cloned().zip(0..).collect();
+        cache.mset_json(&entries, Duration::from_secs(60)).await.unwrap();
+
+        const ROUNDS: u32 = 20;
+        let started = std::time::Instant::now();
+        for _ in 0..ROUNDS {
+            for key in &keys {
+                let _: Option<u32> = cache.get_json(key).await.unwrap();
+            }
+        }
+        let serial = started.elapsed() / ROUNDS;
+
+        let started = std::time::Instant::now();
+        for _ in 0..ROUNDS {
+            let _: Vec<Option<u32>> = cache.mget_json(&keys).await.unwrap();
+        }
+        let pipelined = started.elapsed() / ROUNDS;
+
+        println!("{} keys: {serial:?} with one GET each, {pipelined:?} with one MGET", keys.len());
+        assert!(pipelined < serial, "one round trip beats {}", keys.len());
+    }
+
     // ── InvalidationTag tests ────────────────────────────────────────────────
 
-    /// Verifies that MarketResolved tag produces exactly the expected 6 keys.
+    /// Verifies that MarketResolved tag produces exactly the expected 7 keys.
     #[test]
     fn market_resolved_tag_produces_correct_keys() {
         use super::InvalidationTag;
@@ -911,8 +1596,9 @@ mod tests {
             featured_limit: 10,
         };
         let keys = tag.cache_keys();
-        assert_eq!(keys.len(), 6, "MarketResolved must cover exactly 6 keys");
-        assert!(keys.contains(&"chain:v1:market:7".to_string()));
+        assert_eq!(keys.len(), 7, "MarketResolved must cover exactly 7 keys");
+        assert!(keys.contains(&"chain:v1:market:testnet:7".to_string()));
+        assert!(keys.contains(&"api:v1:market_detail:7".to_string()));
         assert!(keys.contains(&"chain:v1:oracle:testnet:market:7".to_string()));
         assert!(keys.contains(&"api:v1:statistics".to_string()));
         assert!(keys.contains(&"api:v1:featured_markets".to_string()));
@@ -920,6 +1606,95 @@ mod tests {
         assert!(keys.contains(&"dbq:v1:featured_markets:limit:10".to_string()));
     }
 
+    #[test]
+    fn category_changed_tag_covers_only_the_listing() {
+        use super::InvalidationTag;
+        assert_eq!(
+            InvalidationTag::CategoryChanged.cache_keys(),
+            vec!["dbq:v1:categories".to_string()]
+        );
+    }
+
+    #[test]
+    fn blocked_email_domains_tag_covers_only_the_blocklist() {
+        use super::InvalidationTag;
+        assert_eq!(
+            InvalidationTag::BlockedEmailDomainsChanged.cache_keys(),
+            vec!["dbq:v1:blocked_email_domains".to_string()]
+        );
+        assert!(InvalidationTag::BlockedEmailDomainsChanged.cache_patterns().is_empty());
+    }
+
+    #[test]
+    fn market_cancelled_tag_matches_market_resolved() {
+        use super::InvalidationTag;
+        let resolved = InvalidationTag::MarketResolved {
+            market_id: 7,
+            network: "testnet".to_string(),
+            featured_limit: 10,
+        };
+        let cancelled = InvalidationTag::MarketCancelled {
+            market_id: 7,
+            network: "testnet".to_string(),
+            featured_limit: 10,
+        };
+        assert_eq!(cancelled.cache_keys(), resolved.cache_keys());
+    }
+
+    #[test]
+    fn market_image_changed_tag_covers_documents_with_image_url() {
+        use super::InvalidationTag;
+        let tag = InvalidationTag::MarketImageChanged { market_id: 7, featured_limit: 10 };
+        assert_eq!(
+            tag.cache_keys(),
+            vec![
+                "api:v1:market_detail:7".to_string(),
+                "api:v1:featured_markets".to_string(),
+                "dbq:v1:featured_markets:limit:10".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn market_tags_sweep_shaped_detail_variants() {
+        use super::InvalidationTag;
+        let tag = InvalidationTag::MarketImageChanged { market_id: 7, featured_limit: 10 };
+        assert_eq!(tag.cache_patterns(), vec!["api:v1:market_detail:7:*".to_string()]);
+        let tag = InvalidationTag::MarketResolved {
+            market_id: 7,
+            network: "testnet".to_string(),
+            featured_limit: 10,
+        };
+        assert_eq!(tag.cache_patterns(), vec!["api:v1:market_detail:7:*".to_string()]);
+        assert!(InvalidationTag::CategoryChanged.cache_patterns().is_empty());
+
+        // The pattern matches every shape of market 7 and nothing of market 70.
+        let shaped = super::keys::api_market_detail_shaped(7, "oracle", "");
+        assert_eq!(shaped, "api:v1:market_detail:7:embed:oracle:fields:");
+        let other = super::keys::api_market_detail_shaped(70, "oracle", "");
+        assert!(!other.starts_with("api:v1:market_detail:7:"));
+        assert_eq!(super::keys::api_market_detail_shaped(7, "", ""), "api:v1:market_detail:7");
+    }
+
+    #[test]
+    fn market_created_tag_covers_platform_stats() {
+        use super::InvalidationTag;
+        let tag = InvalidationTag::MarketCreated { network: "testnet".to_string() };
+        assert_eq!(tag.cache_keys(), vec!["chain:v1:platform_stats:testnet".to_string()]);
+    }
+
+    #[test]
+    fn user_bets_tag_covers_default_size_pages() {
+        use super::{InvalidationTag, USER_BETS_INVALIDATED_PAGES};
+        let keys = InvalidationTag::UserBetsChanged {
+            network: "testnet".to_string(),
+            address: "GBETTOR".to_string(),
+        }
+        .cache_keys();
+        assert_eq!(keys.len() as i64, USER_BETS_INVALIDATED_PAGES);
+        assert_eq!(keys[0], "chain:v1:user_bets:testnet:gbettor:page:0:size:20");
+    }
+
     /// Verifies that different market IDs produce distinct key sets (no cross-contamination).
     #[test]
     fn market_resolved_tag_keys_are_market_id_scoped() {
@@ -951,6 +1726,7 @@ mod tests {
 
     /// Integration: invalidate_tag deletes exactly the keys in the tag and leaves others.
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn invalidate_tag_deletes_tag_keys_only() {
         use super::InvalidationTag;
         let (cache, _c) = start_cache().await;
@@ -968,7 +1744,7 @@ mod tests {
         cache.set_json("unrelated:key", &42u32, Duration::from_secs(60)).await.unwrap();
 
         let deleted = cache.invalidate_tag(&tag).await.unwrap();
-        assert_eq!(deleted, 6, "must report 6 deletions");
+        assert_eq!(deleted, 7, "must report 7 deletions");
 
         // All tag keys must be gone.
         for key in tag.cache_keys() {
@@ -994,6 +1770,7 @@ mod tests {
             retry_base_delay: Duration::from_millis(10),
             cb_threshold: 2,
             cb_reset_timeout: Duration::from_secs(60),
+            command_timeout: Duration::from_millis(500),
         };
         let cache = RedisCache::new_with_config("redis://127.0.0.1:19999", cfg)
             .await
@@ -1028,15 +1805,27 @@ mod tests {
 //
 // | Tag                          | Keys invalidated                                                  |
 // |------------------------------|-------------------------------------------------------------------|
-// | `MarketResolved(id, net, lim)` | chain_market(id), chain_oracle_result(net,id),                  |
+// | `MarketResolved(id, net, lim)` | chain_market(net,id), chain_oracle_result(net,id),              |
+// |                              | api_market_detail(id), api_market_detail_shaped(id, *, *),        |
 // |                              | api_statistics, api_featured_markets,                             |
 // |                              | dbq_statistics, dbq_featured_markets(lim)                         |
+// | `MarketCancelled(id, net, lim)` | same keys as `MarketResolved`                                  |
+// | `MarketCreated(net)`         | chain_platform_stats(net)                                         |
+// | `UserBetsChanged(net, addr)` | chain_user_bets_page(net,addr,p,DEFAULT_LIMIT) for the first      |
+// |                              | USER_BETS_INVALIDATED_PAGES pages                                 |
+// | `MarketImageChanged(id, lim)` | api_market_detail(id), api_market_detail_shaped(id, *, *),       |
+// |                              | api_featured_markets, dbq_featured_markets(lim)                   |
 //
 // ## Rules
 // - Tags are defined here; handlers import and use them.
 // - A tag must never include keys from unrelated domains (e.g. resolving a
 //   market must not evict content or user-bet keys).
 // - When a new write path is added, add a corresponding tag here first.
+// - Shaped variants (`*` above) are deleted by pattern through
+//   `InvalidationTag::cache_patterns`; keep them off tags for frequent writes.
+
+/// Pages of a user's bets that [`InvalidationTag::UserBetsChanged`] covers.
+pub const USER_BETS_INVALIDATED_PAGES: i64 = 5;
 
 /// Describes a write event and the exact cache keys it invalidates.
 ///
@@ -1046,13 +1835,50 @@ pub enum InvalidationTag {
 
     /// A market was resolved.
     ///
-    /// Invalidates the per-market chain entry, the oracle result, and the
-    /// aggregate statistics / featured-markets lists.
+    /// Invalidates the per-market chain entry, the oracle result, the
+    /// combined market detail document, and the aggregate statistics /
+    /// featured-markets lists.
     MarketResolved {
         market_id: i64,
         network: String,
         featured_limit: i64,
     },
+
+    /// A market was cancelled. Covers the same keys as
+    /// [`InvalidationTag::MarketResolved`].
+    MarketCancelled {
+        market_id: i64,
+        network: String,
+        featured_limit: i64,
+    },
+
+    /// A market was created on-chain.
+    ///
+    /// Invalidates the network's platform statistics.
+    MarketCreated { network: String },
+
+    /// An address placed a bet or claimed winnings.
+    ///
+    /// Invalidates the first [`USER_BETS_INVALIDATED_PAGES`] pages of the
+    /// address's bets at the default page size. Other page sizes are left to
+    /// expire with their TTL.
+    UserBetsChanged { network: String, address: String },
+
+    /// An admin created, edited, or deleted a category.
+    ///
+    /// Invalidates the category listing only; market lists are unaffected.
+    CategoryChanged,
+
+    /// An admin added or removed a blocked signup email domain.
+    ///
+    /// Invalidates the blocklist, so the next signup reads the new one.
+    BlockedEmailDomainsChanged,
+
+    /// An admin uploaded a market's cover image.
+    ///
+    /// Invalidates the documents that carry `image_url`: the market detail
+    /// and the featured-markets lists.
+    MarketImageChanged { market_id: i64, featured_limit: i64 },
 }
 
 impl InvalidationTag {
@@ -1063,14 +1889,55 @@ impl InvalidationTag {
                 market_id,
                 network,
                 featured_limit,
+            }
+            | InvalidationTag::MarketCancelled {
+                market_id,
+                network,
+                featured_limit,
             } => vec![
-                keys::chain_market(*market_id),
+                keys::chain_market(network, *market_id),
                 keys::chain_oracle_result(network, *market_id),
+                keys::api_market_detail(*market_id),
                 keys::api_statistics(),
                 keys::api_featured_markets(),
                 keys::dbq_statistics(),
                 keys::dbq_featured_markets(*featured_limit),
             ],
+            InvalidationTag::MarketCreated { network } => vec![keys::chain_platform_stats(network)],
+            InvalidationTag::UserBetsChanged { network, address } => (0..USER_BETS_INVALIDATED_PAGES)
+                .map(|page| {
+                    keys::chain_user_bets_page(
+                        network,
+                        address,
+                        page,
+                        crate::pagination::DEFAULT_LIMIT as i64,
+                    )
+                })
+                .collect(),
+            InvalidationTag::CategoryChanged => vec![keys::dbq_categories()],
+            InvalidationTag::BlockedEmailDomainsChanged => vec![keys::dbq_blocked_email_domains()],
+            InvalidationTag::MarketImageChanged { market_id, featured_limit } => vec![
+                keys::api_market_detail(*market_id),
+                keys::api_featured_markets(),
+                keys::dbq_featured_markets(*featured_limit),
+            ],
+        }
+    }
+
+    /// Key patterns this tag also covers: variants of a document keyed by
+    /// request shape, which cannot be listed up front. Each is a `SCAN`, so
+    /// only tags for rare writes may have any.
+    pub fn cache_patterns(&self) -> Vec<String> {
+        match self {
+            InvalidationTag::MarketResolved { market_id, .. }
+            | InvalidationTag::MarketCancelled { market_id, .. }
+            | InvalidationTag::MarketImageChanged { market_id, .. } => {
+                vec![keys::api_market_detail_shaped_pattern(*market_id)]
+            }
+            InvalidationTag::MarketCreated { .. }
+            | InvalidationTag::UserBetsChanged { .. }
+            | InvalidationTag::CategoryChanged
+            | InvalidationTag::BlockedEmailDomainsChanged => Vec::new(),
         }
     }
 }
@@ -1172,6 +2039,10 @@ pub mod keys {
     pub const API_PREFIX: &str = "api:v1";
     pub const DBQ_PREFIX: &str = "dbq:v1";
     pub const CHAIN_PREFIX: &str = "chain:v1";
+    pub const PRICE_PREFIX: &str = "price:v1";
+    pub const TXWATCH_PREFIX: &str = "txwatch:v1";
+    pub const USAGE_PREFIX: &str = "usage:v1";
+    pub const AUTH_PREFIX: &str = "auth:v1";
 
     // ---- api:v1 keys ----
 
@@ -1190,6 +2061,76 @@ pub mod keys {
     }
     pub fn api_content_category() -> KeyCategory { KeyCategory::Content }
 
+    /// Rendered page for one published article.
+    pub fn api_content_page(slug: &str) -> String {
+        format!("{API_PREFIX}:content:page:{slug}")
+    }
+
+    /// Every content listing and rendered page. Listings are keyed by limit,
+    /// so admin writes delete by pattern rather than through a tag.
+    pub fn api_content_pattern() -> String {
+        format!("{API_PREFIX}:content:*")
+    }
+
+    pub fn api_market_detail(market_id: i64) -> String {
+        format!("{API_PREFIX}:market_detail:{market_id}")
+    }
+
+    /// Market detail as shaped by `?embed=` and `?fields=`: `embed` is the
+    /// sorted embed list and `fields` a
+    /// [`crate::field_mask::FieldMask::cache_key_part`]. The default shape
+    /// keeps [`api_market_detail`].
+    pub fn api_market_detail_shaped(market_id: i64, embed: &str, fields: &str) -> String {
+        if embed.is_empty() && fields.is_empty() {
+            return api_market_detail(market_id);
+        }
+        format!("{API_PREFIX}:market_detail:{market_id}:embed:{embed}:fields:{fields}")
+    }
+
+    /// Every non-default shape of one market's detail document.
+    pub fn api_market_detail_shaped_pattern(market_id: i64) -> String {
+        format!("{API_PREFIX}:market_detail:{market_id}:*")
+    }
+
+    /// Per-address portfolio document. Dropped by the sync worker whenever it
+    /// indexes a new event for that address rather than through a tag.
+    pub fn api_user_portfolio(address: &str) -> String {
+        format!("{API_PREFIX}:portfolio:{address}")
+    }
+
+    /// One creator's profile. Never invalidated, only expired; a hidden
+    /// profile is refused before the cache is read.
+    pub fn api_creator_profile(address: &str) -> String {
+        format!("{API_PREFIX}:creator_profile:{address}")
+    }
+
+    /// Full top-creators list for one metric; the handler slices it to
+    /// `limit`. Dropped when an address saves its notification settings, so
+    /// hiding a profile takes it off the list at once.
+    pub fn api_top_creators(metric: &str) -> String {
+        format!("{API_PREFIX}:top_creators:{metric}")
+    }
+
+    /// First page of the activity feed for one filter. Short-lived; never
+    /// invalidated, only expired.
+    pub fn api_activity(network: &str, types: &str, market_id: Option<i64>, limit: i64) -> String {
+        let market = market_id.map_or_else(|| "all".to_string(), |id| id.to_string());
+        format!("{API_PREFIX}:activity:{network}:{types}:{market}:{limit}")
+    }
+
+    /// Full snapshot for one board; the handler slices it to `limit`. Dropped
+    /// by the aggregation task after each rebuild.
+    pub fn api_leaderboard(period: &str, metric: &str) -> String {
+        format!("{API_PREFIX}:leaderboard:{period}:{metric}")
+    }
+
+    /// Full [`crate::stats_history::MAX_HISTORY_DAYS`] series for one
+    /// metric; the handler slices it to `days`. Dropped by the nightly
+    /// rollup.
+    pub fn api_statistics_history(metric: &str) -> String {
+        format!("{API_PREFIX}:statistics:history:{metric}")
+    }
+
     // ---- dbq:v1 keys ----
 
     pub fn dbq_statistics() -> String {
@@ -1207,13 +2148,43 @@ pub mod keys {
     }
     pub fn dbq_content_category() -> KeyCategory { KeyCategory::Content }
 
+    pub fn dbq_content_pattern() -> String {
+        format!("{DBQ_PREFIX}:content:*")
+    }
+
+    /// Category listing with per-category counts. Dropped by
+    /// [`super::InvalidationTag::CategoryChanged`].
+    pub fn dbq_categories() -> String {
+        format!("{DBQ_PREFIX}:categories")
+    }
+
+    /// Every blocked signup email domain pattern. Dropped by
+    /// [`super::InvalidationTag::BlockedEmailDomainsChanged`].
+    pub fn dbq_blocked_email_domains() -> String {
+        format!("{DBQ_PREFIX}:blocked_email_domains")
+    }
+
     // ---- chain:v1 keys ----
 
-    pub fn chain_market(market_id: i64) -> String {
-        format!("{CHAIN_PREFIX}:market:{market_id}")
+    pub fn chain_market(network: &str, market_id: i64) -> String {
+        format!("{CHAIN_PREFIX}:market:{network}:{market_id}")
     }
     pub fn chain_market_category() -> KeyCategory { KeyCategory::ChainMarket }
 
+    /// Contract-side description and outcome labels. Immutable, so no
+    /// invalidation tag drops it.
+    pub fn chain_market_metadata(network: &str, market_id: i64) -> String {
+        format!("{CHAIN_PREFIX}:market_metadata:{network}:{market_id}")
+    }
+    pub fn chain_market_metadata_category() -> KeyCategory { KeyCategory::ChainMarket }
+
+    /// Allowlisted getter result (see `contract_read`), keyed by its
+    /// comma-joined arguments. Expires on the function's own TTL.
+    pub fn chain_contract_read(network: &str, function: &str, args: &str) -> String {
+        format!("{CHAIN_PREFIX}:contract_read:{network}:{function}:{args}")
+    }
+    pub fn chain_contract_read_category() -> KeyCategory { KeyCategory::Custom }
+
     pub fn chain_platform_stats(network: &str) -> String {
         format!("{CHAIN_PREFIX}:platform_stats:{network}")
     }
@@ -1253,6 +2224,13 @@ pub mod keys {
     }
     pub fn chain_health_category() -> KeyCategory { KeyCategory::ChainHealth }
 
+    /// Simulated breaker, guardian and upgrade state. Dropped by the sync
+    /// worker when it indexes a `cb_state` event.
+    pub fn chain_protocol_state(network: &str) -> String {
+        format!("{CHAIN_PREFIX}:protocol_state:{network}")
+    }
+    pub fn chain_protocol_state_category() -> KeyCategory { KeyCategory::ChainHealth }
+
     pub fn chain_last_seen_ledger(network: &str) -> String {
         format!("{CHAIN_PREFIX}:last_seen_ledger:{network}")
     }
@@ -1261,14 +2239,70 @@ pub mod keys {
     pub fn chain_sync_cursor(network: &str) -> String {
         format!("{CHAIN_PREFIX}:sync_cursor:{network}")
     }
+    pub fn chain_sync_cursor_category() -> KeyCategory { KeyCategory::ChainSyncCursor }
 
     pub fn chain_replay_progress(network: &str, from_ledger: u32) -> String {
         format!("{CHAIN_PREFIX}:replay:{network}:{from_ledger}")
     }
+
+    // ---- txwatch:v1 keys ----
+
+    /// Sorted set of transaction hashes the monitor is tracking on `network`,
+    /// scored by the Unix time each was first watched. This is state, not a
+    /// cache entry: it has no TTL and sits outside the prefixes cache admin
+    /// may delete.
+    pub fn tx_watch_set(network: &str) -> String {
+        format!("{TXWATCH_PREFIX}:{network}")
+    }
+
+    // ---- usage:v1 keys ----
+
+    /// Requests made with one API key on one UTC day. Like the tx watch set,
+    /// these are state rather than cache entries and sit outside the prefixes
+    /// cache admin may delete; see [`crate::api_key_usage`].
+    pub fn api_key_usage_day(key_id: &uuid::Uuid, day: chrono::NaiveDate) -> String {
+        format!("{USAGE_PREFIX}:key:{key_id}:day:{}", day.format("%Y-%m-%d"))
+    }
+
+    /// Every daily usage counter, for the hourly flush.
+    pub fn api_key_usage_day_pattern() -> String {
+        format!("{USAGE_PREFIX}:key:*:day:*")
+    }
+
+    /// Requests made with one API key in the calendar month containing `day`;
+    /// checked against the key's monthly quota.
+    pub fn api_key_usage_month(key_id: &uuid::Uuid, day: chrono::NaiveDate) -> String {
+        format!("{USAGE_PREFIX}:key:{key_id}:month:{}", day.format("%Y-%m"))
+    }
+
+    // ---- auth:v1 keys ----
+
+    /// Wallet challenge `nonce`, holding the address it was issued to until
+    /// it is answered or expires; see [`crate::wallet_auth`].
+    pub fn auth_challenge(nonce: &str) -> String {
+        format!("{AUTH_PREFIX}:challenge:{nonce}")
+    }
+
+    // ---- price:v1 keys ----
+
+    /// Latest USD quote for one price-source asset, written by the price task.
+    pub fn price_usd(asset: &str) -> String {
+        format!("{PRICE_PREFIX}:usd:{asset}")
+    }
+
+    // ---- pending overrides ----
+
+    /// State an admin write expects `market_id` to have on chain, layered
+    /// over RPC reads until the chain confirms it; see
+    /// [`crate::blockchain::PendingOverride`]. Outside the cache prefixes, so
+    /// a cache flush does not drop it early.
+    pub fn pending_override_market(market_id: i64) -> String {
+        format!("pending_override:market:{market_id}")
+    }
 }
 
 #[cfg(test)]
-mod tests {
+mod key_tests {
     use super::*;
     use std::sync::{
         atomic::{AtomicUsize, Ordering},
@@ -1350,11 +2384,14 @@ mod tests {
         assert_eq!(keys::api_content_category(),             KeyCategory::Content);
         assert_eq!(keys::dbq_statistics_category(),          KeyCategory::Statistics);
         assert_eq!(keys::chain_market_category(),            KeyCategory::ChainMarket);
+        assert_eq!(keys::chain_market_metadata_category(),   KeyCategory::ChainMarket);
+        assert_eq!(keys::chain_contract_read_category(),     KeyCategory::Custom);
         assert_eq!(keys::chain_platform_stats_category(),    KeyCategory::ChainPlatformStats);
         assert_eq!(keys::chain_user_bets_category(),         KeyCategory::ChainUserBets);
         assert_eq!(keys::chain_oracle_result_category(),     KeyCategory::ChainOracleResult);
         assert_eq!(keys::chain_tx_status_category(),         KeyCategory::ChainTxStatus);
         assert_eq!(keys::chain_health_category(),            KeyCategory::ChainHealth);
+        assert_eq!(keys::chain_protocol_state_category(),    KeyCategory::ChainHealth);
         assert_eq!(keys::chain_last_seen_ledger_category(),  KeyCategory::ChainLedger);
         assert_eq!(keys::chain_sync_cursor_category(),       KeyCategory::ChainSyncCursor);
     }
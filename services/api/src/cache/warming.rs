@@ -0,0 +1,235 @@
+//! Scheduled cache warming.
+//!
+//! The hot read-path entries are rewritten shortly before their TTL lapses so
+//! requests keep hitting a warm cache instead of paying for the refill. Each
+//! [`WarmTarget`] is refreshed through the same `Database` / `BlockchainClient`
+//! method the request path calls, wrapped in [`super::refreshing`] so the read
+//! is skipped and the entry rewritten. Targets are independent: one that fails
+//! is logged, counted in `cache_warm_total{target,outcome}`, and retried on the
+//! next tick without holding up the others.
+
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::{CacheTtls, WarmTarget},
+    metrics::Metrics,
+    AppState,
+};
+
+const WORKER_NAME: &str = "cache_warming";
+
+/// Lifetime of the entry behind `target`.
+pub fn ttl(ttls: &CacheTtls, target: WarmTarget) -> Duration {
+    match target {
+        WarmTarget::Statistics => ttls.statistics,
+        WarmTarget::FeaturedMarkets => ttls.featured_markets,
+        WarmTarget::PlatformStats => ttls.platform_stats,
+        WarmTarget::Health => ttls.health,
+    }
+}
+
+/// Tracks when each target was last warmed and which are due.
+pub struct CacheWarmer {
+    interval: Duration,
+    ttls: CacheTtls,
+    targets: Vec<(WarmTarget, Option<Instant>)>,
+}
+
+impl CacheWarmer {
+    pub fn new(targets: &[WarmTarget], interval: Duration, ttls: CacheTtls) -> Self {
+        Self {
+            interval,
+            ttls,
+            targets: targets.iter().map(|&t| (t, None)).collect(),
+        }
+    }
+
+    /// A target is due when it has never been warmed, or when its entry would
+    /// expire before the next tick.
+    fn is_due(&self, target: WarmTarget, last: Option<Instant>, now: Instant) -> bool {
+        match last {
+            None => true,
+            Some(at) => now.saturating_duration_since(at) + self.interval >= ttl(&self.ttls, target),
+        }
+    }
+
+    /// Run `warm` for every due target, in order. A failure is recorded and
+    /// left due for the next tick; it never stops the remaining targets.
+    /// Returns the number of targets that failed.
+    pub async fn warm_due<F, Fut>(&mut self, metrics: &Metrics, now: Instant, mut warm: F) -> usize
+    where
+        F: FnMut(WarmTarget) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        let mut failed = 0;
+        for i in 0..self.targets.len() {
+            let (target, last) = self.targets[i];
+            if !self.is_due(target, last, now) {
+                continue;
+            }
+            match warm(target).await {
+                Ok(()) => {
+                    metrics.observe_cache_warm(target.name(), true);
+                    self.targets[i].1 = Some(now);
+                }
+                Err(e) => {
+                    metrics.observe_cache_warm(target.name(), false);
+                    tracing::warn!(target = target.name(), error = %e, "cache warming failed");
+                    failed += 1;
+                }
+            }
+        }
+        failed
+    }
+}
+
+/// Refresh the entry behind `target`. Per-network targets try every network
+/// and fail if any of them did.
+pub async fn warm_target(state: &AppState, target: WarmTarget) -> anyhow::Result<()> {
+    super::refreshing(async {
+        match target {
+            WarmTarget::Statistics => state.db.statistics_cached().await.map(drop),
+            WarmTarget::FeaturedMarkets => state
+                .db
+                .featured_markets_cached(state.config.featured_limit)
+                .await
+                .map(drop),
+            WarmTarget::PlatformStats | WarmTarget::Health => {
+                let mut errors = Vec::new();
+                for client in state.networks.iter() {
+                    let result = if target == WarmTarget::Health {
+                        client.health_check_cached().await.map(drop)
+                    } else {
+                        client.platform_statistics_cached().await.map(drop)
+                    };
+                    if let Err(e) = result {
+                        errors.push(format!("{}: {e}", client.network()));
+                    }
+                }
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(anyhow::anyhow!(errors.join("; ")))
+                }
+            }
+        }
+    })
+    .await
+}
+
+/// Warm the configured targets every `cache_warm_interval` until `shutdown`
+/// fires. The first tick is immediate, so every target is warmed at boot.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    let mut warmer = CacheWarmer::new(
+        &state.config.cache_warm_targets,
+        state.config.cache_warm_interval,
+        state.config.cache_ttls.clone(),
+    );
+    let mut interval = tokio::time::interval(state.config.cache_warm_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    state.metrics.set_worker_status(WORKER_NAME, true);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        warmer
+            .warm_due(&state.metrics, Instant::now(), |target| warm_target(&state, target))
+            .await;
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warm_count(metrics: &Metrics, target: &str, outcome: &str) -> Option<String> {
+        let rendered = metrics.render().unwrap();
+        rendered
+            .lines()
+            .find(|l| {
+                l.starts_with("cache_warm_total{")
+                    && l.contains(&format!("target=\"{target}\""))
+                    && l.contains(&format!("outcome=\"{outcome}\""))
+            })
+            .and_then(|l| l.rsplit(' ').next().map(str::to_string))
+    }
+
+    #[tokio::test]
+    async fn failing_target_does_not_stop_the_others() {
+        let metrics = Metrics::new().unwrap();
+        let mut warmer = CacheWarmer::new(&WarmTarget::ALL, Duration::from_secs(5), CacheTtls::default());
+        let now = Instant::now();
+
+        let mut attempted = Vec::new();
+        let failed = warmer
+            .warm_due(&metrics, now, |target| {
+                attempted.push(target);
+                async move {
+                    if target == WarmTarget::Statistics {
+                        Err(anyhow::anyhow!("database unavailable"))
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(failed, 1);
+        assert_eq!(attempted, WarmTarget::ALL.to_vec());
+        assert_eq!(warm_count(&metrics, "statistics", "failure").as_deref(), Some("1"));
+        for target in ["featured_markets", "platform_stats", "health"] {
+            assert_eq!(
+                warm_count(&metrics, target, "success").as_deref(),
+                Some("1"),
+                "{target}"
+            );
+        }
+
+        // Only the failed target is retried on the next tick.
+        let mut retried = Vec::new();
+        warmer
+            .warm_due(&metrics, now + Duration::from_secs(1), |target| {
+                retried.push(target);
+                async { Ok(()) }
+            })
+            .await;
+        assert_eq!(retried, vec![WarmTarget::Statistics]);
+    }
+
+    #[tokio::test]
+    async fn target_is_rewarmed_within_one_interval_of_expiry() {
+        let metrics = Metrics::new().unwrap();
+        let interval = Duration::from_secs(5);
+        let ttls = CacheTtls { health: Duration::from_secs(40), ..CacheTtls::default() };
+        let mut warmer = CacheWarmer::new(&[WarmTarget::Health], interval, ttls.clone());
+        let start = Instant::now();
+        warmer.warm_due(&metrics, start, |_| async { Ok(()) }).await;
+
+        let mut runs = 0;
+        let early = start + ttls.health - interval - Duration::from_secs(1);
+        warmer
+            .warm_due(&metrics, early, |_| {
+                runs += 1;
+                async { Ok(()) }
+            })
+            .await;
+        assert_eq!(runs, 0, "entry still has more than one interval left");
+
+        warmer
+            .warm_due(&metrics, start + ttls.health - interval, |_| {
+                runs += 1;
+                async { Ok(()) }
+            })
+            .await;
+        assert_eq!(runs, 1, "entry would lapse before the next tick");
+    }
+}
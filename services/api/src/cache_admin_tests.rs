@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod cache_admin_tests {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+        routing::{delete, get},
+        Router,
+    };
+    use serde_json::Value;
+    use std::{sync::Arc, time::Duration};
+    use tower::ServiceExt;
+
+    use crate::handlers::{cache_entry, cache_invalidate, cache_keys};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Keys the tests write, under the default `api:v1:` allowlist entry.
+    const SEED_PREFIX: &str = "api:v1:cache-admin-test";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/admin/cache/keys", get(cache_keys))
+            .route("/admin/cache/entry", get(cache_entry))
+            .route("/admin/cache", delete(cache_invalidate))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, method: Method, uri: &str) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state))
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn seed(state: &crate::AppState) {
+        for n in 1..=3 {
+            state
+                .cache
+                .set_json(
+                    &format!("{SEED_PREFIX}:{n}"),
+                    &serde_json::json!({ "n": n }),
+                    Duration::from_secs(300),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    async fn seeded_keys(state: &crate::AppState) -> usize {
+        let (keys, _) = state
+            .cache
+            .scan_keys(&format!("{SEED_PREFIX}:*"), 100)
+            .await
+            .unwrap();
+        keys.len()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Patterns and keys broader than, or outside, the allowed prefixes are a
+    /// 400 on all three endpoints, and nothing is deleted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_patterns_outside_allowlist_are_rejected() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        for pattern in ["*", "api:*", "api:v1*", "idempotency:*", "global:*"] {
+            let encoded = pattern.replace('*', "%2A");
+            let (status, body) = send(
+                &state,
+                Method::DELETE,
+                &format!("/admin/cache?pattern={encoded}&confirm=true"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{pattern}");
+            assert_eq!(body["code"], "CACHE_PATTERN_NOT_ALLOWED");
+            assert!(body["details"]["allowed_prefixes"].is_array());
+
+            let (status, _) = send(
+                &state,
+                Method::GET,
+                &format!("/admin/cache/keys?pattern={encoded}"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{pattern}");
+        }
+        let (status, body) = send(
+            &state,
+            Method::GET,
+            "/admin/cache/entry?key=abuse:block:1.2.3.4",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "CACHE_PATTERN_NOT_ALLOWED");
+
+        assert_eq!(seeded_keys(&state).await, 3);
+        state
+            .cache
+            .del_by_pattern(&format!("{SEED_PREFIX}:*"))
+            .await
+            .unwrap();
+    }
+
+    /// A pattern delete without `confirm=true` is refused and leaves the keys;
+    /// with it, every match is deleted and counted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_delete_requires_confirm() {
+        let state = build_test_state().await;
+        seed(&state).await;
+        let uri = format!("/admin/cache?pattern={SEED_PREFIX}:%2A");
+
+        let (status, body) = send(&state, Method::DELETE, &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "CONFIRMATION_REQUIRED");
+        let (status, _) = send(&state, Method::DELETE, &format!("{uri}&confirm=false")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(seeded_keys(&state).await, 3);
+
+        let (status, body) = send(&state, Method::DELETE, &format!("{uri}&confirm=true")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["deleted"], 3);
+        assert_eq!(seeded_keys(&state).await, 0);
+    }
+
+    /// Listing caps at `limit` and flags truncation; an entry comes back as
+    /// parsed JSON with its TTL, and a missing key is a 404.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_and_read_entries() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (status, body) = send(
+            &state,
+            Method::GET,
+            &format!("/admin/cache/keys?pattern={SEED_PREFIX}:%2A&limit=2"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["keys"].as_array().unwrap().len(), 2);
+        assert_eq!(body["truncated"], true);
+
+        let (status, body) = send(
+            &state,
+            Method::GET,
+            &format!("/admin/cache/entry?key={SEED_PREFIX}:2"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["value"]["n"], 2);
+        let ttl = body["ttl_secs"].as_i64().unwrap();
+        assert!((1..=300).contains(&ttl));
+
+        let (status, _) = send(
+            &state,
+            Method::GET,
+            &format!("/admin/cache/entry?key={SEED_PREFIX}:missing"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        state
+            .cache
+            .del_by_pattern(&format!("{SEED_PREFIX}:*"))
+            .await
+            .unwrap();
+    }
+
+}
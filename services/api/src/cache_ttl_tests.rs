@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod cache_ttl_tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        cache::keys,
+        handlers::{content, statistics},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/statistics", get(statistics))
+            .route("/content", get(content))
+            .with_state(state)
+    }
+
+    /// GET `uri` and return the status and `Cache-Control` header.
+    async fn get_cache_control(state: &Arc<crate::AppState>, uri: &str) -> (StatusCode, String) {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let cache_control = response
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        (response.status(), cache_control)
+    }
+
+    /// Remaining Redis TTL of `key` in seconds.
+    async fn remaining_ttl(state: &crate::AppState, key: &str) -> i64 {
+        let (_, ttl) = state
+            .cache
+            .get_raw_with_ttl(key)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("{key} was not cached"));
+        ttl.unwrap_or_else(|| panic!("{key} has no expiry"))
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// `CACHE_TTL_*_SECS` overrides reach both the response cache written by
+    /// the handler and the query cache written by `Database`.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_env_overrides_take_effect() {
+        std::env::set_var("CACHE_TTL_STATISTICS_SECS", "7");
+        std::env::set_var("CACHE_TTL_CONTENT_SECS", "11");
+        let state = build_test_state().await;
+        std::env::remove_var("CACHE_TTL_STATISTICS_SECS");
+        std::env::remove_var("CACHE_TTL_CONTENT_SECS");
+        assert_eq!(state.config.cache_ttls.statistics.as_secs(), 7);
+        assert_eq!(state.config.cache_ttls.content.as_secs(), 11);
+
+        let limit = crate::pagination::PaginationQuery { limit: Some(3), cursor: None }.limit();
+        for key in [keys::api_statistics(), keys::dbq_statistics(), keys::api_content(limit), keys::dbq_content(limit)] {
+            state.cache.del(&key).await.unwrap();
+        }
+
+        let (status, cache_control) = get_cache_control(&state, "/statistics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(cache_control.contains("max-age=7"), "{cache_control}");
+        for key in [keys::api_statistics(), keys::dbq_statistics()] {
+            let ttl = remaining_ttl(&state, &key).await;
+            assert!((1..=7).contains(&ttl), "{key}: {ttl}s");
+        }
+
+        let (status, cache_control) = get_cache_control(&state, "/content?limit=3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(cache_control.contains("max-age=11"), "{cache_control}");
+        for key in [keys::api_content(limit), keys::dbq_content(limit)] {
+            let ttl = remaining_ttl(&state, &key).await;
+            assert!((1..=11).contains(&ttl), "{key}: {ttl}s");
+        }
+    }
+
+}
@@ -0,0 +1,357 @@
+//! Bulk newsletter campaigns.
+//!
+//! An admin creates a campaign (a template plus a payload shared by every
+//! recipient) as a draft, then launches it. Launching snapshots the audience
+//! size and marks the campaign `sending`; the supervised [`run`] loop then
+//! enqueues `email_jobs` for it in batches, pacing them with a [`Throttle`] so
+//! the queue never receives more than `CAMPAIGN_MAX_PER_MINUTE` campaign jobs
+//! a minute and SendGrid's rate limits are respected downstream.
+//!
+//! The audience is every confirmed subscriber who has not unsubscribed, is
+//! not on the suppression list and has not turned off [`CATEGORY`] mail; see
+//! [`crate::db::Database::campaign_next_recipients`]. Each job carries the
+//! campaign id, and the email worker bumps the campaign's sent, failed and
+//! skipped counters as jobs finish. Cancelling stops further batches; jobs
+//! already enqueued still go out.
+//!
+//! Batches are walked in email order from a cursor stored on the campaign
+//! row. The cursor only moves if it still holds the value the batch was read
+//! from, so two instances racing on one campaign never enqueue a recipient
+//! twice. Each instance paces itself, so the effective rate scales with the
+//! number of instances running the sender.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::Config,
+    db::Database,
+    newsletter::{self, EmailCategory},
+    AppState,
+};
+
+/// Preference category a recipient must not have turned off.
+pub const CATEGORY: EmailCategory = EmailCategory::Product;
+
+/// Queue priority of campaign jobs: below transactional mail (priority 0),
+/// so confirmations and alerts are not stuck behind a large campaign.
+pub const PRIORITY: i32 = -1;
+
+/// Longest accepted campaign name, in characters.
+pub const MAX_NAME_CHARS: usize = 200;
+
+/// How long the sender waits before looking again when nothing is sending.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const WORKER_NAME: &str = "campaign_sender";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignStatus {
+    /// Created, not launched; can still be cancelled.
+    Draft,
+    /// Launched; batches are being enqueued.
+    Sending,
+    /// Every batch is enqueued. Jobs may still be in the email queue.
+    Completed,
+    /// Cancelled before every batch was enqueued.
+    Cancelled,
+}
+
+impl CampaignStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Draft => "draft",
+            Self::Sending => "sending",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "draft" => Some(Self::Draft),
+            "sending" => Some(Self::Sending),
+            "completed" => Some(Self::Completed),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Campaign {
+    pub id: i64,
+    pub name: String,
+    pub template_name: String,
+    /// Template data shared by every recipient.
+    #[schema(value_type = Object)]
+    pub payload: Value,
+    pub status: CampaignStatus,
+    /// Audience size when the campaign was launched; 0 for a draft.
+    pub total_recipients: i32,
+    /// Jobs enqueued so far.
+    pub enqueued: i32,
+    /// Jobs the email worker has sent.
+    pub sent: i32,
+    /// Jobs that exhausted their retries. A dead-lettered job an admin
+    /// retries is counted again when it finishes.
+    pub failed: i32,
+    /// Jobs dropped at send time because the address was suppressed, or
+    /// turned campaign mail off, after its batch was enqueued.
+    pub skipped: i32,
+    pub created_at: DateTime<Utc>,
+    pub launched_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    /// Last recipient email enqueued; batches resume after it.
+    #[serde(skip)]
+    pub cursor: Option<String>,
+}
+
+/// How a campaign job finished, as counted on the campaign row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignOutcome {
+    Sent,
+    Failed,
+    Skipped,
+}
+
+impl CampaignOutcome {
+    /// The `campaigns` counter column this outcome increments.
+    pub fn column(self) -> &'static str {
+        match self {
+            Self::Sent => "sent_count",
+            Self::Failed => "failed_count",
+            Self::Skipped => "skipped_count",
+        }
+    }
+}
+
+/// Batch size and pause between batches that keep the enqueue rate at or
+/// under `max_per_minute`. A batch larger than the per-minute cap is shrunk
+/// to it, so no single batch overshoots the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttle {
+    pub batch_size: u32,
+    pub interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(batch_size: u32, max_per_minute: u32) -> Self {
+        let max_per_minute = max_per_minute.max(1);
+        let batch_size = batch_size.clamp(1, max_per_minute);
+        let interval =
+            Duration::from_millis(60_000 * u64::from(batch_size) / u64::from(max_per_minute));
+        Self {
+            batch_size,
+            interval,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.campaign_batch_size, config.campaign_max_per_minute)
+    }
+}
+
+/// Trim a campaign name and check it is 1..=[`MAX_NAME_CHARS`] characters.
+pub fn normalize_name(raw: &str) -> Result<String, String> {
+    let name = raw.trim();
+    if name.is_empty() {
+        return Err("name must not be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_CHARS {
+        return Err(format!("name must be at most {MAX_NAME_CHARS} characters"));
+    }
+    Ok(name.to_string())
+}
+
+/// Template data for one recipient: the campaign payload plus `email` and
+/// `preferences_url`, which take precedence over payload keys of the same
+/// name. A payload that is not an object contributes nothing.
+pub fn recipient_data(payload: &Value, email: &str, preferences_url: &str) -> Value {
+    let mut data = match payload {
+        Value::Object(map) => map.clone(),
+        _ => Map::new(),
+    };
+    data.insert("email".to_string(), Value::from(email));
+    data.insert("preferences_url".to_string(), Value::from(preferences_url));
+    Value::Object(data)
+}
+
+/// What one [`send_next_batch`] call did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOutcome {
+    /// This many jobs were enqueued.
+    Enqueued(usize),
+    /// The audience was exhausted and the campaign is now `completed`.
+    Completed,
+    /// The campaign stopped sending (cancelled) or another instance enqueued
+    /// this batch first; nothing was written.
+    Skipped,
+}
+
+/// Enqueue the next `batch_size` recipients of `campaign`, or mark it
+/// completed when none are left.
+pub async fn send_next_batch(
+    db: &Database,
+    config: &Config,
+    campaign: &Campaign,
+    batch_size: u32,
+) -> anyhow::Result<BatchOutcome> {
+    let recipients = db
+        .campaign_next_recipients(campaign.cursor.as_deref(), batch_size)
+        .await?;
+    let Some((_, last_email)) = recipients.last() else {
+        return Ok(if db.campaign_complete(campaign.id).await? {
+            BatchOutcome::Completed
+        } else {
+            BatchOutcome::Skipped
+        });
+    };
+
+    let secret = config.unsubscribe_signing_secret.as_deref();
+    let jobs: Vec<(String, Value)> = recipients
+        .iter()
+        .map(|(subscriber_id, email)| {
+            let preferences_url =
+                newsletter::preferences_url(&config.base_url, *subscriber_id, secret);
+            (
+                email.clone(),
+                recipient_data(&campaign.payload, email, &preferences_url),
+            )
+        })
+        .collect();
+
+    let enqueued = db
+        .campaign_enqueue_batch(campaign, last_email, &jobs)
+        .await?;
+    Ok(if enqueued {
+        BatchOutcome::Enqueued(jobs.len())
+    } else {
+        BatchOutcome::Skipped
+    })
+}
+
+/// Enqueue one batch of the longest-running campaign that is still sending.
+/// Returns `None` when no campaign is sending.
+pub async fn step(state: &AppState, batch_size: u32) -> anyhow::Result<Option<BatchOutcome>> {
+    let Some(campaign) = state.db.campaign_next_sending().await? else {
+        return Ok(None);
+    };
+    let outcome = send_next_batch(&state.db, &state.config, &campaign, batch_size).await?;
+    match outcome {
+        BatchOutcome::Enqueued(n) => {
+            tracing::info!(
+                campaign_id = campaign.id,
+                enqueued = n,
+                "[campaign] batch enqueued"
+            )
+        }
+        BatchOutcome::Completed => {
+            tracing::info!(campaign_id = campaign.id, "[campaign] every batch enqueued")
+        }
+        BatchOutcome::Skipped => {}
+    }
+    Ok(Some(outcome))
+}
+
+/// Enqueue campaign batches until `shutdown` fires: one batch per
+/// [`Throttle::interval`] while a campaign is sending, one poll every few
+/// seconds otherwise. Batches of all campaigns share the one throttle.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    let throttle = Throttle::from_config(&state.config);
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    loop {
+        let pause = match step(&state, throttle.batch_size).await {
+            Ok(Some(BatchOutcome::Completed)) => Duration::ZERO,
+            Ok(Some(_)) => throttle.interval,
+            Ok(None) => IDLE_POLL_INTERVAL,
+            Err(e) => {
+                tracing::warn!("[campaign] batch error: {e}");
+                IDLE_POLL_INTERVAL
+            }
+        };
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(pause) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn throttle_spreads_batches_over_the_minute() {
+        let throttle = Throttle::new(100, 600);
+        assert_eq!(throttle.batch_size, 100);
+        assert_eq!(throttle.interval, Duration::from_secs(10));
+
+        // One batch per minute when the batch is the whole budget.
+        assert_eq!(Throttle::new(600, 600).interval, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn throttle_never_exceeds_the_per_minute_cap() {
+        // An oversized batch is shrunk to the cap.
+        let throttle = Throttle::new(5_000, 1_000);
+        assert_eq!(throttle.batch_size, 1_000);
+        assert_eq!(throttle.interval, Duration::from_secs(60));
+
+        for (batch, per_minute) in [(1, 1), (7, 30), (250, 1_000), (999, 1_000)] {
+            let throttle = Throttle::new(batch, per_minute);
+            let batches_per_minute = 60_000 / throttle.interval.as_millis().max(1);
+            assert!(
+                batches_per_minute * u128::from(throttle.batch_size) <= u128::from(per_minute),
+                "{batch}/{per_minute}"
+            );
+        }
+
+        // Zeros are clamped rather than dividing by zero or stalling.
+        assert_eq!(Throttle::new(0, 0), Throttle::new(1, 1));
+    }
+
+    #[test]
+    fn recipient_fields_override_the_payload() {
+        let payload = json!({ "headline": "v2 is live", "email": "spoofed@example.com" });
+        let data = recipient_data(&payload, "a@example.com", "https://example.com/p");
+        assert_eq!(data["headline"], "v2 is live");
+        assert_eq!(data["email"], "a@example.com");
+        assert_eq!(data["preferences_url"], "https://example.com/p");
+
+        let data = recipient_data(&json!(["not", "an", "object"]), "a@example.com", "");
+        assert_eq!(
+            data,
+            json!({ "email": "a@example.com", "preferences_url": "" })
+        );
+    }
+
+    #[test]
+    fn names_are_trimmed_and_bounded() {
+        assert_eq!(normalize_name("  Launch  ").unwrap(), "Launch");
+        assert!(normalize_name("   ").is_err());
+        assert!(normalize_name(&"x".repeat(MAX_NAME_CHARS)).is_ok());
+        assert!(normalize_name(&"x".repeat(MAX_NAME_CHARS + 1)).is_err());
+    }
+
+    #[test]
+    fn status_labels_round_trip() {
+        for status in [
+            CampaignStatus::Draft,
+            CampaignStatus::Sending,
+            CampaignStatus::Completed,
+            CampaignStatus::Cancelled,
+        ] {
+            assert_eq!(CampaignStatus::parse(status.label()), Some(status));
+        }
+        assert_eq!(CampaignStatus::parse("paused"), None);
+    }
+}
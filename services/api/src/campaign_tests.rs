@@ -0,0 +1,357 @@
+#[cfg(test)]
+mod campaign_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+        Router,
+    };
+    use serde_json::{json, Value};
+    use sqlx::Row;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::campaign::{self, BatchOutcome, Campaign, CampaignStatus};
+    use crate::db::NewsletterConfirmation;
+    use crate::email::types::SuppressionType;
+    use crate::handlers::campaign_create;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Subscribers and campaign names live in a reserved range so cleanup
+    /// never touches rows created by other tests or the seed data.
+    const PREFIX: &str = "campaign-636";
+
+    fn email(n: usize) -> String {
+        format!("{PREFIX}-{n:02}@example.com")
+    }
+
+    /// A confirmed, subscribed address.
+    async fn seed_subscriber(state: &crate::AppState, address: &str) {
+        let token = format!("{address}-token");
+        state
+            .db
+            .newsletter_upsert_pending(address, "test", &token)
+            .await
+            .unwrap();
+        let confirmed = state
+            .db
+            .newsletter_confirm_by_token(&token, state.config.newsletter_token_ttl_secs)
+            .await
+            .unwrap();
+        assert_eq!(confirmed, NewsletterConfirmation::Confirmed);
+    }
+
+    /// Three eligible recipients (1-3) and one of each exclusion: suppressed
+    /// (4), product mail off (5), unsubscribed (6) and never confirmed (7).
+    async fn seed_audience(state: &crate::AppState) {
+        for n in 1..=6 {
+            seed_subscriber(state, &email(n)).await;
+        }
+        state
+            .db
+            .email_add_suppression(&email(4), SuppressionType::Manual.as_str(), None, None)
+            .await
+            .unwrap();
+        state
+            .db
+            .newsletter_set_preferences(&email(5), &[(campaign::CATEGORY, false)])
+            .await
+            .unwrap();
+        state.db.newsletter_unsubscribe(&email(6)).await.unwrap();
+        state
+            .db
+            .newsletter_upsert_pending(&email(7), "test", "campaign-636-pending")
+            .await
+            .unwrap();
+    }
+
+    async fn launched(state: &crate::AppState, name: &str) -> Campaign {
+        let created = state
+            .db
+            .campaign_create(
+                &format!("{PREFIX} {name}"),
+                "newsletter_test",
+                &json!({ "headline": "Hi" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.status, CampaignStatus::Draft);
+        state
+            .db
+            .campaign_launch(created.id)
+            .await
+            .unwrap()
+            .expect("draft launches")
+    }
+
+    async fn reload(state: &crate::AppState, id: i64) -> Campaign {
+        state.db.campaign_get(id).await.unwrap().unwrap()
+    }
+
+    /// Recipients in the reserved range with a job for `campaign_id`.
+    async fn job_recipients(state: &crate::AppState, campaign_id: i64) -> Vec<String> {
+        sqlx::query(
+            "SELECT recipient_email FROM email_jobs \
+             WHERE campaign_id = $1 AND recipient_email LIKE $2 \
+             ORDER BY recipient_email",
+        )
+        .bind(campaign_id)
+        .bind(format!("{PREFIX}-%"))
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.get("recipient_email"))
+        .collect()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{PREFIX}%");
+        for statement in [
+            "DELETE FROM email_jobs WHERE campaign_id IN (SELECT id FROM campaigns WHERE name LIKE $1)",
+            "DELETE FROM campaigns WHERE name LIKE $1",
+            "DELETE FROM email_jobs WHERE recipient_email LIKE $1",
+            "DELETE FROM email_suppressions WHERE email LIKE $1",
+            "DELETE FROM newsletter_preferences WHERE email LIKE $1",
+            "DELETE FROM newsletter_subscribers WHERE email LIKE $1",
+        ] {
+            sqlx::query(statement)
+                .bind(&pattern)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Batches walk the audience until it is exhausted: every eligible
+    /// subscriber gets exactly one job and nobody excluded gets one. A batch
+    /// read with a stale cursor writes nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_batches_reach_exactly_the_eligible_audience() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        seed_audience(&state).await;
+
+        let campaign = launched(&state, "audience").await;
+        assert_eq!(campaign.status, CampaignStatus::Sending);
+        assert!(campaign.total_recipients >= 3);
+
+        let first = campaign::send_next_batch(&state.db, &state.config, &campaign, 2)
+            .await
+            .unwrap();
+        assert_eq!(first, BatchOutcome::Enqueued(2));
+        // `campaign` still holds the pre-batch cursor.
+        assert_eq!(
+            campaign::send_next_batch(&state.db, &state.config, &campaign, 2)
+                .await
+                .unwrap(),
+            BatchOutcome::Skipped
+        );
+
+        let mut batches = 0;
+        loop {
+            let current = reload(&state, campaign.id).await;
+            match campaign::send_next_batch(&state.db, &state.config, &current, 2)
+                .await
+                .unwrap()
+            {
+                BatchOutcome::Completed => break,
+                outcome => assert!(matches!(outcome, BatchOutcome::Enqueued(_))),
+            }
+            batches += 1;
+            assert!(batches < 10_000, "campaign never completed");
+        }
+
+        let done = reload(&state, campaign.id).await;
+        assert_eq!(done.status, CampaignStatus::Completed);
+        assert!(done.completed_at.is_some());
+        assert_eq!(done.enqueued, done.total_recipients);
+        assert_eq!(
+            job_recipients(&state, campaign.id).await,
+            vec![email(1), email(2), email(3)]
+        );
+
+        let job: (String, Value, i32) = {
+            let row = sqlx::query(
+                "SELECT job_type, template_data, priority FROM email_jobs \
+                 WHERE campaign_id = $1 AND recipient_email = $2",
+            )
+            .bind(campaign.id)
+            .bind(email(1))
+            .fetch_one(&state.db.pool())
+            .await
+            .unwrap();
+            (
+                row.get("job_type"),
+                row.get("template_data"),
+                row.get("priority"),
+            )
+        };
+        assert_eq!(job.0, "campaign");
+        assert_eq!(job.1["headline"], "Hi");
+        assert_eq!(job.1["email"], email(1));
+        assert!(job.1["preferences_url"]
+            .as_str()
+            .unwrap()
+            .contains("token="));
+        assert_eq!(job.2, campaign::PRIORITY);
+
+        cleanup(&state).await;
+    }
+
+    /// Cancelling stops further batches and keeps what was enqueued; a
+    /// cancelled campaign can neither be relaunched nor cancelled again.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_cancel_stops_further_batches() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        seed_audience(&state).await;
+
+        let campaign = launched(&state, "cancel").await;
+        campaign::send_next_batch(&state.db, &state.config, &campaign, 1)
+            .await
+            .unwrap();
+        let cancelled = state
+            .db
+            .campaign_cancel(campaign.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cancelled.status, CampaignStatus::Cancelled);
+        assert_eq!(cancelled.enqueued, 1);
+
+        assert_eq!(
+            campaign::send_next_batch(&state.db, &state.config, &cancelled, 1)
+                .await
+                .unwrap(),
+            BatchOutcome::Skipped
+        );
+        assert_eq!(reload(&state, campaign.id).await.enqueued, 1);
+        assert!(state
+            .db
+            .campaign_launch(campaign.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(state
+            .db
+            .campaign_cancel(campaign.id)
+            .await
+            .unwrap()
+            .is_none());
+
+        cleanup(&state).await;
+    }
+
+    /// Finished jobs bump the campaign's sent, skipped and failed counters.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_job_outcomes_update_campaign_counters() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        seed_audience(&state).await;
+
+        let campaign = launched(&state, "counters").await;
+        campaign::send_next_batch(&state.db, &state.config, &campaign, 3)
+            .await
+            .unwrap();
+        let ids: Vec<uuid::Uuid> = sqlx::query("SELECT id FROM email_jobs WHERE campaign_id = $1")
+            .bind(campaign.id)
+            .fetch_all(&state.db.pool())
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| r.get("id"))
+            .collect();
+        assert_eq!(ids.len(), 3);
+        let mut jobs = Vec::new();
+        for id in ids {
+            let job = state.db.email_get_job(id).await.unwrap().unwrap();
+            assert_eq!(job.campaign_id, Some(campaign.id));
+            jobs.push(job);
+        }
+
+        state
+            .email_queue
+            .mark_completed(&jobs[0], Some("campaign-636-message".to_string()))
+            .await
+            .unwrap();
+        state
+            .email_queue
+            .mark_completed(&jobs[1], None)
+            .await
+            .unwrap();
+        let mut last_attempt = jobs[2].clone();
+        last_attempt.attempts = last_attempt.max_attempts - 1;
+        state
+            .email_queue
+            .mark_failed(&last_attempt, "campaign-636 provider error")
+            .await
+            .unwrap();
+
+        let counted = reload(&state, campaign.id).await;
+        assert_eq!((counted.sent, counted.skipped, counted.failed), (1, 1, 1));
+
+        cleanup(&state).await;
+    }
+
+    /// Creation rejects an empty name and a template that does not render.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_create_validates_name_and_template() {
+        let state = build_state().await;
+        let app = Router::new()
+            .route("/admin/campaigns", post(campaign_create))
+            .with_state(Arc::clone(&state));
+
+        for (body, code) in [
+            (
+                json!({ "name": "  ", "template_name": "newsletter_confirmation" }),
+                "BAD_REQUEST",
+            ),
+            (
+                json!({ "name": format!("{PREFIX} bad"), "template_name": "campaign-636-missing" }),
+                "INVALID_CAMPAIGN_TEMPLATE",
+            ),
+        ] {
+            let request = Request::builder()
+                .method("POST")
+                .uri("/admin/campaigns")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let error: Value = serde_json::from_slice(&bytes).unwrap();
+            assert_eq!(error["code"], code);
+        }
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config
+            .unsubscribe_signing_secret
+            .get_or_insert_with(|| "campaign-test-signing-secret".to_string());
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -0,0 +1,73 @@
+//! Market categories.
+//!
+//! `GET /api/v1/categories` lists every row of `categories` with its
+//! active-market count and total volume, aggregated from `markets.category`.
+//! The listing is cached for [`crate::config::CacheTtls::categories`] and
+//! dropped whenever an admin creates, edits, or deletes a category.
+//! `GET /api/v1/markets` rejects a `category` filter that is not in the table
+//! with a 400.
+
+use serde::{Deserialize, Serialize};
+
+/// Column widths of `categories.slug` and `categories.name`.
+pub const MAX_SLUG_LEN: usize = 64;
+pub const MAX_NAME_LEN: usize = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Category {
+    /// Stable identifier; the value of `markets.category`.
+    pub slug: String,
+    /// Display name.
+    pub name: String,
+    pub is_featured: bool,
+    pub active_market_count: i64,
+    /// Summed `total_volume` of every non-deleted market in the category.
+    pub total_volume: f64,
+}
+
+/// Slugs are lowercase ASCII letters and digits in hyphen-separated words,
+/// e.g. `us-politics`; `chk_categories_slug` enforces the same rule.
+pub fn validate_slug(slug: &str) -> Result<(), String> {
+    if slug.is_empty() || slug.len() > MAX_SLUG_LEN {
+        return Err(format!("slug must be 1-{MAX_SLUG_LEN} characters"));
+    }
+    let valid_words = slug
+        .split('-')
+        .all(|word| !word.is_empty() && word.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()));
+    if !valid_words {
+        return Err("slug must be lowercase letters and digits separated by single hyphens".to_string());
+    }
+    Ok(())
+}
+
+/// Trimmed display name, or an error when it is blank or too long.
+pub fn normalize_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("name must be 1-{MAX_NAME_LEN} characters"));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_are_lowercase_hyphenated_words() {
+        for slug in ["crypto", "us-politics", "f1", "world-cup-2026"] {
+            assert!(validate_slug(slug).is_ok(), "{slug}");
+        }
+        let too_long = "a".repeat(MAX_SLUG_LEN + 1);
+        for slug in ["", "Crypto", "us_politics", "-crypto", "crypto-", "us--politics", "café", too_long.as_str()] {
+            assert!(validate_slug(slug).is_err(), "{slug}");
+        }
+    }
+
+    #[test]
+    fn names_are_trimmed_and_bounded() {
+        assert_eq!(normalize_name("  US Politics ").unwrap(), "US Politics");
+        assert!(normalize_name("   ").is_err());
+        assert!(normalize_name(&"x".repeat(MAX_NAME_LEN + 1)).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+#[cfg(test)]
+mod category_tests {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+        routing::{get, patch, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::handlers::{categories, category_create, category_delete, category_update, list_markets};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_IDS: [i64; 3] = [9291, 9292, 9293];
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/categories", get(categories))
+            .route("/admin/categories", post(category_create))
+            .route("/admin/categories/:slug", patch(category_update).delete(category_delete))
+            .route("/markets", get(list_markets))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let builder = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    /// A slug no other test run uses.
+    fn unique_slug() -> String {
+        format!("test-{}", uuid::Uuid::new_v4().simple())
+    }
+
+    fn find<'a>(listing: &'a Value, slug: &str) -> Option<&'a Value> {
+        listing.as_array().unwrap().iter().find(|c| c["slug"] == slug)
+    }
+
+    async fn seed_markets(state: &crate::AppState, slug: &str) {
+        cleanup(state, slug).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, category) \
+             VALUES \
+                (9291, 'Cat A', 'active',   100, NOW() + INTERVAL '1 day', NOW(), $1), \
+                (9292, 'Cat B', 'active',   250, NOW() + INTERVAL '2 day', NOW(), $1), \
+                (9293, 'Cat C', 'resolved', 50,  NOW() - INTERVAL '1 day', NOW(), $1)",
+        )
+        .bind(slug)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState, slug: &str) {
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_IDS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM categories WHERE slug = $1")
+            .bind(slug)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Admin create, rename, feature, and delete each show up in the cached
+    /// listing straight away, with counts aggregated from `markets`.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_writes_refresh_cached_listing() {
+        let state = build_test_state().await;
+        let slug = unique_slug();
+        seed_markets(&state, &slug).await;
+
+        // Prime the cache before the category exists.
+        let (status, listing) = send(&state, Method::GET, "/categories", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(find(&listing, &slug).is_none());
+
+        let (status, created) = send(
+            &state,
+            Method::POST,
+            "/admin/categories",
+            Some(json!({ "slug": slug, "name": "  Test Category " })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["name"], "Test Category");
+        assert_eq!(created["is_featured"], false);
+        assert_eq!(created["active_market_count"], 2);
+        assert_eq!(created["total_volume"], 400.0);
+
+        let (_, listing) = send(&state, Method::GET, "/categories", None).await;
+        assert!(find(&listing, &slug).is_some(), "create must invalidate the listing");
+
+        let (status, updated) = send(
+            &state,
+            Method::PATCH,
+            &format!("/admin/categories/{slug}"),
+            Some(json!({ "name": "Renamed", "is_featured": true })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["name"], "Renamed");
+        assert_eq!(updated["is_featured"], true);
+
+        let (_, listing) = send(&state, Method::GET, "/categories", None).await;
+        assert_eq!(find(&listing, &slug).unwrap()["name"], "Renamed");
+        assert_eq!(listing[0]["is_featured"], true, "featured categories come first");
+
+        let (status, _) = send(&state, Method::DELETE, &format!("/admin/categories/{slug}"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, listing) = send(&state, Method::GET, "/categories", None).await;
+        assert!(find(&listing, &slug).is_none());
+
+        cleanup(&state, &slug).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_rejects_bad_slug_duplicate_and_unknown() {
+        let state = build_test_state().await;
+        let slug = unique_slug();
+        cleanup(&state, &slug).await;
+
+        let (status, body) = send(
+            &state,
+            Method::POST,
+            "/admin/categories",
+            Some(json!({ "slug": "Not A Slug", "name": "x" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+
+        let create = json!({ "slug": slug, "name": "Dup" });
+        let (status, _) = send(&state, Method::POST, "/admin/categories", Some(create.clone())).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, body) = send(&state, Method::POST, "/admin/categories", Some(create)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "CONFLICT");
+
+        let (status, _) = send(&state, Method::PATCH, &format!("/admin/categories/{slug}"), Some(json!({}))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let missing = unique_slug();
+        let (status, _) = send(
+            &state,
+            Method::PATCH,
+            &format!("/admin/categories/{missing}"),
+            Some(json!({ "is_featured": true })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = send(&state, Method::DELETE, &format!("/admin/categories/{missing}"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        cleanup(&state, &slug).await;
+    }
+
+    /// The market list rejects a category slug that is not in the table
+    /// instead of returning an empty page.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_market_list_rejects_unknown_category() {
+        let state = build_test_state().await;
+        let slug = unique_slug();
+        seed_markets(&state, &slug).await;
+        state
+            .cache
+            .invalidate_tag(&crate::cache::InvalidationTag::CategoryChanged)
+            .await
+            .unwrap();
+
+        let (status, body) = send(&state, Method::GET, &format!("/markets?category={slug}"), None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "UNKNOWN_CATEGORY");
+
+        let (status, _) = send(
+            &state,
+            Method::POST,
+            "/admin/categories",
+            Some(json!({ "slug": slug, "name": "Known" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, body) = send(&state, Method::GET, &format!("/markets?category={slug}&limit=100"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["items"].as_array().unwrap().len(), 3);
+
+        cleanup(&state, &slug).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // DB layer
+    // ---------------------------------------------------------------------------
+
+    /// `category_create` reports a taken slug as `None`; `category_update`
+    /// leaves omitted fields alone; `categories_cached` serves the cached
+    /// listing until the tag is invalidated.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_db_category_crud_and_cache() {
+        let state = build_test_state().await;
+        let db = &state.db;
+        let slug = unique_slug();
+        cleanup(&state, &slug).await;
+        state
+            .cache
+            .invalidate_tag(&crate::cache::InvalidationTag::CategoryChanged)
+            .await
+            .unwrap();
+
+        let before = db.categories_cached().await.unwrap();
+        let created = db.category_create(&slug, "Db Test", false).await.unwrap().unwrap();
+        assert_eq!(created.active_market_count, 0);
+        assert_eq!(created.total_volume, 0.0);
+        assert!(db.category_create(&slug, "Again", true).await.unwrap().is_none());
+
+        let cached = db.categories_cached().await.unwrap();
+        assert_eq!(cached.len(), before.len(), "listing is served from cache until invalidated");
+        state
+            .cache
+            .invalidate_tag(&crate::cache::InvalidationTag::CategoryChanged)
+            .await
+            .unwrap();
+        let fresh = db.categories_cached().await.unwrap();
+        assert!(fresh.iter().any(|c| c.slug == slug));
+
+        let updated = db.category_update(&slug, None, Some(true)).await.unwrap().unwrap();
+        assert_eq!(updated.name, "Db Test");
+        assert!(updated.is_featured);
+        assert!(db.category_update("no-such-category", Some("x"), None).await.unwrap().is_none());
+
+        assert!(db.category_delete(&slug).await.unwrap());
+        assert!(!db.category_delete(&slug).await.unwrap());
+        assert!(db.category_get(&slug).await.unwrap().is_none());
+
+        cleanup(&state, &slug).await;
+    }
+
+}
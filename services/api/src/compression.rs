@@ -1,25 +1,186 @@
-use axum::http::{header, Extensions, HeaderMap, StatusCode, Version};
-use tower_http::compression::CompressionLayer;
-
-type CompressFn = fn(StatusCode, Version, &HeaderMap, &Extensions) -> bool;
-
-fn should_compress(
-    _: StatusCode,
-    _: Version,
-    headers: &HeaderMap,
-    _: &Extensions,
-) -> bool {
-    let ct = headers
-        .get(header::CONTENT_TYPE)
-        .and_then(|h| h.to_str().ok())
-        .unwrap_or("");
-    let ct = ct.split(';').next().unwrap_or(ct).trim();
-    ct == "application/json" || ct.starts_with("text/")
+//! Response compression policy.
+//!
+//! A response is gzip/brotli encoded only when its media type is on the
+//! allowlist (`COMPRESSION_CONTENT_TYPES`) and it is larger than
+//! `COMPRESSION_MIN_SIZE_BYTES`. Small JSON bodies cost more to compress than
+//! they save on the wire. Streamed bodies such as CSV exports have no known
+//! size, so they always pass the size check and are compressed frame by frame
+//! as they stream; the encoder never buffers the whole body.
+
+use axum::http::{header, Response};
+use tower_http::compression::{
+    predicate::{And, Predicate, SizeAbove},
+    CompressionLayer,
+};
+
+/// Smallest body compressed, in bytes.
+pub const DEFAULT_MIN_SIZE: u16 = 1024;
+
+/// Media types compressed by default. An entry ending in `/*` matches every
+/// subtype.
+pub const DEFAULT_CONTENT_TYPES: &[&str] = &["application/json", "text/*"];
+
+/// Never compressed, whatever the allowlist says: a compressed event stream
+/// is held back by the encoder instead of reaching the client per event.
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// Parse a comma-separated allowlist, lowercased, skipping blank entries.
+pub fn parse_content_types(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|t| t.trim().to_ascii_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Media types eligible for compression.
+#[derive(Debug, Clone)]
+pub struct ContentTypeAllowlist {
+    types: Vec<String>,
+}
+
+impl ContentTypeAllowlist {
+    pub fn new(types: Vec<String>) -> Self {
+        Self { types }
+    }
+
+    /// Whether a `Content-Type` value (parameters allowed) is eligible.
+    pub fn allows(&self, content_type: &str) -> bool {
+        let media = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        if media.is_empty() || media == EVENT_STREAM {
+            return false;
+        }
+        self.types.iter().any(|allowed| match allowed.strip_suffix('*') {
+            Some(prefix) => media.starts_with(prefix),
+            None => media == *allowed,
+        })
+    }
+}
+
+impl Predicate for ContentTypeAllowlist {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: http_body::Body,
+    {
+        response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .is_some_and(|ct| self.allows(ct))
+    }
 }
 
-pub fn compression_layer() -> CompressionLayer<CompressFn> {
+pub type CompressionPolicy = And<SizeAbove, ContentTypeAllowlist>;
+
+pub fn compression_layer(min_size: u16, content_types: &[String]) -> CompressionLayer<CompressionPolicy> {
     CompressionLayer::new()
         .gzip(true)
         .br(true)
-        .compress_when(should_compress as CompressFn)
+        .compress_when(
+            SizeAbove::new(min_size).and(ContentTypeAllowlist::new(content_types.to_vec())),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::{Body, Bytes},
+        http::{HeaderValue, Request, StatusCode},
+        response::IntoResponse,
+        routing::get,
+        Json, Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    fn defaults() -> Vec<String> {
+        DEFAULT_CONTENT_TYPES.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn app() -> Router {
+        let small = serde_json::json!({ "status": "ok", "padding": "x".repeat(160) });
+        let large = serde_json::json!({ "items": vec!["market"; 2000] });
+        Router::new()
+            .route("/small", get(move || async move { Json(small) }))
+            .route("/large", get(move || async move { Json(large) }))
+            .route(
+                "/image",
+                get(|| async {
+                    ([(header::CONTENT_TYPE, "image/png")], vec![0u8; 8192]).into_response()
+                }),
+            )
+            .route(
+                "/stream",
+                get(|| async {
+                    let chunks = (0..100).map(|i| Ok::<_, std::io::Error>(Bytes::from(format!("{i},row\r\n"))));
+                    (
+                        [(header::CONTENT_TYPE, "text/csv; charset=utf-8")],
+                        Body::from_stream(futures::stream::iter(chunks)),
+                    )
+                        .into_response()
+                }),
+            )
+            .layer(compression_layer(DEFAULT_MIN_SIZE, &defaults()))
+    }
+
+    async fn get_gzip(uri: &str) -> (StatusCode, Option<HeaderValue>, usize) {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri(uri)
+                    .header(header::ACCEPT_ENCODING, "gzip")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let encoding = response.headers().get(header::CONTENT_ENCODING).cloned();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, encoding, body.len())
+    }
+
+    #[tokio::test]
+    async fn small_json_is_served_uncompressed() {
+        let (status, encoding, len) = get_gzip("/small").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(len > 150 && len < usize::from(DEFAULT_MIN_SIZE), "{len} bytes");
+        assert_eq!(encoding, None);
+    }
+
+    #[tokio::test]
+    async fn large_json_is_compressed() {
+        let (_, encoding, _) = get_gzip("/large").await;
+        assert_eq!(encoding.unwrap(), "gzip");
+    }
+
+    #[tokio::test]
+    async fn types_off_the_allowlist_are_not_compressed() {
+        let (_, encoding, len) = get_gzip("/image").await;
+        assert_eq!(encoding, None);
+        assert_eq!(len, 8192);
+    }
+
+    #[tokio::test]
+    async fn streamed_bodies_of_unknown_size_are_compressed() {
+        let (_, encoding, _) = get_gzip("/stream").await;
+        assert_eq!(encoding.unwrap(), "gzip");
+    }
+
+    #[test]
+    fn allowlist_matches_media_types_and_wildcards() {
+        let list = ContentTypeAllowlist::new(parse_content_types(" application/json , TEXT/*,, "));
+        assert!(list.allows("application/json"));
+        assert!(list.allows("Application/JSON; charset=utf-8"));
+        assert!(list.allows("text/csv; charset=utf-8"));
+        assert!(!list.allows("application/jsonx"));
+        assert!(!list.allows("image/png"));
+        assert!(!list.allows(""));
+        assert!(!list.allows("text/event-stream"));
+    }
 }
@@ -0,0 +1,104 @@
+//! Contact form submissions.
+//!
+//! `POST /api/v1/contact` stores a [`ContactSubmission`] in
+//! `contact_form_submissions` and queues two emails: the
+//! `contact_form_auto_response` to the sender (skipped for suppressed
+//! addresses) and a `contact_form_notification` to the configured ops
+//! address. Admins triage submissions through `new -> answered -> closed`;
+//! see [`ContactStatus::can_transition_to`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Auto-response sent to the person who submitted the form.
+pub const AUTO_RESPONSE_TEMPLATE: &str = "contact_form_auto_response";
+
+/// Internal notification sent to the ops address.
+pub const NOTIFICATION_TEMPLATE: &str = "contact_form_notification";
+
+/// Field limits; `message` matches `chk_contact_message_length`, the others
+/// the column widths. `u64` to match `#[validate(length)]`.
+pub const MAX_NAME_LEN: u64 = 120;
+pub const MAX_SUBJECT_LEN: u64 = 200;
+pub const MAX_MESSAGE_LEN: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactStatus {
+    /// Awaiting a reply.
+    New,
+    /// Ops has replied to the sender.
+    Answered,
+    /// No further action needed.
+    Closed,
+}
+
+impl ContactStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Answered => "answered",
+            Self::Closed => "closed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "new" => Some(Self::New),
+            "answered" => Some(Self::Answered),
+            "closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
+
+    /// Submissions only move forward; a closed submission is final.
+    pub fn can_transition_to(self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::New, Self::Answered) | (Self::New, Self::Closed) | (Self::Answered, Self::Closed)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContactSubmission {
+    pub id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub subject: String,
+    pub message: String,
+    pub status: ContactStatus,
+    pub submitted_at: DateTime<Utc>,
+    pub answered_at: Option<DateTime<Utc>>,
+    /// When the submission was closed.
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_labels_round_trip() {
+        for status in [ContactStatus::New, ContactStatus::Answered, ContactStatus::Closed] {
+            assert_eq!(ContactStatus::parse(status.label()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.label());
+        }
+        assert_eq!(ContactStatus::parse("resolved"), None);
+    }
+
+    #[test]
+    fn submissions_only_move_forward() {
+        use ContactStatus::*;
+        assert!(New.can_transition_to(Answered));
+        assert!(New.can_transition_to(Closed));
+        assert!(Answered.can_transition_to(Closed));
+
+        assert!(!Answered.can_transition_to(New));
+        assert!(!Closed.can_transition_to(New));
+        assert!(!Closed.can_transition_to(Answered));
+        assert!(!New.can_transition_to(New));
+    }
+}
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod contact_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::handlers::{contact_list, contact_set_status, contact_submit};
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Senders live in a reserved range so cleanup never touches rows created
+    /// by other tests.
+    const SENDER_PREFIX: &str = "contact-97";
+    const OPS_EMAIL: &str = "contact-ops-97@example.com";
+    const RATE_LIMIT_MAX: usize = 3;
+
+    fn sender(n: usize) -> String {
+        format!("{SENDER_PREFIX}{n:02}@example.com")
+    }
+
+    /// A fresh client IP per test run so the Redis-backed limiter starts at
+    /// zero.
+    fn client_ip() -> String {
+        let bytes = Uuid::new_v4().into_bytes();
+        format!("10.97.{}.{}", bytes[0], bytes[1])
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/contact", post(contact_submit))
+            .route("/admin/contact", get(contact_list))
+            .route("/admin/contact/:id/status", post(contact_set_status))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    fn submit(ip: &str, body: Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/contact")
+            .header("content-type", "application/json")
+            .header("x-forwarded-for", ip)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    fn form(n: usize) -> Value {
+        json!({
+            "name": "Test Sender",
+            "email": sender(n),
+            "subject": "Question about market payouts",
+            "message": "When are winnings paid out after resolution?",
+        })
+    }
+
+    fn set_status(id: &str, status: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/admin/contact/{id}/status"))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "status": status }).to_string()))
+            .unwrap()
+    }
+
+    async fn submissions(state: &crate::AppState, email: &str) -> Vec<Uuid> {
+        sqlx::query_scalar("SELECT id FROM contact_form_submissions WHERE email = $1")
+            .bind(email)
+            .fetch_all(&state.db.pool())
+            .await
+            .unwrap()
+    }
+
+    /// Templates queued for a submission, in no particular order.
+    async fn queued_templates(state: &crate::AppState, submission_id: Uuid) -> Vec<(String, String)> {
+        sqlx::query_as(
+            "SELECT template_name, recipient_email FROM email_jobs \
+             WHERE template_data->>'submission_id' = $1",
+        )
+        .bind(submission_id.to_string())
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{SENDER_PREFIX}%@example.com");
+        sqlx::query("DELETE FROM email_jobs WHERE recipient_email LIKE $1 OR recipient_email = $2")
+            .bind(&pattern)
+            .bind(OPS_EMAIL)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM email_suppressions WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM contact_form_submissions WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A filled honeypot gets the normal 202 but nothing is stored or queued.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_honeypot_submission_is_dropped() {
+        let state = build_state().await;
+        cleanup(&state).await;
+
+        let mut body = form(0);
+        body["website"] = json!("https://spam.example.com");
+        let (code, response) = send(&state, submit(&client_ip(), body)).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+        assert_eq!(response["success"], true);
+        assert!(submissions(&state, &sender(0)).await.is_empty());
+
+        let queued: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM email_jobs WHERE recipient_email = $1 OR recipient_email = $2",
+        )
+        .bind(sender(0))
+        .bind(OPS_EMAIL)
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(queued, 0);
+
+        cleanup(&state).await;
+    }
+
+    /// Submissions past CONTACT_RATE_LIMIT_MAX from one IP are rejected.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rate_limit_rejects_after_max() {
+        let state = build_state().await;
+        cleanup(&state).await;
+
+        let ip = client_ip();
+        for n in 0..RATE_LIMIT_MAX {
+            let (code, _) = send(&state, submit(&ip, form(n))).await;
+            assert_eq!(code, StatusCode::ACCEPTED, "submission {n} is within the limit");
+        }
+        let (code, body) = send(&state, submit(&ip, form(9))).await;
+        assert_eq!(code, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["code"], "RATE_LIMITED");
+        assert!(submissions(&state, &sender(9)).await.is_empty());
+
+        // Another client is unaffected.
+        let (code, _) = send(&state, submit(&client_ip(), form(9))).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+
+        cleanup(&state).await;
+    }
+
+    /// Suppressed senders get no auto-response, but ops still hears about it.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_suppressed_sender_only_notifies_ops() {
+        let state = build_state().await;
+        cleanup(&state).await;
+
+        let (code, _) = send(&state, submit(&client_ip(), form(1))).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+        let id = submissions(&state, &sender(1)).await[0];
+        let mut queued = queued_templates(&state, id).await;
+        queued.sort();
+        assert_eq!(
+            queued,
+            vec![
+                ("contact_form_auto_response".to_string(), sender(1)),
+                ("contact_form_notification".to_string(), OPS_EMAIL.to_string()),
+            ]
+        );
+
+        state
+            .db
+            .email_add_suppression(&sender(2), "bounce", Some("test"), Some("hard"))
+            .await
+            .unwrap();
+        let (code, _) = send(&state, submit(&client_ip(), form(2))).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+        let id = submissions(&state, &sender(2)).await[0];
+        assert_eq!(
+            queued_templates(&state, id).await,
+            vec![("contact_form_notification".to_string(), OPS_EMAIL.to_string())]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Admins move submissions forward only; backwards moves are 422 and
+    /// unknown ids 404.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_status_transitions() {
+        let state = build_state().await;
+        cleanup(&state).await;
+
+        let (code, _) = send(&state, submit(&client_ip(), form(3))).await;
+        assert_eq!(code, StatusCode::ACCEPTED);
+        let id = submissions(&state, &sender(3)).await[0].to_string();
+
+        let (code, body) = send(
+            &state,
+            Request::builder()
+                .uri("/admin/contact?status=new&limit=100")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::OK);
+        assert!(body["items"].as_array().unwrap().iter().any(|s| s["id"] == id));
+
+        let (code, body) = send(&state, set_status(&id, "answered")).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["status"], "answered");
+        assert!(!body["answered_at"].is_null());
+
+        let (code, body) = send(&state, set_status(&id, "new")).await;
+        assert_eq!(code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "INVALID_STATUS_TRANSITION");
+
+        let (code, body) = send(&state, set_status(&id, "closed")).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["status"], "closed");
+        assert!(!body["resolved_at"].is_null());
+
+        let (code, _) = send(&state, set_status(&Uuid::new_v4().to_string(), "closed")).await;
+        assert_eq!(code, StatusCode::NOT_FOUND);
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.trust_proxy = true;
+        config.trusted_proxy_cidrs = Vec::new();
+        config.contact_rate_limit_max = RATE_LIMIT_MAX;
+        config.contact_ops_email = Some(OPS_EMAIL.to_string());
+        build_test_state_with(config, &[]).await
+    }
+}
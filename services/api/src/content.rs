@@ -0,0 +1,154 @@
+//! Editorial content.
+//!
+//! Admins write markdown articles through `/api/v1/admin/content`. A new
+//! article is a draft unless created with `is_published`; drafts never appear
+//! on the public routes. Publishing stamps `published_at` (unless one is given)
+//! and every write drops the cached `GET /api/v1/content` listings and rendered
+//! pages, so a change shows up on the next request.
+//!
+//! `GET /api/v1/content/:slug` renders the markdown body to HTML and runs it
+//! through [`ammonia`], which strips scripts, event handlers, and any tag or
+//! attribute outside its allow-list. The rendered page is cached for
+//! [`crate::config::CacheTtls::content`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Column widths of `content.title` and `content.category`, and a cap on the
+/// markdown body.
+pub const MAX_TITLE_LEN: usize = 220;
+pub const MAX_CATEGORY_LEN: usize = 80;
+pub const MAX_BODY_LEN: usize = 200_000;
+
+/// A content row as admins see it, draft or published.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContentEntry {
+    pub id: i64,
+    pub slug: String,
+    pub title: String,
+    pub category: String,
+    /// Markdown source.
+    pub body: String,
+    pub is_published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Validated fields for creating or replacing an article.
+#[derive(Debug, Clone)]
+pub struct ContentFields {
+    pub slug: String,
+    pub title: String,
+    pub category: String,
+    pub body: String,
+    pub is_published: bool,
+    /// Publication time to record; `None` keeps an existing one or stamps
+    /// the time of publishing. Ignored for drafts.
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// A published article with its body rendered to sanitized HTML.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RenderedContent {
+    pub slug: String,
+    pub title: String,
+    pub category: String,
+    pub published_at: DateTime<Utc>,
+    pub html: String,
+}
+
+impl RenderedContent {
+    /// Render a published entry; drafts yield `None`.
+    pub fn from_published(entry: ContentEntry) -> Option<Self> {
+        let published_at = entry.published_at.filter(|_| entry.is_published)?;
+        Some(Self {
+            html: render_markdown(&entry.body),
+            slug: entry.slug,
+            title: entry.title,
+            category: entry.category,
+            published_at,
+        })
+    }
+}
+
+/// Content slugs follow the category rule: lowercase letters and digits in
+/// hyphen-separated words, at most [`crate::category::MAX_SLUG_LEN`] long.
+pub fn validate_slug(slug: &str) -> Result<(), String> {
+    crate::category::validate_slug(slug)
+}
+
+/// Trimmed title, or an error when it is blank or too long.
+pub fn normalize_title(title: &str) -> Result<String, String> {
+    normalize_field("title", title, MAX_TITLE_LEN)
+}
+
+/// Trimmed category, or an error when it is blank or too long.
+pub fn normalize_category(category: &str) -> Result<String, String> {
+    normalize_field("category", category, MAX_CATEGORY_LEN)
+}
+
+pub fn validate_body(body: &str) -> Result<(), String> {
+    if body.len() > MAX_BODY_LEN {
+        return Err(format!("body must be at most {MAX_BODY_LEN} bytes"));
+    }
+    Ok(())
+}
+
+fn normalize_field(field: &str, value: &str, max: usize) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() || value.chars().count() > max {
+        return Err(format!("{field} must be 1-{max} characters"));
+    }
+    Ok(value.to_string())
+}
+
+/// Render CommonMark (plus tables and strikethrough) to HTML safe to embed
+/// in a page. Raw HTML in the source passes through the parser and is then
+/// cleaned along with everything else.
+pub fn render_markdown(markdown: &str) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+
+    let mut unsafe_html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, Parser::new_ext(markdown, options));
+    ammonia::clean(&unsafe_html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_is_rendered() {
+        let html = render_markdown("# Title\n\nSome **bold** and [a link](https://example.com).");
+        assert!(html.contains("<h1>Title</h1>"), "{html}");
+        assert!(html.contains("<strong>bold</strong>"), "{html}");
+        assert!(html.contains("href=\"https://example.com\""), "{html}");
+    }
+
+    #[test]
+    fn scripts_and_handlers_are_stripped() {
+        let html = render_markdown(
+            "<script>alert(1)</script>\n\n<img src=\"x.png\" onerror=\"alert(2)\">\n\n[x](javascript:alert(3))",
+        );
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("onerror"), "{html}");
+        assert!(!html.contains("javascript:"), "{html}");
+        assert!(html.contains("<img src=\"x.png\""), "{html}");
+    }
+
+    #[test]
+    fn fields_are_trimmed_and_bounded() {
+        assert_eq!(normalize_title("  Weekly Recap ").unwrap(), "Weekly Recap");
+        assert!(normalize_title(" ").is_err());
+        assert!(normalize_title(&"x".repeat(MAX_TITLE_LEN + 1)).is_err());
+        assert!(normalize_category(&"x".repeat(MAX_CATEGORY_LEN + 1)).is_err());
+        assert!(validate_body(&"x".repeat(MAX_BODY_LEN + 1)).is_err());
+        assert!(validate_slug("weekly-recap-12").is_ok());
+        assert!(validate_slug("Weekly Recap").is_err());
+    }
+}
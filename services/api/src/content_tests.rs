@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod content_tests {
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+        routing::{get, post, put},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        cache::keys,
+        handlers::{content, content_create, content_delete, content_page, content_publish, content_update},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/content", get(content))
+            .route("/content/:slug", get(content_page))
+            .route("/admin/content", post(content_create))
+            .route("/admin/content/:id", put(content_update).delete(content_delete))
+            .route("/admin/content/:id/publish", post(content_publish))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, method: Method, uri: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let builder = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        };
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    /// A slug no other test run uses.
+    fn unique_slug() -> String {
+        format!("test-{}", uuid::Uuid::new_v4().simple())
+    }
+
+    fn article(slug: &str) -> Value {
+        json!({
+            "slug": slug,
+            "title": "  Weekly Recap ",
+            "category": "news",
+            "body": "# Recap\n\nMarkets moved **a lot**.",
+        })
+    }
+
+    async fn create_draft(state: &Arc<crate::AppState>, slug: &str) -> i64 {
+        let (status, created) = send(state, Method::POST, "/admin/content", Some(article(slug))).await;
+        assert_eq!(status, StatusCode::CREATED);
+        created["id"].as_i64().unwrap()
+    }
+
+    fn listed(listing: &Value, slug: &str) -> bool {
+        listing["items"].as_array().unwrap().iter().any(|item| item["slug"] == slug)
+    }
+
+    async fn cached(state: &crate::AppState, key: &str) -> bool {
+        state.cache.get_json::<Value>(key).await.unwrap().is_some()
+    }
+
+    async fn cleanup(state: &crate::AppState, slugs: &[&str]) {
+        sqlx::query("DELETE FROM content WHERE slug = ANY($1)")
+            .bind(slugs)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_slug_conflicts_and_validation() {
+        let state = build_test_state().await;
+        let (first, second) = (unique_slug(), unique_slug());
+
+        let (status, body) = send(&state, Method::POST, "/admin/content", Some(article("Not A Slug"))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+
+        create_draft(&state, &first).await;
+        let (status, body) = send(&state, Method::POST, "/admin/content", Some(article(&first))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "CONFLICT");
+
+        let second_id = create_draft(&state, &second).await;
+        let (status, _) = send(
+            &state,
+            Method::PUT,
+            &format!("/admin/content/{second_id}"),
+            Some(article(&first)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT, "renaming onto a taken slug conflicts");
+
+        let (status, _) = send(&state, Method::PUT, "/admin/content/0", Some(article(&unique_slug()))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        cleanup(&state, &[&first, &second]).await;
+    }
+
+    /// Drafts 404 on the public page and stay out of the listing until
+    /// published; unpublishing takes the article down again.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_drafts_are_invisible_on_public_routes() {
+        let state = build_test_state().await;
+        let slug = unique_slug();
+        let id = create_draft(&state, &slug).await;
+
+        let (status, _) = send(&state, Method::GET, &format!("/content/{slug}"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (_, listing) = send(&state, Method::GET, "/content?limit=100", None).await;
+        assert!(!listed(&listing, &slug));
+
+        let (status, published) = send(&state, Method::POST, &format!("/admin/content/{id}/publish"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(published["is_published"], true);
+        assert!(published["published_at"].is_string(), "publishing stamps published_at");
+
+        let (status, page) = send(&state, Method::GET, &format!("/content/{slug}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["title"], "Weekly Recap");
+        assert_eq!(page["published_at"], published["published_at"]);
+        assert!(page["html"].as_str().unwrap().contains("<strong>a lot</strong>"));
+        let (_, listing) = send(&state, Method::GET, "/content?limit=100", None).await;
+        assert!(listed(&listing, &slug));
+
+        let (status, _) = send(&state, Method::PUT, &format!("/admin/content/{id}"), Some(article(&slug))).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = send(&state, Method::GET, &format!("/content/{slug}"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "saving without is_published unpublishes");
+
+        cleanup(&state, &[&slug]).await;
+    }
+
+    /// Publishing drops the cached listings and rendered pages so the next
+    /// read sees the change.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_publish_invalidates_cached_content() {
+        let state = build_test_state().await;
+        let (live, draft) = (unique_slug(), unique_slug());
+        let live_id = create_draft(&state, &live).await;
+        send(&state, Method::POST, &format!("/admin/content/{live_id}/publish"), None).await;
+        let draft_id = create_draft(&state, &draft).await;
+
+        // Prime both content caches and the rendered page.
+        let (_, listing) = send(&state, Method::GET, "/content?limit=100", None).await;
+        assert!(!listed(&listing, &draft));
+        let (status, _) = send(&state, Method::GET, &format!("/content/{live}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let limit = crate::pagination::PaginationQuery { limit: Some(100), cursor: None }.limit();
+        assert!(cached(&state, &keys::api_content(limit)).await);
+        assert!(cached(&state, &keys::dbq_content(limit)).await);
+        assert!(cached(&state, &keys::api_content_page(&live)).await);
+
+        let (status, _) = send(&state, Method::POST, &format!("/admin/content/{draft_id}/publish"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(!cached(&state, &keys::api_content(limit)).await);
+        assert!(!cached(&state, &keys::dbq_content(limit)).await);
+        assert!(!cached(&state, &keys::api_content_page(&live)).await);
+
+        let (_, listing) = send(&state, Method::GET, "/content?limit=100", None).await;
+        assert!(listed(&listing, &draft), "the published draft shows up straight away");
+
+        let (status, _) = send(&state, Method::DELETE, &format!("/admin/content/{live_id}"), None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&state, Method::GET, &format!("/content/{live}"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "delete drops the cached page");
+
+        cleanup(&state, &[&live, &draft]).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rendered_page_is_sanitized() {
+        let state = build_test_state().await;
+        let slug = unique_slug();
+        let mut body = article(&slug);
+        body["body"] = json!("Hello <script>alert(1)</script><a href=\"javascript:alert(2)\" onclick=\"x()\">link</a>");
+        body["is_published"] = json!(true);
+
+        let (status, _) = send(&state, Method::POST, "/admin/content", Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, page) = send(&state, Method::GET, &format!("/content/{slug}"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        let html = page["html"].as_str().unwrap();
+        assert!(html.contains("Hello"));
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("javascript:"), "{html}");
+        assert!(!html.contains("onclick"), "{html}");
+
+        cleanup(&state, &[&slug]).await;
+    }
+
+}
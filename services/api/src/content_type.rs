@@ -5,22 +5,14 @@
 
 use axum::{
     body::Body,
-    http::{Method, Request, StatusCode},
+    http::{Method, Request},
     middleware::Next,
-    response::{IntoResponse, Response},
-    Json,
+    response::Response,
 };
-use serde::Serialize;
 
-const JSON_REQUIRED_METHODS: &[Method] = &[Method::POST, Method::PUT, Method::PATCH];
+use crate::validation::unsupported_media_type;
 
-#[derive(Serialize)]
-struct UnsupportedMediaTypeError {
-    error:    &'static str,
-    message:  String,
-    required: &'static str,
-    received: String,
-}
+const JSON_REQUIRED_METHODS: &[Method] = &[Method::POST, Method::PUT, Method::PATCH];
 
 pub async fn require_json_content_type(
     req: Request<Body>,
@@ -37,19 +29,11 @@ pub async fn require_json_content_type(
         .unwrap_or("");
 
     if !content_type.starts_with("application/json") {
-        let body = UnsupportedMediaTypeError {
-            error:    "unsupported_media_type",
-            message:  "Content-Type must be application/json for POST, PUT, and PATCH requests. \
-                       Ensure the header is set to 'application/json' and the body is valid JSON."
-                .to_string(),
-            required: "application/json",
-            received: if content_type.is_empty() {
-                "not set".to_string()
-            } else {
-                content_type.to_string()
-            },
-        };
-        return (StatusCode::UNSUPPORTED_MEDIA_TYPE, Json(body)).into_response();
+        return unsupported_media_type(
+            "Content-Type must be application/json for POST, PUT, and PATCH requests. \
+             Ensure the header is set to 'application/json' and the body is valid JSON.",
+            content_type,
+        );
     }
 
     next.run(req).await
@@ -0,0 +1,409 @@
+//! Allowlisted read-only contract getters for
+//! `GET /api/v1/blockchain/call/{fn_name}`.
+//!
+//! Rather than a handler per getter, `CONTRACT_READ_FUNCTIONS` lists the
+//! functions the endpoint may simulate, each with its argument types and how
+//! long a result is cached: `get_creator_reputation(address):300`. Arguments
+//! arrive as one comma-separated `args` parameter and are parsed against the
+//! entry's signature before anything reaches the RPC node, so a string
+//! argument cannot contain a comma. The result is returned as JSON through
+//! [`scval_to_json`].
+
+use std::{str::FromStr, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use stellar_xdr::curr as xdr;
+
+/// The allowlist when `CONTRACT_READ_FUNCTIONS` is unset.
+pub const DEFAULT_FUNCTIONS: &str = "get_creator_reputation(address):300;\
+     get_creation_deposit():300;\
+     get_creation_fee():300;\
+     get_base_fee():300;\
+     get_guardians():300;\
+     get_market_dispute_window(u64):300";
+
+/// Longest `string` or `symbol` argument accepted.
+const MAX_STRING_ARG_LEN: usize = 256;
+
+/// Cap on a function's cache lifetime.
+const MAX_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Argument types a getter on the allowlist may take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgType {
+    U32,
+    U64,
+    I128,
+    Bool,
+    /// A `G...` account or `C...` contract strkey.
+    Address,
+    String,
+    Symbol,
+}
+
+impl ArgType {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I128 => "i128",
+            Self::Bool => "bool",
+            Self::Address => "address",
+            Self::String => "string",
+            Self::Symbol => "symbol",
+        }
+    }
+
+    fn from_label(raw: &str) -> Option<Self> {
+        [
+            Self::U32,
+            Self::U64,
+            Self::I128,
+            Self::Bool,
+            Self::Address,
+            Self::String,
+            Self::Symbol,
+        ]
+        .into_iter()
+        .find(|t| t.label() == raw)
+    }
+
+    /// `raw` as an argument of this type.
+    fn parse(self, raw: &str) -> Result<xdr::ScVal, String> {
+        let invalid = || format!("'{raw}' is not a valid {}", self.label());
+        match self {
+            Self::U32 => raw.parse().map(xdr::ScVal::U32).map_err(|_| invalid()),
+            Self::U64 => raw.parse().map(xdr::ScVal::U64).map_err(|_| invalid()),
+            Self::I128 => raw
+                .parse::<i128>()
+                .map(|n| {
+                    xdr::ScVal::I128(xdr::Int128Parts {
+                        hi: (n >> 64) as i64,
+                        lo: n as u64,
+                    })
+                })
+                .map_err(|_| invalid()),
+            Self::Bool => raw.parse().map(xdr::ScVal::Bool).map_err(|_| invalid()),
+            Self::Address => parse_address(raw).map(xdr::ScVal::Address).ok_or_else(|| {
+                format!("'{raw}' is not a Stellar account (G...) or contract (C...) address")
+            }),
+            Self::String | Self::Symbol if raw.len() > MAX_STRING_ARG_LEN => Err(format!(
+                "{} arguments are at most {MAX_STRING_ARG_LEN} bytes",
+                self.label()
+            )),
+            Self::String => raw
+                .try_into()
+                .map(|s| xdr::ScVal::String(xdr::ScString(s)))
+                .map_err(|_| invalid()),
+            Self::Symbol => raw
+                .try_into()
+                .map(|s| xdr::ScVal::Symbol(xdr::ScSymbol(s)))
+                .map_err(|_| invalid()),
+        }
+    }
+}
+
+/// `raw` as a contract address argument, when it is a `G...` or `C...`
+/// strkey.
+pub(crate) fn parse_address(raw: &str) -> Option<xdr::ScAddress> {
+    if let Ok(key) = stellar_strkey::ed25519::PublicKey::from_string(raw) {
+        return Some(xdr::ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key.0)),
+        )));
+    }
+    stellar_strkey::Contract::from_string(raw)
+        .ok()
+        .map(|contract| xdr::ScAddress::Contract(xdr::Hash(contract.0)))
+}
+
+/// One allowlist entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadFunction {
+    pub name: String,
+    pub args: Vec<ArgType>,
+    pub ttl: Duration,
+}
+
+impl ReadFunction {
+    /// `name(type, ...)`, as shown to callers whose arguments do not parse.
+    pub fn signature(&self) -> String {
+        let args: Vec<&str> = self.args.iter().map(|t| t.label()).collect();
+        format!("{}({})", self.name, args.join(", "))
+    }
+
+    /// Split `raw` on commas and parse each part against the signature.
+    /// Returns the trimmed arguments, which key the cache, with their values.
+    pub fn parse_args(&self, raw: Option<&str>) -> Result<(Vec<String>, Vec<xdr::ScVal>), String> {
+        let parts: Vec<String> = match raw.map(str::trim) {
+            None | Some("") => Vec::new(),
+            Some(raw) => raw.split(',').map(|part| part.trim().to_string()).collect(),
+        };
+        if parts.len() != self.args.len() {
+            return Err(format!(
+                "{} takes {} argument(s), got {}",
+                self.name,
+                self.args.len(),
+                parts.len()
+            ));
+        }
+        let values = parts
+            .iter()
+            .zip(&self.args)
+            .enumerate()
+            .map(|(i, (part, ty))| {
+                ty.parse(part)
+                    .map_err(|e| format!("argument {}: {e}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok((parts, values))
+    }
+}
+
+impl FromStr for ReadFunction {
+    type Err = String;
+
+    /// `name(type, ...):ttl_secs`.
+    fn from_str(raw: &str) -> Result<Self, String> {
+        let invalid = || format!("'{raw}' is not of the form name(type, ...):ttl_secs");
+        let (call, ttl) = raw.trim().rsplit_once(':').ok_or_else(invalid)?;
+        let (name, args) = call
+            .trim()
+            .strip_suffix(')')
+            .and_then(|c| c.split_once('('))
+            .ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty()
+            || name.len() > 32
+            || !name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+        {
+            return Err(format!("'{name}' is not a contract function name"));
+        }
+        let args = args
+            .split(',')
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(|a| {
+                ArgType::from_label(a).ok_or_else(|| format!("{name}: unknown argument type '{a}'"))
+            })
+            .collect::<Result<_, _>>()?;
+        let ttl = ttl
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
+            .filter(|ttl| !ttl.is_zero() && *ttl <= MAX_TTL)
+            .ok_or_else(|| format!("{name}: cache TTL must be 1-{} seconds", MAX_TTL.as_secs()))?;
+        Ok(Self {
+            name: name.to_string(),
+            args,
+            ttl,
+        })
+    }
+}
+
+/// Parse a `;`-separated allowlist. A function may appear once.
+pub fn parse_functions(raw: &str) -> Result<Vec<ReadFunction>, String> {
+    let mut functions: Vec<ReadFunction> = Vec::new();
+    for entry in raw.split(';').filter(|e| !e.trim().is_empty()) {
+        let function: ReadFunction = entry.parse()?;
+        if functions.iter().any(|f| f.name == function.name) {
+            return Err(format!("{} is listed twice", function.name));
+        }
+        functions.push(function);
+    }
+    Ok(functions)
+}
+
+/// A simulated getter result as cached and returned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContractRead {
+    pub function: String,
+    pub args: Vec<String>,
+    /// The return value; see [`scval_to_json`] for the mapping.
+    #[schema(value_type = Object)]
+    pub result: Value,
+    /// Ledger the simulation ran against.
+    pub ledger: u32,
+}
+
+/// JSON for a contract value. Numbers up to 64 bits stay numbers, wider ones
+/// become decimal strings (256-bit ones hex). Structs, whose keys are all
+/// symbols, become objects and other maps `[key, value]` pairs. A contract
+/// enum is a vec of its variant name and any payload, e.g. `["Pro"]`.
+pub fn scval_to_json(val: &xdr::ScVal) -> Value {
+    match val {
+        xdr::ScVal::Void => Value::Null,
+        xdr::ScVal::Bool(b) => json!(b),
+        xdr::ScVal::U32(n) => json!(n),
+        xdr::ScVal::I32(n) => json!(n),
+        xdr::ScVal::U64(n) => json!(n),
+        xdr::ScVal::I64(n) => json!(n),
+        xdr::ScVal::Timepoint(t) => json!(t.0),
+        xdr::ScVal::Duration(d) => json!(d.0),
+        xdr::ScVal::U128(parts) => {
+            json!(((u128::from(parts.hi) << 64) | u128::from(parts.lo)).to_string())
+        }
+        xdr::ScVal::I128(parts) => {
+            json!(((i128::from(parts.hi) << 64) | i128::from(parts.lo)).to_string())
+        }
+        xdr::ScVal::U256(parts) => json!(format!(
+            "0x{:016x}{:016x}{:016x}{:016x}",
+            parts.hi_hi, parts.hi_lo, parts.lo_hi, parts.lo_lo
+        )),
+        xdr::ScVal::I256(parts) => json!(format!(
+            "0x{:016x}{:016x}{:016x}{:016x}",
+            parts.hi_hi, parts.hi_lo, parts.lo_hi, parts.lo_lo
+        )),
+        xdr::ScVal::Bytes(bytes) => json!(hex::encode(bytes.0.as_slice())),
+        xdr::ScVal::String(s) => json!(s.0.to_utf8_string_lossy()),
+        xdr::ScVal::Symbol(s) => json!(s.0.to_utf8_string_lossy()),
+        xdr::ScVal::Vec(None) => json!([]),
+        xdr::ScVal::Vec(Some(items)) => Value::Array(items.0.iter().map(scval_to_json).collect()),
+        xdr::ScVal::Map(None) => json!({}),
+        xdr::ScVal::Map(Some(map)) => {
+            let symbol_keys: Option<Map<String, Value>> = map
+                .0
+                .iter()
+                .map(|entry| match &entry.key {
+                    xdr::ScVal::Symbol(key) => {
+                        Some((key.0.to_utf8_string_lossy(), scval_to_json(&entry.val)))
+                    }
+                    _ => None,
+                })
+                .collect();
+            match symbol_keys {
+                Some(object) => Value::Object(object),
+                None => map
+                    .0
+                    .iter()
+                    .map(|entry| json!([scval_to_json(&entry.key), scval_to_json(&entry.val)]))
+                    .collect(),
+            }
+        }
+        xdr::ScVal::Address(xdr::ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key)),
+        ))) => json!(stellar_strkey::ed25519::PublicKey(*key).to_string()),
+        xdr::ScVal::Address(xdr::ScAddress::Contract(xdr::Hash(hash))) => {
+            json!(stellar_strkey::Contract(*hash).to_string())
+        }
+        xdr::ScVal::Error(e) => json!({ "error": format!("{e:?}") }),
+        other => json!({ "unsupported": format!("{:?}", other.discriminant()) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+    fn function(raw: &str) -> ReadFunction {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn default_allowlist_parses() {
+        let functions = parse_functions(DEFAULT_FUNCTIONS).unwrap();
+        assert!(functions
+            .iter()
+            .any(|f| f.signature() == "get_creator_reputation(address)"));
+        assert!(functions.iter().all(|f| f.ttl == Duration::from_secs(300)));
+    }
+
+    #[test]
+    fn allowlist_entries_are_validated() {
+        assert_eq!(
+            function("get_market_dispute_window(u64):60"),
+            ReadFunction {
+                name: "get_market_dispute_window".into(),
+                args: vec![ArgType::U64],
+                ttl: Duration::from_secs(60),
+            }
+        );
+        assert!("get_x(u64)".parse::<ReadFunction>().is_err(), "no ttl");
+        assert!("get_x(u64):0".parse::<ReadFunction>().is_err());
+        assert!("get_x(float):60".parse::<ReadFunction>().is_err());
+        assert!("get x():60".parse::<ReadFunction>().is_err());
+        assert!(parse_functions("get_x():60;get_x(u64):60").is_err());
+    }
+
+    #[test]
+    fn arguments_follow_the_signature() {
+        let f = function("get_referral_debt(address, address):60");
+        assert_eq!(f.signature(), "get_referral_debt(address, address)");
+        assert!(f.parse_args(Some(ACCOUNT)).is_err(), "too few");
+
+        let f = function("get_market_dispute_window(u64):60");
+        let (raw, values) = f.parse_args(Some(" 42 ")).unwrap();
+        assert_eq!(raw, vec!["42".to_string()]);
+        assert_eq!(values, vec![xdr::ScVal::U64(42)]);
+        assert!(f.parse_args(Some("-1")).is_err());
+        assert!(f.parse_args(None).is_err());
+
+        let (_, values) = function("get_base_fee():60").parse_args(Some("")).unwrap();
+        assert!(values.is_empty());
+    }
+
+    #[test]
+    fn address_arguments_must_be_strkeys() {
+        let f = function("get_creator_reputation(address):60");
+        let (_, values) = f.parse_args(Some(ACCOUNT)).unwrap();
+        assert!(matches!(
+            values[0],
+            xdr::ScVal::Address(xdr::ScAddress::Account(_))
+        ));
+        // A bad checksum, a secret seed and a bare name are all rejected.
+        for bad in [
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHG",
+            "SAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+            "alice",
+        ] {
+            let err = f.parse_args(Some(bad)).unwrap_err();
+            assert!(err.contains("argument 1"), "{err}");
+        }
+    }
+
+    #[test]
+    fn values_convert_to_json() {
+        let symbol = |s: &str| xdr::ScVal::Symbol(xdr::ScSymbol(s.try_into().unwrap()));
+        let i128_val = |n: i128| {
+            xdr::ScVal::I128(xdr::Int128Parts {
+                hi: (n >> 64) as i64,
+                lo: n as u64,
+            })
+        };
+        let address = xdr::ScVal::Address(parse_address(ACCOUNT).unwrap());
+        let guardian = xdr::ScVal::Map(Some(
+            vec![
+                xdr::ScMapEntry {
+                    key: symbol("address"),
+                    val: address,
+                },
+                xdr::ScMapEntry {
+                    key: symbol("voting_power"),
+                    val: xdr::ScVal::U32(1),
+                },
+            ]
+            .try_into()
+            .unwrap(),
+        ));
+
+        assert_eq!(scval_to_json(&i128_val(-5)), json!("-5"));
+        assert_eq!(
+            scval_to_json(&i128_val(i128::MAX)),
+            json!(i128::MAX.to_string())
+        );
+        assert_eq!(scval_to_json(&xdr::ScVal::Void), Value::Null);
+        assert_eq!(
+            scval_to_json(&xdr::ScVal::Vec(Some(
+                vec![symbol("Pro")].try_into().unwrap()
+            ))),
+            json!(["Pro"])
+        );
+        assert_eq!(
+            scval_to_json(&guardian),
+            json!({ "address": ACCOUNT, "voting_power": 1 })
+        );
+    }
+}
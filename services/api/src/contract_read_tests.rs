@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod contract_read_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+    use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+    use tower::ServiceExt;
+
+    use crate::{contract_read, handlers::blockchain_contract_call};
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    const CONTRACT_ID: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+    const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route(
+                "/api/v1/blockchain/call/:fn_name",
+                get(blockchain_contract_call),
+            )
+            .with_state(state)
+    }
+
+    async fn call(router: Router, uri: &str) -> (StatusCode, Value) {
+        let resp = router
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// The function a simulated envelope invokes, and its arguments.
+    fn invocation(envelope: &str) -> (String, Vec<xdr::ScVal>) {
+        let xdr::TransactionEnvelope::Tx(env) =
+            xdr::TransactionEnvelope::from_xdr_base64(envelope, xdr::Limits::none()).unwrap()
+        else {
+            panic!("not a v1 envelope");
+        };
+        let xdr::OperationBody::InvokeHostFunction(op) = &env.tx.operations[0].body else {
+            panic!("not an invocation");
+        };
+        let xdr::HostFunction::InvokeContract(args) = &op.host_function else {
+            panic!("not a contract call");
+        };
+        (
+            args.function_name.0.to_utf8_string_lossy(),
+            args.args.to_vec(),
+        )
+    }
+
+    fn symbol(s: &str) -> xdr::ScVal {
+        xdr::ScVal::Symbol(xdr::ScSymbol(s.try_into().unwrap()))
+    }
+
+    /// Answers `simulateTransaction` as the contract would: the dispute
+    /// window echoes its market id times 10, a reputation is `Pro`. Counts
+    /// the simulations it serves.
+    async fn start_mock_rpc(calls: Arc<AtomicUsize>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let calls = calls.clone();
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    let (function, args) = invocation(body["params"]["transaction"].as_str().unwrap());
+                    let result = match (function.as_str(), args.as_slice()) {
+                        ("get_market_dispute_window", [xdr::ScVal::U64(id)]) => xdr::ScVal::U64(id * 10),
+                        ("get_creator_reputation", [xdr::ScVal::Address(_)]) => {
+                            xdr::ScVal::Vec(Some(vec![symbol("Pro")].try_into().unwrap()))
+                        }
+                        _ => {
+                            return Json(json!({ "result": {
+                                "latestLedger": 900,
+                                "error": "HostError: Error(WasmVm, MissingValue)"
+                            } }))
+                        }
+                    };
+                    Json(json!({ "result": {
+                        "latestLedger": 900,
+                        "results": [{ "xdr": result.to_xdr_base64(xdr::Limits::none()).unwrap(), "auth": [] }]
+                    } }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// State on a network name unique to this run, so no cached read from an
+    /// earlier run answers for the mock.
+    async fn build_state(rpc_url: String) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.contract_id = CONTRACT_ID.to_string();
+        config.contract_call_timeout = Duration::from_secs(1);
+        config.retry_attempts = 1;
+        config.contract_read_functions = contract_read::parse_functions(
+            "get_market_dispute_window(u64):60;get_creator_reputation(address):60;get_creation_fee():60",
+        )
+        .unwrap();
+        let network = format!("readtest{}", uuid::Uuid::new_v4().simple());
+        build_test_state_with(config, &[&network]).await
+    }
+
+    // ---------------------------------------------------------------------------
+    // Tests
+    // ---------------------------------------------------------------------------
+
+    /// Functions off the allowlist never reach the node, even real getters.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_only_allowlisted_functions_are_simulated() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let state = build_state(start_mock_rpc(calls.clone()).await).await;
+
+        for uri in [
+            "/api/v1/blockchain/call/get_admin",
+            "/api/v1/blockchain/call/resolve_market?args=1,0",
+        ] {
+            let (status, body) = call(app(state.clone()), uri).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{uri}");
+            assert_eq!(body["code"], "NOT_FOUND");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    /// A u64 getter decodes its result, is cached per argument, and rejects
+    /// arguments that do not fit its signature.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_u64_getter_is_cached_per_argument() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let state = build_state(start_mock_rpc(calls.clone()).await).await;
+
+        let (status, body) = call(
+            app(state.clone()),
+            "/api/v1/blockchain/call/get_market_dispute_window?args=42",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({ "function": "get_market_dispute_window", "args": ["42"], "result": 420, "ledger": 900 })
+        );
+        call(
+            app(state.clone()),
+            "/api/v1/blockchain/call/get_market_dispute_window?args=42",
+        )
+        .await;
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            1,
+            "second read served from cache"
+        );
+
+        let (_, body) = call(
+            app(state.clone()),
+            "/api/v1/blockchain/call/get_market_dispute_window?args=7",
+        )
+        .await;
+        assert_eq!(body["result"], 70);
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "another argument is another entry"
+        );
+
+        for args in ["", "?args=-1", "?args=abc", "?args=1,2"] {
+            let (status, body) = call(
+                app(state.clone()),
+                &format!("/api/v1/blockchain/call/get_market_dispute_window{args}"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{args}");
+            assert_eq!(body["code"], "INVALID_ARGS");
+            assert_eq!(
+                body["details"]["signature"],
+                "get_market_dispute_window(u64)"
+            );
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Address arguments must be strkeys; a valid one reaches the contract
+    /// and the enum it returns comes back as JSON.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_address_getter_validates_strkeys() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let state = build_state(start_mock_rpc(calls.clone()).await).await;
+
+        for bad in [
+            "alice",
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHG",
+        ] {
+            let (status, body) = call(
+                app(state.clone()),
+                &format!("/api/v1/blockchain/call/get_creator_reputation?args={bad}"),
+            )
+            .await;
+            assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY, "{bad}");
+            assert_eq!(body["code"], "INVALID_ARGS");
+            assert_eq!(
+                body["details"]["signature"],
+                "get_creator_reputation(address)"
+            );
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let (status, body) = call(
+            app(state.clone()),
+            &format!("/api/v1/blockchain/call/get_creator_reputation?args={ACCOUNT}"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"], json!(["Pro"]));
+        assert_eq!(body["args"], json!([ACCOUNT]));
+
+        // The mock fails any call it does not know, like a contract would.
+        let (status, body) = call(app(state), "/api/v1/blockchain/call/get_creation_fee").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "SIMULATION_FAILED");
+    }
+}
@@ -4,10 +4,26 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use tracing::Instrument;
 use uuid::Uuid;
 
 pub const REQUEST_ID_HEADER: &str = "x-request-id";
 
+/// The request's correlation ID, stored in request extensions by
+/// [`correlation_id_middleware`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/// The correlation ID of the request being handled on this task, if any.
+/// Work spawned onto other tasks does not inherit it; pass it explicitly.
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(Clone::clone).ok()
+}
+
 /// Maximum allowed header length for the correlation/correlation ID.
 ///
 /// UUIDs in canonical string form are 36 bytes (e.g. `550e8400-e29b-41d4-a716-446655440000`).
@@ -18,9 +34,9 @@ fn parse_valid_request_id(header_value: &str) -> Option<String> {
         return None;
     }
 
-    // Validate as UUID v4.
+    // Validate as UUID v4 (older clients) or v7 (what we generate).
     let uuid = Uuid::parse_str(header_value).ok()?;
-    if uuid.get_version_num() != 4 {
+    if !matches!(uuid.get_version_num(), 4 | 7) {
         return None;
     }
 
@@ -29,10 +45,12 @@ fn parse_valid_request_id(header_value: &str) -> Option<String> {
 
 /// Middleware that attaches a correlation ID to every request.
 ///
-/// - Reads `X-Request-ID` from the incoming request if present and validates it as UUID v4.
-///   Otherwise generates a new UUID v4.
-/// - Records the ID as a `request_id` field on the current tracing span so
-///   every log line emitted within the request carries it automatically.
+/// - Reads `X-Request-ID` from the incoming request if present and validates it as UUID v4
+///   or v7. Otherwise generates a new UUID v7, so IDs sort by arrival time.
+/// - Stores it as a [`RequestId`] extension and as the task-local read by
+///   [`current_request_id`], which `ApiError` bodies and RPC logs use.
+/// - Runs the rest of the request inside a `request` span with a `request_id`
+///   field, so every log line emitted within the request carries it.
 /// - Echoes the ID back in the `X-Request-ID` response header.
 pub async fn correlation_id_middleware(mut req: Request, next: Next) -> Response {
     let id = req
@@ -40,18 +58,19 @@ pub async fn correlation_id_middleware(mut req: Request, next: Next) -> Response
         .get(REQUEST_ID_HEADER)
         .and_then(|v| v.to_str().ok())
         .and_then(parse_valid_request_id)
-        .unwrap_or_else(|| Uuid::new_v4().to_string());
+        .unwrap_or_else(|| Uuid::now_v7().to_string());
 
     // Normalise: ensure the header is present on the request for downstream handlers.
     // (If we ever failed to create a HeaderValue, fall back to not inserting.)
     if let Ok(val) = HeaderValue::from_str(&id) {
         req.headers_mut().insert(REQUEST_ID_HEADER, val);
     }
+    req.extensions_mut().insert(RequestId(id.clone()));
 
-    let span = tracing::Span::current();
-    span.record("request_id", &id.as_str());
-
-    let mut response = next.run(req).await;
+    let span = tracing::info_span!("request", request_id = %id);
+    let mut response = CURRENT_REQUEST_ID
+        .scope(id.clone(), next.run(req).instrument(span))
+        .await;
 
     if let Ok(val) = HeaderValue::from_str(&id) {
         response.headers_mut().insert(REQUEST_ID_HEADER, val);
@@ -89,5 +108,105 @@ mod tests {
         let long = format!("{}{}", "550e8400-e29b-41d4-a716-446655440000", "x".repeat(100));
         assert!(parse_valid_request_id(&long).is_none());
     }
-}
 
+    #[test]
+    fn uuid_v7_is_accepted() {
+        let header = Uuid::now_v7().to_string();
+        assert_eq!(parse_valid_request_id(&header), Some(header));
+    }
+
+    mod middleware {
+        use super::super::*;
+        use axum::{body::Body, http::StatusCode, routing::get, Router};
+        use std::{
+            io::Write,
+            sync::{Arc, Mutex},
+        };
+        use tower::ServiceExt;
+
+        /// Log output captured by a test subscriber.
+        #[derive(Clone, Default)]
+        struct Captured(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Captured {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        fn app() -> Router {
+            Router::new()
+                .route(
+                    "/work",
+                    get(|| async {
+                        tracing::info!("handler doing work");
+                        current_request_id().unwrap_or_default()
+                    }),
+                )
+                .route(
+                    "/fail",
+                    get(|| async { crate::handlers::ApiError::not_found("no such thing") }),
+                )
+                .layer(axum::middleware::from_fn(correlation_id_middleware))
+        }
+
+        async fn send(uri: &str, request_id: Option<&str>) -> Response {
+            let mut req = Request::builder().uri(uri);
+            if let Some(id) = request_id {
+                req = req.header(REQUEST_ID_HEADER, id);
+            }
+            app().oneshot(req.body(Body::empty()).unwrap()).await.unwrap()
+        }
+
+        async fn body_string(response: Response) -> String {
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            String::from_utf8(bytes.to_vec()).unwrap()
+        }
+
+        #[tokio::test]
+        async fn supplied_id_round_trips_and_tags_log_lines() {
+            let captured = Captured::default();
+            let writer = captured.clone();
+            let subscriber = tracing_subscriber::fmt()
+                .with_writer(move || writer.clone())
+                .with_ansi(false)
+                .finish();
+            let _guard = tracing::subscriber::set_default(subscriber);
+
+            let id = "550e8400-e29b-41d4-a716-446655440000";
+            let response = send("/work", Some(id)).await;
+            assert_eq!(response.headers()[REQUEST_ID_HEADER], id);
+            assert_eq!(body_string(response).await, id);
+
+            let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+            let line = logs
+                .lines()
+                .find(|l| l.contains("handler doing work"))
+                .expect("handler log line");
+            assert!(line.contains(&format!("request_id={id}")), "{line}");
+        }
+
+        #[tokio::test]
+        async fn missing_id_is_generated_as_v7() {
+            let response = send("/work", None).await;
+            let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap().to_string();
+            assert_eq!(Uuid::parse_str(&id).unwrap().get_version_num(), 7);
+            assert_eq!(body_string(response).await, id);
+        }
+
+        #[tokio::test]
+        async fn api_error_body_carries_request_id() {
+            let id = "550e8400-e29b-41d4-a716-446655440000";
+            let response = send("/fail", Some(id)).await;
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+            let body: serde_json::Value = serde_json::from_str(&body_string(response).await).unwrap();
+            assert_eq!(body["request_id"], id);
+            assert_eq!(body["code"], "NOT_FOUND");
+        }
+    }
+}
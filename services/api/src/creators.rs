@@ -0,0 +1,326 @@
+//! Creator profiles and the top-creators list.
+//!
+//! [`profile`] describes one market creator on the primary network: the
+//! contract's `get_creator_reputation` tier (simulated), stats over the
+//! markets their `mkt_creat` events announce, and the markets of theirs that
+//! are still active. The stats come from the indexed events: a market is
+//! resolved once it has a `resolv_fx`, disputed once it has a `disp_file`,
+//! and its volume is the sum of its `bet_place` amounts. A dispute is
+//! overturned when the market resolved to an outcome other than the last one
+//! its oracle reported (`orcl_res`/`oracle_resolved`); the overturn rate is
+//! over the disputed markets that have both.
+//!
+//! When the simulation fails the profile is still served with a `null`
+//! reputation, and is not cached, so the next request tries the RPC again.
+//! A creator hides their profile, and drops out of [`top_creators`], with
+//! `hide_creator_profile` in their notification settings.
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{self as xdr, ReadXdr};
+
+use crate::{blockchain::BlockchainClient, db::Database};
+
+/// Entries kept in a cached top-creators list; the handler slices it.
+pub const TOP_CREATORS_SIZE: i64 = 50;
+
+/// The contract's `CreatorReputation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreatorReputation {
+    None,
+    Basic,
+    Pro,
+    Institutional,
+}
+
+/// Decode `get_creator_reputation`'s result: a unit enum variant, which the
+/// host encodes as a one-symbol vec.
+pub fn decode_reputation(result_xdr: &str) -> anyhow::Result<CreatorReputation> {
+    let val = xdr::ScVal::from_xdr_base64(result_xdr, xdr::Limits::none())?;
+    let variant = match &val {
+        xdr::ScVal::Vec(Some(items)) => match items.0.first() {
+            Some(xdr::ScVal::Symbol(symbol)) => symbol.0.to_utf8_string_lossy(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    };
+    match variant.as_str() {
+        "None" => Ok(CreatorReputation::None),
+        "Basic" => Ok(CreatorReputation::Basic),
+        "Pro" => Ok(CreatorReputation::Pro),
+        "Institutional" => Ok(CreatorReputation::Institutional),
+        _ => Err(anyhow!(
+            "unexpected creator reputation {:?}",
+            val.discriminant()
+        )),
+    }
+}
+
+/// One market a creator created, as returned by
+/// [`Database::creator_markets`].
+#[derive(Debug, Clone)]
+pub struct CreatorMarketRow {
+    pub market_id: i64,
+    /// Sum of the market's `bet_place` amounts, in stroops.
+    pub volume: String,
+    pub disputed: bool,
+    /// Outcome of the market's latest `resolv_fx`.
+    pub resolved_outcome: Option<i32>,
+    /// Outcome the market's oracle last reported.
+    pub oracle_outcome: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreatorStats {
+    pub markets_created: u64,
+    pub markets_resolved: u64,
+    pub markets_disputed: u64,
+    /// Stake placed on the creator's markets, in stroops.
+    pub total_volume: String,
+    /// Share of disputed markets that resolved against the oracle's outcome.
+    /// `None` until a disputed market has resolved.
+    pub dispute_overturn_rate: Option<f64>,
+}
+
+/// Aggregate a creator's market rows.
+pub fn creator_stats(rows: &[CreatorMarketRow]) -> anyhow::Result<CreatorStats> {
+    let mut volume: i128 = 0;
+    let (mut resolved, mut disputed, mut settled_disputes, mut overturned) = (0, 0, 0, 0);
+    for row in rows {
+        volume += row.volume.parse::<i128>().with_context(|| {
+            format!(
+                "creators: volume is not an integer amount: {:?}",
+                row.volume
+            )
+        })?;
+        if row.resolved_outcome.is_some() {
+            resolved += 1;
+        }
+        if !row.disputed {
+            continue;
+        }
+        disputed += 1;
+        if let (Some(final_outcome), Some(oracle)) = (row.resolved_outcome, row.oracle_outcome) {
+            settled_disputes += 1;
+            if final_outcome != oracle {
+                overturned += 1;
+            }
+        }
+    }
+
+    Ok(CreatorStats {
+        markets_created: rows.len() as u64,
+        markets_resolved: resolved,
+        markets_disputed: disputed,
+        total_volume: volume.to_string(),
+        dispute_overturn_rate: (settled_disputes > 0)
+            .then(|| overturned as f64 / settled_disputes as f64),
+    })
+}
+
+/// A creator's market that still takes bets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ActiveMarket {
+    pub id: i64,
+    pub title: String,
+    pub category: Option<String>,
+    pub volume: f64,
+    pub participant_count: i64,
+    pub ends_at: DateTime<Utc>,
+}
+
+/// `GET /api/v1/creators/:address`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreatorProfile {
+    pub address: String,
+    /// The contract's tier for the creator; `null` when it could not be
+    /// read.
+    pub reputation: Option<CreatorReputation>,
+    pub stats: CreatorStats,
+    /// Active markets, soonest to close first.
+    pub active_markets: Vec<ActiveMarket>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// `address`'s profile on `network`, as of `now`. The reputation is `None`
+/// when `client` could not simulate it.
+pub async fn profile(
+    db: &Database,
+    client: &BlockchainClient,
+    network: &str,
+    address: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<CreatorProfile> {
+    let (reputation, rows, active_markets) = tokio::join!(
+        client.creator_reputation(address),
+        db.creator_markets(network, address),
+        db.creator_active_markets(address),
+    );
+    Ok(CreatorProfile {
+        address: address.to_string(),
+        reputation,
+        stats: creator_stats(&rows?)?,
+        active_markets: active_markets?,
+        computed_at: now,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TopCreatorsMetric {
+    /// Stake placed on the creator's markets.
+    #[default]
+    Volume,
+    /// Markets created.
+    Markets,
+}
+
+impl TopCreatorsMetric {
+    pub const ALL: [Self; 2] = [Self::Volume, Self::Markets];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Volume => "volume",
+            Self::Markets => "markets",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopCreator {
+    pub rank: i32,
+    pub address: String,
+    pub markets_created: i64,
+    /// In stroops.
+    pub total_volume: String,
+}
+
+/// `GET /api/v1/creators/top`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopCreators {
+    pub metric: TopCreatorsMetric,
+    pub entries: Vec<TopCreator>,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// The [`TOP_CREATORS_SIZE`] leading creators on `network` by `metric`,
+/// leaving out hidden profiles.
+pub async fn top_creators(
+    db: &Database,
+    network: &str,
+    metric: TopCreatorsMetric,
+    now: DateTime<Utc>,
+) -> anyhow::Result<TopCreators> {
+    let entries = db
+        .top_creators(network, metric, TOP_CREATORS_SIZE)
+        .await?
+        .into_iter()
+        .enumerate()
+        .map(|(i, (address, markets_created, total_volume))| TopCreator {
+            rank: i as i32 + 1,
+            address,
+            markets_created,
+            total_volume,
+        })
+        .collect();
+    Ok(TopCreators {
+        metric,
+        entries,
+        computed_at: now,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::curr::WriteXdr;
+
+    use super::*;
+
+    fn row(
+        market_id: i64,
+        volume: i128,
+        disputed: bool,
+        resolved_outcome: Option<i32>,
+        oracle_outcome: Option<i32>,
+    ) -> CreatorMarketRow {
+        CreatorMarketRow {
+            market_id,
+            volume: volume.to_string(),
+            disputed,
+            resolved_outcome,
+            oracle_outcome,
+        }
+    }
+
+    #[test]
+    fn stats_count_resolutions_disputes_and_overturns() {
+        let rows = vec![
+            // Resolved as the oracle said, no dispute.
+            row(1, 1_000, false, Some(0), Some(0)),
+            // Disputed and overturned.
+            row(2, 2_500, true, Some(1), Some(0)),
+            // Disputed and upheld.
+            row(3, 500, true, Some(0), Some(0)),
+            // Disputed, not resolved yet.
+            row(4, 0, true, None, Some(1)),
+            // Open.
+            row(5, 10_000_000_000_000_000_000, false, None, None),
+        ];
+        let stats = creator_stats(&rows).unwrap();
+
+        assert_eq!(stats.markets_created, 5);
+        assert_eq!(stats.markets_resolved, 3);
+        assert_eq!(stats.markets_disputed, 3);
+        assert_eq!(stats.total_volume, "10000000000000004000");
+        assert_eq!(stats.dispute_overturn_rate, Some(0.5));
+    }
+
+    #[test]
+    fn overturn_rate_is_none_without_a_settled_dispute() {
+        let stats = creator_stats(&[row(1, 10, true, None, Some(0))]).unwrap();
+        assert_eq!(stats.dispute_overturn_rate, None);
+        let stats = creator_stats(&[]).unwrap();
+        assert_eq!(stats.markets_created, 0);
+        assert_eq!(stats.total_volume, "0");
+        assert_eq!(stats.dispute_overturn_rate, None);
+    }
+
+    #[test]
+    fn malformed_volume_is_an_error() {
+        let mut bad = row(1, 0, false, None, None);
+        bad.volume = "1.5".to_string();
+        assert!(creator_stats(&[bad]).is_err());
+    }
+
+    #[test]
+    fn reputation_decodes_from_a_unit_variant() {
+        let encode = |val: xdr::ScVal| val.to_xdr_base64(xdr::Limits::none()).unwrap();
+        let variant = |name: &str| {
+            encode(xdr::ScVal::Vec(Some(
+                vec![xdr::ScVal::Symbol(xdr::ScSymbol(name.try_into().unwrap()))]
+                    .try_into()
+                    .unwrap(),
+            )))
+        };
+        assert_eq!(
+            decode_reputation(&variant("Pro")).unwrap(),
+            CreatorReputation::Pro
+        );
+        assert_eq!(
+            decode_reputation(&variant("None")).unwrap(),
+            CreatorReputation::None
+        );
+        assert!(decode_reputation(&variant("Legendary")).is_err());
+        assert!(decode_reputation(&encode(xdr::ScVal::U32(1))).is_err());
+    }
+
+    #[test]
+    fn metric_labels_match_serde() {
+        for metric in TopCreatorsMetric::ALL {
+            let json = serde_json::to_value(metric).unwrap();
+            assert_eq!(json, metric.label());
+        }
+    }
+}
@@ -0,0 +1,345 @@
+#[cfg(test)]
+mod creators_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use stellar_xdr::curr::{self as xdr, WriteXdr};
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    use crate::{
+        cache::keys,
+        handlers::{creator_profile, top_creators},
+        user_notifications::{new_token, NotificationSettingsUpdate},
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded rows use reserved market ids and an event id prefix, so
+    /// cleanup only touches rows created here.
+    const SEED_MARKETS: [i64; 4] = [9801, 9802, 9803, 9804];
+    const SEED_EVENT_PREFIX: &str = "creator-test-";
+
+    const CREATOR: &str = "GCLZPF4XIJBEEQSCIJBEEQSCIJBEEQSCIJBEEQSCIJBEEQSCIJBEENZP";
+    const QUIET: &str = "GCLZPF4XINBUGQ2DINBUGQ2DINBUGQ2DINBUGQ2DINBUGQ2DINBUGHQD";
+    const CONTRACT_ID: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+
+    /// Every test seeds and cleans up the same creator, so they take turns.
+    static SERIAL: Mutex<()> = Mutex::const_new(());
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/creators/top", get(top_creators))
+            .route("/creators/:address", get(creator_profile))
+            .with_state(state)
+    }
+
+    async fn call(state: &Arc<crate::AppState>, uri: &str) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Answers every `simulateTransaction` with the `Pro` reputation tier.
+    async fn start_mock_rpc() -> String {
+        let app = Router::new().route(
+            "/",
+            post(|Json(_): Json<Value>| async {
+                let pro = xdr::ScVal::Vec(Some(
+                    vec![xdr::ScVal::Symbol(xdr::ScSymbol("Pro".try_into().unwrap()))]
+                        .try_into()
+                        .unwrap(),
+                ));
+                Json(json!({ "result": {
+                    "latestLedger": 900,
+                    "results": [{ "xdr": pro.to_xdr_base64(xdr::Limits::none()).unwrap(), "auth": [] }]
+                } }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// A URL nothing listens on, standing in for an RPC that is down.
+    fn dead_rpc() -> String {
+        "http://127.0.0.1:1".to_string()
+    }
+
+    async fn event(
+        state: &crate::AppState,
+        kind: &str,
+        market_id: i64,
+        ledger: i64,
+        address: &str,
+        outcome: Option<i32>,
+        amount: Option<&str>,
+    ) {
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id, address, outcome, amount) \
+             VALUES ($1 || gen_random_uuid(), $2, $3, $4, $5, $6, $7, $8::NUMERIC)",
+        )
+        .bind(SEED_EVENT_PREFIX)
+        .bind(state.config.network_name())
+        .bind(ledger)
+        .bind(kind)
+        .bind(market_id)
+        .bind(address)
+        .bind(outcome)
+        .bind(amount)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// `CREATOR`'s markets:
+    /// - 9801: resolved as the oracle reported, after a dispute (upheld).
+    /// - 9802: disputed and resolved to another outcome (overturned).
+    /// - 9803: resolved without a dispute.
+    /// - 9804: still active.
+    ///
+    /// 10^30 is bet on 9801 so the creator leads the volume ranking.
+    async fn seed(state: &crate::AppState) {
+        cleanup(state).await;
+        for market_id in SEED_MARKETS {
+            event(state, "mkt_creat", market_id, 1, CREATOR, None, None).await;
+        }
+        event(
+            state,
+            "bet_place",
+            9801,
+            2,
+            QUIET,
+            Some(0),
+            Some("1000000000000000000000000000000"),
+        )
+        .await;
+        event(state, "bet_place", 9802, 2, QUIET, Some(1), Some("250")).await;
+        event(state, "bet_place", 9804, 2, QUIET, Some(0), Some("50")).await;
+        for (market_id, oracle, resolved) in [(9801, 0, 0), (9802, 0, 1)] {
+            event(
+                state,
+                "orcl_res",
+                market_id,
+                3,
+                "CORACLE",
+                Some(oracle),
+                None,
+            )
+            .await;
+            event(state, "disp_file", market_id, 4, QUIET, None, None).await;
+            event(
+                state,
+                "resolv_fx",
+                market_id,
+                5,
+                "GRESOLVER",
+                Some(resolved),
+                Some("0"),
+            )
+            .await;
+        }
+        event(state, "resolv_fx", 9803, 5, "GRESOLVER", Some(1), Some("0")).await;
+
+        sqlx::query(
+            "INSERT INTO markets (id, title, creator, status, total_volume, ends_at, created_at) \
+             SELECT id, 'Creator ' || id, $2, CASE WHEN id = 9804 THEN 'active' ELSE 'resolved' END, \
+                    0, NOW() + INTERVAL '1 day', NOW() \
+             FROM UNNEST($1::BIGINT[]) AS id",
+        )
+        .bind(&SEED_MARKETS[..])
+        .bind(CREATOR)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn set_hidden(state: &crate::AppState, hidden: bool) {
+        let update = NotificationSettingsUpdate {
+            email: None,
+            market_resolved: false,
+            claim_available: false,
+            dispute_filed: false,
+            hide_creator_profile: hidden,
+        };
+        state
+            .db
+            .notification_settings_update(CREATOR, &update, &new_token())
+            .await
+            .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pool = state.db.pool();
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(SEED_EVENT_PREFIX)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM user_notification_settings WHERE address = $1")
+            .bind(CREATOR)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let _ = state.cache.del(&keys::api_creator_profile(CREATOR)).await;
+        for metric in crate::creators::TopCreatorsMetric::ALL {
+            let _ = state
+                .cache
+                .del(&keys::api_top_creators(metric.label()))
+                .await;
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// The profile combines the simulated reputation with stats from the
+    /// indexed events and the active markets, and is cached.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_profile_aggregates_indexed_events() {
+        let _serial = SERIAL.lock().await;
+        let state = build_state(start_mock_rpc().await).await;
+        seed(&state).await;
+
+        let (status, body) = call(&state, &format!("/creators/{CREATOR}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["reputation"], "pro");
+        assert_eq!(
+            body["stats"],
+            json!({
+                "markets_created": 4,
+                "markets_resolved": 3,
+                "markets_disputed": 2,
+                "total_volume": "1000000000000000000000000000300",
+                "dispute_overturn_rate": 0.5,
+            })
+        );
+        let active: Vec<i64> = body["active_markets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(active, vec![9804]);
+        assert!(state
+            .cache
+            .get_raw_with_ttl(&keys::api_creator_profile(CREATOR))
+            .await
+            .unwrap()
+            .is_some());
+
+        let (status, _) = call(&state, "/creators/not-a-strkey").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        cleanup(&state).await;
+    }
+
+    /// A hidden profile is a 404 even while a copy is cached, and leaves the
+    /// top creators; showing it again brings both back.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_hidden_profile_is_not_found() {
+        let _serial = SERIAL.lock().await;
+        let state = build_state(start_mock_rpc().await).await;
+        seed(&state).await;
+
+        let (status, body) = call(&state, "/creators/top?metric=volume&limit=1").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["entries"][0]["address"], CREATOR);
+        assert_eq!(body["entries"][0]["markets_created"], 4);
+        let (status, _) = call(&state, &format!("/creators/{CREATOR}")).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // What PUT /users/:address/notifications does after saving.
+        set_hidden(&state, true).await;
+        for metric in crate::creators::TopCreatorsMetric::ALL {
+            state
+                .cache
+                .del(&keys::api_top_creators(metric.label()))
+                .await
+                .unwrap();
+        }
+        let (status, body) = call(&state, &format!("/creators/{CREATOR}")).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "NOT_FOUND");
+        let (_, body) = call(&state, "/creators/top?metric=markets&limit=50").await;
+        assert!(body["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|e| e["address"] != CREATOR));
+
+        set_hidden(&state, false).await;
+        let (status, _) = call(&state, &format!("/creators/{CREATOR}")).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = call(&state, "/creators/top?metric=profit").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        cleanup(&state).await;
+    }
+
+    /// With the RPC down the profile is still served, without a reputation,
+    /// and is not cached.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_profile_falls_back_when_rpc_is_down() {
+        let _serial = SERIAL.lock().await;
+        let state = build_state(dead_rpc()).await;
+        seed(&state).await;
+
+        let (status, body) = call(&state, &format!("/creators/{CREATOR}")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["reputation"], Value::Null);
+        assert_eq!(body["stats"]["markets_created"], 4);
+        assert!(state
+            .cache
+            .get_raw_with_ttl(&keys::api_creator_profile(CREATOR))
+            .await
+            .unwrap()
+            .is_none());
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state(rpc_url: String) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.contract_id = CONTRACT_ID.to_string();
+        config.contract_call_timeout = Duration::from_secs(1);
+        config.retry_attempts = 1;
+        build_test_state_with(config, &[]).await
+    }
+}
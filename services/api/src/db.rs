@@ -1,17 +1,102 @@
 use std::time::Duration;
 
 use anyhow::Context;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use futures::{stream::BoxStream, StreamExt};
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use sha2::{Digest as _, Sha256};
 use sqlx::{postgres::PgPoolOptions, PgPool, Row};
 use tokio::time::error::Elapsed;
 
 use crate::{
+    acquisition::{AcquisitionCounts, AcquisitionGroupBy},
+    activity::{ActivityFilter, ActivityRow},
+    analytics::{AnalyticsDailyCount, AnalyticsEvent},
+    backfill::{self, BackfillJob, BackfillStatus},
     cache::{keys, RedisCache},
+    campaign::{self, Campaign, CampaignOutcome, CampaignStatus},
+    category::Category,
+    config::CacheTtls,
+    contact::{ContactStatus, ContactSubmission},
+    content::{ContentEntry, ContentFields},
+    creators::{ActiveMarket, CreatorMarketRow, TopCreatorsMetric},
+    digest::{self, Digest},
+    email::types::EmailJobType,
+    export::{
+        ChainEventExportRow, NewsletterExportRow, NewsletterExportStatus, WaitlistExportRow,
+        EXPORT_BUFFER_ROWS,
+    },
+    gdpr::{self, Erasure, GdprDeleteReport, GdprExport, EXPORT_EXCLUDED_COLUMNS, GDPR_TABLES},
+    leaderboard::{Leaderboard, LeaderboardEntry, LeaderboardMetric, LeaderboardPeriod},
+    market_ids::MarketIdMapping,
+    market_image::MarketImage,
+    market_watch::{MarketWatch, WatchRecipient, WatchTrigger},
     metrics::Metrics,
+    newsletter::EmailCategory,
+    oracle_keeper::{OracleSubmission, OracleSubmissionStatus, SubmissionClaim},
+    platform_stats::{DerivedStats, StatsSnapshot, TokenVolume},
+    price_history::{HistoryResolution, OutcomeSeries, PriceCandle},
+    protocol_state::{BreakerState, ProtocolStateChange},
+    resolution_reminder::ReminderCandidate,
+    retention::RetentionPolicy,
+    risk::{PositionRow as RiskPositionRow, ProbabilityRow, RiskAlert, RiskAlertKind, RiskSignal},
+    signup_guard::BlockedDomain,
+    stats_history::StatsMetric,
+    sync_refresh::MarketProfile,
+    tx_watch::{ClaimedSubscription, TxSubscription},
+    user_notifications::{
+        NotificationKind, NotificationSettings, NotificationSettingsUpdate, PositionEvent,
+        PositionRecipient,
+    },
+    waitlist::{
+        generate_referral_code, WaitlistEntry, WaitlistInvitee, WaitlistJoin, WaitlistStats,
+        WaitlistStatus, REFERRAL_BONUS,
+    },
+    watchlist::{WatchlistEntry, WATCHLIST_MAX_ENTRIES},
 };
 
+/// Attempts at drawing an unused referral code before a join gives up.
+const REFERRAL_CODE_ATTEMPTS: usize = 5;
+
+/// Waitlist columns plus the derived `referral_count` and `position`. Queue
+/// order is `priority_score DESC, joined_at, id`; keep it in sync with
+/// `waitlist_invite`.
+const WAITLIST_ENTRY_SELECT: &str = "\
+    SELECT w.*, \
+           (SELECT COUNT(*) FROM waitlist_entries r WHERE r.referred_by = w.id) AS referral_count, \
+           CASE WHEN w.status = 'pending' THEN ( \
+               SELECT COUNT(*) + 1 FROM waitlist_entries a \
+               WHERE a.status = 'pending' \
+                 AND (a.priority_score > w.priority_score \
+                      OR (a.priority_score = w.priority_score \
+                          AND (a.joined_at, a.id) < (w.joined_at, w.id))) \
+           ) END AS position \
+    FROM waitlist_entries w";
+
+/// Category columns plus aggregates over its non-deleted markets. Callers
+/// append an optional `WHERE` and then `GROUP BY c.slug`.
+const CATEGORY_SELECT: &str = "\
+    SELECT c.slug, c.name, c.is_featured, \
+           COUNT(m.id) FILTER (WHERE m.status = 'active') AS active_market_count, \
+           COALESCE(SUM(m.total_volume), 0) AS total_volume \
+    FROM categories c \
+    LEFT JOIN markets m ON m.category = c.slug AND m.deleted_at IS NULL";
+
+/// Subscribers bulk mail (campaigns, the weekly digest) goes to: confirmed,
+/// not unsubscribed or deleted, not suppressed and not opted out of the
+/// category bound as `$1`. Callers select from `s` and may append `AND ...`
+/// conditions.
+const SUBSCRIBER_AUDIENCE: &str = "\
+    FROM newsletter_subscribers s \
+    WHERE s.confirmed \
+      AND s.unsubscribed_at IS NULL \
+      AND s.deleted_at IS NULL \
+      AND NOT EXISTS (SELECT 1 FROM email_suppressions x WHERE x.email = s.email) \
+      AND NOT EXISTS ( \
+          SELECT 1 FROM newsletter_preferences p \
+          WHERE p.email = s.email AND p.category = $1 AND NOT p.enabled \
+      )";
+
 /// Errors that can be returned by [`Database`] methods.
 #[derive(Debug)]
 pub enum DbError {
@@ -65,14 +150,57 @@ impl From<Elapsed> for DbError {
     }
 }
 
+const EMAIL_EVENT_INSERT: &str =
+    "INSERT INTO email_events (email_job_id, message_id, event_type, recipient_email, metadata)
+     VALUES ($1, $2, $3, $4, $5)
+     RETURNING id";
+
+const EMAIL_SUPPRESSION_UPSERT: &str =
+    "INSERT INTO email_suppressions (email, suppression_type, reason, bounce_type)
+     VALUES ($1, $2, $3, $4)
+     ON CONFLICT (email) DO UPDATE SET
+         suppression_type = EXCLUDED.suppression_type,
+         reason = EXCLUDED.reason,
+         bounce_type = EXCLUDED.bounce_type,
+         updated_at = NOW()";
+
+/// Upsert adding one to today's `counter_type` count for a template
+/// (`$1`, `$2` = date). `None` for a counter `email_analytics` has no
+/// column for.
+fn email_analytics_increment_sql(counter_type: &str) -> Option<String> {
+    let column = match counter_type {
+        "sent" => "sent_count",
+        "delivered" => "delivered_count",
+        "opened" => "opened_count",
+        "clicked" => "clicked_count",
+        "bounced" => "bounced_count",
+        "complained" => "complained_count",
+        "unsubscribed" => "unsubscribed_count",
+        _ => return None,
+    };
+    Some(format!(
+        "INSERT INTO email_analytics (template_name, date, {column})
+         VALUES ($1, $2, 1)
+         ON CONFLICT (template_name, variant_name, date) DO UPDATE SET
+             {column} = email_analytics.{column} + 1,
+             updated_at = NOW()"
+    ))
+}
+
 #[derive(Clone)]
 pub struct Database {
     pool: PgPool,
     cache: RedisCache,
     metrics: Metrics,
     query_timeout: Duration,
+    slow_query_threshold: Duration,
+    sample_interval: Duration,
+    cache_ttls: CacheTtls,
 }
 
+/// `pool` label of the API's connection pool metrics.
+const POOL_LABEL: &str = "primary";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Statistics {
     pub total_markets: i64,
@@ -87,11 +215,184 @@ pub struct FeaturedMarket {
     pub title: String,
     pub volume: f64,
     pub ends_at: DateTime<Utc>,
+    /// Stake token contract id; `None` for markets indexed before tokens
+    /// were recorded.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Versioned cover image URL; `None` until one is uploaded.
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
+/// Sort orders supported by [`Database::list_markets`].
+///
+/// `ends_at` sorts soonest-first; every other order sorts largest/newest-first.
+/// Ties are always broken by `id` in the same direction so the keyset cursor
+/// is total.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketSort {
+    #[default]
+    Volume,
+    EndsAt,
+    CreatedAt,
+    ParticipantCount,
+}
+
+impl MarketSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarketSort::Volume => "volume",
+            MarketSort::EndsAt => "ends_at",
+            MarketSort::CreatedAt => "created_at",
+            MarketSort::ParticipantCount => "participant_count",
+        }
+    }
+
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "volume" => Some(MarketSort::Volume),
+            "ends_at" => Some(MarketSort::EndsAt),
+            "created_at" => Some(MarketSort::CreatedAt),
+            "participant_count" => Some(MarketSort::ParticipantCount),
+            _ => None,
+        }
+    }
+
+    fn column(&self) -> &'static str {
+        match self {
+            MarketSort::Volume => "total_volume",
+            MarketSort::EndsAt => "ends_at",
+            MarketSort::CreatedAt => "created_at",
+            MarketSort::ParticipantCount => "participant_count",
+        }
+    }
+
+    fn ascending(&self) -> bool {
+        matches!(self, MarketSort::EndsAt)
+    }
+}
+
+/// Filters accepted by [`Database::list_markets`]. `None` means "no filter".
+#[derive(Debug, Clone, Default)]
+pub struct MarketListFilter {
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub creator: Option<String>,
+    pub ends_before: Option<DateTime<Utc>>,
+    pub ends_after: Option<DateTime<Utc>>,
+    pub sort: MarketSort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketListRow {
+    pub id: i64,
+    pub title: String,
+    pub status: String,
+    pub outcome_index: Option<i32>,
+    pub volume: f64,
+    pub participant_count: i64,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Off-chain metadata for a single market, as returned by
+/// [`Database::market_detail`].
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketDetail {
+    pub id: i64,
+    pub title: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub creator: Option<String>,
+    pub status: String,
+    /// Outcome labels, index-aligned with `outcome_odds`.
+    pub outcome_options: Vec<String>,
+    /// Implied probability per outcome in `[0, 1]`.
+    pub outcome_odds: Vec<f64>,
+    pub outcome_index: Option<i32>,
+    pub volume: f64,
+    pub participant_count: i64,
+    pub ends_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Stake token contract id, when recorded.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Versioned cover image URL; `None` until one is uploaded.
+    #[serde(default)]
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MarketSortKey {
+    Float(f64),
+    Int(i64),
+    Time(DateTime<Utc>),
+}
+
+/// Opaque keyset cursor for [`Database::list_markets`].
+///
+/// Encodes the sort order, the sort-key value of the last row on the page,
+/// and that row's id. A cursor is only valid for the sort order it was
+/// issued under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketCursor {
+    sort: MarketSort,
+    key: MarketSortKey,
+    id: i64,
+}
+
+impl MarketCursor {
+    /// Cursor pointing just past `row` in `sort` order.
+    pub fn after(row: &MarketListRow, sort: MarketSort) -> Self {
+        let key = match sort {
+            MarketSort::Volume => MarketSortKey::Float(row.volume),
+            MarketSort::EndsAt => MarketSortKey::Time(row.ends_at),
+            MarketSort::CreatedAt => MarketSortKey::Time(row.created_at),
+            MarketSort::ParticipantCount => MarketSortKey::Int(row.participant_count),
+        };
+        Self { sort, key, id: row.id }
+    }
+
+    pub fn encode(&self) -> String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        let key = match self.key {
+            MarketSortKey::Float(v) => v.to_string(),
+            MarketSortKey::Int(v) => v.to_string(),
+            MarketSortKey::Time(v) => v.timestamp_micros().to_string(),
+        };
+        URL_SAFE_NO_PAD.encode(format!("{}:{}:{}", self.sort.label(), key, self.id))
+    }
+
+    /// Decode a cursor issued for `sort`. Returns `None` for malformed
+    /// cursors and for cursors issued under a different sort order.
+    pub fn decode(raw: &str, sort: MarketSort) -> Option<Self> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+        let bytes = URL_SAFE_NO_PAD.decode(raw).ok()?;
+        let text = String::from_utf8(bytes).ok()?;
+        let mut parts = text.splitn(3, ':');
+        let cursor_sort = MarketSort::from_label(parts.next()?)?;
+        if cursor_sort != sort {
+            return None;
+        }
+        let raw_key = parts.next()?;
+        let id = parts.next()?.parse::<i64>().ok()?;
+        let key = match sort {
+            MarketSort::Volume => MarketSortKey::Float(raw_key.parse::<f64>().ok().filter(|v| v.is_finite())?),
+            MarketSort::ParticipantCount => MarketSortKey::Int(raw_key.parse::<i64>().ok()?),
+            MarketSort::EndsAt | MarketSort::CreatedAt => {
+                MarketSortKey::Time(DateTime::from_timestamp_micros(raw_key.parse::<i64>().ok()?)?)
+            }
+        };
+        Some(Self { sort, key, id })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentItem {
     pub id: i64,
+    pub slug: String,
     pub title: String,
     pub category: String,
     pub published_at: DateTime<Utc>,
@@ -117,6 +418,21 @@ pub struct NewsletterSubscriber {
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
+/// Result of redeeming a newsletter confirmation token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewsletterConfirmation {
+    Confirmed,
+    /// The token exists but was sent longer ago than the confirmation window;
+    /// the subscriber can ask for a fresh one.
+    Expired,
+    NotFound,
+}
+
+/// Every [`EmailCategory`] slug, for statements that write a row per category.
+fn email_categories() -> Vec<&'static str> {
+    EmailCategory::ALL.iter().map(EmailCategory::as_str).collect()
+}
+
 /// A single row from the `api_keys` table.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiKeyRecord {
@@ -126,6 +442,8 @@ pub struct ApiKeyRecord {
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
     pub revoked_at: Option<DateTime<Utc>>,
+    /// Requests allowed per calendar month (UTC); `None` is unlimited.
+    pub monthly_quota: Option<i64>,
 }
 
 impl Database {
@@ -136,7 +454,51 @@ impl Database {
     /// Snapshot pool size/idle into Prometheus gauges.
     /// Call this just before rendering `/metrics` so the values are current.
     pub fn record_pool_metrics(&self) {
-        self.metrics.observe_pool_connections("primary", self.pool.size() as i64, self.pool.num_idle() as i64);
+        let (size, idle) = (self.pool.size(), self.pool.num_idle());
+        self.metrics.set_pool_state(POOL_LABEL, i64::from(size), idle as i64);
+    }
+
+    /// Sample the pool every `DB_POOL_SAMPLE_INTERVAL_SECS` until `shutdown`,
+    /// so the gauges stay current between scrapes and a stall shows up as
+    /// acquire wait.
+    pub async fn run_pool_sampler(self, shutdown: tokio_util::sync::CancellationToken) {
+        const WORKER_NAME: &str = "db_pool_sampler";
+        self.metrics.set_worker_status(WORKER_NAME, true);
+        let mut interval = tokio::time::interval(self.sample_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            self.sample_pool().await;
+        }
+        self.metrics.set_worker_status(WORKER_NAME, false);
+    }
+
+    /// Record the pool gauges, then time acquiring a connection: the wait a
+    /// request arriving now would see. The probe runs no query.
+    pub(crate) async fn sample_pool(&self) {
+        self.record_pool_metrics();
+        let start = std::time::Instant::now();
+        let acquired = self.pool.acquire().await;
+        let waited = start.elapsed();
+        match acquired {
+            Ok(conn) => {
+                self.metrics.observe_pool_acquire(POOL_LABEL, waited);
+                drop(conn);
+            }
+            Err(sqlx::Error::PoolTimedOut) => {
+                self.metrics.observe_pool_acquire(POOL_LABEL, waited);
+                self.metrics.observe_db_pool_exhaustion(POOL_LABEL);
+                tracing::warn!(
+                    size = self.pool.size(),
+                    idle = self.pool.num_idle(),
+                    "db pool sampler: no connection within the acquire timeout"
+                );
+            }
+            Err(e) => tracing::warn!(error = %e, "db pool sampler: acquire failed"),
+        }
     }
 
     pub async fn new(
@@ -176,32 +538,72 @@ impl Database {
             .await
             .context("failed to connect to postgres")?;
 
+        let migrations = crate::migrations::MigrationRunner::new(&pool);
+        if pool_config.run_migrations {
+            migrations.run().await.context("failed to run database migrations")?;
+        } else {
+            let pending = migrations
+                .pending()
+                .await
+                .context("failed to check for pending database migrations")?;
+            if !pending.is_empty() {
+                anyhow::bail!(
+                    "{} database migration(s) pending ({}); set RUN_MIGRATIONS=true \
+                     or apply them with scripts/run_migrations.sh",
+                    pending.len(),
+                    pending.join(", ")
+                );
+            }
+        }
+
         Ok(Self {
             pool,
             cache,
             metrics,
             query_timeout: pool_config.query_timeout,
+            slow_query_threshold: pool_config.slow_query_threshold,
+            sample_interval: pool_config.sample_interval,
+            cache_ttls: CacheTtls::default(),
         })
     }
 
+    /// Use `ttls` for the cached queries instead of the defaults.
+    pub fn with_cache_ttls(mut self, ttls: CacheTtls) -> Self {
+        self.cache_ttls = ttls;
+        self
+    }
+
     /// Run `fut` with the configured query timeout.
     /// On success, records the query duration in the `db_query_duration_seconds` histogram.
     /// On timeout, increments the `db_timeouts` metric and logs a warning.
     /// On pool exhaustion, increments the `db_pool_exhaustion_total` counter.
-    async fn with_timeout<F, T>(&self, operation: &str, fut: F) -> Result<T, DbError>
+    /// Whatever the outcome, a query slower than `DB_SLOW_QUERY_THRESHOLD_MS`
+    /// is logged by `operation` (never its bound values) and counted in
+    /// `slow_queries_total`.
+    pub(crate) async fn with_timeout<F, T>(&self, operation: &str, fut: F) -> Result<T, DbError>
     where
         F: std::future::Future<Output = Result<T, sqlx::Error>>,
     {
         let start = std::time::Instant::now();
-        match tokio::time::timeout(self.query_timeout, fut).await {
+        let result = tokio::time::timeout(self.query_timeout, fut).await;
+        let elapsed = start.elapsed();
+        if elapsed >= self.slow_query_threshold {
+            self.metrics.observe_slow_query(operation);
+            tracing::warn!(
+                query = operation,
+                elapsed_ms = elapsed.as_millis() as u64,
+                threshold_ms = self.slow_query_threshold.as_millis() as u64,
+                "slow db query"
+            );
+        }
+        match result {
             Ok(Ok(v)) => {
-                self.metrics
-                    .observe_db_query_duration(operation, start.elapsed());
+                self.metrics.observe_db_query_duration(operation, elapsed);
                 Ok(v)
             }
             Ok(Err(e)) => {
                 if matches!(&e, sqlx::Error::PoolTimedOut) {
-                    self.metrics.observe_db_pool_exhaustion("api");
+                    self.metrics.observe_db_pool_exhaustion(POOL_LABEL);
                     return Err(DbError::PoolExhausted);
                 }
                 Err(DbError::Other(anyhow::Error::from(e)))
@@ -216,7 +618,7 @@ impl Database {
 
     pub async fn statistics_cached(&self) -> anyhow::Result<Statistics> {
         let key = keys::dbq_statistics();
-        let ttl = Duration::from_secs(5 * 60);
+        let ttl = self.cache_ttls.statistics;
         let endpoint = "statistics";
 
         let (value, hit) = self
@@ -252,14 +654,14 @@ impl Database {
 
     pub async fn featured_markets_cached(&self, limit: i64) -> anyhow::Result<Vec<FeaturedMarket>> {
         let key = keys::dbq_featured_markets(limit);
-        let ttl = Duration::from_secs(2 * 60);
+        let ttl = self.cache_ttls.featured_markets;
         let endpoint = "featured_markets";
 
         let (value, hit) = self
             .cache
             .get_or_set_json(&key, ttl, || async move {
                 let rows = self.with_timeout("featured_markets", sqlx::query(
-                    "SELECT id, title, total_volume, ends_at \
+                    "SELECT id, title, total_volume, ends_at, token, image_url \
                     FROM markets \
                     WHERE status = 'active' AND deleted_at IS NULL \
                     ORDER BY total_volume DESC, ends_at ASC \
@@ -275,6 +677,8 @@ impl Database {
                         title: row.try_get::<String, _>("title")?,
                         volume: row.try_get::<f64, _>("total_volume")?,
                         ends_at: row.try_get::<DateTime<Utc>, _>("ends_at")?,
+                        token: row.try_get::<Option<String>, _>("token")?,
+                        image_url: row.try_get::<Option<String>, _>("image_url")?,
                     });
                 }
 
@@ -291,16 +695,225 @@ impl Database {
         Ok(value)
     }
 
+    /// Ids of the markets the sync worker keeps warm: active markets plus
+    /// those resolved within `resolved_within`, highest volume first. Not
+    /// cached; the worker calls this every few passes.
+    pub async fn active_market_ids(
+        &self,
+        limit: i64,
+        resolved_within: Duration,
+    ) -> anyhow::Result<Vec<i64>> {
+        let ids = self.with_timeout("active_market_ids", sqlx::query_scalar::<_, i64>(
+            "SELECT id FROM markets \
+             WHERE deleted_at IS NULL \
+               AND (status = 'active' \
+                    OR (status = 'resolved' \
+                        AND resolved_at >= NOW() - make_interval(secs => $2))) \
+             ORDER BY total_volume DESC, id ASC \
+             LIMIT $1",
+        )
+        .bind(limit)
+        .bind(resolved_within.as_secs_f64())
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(ids)
+    }
+
+    /// Refresh profiles of the watched markets `ids`: status, deadline and
+    /// when `network` last indexed an event for each. Ids with no market row
+    /// are left out.
+    pub async fn sync_market_profiles(
+        &self,
+        network: &str,
+        ids: &[i64],
+    ) -> anyhow::Result<Vec<MarketProfile>> {
+        let rows = self.with_timeout("sync_market_profiles", sqlx::query(
+            "SELECT m.id, m.status, m.ends_at, \
+                    (SELECT MAX(e.indexed_at) FROM chain_events e \
+                     WHERE e.market_id = m.id AND e.network = $2) AS last_event_at \
+             FROM markets m \
+             WHERE m.id = ANY($1)",
+        )
+        .bind(ids)
+        .bind(network)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(MarketProfile {
+                    market_id: row.try_get("id")?,
+                    resolved: row.try_get::<String, _>("status")? == "resolved",
+                    ends_at: row.try_get("ends_at")?,
+                    last_event_at: row.try_get("last_event_at")?,
+                })
+            })
+            .collect()
+    }
+
+    /// List markets matching `filter`, ordered by `filter.sort`, starting just
+    /// after `cursor`.
+    ///
+    /// Fetches up to `limit + 1` rows so callers can detect whether another
+    /// page exists (see [`crate::pagination::PageResponse::from_fetched`]).
+    /// Not cached: the filter space is too wide for useful hit rates, and the
+    /// keyset indexes from migration 021 keep each page a single range scan.
+    pub async fn list_markets(
+        &self,
+        filter: &MarketListFilter,
+        cursor: Option<&MarketCursor>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<MarketListRow>> {
+        let sort = filter.sort;
+        let column = sort.column();
+        let direction = if sort.ascending() { "ASC" } else { "DESC" };
+
+        let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+            "SELECT id, title, status, outcome_index, total_volume, participant_count, \
+             ends_at, created_at \
+             FROM markets WHERE deleted_at IS NULL",
+        );
+
+        if let Some(status) = &filter.status {
+            qb.push(" AND status = ").push_bind(status);
+        }
+        if let Some(category) = &filter.category {
+            qb.push(" AND category = ").push_bind(category);
+        }
+        if let Some(creator) = &filter.creator {
+            qb.push(" AND creator = ").push_bind(creator);
+        }
+        if let Some(before) = filter.ends_before {
+            qb.push(" AND ends_at < ").push_bind(before);
+        }
+        if let Some(after) = filter.ends_after {
+            qb.push(" AND ends_at > ").push_bind(after);
+        }
+        if let Some(c) = cursor {
+            let cmp = if sort.ascending() { ">" } else { "<" };
+            qb.push(format!(" AND ({column}, id) {cmp} ("));
+            match c.key {
+                MarketSortKey::Float(v) => qb.push_bind(v),
+                MarketSortKey::Int(v) => qb.push_bind(v),
+                MarketSortKey::Time(v) => qb.push_bind(v),
+            };
+            qb.push(", ").push_bind(c.id).push(")");
+        }
+
+        qb.push(format!(" ORDER BY {column} {direction}, id {direction} LIMIT "))
+            .push_bind(limit + 1);
+
+        let rows = self
+            .with_timeout("list_markets", qb.build().fetch_all(&self.pool))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        let mut markets = Vec::with_capacity(rows.len());
+        for row in rows {
+            markets.push(MarketListRow {
+                id: row.try_get::<i64, _>("id")?,
+                title: row.try_get::<String, _>("title")?,
+                status: row.try_get::<String, _>("status")?,
+                outcome_index: row.try_get::<Option<i32>, _>("outcome_index")?,
+                volume: row.try_get::<f64, _>("total_volume")?,
+                participant_count: row.try_get::<i64, _>("participant_count")?,
+                ends_at: row.try_get::<DateTime<Utc>, _>("ends_at")?,
+                created_at: row.try_get::<DateTime<Utc>, _>("created_at")?,
+            });
+        }
+
+        Ok(markets)
+    }
+
+    /// Fetch the off-chain metadata for one market. Returns `Ok(None)` when no
+    /// live (non-soft-deleted) row exists. Not cached here — the combined
+    /// detail handler caches the merged document instead.
+    pub async fn market_detail(&self, market_id: i64) -> anyhow::Result<Option<MarketDetail>> {
+        let row = self.with_timeout("market_detail", sqlx::query(
+            "SELECT id, title, description, category, creator, status, outcome_options, \
+             outcome_odds, outcome_index, total_volume, participant_count, ends_at, \
+             created_at, resolved_at, token, image_url \
+             FROM markets \
+             WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.as_ref().map(market_detail_from_row).transpose()
+    }
+
+    /// The stored image of a live market, or `None` when the market does not
+    /// exist or has no image.
+    pub async fn market_image(&self, market_id: i64) -> anyhow::Result<Option<MarketImage>> {
+        let row = self.with_timeout("market_image", sqlx::query(
+            "SELECT id, image_key, image_content_type, image_etag, image_url \
+             FROM markets \
+             WHERE id = $1 AND deleted_at IS NULL AND image_key IS NOT NULL",
+        )
+        .bind(market_id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.map(|row| {
+            Ok(MarketImage {
+                market_id: row.try_get("id")?,
+                key: row.try_get("image_key")?,
+                content_type: row.try_get("image_content_type")?,
+                etag: row.try_get("image_etag")?,
+                url: row.try_get("image_url")?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Record `image` on its market. Returns `None` when the market does not
+    /// exist, otherwise the key of the image it replaced, if any.
+    pub async fn set_market_image(&self, image: &MarketImage) -> anyhow::Result<Option<Option<String>>> {
+        let row = self.with_timeout("set_market_image", sqlx::query(
+            "UPDATE markets m \
+             SET image_key = $2, image_content_type = $3, image_etag = $4, image_url = $5 \
+             FROM (SELECT id, image_key FROM markets \
+                   WHERE id = $1 AND deleted_at IS NULL FOR UPDATE) previous \
+             WHERE m.id = previous.id \
+             RETURNING previous.image_key AS previous_key",
+        )
+        .bind(image.market_id)
+        .bind(&image.key)
+        .bind(&image.content_type)
+        .bind(&image.etag)
+        .bind(&image.url)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.map(|row| Ok(row.try_get("previous_key")?)).transpose()
+    }
+
+    /// Indexed bet volume on `network` per stake token, in integer token
+    /// units. Amounts in different tokens are never summed; bets on markets
+    /// without a recorded token are grouped under `None`.
+    pub async fn volume_by_token(&self, network: &str) -> anyhow::Result<Vec<(Option<String>, String)>> {
+        let rows = self.with_timeout("volume_by_token", sqlx::query(
+            "SELECT m.token, COALESCE(SUM(e.amount), 0)::TEXT AS volume \
+             FROM chain_events e \
+             JOIN markets m ON m.id = e.market_id \
+             WHERE e.network = $1 AND e.kind = 'bet_place' AND m.deleted_at IS NULL \
+             GROUP BY m.token \
+             ORDER BY m.token",
+        )
+        .bind(network)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("token")?, row.try_get("volume")?)))
+            .collect()
+    }
+
     pub async fn content_cached(&self, limit: i64) -> anyhow::Result<Vec<ContentItem>> {
         let key = keys::dbq_content(limit);
-        let ttl = Duration::from_secs(60 * 60);
+        let ttl = self.cache_ttls.content;
         let endpoint = "content";
 
         let (value, hit) = self
             .cache
             .get_or_set_json(&key, ttl, || async move {
                 let rows = self.with_timeout("content", sqlx::query(
-                    "SELECT id, title, category, published_at \
+                    "SELECT id, slug, title, category, published_at \
                     FROM content \
                     WHERE is_published = TRUE \
                     ORDER BY published_at DESC \
@@ -313,6 +926,7 @@ impl Database {
                 for row in rows {
                     items.push(ContentItem {
                         id: row.try_get::<i64, _>("id")?,
+                        slug: row.try_get::<String, _>("slug")?,
                         title: row.try_get::<String, _>("title")?,
                         category: row.try_get::<String, _>("category")?,
                         published_at: row.try_get::<DateTime<Utc>, _>("published_at")?,
@@ -332,6 +946,92 @@ impl Database {
         Ok(value)
     }
 
+    /// A published article by slug; drafts are never returned.
+    pub async fn content_published_by_slug(&self, slug: &str) -> anyhow::Result<Option<ContentEntry>> {
+        let row = self.with_timeout("content_published_by_slug", sqlx::query(
+            "SELECT * FROM content WHERE slug = $1 AND is_published = TRUE",
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(content_entry_from_row).transpose()
+    }
+
+    /// Insert an article. A taken slug fails with
+    /// [`DbError::ConstraintViolation`].
+    pub async fn content_create(&self, fields: &ContentFields) -> anyhow::Result<ContentEntry> {
+        let row = self.with_timeout("content_create", sqlx::query(
+            "INSERT INTO content (slug, title, category, body, is_published, published_at) \
+             VALUES ($1, $2, $3, $4, $5, CASE WHEN $5 THEN COALESCE($6, NOW()) END) \
+             RETURNING *",
+        )
+        .bind(&fields.slug)
+        .bind(&fields.title)
+        .bind(&fields.category)
+        .bind(&fields.body)
+        .bind(fields.is_published)
+        .bind(fields.published_at)
+        .fetch_one(&self.pool)).await.map_err(unique_violation)?;
+        content_entry_from_row(&row)
+    }
+
+    /// Replace every editable field of an article. Publishing a draft stamps
+    /// `published_at` unless one is given; an already-published article keeps
+    /// its time. Returns `None` for an unknown id; a taken slug fails with
+    /// [`DbError::ConstraintViolation`].
+    pub async fn content_update(
+        &self,
+        id: i64,
+        fields: &ContentFields,
+    ) -> anyhow::Result<Option<ContentEntry>> {
+        let row = self.with_timeout("content_update", sqlx::query(
+            "UPDATE content \
+             SET slug = $2, title = $3, category = $4, body = $5, \
+                 published_at = CASE WHEN $6 \
+                     THEN COALESCE($7, CASE WHEN is_published THEN published_at END, NOW()) \
+                 END, \
+                 is_published = $6, \
+                 updated_at = NOW() \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(&fields.slug)
+        .bind(&fields.title)
+        .bind(&fields.category)
+        .bind(&fields.body)
+        .bind(fields.is_published)
+        .bind(fields.published_at)
+        .fetch_optional(&self.pool)).await.map_err(unique_violation)?;
+        row.as_ref().map(content_entry_from_row).transpose()
+    }
+
+    /// Publish a draft, stamping `published_at` with the current time. An
+    /// already-published article is returned unchanged. Returns `None` for an
+    /// unknown id.
+    pub async fn content_publish(&self, id: i64) -> anyhow::Result<Option<ContentEntry>> {
+        let row = self.with_timeout("content_publish", sqlx::query(
+            "UPDATE content \
+             SET published_at = CASE WHEN is_published THEN published_at ELSE NOW() END, \
+                 updated_at = CASE WHEN is_published THEN updated_at ELSE NOW() END, \
+                 is_published = TRUE \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(content_entry_from_row).transpose()
+    }
+
+    /// Returns whether a row existed.
+    pub async fn content_delete(&self, id: i64) -> anyhow::Result<bool> {
+        let deleted = self.with_timeout("content_delete", sqlx::query(
+            "DELETE FROM content WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
     pub async fn newsletter_get_by_email(
         &self,
         normalized_email: &str,
@@ -365,79 +1065,151 @@ impl Database {
         normalized_email: &str,
         source: &str,
         confirmation_token: &str,
-    ) -> anyhow::Result<()> {
-        self.with_timeout("newsletter_upsert_pending", sqlx::query(
-            "INSERT INTO newsletter_subscribers (email, source, confirmed, confirmation_token, created_at, confirmed_at, unsubscribed_at)
-             VALUES ($1, $2, FALSE, $3, NOW(), NULL, NULL)
+    ) -> anyhow::Result<uuid::Uuid> {
+        let row = self.with_timeout("newsletter_upsert_pending", sqlx::query(
+            "INSERT INTO newsletter_subscribers (email, source, confirmed, confirmation_token, confirmation_sent_at, created_at, confirmed_at, unsubscribed_at)
+             VALUES ($1, $2, FALSE, $3, NOW(), NOW(), NULL, NULL)
              ON CONFLICT (email) DO UPDATE SET
                  source = EXCLUDED.source,
                  confirmed = FALSE,
                  confirmation_token = EXCLUDED.confirmation_token,
+                 confirmation_sent_at = NOW(),
                  created_at = NOW(),
                  confirmed_at = NULL,
-                 unsubscribed_at = NULL",
+                 unsubscribed_at = NULL
+             RETURNING id",
         )
         .bind(normalized_email)
         .bind(source)
         .bind(confirmation_token)
-        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
 
-        Ok(())
+        Ok(row.try_get("id")?)
     }
 
+    /// Redeem a confirmation token. A token sent more than `token_ttl_secs`
+    /// ago is left in place and reported as expired, so the subscriber can
+    /// be told to request a new one rather than that the link is unknown.
+    /// Confirming turns every [`EmailCategory`] on for the address.
     pub async fn newsletter_confirm_by_token(
         &self,
         token: &str,
         token_ttl_secs: u64,
-    ) -> anyhow::Result<bool> {
-        let result = self.with_timeout("newsletter_confirm_by_token", sqlx::query(
-            "UPDATE newsletter_subscribers
-             SET confirmed = TRUE, confirmation_token = NULL, confirmed_at = NOW(), unsubscribed_at = NULL
-             WHERE confirmation_token = $1
-               AND created_at > NOW() - ($2 || ' seconds')::INTERVAL",
+    ) -> anyhow::Result<NewsletterConfirmation> {
+        let row = self.with_timeout("newsletter_confirm_by_token", sqlx::query(
+            "WITH target AS (
+                 SELECT id, confirmation_sent_at > NOW() - ($2 || ' seconds')::INTERVAL AS fresh
+                 FROM newsletter_subscribers
+                 WHERE confirmation_token = $1 AND deleted_at IS NULL
+             ),
+             redeemed AS (
+                 UPDATE newsletter_subscribers s
+                 SET confirmed = TRUE, confirmation_token = NULL, confirmation_sent_at = NULL,
+                     confirmed_at = NOW(), unsubscribed_at = NULL
+                 FROM target
+                 WHERE s.id = target.id AND target.fresh
+                 RETURNING s.email
+             ),
+             defaults AS (
+                 INSERT INTO newsletter_preferences (email, category, enabled)
+                 SELECT redeemed.email, category, TRUE
+                 FROM redeemed CROSS JOIN UNNEST($3::TEXT[]) AS category
+                 ON CONFLICT (email, category) DO UPDATE SET enabled = TRUE, updated_at = NOW()
+             )
+             SELECT COALESCE(fresh, FALSE) AS fresh FROM target",
         )
         .bind(token)
         .bind(token_ttl_secs as i64)
-        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .bind(email_categories())
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(match row {
+            None => NewsletterConfirmation::NotFound,
+            Some(row) if row.try_get::<bool, _>("fresh")? => NewsletterConfirmation::Confirmed,
+            Some(_) => NewsletterConfirmation::Expired,
+        })
+    }
+
+    /// Replace the token of a pending subscription and restart its
+    /// confirmation window, unless the last token went out less than
+    /// `cooldown_secs` ago. Returns the subscriber id, or `None` (and changes
+    /// nothing) when there is no pending subscription for the email or it is
+    /// still cooling down.
+    pub async fn newsletter_resend_confirmation(
+        &self,
+        normalized_email: &str,
+        confirmation_token: &str,
+        cooldown_secs: u64,
+    ) -> anyhow::Result<Option<uuid::Uuid>> {
+        let row = self.with_timeout("newsletter_resend_confirmation", sqlx::query(
+            "UPDATE newsletter_subscribers
+             SET confirmation_token = $2, confirmation_sent_at = NOW()
+             WHERE email = $1
+               AND deleted_at IS NULL
+               AND unsubscribed_at IS NULL
+               AND confirmation_token IS NOT NULL
+               AND confirmation_sent_at <= NOW() - ($3 || ' seconds')::INTERVAL
+             RETURNING id",
+        )
+        .bind(normalized_email)
+        .bind(confirmation_token)
+        .bind(cooldown_secs as i64)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(row.map(|row| row.try_get("id")).transpose()?)
     }
 
-    /// Remove pending (unconfirmed) subscriptions whose token has expired.
+    /// Purge pending (unconfirmed) subscriptions whose last confirmation
+    /// email went out more than `retention_secs` ago. Confirmed and
+    /// unsubscribed rows hold no token and are never touched.
     /// `batch_size` caps the number of rows deleted per call to prevent long
     /// table locks on large datasets; callers should loop until 0 rows are
     /// returned if they need to drain the full backlog.
     pub async fn newsletter_delete_expired_pending(
         &self,
-        token_ttl_secs: u64,
+        retention_secs: u64,
         batch_size: u64,
     ) -> anyhow::Result<u64> {
         let result = self.with_timeout("newsletter_delete_expired_pending", sqlx::query(
             "DELETE FROM newsletter_subscribers
              WHERE id IN (
                  SELECT id FROM newsletter_subscribers
-                 WHERE confirmed = FALSE
-                   AND created_at <= NOW() - ($1 || ' seconds')::INTERVAL
+                 WHERE confirmation_token IS NOT NULL
+                   AND confirmed = FALSE
+                   AND confirmation_sent_at <= NOW() - ($1 || ' seconds')::INTERVAL
                  LIMIT $2
              )",
         )
-        .bind(token_ttl_secs as i64)
+        .bind(retention_secs as i64)
         .bind(batch_size as i64)
         .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
 
         Ok(result.rows_affected())
     }
 
+    /// Unsubscribe from everything: the subscription is ended and every
+    /// [`EmailCategory`] is turned off for the address.
     pub async fn newsletter_unsubscribe(&self, normalized_email: &str) -> anyhow::Result<bool> {
-        let result = self.with_timeout("newsletter_unsubscribe", sqlx::query(
-            "UPDATE newsletter_subscribers
-             SET unsubscribed_at = NOW(), confirmed = FALSE
-             WHERE email = $1 AND deleted_at IS NULL",
+        let row = self.with_timeout("newsletter_unsubscribe", sqlx::query(
+            "WITH unsubscribed AS (
+                 UPDATE newsletter_subscribers
+                 SET unsubscribed_at = NOW(), confirmed = FALSE
+                 WHERE email = $1 AND deleted_at IS NULL
+                 RETURNING email
+             ),
+             muted AS (
+                 INSERT INTO newsletter_preferences (email, category, enabled)
+                 SELECT unsubscribed.email, category, FALSE
+                 FROM unsubscribed CROSS JOIN UNNEST($2::TEXT[]) AS category
+                 ON CONFLICT (email, category) DO UPDATE SET enabled = FALSE, updated_at = NOW()
+             )
+             SELECT COUNT(*) AS count FROM unsubscribed",
         )
         .bind(normalized_email)
-        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .bind(email_categories())
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
 
-        Ok(result.rows_affected() > 0)
+        Ok(row.try_get::<i64, _>("count")? > 0)
     }
 
     pub async fn newsletter_soft_delete(&self, normalized_email: &str) -> anyhow::Result<bool> {
@@ -453,13 +1225,87 @@ impl Database {
     }
 
     pub async fn newsletter_gdpr_delete(&self, normalized_email: &str) -> anyhow::Result<bool> {
-        let result = self.with_timeout("newsletter_gdpr_delete", sqlx::query("DELETE FROM newsletter_subscribers WHERE email = $1")
+        let result = self.with_timeout("newsletter_gdpr_delete", sqlx::query(
+            "WITH prefs AS (DELETE FROM newsletter_preferences WHERE email = $1)
+             DELETE FROM newsletter_subscribers WHERE email = $1",
+        )
             .bind(normalized_email)
             .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
 
         Ok(result.rows_affected() > 0)
     }
 
+    /// Email of the (not deleted) subscriber a preferences token names.
+    pub async fn newsletter_email_by_id(&self, subscriber_id: uuid::Uuid) -> anyhow::Result<Option<String>> {
+        let row = self.with_timeout("newsletter_email_by_id", sqlx::query(
+            "SELECT email FROM newsletter_subscribers WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(subscriber_id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(row.map(|row| row.try_get("email")).transpose()?)
+    }
+
+    /// Stored preferences for an address as `(category, enabled)`. Categories
+    /// without a row are not returned; they count as enabled.
+    pub async fn newsletter_get_preferences(
+        &self,
+        normalized_email: &str,
+    ) -> anyhow::Result<Vec<(String, bool)>> {
+        let rows = self.with_timeout("newsletter_get_preferences", sqlx::query(
+            "SELECT category, enabled FROM newsletter_preferences WHERE email = $1 ORDER BY category",
+        )
+        .bind(normalized_email)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("category")?, row.try_get("enabled")?)))
+            .collect()
+    }
+
+    /// Upsert one preference per `(category, enabled)` pair; categories not
+    /// listed are left as they are.
+    pub async fn newsletter_set_preferences(
+        &self,
+        normalized_email: &str,
+        changes: &[(EmailCategory, bool)],
+    ) -> anyhow::Result<()> {
+        let categories: Vec<&str> = changes.iter().map(|(category, _)| category.as_str()).collect();
+        let enabled: Vec<bool> = changes.iter().map(|(_, enabled)| *enabled).collect();
+        self.with_timeout("newsletter_set_preferences", sqlx::query(
+            "INSERT INTO newsletter_preferences (email, category, enabled)
+             SELECT $1, category, enabled FROM UNNEST($2::TEXT[], $3::BOOL[]) AS t(category, enabled)
+             ON CONFLICT (email, category) DO UPDATE SET enabled = EXCLUDED.enabled, updated_at = NOW()",
+        )
+        .bind(normalized_email)
+        .bind(categories)
+        .bind(enabled)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    /// Whether mail of `category` may be sent to `email`: the address is not
+    /// on the suppression list and has not turned the category off.
+    pub async fn email_allowed_for_category(
+        &self,
+        email: &str,
+        category: EmailCategory,
+    ) -> anyhow::Result<bool> {
+        let row = self.with_timeout("email_allowed_for_category", sqlx::query(
+            "SELECT NOT EXISTS (SELECT 1 FROM email_suppressions WHERE email = $1)
+                AND NOT EXISTS (
+                    SELECT 1 FROM newsletter_preferences
+                    WHERE email = $1 AND category = $2 AND NOT enabled
+                ) AS allowed",
+        )
+        .bind(email)
+        .bind(category.as_str())
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(row.try_get("allowed")?)
+    }
+
     // Email job management
     pub async fn email_create_job(
         &self,
@@ -486,68 +1332,69 @@ impl Database {
 
     pub async fn email_get_job(&self, job_id: uuid::Uuid) -> anyhow::Result<Option<crate::email::EmailJob>> {
         let row = self.with_timeout("email_get_job", sqlx::query(
-            "SELECT id, job_type, recipient_email, template_name, template_data, status, priority,
-                    attempts, max_attempts, scheduled_at, started_at, completed_at, failed_at,
-                    error_message, created_at, updated_at
-             FROM email_jobs WHERE id = $1",
+            "SELECT * FROM email_jobs WHERE id = $1",
         )
         .bind(job_id)
         .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
 
-        if let Some(row) = row {
-            return Ok(Some(crate::email::EmailJob {
-                id: row.try_get("id")?,
-                job_type: row.try_get("job_type")?,
-                recipient_email: row.try_get("recipient_email")?,
-                template_name: row.try_get("template_name")?,
-                template_data: row.try_get("template_data")?,
-                status: row.try_get("status")?,
-                priority: row.try_get("priority")?,
-                attempts: row.try_get("attempts")?,
-                max_attempts: row.try_get("max_attempts")?,
-                scheduled_at: row.try_get("scheduled_at")?,
-                started_at: row.try_get("started_at")?,
-                completed_at: row.try_get("completed_at")?,
-                failed_at: row.try_get("failed_at")?,
-                error_message: row.try_get("error_message")?,
-                created_at: row.try_get("created_at")?,
-                updated_at: row.try_get("updated_at")?,
-            }));
-        }
+        row.as_ref().map(email_job_from_row).transpose()
+    }
 
-        Ok(None)
+    /// Claim the next due job: highest priority first, then oldest
+    /// `scheduled_at`. `SKIP LOCKED` lets concurrent workers each take a
+    /// different row instead of queueing on the same one; the claimed row
+    /// is `processing` before the lock is released.
+    pub async fn email_claim_due_job(&self) -> anyhow::Result<Option<crate::email::EmailJob>> {
+        let row = self.with_timeout("email_claim_due_job", sqlx::query(
+            "UPDATE email_jobs \
+             SET status = 'processing', started_at = NOW(), updated_at = NOW() \
+             WHERE id = ( \
+                 SELECT id FROM email_jobs \
+                 WHERE status = 'pending' AND scheduled_at <= NOW() \
+                 ORDER BY priority DESC, scheduled_at, created_at \
+                 LIMIT 1 \
+                 FOR UPDATE SKIP LOCKED \
+             ) \
+             RETURNING *",
+        )
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.as_ref().map(email_job_from_row).transpose()
     }
 
-    pub async fn email_update_job_status(
+    /// Put a failed job back in the queue, due at `scheduled_at`.
+    pub async fn email_reschedule_job(
         &self,
         job_id: uuid::Uuid,
-        status: &str,
-        error_message: Option<&str>,
+        attempts: i32,
+        scheduled_at: DateTime<Utc>,
+        error_message: &str,
     ) -> anyhow::Result<()> {
-        self.with_timeout("email_update_job_status", sqlx::query(
-            "UPDATE email_jobs
-             SET status = $2, error_message = $3, updated_at = NOW(),
-                 completed_at = CASE WHEN $2 = 'completed' THEN NOW() ELSE completed_at END,
-                 failed_at = CASE WHEN $2 = 'failed' THEN NOW() ELSE failed_at END
+        self.with_timeout("email_reschedule_job", sqlx::query(
+            "UPDATE email_jobs \
+             SET status = 'pending', attempts = $2, scheduled_at = $3, error_message = $4, updated_at = NOW() \
              WHERE id = $1",
         )
         .bind(job_id)
-        .bind(status)
+        .bind(attempts)
+        .bind(scheduled_at)
         .bind(error_message)
         .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
 
         Ok(())
     }
 
-    pub async fn email_update_job_attempts(
+    /// Park a job that exhausted its attempts in the `dead_letter` state.
+    pub async fn email_dead_letter_job(
         &self,
         job_id: uuid::Uuid,
         attempts: i32,
-        error_message: Option<&str>,
+        error_message: &str,
     ) -> anyhow::Result<()> {
-        self.with_timeout("email_update_job_attempts", sqlx::query(
-            "UPDATE email_jobs
-             SET attempts = $2, error_message = $3, updated_at = NOW()
+        self.with_timeout("email_dead_letter_job", sqlx::query(
+            "UPDATE email_jobs \
+             SET status = 'dead_letter', attempts = $2, error_message = $3, \
+                 failed_at = NOW(), updated_at = NOW() \
              WHERE id = $1",
         )
         .bind(job_id)
@@ -558,12 +1405,129 @@ impl Database {
         Ok(())
     }
 
-    // Email event tracking
-    /// Create an email event record.
-    ///
-    /// ## PII Considerations
-    ///
-    /// The `recipient` field contains a personally identifiable email address.
+    /// Dead-lettered jobs, most recently failed first.
+    pub async fn email_list_dead_letter_jobs(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<crate::email::EmailJob>> {
+        let rows = self.with_timeout("email_list_dead_letter_jobs", sqlx::query(
+            "SELECT * FROM email_jobs WHERE status = 'dead_letter' \
+             ORDER BY failed_at DESC NULLS LAST, id \
+             LIMIT $1 OFFSET $2",
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter().map(email_job_from_row).collect()
+    }
+
+    /// Return a dead-lettered job to the queue with a fresh attempt budget,
+    /// due at `scheduled_at`. Returns `None` when `job_id` is not dead-lettered.
+    pub async fn email_retry_dead_letter_job(
+        &self,
+        job_id: uuid::Uuid,
+        scheduled_at: DateTime<Utc>,
+    ) -> anyhow::Result<Option<crate::email::EmailJob>> {
+        let row = self.with_timeout("email_retry_dead_letter_job", sqlx::query(
+            "UPDATE email_jobs \
+             SET status = 'pending', attempts = 0, scheduled_at = $2, \
+                 error_message = NULL, failed_at = NULL, updated_at = NOW() \
+             WHERE id = $1 AND status = 'dead_letter' \
+             RETURNING *",
+        )
+        .bind(job_id)
+        .bind(scheduled_at)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.as_ref().map(email_job_from_row).transpose()
+    }
+
+    /// Return jobs left `processing` for longer than `stale_after` (a worker
+    /// died mid-send) to the queue. Returns how many were recovered.
+    pub async fn email_recover_stale_jobs(&self, stale_after: Duration) -> anyhow::Result<u64> {
+        let result = self.with_timeout("email_recover_stale_jobs", sqlx::query(
+            "UPDATE email_jobs \
+             SET status = 'pending', started_at = NULL, updated_at = NOW() \
+             WHERE status = 'processing' \
+               AND started_at < NOW() - make_interval(secs => $1)",
+        )
+        .bind(stale_after.as_secs_f64())
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Job counts by queue state. `retry` counts pending jobs that have
+    /// already failed at least once.
+    pub async fn email_job_counts(&self) -> anyhow::Result<crate::email::queue::QueueStats> {
+        let row = self.with_timeout("email_job_counts", sqlx::query(
+            "SELECT \
+                 COUNT(*) FILTER (WHERE status = 'pending' AND attempts = 0) AS pending, \
+                 COUNT(*) FILTER (WHERE status = 'processing') AS processing, \
+                 COUNT(*) FILTER (WHERE status = 'pending' AND attempts > 0) AS retry, \
+                 COUNT(*) FILTER (WHERE status = 'dead_letter') AS dead_letter \
+             FROM email_jobs \
+             WHERE status IN ('pending', 'processing', 'dead_letter')",
+        )
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let count = |name: &str| -> anyhow::Result<usize> { Ok(row.try_get::<i64, _>(name)? as usize) };
+        Ok(crate::email::queue::QueueStats {
+            pending: count("pending")?,
+            processing: count("processing")?,
+            retry: count("retry")?,
+            dead_letter: count("dead_letter")?,
+        })
+    }
+
+    pub async fn email_update_job_status(
+        &self,
+        job_id: uuid::Uuid,
+        status: &str,
+        error_message: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("email_update_job_status", sqlx::query(
+            "UPDATE email_jobs
+             SET status = $2, error_message = $3, updated_at = NOW(),
+                 completed_at = CASE WHEN $2 = 'completed' THEN NOW() ELSE completed_at END,
+                 failed_at = CASE WHEN $2 = 'failed' THEN NOW() ELSE failed_at END
+             WHERE id = $1",
+        )
+        .bind(job_id)
+        .bind(status)
+        .bind(error_message)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    pub async fn email_update_job_attempts(
+        &self,
+        job_id: uuid::Uuid,
+        attempts: i32,
+        error_message: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("email_update_job_attempts", sqlx::query(
+            "UPDATE email_jobs
+             SET attempts = $2, error_message = $3, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(job_id)
+        .bind(attempts)
+        .bind(error_message)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
+    // Email event tracking
+    /// Create an email event record.
+    ///
+    /// ## PII Considerations
+    ///
+    /// The `recipient` field contains a personally identifiable email address.
     /// This is stored for analytics purposes: to correlate events with recipients,
     /// calculate delivery success rates, and track engagement per email domain.
     ///
@@ -580,11 +1544,7 @@ impl Database {
         recipient: &str,
         metadata: serde_json::Value,
     ) -> anyhow::Result<uuid::Uuid> {
-        let row = self.with_timeout("email_create_event", sqlx::query(
-            "INSERT INTO email_events (email_job_id, message_id, event_type, recipient_email, metadata)
-             VALUES ($1, $2, $3, $4, $5)
-             RETURNING id",
-        )
+        let row = self.with_timeout("email_create_event", sqlx::query(EMAIL_EVENT_INSERT)
         .bind(job_id)
         .bind(message_id)
         .bind(event_type)
@@ -603,15 +1563,7 @@ impl Database {
         reason: Option<&str>,
         bounce_type: Option<&str>,
     ) -> anyhow::Result<()> {
-        self.with_timeout("email_add_suppression", sqlx::query(
-            "INSERT INTO email_suppressions (email, suppression_type, reason, bounce_type)
-             VALUES ($1, $2, $3, $4)
-             ON CONFLICT (email) DO UPDATE SET
-                 suppression_type = EXCLUDED.suppression_type,
-                 reason = EXCLUDED.reason,
-                 bounce_type = EXCLUDED.bounce_type,
-                 updated_at = NOW()",
-        )
+        self.with_timeout("email_add_suppression", sqlx::query(EMAIL_SUPPRESSION_UPSERT)
         .bind(email)
         .bind(suppression_type)
         .bind(reason)
@@ -644,37 +1596,63 @@ impl Database {
         counter_type: &str,
         template_name: Option<&str>,
     ) -> anyhow::Result<()> {
-        let template = template_name.unwrap_or("unknown");
-        let today = chrono::Utc::now().date_naive();
-
-        let column = match counter_type {
-            "sent" => "sent_count",
-            "delivered" => "delivered_count",
-            "opened" => "opened_count",
-            "clicked" => "clicked_count",
-            "bounced" => "bounced_count",
-            "complained" => "complained_count",
-            "unsubscribed" => "unsubscribed_count",
-            _ => return Ok(()),
+        let Some(query_str) = email_analytics_increment_sql(counter_type) else {
+            return Ok(());
         };
 
-        let query_str = format!(
-            "INSERT INTO email_analytics (template_name, date, {})
-             VALUES ($1, $2, 1)
-             ON CONFLICT (template_name, variant_name, date) DO UPDATE SET
-                 {} = email_analytics.{} + 1,
-                 updated_at = NOW()",
-            column, column, column
-        );
-
         self.with_timeout("email_increment_analytics_counter", sqlx::query(&query_str)
-            .bind(template)
-            .bind(today)
+            .bind(template_name.unwrap_or("unknown"))
+            .bind(chrono::Utc::now().date_naive())
             .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
 
         Ok(())
     }
 
+    /// Store a provider webhook event together with its suppression and
+    /// analytics counter in one transaction. Either all of them land or none
+    /// does, so a retried event is neither lost nor counted twice.
+    pub async fn email_record_webhook_event(
+        &self,
+        message_id: Option<&str>,
+        event_type: &str,
+        recipient: &str,
+        metadata: serde_json::Value,
+        effect: &crate::email::webhook::EventEffect<'_>,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("email_record_webhook_event", async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(EMAIL_EVENT_INSERT)
+                .bind(None::<uuid::Uuid>)
+                .bind(message_id)
+                .bind(event_type)
+                .bind(recipient)
+                .bind(metadata)
+                .execute(&mut *tx)
+                .await?;
+            if let Some(suppression) = &effect.suppression {
+                sqlx::query(EMAIL_SUPPRESSION_UPSERT)
+                    .bind(recipient)
+                    .bind(suppression.kind.as_str())
+                    .bind(suppression.reason)
+                    .bind(suppression.bounce_type)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            if let Some(query_str) = effect.counter.and_then(email_analytics_increment_sql) {
+                sqlx::query(&query_str)
+                    .bind("unknown")
+                    .bind(chrono::Utc::now().date_naive())
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            tx.commit().await
+        })
+        .await
+        .map_err(anyhow::Error::from)?;
+
+        Ok(())
+    }
+
     pub async fn email_get_analytics(
         &self,
         template_name: Option<&str>,
@@ -821,39 +1799,10 @@ impl Database {
         Ok(count > 0)
     }
 
-    /// Persist a dead-letter job to PostgreSQL for durable audit and recovery.
-    ///
-    /// Called alongside the Redis ZADD so a Redis flush never loses the entry.
-    pub async fn email_create_dead_letter_job(
-        &self,
-        id: uuid::Uuid,
-        payload: serde_json::Value,
-        failure_reason: &str,
-        retry_count: i32,
-    ) -> anyhow::Result<()> {
-        self.with_timeout(
-            "email_create_dead_letter_job",
-            sqlx::query(
-                "INSERT INTO email_dead_letter_jobs (id, payload, failure_reason, retry_count)
-                 VALUES ($1, $2, $3, $4)
-                 ON CONFLICT (id) DO UPDATE
-                   SET failure_reason = EXCLUDED.failure_reason,
-                       retry_count    = EXCLUDED.retry_count",
-            )
-            .bind(id)
-            .bind(payload)
-            .bind(failure_reason)
-            .bind(retry_count)
-            .execute(&self.pool),
-        )
-        .await
-        .map_err(anyhow::Error::from)?;
-        Ok(())
-    }
-
     /// Upsert a watched transaction record. Called when a new tx hash is added to the monitor.
     pub async fn watched_tx_upsert(
         &self,
+        network: &str,
         tx_hash: &str,
         market_id: Option<i64>,
         expires_at: DateTime<Utc>,
@@ -861,8 +1810,8 @@ impl Database {
         self.with_timeout(
             "watched_tx_upsert",
             sqlx::query(
-                "INSERT INTO watched_transactions (tx_hash, market_id, expires_at, status)
-                 VALUES ($1, $2, $3, 'pending')
+                "INSERT INTO watched_transactions (tx_hash, market_id, expires_at, status, network)
+                 VALUES ($1, $2, $3, 'pending', $4)
                  ON CONFLICT (tx_hash) DO UPDATE
                      SET expires_at = EXCLUDED.expires_at,
                          status = CASE WHEN watched_transactions.status = 'pending'
@@ -872,6 +1821,7 @@ impl Database {
             .bind(tx_hash)
             .bind(market_id)
             .bind(expires_at)
+            .bind(network)
             .execute(&self.pool),
         )
         .await
@@ -879,42 +1829,24 @@ impl Database {
         Ok(())
     }
 
-    /// List all dead-letter job IDs from PostgreSQL (oldest-failed first).
-    ///
-    /// Used as a fallback when Redis is unavailable or has been flushed.
-    pub async fn email_list_dead_letter_jobs(&self) -> anyhow::Result<Vec<uuid::Uuid>> {
-        let rows = self
-            .with_timeout(
-                "email_list_dead_letter_jobs",
-                sqlx::query("SELECT id FROM email_dead_letter_jobs ORDER BY failed_at ASC")
-                    .fetch_all(&self.pool),
-            )
-            .await
-            .map_err(anyhow::Error::from)?;
-        rows.iter().map(|r| r.try_get("id").map_err(anyhow::Error::from)).collect()
-    }
-
-    /// Remove a dead-letter entry from PostgreSQL when the job is re-queued.
-    pub async fn email_delete_dead_letter_job(&self, id: uuid::Uuid) -> anyhow::Result<()> {
-        self.with_timeout(
-            "email_delete_dead_letter_job",
-            sqlx::query("DELETE FROM email_dead_letter_jobs WHERE id = $1")
-                .bind(id)
-                .execute(&self.pool),
-        )
-        .await
-        .map_err(anyhow::Error::from)?;
-        Ok(())
-    }
-
-    /// Load all non-expired pending transaction hashes for in-memory restoration on startup.
-    pub async fn watched_tx_load_pending(&self) -> anyhow::Result<Vec<String>> {
+    /// Load all non-expired pending transaction hashes for `network` for
+    /// in-memory restoration on startup. `include_unscoped` also returns rows
+    /// persisted before watches were scoped by network; only the primary
+    /// network's client should set it.
+    pub async fn watched_tx_load_pending(
+        &self,
+        network: &str,
+        include_unscoped: bool,
+    ) -> anyhow::Result<Vec<String>> {
         let rows = self.with_timeout(
             "watched_tx_load_pending",
             sqlx::query_scalar::<_, String>(
                 "SELECT tx_hash FROM watched_transactions
-                 WHERE status = 'pending' AND expires_at > NOW()",
+                 WHERE status = 'pending' AND expires_at > NOW()
+                   AND (network = $1 OR ($2 AND network IS NULL))",
             )
+            .bind(network)
+            .bind(include_unscoped)
             .fetch_all(&self.pool),
         )
         .await
@@ -942,110 +1874,3328 @@ impl Database {
         Ok(())
     }
 
-    // ── API key management (issue #892) ───────────────────────────────────────
+    // ── Transaction watch subscriptions ───────────────────────────────────────
 
-    /// Insert a new API key into the database.
-    /// The caller is responsible for supplying the SHA-256 hex hash of the raw key.
-    pub async fn api_key_insert(&self, key_hash: &str, label: &str) -> anyhow::Result<ApiKeyRecord> {
-        let row = self.with_timeout("api_key_insert", sqlx::query(
-            "INSERT INTO api_keys (key_hash, label)
-             VALUES ($1, $2)
-             RETURNING id, key_hash, label, created_at, expires_at, revoked_at",
-        )
-        .bind(key_hash)
-        .bind(label)
-        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+    /// Record a subscription to `tx_hash` finalizing. `secret` signs its
+    /// webhook payload.
+    pub async fn tx_watch_subscription_insert(
+        &self,
+        network: &str,
+        tx_hash: &str,
+        callback_url: Option<&str>,
+        email: Option<&str>,
+        secret: &str,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<TxSubscription> {
+        let row = self
+            .with_timeout(
+                "tx_watch_subscription_insert",
+                sqlx::query(
+                    "INSERT INTO tx_watch_subscriptions
+                         (id, network, tx_hash, callback_url, email, secret, expires_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)
+                     RETURNING id, network, tx_hash, callback_url, email, created_at, expires_at",
+                )
+                .bind(uuid::Uuid::new_v4())
+                .bind(network)
+                .bind(tx_hash)
+                .bind(callback_url)
+                .bind(email)
+                .bind(secret)
+                .bind(expires_at)
+                .fetch_one(&self.pool),
+            )
+            .await
+            .map_err(anyhow::Error::from)?;
 
-        Ok(ApiKeyRecord {
+        Ok(TxSubscription {
             id: row.try_get("id")?,
-            key_hash: row.try_get("key_hash")?,
-            label: row.try_get("label")?,
+            network: row.try_get("network")?,
+            tx_hash: row.try_get("tx_hash")?,
+            callback_url: row.try_get("callback_url")?,
+            email: row.try_get("email")?,
             created_at: row.try_get("created_at")?,
             expires_at: row.try_get("expires_at")?,
-            revoked_at: row.try_get("revoked_at")?,
         })
     }
 
-    /// Mark an existing key as expiring at the given timestamp (rotation overlap window).
-    /// Returns `true` if a row was updated, `false` if no key with that hash was found.
-    pub async fn api_key_set_expires(
-        &self,
-        key_hash: &str,
-        expires_at: DateTime<Utc>,
-    ) -> anyhow::Result<bool> {
-        let result = self.with_timeout("api_key_set_expires", sqlx::query(
-            "UPDATE api_keys SET expires_at = $1 WHERE key_hash = $2",
+    /// Remove a subscription that was never registered with the monitor.
+    pub async fn tx_watch_subscription_delete(&self, id: uuid::Uuid) -> anyhow::Result<()> {
+        self.with_timeout(
+            "tx_watch_subscription_delete",
+            sqlx::query("DELETE FROM tx_watch_subscriptions WHERE id = $1")
+                .bind(id)
+                .execute(&self.pool),
         )
-        .bind(expires_at)
-        .bind(key_hash)
-        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(())
+    }
 
-        Ok(result.rows_affected() > 0)
+    /// Mark every open, unexpired subscription to `tx_hash` notified and
+    /// return them. The update is atomic, so two monitors finalizing the same
+    /// hash never both notify a subscriber.
+    pub async fn tx_watch_subscriptions_claim(
+        &self,
+        network: &str,
+        tx_hash: &str,
+    ) -> anyhow::Result<Vec<ClaimedSubscription>> {
+        let rows = self
+            .with_timeout(
+                "tx_watch_subscriptions_claim",
+                sqlx::query(
+                    "UPDATE tx_watch_subscriptions
+                     SET notified_at = NOW()
+                     WHERE network = $1 AND tx_hash = $2
+                       AND notified_at IS NULL AND expires_at > NOW()
+                     RETURNING id, callback_url, email, secret",
+                )
+                .bind(network)
+                .bind(tx_hash)
+                .fetch_all(&self.pool),
+            )
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ClaimedSubscription {
+                    id: row.try_get("id")?,
+                    callback_url: row.try_get("callback_url")?,
+                    email: row.try_get("email")?,
+                    secret: row.try_get("secret")?,
+                })
+            })
+            .collect()
     }
 
-    /// Validate a raw API key: compute its SHA-256 hash, then return the record
-    /// if it exists, is not revoked, and is not expired.
-    pub async fn api_key_validate(&self, key_hash: &str) -> anyhow::Result<Option<ApiKeyRecord>> {
-        let row = self.with_timeout("api_key_validate", sqlx::query(
-            "SELECT id, key_hash, label, created_at, expires_at, revoked_at
-             FROM api_keys
-             WHERE key_hash = $1
-               AND revoked_at IS NULL
-               AND (expires_at IS NULL OR expires_at > NOW())",
-        )
-        .bind(key_hash)
-        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+    /// Delete subscriptions past their expiry, notified or not. Returns the
+    /// number removed.
+    pub async fn tx_watch_subscriptions_delete_expired(&self) -> anyhow::Result<u64> {
+        let result = self
+            .with_timeout(
+                "tx_watch_subscriptions_delete_expired",
+                sqlx::query("DELETE FROM tx_watch_subscriptions WHERE expires_at <= NOW()")
+                    .execute(&self.pool),
+            )
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
 
-        if let Some(row) = row {
-            return Ok(Some(ApiKeyRecord {
-                id: row.try_get("id")?,
-                key_hash: row.try_get("key_hash")?,
-                label: row.try_get("label")?,
-                created_at: row.try_get("created_at")?,
-                expires_at: row.try_get("expires_at")?,
-                revoked_at: row.try_get("revoked_at")?,
-            }));
-        }
+    // ── Indexed chain events ──────────────────────────────────────────────────
 
-        Ok(None)
+    /// Persist one decoded contract event. Replays of the same RPC event id are
+    /// no-ops, so the sync worker can re-scan ledgers freely.
+    pub async fn chain_event_insert(
+        &self,
+        network: &str,
+        event: &crate::blockchain::IndexedEvent,
+    ) -> anyhow::Result<()> {
+        self.chain_event_insert_at(network, event, None).await?;
+        Ok(())
     }
 
-    /// Hard-delete all keys where `expires_at <= NOW()` and `expires_at IS NOT NULL`.
-    /// Returns the number of rows deleted.
-    pub async fn api_key_delete_expired(&self) -> anyhow::Result<u64> {
-        let result = self.with_timeout("api_key_delete_expired", sqlx::query(
-            "DELETE FROM api_keys
-             WHERE expires_at IS NOT NULL AND expires_at <= NOW()",
+    /// [`Self::chain_event_insert`] with an explicit `indexed_at`, for
+    /// historical events stored long after their ledger closed; `None` means
+    /// now. Returns whether the event was new.
+    pub async fn chain_event_insert_at(
+        &self,
+        network: &str,
+        event: &crate::blockchain::IndexedEvent,
+        indexed_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<bool> {
+        let result = self.with_timeout(
+            "chain_event_insert",
+            sqlx::query(
+                "INSERT INTO chain_events
+                     (id, network, ledger, tx_hash, kind, market_id, address, outcome,
+                      amount, token, is_refund, indexed_at)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9::NUMERIC, $10, $11, COALESCE($12, NOW()))
+                 ON CONFLICT (id) DO NOTHING",
+            )
+            .bind(&event.id)
+            .bind(network)
+            .bind(i64::from(event.ledger))
+            .bind(&event.tx_hash)
+            .bind(&event.kind)
+            .bind(event.market_id)
+            .bind(&event.address)
+            .bind(event.outcome.map(|o| o as i32))
+            .bind(&event.amount)
+            .bind(&event.token)
+            .bind(event.is_refund)
+            .bind(indexed_at)
+            .execute(&self.pool),
         )
-        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() > 0)
+    }
 
-        Ok(result.rows_affected())
+    /// Give a market created on chain a `markets` row: its title from the
+    /// chain description, its category and creator, ending at its deadline.
+    /// A row that already exists keeps everything it has and only gains a
+    /// missing category or creator. Returns whether the row was inserted.
+    pub async fn market_skeleton_upsert(
+        &self,
+        creation: &crate::blockchain::MarketCreation,
+    ) -> anyhow::Result<bool> {
+        let row = self.with_timeout(
+            "market_skeleton_upsert",
+            sqlx::query(
+                "INSERT INTO markets (id, title, category, creator, ends_at)
+                 VALUES ($1, $2, $3, $4, to_timestamp($5))
+                 ON CONFLICT (id) DO UPDATE
+                    SET category = COALESCE(markets.category, EXCLUDED.category),
+                        creator  = COALESCE(markets.creator, EXCLUDED.creator)
+                 RETURNING (xmax = 0) AS inserted",
+            )
+            .bind(creation.market_id)
+            .bind(creation.title())
+            .bind(creation.category_slug())
+            .bind(&creation.creator)
+            .bind(creation.deadline as f64)
+            .fetch_one(&self.pool),
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(row.try_get::<bool, _>("inserted")?)
     }
 
-    /// List all active (non-revoked, non-expired) API keys.
-    pub async fn api_key_list_active(&self) -> anyhow::Result<Vec<ApiKeyRecord>> {
-        let rows = self.with_timeout("api_key_list_active", sqlx::query(
-            "SELECT id, key_hash, label, created_at, expires_at, revoked_at
-             FROM api_keys
-             WHERE revoked_at IS NULL
-               AND (expires_at IS NULL OR expires_at > NOW())
-             ORDER BY created_at DESC",
+    /// Drop indexed events above `ledger` so a reorged range can be replayed.
+    /// Returns the number of events removed.
+    pub async fn chain_events_delete_above(&self, network: &str, ledger: u32) -> anyhow::Result<u64> {
+        let result = self.with_timeout(
+            "chain_events_delete_above",
+            sqlx::query("DELETE FROM chain_events WHERE network = $1 AND ledger > $2")
+                .bind(network)
+                .bind(i64::from(ledger))
+                .execute(&self.pool),
         )
-        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
 
-        let mut keys = Vec::with_capacity(rows.len());
-        for row in rows {
-            keys.push(ApiKeyRecord {
-                id: row.try_get("id")?,
-                key_hash: row.try_get("key_hash")?,
-                label: row.try_get("label")?,
-                created_at: row.try_get("created_at")?,
-                expires_at: row.try_get("expires_at")?,
-                revoked_at: row.try_get("revoked_at")?,
-            });
+    /// One page of the activity feed: up to `limit + 1` events of the
+    /// filter's kinds, newest first, skipping `offset`. With a `since_id`,
+    /// the events indexed after it, oldest first, from the start.
+    ///
+    /// A market filter is answered from `idx_chain_events_market_kind` and
+    /// the unfiltered feed walks `idx_chain_events_network_ledger` in order
+    /// (both from migration 023). `since_id` is turned into a ledger bound
+    /// for the same index; event ids sort in ledger order, so `id >` does
+    /// the rest.
+    pub async fn activity_page(
+        &self,
+        network: &str,
+        filter: &ActivityFilter,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<ActivityRow>> {
+        let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+            "SELECT e.id, e.ledger, e.tx_hash, e.kind, e.market_id, e.address, e.outcome, \
+             e.amount::TEXT AS amount, p.state AS breaker_state, e.indexed_at \
+             FROM chain_events e \
+             LEFT JOIN protocol_state_changes p ON p.event_id = e.id",
+        );
+        push_activity_filter(&mut qb, network, filter);
+        if filter.since_id.is_some() {
+            qb.push(" ORDER BY e.ledger, e.id LIMIT ").push_bind(limit + 1);
+        } else {
+            qb.push(" ORDER BY e.ledger DESC, e.id DESC LIMIT ")
+                .push_bind(limit + 1)
+                .push(" OFFSET ")
+                .push_bind(offset);
         }
 
-        Ok(keys)
+        let rows = self
+            .with_timeout("activity_page", qb.build().fetch_all(&self.pool))
+            .await
+            .map_err(anyhow::Error::from)?;
+        rows.iter()
+            .map(|row| {
+                Ok(ActivityRow {
+                    id: row.try_get("id")?,
+                    ledger: row.try_get("ledger")?,
+                    tx_hash: row.try_get("tx_hash")?,
+                    kind: row.try_get("kind")?,
+                    market_id: row.try_get("market_id")?,
+                    address: row.try_get("address")?,
+                    outcome: row.try_get("outcome")?,
+                    amount: row.try_get("amount")?,
+                    breaker_state: row.try_get("breaker_state")?,
+                    indexed_at: row.try_get("indexed_at")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Events per kind matching `filter`, across every page.
+    pub async fn activity_counts(&self, network: &str, filter: &ActivityFilter) -> anyhow::Result<Vec<(String, i64)>> {
+        let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new("SELECT e.kind, COUNT(*) AS n FROM chain_events e");
+        push_activity_filter(&mut qb, network, filter);
+        qb.push(" GROUP BY e.kind");
+
+        let rows = self
+            .with_timeout("activity_counts", qb.build().fetch_all(&self.pool))
+            .await
+            .map_err(anyhow::Error::from)?;
+        rows.iter()
+            .map(|row| Ok((row.try_get("kind")?, row.try_get("n")?)))
+            .collect()
+    }
+
+    /// Record a circuit breaker transition. Idempotent per event.
+    pub async fn protocol_state_change_insert(
+        &self,
+        network: &str,
+        change: &ProtocolStateChange,
+    ) -> anyhow::Result<()> {
+        self.with_timeout(
+            "protocol_state_change_insert",
+            sqlx::query(
+                "INSERT INTO protocol_state_changes (event_id, network, ledger, state, changed_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (event_id) DO NOTHING",
+            )
+            .bind(&change.event_id)
+            .bind(network)
+            .bind(i64::from(change.ledger))
+            .bind(change.state.label())
+            .bind(change.changed_at)
+            .execute(&self.pool),
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// The `limit` latest circuit breaker transitions on `network`, newest
+    /// first.
+    pub async fn protocol_state_changes_recent(
+        &self,
+        network: &str,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ProtocolStateChange>> {
+        let rows = self
+            .with_timeout(
+                "protocol_state_changes_recent",
+                sqlx::query(
+                    "SELECT event_id, ledger, state, changed_at
+                     FROM protocol_state_changes
+                     WHERE network = $1
+                     ORDER BY ledger DESC, event_id DESC
+                     LIMIT $2",
+                )
+                .bind(network)
+                .bind(limit)
+                .fetch_all(&self.pool),
+            )
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                let state: String = row.try_get("state")?;
+                Ok(ProtocolStateChange {
+                    event_id: row.try_get("event_id")?,
+                    ledger: u32::try_from(row.try_get::<i64, _>("ledger")?)?,
+                    state: BreakerState::parse(&state)
+                        .with_context(|| format!("unknown breaker state {state}"))?,
+                    changed_at: row.try_get("changed_at")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Drop recorded transitions above `ledger`, alongside
+    /// [`Self::chain_events_delete_above`].
+    pub async fn protocol_state_changes_delete_above(&self, network: &str, ledger: u32) -> anyhow::Result<u64> {
+        let result = self.with_timeout(
+            "protocol_state_changes_delete_above",
+            sqlx::query("DELETE FROM protocol_state_changes WHERE network = $1 AND ledger > $2")
+                .bind(network)
+                .bind(i64::from(ledger))
+                .execute(&self.pool),
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Last ledger whose events are fully indexed, or `None` before the
+    /// first sync on this network.
+    pub async fn sync_state_get(&self, network: &str) -> anyhow::Result<Option<u32>> {
+        let ledger = self.with_timeout(
+            "sync_state_get",
+            sqlx::query_scalar::<_, i64>(
+                "SELECT last_confirmed_ledger FROM sync_state WHERE network = $1",
+            )
+            .bind(network)
+            .fetch_optional(&self.pool),
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        ledger
+            .map(|l| u32::try_from(l).context("sync_state ledger out of range"))
+            .transpose()
+    }
+
+    /// Record the sync cursor. Unconditional so a reorg can move it backwards.
+    pub async fn sync_state_set(&self, network: &str, ledger: u32) -> anyhow::Result<()> {
+        self.with_timeout(
+            "sync_state_set",
+            sqlx::query(
+                "INSERT INTO sync_state (network, last_confirmed_ledger, updated_at)
+                 VALUES ($1, $2, NOW())
+                 ON CONFLICT (network) DO UPDATE
+                     SET last_confirmed_ledger = EXCLUDED.last_confirmed_ledger,
+                         updated_at = NOW()",
+            )
+            .bind(network)
+            .bind(i64::from(ledger))
+            .execute(&self.pool),
+        )
+        .await
+        .map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Positions and P&L for one address, built from its indexed bet and claim
+    /// events joined with each market's status and resolved outcome.
+    ///
+    /// Bet events carry no token, so a position's token comes from
+    /// `markets.token`, falling back to whatever token the address was paid in.
+    pub async fn user_portfolio(
+        &self,
+        network: &str,
+        address: &str,
+    ) -> anyhow::Result<crate::portfolio::Portfolio> {
+        let rows = self.with_timeout("user_portfolio", sqlx::query(
+            "SELECT e.market_id, \
+                    COALESCE(m.token, MAX(e.token)) AS token, \
+                    m.status, m.outcome_index, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'bet_place'), 0)::TEXT AS staked, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'bet_place' \
+                        AND e.outcome = m.outcome_index), 0)::TEXT AS staked_on_winner, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'reward_fx' \
+                        AND NOT e.is_refund), 0)::TEXT AS claimed, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'reward_fx' \
+                        AND e.is_refund), 0)::TEXT AS refunded \
+             FROM chain_events e \
+             LEFT JOIN markets m ON m.id = e.market_id \
+             WHERE e.network = $1 AND e.address = $2 AND e.market_id IS NOT NULL \
+               AND e.kind IN ('bet_place', 'reward_fx') \
+             GROUP BY e.market_id, m.token, m.status, m.outcome_index \
+             ORDER BY e.market_id DESC",
+        )
+        .bind(network)
+        .bind(address)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let mut positions = Vec::with_capacity(rows.len());
+        for row in rows {
+            positions.push(crate::portfolio::PositionRow {
+                market_id: row.try_get::<i64, _>("market_id")?,
+                token: row.try_get::<Option<String>, _>("token")?,
+                market_status: row.try_get::<Option<String>, _>("status")?,
+                resolved_outcome: row.try_get::<Option<i32>, _>("outcome_index")?,
+                staked: row.try_get::<String, _>("staked")?,
+                staked_on_winner: row.try_get::<String, _>("staked_on_winner")?,
+                claimed: row.try_get::<String, _>("claimed")?,
+                refunded: row.try_get::<String, _>("refunded")?,
+            });
+        }
+
+        crate::portfolio::portfolio_from_rows(address, positions)
+    }
+
+    // ── Leaderboards ──────────────────────────────────────────────────────────
+
+    /// Replace one leaderboard snapshot with a fresh aggregation over
+    /// `chain_events`. The delete and insert share a transaction so readers
+    /// never observe a half-built board. Returns the number of ranked rows.
+    ///
+    /// Ties are broken by market count, then address, so ranks are stable
+    /// between runs.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn leaderboard_rebuild(
+        &self,
+        network: &str,
+        period: LeaderboardPeriod,
+        metric: LeaderboardMetric,
+        since: Option<DateTime<Utc>>,
+        excluded: &[String],
+        min_markets: i64,
+        size: i64,
+    ) -> anyhow::Result<u64> {
+        // $1 network, $2 excluded addresses, $3 since; winrate also uses $7.
+        const POSITIONS: &str = "WITH positions AS ( \
+             SELECT e.address, e.market_id, m.status, m.resolved_at, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'bet_place'), 0) AS staked, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'bet_place' \
+                        AND e.outcome = m.outcome_index), 0) AS staked_on_winner, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.kind = 'reward_fx'), 0) AS returned \
+             FROM chain_events e \
+             JOIN markets m ON m.id = e.market_id \
+             WHERE e.network = $1 AND e.address IS NOT NULL AND e.address <> ALL($2) \
+               AND e.kind IN ('bet_place', 'reward_fx') \
+             GROUP BY e.address, e.market_id, m.status, m.resolved_at, m.outcome_index) ";
+
+        let scores = match metric {
+            // Unclaimed winnings count as zero rather than a loss, matching the
+            // portfolio endpoint's realized P&L.
+            LeaderboardMetric::Profit => {
+                "SELECT address, \
+                        SUM(CASE WHEN staked_on_winner > 0 AND returned = 0 THEN 0 \
+                                 ELSE returned - staked END) AS score, \
+                        COUNT(*) AS markets \
+                 FROM positions \
+                 WHERE status = 'resolved' AND ($3::TIMESTAMPTZ IS NULL OR resolved_at >= $3) \
+                 GROUP BY address"
+            }
+            LeaderboardMetric::Volume => {
+                "SELECT e.address, SUM(e.amount) AS score, COUNT(DISTINCT e.market_id) AS markets \
+                 FROM chain_events e \
+                 WHERE e.network = $1 AND e.kind = 'bet_place' \
+                   AND e.address IS NOT NULL AND e.address <> ALL($2) \
+                   AND ($3::TIMESTAMPTZ IS NULL OR e.indexed_at >= $3) \
+                 GROUP BY e.address"
+            }
+            LeaderboardMetric::Winrate => {
+                "SELECT address, \
+                        ROUND((COUNT(*) FILTER (WHERE staked_on_winner > 0))::NUMERIC \
+                              / COUNT(*), 6) AS score, \
+                        COUNT(*) AS markets \
+                 FROM positions \
+                 WHERE status = 'resolved' AND ($3::TIMESTAMPTZ IS NULL OR resolved_at >= $3) \
+                 GROUP BY address \
+                 HAVING COUNT(*) >= $7"
+            }
+        };
+
+        let insert = format!(
+            "{POSITIONS} \
+             INSERT INTO leaderboard_snapshots \
+                 (period, metric, rank, address, score, markets, computed_at) \
+             SELECT $4, $5, \
+                    (ROW_NUMBER() OVER (ORDER BY s.score DESC, s.markets DESC, s.address ASC))::INTEGER, \
+                    s.address, s.score, s.markets, NOW() \
+             FROM ({scores}) s \
+             ORDER BY s.score DESC, s.markets DESC, s.address ASC \
+             LIMIT $6"
+        );
+
+        let inserted = self
+            .with_timeout("leaderboard_rebuild", async {
+                let mut tx = self.pool.begin().await?;
+                sqlx::query("DELETE FROM leaderboard_snapshots WHERE period = $1 AND metric = $2")
+                    .bind(period.label())
+                    .bind(metric.label())
+                    .execute(&mut *tx)
+                    .await?;
+
+                let mut query = sqlx::query(&insert)
+                    .bind(network)
+                    .bind(excluded)
+                    .bind(since)
+                    .bind(period.label())
+                    .bind(metric.label())
+                    .bind(size);
+                if metric == LeaderboardMetric::Winrate {
+                    query = query.bind(min_markets);
+                }
+                let inserted = query.execute(&mut *tx).await?.rows_affected();
+
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(inserted)
+            })
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        Ok(inserted)
+    }
+
+    /// Read the current snapshot for one board, best rank first.
+    pub async fn leaderboard(
+        &self,
+        period: LeaderboardPeriod,
+        metric: LeaderboardMetric,
+        limit: i64,
+    ) -> anyhow::Result<Leaderboard> {
+        let rows = self.with_timeout("leaderboard", sqlx::query(
+            "SELECT rank, address, score::TEXT AS score, markets, computed_at \
+             FROM leaderboard_snapshots \
+             WHERE period = $1 AND metric = $2 \
+             ORDER BY rank ASC \
+             LIMIT $3",
+        )
+        .bind(period.label())
+        .bind(metric.label())
+        .bind(limit)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let mut computed_at = None;
+        let mut entries = Vec::with_capacity(rows.len());
+        for row in rows {
+            computed_at.get_or_insert(row.try_get::<DateTime<Utc>, _>("computed_at")?);
+            entries.push(LeaderboardEntry {
+                rank: row.try_get::<i32, _>("rank")?,
+                address: row.try_get::<String, _>("address")?,
+                score: row.try_get::<String, _>("score")?,
+                markets: row.try_get::<i64, _>("markets")?,
+            });
+        }
+
+        Ok(Leaderboard {
+            period,
+            metric,
+            computed_at,
+            entries,
+        })
+    }
+
+    // ── Market price history ──────────────────────────────────────────────────
+
+    /// Record the current stake-weighted odds of every outcome on active
+    /// markets as this minute's raw price point. Repeated samples within the
+    /// same minute fold into that row's high/low/close. Returns rows written.
+    pub async fn price_points_sample(&self, network: &str) -> anyhow::Result<u64> {
+        let result = self.with_timeout("price_points_sample", sqlx::query(
+            "INSERT INTO market_price_points (market_id, outcome, ts, open, high, low, close) \
+             SELECT s.market_id, s.outcome, \
+                    to_timestamp(floor(EXTRACT(EPOCH FROM NOW())::DOUBLE PRECISION / 60) * 60), \
+                    s.p, s.p, s.p, s.p \
+             FROM ( \
+                 SELECT e.market_id, e.outcome, \
+                        (SUM(e.amount) / NULLIF(SUM(SUM(e.amount)) OVER (PARTITION BY e.market_id), 0)) \
+                            ::DOUBLE PRECISION AS p \
+                 FROM chain_events e \
+                 JOIN markets m ON m.id = e.market_id \
+                 WHERE e.network = $1 AND e.kind = 'bet_place' AND e.outcome IS NOT NULL \
+                   AND m.status = 'active' AND m.deleted_at IS NULL \
+                 GROUP BY e.market_id, e.outcome \
+             ) s \
+             WHERE s.p IS NOT NULL \
+             ON CONFLICT (market_id, outcome, is_rollup, ts) DO UPDATE \
+                 SET high = GREATEST(market_price_points.high, EXCLUDED.close), \
+                     low = LEAST(market_price_points.low, EXCLUDED.close), \
+                     close = EXCLUDED.close",
+        )
+        .bind(network)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rebuild raw price points for every minute in which a bet in ledgers
+    /// `[from_ledger, to_ledger]` was indexed, each from the stake totals at
+    /// the end of that minute. For backfilled history, whose minutes the
+    /// per-pass sampler never saw; rebuilt rows replace what was there.
+    pub async fn price_points_backfill(
+        &self,
+        network: &str,
+        from_ledger: i64,
+        to_ledger: i64,
+    ) -> anyhow::Result<u64> {
+        let result = self.with_timeout("price_points_backfill", sqlx::query(
+            "WITH bets AS ( \
+                 SELECT market_id, outcome, amount, \
+                        to_timestamp(floor(EXTRACT(EPOCH FROM indexed_at)::DOUBLE PRECISION / 60) * 60) AS ts, \
+                        ledger \
+                 FROM chain_events \
+                 WHERE network = $1 AND kind = 'bet_place' \
+                   AND market_id IS NOT NULL AND outcome IS NOT NULL \
+             ), \
+             minutes AS ( \
+                 SELECT DISTINCT market_id, ts FROM bets WHERE ledger BETWEEN $2 AND $3 \
+             ), \
+             stakes AS ( \
+                 SELECT m.market_id, m.ts, b.outcome, SUM(b.amount) AS stake \
+                 FROM minutes m \
+                 JOIN bets b ON b.market_id = m.market_id AND b.ts <= m.ts \
+                 GROUP BY m.market_id, m.ts, b.outcome \
+             ) \
+             INSERT INTO market_price_points (market_id, outcome, ts, open, high, low, close) \
+             SELECT market_id, outcome, ts, p, p, p, p \
+             FROM ( \
+                 SELECT market_id, outcome, ts, \
+                        (stake / NULLIF(SUM(stake) OVER (PARTITION BY market_id, ts), 0)) \
+                            ::DOUBLE PRECISION AS p \
+                 FROM stakes \
+             ) s \
+             WHERE p IS NOT NULL \
+             ON CONFLICT (market_id, outcome, is_rollup, ts) DO UPDATE \
+                 SET open = EXCLUDED.open, high = EXCLUDED.high, \
+                     low = EXCLUDED.low, close = EXCLUDED.close",
+        )
+        .bind(network)
+        .bind(from_ledger)
+        .bind(to_ledger)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// OHLC candles for one market in `[from, to)`, one series per outcome.
+    /// Buckets are aligned to the Unix epoch, so `1d` candles start at UTC
+    /// midnight regardless of `from`.
+    pub async fn price_history(
+        &self,
+        market_id: i64,
+        outcome: Option<i32>,
+        resolution: HistoryResolution,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<OutcomeSeries>> {
+        let rows = self.with_timeout("price_history", sqlx::query(
+            "SELECT outcome, \
+                    to_timestamp(floor(EXTRACT(EPOCH FROM ts)::DOUBLE PRECISION / $5) * $5) AS bucket_start, \
+                    (array_agg(open ORDER BY ts ASC))[1] AS open, \
+                    MAX(high) AS high, \
+                    MIN(low) AS low, \
+                    (array_agg(close ORDER BY ts DESC))[1] AS close \
+             FROM market_price_points \
+             WHERE market_id = $1 AND ts >= $2 AND ts < $3 \
+               AND ($4::INTEGER IS NULL OR outcome = $4) \
+             GROUP BY outcome, bucket_start \
+             ORDER BY outcome ASC, bucket_start ASC",
+        )
+        .bind(market_id)
+        .bind(from)
+        .bind(to)
+        .bind(outcome)
+        .bind(resolution.seconds() as f64)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let mut series: Vec<OutcomeSeries> = Vec::new();
+        for row in rows {
+            let outcome = row.try_get::<i32, _>("outcome")?;
+            let candle = PriceCandle {
+                bucket_start: row.try_get::<DateTime<Utc>, _>("bucket_start")?,
+                open: row.try_get::<f64, _>("open")?,
+                high: row.try_get::<f64, _>("high")?,
+                low: row.try_get::<f64, _>("low")?,
+                close: row.try_get::<f64, _>("close")?,
+            };
+            match series.last_mut() {
+                Some(s) if s.outcome == outcome => s.candles.push(candle),
+                _ => series.push(OutcomeSeries {
+                    outcome,
+                    candles: vec![candle],
+                }),
+            }
+        }
+        Ok(series)
+    }
+
+    /// Roll raw price points older than `cutoff` into one row per hour. The
+    /// delete and insert run as one statement, so a failure leaves the raw
+    /// rows in place. `cutoff` should be hour-aligned (see
+    /// [`crate::price_history::retention_cutoff`]). Returns roll-up rows written.
+    pub async fn price_points_downsample(&self, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+        let result = self.with_timeout("price_points_downsample", sqlx::query(
+            "WITH raw AS ( \
+                 DELETE FROM market_price_points \
+                 WHERE NOT is_rollup AND ts < $1 \
+                 RETURNING market_id, outcome, ts, open, high, low, close \
+             ) \
+             INSERT INTO market_price_points \
+                 (market_id, outcome, is_rollup, ts, open, high, low, close) \
+             SELECT market_id, outcome, TRUE, \
+                    to_timestamp(floor(EXTRACT(EPOCH FROM ts)::DOUBLE PRECISION / 3600) * 3600) AS hour, \
+                    (array_agg(open ORDER BY ts ASC))[1], \
+                    MAX(high), \
+                    MIN(low), \
+                    (array_agg(close ORDER BY ts DESC))[1] \
+             FROM raw \
+             GROUP BY market_id, outcome, hour \
+             ON CONFLICT (market_id, outcome, is_rollup, ts) DO UPDATE \
+                 SET high = GREATEST(market_price_points.high, EXCLUDED.high), \
+                     low = LEAST(market_price_points.low, EXCLUDED.low), \
+                     close = EXCLUDED.close",
+        )
+        .bind(cutoff)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    // ── Oracle keeper submissions ─────────────────────────────────────────────
+
+    /// Record a keeper submission unless a live one already exists for the
+    /// (market, oracle) pair, in which case that row is returned classified
+    /// against `outcome`. The partial unique index makes this race-free: of two
+    /// concurrent submissions exactly one gets [`SubmissionClaim::New`].
+    pub async fn oracle_submission_claim(
+        &self,
+        market_id: i64,
+        oracle_id: i32,
+        outcome: i32,
+        source: &str,
+        confidence_bps: i32,
+    ) -> anyhow::Result<SubmissionClaim> {
+        // Two passes: the live row can flip to 'failed' between the insert
+        // and the lookup, which frees the slot for this submission.
+        for _ in 0..2 {
+            let inserted = self.with_timeout("oracle_submission_insert", sqlx::query(
+                "INSERT INTO oracle_submissions (market_id, oracle_id, outcome, source, confidence_bps) \
+                 VALUES ($1, $2, $3, $4, $5) \
+                 ON CONFLICT (market_id, oracle_id) WHERE status IN ('pending', 'confirmed') DO NOTHING \
+                 RETURNING *",
+            )
+            .bind(market_id)
+            .bind(oracle_id)
+            .bind(outcome)
+            .bind(source)
+            .bind(confidence_bps)
+            .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+            if let Some(row) = inserted {
+                return Ok(SubmissionClaim::New(oracle_submission_from_row(&row)?));
+            }
+
+            let live = self.with_timeout("oracle_submission_live", sqlx::query(
+                "SELECT * FROM oracle_submissions \
+                 WHERE market_id = $1 AND oracle_id = $2 AND status IN ('pending', 'confirmed')",
+            )
+            .bind(market_id)
+            .bind(oracle_id)
+            .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+            if let Some(row) = live {
+                return Ok(SubmissionClaim::against(oracle_submission_from_row(&row)?, outcome));
+            }
+        }
+        anyhow::bail!("oracle submission for market {market_id} kept changing state; retry")
+    }
+
+    /// Record the outcome of the `set_oracle_result` call for a claimed row.
+    pub async fn oracle_submission_finish(
+        &self,
+        id: i64,
+        status: OracleSubmissionStatus,
+        tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> anyhow::Result<OracleSubmission> {
+        let row = self.with_timeout("oracle_submission_finish", sqlx::query(
+            "UPDATE oracle_submissions \
+             SET status = $2, tx_hash = COALESCE($3, tx_hash), error = $4, updated_at = NOW() \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(status.label())
+        .bind(tx_hash)
+        .bind(error)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        oracle_submission_from_row(&row)
+    }
+
+    /// Record the `attempt_oracle_resolution` call made after a confirmed result.
+    pub async fn oracle_submission_set_resolution(
+        &self,
+        id: i64,
+        tx_hash: Option<&str>,
+        error: Option<&str>,
+    ) -> anyhow::Result<OracleSubmission> {
+        let row = self.with_timeout("oracle_submission_set_resolution", sqlx::query(
+            "UPDATE oracle_submissions \
+             SET resolution_tx_hash = $2, resolution_error = $3, updated_at = NOW() \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(tx_hash)
+        .bind(error)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        oracle_submission_from_row(&row)
+    }
+
+    // ── Market watches ────────────────────────────────────────────────────────
+
+    /// Create or update the watch for (network, market, email). Re-watching
+    /// keeps the existing unsubscribe token and replaces `notify_on` and
+    /// `address`. Returns the watch and its token, or `None` when the market
+    /// does not exist.
+    pub async fn market_watch_upsert(
+        &self,
+        network: &str,
+        market_id: i64,
+        email: &str,
+        address: Option<&str>,
+        notify_on: &[WatchTrigger],
+        unsubscribe_token: &str,
+    ) -> anyhow::Result<Option<(MarketWatch, String)>> {
+        let labels: Vec<&str> = notify_on.iter().map(|t| t.label()).collect();
+        let row = self.with_timeout("market_watch_upsert", sqlx::query(
+            "INSERT INTO market_watches (network, market_id, email, address, notify_on, unsubscribe_token) \
+             SELECT $1, id, $3, $4, $5, $6 FROM markets WHERE id = $2 \
+             ON CONFLICT (network, market_id, email) DO UPDATE \
+             SET address = EXCLUDED.address, notify_on = EXCLUDED.notify_on, updated_at = NOW() \
+             RETURNING *",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(email)
+        .bind(address)
+        .bind(&labels)
+        .bind(unsubscribe_token)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.map(|row| Ok((market_watch_from_row(&row)?, row.try_get("unsubscribe_token")?)))
+            .transpose()
+    }
+
+    /// Remove the watch carrying `token`. Returns whether one existed.
+    pub async fn market_watch_delete_by_token(&self, token: &str) -> anyhow::Result<bool> {
+        let result = self.with_timeout("market_watch_delete_by_token", sqlx::query(
+            "DELETE FROM market_watches WHERE unsubscribe_token = $1",
+        )
+        .bind(token)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Claim the notification for `event_id` on every watch of the market
+    /// that subscribes to `trigger` and whose email is neither suppressed nor
+    /// opted out of [`EmailCategory::Markets`].
+    /// Watches already claimed for this event are not returned again, so
+    /// concurrent or repeated calls for one event yield each watcher once.
+    pub async fn market_watch_claim_notifications(
+        &self,
+        network: &str,
+        market_id: i64,
+        trigger: WatchTrigger,
+        event_id: &str,
+    ) -> anyhow::Result<Vec<WatchRecipient>> {
+        let rows = self.with_timeout("market_watch_claim_notifications", sqlx::query(
+            "WITH claimed AS ( \
+                 INSERT INTO market_watch_notifications (watch_id, event_id) \
+                 SELECT w.id, $4 FROM market_watches w \
+                 WHERE w.network = $1 AND w.market_id = $2 AND $3 = ANY(w.notify_on) \
+                   AND NOT EXISTS (SELECT 1 FROM email_suppressions s WHERE s.email = w.email) \
+                   AND NOT EXISTS ( \
+                       SELECT 1 FROM newsletter_preferences p \
+                       WHERE p.email = w.email AND p.category = $5 AND NOT p.enabled \
+                   ) \
+                 ON CONFLICT (watch_id, event_id) DO NOTHING \
+                 RETURNING watch_id \
+             ) \
+             SELECT w.id, w.email, w.unsubscribe_token, m.title, m.outcome_options \
+             FROM claimed c \
+             JOIN market_watches w ON w.id = c.watch_id \
+             JOIN markets m ON m.id = w.market_id \
+             ORDER BY w.id",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(trigger.label())
+        .bind(event_id)
+        .bind(EmailCategory::Markets.as_str())
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(WatchRecipient {
+                    watch_id: row.try_get("id")?,
+                    email: row.try_get("email")?,
+                    unsubscribe_token: row.try_get("unsubscribe_token")?,
+                    market_title: row.try_get("title")?,
+                    outcome_options: row.try_get("outcome_options")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the email job enqueued for a claimed notification.
+    pub async fn market_watch_set_notification_job(
+        &self,
+        watch_id: i64,
+        event_id: &str,
+        email_job_id: uuid::Uuid,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("market_watch_set_notification_job", sqlx::query(
+            "UPDATE market_watch_notifications SET email_job_id = $3 \
+             WHERE watch_id = $1 AND event_id = $2",
+        )
+        .bind(watch_id)
+        .bind(event_id)
+        .bind(email_job_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Drop a claim whose email could not be enqueued so it can be retried.
+    pub async fn market_watch_release_notification(&self, watch_id: i64, event_id: &str) -> anyhow::Result<()> {
+        self.with_timeout("market_watch_release_notification", sqlx::query(
+            "DELETE FROM market_watch_notifications \
+             WHERE watch_id = $1 AND event_id = $2 AND email_job_id IS NULL",
+        )
+        .bind(watch_id)
+        .bind(event_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    // ── Watchlists ────────────────────────────────────────────────────────────
+
+    /// Star `market_id` for `address`. Starring again keeps the original
+    /// `added_at`. Returns when the market was starred, or `None` when it does
+    /// not exist.
+    pub async fn watchlist_add(&self, address: &str, market_id: i64) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let row = self.with_timeout("watchlist_add", sqlx::query(
+            "INSERT INTO watchlists (address, market_id) \
+             SELECT $1, id FROM markets WHERE id = $2 AND deleted_at IS NULL \
+             ON CONFLICT (address, market_id) DO UPDATE SET address = EXCLUDED.address \
+             RETURNING created_at",
+        )
+        .bind(address)
+        .bind(market_id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.map(|row| Ok(row.try_get("created_at")?)).transpose()
+    }
+
+    /// Unstar `market_id` for `address`. Returns whether it was starred.
+    pub async fn watchlist_remove(&self, address: &str, market_id: i64) -> anyhow::Result<bool> {
+        let result = self.with_timeout("watchlist_remove", sqlx::query(
+            "DELETE FROM watchlists WHERE address = $1 AND market_id = $2",
+        )
+        .bind(address)
+        .bind(market_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Every live market `address` has starred, most recently starred first.
+    pub async fn watchlist(&self, address: &str) -> anyhow::Result<Vec<WatchlistEntry>> {
+        let rows = self.with_timeout("watchlist", sqlx::query(
+            "SELECT w.created_at AS added_at, m.id, m.title, m.description, m.category, \
+             m.creator, m.status, m.outcome_options, m.outcome_odds, m.outcome_index, \
+             m.total_volume, m.participant_count, m.ends_at, m.created_at, m.resolved_at, m.token, \
+             m.image_url \
+             FROM watchlists w JOIN markets m ON m.id = w.market_id \
+             WHERE w.address = $1 AND m.deleted_at IS NULL \
+             ORDER BY w.created_at DESC, m.id DESC \
+             LIMIT $2",
+        )
+        .bind(address)
+        .bind(WATCHLIST_MAX_ENTRIES)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter()
+            .map(|row| {
+                Ok(WatchlistEntry {
+                    added_at: row.try_get("added_at")?,
+                    market: market_detail_from_row(row)?,
+                })
+            })
+            .collect()
+    }
+
+    // ── User notification settings ────────────────────────────────────────────
+
+    /// `address`'s notification settings, or `None` if it never saved any.
+    pub async fn notification_settings(&self, address: &str) -> anyhow::Result<Option<NotificationSettings>> {
+        let row = self.with_timeout("notification_settings", sqlx::query(
+            "SELECT * FROM user_notification_settings WHERE address = $1",
+        )
+        .bind(address)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.map(|row| notification_settings_from_row(&row)).transpose()
+    }
+
+    /// Replace `address`'s settings. Keeping the same email keeps its
+    /// verification and any outstanding token; a different email (or none)
+    /// starts unverified. `unsubscribe_token` is only used for a new row.
+    pub async fn notification_settings_update(
+        &self,
+        address: &str,
+        update: &NotificationSettingsUpdate,
+        unsubscribe_token: &str,
+    ) -> anyhow::Result<NotificationSettings> {
+        let row = self.with_timeout("notification_settings_update", sqlx::query(
+            "INSERT INTO user_notification_settings AS s \
+                 (address, email, market_resolved, claim_available, dispute_filed, \
+                  hide_creator_profile, unsubscribe_token) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT (address) DO UPDATE SET \
+                 email = EXCLUDED.email, \
+                 email_verified_at = CASE WHEN s.email IS NOT DISTINCT FROM EXCLUDED.email \
+                     THEN s.email_verified_at END, \
+                 confirmation_token = CASE WHEN s.email IS NOT DISTINCT FROM EXCLUDED.email \
+                     THEN s.confirmation_token END, \
+                 confirmation_sent_at = CASE WHEN s.email IS NOT DISTINCT FROM EXCLUDED.email \
+                     THEN s.confirmation_sent_at END, \
+                 market_resolved = EXCLUDED.market_resolved, \
+                 claim_available = EXCLUDED.claim_available, \
+                 dispute_filed = EXCLUDED.dispute_filed, \
+                 hide_creator_profile = EXCLUDED.hide_creator_profile, \
+                 updated_at = NOW() \
+             RETURNING *",
+        )
+        .bind(address)
+        .bind(update.email.as_deref())
+        .bind(update.market_resolved)
+        .bind(update.claim_available)
+        .bind(update.dispute_filed)
+        .bind(update.hide_creator_profile)
+        .bind(unsubscribe_token)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        notification_settings_from_row(&row)
+    }
+
+    /// Give `address`'s unverified email a new confirmation token, unless the
+    /// last one went out less than `cooldown_secs` ago. Returns the email to
+    /// send it to, or `None` (and changes nothing) when there is no
+    /// unverified email or it is still cooling down.
+    pub async fn notification_issue_confirmation(
+        &self,
+        address: &str,
+        confirmation_token: &str,
+        cooldown_secs: u64,
+    ) -> anyhow::Result<Option<String>> {
+        let row = self.with_timeout("notification_issue_confirmation", sqlx::query(
+            "UPDATE user_notification_settings \
+             SET confirmation_token = $2, confirmation_sent_at = NOW() \
+             WHERE address = $1 AND email IS NOT NULL AND email_verified_at IS NULL \
+               AND (confirmation_sent_at IS NULL \
+                    OR confirmation_sent_at <= NOW() - ($3 || ' seconds')::INTERVAL) \
+             RETURNING email",
+        )
+        .bind(address)
+        .bind(confirmation_token)
+        .bind(cooldown_secs as i64)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.map(|row| Ok(row.try_get("email")?)).transpose()
+    }
+
+    /// Redeem a notification email confirmation token. Tokens are valid for
+    /// `token_ttl_secs` after they were sent, the same window as newsletter
+    /// confirmations.
+    pub async fn notification_confirm_by_token(
+        &self,
+        token: &str,
+        token_ttl_secs: u64,
+    ) -> anyhow::Result<NewsletterConfirmation> {
+        let row = self.with_timeout("notification_confirm_by_token", sqlx::query(
+            "WITH target AS ( \
+                 SELECT address, confirmation_sent_at > NOW() - ($2 || ' seconds')::INTERVAL AS fresh \
+                 FROM user_notification_settings \
+                 WHERE confirmation_token = $1 \
+             ), \
+             redeemed AS ( \
+                 UPDATE user_notification_settings s \
+                 SET email_verified_at = NOW(), confirmation_token = NULL, \
+                     confirmation_sent_at = NULL, updated_at = NOW() \
+                 FROM target \
+                 WHERE s.address = target.address AND target.fresh \
+             ) \
+             SELECT COALESCE(fresh, FALSE) AS fresh FROM target",
+        )
+        .bind(token)
+        .bind(token_ttl_secs as i64)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(match row {
+            None => NewsletterConfirmation::NotFound,
+            Some(row) if row.try_get::<bool, _>("fresh")? => NewsletterConfirmation::Confirmed,
+            Some(_) => NewsletterConfirmation::Expired,
+        })
+    }
+
+    /// Turn every notification off for the settings holding `token`. Returns
+    /// whether the token matched.
+    pub async fn notification_unsubscribe_by_token(&self, token: &str) -> anyhow::Result<bool> {
+        let result = self.with_timeout("notification_unsubscribe_by_token", sqlx::query(
+            "UPDATE user_notification_settings \
+             SET market_resolved = FALSE, claim_available = FALSE, dispute_filed = FALSE, \
+                 updated_at = NOW() \
+             WHERE unsubscribe_token = $1",
+        )
+        .bind(token)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Claim the notifications `event` triggers for holders of `market_id` on
+    /// `network` and return their recipients. A holder is an address with an
+    /// indexed bet in the market; it is claimed at most once per event, for
+    /// the first kind that applies and that it has turned on: `dispute_filed`
+    /// for a dispute; `claim_available` if it bet on the winning outcome,
+    /// otherwise `market_resolved`, for a resolution. Unverified and
+    /// suppressed emails are skipped.
+    pub async fn notification_claim_sends(
+        &self,
+        network: &str,
+        market_id: i64,
+        event_id: &str,
+        event: PositionEvent,
+    ) -> anyhow::Result<Vec<PositionRecipient>> {
+        let (winning_outcome, is_dispute) = match event {
+            PositionEvent::Resolved { outcome } => (Some(outcome as i32), false),
+            PositionEvent::Disputed => (None, true),
+        };
+        let rows = self.with_timeout("notification_claim_sends", sqlx::query(
+            "WITH holders AS ( \
+                 SELECT address, BOOL_OR(outcome = $4) AS won \
+                 FROM chain_events \
+                 WHERE network = $1 AND market_id = $2 AND kind = 'bet_place' AND address IS NOT NULL \
+                 GROUP BY address \
+             ), \
+             chosen AS ( \
+                 SELECT s.address, s.email, s.unsubscribe_token, m.title, m.outcome_options, \
+                        CASE \
+                            WHEN $5 THEN CASE WHEN s.dispute_filed THEN 'dispute_filed' END \
+                            WHEN h.won AND s.claim_available THEN 'claim_available' \
+                            WHEN s.market_resolved THEN 'market_resolved' \
+                        END AS kind \
+                 FROM holders h \
+                 JOIN user_notification_settings s ON s.address = h.address \
+                 JOIN markets m ON m.id = $2 \
+                 WHERE s.email IS NOT NULL AND s.email_verified_at IS NOT NULL \
+                   AND NOT EXISTS (SELECT 1 FROM email_suppressions x WHERE x.email = s.email) \
+             ), \
+             claimed AS ( \
+                 INSERT INTO user_notification_sends (address, event_id, kind) \
+                 SELECT address, $3, kind FROM chosen WHERE kind IS NOT NULL \
+                 ON CONFLICT (address, event_id) DO NOTHING \
+                 RETURNING address \
+             ) \
+             SELECT ch.address, ch.email, ch.kind, ch.unsubscribe_token, ch.title, ch.outcome_options \
+             FROM claimed c \
+             JOIN chosen ch ON ch.address = c.address \
+             ORDER BY ch.address",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(event_id)
+        .bind(winning_outcome)
+        .bind(is_dispute)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                let kind: String = row.try_get("kind")?;
+                Ok(PositionRecipient {
+                    address: row.try_get("address")?,
+                    email: row.try_get("email")?,
+                    kind: NotificationKind::parse(&kind)
+                        .with_context(|| format!("user_notification_sends: unknown kind {kind:?}"))?,
+                    unsubscribe_token: row.try_get("unsubscribe_token")?,
+                    market_title: row.try_get("title")?,
+                    outcome_options: row.try_get("outcome_options")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Record the email job enqueued for a claimed position notification.
+    pub async fn notification_set_send_job(
+        &self,
+        address: &str,
+        event_id: &str,
+        email_job_id: uuid::Uuid,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("notification_set_send_job", sqlx::query(
+            "UPDATE user_notification_sends SET email_job_id = $3 \
+             WHERE address = $1 AND event_id = $2",
+        )
+        .bind(address)
+        .bind(event_id)
+        .bind(email_job_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Drop a claim whose email could not be enqueued so it can be retried.
+    pub async fn notification_release_send(&self, address: &str, event_id: &str) -> anyhow::Result<()> {
+        self.with_timeout("notification_release_send", sqlx::query(
+            "DELETE FROM user_notification_sends \
+             WHERE address = $1 AND event_id = $2 AND email_job_id IS NULL",
+        )
+        .bind(address)
+        .bind(event_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    // ── Newsletter campaigns ──────────────────────────────────────────────────
+
+    pub async fn campaign_create(
+        &self,
+        name: &str,
+        template_name: &str,
+        payload: &serde_json::Value,
+    ) -> anyhow::Result<Campaign> {
+        let row = self.with_timeout("campaign_create", sqlx::query(
+            "INSERT INTO campaigns (name, template_name, payload) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(name)
+        .bind(template_name)
+        .bind(payload)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        campaign_from_row(&row)
+    }
+
+    pub async fn campaign_get(&self, id: i64) -> anyhow::Result<Option<Campaign>> {
+        let row = self.with_timeout("campaign_get", sqlx::query(
+            "SELECT * FROM campaigns WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(campaign_from_row).transpose()
+    }
+
+    /// Move a draft to `sending`, recording the current audience size.
+    /// Returns `None` when the campaign does not exist or is not a draft.
+    pub async fn campaign_launch(&self, id: i64) -> anyhow::Result<Option<Campaign>> {
+        let row = self.with_timeout("campaign_launch", sqlx::query(&format!(
+            "UPDATE campaigns \
+             SET status = 'sending', launched_at = NOW(), updated_at = NOW(), \
+                 total_recipients = (SELECT COUNT(*) {SUBSCRIBER_AUDIENCE}) \
+             WHERE id = $2 AND status = 'draft' \
+             RETURNING *"
+        ))
+        .bind(campaign::CATEGORY.as_str())
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(campaign_from_row).transpose()
+    }
+
+    /// Cancel a draft or sending campaign. Returns `None` when the campaign
+    /// does not exist or has already completed or been cancelled.
+    pub async fn campaign_cancel(&self, id: i64) -> anyhow::Result<Option<Campaign>> {
+        let row = self.with_timeout("campaign_cancel", sqlx::query(
+            "UPDATE campaigns \
+             SET status = 'cancelled', cancelled_at = NOW(), updated_at = NOW() \
+             WHERE id = $1 AND status IN ('draft', 'sending') \
+             RETURNING *",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(campaign_from_row).transpose()
+    }
+
+    /// The campaign that has been sending longest, if any.
+    pub async fn campaign_next_sending(&self) -> anyhow::Result<Option<Campaign>> {
+        let row = self.with_timeout("campaign_next_sending", sqlx::query(
+            "SELECT * FROM campaigns WHERE status = 'sending' ORDER BY launched_at, id LIMIT 1",
+        )
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(campaign_from_row).transpose()
+    }
+
+    /// Up to `limit` campaign recipients as `(subscriber id, email)`, in email
+    /// order, starting after `after`.
+    pub async fn campaign_next_recipients(
+        &self,
+        after: Option<&str>,
+        limit: u32,
+    ) -> anyhow::Result<Vec<(uuid::Uuid, String)>> {
+        let rows = self.with_timeout("campaign_next_recipients", sqlx::query(&format!(
+            "SELECT s.id, s.email {SUBSCRIBER_AUDIENCE} \
+               AND ($2::TEXT IS NULL OR s.email > $2) \
+             ORDER BY s.email \
+             LIMIT $3"
+        ))
+        .bind(campaign::CATEGORY.as_str())
+        .bind(after)
+        .bind(i64::from(limit))
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("id")?, row.try_get("email")?)))
+            .collect()
+    }
+
+    /// Insert one campaign job per `(recipient, template data)` and move the
+    /// cursor to `new_cursor`, in one transaction. Nothing is written, and
+    /// `false` returned, unless the campaign is still sending and its cursor
+    /// is still the one `campaign` was read with.
+    pub async fn campaign_enqueue_batch(
+        &self,
+        campaign: &Campaign,
+        new_cursor: &str,
+        jobs: &[(String, serde_json::Value)],
+    ) -> anyhow::Result<bool> {
+        let recipients: Vec<&str> = jobs.iter().map(|(email, _)| email.as_str()).collect();
+        let data: Vec<serde_json::Value> = jobs.iter().map(|(_, data)| data.clone()).collect();
+
+        let enqueued = self
+            .with_timeout("campaign_enqueue_batch", async {
+                let mut tx = self.pool.begin().await?;
+                let current = sqlx::query(
+                    "SELECT status, cursor FROM campaigns WHERE id = $1 FOR UPDATE",
+                )
+                .bind(campaign.id)
+                .fetch_optional(&mut *tx)
+                .await?;
+                let Some(current) = current else {
+                    return Ok(false);
+                };
+                let status: String = current.try_get("status")?;
+                let cursor: Option<String> = current.try_get("cursor")?;
+                if status != CampaignStatus::Sending.label() || cursor != campaign.cursor {
+                    return Ok(false);
+                }
+
+                sqlx::query(
+                    "INSERT INTO email_jobs \
+                         (job_type, recipient_email, template_name, template_data, priority, campaign_id) \
+                     SELECT $1, t.recipient, $2, t.data, $3, $4 \
+                     FROM UNNEST($5::TEXT[], $6::JSONB[]) AS t(recipient, data)",
+                )
+                .bind(EmailJobType::Campaign.as_str())
+                .bind(&campaign.template_name)
+                .bind(campaign::PRIORITY)
+                .bind(campaign.id)
+                .bind(&recipients)
+                .bind(&data)
+                .execute(&mut *tx)
+                .await?;
+
+                sqlx::query(
+                    "UPDATE campaigns \
+                     SET cursor = $2, enqueued_count = enqueued_count + $3, updated_at = NOW() \
+                     WHERE id = $1",
+                )
+                .bind(campaign.id)
+                .bind(new_cursor)
+                .bind(jobs.len() as i32)
+                .execute(&mut *tx)
+                .await?;
+
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(true)
+            })
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        Ok(enqueued)
+    }
+
+    /// Mark a sending campaign `completed`. Returns `false` when it was no
+    /// longer sending.
+    pub async fn campaign_complete(&self, id: i64) -> anyhow::Result<bool> {
+        let result = self.with_timeout("campaign_complete", sqlx::query(
+            "UPDATE campaigns \
+             SET status = 'completed', completed_at = NOW(), updated_at = NOW() \
+             WHERE id = $1 AND status = 'sending'",
+        )
+        .bind(id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count one finished campaign job.
+    pub async fn campaign_record_outcome(
+        &self,
+        id: i64,
+        outcome: CampaignOutcome,
+    ) -> anyhow::Result<()> {
+        let column = outcome.column();
+        self.with_timeout("campaign_record_outcome", sqlx::query(&format!(
+            "UPDATE campaigns SET {column} = {column} + 1, updated_at = NOW() WHERE id = $1"
+        ))
+        .bind(id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    // ── Weekly digest ─────────────────────────────────────────────────────────
+
+    /// Whether the digest for the week starting `week_start` was sent.
+    pub async fn digest_week_sent(&self, week_start: NaiveDate) -> anyhow::Result<bool> {
+        let row = self.with_timeout("digest_week_sent", sqlx::query(
+            "SELECT EXISTS (SELECT 1 FROM digest_sends WHERE week_start = $1) AS sent",
+        )
+        .bind(week_start)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(row.try_get("sent")?)
+    }
+
+    /// Subscribers the digest goes to, as `(subscriber id, email)`.
+    pub async fn digest_recipients(&self) -> anyhow::Result<Vec<(uuid::Uuid, String)>> {
+        let rows = self.with_timeout("digest_recipients", sqlx::query(&format!(
+            "SELECT s.id, s.email {SUBSCRIBER_AUDIENCE} ORDER BY s.email"
+        ))
+        .bind(digest::CATEGORY.as_str())
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("id")?, row.try_get("email")?)))
+            .collect()
+    }
+
+    /// Record the digest week and insert one job per `(recipient, template
+    /// data)`, in one transaction. Nothing is written, and `false` returned,
+    /// when the week is already recorded.
+    pub async fn digest_enqueue_week(
+        &self,
+        digest: &Digest,
+        jobs: &[(String, serde_json::Value)],
+    ) -> anyhow::Result<bool> {
+        let recipients: Vec<&str> = jobs.iter().map(|(email, _)| email.as_str()).collect();
+        let data: Vec<serde_json::Value> = jobs.iter().map(|(_, data)| data.clone()).collect();
+
+        let enqueued = self
+            .with_timeout("digest_enqueue_week", async {
+                let mut tx = self.pool.begin().await?;
+                let claimed = sqlx::query(
+                    "INSERT INTO digest_sends (week_start, recipients, markets, resolutions) \
+                     VALUES ($1, $2, $3, $4) \
+                     ON CONFLICT (week_start) DO NOTHING",
+                )
+                .bind(digest.week_start)
+                .bind(jobs.len() as i32)
+                .bind(digest.markets.len() as i32)
+                .bind(digest.resolutions.len() as i32)
+                .execute(&mut *tx)
+                .await?;
+                if claimed.rows_affected() == 0 {
+                    return Ok(false);
+                }
+
+                sqlx::query(
+                    "INSERT INTO email_jobs \
+                         (job_type, recipient_email, template_name, template_data, priority) \
+                     SELECT $1, t.recipient, $2, t.data, $3 \
+                     FROM UNNEST($4::TEXT[], $5::JSONB[]) AS t(recipient, data)",
+                )
+                .bind(EmailJobType::Digest.as_str())
+                .bind(digest::TEMPLATE)
+                .bind(digest::PRIORITY)
+                .bind(&recipients)
+                .bind(&data)
+                .execute(&mut *tx)
+                .await?;
+
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(true)
+            })
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        Ok(enqueued)
+    }
+
+    /// Live markets resolved at or after `since`, highest volume first.
+    pub async fn markets_resolved_since(
+        &self,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<MarketDetail>> {
+        let rows = self.with_timeout("markets_resolved_since", sqlx::query(
+            "SELECT id, title, description, category, creator, status, outcome_options, \
+             outcome_odds, outcome_index, total_volume, participant_count, ends_at, \
+             created_at, resolved_at, token, image_url \
+             FROM markets \
+             WHERE status = 'resolved' AND resolved_at >= $1 AND deleted_at IS NULL \
+             ORDER BY total_volume DESC, id \
+             LIMIT $2",
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter().map(market_detail_from_row).collect()
+    }
+
+    // ── Backfill jobs ─────────────────────────────────────────────────────────
+
+    pub async fn backfill_job_create(
+        &self,
+        network: &str,
+        from_ledger: u32,
+        to_ledger: u32,
+    ) -> anyhow::Result<BackfillJob> {
+        let row = self.with_timeout("backfill_job_create", sqlx::query(
+            "INSERT INTO backfill_jobs (network, from_ledger, to_ledger) VALUES ($1, $2, $3) RETURNING *",
+        )
+        .bind(network)
+        .bind(i64::from(from_ledger))
+        .bind(i64::from(to_ledger))
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        backfill_job_from_row(&row)
+    }
+
+    pub async fn backfill_job_get(&self, id: i64) -> anyhow::Result<Option<BackfillJob>> {
+        let row = self.with_timeout("backfill_job_get", sqlx::query(
+            "SELECT * FROM backfill_jobs WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(backfill_job_from_row).transpose()
+    }
+
+    /// Take the oldest unfinished job on one of `networks` whose lease is
+    /// free or has lapsed, holding it for `lease`. A pending job becomes
+    /// running; a running or reconciling one resumes where it stopped.
+    pub async fn backfill_job_claim(
+        &self,
+        networks: &[String],
+        lease: Duration,
+    ) -> anyhow::Result<Option<BackfillJob>> {
+        let row = self.with_timeout("backfill_job_claim", sqlx::query(
+            "UPDATE backfill_jobs \
+             SET status = CASE WHEN status = 'pending' THEN 'running' ELSE status END, \
+                 lease_until = NOW() + $2::BIGINT * INTERVAL '1 millisecond', \
+                 updated_at = NOW() \
+             WHERE id = ( \
+                 SELECT id FROM backfill_jobs \
+                 WHERE status <> 'completed' AND network = ANY($1) \
+                   AND (lease_until IS NULL OR lease_until < NOW()) \
+                 ORDER BY id \
+                 LIMIT 1 \
+                 FOR UPDATE SKIP LOCKED \
+             ) \
+             RETURNING *",
+        )
+        .bind(networks)
+        .bind(lease.as_millis() as i64)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(backfill_job_from_row).transpose()
+    }
+
+    /// Record a stored page: the cursor to continue after, the highest ledger
+    /// stored, and the event counts. Renews the lease and clears the last
+    /// error.
+    pub async fn backfill_job_record_page(
+        &self,
+        id: i64,
+        page_cursor: Option<&str>,
+        last_ledger: Option<i64>,
+        scanned: i64,
+        inserted: i64,
+        lease: Duration,
+    ) -> anyhow::Result<BackfillJob> {
+        let row = self.with_timeout("backfill_job_record_page", sqlx::query(
+            "UPDATE backfill_jobs \
+             SET status = CASE WHEN status = 'pending' THEN 'running' ELSE status END, \
+                 page_cursor = $2, \
+                 last_ledger = GREATEST(last_ledger, $3), \
+                 pages = pages + 1, \
+                 events_scanned = events_scanned + $4, \
+                 events_inserted = events_inserted + $5, \
+                 last_error = NULL, \
+                 lease_until = NOW() + $6::BIGINT * INTERVAL '1 millisecond', \
+                 updated_at = NOW() \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(page_cursor)
+        .bind(last_ledger)
+        .bind(scanned)
+        .bind(inserted)
+        .bind(lease.as_millis() as i64)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        backfill_job_from_row(&row)
+    }
+
+    /// Move a job to `status`. Completing it releases the lease and stamps
+    /// `completed_at`.
+    pub async fn backfill_job_set_status(
+        &self,
+        id: i64,
+        status: BackfillStatus,
+    ) -> anyhow::Result<BackfillJob> {
+        let row = self.with_timeout("backfill_job_set_status", sqlx::query(
+            "UPDATE backfill_jobs \
+             SET status = $2, \
+                 lease_until = CASE WHEN $2 = 'completed' THEN NULL ELSE lease_until END, \
+                 completed_at = CASE WHEN $2 = 'completed' THEN NOW() ELSE completed_at END, \
+                 updated_at = NOW() \
+             WHERE id = $1 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(status.label())
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        backfill_job_from_row(&row)
+    }
+
+    /// Give up the lease on a job so the next poll, here or elsewhere, picks
+    /// it up again, recording `error` when it stopped on one.
+    pub async fn backfill_job_release(&self, id: i64, error: Option<&str>) -> anyhow::Result<()> {
+        self.with_timeout("backfill_job_release", sqlx::query(
+            "UPDATE backfill_jobs \
+             SET lease_until = NULL, last_error = COALESCE($2, last_error), updated_at = NOW() \
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    // ── Resolution reminders ──────────────────────────────────────────────────
+
+    /// Markets past their betting deadline and still awaiting resolution
+    /// whose resolution deadline (`ends_at + window`) is after `now` and
+    /// within `horizon` of it, with their creator's verified, unsuppressed
+    /// notification email. Soonest deadline first.
+    pub async fn resolution_reminder_candidates(
+        &self,
+        window: Duration,
+        horizon: Duration,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ReminderCandidate>> {
+        let rows = self.with_timeout("resolution_reminder_candidates", sqlx::query(
+            "SELECT m.id, m.title, s.email, \
+                    m.ends_at + $1::BIGINT * INTERVAL '1 second' AS resolution_deadline \
+             FROM markets m \
+             JOIN user_notification_settings s ON s.address = m.creator \
+             WHERE m.status = 'active' AND m.deleted_at IS NULL \
+               AND NOT EXISTS (SELECT 1 FROM chain_events e \
+                               WHERE e.market_id = m.id AND e.kind IN ('resolv_fx', 'mkt_cncl')) \
+               AND m.ends_at <= $3 \
+               AND m.ends_at + $1::BIGINT * INTERVAL '1 second' > $3 \
+               AND m.ends_at + $1::BIGINT * INTERVAL '1 second' <= $3 + $2::BIGINT * INTERVAL '1 second' \
+               AND s.email IS NOT NULL AND s.email_verified_at IS NOT NULL \
+               AND NOT EXISTS (SELECT 1 FROM email_suppressions x WHERE x.email = s.email) \
+             ORDER BY resolution_deadline, m.id",
+        )
+        .bind(window.as_secs() as i64)
+        .bind(horizon.as_secs() as i64)
+        .bind(now)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ReminderCandidate {
+                    market_id: row.try_get("id")?,
+                    title: row.try_get("title")?,
+                    email: row.try_get("email")?,
+                    resolution_deadline: row.try_get("resolution_deadline")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Claim the `lead_hours` reminder for `market_id`. `false` when it was
+    /// already claimed, or when the market has been resolved or cancelled
+    /// (in the table or by an indexed event) since it was listed.
+    pub async fn resolution_reminder_claim(&self, market_id: i64, lead_hours: u32) -> anyhow::Result<bool> {
+        let result = self.with_timeout("resolution_reminder_claim", sqlx::query(
+            "INSERT INTO sent_reminders (market_id, lead_hours) \
+             SELECT m.id, $2 FROM markets m \
+             WHERE m.id = $1 AND m.status = 'active' AND m.deleted_at IS NULL \
+               AND NOT EXISTS (SELECT 1 FROM chain_events e \
+                               WHERE e.market_id = m.id AND e.kind IN ('resolv_fx', 'mkt_cncl')) \
+             ON CONFLICT (market_id, lead_hours) DO NOTHING",
+        )
+        .bind(market_id)
+        .bind(lead_hours as i32)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Record the email job enqueued for a claimed reminder.
+    pub async fn resolution_reminder_set_job(
+        &self,
+        market_id: i64,
+        lead_hours: u32,
+        email_job_id: uuid::Uuid,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("resolution_reminder_set_job", sqlx::query(
+            "UPDATE sent_reminders SET email_job_id = $3 \
+             WHERE market_id = $1 AND lead_hours = $2",
+        )
+        .bind(market_id)
+        .bind(lead_hours as i32)
+        .bind(email_job_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Drop a claim whose reminder could not be enqueued so it can be retried.
+    pub async fn resolution_reminder_release(&self, market_id: i64, lead_hours: u32) -> anyhow::Result<()> {
+        self.with_timeout("resolution_reminder_release", sqlx::query(
+            "DELETE FROM sent_reminders \
+             WHERE market_id = $1 AND lead_hours = $2 AND email_job_id IS NULL",
+        )
+        .bind(market_id)
+        .bind(lead_hours as i32)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Whether `market_id` is live and neither resolved nor cancelled, in the
+    /// table or by an indexed event.
+    pub async fn market_awaiting_resolution(&self, market_id: i64) -> anyhow::Result<bool> {
+        let awaiting: bool = self.with_timeout("market_awaiting_resolution", sqlx::query_scalar(
+            "SELECT EXISTS ( \
+                 SELECT 1 FROM markets m \
+                 WHERE m.id = $1 AND m.status = 'active' AND m.deleted_at IS NULL \
+                   AND NOT EXISTS (SELECT 1 FROM chain_events e \
+                                   WHERE e.market_id = m.id AND e.kind IN ('resolv_fx', 'mkt_cncl')) \
+             )",
+        )
+        .bind(market_id)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(awaiting)
+    }
+
+    // ── Categories ────────────────────────────────────────────────────────────
+
+    /// Every category, featured first and then by name. Cached for
+    /// [`CacheTtls::categories`]; admin writes drop the entry through
+    /// [`crate::cache::InvalidationTag::CategoryChanged`].
+    pub async fn categories_cached(&self) -> anyhow::Result<Vec<Category>> {
+        let key = keys::dbq_categories();
+        let endpoint = "categories";
+
+        let (value, hit) = self
+            .cache
+            .get_or_set_json(&key, self.cache_ttls.categories, || async {
+                let rows = self.with_timeout("categories", sqlx::query(
+                    &format!("{CATEGORY_SELECT} GROUP BY c.slug ORDER BY c.is_featured DESC, c.name, c.slug"),
+                )
+                .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+                rows.iter().map(category_from_row).collect::<anyhow::Result<Vec<_>>>()
+            })
+            .await?;
+
+        if hit {
+            self.metrics.observe_hit("db", endpoint);
+        } else {
+            self.metrics.observe_miss("db", endpoint);
+        }
+
+        Ok(value)
+    }
+
+    pub async fn category_get(&self, slug: &str) -> anyhow::Result<Option<Category>> {
+        let row = self.with_timeout("category_get", sqlx::query(
+            &format!("{CATEGORY_SELECT} WHERE c.slug = $1 GROUP BY c.slug"),
+        )
+        .bind(slug)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(category_from_row).transpose()
+    }
+
+    /// Insert a category. Returns `None` when the slug is already taken.
+    pub async fn category_create(
+        &self,
+        slug: &str,
+        name: &str,
+        is_featured: bool,
+    ) -> anyhow::Result<Option<Category>> {
+        let inserted = self.with_timeout("category_create", sqlx::query(
+            "INSERT INTO categories (slug, name, is_featured) VALUES ($1, $2, $3) \
+             ON CONFLICT (slug) DO NOTHING",
+        )
+        .bind(slug)
+        .bind(name)
+        .bind(is_featured)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.category_get(slug).await
+    }
+
+    /// Rename and/or (un)feature a category; `None` fields are left as they
+    /// are. Returns `None` for an unknown slug.
+    pub async fn category_update(
+        &self,
+        slug: &str,
+        name: Option<&str>,
+        is_featured: Option<bool>,
+    ) -> anyhow::Result<Option<Category>> {
+        let updated = self.with_timeout("category_update", sqlx::query(
+            "UPDATE categories \
+             SET name = COALESCE($2, name), \
+                 is_featured = COALESCE($3, is_featured), \
+                 updated_at = NOW() \
+             WHERE slug = $1",
+        )
+        .bind(slug)
+        .bind(name)
+        .bind(is_featured)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        if updated.rows_affected() == 0 {
+            return Ok(None);
+        }
+        self.category_get(slug).await
+    }
+
+    /// Remove a category. Markets keep their `category` value, but it no
+    /// longer passes the market-list filter. Returns whether a row existed.
+    pub async fn category_delete(&self, slug: &str) -> anyhow::Result<bool> {
+        let deleted = self.with_timeout("category_delete", sqlx::query(
+            "DELETE FROM categories WHERE slug = $1",
+        )
+        .bind(slug)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    // ── Signup email blocklist ────────────────────────────────────────────────
+
+    /// Every blocked email domain pattern, as signups check them. Cached for
+    /// [`CacheTtls::blocked_email_domains`]; admin writes drop the entry
+    /// through [`crate::cache::InvalidationTag::BlockedEmailDomainsChanged`].
+    pub async fn blocked_email_patterns_cached(&self) -> anyhow::Result<Vec<String>> {
+        let key = keys::dbq_blocked_email_domains();
+        let endpoint = "blocked_email_domains";
+
+        let (value, hit) = self
+            .cache
+            .get_or_set_json(&key, self.cache_ttls.blocked_email_domains, || async {
+                let patterns: Vec<String> = self.with_timeout("blocked_email_patterns", sqlx::query_scalar(
+                    "SELECT pattern FROM blocked_email_domains ORDER BY pattern",
+                )
+                .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+                Ok(patterns)
+            })
+            .await?;
+
+        if hit {
+            self.metrics.observe_hit("db", endpoint);
+        } else {
+            self.metrics.observe_miss("db", endpoint);
+        }
+
+        Ok(value)
+    }
+
+    /// The blocklist for admins, by pattern.
+    pub async fn blocked_email_domains(&self) -> anyhow::Result<Vec<BlockedDomain>> {
+        let rows = self.with_timeout("blocked_email_domains", sqlx::query(
+            "SELECT id, pattern, note, created_at FROM blocked_email_domains ORDER BY pattern",
+        )
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter().map(blocked_domain_from_row).collect()
+    }
+
+    /// Block `pattern`. Returns `None` when it is already blocked.
+    pub async fn blocked_email_domain_create(
+        &self,
+        pattern: &str,
+        note: Option<&str>,
+    ) -> anyhow::Result<Option<BlockedDomain>> {
+        let row = self.with_timeout("blocked_email_domain_create", sqlx::query(
+            "INSERT INTO blocked_email_domains (pattern, note) VALUES ($1, $2) \
+             ON CONFLICT (pattern) DO NOTHING \
+             RETURNING id, pattern, note, created_at",
+        )
+        .bind(pattern)
+        .bind(note)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(blocked_domain_from_row).transpose()
+    }
+
+    /// Unblock a pattern. Returns whether a row existed.
+    pub async fn blocked_email_domain_delete(&self, id: i64) -> anyhow::Result<bool> {
+        let deleted = self.with_timeout("blocked_email_domain_delete", sqlx::query(
+            "DELETE FROM blocked_email_domains WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    // ── Contact form ──────────────────────────────────────────────────────────
+
+    pub async fn contact_create(
+        &self,
+        name: &str,
+        email: &str,
+        subject: &str,
+        message: &str,
+        metadata: serde_json::Value,
+    ) -> anyhow::Result<ContactSubmission> {
+        let row = self.with_timeout("contact_create", sqlx::query(
+            "INSERT INTO contact_form_submissions (name, email, subject, message, metadata) \
+             VALUES ($1, $2, $3, $4, $5) \
+             RETURNING *",
+        )
+        .bind(name)
+        .bind(email)
+        .bind(subject)
+        .bind(message)
+        .bind(metadata)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        contact_submission_from_row(&row)
+    }
+
+    /// Submissions, newest first, optionally filtered by status.
+    pub async fn contact_list(
+        &self,
+        status: Option<ContactStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> anyhow::Result<Vec<ContactSubmission>> {
+        let rows = self.with_timeout("contact_list", sqlx::query(
+            "SELECT * FROM contact_form_submissions \
+             WHERE $1::TEXT IS NULL OR status = $1 \
+             ORDER BY submitted_at DESC, id \
+             LIMIT $2 OFFSET $3",
+        )
+        .bind(status.map(ContactStatus::label))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter().map(contact_submission_from_row).collect()
+    }
+
+    pub async fn contact_get(&self, id: uuid::Uuid) -> anyhow::Result<Option<ContactSubmission>> {
+        let row = self.with_timeout("contact_get", sqlx::query(
+            "SELECT * FROM contact_form_submissions WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(contact_submission_from_row).transpose()
+    }
+
+    /// Move a submission from `from` to `to`, stamping `answered_at` or
+    /// `resolved_at`. Returns `None` when the row is no longer in `from`, so
+    /// two admins racing on the same submission cannot both apply a change.
+    pub async fn contact_set_status(
+        &self,
+        id: uuid::Uuid,
+        from: ContactStatus,
+        to: ContactStatus,
+    ) -> anyhow::Result<Option<ContactSubmission>> {
+        let row = self.with_timeout("contact_set_status", sqlx::query(
+            "UPDATE contact_form_submissions \
+             SET status = $3, \
+                 answered_at = CASE WHEN $3 = 'answered' THEN NOW() ELSE answered_at END, \
+                 resolved_at = CASE WHEN $3 = 'closed' THEN NOW() ELSE resolved_at END, \
+                 updated_at = NOW() \
+             WHERE id = $1 AND status = $2 \
+             RETURNING *",
+        )
+        .bind(id)
+        .bind(from.label())
+        .bind(to.label())
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(contact_submission_from_row).transpose()
+    }
+
+    // ── Waitlist ──────────────────────────────────────────────────────────────
+
+    pub async fn waitlist_get_by_email(&self, email: &str) -> anyhow::Result<Option<WaitlistEntry>> {
+        let row = self.with_timeout("waitlist_get_by_email", sqlx::query(
+            &format!("{WAITLIST_ENTRY_SELECT} WHERE w.email = $1"),
+        )
+        .bind(email)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(waitlist_entry_from_row).transpose()
+    }
+
+    /// Id of the entry that owns `code`, if any.
+    pub async fn waitlist_referrer_id(&self, code: &str) -> anyhow::Result<Option<uuid::Uuid>> {
+        let row = self.with_timeout("waitlist_referrer_id", sqlx::query(
+            "SELECT id FROM waitlist_entries WHERE referral_code = $1",
+        )
+        .bind(code)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.map(|row| row.try_get("id").map_err(anyhow::Error::from)).transpose()
+    }
+
+    /// Add `email` to the waitlist with a fresh referral code, crediting
+    /// `referred_by` with [`REFERRAL_BONUS`] in the same statement. An email
+    /// that is already on the list is returned unchanged, so joining twice
+    /// never resets a position or grants a second bonus.
+    pub async fn waitlist_join(
+        &self,
+        email: &str,
+        source: &str,
+        referred_by: Option<uuid::Uuid>,
+    ) -> anyhow::Result<WaitlistJoin> {
+        for _ in 0..REFERRAL_CODE_ATTEMPTS {
+            // DO NOTHING covers both unique keys: an email conflict means the
+            // entry exists, a referral_code conflict means draw another code.
+            let inserted = self.with_timeout("waitlist_join", sqlx::query(
+                "WITH inserted AS ( \
+                     INSERT INTO waitlist_entries (email, source, referral_code, referred_by) \
+                     VALUES ($1, $2, $3, $4) \
+                     ON CONFLICT DO NOTHING \
+                     RETURNING id, referred_by \
+                 ), bonus AS ( \
+                     UPDATE waitlist_entries \
+                     SET priority_score = priority_score + $5, updated_at = NOW() \
+                     WHERE id = (SELECT referred_by FROM inserted) \
+                 ) \
+                 SELECT id FROM inserted",
+            )
+            .bind(email)
+            .bind(source)
+            .bind(generate_referral_code())
+            .bind(referred_by)
+            .bind(REFERRAL_BONUS)
+            .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+            let entry = self.waitlist_get_by_email(email).await?;
+            match (inserted, entry) {
+                (Some(_), Some(entry)) => return Ok(WaitlistJoin::Joined(entry)),
+                (None, Some(entry)) => return Ok(WaitlistJoin::Existing(entry)),
+                (_, None) => continue,
+            }
+        }
+        anyhow::bail!("waitlist_join: no unused referral code after {REFERRAL_CODE_ATTEMPTS} attempts")
+    }
+
+    /// Move the pending entries at the given 1-based queue `positions` to
+    /// `invited`. Entries invited concurrently by another request are skipped.
+    pub async fn waitlist_invite(&self, positions: &[i64]) -> anyhow::Result<Vec<WaitlistInvitee>> {
+        let rows = self.with_timeout("waitlist_invite", sqlx::query(
+            "WITH ranked AS ( \
+                 SELECT id, ROW_NUMBER() OVER (ORDER BY priority_score DESC, joined_at, id) AS position \
+                 FROM waitlist_entries WHERE status = 'pending' \
+             ) \
+             UPDATE waitlist_entries w \
+             SET status = 'invited', invited_at = NOW(), updated_at = NOW() \
+             FROM ranked r \
+             WHERE w.id = r.id AND r.position = ANY($1) AND w.status = 'pending' \
+             RETURNING w.id, w.email, r.position",
+        )
+        .bind(positions)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        let mut invitees = rows
+            .iter()
+            .map(|row| {
+                Ok(WaitlistInvitee {
+                    id: row.try_get("id")?,
+                    email: row.try_get("email")?,
+                    position: row.try_get("position")?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        invitees.sort_by_key(|invitee| invitee.position);
+        Ok(invitees)
+    }
+
+    pub async fn waitlist_stats(&self) -> anyhow::Result<WaitlistStats> {
+        let row = self.with_timeout("waitlist_stats", sqlx::query(
+            "SELECT COUNT(*) AS total, \
+                    COUNT(*) FILTER (WHERE status = 'pending') AS pending, \
+                    COUNT(*) FILTER (WHERE status = 'invited') AS invited, \
+                    COUNT(*) FILTER (WHERE status = 'converted') AS converted, \
+                    COUNT(*) FILTER (WHERE referred_by IS NOT NULL) AS referred, \
+                    COUNT(*) FILTER (WHERE joined_at > NOW() - INTERVAL '24 hours') AS joined_last_24h \
+             FROM waitlist_entries",
+        )
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(WaitlistStats {
+            total: row.try_get("total")?,
+            pending: row.try_get("pending")?,
+            invited: row.try_get("invited")?,
+            converted: row.try_get("converted")?,
+            referred: row.try_get("referred")?,
+            joined_last_24h: row.try_get("joined_last_24h")?,
+        })
+    }
+
+    // ── Analytics ─────────────────────────────────────────────────────────────
+
+    /// Insert a validated batch with one multi-row statement, so the batch is
+    /// stored whole or not at all. `ip` must already be a valid address.
+    pub async fn analytics_insert_events(
+        &self,
+        events: &[AnalyticsEvent],
+        ip: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> anyhow::Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+        let mut qb = sqlx::QueryBuilder::<sqlx::Postgres>::new(
+            "INSERT INTO analytics_events \
+             (event_name, properties, session_id, occurred_at, ip_address, user_agent) ",
+        );
+        qb.push_values(events, |mut row, event| {
+            row.push_bind(&event.event_type)
+                .push_bind(&event.properties)
+                .push_bind(&event.session_id)
+                .push_bind(event.occurred_at)
+                .push_bind(ip)
+                .push_unseparated("::INET")
+                .push_bind(user_agent);
+        });
+        let result = self
+            .with_timeout("analytics_insert_events", qb.build().execute(&self.pool))
+            .await
+            .map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Recompute `analytics_daily` for the UTC days in `[from, to)`,
+    /// overwriting existing counts. Returns the number of (day, type) rows
+    /// written.
+    pub async fn analytics_rollup(&self, from: NaiveDate, to: NaiveDate) -> anyhow::Result<u64> {
+        let result = self.with_timeout("analytics_rollup", sqlx::query(
+            "INSERT INTO analytics_daily (day, event_type, event_count, unique_sessions) \
+             SELECT (occurred_at AT TIME ZONE 'UTC')::DATE, event_name, \
+                    COUNT(*), COUNT(DISTINCT session_id) \
+             FROM analytics_events \
+             WHERE occurred_at >= $1 AND occurred_at < $2 \
+             GROUP BY 1, 2 \
+             ON CONFLICT (day, event_type) DO UPDATE \
+             SET event_count = EXCLUDED.event_count, \
+                 unique_sessions = EXCLUDED.unique_sessions, \
+                 updated_at = NOW()",
+        )
+        .bind(from.and_time(NaiveTime::MIN).and_utc())
+        .bind(to.and_time(NaiveTime::MIN).and_utc())
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Rolled-up counts for the UTC days in `[from, to]`, by day then type,
+    /// and when the rollup last wrote anything.
+    pub async fn analytics_daily(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<(Vec<AnalyticsDailyCount>, Option<DateTime<Utc>>)> {
+        let rows = self.with_timeout("analytics_daily", sqlx::query(
+            "SELECT day, event_type, event_count, unique_sessions \
+             FROM analytics_daily \
+             WHERE day BETWEEN $1 AND $2 \
+             ORDER BY day, event_type",
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        let rolled_up_at: Option<DateTime<Utc>> = self.with_timeout("analytics_daily", sqlx::query_scalar(
+            "SELECT MAX(updated_at) FROM analytics_daily",
+        )
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let daily = rows
+            .iter()
+            .map(|row| {
+                Ok(AnalyticsDailyCount {
+                    day: row.try_get("day")?,
+                    event_type: row.try_get("event_type")?,
+                    event_count: row.try_get("event_count")?,
+                    unique_sessions: row.try_get("unique_sessions")?,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok((daily, rolled_up_at))
+    }
+
+    /// Newsletter and waitlist signups made in `[from, to)`, grouped by
+    /// source or UTC day, with how many of them confirmed, unsubscribed or
+    /// converted since. GDPR-deleted subscribers are left out. Sources are
+    /// ordered by signups, most first; days oldest first.
+    pub async fn acquisition_counts(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        group_by: AcquisitionGroupBy,
+    ) -> anyhow::Result<Vec<AcquisitionCounts>> {
+        let (key, order) = match group_by {
+            AcquisitionGroupBy::Source => ("source", "newsletter_signups + waitlist_signups DESC, key"),
+            AcquisitionGroupBy::Day => ("to_char(signed_up_at AT TIME ZONE 'UTC', 'YYYY-MM-DD')", "key"),
+        };
+        let rows = self.with_timeout("acquisition_counts", sqlx::query(&format!(
+            "WITH signups AS ( \
+                 SELECT source, created_at AS signed_up_at, TRUE AS newsletter, \
+                        confirmed AS converted, unsubscribed_at IS NOT NULL AS unsubscribed \
+                 FROM newsletter_subscribers \
+                 WHERE deleted_at IS NULL AND created_at >= $1 AND created_at < $2 \
+                 UNION ALL \
+                 SELECT COALESCE(source, 'direct'), joined_at, FALSE, status = 'converted', FALSE \
+                 FROM waitlist_entries \
+                 WHERE joined_at >= $1 AND joined_at < $2 \
+             ) \
+             SELECT key, newsletter_signups, confirmations, unsubscribes, waitlist_signups, waitlist_conversions \
+             FROM ( \
+                 SELECT {key} AS key, \
+                        COUNT(*) FILTER (WHERE newsletter) AS newsletter_signups, \
+                        COUNT(*) FILTER (WHERE newsletter AND converted) AS confirmations, \
+                        COUNT(*) FILTER (WHERE newsletter AND unsubscribed) AS unsubscribes, \
+                        COUNT(*) FILTER (WHERE NOT newsletter) AS waitlist_signups, \
+                        COUNT(*) FILTER (WHERE NOT newsletter AND converted) AS waitlist_conversions \
+                 FROM signups \
+                 GROUP BY 1 \
+             ) grouped \
+             ORDER BY {order}"
+        ))
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(AcquisitionCounts {
+                    key: row.try_get("key")?,
+                    newsletter_signups: row.try_get("newsletter_signups")?,
+                    confirmations: row.try_get("confirmations")?,
+                    unsubscribes: row.try_get("unsubscribes")?,
+                    waitlist_signups: row.try_get("waitlist_signups")?,
+                    waitlist_conversions: row.try_get("waitlist_conversions")?,
+                })
+            })
+            .collect()
+    }
+
+    // ── Platform statistics history ───────────────────────────────────────────
+
+    /// Whether the nightly rollup has written anything yet.
+    pub async fn platform_stats_has_rows(&self) -> anyhow::Result<bool> {
+        let exists: bool = self.with_timeout("platform_stats_has_rows", sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM platform_stats_daily)",
+        )
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(exists)
+    }
+
+    /// UTC day of the earliest market or indexed event on `network`, where a
+    /// backfill starts. `None` when there is neither.
+    pub async fn platform_stats_first_day(&self, network: &str) -> anyhow::Result<Option<NaiveDate>> {
+        let first: Option<DateTime<Utc>> = self.with_timeout("platform_stats_first_day", sqlx::query_scalar(
+            "SELECT LEAST( \
+                 (SELECT MIN(created_at) FROM markets WHERE deleted_at IS NULL), \
+                 (SELECT MIN(indexed_at) FROM chain_events WHERE network = $1))",
+        )
+        .bind(network)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(first.map(|at| at.date_naive()))
+    }
+
+    /// Recompute `platform_stats_daily` for every UTC day in `[from, to)`,
+    /// overwriting existing values. Days without activity get a zero row.
+    /// Returns the number of day rows written.
+    pub async fn platform_stats_rollup(&self, network: &str, from: NaiveDate, to: NaiveDate) -> anyhow::Result<u64> {
+        let result = self.with_timeout("platform_stats_rollup", sqlx::query(
+            "WITH days AS ( \
+                 SELECT d::DATE AS day \
+                 FROM generate_series($1::DATE, $2::DATE - 1, INTERVAL '1 day') d \
+             ), created AS ( \
+                 SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS n \
+                 FROM markets \
+                 WHERE deleted_at IS NULL AND created_at >= $3 AND created_at < $4 \
+                 GROUP BY 1 \
+             ), staked AS ( \
+                 SELECT (indexed_at AT TIME ZONE 'UTC')::DATE AS day, SUM(amount) AS total \
+                 FROM chain_events \
+                 WHERE network = $5 AND kind = 'bet_place' AND indexed_at >= $3 AND indexed_at < $4 \
+                 GROUP BY 1 \
+             ), joined AS ( \
+                 SELECT (first_bet AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS n \
+                 FROM ( \
+                     SELECT address, MIN(indexed_at) AS first_bet \
+                     FROM chain_events \
+                     WHERE network = $5 AND kind = 'bet_place' AND address IS NOT NULL \
+                     GROUP BY address \
+                 ) f \
+                 WHERE first_bet >= $3 AND first_bet < $4 \
+                 GROUP BY 1 \
+             ) \
+             INSERT INTO platform_stats_daily (day, markets_created, volume, new_participants) \
+             SELECT days.day, COALESCE(created.n, 0), COALESCE(staked.total, 0), COALESCE(joined.n, 0) \
+             FROM days \
+             LEFT JOIN created USING (day) \
+             LEFT JOIN staked USING (day) \
+             LEFT JOIN joined USING (day) \
+             ON CONFLICT (day) DO UPDATE \
+             SET markets_created = EXCLUDED.markets_created, \
+                 volume = EXCLUDED.volume, \
+                 new_participants = EXCLUDED.new_participants, \
+                 updated_at = NOW()",
+        )
+        .bind(from)
+        .bind(to)
+        .bind(from.and_time(NaiveTime::MIN).and_utc())
+        .bind(to.and_time(NaiveTime::MIN).and_utc())
+        .bind(network)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Stored `(day, value)` pairs of one metric for the UTC days in
+    /// `[from, to]`, oldest first, and when the rollup last wrote anything.
+    /// Days the rollup never covered are absent.
+    pub async fn platform_stats_daily(
+        &self,
+        metric: StatsMetric,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> anyhow::Result<(Vec<(NaiveDate, String)>, Option<DateTime<Utc>>)> {
+        let sql = format!(
+            "SELECT day, {}::TEXT AS value \
+             FROM platform_stats_daily \
+             WHERE day BETWEEN $1 AND $2 \
+             ORDER BY day",
+            metric.column()
+        );
+        let rows = self.with_timeout("platform_stats_daily", sqlx::query(&sql)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        let rolled_up_at: Option<DateTime<Utc>> = self.with_timeout("platform_stats_daily", sqlx::query_scalar(
+            "SELECT MAX(updated_at) FROM platform_stats_daily",
+        )
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let points = rows
+            .iter()
+            .map(|row| Ok((row.try_get("day")?, row.try_get("value")?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok((points, rolled_up_at))
+    }
+
+    /// Market counts and bet volume of `network` derived from indexed data.
+    ///
+    /// Markets are those in the `markets` table plus any with a `mkt_creat`
+    /// event, so a market the API never stored still counts. A market is
+    /// resolved when it has a `resolv_fx` event or a resolved row, and active
+    /// when it has neither a resolve nor a cancel event and its row (if any)
+    /// is active. The total volume counts every indexed bet, as the
+    /// contract's counter does; per-token volumes are [`Self::volume_by_token`].
+    pub async fn platform_stats_derive(&self, network: &str) -> anyhow::Result<DerivedStats> {
+        let row = self.with_timeout("platform_stats_derive", sqlx::query(
+            "WITH ids AS ( \
+                 SELECT id AS market_id FROM markets WHERE deleted_at IS NULL \
+                 UNION \
+                 SELECT market_id FROM chain_events \
+                 WHERE network = $1 AND kind = 'mkt_creat' AND market_id IS NOT NULL \
+             ), ended AS ( \
+                 SELECT market_id, BOOL_OR(kind = 'resolv_fx') AS resolved \
+                 FROM chain_events \
+                 WHERE network = $1 AND kind IN ('resolv_fx', 'mkt_cncl') AND market_id IS NOT NULL \
+                 GROUP BY market_id \
+             ) \
+             SELECT COUNT(*)::BIGINT AS total_markets, \
+                    COUNT(*) FILTER (WHERE e.market_id IS NULL \
+                        AND COALESCE(m.status, 'active') = 'active')::BIGINT AS active_markets, \
+                    COUNT(*) FILTER (WHERE e.resolved OR m.status = 'resolved')::BIGINT AS resolved_markets, \
+                    (SELECT COALESCE(SUM(amount), 0)::TEXT FROM chain_events \
+                     WHERE network = $1 AND kind = 'bet_place') AS total_volume \
+             FROM ids \
+             LEFT JOIN markets m ON m.id = ids.market_id \
+             LEFT JOIN ended e ON e.market_id = ids.market_id",
+        )
+        .bind(network)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let volumes = self.volume_by_token(network).await?;
+        Ok(DerivedStats {
+            total_markets: row.try_get::<i64, _>("total_markets")? as u64,
+            active_markets: row.try_get::<i64, _>("active_markets")? as u64,
+            resolved_markets: row.try_get::<i64, _>("resolved_markets")? as u64,
+            total_volume: row.try_get("total_volume")?,
+            token_volumes: volumes
+                .into_iter()
+                .map(|(token, volume)| TokenVolume { token, volume })
+                .collect(),
+        })
+    }
+
+    /// Store `stats` as a snapshot of `network` taken at `ledger`.
+    pub async fn platform_stats_snapshot_insert(
+        &self,
+        network: &str,
+        ledger: u32,
+        stats: &DerivedStats,
+    ) -> anyhow::Result<StatsSnapshot> {
+        let token_volumes = serde_json::to_string(&stats.token_volumes)?;
+        let created_at: DateTime<Utc> = self.with_timeout("platform_stats_snapshot_insert", sqlx::query_scalar(
+            "INSERT INTO platform_stats_snapshots \
+                 (network, ledger, total_markets, active_markets, resolved_markets, total_volume, token_volumes) \
+             VALUES ($1, $2, $3, $4, $5, $6::NUMERIC, $7::JSONB) \
+             RETURNING created_at",
+        )
+        .bind(network)
+        .bind(i64::from(ledger))
+        .bind(stats.total_markets as i64)
+        .bind(stats.active_markets as i64)
+        .bind(stats.resolved_markets as i64)
+        .bind(&stats.total_volume)
+        .bind(&token_volumes)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(StatsSnapshot {
+            network: network.to_string(),
+            ledger,
+            stats: stats.clone(),
+            created_at,
+        })
+    }
+
+    /// The newest snapshot of `network`, if any.
+    pub async fn platform_stats_snapshot_latest(
+        &self,
+        network: &str,
+    ) -> anyhow::Result<Option<StatsSnapshot>> {
+        let row = self.with_timeout("platform_stats_snapshot_latest", sqlx::query(
+            "SELECT network, ledger, total_markets, active_markets, resolved_markets, \
+                    total_volume::TEXT AS total_volume, token_volumes::TEXT AS token_volumes, created_at \
+             FROM platform_stats_snapshots \
+             WHERE network = $1 \
+             ORDER BY created_at DESC, id DESC \
+             LIMIT 1",
+        )
+        .bind(network)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        row.as_ref().map(stats_snapshot_from_row).transpose()
+    }
+
+    /// Delete snapshots of `network` taken before `cutoff`. Returns the
+    /// number removed.
+    pub async fn platform_stats_snapshots_delete_before(
+        &self,
+        network: &str,
+        cutoff: DateTime<Utc>,
+    ) -> anyhow::Result<u64> {
+        let result = self.with_timeout("platform_stats_snapshots_delete_before", sqlx::query(
+            "DELETE FROM platform_stats_snapshots WHERE network = $1 AND created_at < $2",
+        )
+        .bind(network)
+        .bind(cutoff)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    // ── CSV exports ───────────────────────────────────────────────────────────
+
+    /// Waitlist entries oldest first, filtered in SQL by status and a
+    /// half-open `[from, to)` range on `joined_at`.
+    pub fn waitlist_export(
+        &self,
+        status: Option<WaitlistStatus>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, anyhow::Result<WaitlistExportRow>> {
+        let query = sqlx::query(
+            "SELECT w.email, w.status, w.source, w.referral_code, w.priority_score, \
+                    w.joined_at, w.invited_at, w.converted_at, \
+                    (SELECT COUNT(*) FROM waitlist_entries r WHERE r.referred_by = w.id) AS referral_count \
+             FROM waitlist_entries w \
+             WHERE ($1::TEXT IS NULL OR w.status = $1) \
+               AND ($2::TIMESTAMPTZ IS NULL OR w.joined_at >= $2) \
+               AND ($3::TIMESTAMPTZ IS NULL OR w.joined_at < $3) \
+             ORDER BY w.joined_at, w.id",
+        )
+        .bind(status.map(WaitlistStatus::label))
+        .bind(from)
+        .bind(to);
+        self.stream_rows("waitlist_export", query, |row| {
+            Ok(WaitlistExportRow {
+                email: row.try_get("email")?,
+                status: row.try_get("status")?,
+                source: row.try_get("source")?,
+                referral_code: row.try_get("referral_code")?,
+                referral_count: row.try_get("referral_count")?,
+                priority_score: row.try_get("priority_score")?,
+                joined_at: row.try_get("joined_at")?,
+                invited_at: row.try_get("invited_at")?,
+                converted_at: row.try_get("converted_at")?,
+            })
+        })
+    }
+
+    /// Newsletter subscribers oldest first, excluding GDPR-deleted rows,
+    /// filtered in SQL by status and a half-open `[from, to)` range on
+    /// `created_at`.
+    pub fn newsletter_export(
+        &self,
+        status: Option<NewsletterExportStatus>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, anyhow::Result<NewsletterExportRow>> {
+        let query = sqlx::query(
+            "SELECT email, source, created_at, confirmed_at, unsubscribed_at, \
+                    CASE WHEN unsubscribed_at IS NOT NULL THEN 'unsubscribed' \
+                         WHEN confirmed THEN 'confirmed' \
+                         ELSE 'pending' END AS status \
+             FROM newsletter_subscribers \
+             WHERE deleted_at IS NULL \
+               AND ($1::TEXT IS NULL OR CASE WHEN unsubscribed_at IS NOT NULL THEN 'unsubscribed' \
+                                             WHEN confirmed THEN 'confirmed' \
+                                             ELSE 'pending' END = $1) \
+               AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2) \
+               AND ($3::TIMESTAMPTZ IS NULL OR created_at < $3) \
+             ORDER BY created_at, id",
+        )
+        .bind(status.map(NewsletterExportStatus::label))
+        .bind(from)
+        .bind(to);
+        self.stream_rows("newsletter_export", query, |row| {
+            let status: String = row.try_get("status")?;
+            Ok(NewsletterExportRow {
+                email: row.try_get("email")?,
+                source: row.try_get("source")?,
+                status: NewsletterExportStatus::parse(&status)
+                    .with_context(|| format!("newsletter_export: unknown status {status:?}"))?,
+                created_at: row.try_get("created_at")?,
+                confirmed_at: row.try_get("confirmed_at")?,
+                unsubscribed_at: row.try_get("unsubscribed_at")?,
+            })
+        })
+    }
+
+    /// Indexed contract events on `network` in ledger order, filtered in SQL
+    /// by kind and a half-open `[from, to)` range on `indexed_at`.
+    pub fn chain_events_export(
+        &self,
+        network: &str,
+        kind: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> BoxStream<'static, anyhow::Result<ChainEventExportRow>> {
+        let query = sqlx::query(
+            "SELECT id, ledger, tx_hash, kind, market_id, address, outcome, \
+                    amount::TEXT AS amount, token, is_refund, indexed_at \
+             FROM chain_events \
+             WHERE network = $1 \
+               AND ($2::TEXT IS NULL OR kind = $2) \
+               AND ($3::TIMESTAMPTZ IS NULL OR indexed_at >= $3) \
+               AND ($4::TIMESTAMPTZ IS NULL OR indexed_at < $4) \
+             ORDER BY ledger, id",
+        )
+        .bind(network.to_string())
+        .bind(kind.map(str::to_string))
+        .bind(from)
+        .bind(to);
+        self.stream_rows("chain_events_export", query, |row| {
+            Ok(ChainEventExportRow {
+                id: row.try_get("id")?,
+                ledger: row.try_get("ledger")?,
+                tx_hash: row.try_get("tx_hash")?,
+                kind: row.try_get("kind")?,
+                market_id: row.try_get("market_id")?,
+                address: row.try_get("address")?,
+                outcome: row.try_get("outcome")?,
+                amount: row.try_get("amount")?,
+                token: row.try_get("token")?,
+                is_refund: row.try_get("is_refund")?,
+                indexed_at: row.try_get("indexed_at")?,
+            })
+        })
+    }
+
+    /// Run `query` on a spawned task and hand mapped rows back through a
+    /// channel of [`EXPORT_BUFFER_ROWS`], so a large result is never held in
+    /// memory and the query stops once the consumer is dropped. The first
+    /// error ends the stream. Exports can legitimately outlast the per-query
+    /// timeout, so it does not apply here.
+    fn stream_rows<T, F>(
+        &self,
+        operation: &'static str,
+        query: sqlx::query::Query<'static, sqlx::Postgres, sqlx::postgres::PgArguments>,
+        map: F,
+    ) -> BoxStream<'static, anyhow::Result<T>>
+    where
+        T: Send + 'static,
+        F: Fn(&sqlx::postgres::PgRow) -> anyhow::Result<T> + Send + 'static,
+    {
+        let pool = self.pool.clone();
+        let metrics = self.metrics.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(EXPORT_BUFFER_ROWS);
+        tokio::spawn(async move {
+            let start = std::time::Instant::now();
+            let mut rows = query.fetch(&pool);
+            while let Some(row) = rows.next().await {
+                let item = row.map_err(anyhow::Error::from).and_then(|row| map(&row));
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() {
+                    tracing::debug!(operation, "export consumer went away; stopping");
+                    break;
+                }
+                if failed {
+                    break;
+                }
+            }
+            metrics.observe_db_query_duration(operation, start.elapsed());
+        });
+        futures::stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) }).boxed()
+    }
+
+    // ── GDPR data-subject requests ────────────────────────────────────────────
+
+    /// Every row in [`GDPR_TABLES`] that belongs to `email`, one section per
+    /// table, read from a single snapshot.
+    pub async fn gdpr_collect(&self, normalized_email: &str) -> anyhow::Result<GdprExport> {
+        let sections = self
+            .with_timeout("gdpr_collect", async {
+                let mut tx = self.pool.begin().await?;
+                sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+                    .execute(&mut *tx)
+                    .await?;
+
+                let mut sections = std::collections::BTreeMap::new();
+                for table in GDPR_TABLES {
+                    let rows = sqlx::query(&format!(
+                        "SELECT to_jsonb(t) - $2::TEXT[] AS row FROM {} t WHERE {}",
+                        table.table, table.matches
+                    ))
+                    .bind(normalized_email)
+                    .bind(EXPORT_EXCLUDED_COLUMNS)
+                    .fetch_all(&mut *tx)
+                    .await?;
+                    let rows = rows
+                        .iter()
+                        .map(|row| row.try_get::<serde_json::Value, _>("row"))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    sections.insert(table.section, rows);
+                }
+
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(sections)
+            })
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        Ok(GdprExport {
+            email: normalized_email.to_string(),
+            generated_at: Utc::now(),
+            sections,
+        })
+    }
+
+    /// Apply each table's [`Erasure`] to `email`'s rows in one transaction.
+    /// Unsent email jobs are cancelled before they are anonymized, so none is
+    /// later attempted against the placeholder address.
+    pub async fn gdpr_erase(&self, normalized_email: &str) -> anyhow::Result<GdprDeleteReport> {
+        let report = self
+            .with_timeout("gdpr_erase", async {
+                let mut tx = self.pool.begin().await?;
+                sqlx::query(
+                    "UPDATE email_jobs SET status = 'cancelled', updated_at = NOW() \
+                     WHERE recipient_email = $1 AND status IN ('pending', 'dead_letter')",
+                )
+                .bind(normalized_email)
+                .execute(&mut *tx)
+                .await?;
+
+                let mut report = GdprDeleteReport::default();
+                for table in GDPR_TABLES {
+                    match table.erasure {
+                        Erasure::Delete => {
+                            let result = sqlx::query(&format!(
+                                "DELETE FROM {} WHERE {}",
+                                table.table, table.matches
+                            ))
+                            .bind(normalized_email)
+                            .execute(&mut *tx)
+                            .await?;
+                            report.deleted.insert(table.section, result.rows_affected());
+                        }
+                        Erasure::Anonymize { email_column, json_column } => {
+                            let result = sqlx::query(&format!(
+                                "UPDATE {} SET {} WHERE {}",
+                                table.table,
+                                gdpr::anonymize_assignments(email_column, json_column),
+                                table.matches
+                            ))
+                            .bind(normalized_email)
+                            .execute(&mut *tx)
+                            .await?;
+                            report.anonymized.insert(table.section, result.rows_affected());
+                        }
+                        Erasure::Retain => report.retained.push(table.section),
+                    }
+                }
+
+                tx.commit().await?;
+                Ok::<_, sqlx::Error>(report)
+            })
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        Ok(report)
+    }
+
+    // ── Risk monitoring ───────────────────────────────────────────────────────
+
+    /// Stake per (address, outcome) on `market_id`, with the part placed
+    /// since `since`. Addresses refunded on the market are left out.
+    pub async fn risk_positions(
+        &self,
+        network: &str,
+        market_id: i64,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<RiskPositionRow>> {
+        let rows = self.with_timeout("risk_positions", sqlx::query(
+            "SELECT e.address, e.outcome, SUM(e.amount)::TEXT AS staked, \
+                    COALESCE(SUM(e.amount) FILTER (WHERE e.indexed_at >= $3), 0)::TEXT AS last_hour \
+             FROM chain_events e \
+             WHERE e.network = $1 AND e.market_id = $2 AND e.kind = 'bet_place' \
+               AND e.address IS NOT NULL AND e.outcome IS NOT NULL AND e.amount IS NOT NULL \
+               AND NOT EXISTS ( \
+                   SELECT 1 FROM chain_events r \
+                   WHERE r.network = e.network AND r.market_id = e.market_id \
+                     AND r.address = e.address AND r.kind = 'reward_fx' AND r.is_refund \
+               ) \
+             GROUP BY e.address, e.outcome",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(since)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let mut positions = Vec::with_capacity(rows.len());
+        for row in rows {
+            positions.push(RiskPositionRow {
+                address: row.try_get("address")?,
+                outcome: row.try_get("outcome")?,
+                staked: row.try_get("staked")?,
+                last_hour: row.try_get("last_hour")?,
+            });
+        }
+        Ok(positions)
+    }
+
+    /// Each outcome's newest sampled probability, and the newest one taken
+    /// at or before `before`.
+    pub async fn risk_probabilities(
+        &self,
+        market_id: i64,
+        before: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ProbabilityRow>> {
+        let rows = self.with_timeout("risk_probabilities", sqlx::query(
+            "SELECT o.outcome, \
+                    (SELECT p.close FROM market_price_points p \
+                     WHERE p.market_id = $1 AND p.outcome = o.outcome \
+                     ORDER BY p.ts DESC LIMIT 1) AS latest, \
+                    (SELECT p.close FROM market_price_points p \
+                     WHERE p.market_id = $1 AND p.outcome = o.outcome AND p.ts <= $2 \
+                     ORDER BY p.ts DESC LIMIT 1) AS hour_ago \
+             FROM (SELECT DISTINCT outcome FROM market_price_points WHERE market_id = $1) o",
+        )
+        .bind(market_id)
+        .bind(before)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        let mut probabilities = Vec::with_capacity(rows.len());
+        for row in rows {
+            probabilities.push(ProbabilityRow {
+                outcome: row.try_get("outcome")?,
+                latest: row.try_get("latest")?,
+                hour_ago: row.try_get("hour_ago")?,
+            });
+        }
+        Ok(probabilities)
+    }
+
+    /// Active markets with at least `min_stake` bet on `network`.
+    pub async fn risk_candidate_markets(
+        &self,
+        network: &str,
+        min_stake: i128,
+    ) -> anyhow::Result<Vec<i64>> {
+        let ids = self.with_timeout("risk_candidate_markets", sqlx::query_scalar(
+            "SELECT e.market_id FROM chain_events e \
+             JOIN markets m ON m.id = e.market_id \
+             WHERE e.network = $1 AND e.kind = 'bet_place' \
+               AND m.status = 'active' AND m.deleted_at IS NULL \
+             GROUP BY e.market_id \
+             HAVING SUM(e.amount) >= $2::NUMERIC \
+             ORDER BY e.market_id",
+        )
+        .bind(network)
+        .bind(min_stake.to_string())
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(ids)
+    }
+
+    /// Open an alert for `signal`, or refresh the one already open.
+    pub async fn risk_alert_upsert(
+        &self,
+        network: &str,
+        market_id: i64,
+        signal: &RiskSignal,
+        total_staked: &str,
+    ) -> anyhow::Result<()> {
+        self.with_timeout("risk_alert_upsert", sqlx::query(
+            "INSERT INTO risk_alerts (network, market_id, kind, value, threshold, total_staked) \
+             VALUES ($1, $2, $3, $4, $5, $6::NUMERIC) \
+             ON CONFLICT (network, market_id, kind) WHERE cleared_at IS NULL DO UPDATE \
+                 SET value = EXCLUDED.value, threshold = EXCLUDED.threshold, \
+                     total_staked = EXCLUDED.total_staked, updated_at = NOW()",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(signal.kind.as_str())
+        .bind(signal.value)
+        .bind(signal.threshold)
+        .bind(total_staked)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    /// Clear `market_id`'s open alerts except those of the `holding` kinds.
+    pub async fn risk_alerts_clear(
+        &self,
+        network: &str,
+        market_id: i64,
+        holding: &[&str],
+    ) -> anyhow::Result<u64> {
+        let result = self.with_timeout("risk_alerts_clear", sqlx::query(
+            "UPDATE risk_alerts SET cleared_at = NOW(), updated_at = NOW() \
+             WHERE network = $1 AND market_id = $2 AND cleared_at IS NULL \
+               AND NOT (kind = ANY($3))",
+        )
+        .bind(network)
+        .bind(market_id)
+        .bind(holding)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Clear the open alerts of markets not in `markets`: those resolved,
+    /// cancelled or refunded below the minimum stake since they alerted.
+    pub async fn risk_alerts_clear_other_markets(
+        &self,
+        network: &str,
+        markets: &[i64],
+    ) -> anyhow::Result<u64> {
+        let result = self.with_timeout("risk_alerts_clear_other_markets", sqlx::query(
+            "UPDATE risk_alerts SET cleared_at = NOW(), updated_at = NOW() \
+             WHERE network = $1 AND cleared_at IS NULL AND NOT (market_id = ANY($2))",
+        )
+        .bind(network)
+        .bind(markets)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    /// Open alerts on `network`, newest first.
+    pub async fn risk_alerts_open(&self, network: &str) -> anyhow::Result<Vec<RiskAlert>> {
+        let rows = self.with_timeout("risk_alerts_open", sqlx::query(
+            "SELECT id, network, market_id, kind, value, threshold, total_staked::TEXT AS total_staked, \
+                    opened_at, updated_at, notified_at \
+             FROM risk_alerts WHERE network = $1 AND cleared_at IS NULL \
+             ORDER BY opened_at DESC, id DESC",
+        )
+        .bind(network)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter().map(risk_alert_from_row).collect()
+    }
+
+    /// Mark every open alert not yet pushed as notified and return them, so
+    /// two API instances never push the same alert.
+    pub async fn risk_alerts_claim_unnotified(
+        &self,
+        network: &str,
+    ) -> anyhow::Result<Vec<RiskAlert>> {
+        let rows = self.with_timeout("risk_alerts_claim_unnotified", sqlx::query(
+            "UPDATE risk_alerts SET notified_at = NOW() \
+             WHERE network = $1 AND cleared_at IS NULL AND notified_at IS NULL \
+             RETURNING id, network, market_id, kind, value, threshold, \
+                       total_staked::TEXT AS total_staked, opened_at, updated_at, notified_at",
+        )
+        .bind(network)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter().map(risk_alert_from_row).collect()
+    }
+
+    /// Undo a claim whose push failed, so the next check retries it.
+    pub async fn risk_alert_release(&self, id: i64) -> anyhow::Result<()> {
+        self.with_timeout("risk_alert_release", sqlx::query(
+            "UPDATE risk_alerts SET notified_at = NULL WHERE id = $1",
+        )
+        .bind(id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(())
+    }
+
+    // ── Creator profiles ──────────────────────────────────────────────────────
+
+    /// Whether `address` has hidden its creator profile.
+    pub async fn creator_profile_hidden(&self, address: &str) -> anyhow::Result<bool> {
+        let hidden = self.with_timeout("creator_profile_hidden", sqlx::query_scalar(
+            "SELECT hide_creator_profile FROM user_notification_settings WHERE address = $1",
+        )
+        .bind(address)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(hidden.unwrap_or(false))
+    }
+
+    /// Every market `address` created on `network`, with its volume, whether
+    /// it was disputed, and its final and oracle-reported outcomes.
+    pub async fn creator_markets(&self, network: &str, address: &str) -> anyhow::Result<Vec<CreatorMarketRow>> {
+        let rows = self.with_timeout("creator_markets", sqlx::query(
+            "SELECT c.market_id, \
+                    COALESCE((SELECT SUM(b.amount) FROM chain_events b \
+                              WHERE b.network = $1 AND b.market_id = c.market_id \
+                                AND b.kind = 'bet_place'), 0)::TEXT AS volume, \
+                    EXISTS (SELECT 1 FROM chain_events d \
+                            WHERE d.network = $1 AND d.market_id = c.market_id \
+                              AND d.kind = 'disp_file') AS disputed, \
+                    (SELECT r.outcome FROM chain_events r \
+                     WHERE r.network = $1 AND r.market_id = c.market_id AND r.kind = 'resolv_fx' \
+                     ORDER BY r.ledger DESC, r.id DESC LIMIT 1) AS resolved_outcome, \
+                    (SELECT o.outcome FROM chain_events o \
+                     WHERE o.network = $1 AND o.market_id = c.market_id \
+                       AND o.kind IN ('orcl_res', 'oracle_resolved') \
+                     ORDER BY o.ledger DESC, o.id DESC LIMIT 1) AS oracle_outcome \
+             FROM chain_events c \
+             WHERE c.network = $1 AND c.kind = 'mkt_creat' AND c.address = $2 \
+               AND c.market_id IS NOT NULL \
+             ORDER BY c.market_id",
+        )
+        .bind(network)
+        .bind(address)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter()
+            .map(|row| {
+                Ok(CreatorMarketRow {
+                    market_id: row.try_get("market_id")?,
+                    volume: row.try_get("volume")?,
+                    disputed: row.try_get("disputed")?,
+                    resolved_outcome: row.try_get("resolved_outcome")?,
+                    oracle_outcome: row.try_get("oracle_outcome")?,
+                })
+            })
+            .collect()
+    }
+
+    /// `address`'s live markets that are still active, soonest to close
+    /// first.
+    pub async fn creator_active_markets(&self, address: &str) -> anyhow::Result<Vec<ActiveMarket>> {
+        let rows = self.with_timeout("creator_active_markets", sqlx::query(
+            "SELECT id, title, category, total_volume, participant_count, ends_at \
+             FROM markets \
+             WHERE creator = $1 AND status = 'active' AND deleted_at IS NULL \
+             ORDER BY ends_at, id",
+        )
+        .bind(address)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        rows.iter()
+            .map(|row| {
+                Ok(ActiveMarket {
+                    id: row.try_get("id")?,
+                    title: row.try_get("title")?,
+                    category: row.try_get("category")?,
+                    volume: row.try_get("total_volume")?,
+                    participant_count: row.try_get("participant_count")?,
+                    ends_at: row.try_get("ends_at")?,
+                })
+            })
+            .collect()
+    }
+
+    /// The `limit` leading creators on `network` by `metric` as
+    /// `(address, markets created, volume)`, leaving out hidden profiles.
+    /// Ties go to the other metric, then the address.
+    pub async fn top_creators(
+        &self,
+        network: &str,
+        metric: TopCreatorsMetric,
+        limit: i64,
+    ) -> anyhow::Result<Vec<(String, i64, String)>> {
+        let order = match metric {
+            TopCreatorsMetric::Volume => "staked DESC, markets DESC",
+            TopCreatorsMetric::Markets => "markets DESC, staked DESC",
+        };
+        let rows: Vec<(String, i64, String)> = self.with_timeout("top_creators", sqlx::query_as(&format!(
+            "SELECT address, markets, staked::TEXT FROM ( \
+                 SELECT c.address, COUNT(*) AS markets, COALESCE(SUM(v.volume), 0) AS staked \
+                 FROM chain_events c \
+                 LEFT JOIN (SELECT market_id, SUM(amount) AS volume FROM chain_events \
+                            WHERE network = $1 AND kind = 'bet_place' AND market_id IS NOT NULL \
+                            GROUP BY market_id) v ON v.market_id = c.market_id \
+                 WHERE c.network = $1 AND c.kind = 'mkt_creat' \
+                   AND c.address IS NOT NULL AND c.market_id IS NOT NULL \
+                   AND NOT EXISTS (SELECT 1 FROM user_notification_settings s \
+                                   WHERE s.address = c.address AND s.hide_creator_profile) \
+                 GROUP BY c.address \
+             ) t \
+             ORDER BY {order}, address \
+             LIMIT $2",
+        ))
+        .bind(network)
+        .bind(limit)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(rows)
+    }
+
+    // ── Market id mappings ────────────────────────────────────────────────────
+
+    /// The chain id public market `internal_id` stands for on `network`,
+    /// under `contract_id`.
+    pub async fn market_id_mapping_get(
+        &self,
+        network: &str,
+        contract_id: &str,
+        internal_id: i64,
+    ) -> anyhow::Result<Option<i64>> {
+        let chain_market_id = self.with_timeout("market_id_mapping_get", sqlx::query_scalar(
+            "SELECT chain_market_id FROM market_id_mappings \
+             WHERE network = $1 AND contract_id = $2 AND internal_id = $3",
+        )
+        .bind(network)
+        .bind(contract_id)
+        .bind(internal_id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(chain_market_id)
+    }
+
+    /// [`Self::market_id_mapping_get`] for many public ids at once, as
+    /// `(internal_id, chain_market_id)` pairs; unmapped ids are left out.
+    pub async fn market_id_mappings_get_many(
+        &self,
+        network: &str,
+        contract_id: &str,
+        internal_ids: &[i64],
+    ) -> anyhow::Result<Vec<(i64, i64)>> {
+        let rows = self.with_timeout("market_id_mappings_get_many", sqlx::query_as(
+            "SELECT internal_id, chain_market_id FROM market_id_mappings \
+             WHERE network = $1 AND contract_id = $2 AND internal_id = ANY($3)",
+        )
+        .bind(network)
+        .bind(contract_id)
+        .bind(internal_ids)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(rows)
+    }
+
+    /// Bind public market `internal_id` to `chain_market_id` on `network`,
+    /// replacing its binding there, if any. A chain market already bound to
+    /// another public id fails with [`DbError::ConstraintViolation`].
+    pub async fn market_id_mapping_bind(
+        &self,
+        internal_id: i64,
+        network: &str,
+        chain_market_id: i64,
+        contract_id: &str,
+    ) -> anyhow::Result<MarketIdMapping> {
+        let row = self.with_timeout("market_id_mapping_bind", sqlx::query(
+            "INSERT INTO market_id_mappings (internal_id, network, chain_market_id, contract_id) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (internal_id, network) DO UPDATE \
+                SET chain_market_id = EXCLUDED.chain_market_id, \
+                    contract_id = EXCLUDED.contract_id, \
+                    created_at = NOW() \
+             RETURNING *",
+        )
+        .bind(internal_id)
+        .bind(network)
+        .bind(chain_market_id)
+        .bind(contract_id)
+        .fetch_one(&self.pool)).await.map_err(unique_violation)?;
+        market_id_mapping_from_row(&row)
+    }
+
+    /// Remove public market `internal_id`'s binding on `network`. Returns
+    /// whether one existed.
+    pub async fn market_id_mapping_unbind(&self, internal_id: i64, network: &str) -> anyhow::Result<bool> {
+        let deleted = self.with_timeout("market_id_mapping_unbind", sqlx::query(
+            "DELETE FROM market_id_mappings WHERE internal_id = $1 AND network = $2",
+        )
+        .bind(internal_id)
+        .bind(network)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    /// Map market `market_id` on `network` to itself under `contract_id`,
+    /// unless the public id or the chain market is already bound. Returns
+    /// whether the mapping was added.
+    pub async fn market_id_mapping_identity(
+        &self,
+        network: &str,
+        contract_id: &str,
+        market_id: i64,
+    ) -> anyhow::Result<bool> {
+        let inserted = self.with_timeout("market_id_mapping_identity", sqlx::query(
+            "INSERT INTO market_id_mappings (internal_id, network, chain_market_id, contract_id) \
+             VALUES ($1, $2, $1, $3) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(market_id)
+        .bind(network)
+        .bind(contract_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(inserted.rows_affected() > 0)
+    }
+
+    /// Map every market with an indexed `mkt_creat` event on `network` to
+    /// itself under `contract_id`, when the network has no mappings yet. That
+    /// is once, after the table is created: events indexed from an earlier
+    /// contract are not mapped under a later one, and an unbound market is
+    /// not mapped again. Returns the mappings added.
+    pub async fn market_id_mappings_seed(&self, network: &str, contract_id: &str) -> anyhow::Result<u64> {
+        let inserted = self.with_timeout("market_id_mappings_seed", sqlx::query(
+            "INSERT INTO market_id_mappings (internal_id, network, chain_market_id, contract_id) \
+             SELECT DISTINCT market_id, network, market_id, $2 FROM chain_events \
+             WHERE network = $1 AND kind = 'mkt_creat' AND market_id IS NOT NULL \
+               AND NOT EXISTS ( \
+                   SELECT 1 FROM market_id_mappings WHERE network = $1 \
+               ) \
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(network)
+        .bind(contract_id)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(inserted.rows_affected())
+    }
+
+    // ── Retention ─────────────────────────────────────────────────────────────
+
+    /// Rows `policy` would change at `cutoff`; see [`crate::retention`].
+    pub async fn retention_count(&self, policy: &RetentionPolicy, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+        let count = self.with_timeout("retention_count", sqlx::query_scalar::<_, i64>(&policy.count_sql())
+            .bind(cutoff)
+            .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(count.max(0) as u64)
+    }
+
+    /// Delete or anonymize up to `limit` of the rows `policy` covers at
+    /// `cutoff`, in one statement. Returns the rows changed.
+    pub async fn retention_batch(&self, policy: &RetentionPolicy, cutoff: DateTime<Utc>, limit: u64) -> anyhow::Result<u64> {
+        let result = self.with_timeout("retention_batch", sqlx::query(&policy.batch_sql())
+            .bind(cutoff)
+            .bind(limit as i64)
+            .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+        Ok(result.rows_affected())
+    }
+
+    // ── API key management (issue #892) ───────────────────────────────────────
+
+    /// Insert a new API key into the database.
+    /// The caller is responsible for supplying the SHA-256 hex hash of the raw key.
+    pub async fn api_key_insert(
+        &self,
+        key_hash: &str,
+        label: &str,
+        monthly_quota: Option<i64>,
+    ) -> anyhow::Result<ApiKeyRecord> {
+        let row = self.with_timeout("api_key_insert", sqlx::query(
+            "INSERT INTO api_keys (key_hash, label, monthly_quota)
+             VALUES ($1, $2, $3)
+             RETURNING id, key_hash, label, created_at, expires_at, revoked_at, monthly_quota",
+        )
+        .bind(key_hash)
+        .bind(label)
+        .bind(monthly_quota)
+        .fetch_one(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        api_key_record_from_row(&row)
+    }
+
+    /// Mark an existing key as expiring at the given timestamp (rotation overlap window).
+    /// Returns `true` if a row was updated, `false` if no key with that hash was found.
+    pub async fn api_key_set_expires(
+        &self,
+        key_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let result = self.with_timeout("api_key_set_expires", sqlx::query(
+            "UPDATE api_keys SET expires_at = $1 WHERE key_hash = $2",
+        )
+        .bind(expires_at)
+        .bind(key_hash)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Validate a raw API key: compute its SHA-256 hash, then return the record
+    /// if it exists, is not revoked, and is not expired.
+    pub async fn api_key_validate(&self, key_hash: &str) -> anyhow::Result<Option<ApiKeyRecord>> {
+        let row = self.with_timeout("api_key_validate", sqlx::query(
+            "SELECT id, key_hash, label, created_at, expires_at, revoked_at, monthly_quota
+             FROM api_keys
+             WHERE key_hash = $1
+               AND revoked_at IS NULL
+               AND (expires_at IS NULL OR expires_at > NOW())",
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.as_ref().map(api_key_record_from_row).transpose()
+    }
+
+    /// Fetch a key by id, including revoked and expired ones.
+    pub async fn api_key_get(&self, id: uuid::Uuid) -> anyhow::Result<Option<ApiKeyRecord>> {
+        let row = self.with_timeout("api_key_get", sqlx::query(
+            "SELECT id, key_hash, label, created_at, expires_at, revoked_at, monthly_quota
+             FROM api_keys
+             WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        row.as_ref().map(api_key_record_from_row).transpose()
+    }
+
+    /// Hard-delete all keys where `expires_at <= NOW()` and `expires_at IS NOT NULL`.
+    /// Returns the number of rows deleted.
+    pub async fn api_key_delete_expired(&self) -> anyhow::Result<u64> {
+        let result = self.with_timeout("api_key_delete_expired", sqlx::query(
+            "DELETE FROM api_keys
+             WHERE expires_at IS NOT NULL AND expires_at <= NOW()",
+        )
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// List all active (non-revoked, non-expired) API keys.
+    pub async fn api_key_list_active(&self) -> anyhow::Result<Vec<ApiKeyRecord>> {
+        let rows = self.with_timeout("api_key_list_active", sqlx::query(
+            "SELECT id, key_hash, label, created_at, expires_at, revoked_at, monthly_quota
+             FROM api_keys
+             WHERE revoked_at IS NULL
+               AND (expires_at IS NULL OR expires_at > NOW())
+             ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter().map(api_key_record_from_row).collect()
+    }
+
+    /// Write daily request totals counted in Redis. Each entry is the running
+    /// total for that key and day, so an existing row is overwritten (never
+    /// lowered) rather than added to, and re-flushing the same counters is a
+    /// no-op. Entries for keys that have since been deleted are skipped.
+    /// Returns the number of rows written.
+    pub async fn api_key_usage_upsert(
+        &self,
+        entries: &[(uuid::Uuid, NaiveDate, i64)],
+    ) -> anyhow::Result<u64> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+        let ids: Vec<uuid::Uuid> = entries.iter().map(|(id, _, _)| *id).collect();
+        let days: Vec<NaiveDate> = entries.iter().map(|(_, day, _)| *day).collect();
+        let requests: Vec<i64> = entries.iter().map(|(_, _, n)| *n).collect();
+
+        let result = self.with_timeout("api_key_usage_upsert", sqlx::query(
+            "INSERT INTO api_key_usage (key_id, day, requests)
+             SELECT u.key_id, u.day, u.requests
+             FROM UNNEST($1::UUID[], $2::DATE[], $3::BIGINT[]) AS u(key_id, day, requests)
+             JOIN api_keys k ON k.id = u.key_id
+             ON CONFLICT (key_id, day) DO UPDATE
+                SET requests = GREATEST(api_key_usage.requests, EXCLUDED.requests),
+                    updated_at = NOW()",
+        )
+        .bind(&ids)
+        .bind(&days)
+        .bind(&requests)
+        .execute(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Flushed daily request totals for one key from `from` onwards, oldest
+    /// first. Days without requests have no row.
+    pub async fn api_key_usage_series(
+        &self,
+        key_id: uuid::Uuid,
+        from: NaiveDate,
+    ) -> anyhow::Result<Vec<(NaiveDate, i64)>> {
+        let rows = self.with_timeout("api_key_usage_series", sqlx::query(
+            "SELECT day, requests
+             FROM api_key_usage
+             WHERE key_id = $1 AND day >= $2
+             ORDER BY day",
+        )
+        .bind(key_id)
+        .bind(from)
+        .fetch_all(&self.pool)).await.map_err(anyhow::Error::from)?;
+
+        rows.iter()
+            .map(|row| Ok((row.try_get("day")?, row.try_get("requests")?)))
+            .collect()
     }
 
     /// Compute the SHA-256 hex digest of a raw API key string.
@@ -1057,6 +5207,309 @@ impl Database {
     }
 }
 
+fn api_key_record_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<ApiKeyRecord> {
+    Ok(ApiKeyRecord {
+        id: row.try_get("id")?,
+        key_hash: row.try_get("key_hash")?,
+        label: row.try_get("label")?,
+        created_at: row.try_get("created_at")?,
+        expires_at: row.try_get("expires_at")?,
+        revoked_at: row.try_get("revoked_at")?,
+        monthly_quota: row.try_get("monthly_quota")?,
+    })
+}
+
+fn oracle_submission_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<OracleSubmission> {
+    let status: String = row.try_get("status")?;
+    Ok(OracleSubmission {
+        id: row.try_get("id")?,
+        market_id: row.try_get("market_id")?,
+        oracle_id: row.try_get("oracle_id")?,
+        outcome: row.try_get("outcome")?,
+        source: row.try_get("source")?,
+        confidence_bps: row.try_get("confidence_bps")?,
+        status: OracleSubmissionStatus::parse(&status)
+            .with_context(|| format!("oracle_submissions: unknown status {status:?}"))?,
+        tx_hash: row.try_get("tx_hash")?,
+        error: row.try_get("error")?,
+        resolution_tx_hash: row.try_get("resolution_tx_hash")?,
+        resolution_error: row.try_get("resolution_error")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+/// The `WHERE` clause shared by [`Database::activity_page`] and
+/// [`Database::activity_counts`].
+fn push_activity_filter(qb: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>, network: &str, filter: &ActivityFilter) {
+    qb.push(" WHERE e.network = ")
+        .push_bind(network.to_string())
+        .push(" AND e.kind = ANY(")
+        .push_bind(filter.kinds())
+        .push(")");
+    if let Some(market_id) = filter.market_id {
+        qb.push(" AND e.market_id = ").push_bind(market_id);
+    }
+    if let Some(since_id) = &filter.since_id {
+        qb.push(" AND e.ledger >= COALESCE((SELECT ledger FROM chain_events WHERE id = ")
+            .push_bind(since_id.clone())
+            .push("), 0) AND e.id > ")
+            .push_bind(since_id.clone());
+    }
+}
+
+fn backfill_job_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<BackfillJob> {
+    let status: String = row.try_get("status")?;
+    let status = BackfillStatus::parse(&status)
+        .with_context(|| format!("backfill_jobs: unknown status {status:?}"))?;
+    let from_ledger: i64 = row.try_get("from_ledger")?;
+    let to_ledger: i64 = row.try_get("to_ledger")?;
+    let last_ledger: Option<i64> = row.try_get("last_ledger")?;
+    Ok(BackfillJob {
+        id: row.try_get("id")?,
+        network: row.try_get("network")?,
+        from_ledger,
+        to_ledger,
+        status,
+        page_cursor: row.try_get("page_cursor")?,
+        last_ledger,
+        pages: row.try_get("pages")?,
+        events_scanned: row.try_get("events_scanned")?,
+        events_inserted: row.try_get("events_inserted")?,
+        progress: backfill::progress(from_ledger, to_ledger, last_ledger, status),
+        last_error: row.try_get("last_error")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        completed_at: row.try_get("completed_at")?,
+    })
+}
+
+fn category_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<Category> {
+    Ok(Category {
+        slug: row.try_get("slug")?,
+        name: row.try_get("name")?,
+        is_featured: row.try_get("is_featured")?,
+        active_market_count: row.try_get("active_market_count")?,
+        total_volume: row.try_get("total_volume")?,
+    })
+}
+
+fn risk_alert_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<RiskAlert> {
+    let kind: String = row.try_get("kind")?;
+    Ok(RiskAlert {
+        id: row.try_get("id")?,
+        network: row.try_get("network")?,
+        market_id: row.try_get("market_id")?,
+        kind: RiskAlertKind::parse(&kind)
+            .with_context(|| format!("unknown risk alert kind {kind:?}"))?,
+        value: row.try_get("value")?,
+        threshold: row.try_get("threshold")?,
+        total_staked: row.try_get("total_staked")?,
+        opened_at: row.try_get("opened_at")?,
+        updated_at: row.try_get("updated_at")?,
+        notified_at: row.try_get("notified_at")?,
+    })
+}
+
+fn blocked_domain_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<BlockedDomain> {
+    Ok(BlockedDomain {
+        id: row.try_get("id")?,
+        pattern: row.try_get("pattern")?,
+        note: row.try_get("note")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn market_id_mapping_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<MarketIdMapping> {
+    Ok(MarketIdMapping {
+        internal_id: row.try_get("internal_id")?,
+        network: row.try_get("network")?,
+        chain_market_id: row.try_get("chain_market_id")?,
+        contract_id: row.try_get("contract_id")?,
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn content_entry_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<ContentEntry> {
+    Ok(ContentEntry {
+        id: row.try_get("id")?,
+        slug: row.try_get("slug")?,
+        title: row.try_get("title")?,
+        category: row.try_get("category")?,
+        body: row.try_get("body")?,
+        is_published: row.try_get("is_published")?,
+        published_at: row.try_get("published_at")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+/// Build a [`MarketDetail`] from a row carrying the `markets` columns that
+/// [`Database::market_detail`] selects.
+fn market_detail_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<MarketDetail> {
+    Ok(MarketDetail {
+        id: row.try_get::<i64, _>("id")?,
+        title: row.try_get::<String, _>("title")?,
+        description: row.try_get::<Option<String>, _>("description")?,
+        category: row.try_get::<Option<String>, _>("category")?,
+        creator: row.try_get::<Option<String>, _>("creator")?,
+        status: row.try_get::<String, _>("status")?,
+        outcome_options: row.try_get::<Vec<String>, _>("outcome_options")?,
+        outcome_odds: row.try_get::<Vec<f64>, _>("outcome_odds")?,
+        outcome_index: row.try_get::<Option<i32>, _>("outcome_index")?,
+        volume: row.try_get::<f64, _>("total_volume")?,
+        participant_count: row.try_get::<i64, _>("participant_count")?,
+        ends_at: row.try_get::<DateTime<Utc>, _>("ends_at")?,
+        created_at: row.try_get::<DateTime<Utc>, _>("created_at")?,
+        resolved_at: row.try_get::<Option<DateTime<Utc>>, _>("resolved_at")?,
+        token: row.try_get::<Option<String>, _>("token")?,
+        image_url: row.try_get::<Option<String>, _>("image_url")?,
+    })
+}
+
+/// [`Database::with_timeout`] reports every query error as
+/// [`DbError::Other`]; surface unique violations as
+/// [`DbError::ConstraintViolation`] so callers can answer 409.
+fn unique_violation(err: DbError) -> anyhow::Error {
+    if let DbError::Other(e) = &err {
+        let unique = e
+            .downcast_ref::<sqlx::Error>()
+            .and_then(|e| e.as_database_error())
+            .is_some_and(|e| e.is_unique_violation());
+        if unique {
+            return DbError::ConstraintViolation(e.to_string()).into();
+        }
+    }
+    err.into()
+}
+
+fn contact_submission_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<ContactSubmission> {
+    let status: String = row.try_get("status")?;
+    Ok(ContactSubmission {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        email: row.try_get("email")?,
+        subject: row.try_get("subject")?,
+        message: row.try_get("message")?,
+        status: ContactStatus::parse(&status)
+            .with_context(|| format!("contact_form_submissions: unknown status {status:?}"))?,
+        submitted_at: row.try_get("submitted_at")?,
+        answered_at: row.try_get("answered_at")?,
+        resolved_at: row.try_get("resolved_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn waitlist_entry_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<WaitlistEntry> {
+    let status: String = row.try_get("status")?;
+    Ok(WaitlistEntry {
+        id: row.try_get("id")?,
+        email: row.try_get("email")?,
+        status: WaitlistStatus::parse(&status)
+            .with_context(|| format!("waitlist_entries: unknown status {status:?}"))?,
+        referral_code: row.try_get("referral_code")?,
+        referral_count: row.try_get("referral_count")?,
+        position: row.try_get("position")?,
+        joined_at: row.try_get("joined_at")?,
+        invited_at: row.try_get("invited_at")?,
+    })
+}
+
+fn email_job_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<crate::email::EmailJob> {
+    Ok(crate::email::EmailJob {
+        id: row.try_get("id")?,
+        job_type: row.try_get("job_type")?,
+        recipient_email: row.try_get("recipient_email")?,
+        template_name: row.try_get("template_name")?,
+        template_data: row.try_get("template_data")?,
+        status: row.try_get("status")?,
+        priority: row.try_get("priority")?,
+        attempts: row.try_get("attempts")?,
+        max_attempts: row.try_get("max_attempts")?,
+        scheduled_at: row.try_get("scheduled_at")?,
+        started_at: row.try_get("started_at")?,
+        completed_at: row.try_get("completed_at")?,
+        failed_at: row.try_get("failed_at")?,
+        error_message: row.try_get("error_message")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+        campaign_id: row.try_get("campaign_id")?,
+    })
+}
+
+fn stats_snapshot_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<StatsSnapshot> {
+    let ledger: i64 = row.try_get("ledger")?;
+    let token_volumes: String = row.try_get("token_volumes")?;
+    Ok(StatsSnapshot {
+        network: row.try_get("network")?,
+        ledger: u32::try_from(ledger).context("snapshot ledger out of range")?,
+        stats: DerivedStats {
+            total_markets: row.try_get::<i64, _>("total_markets")? as u64,
+            active_markets: row.try_get::<i64, _>("active_markets")? as u64,
+            resolved_markets: row.try_get::<i64, _>("resolved_markets")? as u64,
+            total_volume: row.try_get("total_volume")?,
+            token_volumes: serde_json::from_str(&token_volumes).context("malformed snapshot token_volumes")?,
+        },
+        created_at: row.try_get("created_at")?,
+    })
+}
+
+fn campaign_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<Campaign> {
+    let status: String = row.try_get("status")?;
+    Ok(Campaign {
+        id: row.try_get("id")?,
+        name: row.try_get("name")?,
+        template_name: row.try_get("template_name")?,
+        payload: row.try_get("payload")?,
+        status: CampaignStatus::parse(&status)
+            .with_context(|| format!("campaigns: unknown status {status:?}"))?,
+        total_recipients: row.try_get("total_recipients")?,
+        enqueued: row.try_get("enqueued_count")?,
+        sent: row.try_get("sent_count")?,
+        failed: row.try_get("failed_count")?,
+        skipped: row.try_get("skipped_count")?,
+        created_at: row.try_get("created_at")?,
+        launched_at: row.try_get("launched_at")?,
+        completed_at: row.try_get("completed_at")?,
+        cancelled_at: row.try_get("cancelled_at")?,
+        cursor: row.try_get("cursor")?,
+    })
+}
+
+fn market_watch_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<MarketWatch> {
+    let labels: Vec<String> = row.try_get("notify_on")?;
+    let notify_on = labels
+        .iter()
+        .map(|label| {
+            WatchTrigger::parse(label)
+                .with_context(|| format!("market_watches: unknown trigger {label:?}"))
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(MarketWatch {
+        id: row.try_get("id")?,
+        network: row.try_get("network")?,
+        market_id: row.try_get("market_id")?,
+        email: row.try_get("email")?,
+        address: row.try_get("address")?,
+        notify_on,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+fn notification_settings_from_row(row: &sqlx::postgres::PgRow) -> anyhow::Result<NotificationSettings> {
+    Ok(NotificationSettings {
+        address: row.try_get("address")?,
+        email: row.try_get("email")?,
+        email_verified: row.try_get::<Option<DateTime<Utc>>, _>("email_verified_at")?.is_some(),
+        market_resolved: row.try_get("market_resolved")?,
+        claim_available: row.try_get("claim_available")?,
+        dispute_filed: row.try_get("dispute_filed")?,
+        hide_creator_profile: row.try_get("hide_creator_profile")?,
+        updated_at: Some(row.try_get("updated_at")?),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1091,4 +5544,44 @@ mod tests {
         let e = DbError::from(sqlx::Error::RowNotFound);
         assert!(matches!(e, DbError::Other(_)));
     }
+
+    fn market_row() -> MarketListRow {
+        MarketListRow {
+            id: 42,
+            title: "Will it rain?".to_string(),
+            status: "active".to_string(),
+            outcome_index: None,
+            volume: 1234.5,
+            participant_count: 17,
+            ends_at: DateTime::from_timestamp_micros(1_800_000_000_123_456).unwrap(),
+            created_at: DateTime::from_timestamp_micros(1_700_000_000_000_001).unwrap(),
+        }
+    }
+
+    #[test]
+    fn market_cursor_round_trips_for_every_sort() {
+        let row = market_row();
+        for sort in [
+            MarketSort::Volume,
+            MarketSort::EndsAt,
+            MarketSort::CreatedAt,
+            MarketSort::ParticipantCount,
+        ] {
+            let cursor = MarketCursor::after(&row, sort);
+            let decoded = MarketCursor::decode(&cursor.encode(), sort);
+            assert_eq!(decoded, Some(cursor), "round trip failed for {}", sort.label());
+        }
+    }
+
+    #[test]
+    fn market_cursor_rejects_other_sort() {
+        let encoded = MarketCursor::after(&market_row(), MarketSort::Volume).encode();
+        assert!(MarketCursor::decode(&encoded, MarketSort::EndsAt).is_none());
+    }
+
+    #[test]
+    fn market_cursor_rejects_garbage() {
+        assert!(MarketCursor::decode("not-a-cursor", MarketSort::Volume).is_none());
+        assert!(MarketCursor::decode("", MarketSort::Volume).is_none());
+    }
 }
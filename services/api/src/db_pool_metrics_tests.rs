@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod db_pool_metrics_tests {
+    use std::time::Duration;
+
+    use crate::{
+        cache::RedisCache,
+        config::Config,
+        db::{Database, DbError},
+        metrics::Metrics,
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// A database whose metrics the test can read back, with the pool config
+    /// adjusted by `tweak`.
+    async fn build_db(tweak: impl FnOnce(&mut crate::config::DbPoolConfig)) -> (Database, Metrics) {
+        let config = Config::from_env();
+        let mut pool_config = config.db_pool.clone();
+        tweak(&mut pool_config);
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics.clone(), &pool_config)
+            .await
+            .expect("db");
+        (db, metrics)
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A query over the threshold is counted under its statement name; a fast
+    /// one is not.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_slow_query_is_counted_by_name() {
+        let (db, metrics) = build_db(|c| c.slow_query_threshold = Duration::from_millis(50)).await;
+        let pool = db.pool();
+
+        db.with_timeout(
+            "test_sleep",
+            sqlx::query("SELECT pg_sleep(0.2)").execute(&pool),
+        )
+        .await
+        .unwrap();
+        db.with_timeout("test_select_one", sqlx::query("SELECT 1").execute(&pool))
+            .await
+            .unwrap();
+
+        assert_eq!(metrics.slow_query_count("test_sleep"), 1);
+        assert_eq!(metrics.slow_query_count("test_select_one"), 0);
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains("slow_queries_total{query=\"test_sleep\"} 1"));
+    }
+
+    /// A query that fails slowly still counts as slow.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_slow_failing_query_is_counted() {
+        let (db, metrics) = build_db(|c| c.slow_query_threshold = Duration::from_millis(50)).await;
+        let pool = db.pool();
+
+        let result = db
+            .with_timeout(
+                "test_sleep_then_fail",
+                sqlx::query("SELECT pg_sleep(0.2), 1 / 0").execute(&pool),
+            )
+            .await;
+
+        assert!(matches!(result, Err(DbError::Other(_))));
+        assert_eq!(metrics.slow_query_count("test_sleep_then_fail"), 1);
+    }
+
+    /// With the only connection held, a query waits out the acquire timeout
+    /// and fails as `PoolExhausted`, which the sampler also reports.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_acquire_timeout_is_pool_exhausted() {
+        let (db, metrics) = build_db(|c| {
+            c.min_connections = 0;
+            c.max_connections = 1;
+            c.acquire_timeout = Duration::from_millis(200);
+        })
+        .await;
+        let pool = db.pool();
+        let held = pool.acquire().await.unwrap();
+
+        let result = db
+            .with_timeout("test_select_one", sqlx::query("SELECT 1").execute(&pool))
+            .await;
+        assert!(matches!(result, Err(DbError::PoolExhausted)));
+
+        db.sample_pool().await;
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains("db_pool_size{pool=\"primary\"} 1"));
+        assert!(rendered.contains("db_pool_connections_active{pool=\"primary\"} 1"));
+        assert!(rendered.contains("db_pool_exhaustion_total{pool=\"primary\"} 2"));
+
+        drop(held);
+    }
+}
@@ -0,0 +1,347 @@
+//! Weekly featured-markets digest.
+//!
+//! When `DIGEST_SCHEDULE` is set, the supervised [`run`] loop emails every
+//! confirmed subscriber who is not suppressed and has not turned off
+//! [`CATEGORY`] mail once a week: the top [`TOP_MARKETS`] active markets by
+//! volume (from [`Database::featured_markets_cached`]) with their current
+//! odds, and the biggest markets resolved in the past seven days.
+//!
+//! Weeks are keyed by the Monday they start on. [`send_week`] records the
+//! week in `digest_sends` in the same transaction that enqueues its jobs
+//! (see [`Database::digest_enqueue_week`]), so neither a restart nor a second
+//! instance sends a week twice. A slot missed while the service was down is
+//! caught up on the next start, as long as that week has not ended.
+//!
+//! `GET /api/v1/admin/digest/preview` renders the current digest without
+//! sending anything.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::{Config, DigestSchedule},
+    db::Database,
+    newsletter::{self, EmailCategory},
+    AppState,
+};
+
+/// Email template the digest is rendered with.
+pub const TEMPLATE: &str = "weekly_digest";
+
+/// Preference category a recipient must not have turned off.
+pub const CATEGORY: EmailCategory = EmailCategory::Digest;
+
+/// Queue priority of digest jobs: below transactional mail, like campaigns.
+pub const PRIORITY: i32 = -1;
+
+/// Featured markets listed per digest.
+pub const TOP_MARKETS: i64 = 5;
+
+/// Most resolved markets listed per digest.
+pub const MAX_RESOLUTIONS: i64 = 3;
+
+/// How far back a resolution is still news.
+const RESOLUTION_WINDOW_DAYS: i64 = 7;
+
+/// Wait before retrying a week whose send failed.
+const RETRY_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+const WORKER_NAME: &str = "digest_sender";
+
+/// One featured market as listed in the digest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestMarket {
+    pub title: String,
+    /// e.g. `Yes 62% · No 38%`; empty when the market has no odds yet.
+    pub odds: String,
+    pub url: String,
+}
+
+/// One recently resolved market as listed in the digest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DigestResolution {
+    pub title: String,
+    /// Label of the winning outcome; empty when it is not recorded.
+    pub outcome: String,
+    pub url: String,
+}
+
+/// A week's digest content, shared by every recipient.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Digest {
+    pub week_start: NaiveDate,
+    pub markets: Vec<DigestMarket>,
+    pub resolutions: Vec<DigestResolution>,
+}
+
+impl Digest {
+    /// Whether there is nothing to tell subscribers this week.
+    pub fn is_empty(&self) -> bool {
+        self.markets.is_empty() && self.resolutions.is_empty()
+    }
+
+    /// Template data for one recipient.
+    pub fn template_data(&self, email: &str, preferences_url: &str) -> Value {
+        serde_json::json!({
+            "week_label": self.week_start.format("%-d %B %Y").to_string(),
+            "markets": self.markets,
+            "resolutions": self.resolutions,
+            "email": email,
+            "preferences_url": preferences_url,
+        })
+    }
+}
+
+/// What one [`send_week`] call did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// The week was recorded and this many jobs enqueued. Zero when the
+    /// digest was empty or nobody is subscribed.
+    Enqueued(usize),
+    /// The week was already recorded; nothing was written.
+    AlreadySent,
+}
+
+/// The Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(u64::from(date.weekday().num_days_from_monday()))
+}
+
+/// When `schedule` falls in the week starting `week_start`.
+fn slot(schedule: DigestSchedule, week_start: NaiveDate) -> DateTime<Utc> {
+    (week_start + Days::new(u64::from(schedule.weekday.num_days_from_monday())))
+        .and_time(schedule.time)
+        .and_utc()
+}
+
+/// The week whose digest is due at `now`: the current week once its slot
+/// has passed, otherwise none.
+pub fn due_week(schedule: DigestSchedule, now: DateTime<Utc>) -> Option<NaiveDate> {
+    let week = week_start(now.date_naive());
+    (now >= slot(schedule, week)).then_some(week)
+}
+
+/// Time from `now` until the next slot.
+pub fn next_run_delay(schedule: DigestSchedule, now: DateTime<Utc>) -> Duration {
+    let week = week_start(now.date_naive());
+    let this_week = slot(schedule, week);
+    let next = if this_week > now {
+        this_week
+    } else {
+        slot(schedule, week + Days::new(7))
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// `Yes 62% · No 38%` from index-aligned labels and `[0, 1]` odds.
+pub fn odds_label(options: &[String], odds: &[f64]) -> String {
+    options
+        .iter()
+        .zip(odds)
+        .map(|(option, p)| format!("{option} {:.0}%", p * 100.0))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+fn market_url(base_url: &str, market_id: i64) -> String {
+    format!("{}/markets/{market_id}", base_url.trim_end_matches('/'))
+}
+
+/// Build the digest for `week_start`: the featured markets now, and the
+/// markets resolved in the seven days before `now`.
+pub async fn assemble(
+    db: &Database,
+    base_url: &str,
+    week_start: NaiveDate,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Digest> {
+    let mut markets = Vec::new();
+    for market in db.featured_markets_cached(TOP_MARKETS).await? {
+        // Featured rows carry no odds; the detail row does.
+        let odds = db
+            .market_detail(market.id)
+            .await?
+            .map(|detail| odds_label(&detail.outcome_options, &detail.outcome_odds))
+            .unwrap_or_default();
+        markets.push(DigestMarket {
+            url: market_url(base_url, market.id),
+            title: market.title,
+            odds,
+        });
+    }
+
+    let since = now - chrono::Duration::days(RESOLUTION_WINDOW_DAYS);
+    let resolutions = db
+        .markets_resolved_since(since, MAX_RESOLUTIONS)
+        .await?
+        .into_iter()
+        .map(|market| DigestResolution {
+            outcome: market
+                .outcome_index
+                .and_then(|i| usize::try_from(i).ok())
+                .and_then(|i| market.outcome_options.get(i).cloned())
+                .unwrap_or_default(),
+            url: market_url(base_url, market.id),
+            title: market.title,
+        })
+        .collect();
+
+    Ok(Digest {
+        week_start,
+        markets,
+        resolutions,
+    })
+}
+
+/// Send the digest for `week_start` unless that week is already recorded.
+/// An empty digest is recorded without recipients, so the week is not
+/// retried.
+pub async fn send_week(
+    db: &Database,
+    config: &Config,
+    week_start: NaiveDate,
+    now: DateTime<Utc>,
+) -> anyhow::Result<SendOutcome> {
+    // Cheap check first; digest_enqueue_week is what guarantees it.
+    if db.digest_week_sent(week_start).await? {
+        return Ok(SendOutcome::AlreadySent);
+    }
+
+    let digest = assemble(db, &config.base_url, week_start, now).await?;
+    let jobs: Vec<(String, Value)> = if digest.is_empty() {
+        Vec::new()
+    } else {
+        let secret = config.unsubscribe_signing_secret.as_deref();
+        db.digest_recipients()
+            .await?
+            .into_iter()
+            .map(|(subscriber_id, email)| {
+                let preferences_url =
+                    newsletter::preferences_url(&config.base_url, subscriber_id, secret);
+                let data = digest.template_data(&email, &preferences_url);
+                (email, data)
+            })
+            .collect()
+    };
+
+    Ok(if db.digest_enqueue_week(&digest, &jobs).await? {
+        SendOutcome::Enqueued(jobs.len())
+    } else {
+        SendOutcome::AlreadySent
+    })
+}
+
+/// Send each week's digest at its `schedule` slot until `shutdown` fires.
+/// On startup the current week is sent at once if its slot has passed and
+/// it is not recorded; a failed send is retried every [`RETRY_INTERVAL`]
+/// until the next slot.
+pub async fn run(state: Arc<AppState>, schedule: DigestSchedule, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    loop {
+        let now = Utc::now();
+        let mut pause = next_run_delay(schedule, now);
+        if let Some(week) = due_week(schedule, now) {
+            match send_week(&state.db, &state.config, week, now).await {
+                Ok(SendOutcome::Enqueued(n)) => {
+                    tracing::info!(week = %week, enqueued = n, "[digest] week enqueued")
+                }
+                Ok(SendOutcome::AlreadySent) => {}
+                Err(e) => {
+                    tracing::warn!("[digest] send error: {e}");
+                    pause = pause.min(RETRY_INTERVAL);
+                }
+            }
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(pause) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveTime, TimeZone, Weekday};
+
+    fn monday_nine() -> DigestSchedule {
+        DigestSchedule {
+            weekday: Weekday::Mon,
+            time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        }
+    }
+
+    fn at(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        // October 2026: the 12th and 19th are Mondays.
+        Utc.with_ymd_and_hms(2026, 10, d, h, m, 0).unwrap()
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn weeks_start_on_monday() {
+        assert_eq!(week_start(day(12)), day(12));
+        assert_eq!(week_start(day(16)), day(12));
+        assert_eq!(week_start(day(18)), day(12));
+        assert_eq!(week_start(day(19)), day(19));
+    }
+
+    #[test]
+    fn a_week_is_due_once_its_slot_has_passed() {
+        let schedule = monday_nine();
+        assert_eq!(due_week(schedule, at(12, 8, 59)), None);
+        assert_eq!(due_week(schedule, at(12, 9, 0)), Some(day(12)));
+        // Catching up later in the week still targets that week.
+        assert_eq!(due_week(schedule, at(17, 23, 0)), Some(day(12)));
+
+        let friday = DigestSchedule { weekday: Weekday::Fri, ..schedule };
+        assert_eq!(due_week(friday, at(15, 12, 0)), None);
+        assert_eq!(due_week(friday, at(16, 9, 0)), Some(day(12)));
+    }
+
+    #[test]
+    fn next_run_is_the_next_slot() {
+        let schedule = monday_nine();
+        assert_eq!(
+            next_run_delay(schedule, at(12, 8, 0)),
+            Duration::from_secs(60 * 60)
+        );
+        assert_eq!(
+            next_run_delay(schedule, at(12, 9, 0)),
+            Duration::from_secs(7 * 24 * 60 * 60)
+        );
+        assert_eq!(
+            next_run_delay(schedule, at(18, 9, 0)),
+            Duration::from_secs(24 * 60 * 60)
+        );
+    }
+
+    #[test]
+    fn odds_are_labelled_as_whole_percentages() {
+        let options = vec!["Yes".to_string(), "No".to_string()];
+        assert_eq!(odds_label(&options, &[0.62, 0.38]), "Yes 62% · No 38%");
+        assert_eq!(odds_label(&options, &[]), "");
+    }
+
+    #[test]
+    fn template_data_carries_recipient_fields() {
+        let digest = Digest {
+            week_start: day(12),
+            markets: vec![],
+            resolutions: vec![],
+        };
+        assert!(digest.is_empty());
+        let data = digest.template_data("a@example.com", "https://example.com/p");
+        assert_eq!(data["week_label"], "12 October 2026");
+        assert_eq!(data["markets"], serde_json::json!([]));
+        assert_eq!(data["email"], "a@example.com");
+        assert_eq!(data["preferences_url"], "https://example.com/p");
+    }
+}
@@ -0,0 +1,196 @@
+#[cfg(test)]
+mod digest_tests {
+    use chrono::{NaiveDate, Utc};
+    use sqlx::Row;
+
+    use crate::db::NewsletterConfirmation;
+    use crate::digest::{self, SendOutcome};
+    use crate::email::types::SuppressionType;
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Subscribers and markets live in a reserved range so cleanup never
+    /// touches rows created by other tests or the seed data.
+    const PREFIX: &str = "digest-653";
+    const SEED_MARKET: i64 = 9631;
+
+    /// Weeks long past, so a real schedule never claims them first.
+    fn week(n: u64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2001, 1, 1).unwrap() + chrono::Days::new(7 * n)
+    }
+
+    fn email(n: usize) -> String {
+        format!("{PREFIX}-{n:02}@example.com")
+    }
+
+    async fn seed_subscriber(state: &crate::AppState, address: &str) {
+        let token = format!("{address}-token");
+        state
+            .db
+            .newsletter_upsert_pending(address, "test", &token)
+            .await
+            .unwrap();
+        let confirmed = state
+            .db
+            .newsletter_confirm_by_token(&token, state.config.newsletter_token_ttl_secs)
+            .await
+            .unwrap();
+        assert_eq!(confirmed, NewsletterConfirmation::Confirmed);
+    }
+
+    /// Two eligible recipients (1, 2), one with digest mail off (3) and one
+    /// suppressed (4), plus a large market resolved just now so the digest
+    /// is never empty.
+    async fn seed(state: &crate::AppState) {
+        for n in 1..=4 {
+            seed_subscriber(state, &email(n)).await;
+        }
+        state
+            .db
+            .newsletter_set_preferences(&email(3), &[(digest::CATEGORY, false)])
+            .await
+            .unwrap();
+        state
+            .db
+            .email_add_suppression(&email(4), SuppressionType::Manual.as_str(), None, None)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO markets \
+                 (id, title, status, total_volume, ends_at, created_at, outcome_options, \
+                  outcome_index, resolved_at) \
+             VALUES ($1, 'Digest resolution', 'resolved', 1e15, NOW() - INTERVAL '1 day', \
+                     NOW() - INTERVAL '2 days', ARRAY['Yes', 'No'], 1, NOW())",
+        )
+        .bind(SEED_MARKET)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// Recipients in the reserved range with a digest job.
+    async fn job_recipients(state: &crate::AppState) -> Vec<String> {
+        sqlx::query(
+            "SELECT recipient_email FROM email_jobs \
+             WHERE job_type = 'digest' AND recipient_email LIKE $1 \
+             ORDER BY recipient_email",
+        )
+        .bind(format!("{PREFIX}-%"))
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.get("recipient_email"))
+        .collect()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let weeks: Vec<NaiveDate> = (0..2).map(week).collect();
+        sqlx::query("DELETE FROM digest_sends WHERE week_start = ANY($1)")
+            .bind(&weeks)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        // Jobs for subscribers outside the reserved range, enqueued by these
+        // tests' weeks.
+        sqlx::query(
+            "DELETE FROM email_jobs \
+             WHERE job_type = 'digest' AND template_data->>'week_label' LIKE '% 2001'",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        let pattern = format!("{PREFIX}%");
+        for statement in [
+            "DELETE FROM email_jobs WHERE recipient_email LIKE $1",
+            "DELETE FROM email_suppressions WHERE email LIKE $1",
+            "DELETE FROM newsletter_preferences WHERE email LIKE $1",
+            "DELETE FROM newsletter_subscribers WHERE email LIKE $1",
+        ] {
+            sqlx::query(statement)
+                .bind(&pattern)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM markets WHERE id = $1")
+            .bind(SEED_MARKET)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A week is sent once: the second call, as after a restart, enqueues
+    /// nothing, and only eligible subscribers get a job.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_a_week_is_never_sent_twice() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed(&state).await;
+
+        let first = digest::send_week(&state.db, &state.config, week(0), Utc::now())
+            .await
+            .unwrap();
+        assert!(matches!(first, SendOutcome::Enqueued(n) if n >= 2), "{first:?}");
+        assert!(state.db.digest_week_sent(week(0)).await.unwrap());
+        assert_eq!(job_recipients(&state).await, vec![email(1), email(2)]);
+
+        let again = digest::send_week(&state.db, &state.config, week(0), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(again, SendOutcome::AlreadySent);
+        assert_eq!(job_recipients(&state).await, vec![email(1), email(2)]);
+
+        let job = sqlx::query(
+            "SELECT template_name, template_data FROM email_jobs \
+             WHERE job_type = 'digest' AND recipient_email = $1",
+        )
+        .bind(email(1))
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(job.get::<String, _>("template_name"), digest::TEMPLATE);
+        let data: serde_json::Value = job.get("template_data");
+        assert_eq!(data["week_label"], "1 January 2001");
+        assert!(data["resolutions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["title"] == "Digest resolution" && r["outcome"] == "No"));
+
+        cleanup(&state).await;
+    }
+
+    /// Two instances sending the same week at once enqueue it exactly once.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_concurrent_senders_enqueue_a_week_once() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed(&state).await;
+
+        let now = Utc::now();
+        let (a, b) = tokio::join!(
+            digest::send_week(&state.db, &state.config, week(1), now),
+            digest::send_week(&state.db, &state.config, week(1), now),
+        );
+        let outcomes = [a.unwrap(), b.unwrap()];
+        assert_eq!(
+            outcomes.iter().filter(|o| **o == SendOutcome::AlreadySent).count(),
+            1,
+            "{outcomes:?}"
+        );
+        assert_eq!(job_recipients(&state).await, vec![email(1), email(2)]);
+
+        cleanup(&state).await;
+    }
+
+}
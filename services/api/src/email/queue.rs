@@ -1,39 +1,70 @@
-use anyhow::{Context, Result};
-use redis::AsyncCommands;
+//! Durable email queue backed by the `email_jobs` table.
+//!
+//! [`EmailQueue::enqueue`] inserts a `pending` row; the worker started by
+//! [`EmailQueue::start_worker`] claims due rows with `FOR UPDATE SKIP LOCKED`
+//! (see [`Database::email_claim_due_job`]), so any number of workers, in one
+//! process or many, can drain the same table without sending a job twice. A
+//! failed send is rescheduled after [`retry_backoff`]; once `max_attempts` is
+//! reached the row moves to `dead_letter` until an admin retries it.
+
+use anyhow::Result;
+use rand::Rng as _;
 use serde_json::Value;
 use std::time::Duration;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use crate::cache::RedisCache;
+use crate::campaign::{self, CampaignOutcome};
 use crate::db::Database;
+use crate::digest;
 use crate::email::service::idempotency_key;
-use crate::email::types::{EmailJobStatus, EmailJobType};
+use crate::email::types::{EmailJob, EmailJobStatus, EmailJobType};
+use crate::log_redact::mask_email;
 use crate::metrics::Metrics;
+use crate::newsletter::EmailCategory;
+use crate::resolution_reminder;
 use crate::shutdown::ShutdownCoordinator;
 
-const EMAIL_QUEUE_KEY: &str = "email:queue";
-const EMAIL_PROCESSING_KEY: &str = "email:processing";
-const EMAIL_RETRY_KEY: &str = "email:retry";
-const EMAIL_DEAD_LETTER_KEY: &str = "email:dead_letter";
+/// How long an idle worker waits before polling for due jobs again.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-/// Default stale job threshold (seconds). If not overridden via config, jobs stuck
-/// in processing for longer than this are considered orphaned and safe to re-queue.
-const DEFAULT_STALE_JOB_THRESHOLD_SECS: u64 = 3600;  // 1 hour
+/// Fraction of the backoff added or removed at random so jobs that failed
+/// together (e.g. during a provider outage) do not all retry in the same tick.
+const RETRY_JITTER_FACTOR: f64 = 0.2;
+
+/// Exponent cap for [`retry_backoff`]: 2^10 minutes is about 17 hours.
+const MAX_BACKOFF_EXPONENT: i32 = 10;
+
+/// Delay before retrying a job that has failed `attempts` times:
+/// 2^attempts minutes, scaled by `1 + RETRY_JITTER_FACTOR * jitter` where
+/// `jitter` is in `[-1, 1]`.
+pub fn retry_backoff(attempts: i32, jitter: f64) -> Duration {
+    let base_secs = 60.0 * 2_f64.powi(attempts.clamp(0, MAX_BACKOFF_EXPONENT));
+    let factor = 1.0 + RETRY_JITTER_FACTOR * jitter.clamp(-1.0, 1.0);
+    Duration::from_secs_f64(base_secs * factor)
+}
+
+/// What happened to a job whose send failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureOutcome {
+    /// Back in the queue, due at the given time.
+    Rescheduled(chrono::DateTime<chrono::Utc>),
+    /// `max_attempts` reached; the job is parked in `dead_letter`.
+    DeadLettered,
+}
 
 #[derive(Clone)]
 pub struct EmailQueue {
-    cache: RedisCache,
     db: Database,
 }
 
 impl EmailQueue {
-    pub fn new(cache: RedisCache, db: Database) -> Self {
-        Self { cache, db }
+    pub fn new(db: Database) -> Self {
+        Self { db }
     }
 
-    /// Enqueue a new email job
+    /// Enqueue a new email job. Higher `priority` is sent first.
     pub async fn enqueue(
         &self,
         job_type: EmailJobType,
@@ -53,53 +84,45 @@ impl EmailQueue {
             )
             .await?;
 
-        // Add to Redis queue for processing
-        let score = if priority > 0 {
-            // Higher priority = lower score (processed first)
-            -(priority as f64)
-        } else {
-            chrono::Utc::now().timestamp() as f64
-        };
-
-        let mut conn = self.cache.get_connection().await?;
-        let _: () = conn.zadd(EMAIL_QUEUE_KEY, job_id.to_string(), score)
-            .await
-            .context("Failed to add job to queue")?;
-
-        tracing::info!("Enqueued email job: {} for {}", job_id, recipient);
+        tracing::info!(
+            "Enqueued email job: {} for {}",
+            job_id,
+            mask_email(recipient)
+        );
         Ok(job_id)
     }
 
-    /// Dequeue the next job for processing
-    /// 
-    /// Moves a job from the queue to the processing set with a timestamp.
-    /// This timestamp is used to detect orphaned jobs (crashed workers) on startup.
-    pub async fn dequeue(&self) -> Result<Option<Uuid>> {
-        let mut conn = self.cache.get_connection().await?;
-
-        // Use ZPOPMIN to atomically get and remove the lowest score item
-        let result: Option<(String, f64)> = conn
-            .zpopmin(EMAIL_QUEUE_KEY, 1)
-            .await
-            .context("Failed to dequeue job")?;
-
-        if let Some((job_id_str, _score)) = result {
-            let job_id = Uuid::parse_str(&job_id_str)?;
-
-            // Add to processing set (sorted set) with current timestamp as score.
-            // This allows us to identify stale jobs on startup by checking age.
-            let processing_started = chrono::Utc::now().timestamp() as f64;
-            let _: () = conn.zadd(EMAIL_PROCESSING_KEY, &job_id_str, processing_started)
-                .await
-                .context("Failed to mark job as processing")?;
-
-            return Ok(Some(job_id));
+    /// Enqueue mail of a subscriber-controlled `category` (campaigns, digests,
+    /// market alerts). Returns `None` without enqueueing when the recipient is
+    /// suppressed or has turned the category off.
+    pub async fn enqueue_for_category(
+        &self,
+        category: EmailCategory,
+        job_type: EmailJobType,
+        recipient: &str,
+        template_name: &str,
+        template_data: Value,
+        priority: i32,
+    ) -> Result<Option<Uuid>> {
+        if !self.db.email_allowed_for_category(recipient, category).await? {
+            tracing::debug!(
+                category = category.as_str(),
+                "Skipping {} email to opted-out or suppressed address",
+                job_type.as_str()
+            );
+            return Ok(None);
         }
+        self.enqueue(job_type, recipient, template_name, template_data, priority)
+            .await
+            .map(Some)
+    }
 
-        Ok(None)
+    /// Claim the next due job, if any. The job is `processing` on return and
+    /// no other worker can claim it.
+    pub async fn claim_next(&self) -> Result<Option<EmailJob>> {
+        self.db.email_claim_due_job().await
     }
 
-    /// Mark a job as completed
     /// Mark a job as completed and create a sent event record.
     ///
     /// ## PII Handling
@@ -110,361 +133,181 @@ impl EmailQueue {
     /// - Events should only be read by authorized analytics users
     /// - Retention policy must comply with your privacy regulations (GDPR, etc)
     /// - Email analytics queries should filter or anonymize recipient data for reports
-    pub async fn mark_completed(&self, job_id: Uuid, message_id: Option<String>) -> Result<()> {
+    pub async fn mark_completed(&self, job: &EmailJob, message_id: Option<String>) -> Result<()> {
         self.db
-            .email_update_job_status(job_id, EmailJobStatus::Completed.as_str(), None)
+            .email_update_job_status(job.id, EmailJobStatus::Completed.as_str(), None)
             .await?;
 
-        // Remove from processing set (now a sorted set with timestamps)
-        let mut conn = self.cache.get_connection().await?;
-        let _: () = conn.zrem(EMAIL_PROCESSING_KEY, job_id.to_string())
-            .await
-            .context("Failed to remove from processing set")?;
-
-        // Track sent event with real recipient email (for analytics)
-        if let Some(msg_id) = message_id {
-            // Look up recipient from DB to ensure we store the actual recipient address,
-            // not an empty string. This makes email analytics reliable and queryable.
-            let recipient = match self.db.email_get_job(job_id).await {
-                Ok(Some(job)) => job.recipient_email,
-                Ok(None) => {
-                    tracing::warn!(
-                        "Could not find job {} for completed email event — recipient will be empty",
-                        job_id
-                    );
-                    String::new()
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Error looking up job {} for completed email event: {} — recipient will be empty",
-                        job_id, e
-                    );
-                    String::new()
-                }
+        if let Some(campaign_id) = job.campaign_id {
+            let outcome = if message_id.is_some() {
+                CampaignOutcome::Sent
+            } else {
+                CampaignOutcome::Skipped
             };
+            self.record_campaign_outcome(job, campaign_id, outcome).await;
+        }
 
+        if let Some(msg_id) = message_id {
             self.db
                 .email_create_event(
-                    Some(job_id),
+                    Some(job.id),
                     Some(&msg_id),
                     "sent",
-                    &recipient,
+                    &job.recipient_email,
                     serde_json::json!({}),
                 )
                 .await?;
         }
 
-        tracing::info!("Marked email job as completed: {}", job_id);
+        tracing::info!("Marked email job as completed: {}", job.id);
         Ok(())
     }
 
-    /// Mark a job as failed and schedule retry if attempts remain
-    pub async fn mark_failed(&self, job_id: Uuid, error: &str) -> Result<()> {
-        let job = self.db.email_get_job(job_id).await?;
-
-        if let Some(job) = job {
-            let new_attempts = job.attempts + 1;
-
-            if new_attempts < job.max_attempts {
-                // Schedule retry with exponential backoff
-                let backoff_seconds = 2_u64.pow(new_attempts as u32) * 60; // 2min, 4min, 8min...
-                let retry_at = chrono::Utc::now() + chrono::Duration::seconds(backoff_seconds as i64);
-
-                self.db
-                    .email_update_job_attempts(job_id, new_attempts, Some(error))
-                    .await?;
-
-                // Add to retry queue
-                let mut conn = self.cache.get_connection().await?;
-                let _: () = conn.zadd(
-                    EMAIL_RETRY_KEY,
-                    job_id.to_string(),
-                    retry_at.timestamp() as f64,
-                )
-                .await
-                .context("Failed to schedule retry")?;
-
-                tracing::warn!(
-                    "Email job {} failed (attempt {}/{}), retrying in {}s: {}",
-                    job_id,
-                    new_attempts,
-                    job.max_attempts,
-                    backoff_seconds,
-                    error
-                );
-            } else {
-                // Max attempts reached — mark as permanently failed and move to dead-letter set.
-                self.db
-                    .email_update_job_status(job_id, EmailJobStatus::Failed.as_str(), Some(error))
-                    .await?;
-
-                let mut conn = self.cache.get_connection().await?;
-                let failed_at = chrono::Utc::now().timestamp() as f64;
-                let _: () = conn
-                    .zadd(EMAIL_DEAD_LETTER_KEY, job_id.to_string(), failed_at)
-                    .await
-                    .context("Failed to add job to dead-letter set")?;
-
-                // Dual-write to PostgreSQL for durability — Redis DLQ survives only
-                // as long as Redis data does; the DB copy survives restarts and flushes.
-                let dlq_payload = serde_json::json!({
-                    "job_type": job.job_type,
-                    "recipient_email": job.recipient_email,
-                    "template_name": job.template_name,
-                    "template_data": job.template_data,
-                });
-                if let Err(e) = self
-                    .db
-                    .email_create_dead_letter_job(job_id, dlq_payload, error, new_attempts)
-                    .await
-                {
-                    tracing::error!(
-                        job_id = %job_id,
-                        error = %e,
-                        "Failed to persist dead-letter job to PostgreSQL — Redis copy still exists"
-                    );
-                }
+    /// Record a failed send: reschedule with [`retry_backoff`] while attempts
+    /// remain, otherwise move the job to `dead_letter`.
+    pub async fn mark_failed(&self, job: &EmailJob, error: &str) -> Result<FailureOutcome> {
+        let attempts = job.attempts + 1;
 
-                tracing::error!(
-                    "Email job {} permanently failed after {} attempts: {}",
-                    job_id,
-                    new_attempts,
-                    error
-                );
-            }
+        if attempts < job.max_attempts {
+            let jitter = rand::thread_rng().gen_range(-1.0..=1.0);
+            let backoff = retry_backoff(attempts, jitter);
+            let retry_at = chrono::Utc::now() + chrono::Duration::from_std(backoff)?;
+            self.db
+                .email_reschedule_job(job.id, attempts, retry_at, error)
+                .await?;
 
-            // Remove from processing set (now a sorted set with timestamps)
-            let mut conn = self.cache.get_connection().await?;
-            let _: () = conn.zrem(EMAIL_PROCESSING_KEY, job_id.to_string())
-                .await
-                .context("Failed to remove from processing set")?;
+            tracing::warn!(
+                job_id = %job.id,
+                attempts,
+                max_attempts = job.max_attempts,
+                retry_in_secs = backoff.as_secs(),
+                error,
+                "Email job failed; rescheduled"
+            );
+            return Ok(FailureOutcome::Rescheduled(retry_at));
         }
 
-        Ok(())
+        self.db.email_dead_letter_job(job.id, attempts, error).await?;
+        if let Some(campaign_id) = job.campaign_id {
+            self.record_campaign_outcome(job, campaign_id, CampaignOutcome::Failed)
+                .await;
+        }
+        tracing::error!(
+            job_id = %job.id,
+            attempts,
+            error,
+            "Email job exhausted its attempts; moved to dead-letter"
+        );
+        Ok(FailureOutcome::DeadLettered)
     }
 
-    /// Process retry queue - move jobs back to main queue if retry time has passed
-    pub async fn process_retries(&self) -> Result<usize> {
-        let mut conn = self.cache.get_connection().await?;
-        let now = chrono::Utc::now().timestamp() as f64;
-
-        // Get all jobs that are ready to retry
-        let jobs: Vec<String> = conn
-            .zrangebyscore(EMAIL_RETRY_KEY, "-inf", now)
-            .await
-            .context("Failed to get retry jobs")?;
-
-        let count = jobs.len();
-
-        for job_id_str in jobs {
-            // Move back to main queue
-            let job_id = Uuid::parse_str(&job_id_str)?;
-            let _: () = conn.zadd(EMAIL_QUEUE_KEY, &job_id_str, now)
-                .await
-                .context("Failed to re-queue job")?;
-
-            // Remove from retry queue
-            let _: () = conn.zrem(EMAIL_RETRY_KEY, &job_id_str)
-                .await
-                .context("Failed to remove from retry queue")?;
-
-            tracing::info!("Re-queued email job for retry: {}", job_id);
+    /// Bump the campaign counter for a finished job. Only logged on error:
+    /// the send itself already happened and must not be retried over a
+    /// progress counter.
+    async fn record_campaign_outcome(&self, job: &EmailJob, campaign_id: i64, outcome: CampaignOutcome) {
+        if let Err(e) = self.db.campaign_record_outcome(campaign_id, outcome).await {
+            tracing::warn!(
+                job_id = %job.id,
+                campaign_id,
+                error = %e,
+                "Failed to record campaign job outcome"
+            );
         }
-
-        Ok(count)
     }
 
-    /// List all job IDs currently in the dead-letter set (oldest-failed first).
-    pub async fn list_dead_letter(&self) -> Result<Vec<Uuid>> {
-        let mut conn = self.cache.get_connection().await?;
-        let items: Vec<String> = conn
-            .zrange(EMAIL_DEAD_LETTER_KEY, 0isize, -1isize)
-            .await
-            .context("Failed to list dead-letter set")?;
-
-        items
-            .iter()
-            .map(|s| Uuid::parse_str(s).context("Invalid UUID in dead-letter set"))
-            .collect()
+    /// Dead-lettered jobs, most recently failed first.
+    pub async fn list_dead_letter(&self, limit: i64, offset: i64) -> Result<Vec<EmailJob>> {
+        self.db.email_list_dead_letter_jobs(limit, offset).await
     }
 
     /// Minimum delay (seconds) before a requeued dead-letter job is eligible
     /// for processing. Prevents immediate re-failure loops on persistent errors.
     const DEAD_LETTER_REQUEUE_DELAY_SECS: i64 = 60;
 
-    /// Move a job from the dead-letter set back to the main queue for reprocessing.
+    /// Move a dead-lettered job back to the queue.
     ///
     /// The job is scheduled `DEAD_LETTER_REQUEUE_DELAY_SECS` seconds in the future
     /// so a persistent failure does not cause a tight retry loop. The attempts counter
-    /// is also reset to 0 so the job gets its full retry budget again.
-    pub async fn requeue_dead_letter(&self, job_id: Uuid) -> Result<bool> {
-        let mut conn = self.cache.get_connection().await?;
-
-        let removed: usize = conn
-            .zrem(EMAIL_DEAD_LETTER_KEY, job_id.to_string())
-            .await
-            .context("Failed to remove job from dead-letter set")?;
-
-        if removed == 0 {
-            return Ok(false);
-        }
-
-        // Reset attempts to 0 so the job gets its full retry budget.
-        self.db
-            .email_update_job_attempts(job_id, 0, None)
-            .await?;
-
-        // Reset status to pending.
-        self.db
-            .email_update_job_status(job_id, crate::email::types::EmailJobStatus::Pending.as_str(), None)
-            .await?;
-
-        // Schedule processing after the cooling-off delay to prevent tight loops.
-        let eligible_at = chrono::Utc::now().timestamp() + Self::DEAD_LETTER_REQUEUE_DELAY_SECS;
-        let _: () = conn
-            .zadd(EMAIL_QUEUE_KEY, job_id.to_string(), eligible_at as f64)
-            .await
-            .context("Failed to re-enqueue dead-letter job")?;
-
-        // Remove the durable DB copy now that the job is back in the queue.
-        if let Err(e) = self.db.email_delete_dead_letter_job(job_id).await {
-            tracing::warn!(
+    /// is also reset to 0 so the job gets its full retry budget again. Returns
+    /// `None` when the job is not dead-lettered.
+    pub async fn requeue_dead_letter(&self, job_id: Uuid) -> Result<Option<EmailJob>> {
+        let eligible_at =
+            chrono::Utc::now() + chrono::Duration::seconds(Self::DEAD_LETTER_REQUEUE_DELAY_SECS);
+        let job = self.db.email_retry_dead_letter_job(job_id, eligible_at).await?;
+        if job.is_some() {
+            tracing::info!(
                 job_id = %job_id,
-                error = %e,
-                "Failed to remove dead-letter job from PostgreSQL — stale entry will remain"
+                delay_secs = Self::DEAD_LETTER_REQUEUE_DELAY_SECS,
+                "Requeued dead-letter email job with cooling-off delay"
             );
         }
-
-        tracing::info!(
-            job_id = %job_id,
-            delay_secs = Self::DEAD_LETTER_REQUEUE_DELAY_SECS,
-            "Requeued dead-letter email job with cooling-off delay"
-        );
-        Ok(true)
+        Ok(job)
     }
 
     /// Get queue statistics
     pub async fn get_stats(&self) -> Result<QueueStats> {
-        let mut conn = self.cache.get_connection().await?;
-
-        let pending: usize = conn
-            .zcard(EMAIL_QUEUE_KEY)
-            .await
-            .context("Failed to get queue size")?;
-
-        let processing: usize = conn
-            .zcard(EMAIL_PROCESSING_KEY)
-            .await
-            .context("Failed to get processing count")?;
-
-        let retry: usize = conn
-            .zcard(EMAIL_RETRY_KEY)
-            .await
-            .context("Failed to get retry count")?;
-
-        let dead_letter: usize = conn
-            .zcard(EMAIL_DEAD_LETTER_KEY)
-            .await
-            .context("Failed to get dead-letter count")?;
-
-        Ok(QueueStats {
-            pending,
-            processing,
-            retry,
-            dead_letter,
-        })
+        self.db.email_job_counts().await
     }
 
-    /// Get the current depth of the main email queue.
+    /// Number of jobs waiting to be sent, including scheduled retries.
     pub async fn get_queue_depth(&self) -> Result<usize> {
-        let mut conn = self.cache.get_connection().await?;
-        let depth: usize = conn
-            .zcard(EMAIL_QUEUE_KEY)
-            .await
-            .context("Failed to get queue depth")?;
-        Ok(depth)
+        let stats = self.get_stats().await?;
+        Ok(stats.pending + stats.retry)
     }
 
-    /// Re-queue any jobs stuck in the processing set (e.g. from a previous crash).
+    /// Return jobs stuck in `processing` (e.g. from a previous crash) to the queue.
     ///
     /// Recovers jobs that have been in processing longer than the configured
     /// stale threshold. This mechanism handles worker crashes gracefully:
     /// - On startup, the worker scans for orphaned jobs
     /// - Jobs older than the threshold are considered abandoned by crashed workers
-    /// - These jobs are re-queued at the current time for reprocessing
+    /// - These jobs are made due again immediately
     /// - Behavior is idempotent: repeated calls on the same set recover nothing
     pub async fn recover_orphaned_jobs(&self, stale_threshold_secs: u64) -> Result<usize> {
-        let mut conn = self.cache.get_connection().await?;
-        let now = chrono::Utc::now().timestamp() as f64;
-        let stale_cutoff = now - (stale_threshold_secs as f64);
-
-        // Get all jobs in processing that are older than the stale threshold.
-        // Processing set is a sorted set with timestamps as scores.
-        let stale_jobs: Vec<String> = conn
-            .zrangebyscore(EMAIL_PROCESSING_KEY, "-inf", stale_cutoff)
-            .await
-            .context("Failed to scan processing set for stale jobs")?;
-
-        let count = stale_jobs.len();
+        let count = self
+            .db
+            .email_recover_stale_jobs(Duration::from_secs(stale_threshold_secs))
+            .await? as usize;
         if count > 0 {
             tracing::warn!(
-                "Recovering {} orphaned email jobs (stale for > {}s)",
+                "Recovered {} orphaned email jobs (stale for > {}s)",
                 count,
                 stale_threshold_secs
             );
         }
-
-        for job_id_str in stale_jobs {
-            let requeue_score = now;
-            let _: () = conn
-                .zadd(EMAIL_QUEUE_KEY, &job_id_str, requeue_score)
-                .await
-                .context("Failed to re-queue orphaned job")?;
-            let _: () = conn
-                .zrem(EMAIL_PROCESSING_KEY, &job_id_str)
-                .await
-                .context("Failed to remove orphaned job from processing set")?;
-            tracing::warn!("Recovered orphaned email job: {}", job_id_str);
-        }
-
         Ok(count)
     }
 
     /// Get the number of jobs currently being processed.
     pub async fn get_processing_count(&self) -> Result<usize> {
-        let mut conn = self.cache.get_connection().await?;
-        let count: usize = conn
-            .zcard(EMAIL_PROCESSING_KEY)
-            .await
-            .context("Failed to get processing count")?;
-        Ok(count)
+        Ok(self.get_stats().await?.processing)
     }
 
     /// Background worker to process email queue.
     ///
     /// Accepts a [`CancellationToken`] and a [`ShutdownCoordinator`].
     /// On startup:
-    ///   - Scans for orphaned jobs from previous crashes
-    ///   - Re-queues jobs stuck in processing longer than the configured threshold
+    /// - Re-queues jobs stuck in processing longer than the configured threshold
+    ///
     /// On shutdown:
-    ///   - stops dequeuing new jobs immediately
-    ///   - allows any in-flight `process_job` call to complete
-    ///   - calls `coordinator.worker_completed()` before returning
+    /// - stops claiming new jobs immediately
+    /// - allows any in-flight `process_job` call to complete
+    /// - calls `coordinator.worker_completed()` before returning
     pub async fn start_worker(
         &self,
         service: crate::email::EmailService,
         shutdown: CancellationToken,
         coordinator: ShutdownCoordinator,
         stale_job_threshold_secs: u64,
-        metrics: Option<crate::metrics::Metrics>,
+        metrics: Option<Metrics>,
     ) {
         const WORKER_NAME: &str = "email_queue";
-        
+
         // Set worker status to running
         if let Some(ref m) = metrics {
             m.set_worker_status(WORKER_NAME, true);
         }
-        
+
         tracing::info!("Email queue worker started");
 
         if let Err(e) = self.recover_orphaned_jobs(stale_job_threshold_secs).await {
@@ -484,30 +327,36 @@ impl EmailQueue {
                 }
                 else => {}
             }
-            
+
             // Do not pick up new work after shutdown signal.
             if shutdown.is_cancelled() {
                 tracing::info!("Email queue worker: shutdown signal received, draining stops");
                 break;
             }
 
-            // Process retries first (quick Redis operation, safe to run).
-            if let Err(e) = self.process_retries().await {
-                tracing::error!("Error processing retries: {}", e);
-            }
-
-            match self.dequeue().await {
-                Ok(Some(job_id)) => {
+            match self.claim_next().await {
+                Ok(Some(job)) => {
                     // In-flight job always runs to completion.
-                    if let Err(e) = self.process_job(job_id, &service).await {
-                        tracing::error!("Error processing job {}: {}", job_id, e);
-                        let _ = self.mark_failed(job_id, &e.to_string()).await;
+                    if let Err(e) = self.process_job(&job, &service).await {
+                        match self.mark_failed(&job, &e.to_string()).await {
+                            Ok(FailureOutcome::DeadLettered) => {
+                                if let Some(ref m) = metrics {
+                                    m.observe_email_dead_lettered(&job.template_name);
+                                }
+                            }
+                            Ok(FailureOutcome::Rescheduled(_)) => {}
+                            Err(mark_err) => tracing::error!(
+                                job_id = %job.id,
+                                error = %mark_err,
+                                "Failed to record email job failure; it will be recovered as stale"
+                            ),
+                        }
                     }
                 }
                 Ok(None) => {
-                    // Queue empty — wait briefly or exit early on shutdown.
+                    // Nothing due — wait briefly or exit early on shutdown.
                     tokio::select! {
-                        _ = sleep(Duration::from_secs(1)) => {}
+                        _ = sleep(IDLE_POLL_INTERVAL) => {}
                         _ = shutdown.cancelled() => {
                             tracing::info!("Email queue worker: shutdown during idle sleep, stopping");
                             break;
@@ -515,7 +364,7 @@ impl EmailQueue {
                     }
                 }
                 Err(e) => {
-                    tracing::error!("Error dequeuing job: {}", e);
+                    tracing::error!("Error claiming email job: {}", e);
                     tokio::select! {
                         _ = sleep(Duration::from_secs(5)) => {}
                         _ = shutdown.cancelled() => {
@@ -527,8 +376,9 @@ impl EmailQueue {
             }
 
             if let Some(ref m) = metrics {
-                if let Ok(depth) = self.get_queue_depth().await {
-                    m.set_email_queue_depth(depth as i64);
+                if let Ok(stats) = self.get_stats().await {
+                    m.set_email_queue_depth((stats.pending + stats.retry) as i64);
+                    m.set_dlq_size(stats.dead_letter as i64);
                 }
             }
         }
@@ -537,39 +387,67 @@ impl EmailQueue {
         if let Some(ref m) = metrics {
             m.set_worker_status(WORKER_NAME, false);
         }
-        
+
         tracing::info!("Email queue worker stopped");
         coordinator.worker_completed();
     }
 
-    async fn process_job(&self, job_id: Uuid, service: &crate::email::EmailService) -> Result<()> {
-        let job = self
-            .db
-            .email_get_job(job_id)
-            .await?
-            .context("Job not found")?;
-
-        // Check if email is suppressed
-        if self.db.email_is_suppressed(&job.recipient_email).await? {
+    async fn process_job(&self, job: &EmailJob, service: &crate::email::EmailService) -> Result<()> {
+        // Check if email is suppressed. Campaign and digest recipients may
+        // also have turned that mail off since their job was enqueued.
+        let category = if job.campaign_id.is_some() {
+            Some(campaign::CATEGORY)
+        } else if job.job_type == EmailJobType::Digest.as_str() {
+            Some(digest::CATEGORY)
+        } else {
+            None
+        };
+        let allowed = match category {
+            Some(category) => {
+                self.db
+                    .email_allowed_for_category(&job.recipient_email, category)
+                    .await?
+            }
+            None => !self.db.email_is_suppressed(&job.recipient_email).await?,
+        };
+        if !allowed {
             tracing::warn!(
                 "Skipping email to suppressed address: {}",
-                job.recipient_email
+                mask_email(&job.recipient_email)
             );
-            return self.mark_completed(job_id, None).await;
+            return self.mark_completed(job, None).await;
         }
 
-        // Update status to processing
-        self.db
-            .email_update_job_status(job_id, EmailJobStatus::Processing.as_str(), None)
-            .await?;
+        // A reminder about a market resolved or cancelled since it was
+        // enqueued no longer asks anything of its creator.
+        if job.job_type == EmailJobType::ResolutionReminder.as_str()
+            && !resolution_reminder::still_due(&self.db, &job.template_data).await?
+        {
+            tracing::info!(job_id = %job.id, "Skipping reminder for a market no longer awaiting resolution");
+            return self.mark_completed(job, None).await;
+        }
 
         // Derive a stable idempotency key for this job so retries never
-        // produce duplicate sends within the configured TTL window.
-        let idem = idempotency_key(
-            &job.recipient_email,
-            &job.template_name,
-            &service.idempotency_secret,
-        );
+        // produce duplicate sends within the configured TTL window. Event
+        // notifications carry an `event_id`, and contact form emails a
+        // `submission_id`, that scopes the key, so two different events or
+        // submissions for the same recipient within the hour both send.
+        // Campaign jobs are scoped by campaign, so two campaigns sharing a
+        // template both reach every recipient.
+        let scope_id = match job.campaign_id {
+            Some(id) => Some(format!("campaign-{id}")),
+            None => job
+                .template_data
+                .get("event_id")
+                .or_else(|| job.template_data.get("submission_id"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+        };
+        let scope = match scope_id {
+            Some(id) => format!("{}#{}", job.template_name, id),
+            None => job.template_name.clone(),
+        };
+        let idem = idempotency_key(&job.recipient_email, &scope, &service.idempotency_secret);
 
         // Send email (deduplication handled inside send_email_idempotent)
         let message_id = service
@@ -583,14 +461,14 @@ impl EmailQueue {
 
         if message_id.starts_with("deduplicated:") {
             tracing::info!(
-                job_id = %job_id,
+                job_id = %job.id,
                 idem_key = %idem,
                 "Email job skipped — already sent within idempotency window"
             );
         }
 
         // Mark as completed regardless (dedup counts as success)
-        self.mark_completed(job_id, Some(message_id)).await?;
+        self.mark_completed(job, Some(message_id)).await?;
 
         Ok(())
     }
@@ -609,11 +487,20 @@ mod tests {
     use super::*;
 
     #[test]
-    fn dead_letter_requeue_delay_is_positive() {
-        assert!(EmailQueue::DEAD_LETTER_REQUEUE_DELAY_SECS > 0,
-            "cooling-off delay must be positive to prevent immediate re-failure loops");
+    fn retry_backoff_doubles_per_attempt_within_jitter() {
+        assert_eq!(retry_backoff(1, 0.0), Duration::from_secs(120));
+        assert_eq!(retry_backoff(2, 0.0), Duration::from_secs(240));
+        assert_eq!(retry_backoff(3, 0.0), Duration::from_secs(480));
+        assert_eq!(retry_backoff(2, -1.0), Duration::from_secs(192));
+        assert_eq!(retry_backoff(2, 1.0), Duration::from_secs(288));
+        // Out-of-range jitter and attempt counts are clamped.
+        assert_eq!(retry_backoff(2, 5.0), retry_backoff(2, 1.0));
+        assert_eq!(retry_backoff(99, 0.0), retry_backoff(MAX_BACKOFF_EXPONENT, 0.0));
     }
 
+    // The cooling-off delay must be positive to prevent immediate re-failure loops.
+    const _: () = assert!(EmailQueue::DEAD_LETTER_REQUEUE_DELAY_SECS > 0);
+
     /// Test that recover_orphaned_jobs correctly identifies stale jobs.
     /// 
     /// Acceptance criteria for #472: 
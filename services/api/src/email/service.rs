@@ -1,6 +1,5 @@
 use anyhow::{Context, Result};
 use hmac::{Hmac, Mac};
-use redis::AsyncCommands as _;
 use serde_json::Value;
 use sha2::Sha256;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -9,6 +8,7 @@ use validator::ValidateEmail;
 use crate::cache::RedisCache;
 use crate::config::Config;
 use crate::email::templates::EmailTemplateEngine;
+use crate::log_redact::mask_email;
 use crate::metrics::Metrics;
 
 /// Configuration for email idempotency deduplication.
@@ -68,13 +68,13 @@ pub fn sanitize_email(raw: &str) -> Result<String> {
     let trimmed = raw.trim().to_string();
 
     if trimmed.is_empty() {
-        tracing::warn!(raw_input = raw, "Email validation failed: address is empty");
+        tracing::warn!(raw_input = %mask_email(raw), "Email validation failed: address is empty");
         anyhow::bail!("email address must not be empty");
     }
 
     if trimmed.len() > 254 {
         tracing::warn!(
-            raw_input = raw,
+            raw_input = %mask_email(raw),
             length = trimmed.len(),
             "Email validation failed: address exceeds 254-character RFC 5321 limit"
         );
@@ -86,7 +86,7 @@ pub fn sanitize_email(raw: &str) -> Result<String> {
 
     if !trimmed.validate_email() {
         tracing::warn!(
-            raw_input = raw,
+            raw_input = %mask_email(raw),
             "Email validation failed: address does not conform to RFC 5322"
         );
         anyhow::bail!("invalid email address: '{trimmed}'");
@@ -119,7 +119,9 @@ impl EmailService {
         idempotency: IdempotencyConfig,
         idempotency_secret: String,
     ) -> Result<Self> {
-        Self::with_cache_and_metrics(config, cache, idempotency, None)
+        let mut service = Self::with_cache_and_metrics(config, cache, idempotency, None)?;
+        service.idempotency_secret = idempotency_secret;
+        Ok(service)
     }
 
     pub fn with_cache_and_metrics(
@@ -134,12 +136,12 @@ impl EmailService {
             .build()?;
 
         Ok(Self {
+            idempotency_secret: config.email_idempotency_secret.clone(),
             config,
             template_engine,
             client,
             cache,
             idempotency,
-            idempotency_secret,
             metrics,
             sendgrid_base_url: "https://api.sendgrid.com".to_string(),
         })
@@ -220,7 +222,7 @@ impl EmailService {
                 // Key already existed — this is a duplicate send.
                 tracing::info!(
                     idem_key = key,
-                    recipient = recipient,
+                    recipient = %mask_email(recipient),
                     template = template_name,
                     "Duplicate email send suppressed by idempotency key"
                 );
@@ -308,7 +310,7 @@ impl EmailService {
 
                 tracing::info!(
                     "Email sent successfully to {} using template {} (message_id: {})",
-                    recipient,
+                    mask_email(recipient),
                     template_name,
                     message_id
                 );
@@ -392,17 +394,70 @@ impl EmailService {
             "waitlist_confirmation" => serde_json::json!({
                 "email": "test@example.com"
             }),
+            "waitlist_invitation" => serde_json::json!({
+                "email": "test@example.com",
+                "signup_url": format!("{}/signup", self.config.base_url)
+            }),
             "contact_form_auto_response" => serde_json::json!({
                 "name": "Test User",
                 "subject": "Test Subject",
                 "message": "This is a test message from the contact form."
             }),
+            "contact_form_notification" => serde_json::json!({
+                "submission_id": "00000000-0000-0000-0000-000000000000",
+                "name": "Test User",
+                "email": "test@example.com",
+                "subject": "Test Subject",
+                "message": "This is a test message from the contact form."
+            }),
             "welcome_email" => serde_json::json!({
                 "name": "Test User",
                 "dashboard_url": format!("{}/dashboard", self.config.base_url),
                 "help_url": format!("{}/help", self.config.base_url),
                 "unsubscribe_url": format!("{}/api/v1/newsletter/unsubscribe", self.config.base_url)
             }),
+            "market_resolved" => serde_json::json!({
+                "market_title": "Will it rain in London tomorrow?",
+                "event": "resolved",
+                "is_dispute": false,
+                "outcome_label": "Yes",
+                "payout_url": format!("{}/markets/1", self.config.base_url),
+                "unsubscribe_url": format!("{}/api/v1/watches/unsubscribe?token=preview", self.config.base_url)
+            }),
+            "weekly_digest" => serde_json::json!({
+                "week_label": "12 October 2026",
+                "markets": [{
+                    "title": "Will it rain in London tomorrow?",
+                    "odds": "Yes 62% · No 38%",
+                    "url": format!("{}/markets/1", self.config.base_url)
+                }],
+                "resolutions": [{
+                    "title": "Will BTC close above $100k this week?",
+                    "outcome": "No",
+                    "url": format!("{}/markets/2", self.config.base_url)
+                }],
+                "email": "test@example.com",
+                "preferences_url": format!("{}/api/v1/newsletter/preferences?token=preview", self.config.base_url)
+            }),
+            "position_update" => serde_json::json!({
+                "market_title": "Will it rain in London tomorrow?",
+                "is_claim": true,
+                "is_dispute": false,
+                "outcome_label": "Yes",
+                "market_url": format!("{}/markets/1", self.config.base_url),
+                "unsubscribe_url": format!("{}/api/v1/notifications/unsubscribe?token=preview", self.config.base_url)
+            }),
+            "notification_email_confirmation" => serde_json::json!({
+                "confirm_url": format!("{}/api/v1/notifications/confirm?token=test-token-123", self.config.base_url),
+                "email": "test@example.com"
+            }),
+            "tx_finalized" => serde_json::json!({
+                "tx_hash": "3389e9f0f1a65f19736cacf544c2e825313e8447f569233bb8db39aa607c8889",
+                "network": "testnet",
+                "status": "SUCCESS",
+                "is_success": true,
+                "ledger": "51234"
+            }),
             _ => serde_json::json!({}),
         }
     }
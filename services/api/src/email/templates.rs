@@ -23,16 +23,56 @@ impl EmailTemplateEngine {
             include_str!("../../templates/waitlist_confirmation.html"),
         )?;
 
+        handlebars.register_template_string(
+            "waitlist_invitation",
+            include_str!("../../templates/waitlist_invitation.html"),
+        )?;
+
         handlebars.register_template_string(
             "contact_form_auto_response",
             include_str!("../../templates/contact_form_auto_response.html"),
         )?;
 
+        handlebars.register_template_string(
+            "contact_form_notification",
+            include_str!("../../templates/contact_form_notification.html"),
+        )?;
+
         handlebars.register_template_string(
             "welcome_email",
             include_str!("../../templates/welcome_email.html"),
         )?;
 
+        handlebars.register_template_string(
+            "market_resolved",
+            include_str!("../../templates/market_resolved.html"),
+        )?;
+
+        handlebars.register_template_string(
+            "weekly_digest",
+            include_str!("../../templates/weekly_digest.html"),
+        )?;
+
+        handlebars.register_template_string(
+            "position_update",
+            include_str!("../../templates/position_update.html"),
+        )?;
+
+        handlebars.register_template_string(
+            "notification_email_confirmation",
+            include_str!("../../templates/notification_email_confirmation.html"),
+        )?;
+
+        handlebars.register_template_string(
+            "tx_finalized",
+            include_str!("../../templates/tx_finalized.html"),
+        )?;
+
+        handlebars.register_template_string(
+            "resolution_reminder",
+            include_str!("../../templates/resolution_reminder.html"),
+        )?;
+
         let engine = Self { handlebars };
 
         // Validate all templates at startup by rendering with representative data.
@@ -53,17 +93,68 @@ impl EmailTemplateEngine {
             ("waitlist_confirmation", serde_json::json!({
                 "email": "startup@example.com"
             })),
+            ("waitlist_invitation", serde_json::json!({
+                "email": "startup@example.com",
+                "signup_url": "https://example.com/signup"
+            })),
             ("contact_form_auto_response", serde_json::json!({
                 "name": "Startup Check",
                 "subject": "Startup Check",
                 "message": "Startup validation render."
             })),
+            ("contact_form_notification", serde_json::json!({
+                "submission_id": "00000000-0000-0000-0000-000000000000",
+                "name": "Startup Check",
+                "email": "startup@example.com",
+                "subject": "Startup Check",
+                "message": "Startup validation render."
+            })),
             ("welcome_email", serde_json::json!({
                 "name": "Startup Check",
                 "dashboard_url": "https://example.com/dashboard",
                 "help_url": "https://example.com/help",
                 "unsubscribe_url": "https://example.com/unsubscribe"
             })),
+            ("market_resolved", serde_json::json!({
+                "market_title": "Startup Check",
+                "event": "resolved",
+                "is_dispute": false,
+                "outcome_label": "Yes",
+                "payout_url": "https://example.com/markets/1",
+                "unsubscribe_url": "https://example.com/api/v1/watches/unsubscribe?token=startup-check"
+            })),
+            ("weekly_digest", serde_json::json!({
+                "week_label": "1 January 2026",
+                "markets": [{ "title": "Startup Check", "odds": "Yes 50% · No 50%", "url": "https://example.com/markets/1" }],
+                "resolutions": [{ "title": "Startup Check", "outcome": "Yes", "url": "https://example.com/markets/2" }],
+                "email": "startup@example.com",
+                "preferences_url": "https://example.com/api/v1/newsletter/preferences?token=startup-check"
+            })),
+            ("position_update", serde_json::json!({
+                "market_title": "Startup Check",
+                "is_claim": true,
+                "is_dispute": false,
+                "outcome_label": "Yes",
+                "market_url": "https://example.com/markets/1",
+                "unsubscribe_url": "https://example.com/api/v1/notifications/unsubscribe?token=startup-check"
+            })),
+            ("notification_email_confirmation", serde_json::json!({
+                "confirm_url": "https://example.com/api/v1/notifications/confirm?token=startup-check",
+                "email": "startup@example.com"
+            })),
+            ("tx_finalized", serde_json::json!({
+                "tx_hash": "0000000000000000000000000000000000000000000000000000000000000000",
+                "network": "testnet",
+                "status": "SUCCESS",
+                "is_success": true,
+                "ledger": "1"
+            })),
+            ("resolution_reminder", serde_json::json!({
+                "market_title": "Startup Check",
+                "market_url": "https://example.com/markets/1",
+                "deadline": "1 January 2026, 12:00 UTC",
+                "lead_hours": 48
+            })),
         ];
 
         for (name, data) in fixtures {
@@ -98,6 +189,7 @@ impl EmailTemplateEngine {
         match template_name {
             "newsletter_confirmation" => "Confirm your newsletter subscription".to_string(),
             "waitlist_confirmation" => "You're on the waitlist!".to_string(),
+            "waitlist_invitation" => "Your PredictIQ invitation is here".to_string(),
             "contact_form_auto_response" => {
                 format!(
                     "We received your message: {}",
@@ -106,7 +198,60 @@ impl EmailTemplateEngine {
                         .unwrap_or("Your inquiry")
                 )
             }
+            "contact_form_notification" => {
+                format!(
+                    "[Contact] {}",
+                    data.get("subject")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("New submission")
+                )
+            }
             "welcome_email" => "Welcome to PredictIQ!".to_string(),
+            "market_resolved" => {
+                let title = data
+                    .get("market_title")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("a market you're watching");
+                if data.get("is_dispute").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    format!("Market disputed: {}", title)
+                } else {
+                    format!("Market resolved: {}", title)
+                }
+            }
+            "weekly_digest" => format!(
+                "This week on PredictIQ: {}",
+                data.get("week_label")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("your weekly roundup")
+            ),
+            "position_update" => {
+                let title = data
+                    .get("market_title")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("a market you bet on");
+                let flag = |key: &str| data.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+                if flag("is_claim") {
+                    format!("Winnings ready to claim: {}", title)
+                } else if flag("is_dispute") {
+                    format!("Market disputed: {}", title)
+                } else {
+                    format!("Market resolved: {}", title)
+                }
+            }
+            "notification_email_confirmation" => "Confirm your notification email".to_string(),
+            "tx_finalized" => {
+                if data.get("is_success").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    "Your transaction succeeded".to_string()
+                } else {
+                    "Your transaction failed".to_string()
+                }
+            }
+            "resolution_reminder" => format!(
+                "Resolution due soon: {}",
+                data.get("market_title")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("your market")
+            ),
             _ => "Message from PredictIQ".to_string(),
         }
     }
@@ -130,15 +275,139 @@ impl EmailTemplateEngine {
                     data.get("email").and_then(|v| v.as_str()).unwrap_or("")
                 )
             }
+            "waitlist_invitation" => {
+                format!(
+                    "Your spot on the PredictIQ waitlist has come up!\n\nCreate your account: {}\n\nSee you inside,\nThe PredictIQ Team",
+                    data.get("signup_url").and_then(|v| v.as_str()).unwrap_or("")
+                )
+            }
             "contact_form_auto_response" => {
                 format!(
                     "Thank you for contacting PredictIQ!\n\nWe've received your message and will get back to you soon.\n\nYour message:\n{}\n\nBest regards,\nThe PredictIQ Team",
                     data.get("message").and_then(|v| v.as_str()).unwrap_or("")
                 )
             }
+            "contact_form_notification" => {
+                let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                format!(
+                    "New contact form submission {}\n\nFrom: {} <{}>\nSubject: {}\n\n{}",
+                    field("submission_id"),
+                    field("name"),
+                    field("email"),
+                    field("subject"),
+                    field("message")
+                )
+            }
             "welcome_email" => {
+                "Welcome to PredictIQ!\n\nWe're excited to have you on board. Get started by exploring our prediction markets.\n\nBest regards,\nThe PredictIQ Team".to_string()
+            }
+            "market_resolved" => {
+                let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let status = if data.get("is_dispute").and_then(|v| v.as_bool()).unwrap_or(false) {
+                    "A dispute has been filed against this market's outcome.".to_string()
+                } else {
+                    format!("The market has resolved to: {}", field("outcome_label"))
+                };
+                format!(
+                    "{}\n\n{}\n\nClaim your payout or view the market: {}\n\nStop watching this market: {}\n\nBest regards,\nThe PredictIQ Team",
+                    field("market_title"),
+                    status,
+                    field("payout_url"),
+                    field("unsubscribe_url")
+                )
+            }
+            "weekly_digest" => {
+                let field = |item: &Value, key: &str| {
+                    item.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string()
+                };
+                let list = |key: &str, detail: &str| -> Vec<String> {
+                    data.get(key)
+                        .and_then(|v| v.as_array())
+                        .map(|items| {
+                            items
+                                .iter()
+                                .map(|item| {
+                                    let (title, url) = (field(item, "title"), field(item, "url"));
+                                    match field(item, detail) {
+                                        d if d.is_empty() => format!("- {title}\n  {url}"),
+                                        d => format!("- {title} ({d})\n  {url}"),
+                                    }
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                };
+                let markets = list("markets", "odds");
+                let resolutions = list("resolutions", "outcome");
+
+                let mut text = String::from("This week on PredictIQ\n\nTop markets by volume:\n");
+                if markets.is_empty() {
+                    text.push_str("No markets are open right now. Check back soon.\n");
+                } else {
+                    text.push_str(&markets.join("\n"));
+                    text.push('\n');
+                }
+                if !resolutions.is_empty() {
+                    text.push_str("\nResolved this week:\n");
+                    text.push_str(&resolutions.join("\n"));
+                    text.push('\n');
+                }
+                text.push_str(&format!(
+                    "\nManage email preferences: {}\n\nBest regards,\nThe PredictIQ Team",
+                    data.get("preferences_url").and_then(|v| v.as_str()).unwrap_or("")
+                ));
+                text
+            }
+            "position_update" => {
+                let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let flag = |key: &str| data.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+                let status = if flag("is_dispute") {
+                    "A dispute has been filed against this market's outcome.".to_string()
+                } else if flag("is_claim") {
+                    format!(
+                        "The market has resolved to: {}. You bet on this outcome and can now claim your payout.",
+                        field("outcome_label")
+                    )
+                } else {
+                    format!("The market has resolved to: {}", field("outcome_label"))
+                };
+                format!(
+                    "{}\n\n{}\n\nView the market: {}\n\nTurn off position notifications: {}\n\nBest regards,\nThe PredictIQ Team",
+                    field("market_title"),
+                    status,
+                    field("market_url"),
+                    field("unsubscribe_url")
+                )
+            }
+            "notification_email_confirmation" => {
+                format!(
+                    "Please confirm that PredictIQ may email you about your positions by visiting: {}\n\nIf you didn't request this, please ignore this email.",
+                    data.get("confirm_url").and_then(|v| v.as_str()).unwrap_or("")
+                )
+            }
+            "tx_finalized" => {
+                let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let ledger = match field("ledger") {
+                    "" => String::new(),
+                    ledger => format!(" in ledger {ledger}"),
+                };
+                format!(
+                    "The transaction you are watching on {} reached a final status: {}{}.\n\nTransaction hash: {}\n\nBest regards,\nThe PredictIQ Team",
+                    field("network"),
+                    field("status"),
+                    ledger,
+                    field("tx_hash")
+                )
+            }
+            "resolution_reminder" => {
+                let field = |key: &str| data.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let lead_hours = data.get("lead_hours").and_then(|v| v.as_u64()).unwrap_or(0);
                 format!(
-                    "Welcome to PredictIQ!\n\nWe're excited to have you on board. Get started by exploring our prediction markets.\n\nBest regards,\nThe PredictIQ Team"
+                    "{}\n\nBetting on this market has closed, but no oracle result has been recorded yet. The resolution deadline is {}, about {} hours from now.\n\nTo resolve it:\n1. Open the market and check the outcome against its resolution source.\n2. Submit the oracle result from the creator account before the deadline.\n3. Confirm the market shows as resolved so bettors can claim their payouts.\n\nOpen the market: {}\n\nBest regards,\nThe PredictIQ Team",
+                    field("market_title"),
+                    field("deadline"),
+                    lead_hours,
+                    field("market_url")
                 )
             }
             _ => "Message from PredictIQ".to_string(),
@@ -279,6 +548,23 @@ mod tests {
         assert!(engine.render("waitlist_confirmation", &data).is_ok());
     }
 
+    // waitlist_invitation
+
+    #[test]
+    fn waitlist_invitation_links_to_signup() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = json!({
+            "email": "user@example.com",
+            "signup_url": "https://predictiq.io/signup"
+        });
+        let html = engine.render("waitlist_invitation", &data).unwrap();
+        assert!(html.contains("https://predictiq.io/signup"));
+        assert!(html.contains("user@example.com"));
+        assert!(engine
+            .render_text("waitlist_invitation", &data)
+            .contains("Create your account: https://predictiq.io/signup"));
+    }
+
     // contact_form_auto_response
 
     #[test]
@@ -351,6 +637,164 @@ mod tests {
         assert!(engine.render("welcome_email", &data).is_ok());
     }
 
+    // contact_form_notification
+
+    #[test]
+    fn contact_form_notification_names_the_sender() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = json!({
+            "submission_id": "abc",
+            "name": "Ada <admin>",
+            "email": "ada@example.com",
+            "subject": "Payout question",
+            "message": "Where is my payout?"
+        });
+        let html = engine.render("contact_form_notification", &data).unwrap();
+        assert!(html.contains("ada@example.com"));
+        assert!(!html.contains("<admin>"), "sender input must be escaped");
+        assert_eq!(
+            engine.get_subject("contact_form_notification", &data),
+            "[Contact] Payout question"
+        );
+        assert!(engine
+            .render_text("contact_form_notification", &data)
+            .contains("From: Ada <admin> <ada@example.com>"));
+    }
+
+    // market_resolved
+
+    fn market_resolved_data(title: &str, is_dispute: bool) -> Value {
+        json!({
+            "market_title": title,
+            "event": if is_dispute { "disputed" } else { "resolved" },
+            "is_dispute": is_dispute,
+            "outcome_label": if is_dispute { "" } else { "Yes" },
+            "payout_url": "https://example.com/markets/1",
+            "unsubscribe_url": "https://example.com/api/v1/watches/unsubscribe?token=abc"
+        })
+    }
+
+    #[test]
+    fn market_resolved_empty_strings() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = json!({
+            "market_title": "",
+            "event": "",
+            "is_dispute": false,
+            "outcome_label": "",
+            "payout_url": "",
+            "unsubscribe_url": ""
+        });
+        assert!(engine.render("market_resolved", &data).is_ok());
+    }
+
+    #[test]
+    fn market_resolved_special_chars_in_title() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let html = engine
+            .render("market_resolved", &market_resolved_data("Will <BTC> hit $100k?", false))
+            .unwrap();
+        assert!(!html.contains("<BTC>"), "angle brackets must be escaped");
+    }
+
+    #[test]
+    fn market_resolved_long_title() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = market_resolved_data(&"A".repeat(500), true);
+        assert!(engine.render("market_resolved", &data).is_ok());
+    }
+
+    #[test]
+    fn market_resolved_subject_and_body_follow_the_event() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let resolved = market_resolved_data("Rain?", false);
+        assert_eq!(engine.get_subject("market_resolved", &resolved), "Market resolved: Rain?");
+        assert!(engine.render("market_resolved", &resolved).unwrap().contains("Yes"));
+
+        let disputed = market_resolved_data("Rain?", true);
+        assert_eq!(engine.get_subject("market_resolved", &disputed), "Market disputed: Rain?");
+        assert!(engine.render_text("market_resolved", &disputed).contains("dispute has been filed"));
+    }
+
+    // weekly_digest
+
+    fn weekly_digest_data(markets: Value, resolutions: Value) -> Value {
+        json!({
+            "week_label": "12 October 2026",
+            "markets": markets,
+            "resolutions": resolutions,
+            "email": "user@example.com",
+            "preferences_url": "https://example.com/api/v1/newsletter/preferences?token=abc"
+        })
+    }
+
+    #[test]
+    fn weekly_digest_renders_with_zero_markets() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = weekly_digest_data(json!([]), json!([]));
+        let html = engine.render("weekly_digest", &data).unwrap();
+        assert!(html.contains("No markets are open right now"));
+        assert!(!html.contains("Resolved this week"));
+        // Handlebars escapes `=` in attributes; browsers decode it back.
+        assert!(html.contains("token&#x3D;abc"));
+
+        let text = engine.render_text("weekly_digest", &data);
+        assert!(text.contains("No markets are open right now"));
+        assert!(!text.contains("Resolved this week"));
+    }
+
+    #[test]
+    fn weekly_digest_lists_markets_and_resolutions() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = weekly_digest_data(
+            json!([
+                { "title": "Will <BTC> hit $100k?", "odds": "Yes 62% · No 38%", "url": "https://example.com/markets/1" },
+                { "title": "Rain in London?", "odds": "", "url": "https://example.com/markets/2" }
+            ]),
+            json!([{ "title": "Election", "outcome": "No", "url": "https://example.com/markets/3" }]),
+        );
+        let html = engine.render("weekly_digest", &data).unwrap();
+        assert!(!html.contains("<BTC>"), "titles must be escaped");
+        assert!(html.contains("Yes 62% · No 38%"));
+        assert!(html.contains("https://example.com/markets/2"));
+        assert!(html.contains("Resolved this week"));
+        assert!(!html.contains("No markets are open right now"));
+        assert_eq!(
+            engine.get_subject("weekly_digest", &data),
+            "This week on PredictIQ: 12 October 2026"
+        );
+
+        let text = engine.render_text("weekly_digest", &data);
+        assert!(text.contains("- Rain in London?\n  https://example.com/markets/2"));
+        assert!(text.contains("- Election (No)\n  https://example.com/markets/3"));
+    }
+
+    // resolution_reminder
+
+    #[test]
+    fn resolution_reminder_names_the_deadline_and_links_the_market() {
+        let engine = EmailTemplateEngine::new().unwrap();
+        let data = json!({
+            "market_title": "Will <BTC> hit $100k?",
+            "market_url": "https://example.com/markets/7",
+            "deadline": "18 October 2026, 12:00 UTC",
+            "lead_hours": 6
+        });
+        let html = engine.render("resolution_reminder", &data).unwrap();
+        assert!(!html.contains("<BTC>"), "titles must be escaped");
+        assert!(html.contains("18 October 2026, 12:00 UTC"));
+        assert!(html.contains("about 6 hours"));
+        assert!(html.contains("https://example.com/markets/7"));
+        assert_eq!(
+            engine.get_subject("resolution_reminder", &data),
+            "Resolution due soon: Will <BTC> hit $100k?"
+        );
+
+        let text = engine.render_text("resolution_reminder", &data);
+        assert!(text.contains("about 6 hours"));
+        assert!(text.contains("Open the market: https://example.com/markets/7"));
+    }
+
     // ── Startup validation sanity check ──────────────────────────────────────
 
     #[test]
@@ -6,8 +6,24 @@ use uuid::Uuid;
 pub enum EmailJobType {
     NewsletterConfirmation,
     WaitlistConfirmation,
+    WaitlistInvitation,
     ContactFormAutoResponse,
+    ContactFormNotification,
     WelcomeEmail,
+    MarketNotification,
+    /// One recipient of a bulk campaign; see [`crate::campaign`].
+    Campaign,
+    /// One recipient of the weekly digest; see [`crate::digest`].
+    Digest,
+    /// Verification link for a wallet's notification email; see
+    /// [`crate::user_notifications`].
+    NotificationEmailConfirmation,
+    /// A watched transaction reached a final status; see
+    /// [`crate::tx_watch`].
+    TxFinalized,
+    /// A market creator's resolution deadline is approaching; see
+    /// [`crate::resolution_reminder`].
+    ResolutionReminder,
     Custom(String),
 }
 
@@ -16,8 +32,16 @@ impl EmailJobType {
         match self {
             Self::NewsletterConfirmation => "newsletter_confirmation",
             Self::WaitlistConfirmation => "waitlist_confirmation",
+            Self::WaitlistInvitation => "waitlist_invitation",
             Self::ContactFormAutoResponse => "contact_form_auto_response",
+            Self::ContactFormNotification => "contact_form_notification",
             Self::WelcomeEmail => "welcome_email",
+            Self::MarketNotification => "market_notification",
+            Self::Campaign => "campaign",
+            Self::Digest => "digest",
+            Self::NotificationEmailConfirmation => "notification_email_confirmation",
+            Self::TxFinalized => "tx_finalized",
+            Self::ResolutionReminder => "resolution_reminder",
             Self::Custom(s) => s,
         }
     }
@@ -30,6 +54,8 @@ pub enum EmailJobStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Exhausted `max_attempts`; waits for an admin retry.
+    DeadLetter,
 }
 
 impl EmailJobStatus {
@@ -40,6 +66,7 @@ impl EmailJobStatus {
             Self::Completed => "completed",
             Self::Failed => "failed",
             Self::Cancelled => "cancelled",
+            Self::DeadLetter => "dead_letter",
         }
     }
 }
@@ -69,7 +96,7 @@ impl EmailEventType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SuppressionType {
     Bounce,
     Complaint,
@@ -106,6 +133,8 @@ pub struct EmailJob {
     pub error_message: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set on jobs enqueued by a bulk campaign; see [`crate::campaign`].
+    pub campaign_id: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
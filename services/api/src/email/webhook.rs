@@ -1,16 +1,44 @@
 use anyhow::{anyhow, Result};
-use axum::{extract::State, http::{HeaderMap, StatusCode}, response::IntoResponse, Json};
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use p256::ecdsa::{signature::Verifier as _, Signature, VerifyingKey};
+use p256::pkcs8::DecodePublicKey as _;
 use serde::{Deserialize, Serialize};
 use std::{sync::Arc, time::Duration};
 
 use crate::cache::RedisCache;
 use crate::db::Database;
 use crate::email::types::SuppressionType;
+use crate::log_redact::mask_email;
 
 /// Maximum allowed raw webhook body size: 64 KiB.
 /// Payloads larger than this are rejected before any parsing occurs.
 pub const MAX_WEBHOOK_PAYLOAD_BYTES: usize = 64 * 1024;
 
+/// Header carrying the base64 DER ECDSA P-256 signature of `timestamp || body`.
+pub const SIGNATURE_HEADER: &str = "x-twilio-email-event-webhook-signature";
+
+/// Header carrying the Unix timestamp (seconds) the payload was signed at.
+pub const TIMESTAMP_HEADER: &str = "x-twilio-email-event-webhook-timestamp";
+
+/// Signed payloads older than this are rejected as stale.
+pub const SIGNATURE_MAX_AGE_SECS: i64 = 600;
+
+/// `sg_event_id`s are remembered for between one and two of these windows,
+/// comfortably longer than [`SIGNATURE_MAX_AGE_SECS`], so a captured payload
+/// cannot be replayed while its signature is still fresh.
+const EVENT_ID_DEDUP_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Redis key prefix of the windowed `sg_event_id` sets.
+const EVENT_ID_SET: &str = "webhook_event_ids";
+
 /// Maximum length for free-text fields stored in the database (reason, response, etc.).
 const MAX_TEXT_FIELD_LEN: usize = 1024;
 
@@ -26,6 +54,8 @@ struct RawSendGridEvent {
     timestamp: Option<serde_json::Value>,
     #[serde(rename = "sg_message_id")]
     message_id: Option<String>,
+    #[serde(rename = "sg_event_id")]
+    event_id: Option<String>,
     reason: Option<String>,
     status: Option<String>,
     response: Option<String>,
@@ -44,6 +74,9 @@ pub struct SendGridEvent {
     pub event: String,
     pub timestamp: i64,
     pub message_id: Option<String>,
+    /// SendGrid's unique id for this event (`sg_event_id`); stable across
+    /// retried deliveries of the same event.
+    pub event_id: Option<String>,
     pub reason: Option<String>,
     pub status: Option<String>,
     pub response: Option<String>,
@@ -145,6 +178,7 @@ fn sanitize_event(raw: RawSendGridEvent) -> Result<SendGridEvent> {
         event,
         timestamp,
         message_id: sanitize_id(raw.message_id),
+        event_id: sanitize_id(raw.event_id),
         reason: sanitize_opt(raw.reason),
         status: sanitize_id(raw.status),
         response: sanitize_opt(raw.response),
@@ -170,6 +204,119 @@ pub fn parse_and_sanitize_events(raw_bytes: &[u8]) -> Result<Vec<SendGridEvent>>
     Ok(events)
 }
 
+// ── signature verification ───────────────────────────────────────────────────
+
+/// Why a webhook request failed signature verification. Every variant is
+/// answered with 401.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureError {
+    /// The signature or timestamp header is absent.
+    Missing,
+    /// The timestamp is unparseable, in the future, or older than
+    /// [`SIGNATURE_MAX_AGE_SECS`].
+    Stale,
+    /// The signature does not verify against the configured key.
+    Invalid,
+}
+
+/// Parse the verification key from the SendGrid console: base64 of a DER
+/// P-256 `SubjectPublicKeyInfo`.
+pub fn parse_public_key(raw: &str) -> Result<VerifyingKey> {
+    let der = BASE64
+        .decode(raw.trim())
+        .map_err(|e| anyhow!("public key is not valid base64: {e}"))?;
+    VerifyingKey::from_public_key_der(&der).map_err(|e| anyhow!("invalid P-256 public key: {e}"))
+}
+
+/// Verify SendGrid's signature over `timestamp || body` and reject payloads
+/// signed more than [`SIGNATURE_MAX_AGE_SECS`] before `now`, or after it.
+pub fn verify_signature(
+    key: &VerifyingKey,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+) -> std::result::Result<(), SignatureError> {
+    if timestamp.is_empty() || signature.is_empty() {
+        return Err(SignatureError::Missing);
+    }
+
+    let signed_at: i64 = timestamp.parse().map_err(|_| SignatureError::Stale)?;
+    let age_secs = now - signed_at;
+    if !(0..=SIGNATURE_MAX_AGE_SECS).contains(&age_secs) {
+        return Err(SignatureError::Stale);
+    }
+
+    let signature = BASE64
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_der(&bytes).ok())
+        .ok_or(SignatureError::Invalid)?;
+
+    let mut message = Vec::with_capacity(timestamp.len() + body.len());
+    message.extend_from_slice(timestamp.as_bytes());
+    message.extend_from_slice(body);
+    key.verify(&message, &signature)
+        .map_err(|_| SignatureError::Invalid)
+}
+
+/// State for [`signature_middleware`].
+#[derive(Clone)]
+pub struct WebhookSignatureConfig {
+    pub public_key: Option<VerifyingKey>,
+    /// Accept unsigned requests when no key is configured. Only ever set in
+    /// local development.
+    pub allow_unsigned: bool,
+}
+
+/// Reject `POST /webhooks/sendgrid` requests whose ECDSA signature is
+/// missing, invalid, or stale with 401 before the body is parsed.
+///
+/// # OpenAPI policy
+/// Route: `POST /webhooks/sendgrid`
+/// Auth: provider-signed (SendGrid ECDSA P-256) — no API key required.
+pub async fn signature_middleware(
+    State(config): State<WebhookSignatureConfig>,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, StatusCode> {
+    let Some(key) = config.public_key else {
+        if config.allow_unsigned {
+            return Ok(next.run(request).await);
+        }
+        tracing::warn!("sendgrid webhook rejected: SENDGRID_WEBHOOK_PUBLIC_KEY is not configured");
+        return Err(StatusCode::UNAUTHORIZED);
+    };
+
+    // Scoped so the borrow of the request (whose body isn't `Sync`) ends
+    // before the await below and the middleware future stays `Send`.
+    let (signature, timestamp) = {
+        let header = |name: &str| {
+            request
+                .headers()
+                .get(name)
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("")
+                .to_string()
+        };
+        (header(SIGNATURE_HEADER), header(TIMESTAMP_HEADER))
+    };
+
+    let (parts, body) = request.into_parts();
+    let bytes = axum::body::to_bytes(body, MAX_WEBHOOK_PAYLOAD_BYTES)
+        .await
+        .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+
+    if let Err(reason) =
+        verify_signature(&key, &timestamp, &bytes, &signature, chrono::Utc::now().timestamp())
+    {
+        tracing::warn!(?reason, "sendgrid webhook rejected: signature verification failed");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(next.run(Request::from_parts(parts, Body::from(bytes))).await)
+}
+
 // ── domain logic ─────────────────────────────────────────────────────────────
 
 #[derive(Clone)]
@@ -219,12 +366,31 @@ impl WebhookHandler {
 
         tracing::info!(
             event_type,
-            email,
+            email = %mask_email(email),
             message_id,
             "Processing SendGrid event"
         );
 
-        // Primary replay guard: atomic Redis nonce using server-side received_at.
+        // SendGrid retries deliveries it did not see acknowledged; the same
+        // `sg_event_id` must not be counted twice. Redis errors fall through to
+        // the nonce and DB checks below.
+        let mut claimed_event_id = None;
+        if let Some(event_id) = event.event_id.as_deref() {
+            match self
+                .cache
+                .add_to_windowed_set(EVENT_ID_SET, event_id, EVENT_ID_DEDUP_WINDOW)
+                .await
+            {
+                Ok(false) => {
+                    tracing::info!(event_id, event_type, "Duplicate SendGrid event skipped");
+                    return Ok(());
+                }
+                Ok(true) => claimed_event_id = Some(event_id),
+                Err(e) => tracing::warn!(event_id, error = %e, "SendGrid event dedup unavailable"),
+            }
+        }
+
+        // Replay guard: atomic Redis nonce using server-side received_at.
         // The SendGrid-supplied timestamp is NOT used here — it originates from an
         // external source and can be forged to bypass window-based checks.
         let nonce_key = format!(
@@ -240,145 +406,149 @@ impl WebhookHandler {
                 "Replay attack detected (Redis nonce) for event: {} {} {}",
                 message_id.unwrap_or(""),
                 event_type,
-                email
+                mask_email(email)
             );
             return Ok(());
         }
 
+        let result = self.record_event(&event).await;
+        if result.is_err() {
+            // Only a processed event is a duplicate: let SendGrid's retry of
+            // this one through both guards.
+            if let Some(event_id) = claimed_event_id {
+                if let Err(e) = self
+                    .cache
+                    .remove_from_windowed_set(EVENT_ID_SET, event_id, EVENT_ID_DEDUP_WINDOW)
+                    .await
+                {
+                    tracing::warn!(event_id, error = %e, "failed to release SendGrid event id");
+                }
+            }
+            if let Err(e) = self.cache.del(&nonce_key).await {
+                tracing::warn!(error = %e, "failed to release SendGrid event nonce");
+            }
+        }
+        result
+    }
+
+    /// Persist `event` and apply its effect, unless the DB already has it.
+    async fn record_event(&self, event: &SendGridEvent) -> Result<()> {
+        let event_type = event.event.as_str();
+        let email = event.email.as_str();
+        let message_id = event.message_id.as_deref();
+
         // Secondary guard: DB dedup for events that arrive after the Redis TTL expires.
         if self.db.email_event_exists(message_id, event_type, email).await? {
             tracing::warn!(
                 "Duplicate event detected (DB) for event: {} {} {}",
                 message_id.unwrap_or(""),
                 event_type,
-                email
+                mask_email(email)
             );
             return Ok(());
         }
 
-        // Persist the sanitized, allow-listed event.
+        // The event row, its suppression and its counter are written in one
+        // transaction: a failure leaves nothing behind for the retry to trip
+        // over, and a success cannot be counted again.
         // Only the fields present in `SendGridEvent` are serialized — the raw
         // `extra` catch-all from the old schema is intentionally absent.
+        let effect = EventEffect::of(event);
         self.db
-            .email_create_event(
-                None,
+            .email_record_webhook_event(
                 message_id,
                 event_type,
                 email,
-                serde_json::to_value(&event)?,
+                serde_json::to_value(event)?,
+                &effect,
             )
             .await?;
 
-        // Handle specific event types
-        match event_type {
-            "delivered" => {
-                self.db
-                    .email_increment_analytics_counter("delivered", None)
-                    .await?;
+        match (event_type, &effect.suppression) {
+            ("bounce" | "dropped", Some(suppression)) => tracing::warn!(
+                email = %mask_email(email),
+                bounce_type = suppression.bounce_type,
+                reason = suppression.reason,
+                "Email bounced"
+            ),
+            ("spamreport", _) => {
+                tracing::warn!(email = %mask_email(email), "Spam complaint received")
             }
-            "open" => {
-                self.db
-                    .email_increment_analytics_counter("opened", None)
-                    .await?;
+            ("unsubscribe", _) => {
+                let _ = self.db.newsletter_unsubscribe(email).await;
+                tracing::info!(email = %mask_email(email), "User unsubscribed");
             }
-            "click" => {
-                self.db
-                    .email_increment_analytics_counter("clicked", None)
-                    .await?;
-            }
-            "bounce" | "dropped" => {
-                self.handle_bounce(&event).await?;
-            }
-            "spamreport" => {
-                self.handle_complaint(&event).await?;
-            }
-            "unsubscribe" => {
-                self.handle_unsubscribe(&event).await?;
-            }
-            _ => {
-                tracing::debug!(event_type, "Unhandled SendGrid event type");
+            _ if effect.counter.is_none() => {
+                tracing::debug!(event_type, "Unhandled SendGrid event type")
             }
+            _ => {}
         }
 
         Ok(())
     }
+}
 
-    async fn handle_bounce(&self, event: &SendGridEvent) -> Result<()> {
-        let bounce_type = event
-            .bounce_classification
-            .as_deref()
-            .or(event.status.as_deref())
-            .unwrap_or("unknown");
-
-        let reason = event
-            .reason
-            .as_deref()
-            .or(event.response.as_deref())
-            .unwrap_or("No reason provided");
-
-        self.db
-            .email_add_suppression(
-                &event.email,
-                SuppressionType::Bounce.as_str(),
-                Some(reason),
-                Some(bounce_type),
-            )
-            .await?;
-
-        self.db
-            .email_increment_analytics_counter("bounced", None)
-            .await?;
-
-        tracing::warn!(
-            email = %event.email,
-            bounce_type,
-            reason,
-            "Email bounced"
-        );
-
-        Ok(())
-    }
-
-    async fn handle_complaint(&self, event: &SendGridEvent) -> Result<()> {
-        let reason = event.reason.as_deref().unwrap_or("Spam complaint");
-
-        self.db
-            .email_add_suppression(
-                &event.email,
-                SuppressionType::Complaint.as_str(),
-                Some(reason),
-                None,
-            )
-            .await?;
-
-        self.db
-            .email_increment_analytics_counter("complained", None)
-            .await?;
-
-        tracing::warn!(email = %event.email, "Spam complaint received");
-
-        Ok(())
-    }
-
-    async fn handle_unsubscribe(&self, event: &SendGridEvent) -> Result<()> {
-        self.db
-            .email_add_suppression(
-                &event.email,
-                SuppressionType::Unsubscribe.as_str(),
-                Some("User unsubscribed via email link"),
-                None,
-            )
-            .await?;
-
-        let _ = self.db.newsletter_unsubscribe(&event.email).await;
-
-        self.db
-            .email_increment_analytics_counter("unsubscribed", None)
-            .await?;
+/// A suppression a webhook event adds for its recipient.
+#[derive(Debug, PartialEq)]
+pub struct Suppression<'a> {
+    pub kind: SuppressionType,
+    pub reason: Option<&'a str>,
+    pub bounce_type: Option<&'a str>,
+}
 
-        tracing::info!(email = %event.email, "User unsubscribed");
+/// What a webhook event changes besides its own `email_events` row.
+#[derive(Debug, PartialEq)]
+pub struct EventEffect<'a> {
+    /// `email_analytics` counter to increment.
+    pub counter: Option<&'static str>,
+    pub suppression: Option<Suppression<'a>>,
+}
 
-        Ok(())
+impl<'a> EventEffect<'a> {
+    pub fn of(event: &'a SendGridEvent) -> Self {
+        let (counter, suppression) = match event.event.as_str() {
+            "delivered" => (Some("delivered"), None),
+            "open" => (Some("opened"), None),
+            "click" => (Some("clicked"), None),
+            "bounce" | "dropped" => (
+                Some("bounced"),
+                Some(Suppression {
+                    kind: SuppressionType::Bounce,
+                    reason: Some(
+                        event
+                            .reason
+                            .as_deref()
+                            .or(event.response.as_deref())
+                            .unwrap_or("No reason provided"),
+                    ),
+                    bounce_type: Some(
+                        event
+                            .bounce_classification
+                            .as_deref()
+                            .or(event.status.as_deref())
+                            .unwrap_or("unknown"),
+                    ),
+                }),
+            ),
+            "spamreport" => (
+                Some("complained"),
+                Some(Suppression {
+                    kind: SuppressionType::Complaint,
+                    reason: Some(event.reason.as_deref().unwrap_or("Spam complaint")),
+                    bounce_type: None,
+                }),
+            ),
+            "unsubscribe" => (
+                Some("unsubscribed"),
+                Some(Suppression {
+                    kind: SuppressionType::Unsubscribe,
+                    reason: Some("User unsubscribed via email link"),
+                    bounce_type: None,
+                }),
+            ),
+            _ => (None, None),
+        };
+        Self { counter, suppression }
     }
 }
 
@@ -388,10 +558,30 @@ pub struct WebhookResponse {
     pub errors: Option<Vec<String>>,
 }
 
+impl WebhookResponse {
+    /// The batch as a response: a 500 if any event failed, since SendGrid
+    /// only retries on 5xx. The events that did go through are skipped on
+    /// the retry as duplicates.
+    fn into_result(self) -> std::result::Result<Self, (StatusCode, String)> {
+        match &self.errors {
+            Some(errors) => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!(
+                    "{} of {} events failed: {}",
+                    errors.len(),
+                    errors.len() + self.processed,
+                    errors.join("; ")
+                ),
+            )),
+            None => Ok(self),
+        }
+    }
+}
+
 /// Axum handler for SendGrid webhooks.
 ///
-/// The raw body size limit is enforced at the middleware layer
-/// (see `sendgrid_webhook_middleware` in `security.rs`).  This handler
+/// The signature and raw body size are checked at the middleware layer
+/// (see [`signature_middleware`]).  This handler
 /// deserializes the already-size-checked bytes through the sanitizing
 /// `SendGridEvent` deserializer, so no raw event data ever reaches the DB.
 pub async fn sendgrid_webhook_handler(
@@ -400,7 +590,7 @@ pub async fn sendgrid_webhook_handler(
     Json(events): Json<Vec<SendGridEvent>>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     match handler.handle_sendgrid_webhook(events).await {
-        Ok(response) => Ok((StatusCode::OK, Json(response))),
+        Ok(response) => Ok((StatusCode::OK, Json(response.into_result()?))),
         Err(e) => {
             tracing::error!("Webhook processing error: {}", e);
             Err((
@@ -580,4 +770,221 @@ mod tests {
         assert!(output.contains("bold italic"));
         assert!(output.contains("text"));
     }
+
+    // ── signature verification ───────────────────────────────────────────────
+
+    use p256::ecdsa::{signature::Signer as _, SigningKey};
+    use p256::pkcs8::EncodePublicKey as _;
+
+    const NOW: i64 = 1_700_000_600;
+
+    /// A delivery as SendGrid posts it, retried events included.
+    const FIXTURE_PAYLOAD: &str = r#"[{"email":"user@example.com","event":"delivered","timestamp":1700000000,"sg_event_id":"sg-evt-1","sg_message_id":"msg-1"},{"email":"user@example.com","event":"open","timestamp":1700000300,"sg_event_id":"sg-evt-2","sg_message_id":"msg-1"}]"#;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32].into()).unwrap()
+    }
+
+    fn verifying_key() -> VerifyingKey {
+        *signing_key().verifying_key()
+    }
+
+    /// Sign as SendGrid does: ECDSA P-256 over SHA-256, base64 DER.
+    fn sign(timestamp: &str, body: &[u8]) -> String {
+        let mut message = timestamp.as_bytes().to_vec();
+        message.extend_from_slice(body);
+        let signature: Signature = signing_key().sign(&message);
+        BASE64.encode(signature.to_der().as_bytes())
+    }
+
+    #[test]
+    fn test_valid_signature_accepted() {
+        let key = verifying_key();
+        let ts = (NOW - 30).to_string();
+        let sig = sign(&ts, FIXTURE_PAYLOAD.as_bytes());
+        assert_eq!(verify_signature(&key, &ts, FIXTURE_PAYLOAD.as_bytes(), &sig, NOW), Ok(()));
+    }
+
+    #[test]
+    fn test_tampered_body_rejected() {
+        let key = verifying_key();
+        let ts = (NOW - 30).to_string();
+        let sig = sign(&ts, FIXTURE_PAYLOAD.as_bytes());
+        let tampered = FIXTURE_PAYLOAD.replace("delivered", "bounce");
+        assert_eq!(
+            verify_signature(&key, &ts, tampered.as_bytes(), &sig, NOW),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_signature_bound_to_timestamp() {
+        // Re-stamping a captured payload with a fresh timestamp breaks the signature.
+        let key = verifying_key();
+        let sig = sign(&(NOW - 3_600).to_string(), FIXTURE_PAYLOAD.as_bytes());
+        assert_eq!(
+            verify_signature(&key, &NOW.to_string(), FIXTURE_PAYLOAD.as_bytes(), &sig, NOW),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_stale_and_future_timestamps_rejected() {
+        let key = verifying_key();
+        let body = FIXTURE_PAYLOAD.as_bytes();
+        let at = |ts: i64| verify_signature(&key, &ts.to_string(), body, &sign(&ts.to_string(), body), NOW);
+
+        assert_eq!(at(NOW - SIGNATURE_MAX_AGE_SECS), Ok(()));
+        assert_eq!(at(NOW - SIGNATURE_MAX_AGE_SECS - 1), Err(SignatureError::Stale));
+        assert_eq!(at(NOW + 1), Err(SignatureError::Stale));
+        assert_eq!(
+            verify_signature(&key, "yesterday", body, &sign("yesterday", body), NOW),
+            Err(SignatureError::Stale)
+        );
+    }
+
+    #[test]
+    fn test_missing_headers_and_garbage_signature_rejected() {
+        let key = verifying_key();
+        let body = FIXTURE_PAYLOAD.as_bytes();
+        let ts = NOW.to_string();
+        assert_eq!(verify_signature(&key, &ts, body, "", NOW), Err(SignatureError::Missing));
+        assert_eq!(verify_signature(&key, "", body, &sign(&ts, body), NOW), Err(SignatureError::Missing));
+        assert_eq!(verify_signature(&key, &ts, body, "not base64!", NOW), Err(SignatureError::Invalid));
+    }
+
+    #[test]
+    fn test_public_key_parsed_from_der_spki() {
+        let key = verifying_key();
+        let der = key.to_public_key_der().unwrap();
+
+        assert_eq!(parse_public_key(&BASE64.encode(der.as_bytes())).unwrap(), key);
+        assert!(parse_public_key("not base64!").is_err());
+        assert!(parse_public_key(&BASE64.encode([1u8; 16])).is_err());
+        // A bare SEC1 point is not what the SendGrid console publishes.
+        let sec1 = key.to_encoded_point(false);
+        assert!(parse_public_key(&BASE64.encode(sec1.as_bytes())).is_err());
+    }
+
+    #[test]
+    fn test_fixed_width_signature_rejected() {
+        // SendGrid sends DER; an r||s signature is not accepted.
+        let key = verifying_key();
+        let ts = NOW.to_string();
+        let mut message = ts.as_bytes().to_vec();
+        message.extend_from_slice(FIXTURE_PAYLOAD.as_bytes());
+        let signature: Signature = signing_key().sign(&message);
+        let fixed = BASE64.encode(signature.to_bytes());
+        assert_eq!(
+            verify_signature(&key, &ts, FIXTURE_PAYLOAD.as_bytes(), &fixed, NOW),
+            Err(SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_event_id_parsed_from_sg_event_id() {
+        let events = parse_and_sanitize_events(FIXTURE_PAYLOAD.as_bytes()).unwrap();
+        let ids: Vec<_> = events.iter().map(|e| e.event_id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("sg-evt-1"), Some("sg-evt-2")]);
+    }
+
+    fn event(json: &str) -> SendGridEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_any_failed_event_fails_the_batch_with_a_5xx() {
+        let ok = WebhookResponse { processed: 2, errors: None };
+        assert_eq!(ok.into_result().unwrap().processed, 2);
+
+        let partial = WebhookResponse {
+            processed: 1,
+            errors: Some(vec!["Event bounce: db down".to_string()]),
+        };
+        let (status, message) = partial.into_result().unwrap_err();
+        assert!(status.is_server_error());
+        assert_eq!(message, "1 of 2 events failed: Event bounce: db down");
+    }
+
+    #[test]
+    fn test_event_effects() {
+        let delivered = event(r#"{"email":"a@example.com","event":"delivered","timestamp":1}"#);
+        assert_eq!(
+            EventEffect::of(&delivered),
+            EventEffect { counter: Some("delivered"), suppression: None }
+        );
+
+        let bounce = event(
+            r#"{"email":"a@example.com","event":"bounce","timestamp":1,"reason":"mailbox full","status":"5.2.2"}"#,
+        );
+        assert_eq!(
+            EventEffect::of(&bounce),
+            EventEffect {
+                counter: Some("bounced"),
+                suppression: Some(Suppression {
+                    kind: SuppressionType::Bounce,
+                    reason: Some("mailbox full"),
+                    bounce_type: Some("5.2.2"),
+                }),
+            }
+        );
+
+        let unknown = event(r#"{"email":"a@example.com","event":"deferred","timestamp":1}"#);
+        assert_eq!(EventEffect::of(&unknown), EventEffect { counter: None, suppression: None });
+    }
+
+    async fn post_through_middleware(
+        config: WebhookSignatureConfig,
+        body: &str,
+        timestamp: &str,
+        signature: &str,
+    ) -> StatusCode {
+        use axum::{middleware, routing::post, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/webhooks/sendgrid", post(|| async { StatusCode::OK }))
+            .layer(middleware::from_fn_with_state(config, signature_middleware));
+        let request = axum::http::Request::builder()
+            .method("POST")
+            .uri("/webhooks/sendgrid")
+            .header(TIMESTAMP_HEADER, timestamp)
+            .header(SIGNATURE_HEADER, signature)
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rejects_tampered_body_with_401() {
+        let config = WebhookSignatureConfig {
+            public_key: Some(verifying_key()),
+            allow_unsigned: false,
+        };
+        let ts = chrono::Utc::now().timestamp().to_string();
+        let sig = sign(&ts, FIXTURE_PAYLOAD.as_bytes());
+
+        assert_eq!(
+            post_through_middleware(config.clone(), FIXTURE_PAYLOAD, &ts, &sig).await,
+            StatusCode::OK
+        );
+        let tampered = FIXTURE_PAYLOAD.replace("user@example.com", "victim@example.com");
+        assert_eq!(
+            post_through_middleware(config, &tampered, &ts, &sig).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_requires_key_outside_development() {
+        let unsigned = |allow_unsigned| WebhookSignatureConfig { public_key: None, allow_unsigned };
+        assert_eq!(
+            post_through_middleware(unsigned(false), FIXTURE_PAYLOAD, "", "").await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            post_through_middleware(unsigned(true), FIXTURE_PAYLOAD, "", "").await,
+            StatusCode::OK
+        );
+    }
 }
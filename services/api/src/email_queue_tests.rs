@@ -0,0 +1,279 @@
+#[cfg(test)]
+mod email_queue_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::{
+        email::{
+            queue::{EmailQueue, FailureOutcome},
+            service::{EmailService, IdempotencyConfig},
+            types::EmailJobType,
+        },
+        handlers::{email_dead_letter_list, email_dead_letter_retry},
+        shutdown::ShutdownCoordinator,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Recipients live in a reserved range so cleanup never touches jobs
+    /// created by other tests.
+    const RECIPIENT_PREFIX: &str = "queue-96";
+
+    /// Above anything the application enqueues, so these jobs are claimed
+    /// before any stray pending rows in a shared test database.
+    const TEST_PRIORITY: i32 = 100;
+
+    fn recipient(n: usize) -> String {
+        format!("{RECIPIENT_PREFIX}{n:02}@example.com")
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/dead-letter", get(email_dead_letter_list))
+            .route("/dead-letter/:job_id/retry", post(email_dead_letter_retry))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    async fn enqueue(queue: &EmailQueue, n: usize) -> Uuid {
+        queue
+            .enqueue(
+                EmailJobType::NewsletterConfirmation,
+                &recipient(n),
+                "newsletter_confirmation",
+                json!({ "confirm_url": format!("https://example.com/confirm?token={n}") }),
+                TEST_PRIORITY,
+            )
+            .await
+            .unwrap()
+    }
+
+    async fn status(state: &crate::AppState, job_id: Uuid) -> (String, i32) {
+        let job = state.db.email_get_job(job_id).await.unwrap().expect("job exists");
+        (job.status, job.attempts)
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM email_jobs WHERE recipient_email LIKE $1")
+            .bind(format!("{RECIPIENT_PREFIX}%@example.com"))
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Two claimers racing over the same table never receive the same job.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_concurrent_claims_are_disjoint() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let mut ours = HashSet::new();
+        for n in 0..20 {
+            ours.insert(enqueue(&state.email_queue, n).await);
+        }
+
+        let claimers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = state.email_queue.clone();
+                tokio::spawn(async move {
+                    let mut claimed = Vec::new();
+                    while let Some(job) = queue.claim_next().await.unwrap() {
+                        claimed.push(job.id);
+                    }
+                    claimed
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for claimer in claimers {
+            for id in claimer.await.unwrap() {
+                assert!(seen.insert(id), "job {id} was claimed twice");
+            }
+        }
+        assert!(ours.is_subset(&seen), "every enqueued job is claimed");
+
+        for id in &ours {
+            assert_eq!(status(&state, *id).await.0, "processing");
+        }
+
+        cleanup(&state).await;
+    }
+
+    /// Two workers draining the queue against a mocked SendGrid send every
+    /// job exactly once.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_two_workers_send_each_job_once() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let sendgrid = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v3/mail/send"))
+            .respond_with(ResponseTemplate::new(202).insert_header("x-message-id", "queue-test"))
+            .mount(&sendgrid)
+            .await;
+
+        let mut config = state.config.clone();
+        config.sendgrid_api_key = Some("test-key".to_string());
+        config.from_email = Some("from@example.com".to_string());
+        let service = EmailService::with_cache_and_metrics(config, None, IdempotencyConfig::default(), None)
+            .unwrap()
+            .with_base_url(sendgrid.uri());
+
+        let mut ours = Vec::new();
+        for n in 0..12 {
+            ours.push(enqueue(&state.email_queue, n).await);
+        }
+
+        let coordinator = ShutdownCoordinator::new(2);
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                let queue = state.email_queue.clone();
+                let service = service.clone();
+                let shutdown = coordinator.subscribe();
+                let coordinator = coordinator.clone();
+                tokio::spawn(async move {
+                    queue.start_worker(service, shutdown, coordinator, 300, None).await;
+                })
+            })
+            .collect();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(30);
+        loop {
+            let mut done = 0;
+            for id in &ours {
+                if status(&state, *id).await.0 == "completed" {
+                    done += 1;
+                }
+            }
+            if done == ours.len() {
+                break;
+            }
+            assert!(tokio::time::Instant::now() < deadline, "workers did not drain the queue");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        coordinator.token().cancel();
+        for worker in workers {
+            worker.await.unwrap();
+        }
+
+        let mut sends: HashMap<String, usize> = HashMap::new();
+        for request in sendgrid.received_requests().await.unwrap() {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            let to = body["personalizations"][0]["to"][0]["email"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            if to.starts_with(RECIPIENT_PREFIX) {
+                *sends.entry(to).or_default() += 1;
+            }
+        }
+        assert_eq!(sends.len(), ours.len());
+        assert!(sends.values().all(|&count| count == 1), "duplicate sends: {sends:?}");
+
+        cleanup(&state).await;
+    }
+
+    /// Failures back off exponentially with jitter, then park the job in
+    /// `dead_letter`, where the admin endpoints list and retry it.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_failures_back_off_then_dead_letter_and_retry() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let job_id = enqueue(&state.email_queue, 0).await;
+        let job = state.email_queue.claim_next().await.unwrap().expect("job is due");
+        assert_eq!(job.id, job_id);
+
+        // First failure: due again in 2^1 minutes, give or take 20%.
+        let before = chrono::Utc::now();
+        let outcome = state.email_queue.mark_failed(&job, "sendgrid 500").await.unwrap();
+        let FailureOutcome::Rescheduled(retry_at) = outcome else {
+            panic!("expected a reschedule, got {outcome:?}");
+        };
+        let delay = (retry_at - before).num_seconds();
+        assert!((96..=145).contains(&delay), "backoff {delay}s outside 120s ± 20%");
+        assert_eq!(status(&state, job_id).await, ("pending".to_string(), 1));
+        assert!(state.email_queue.claim_next().await.unwrap().map(|j| j.id) != Some(job_id));
+
+        // Final failure: no attempts left.
+        sqlx::query("UPDATE email_jobs SET attempts = max_attempts - 1, status = 'processing' WHERE id = $1")
+            .bind(job_id)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        let job = state.db.email_get_job(job_id).await.unwrap().unwrap();
+        let outcome = state.email_queue.mark_failed(&job, "sendgrid 500").await.unwrap();
+        assert_eq!(outcome, FailureOutcome::DeadLettered);
+        assert_eq!(status(&state, job_id).await.0, "dead_letter");
+
+        let (code, body) = send(
+            &state,
+            Request::builder().uri("/dead-letter?limit=100").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::OK);
+        let listed = body["items"].as_array().unwrap();
+        let entry = listed
+            .iter()
+            .find(|job| job["id"] == job_id.to_string())
+            .expect("dead-lettered job is listed");
+        assert_eq!(entry["error_message"], "sendgrid 500");
+
+        let retry = || {
+            Request::builder()
+                .method("POST")
+                .uri(format!("/dead-letter/{job_id}/retry"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let (code, body) = send(&state, retry()).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["requeued"], true);
+        assert_eq!(status(&state, job_id).await, ("pending".to_string(), 0));
+
+        let (code, _) = send(&state, retry()).await;
+        assert_eq!(code, StatusCode::NOT_FOUND);
+
+        cleanup(&state).await;
+    }
+
+}
@@ -0,0 +1,120 @@
+//! Conditional GET for cacheable read endpoints.
+//!
+//! Handlers get a strong ETag for their payload from
+//! [`crate::cache::RedisCache::get_or_set_json_tagged`], which computes it once
+//! per cache fill, and answer through [`json_response`]: `304 Not Modified`
+//! with no body when `If-None-Match` matches, otherwise `200` with the JSON
+//! body. Both carry the ETag and a `Cache-Control: public, max-age` matching
+//! the endpoint's Redis TTL.
+
+use std::time::Duration;
+
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use crate::security::integrity_hash;
+
+/// Quoted strong ETag for a serialized payload.
+pub fn strong_etag(bytes: &[u8]) -> String {
+    format!("\"{}\"", &integrity_hash(bytes)[..32])
+}
+
+/// ETag for a response derived from a cached payload, e.g. one page of it.
+/// `parts` must cover every input besides the payload that shapes the body.
+pub fn derived_etag(etag: &str, parts: &[&str]) -> String {
+    let mut input = etag.to_string();
+    for part in parts {
+        input.push('\n');
+        input.push_str(part);
+    }
+    strong_etag(input.as_bytes())
+}
+
+/// Whether `If-None-Match` lists `etag` or is `*`. Comparison is weak, as
+/// RFC 9110 requires for `If-None-Match`, so a `W/` prefix still matches.
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// `304` when the client already has `etag`, otherwise `200` with `body`.
+pub fn json_response<T: Serialize>(headers: &HeaderMap, etag: &str, max_age: Duration, body: T) -> Response {
+    let mut response = if if_none_match(headers, etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (StatusCode::OK, Json(body)).into_response()
+    };
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&format!("public, max-age={}", max_age.as_secs())) {
+        response_headers.insert(header::CACHE_CONTROL, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_if_none_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn etag_is_quoted_and_content_addressed() {
+        let a = strong_etag(br#"{"total":1}"#);
+        assert!(a.starts_with('"') && a.ends_with('"'));
+        assert_eq!(a, strong_etag(br#"{"total":1}"#));
+        assert_ne!(a, strong_etag(br#"{"total":2}"#));
+    }
+
+    #[test]
+    fn derived_etag_depends_on_every_part() {
+        let base = strong_etag(b"payload");
+        assert_ne!(derived_etag(&base, &["0", "20"]), derived_etag(&base, &["20", "20"]));
+        assert_eq!(derived_etag(&base, &["0", "20"]), derived_etag(&base, &["0", "20"]));
+    }
+
+    #[test]
+    fn if_none_match_accepts_lists_weak_tags_and_wildcard() {
+        let etag = strong_etag(b"x");
+        assert!(if_none_match(&with_if_none_match(&etag), &etag));
+        assert!(if_none_match(&with_if_none_match(&format!("\"other\", W/{etag}")), &etag));
+        assert!(if_none_match(&with_if_none_match("*"), &etag));
+        assert!(!if_none_match(&with_if_none_match("\"other\""), &etag));
+        assert!(!if_none_match(&HeaderMap::new(), &etag));
+    }
+
+    #[tokio::test]
+    async fn matching_request_gets_304_without_body() {
+        use http_body_util::BodyExt;
+
+        let etag = strong_etag(b"x");
+        let max_age = Duration::from_secs(120);
+
+        let fresh = json_response(&HeaderMap::new(), &etag, max_age, serde_json::json!({ "a": 1 }));
+        assert_eq!(fresh.status(), StatusCode::OK);
+        assert_eq!(fresh.headers()[header::ETAG], etag.as_str());
+        assert_eq!(fresh.headers()[header::CACHE_CONTROL], "public, max-age=120");
+
+        let cached = json_response(&with_if_none_match(&etag), &etag, max_age, serde_json::json!({ "a": 1 }));
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag.as_str());
+        assert_eq!(cached.headers()[header::CACHE_CONTROL], "public, max-age=120");
+        assert!(cached.into_body().collect().await.unwrap().to_bytes().is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod etag_tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{cache::InvalidationTag, handlers::statistics};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/api/v1/statistics", get(statistics))
+            .with_state(state)
+    }
+
+    /// Status, ETag, and body length of one GET.
+    async fn get_statistics(state: &Arc<crate::AppState>, if_none_match: Option<&str>) -> (StatusCode, String, usize) {
+        let mut builder = Request::builder().uri("/api/v1/statistics");
+        if let Some(etag) = if_none_match {
+            builder = builder.header(header::IF_NONE_MATCH, etag);
+        }
+        let response = app(Arc::clone(state))
+            .oneshot(builder.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .expect("ETag header")
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert_eq!(response.headers()[header::CACHE_CONTROL], "public, max-age=300");
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, etag, bytes.len())
+    }
+
+    fn invalidation(state: &crate::AppState, market_id: i64) -> InvalidationTag {
+        InvalidationTag::MarketResolved {
+            market_id,
+            network: state.config.network_name().to_string(),
+            featured_limit: state.config.featured_limit,
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A repeat request with the ETag gets an empty 304; once the data
+    /// changes and the cache is invalidated, the old ETag no longer matches.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_statistics_etag_round_trip() {
+        let state = build_test_state().await;
+        let market_id = 9_625_000 + (uuid::Uuid::new_v4().as_u128() % 1_000) as i64;
+        state.cache.invalidate_tag(&invalidation(&state, market_id)).await.unwrap();
+
+        let (status, etag, len) = get_statistics(&state, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(len > 0);
+
+        let (status, same, len) = get_statistics(&state, Some(&etag)).await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED);
+        assert_eq!(same, etag);
+        assert_eq!(len, 0);
+
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at) \
+             VALUES ($1, 'ETag test', 'active', 0, NOW() + INTERVAL '1 day', NOW())",
+        )
+        .bind(market_id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        state.cache.invalidate_tag(&invalidation(&state, market_id)).await.unwrap();
+
+        let (status, changed, _) = get_statistics(&state, Some(&etag)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_ne!(changed, etag);
+
+        sqlx::query("DELETE FROM markets WHERE id = $1")
+            .bind(market_id)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        state.cache.invalidate_tag(&invalidation(&state, market_id)).await.unwrap();
+    }
+
+}
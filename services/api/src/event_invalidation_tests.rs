@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod event_invalidation_tests {
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    use crate::{
+        blockchain::BlockchainClient,
+        cache::{keys, InvalidationTag, RedisCache},
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    const LATEST_LEDGER: u32 = 1_000;
+
+    /// Answers `getLatestLedger` with [`LATEST_LEDGER`] and `getEvents` with
+    /// `events`; every other method is a JSON-RPC error.
+    async fn start_mock_rpc(events: Vec<Value>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let events = events.clone();
+                async move {
+                    let resp = match body["method"].as_str().unwrap_or_default() {
+                        "getLatestLedger" => json!({ "result": { "latestLedger": { "sequence": LATEST_LEDGER } } }),
+                        "getEvents" => json!({ "result": { "events": events, "latestLedger": LATEST_LEDGER } }),
+                        _ => json!({ "error": { "code": -32601, "message": "not mocked" } }),
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// A client on a network name unique to this run, so its events, cursor
+    /// and cache keys never meet another run's.
+    async fn build_client(rpc_url: String) -> (BlockchainClient, RedisCache, Database, Metrics, String) {
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.retry_attempts = 1;
+        let network = format!("evtest{}", uuid::Uuid::new_v4().simple());
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(config.db_credentials.to_connection_string().expose_secret(), cache.clone(), metrics.clone(), &config.db_pool)
+            .await
+            .expect("db");
+        let client = BlockchainClient::new(&config, cache.clone(), db.clone(), metrics.clone())
+            .expect("blockchain")
+            .with_network(&network);
+        (client, cache, db, metrics, network)
+    }
+
+    fn unique_market_id() -> i64 {
+        (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 3_000_000_000
+    }
+
+    fn event(seq: u32, topic: Value) -> Value {
+        json!({
+            "id": format!("{:019}-{seq:010}", LATEST_LEDGER - 10),
+            "ledger": LATEST_LEDGER - 10,
+            "topic": topic,
+            "value": [1],
+        })
+    }
+
+    /// Seed every key of `tag` with a marker the sync pass must not leave behind.
+    async fn seed_stale(cache: &RedisCache, tag: &InvalidationTag) {
+        for key in tag.cache_keys() {
+            cache.set_json(&key, &"stale", Duration::from_secs(300)).await.unwrap();
+        }
+    }
+
+    async fn is_stale(cache: &RedisCache, key: &str) -> bool {
+        cache.get_json::<Value>(key).await.unwrap() == Some(json!("stale"))
+    }
+
+    fn event_invalidations(metrics: &Metrics, network: &str, topic: &str) -> Option<String> {
+        let rendered = metrics.render().unwrap();
+        rendered
+            .lines()
+            .find(|l| {
+                l.starts_with("cache_event_invalidations_total{")
+                    && l.contains(&format!("network=\"{network}\""))
+                    && l.contains(&format!("topic=\"{topic}\""))
+            })
+            .and_then(|l| l.rsplit(' ').next().map(str::to_string))
+    }
+
+    async fn cleanup(db: &Database, network: &str) {
+        sqlx::query("DELETE FROM chain_events WHERE network = $1")
+            .bind(network)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Each ingested topic drops exactly the keys of its tag; keys of other
+    /// users and markets survive.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_sync_invalidates_keys_by_event_topic() {
+        let (resolved, cancelled, untouched) = (unique_market_id(), unique_market_id(), unique_market_id());
+        let events = vec![
+            event(1, json!(["resolv_fx", resolved, "GRESOLVER"])),
+            event(2, json!(["mkt_cncl", cancelled, "GADMIN"])),
+            event(3, json!(["mkt_creat", untouched, "GCREATOR"])),
+            event(4, json!(["bet_place", resolved, "GBETTOR"])),
+            event(5, json!(["reward_fx", resolved, "GCLAIMER"])),
+            event(6, json!(["vote_cast", untouched, "GVOTER"])),
+        ];
+        let (client, cache, db, metrics, network) = build_client(start_mock_rpc(events).await).await;
+        let featured_limit = Config::from_env().featured_limit;
+
+        let tags = [
+            InvalidationTag::MarketResolved { market_id: resolved, network: network.clone(), featured_limit },
+            InvalidationTag::MarketCancelled { market_id: cancelled, network: network.clone(), featured_limit },
+            InvalidationTag::MarketCreated { network: network.clone() },
+            InvalidationTag::UserBetsChanged { network: network.clone(), address: "GBETTOR".into() },
+            InvalidationTag::UserBetsChanged { network: network.clone(), address: "GCLAIMER".into() },
+        ];
+        let survivors = [
+            keys::chain_market(&network, untouched),
+            keys::chain_user_bets_page(&network, "GVOTER", 0, 20),
+        ];
+        for tag in &tags {
+            seed_stale(&cache, tag).await;
+        }
+        for key in &survivors {
+            cache.set_json(key, &"stale", Duration::from_secs(300)).await.unwrap();
+        }
+
+        let tip = client.sync_once(LATEST_LEDGER - 100).await.unwrap();
+        assert!(tip > LATEST_LEDGER - 100, "the pass advanced the cursor");
+
+        // The pass may refill some entries (platform stats); none may still
+        // hold the pre-event value.
+        for tag in &tags {
+            for key in tag.cache_keys() {
+                assert!(!is_stale(&cache, &key).await, "{key} survived {tag:?}");
+            }
+        }
+        for key in &survivors {
+            assert!(is_stale(&cache, key).await, "{key} was invalidated by an unrelated event");
+        }
+
+        assert_eq!(event_invalidations(&metrics, &network, "resolv_fx").as_deref(), Some("7"));
+        assert_eq!(event_invalidations(&metrics, &network, "mkt_cncl").as_deref(), Some("7"));
+        assert_eq!(event_invalidations(&metrics, &network, "mkt_creat").as_deref(), Some("1"));
+        let pages = crate::cache::USER_BETS_INVALIDATED_PAGES.to_string();
+        assert_eq!(event_invalidations(&metrics, &network, "bet_place"), Some(pages.clone()));
+        assert_eq!(event_invalidations(&metrics, &network, "reward_fx"), Some(pages));
+        assert_eq!(event_invalidations(&metrics, &network, "vote_cast"), None);
+
+        for key in &survivors {
+            cache.del(key).await.unwrap();
+        }
+        cleanup(&db, &network).await;
+    }
+}
@@ -0,0 +1,420 @@
+//! Streaming CSV exports for the admin API.
+//!
+//! Rows are read on a spawned task and handed to the response body through a
+//! bounded channel (see `Database::stream_rows`), so an export holds at most
+//! [`EXPORT_BUFFER_ROWS`] rows in memory however large the table is, and stops
+//! reading as soon as the client disconnects. Fields are quoted per RFC 4180
+//! and the number of data rows is sent as the `X-Row-Count` trailer once the
+//! last row has been written.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::{stream::BoxStream, StreamExt};
+use http_body::Frame;
+use serde::Deserialize;
+
+use crate::handlers::ApiError;
+
+/// Rows buffered between the database and the response body.
+pub const EXPORT_BUFFER_ROWS: usize = 512;
+
+/// Rows written per body frame.
+const ROWS_PER_FRAME: usize = 256;
+
+/// Trailer carrying the number of data rows, excluding the header.
+pub const ROW_COUNT_TRAILER: &str = "x-row-count";
+
+/// A row that can be written as one CSV record.
+pub trait CsvRecord {
+    const HEADER: &'static [&'static str];
+
+    fn fields(&self) -> Vec<String>;
+}
+
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+pub struct ExportQuery {
+    /// Only rows in this status.
+    pub status: Option<String>,
+    /// Only rows created on or after this date (`YYYY-MM-DD`, UTC).
+    pub from: Option<NaiveDate>,
+    /// Only rows created on or before this date (`YYYY-MM-DD`, UTC).
+    pub to: Option<NaiveDate>,
+}
+
+/// Optional `[start, end)` bounds in UTC.
+pub type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+impl ExportQuery {
+    /// Half-open `[from, to + 1 day)` bounds, or a 400 when `from` is after `to`.
+    pub fn range(&self) -> Result<DateRange, ApiError> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(ApiError::bad_request("from must not be after to"));
+            }
+        }
+        let start = |d: NaiveDate| d.and_hms_opt(0, 0, 0).map(|t| t.and_utc());
+        Ok((
+            self.from.and_then(start),
+            self.to.and_then(|d| d.succ_opt()).and_then(start),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+pub struct EventExportQuery {
+    /// Only events of this kind (`bet_place`, `reward_fx`, `resolv_fx`,
+    /// `disp_file`, `mkt_creat`, `mkt_cncl`).
+    pub kind: Option<String>,
+    /// Only events indexed on or after this date (`YYYY-MM-DD`, UTC).
+    pub from: Option<NaiveDate>,
+    /// Only events indexed on or before this date (`YYYY-MM-DD`, UTC).
+    pub to: Option<NaiveDate>,
+}
+
+impl EventExportQuery {
+    /// Same bounds as [`ExportQuery::range`].
+    pub fn range(&self) -> Result<DateRange, ApiError> {
+        ExportQuery {
+            status: None,
+            from: self.from,
+            to: self.to,
+        }
+        .range()
+    }
+}
+
+/// Quote `value` if it contains a delimiter, quote, or line break, doubling
+/// any embedded quotes (RFC 4180 §2.6–2.7).
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One CRLF-terminated record (RFC 4180 §2.1).
+pub fn csv_record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| csv_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// RFC 3339 timestamp, or an empty field.
+pub fn timestamp(value: Option<DateTime<Utc>>) -> String {
+    value.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
+
+/// One waitlist entry as exported to CSV.
+#[derive(Debug, Clone)]
+pub struct WaitlistExportRow {
+    pub email: String,
+    pub status: String,
+    pub source: Option<String>,
+    pub referral_code: String,
+    pub referral_count: i64,
+    pub priority_score: i32,
+    pub joined_at: DateTime<Utc>,
+    pub invited_at: Option<DateTime<Utc>>,
+    pub converted_at: Option<DateTime<Utc>>,
+}
+
+impl CsvRecord for WaitlistExportRow {
+    const HEADER: &'static [&'static str] = &[
+        "email",
+        "status",
+        "source",
+        "referral_code",
+        "referral_count",
+        "priority_score",
+        "joined_at",
+        "invited_at",
+        "converted_at",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.email.clone(),
+            self.status.clone(),
+            self.source.clone().unwrap_or_default(),
+            self.referral_code.clone(),
+            self.referral_count.to_string(),
+            self.priority_score.to_string(),
+            timestamp(Some(self.joined_at)),
+            timestamp(self.invited_at),
+            timestamp(self.converted_at),
+        ]
+    }
+}
+
+/// Newsletter statuses an export can filter on. GDPR-deleted subscribers are
+/// never exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewsletterExportStatus {
+    /// Subscribed but not yet confirmed.
+    Pending,
+    Confirmed,
+    Unsubscribed,
+}
+
+impl NewsletterExportStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Confirmed => "confirmed",
+            Self::Unsubscribed => "unsubscribed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "pending" => Some(Self::Pending),
+            "confirmed" => Some(Self::Confirmed),
+            "unsubscribed" => Some(Self::Unsubscribed),
+            _ => None,
+        }
+    }
+}
+
+/// One newsletter subscriber as exported to CSV.
+#[derive(Debug, Clone)]
+pub struct NewsletterExportRow {
+    pub email: String,
+    pub source: String,
+    pub status: NewsletterExportStatus,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub unsubscribed_at: Option<DateTime<Utc>>,
+}
+
+impl CsvRecord for NewsletterExportRow {
+    const HEADER: &'static [&'static str] = &[
+        "email",
+        "source",
+        "status",
+        "created_at",
+        "confirmed_at",
+        "unsubscribed_at",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.email.clone(),
+            self.source.clone(),
+            self.status.label().to_string(),
+            timestamp(Some(self.created_at)),
+            timestamp(self.confirmed_at),
+            timestamp(self.unsubscribed_at),
+        ]
+    }
+}
+
+/// One indexed contract event as exported to CSV.
+#[derive(Debug, Clone)]
+pub struct ChainEventExportRow {
+    pub id: String,
+    pub ledger: i64,
+    pub tx_hash: Option<String>,
+    pub kind: String,
+    pub market_id: Option<i64>,
+    pub address: Option<String>,
+    pub outcome: Option<i32>,
+    /// Integer amount in stroops, as a decimal string.
+    pub amount: Option<String>,
+    pub token: Option<String>,
+    pub is_refund: bool,
+    pub indexed_at: DateTime<Utc>,
+}
+
+impl CsvRecord for ChainEventExportRow {
+    const HEADER: &'static [&'static str] = &[
+        "id",
+        "ledger",
+        "tx_hash",
+        "kind",
+        "market_id",
+        "address",
+        "outcome",
+        "amount",
+        "token",
+        "is_refund",
+        "indexed_at",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        let opt = |v: Option<String>| v.unwrap_or_default();
+        vec![
+            self.id.clone(),
+            self.ledger.to_string(),
+            opt(self.tx_hash.clone()),
+            self.kind.clone(),
+            opt(self.market_id.map(|v| v.to_string())),
+            opt(self.address.clone()),
+            opt(self.outcome.map(|v| v.to_string())),
+            opt(self.amount.clone()),
+            opt(self.token.clone()),
+            self.is_refund.to_string(),
+            timestamp(Some(self.indexed_at)),
+        ]
+    }
+}
+
+/// Stream `rows` as a CSV attachment named `{name}-{YYYYMMDD}.csv`.
+///
+/// A database error after the first byte cannot change the status code, so
+/// it aborts the body instead; clients see a truncated transfer without the
+/// row-count trailer rather than a silently short file.
+pub fn csv_response<T>(name: &str, rows: BoxStream<'static, anyhow::Result<T>>) -> Response
+where
+    T: CsvRecord + Send + 'static,
+{
+    let filename = format!("{name}-{}.csv", Utc::now().format("%Y%m%d"));
+    let header_row = Frame::data(Bytes::from(csv_record(T::HEADER)));
+
+    // State is (batches, rows written so far); `None` once the trailer or an
+    // error has been emitted.
+    let batches = rows.ready_chunks(ROWS_PER_FRAME);
+    let data = futures::stream::unfold(Some((batches, 0u64)), |state| async move {
+        let (mut batches, mut count) = state?;
+        let Some(batch) = batches.next().await else {
+            let mut trailers = HeaderMap::new();
+            trailers.insert(ROW_COUNT_TRAILER, HeaderValue::from(count));
+            return Some((Ok(Frame::trailers(trailers)), None));
+        };
+        let mut chunk = String::new();
+        for row in batch {
+            match row {
+                Ok(row) => {
+                    chunk.push_str(&csv_record(&row.fields()));
+                    count += 1;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, rows_written = count, "CSV export aborted");
+                    return Some((Err(e), None));
+                }
+            }
+        }
+        Some((Ok(Frame::data(Bytes::from(chunk))), Some((batches, count))))
+    });
+    let frames = futures::stream::once(async move { Ok::<_, anyhow::Error>(header_row) }).chain(data);
+    let body = Body::new(http_body_util::StreamBody::new(frames));
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+            (header::TRAILER, ROW_COUNT_TRAILER.to_string()),
+            (header::CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_fields_are_unquoted() {
+        assert_eq!(csv_field("user@example.com"), "user@example.com");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn commas_quotes_and_line_breaks_are_quoted() {
+        assert_eq!(csv_field("Doe, Jane"), "\"Doe, Jane\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\rlf"), "\"cr\rlf\"");
+    }
+
+    #[test]
+    fn records_are_crlf_terminated() {
+        assert_eq!(
+            csv_record(&["a", "b,c", "d\"e"]),
+            "a,\"b,c\",\"d\"\"e\"\r\n"
+        );
+    }
+
+    struct Named(&'static str);
+
+    impl CsvRecord for Named {
+        const HEADER: &'static [&'static str] = &["name"];
+
+        fn fields(&self) -> Vec<String> {
+            vec![self.0.to_string()]
+        }
+    }
+
+    async fn collect(response: Response) -> (String, Option<HeaderMap>) {
+        use http_body_util::BodyExt;
+
+        let mut body = response.into_body();
+        let mut csv = String::new();
+        let mut trailers = None;
+        while let Some(frame) = body.frame().await {
+            let frame = frame.unwrap();
+            if let Some(data) = frame.data_ref() {
+                csv.push_str(std::str::from_utf8(data).unwrap());
+            } else {
+                trailers = frame.into_trailers().ok();
+            }
+        }
+        (csv, trailers)
+    }
+
+    #[tokio::test]
+    async fn response_escapes_rows_and_ends_with_row_count_trailer() {
+        let rows = futures::stream::iter([Ok(Named("Doe, Jane")), Ok(Named("\"Ace\" Lee"))]).boxed();
+        let response = csv_response("people", rows);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert!(response.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("attachment; filename=\"people-"));
+        assert_eq!(response.headers()[header::TRAILER], ROW_COUNT_TRAILER);
+
+        let (csv, trailers) = collect(response).await;
+        assert_eq!(csv, "name\r\n\"Doe, Jane\"\r\n\"\"\"Ace\"\" Lee\"\r\n");
+        assert_eq!(trailers.unwrap()[ROW_COUNT_TRAILER], "2");
+    }
+
+    #[tokio::test]
+    async fn response_aborts_without_trailer_on_row_error() {
+        let rows = futures::stream::iter([Ok(Named("ok")), Err(anyhow::anyhow!("connection reset"))]).boxed();
+        let mut body = csv_response("people", rows).into_body();
+
+        use http_body_util::BodyExt;
+        assert!(body.frame().await.unwrap().unwrap().is_data(), "header row");
+        assert!(body.frame().await.unwrap().is_err());
+        assert!(body.frame().await.is_none());
+    }
+
+    #[test]
+    fn date_range_is_inclusive_of_the_end_day() {
+        let query = ExportQuery {
+            status: None,
+            from: NaiveDate::from_ymd_opt(2026, 1, 1),
+            to: NaiveDate::from_ymd_opt(2026, 1, 31),
+        };
+        let (from, to) = query.range().unwrap();
+        assert_eq!(from.unwrap().to_rfc3339(), "2026-01-01T00:00:00+00:00");
+        assert_eq!(to.unwrap().to_rfc3339(), "2026-02-01T00:00:00+00:00");
+
+        let backwards = ExportQuery { from: query.to, to: query.from, ..query };
+        assert!(backwards.range().is_err());
+    }
+}
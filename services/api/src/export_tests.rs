@@ -0,0 +1,333 @@
+#[cfg(test)]
+mod export_tests {
+    use axum::{
+        body::Body,
+        http::{header, HeaderMap, Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        compression,
+        export::ROW_COUNT_TRAILER,
+        handlers::{chain_events_export_csv, newsletter_export_csv, waitlist_export_csv, NETWORK_HEADER},
+        metrics::http_metrics_middleware,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded emails live in a reserved range, and seeded timestamps in
+    /// January 2020, so date-bounded exports see only rows created here.
+    const EMAIL_PREFIX: &str = "export-99";
+    const SEEDED_WAITLIST: usize = 3000;
+    const SEEDED_RANGE: &str = "from=2020-01-01&to=2020-01-03";
+
+    /// Seeded events are indexed in January 2020 on the primary network under
+    /// reserved ids, so a date-bounded export sees only rows created here.
+    const EVENT_ID_PREFIX: &str = "export-test-";
+    const SEEDED_EVENTS: usize = 100_000;
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/admin/waitlist/export.csv", get(waitlist_export_csv))
+            .route("/admin/newsletter/export.csv", get(newsletter_export_csv))
+            .route("/admin/events/export.csv", get(chain_events_export_csv))
+            .with_state(state)
+    }
+
+    /// Resident set size of this process in bytes, assuming 4 KiB pages.
+    fn resident_bytes() -> usize {
+        let statm = std::fs::read_to_string("/proc/self/statm").expect("/proc/self/statm");
+        let pages: usize = statm.split_whitespace().nth(1).unwrap().parse().unwrap();
+        pages * 4096
+    }
+
+    /// A CSV export read frame by frame.
+    struct Export {
+        status: StatusCode,
+        headers: HeaderMap,
+        csv: String,
+        data_frames: Vec<usize>,
+        trailers: Option<HeaderMap>,
+    }
+
+    impl Export {
+        fn lines(&self) -> Vec<&str> {
+            self.csv.split_terminator("\r\n").collect()
+        }
+
+        fn row_count(&self) -> &str {
+            self.trailers.as_ref().expect("row-count trailer")[ROW_COUNT_TRAILER]
+                .to_str()
+                .unwrap()
+        }
+    }
+
+    async fn export(state: &Arc<crate::AppState>, uri: &str) -> Export {
+        let response = app(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let mut body = response.into_body();
+        let mut export = Export {
+            status,
+            headers,
+            csv: String::new(),
+            data_frames: Vec::new(),
+            trailers: None,
+        };
+        while let Some(frame) = body.frame().await {
+            let frame = frame.expect("body frame");
+            if let Some(data) = frame.data_ref() {
+                export.data_frames.push(data.len());
+                export.csv.push_str(std::str::from_utf8(data).unwrap());
+            } else {
+                export.trailers = frame.into_trailers().ok();
+            }
+        }
+        export
+    }
+
+    /// Entry `n` joined `n` minutes into 2020; every third one is invited and
+    /// entry 1 has a source that needs quoting.
+    async fn seed_waitlist(state: &crate::AppState) {
+        sqlx::query(
+            "INSERT INTO waitlist_entries (email, status, source, referral_code, joined_at, invited_at) \
+             SELECT $1 || LPAD(n::TEXT, 4, '0') || '@example.com', \
+                    CASE WHEN n % 3 = 0 THEN 'invited' ELSE 'pending' END, \
+                    CASE WHEN n = 1 THEN 'Doe, \"Jane\"' ELSE 'landing' END, \
+                    'EXP99' || LPAD(n::TEXT, 4, '0'), \
+                    TIMESTAMPTZ '2020-01-01' + n * INTERVAL '1 minute', \
+                    CASE WHEN n % 3 = 0 THEN TIMESTAMPTZ '2020-02-01' END \
+             FROM generate_series(1, $2) AS n",
+        )
+        .bind(EMAIL_PREFIX)
+        .bind(SEEDED_WAITLIST as i32)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// One subscriber per status, plus a GDPR-deleted one.
+    async fn seed_newsletter(state: &crate::AppState) {
+        sqlx::query(
+            "INSERT INTO newsletter_subscribers \
+                 (email, source, confirmed, created_at, confirmed_at, unsubscribed_at, deleted_at) \
+             VALUES \
+                 ($1 || 'nl1@example.com', 'footer', FALSE, '2020-01-01 10:00Z', NULL, NULL, NULL), \
+                 ($1 || 'nl2@example.com', 'footer', TRUE, '2020-01-01 11:00Z', '2020-01-01 11:05Z', NULL, NULL), \
+                 ($1 || 'nl3@example.com', 'blog', TRUE, '2020-01-02 10:00Z', '2020-01-02 10:05Z', '2020-01-05 00:00Z', NULL), \
+                 ($1 || 'nl4@example.com', 'blog', TRUE, '2020-01-02 11:00Z', '2020-01-02 11:05Z', NULL, '2020-01-06 00:00Z')",
+        )
+        .bind(EMAIL_PREFIX)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// Event `n` is at ledger `n`, indexed `n` seconds into 2020, with a
+    /// full-width address and transaction hash so rows are export-sized.
+    async fn seed_events(state: &crate::AppState) {
+        sqlx::query(
+            "INSERT INTO chain_events \
+                 (id, network, ledger, tx_hash, kind, market_id, address, outcome, amount, indexed_at) \
+             SELECT $1 || n, $2, n, MD5(n::TEXT) || MD5((n + 1)::TEXT), 'bet_place', n % 50, \
+                    'G' || UPPER(LPAD(n::TEXT, 55, 'X')), n % 2, n * 1000, \
+                    TIMESTAMPTZ '2020-01-01' + n * INTERVAL '1 second' \
+             FROM generate_series(1, $3) AS n",
+        )
+        .bind(EVENT_ID_PREFIX)
+        .bind(state.networks.primary_name())
+        .bind(SEEDED_EVENTS as i32)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{EMAIL_PREFIX}%@example.com");
+        sqlx::query("DELETE FROM waitlist_entries WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM newsletter_subscribers WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(EVENT_ID_PREFIX)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A large export arrives as many small frames rather than one buffered
+    /// body, in join order, with the row count as a trailer.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_waitlist_export_streams_in_frames() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_waitlist(&state).await;
+
+        let export = export(&state, &format!("/admin/waitlist/export.csv?{SEEDED_RANGE}")).await;
+        assert_eq!(export.status, StatusCode::OK);
+        assert_eq!(export.headers[header::CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert!(export.headers[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .starts_with("attachment; filename=\"waitlist-"));
+
+        let total: usize = export.data_frames.iter().sum();
+        let largest = *export.data_frames.iter().max().unwrap();
+        assert!(export.data_frames.len() > 5, "{} frames", export.data_frames.len());
+        assert!(largest < total / 5, "largest frame {largest} of {total} bytes");
+
+        let lines = export.lines();
+        assert_eq!(lines.len(), SEEDED_WAITLIST + 1);
+        assert!(lines[0].starts_with("email,status,source,referral_code,"));
+        assert!(lines[1].starts_with("export-990001@example.com,pending,\"Doe, \"\"Jane\"\"\",EXP990001,"));
+        assert!(lines[SEEDED_WAITLIST].starts_with("export-993000@example.com,invited,landing,"));
+        assert_eq!(export.row_count(), SEEDED_WAITLIST.to_string());
+
+        cleanup(&state).await;
+    }
+
+    /// Status and date filters are applied in SQL; bad filters are 400s.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_waitlist_export_filters() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_waitlist(&state).await;
+
+        let invited = export(&state, &format!("/admin/waitlist/export.csv?status=invited&{SEEDED_RANGE}")).await;
+        assert_eq!(invited.row_count(), (SEEDED_WAITLIST / 3).to_string());
+        assert!(invited.lines()[1..].iter().all(|l| l.contains(",invited,")));
+
+        // Entries 1..=1439 joined on 2020-01-01.
+        let first_day = export(&state, "/admin/waitlist/export.csv?from=2020-01-01&to=2020-01-01").await;
+        assert_eq!(first_day.row_count(), "1439");
+
+        let bad_status = export(&state, "/admin/waitlist/export.csv?status=archived").await;
+        assert_eq!(bad_status.status, StatusCode::BAD_REQUEST);
+        let backwards = export(&state, "/admin/waitlist/export.csv?from=2020-01-03&to=2020-01-01").await;
+        assert_eq!(backwards.status, StatusCode::BAD_REQUEST);
+
+        cleanup(&state).await;
+    }
+
+    /// GDPR-deleted subscribers are never exported; status is derived from
+    /// the confirmation and unsubscribe timestamps.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_newsletter_export_excludes_deleted_subscribers() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_newsletter(&state).await;
+
+        let all = export(&state, &format!("/admin/newsletter/export.csv?{SEEDED_RANGE}")).await;
+        assert_eq!(all.status, StatusCode::OK);
+        assert_eq!(all.row_count(), "3");
+        let lines = all.lines();
+        assert_eq!(lines[0], "email,source,status,created_at,confirmed_at,unsubscribed_at");
+        assert!(lines[1].starts_with("export-99nl1@example.com,footer,pending,"));
+        assert!(lines[2].starts_with("export-99nl2@example.com,footer,confirmed,"));
+        assert!(lines[3].starts_with("export-99nl3@example.com,blog,unsubscribed,"));
+        assert!(!all.csv.contains("export-99nl4"));
+
+        let confirmed = export(&state, &format!("/admin/newsletter/export.csv?status=confirmed&{SEEDED_RANGE}")).await;
+        assert_eq!(confirmed.row_count(), "1");
+        assert!(confirmed.csv.contains("export-99nl2@example.com"));
+
+        cleanup(&state).await;
+    }
+
+    /// A 100k-row events export streams through the compression layer frame
+    /// by frame: resident memory grows by far less than the export's size,
+    /// the body is gzipped, and the uncompressed size is recorded per
+    /// endpoint. RSS is process-wide, so run this test on its own.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_large_events_export_keeps_memory_bounded() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_events(&state).await;
+
+        let app = app(Arc::clone(&state))
+            .layer(axum::middleware::from_fn_with_state(
+                state.metrics.clone(),
+                http_metrics_middleware,
+            ))
+            .layer(compression::compression_layer(
+                compression::DEFAULT_MIN_SIZE,
+                &state.config.compression_content_types,
+            ));
+        let request = Request::builder()
+            .uri(format!("/admin/events/export.csv?kind=bet_place&{SEEDED_RANGE}"))
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .header(NETWORK_HEADER, state.networks.primary_name())
+            .body(Body::empty())
+            .unwrap();
+
+        let baseline = resident_bytes();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+        assert!(response.headers().get(header::CONTENT_LENGTH).is_none());
+
+        let mut body = response.into_body();
+        let (mut frames, mut compressed, mut peak) = (0usize, 0usize, baseline);
+        let mut trailers = None;
+        while let Some(frame) = body.frame().await {
+            let frame = frame.expect("body frame");
+            if let Some(data) = frame.data_ref() {
+                frames += 1;
+                compressed += data.len();
+                peak = peak.max(resident_bytes());
+            } else {
+                trailers = frame.into_trailers().ok();
+            }
+        }
+        assert_eq!(
+            trailers.expect("row-count trailer")[ROW_COUNT_TRAILER],
+            SEEDED_EVENTS.to_string().as_str()
+        );
+        assert!(frames > 50, "{frames} frames");
+
+        let rendered = state.metrics.render().unwrap();
+        let sum_line = "http_endpoint_response_size_bytes_sum{endpoint=\"/admin/events/export.csv\"} ";
+        let uncompressed: f64 = rendered
+            .lines()
+            .find_map(|l| l.strip_prefix(sum_line))
+            .expect("response size recorded")
+            .parse()
+            .unwrap();
+        let uncompressed = uncompressed as usize;
+        assert!(uncompressed > SEEDED_EVENTS * 150, "{uncompressed} bytes uncompressed");
+        assert!(compressed < uncompressed, "{compressed} of {uncompressed} bytes");
+
+        let growth = peak.saturating_sub(baseline);
+        assert!(
+            growth < uncompressed / 4,
+            "resident memory grew {growth} bytes for a {uncompressed}-byte export"
+        );
+
+        cleanup(&state).await;
+    }
+
+}
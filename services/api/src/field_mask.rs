@@ -0,0 +1,289 @@
+//! `?fields=` response field selection.
+//!
+//! A mask is a comma-separated list of dotted paths (`id,title,chain.market`)
+//! applied to a response after it is serialized, so handlers keep returning
+//! their typed views and only the JSON is trimmed. A path selects the value
+//! at that location with everything below it; a path that crosses an array
+//! applies the rest of the path to every element. Paths naming fields the
+//! document does not have are ignored, and an empty mask keeps everything.
+
+use std::collections::{btree_map::Entry, BTreeMap};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Most paths accepted in one mask.
+pub const MAX_PATHS: usize = 64;
+
+/// Deepest path accepted, in segments.
+pub const MAX_DEPTH: usize = 8;
+
+/// A parsed `fields` mask. Paths are kept sorted and deduplicated, so two
+/// spellings of the same selection have the same [`FieldMask::canonical`]
+/// form and share a cache entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldMask {
+    root: Node,
+}
+
+/// One level of the mask. A node with no children selects the whole value
+/// below it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, path: &[&str]) {
+        let Some((head, rest)) = path.split_first() else {
+            return;
+        };
+        match self.children.entry((*head).to_string()) {
+            Entry::Vacant(slot) => {
+                slot.insert(Node::default()).extend(rest);
+            }
+            // An existing leaf already selects everything below it.
+            Entry::Occupied(mut slot) => {
+                if rest.is_empty() {
+                    slot.get_mut().children.clear();
+                } else if !slot.get().children.is_empty() {
+                    slot.get_mut().insert(rest);
+                }
+            }
+        }
+    }
+
+    /// Build the chain for a path not yet in the tree.
+    fn extend(&mut self, path: &[&str]) {
+        let mut node = self;
+        for segment in path {
+            node = node.children.entry((*segment).to_string()).or_default();
+        }
+    }
+
+    fn apply(&self, value: Value) -> Option<Value> {
+        if self.children.is_empty() {
+            return Some(value);
+        }
+        match value {
+            Value::Object(mut object) => {
+                let mut kept = serde_json::Map::new();
+                for (key, child) in &self.children {
+                    if let Some(v) = object.remove(key).and_then(|v| child.apply(v)) {
+                        kept.insert(key.clone(), v);
+                    }
+                }
+                Some(Value::Object(kept))
+            }
+            Value::Array(items) => Some(Value::Array(
+                items
+                    .into_iter()
+                    .filter_map(|item| self.apply(item))
+                    .collect(),
+            )),
+            // `null` stays so a selected-but-empty part is still visible.
+            Value::Null => Some(Value::Null),
+            // A path into a scalar names nothing.
+            _ => None,
+        }
+    }
+
+    fn paths(&self, prefix: &mut Vec<String>, out: &mut Vec<String>) {
+        for (key, child) in &self.children {
+            prefix.push(key.clone());
+            if child.children.is_empty() {
+                out.push(prefix.join("."));
+            } else {
+                child.paths(prefix, out);
+            }
+            prefix.pop();
+        }
+    }
+}
+
+impl FieldMask {
+    /// Parse a `fields` parameter. Blank entries are skipped; the error is a
+    /// message for the 400 response.
+    pub fn parse(raw: &str) -> Result<Self, String> {
+        let mut mask = Self::default();
+        let mut count = 0;
+        for path in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            count += 1;
+            if count > MAX_PATHS {
+                return Err(format!("fields accepts at most {MAX_PATHS} paths"));
+            }
+            let segments: Vec<&str> = path.split('.').collect();
+            if segments.len() > MAX_DEPTH {
+                return Err(format!("field paths are at most {MAX_DEPTH} segments deep"));
+            }
+            if segments.iter().any(|s| s.is_empty()) {
+                return Err(format!("malformed field path: {path:?}"));
+            }
+            mask.root.insert(&segments);
+        }
+        Ok(mask)
+    }
+
+    /// Parse an optional `fields` parameter; absent means the empty mask.
+    pub fn from_query(raw: Option<&str>) -> Result<Self, String> {
+        raw.map_or_else(|| Ok(Self::default()), Self::parse)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.children.is_empty()
+    }
+
+    /// Keep only the selected fields of `value`. An empty mask returns it
+    /// unchanged; a top-level array is masked element by element.
+    pub fn apply(&self, value: Value) -> Value {
+        if self.is_empty() {
+            return value;
+        }
+        self.root
+            .apply(value)
+            .unwrap_or_else(|| Value::Object(serde_json::Map::new()))
+    }
+
+    /// Sorted, deduplicated paths joined by commas; empty for the empty mask.
+    pub fn canonical(&self) -> String {
+        let mut out = Vec::new();
+        self.root.paths(&mut Vec::new(), &mut out);
+        out.join(",")
+    }
+
+    /// Fixed-length digest of [`FieldMask::canonical`] for cache keys, so
+    /// client-chosen paths never end up verbatim in a Redis key. Empty for
+    /// the empty mask.
+    pub fn cache_key_part(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let digest = hex::encode(Sha256::digest(self.canonical().as_bytes()));
+        digest[..16].to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn detail() -> Value {
+        json!({
+            "id": 7,
+            "title": "Will it rain?",
+            "status": "active",
+            "chain": {
+                "onchain_volume": "1200",
+                "market": { "resolved_outcome": null, "options": ["yes", "no"] },
+                "oracle": null,
+            },
+            "positions": [
+                { "market_id": 1, "staked": "10", "claimed": "0" },
+                { "market_id": 2, "staked": "5", "claimed": "5" },
+            ],
+        })
+    }
+
+    #[test]
+    fn empty_mask_returns_the_whole_document() {
+        let mask = FieldMask::parse(" , ").unwrap();
+        assert!(mask.is_empty());
+        assert_eq!(mask.apply(detail()), detail());
+        assert_eq!(FieldMask::from_query(None).unwrap(), FieldMask::default());
+    }
+
+    #[test]
+    fn top_level_fields_are_selected() {
+        let mask = FieldMask::parse("id,title").unwrap();
+        assert_eq!(
+            mask.apply(detail()),
+            json!({ "id": 7, "title": "Will it rain?" })
+        );
+    }
+
+    #[test]
+    fn dotted_paths_select_nested_fields() {
+        let mask = FieldMask::parse("id,chain.onchain_volume,chain.market.options").unwrap();
+        assert_eq!(
+            mask.apply(detail()),
+            json!({
+                "id": 7,
+                "chain": {
+                    "onchain_volume": "1200",
+                    "market": { "options": ["yes", "no"] },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn paths_through_arrays_apply_to_every_element() {
+        let mask = FieldMask::parse("positions.market_id,positions.staked").unwrap();
+        assert_eq!(
+            mask.apply(detail()),
+            json!({
+                "positions": [
+                    { "market_id": 1, "staked": "10" },
+                    { "market_id": 2, "staked": "5" },
+                ],
+            })
+        );
+
+        let items = json!([{ "id": 1, "title": "a" }, { "id": 2, "title": "b" }]);
+        let mask = FieldMask::parse("id").unwrap();
+        assert_eq!(mask.apply(items), json!([{ "id": 1 }, { "id": 2 }]));
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        let mask = FieldMask::parse("id,nope,chain.nope,title.length").unwrap();
+        assert_eq!(mask.apply(detail()), json!({ "id": 7, "chain": {} }));
+
+        let mask = FieldMask::parse("nope").unwrap();
+        assert_eq!(mask.apply(detail()), json!({}));
+    }
+
+    #[test]
+    fn selected_nulls_are_kept() {
+        let mask = FieldMask::parse("chain.oracle.outcome").unwrap();
+        assert_eq!(mask.apply(detail()), json!({ "chain": { "oracle": null } }));
+    }
+
+    #[test]
+    fn a_shorter_path_covers_longer_ones() {
+        let whole = json!({ "chain": detail()["chain"].clone() });
+        for raw in ["chain,chain.market", "chain.market,chain"] {
+            let mask = FieldMask::parse(raw).unwrap();
+            assert_eq!(mask.apply(detail()), whole, "{raw}");
+            assert_eq!(mask.canonical(), "chain");
+        }
+    }
+
+    #[test]
+    fn canonical_form_ignores_order_and_duplicates() {
+        let a = FieldMask::parse("title,id,chain.market.options").unwrap();
+        let b = FieldMask::parse("chain.market.options, id,title,id").unwrap();
+        assert_eq!(a.canonical(), "chain.market.options,id,title");
+        assert_eq!(a.canonical(), b.canonical());
+        assert_eq!(a.cache_key_part(), b.cache_key_part());
+        assert_eq!(a.cache_key_part().len(), 16);
+        assert_ne!(
+            a.cache_key_part(),
+            FieldMask::parse("id").unwrap().cache_key_part()
+        );
+        assert_eq!(FieldMask::default().canonical(), "");
+        assert_eq!(FieldMask::default().cache_key_part(), "");
+    }
+
+    #[test]
+    fn oversized_or_malformed_masks_are_rejected() {
+        let many = (0..=MAX_PATHS)
+            .map(|i| format!("f{i}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(FieldMask::parse(&many).is_err());
+        assert!(FieldMask::parse(&["a"; MAX_DEPTH + 1].join(".")).is_err());
+        assert!(FieldMask::parse("chain..market").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Data-subject requests across every table that stores an email address.
+//!
+//! [`GDPR_TABLES`] is the inventory: each entry names a table, how a row is
+//! matched to the subject's email, and what erasure does to it.
+//! `GET /api/v1/gdpr/export` returns one section per table with every
+//! matching row as JSON ([`crate::db::Database::gdpr_collect`]);
+//! `DELETE /api/v1/gdpr/delete` applies each table's [`Erasure`] in a single
+//! transaction ([`crate::db::Database::gdpr_erase`]) and reports row counts.
+//! Email jobs are anonymized rather than deleted so delivery totals still add
+//! up; any that had not been sent are cancelled first.
+//!
+//! Both are admin routes: the export includes contact-form messages and the
+//! delete removes waitlist places, so they run only after the operator has
+//! verified the requester. The public `/api/v1/newsletter/gdpr/*` endpoints
+//! still cover the newsletter subscription alone.
+//!
+//! A table that gains an email column must be added here, and to the
+//! integration tests in `gdpr_tests.rs`.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Recipient written over anonymized email jobs and events. `.invalid` never
+/// resolves, so an anonymized job that is retried cannot reach anyone.
+pub const ANONYMIZED_RECIPIENT: &str = "erased@gdpr.invalid";
+
+/// What erasure does to a table's matching rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Erasure {
+    Delete,
+    /// Overwrite `email_column` with [`ANONYMIZED_RECIPIENT`] and empty
+    /// `json_column` (template data or provider payload, which can repeat the
+    /// address), keeping the row for delivery accounting.
+    Anonymize {
+        email_column: &'static str,
+        json_column: &'static str,
+    },
+    /// Exported but kept: the suppression list is what stops us emailing an
+    /// address that bounced or complained.
+    Retain,
+}
+
+/// One table holding personal data keyed by email.
+#[derive(Debug, Clone, Copy)]
+pub struct GdprTable {
+    /// Section name in the export and the delete report.
+    pub section: &'static str,
+    pub table: &'static str,
+    /// SQL condition matching the subject's rows, with the normalized
+    /// (lowercase) email bound as `$1`.
+    pub matches: &'static str,
+    pub erasure: Erasure,
+}
+
+/// Every table that can hold a subject's email, in the order they are
+/// exported and erased.
+pub const GDPR_TABLES: &[GdprTable] = &[
+    GdprTable {
+        section: "newsletter_subscribers",
+        table: "newsletter_subscribers",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "newsletter_preferences",
+        table: "newsletter_preferences",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "waitlist_entries",
+        table: "waitlist_entries",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "contact_submissions",
+        table: "contact_form_submissions",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "market_watches",
+        table: "market_watches",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    // Deleting the row also drops its send log and stops the notifications.
+    GdprTable {
+        section: "user_notification_settings",
+        table: "user_notification_settings",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "tx_watch_subscriptions",
+        table: "tx_watch_subscriptions",
+        matches: "email = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "email_events",
+        table: "email_events",
+        // Recipients here come back from the provider, not our normalizer.
+        matches: "lower(recipient_email) = $1",
+        erasure: Erasure::Anonymize {
+            email_column: "recipient_email",
+            json_column: "metadata",
+        },
+    },
+    GdprTable {
+        section: "email_jobs",
+        table: "email_jobs",
+        matches: "recipient_email = $1",
+        erasure: Erasure::Anonymize {
+            email_column: "recipient_email",
+            json_column: "template_data",
+        },
+    },
+    // Analytics events carry no email column; clients may still put one in
+    // `properties` (e.g. on `newsletter_signup`).
+    GdprTable {
+        section: "analytics_events",
+        table: "analytics_events",
+        matches: "lower(properties->>'email') = $1",
+        erasure: Erasure::Delete,
+    },
+    GdprTable {
+        section: "email_suppressions",
+        table: "email_suppressions",
+        matches: "email = $1",
+        erasure: Erasure::Retain,
+    },
+];
+
+/// The erasure [`GDPR_TABLES`] gives `table`, if it holds personal data.
+pub fn erasure_for(table: &str) -> Option<Erasure> {
+    GDPR_TABLES.iter().find(|t| t.table == table).map(|t| t.erasure)
+}
+
+/// The `SET` list of an [`Erasure::Anonymize`], shared by erasure requests
+/// and retention ([`crate::retention`]).
+pub fn anonymize_assignments(email_column: &str, json_column: &str) -> String {
+    format!("{email_column} = '{ANONYMIZED_RECIPIENT}', {json_column} = '{{}}'::JSONB")
+}
+
+/// Columns left out of exported rows: live credentials, not personal data.
+pub const EXPORT_EXCLUDED_COLUMNS: &[&str] = &["confirmation_token", "secret"];
+
+/// Everything stored about one email address.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct GdprExport {
+    pub email: String,
+    pub generated_at: DateTime<Utc>,
+    /// Matching rows per [`GdprTable::section`]; every section is present,
+    /// empty when nothing matched.
+    #[schema(value_type = Object)]
+    pub sections: BTreeMap<&'static str, Vec<serde_json::Value>>,
+}
+
+impl GdprExport {
+    pub fn is_empty(&self) -> bool {
+        self.sections.values().all(Vec::is_empty)
+    }
+}
+
+/// Rows affected by an erasure, per section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct GdprDeleteReport {
+    #[schema(value_type = Object)]
+    pub deleted: BTreeMap<&'static str, u64>,
+    #[schema(value_type = Object)]
+    pub anonymized: BTreeMap<&'static str, u64>,
+    /// Sections kept on purpose; see [`Erasure::Retain`].
+    pub retained: Vec<&'static str>,
+}
+
+impl GdprDeleteReport {
+    pub fn total(&self) -> u64 {
+        self.deleted.values().chain(self.anonymized.values()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inventory_sections_are_unique_and_match_on_the_bound_email() {
+        let mut sections: Vec<_> = GDPR_TABLES.iter().map(|t| t.section).collect();
+        sections.sort_unstable();
+        sections.dedup();
+        assert_eq!(sections.len(), GDPR_TABLES.len());
+
+        for table in GDPR_TABLES {
+            assert!(table.matches.contains("$1"), "{}", table.section);
+        }
+    }
+
+    #[test]
+    fn email_jobs_are_anonymized_not_deleted() {
+        let jobs = GDPR_TABLES
+            .iter()
+            .find(|t| t.table == "email_jobs")
+            .unwrap();
+        assert!(matches!(jobs.erasure, Erasure::Anonymize { .. }));
+    }
+
+    #[test]
+    fn anonymization_blanks_the_email_and_payload() {
+        assert_eq!(
+            anonymize_assignments("recipient_email", "metadata"),
+            "recipient_email = 'erased@gdpr.invalid', metadata = '{}'::JSONB"
+        );
+        assert_eq!(erasure_for("email_suppressions"), Some(Erasure::Retain));
+        assert_eq!(erasure_for("chain_events"), None);
+    }
+
+    #[test]
+    fn report_total_counts_deleted_and_anonymized_rows() {
+        let mut report = GdprDeleteReport::default();
+        report.deleted.insert("waitlist_entries", 1);
+        report.anonymized.insert("email_jobs", 3);
+        report.retained.push("email_suppressions");
+        assert_eq!(report.total(), 4);
+    }
+}
@@ -0,0 +1,295 @@
+#[cfg(test)]
+mod gdpr_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{delete, get},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use sqlx::Row;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::analytics::AnalyticsEvent;
+    use crate::email::types::SuppressionType;
+    use crate::gdpr::ANONYMIZED_RECIPIENT;
+    use crate::handlers::{gdpr_delete, gdpr_export};
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Addresses and job templates live in a reserved range so cleanup never
+    /// touches rows created by other tests or the seed data.
+    const PREFIX: &str = "gdpr-637";
+    const SUBJECT: &str = "gdpr-637-subject@example.com";
+    const BYSTANDER: &str = "gdpr-637-bystander@example.com";
+    const TEMPLATE: &str = "gdpr_637_test";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/gdpr/export", get(gdpr_export))
+            .route("/gdpr/delete", delete(gdpr_delete))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn export(state: &Arc<crate::AppState>, address: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .uri(format!("/gdpr/export?email={address}"))
+            .body(Body::empty())
+            .unwrap();
+        send(state, request).await
+    }
+
+    async fn erase(state: &Arc<crate::AppState>, address: &str) -> (StatusCode, Value) {
+        let request = Request::builder()
+            .method("DELETE")
+            .uri("/gdpr/delete")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "email": address }).to_string()))
+            .unwrap();
+        send(state, request).await
+    }
+
+    /// Rows in newsletter, waitlist, contact, email job/event, analytics and
+    /// suppression tables for `address`.
+    async fn seed(state: &crate::AppState, address: &str) {
+        state
+            .db
+            .newsletter_upsert_pending(address, "test", &format!("{address}-token"))
+            .await
+            .unwrap();
+        state.db.waitlist_join(address, "test", None).await.unwrap();
+        for subject in ["Question", "Follow-up"] {
+            state
+                .db
+                .contact_create("Test User", address, subject, "Hello", json!({}))
+                .await
+                .unwrap();
+        }
+        let sent = state
+            .db
+            .email_create_job("custom", address, TEMPLATE, json!({ "email": address }), 0)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE email_jobs SET status = 'completed' WHERE id = $1")
+            .bind(sent)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        state
+            .db
+            .email_create_job("custom", address, TEMPLATE, json!({ "email": address }), 0)
+            .await
+            .unwrap();
+        state
+            .db
+            .email_create_event(
+                Some(sent),
+                Some("gdpr-637-message"),
+                "delivered",
+                address,
+                json!({ "email": address }),
+            )
+            .await
+            .unwrap();
+        // Clients do not normalize addresses in event properties.
+        let event = AnalyticsEvent {
+            event_type: "newsletter_signup".to_string(),
+            properties: json!({ "email": address.to_uppercase() }),
+            session_id: None,
+            occurred_at: chrono::Utc::now(),
+        };
+        state
+            .db
+            .analytics_insert_events(&[event], None, None)
+            .await
+            .unwrap();
+        state
+            .db
+            .email_add_suppression(address, SuppressionType::Complaint.as_str(), None, None)
+            .await
+            .unwrap();
+    }
+
+    fn section_len(export: &Value, section: &str) -> usize {
+        export["sections"][section]
+            .as_array()
+            .unwrap_or_else(|| panic!("missing section {section}"))
+            .len()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{PREFIX}-%");
+        for statement in [
+            "DELETE FROM newsletter_subscribers WHERE email LIKE $1",
+            "DELETE FROM waitlist_entries WHERE email LIKE $1",
+            "DELETE FROM contact_form_submissions WHERE email LIKE $1",
+            "DELETE FROM email_suppressions WHERE email LIKE $1",
+            "DELETE FROM analytics_events WHERE lower(properties->>'email') LIKE $1",
+            "DELETE FROM email_events WHERE recipient_email LIKE $1",
+        ] {
+            sqlx::query(statement)
+                .bind(&pattern)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+        // Anonymized jobs no longer carry the address; their events cascade.
+        sqlx::query("DELETE FROM email_jobs WHERE template_name = $1")
+            .bind(TEMPLATE)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// The export has every table's rows for the subject and nobody else's,
+    /// without live confirmation tokens.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_export_covers_every_table() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        seed(&state, SUBJECT).await;
+        seed(&state, BYSTANDER).await;
+
+        let (status, body) = export(&state, &SUBJECT.to_uppercase()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["email"], SUBJECT);
+        for (section, rows) in [
+            ("newsletter_subscribers", 1),
+            ("newsletter_preferences", 0),
+            ("waitlist_entries", 1),
+            ("contact_submissions", 2),
+            ("market_watches", 0),
+            ("user_notification_settings", 0),
+            ("tx_watch_subscriptions", 0),
+            ("email_events", 1),
+            ("email_jobs", 2),
+            ("analytics_events", 1),
+            ("email_suppressions", 1),
+        ] {
+            assert_eq!(section_len(&body, section), rows, "{section}");
+        }
+        let subscriber = &body["sections"]["newsletter_subscribers"][0];
+        assert_eq!(subscriber["email"], SUBJECT);
+        assert!(subscriber.get("confirmation_token").is_none());
+        assert!(body.to_string().find(BYSTANDER).is_none());
+
+        let (status, body) = export(&state, "not-an-email").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+
+        cleanup(&state).await;
+    }
+
+    /// Erasure deletes or anonymizes every row in one go and reports the
+    /// counts; the suppression list and other subjects are untouched.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_delete_cascades_with_counts() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        seed(&state, SUBJECT).await;
+        seed(&state, BYSTANDER).await;
+
+        let (status, report) = erase(&state, SUBJECT).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            report,
+            json!({
+                "deleted": {
+                    "analytics_events": 1,
+                    "contact_submissions": 2,
+                    "market_watches": 0,
+                    "newsletter_preferences": 0,
+                    "newsletter_subscribers": 1,
+                    "tx_watch_subscriptions": 0,
+                    "user_notification_settings": 0,
+                    "waitlist_entries": 1,
+                },
+                "anonymized": { "email_events": 1, "email_jobs": 2 },
+                "retained": ["email_suppressions"],
+            })
+        );
+
+        // Jobs stay for delivery accounting, without the address; the unsent
+        // one is cancelled.
+        let jobs = sqlx::query(
+            "SELECT recipient_email, template_data, status FROM email_jobs \
+             WHERE template_name = $1 AND recipient_email = $2 ORDER BY status",
+        )
+        .bind(TEMPLATE)
+        .bind(ANONYMIZED_RECIPIENT)
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap();
+        let jobs: Vec<(Value, String)> = jobs
+            .iter()
+            .map(|r| (r.get("template_data"), r.get("status")))
+            .collect();
+        assert_eq!(
+            jobs,
+            vec![
+                (json!({}), "cancelled".to_string()),
+                (json!({}), "completed".to_string()),
+            ]
+        );
+
+        let (_, after) = export(&state, SUBJECT).await;
+        let remaining: usize = after["sections"]
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|rows| rows.as_array().unwrap().len())
+            .sum();
+        assert_eq!(remaining, 1);
+        assert_eq!(section_len(&after, "email_suppressions"), 1);
+
+        let (_, bystander) = export(&state, BYSTANDER).await;
+        assert_eq!(section_len(&bystander, "waitlist_entries"), 1);
+        assert_eq!(section_len(&bystander, "email_jobs"), 2);
+
+        // A second erasure finds nothing left to remove.
+        let (_, again) = erase(&state, SUBJECT).await;
+        assert!(again["deleted"]
+            .as_object()
+            .unwrap()
+            .values()
+            .chain(again["anonymized"].as_object().unwrap().values())
+            .all(|count| count == 0));
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config
+            .unsubscribe_signing_secret
+            .get_or_insert_with(|| "gdpr-test-signing-secret".to_string());
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -4,99 +4,239 @@ use std::{
 };
 
 use axum::{
-    extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
+    extract::{FromRequestParts, Multipart, Path, Query, State},
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use validator::ValidateEmail;
+use validator::{Validate, ValidateEmail};
+
+use crate::{acquisition::{self, AcquisitionGroupBy, AcquisitionReport}, activity::{self, ActivityFeed, ActivityFilter, ActivityType}, analytics::{AnalyticsEvent, AnalyticsSummary}, api_key_usage::ApiKeyUsage, backfill::BackfillJob, blockchain::{BlockchainClient, ChainMarketData, ContractCallError, DataSource, HealthStatus, MarketMetadata, OracleResult, RpcError}, cache::{admin::{self as cache_admin, CacheDeleteResult, CacheEntry, CacheKeyList}, keys, InvalidationTag, TaggedLookup}, campaign::{self, Campaign}, category::{self}, contact::ContactStatus, content::{self, ContentFields, RenderedContent}, creators::{self, CreatorProfile, TopCreatorsMetric}, db::{DbError, MarketCursor, MarketDetail, MarketListFilter, MarketSort, Statistics}, digest, email::webhook::sendgrid_webhook_handler, export::{csv_response, EventExportQuery, ExportQuery, NewsletterExportStatus}, field_mask::FieldMask, gdpr::{GdprDeleteReport, GdprExport}, leaderboard::{LeaderboardMetric, LeaderboardPeriod}, market_ids::{self, MarketIdBindRequest, UnmappedMarket}, market_image::{self, ImageFormat, MarketImage}, market_watch::{self, MarketWatch, WatchTrigger}, oracle_keeper::{OracleSubmission, OracleSubmissionStatus, SubmissionClaim}, pagination::{PageResponse, PaginatedResponse, PaginationQuery}, price::PriceService, price_history::{self, HistoryResolution, PriceHistory}, protocol_state::{self, MarketBettingState, ProtocolStateView}, risk::{self, RiskAlertList, RiskThresholds}, signup_guard::{self}, stats_history::{self, StatsHistory, StatsMetric}, storage, tx_watch::{self, TxSubscription}, user_notifications::{self, NotificationSettings, NotificationSettingsUpdate}, validation::{self, ValidatedJson, ValidatedQuery}, waitlist::{normalize_referral_code, WaitlistEntry, WaitlistInvitee, WaitlistJoin, WaitlistStatus}, wallet_auth::{self, AuthedAddress, SessionKeys, SessionToken}, watchlist::Watchlist, AppState};
+/// Response types shared with `predictiq-api-client`.
+pub use predictiq_api_types::{FeaturedMarketView, NewsletterResponse};
+use predictiq_api_types::ErrorEnvelope;
+
+/// Error classes the API distinguishes. Each maps to one HTTP status; the
+/// stable, machine-readable `code` in the body narrows it further (e.g.
+/// `NotFound` + `MARKET_NOT_FOUND`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// 400: the request is malformed or fails validation.
+    Validation,
+    /// 401: the caller did not prove who they are.
+    Unauthorized,
+    /// 403: the caller is authenticated but may not act on this resource.
+    Forbidden,
+    /// 404
+    NotFound,
+    /// 409: the request conflicts with current state.
+    Conflict,
+    /// 413
+    PayloadTooLarge,
+    /// 415
+    UnsupportedMediaType,
+    /// 422: well-formed, but not applicable to the resource as it is.
+    Unprocessable,
+    /// 429
+    RateLimited,
+    /// 500: a bug or unexpected failure; the cause is logged, never returned.
+    Internal,
+    /// 502: the Soroban RPC or another dependency failed.
+    Upstream,
+    /// 503: a local dependency (database, signer) is unavailable.
+    Unavailable,
+    /// 503: every database connection is in use; retrying shortly should
+    /// succeed once load drops.
+    Overloaded,
+    /// 504: an upstream operation did not finish in time.
+    UpstreamTimeout,
+}
 
-use crate::{blockchain::HealthStatus, cache::{keys, InvalidationTag}, db::DbError, email::webhook::sendgrid_webhook_handler, pagination::{PaginatedResponse, PaginationQuery}, AppState};
+impl ApiErrorKind {
+    pub fn status(self) -> StatusCode {
+        match self {
+            Self::Validation => StatusCode::BAD_REQUEST,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Conflict => StatusCode::CONFLICT,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Unprocessable => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Upstream => StatusCode::BAD_GATEWAY,
+            Self::Unavailable | Self::Overloaded => StatusCode::SERVICE_UNAVAILABLE,
+            Self::UpstreamTimeout => StatusCode::GATEWAY_TIMEOUT,
+        }
+    }
+}
 
+/// The error envelope every handler and middleware responds with:
+/// `{ code, message, details?, request_id? }`. `message` is always safe to
+/// show; internals go to the logs, not the body.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ApiError {
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub kind: ApiErrorKind,
     pub code: &'static str,
     pub message: String,
+    /// Structured context a client can act on, e.g. `retry_after`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
     #[serde(skip)]
     #[schema(ignore)]
     pub status: StatusCode,
+    /// Correlation ID of the failed request, matching the `X-Request-ID`
+    /// response header. Filled in from the request context when the error is
+    /// turned into a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ApiError {
+    pub fn new(kind: ApiErrorKind, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            code,
+            message: message.into(),
+            details: None,
+            status: kind.status(),
+            request_id: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
     pub fn internal(err: anyhow::Error) -> Self {
         // Log the full error chain for debugging, then record it on the active OTel span
         // so traces carry the root cause even though the HTTP response is sanitised.
         tracing::error!(error = %err, "internal server error");
         {
             use tracing_opentelemetry::OpenTelemetrySpanExt;
-            tracing::Span::current()
-                .set_status(opentelemetry::trace::Status::error(format!("{err:#}")));
-        }
-        Self {
-            code: "INTERNAL_ERROR",
-            message: "An internal error occurred.".to_string(),
-            status: StatusCode::INTERNAL_SERVER_ERROR,
+            tracing::Span::current().set_attribute("exception.message", format!("{err:#}"));
         }
+        Self::new(ApiErrorKind::Internal, "INTERNAL_ERROR", "An internal error occurred.")
+    }
+
+    /// A dependency we call out to failed. Like [`ApiError::internal`], the
+    /// cause is logged rather than returned.
+    pub fn upstream(err: anyhow::Error) -> Self {
+        tracing::warn!(error = %format!("{err:#}"), "upstream dependency failed");
+        Self::new(
+            ApiErrorKind::Upstream,
+            "UPSTREAM_UNAVAILABLE",
+            "An upstream service is unavailable; please retry shortly.",
+        )
     }
 
     pub fn bad_request(message: impl Into<String>) -> Self {
-        Self {
-            code: "BAD_REQUEST",
-            message: message.into(),
-            status: StatusCode::BAD_REQUEST,
-        }
+        Self::new(ApiErrorKind::Validation, "BAD_REQUEST", message)
+    }
+
+    pub fn unauthorized(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(ApiErrorKind::Unauthorized, code, message)
+    }
+
+    pub fn forbidden(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(ApiErrorKind::Forbidden, code, message)
     }
 
     pub fn not_found(message: impl Into<String>) -> Self {
-        Self {
-            code: "NOT_FOUND",
-            message: message.into(),
-            status: StatusCode::NOT_FOUND,
-        }
+        Self::new(ApiErrorKind::NotFound, "NOT_FOUND", message)
+    }
+
+    pub fn market_not_found(market_id: i64) -> Self {
+        Self::new(
+            ApiErrorKind::NotFound,
+            "MARKET_NOT_FOUND",
+            format!("market {market_id} not found"),
+        )
+        .with_details(serde_json::json!({ "market_id": market_id }))
+    }
+
+    /// A public market id with no chain id on `network`; see
+    /// [`crate::market_ids`].
+    pub fn market_not_mapped(market_id: i64, network: &str) -> Self {
+        Self::new(
+            ApiErrorKind::NotFound,
+            "MARKET_NOT_MAPPED",
+            format!("market {market_id} is not mapped on network {network}"),
+        )
+        .with_details(serde_json::json!({ "market_id": market_id, "network": network }))
     }
 
     pub fn conflict(message: impl Into<String>) -> Self {
-        Self {
-            code: "CONFLICT",
-            message: message.into(),
-            status: StatusCode::CONFLICT,
-        }
+        Self::new(ApiErrorKind::Conflict, "CONFLICT", message)
+    }
+
+    pub fn unprocessable(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(ApiErrorKind::Unprocessable, code, message)
     }
 
     pub fn rate_limited() -> Self {
-        Self {
-            code: "RATE_LIMITED",
-            message: "Too many requests, please try again later.".to_string(),
-            status: StatusCode::TOO_MANY_REQUESTS,
-        }
+        Self::new(
+            ApiErrorKind::RateLimited,
+            "RATE_LIMITED",
+            "Too many requests, please try again later.",
+        )
     }
 
     pub fn service_unavailable(message: impl Into<String>) -> Self {
-        Self {
-            code: "SERVICE_UNAVAILABLE",
-            message: message.into(),
-            status: StatusCode::SERVICE_UNAVAILABLE,
-        }
+        Self::new(ApiErrorKind::Unavailable, "SERVICE_UNAVAILABLE", message)
+    }
+
+    pub fn overloaded() -> Self {
+        Self::new(
+            ApiErrorKind::Overloaded,
+            "OVERLOADED",
+            "The service is busy; please retry shortly.",
+        )
     }
 }
 
 impl IntoResponse for ApiError {
-    fn into_response(self) -> Response {
-        (self.status, Json(self)).into_response()
+    fn into_response(mut self) -> Response {
+        if self.request_id.is_none() {
+            self.request_id = crate::correlation::current_request_id();
+        }
+        (self.status, Json(ErrorEnvelope::from(self))).into_response()
+    }
+}
+
+/// The wire form of the error, shared with clients.
+impl From<ApiError> for ErrorEnvelope {
+    fn from(err: ApiError) -> Self {
+        Self {
+            code: err.code.to_string(),
+            message: err.message,
+            details: err.details,
+            request_id: err.request_id,
+        }
     }
 }
 
+/// Classify an error from the data layer: typed database failures and RPC
+/// failures the node is to blame for get their own status, everything else
+/// (including RPC requests the node refused as malformed) is a sanitised 500.
 fn into_api_error(err: anyhow::Error) -> ApiError {
+    if let Some(unmapped) = err.downcast_ref::<UnmappedMarket>() {
+        return ApiError::market_not_mapped(unmapped.market_id, &unmapped.network);
+    }
     if let Some(db_err) = err.downcast_ref::<DbError>() {
         match db_err {
             DbError::Timeout => {
                 return ApiError::service_unavailable("database query timed out");
             }
             DbError::PoolExhausted => {
-                return ApiError::service_unavailable("database connection pool exhausted");
+                return ApiError::overloaded();
             }
             DbError::ConstraintViolation(msg) => {
                 tracing::error!(db_constraint = %msg, "database constraint violation");
@@ -105,17 +245,79 @@ fn into_api_error(err: anyhow::Error) -> ApiError {
             DbError::Other(_) => {}
         }
     }
+    let upstream = err
+        .chain()
+        .find_map(|e| e.downcast_ref::<RpcError>())
+        .map(RpcError::is_upstream);
+    if upstream == Some(true) {
+        return ApiError::upstream(err);
+    }
     ApiError::internal(err)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
-pub struct FeaturedMarketView {
-    pub id: i64,
-    pub title: String,
-    pub volume: f64,
-    pub ends_at: chrono::DateTime<chrono::Utc>,
-    pub onchain_volume: String,
-    pub resolved_outcome: Option<u32>,
+/// Request header selecting which network a blockchain route reads from.
+pub const NETWORK_HEADER: &str = "x-network";
+
+/// The blockchain client for the network named in `X-Network`, or the
+/// primary network when the header is absent. Unknown names are a 400 that
+/// lists the served networks.
+pub struct Network(pub BlockchainClient);
+
+#[axum::async_trait]
+impl FromRequestParts<Arc<AppState>> for Network {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let requested = match parts.headers.get(NETWORK_HEADER) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| ApiError::bad_request("X-Network must be a network name"))?,
+            ),
+            None => None,
+        };
+        state
+            .networks
+            .resolve(requested)
+            .cloned()
+            .map(Network)
+            .ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "unknown network {:?}; this API serves: {}",
+                    requested.unwrap_or_default(),
+                    state.networks.names().join(", "),
+                ))
+            })
+    }
+}
+
+/// Run `read`, a many-market chain read on `client`, over the chain ids
+/// `chain_ids` resolved for `public_ids`. Results are in `public_ids` order;
+/// ids without a chain id fail with [`UnmappedMarket`] and are not read.
+async fn read_mapped_many<T, F, Fut>(
+    client: &BlockchainClient,
+    public_ids: &[i64],
+    chain_ids: &[Option<i64>],
+    read: F,
+) -> Vec<anyhow::Result<T>>
+where
+    F: FnOnce(Vec<i64>) -> Fut,
+    Fut: std::future::Future<Output = Vec<anyhow::Result<T>>>,
+{
+    let mut results = read(chain_ids.iter().flatten().copied().collect())
+        .await
+        .into_iter();
+    public_ids
+        .iter()
+        .zip(chain_ids)
+        .map(|(&market_id, chain_id)| match chain_id {
+            Some(_) => results.next().expect("one result per mapped market"),
+            None => Err(market_ids::unmapped(client, market_id)),
+        })
+        .collect()
 }
 
 /// Legacy `/health` endpoint — retained for backward compatibility.
@@ -204,6 +406,31 @@ pub async fn health(State(state): State<Arc<AppState>>, headers: HeaderMap) -> i
     (status_code, Json(health_status))
 }
 
+/// The generated OpenAPI document (`GET /api/v1/openapi.json`).
+pub async fn openapi_document() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        crate::openapi_spec::openapi_json(),
+    )
+}
+
+/// Swagger UI for the document above (`GET /api/v1/docs`), mounted only
+/// when `SWAGGER_UI_ENABLED` is set.
+pub async fn swagger_ui() -> impl IntoResponse {
+    (
+        [(header::CONTENT_SECURITY_POLICY, crate::openapi_spec::SWAGGER_UI_CSP)],
+        axum::response::Html(crate::openapi_spec::swagger_ui_html()),
+    )
+}
+
+/// `GET /api/v1/docs/swagger-initializer.js`, loaded by [`swagger_ui`].
+pub async fn swagger_ui_initializer() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/javascript; charset=utf-8")],
+        crate::openapi_spec::SWAGGER_UI_INITIALIZER,
+    )
+}
+
 /// Liveness probe: just confirms the process is alive and serving requests.
 /// Never returns 503 — if this endpoint is reachable the process is up.
 pub async fn health_live() -> impl IntoResponse {
@@ -220,7 +447,9 @@ pub async fn health_live() -> impl IntoResponse {
 ///
 /// Returns 503 if the database or Redis is unavailable.  Blockchain RPC
 /// degradation is surfaced in the body but does not affect the status code
-/// because the API can continue to serve cached data without it.
+/// because the API can continue to serve cached data without it.  Each check
+/// is bounded by a short timeout and the result is cached briefly; see
+/// [`crate::readiness`].
 pub async fn health_ready(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
@@ -232,58 +461,21 @@ pub async fn health_ready(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("-");
 
-    // ── Database ──────────────────────────────────────────────────────────────
-    let db_ok = state.db.ping().await.is_ok();
-
-    // ── Redis: direct PING bypassing circuit breaker, with latency ───────────
-    let redis_result = state.cache.ping_direct_ms().await;
-    let redis_latency_ms = redis_result.as_ref().ok().copied().unwrap_or(0);
-    let redis_ok = redis_result.is_ok();
-    let redis_degraded = redis_ok && redis_latency_ms > 100;
-
-    // ── Blockchain RPC (cached; does not block readiness) ─────────────────────
-    let rpc_health = state.blockchain.health_check_cached().await.ok();
-    let rpc_status = match &rpc_health {
-        Some(h) if h.is_healthy => "ok",
-        Some(_) => "degraded",
-        None => "unknown",
-    };
-
-    let all_critical_ok = db_ok && redis_ok;
-    let overall_status = if !all_critical_ok {
-        "unavailable"
-    } else if redis_degraded {
-        "degraded"
-    } else {
-        "ok"
-    };
+    let report = state
+        .readiness
+        .get_or_check(crate::readiness::CACHE_TTL, || crate::readiness::check(&state))
+        .await;
 
-    let status_code = if all_critical_ok {
+    let status_code = if report.ready {
         StatusCode::OK
     } else {
         StatusCode::SERVICE_UNAVAILABLE
     };
 
-    (
-        status_code,
-        Json(serde_json::json!({
-            "status": overall_status,
-            "request_id": request_id,
-            "timestamp": chrono::Utc::now().to_rfc3339(),
-            "dependencies": {
-                "database": {
-                    "status": if db_ok { "ok" } else { "unavailable" }
-                },
-                "redis": {
-                    "status": if !redis_ok { "unavailable" } else if redis_degraded { "degraded" } else { "ok" },
-                    "latency_ms": redis_latency_ms,
-                },
-                "blockchain_rpc": {
-                    "status": rpc_status,
-                }
-            }
-        })),
-    )
+    let mut body = serde_json::to_value(&report).unwrap_or_default();
+    body["request_id"] = serde_json::json!(request_id);
+    body["timestamp"] = serde_json::json!(chrono::Utc::now().to_rfc3339());
+    (status_code, Json(body))
 }
 
 /// Dependency details endpoint: structured per-dependency health with latency.
@@ -307,7 +499,7 @@ pub async fn health_dependencies(State(state): State<Arc<AppState>>) -> impl Int
         "ok"
     };
 
-    let rpc_health = state.blockchain.health_check_cached().await.ok();
+    let rpc_health = state.networks.primary().health_check_cached().await.ok();
     let rpc_status = match &rpc_health {
         Some(h) if h.is_healthy => "ok",
         Some(_) => "degraded",
@@ -340,14 +532,25 @@ pub async fn health_dependencies(State(state): State<Arc<AppState>>) -> impl Int
     )
 }
 
-#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+/// Longest signup `source` label accepted by the newsletter and waitlist.
+const MAX_SOURCE_LEN: u64 = acquisition::MAX_SOURCE_LEN as u64;
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
 pub struct NewsletterSubscribeRequest {
+    #[validate(custom(function = "crate::validation::email_address"))]
     pub email: String,
+    /// Where the signup came from, stored lowercased; `direct` when omitted
+    /// or blank.
+    #[validate(length(max = MAX_SOURCE_LEN))]
     pub source: Option<String>,
+    /// Honeypot: hidden from people by the form, so only bots fill it in.
+    #[serde(default)]
+    pub website: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
 pub struct NewsletterEmailRequest {
+    #[validate(custom(function = "crate::validation::email_address"))]
     pub email: String,
 }
 
@@ -362,14 +565,65 @@ pub struct NewsletterUnsubscribeQuery {
 }
 
 #[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
-pub struct NewsletterExportQuery {
-    pub email: String,
+pub struct NewsletterPreferencesQuery {
+    /// Signed token from the preferences link in a newsletter footer.
+    pub token: String,
 }
 
-#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
-pub struct NewsletterResponse {
-    pub success: bool,
-    pub message: String,
+/// Whether each category of email is sent to the subscriber.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct NewsletterPreferences {
+    /// Product announcements and campaigns.
+    pub product: bool,
+    /// Market resolution and dispute alerts.
+    pub markets: bool,
+    /// Periodic activity digest.
+    pub digest: bool,
+}
+
+impl NewsletterPreferences {
+    /// Build from stored `(category, enabled)` rows; a category without a
+    /// row is on.
+    fn from_rows(rows: &[(String, bool)]) -> Self {
+        let enabled = |category: crate::newsletter::EmailCategory| {
+            rows.iter()
+                .find(|(stored, _)| stored == category.as_str())
+                .map_or(true, |(_, enabled)| *enabled)
+        };
+        Self {
+            product: enabled(crate::newsletter::EmailCategory::Product),
+            markets: enabled(crate::newsletter::EmailCategory::Markets),
+            digest: enabled(crate::newsletter::EmailCategory::Digest),
+        }
+    }
+}
+
+/// Categories to change; omitted categories keep their current setting.
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NewsletterPreferencesUpdate {
+    pub product: Option<bool>,
+    pub markets: Option<bool>,
+    pub digest: Option<bool>,
+}
+
+impl NewsletterPreferencesUpdate {
+    fn changes(&self) -> Vec<(crate::newsletter::EmailCategory, bool)> {
+        use crate::newsletter::EmailCategory;
+        [
+            (EmailCategory::Product, self.product),
+            (EmailCategory::Markets, self.markets),
+            (EmailCategory::Digest, self.digest),
+        ]
+        .into_iter()
+        .filter_map(|(category, enabled)| enabled.map(|enabled| (category, enabled)))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct NewsletterExportQuery {
+    pub email: String,
 }
 
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
@@ -388,17 +642,54 @@ fn normalized_email(raw: &str) -> Option<String> {
     }
 }
 
-fn is_disposable_email(email: &str) -> bool {
-    const DISPOSABLE_DOMAINS: &[&str] = &["mailinator.com", "tempmail.com", "guerrillamail.com"];
+/// Whether a normalized `email` is refused: its domain is on the blocklist
+/// or, with `SIGNUP_MX_CHECK`, cannot receive mail. See [`signup_guard`].
+async fn is_refused_email(state: &AppState, email: &str) -> Result<bool, ApiError> {
+    let patterns = state.db.blocked_email_patterns_cached().await.map_err(into_api_error)?;
+    if signup_guard::is_blocked(&patterns, email) {
+        return Ok(true);
+    }
+    if !state.config.signup_mx_check {
+        return Ok(false);
+    }
+    Ok(match signup_guard::email_domain(email) {
+        Some(domain) => !signup_guard::has_mail_host(domain, state.config.signup_mx_timeout).await,
+        None => false,
+    })
+}
 
-    email
-        .rsplit_once('@')
-        .map(|(_, domain)| DISPOSABLE_DOMAINS.contains(&domain))
-        .unwrap_or(false)
+/// A 400 when [`is_refused_email`].
+async fn reject_refused_email(state: &AppState, email: &str) -> Result<(), ApiError> {
+    if is_refused_email(state, email).await? {
+        return Err(ApiError::bad_request("email must be a valid, non-disposable address"));
+    }
+    Ok(())
 }
 
-use crate::security::extract_client_ip_cidrs;
+/// The caller's address for rate limiting, honouring forwarding headers only
+/// from `TRUSTED_PROXY_CIDRS`; see [`crate::security::extract_client_ip_cidrs`].
+fn client_ip(
+    config: &crate::config::Config,
+    headers: &HeaderMap,
+    connect_info: Option<&axum::extract::ConnectInfo<std::net::SocketAddr>>,
+) -> String {
+    crate::security::extract_client_ip_cidrs(
+        headers,
+        connect_info,
+        config.trust_proxy,
+        &config.trusted_proxy_cidrs,
+    )
+}
 
+/// Start a double-opt-in subscription.
+///
+/// This handler is the pattern for request validation: the field rules live
+/// on [`NewsletterSubscribeRequest`] as `#[validate]` attributes, and
+/// [`ValidatedJson`] rejects a body that breaks any of them with a
+/// `422 INVALID_FIELDS` before the handler runs. The handler then only
+/// normalises the fields and applies policy that is not about their shape,
+/// such as the blocked-domain check. Honeypot hits get the same 202 as real
+/// signups but nothing is stored or emailed.
 #[utoipa::path(
     post,
     path = "/api/v1/newsletter/subscribe",
@@ -406,36 +697,31 @@ use crate::security::extract_client_ip_cidrs;
     request_body = NewsletterSubscribeRequest,
     responses(
         (status = 202, description = "Subscription request accepted", body = NewsletterResponse),
-        (status = 400, description = "Invalid email address", body = NewsletterResponse),
+        (status = 400, description = "Email domain is blocked", body = NewsletterResponse),
+        (status = 422, description = "Invalid email or oversized source", body = ApiError),
     )
 )]
 pub async fn newsletter_subscribe(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
-    Json(payload): Json<NewsletterSubscribeRequest>,
+    ValidatedJson(payload): ValidatedJson<NewsletterSubscribeRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let ip = extract_client_ip_cidrs(
-        &headers,
-        connect_info.as_ref(),
-        state.config.trust_proxy,
-        &state.config.trusted_proxy_cidrs,
+    let ip = client_ip(&state.config, &headers, connect_info.as_ref());
+    let email = validation::normalize_email(&payload.email);
+    let accepted = (
+        StatusCode::ACCEPTED,
+        Json(NewsletterResponse {
+            success: true,
+            message: "Please check your email to confirm your subscription.".to_string(),
+        }),
     );
 
-    let email = match normalized_email(&payload.email) {
-        Some(value) => value,
-        None => {
-            return Ok((
-                StatusCode::BAD_REQUEST,
-                Json(NewsletterResponse {
-                    success: false,
-                    message: "Invalid email address.".to_string(),
-                }),
-            ));
-        }
-    };
-
-    if is_disposable_email(&email) {
+    if signup_guard::is_honeypot_hit(payload.website.as_deref()) {
+        tracing::info!(client_ip = %ip, "newsletter honeypot hit; signup dropped");
+        return Ok(accepted);
+    }
+    if is_refused_email(&state, &email).await? {
         return Ok((
             StatusCode::BAD_REQUEST,
             Json(NewsletterResponse {
@@ -444,18 +730,7 @@ pub async fn newsletter_subscribe(
             }),
         ));
     }
-    let source = payload
-        .source
-        .unwrap_or_else(|| "direct".to_string())
-        .trim()
-        .chars()
-        .take(64)
-        .collect::<String>();
-    let source = if source.is_empty() {
-        "direct".to_string()
-    } else {
-        source
-    };
+    let source = acquisition::normalize_source(payload.source.as_deref());
 
     // Always upsert and send confirmation — uniform response prevents enumeration.
     // For already-confirmed active subscribers we skip the DB write but still
@@ -473,55 +748,116 @@ pub async fn newsletter_subscribe(
 
     if !already_active {
         let token = Uuid::new_v4().to_string();
-        state
+        let subscriber_id = state
             .db
             .newsletter_upsert_pending(&email, &source, &token)
             .await
             .map_err(into_api_error)?;
 
-        let confirm_url = format!(
-            "{}/api/v1/newsletter/confirm?token={token}",
-            state.config.base_url.trim_end_matches('/')
-        );
-        let unsubscribe_url = state
-            .config
-            .unsubscribe_signing_secret
-            .as_deref()
-            .and_then(|secret| crate::newsletter::generate_unsubscribe_token(&email, secret).ok())
-            .map(|tok| format!(
-                "{}/api/v1/newsletter/unsubscribe?token={tok}",
-                state.config.base_url.trim_end_matches('/')
-            ))
-            .unwrap_or_default();
-        let template_data = serde_json::json!({
-            "confirm_url": confirm_url,
-            "unsubscribe_url": unsubscribe_url,
-            "email": email
-        });
-        state
-            .email_queue
-            .enqueue(
-                crate::email::types::EmailJobType::NewsletterConfirmation,
-                &email,
-                "newsletter_confirmation",
-                template_data,
-                0,
-            )
-            .await
-            .map_err(into_api_error)?;
+        enqueue_newsletter_confirmation(&state, subscriber_id, &email, &token).await?;
     }
 
     let request_id = headers
         .get(crate::correlation::REQUEST_ID_HEADER)
         .and_then(|v| v.to_str().ok())
         .unwrap_or("-");
-    tracing::info!(request_id, email = %email, source = %source, ip = %ip, "newsletter subscription attempt");
+    tracing::info!(
+        request_id,
+        email = %crate::log_redact::mask_email(&email),
+        source = %source,
+        ip = %ip,
+        "newsletter subscription attempt"
+    );
+
+    Ok(accepted)
+}
+
+/// Queue the double-opt-in email carrying `token` for `email`.
+async fn enqueue_newsletter_confirmation(
+    state: &AppState,
+    subscriber_id: Uuid,
+    email: &str,
+    token: &str,
+) -> Result<(), ApiError> {
+    let confirm_url = format!(
+        "{}/api/v1/newsletter/confirm?token={token}",
+        state.config.base_url.trim_end_matches('/')
+    );
+    let unsubscribe_url = state
+        .config
+        .unsubscribe_signing_secret
+        .as_deref()
+        .and_then(|secret| crate::newsletter::sign_preferences_token(subscriber_id, secret))
+        .map(|tok| format!(
+            "{}/api/v1/newsletter/unsubscribe?token={tok}",
+            state.config.base_url.trim_end_matches('/')
+        ))
+        .unwrap_or_default();
+    let preferences_url = crate::newsletter::preferences_url(
+        &state.config.base_url,
+        subscriber_id,
+        state.config.unsubscribe_signing_secret.as_deref(),
+    );
+    let template_data = serde_json::json!({
+        "confirm_url": confirm_url,
+        "unsubscribe_url": unsubscribe_url,
+        "preferences_url": preferences_url,
+        "email": email
+    });
+    state
+        .email_queue
+        .enqueue(
+            crate::email::types::EmailJobType::NewsletterConfirmation,
+            email,
+            "newsletter_confirmation",
+            template_data,
+            0,
+        )
+        .await
+        .map_err(into_api_error)?;
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/newsletter/resend-confirmation",
+    tag = "newsletter",
+    request_body = NewsletterEmailRequest,
+    responses(
+        (status = 202, description = "Accepted; a new confirmation email is sent if a pending subscription exists", body = NewsletterResponse),
+        (status = 422, description = "Invalid email", body = ApiError),
+        (status = 429, description = "Rate limit exceeded"),
+    )
+)]
+pub async fn newsletter_resend_confirmation(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(payload): ValidatedJson<NewsletterEmailRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let email = validation::normalize_email(&payload.email);
+
+    // The token is only swapped for a pending subscription whose last email
+    // went out at least RESEND_COOLDOWN ago. Unknown, confirmed and cooling
+    // down addresses all get the same 202 so the endpoint cannot be used to
+    // probe the subscriber list.
+    let token = Uuid::new_v4().to_string();
+    let subscriber_id = state
+        .db
+        .newsletter_resend_confirmation(
+            &email,
+            &token,
+            crate::newsletter::RESEND_COOLDOWN.as_secs(),
+        )
+        .await
+        .map_err(into_api_error)?;
+    if let Some(subscriber_id) = subscriber_id {
+        enqueue_newsletter_confirmation(&state, subscriber_id, &email, &token).await?;
+    }
 
     Ok((
         StatusCode::ACCEPTED,
         Json(NewsletterResponse {
             success: true,
-            message: "Please check your email to confirm your subscription.".to_string(),
+            message: "If a subscription is awaiting confirmation, a new email is on its way.".to_string(),
         }),
     ))
 }
@@ -534,7 +870,8 @@ pub async fn newsletter_subscribe(
     responses(
         (status = 200, description = "Subscription confirmed", body = NewsletterResponse),
         (status = 400, description = "Missing or invalid token", body = NewsletterResponse),
-        (status = 404, description = "Token not found or expired", body = NewsletterResponse),
+        (status = 404, description = "Token not found", body = NewsletterResponse),
+        (status = 410, description = "Token expired; request a new one via /api/v1/newsletter/resend-confirmation", body = NewsletterResponse),
     )
 )]
 pub async fn newsletter_confirm(
@@ -551,20 +888,32 @@ pub async fn newsletter_confirm(
         ));
     }
 
-    let updated = state
+    let outcome = state
         .db
         .newsletter_confirm_by_token(query.token.trim(), state.config.newsletter_token_ttl_secs)
         .await
         .map_err(into_api_error)?;
 
-    if !updated {
-        return Ok((
-            StatusCode::NOT_FOUND,
-            Json(NewsletterResponse {
-                success: false,
-                message: "Invalid or expired confirmation token.".to_string(),
-            }),
-        ));
+    match outcome {
+        crate::db::NewsletterConfirmation::Confirmed => {}
+        crate::db::NewsletterConfirmation::Expired => {
+            return Ok((
+                StatusCode::GONE,
+                Json(NewsletterResponse {
+                    success: false,
+                    message: "Confirmation token has expired. Request a new confirmation email.".to_string(),
+                }),
+            ));
+        }
+        crate::db::NewsletterConfirmation::NotFound => {
+            return Ok((
+                StatusCode::NOT_FOUND,
+                Json(NewsletterResponse {
+                    success: false,
+                    message: "Invalid confirmation token.".to_string(),
+                }),
+            ));
+        }
     }
 
     Ok((
@@ -603,7 +952,18 @@ pub async fn newsletter_unsubscribe(
         }
     };
 
-    let email = match crate::newsletter::validate_unsubscribe_token(&query.token, &secret) {
+    // Unsubscribe links carry the same signed subscriber token as the
+    // preferences page.
+    let subscriber_id = crate::newsletter::verify_preferences_token(query.token.trim(), &secret);
+    let email = match subscriber_id {
+        Some(id) => state
+            .db
+            .newsletter_email_by_id(id)
+            .await
+            .map_err(into_api_error)?,
+        None => None,
+    };
+    let email = match email {
         Some(e) => e,
         None => {
             return Ok((
@@ -622,7 +982,10 @@ pub async fn newsletter_unsubscribe(
         .await
         .map_err(into_api_error)?;
 
-    tracing::info!("[newsletter] unsubscribed email={email}");
+    tracing::info!(
+        "[newsletter] unsubscribed email={}",
+        crate::log_redact::mask_email(&email)
+    );
 
     Ok((
         StatusCode::OK,
@@ -633,38 +996,117 @@ pub async fn newsletter_unsubscribe(
     ))
 }
 
+/// Resolve a preferences token to the subscriber's email. A bad signature
+/// and a subscriber that no longer exists are the same 404.
+async fn preferences_subscriber(state: &AppState, token: &str) -> Result<String, ApiError> {
+    let secret = state
+        .config
+        .unsubscribe_signing_secret
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Email preferences are not configured."))?;
+    let invalid = || {
+        ApiError::new(
+            ApiErrorKind::NotFound,
+            "INVALID_PREFERENCES_TOKEN",
+            "Invalid preferences token.",
+        )
+    };
+    let subscriber_id =
+        crate::newsletter::verify_preferences_token(token.trim(), secret).ok_or_else(invalid)?;
+    state
+        .db
+        .newsletter_email_by_id(subscriber_id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(invalid)
+}
+
 #[utoipa::path(
     get,
-    path = "/api/v1/newsletter/gdpr/export",
+    path = "/api/v1/newsletter/preferences",
     tag = "newsletter",
-    params(NewsletterExportQuery),
+    params(NewsletterPreferencesQuery),
     responses(
-        (status = 200, description = "GDPR data export", body = NewsletterExportResponse),
-        (status = 400, description = "Invalid email", body = NewsletterResponse),
-        (status = 404, description = "No record found", body = NewsletterResponse),
-        (status = 429, description = "Rate limited", body = NewsletterResponse),
+        (status = 200, description = "Current email preferences", body = NewsletterPreferences),
+        (status = 404, description = "Invalid preferences token", body = ApiError),
+        (status = 503, description = "Preference tokens are not configured", body = ApiError),
     )
 )]
-pub async fn newsletter_gdpr_export(
+pub async fn newsletter_preferences_get(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
-    Query(query): Query<NewsletterExportQuery>,
-) -> Result<Response, ApiError> {
-    use crate::security::extract_client_ip_cidrs;
-    let ip = extract_client_ip_cidrs(
-        &headers,
-        connect_info.as_ref(),
-        state.config.trust_proxy,
-        &state.config.trusted_proxy_cidrs,
-    );
-    let allowed_ip = state
-        .newsletter_rate_limiter
-        .allow(
-            &format!("gdpr_export:ip:{ip}"),
-            state.config.gdpr_export_rate_limit as usize,
-            std::time::Duration::from_secs(state.config.gdpr_export_rate_window_secs),
-        )
+    Query(query): Query<NewsletterPreferencesQuery>,
+) -> Result<Json<NewsletterPreferences>, ApiError> {
+    let email = preferences_subscriber(&state, &query.token).await?;
+    let rows = state
+        .db
+        .newsletter_get_preferences(&email)
+        .await
+        .map_err(into_api_error)?;
+    Ok(Json(NewsletterPreferences::from_rows(&rows)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/newsletter/preferences",
+    tag = "newsletter",
+    params(NewsletterPreferencesQuery),
+    request_body = NewsletterPreferencesUpdate,
+    responses(
+        (status = 200, description = "Preferences after the update", body = NewsletterPreferences),
+        (status = 422, description = "Unknown category or non-boolean value in the body"),
+        (status = 404, description = "Invalid preferences token", body = ApiError),
+        (status = 503, description = "Preference tokens are not configured", body = ApiError),
+    )
+)]
+pub async fn newsletter_preferences_update(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<NewsletterPreferencesQuery>,
+    Json(payload): Json<NewsletterPreferencesUpdate>,
+) -> Result<Json<NewsletterPreferences>, ApiError> {
+    let email = preferences_subscriber(&state, &query.token).await?;
+    let changes = payload.changes();
+    if !changes.is_empty() {
+        state
+            .db
+            .newsletter_set_preferences(&email, &changes)
+            .await
+            .map_err(into_api_error)?;
+        tracing::info!(changed = changes.len(), "[newsletter] preferences updated");
+    }
+    let rows = state
+        .db
+        .newsletter_get_preferences(&email)
+        .await
+        .map_err(into_api_error)?;
+    Ok(Json(NewsletterPreferences::from_rows(&rows)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/newsletter/gdpr/export",
+    tag = "newsletter",
+    params(NewsletterExportQuery),
+    responses(
+        (status = 200, description = "GDPR data export", body = NewsletterExportResponse),
+        (status = 400, description = "Invalid email", body = NewsletterResponse),
+        (status = 404, description = "No record found", body = NewsletterResponse),
+        (status = 429, description = "Rate limited", body = NewsletterResponse),
+    )
+)]
+pub async fn newsletter_gdpr_export(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    Query(query): Query<NewsletterExportQuery>,
+) -> Result<Response, ApiError> {
+    let ip = client_ip(&state.config, &headers, connect_info.as_ref());
+    let allowed_ip = state
+        .newsletter_rate_limiter
+        .allow(
+            &format!("gdpr_export:ip:{ip}"),
+            state.config.gdpr_export_rate_limit as usize,
+            std::time::Duration::from_secs(state.config.gdpr_export_rate_window_secs),
+        )
         .await;
     if !allowed_ip {
         return Ok((
@@ -742,22 +1184,14 @@ pub async fn newsletter_gdpr_export(
     request_body = NewsletterEmailRequest,
     responses(
         (status = 200, description = "Data deleted", body = NewsletterResponse),
-        (status = 400, description = "Invalid email", body = NewsletterResponse),
+        (status = 422, description = "Invalid email", body = ApiError),
     )
 )]
 pub async fn newsletter_gdpr_delete(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<NewsletterEmailRequest>,
+    ValidatedJson(payload): ValidatedJson<NewsletterEmailRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let Some(email) = normalized_email(&payload.email) else {
-        return Ok((
-            StatusCode::BAD_REQUEST,
-            Json(NewsletterResponse {
-                success: false,
-                message: "Invalid email address.".to_string(),
-            }),
-        ));
-    };
+    let email = validation::normalize_email(&payload.email);
 
     let _ = state
         .db
@@ -765,7 +1199,10 @@ pub async fn newsletter_gdpr_delete(
         .await
         .map_err(into_api_error)?;
 
-    tracing::info!("[newsletter] gdpr delete email={email}");
+    tracing::info!(
+        "[newsletter] gdpr delete email={}",
+        crate::log_redact::mask_email(&email)
+    );
 
     Ok((
         StatusCode::OK,
@@ -776,242 +1213,4003 @@ pub async fn newsletter_gdpr_delete(
     ))
 }
 
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
+pub struct GdprSubjectRequest {
+    /// Email address of the data subject; matched case-insensitively.
+    pub email: String,
+}
+
+fn gdpr_subject(raw: &str) -> Result<String, ApiError> {
+    normalized_email(raw).ok_or_else(|| ApiError::bad_request("Invalid email address."))
+}
+
+/// Everything stored about an email address, one section per table (see
+/// [`crate::gdpr::GDPR_TABLES`]). Sections are empty, not missing, when
+/// nothing matched.
 #[utoipa::path(
     get,
-    path = "/api/v1/statistics",
-    tag = "markets",
+    path = "/api/v1/gdpr/export",
+    tag = "gdpr",
+    params(GdprSubjectRequest),
     responses(
-        (status = 200, description = "Platform statistics"),
-    )
+        (status = 200, description = "Personal data held for the address", body = GdprExport),
+        (status = 400, description = "Invalid email", body = ApiError),
+    ),
+    security(("api_key" = []))
 )]
-pub async fn statistics(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
-    let start = Instant::now();
-    let cache_key = keys::api_statistics();
-    let ttl = Duration::from_secs(5 * 60);
-    let endpoint = "statistics";
+pub async fn gdpr_export(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<GdprSubjectRequest>,
+) -> Result<Json<GdprExport>, ApiError> {
+    let email = gdpr_subject(&query.email)?;
+    let export = state.db.gdpr_collect(&email).await.map_err(into_api_error)?;
+    let rows: usize = export.sections.values().map(Vec::len).sum();
+    tracing::info!(rows, "[gdpr] data export generated");
+    Ok(Json(export))
+}
 
-    let (payload, hit) = state
-        .cache
-        .get_or_set_json(&cache_key, ttl, || async {
-            let data = state.db.statistics_cached().await?;
-            Ok(data)
-        })
-        .await
-        .map_err(into_api_error)?;
+/// Erase an email address from every table in one transaction: rows are
+/// deleted, email jobs and events anonymized, and the suppression list kept.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/gdpr/delete",
+    tag = "gdpr",
+    request_body = GdprSubjectRequest,
+    responses(
+        (status = 200, description = "Rows deleted and anonymized per table", body = GdprDeleteReport),
+        (status = 400, description = "Invalid email", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn gdpr_delete(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<GdprSubjectRequest>,
+) -> Result<Json<GdprDeleteReport>, ApiError> {
+    let email = gdpr_subject(&payload.email)?;
+    let report = state.db.gdpr_erase(&email).await.map_err(into_api_error)?;
+    tracing::info!(rows = report.total(), "[gdpr] data subject erased");
+    Ok(Json(report))
+}
 
-    if hit {
-        state.metrics.observe_hit("api", endpoint);
-    } else {
-        state.metrics.observe_miss("api", endpoint);
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct MarketWatchRequest {
+    /// Address notifications are sent to.
+    pub email: String,
+    /// Optional Stellar account (`G...`) the watcher bets from.
+    pub address: Option<String>,
+    /// Events to be notified about; defaults to `["resolve"]`.
+    pub notify_on: Option<Vec<WatchTrigger>>,
+}
+
+impl MarketWatchRequest {
+    /// Normalised (email, address, triggers) or a 400.
+    fn validate(&self) -> Result<(String, Option<String>, Vec<WatchTrigger>), ApiError> {
+        let email = normalized_email(&self.email)
+            .ok_or_else(|| ApiError::bad_request("email must be a valid, non-disposable address"))?;
+        let address = match self.address.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(raw) => {
+                stellar_strkey::ed25519::PublicKey::from_string(raw)
+                    .map_err(|_| ApiError::bad_request("address must be a Stellar account (G...)"))?;
+                Some(raw.to_string())
+            }
+        };
+        let mut notify_on = self.notify_on.clone().unwrap_or_else(|| vec![WatchTrigger::Resolve]);
+        notify_on.sort_by_key(|t| t.label());
+        notify_on.dedup();
+        if notify_on.is_empty() {
+            return Err(ApiError::bad_request("notify_on must name at least one event"));
+        }
+        Ok((email, address, notify_on))
     }
-    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketWatchResponse {
+    pub watch: MarketWatch,
+    /// Removes this watch via `DELETE /api/v1/watches/{token}`. The same
+    /// token is carried in every notification's unsubscribe link.
+    pub unsubscribe_token: String,
+}
 
-    Ok((StatusCode::OK, Json(payload)))
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct MarketWatchUnsubscribeQuery {
+    pub token: String,
 }
 
+/// Watch a market for resolution, dispute, or deadline emails on the network
+/// named by `X-Network`. Watching again with the same email replaces the
+/// triggers and keeps the existing unsubscribe token.
 #[utoipa::path(
-    get,
-    path = "/api/v1/markets/featured",
+    post,
+    path = "/api/v1/markets/{market_id}/watches",
     tag = "markets",
-    params(PaginationQuery),
+    params(
+        ("market_id" = i64, Path, description = "Market ID"),
+        ("X-Network" = Option<String>, Header, description = "Network the market lives on; defaults to the primary network"),
+    ),
+    request_body = MarketWatchRequest,
     responses(
-        (status = 200, description = "Paginated list of featured markets"),
+        (status = 201, description = "Watch created or updated", body = MarketWatchResponse),
+        (status = 400, description = "Invalid email, address, or triggers", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
     )
 )]
-pub async fn featured_markets(
+pub async fn market_watch_create(
     State(state): State<Arc<AppState>>,
-    Query(query): Query<PaginationQuery>,
+    Network(client): Network,
+    Path(market_id): Path<i64>,
+    Json(body): Json<MarketWatchRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let start = Instant::now();
-    let limit = query.limit();
-    let cursor = query.cursor();
-    let cache_key = keys::api_featured_markets();
-    let ttl = Duration::from_secs(2 * 60);
-    let endpoint = "featured_markets";
-
-    let featured_limit = state.config.featured_limit;
-
-    let (payload, hit) = state
-        .cache
-        .get_or_set_json(&cache_key, ttl, || async {
-            let markets = state.db.featured_markets_cached(featured_limit).await?;
-            let chain_futures = markets
-                .iter()
-                .map(|m| state.blockchain.market_data_cached(m.id));
-            let chain_data = join_all(chain_futures).await;
-
-            let mut view = Vec::with_capacity(markets.len());
-            for (m, chain_result) in markets.into_iter().zip(chain_data.into_iter()) {
-                let chain = chain_result?;
-                view.push(FeaturedMarketView {
-                    id: m.id,
-                    title: m.title,
-                    volume: m.volume,
-                    ends_at: m.ends_at,
-                    onchain_volume: chain.onchain_volume,
-                    resolved_outcome: chain.resolved_outcome,
-                });
-            }
-            Ok(view)
-        })
+    let (email, address, notify_on) = body.validate()?;
+    reject_refused_email(&state, &email).await?;
+    let (watch, unsubscribe_token) = state
+        .db
+        .market_watch_upsert(
+            client.network(),
+            market_id,
+            &email,
+            address.as_deref(),
+            &notify_on,
+            &market_watch::new_unsubscribe_token(),
+        )
         .await
-        .map_err(into_api_error)?;
-
-    let start_idx = cursor
-        .as_ref()
-        .and_then(|c| c.parse::<usize>().ok())
-        .unwrap_or(0);
-    let end_idx = (start_idx + limit as usize).min(payload.len());
-    let has_more = end_idx < payload.len();
-    let next_cursor = if has_more {
-        Some(end_idx.to_string())
-    } else {
-        None
-    };
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::market_not_found(market_id))?;
 
-    let paginated = PaginatedResponse::new(
-        payload[start_idx..end_idx].to_vec(),
-        next_cursor,
-        limit,
-        has_more,
-    );
-
-    if hit {
-        state.metrics.observe_hit("api", endpoint);
-    } else {
-        state.metrics.observe_miss("api", endpoint);
-    }
-    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+    Ok((
+        StatusCode::CREATED,
+        Json(MarketWatchResponse {
+            watch,
+            unsubscribe_token,
+        }),
+    ))
+}
 
-    Ok((StatusCode::OK, Json(paginated)))
+#[utoipa::path(
+    delete,
+    path = "/api/v1/watches/{token}",
+    tag = "markets",
+    params(
+        ("token" = String, Path, description = "Unsubscribe token returned when the watch was created"),
+    ),
+    responses(
+        (status = 204, description = "Watch removed"),
+        (status = 404, description = "Unknown token", body = ApiError),
+    )
+)]
+pub async fn market_watch_delete(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    remove_market_watch(&state, &token).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
 
+/// Unsubscribe link carried in watch notification emails.
 #[utoipa::path(
     get,
-    path = "/api/v1/content",
+    path = "/api/v1/watches/unsubscribe",
     tag = "markets",
-    params(PaginationQuery),
+    params(MarketWatchUnsubscribeQuery),
     responses(
-        (status = 200, description = "Paginated content items"),
+        (status = 200, description = "Watch removed", body = NewsletterResponse),
+        (status = 404, description = "Unknown token", body = ApiError),
     )
 )]
-pub async fn content(
+pub async fn market_watch_unsubscribe(
     State(state): State<Arc<AppState>>,
-    Query(query): Query<PaginationQuery>,
+    Query(query): Query<MarketWatchUnsubscribeQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let start = Instant::now();
-    let limit = query.limit();
-    let cursor = query.cursor();
-    let endpoint = "content";
-
-    let cache_key = keys::api_content(limit.into());
-    let ttl = Duration::from_secs(60 * 60);
+    remove_market_watch(&state, &query.token).await?;
+    Ok((
+        StatusCode::OK,
+        Json(NewsletterResponse {
+            success: true,
+            message: "You will no longer receive emails about this market.".to_string(),
+        }),
+    ))
+}
 
-    let (payload, hit) = state
-        .cache
-        .get_or_set_json(&cache_key, ttl, || async {
-            let data = state.db.content_cached(limit.into()).await?;
-            Ok(data)
-        })
+async fn remove_market_watch(state: &AppState, token: &str) -> Result<(), ApiError> {
+    let removed = state
+        .db
+        .market_watch_delete_by_token(token.trim())
         .await
         .map_err(into_api_error)?;
-
-    let start_idx = cursor
-        .as_ref()
-        .and_then(|c| c.parse::<usize>().ok())
-        .unwrap_or(0);
-    let end_idx = (start_idx + limit as usize).min(payload.len());
-    let has_more = end_idx < payload.len();
-    let next_cursor = if has_more {
-        Some(end_idx.to_string())
-    } else {
-        None
-    };
-
-    let paginated = PaginatedResponse::new(
-        payload[start_idx..end_idx].to_vec(),
-        next_cursor,
-        limit,
-        has_more,
-    );
-
-    if hit {
-        state.metrics.observe_hit("api", endpoint);
+    if removed {
+        Ok(())
     } else {
-        state.metrics.observe_miss("api", endpoint);
+        Err(ApiError::not_found("unknown or already used watch token"))
     }
-    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
-
-    Ok((StatusCode::OK, Json(paginated)))
 }
 
-#[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct InvalidationResult {
-    pub invalidated_keys: usize,
-}
+// ── Market images ─────────────────────────────────────────────────────────────
 
-/// Resolve a market by its ID.
-///
-/// Workflow:
-/// 1. Fetch the current market state from the blockchain.
-/// 2. Persist the resolved outcome to the database.
-/// 3. Invalidate only the cache keys that are directly affected by this market's
-///    resolution (specific market key, oracle result, statistics aggregates, and
-///    featured-markets list). Content pages and per-user bet lists are left intact
-///    because they are not affected by a single market resolution.
-/// 4. Cache invalidation only runs after a successful write — a failed DB update
-///    leaves the cache untouched.
-#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
-pub struct ResolveMarketRequest {
-    /// The winning outcome index (0-based).
-    pub outcome_index: u32,
+/// Log a media storage failure and answer 503 without its details.
+fn storage_unavailable(err: anyhow::Error) -> ApiError {
+    tracing::warn!(error = %format!("{err:#}"), "media storage failed");
+    ApiError::service_unavailable("Media storage is temporarily unavailable.")
 }
 
+/// Upload a market's cover image as the `image` field of a multipart form.
+/// The format is read from the file's leading bytes; only PNG, JPEG and WebP
+/// are accepted, whatever the declared type or file name.
 #[utoipa::path(
     post,
-    path = "/api/v1/markets/{market_id}/resolve",
+    path = "/api/v1/admin/markets/{market_id}/image",
     tag = "markets",
-    params(
-        ("market_id" = i64, Path, description = "Market database ID"),
-    ),
-    request_body = ResolveMarketRequest,
+    params(("market_id" = i64, Path, description = "Market ID")),
+    request_body(content = String, content_type = "multipart/form-data", description = "`image` file field"),
     responses(
-        (status = 200, description = "Market resolved and cache invalidated", body = InvalidationResult),
-        (status = 400, description = "Bad request", body = ApiError),
-        (status = 500, description = "Internal error", body = ApiError),
+        (status = 200, description = "Image stored", body = MarketImage),
+        (status = 400, description = "Malformed form or missing `image` field", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+        (status = 413, description = "Image larger than MARKET_IMAGE_MAX_BYTES", body = ApiError),
+        (status = 415, description = "Not a PNG, JPEG or WebP file", body = ApiError),
+        (status = 503, description = "Media storage unavailable", body = ApiError),
     ),
     security(("api_key" = []))
 )]
-pub async fn resolve_market(
+pub async fn market_image_upload(
     State(state): State<Arc<AppState>>,
     Path(market_id): Path<i64>,
-    Json(payload): Json<ResolveMarketRequest>,
-) -> Result<impl IntoResponse, ApiError> {
-    // 1. Persist the resolution to the database.
+    mut multipart: Multipart,
+) -> Result<Json<MarketImage>, ApiError> {
+    let malformed = |e: axum::extract::multipart::MultipartError| {
+        ApiError::bad_request(format!("malformed multipart body: {}", e.body_text()))
+    };
+    let mut upload = None;
+    while let Some(field) = multipart.next_field().await.map_err(malformed)? {
+        if field.name() == Some(market_image::UPLOAD_FIELD) {
+            upload = Some(field.bytes().await.map_err(malformed)?);
+            break;
+        }
+    }
+    let bytes = upload.ok_or_else(|| {
+        ApiError::bad_request(format!("missing `{}` file field", market_image::UPLOAD_FIELD))
+    })?;
+
+    let limit = state.config.market_image_max_bytes;
+    if bytes.len() > limit {
+        return Err(ApiError::new(
+            ApiErrorKind::PayloadTooLarge,
+            "IMAGE_TOO_LARGE",
+            format!("image exceeds the maximum size of {limit} bytes"),
+        )
+        .with_details(serde_json::json!({ "limit_bytes": limit })));
+    }
+    let format = ImageFormat::sniff(&bytes).ok_or_else(|| {
+        ApiError::new(
+            ApiErrorKind::UnsupportedMediaType,
+            "UNSUPPORTED_IMAGE_TYPE",
+            "image must be a PNG, JPEG or WebP file",
+        )
+        .with_details(serde_json::json!({ "allowed": ["image/png", "image/jpeg", "image/webp"] }))
+    })?;
+
+    // Check first so a 404 never leaves an orphaned object behind.
     state
         .db
-        .resolve_market(market_id, payload.outcome_index)
+        .market_detail(market_id)
         .await
-        .map_err(into_api_error)?;
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::market_not_found(market_id))?;
 
-    // 2. Invalidate only the keys affected by this market's resolution via tag.
+    let image = MarketImage::new(market_id, format, &bytes, &state.config.base_url);
+    let storage = storage::from_config(&state.config.media_storage);
+    storage
+        .put(&image.key, bytes, &image.content_type)
+        .await
+        .map_err(storage_unavailable)?;
+
+    let previous = state
+        .db
+        .set_market_image(&image)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::market_not_found(market_id))?;
+    if let Some(previous) = previous.filter(|key| *key != image.key) {
+        if let Err(e) = storage.delete(&previous).await {
+            tracing::warn!(market_id, key = %previous, error = %format!("{e:#}"), "failed to delete replaced market image");
+        }
+    }
+
+    let invalidated = state
+        .cache
+        .invalidate_tag(&InvalidationTag::MarketImageChanged {
+            market_id,
+            featured_limit: state.config.featured_limit,
+        })
+        .await
+        .map_err(into_api_error)?;
+    state.metrics.observe_invalidation("market_image", invalidated);
+
+    Ok(Json(image))
+}
+
+/// A market's cover image. Responses are cacheable for a year: uploads
+/// change `image_url`, so a given URL always names the same bytes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/markets/{market_id}/image",
+    tag = "markets",
+    params(("market_id" = i64, Path, description = "Market ID")),
+    responses(
+        (status = 200, description = "Image bytes (PNG, JPEG or WebP)"),
+        (status = 304, description = "Client copy is current"),
+        (status = 404, description = "Market not found or has no image", body = ApiError),
+        (status = 503, description = "Media storage unavailable", body = ApiError),
+    )
+)]
+pub async fn market_image(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(market_id): Path<i64>,
+) -> Result<Response, ApiError> {
+    let no_image = || ApiError::not_found(format!("market {market_id} has no image"));
+    let image = state
+        .db
+        .market_image(market_id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(no_image)?;
+
+    let mut response = if crate::etag::if_none_match(&headers, &image.etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        let object = storage::from_config(&state.config.media_storage)
+            .get(&image.key)
+            .await
+            .map_err(storage_unavailable)?
+            .ok_or_else(|| {
+                tracing::warn!(market_id, key = %image.key, "market image missing from storage");
+                no_image()
+            })?;
+        ([(header::CONTENT_TYPE, image.content_type.clone())], object.bytes).into_response()
+    };
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&image.etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(market_image::CACHE_CONTROL),
+    );
+    Ok(response)
+}
+
+// ── Wallet auth and watchlists ────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AuthChallengeQuery {
+    /// Stellar account (`G...`) the challenge is for.
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AuthVerifyRequest {
+    /// Stellar account (`G...`) the challenge was issued to.
+    pub address: String,
+    pub nonce: String,
+    /// Base64 ed25519 signature of the challenge message.
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AuthRefreshRequest {
+    /// A session token that has not yet expired.
+    pub token: String,
+}
+
+/// 403 unless the bearer token was issued to `address`.
+fn require_same_address(authed: &AuthedAddress, address: &str) -> Result<(), ApiError> {
+    if authed.0 == address {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "ADDRESS_MISMATCH",
+            "bearer token was issued to a different address",
+        ))
+    }
+}
+
+/// Issue a single-use challenge for `address`. Sign `message` with the
+/// account's key and post the nonce and base64 signature to
+/// `/api/v1/auth/verify` within five minutes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/challenge",
+    tag = "auth",
+    params(AuthChallengeQuery),
+    responses(
+        (status = 200, description = "Challenge issued", body = Challenge),
+        (status = 400, description = "Malformed address", body = ApiError),
+        (status = 503, description = "Challenge store unavailable", body = ApiError),
+    )
+)]
+pub async fn auth_challenge(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AuthChallengeQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let challenge = wallet_auth::issue_challenge(&state.cache, query.address.trim()).await?;
+    Ok((StatusCode::OK, Json(challenge)))
+}
+
+/// Exchange a signed challenge for a session token. The challenge is used up
+/// whether or not the signature verifies.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/verify",
+    tag = "auth",
+    request_body = AuthVerifyRequest,
+    responses(
+        (status = 200, description = "Session token issued", body = SessionToken),
+        (status = 400, description = "Malformed address or missing fields", body = ApiError),
+        (status = 401, description = "Unknown, used, or wrongly signed challenge", body = ApiError),
+        (status = 503, description = "Challenge store unavailable", body = ApiError),
+    )
+)]
+pub async fn auth_verify(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<AuthVerifyRequest>,
+) -> Result<Json<SessionToken>, ApiError> {
+    let address = payload.address.trim();
+    wallet_auth::verify_challenge(
+        &state.cache,
+        address,
+        payload.nonce.trim(),
+        payload.signature.trim(),
+    )
+    .await?;
+    let now = chrono::Utc::now().timestamp();
+    let session = SessionKeys::from_config(&state.config)
+        .issue(address, now, now)
+        .map_err(ApiError::internal)?;
+    Ok(Json(session))
+}
+
+/// Trade an unexpired session token for a fresh one. Sessions can be
+/// extended this way until `refresh_until`, after which the wallet signs a
+/// new challenge.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    tag = "auth",
+    request_body = AuthRefreshRequest,
+    responses(
+        (status = 200, description = "Session token reissued", body = SessionToken),
+        (status = 401, description = "Invalid or expired token, or session too old", body = ApiError),
+    )
+)]
+pub async fn auth_refresh(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<AuthRefreshRequest>,
+) -> Result<Json<SessionToken>, ApiError> {
+    let session = SessionKeys::from_config(&state.config)
+        .refresh(payload.token.trim(), chrono::Utc::now().timestamp())?;
+    Ok(Json(session))
+}
+
+/// The markets `address` has starred, with their details.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{address}/watchlist",
+    tag = "markets",
+    params(
+        ("address" = String, Path, description = "Stellar account (G...)"),
+    ),
+    responses(
+        (status = 200, description = "Starred markets, most recent first", body = Watchlist),
+        (status = 400, description = "Malformed address", body = ApiError),
+    )
+)]
+pub async fn watchlist_get(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    wallet_auth::account_key(&address)?;
+    let entries = state.db.watchlist(&address).await.map_err(into_api_error)?;
+    Ok((StatusCode::OK, Json(Watchlist { address, entries })))
+}
+
+/// Star a market. Requires a session token for `address`; starring an
+/// already starred market succeeds without changing it.
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{address}/watchlist/{market_id}",
+    tag = "markets",
+    params(
+        ("address" = String, Path, description = "Stellar account (G...)"),
+        ("market_id" = i64, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 204, description = "Market starred"),
+        (status = 400, description = "Malformed address", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 403, description = "Token issued to another address", body = ApiError),
+        (status = 404, description = "Market not found", body = ApiError),
+    ),
+    security(("wallet_session" = []))
+)]
+pub async fn watchlist_put(
+    State(state): State<Arc<AppState>>,
+    authed: AuthedAddress,
+    Path((address, market_id)): Path<(String, i64)>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_same_address(&authed, &address)?;
+    state
+        .db
+        .watchlist_add(&address, market_id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::market_not_found(market_id))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Unstar a market. Requires a session token for `address`.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{address}/watchlist/{market_id}",
+    tag = "markets",
+    params(
+        ("address" = String, Path, description = "Stellar account (G...)"),
+        ("market_id" = i64, Path, description = "Market ID"),
+    ),
+    responses(
+        (status = 204, description = "Market unstarred"),
+        (status = 400, description = "Malformed address", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 403, description = "Token issued to another address", body = ApiError),
+        (status = 404, description = "Market was not starred", body = ApiError),
+    ),
+    security(("wallet_session" = []))
+)]
+pub async fn watchlist_delete(
+    State(state): State<Arc<AppState>>,
+    authed: AuthedAddress,
+    Path((address, market_id)): Path<(String, i64)>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_same_address(&authed, &address)?;
+    let removed = state
+        .db
+        .watchlist_remove(&address, market_id)
+        .await
+        .map_err(into_api_error)?;
+    if removed {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::not_found(format!("market {market_id} is not on this watchlist")))
+    }
+}
+
+// ── Position notifications ────────────────────────────────────────────────────
+
+/// `address`'s notification settings. Requires a session token for
+/// `address`, since the settings include its email.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{address}/notifications",
+    tag = "notifications",
+    params(
+        ("address" = String, Path, description = "Stellar account (G...)"),
+    ),
+    responses(
+        (status = 200, description = "Current settings; all off if never saved", body = NotificationSettings),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 403, description = "Token issued to another address", body = ApiError),
+    ),
+    security(("wallet_session" = []))
+)]
+pub async fn notification_settings_get(
+    State(state): State<Arc<AppState>>,
+    authed: AuthedAddress,
+    Path(address): Path<String>,
+) -> Result<Json<NotificationSettings>, ApiError> {
+    require_same_address(&authed, &address)?;
+    let settings = state
+        .db
+        .notification_settings(&address)
+        .await
+        .map_err(into_api_error)?
+        .unwrap_or_else(|| NotificationSettings::defaults(&address));
+    Ok(Json(settings))
+}
+
+/// Replace `address`'s notification settings. Requires a session token for
+/// `address`. A new or still unverified email is sent a confirmation link,
+/// at most once per resend cooldown; nothing is emailed about positions
+/// until the link is followed.
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{address}/notifications",
+    tag = "notifications",
+    params(
+        ("address" = String, Path, description = "Stellar account (G...)"),
+    ),
+    request_body = NotificationSettingsUpdate,
+    responses(
+        (status = 200, description = "Settings saved", body = NotificationSettings),
+        (status = 400, description = "Invalid email", body = ApiError),
+        (status = 401, description = "Missing or invalid bearer token", body = ApiError),
+        (status = 403, description = "Token issued to another address", body = ApiError),
+    ),
+    security(("wallet_session" = []))
+)]
+pub async fn notification_settings_put(
+    State(state): State<Arc<AppState>>,
+    authed: AuthedAddress,
+    Path(address): Path<String>,
+    Json(mut update): Json<NotificationSettingsUpdate>,
+) -> Result<Json<NotificationSettings>, ApiError> {
+    require_same_address(&authed, &address)?;
+    update.email = match update.email.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(raw) => Some(
+            normalized_email(raw)
+                .ok_or_else(|| ApiError::bad_request("email must be a valid, non-disposable address"))?,
+        ),
+    };
+    if let Some(email) = update.email.as_deref() {
+        reject_refused_email(&state, email).await?;
+    }
+
+    let settings = state
+        .db
+        .notification_settings_update(&address, &update, &user_notifications::new_token())
+        .await
+        .map_err(into_api_error)?;
+    // The top creators lists leave out hidden profiles.
+    for metric in TopCreatorsMetric::ALL {
+        let _ = state.cache.del(&keys::api_top_creators(metric.label())).await;
+    }
+
+    if !settings.email_verified {
+        let token = user_notifications::new_token();
+        let pending = state
+            .db
+            .notification_issue_confirmation(&address, &token, crate::newsletter::RESEND_COOLDOWN.as_secs())
+            .await
+            .map_err(into_api_error)?;
+        if let Some(email) = pending {
+            state
+                .email_queue
+                .enqueue(
+                    crate::email::types::EmailJobType::NotificationEmailConfirmation,
+                    &email,
+                    user_notifications::EMAIL_CONFIRMATION_TEMPLATE,
+                    serde_json::json!({
+                        "confirm_url": user_notifications::confirm_url(&state.config.base_url, &token),
+                        "email": email,
+                    }),
+                    0,
+                )
+                .await
+                .map_err(into_api_error)?;
+        }
+    }
+
+    Ok(Json(settings))
+}
+
+/// Confirmation link carried in the notification email verification
+/// message.
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/confirm",
+    tag = "notifications",
+    params(NewsletterConfirmQuery),
+    responses(
+        (status = 200, description = "Email verified", body = NewsletterResponse),
+        (status = 400, description = "Missing token", body = NewsletterResponse),
+        (status = 404, description = "Token not found", body = NewsletterResponse),
+        (status = 410, description = "Token expired; save the settings again for a new one", body = NewsletterResponse),
+    )
+)]
+pub async fn notification_email_confirm(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<NewsletterConfirmQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let token = query.token.trim();
+    if token.is_empty() {
+        return Ok((
+            StatusCode::BAD_REQUEST,
+            Json(NewsletterResponse {
+                success: false,
+                message: "Missing confirmation token.".to_string(),
+            }),
+        ));
+    }
+
+    let outcome = state
+        .db
+        .notification_confirm_by_token(token, state.config.newsletter_token_ttl_secs)
+        .await
+        .map_err(into_api_error)?;
+
+    let (status, success, message) = match outcome {
+        crate::db::NewsletterConfirmation::Confirmed => {
+            (StatusCode::OK, true, "Email verified. Notifications you turned on will now be sent.")
+        }
+        crate::db::NewsletterConfirmation::Expired => (
+            StatusCode::GONE,
+            false,
+            "Confirmation token has expired. Save your notification settings again for a new one.",
+        ),
+        crate::db::NewsletterConfirmation::NotFound => {
+            (StatusCode::NOT_FOUND, false, "Invalid confirmation token.")
+        }
+    };
+    Ok((
+        status,
+        Json(NewsletterResponse {
+            success,
+            message: message.to_string(),
+        }),
+    ))
+}
+
+/// Unsubscribe link carried in position notification emails. Turns every
+/// notification off; the email stays on file.
+#[utoipa::path(
+    get,
+    path = "/api/v1/notifications/unsubscribe",
+    tag = "notifications",
+    params(NewsletterUnsubscribeQuery),
+    responses(
+        (status = 200, description = "Notifications turned off", body = NewsletterResponse),
+        (status = 404, description = "Unknown token", body = ApiError),
+    )
+)]
+pub async fn notification_unsubscribe(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<NewsletterUnsubscribeQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let matched = state
+        .db
+        .notification_unsubscribe_by_token(query.token.trim())
+        .await
+        .map_err(into_api_error)?;
+    if !matched {
+        return Err(ApiError::not_found("unknown notification token"));
+    }
+    Ok((
+        StatusCode::OK,
+        Json(NewsletterResponse {
+            success: true,
+            message: "You will no longer receive emails about your positions.".to_string(),
+        }),
+    ))
+}
+
+// ── Contact form ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+pub struct ContactRequest {
+    #[validate(
+        custom(function = "crate::validation::not_blank"),
+        length(max = crate::contact::MAX_NAME_LEN)
+    )]
+    pub name: String,
+    #[validate(custom(function = "crate::validation::email_address"))]
+    pub email: String,
+    #[validate(
+        custom(function = "crate::validation::not_blank"),
+        length(max = crate::contact::MAX_SUBJECT_LEN)
+    )]
+    pub subject: String,
+    #[validate(
+        custom(function = "crate::validation::not_blank"),
+        length(max = crate::contact::MAX_MESSAGE_LEN)
+    )]
+    pub message: String,
+    /// Honeypot: hidden from people by the form, so only bots fill it in.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+impl ContactRequest {
+    /// True when the hidden honeypot field was filled in.
+    fn is_spam(&self) -> bool {
+        signup_guard::is_honeypot_hit(self.website.as_deref())
+    }
+
+    /// Trimmed (name, email, subject, message).
+    fn normalized(&self) -> (String, String, String, String) {
+        (
+            self.name.trim().to_string(),
+            validation::normalize_email(&self.email),
+            self.subject.trim().to_string(),
+            self.message.trim().to_string(),
+        )
+    }
+}
+
+const CONTACT_ACCEPTED_MESSAGE: &str = "Thanks for getting in touch. We'll reply by email.";
+
+/// Store a contact form submission and queue the sender's auto-response and
+/// the ops notification. Honeypot hits get the same 202 as real submissions
+/// but are neither stored nor emailed, so bots learn nothing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/contact",
+    tag = "contact",
+    request_body = ContactRequest,
+    responses(
+        (status = 202, description = "Submission received", body = NewsletterResponse),
+        (status = 400, description = "Email domain is blocked", body = ApiError),
+        (status = 422, description = "Missing, oversized, or invalid fields", body = ApiError),
+        (status = 429, description = "Too many submissions from this IP", body = ApiError),
+    )
+)]
+pub async fn contact_submit(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    ValidatedJson(payload): ValidatedJson<ContactRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let ip = client_ip(&state.config, &headers, connect_info.as_ref());
+    let allowed = state
+        .newsletter_rate_limiter
+        .allow(
+            &format!("contact:ip:{ip}"),
+            state.config.contact_rate_limit_max,
+            Duration::from_secs(state.config.contact_rate_limit_window_secs),
+        )
+        .await;
+    if !allowed {
+        tracing::warn!(client_ip = %ip, "contact form rate limit exceeded");
+        state.metrics.observe_rate_limit_rejection("contact");
+        return Err(ApiError::rate_limited());
+    }
+
+    let accepted = (
+        StatusCode::ACCEPTED,
+        Json(NewsletterResponse {
+            success: true,
+            message: CONTACT_ACCEPTED_MESSAGE.to_string(),
+        }),
+    );
+
+    if payload.is_spam() {
+        tracing::info!(client_ip = %ip, "contact form honeypot hit; submission dropped");
+        return Ok(accepted);
+    }
+
+    let (name, email, subject, message) = payload.normalized();
+    reject_refused_email(&state, &email).await?;
+    let user_agent = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(256).collect::<String>());
+    let submission = state
+        .db
+        .contact_create(
+            &name,
+            &email,
+            &subject,
+            &message,
+            serde_json::json!({ "ip": ip, "user_agent": user_agent }),
+        )
+        .await
+        .map_err(into_api_error)?;
+
+    // The submission is stored either way; a queue failure must not make the
+    // sender retry and create a duplicate ticket.
+    let data = serde_json::json!({
+        "submission_id": submission.id.to_string(),
+        "name": name,
+        "email": email,
+        "subject": subject,
+        "message": message,
+    });
+    let suppressed = state
+        .db
+        .email_is_suppressed(&email)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "suppression check failed; skipping auto-response");
+            true
+        });
+    if !suppressed {
+        if let Err(e) = state
+            .email_queue
+            .enqueue(
+                crate::email::types::EmailJobType::ContactFormAutoResponse,
+                &email,
+                crate::contact::AUTO_RESPONSE_TEMPLATE,
+                data.clone(),
+                0,
+            )
+            .await
+        {
+            tracing::error!(submission_id = %submission.id, error = %e, "failed to enqueue contact auto-response");
+        }
+    }
+    if let Some(ops_email) = state.config.contact_ops_email.as_deref() {
+        if let Err(e) = state
+            .email_queue
+            .enqueue(
+                crate::email::types::EmailJobType::ContactFormNotification,
+                ops_email,
+                crate::contact::NOTIFICATION_TEMPLATE,
+                data,
+                1,
+            )
+            .await
+        {
+            tracing::error!(submission_id = %submission.id, error = %e, "failed to enqueue contact notification");
+        }
+    }
+
+    Ok(accepted)
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ContactListQuery {
+    /// Only submissions in this status (`new`, `answered`, `closed`).
+    pub status: Option<String>,
+}
+
+/// Contact form submissions, newest first. The cursor is an offset into
+/// that order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/contact",
+    tag = "contact",
+    params(ContactListQuery, PaginationQuery),
+    responses(
+        (status = 200, description = "Contact form submissions"),
+        (status = 400, description = "Unknown status or bad cursor", body = ApiError),
+        (status = 422, description = "limit out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn contact_list(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<ContactListQuery>,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let status = filter
+        .status
+        .as_deref()
+        .map(|raw| {
+            ContactStatus::parse(raw)
+                .ok_or_else(|| ApiError::bad_request("status must be one of new, answered, closed"))
+        })
+        .transpose()?;
+    let limit = query.limit();
+    let offset = query
+        .cursor()
+        .map(|c| c.parse::<i64>().map_err(|_| ApiError::bad_request("cursor must be an offset")))
+        .transpose()?
+        .unwrap_or(0)
+        .max(0);
+
+    let mut submissions = state
+        .db
+        .contact_list(status, limit + 1, offset)
+        .await
+        .map_err(into_api_error)?;
+    let has_more = submissions.len() as i64 > limit;
+    submissions.truncate(limit as usize);
+    let next_cursor = has_more.then(|| (offset + limit).to_string());
+
+    Ok((
+        StatusCode::OK,
+        Json(PaginatedResponse::new(submissions, next_cursor, limit as u32, has_more)),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct ContactStatusRequest {
+    pub status: ContactStatus,
+}
+
+/// Move a submission forward: `new -> answered`, `new -> closed`, or
+/// `answered -> closed`. Any other change is a 422.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/contact/{id}/status",
+    tag = "contact",
+    params(("id" = String, Path, description = "Submission UUID")),
+    request_body = ContactStatusRequest,
+    responses(
+        (status = 200, description = "Updated submission", body = ContactSubmission),
+        (status = 404, description = "Unknown submission", body = ApiError),
+        (status = 409, description = "Submission changed concurrently", body = ApiError),
+        (status = 422, description = "Transition not allowed", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn contact_set_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<ContactStatusRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let current = state
+        .db
+        .contact_get(id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found(format!("Contact submission {id} not found")))?;
+
+    if !current.status.can_transition_to(payload.status) {
+        return Err(ApiError::unprocessable(
+            "INVALID_STATUS_TRANSITION",
+            format!(
+                "cannot move a submission from {} to {}",
+                current.status.label(),
+                payload.status.label()
+            ),
+        ));
+    }
+
+    let updated = state
+        .db
+        .contact_set_status(id, current.status, payload.status)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::conflict("Submission status changed; reload and try again"))?;
+
+    Ok((StatusCode::OK, Json(updated)))
+}
+
+// ── Categories ────────────────────────────────────────────────────────────────
+
+/// Every category with its active-market count and total volume, featured
+/// first. Cached for ten minutes by default; admin edits show up immediately.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories",
+    tag = "markets",
+    responses(
+        (status = 200, description = "All categories", body = [Category]),
+        (status = 500, description = "Internal error", body = ApiError),
+    )
+)]
+pub async fn categories(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let categories = state.db.categories_cached().await.map_err(into_api_error)?;
+    Ok((StatusCode::OK, Json(categories)))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CategoryCreateRequest {
+    /// Lowercase letters and digits in hyphen-separated words, e.g. `us-politics`.
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub is_featured: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CategoryUpdateRequest {
+    /// New display name; the slug never changes.
+    pub name: Option<String>,
+    pub is_featured: Option<bool>,
+}
+
+async fn invalidate_categories(state: &AppState) -> Result<(), ApiError> {
+    let invalidated = state
+        .cache
+        .invalidate_tag(&InvalidationTag::CategoryChanged)
+        .await
+        .map_err(into_api_error)?;
+    state.metrics.observe_invalidation("category_write", invalidated);
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/categories",
+    tag = "markets",
+    request_body = CategoryCreateRequest,
+    responses(
+        (status = 201, description = "Created category", body = Category),
+        (status = 400, description = "Invalid slug or name", body = ApiError),
+        (status = 409, description = "Slug already exists", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn category_create(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CategoryCreateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    category::validate_slug(&payload.slug).map_err(ApiError::bad_request)?;
+    let name = category::normalize_name(&payload.name).map_err(ApiError::bad_request)?;
+
+    let created = state
+        .db
+        .category_create(&payload.slug, &name, payload.is_featured)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::conflict(format!("category {} already exists", payload.slug)))?;
+    invalidate_categories(&state).await?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Rename and/or (un)feature a category. Omitted fields are unchanged.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/admin/categories/{slug}",
+    tag = "markets",
+    params(("slug" = String, Path, description = "Category slug")),
+    request_body = CategoryUpdateRequest,
+    responses(
+        (status = 200, description = "Updated category", body = Category),
+        (status = 400, description = "Invalid name or empty update", body = ApiError),
+        (status = 404, description = "Unknown category", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn category_update(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    Json(payload): Json<CategoryUpdateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    if payload.name.is_none() && payload.is_featured.is_none() {
+        return Err(ApiError::bad_request("set name and/or is_featured"));
+    }
+    let name = payload
+        .name
+        .as_deref()
+        .map(category::normalize_name)
+        .transpose()
+        .map_err(ApiError::bad_request)?;
+
+    let updated = state
+        .db
+        .category_update(&slug, name.as_deref(), payload.is_featured)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found(format!("category {slug} not found")))?;
+    invalidate_categories(&state).await?;
+
+    Ok((StatusCode::OK, Json(updated)))
+}
+
+/// Remove a category. Its markets keep their `category` value but can no
+/// longer be filtered by it.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/categories/{slug}",
+    tag = "markets",
+    params(("slug" = String, Path, description = "Category slug")),
+    responses(
+        (status = 204, description = "Category deleted"),
+        (status = 404, description = "Unknown category", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn category_delete(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !state.db.category_delete(&slug).await.map_err(into_api_error)? {
+        return Err(ApiError::not_found(format!("category {slug} not found")));
+    }
+    invalidate_categories(&state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Market id mappings ────────────────────────────────────────────────────────
+
+/// The client for a network named in a path, matched like `X-Network`; an
+/// unserved name is a 400 that lists the served networks.
+fn served_network<'a>(state: &'a AppState, network: &str) -> Result<&'a BlockchainClient, ApiError> {
+    state
+        .networks
+        .get(&network.trim().to_ascii_lowercase())
+        .ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "unknown network {network:?}; this API serves: {}",
+                state.networks.names().join(", "),
+            ))
+        })
+}
+
+/// Bind a public market id to its chain id on a served network, replacing
+/// any binding it has there. Reads of the market switch at once; cached
+/// market documents of the primary network expire with their TTL.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/markets/{market_id}/chain-ids/{network}",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Public market ID"),
+        ("network" = String, Path, description = "Served network name"),
+    ),
+    request_body = MarketIdBindRequest,
+    responses(
+        (status = 200, description = "The binding", body = MarketIdMapping),
+        (status = 400, description = "Unknown network, negative chain ID or malformed contract ID", body = ApiError),
+        (status = 409, description = "The chain market is bound to another public ID", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn market_id_bind(
+    State(state): State<Arc<AppState>>,
+    Path((market_id, network)): Path<(i64, String)>,
+    Json(payload): Json<MarketIdBindRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let client = served_network(&state, &network)?;
+    if payload.chain_market_id < 0 {
+        return Err(ApiError::bad_request("chain_market_id must not be negative"));
+    }
+    let contract_id = match payload.contract_id.as_deref().map(str::trim) {
+        Some(contract_id) => {
+            stellar_strkey::Contract::from_string(contract_id)
+                .map_err(|_| ApiError::bad_request("contract_id must be a C... contract address"))?;
+            contract_id
+        }
+        None => client.contract_id(),
+    };
+
+    let mapping = state
+        .db
+        .market_id_mapping_bind(market_id, client.network(), payload.chain_market_id, contract_id)
+        .await
+        .map_err(|err| {
+            if matches!(err.downcast_ref::<DbError>(), Some(DbError::ConstraintViolation(_))) {
+                return ApiError::conflict(format!(
+                    "chain market {} on {} is bound to another market",
+                    payload.chain_market_id,
+                    client.network(),
+                ));
+            }
+            into_api_error(err)
+        })?;
+    Ok((StatusCode::OK, Json(mapping)))
+}
+
+/// Remove a public market id's binding on a network. Its blockchain reads
+/// there answer 404 until it is bound again.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/markets/{market_id}/chain-ids/{network}",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Public market ID"),
+        ("network" = String, Path, description = "Served network name"),
+    ),
+    responses(
+        (status = 204, description = "Binding removed"),
+        (status = 400, description = "Unknown network", body = ApiError),
+        (status = 404, description = "The market has no binding on the network", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn market_id_unbind(
+    State(state): State<Arc<AppState>>,
+    Path((market_id, network)): Path<(i64, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let client = served_network(&state, &network)?;
+    if !state
+        .db
+        .market_id_mapping_unbind(market_id, client.network())
+        .await
+        .map_err(into_api_error)?
+    {
+        return Err(ApiError::market_not_mapped(market_id, client.network()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Content ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct ContentWriteRequest {
+    /// Lowercase letters and digits in hyphen-separated words, e.g. `weekly-recap`.
+    pub slug: String,
+    pub title: String,
+    pub category: String,
+    /// Markdown source.
+    #[serde(default)]
+    pub body: String,
+    /// Omit or `false` to save a draft.
+    #[serde(default)]
+    pub is_published: bool,
+    /// Publication time to show; defaults to when the article is published.
+    /// Only valid with `is_published`.
+    pub published_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ContentWriteRequest {
+    fn into_fields(self) -> Result<ContentFields, ApiError> {
+        content::validate_slug(&self.slug).map_err(ApiError::bad_request)?;
+        let title = content::normalize_title(&self.title).map_err(ApiError::bad_request)?;
+        let category = content::normalize_category(&self.category).map_err(ApiError::bad_request)?;
+        content::validate_body(&self.body).map_err(ApiError::bad_request)?;
+        if self.published_at.is_some() && !self.is_published {
+            return Err(ApiError::bad_request("published_at requires is_published"));
+        }
+        Ok(ContentFields {
+            slug: self.slug,
+            title,
+            category,
+            body: self.body,
+            is_published: self.is_published,
+            published_at: self.published_at,
+        })
+    }
+}
+
+/// Drop every cached content listing and rendered page. Listings are keyed by
+/// page size, so this deletes by pattern instead of through a tag.
+async fn invalidate_content(state: &AppState) -> Result<(), ApiError> {
+    let mut invalidated = 0;
+    for pattern in [keys::api_content_pattern(), keys::dbq_content_pattern()] {
+        invalidated += state.cache.del_by_pattern(&pattern).await.map_err(into_api_error)?;
+    }
+    state.metrics.observe_invalidation("content_write", invalidated);
+    Ok(())
+}
+
+/// A unique violation on write can only be the slug.
+fn content_write_error(err: anyhow::Error, slug: &str) -> ApiError {
+    if matches!(err.downcast_ref::<DbError>(), Some(DbError::ConstraintViolation(_))) {
+        return ApiError::conflict(format!("content slug {slug} already exists"));
+    }
+    into_api_error(err)
+}
+
+/// Create an article, as a draft unless `is_published` is set.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/content",
+    tag = "content",
+    request_body = ContentWriteRequest,
+    responses(
+        (status = 201, description = "Created article", body = ContentEntry),
+        (status = 400, description = "Invalid field", body = ApiError),
+        (status = 409, description = "Slug already exists", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn content_create(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<ContentWriteRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let fields = payload.into_fields()?;
+    let created = state
+        .db
+        .content_create(&fields)
+        .await
+        .map_err(|e| content_write_error(e, &fields.slug))?;
+    invalidate_content(&state).await?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Replace an article. Setting `is_published` publishes a draft; clearing it
+/// takes the article down.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/content/{id}",
+    tag = "content",
+    params(("id" = i64, Path, description = "Content id")),
+    request_body = ContentWriteRequest,
+    responses(
+        (status = 200, description = "Updated article", body = ContentEntry),
+        (status = 400, description = "Invalid field", body = ApiError),
+        (status = 404, description = "Unknown article", body = ApiError),
+        (status = 409, description = "Slug already exists", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn content_update(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Json(payload): Json<ContentWriteRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let fields = payload.into_fields()?;
+    let updated = state
+        .db
+        .content_update(id, &fields)
+        .await
+        .map_err(|e| content_write_error(e, &fields.slug))?
+        .ok_or_else(|| ApiError::not_found(format!("content {id} not found")))?;
+    invalidate_content(&state).await?;
+
+    Ok((StatusCode::OK, Json(updated)))
+}
+
+/// Publish a draft, stamping `published_at` with the current time. Publishing
+/// an already-published article changes nothing.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/content/{id}/publish",
+    tag = "content",
+    params(("id" = i64, Path, description = "Content id")),
+    responses(
+        (status = 200, description = "Published article", body = ContentEntry),
+        (status = 404, description = "Unknown article", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn content_publish(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let published = state
+        .db
+        .content_publish(id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found(format!("content {id} not found")))?;
+    invalidate_content(&state).await?;
+
+    Ok((StatusCode::OK, Json(published)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/content/{id}",
+    tag = "content",
+    params(("id" = i64, Path, description = "Content id")),
+    responses(
+        (status = 204, description = "Article deleted"),
+        (status = 404, description = "Unknown article", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn content_delete(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !state.db.content_delete(id).await.map_err(into_api_error)? {
+        return Err(ApiError::not_found(format!("content {id} not found")));
+    }
+    invalidate_content(&state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ── Admin cache ───────────────────────────────────────────────────────────────
+
+/// Refuse `pattern` unless it sits under `CACHE_ADMIN_ALLOWED_PREFIXES`.
+fn check_cache_pattern(state: &AppState, pattern: &str) -> Result<(), ApiError> {
+    let allowed = &state.config.cache_admin_allowed_prefixes;
+    cache_admin::check_pattern(pattern, allowed).map_err(|e| {
+        ApiError::new(ApiErrorKind::Validation, "CACHE_PATTERN_NOT_ALLOWED", e.to_string())
+            .with_details(serde_json::json!({ "allowed_prefixes": allowed }))
+    })
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CacheKeysQuery {
+    /// Redis glob under an allowed prefix, e.g. `api:v1:market_detail:*`.
+    pub pattern: String,
+    /// Keys to return; default 100, capped at 1000.
+    pub limit: Option<usize>,
+}
+
+/// Cache keys matching `pattern`, found with SCAN and capped at `limit`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/cache/keys",
+    tag = "cache",
+    params(CacheKeysQuery),
+    responses(
+        (status = 200, description = "Matching keys", body = CacheKeyList),
+        (status = 400, description = "Pattern outside the allowed prefixes", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn cache_keys(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CacheKeysQuery>,
+) -> Result<Json<CacheKeyList>, ApiError> {
+    check_cache_pattern(&state, &query.pattern)?;
+    let limit = query
+        .limit
+        .unwrap_or(cache_admin::DEFAULT_KEY_LIMIT)
+        .clamp(1, cache_admin::MAX_KEY_LIMIT);
+    let (keys, truncated) = state
+        .cache
+        .scan_keys(&query.pattern, limit)
+        .await
+        .map_err(into_api_error)?;
+    Ok(Json(CacheKeyList {
+        pattern: query.pattern,
+        keys,
+        truncated,
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CacheEntryQuery {
+    /// Exact key under an allowed prefix.
+    pub key: String,
+}
+
+/// The value stored at one cache key and its remaining TTL.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/cache/entry",
+    tag = "cache",
+    params(CacheEntryQuery),
+    responses(
+        (status = 200, description = "Cache entry", body = CacheEntry),
+        (status = 400, description = "Key outside the allowed prefixes", body = ApiError),
+        (status = 404, description = "No such key", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn cache_entry(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CacheEntryQuery>,
+) -> Result<Json<CacheEntry>, ApiError> {
+    check_cache_pattern(&state, &query.key)?;
+    let (raw, ttl_secs) = state
+        .cache
+        .get_raw_with_ttl(&query.key)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found(format!("cache key {} not found", query.key)))?;
+    let value = serde_json::from_str(&raw).unwrap_or(serde_json::Value::String(raw));
+    Ok(Json(CacheEntry {
+        key: query.key,
+        ttl_secs,
+        value,
+    }))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CacheInvalidateQuery {
+    /// Redis glob under an allowed prefix.
+    pub pattern: String,
+    /// Must be `true`; guards against a pattern sent by mistake.
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Delete every cache key matching `pattern`. Readers refill from the
+/// database or chain on their next miss.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/cache",
+    tag = "cache",
+    params(CacheInvalidateQuery),
+    responses(
+        (status = 200, description = "Keys deleted", body = CacheDeleteResult),
+        (status = 400, description = "Pattern outside the allowed prefixes, or `confirm` not set", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn cache_invalidate(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CacheInvalidateQuery>,
+) -> Result<Json<CacheDeleteResult>, ApiError> {
+    check_cache_pattern(&state, &query.pattern)?;
+    if !query.confirm {
+        return Err(ApiError::new(
+            ApiErrorKind::Validation,
+            "CONFIRMATION_REQUIRED",
+            "deleting by pattern requires confirm=true",
+        ));
+    }
+    let deleted = state
+        .cache
+        .del_by_pattern(&query.pattern)
+        .await
+        .map_err(into_api_error)?;
+    state.metrics.observe_invalidation("admin_cache", deleted);
+    tracing::info!(pattern = %query.pattern, deleted, "admin cache invalidation");
+    Ok(Json(CacheDeleteResult {
+        pattern: query.pattern,
+        deleted,
+    }))
+}
+
+// ── Waitlist ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::ToSchema)]
+pub struct WaitlistJoinRequest {
+    #[validate(custom(function = "crate::validation::email_address"))]
+    pub email: String,
+    /// Another entry's referral code; credits that entry when this email joins.
+    pub referral_code: Option<String>,
+    /// Where the signup came from; `direct` when omitted or blank.
+    #[validate(length(max = MAX_SOURCE_LEN))]
+    pub source: Option<String>,
+    /// Honeypot: hidden from people by the form, so only bots fill it in.
+    #[serde(default)]
+    pub website: Option<String>,
+}
+
+impl WaitlistJoinRequest {
+    /// Normalised (email, source).
+    fn normalized(&self) -> (String, String) {
+        (
+            validation::normalize_email(&self.email),
+            acquisition::normalize_source(self.source.as_deref()),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::IntoParams)]
+pub struct WaitlistStatusQuery {
+    #[validate(custom(function = "crate::validation::email_address"))]
+    pub email: String,
+}
+
+/// Where an email stands on the waitlist.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct WaitlistStatusResponse {
+    pub email: String,
+    pub status: WaitlistStatus,
+    /// 1-based place in the queue; `null` once invited.
+    pub position: Option<i64>,
+    /// Share this code; each entry that joins with it moves you up.
+    pub referral_code: String,
+    pub referral_count: i64,
+    pub joined_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<WaitlistEntry> for WaitlistStatusResponse {
+    fn from(entry: WaitlistEntry) -> Self {
+        Self {
+            email: entry.email,
+            status: entry.status,
+            position: entry.position,
+            referral_code: entry.referral_code,
+            referral_count: entry.referral_count,
+            joined_at: entry.joined_at,
+        }
+    }
+}
+
+/// The 409 for an email that is already on the waitlist. The entry is left
+/// as it was; `details` carries its current place so the form can show it.
+fn already_on_waitlist(entry: &WaitlistEntry) -> ApiError {
+    ApiError::new(
+        ApiErrorKind::Conflict,
+        "ALREADY_ON_WAITLIST",
+        "This email is already on the waitlist",
+    )
+    .with_details(serde_json::json!({
+        "status": entry.status,
+        "position": entry.position,
+    }))
+}
+
+/// Join the launch waitlist. Joining again with the same email is a 409 that
+/// leaves the existing entry and its position unchanged; a referral code is
+/// only checked, and only credited, for new entries. Honeypot hits get a 201
+/// that looks like a new entry but nothing is stored or emailed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/waitlist",
+    tag = "waitlist",
+    request_body = WaitlistJoinRequest,
+    responses(
+        (status = 201, description = "Joined the waitlist", body = WaitlistStatusResponse),
+        (status = 400, description = "Email domain is blocked", body = ApiError),
+        (status = 409, description = "Already on the waitlist; `details` has its status and position", body = ApiError),
+        (status = 422, description = "Invalid fields or unknown referral code", body = ApiError),
+        (status = 429, description = "Too many requests from this IP"),
+    )
+)]
+pub async fn waitlist_join(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(payload): ValidatedJson<WaitlistJoinRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (email, source) = payload.normalized();
+
+    if signup_guard::is_honeypot_hit(payload.website.as_deref()) {
+        tracing::info!(source = %source, "waitlist honeypot hit; join dropped");
+        let decoy = WaitlistStatusResponse {
+            email,
+            status: WaitlistStatus::Pending,
+            position: None,
+            referral_code: crate::waitlist::generate_referral_code(),
+            referral_count: 0,
+            joined_at: chrono::Utc::now(),
+        };
+        return Ok((StatusCode::CREATED, Json(decoy)));
+    }
+    reject_refused_email(&state, &email).await?;
+
+    if let Some(entry) = state
+        .db
+        .waitlist_get_by_email(&email)
+        .await
+        .map_err(into_api_error)?
+    {
+        return Err(already_on_waitlist(&entry));
+    }
+
+    let referred_by = match payload.referral_code.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(raw) => {
+            let code = normalize_referral_code(raw);
+            let referrer = match code {
+                Some(code) => state
+                    .db
+                    .waitlist_referrer_id(&code)
+                    .await
+                    .map_err(into_api_error)?,
+                None => None,
+            };
+            Some(referrer.ok_or_else(|| {
+                ApiError::unprocessable("INVALID_REFERRAL_CODE", "referral_code does not match any waitlist entry")
+            })?)
+        }
+    };
+
+    let entry = match state
+        .db
+        .waitlist_join(&email, &source, referred_by)
+        .await
+        .map_err(into_api_error)?
+    {
+        // Lost a race with a concurrent join for the same email.
+        WaitlistJoin::Existing(entry) => return Err(already_on_waitlist(&entry)),
+        WaitlistJoin::Joined(entry) => entry,
+    };
+
+    // The entry exists either way; a queue failure must not turn the join
+    // into an error the client retries.
+    if let Err(e) = state
+        .email_queue
+        .enqueue(
+            crate::email::types::EmailJobType::WaitlistConfirmation,
+            &email,
+            crate::waitlist::CONFIRMATION_TEMPLATE,
+            serde_json::json!({ "email": email }),
+            0,
+        )
+        .await
+    {
+        tracing::error!(entry_id = %entry.id, error = %e, "failed to enqueue waitlist confirmation");
+    }
+    tracing::info!(entry_id = %entry.id, source = %source, referred = referred_by.is_some(), "waitlist join");
+
+    Ok((StatusCode::CREATED, Json(WaitlistStatusResponse::from(entry))))
+}
+
+/// Position, referral count, and status for an email on the waitlist.
+#[utoipa::path(
+    get,
+    path = "/api/v1/waitlist/status",
+    tag = "waitlist",
+    params(WaitlistStatusQuery),
+    responses(
+        (status = 200, description = "Waitlist entry", body = WaitlistStatusResponse),
+        (status = 422, description = "Invalid email", body = ApiError),
+        (status = 404, description = "Email is not on the waitlist", body = ApiError),
+        (status = 429, description = "Too many requests from this IP"),
+    )
+)]
+pub async fn waitlist_status(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(query): ValidatedQuery<WaitlistStatusQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let email = validation::normalize_email(&query.email);
+    let entry = state
+        .db
+        .waitlist_get_by_email(&email)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found("Email is not on the waitlist"))?;
+    Ok((StatusCode::OK, Json(WaitlistStatusResponse::from(entry))))
+}
+
+/// Exactly one of `count` (invite the first N in the queue) or `positions`
+/// (invite these 1-based queue positions).
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct WaitlistInviteRequest {
+    pub count: Option<usize>,
+    pub positions: Option<Vec<i64>>,
+}
+
+impl WaitlistInviteRequest {
+    /// The queue positions to invite, or a 400.
+    fn positions(&self) -> Result<Vec<i64>, ApiError> {
+        let max = crate::waitlist::MAX_INVITE_BATCH;
+        let mut positions = match (self.count, &self.positions) {
+            (Some(count), None) => {
+                if count == 0 || count > max {
+                    return Err(ApiError::bad_request(format!("count must be between 1 and {max}")));
+                }
+                (1..=count as i64).collect()
+            }
+            (None, Some(positions)) => {
+                if positions.is_empty() || positions.len() > max {
+                    return Err(ApiError::bad_request(format!("positions must list 1 to {max} entries")));
+                }
+                if positions.iter().any(|&p| p < 1) {
+                    return Err(ApiError::bad_request("positions are 1-based"));
+                }
+                positions.clone()
+            }
+            _ => return Err(ApiError::bad_request("provide exactly one of count or positions")),
+        };
+        positions.sort_unstable();
+        positions.dedup();
+        Ok(positions)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct WaitlistInviteResponse {
+    /// Entries moved to `invited`, with the position each held. Positions
+    /// that were empty or already invited are skipped.
+    pub invited: Vec<WaitlistInvitee>,
+    /// Invitation emails successfully queued.
+    pub emails_queued: usize,
+}
+
+/// Invite entries off the front of the queue, or at specific positions, and
+/// queue a `waitlist_invitation` email for each.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/waitlist/invite",
+    tag = "waitlist",
+    request_body = WaitlistInviteRequest,
+    responses(
+        (status = 200, description = "Entries invited", body = WaitlistInviteResponse),
+        (status = 400, description = "Neither or both of count and positions, or out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn waitlist_invite(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<WaitlistInviteRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let positions = payload.positions()?;
+    let invited = state
+        .db
+        .waitlist_invite(&positions)
+        .await
+        .map_err(into_api_error)?;
+
+    let signup_url = format!("{}/signup", state.config.base_url.trim_end_matches('/'));
+    let mut emails_queued = 0;
+    for invitee in &invited {
+        match state
+            .email_queue
+            .enqueue(
+                crate::email::types::EmailJobType::WaitlistInvitation,
+                &invitee.email,
+                crate::waitlist::INVITATION_TEMPLATE,
+                serde_json::json!({ "email": invitee.email, "signup_url": signup_url }),
+                0,
+            )
+            .await
+        {
+            Ok(_) => emails_queued += 1,
+            Err(e) => {
+                tracing::error!(entry_id = %invitee.id, error = %e, "failed to enqueue waitlist invitation")
+            }
+        }
+    }
+    tracing::info!(invited = invited.len(), emails_queued, "waitlist invite");
+
+    Ok((StatusCode::OK, Json(WaitlistInviteResponse { invited, emails_queued })))
+}
+
+/// Waitlist totals by status, referred entries, and joins in the last day.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/waitlist/stats",
+    tag = "waitlist",
+    responses(
+        (status = 200, description = "Waitlist statistics", body = WaitlistStats),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn waitlist_stats(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let stats = state.db.waitlist_stats().await.map_err(into_api_error)?;
+    Ok((StatusCode::OK, Json(stats)))
+}
+
+// ── Analytics ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct AnalyticsEventInput {
+    /// One of the allowlisted event types.
+    #[validate(custom(function = "known_event_type"))]
+    pub event_type: String,
+    /// Free-form JSON object; at most 2 KiB serialized.
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    #[validate(custom(function = "event_properties"))]
+    pub properties: Option<serde_json::Value>,
+    #[validate(length(max = crate::analytics::MAX_SESSION_ID_LEN))]
+    pub session_id: Option<String>,
+    /// When the event happened on the client. Defaults to receipt time; may
+    /// be at most 24 hours old or 5 minutes in the future.
+    pub occurred_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+fn known_event_type(event_type: &str) -> Result<(), validator::ValidationError> {
+    if crate::analytics::is_known_event_type(event_type) {
+        Ok(())
+    } else {
+        Err(validation::rule_error(
+            "unknown_event_type",
+            format!("unknown event_type {event_type:?}"),
+        ))
+    }
+}
+
+fn event_properties(properties: &serde_json::Value) -> Result<(), validator::ValidationError> {
+    use crate::analytics::MAX_PROPERTIES_BYTES;
+
+    if !properties.is_object() {
+        return Err(validation::rule_error("type", "must be an object"));
+    }
+    if properties.to_string().len() > MAX_PROPERTIES_BYTES {
+        return Err(validation::rule_error(
+            "size",
+            format!("must be at most {MAX_PROPERTIES_BYTES} bytes serialized"),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AnalyticsEventsRequest {
+    pub events: Vec<AnalyticsEventInput>,
+}
+
+/// The batch size is checked before the events, and an oversized batch is
+/// not inspected further. The derive cannot express this: it reports a
+/// field's own rules and its items' rules under the same key, which
+/// `ValidationErrors` does not allow.
+impl Validate for AnalyticsEventsRequest {
+    fn validate(&self) -> Result<(), validator::ValidationErrors> {
+        use crate::analytics::MAX_BATCH_EVENTS;
+
+        let mut errors = validator::ValidationErrors::new();
+        if !(1..=MAX_BATCH_EVENTS).contains(&self.events.len()) {
+            errors.add(
+                "events",
+                validation::rule_error(
+                    "length",
+                    format!("must hold 1 to {MAX_BATCH_EVENTS} events; got {}", self.events.len()),
+                ),
+            );
+            return Err(errors);
+        }
+        let invalid: std::collections::BTreeMap<usize, Box<validator::ValidationErrors>> = self
+            .events
+            .iter()
+            .enumerate()
+            .filter_map(|(i, event)| event.validate().err().map(|e| (i, Box::new(e))))
+            .collect();
+        if invalid.is_empty() {
+            return Ok(());
+        }
+        errors
+            .errors_mut()
+            .insert("events", validator::ValidationErrorsKind::List(invalid));
+        Err(errors)
+    }
+}
+
+impl AnalyticsEventsRequest {
+    /// The events to store, stamped against `now`. Call after
+    /// [`Validate::validate`]; only `occurred_at`, which depends on `now`,
+    /// is checked here, with the same 422 as the other rules. The whole
+    /// batch is rejected if any event is.
+    fn into_events(self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<AnalyticsEvent>, ApiError> {
+        use crate::analytics::{MAX_CLOCK_SKEW, MAX_EVENT_AGE};
+
+        let stale: Vec<validation::FieldError> = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| {
+                event
+                    .occurred_at
+                    .is_some_and(|at| at > now + MAX_CLOCK_SKEW || at < now - MAX_EVENT_AGE)
+            })
+            .map(|(i, _)| {
+                validation::FieldError::new(
+                    format!("events[{i}].occurred_at"),
+                    "range",
+                    "must be within the last 24 hours",
+                )
+            })
+            .collect();
+        if !stale.is_empty() {
+            return Err(validation::invalid_fields(stale));
+        }
+        Ok(self
+            .events
+            .into_iter()
+            .map(|event| AnalyticsEvent {
+                event_type: event.event_type,
+                properties: event.properties.unwrap_or_else(|| serde_json::json!({})),
+                session_id: event
+                    .session_id
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string),
+                occurred_at: event.occurred_at.unwrap_or(now),
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AnalyticsIngestResponse {
+    pub accepted: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::IntoParams)]
+pub struct AnalyticsSummaryQuery {
+    /// Days to cover, today included. Default 7, max 90.
+    #[validate(range(min = 1, max = crate::analytics::MAX_SUMMARY_DAYS))]
+    pub days: Option<u32>,
+}
+
+/// Record a batch of client analytics events. The batch is validated as a
+/// whole and written with a single insert; nothing is stored if any event
+/// is rejected.
+#[utoipa::path(
+    post,
+    path = "/api/v1/analytics/events",
+    tag = "analytics",
+    request_body = AnalyticsEventsRequest,
+    responses(
+        (status = 202, description = "Batch stored", body = AnalyticsIngestResponse),
+        (status = 413, description = "Body too large"),
+        (status = 422, description = "Empty or oversized batch, or invalid event fields", body = ApiError),
+        (status = 429, description = "Too many batches from this IP", body = ApiError),
+    )
+)]
+pub async fn analytics_ingest(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    ValidatedJson(payload): ValidatedJson<AnalyticsEventsRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let ip = client_ip(&state.config, &headers, connect_info.as_ref());
+    let allowed = state
+        .newsletter_rate_limiter
+        .allow(
+            &format!("analytics:ip:{ip}"),
+            state.config.analytics_rate_limit_max,
+            Duration::from_secs(state.config.analytics_rate_limit_window_secs),
+        )
+        .await;
+    if !allowed {
+        tracing::warn!(client_ip = %ip, "analytics rate limit exceeded");
+        state.metrics.observe_rate_limit_rejection("analytics");
+        return Err(ApiError::rate_limited());
+    }
+
+    let events = payload.into_events(chrono::Utc::now())?;
+    let ip_address = ip.parse::<std::net::IpAddr>().ok().map(|addr| addr.to_string());
+    let user_agent = headers
+        .get(axum::http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(|ua| ua.chars().take(crate::analytics::MAX_USER_AGENT_LEN).collect::<String>());
+    state
+        .db
+        .analytics_insert_events(&events, ip_address.as_deref(), user_agent.as_deref())
+        .await
+        .map_err(into_api_error)?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(AnalyticsIngestResponse { accepted: events.len() }),
+    ))
+}
+
+/// Daily event counts from the rollup table for the last `days` UTC days.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/analytics/summary",
+    tag = "analytics",
+    params(AnalyticsSummaryQuery),
+    responses(
+        (status = 200, description = "Rolled-up event counts", body = AnalyticsSummary),
+        (status = 422, description = "days out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn analytics_summary(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(query): ValidatedQuery<AnalyticsSummaryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    use crate::analytics::{summary_window, DEFAULT_SUMMARY_DAYS};
+
+    let days = query.days.unwrap_or(DEFAULT_SUMMARY_DAYS);
+    let window = summary_window(chrono::Utc::now(), days);
+    let (daily, rolled_up_at) = state
+        .db
+        .analytics_daily(window.0, window.1)
+        .await
+        .map_err(into_api_error)?;
+    Ok((
+        StatusCode::OK,
+        Json(AnalyticsSummary::new(days, window, daily, rolled_up_at)),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, Validate, utoipa::IntoParams)]
+pub struct AcquisitionQuery {
+    /// Days of signups to cover, today included. Default 30, max 365.
+    #[validate(range(min = 1, max = acquisition::MAX_DAYS))]
+    pub days: Option<u32>,
+    /// `source` (default) or `day`.
+    pub group_by: Option<String>,
+}
+
+impl AcquisitionQuery {
+    async fn report(&self, db: &crate::db::Database) -> Result<AcquisitionReport, ApiError> {
+        let group_by = match self.group_by.as_deref() {
+            None => AcquisitionGroupBy::Source,
+            Some(raw) => AcquisitionGroupBy::parse(raw)
+                .ok_or_else(|| ApiError::bad_request("group_by must be one of source, day"))?,
+        };
+        let days = self.days.unwrap_or(acquisition::DEFAULT_DAYS);
+        let window = crate::analytics::summary_window(chrono::Utc::now(), days);
+        let (from, to) = acquisition::window_bounds(window);
+        let counts = db.acquisition_counts(from, to, group_by).await.map_err(into_api_error)?;
+        Ok(AcquisitionReport::new(days, window, group_by, counts))
+    }
+}
+
+/// Newsletter and waitlist signups of the last `days` UTC days by source or
+/// day, with their confirmation rate, unsubscribes and waitlist
+/// conversions. Computed from the signup tables on every request.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/analytics/acquisition",
+    tag = "analytics",
+    params(AcquisitionQuery),
+    responses(
+        (status = 200, description = "Signup attribution", body = AcquisitionReport),
+        (status = 400, description = "Unknown group_by", body = ApiError),
+        (status = 422, description = "days out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn analytics_acquisition(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(query): ValidatedQuery<AcquisitionQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let report = query.report(&state.db).await?;
+    Ok((StatusCode::OK, Json(report)))
+}
+
+/// The rows of [`analytics_acquisition`] as CSV, without the totals.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/analytics/acquisition.csv",
+    tag = "analytics",
+    params(AcquisitionQuery),
+    responses(
+        (status = 200, description = "CSV attachment", content_type = "text/csv"),
+        (status = 400, description = "Unknown group_by", body = ApiError),
+        (status = 422, description = "days out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn analytics_acquisition_csv(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(query): ValidatedQuery<AcquisitionQuery>,
+) -> Result<Response, ApiError> {
+    let report = query.report(&state.db).await?;
+    let rows = futures::stream::iter(report.rows.into_iter().map(anyhow::Ok));
+    Ok(csv_response("acquisition", futures::StreamExt::boxed(rows)))
+}
+
+// ── CSV exports ───────────────────────────────────────────────────────────────
+
+/// Stream waitlist entries as CSV, oldest first. `status` is one of
+/// `pending`, `invited`, `converted`; `from`/`to` bound `joined_at`
+/// inclusively by UTC date. The data row count follows as the
+/// `X-Row-Count` trailer.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/waitlist/export.csv",
+    tag = "waitlist",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "CSV attachment", content_type = "text/csv"),
+        (status = 400, description = "Unknown status or from after to", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn waitlist_export_csv(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let status = query
+        .status
+        .as_deref()
+        .map(|raw| {
+            WaitlistStatus::parse(raw)
+                .ok_or_else(|| ApiError::bad_request("status must be one of pending, invited, converted"))
+        })
+        .transpose()?;
+    let (from, to) = query.range()?;
+    Ok(csv_response("waitlist", state.db.waitlist_export(status, from, to)))
+}
+
+/// Stream newsletter subscribers as CSV, oldest first, excluding
+/// GDPR-deleted rows. `status` is one of `pending`, `confirmed`,
+/// `unsubscribed`; `from`/`to` bound `created_at` inclusively by UTC date.
+/// The data row count follows as the `X-Row-Count` trailer.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/newsletter/export.csv",
+    tag = "newsletter",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "CSV attachment", content_type = "text/csv"),
+        (status = 400, description = "Unknown status or from after to", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn newsletter_export_csv(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let status = query
+        .status
+        .as_deref()
+        .map(|raw| {
+            NewsletterExportStatus::parse(raw).ok_or_else(|| {
+                ApiError::bad_request("status must be one of pending, confirmed, unsubscribed")
+            })
+        })
+        .transpose()?;
+    let (from, to) = query.range()?;
+    Ok(csv_response("newsletter", state.db.newsletter_export(status, from, to)))
+}
+
+/// Stream indexed contract events for the `X-Network` network as CSV in
+/// ledger order. `kind` is one of the indexed event kinds; `from`/`to`
+/// bound `indexed_at` inclusively by UTC date. The data row count follows
+/// as the `X-Row-Count` trailer.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/events/export.csv",
+    tag = "blockchain",
+    params(EventExportQuery),
+    responses(
+        (status = 200, description = "CSV attachment", content_type = "text/csv"),
+        (status = 400, description = "Unknown kind or network, or from after to", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn chain_events_export_csv(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Query(query): Query<EventExportQuery>,
+) -> Result<Response, ApiError> {
+    let kind = query.kind.as_deref();
+    if let Some(kind) = kind {
+        if !crate::blockchain::EVENT_KINDS.contains(&kind) {
+            return Err(ApiError::bad_request(format!(
+                "kind must be one of {}",
+                crate::blockchain::EVENT_KINDS.join(", ")
+            )));
+        }
+    }
+    let (from, to) = query.range()?;
+    let rows = state.db.chain_events_export(client.network(), kind, from, to);
+    Ok(csv_response("events", rows))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct BackfillRequest {
+    /// First ledger to index, inclusive.
+    pub from_ledger: u32,
+    /// Last ledger to index, inclusive.
+    pub to_ledger: u32,
+}
+
+impl BackfillRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.from_ledger == 0 {
+            return Err(ApiError::bad_request("from_ledger must be at least 1"));
+        }
+        if self.to_ledger < self.from_ledger {
+            return Err(ApiError::bad_request(
+                "to_ledger must not be before from_ledger",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Queue a backfill of contract events for `[from_ledger, to_ledger]` on the
+/// `X-Network` network. The job runs in the background, one page at a time,
+/// and resumes where it stopped after a restart; poll it by id.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/indexer/backfill",
+    tag = "blockchain",
+    request_body = BackfillRequest,
+    responses(
+        (status = 202, description = "Job queued", body = BackfillJob),
+        (status = 400, description = "Invalid ledger range or unknown network", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn backfill_create(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Json(body): Json<BackfillRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    body.validate()?;
+    let job = state
+        .db
+        .backfill_job_create(client.network(), body.from_ledger, body.to_ledger)
+        .await
+        .map_err(into_api_error)?;
+    tracing::info!(
+        job_id = job.id,
+        network = %job.network,
+        from_ledger = job.from_ledger,
+        to_ledger = job.to_ledger,
+        "backfill job queued"
+    );
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// A backfill job's status and progress.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/indexer/backfill/{id}",
+    tag = "blockchain",
+    params(
+        ("id" = i64, Path, description = "Backfill job ID"),
+    ),
+    responses(
+        (status = 200, description = "Backfill job", body = BackfillJob),
+        (status = 404, description = "No such job", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn backfill_get(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<Json<BackfillJob>, ApiError> {
+    state
+        .db
+        .backfill_job_get(id)
+        .await
+        .map_err(into_api_error)?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("backfill job {id} not found")))
+}
+
+/// Platform statistics plus the indexed bet volume in USD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatisticsView {
+    #[serde(flatten)]
+    pub statistics: Statistics,
+    /// Indexed bet volume across every token, in USD to the cent; `null`
+    /// when any token with volume has no fresh price.
+    pub total_volume_usd: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics",
+    tag = "markets",
+    params(
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+    ),
+    responses(
+        (status = 200, description = "Platform statistics"),
+        (status = 304, description = "Unchanged since the ETag in If-None-Match"),
+    )
+)]
+pub async fn statistics(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let cache_key = keys::api_statistics();
+    let ttl = state.config.cache_ttls.statistics;
+    let endpoint = "statistics";
+
+    let TaggedLookup { value: payload, etag, hit } = state
+        .cache
+        .get_or_set_json_tagged(&cache_key, ttl, || async {
+            let statistics = state.db.statistics_cached().await?;
+            let total_volume_usd = match state.db.volume_by_token(state.config.network_name()).await {
+                Ok(volumes) => PriceService::new(state.cache.clone(), &state.config)
+                    .quotes()
+                    .await
+                    .total_usd(&volumes),
+                Err(e) => {
+                    tracing::warn!(error = %e, "statistics: volume by token lookup failed");
+                    None
+                }
+            };
+            Ok(StatisticsView {
+                statistics,
+                total_volume_usd,
+            })
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    let response = crate::etag::json_response(&headers, &etag, ttl, payload);
+    state
+        .metrics
+        .observe_request(endpoint, response.status().as_u16(), start.elapsed().as_secs_f64());
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/markets/featured",
+    tag = "markets",
+    params(
+        PaginationQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+    ),
+    responses(
+        (status = 200, description = "Paginated list of featured markets"),
+        (status = 304, description = "Unchanged since the ETag in If-None-Match"),
+        (status = 422, description = "limit out of range", body = ApiError),
+    )
+)]
+pub async fn featured_markets(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let limit = query.limit();
+    let cursor = query.cursor();
+    let cache_key = keys::api_featured_markets();
+    let ttl = state.config.cache_ttls.featured_markets;
+    let endpoint = "featured_markets";
+
+    let featured_limit = state.config.featured_limit;
+
+    let TaggedLookup { value: payload, etag, hit } = state
+        .cache
+        .get_or_set_json_tagged(&cache_key, ttl, || async {
+            let markets = state.db.featured_markets_cached(featured_limit).await?;
+            let ids: Vec<i64> = markets.iter().map(|m| m.id).collect();
+            let client = state.networks.primary();
+            let chain_ids = market_ids::resolve_many(&state.db, client, &ids).await?;
+            let chain_data = read_mapped_many(client, &ids, &chain_ids, |ids| async move {
+                client.market_data_many(&ids).await
+            })
+            .await;
+            let quotes = PriceService::new(state.cache.clone(), &state.config).quotes().await;
+
+            let mut view = Vec::with_capacity(markets.len());
+            for (m, chain_result) in markets.into_iter().zip(chain_data) {
+                let chain = chain_result?;
+                let title = if has_title(&m.title) {
+                    m.title
+                } else {
+                    let text = client.market_metadata_cached(chain.market_id).await;
+                    merged_title(m.title, &text)
+                };
+                view.push(FeaturedMarketView {
+                    id: m.id,
+                    title,
+                    image_url: m.image_url,
+                    volume: m.volume,
+                    ends_at: m.ends_at,
+                    volume_usd: quotes.volume_usd(m.token.as_deref(), &chain.onchain_volume),
+                    onchain_volume: chain.onchain_volume,
+                    resolved_outcome: chain.resolved_outcome,
+                });
+            }
+            Ok(view)
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    let start_idx = cursor
+        .as_ref()
+        .and_then(|c| c.parse::<usize>().ok())
+        .unwrap_or(0);
+    let end_idx = (start_idx + limit as usize).min(payload.len());
+    let has_more = end_idx < payload.len();
+    let next_cursor = if has_more {
+        Some(end_idx.to_string())
+    } else {
+        None
+    };
+
+    let paginated = PaginatedResponse::new(
+        payload[start_idx..end_idx].to_vec(),
+        next_cursor,
+        limit as u32,
+        has_more,
+    );
+    let etag = crate::etag::derived_etag(&etag, &[&start_idx.to_string(), &limit.to_string()]);
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    let response = crate::etag::json_response(&headers, &etag, ttl, paginated);
+    state
+        .metrics
+        .observe_request(endpoint, response.status().as_u16(), start.elapsed().as_secs_f64());
+
+    Ok(response)
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct MarketListQuery {
+    /// One of `active`, `resolved`, `cancelled`.
+    pub status: Option<String>,
+    /// A slug from `GET /api/v1/categories`; unknown slugs are a 400.
+    pub category: Option<String>,
+    pub creator: Option<String>,
+    pub ends_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub ends_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub sort: Option<MarketSort>,
+    /// Enrich each market with on-chain data. Off by default because it
+    /// costs one RPC lookup (or cache read) per market.
+    #[serde(default)]
+    pub include_chain: bool,
+    /// Comma-separated dotted paths to keep in each item, e.g.
+    /// `id,title,onchain_volume`. Unknown fields are ignored.
+    pub fields: Option<String>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+/// Same shape as [`FeaturedMarketView`] without `volume_usd`;
+/// `onchain_volume` is only present when the request sets
+/// `include_chain=true`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketListView {
+    pub id: i64,
+    pub title: String,
+    pub volume: f64,
+    pub ends_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onchain_volume: Option<String>,
+    pub resolved_outcome: Option<u32>,
+}
+
+const MARKET_STATUSES: [&str; 3] = ["active", "resolved", "cancelled"];
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/markets",
+    tag = "markets",
+    params(MarketListQuery),
+    responses(
+        (status = 200, description = "Cursor-paginated list of markets"),
+        (status = 400, description = "Invalid filter, unknown category, or bad cursor", body = ApiError),
+    )
+)]
+pub async fn list_markets(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<MarketListQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let endpoint = "list_markets";
+    let page = PaginationQuery {
+        limit: query.limit,
+        cursor: query.cursor.clone(),
+    };
+    let limit = page.limit();
+    let sort = query.sort.unwrap_or_default();
+    let mask = FieldMask::from_query(query.fields.as_deref()).map_err(ApiError::bad_request)?;
+
+    if let Some(status) = query.status.as_deref() {
+        if !MARKET_STATUSES.contains(&status) {
+            return Err(ApiError::bad_request(format!(
+                "status must be one of: {}",
+                MARKET_STATUSES.join(", ")
+            )));
+        }
+    }
+    if let (Some(before), Some(after)) = (query.ends_before, query.ends_after) {
+        if before <= after {
+            return Err(ApiError::bad_request("ends_before must be later than ends_after"));
+        }
+    }
+    if let Some(category) = query.category.as_deref() {
+        let known = state.db.categories_cached().await.map_err(into_api_error)?;
+        if !known.iter().any(|c| c.slug == category) {
+            return Err(ApiError::new(
+                ApiErrorKind::Validation,
+                "UNKNOWN_CATEGORY",
+                format!("unknown category: {category}"),
+            ));
+        }
+    }
+
+    let cursor = match page.cursor() {
+        Some(raw) => Some(
+            MarketCursor::decode(&raw, sort)
+                .ok_or_else(|| ApiError::bad_request("invalid cursor for the requested sort"))?,
+        ),
+        None => None,
+    };
+
+    let filter = MarketListFilter {
+        status: query.status,
+        category: query.category,
+        creator: query.creator,
+        ends_before: query.ends_before,
+        ends_after: query.ends_after,
+        sort,
+    };
+
+    let rows = state
+        .db
+        .list_markets(&filter, cursor.as_ref(), limit)
+        .await
+        .map_err(into_api_error)?;
+    let page = PageResponse::from_fetched(rows, limit as u32, |row| {
+        MarketCursor::after(row, sort).encode()
+    });
+    let has_more = page.next_cursor.is_some();
+
+    let chain_data = if query.include_chain {
+        let ids: Vec<i64> = page.items.iter().map(|m| m.id).collect();
+        let client = state.networks.primary();
+        let chain_ids = market_ids::resolve_many(&state.db, client, &ids)
+            .await
+            .map_err(into_api_error)?;
+        Some(
+            read_mapped_many(client, &ids, &chain_ids, |ids| async move {
+                client.market_data_many(&ids).await
+            })
+            .await,
+        )
+    } else {
+        None
+    };
+
+    let mut items = Vec::with_capacity(page.items.len());
+    match chain_data {
+        Some(chain_data) => {
+            for (m, chain_result) in page.items.into_iter().zip(chain_data) {
+                let chain = chain_result.map_err(into_api_error)?;
+                items.push(MarketListView {
+                    id: m.id,
+                    title: m.title,
+                    volume: m.volume,
+                    ends_at: m.ends_at,
+                    onchain_volume: Some(chain.onchain_volume),
+                    resolved_outcome: chain.resolved_outcome,
+                });
+            }
+        }
+        None => {
+            for m in page.items {
+                items.push(MarketListView {
+                    id: m.id,
+                    title: m.title,
+                    volume: m.volume,
+                    ends_at: m.ends_at,
+                    onchain_volume: None,
+                    resolved_outcome: m.outcome_index.and_then(|i| u32::try_from(i).ok()),
+                });
+            }
+        }
+    }
+
+    let items = items
+        .into_iter()
+        .map(|item| serde_json::to_value(item).map(|v| mask.apply(v)))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| into_api_error(e.into()))?;
+
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((
+        StatusCode::OK,
+        Json(PaginatedResponse::new(items, page.next_cursor, limit as u32, has_more)),
+    ))
+}
+
+/// Where one part of a [`MarketDetailView`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PartSource {
+    /// Read from the source of truth while serving this request.
+    Fresh,
+    /// Served from cache (or a stale RPC fallback).
+    Cached,
+    /// The source could not be reached or has no record; the part is `null`.
+    Unavailable,
+    /// The part is optional and the request did not `embed` it; it is `null`
+    /// and was not fetched.
+    NotRequested,
+}
+
+impl PartSource {
+    fn from_lookup(hit: bool, source: DataSource) -> Self {
+        if hit || source == DataSource::StaleFallback {
+            PartSource::Cached
+        } else {
+            PartSource::Fresh
+        }
+    }
+
+    fn as_cached(self) -> Self {
+        match self {
+            PartSource::Fresh => PartSource::Cached,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketDetailSources {
+    pub metadata: PartSource,
+    pub chain_market: PartSource,
+    pub oracle: PartSource,
+    pub history: PartSource,
+}
+
+impl MarketDetailSources {
+    fn all_available(&self) -> bool {
+        [self.metadata, self.chain_market, self.oracle, self.history]
+            .iter()
+            .all(|s| *s != PartSource::Unavailable)
+    }
+}
+
+/// Mark every `fresh` part of a cached, possibly masked detail document as
+/// `cached`. A mask that dropped `data_sources` leaves nothing to mark.
+fn mark_detail_cached(view: &mut serde_json::Value) {
+    let Some(sources) = view.get_mut("data_sources").and_then(|v| v.as_object_mut()) else {
+        return;
+    };
+    for source in sources.values_mut() {
+        if let Ok(part) = serde_json::from_value::<PartSource>(source.clone()) {
+            if let Ok(cached) = serde_json::to_value(part.as_cached()) {
+                *source = cached;
+            }
+        }
+    }
+}
+
+/// Optional parts of [`MarketDetailView`], requested with
+/// `?embed=oracle,history`. Neither is fetched unless asked for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarketEmbeds {
+    pub oracle: bool,
+    pub history: bool,
+}
+
+impl MarketEmbeds {
+    const NAMES: [&'static str; 2] = ["oracle", "history"];
+
+    pub fn parse(raw: Option<&str>) -> Result<Self, ApiError> {
+        let mut embeds = Self::default();
+        for name in raw.unwrap_or_default().split(',').map(str::trim) {
+            match name {
+                "" => {}
+                "oracle" => embeds.oracle = true,
+                "history" => embeds.history = true,
+                other => {
+                    return Err(ApiError::new(
+                        ApiErrorKind::Validation,
+                        "UNKNOWN_EMBED",
+                        format!(
+                            "unknown embed: {other}; expected any of: {}",
+                            Self::NAMES.join(", ")
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(embeds)
+    }
+
+    /// Sorted, comma-separated names; empty when nothing is embedded.
+    pub fn label(self) -> String {
+        let mut names = Vec::new();
+        if self.history {
+            names.push("history");
+        }
+        if self.oracle {
+            names.push("oracle");
+        }
+        names.join(",")
+    }
+}
+
+/// Candles embedded by `?embed=history`: the default window at this
+/// resolution, all outcomes. Use `/history` for anything else.
+const EMBED_HISTORY_RESOLUTION: HistoryResolution = HistoryResolution::OneHour;
+
+/// Await `fetch` only when its part was requested; `None` otherwise.
+async fn fetch_if<T, F, Fut>(requested: bool, fetch: F) -> Option<T>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = T>,
+{
+    if requested {
+        Some(fetch().await)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct MarketDetailQuery {
+    /// Comma-separated optional parts to include: `oracle`, `history`.
+    pub embed: Option<String>,
+    /// Comma-separated dotted paths to keep, e.g.
+    /// `id,metadata.title,chain.market.onchain_volume`. Unknown fields are
+    /// ignored.
+    pub fields: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketChainView {
+    #[schema(value_type = Option<Object>)]
+    pub market: Option<ChainMarketData>,
+    /// Only fetched with `embed=oracle`.
+    #[schema(value_type = Option<Object>)]
+    pub oracle: Option<OracleResult>,
+}
+
+/// Postgres metadata and on-chain state for one market in a single document.
+///
+/// Either half may be `null` when its source is unavailable, and the
+/// embeddable parts are `null` unless requested; `data_sources` says which.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketDetailView {
+    pub id: i64,
+    pub metadata: Option<MarketDetail>,
+    pub chain: MarketChainView,
+    /// On-chain volume in USD to the cent; `null` when either half is
+    /// missing or the market's token has no fresh price.
+    #[serde(default)]
+    pub volume_usd: Option<String>,
+    /// Recent hourly candles per outcome; only fetched with `embed=history`.
+    #[serde(default)]
+    pub history: Option<PriceHistory>,
+    pub data_sources: MarketDetailSources,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/markets/{market_id}",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Market ID (shared by the database and the contract)"),
+        MarketDetailQuery,
+    ),
+    responses(
+        (status = 200, description = "Combined market detail; parts may be null", body = MarketDetailView),
+        (status = 400, description = "Unknown embed or malformed fields", body = ApiError),
+        (status = 404, description = "Market unknown to both the database and the chain", body = ApiError),
+    )
+)]
+pub async fn market_detail(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<i64>,
+    Query(query): Query<MarketDetailQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let embeds = MarketEmbeds::parse(query.embed.as_deref())?;
+    let mask = FieldMask::from_query(query.fields.as_deref()).map_err(ApiError::bad_request)?;
+    let cache_key =
+        keys::api_market_detail_shaped(market_id, &embeds.label(), &mask.cache_key_part());
+    let ttl = state.config.cache_ttls.market_detail;
+    let endpoint = "market_detail";
+
+    if let Ok(Some(mut cached)) = state.cache.get_json::<serde_json::Value>(&cache_key).await {
+        mark_detail_cached(&mut cached);
+        state.metrics.observe_hit("api", endpoint);
+        state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+        return Ok((StatusCode::OK, Json(cached)));
+    }
+    state.metrics.observe_miss("api", endpoint);
+
+    // An unmapped market is served from its metadata alone, like one whose
+    // chain read failed.
+    let (db, chain) = (&state.db, state.networks.primary());
+    let chain_market_id = market_ids::resolve(db, chain, market_id).await.ok();
+    let (metadata, chain_market, oracle, history) = tokio::join!(
+        db.market_detail(market_id),
+        async {
+            match chain_market_id {
+                Some(id) => chain.market_data_lookup(id).await,
+                None => Err(market_ids::unmapped(chain, market_id)),
+            }
+        },
+        fetch_if(embeds.oracle, || async move {
+            match chain_market_id {
+                Some(id) => chain.oracle_result_lookup(id).await,
+                None => Err(market_ids::unmapped(chain, market_id)),
+            }
+        }),
+        fetch_if(embeds.history, || recent_price_history(db, market_id)),
+    );
+
+    let (mut metadata, metadata_source) = match metadata {
+        Ok(Some(m)) => (Some(m), PartSource::Fresh),
+        Ok(None) => (None, PartSource::Unavailable),
+        Err(e) => {
+            if chain_market.is_err() {
+                return Err(into_api_error(e));
+            }
+            tracing::warn!(market_id, error = %e, "market detail: metadata lookup failed");
+            (None, PartSource::Unavailable)
+        }
+    };
+    let (mut chain_market, chain_market_source) = match chain_market {
+        Ok((mut m, hit)) => {
+            let source = PartSource::from_lookup(hit, m.source);
+            m.market_id = market_id;
+            (Some(m), source)
+        }
+        Err(_) => (None, PartSource::Unavailable),
+    };
+    let (oracle, oracle_source) = match oracle {
+        Some(Ok((mut o, hit))) => {
+            let source = PartSource::from_lookup(hit, o.source);
+            o.market_id = market_id;
+            (Some(o), source)
+        }
+        Some(Err(_)) => (None, PartSource::Unavailable),
+        None => (None, PartSource::NotRequested),
+    };
+    let (history, history_source) = match history {
+        Some(Ok(h)) => (Some(h), PartSource::Fresh),
+        Some(Err(e)) => {
+            tracing::warn!(market_id, error = %e, "market detail: price history lookup failed");
+            (None, PartSource::Unavailable)
+        }
+        None => (None, PartSource::NotRequested),
+    };
+
+    if metadata.is_none() && chain_market.is_none() {
+        return Err(ApiError::market_not_found(market_id));
+    }
+
+    if let Some(chain_market_id) =
+        chain_market_id.filter(|_| !metadata.as_ref().is_some_and(|m| has_title(&m.title)))
+    {
+        let text = chain.market_metadata_cached(chain_market_id).await;
+        apply_chain_metadata(metadata.as_mut(), chain_market.as_mut(), text);
+    }
+
+    let quotes = PriceService::new(state.cache.clone(), &state.config).quotes().await;
+    let volume_usd = chain_market.as_ref().and_then(|chain| {
+        let token = metadata.as_ref().and_then(|m| m.token.as_deref());
+        quotes.volume_usd(token, &chain.onchain_volume)
+    });
+
+    let view = MarketDetailView {
+        id: market_id,
+        volume_usd,
+        metadata,
+        chain: MarketChainView {
+            market: chain_market,
+            oracle,
+        },
+        history,
+        data_sources: MarketDetailSources {
+            metadata: metadata_source,
+            chain_market: chain_market_source,
+            oracle: oracle_source,
+            history: history_source,
+        },
+    };
+    let complete = view.data_sources.all_available();
+    let body = mask.apply(serde_json::to_value(view).map_err(|e| into_api_error(e.into()))?);
+
+    // Only cache complete documents so a partial response is retried on the
+    // next request instead of being pinned for the full TTL. The key covers
+    // the embeds and mask, so each shape is cached as served.
+    if complete {
+        if let Err(e) = state.cache.set_json(&cache_key, &body, ttl).await {
+            tracing::warn!(cache_key, error = %e, "market detail cache write failed");
+        }
+    }
+
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(body)))
+}
+
+/// The `?embed=history` part: [`EMBED_HISTORY_RESOLUTION`] candles over its
+/// default window, ending now.
+async fn recent_price_history(
+    db: &crate::db::Database,
+    market_id: i64,
+) -> anyhow::Result<PriceHistory> {
+    let resolution = EMBED_HISTORY_RESOLUTION;
+    let to = chrono::Utc::now();
+    let from = to - resolution.default_window();
+    let series = db.price_history(market_id, None, resolution, from, to).await?;
+    Ok(PriceHistory {
+        market_id,
+        resolution,
+        from,
+        to,
+        series,
+    })
+}
+
+/// Whether a Postgres title is usable. Markets indexed from events alone
+/// have an empty one until an admin fills it in.
+fn has_title(title: &str) -> bool {
+    !title.trim().is_empty()
+}
+
+/// The Postgres title when it has one, otherwise the contract's description.
+fn merged_title(db_title: String, text: &MarketMetadata) -> String {
+    match &text.description {
+        Some(description) if !has_title(&db_title) => description.clone(),
+        _ => db_title,
+    }
+}
+
+/// Fill a market's missing title and outcome labels from the contract.
+/// Postgres values always win; the chain copy only fills gaps.
+fn apply_chain_metadata(
+    metadata: Option<&mut MarketDetail>,
+    chain: Option<&mut ChainMarketData>,
+    text: MarketMetadata,
+) {
+    if let Some(m) = metadata {
+        m.title = merged_title(std::mem::take(&mut m.title), &text);
+        if m.outcome_options.is_empty() {
+            m.outcome_options = text.options.clone().unwrap_or_default();
+        }
+    }
+    if let Some(chain) = chain {
+        chain.title = chain.title.take().or(text.description);
+        chain.options = chain.options.take().or(text.options);
+    }
+}
+
+/// Stellar account (`G…`) and contract (`C…`) strkeys are 56 uppercase
+/// base32 characters.
+fn is_strkey(address: &str) -> bool {
+    address.len() == 56
+        && matches!(address.as_bytes()[0], b'G' | b'C')
+        && address
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || (b'2'..=b'7').contains(&b))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{address}/portfolio",
+    tag = "markets",
+    params(
+        ("address" = String, Path, description = "Stellar address (G… or C… strkey)"),
+        FieldsQuery,
+    ),
+    responses(
+        (status = 200, description = "Per-market positions and per-token totals", body = Portfolio),
+        (status = 400, description = "Malformed address or fields", body = ApiError),
+    )
+)]
+pub async fn user_portfolio(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+    Query(query): Query<FieldsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_strkey(&address) {
+        return Err(ApiError::bad_request("address must be a Stellar strkey"));
+    }
+    let mask = FieldMask::from_query(query.fields.as_deref()).map_err(ApiError::bad_request)?;
+
+    let start = Instant::now();
+    let cache_key = keys::api_user_portfolio(&address);
+    // Short TTL as a backstop; the sync worker drops the key on new events.
+    let ttl = state.config.cache_ttls.portfolio;
+    let endpoint = "user_portfolio";
+    let network = state.config.network_name();
+
+    let (portfolio, hit) = state
+        .cache
+        .get_or_set_json(&cache_key, ttl, || async {
+            state.db.user_portfolio(network, &address).await
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    // The portfolio is cached whole and masked per request: the sync worker
+    // drops it by exact key on every indexed event, which per-mask keys
+    // would turn into a keyspace scan.
+    let body = mask.apply(serde_json::to_value(portfolio).map_err(|e| into_api_error(e.into()))?);
+
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(body)))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct FieldsQuery {
+    /// Comma-separated dotted paths to keep, e.g.
+    /// `positions.market_id,positions.realized_pnl`. Unknown fields are
+    /// ignored.
+    pub fields: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct PriceHistoryQuery {
+    /// Restrict to one outcome index; all outcomes when omitted.
+    pub outcome: Option<i32>,
+    /// `5m`, `1h` (default), or `1d`.
+    pub resolution: Option<HistoryResolution>,
+    /// Inclusive start. Defaults to a resolution-dependent window before `to`.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Exclusive end. Defaults to now.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/markets/{market_id}/history",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Market ID"),
+        PriceHistoryQuery,
+    ),
+    responses(
+        (status = 200, description = "OHLC implied-probability candles per outcome", body = PriceHistory),
+        (status = 400, description = "Invalid range or too many buckets", body = ApiError),
+    )
+)]
+pub async fn market_history(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<i64>,
+    Query(query): Query<PriceHistoryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let endpoint = "market_history";
+    let resolution = query.resolution.unwrap_or_default();
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query.from.unwrap_or(to - resolution.default_window());
+
+    if from >= to {
+        return Err(ApiError::bad_request("from must be before to"));
+    }
+    if resolution.bucket_count(from, to) > price_history::MAX_BUCKETS {
+        return Err(ApiError::bad_request(format!(
+            "range spans more than {} buckets at this resolution",
+            price_history::MAX_BUCKETS
+        )));
+    }
+    if query.outcome.is_some_and(|o| o < 0) {
+        return Err(ApiError::bad_request("outcome must be non-negative"));
+    }
+
+    let series = state
+        .db
+        .price_history(market_id, query.outcome, resolution, from, to)
+        .await
+        .map_err(into_api_error)?;
+
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((
+        StatusCode::OK,
+        Json(PriceHistory {
+            market_id,
+            resolution,
+            from,
+            to,
+            series,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct LeaderboardQuery {
+    /// `weekly` (default) or `all`.
+    pub period: Option<LeaderboardPeriod>,
+    /// `profit` (default), `volume`, or `winrate`.
+    pub metric: Option<LeaderboardMetric>,
+    /// Entries to return. Default 10, capped at `LEADERBOARD_SIZE`.
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/leaderboard",
+    tag = "markets",
+    params(LeaderboardQuery),
+    responses(
+        (status = 200, description = "Latest leaderboard snapshot", body = Leaderboard),
+        (status = 400, description = "Unknown period or metric", body = ApiError),
+    )
+)]
+pub async fn leaderboard(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LeaderboardQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let period = query.period.unwrap_or_default();
+    let metric = query.metric.unwrap_or_default();
+    let size = state.config.leaderboard_size;
+    let limit = query.limit.unwrap_or(10).clamp(1, size) as usize;
+
+    // The whole snapshot is cached once per board and sliced per request, so
+    // the aggregation task only has six keys to drop after a rebuild.
+    let cache_key = keys::api_leaderboard(period.label(), metric.label());
+    let ttl = state.config.cache_ttls.leaderboard;
+    let endpoint = "leaderboard";
+
+    let (mut board, hit) = state
+        .cache
+        .get_or_set_json(&cache_key, ttl, || async {
+            state.db.leaderboard(period, metric, size).await
+        })
+        .await
+        .map_err(into_api_error)?;
+    board.entries.truncate(limit);
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(board)))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct TopCreatorsQuery {
+    /// `volume` (default) or `markets`.
+    pub metric: Option<TopCreatorsMetric>,
+    /// Entries to return. Default 10, capped at 50.
+    pub limit: Option<i64>,
+}
+
+/// Leading market creators on the primary network, for discovery. Creators
+/// who hid their profile are left out.
+#[utoipa::path(
+    get,
+    path = "/api/v1/creators/top",
+    tag = "markets",
+    params(TopCreatorsQuery),
+    responses(
+        (status = 200, description = "Creators ranked by the metric", body = TopCreators),
+        (status = 400, description = "Unknown metric", body = ApiError),
+    )
+)]
+pub async fn top_creators(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TopCreatorsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let metric = query.metric.unwrap_or_default();
+    let limit = query.limit.unwrap_or(10).clamp(1, creators::TOP_CREATORS_SIZE) as usize;
+
+    let cache_key = keys::api_top_creators(metric.label());
+    let ttl = state.config.cache_ttls.creator_profile;
+    let endpoint = "top_creators";
+    let network = state.config.network_name();
+
+    let (mut top, hit) = state
+        .cache
+        .get_or_set_json(&cache_key, ttl, || async {
+            creators::top_creators(&state.db, network, metric, chrono::Utc::now()).await
+        })
+        .await
+        .map_err(into_api_error)?;
+    top.entries.truncate(limit);
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(top)))
+}
+
+/// A market creator's profile: the contract's reputation tier, stats over
+/// the markets they created, and those still active. When the reputation
+/// cannot be simulated it is `null` and the profile is not cached.
+#[utoipa::path(
+    get,
+    path = "/api/v1/creators/{address}",
+    tag = "markets",
+    params(
+        ("address" = String, Path, description = "Stellar address (G… or C… strkey)"),
+    ),
+    responses(
+        (status = 200, description = "Creator profile", body = CreatorProfile),
+        (status = 400, description = "Malformed address", body = ApiError),
+        (status = 404, description = "The creator hid their profile", body = ApiError),
+    )
+)]
+pub async fn creator_profile(
+    State(state): State<Arc<AppState>>,
+    Path(address): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_strkey(&address) {
+        return Err(ApiError::bad_request("address must be a Stellar strkey"));
+    }
+    // Checked on every request, so hiding a profile applies before its
+    // cached copy expires.
+    if state.db.creator_profile_hidden(&address).await.map_err(into_api_error)? {
+        return Err(ApiError::not_found(format!("no creator profile for {address}")));
+    }
+
+    let start = Instant::now();
+    let cache_key = keys::api_creator_profile(&address);
+    let ttl = state.config.cache_ttls.creator_profile;
+    let endpoint = "creator_profile";
+    let network = state.config.network_name();
+
+    let cached = state
+        .cache
+        .get_json::<CreatorProfile>(&cache_key)
+        .await
+        .map_err(into_api_error)?;
+    let profile = match cached {
+        Some(profile) => {
+            state.metrics.observe_hit("api", endpoint);
+            profile
+        }
+        None => {
+            state.metrics.observe_miss("api", endpoint);
+            let profile = creators::profile(
+                &state.db,
+                state.networks.primary(),
+                network,
+                &address,
+                chrono::Utc::now(),
+            )
+            .await
+            .map_err(into_api_error)?;
+            if profile.reputation.is_some() {
+                state
+                    .cache
+                    .set_json(&cache_key, &profile, ttl)
+                    .await
+                    .map_err(into_api_error)?;
+            }
+            profile
+        }
+    };
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(profile)))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct ActivityQuery {
+    /// Comma-separated activity types; all of them when omitted.
+    pub types: Option<String>,
+    pub market_id: Option<i64>,
+    /// 1-based page, newest first. Ignored with `since_id`.
+    pub page: Option<i64>,
+    /// Entries per page. Default 20, capped at 100.
+    pub limit: Option<i64>,
+    /// Return only activity indexed after this event id, oldest first.
+    pub since_id: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/activity",
+    tag = "markets",
+    params(
+        ActivityQuery,
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Disputes, votes, resolutions and guardian actions with per-type counts", body = ActivityFeed),
+        (status = 400, description = "Unknown type, page below 1 or malformed since_id", body = ApiError),
+    )
+)]
+pub async fn activity_feed(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Query(query): Query<ActivityQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let types = ActivityType::parse_list(query.types.as_deref()).map_err(ApiError::bad_request)?;
+    let page = query.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ApiError::bad_request("page must be at least 1"));
+    }
+    if let Some(since_id) = &query.since_id {
+        if !activity::valid_since_id(since_id) {
+            return Err(ApiError::bad_request("since_id must be an event id"));
+        }
+    }
+    let limit = query.limit.unwrap_or(activity::DEFAULT_LIMIT).clamp(1, activity::MAX_LIMIT);
+    let filter = ActivityFilter {
+        types,
+        market_id: query.market_id,
+        since_id: query.since_id,
+    };
+    let network = client.network().to_string();
+    let endpoint = "activity";
+
+    let load = || async {
+        let offset = if filter.since_id.is_some() { 0 } else { (page - 1) * limit };
+        let (rows, counts) = tokio::try_join!(
+            state.db.activity_page(&network, &filter, limit, offset),
+            state.db.activity_counts(&network, &filter),
+        )?;
+        Ok::<_, anyhow::Error>(ActivityFeed::new(&filter, rows, counts, page, limit))
+    };
+
+    // Only the first page of a plain listing is cached: it is what
+    // dashboards refresh, and a short TTL keeps it close to the indexer.
+    let feed = if page == 1 && filter.since_id.is_none() {
+        let labels: Vec<&str> = filter.types.iter().map(|t| t.label()).collect();
+        let cache_key = keys::api_activity(&network, &labels.join(","), filter.market_id, limit);
+        let (feed, hit) = state
+            .cache
+            .get_or_set_json(&cache_key, state.config.cache_ttls.activity, load)
+            .await
+            .map_err(into_api_error)?;
+        if hit {
+            state.metrics.observe_hit("api", endpoint);
+        } else {
+            state.metrics.observe_miss("api", endpoint);
+        }
+        feed
+    } else {
+        load().await.map_err(into_api_error)?
+    };
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(feed)))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct StatsHistoryQuery {
+    /// `markets_created` (default), `volume`, or `new_participants`.
+    pub metric: Option<StatsMetric>,
+    /// Days to return, today included. Default 30, capped at 365.
+    pub days: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/statistics/history",
+    tag = "markets",
+    params(StatsHistoryQuery),
+    responses(
+        (status = 200, description = "One point per UTC day, oldest first", body = StatsHistory),
+        (status = 400, description = "Unknown metric", body = ApiError),
+    )
+)]
+pub async fn statistics_history(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<StatsHistoryQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let metric = query.metric.unwrap_or_default();
+    let days = query
+        .days
+        .unwrap_or(i64::from(stats_history::DEFAULT_HISTORY_DAYS))
+        .clamp(1, i64::from(stats_history::MAX_HISTORY_DAYS)) as u32;
+
+    // The full series is cached once per metric and sliced per request, so
+    // the nightly rollup only has three keys to drop.
+    let cache_key = keys::api_statistics_history(metric.label());
+    let endpoint = "statistics_history";
+
+    let (history, hit) = state
+        .cache
+        .get_or_set_json(&cache_key, stats_history::HISTORY_CACHE_TTL, || async {
+            let window = crate::analytics::summary_window(chrono::Utc::now(), stats_history::MAX_HISTORY_DAYS);
+            let (rows, rolled_up_at) = state.db.platform_stats_daily(metric, window.0, window.1).await?;
+            Ok(StatsHistory::new(metric, window, rows, rolled_up_at))
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    state.metrics.observe_request(endpoint, 200, start.elapsed().as_secs_f64());
+
+    Ok((StatusCode::OK, Json(history.last_days(days))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/content",
+    tag = "markets",
+    params(
+        PaginationQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+    ),
+    responses(
+        (status = 200, description = "Paginated content items"),
+        (status = 304, description = "Unchanged since the ETag in If-None-Match"),
+        (status = 422, description = "limit out of range", body = ApiError),
+    )
+)]
+pub async fn content(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = Instant::now();
+    let limit = query.limit();
+    let cursor = query.cursor();
+    let endpoint = "content";
+
+    let cache_key = keys::api_content(limit);
+    let ttl = state.config.cache_ttls.content;
+
+    let TaggedLookup { value: payload, etag, hit } = state
+        .cache
+        .get_or_set_json_tagged(&cache_key, ttl, || async {
+            let data = state.db.content_cached(limit).await?;
+            Ok(data)
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    let start_idx = cursor
+        .as_ref()
+        .and_then(|c| c.parse::<usize>().ok())
+        .unwrap_or(0);
+    let end_idx = (start_idx + limit as usize).min(payload.len());
+    let has_more = end_idx < payload.len();
+    let next_cursor = if has_more {
+        Some(end_idx.to_string())
+    } else {
+        None
+    };
+
+    let paginated = PaginatedResponse::new(
+        payload[start_idx..end_idx].to_vec(),
+        next_cursor,
+        limit as u32,
+        has_more,
+    );
+    let etag = crate::etag::derived_etag(&etag, &[&start_idx.to_string(), &limit.to_string()]);
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    let response = crate::etag::json_response(&headers, &etag, ttl, paginated);
+    state
+        .metrics
+        .observe_request(endpoint, response.status().as_u16(), start.elapsed().as_secs_f64());
+
+    Ok(response)
+}
+
+/// One published article, its markdown rendered to sanitized HTML. Drafts and
+/// unknown slugs are 404. Cached for an hour by default; admin content writes
+/// drop it.
+#[utoipa::path(
+    get,
+    path = "/api/v1/content/{slug}",
+    tag = "content",
+    params(
+        ("slug" = String, Path, description = "Article slug"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag from a previous response"),
+    ),
+    responses(
+        (status = 200, description = "Rendered article", body = RenderedContent),
+        (status = 304, description = "Unchanged since the ETag in If-None-Match"),
+        (status = 404, description = "No published article with this slug", body = ApiError),
+    )
+)]
+pub async fn content_page(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(slug): Path<String>,
+) -> Result<Response, ApiError> {
+    let start = Instant::now();
+    let endpoint = "content_page";
+    let ttl = state.config.cache_ttls.content;
+    let not_found = || ApiError::not_found(format!("content {slug} not found"));
+    // Malformed slugs can never match, and must not become cache keys.
+    content::validate_slug(&slug).map_err(|_| not_found())?;
+
+    let TaggedLookup { value: page, etag, hit } = state
+        .cache
+        .get_or_set_json_tagged(&keys::api_content_page(&slug), ttl, || async {
+            let entry = state.db.content_published_by_slug(&slug).await?;
+            Ok(entry.and_then(RenderedContent::from_published))
+        })
+        .await
+        .map_err(into_api_error)?;
+
+    if hit {
+        state.metrics.observe_hit("api", endpoint);
+    } else {
+        state.metrics.observe_miss("api", endpoint);
+    }
+    let page = page.ok_or_else(not_found)?;
+    let response = crate::etag::json_response(&headers, &etag, ttl, page);
+    state
+        .metrics
+        .observe_request(endpoint, response.status().as_u16(), start.elapsed().as_secs_f64());
+
+    Ok(response)
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct InvalidationResult {
+    pub invalidated_keys: usize,
+}
+
+/// Resolve a market by its ID.
+///
+/// Workflow:
+/// 1. Invoke the contract's `resolve_market` signed by the admin key
+///    (`ADMIN_SECRET_KEY`) and wait for the transaction to reach `SUCCESS`.
+/// 2. Mirror the outcome into the `markets` row. The chain is authoritative,
+///    so a failed mirror write is logged rather than returned.
+/// 3. Invalidate only the cache keys that are directly affected by this market's
+///    resolution (specific market key, oracle result, combined market detail,
+///    statistics aggregates, and featured-markets list). Content pages and per-user bet lists are left intact
+///    because they are not affected by a single market resolution.
+/// 4. Nothing is written or invalidated unless the transaction succeeded — a
+///    signing error, rejection, or timeout leaves the database and cache untouched.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct ResolveMarketRequest {
+    /// The winning outcome index (0-based).
+    #[serde(alias = "outcome_index")]
+    pub winning_outcome: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResolveMarketResult {
+    pub tx_hash: String,
+    /// Ledger the resolution was applied in.
+    pub ledger: Option<u32>,
+    pub invalidated_keys: usize,
+}
+
+/// Map a failed contract call to a response; each stage gets its own code.
+fn contract_call_error(err: ContractCallError) -> ApiError {
+    match err {
+        ContractCallError::NotConfigured => ApiError::service_unavailable(
+            "No admin signer is configured; on-chain admin actions are disabled.",
+        ),
+        ContractCallError::Signing(e) => {
+            tracing::error!(error = %e, "contract call: build/sign failed");
+            ApiError::new(
+                ApiErrorKind::Internal,
+                "SIGNING_FAILED",
+                "The transaction could not be built or signed.",
+            )
+        }
+        ContractCallError::Simulation(message) => {
+            ApiError::unprocessable("SIMULATION_FAILED", message)
+        }
+        ContractCallError::Rejected {
+            hash,
+            status,
+            result_xdr,
+        } => ApiError::unprocessable(
+            "SUBMISSION_REJECTED",
+            format!(
+                "transaction {hash} {status}: {}",
+                result_xdr.as_deref().unwrap_or("no result XDR")
+            ),
+        ),
+        ContractCallError::Timeout { hash } => ApiError::new(
+            ApiErrorKind::UpstreamTimeout,
+            "CONFIRMATION_TIMEOUT",
+            format!(
+                "transaction {hash} was submitted but is not final yet; \
+                 check /api/v1/blockchain/tx/{hash} before retrying"
+            ),
+        ),
+        ContractCallError::Rpc(e) => into_api_error(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/markets/{market_id}/resolve",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Market database ID"),
+    ),
+    request_body = ResolveMarketRequest,
+    responses(
+        (status = 200, description = "Resolved on-chain and cache invalidated", body = ResolveMarketResult),
+        (status = 400, description = "Bad request", body = ApiError),
+        (status = 422, description = "Simulation failed or the transaction was rejected", body = ApiError),
+        (status = 500, description = "Signing or internal error", body = ApiError),
+        (status = 503, description = "No admin signer configured", body = ApiError),
+        (status = 504, description = "Submitted but not confirmed in time", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn resolve_market(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<i64>,
+    Json(payload): Json<ResolveMarketRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let chain_market_id = u64::try_from(market_id)
+        .map_err(|_| ApiError::bad_request("market_id must be non-negative"))?;
+
+    // 1. Resolve on-chain; returns only once the transaction succeeded.
+    let confirmed = state
+        .networks
+        .primary()
+        .resolve_market_onchain(chain_market_id, payload.winning_outcome)
+        .await
+        .map_err(contract_call_error)?;
+
+    // 2. Mirror into the database.
+    if let Err(e) = state.db.resolve_market(market_id, payload.winning_outcome).await {
+        tracing::warn!(market_id, error = %e, "resolved on-chain but markets row not updated");
+    }
+
+    // 3. Until every RPC node has seen the transaction, serve the resolved
+    //    state over whatever a lagging node returns, so the reads that
+    //    repopulate the caches below cannot re-cache the market as active.
+    if let Err(e) = state
+        .networks
+        .primary()
+        .set_pending_override(market_id, payload.winning_outcome, &confirmed.hash)
+        .await
+    {
+        tracing::warn!(market_id, error = %e, "resolved on-chain but pending override not written");
+    }
+
+    // 4. Invalidate only the keys affected by this market's resolution via tag.
     let tag = InvalidationTag::MarketResolved {
         market_id,
         network: state.config.network_name().to_owned(),
         featured_limit: state.config.featured_limit,
     };
-    let invalidated = state.cache.invalidate_tag(&tag).await.map_err(into_api_error)?;
+    let invalidated = state.cache.invalidate_tag(&tag).await.map_err(into_api_error)?;
+
+    state
+        .metrics
+        .observe_invalidation("market_resolve", invalidated);
+
+    tracing::info!(market_id, tx_hash = %confirmed.hash, invalidated, "market resolved and cache invalidated");
+
+    Ok((
+        StatusCode::OK,
+        Json(ResolveMarketResult {
+            tx_hash: confirmed.hash,
+            ledger: confirmed.ledger,
+            invalidated_keys: invalidated,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct OracleResultRequest {
+    /// Winning outcome index (0-based) as observed by the keeper.
+    pub outcome: u32,
+    /// Where the result came from (feed name, URL, ...). Stored for audit.
+    pub source: String,
+    /// Keeper's confidence in basis points, 0–10000. Stored for audit.
+    pub confidence_bps: u32,
+}
+
+impl OracleResultRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        let source = self.source.trim();
+        if source.is_empty() || source.chars().count() > 200 {
+            return Err(ApiError::bad_request("source must be 1-200 characters"));
+        }
+        if self.confidence_bps > 10_000 {
+            return Err(ApiError::bad_request("confidence_bps must be between 0 and 10000"));
+        }
+        if i32::try_from(self.outcome).is_err() {
+            return Err(ApiError::bad_request("outcome is out of range"));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OracleResultResponse {
+    /// `false` when the same outcome was already recorded and nothing was sent.
+    pub submitted: bool,
+    /// Whether `attempt_oracle_resolution` was invoked by this request.
+    pub resolution_triggered: bool,
+    pub submission: OracleSubmission,
+}
+
+/// Push a keeper-observed result to the contract.
+///
+/// 1. Claim the live `oracle_submissions` row for (market, `ORACLE_ID`). The
+///    same outcome again returns the existing row (200, `submitted: false`);
+///    a different outcome is a 409.
+/// 2. Invoke `set_oracle_result` and record the transaction on the row. On
+///    failure the row is marked `failed`, which frees the slot for a retry.
+/// 3. When the market's `ends_at` has passed, invoke
+///    `attempt_oracle_resolution`. Its failure (e.g. the on-chain deadline
+///    differs) is recorded on the row, not returned: the result itself landed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/oracle/{market_id}/result",
+    tag = "markets",
+    params(
+        ("market_id" = i64, Path, description = "Market ID"),
+    ),
+    request_body = OracleResultRequest,
+    responses(
+        (status = 200, description = "Result recorded on-chain, or an identical submission already exists", body = OracleResultResponse),
+        (status = 400, description = "Bad request", body = ApiError),
+        (status = 409, description = "A different outcome is already recorded for this market", body = ApiError),
+        (status = 422, description = "Simulation failed or the transaction was rejected", body = ApiError),
+        (status = 503, description = "No oracle signer configured", body = ApiError),
+        (status = 504, description = "Submitted but not confirmed in time", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn oracle_submit_result(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<i64>,
+    Json(body): Json<OracleResultRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    body.validate()?;
+    let chain_market_id = u64::try_from(market_id)
+        .map_err(|_| ApiError::bad_request("market_id must be non-negative"))?;
+    let oracle_id = state.config.oracle_id;
+
+    // 1. Claim the idempotency slot.
+    let claim = state
+        .db
+        .oracle_submission_claim(
+            market_id,
+            oracle_id as i32,
+            body.outcome as i32,
+            body.source.trim(),
+            body.confidence_bps as i32,
+        )
+        .await
+        .map_err(into_api_error)?;
+    let submission = match claim {
+        SubmissionClaim::New(submission) => submission,
+        SubmissionClaim::Duplicate(existing) => {
+            return Ok((
+                StatusCode::OK,
+                Json(OracleResultResponse {
+                    submitted: false,
+                    resolution_triggered: false,
+                    submission: existing,
+                }),
+            ));
+        }
+        SubmissionClaim::Conflict(existing) => {
+            return Err(ApiError::conflict(format!(
+                "market {market_id} already has oracle outcome {} (submission {}, {})",
+                existing.outcome,
+                existing.id,
+                existing.status.label()
+            )));
+        }
+    };
+
+    // 2. Write the result on-chain.
+    let mut submission = match state
+        .networks
+        .primary()
+        .set_oracle_result_onchain(chain_market_id, oracle_id, body.outcome)
+        .await
+    {
+        Ok(confirmed) => state
+            .db
+            .oracle_submission_finish(
+                submission.id,
+                OracleSubmissionStatus::Confirmed,
+                Some(&confirmed.hash),
+                None,
+            )
+            .await
+            .map_err(into_api_error)?,
+        Err(err) => {
+            // A timed-out transaction may still land; its hash is kept so the
+            // row can be reconciled, but the slot is released for a retry.
+            let hash = match &err {
+                ContractCallError::Rejected { hash, .. } | ContractCallError::Timeout { hash } => {
+                    Some(hash.clone())
+                }
+                _ => None,
+            };
+            if let Err(e) = state
+                .db
+                .oracle_submission_finish(
+                    submission.id,
+                    OracleSubmissionStatus::Failed,
+                    hash.as_deref(),
+                    Some(&err.to_string()),
+                )
+                .await
+            {
+                tracing::error!(submission_id = submission.id, error = %e, "failed to record oracle submission failure");
+            }
+            return Err(contract_call_error(err));
+        }
+    };
 
-    state
-        .metrics
-        .observe_invalidation("market_resolve", invalidated);
+    // 3. Trigger resolution once the market has closed.
+    let deadline_passed = match state.db.market_detail(market_id).await {
+        Ok(Some(market)) => market.ends_at <= chrono::Utc::now(),
+        Ok(None) => false,
+        Err(e) => {
+            tracing::warn!(market_id, error = %e, "oracle result: market lookup failed; not resolving");
+            false
+        }
+    };
+
+    if deadline_passed {
+        let (hash, error) = match state
+            .networks
+            .primary()
+            .attempt_oracle_resolution_onchain(chain_market_id)
+            .await
+        {
+            Ok(confirmed) => (Some(confirmed.hash), None),
+            Err(e) => {
+                tracing::warn!(market_id, error = %e, "attempt_oracle_resolution failed");
+                (None, Some(e.to_string()))
+            }
+        };
+        submission = state
+            .db
+            .oracle_submission_set_resolution(submission.id, hash.as_deref(), error.as_deref())
+            .await
+            .map_err(into_api_error)?;
 
-    tracing::info!(market_id, invalidated, "market resolved and cache invalidated");
+        if hash.is_some() {
+            let tag = InvalidationTag::MarketResolved {
+                market_id,
+                network: state.config.network_name().to_owned(),
+                featured_limit: state.config.featured_limit,
+            };
+            let invalidated = state.cache.invalidate_tag(&tag).await.map_err(into_api_error)?;
+            state
+                .metrics
+                .observe_invalidation("oracle_resolution", invalidated);
+        }
+    }
+
+    tracing::info!(
+        market_id,
+        outcome = body.outcome,
+        tx_hash = submission.tx_hash.as_deref().unwrap_or(""),
+        resolution_triggered = deadline_passed,
+        "oracle result submitted"
+    );
 
     Ok((
         StatusCode::OK,
-        Json(InvalidationResult {
-            invalidated_keys: invalidated,
+        Json(OracleResultResponse {
+            submitted: true,
+            resolution_triggered: deadline_passed,
+            submission,
         }),
     ))
 }
@@ -1033,16 +5231,18 @@ pub async fn metrics(State(state): State<Arc<AppState>>) -> Result<impl IntoResp
     get,
     path = "/api/v1/blockchain/health",
     tag = "blockchain",
+    params(
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
     responses(
         (status = 200, description = "Blockchain node is healthy"),
         (status = 503, description = "Blockchain node is degraded or unreachable"),
     )
 )]
 pub async fn blockchain_health(
-    State(state): State<Arc<AppState>>,
+    Network(client): Network,
 ) -> Result<impl IntoResponse, ApiError> {
-    let data = state
-        .blockchain
+    let data = client
         .health_check_cached()
         .await
         .map_err(into_api_error)?;
@@ -1053,27 +5253,182 @@ pub async fn blockchain_health(
     Ok((status_code, Json(data)))
 }
 
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct ProtocolStateQuery {
+    /// Comma-separated market ids to report betting state for (at most 50).
+    pub markets: Option<String>,
+    /// Include the latest circuit breaker transitions.
+    #[serde(default)]
+    pub include_history: bool,
+    /// Transitions to include. Default 20, capped at 100.
+    pub history_limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/blockchain/protocol-state",
+    tag = "blockchain",
+    params(
+        ProtocolStateQuery,
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker, per-market betting, guardian removal and upgrade state", body = ProtocolStateView),
+        (status = 400, description = "Malformed or too many market ids", body = ApiError),
+        (status = 422, description = "The contract rejected a getter (`SIMULATION_FAILED`)", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
+    )
+)]
+pub async fn blockchain_protocol_state(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Query(query): Query<ProtocolStateQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let market_ids = query
+        .markets
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(str::parse::<i64>)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ApiError::bad_request("markets must be comma-separated market ids"))?;
+    if market_ids.len() > protocol_state::MAX_MARKETS {
+        return Err(ApiError::bad_request(format!(
+            "at most {} market ids per request",
+            protocol_state::MAX_MARKETS
+        )));
+    }
+
+    let protocol = client
+        .protocol_state_cached()
+        .await
+        .map_err(|err| match err.downcast::<ContractCallError>() {
+            Ok(err) => contract_call_error(err),
+            Err(err) => into_api_error(err),
+        })?;
+
+    let chain_ids = market_ids::resolve_many(&state.db, &client, &market_ids)
+        .await
+        .map_err(into_api_error)?;
+    let markets = read_mapped_many(&client, &market_ids, &chain_ids, |ids| {
+        let client = &client;
+        async move { client.market_data_many(&ids).await }
+    })
+    .await
+    .into_iter()
+    .zip(&market_ids)
+        .map(|(data, &market_id)| {
+            let status = data.ok().and_then(|data| data.status);
+            MarketBettingState::new(market_id, status, protocol.circuit_breaker)
+        })
+        .collect();
+
+    let history = if query.include_history {
+        let limit = query
+            .history_limit
+            .unwrap_or(protocol_state::DEFAULT_HISTORY_LIMIT)
+            .clamp(1, protocol_state::MAX_HISTORY_LIMIT);
+        Some(
+            state
+                .db
+                .protocol_state_changes_recent(client.network(), limit)
+                .await
+                .map_err(into_api_error)?,
+        )
+    } else {
+        None
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(ProtocolStateView {
+            state: protocol,
+            markets,
+            history,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct ContractCallQuery {
+    /// Comma-separated arguments in the order of the function's signature.
+    /// Addresses are `G...` or `C...` strkeys.
+    pub args: Option<String>,
+}
+
+/// Simulate a read-only contract getter from the `CONTRACT_READ_FUNCTIONS`
+/// allowlist and return its result as JSON.
+#[utoipa::path(
+    get,
+    path = "/api/v1/blockchain/call/{fn_name}",
+    tag = "blockchain",
+    params(
+        ("fn_name" = String, Path, description = "Contract function on the allowlist"),
+        ContractCallQuery,
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Simulated return value, cached for the function's TTL", body = ContractRead),
+        (status = 404, description = "Function is not on the allowlist", body = ApiError),
+        (status = 422, description = "Arguments do not match the signature (`INVALID_ARGS`, expected signature in `details`) or the contract rejected the call (`SIMULATION_FAILED`)", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
+    )
+)]
+pub async fn blockchain_contract_call(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Path(fn_name): Path<String>,
+    Query(query): Query<ContractCallQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let function = state
+        .config
+        .contract_read_functions
+        .iter()
+        .find(|f| f.name == fn_name)
+        .ok_or_else(|| ApiError::not_found(format!("{fn_name} is not an allowed contract read")))?;
+    let (raw_args, args) = function.parse_args(query.args.as_deref()).map_err(|e| {
+        ApiError::unprocessable("INVALID_ARGS", e).with_details(serde_json::json!({ "signature": function.signature() }))
+    })?;
+
+    let (read, _) = client
+        .contract_read_cached(function, raw_args, args)
+        .await
+        .map_err(|err| match err.downcast::<ContractCallError>() {
+            Ok(err) => contract_call_error(err),
+            Err(err) => into_api_error(err),
+        })?;
+    Ok((StatusCode::OK, Json(read)))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/blockchain/markets/{market_id}",
     tag = "blockchain",
     params(
         ("market_id" = i64, Path, description = "Market database ID"),
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
     ),
     responses(
         (status = 200, description = "On-chain market data"),
         (status = 500, description = "Blockchain query failed", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
     )
 )]
 pub async fn blockchain_market_data(
     State(state): State<Arc<AppState>>,
+    Network(client): Network,
     Path(market_id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let data = state
-        .blockchain
-        .market_data_cached(market_id)
+    let chain_market_id = market_ids::resolve(&state.db, &client, market_id)
         .await
         .map_err(into_api_error)?;
+    let mut data = client
+        .market_data_cached(chain_market_id)
+        .await
+        .map_err(into_api_error)?;
+    data.market_id = market_id;
     Ok((StatusCode::OK, Json(data)))
 }
 
@@ -1081,16 +5436,19 @@ pub async fn blockchain_market_data(
     get,
     path = "/api/v1/blockchain/stats",
     tag = "blockchain",
+    params(
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
     responses(
-        (status = 200, description = "Platform-wide blockchain statistics"),
+        (status = 200, description = "Platform-wide blockchain statistics; `source` is `stale_fallback` when served from the newest derived snapshot"),
         (status = 500, description = "Blockchain query failed", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
     )
 )]
 pub async fn blockchain_platform_stats(
-    State(state): State<Arc<AppState>>,
+    Network(client): Network,
 ) -> Result<impl IntoResponse, ApiError> {
-    let data = state
-        .blockchain
+    let data = client
         .platform_statistics_cached()
         .await
         .map_err(into_api_error)?;
@@ -1104,16 +5462,19 @@ pub async fn blockchain_platform_stats(
     params(
         ("user" = String, Path, description = "Stellar account address"),
         PaginationQuery,
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
     ),
     responses(
         (status = 200, description = "Paginated list of user bets"),
+        (status = 422, description = "limit out of range", body = ApiError),
         (status = 500, description = "Blockchain query failed", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
     )
 )]
 pub async fn blockchain_user_bets(
-    State(state): State<Arc<AppState>>,
+    Network(client): Network,
     Path(user): Path<String>,
-    Query(query): Query<PaginationQuery>,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
     let page_size = query.limit();
     // cursor encodes the page number (0-based)
@@ -1124,13 +5485,12 @@ pub async fn blockchain_user_bets(
         .unwrap_or(0)
         .max(0);
 
-    let page_data = state
-        .blockchain
-        .user_bets_page(&user, page, page_size.into())
+    let page_data = client
+        .user_bets_page(&user, page, page_size)
         .await
         .map_err(into_api_error)?;
 
-    let has_more = (page + 1) * (page_size as i64) < page_data.total;
+    let has_more = (page + 1) * page_size < page_data.total;
     let next_cursor = if has_more {
         Some((page + 1).to_string())
     } else {
@@ -1140,82 +5500,528 @@ pub async fn blockchain_user_bets(
     let paginated = PaginatedResponse::new(
         page_data.items,
         next_cursor,
-        page_size,
+        page_size as u32,
         has_more,
     );
 
-    Ok((StatusCode::OK, Json(paginated)))
+    Ok((StatusCode::OK, Json(paginated)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/blockchain/oracle/{market_id}",
+    tag = "blockchain",
+    params(
+        ("market_id" = i64, Path, description = "Market database ID"),
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Oracle resolution result for the market"),
+        (status = 500, description = "Blockchain query failed", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
+    )
+)]
+pub async fn blockchain_oracle_result(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Path(market_id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let chain_market_id = market_ids::resolve(&state.db, &client, market_id)
+        .await
+        .map_err(into_api_error)?;
+    let mut data = client
+        .oracle_result_cached(chain_market_id)
+        .await
+        .map_err(into_api_error)?;
+    data.market_id = market_id;
+    Ok((StatusCode::OK, Json(data)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/blockchain/tx/{tx_hash}",
+    tag = "blockchain",
+    params(
+        ("tx_hash" = String, Path, description = "Stellar transaction hash"),
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Transaction status"),
+        (status = 500, description = "Blockchain query failed", body = ApiError),
+        (status = 502, description = "Soroban RPC unavailable (`UPSTREAM_UNAVAILABLE`)", body = ApiError),
+    )
+)]
+pub async fn blockchain_tx_status(
+    Network(client): Network,
+    Path(tx_hash): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let data = watched_tx_status(&client, &tx_hash).await?;
+    Ok((StatusCode::OK, Json(data)))
+}
+
+/// Register `tx_hash` with the transaction monitor, then return its status.
+async fn watched_tx_status(
+    client: &BlockchainClient,
+    tx_hash: &str,
+) -> Result<crate::blockchain::TransactionStatus, ApiError> {
+    use crate::blockchain::WatchTxError;
+
+    match client.watch_transaction(tx_hash).await {
+        Ok(()) => {}
+        Err(WatchTxError::AlreadyWatched) => {
+            // Idempotent: the hash is already registered.  Continue to return
+            // the current status so the caller gets a useful response.
+        }
+        Err(WatchTxError::CapReached) => {
+            return Err(ApiError::service_unavailable(
+                "Transaction watch map is at capacity. \
+                 Too many concurrent transactions are being monitored. \
+                 Please retry later.",
+            ));
+        }
+    }
+
+    client
+        .transaction_status_cached(tx_hash)
+        .await
+        .map_err(into_api_error)
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct TxWatchRequest {
+    /// `https` URL that receives the signed finalization payload.
+    pub callback_url: Option<String>,
+    /// Address emailed once the transaction finalizes.
+    pub email: Option<String>,
+}
+
+impl TxWatchRequest {
+    /// Normalised (callback URL, email), at least one of them set, or a 400.
+    fn validate(&self) -> Result<(Option<String>, Option<String>), ApiError> {
+        let callback_url = match self.callback_url.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(raw) => Some(tx_watch::check_callback_url(raw).map_err(ApiError::bad_request)?),
+        };
+        let email = match self.email.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(raw) => Some(
+                normalized_email(raw)
+                    .ok_or_else(|| ApiError::bad_request("email must be a valid, non-disposable address"))?,
+            ),
+        };
+        if callback_url.is_none() && email.is_none() {
+            return Err(ApiError::bad_request("callback_url or email is required"));
+        }
+        Ok((callback_url, email))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct TxWatchResponse {
+    pub subscription: TxSubscription,
+    /// Key for verifying `X-PredictIQ-Signature` on webhook deliveries.
+    /// Returned only here.
+    pub secret: String,
+}
+
+/// Be notified once when a transaction on the network named by `X-Network`
+/// reaches a final status: a signed POST to `callback_url`, an email, or
+/// both. The hash is watched as by `GET /api/v1/blockchain/tx/{tx_hash}`;
+/// subscribers to the same hash share one poll. Subscriptions expire after
+/// 24 hours.
+#[utoipa::path(
+    post,
+    path = "/api/v1/blockchain/tx/{tx_hash}/watch",
+    tag = "blockchain",
+    params(
+        ("tx_hash" = String, Path, description = "Stellar transaction hash (64 hex characters)"),
+        ("X-Network" = Option<String>, Header, description = "Network the transaction was submitted to; defaults to the primary network"),
+    ),
+    request_body = TxWatchRequest,
+    responses(
+        (status = 201, description = "Subscription created", body = TxWatchResponse),
+        (status = 400, description = "Invalid hash, callback URL, or email, or neither given", body = ApiError),
+        (status = 503, description = "Transaction watch map is at capacity", body = ApiError),
+    )
+)]
+pub async fn blockchain_tx_watch(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    Path(tx_hash): Path<String>,
+    Json(body): Json<TxWatchRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    use crate::blockchain::WatchTxError;
+
+    if tx_hash.len() != 64 || !tx_hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(ApiError::bad_request("tx_hash must be 64 hex characters"));
+    }
+    let (callback_url, email) = body.validate()?;
+    if let Some(email) = email.as_deref() {
+        reject_refused_email(&state, email).await?;
+    }
+
+    // Stored before the hash is watched, so a monitor that finalizes the hash
+    // in between still finds the subscription when it claims.
+    let secret = tx_watch::new_secret();
+    let subscription = state
+        .db
+        .tx_watch_subscription_insert(
+            client.network(),
+            &tx_hash,
+            callback_url.as_deref(),
+            email.as_deref(),
+            &secret,
+            chrono::Utc::now() + chrono::Duration::hours(tx_watch::SUBSCRIPTION_TTL_HOURS),
+        )
+        .await
+        .map_err(into_api_error)?;
+
+    match client.watch_transaction(&tx_hash).await {
+        Ok(()) | Err(WatchTxError::AlreadyWatched) => {}
+        Err(WatchTxError::CapReached) => {
+            if let Err(e) = state.db.tx_watch_subscription_delete(subscription.id).await {
+                tracing::warn!(subscription_id = %subscription.id, error = %e, "failed to remove unwatched subscription");
+            }
+            return Err(ApiError::service_unavailable(
+                "Transaction watch map is at capacity. \
+                 Too many concurrent transactions are being monitored. \
+                 Please retry later.",
+            ));
+        }
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TxWatchResponse { subscription, secret }),
+    ))
+}
+
+/// Most queries accepted by `POST /api/v1/blockchain/batch`.
+pub const BLOCKCHAIN_BATCH_MAX: usize = 25;
+
+/// One lookup in a blockchain batch, answered as the matching `GET` route
+/// would be.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlockchainQuery {
+    /// `GET /api/v1/blockchain/markets/{id}`
+    Market { id: i64 },
+    /// `GET /api/v1/blockchain/oracle/{id}`
+    Oracle { id: i64 },
+    /// `GET /api/v1/blockchain/tx/{hash}`; also watches the transaction.
+    TxStatus { hash: String },
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BlockchainBatchRequest {
+    pub queries: Vec<BlockchainQuery>,
+}
+
+/// The outcome of one query: `data` on success, otherwise `error` with the
+/// envelope the single-item route would have returned. `status` is that
+/// route's HTTP status.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BlockchainBatchItem {
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ApiError>,
+}
+
+impl BlockchainBatchItem {
+    fn from_result<T: Serialize>(result: Result<T, ApiError>) -> Self {
+        let result = result
+            .and_then(|data| serde_json::to_value(data).map_err(|e| ApiError::internal(e.into())));
+        match result {
+            Ok(data) => Self {
+                status: StatusCode::OK.as_u16(),
+                data: Some(data),
+                error: None,
+            },
+            Err(error) => Self {
+                status: error.status.as_u16(),
+                data: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Results in the same order as the request's `queries`.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BlockchainBatchResponse {
+    pub results: Vec<BlockchainBatchItem>,
+}
+
+/// Run up to [`BLOCKCHAIN_BATCH_MAX`] lookups in one request. Items fail
+/// independently; the response is 200 whenever the batch itself is valid.
+/// The batch costs one request per query against the caller's per-IP rate
+/// limit, and market/oracle items still count toward each market's limit.
+#[utoipa::path(
+    post,
+    path = "/api/v1/blockchain/batch",
+    tag = "blockchain",
+    request_body = BlockchainBatchRequest,
+    params(
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
+    responses(
+        (status = 200, description = "Per-query results, in request order", body = BlockchainBatchResponse),
+        (status = 400, description = "Empty batch", body = ApiError),
+        (status = 422, description = "More than 25 queries (`BATCH_TOO_LARGE`)", body = ApiError),
+        (status = 429, description = "Batch exceeds the remaining rate limit", body = ApiError),
+    )
+)]
+pub async fn blockchain_batch(
+    State(state): State<Arc<AppState>>,
+    Network(client): Network,
+    headers: HeaderMap,
+    connect_info: Option<axum::extract::ConnectInfo<std::net::SocketAddr>>,
+    Json(body): Json<BlockchainBatchRequest>,
+) -> Result<Response, ApiError> {
+    if body.queries.is_empty() {
+        return Err(ApiError::bad_request("queries must not be empty"));
+    }
+    if body.queries.len() > BLOCKCHAIN_BATCH_MAX {
+        return Err(ApiError::unprocessable(
+            "BATCH_TOO_LARGE",
+            format!("A batch may contain at most {BLOCKCHAIN_BATCH_MAX} queries."),
+        ));
+    }
+
+    let ip = client_ip(&state.config, &headers, connect_info.as_ref());
+    let extra = body.queries.len() as u64 - 1;
+    if let Err(rejected) = crate::rate_limit::charge_global_rate_limit(&state, &ip, extra).await
+    {
+        return Ok(rejected);
+    }
+
+    // Per-market limits first; the allowed market and oracle items are then
+    // read with one multi-key cache lookup per kind.
+    let market_limit = crate::rate_limit::ResourceRateLimitState::from_app_state(&state);
+    let allowed = join_all(body.queries.iter().map(|query| {
+        let market_limit = &market_limit;
+        async move {
+            match query {
+                BlockchainQuery::Market { id } | BlockchainQuery::Oracle { id } => {
+                    batch_market_allowed(market_limit, *id).await
+                }
+                BlockchainQuery::TxStatus { .. } => Ok(()),
+            }
+        }
+    }))
+    .await;
+
+    let allowed_ids = |oracle: bool| -> Vec<i64> {
+        body.queries
+            .iter()
+            .zip(&allowed)
+            .filter_map(|(query, allowed)| match (query, allowed) {
+                (BlockchainQuery::Market { id }, Ok(())) if !oracle => Some(*id),
+                (BlockchainQuery::Oracle { id }, Ok(())) if oracle => Some(*id),
+                _ => None,
+            })
+            .collect()
+    };
+    let (market_ids, oracle_ids) = (allowed_ids(false), allowed_ids(true));
+    let (market_chain_ids, oracle_chain_ids) = tokio::try_join!(
+        market_ids::resolve_many(&state.db, &client, &market_ids),
+        market_ids::resolve_many(&state.db, &client, &oracle_ids),
+    )
+    .map_err(into_api_error)?;
+    let tx_lookups = body.queries.iter().filter_map(|query| match query {
+        BlockchainQuery::TxStatus { hash } if !hash.trim().is_empty() => {
+            Some(watched_tx_status(&client, hash.trim()))
+        }
+        _ => None,
+    });
+    let (markets, oracles, tx_statuses) = tokio::join!(
+        read_mapped_many(&client, &market_ids, &market_chain_ids, |ids| {
+            let client = &client;
+            async move { client.market_data_many(&ids).await }
+        }),
+        read_mapped_many(&client, &oracle_ids, &oracle_chain_ids, |ids| {
+            let client = &client;
+            async move { client.oracle_result_many(&ids).await }
+        }),
+        join_all(tx_lookups),
+    );
+
+    let (mut markets, mut oracles, mut tx_statuses) =
+        (markets.into_iter(), oracles.into_iter(), tx_statuses.into_iter());
+    let results = body
+        .queries
+        .iter()
+        .zip(allowed)
+        .map(|(query, allowed)| match (query, allowed) {
+            (BlockchainQuery::TxStatus { hash }, _) if hash.trim().is_empty() => {
+                BlockchainBatchItem::from_result::<()>(Err(ApiError::bad_request(
+                    "hash must not be empty",
+                )))
+            }
+            (BlockchainQuery::TxStatus { .. }, _) => {
+                BlockchainBatchItem::from_result(tx_statuses.next().expect("one status per tx query"))
+            }
+            (_, Err(rejected)) => BlockchainBatchItem::from_result::<()>(Err(rejected)),
+            (BlockchainQuery::Market { id }, Ok(())) => BlockchainBatchItem::from_result(
+                markets
+                    .next()
+                    .expect("one result per allowed market")
+                    .map(|mut data| {
+                        data.market_id = *id;
+                        data
+                    })
+                    .map_err(into_api_error),
+            ),
+            (BlockchainQuery::Oracle { id }, Ok(())) => BlockchainBatchItem::from_result(
+                oracles
+                    .next()
+                    .expect("one result per allowed oracle")
+                    .map(|mut data| {
+                        data.market_id = *id;
+                        data
+                    })
+                    .map_err(into_api_error),
+            ),
+        })
+        .collect();
+
+    Ok(Json(BlockchainBatchResponse { results }).into_response())
+}
+
+/// The per-market limit `resource_rate_limit_middleware` applies to the
+/// single-item routes, which a batch would otherwise bypass.
+async fn batch_market_allowed(
+    limit: &crate::rate_limit::ResourceRateLimitState,
+    market_id: i64,
+) -> Result<(), ApiError> {
+    let key = format!("market:{market_id}");
+    crate::rate_limit::check_rate_limit(&limit.redis, &limit.config, &key)
+        .await
+        .map(|_| ())
+        .map_err(|retry_after| {
+            ApiError::rate_limited().with_details(serde_json::json!({ "retry_after": retry_after }))
+        })
+}
+
+/// Request body cap for the transaction relay routes: one envelope at the
+/// network's maximum transaction size, base64-encoded, plus JSON framing.
+pub const TX_BODY_LIMIT_BYTES: usize = 192 * 1024;
+
+/// Largest decoded envelope accepted; matches the network's
+/// `tx_max_size_bytes` so oversize envelopes never reach the RPC node.
+const TX_ENVELOPE_MAX_BYTES: usize = 132 * 1024;
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct TxEnvelopeRequest {
+    /// Base64 `TransactionEnvelope` XDR.
+    pub transaction: String,
+}
+
+impl TxEnvelopeRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        use base64::Engine as _;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(self.transaction.trim())
+            .map_err(|_| ApiError::bad_request("transaction must be base64-encoded XDR"))?;
+        if decoded.is_empty() {
+            return Err(ApiError::bad_request("transaction is empty"));
+        }
+        if decoded.len() > TX_ENVELOPE_MAX_BYTES {
+            return Err(ApiError::bad_request(format!(
+                "transaction exceeds {TX_ENVELOPE_MAX_BYTES} bytes"
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/blockchain/oracle/{market_id}",
+    post,
+    path = "/api/v1/tx/simulate",
     tag = "blockchain",
+    request_body = TxEnvelopeRequest,
     params(
-        ("market_id" = i64, Path, description = "Market database ID"),
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
     ),
     responses(
-        (status = 200, description = "Oracle resolution result for the market"),
-        (status = 500, description = "Blockchain query failed", body = ApiError),
-    )
+        (status = 200, description = "Simulation result: fee, footprint, return values", body = TxSimulation),
+        (status = 400, description = "Malformed or oversize envelope", body = ApiError),
+        (status = 422, description = "The node rejected the simulation; message is passed through", body = ApiError),
+    ),
+    security(("api_key" = []))
 )]
-pub async fn blockchain_oracle_result(
-    State(state): State<Arc<AppState>>,
-    Path(market_id): Path<i64>,
+pub async fn tx_simulate(
+    Network(client): Network,
+    Json(body): Json<TxEnvelopeRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let data = state
-        .blockchain
-        .oracle_result_cached(market_id)
+    body.validate()?;
+
+    let simulation = client
+        .simulate_transaction(body.transaction.trim())
         .await
         .map_err(into_api_error)?;
-    Ok((StatusCode::OK, Json(data)))
+
+    if let Some(error) = &simulation.error {
+        return Err(ApiError::unprocessable("SIMULATION_FAILED", error.clone()));
+    }
+
+    Ok((StatusCode::OK, Json(simulation)))
 }
 
 #[utoipa::path(
-    get,
-    path = "/api/v1/blockchain/tx/{tx_hash}",
+    post,
+    path = "/api/v1/tx/submit",
     tag = "blockchain",
+    request_body = TxEnvelopeRequest,
     params(
-        ("tx_hash" = String, Path, description = "Stellar transaction hash"),
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
     ),
     responses(
-        (status = 200, description = "Transaction status"),
-        (status = 500, description = "Blockchain query failed", body = ApiError),
-    )
+        (status = 202, description = "Accepted (PENDING or DUPLICATE); hash registered with the tx monitor", body = TxSubmission),
+        (status = 400, description = "Malformed or oversize envelope", body = ApiError),
+        (status = 422, description = "The network rejected the transaction", body = ApiError),
+        (status = 503, description = "The node asked to retry later", body = ApiError),
+    ),
+    security(("api_key" = []))
 )]
-pub async fn blockchain_tx_status(
-    State(state): State<Arc<AppState>>,
-    Path(tx_hash): Path<String>,
+pub async fn tx_submit(
+    Network(client): Network,
+    Json(body): Json<TxEnvelopeRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    use crate::blockchain::WatchTxError;
-
-    match state.blockchain.watch_transaction(&tx_hash).await {
-        Ok(()) => {}
-        Err(WatchTxError::AlreadyWatched) => {
-            // Idempotent: the hash is already registered.  Continue to return
-            // the current status so the caller gets a useful response.
-        }
-        Err(WatchTxError::CapReached) => {
-            return Err(ApiError::service_unavailable(
-                "Transaction watch map is at capacity. \
-                 Too many concurrent transactions are being monitored. \
-                 Please retry later.",
-            ));
-        }
-    }
+    body.validate()?;
 
-    let data = state
-        .blockchain
-        .transaction_status_cached(&tx_hash)
+    let submission = client
+        .submit_transaction(body.transaction.trim())
         .await
         .map_err(into_api_error)?;
-    Ok((StatusCode::OK, Json(data)))
+
+    match submission.status.as_str() {
+        _ if submission.is_accepted() => Ok((StatusCode::ACCEPTED, Json(submission))),
+        "TRY_AGAIN_LATER" => Err(ApiError::service_unavailable(
+            "The RPC node is congested; resubmit the same envelope later.",
+        )),
+        _ => Err(ApiError::unprocessable(
+            "SUBMISSION_REJECTED",
+            format!(
+                "transaction {} rejected with status {}: {}",
+                submission.hash,
+                submission.status,
+                submission.error_result_xdr.as_deref().unwrap_or("no result XDR"),
+            ),
+        )),
+    }
 }
 
 #[utoipa::path(
     post,
     path = "/api/blockchain/replay",
     tag = "blockchain",
+    params(
+        ("X-Network" = Option<String>, Header, description = "Network to read from; defaults to the primary network"),
+    ),
     responses(
         (status = 200, description = "Replay progress"),
         (status = 500, description = "Replay failed", body = ApiError),
@@ -1223,43 +6029,16 @@ pub async fn blockchain_tx_status(
     security(("api_key" = []))
 )]
 pub async fn blockchain_replay(
-    State(state): State<Arc<AppState>>,
+    Network(client): Network,
     Json(payload): Json<crate::blockchain::ReplayRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let progress = state
-        .blockchain
+    let progress = client
         .replay_events(payload.from_ledger)
         .await
         .map_err(into_api_error)?;
     Ok((StatusCode::OK, Json(progress)))
 }
 
-pub async fn warm_critical_caches(state: Arc<AppState>) -> anyhow::Result<()> {
-    macro_rules! warm {
-        ($name:expr, $fut:expr, $ok:ident, $fail:ident) => {
-            if let Err(e) = $fut.await {
-                $fail += 1;
-                tracing::warn!(endpoint = $name, error = %e, "cache warming failed for endpoint");
-            } else {
-                $ok += 1;
-            }
-        };
-    }
-
-    let (mut succeeded, mut failed) = (0usize, 0usize);
-
-    warm!("db.statistics",             state.db.statistics_cached(),                                                                                                                succeeded, failed);
-    warm!("db.featured_markets",       state.db.featured_markets_cached(state.config.featured_limit),                                                                               succeeded, failed);
-    warm!("blockchain.health",         state.blockchain.health_check_cached(),                                                                                                       succeeded, failed);
-    warm!("blockchain.platform_stats", state.blockchain.platform_statistics_cached(),                                                                                               succeeded, failed);
-    warm!("api.statistics",            async { statistics(State(state.clone())).await.map(|_| ()).map_err(|e| anyhow::anyhow!("{e:?}")) },                                          succeeded, failed);
-    warm!("api.featured_markets",      async { featured_markets(State(state.clone()), Query(PaginationQuery::default())).await.map(|_| ()).map_err(|e| anyhow::anyhow!("{e:?}")) }, succeeded, failed);
-    warm!("api.content",               async { content(State(state.clone()), Query(PaginationQuery::default())).await.map(|_| ()).map_err(|e| anyhow::anyhow!("{e:?}")) },          succeeded, failed);
-
-    tracing::info!(succeeded, failed, total = succeeded + failed, "cache warming complete");
-    Ok(())
-}
-
 // Email service handlers
 
 #[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
@@ -1299,17 +6078,70 @@ pub async fn email_preview(
         "waitlist_confirmation" => serde_json::json!({
             "email": "test@example.com"
         }),
+        "waitlist_invitation" => serde_json::json!({
+            "email": "test@example.com",
+            "signup_url": format!("{}/signup", state.config.base_url)
+        }),
         "contact_form_auto_response" => serde_json::json!({
             "name": "Test User",
             "subject": "Test Subject",
             "message": "This is a test message."
         }),
+        "contact_form_notification" => serde_json::json!({
+            "submission_id": "00000000-0000-0000-0000-000000000000",
+            "name": "Test User",
+            "email": "test@example.com",
+            "subject": "Test Subject",
+            "message": "This is a test message."
+        }),
         "welcome_email" => serde_json::json!({
             "name": "Test User",
             "dashboard_url": format!("{}/dashboard", state.config.base_url),
             "help_url": format!("{}/help", state.config.base_url),
             "unsubscribe_url": format!("{}/api/v1/newsletter/unsubscribe", state.config.base_url)
         }),
+        "market_resolved" => serde_json::json!({
+            "market_title": "Will it rain in London tomorrow?",
+            "event": "resolved",
+            "is_dispute": false,
+            "outcome_label": "Yes",
+            "payout_url": format!("{}/markets/1", state.config.base_url),
+            "unsubscribe_url": format!("{}/api/v1/watches/unsubscribe?token=preview", state.config.base_url)
+        }),
+        "weekly_digest" => serde_json::json!({
+            "week_label": "12 October 2026",
+            "markets": [{
+                "title": "Will it rain in London tomorrow?",
+                "odds": "Yes 62% · No 38%",
+                "url": format!("{}/markets/1", state.config.base_url)
+            }],
+            "resolutions": [{
+                "title": "Will BTC close above $100k this week?",
+                "outcome": "No",
+                "url": format!("{}/markets/2", state.config.base_url)
+            }],
+            "email": "test@example.com",
+            "preferences_url": format!("{}/api/v1/newsletter/preferences?token=preview", state.config.base_url)
+        }),
+        "position_update" => serde_json::json!({
+            "market_title": "Will it rain in London tomorrow?",
+            "is_claim": true,
+            "is_dispute": false,
+            "outcome_label": "Yes",
+            "market_url": format!("{}/markets/1", state.config.base_url),
+            "unsubscribe_url": format!("{}/api/v1/notifications/unsubscribe?token=preview", state.config.base_url)
+        }),
+        "notification_email_confirmation" => serde_json::json!({
+            "confirm_url": format!("{}/api/v1/notifications/confirm?token=test-token-123", state.config.base_url),
+            "email": "test@example.com"
+        }),
+        "tx_finalized" => serde_json::json!({
+            "tx_hash": "3389e9f0f1a65f19736cacf544c2e825313e8447f569233bb8db39aa607c8889",
+            "network": "testnet",
+            "status": "SUCCESS",
+            "is_success": true,
+            "ledger": "51234"
+        }),
         _ => serde_json::json!({}),
     };
 
@@ -1354,105 +6186,428 @@ pub async fn email_send_test(
 
 #[utoipa::path(
     get,
-    path = "/api/v1/email/analytics",
+    path = "/api/v1/email/analytics",
+    tag = "email",
+    params(EmailAnalyticsQuery),
+    responses(
+        (status = 200, description = "Email delivery analytics"),
+        (status = 500, description = "Query failed", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn email_analytics(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EmailAnalyticsQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let days = query.days.unwrap_or(30).clamp(1, 365);
+    let analytics = state
+        .db
+        .email_get_analytics(query.template_name.as_deref(), days)
+        .await
+        .map_err(into_api_error)?;
+
+    Ok((StatusCode::OK, Json(analytics)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/email/queue/stats",
+    tag = "email",
+    responses(
+        (status = 200, description = "Email queue statistics"),
+        (status = 500, description = "Query failed", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn email_queue_stats(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let stats = state
+        .email_queue
+        .get_stats()
+        .await
+        .map_err(into_api_error)?;
+
+    state.metrics.set_dlq_size(stats.dead_letter as i64);
+    state.metrics.set_email_queue_depth(stats.pending as i64);
+
+    Ok((StatusCode::OK, Json(stats)))
+}
+
+/// Jobs that exhausted `max_attempts`, most recently failed first. The cursor
+/// is an offset into that order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/email/dead-letter",
+    tag = "email",
+    params(PaginationQuery),
+    responses(
+        (status = 200, description = "Dead-lettered email jobs with their last error"),
+        (status = 422, description = "limit out of range", body = ApiError),
+        (status = 500, description = "Query failed", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn email_dead_letter_list(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = query.limit();
+    let offset = query
+        .cursor()
+        .map(|c| c.parse::<i64>().map_err(|_| ApiError::bad_request("cursor must be an offset")))
+        .transpose()?
+        .unwrap_or(0)
+        .max(0);
+
+    // One extra row tells us whether another page exists.
+    let mut jobs = state
+        .email_queue
+        .list_dead_letter(limit + 1, offset)
+        .await
+        .map_err(into_api_error)?;
+    let has_more = jobs.len() as i64 > limit;
+    jobs.truncate(limit as usize);
+    let next_cursor = has_more.then(|| (offset + limit).to_string());
+
+    Ok((
+        StatusCode::OK,
+        Json(PaginatedResponse::new(jobs, next_cursor, limit as u32, has_more)),
+    ))
+}
+
+/// Return a dead-lettered job to the queue with a fresh attempt budget. It
+/// becomes due after a short cooling-off delay.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/email/dead-letter/{job_id}/retry",
+    tag = "email",
+    params(
+        ("job_id" = String, Path, description = "Dead-lettered job UUID"),
+    ),
+    responses(
+        (status = 200, description = "Job requeued"),
+        (status = 404, description = "Job is not dead-lettered", body = ApiError),
+        (status = 500, description = "Requeue failed", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn email_dead_letter_retry(
+    State(state): State<Arc<AppState>>,
+    Path(job_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ApiError> {
+    let job = state
+        .email_queue
+        .requeue_dead_letter(job_id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found(format!("Job {job_id} is not dead-lettered")))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "requeued": true,
+            "job_id": job.id,
+            "scheduled_at": job.scheduled_at,
+        })),
+    ))
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct BlockedDomainCreateRequest {
+    /// A domain such as `mailinator.com`, in which `*` matches any run of
+    /// characters: `*.tempmail.*` blocks `x.tempmail.org` but not
+    /// `tempmail.org`. Stored lowercased.
+    pub pattern: String,
+    /// Why the domain is blocked, for other admins.
+    pub note: Option<String>,
+}
+
+async fn invalidate_blocked_email_domains(state: &AppState) -> Result<(), ApiError> {
+    let invalidated = state
+        .cache
+        .invalidate_tag(&InvalidationTag::BlockedEmailDomainsChanged)
+        .await
+        .map_err(into_api_error)?;
+    state.metrics.observe_invalidation("blocked_email_domain_write", invalidated);
+    Ok(())
+}
+
+/// Every blocked signup email domain pattern, by pattern.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/email/blocked-domains",
+    tag = "email",
+    responses(
+        (status = 200, description = "Blocked domain patterns", body = [BlockedDomain]),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn blocked_domains_list(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, ApiError> {
+    let domains = state.db.blocked_email_domains().await.map_err(into_api_error)?;
+    Ok((StatusCode::OK, Json(domains)))
+}
+
+/// Block a domain pattern for newsletter, waitlist, contact, and
+/// notification emails. Signups see it as soon as this returns.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/email/blocked-domains",
+    tag = "email",
+    request_body = BlockedDomainCreateRequest,
+    responses(
+        (status = 201, description = "Pattern blocked", body = BlockedDomain),
+        (status = 400, description = "Invalid pattern", body = ApiError),
+        (status = 409, description = "Pattern already blocked", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn blocked_domain_create(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<BlockedDomainCreateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let pattern = signup_guard::normalize_pattern(&payload.pattern).map_err(ApiError::bad_request)?;
+    let note = payload.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+
+    let created = state
+        .db
+        .blocked_email_domain_create(&pattern, note)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::conflict(format!("{pattern} is already blocked")))?;
+    invalidate_blocked_email_domains(&state).await?;
+    tracing::info!(pattern = %created.pattern, "email domain blocked");
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// Unblock a domain pattern.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/email/blocked-domains/{id}",
+    tag = "email",
+    params(("id" = i64, Path, description = "Blocked domain ID")),
+    responses(
+        (status = 204, description = "Pattern unblocked"),
+        (status = 404, description = "Unknown blocked domain", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn blocked_domain_delete(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !state.db.blocked_email_domain_delete(id).await.map_err(into_api_error)? {
+        return Err(ApiError::not_found(format!("blocked domain {id} not found")));
+    }
+    invalidate_blocked_email_domains(&state).await?;
+    tracing::info!(id, "email domain unblocked");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CampaignCreateRequest {
+    /// Admin-facing label; trimmed, 1-200 characters.
+    pub name: String,
+    /// Email template every recipient receives.
+    pub template_name: String,
+    /// Template data shared by every recipient. Each recipient also gets
+    /// `email` and `preferences_url`.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+}
+
+async fn campaign_or_404(state: &AppState, id: i64) -> Result<Campaign, ApiError> {
+    state
+        .db
+        .campaign_get(id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::new(ApiErrorKind::NotFound, "CAMPAIGN_NOT_FOUND", format!("Campaign {id} not found")))
+}
+
+/// Create a draft campaign. The template is rendered once against the payload
+/// and a sample recipient, so a missing variable fails here rather than on
+/// every send.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/campaigns",
+    tag = "email",
+    request_body = CampaignCreateRequest,
+    responses(
+        (status = 201, description = "Draft campaign", body = Campaign),
+        (status = 400, description = "Invalid name, or the template does not render with the payload", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn campaign_create(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CampaignCreateRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let name = campaign::normalize_name(&payload.name).map_err(ApiError::bad_request)?;
+    let data = match payload.payload {
+        serde_json::Value::Null => serde_json::json!({}),
+        data @ serde_json::Value::Object(_) => data,
+        _ => return Err(ApiError::bad_request("payload must be a JSON object")),
+    };
+
+    let sample = campaign::recipient_data(
+        &data,
+        "subscriber@example.com",
+        &format!("{}/api/v1/newsletter/preferences?token=preview", state.config.base_url),
+    );
+    if let Err(e) = state.email_service.preview_email(&payload.template_name, &sample) {
+        return Err(ApiError::new(
+            ApiErrorKind::Validation,
+            "INVALID_CAMPAIGN_TEMPLATE",
+            format!("template {} does not render with this payload: {e}", payload.template_name),
+        ));
+    }
+
+    let created = state
+        .db
+        .campaign_create(&name, &payload.template_name, &data)
+        .await
+        .map_err(into_api_error)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+/// A campaign and its progress counters.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/campaigns/{id}",
     tag = "email",
-    params(EmailAnalyticsQuery),
+    params(("id" = i64, Path, description = "Campaign id")),
     responses(
-        (status = 200, description = "Email delivery analytics"),
-        (status = 500, description = "Query failed", body = ApiError),
+        (status = 200, description = "Campaign", body = Campaign),
+        (status = 404, description = "Unknown campaign", body = ApiError),
     ),
     security(("api_key" = []))
 )]
-pub async fn email_analytics(
+pub async fn campaign_get(
     State(state): State<Arc<AppState>>,
-    Query(query): Query<EmailAnalyticsQuery>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let days = query.days.unwrap_or(30).clamp(1, 365);
-    let analytics = state
-        .db
-        .email_get_analytics(query.template_name.as_deref(), days)
-        .await
-        .map_err(into_api_error)?;
-
-    Ok((StatusCode::OK, Json(analytics)))
+    Ok((StatusCode::OK, Json(campaign_or_404(&state, id).await?)))
 }
 
+/// Start sending a draft. `total_recipients` is the audience at this moment;
+/// batches are enqueued by the `campaign_sender` task.
 #[utoipa::path(
-    get,
-    path = "/api/v1/email/queue/stats",
+    post,
+    path = "/api/v1/admin/campaigns/{id}/launch",
     tag = "email",
+    params(("id" = i64, Path, description = "Campaign id")),
     responses(
-        (status = 200, description = "Email queue statistics"),
-        (status = 500, description = "Query failed", body = ApiError),
+        (status = 200, description = "Campaign, now sending", body = Campaign),
+        (status = 404, description = "Unknown campaign", body = ApiError),
+        (status = 409, description = "Campaign is not a draft", body = ApiError),
     ),
     security(("api_key" = []))
 )]
-pub async fn email_queue_stats(
+pub async fn campaign_launch(
     State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let stats = state
-        .email_queue
-        .get_stats()
-        .await
-        .map_err(into_api_error)?;
-
-    state.metrics.set_dlq_size(stats.dead_letter as i64);
-    state.metrics.set_email_queue_depth(stats.pending as i64);
-
-    Ok((StatusCode::OK, Json(stats)))
+    if let Some(launched) = state.db.campaign_launch(id).await.map_err(into_api_error)? {
+        return Ok((StatusCode::OK, Json(launched)));
+    }
+    let existing = campaign_or_404(&state, id).await?;
+    Err(ApiError::new(
+        ApiErrorKind::Conflict,
+        "CAMPAIGN_NOT_DRAFT",
+        format!("Campaign {id} is {}; only drafts can be launched", existing.status.label()),
+    ))
 }
 
+/// Stop a draft or sending campaign. Jobs already enqueued are still sent.
 #[utoipa::path(
-    get,
-    path = "/api/v1/email/queue/dead-letter",
+    post,
+    path = "/api/v1/admin/campaigns/{id}/cancel",
     tag = "email",
+    params(("id" = i64, Path, description = "Campaign id")),
     responses(
-        (status = 200, description = "List of dead-letter email job IDs"),
-        (status = 500, description = "Query failed", body = ApiError),
+        (status = 200, description = "Campaign, now cancelled", body = Campaign),
+        (status = 404, description = "Unknown campaign", body = ApiError),
+        (status = 409, description = "Campaign already completed or cancelled", body = ApiError),
     ),
     security(("api_key" = []))
 )]
-pub async fn email_dead_letter_list(
+pub async fn campaign_cancel(
     State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
 ) -> Result<impl IntoResponse, ApiError> {
-    let ids = state
-        .email_queue
-        .list_dead_letter()
-        .await
-        .map_err(into_api_error)?;
+    if let Some(cancelled) = state.db.campaign_cancel(id).await.map_err(into_api_error)? {
+        return Ok((StatusCode::OK, Json(cancelled)));
+    }
+    let existing = campaign_or_404(&state, id).await?;
+    Err(ApiError::new(
+        ApiErrorKind::Conflict,
+        "CAMPAIGN_FINISHED",
+        format!("Campaign {id} is already {}", existing.status.label()),
+    ))
+}
 
-    Ok((StatusCode::OK, Json(serde_json::json!({ "jobs": ids, "count": ids.len() }))))
+/// The current week's digest, rendered as subscribers would receive it.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DigestPreview {
+    /// Monday of the week the digest is for.
+    pub week_start: chrono::NaiveDate,
+    /// Whether this week's digest has already been enqueued.
+    pub already_sent: bool,
+    /// An empty digest is recorded but not sent.
+    pub empty: bool,
+    pub subject: String,
+    pub html_content: String,
+    pub text_content: String,
 }
 
+/// Render this week's digest from live data without enqueueing anything.
 #[utoipa::path(
-    post,
-    path = "/api/v1/email/queue/dead-letter/{job_id}/requeue",
+    get,
+    path = "/api/v1/admin/digest/preview",
     tag = "email",
-    params(
-        ("job_id" = String, Path, description = "Dead-letter job UUID"),
-    ),
     responses(
-        (status = 200, description = "Job requeued"),
-        (status = 404, description = "Job not found in dead-letter set", body = ApiError),
-        (status = 500, description = "Requeue failed", body = ApiError),
+        (status = 200, description = "Rendered digest", body = DigestPreview),
+        (status = 500, description = "Template render error", body = ApiError),
     ),
     security(("api_key" = []))
 )]
-pub async fn email_dead_letter_requeue(
+pub async fn digest_preview(
     State(state): State<Arc<AppState>>,
-    Path(job_id): Path<Uuid>,
-) -> Result<impl IntoResponse, ApiError> {
-    let requeued = state
-        .email_queue
-        .requeue_dead_letter(job_id)
+) -> Result<Json<DigestPreview>, ApiError> {
+    let now = chrono::Utc::now();
+    let week_start = digest::week_start(now.date_naive());
+    let content = digest::assemble(&state.db, &state.config.base_url, week_start, now)
+        .await
+        .map_err(into_api_error)?;
+    let already_sent = state
+        .db
+        .digest_week_sent(week_start)
         .await
         .map_err(into_api_error)?;
 
-    if requeued {
-        Ok((StatusCode::OK, Json(serde_json::json!({ "requeued": true, "job_id": job_id }))))
-    } else {
-        Err(ApiError::not_found(format!("Job {job_id} not found in dead-letter set")))
-    }
+    let data = content.template_data(
+        "subscriber@example.com",
+        &format!("{}/api/v1/newsletter/preferences?token=preview", state.config.base_url),
+    );
+    let preview = state
+        .email_service
+        .preview_email(digest::TEMPLATE, &data)
+        .map_err(into_api_error)?;
+
+    Ok(Json(DigestPreview {
+        week_start,
+        already_sent,
+        empty: content.is_empty(),
+        subject: preview.subject,
+        html_content: preview.html_content,
+        text_content: preview.text_content,
+    }))
 }
 
 #[utoipa::path(
@@ -1461,7 +6616,9 @@ pub async fn email_dead_letter_requeue(
     tag = "webhooks",
     responses(
         (status = 200, description = "Events processed"),
-        (status = 400, description = "Invalid signature or payload", body = ApiError),
+        (status = 400, description = "Invalid payload", body = ApiError),
+        (status = 401, description = "Missing, invalid, or stale signature"),
+        (status = 500, description = "At least one event failed to process; SendGrid retries the batch", body = ApiError),
     )
 )]
 pub async fn sendgrid_webhook(
@@ -1471,10 +6628,17 @@ pub async fn sendgrid_webhook(
 ) -> Result<impl IntoResponse, ApiError> {
     sendgrid_webhook_handler(State(Arc::new(state.webhook_handler.clone())), headers, Json(events))
         .await
-        .map_err(|(status, msg)| ApiError {
-            code: "WEBHOOK_ERROR",
-            message: msg,
-            status,
+        .map_err(|(status, msg)| {
+            // Keep the webhook handler's status (SendGrid retries on 5xx only).
+            let kind = if status.is_server_error() {
+                ApiErrorKind::Internal
+            } else {
+                ApiErrorKind::Validation
+            };
+            ApiError {
+                status,
+                ..ApiError::new(kind, "WEBHOOK_ERROR", msg)
+            }
         })
 }
 
@@ -1500,17 +6664,17 @@ pub async fn audit_logs(
     let limit = params.limit.unwrap_or(100).min(1000);
     let offset = params.offset.unwrap_or(0);
     
+    let filter = crate::audit::AuditLogFilter {
+        actor: params.actor,
+        action: params.action,
+        resource_type: params.resource_type,
+        route: None,
+        from,
+        to,
+    };
     let logs = state
         .audit_logger
-        .query(
-            params.actor.as_deref(),
-            params.action.as_deref(),
-            params.resource_type.as_deref(),
-            from,
-            to,
-            limit,
-            offset,
-        )
+        .query(&filter, limit, offset)
         .await
         .map_err(into_api_error)?;
     
@@ -1570,6 +6734,73 @@ pub struct AuditStatisticsQuery {
     pub to: Option<String>,
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AdminAuditQuery {
+    /// Exact actor: an API key label, or `static:<n>` for the n-th key in
+    /// `API_KEYS`.
+    pub actor: Option<String>,
+    /// Route prefix, e.g. `/api/v1/admin/markets`.
+    pub route: Option<String>,
+    /// Inclusive RFC 3339 lower bound.
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Inclusive RFC 3339 upper bound.
+    pub to: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Admin requests recorded by the audit middleware, newest first. The
+/// cursor is an offset into that order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/audit",
+    tag = "audit",
+    params(AdminAuditQuery, PaginationQuery),
+    responses(
+        (status = 200, description = "Audit log entries"),
+        (status = 400, description = "Bad time range or cursor", body = ApiError),
+        (status = 422, description = "limit out of range", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn admin_audit_list(
+    State(state): State<Arc<AppState>>,
+    Query(filter): Query<AdminAuditQuery>,
+    ValidatedQuery(query): ValidatedQuery<PaginationQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from > to {
+            return Err(ApiError::bad_request("from must not be after to"));
+        }
+    }
+    let limit = query.limit();
+    let offset = query
+        .cursor()
+        .map(|c| c.parse::<i64>().map_err(|_| ApiError::bad_request("cursor must be an offset")))
+        .transpose()?
+        .unwrap_or(0)
+        .max(0);
+
+    let filter = crate::audit::AuditLogFilter {
+        actor: filter.actor,
+        route: filter.route,
+        from: filter.from,
+        to: filter.to,
+        ..Default::default()
+    };
+    let mut entries = state
+        .audit_logger
+        .query(&filter, limit + 1, offset)
+        .await
+        .map_err(into_api_error)?;
+    let has_more = entries.len() as i64 > limit;
+    entries.truncate(limit as usize);
+    let next_cursor = has_more.then(|| (offset + limit).to_string());
+
+    Ok((
+        StatusCode::OK,
+        Json(PaginatedResponse::new(entries, next_cursor, limit as u32, has_more)),
+    ))
+}
+
 // ── API key rotation (issue #892) ─────────────────────────────────────────────
 
 /// Request body for POST /api/v1/admin/api-keys/rotate
@@ -1580,6 +6811,10 @@ pub struct RotateApiKeyRequest {
     /// Days the old key remains valid after rotation (overlap window).
     /// Defaults to 7 days when omitted.
     pub overlap_days: Option<u32>,
+    /// Requests the new key may make per calendar month (UTC). When omitted
+    /// the new key inherits the quota of the key it replaces, or is unlimited
+    /// if there is none.
+    pub monthly_quota: Option<i64>,
 }
 
 /// Response body for POST /api/v1/admin/api-keys/rotate
@@ -1594,6 +6829,8 @@ pub struct RotateApiKeyResponse {
     pub old_key_expires_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Label of the key that was rotated out (if any).
     pub old_key_label: Option<String>,
+    /// Monthly quota of the new key; `null` when unlimited.
+    pub monthly_quota: Option<i64>,
 }
 
 /// POST /api/v1/admin/api-keys/rotate
@@ -1619,6 +6856,9 @@ pub async fn rotate_api_key(
     use sha2::{Digest, Sha256};
 
     let overlap_days = body.overlap_days.unwrap_or(7).max(1) as i64;
+    if body.monthly_quota.is_some_and(|q| q < 0) {
+        return Err(ApiError::bad_request("monthly_quota must not be negative"));
+    }
 
     // Generate a new cryptographically random key (256-bit hex string).
     let new_raw_key = {
@@ -1643,6 +6883,7 @@ pub async fn rotate_api_key(
 
     let mut old_expires_at: Option<chrono::DateTime<chrono::Utc>> = None;
     let mut old_label: Option<String> = None;
+    let mut monthly_quota = body.monthly_quota;
 
     let expires_at = chrono::Utc::now() + chrono::Duration::days(overlap_days);
 
@@ -1655,6 +6896,9 @@ pub async fn rotate_api_key(
                 .map_err(into_api_error)?;
             old_expires_at = Some(expires_at);
             old_label = Some(key.label.clone());
+            if body.monthly_quota.is_none() {
+                monthly_quota = key.monthly_quota;
+            }
             tracing::info!(
                 label = %key.label,
                 expires_at = %expires_at,
@@ -1666,7 +6910,7 @@ pub async fn rotate_api_key(
     // Insert the new key.
     state
         .db
-        .api_key_insert(&new_hash, &body.key_label)
+        .api_key_insert(&new_hash, &body.key_label, monthly_quota)
         .await
         .map_err(into_api_error)?;
 
@@ -1679,6 +6923,7 @@ pub async fn rotate_api_key(
             new_key_label: body.key_label,
             old_key_expires_at: old_expires_at,
             old_key_label: old_label,
+            monthly_quota,
         }),
     ))
 }
@@ -1693,6 +6938,8 @@ pub struct ApiKeyListItem {
     /// `true` when the key has an `expires_at` set (it was rotated out and is
     /// in its overlap window).
     pub is_expiring: bool,
+    /// Requests allowed per calendar month (UTC); `null` when unlimited.
+    pub monthly_quota: Option<i64>,
 }
 
 /// GET /api/v1/admin/api-keys
@@ -1716,12 +6963,123 @@ pub async fn list_api_keys(
             created_at: r.created_at,
             expires_at: r.expires_at,
             is_expiring: r.expires_at.is_some(),
+            monthly_quota: r.monthly_quota,
         })
         .collect();
 
     Ok((StatusCode::OK, Json(items)))
 }
 
+/// Bet exposure of one market on the primary network: stake per outcome,
+/// the largest position, top-five concentration and the last hour's inflow
+/// and probability moves. Computed on each request from indexed events.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/risk/markets/{market_id}/exposure",
+    tag = "risk",
+    params(("market_id" = i64, Path, description = "Market id")),
+    responses(
+        (status = 200, description = "Current exposure; zero for a market without bets", body = MarketExposure),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn risk_market_exposure(
+    State(state): State<Arc<AppState>>,
+    Path(market_id): Path<i64>,
+) -> Result<impl IntoResponse, ApiError> {
+    let network = state.config.network_name();
+    let exposure = risk::exposure(&state.db, network, market_id, chrono::Utc::now())
+        .await
+        .map_err(into_api_error)?;
+    Ok(Json(exposure))
+}
+
+/// Markets whose concentration or hourly inflow is over its threshold, as
+/// of the risk task's last check, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/risk/alerts",
+    tag = "risk",
+    responses(
+        (status = 200, description = "Open alerts and the thresholds they were raised against", body = RiskAlertList),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn risk_alerts(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let alerts = state
+        .db
+        .risk_alerts_open(state.config.network_name())
+        .await
+        .map_err(into_api_error)?;
+    Ok(Json(RiskAlertList {
+        alerts,
+        thresholds: RiskThresholds::from_config(&state.config),
+    }))
+}
+
+#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+pub struct ApiKeyUsageQuery {
+    /// Days to return, today included. Default 30, capped at 90.
+    pub days: Option<i64>,
+}
+
+/// Daily request counts for one API key, with its monthly quota and
+/// month-to-date total. Revoked and expired keys are included so their
+/// history stays readable until the key is deleted.
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/keys/{id}/usage",
+    tag = "api-keys",
+    params(("id" = uuid::Uuid, Path, description = "API key id"), ApiKeyUsageQuery),
+    responses(
+        (status = 200, description = "One point per UTC day, oldest first", body = ApiKeyUsage),
+        (status = 404, description = "Unknown API key", body = ApiError),
+    ),
+    security(("api_key" = []))
+)]
+pub async fn api_key_usage(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<ApiKeyUsageQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    use crate::api_key_usage::{usage_series, UsageMeter, DEFAULT_USAGE_DAYS, MAX_USAGE_DAYS};
+
+    let days = query
+        .days
+        .unwrap_or(i64::from(DEFAULT_USAGE_DAYS))
+        .clamp(1, i64::from(MAX_USAGE_DAYS)) as u64;
+    let key = state
+        .db
+        .api_key_get(id)
+        .await
+        .map_err(into_api_error)?
+        .ok_or_else(|| ApiError::not_found("API key not found"))?;
+
+    let now = chrono::Utc::now();
+    let today = now.date_naive();
+    let from = today - chrono::Days::new(days - 1);
+    let flushed = state
+        .db
+        .api_key_usage_series(id, from)
+        .await
+        .map_err(into_api_error)?;
+    let (live, month_to_date) = UsageMeter::new(state.cache.clone(), state.metrics.clone())
+        .live(id, from, now)
+        .await
+        .map_err(into_api_error)?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiKeyUsage {
+            key_id: key.id,
+            label: key.label,
+            monthly_quota: key.monthly_quota,
+            month_to_date,
+            days: usage_series(from, today, &flushed, &live),
+        }),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1752,4 +7110,406 @@ mod tests {
         assert_eq!(api_err.code, "INTERNAL_ERROR");
         assert_eq!(api_err.status, StatusCode::INTERNAL_SERVER_ERROR);
     }
+
+    #[test]
+    fn each_error_kind_maps_to_its_status() {
+        let cases = [
+            (ApiError::bad_request("bad"), StatusCode::BAD_REQUEST, "BAD_REQUEST"),
+            (ApiError::market_not_found(7), StatusCode::NOT_FOUND, "MARKET_NOT_FOUND"),
+            (ApiError::conflict("dup"), StatusCode::CONFLICT, "CONFLICT"),
+            (ApiError::rate_limited(), StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED"),
+            (ApiError::upstream(anyhow::anyhow!("down")), StatusCode::BAD_GATEWAY, "UPSTREAM_UNAVAILABLE"),
+            (ApiError::service_unavailable("busy"), StatusCode::SERVICE_UNAVAILABLE, "SERVICE_UNAVAILABLE"),
+        ];
+        for (err, status, code) in cases {
+            assert_eq!(err.status, status, "{code}");
+            assert_eq!(err.kind.status(), status, "{code}");
+            assert_eq!(err.code, code);
+        }
+    }
+
+    #[test]
+    fn market_not_found_carries_market_id_in_details() {
+        let json = serde_json::to_value(ApiError::market_not_found(42)).unwrap();
+        assert_eq!(json["code"], "MARKET_NOT_FOUND");
+        assert_eq!(json["message"], "market 42 not found");
+        assert_eq!(json["details"]["market_id"], 42);
+        assert!(json.get("kind").is_none());
+        assert!(json.get("status").is_none());
+    }
+
+    #[test]
+    fn details_are_omitted_when_absent() {
+        let json = serde_json::to_value(ApiError::not_found("gone")).unwrap();
+        assert!(json.get("details").is_none());
+    }
+
+    /// Responses carry the shared envelope; it must read back what the
+    /// documented `ApiError` schema describes.
+    #[test]
+    fn error_envelope_matches_the_api_error_schema() {
+        let mut err = ApiError::market_not_found(42);
+        err.request_id = Some("req-1".to_string());
+        let documented = serde_json::to_value(&err).unwrap();
+        let sent = serde_json::to_value(ErrorEnvelope::from(err)).unwrap();
+        assert_eq!(sent, documented);
+        let parsed: ErrorEnvelope = serde_json::from_value(sent).unwrap();
+        assert_eq!(parsed.code, "MARKET_NOT_FOUND");
+        assert_eq!(parsed.request_id.as_deref(), Some("req-1"));
+    }
+
+    /// RPC failures become a 502 without leaking the node's response, even
+    /// when wrapped in further context.
+    #[test]
+    fn rpc_error_maps_to_upstream_unavailable() {
+        let err = anyhow::Error::new(RpcError::new("getLedgerEntries", "transport failed: refused"))
+            .context("market data");
+        let api_err = into_api_error(err);
+        assert_eq!(api_err.status, StatusCode::BAD_GATEWAY);
+        assert_eq!(api_err.code, "UPSTREAM_UNAVAILABLE");
+        assert!(!api_err.message.contains("refused"));
+
+        let api_err = contract_call_error(ContractCallError::Rpc(
+            RpcError::new("sendTransaction", "returned empty result").into(),
+        ));
+        assert_eq!(api_err.code, "UPSTREAM_UNAVAILABLE");
+    }
+
+    /// Running out of budget is the node's fault; a request the node refused
+    /// as malformed is ours and is a 500.
+    #[test]
+    fn rpc_error_kind_picks_502_or_500() {
+        use crate::blockchain::RpcErrorKind;
+
+        let rpc_error = |kind| RpcError {
+            method: "getContractData".to_string(),
+            message: "boom".to_string(),
+            kind,
+            attempts: 2,
+        };
+
+        let api_err = into_api_error(anyhow::Error::new(rpc_error(RpcErrorKind::Timeout)).context("market data"));
+        assert_eq!(api_err.status, StatusCode::BAD_GATEWAY);
+
+        let api_err = into_api_error(anyhow::Error::new(rpc_error(RpcErrorKind::Terminal)).context("market data"));
+        assert_eq!(api_err.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!api_err.message.contains("boom"));
+    }
+
+    #[test]
+    fn db_errors_map_to_conflict_and_unavailable() {
+        let api_err = into_api_error(DbError::ConstraintViolation("users_email_key".into()).into());
+        assert_eq!(api_err.status, StatusCode::CONFLICT);
+        assert_eq!(api_err.code, "CONFLICT");
+
+        let api_err = into_api_error(DbError::Timeout.into());
+        assert_eq!(api_err.status, StatusCode::SERVICE_UNAVAILABLE);
+
+        let api_err = into_api_error(DbError::PoolExhausted.into());
+        assert_eq!(api_err.kind, ApiErrorKind::Overloaded);
+        assert_eq!(api_err.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(api_err.code, "OVERLOADED");
+
+        let api_err = into_api_error(anyhow::anyhow!("unclassified"));
+        assert_eq!(api_err.code, "INTERNAL_ERROR");
+    }
+
+    #[test]
+    fn part_source_reflects_cache_hit_and_stale_fallback() {
+        assert_eq!(PartSource::from_lookup(false, DataSource::Live), PartSource::Fresh);
+        assert_eq!(PartSource::from_lookup(true, DataSource::Live), PartSource::Cached);
+        assert_eq!(PartSource::from_lookup(false, DataSource::StaleFallback), PartSource::Cached);
+    }
+
+    /// A document served from the detail cache must not claim any part is
+    /// fresh, but must keep reporting parts that were unavailable or not
+    /// requested.
+    #[test]
+    fn market_detail_sources_as_cached_keeps_unavailable() {
+        let sources = MarketDetailSources {
+            metadata: PartSource::Fresh,
+            chain_market: PartSource::Cached,
+            oracle: PartSource::Unavailable,
+            history: PartSource::NotRequested,
+        };
+        assert!(!sources.all_available());
+        let mut cached = serde_json::json!({ "id": 7, "data_sources": sources });
+        mark_detail_cached(&mut cached);
+        assert_eq!(
+            cached["data_sources"],
+            serde_json::json!({
+                "metadata": "cached",
+                "chain_market": "cached",
+                "oracle": "unavailable",
+                "history": "not_requested",
+            })
+        );
+
+        // A mask that dropped data_sources leaves the document alone.
+        let mut masked = serde_json::json!({ "id": 7 });
+        mark_detail_cached(&mut masked);
+        assert_eq!(masked, serde_json::json!({ "id": 7 }));
+    }
+
+    #[test]
+    fn parts_not_requested_still_count_as_complete() {
+        let sources = MarketDetailSources {
+            metadata: PartSource::Fresh,
+            chain_market: PartSource::Fresh,
+            oracle: PartSource::NotRequested,
+            history: PartSource::NotRequested,
+        };
+        assert!(sources.all_available());
+    }
+
+    #[test]
+    fn embeds_parse_and_label_in_a_stable_order() {
+        assert_eq!(MarketEmbeds::parse(None).unwrap(), MarketEmbeds::default());
+        assert_eq!(MarketEmbeds::parse(Some("")).unwrap().label(), "");
+        let both = MarketEmbeds::parse(Some("oracle, history")).unwrap();
+        assert!(both.oracle && both.history);
+        assert_eq!(both.label(), "history,oracle");
+        assert_eq!(MarketEmbeds::parse(Some("history,oracle")).unwrap(), both);
+
+        let err = MarketEmbeds::parse(Some("oracle,comments")).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        assert_eq!(err.code, "UNKNOWN_EMBED");
+    }
+
+    /// An embed that was not requested is never fetched.
+    #[tokio::test]
+    async fn embed_fetch_is_skipped_when_not_requested() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let counter = AtomicUsize::new(0);
+        let calls = &counter;
+        let fetch = move || async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            "oracle result"
+        };
+
+        let embeds = MarketEmbeds::parse(Some("history")).unwrap();
+        assert_eq!(fetch_if(embeds.oracle, fetch).await, None);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let embeds = MarketEmbeds::parse(Some("oracle")).unwrap();
+        assert_eq!(fetch_if(embeds.oracle, fetch).await, Some("oracle result"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    fn chain_text() -> MarketMetadata {
+        MarketMetadata {
+            description: Some("Chain title".to_string()),
+            options: Some(vec!["Yes".to_string(), "No".to_string()]),
+        }
+    }
+
+    fn detail_row(title: &str) -> MarketDetail {
+        MarketDetail {
+            id: 7,
+            title: title.to_string(),
+            description: None,
+            category: None,
+            creator: None,
+            status: "active".to_string(),
+            outcome_options: Vec::new(),
+            outcome_odds: Vec::new(),
+            outcome_index: None,
+            volume: 0.0,
+            participant_count: 0,
+            ends_at: chrono::Utc::now(),
+            created_at: chrono::Utc::now(),
+            resolved_at: None,
+            token: None,
+            image_url: None,
+        }
+    }
+
+    fn chain_market(title: Option<&str>) -> ChainMarketData {
+        ChainMarketData {
+            market_id: 7,
+            title: title.map(ToOwned::to_owned),
+            options: None,
+            status: None,
+            onchain_volume: "0".to_string(),
+            resolved_outcome: None,
+            ledger: 1,
+            source: DataSource::Live,
+        }
+    }
+
+    #[test]
+    fn database_title_wins_over_chain_title() {
+        assert_eq!(merged_title("DB title".to_string(), &chain_text()), "DB title");
+        assert_eq!(merged_title(String::new(), &chain_text()), "Chain title");
+        assert_eq!(merged_title("  ".to_string(), &MarketMetadata::default()), "  ");
+
+        let mut metadata = detail_row("DB title");
+        let mut chain = chain_market(None);
+        apply_chain_metadata(Some(&mut metadata), Some(&mut chain), chain_text());
+        assert_eq!(metadata.title, "DB title");
+        assert_eq!(metadata.outcome_options, vec!["Yes", "No"]);
+        assert_eq!(chain.title.as_deref(), Some("Chain title"));
+        assert_eq!(chain.options.as_deref(), Some(&["Yes".to_string(), "No".to_string()][..]));
+    }
+
+    #[test]
+    fn chain_metadata_fills_only_missing_fields() {
+        let mut metadata = detail_row("");
+        metadata.outcome_options = vec!["Up".to_string(), "Down".to_string()];
+        let mut chain = chain_market(Some("RPC title"));
+        apply_chain_metadata(Some(&mut metadata), Some(&mut chain), chain_text());
+        assert_eq!(metadata.title, "Chain title");
+        assert_eq!(metadata.outcome_options, vec!["Up", "Down"]);
+        assert_eq!(chain.title.as_deref(), Some("RPC title"));
+
+        // A failed simulation degrades to nulls and leaves everything as is.
+        let mut metadata = detail_row("");
+        apply_chain_metadata(Some(&mut metadata), None, MarketMetadata::default());
+        assert_eq!(metadata.title, "");
+        assert!(metadata.outcome_options.is_empty());
+    }
+
+    #[test]
+    fn is_strkey_accepts_account_and_contract_addresses() {
+        assert!(is_strkey("GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7"));
+        assert!(is_strkey("CAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7"));
+        assert!(!is_strkey("gaazi4tcr3ty5ojhctjc2a4qsy6cjwjh5iajtgkin2er7lbnvkoccwn7"));
+        assert!(!is_strkey("GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN"));
+        assert!(!is_strkey("SAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7"));
+    }
+
+    #[test]
+    fn tx_envelope_rejects_non_base64_empty_and_oversize() {
+        use base64::Engine as _;
+        let req = |transaction: String| TxEnvelopeRequest { transaction };
+        assert!(req("AAAAAgAAAAA=".to_string()).validate().is_ok());
+        assert!(req("not base64!".to_string()).validate().is_err());
+        assert!(req(String::new()).validate().is_err());
+        let oversize = base64::engine::general_purpose::STANDARD
+            .encode(vec![0u8; TX_ENVELOPE_MAX_BYTES + 1]);
+        assert!(req(oversize).validate().is_err());
+    }
+
+    #[test]
+    fn oracle_result_request_bounds() {
+        let req = |source: &str, confidence_bps: u32| OracleResultRequest {
+            outcome: 1,
+            source: source.to_string(),
+            confidence_bps,
+        };
+        assert!(req("feed", 10_000).validate().is_ok());
+        assert!(req("feed", 10_001).validate().is_err());
+        assert!(req("   ", 5_000).validate().is_err());
+        assert!(req(&"x".repeat(201), 5_000).validate().is_err());
+    }
+
+    fn analytics_event(event_type: &str) -> AnalyticsEventInput {
+        AnalyticsEventInput {
+            event_type: event_type.to_string(),
+            properties: Some(serde_json::json!({ "path": "/markets" })),
+            session_id: Some(" s-1 ".to_string()),
+            occurred_at: None,
+        }
+    }
+
+    /// The `details.fields` of the 422 for `errors`.
+    fn invalid_fields(errors: validator::ValidationErrors) -> Vec<(String, String)> {
+        let err = ApiError::from(errors);
+        assert_eq!((err.status, err.code), (StatusCode::UNPROCESSABLE_ENTITY, "INVALID_FIELDS"));
+        err.details.unwrap()["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| (f["field"].as_str().unwrap().to_string(), f["code"].as_str().unwrap().to_string()))
+            .collect()
+    }
+
+    fn field(path: &str, code: &str) -> (String, String) {
+        (path.to_string(), code.to_string())
+    }
+
+    #[test]
+    fn analytics_batch_rejects_oversize_and_unknown_types_as_422() {
+        let now = chrono::Utc::now();
+        let batch = |events| AnalyticsEventsRequest { events };
+
+        let full = batch(vec![analytics_event("page_view"); crate::analytics::MAX_BATCH_EVENTS]);
+        full.validate().unwrap();
+        let ok = full.into_events(now).unwrap();
+        assert_eq!(ok.len(), crate::analytics::MAX_BATCH_EVENTS);
+        assert_eq!(ok[0].session_id.as_deref(), Some("s-1"));
+        assert_eq!(ok[0].occurred_at, now);
+
+        let errors = batch(vec![analytics_event("page_view"); crate::analytics::MAX_BATCH_EVENTS + 1])
+            .validate()
+            .unwrap_err();
+        assert_eq!(invalid_fields(errors), vec![field("events", "length")]);
+
+        let errors = batch(vec![analytics_event("page_view"), analytics_event("drop_table")])
+            .validate()
+            .unwrap_err();
+        assert_eq!(invalid_fields(errors), vec![field("events[1].event_type", "unknown_event_type")]);
+
+        let errors = batch(vec![]).validate().unwrap_err();
+        assert_eq!(invalid_fields(errors), vec![field("events", "length")]);
+    }
+
+    #[test]
+    fn analytics_event_field_bounds() {
+        let now = chrono::Utc::now();
+        let check = |event: AnalyticsEventInput| AnalyticsEventsRequest { events: vec![event] }.validate();
+
+        let mut e = analytics_event("cta_click");
+        e.properties = None;
+        let events = AnalyticsEventsRequest { events: vec![e] }.into_events(now).unwrap();
+        assert_eq!(events[0].properties, serde_json::json!({}));
+
+        let mut e = analytics_event("cta_click");
+        e.properties = Some(serde_json::json!(["not", "an", "object"]));
+        assert_eq!(invalid_fields(check(e).unwrap_err()), vec![field("events[0].properties", "type")]);
+
+        let mut e = analytics_event("cta_click");
+        e.properties = Some(serde_json::json!({ "blob": "x".repeat(crate::analytics::MAX_PROPERTIES_BYTES) }));
+        assert_eq!(invalid_fields(check(e).unwrap_err()), vec![field("events[0].properties", "size")]);
+
+        let mut e = analytics_event("cta_click");
+        e.session_id = Some("s".repeat(crate::analytics::MAX_SESSION_ID_LEN as usize + 1));
+        assert_eq!(invalid_fields(check(e).unwrap_err()), vec![field("events[0].session_id", "length")]);
+
+        for occurred_at in [now - chrono::Duration::hours(25), now + chrono::Duration::hours(1)] {
+            let mut e = analytics_event("cta_click");
+            e.occurred_at = Some(occurred_at);
+            check(e.clone()).unwrap();
+            let err = AnalyticsEventsRequest { events: vec![e] }.into_events(now).unwrap_err();
+            assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(err.details.unwrap()["fields"][0]["field"], "events[0].occurred_at");
+        }
+    }
+
+    #[test]
+    fn contact_request_reports_every_invalid_field() {
+        let request = ContactRequest {
+            name: "   ".to_string(),
+            email: "not-an-email".to_string(),
+            subject: "s".repeat(crate::contact::MAX_SUBJECT_LEN as usize + 1),
+            message: "Hello".to_string(),
+            website: None,
+        };
+        assert_eq!(
+            invalid_fields(request.validate().unwrap_err()),
+            vec![field("email", "email"), field("name", "required"), field("subject", "length")]
+        );
+
+        let request = ContactRequest {
+            name: " Ada ".to_string(),
+            email: " Ada@Example.com ".to_string(),
+            subject: "Hi".to_string(),
+            message: "Hello".to_string(),
+            website: None,
+        };
+        request.validate().unwrap();
+        assert_eq!(request.normalized().1, "ada@example.com");
+    }
 }
+
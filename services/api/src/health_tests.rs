@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod health_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use std::{sync::Arc, time::Instant};
+    use tower::ServiceExt;
+
+    use crate::{
+        handlers::{health_live, health_ready},
+        readiness::CHECK_TIMEOUT,
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Nothing listens on port 1, so every Redis connection is refused.
+    const CLOSED_REDIS_URL: &str = "redis://127.0.0.1:1";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/health/live", get(health_live))
+            .route("/health/ready", get(health_ready))
+            .with_state(state)
+    }
+
+    async fn get_json(state: &Arc<crate::AppState>, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// With every dependency up the instance is ready.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_ready_when_dependencies_are_up() {
+        let state = build_state(None).await;
+
+        let (status, body) = get_json(&state, "/health/ready").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["ready"], true);
+        assert_eq!(body["dependencies"]["database"]["status"], "ok");
+        assert!(body["dependencies"]["redis"]["latency_ms"].is_u64());
+        assert!(body["dependencies"]["blockchain_rpc"]["status"].is_string());
+    }
+
+    /// A refused Redis makes the instance unready with a breakdown that
+    /// names Redis, without the probe waiting longer than the check timeout.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL
+    async fn test_not_ready_when_redis_is_down() {
+        let state = build_state(Some(CLOSED_REDIS_URL)).await;
+
+        let start = Instant::now();
+        let (status, body) = get_json(&state, "/health/ready").await;
+        assert!(start.elapsed() < CHECK_TIMEOUT * 2, "probe took {:?}", start.elapsed());
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["status"], "unavailable");
+        assert_eq!(body["dependencies"]["database"]["status"], "ok");
+        assert_eq!(body["dependencies"]["redis"]["status"], "unavailable");
+        assert!(body["dependencies"]["redis"]["error"].is_string());
+
+        // Liveness does not touch dependencies.
+        let (status, body) = get_json(&state, "/health/live").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+    }
+
+    /// Back-to-back probes share one check.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL
+    async fn test_readiness_is_cached() {
+        let state = build_state(Some(CLOSED_REDIS_URL)).await;
+
+        let (_, first) = get_json(&state, "/health/ready").await;
+        let (_, second) = get_json(&state, "/health/ready").await;
+        assert_eq!(first["checked_at"], second["checked_at"]);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    /// `redis_url` overrides `REDIS_URL`. The Redis pool connects lazily, so
+    /// the state builds even when nothing is listening.
+    async fn build_state(redis_url: Option<&str>) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        if let Some(url) = redis_url {
+            config.redis_url = url.to_string();
+        }
+        build_test_state_with(config, &[]).await
+    }
+}
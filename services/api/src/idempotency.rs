@@ -3,45 +3,74 @@
 //! Keys are namespaced as `{user_id}:{idempotency_key}`.
 //! A key submitted by user A cannot be replayed by user B — attempting to do
 //! so returns HTTP 422 Unprocessable Entity.
+//!
+//! Each key is bound to a fingerprint of the request (method, path, and body).
+//! A replay with the same fingerprint gets the stored response; a different
+//! request under the same key gets `409 IDEMPOTENCY_KEY_REUSED`. The key is
+//! claimed with `SET NX` before the handler runs, so a duplicate that arrives
+//! while the first request is still running gets
+//! `409 IDEMPOTENCY_REQUEST_IN_PROGRESS` instead of repeating the side effect.
 
 use std::{
     collections::HashMap,
+    net::SocketAddr,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use axum::{
     body::Body,
-    extract::{Request, State},
-    http::{HeaderValue, StatusCode},
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderValue, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::AppState;
+use crate::{
+    config::Config,
+    handlers::{ApiError, ApiErrorKind},
+    security::extract_client_ip_cidrs,
+    AppState,
+};
 
 const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
 const MAX_KEY_LEN: usize = 128;
 
+/// How long a claimed key stays locked while its first request runs. Longer
+/// than any handler should take (contract calls wait up to
+/// `CONTRACT_CALL_TIMEOUT_SECS`), short enough that a crash mid-request does
+/// not lock the key for the whole idempotency window.
+const IN_FLIGHT_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct IdempotencyRecord {
+    /// The user/API-key identity that originally created this entry.
+    owner: String,
+    /// SHA-256 of the method, path, and body of the original request.
+    fingerprint: String,
+    /// `None` while the original request is still running.
+    response: Option<CachedResponse>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 struct CachedResponse {
     status: u16,
     body: Vec<u8>,
     content_type: Option<String>,
-    /// The user/API-key identity that originally created this entry.
-    owner: String,
 }
 
-/// Build a per-user scoped cache key. Format: `idempotency:v2:{user_id}:{raw_key}`.
+/// Build a per-user scoped cache key. Format: `idempotency:v3:{user_id}:{raw_key}`.
 fn idempotency_cache_key(user_id: &str, raw_key: &str) -> String {
-    format!("idempotency:v2:{}:{}", user_id, raw_key)
+    format!("idempotency:v3:{}:{}", user_id, raw_key)
 }
 
 /// Extract a stable identity string from request headers.
 /// Uses the API key prefix, or falls back to the Authorization header value.
-fn extract_user_identity(req: &Request) -> String {
+/// Anonymous callers are scoped by their resolved client IP so they cannot
+/// replay each other's responses.
+fn extract_user_identity(req: &Request, config: &Config) -> String {
     req.headers()
         .get("x-api-key")
         .and_then(|v| v.to_str().ok())
@@ -55,20 +84,46 @@ fn extract_user_identity(req: &Request) -> String {
                 .and_then(|v| v.to_str().ok())
                 .map(|s| format!("auth:{}", &s[..32.min(s.len())]))
         })
-        .unwrap_or_else(|| "anonymous".to_string())
+        .unwrap_or_else(|| {
+            let ip = extract_client_ip_cidrs(
+                req.headers(),
+                req.extensions().get::<ConnectInfo<SocketAddr>>(),
+                config.trust_proxy,
+                &config.trusted_proxy_cidrs,
+            );
+            format!("anon:{}", ip)
+        })
+}
+
+/// What a replay must match: the same method, path, and body bytes.
+fn request_fingerprint(method: &Method, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
 }
 
-/// Middleware that deduplicates POST requests using an `Idempotency-Key` header.
+/// Middleware that deduplicates mutating requests using an `Idempotency-Key`
+/// header. Add it to a router to opt its POST/PUT/PATCH/DELETE routes in.
 ///
-/// - If the header is absent the request passes through unchanged.
-/// - If a cached response exists for the key AND owner matches, it is returned.
-/// - If a cached response exists but owner differs, returns 422 (cross-user collision).
-/// - Otherwise the request is executed, the response is cached, and returned.
+/// - If the header is absent, or the method is safe, the request passes through unchanged.
+/// - If a response is stored for the key and the request matches, it is replayed.
+/// - If the key was used for a different request, returns 409.
+/// - If the key's first request is still running, returns 409 with `Retry-After`.
+/// - Otherwise the request is executed. A 2xx response is stored for
+///   `IDEMPOTENCY_WINDOW_SECS`; any other response releases the key so the
+///   client can retry.
 pub async fn idempotency_middleware(
     State(state): State<Arc<AppState>>,
     req: Request,
     next: Next,
 ) -> Response {
+    if matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS) {
+        return next.run(req).await;
+    }
     let raw_key = match req
         .headers()
         .get(IDEMPOTENCY_HEADER)
@@ -80,26 +135,42 @@ pub async fn idempotency_middleware(
         None => return next.run(req).await,
     };
 
-    let user_id = extract_user_identity(&req);
+    let user_id = extract_user_identity(&req, &state.config);
     let cache_key = idempotency_cache_key(&user_id, &raw_key);
     let ttl = Duration::from_secs(state.config.idempotency_window_secs);
 
-    // Return cached response if present and owned by this user
-    if let Ok(Some(cached)) = state.cache.get_json::<CachedResponse>(&cache_key).await {
-        if cached.owner != user_id {
-            // Cross-user collision: same scoped key with different owner (should not happen
-            // with scoped keys, but defensive check against key-format changes)
-            return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+    // Buffer the body to fingerprint it; the size middleware has already
+    // bounded it.
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, crate::validation::body_limit()).await {
+        Ok(b) => b,
+        Err(_) => return crate::validation::payload_too_large(crate::validation::body_limit()),
+    };
+    let fingerprint = request_fingerprint(&parts.method, parts.uri.path(), &body);
+    let req = Request::from_parts(parts, Body::from(body));
+
+    let claim = IdempotencyRecord {
+        owner: user_id.clone(),
+        fingerprint: fingerprint.clone(),
+        response: None,
+    };
+    match state.cache.set_json_nx(&cache_key, &claim, IN_FLIGHT_TTL).await {
+        Ok(true) => {}
+        Ok(false) => {
+            let existing = state
+                .cache
+                .get_json::<IdempotencyRecord>(&cache_key)
+                .await
+                .ok()
+                .flatten();
+            return replay(existing, &user_id, &fingerprint);
         }
-        let status = StatusCode::from_u16(cached.status).unwrap_or(StatusCode::OK);
-        let mut resp = Response::builder().status(status);
-        if let Some(ct) = cached.content_type {
-            resp = resp.header(axum::http::header::CONTENT_TYPE, ct);
+        Err(e) => {
+            // Fail open: without Redis the request runs unprotected rather
+            // than not at all.
+            tracing::warn!(error = %e, "idempotency store unavailable; running request without a claim");
+            return next.run(req).await;
         }
-        resp = resp.header("Idempotency-Replayed", "true");
-        return resp
-            .body(Body::from(cached.body))
-            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
     }
 
     // Execute the request
@@ -108,10 +179,13 @@ pub async fn idempotency_middleware(
 
     let bytes = match axum::body::to_bytes(body, usize::MAX).await {
         Ok(b) => b,
-        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        Err(_) => {
+            let _ = state.cache.del(&cache_key).await;
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
     };
 
-    // Cache only successful responses (2xx)
+    // Store only successful responses (2xx); release the claim otherwise.
     if parts.status.is_success() {
         let content_type = parts
             .headers
@@ -119,13 +193,19 @@ pub async fn idempotency_middleware(
             .and_then(|v| v.to_str().ok())
             .map(|s| s.to_string());
 
-        let cached = CachedResponse {
-            status: parts.status.as_u16(),
-            body: bytes.to_vec(),
-            content_type,
-            owner: user_id,
+        let completed = IdempotencyRecord {
+            response: Some(CachedResponse {
+                status: parts.status.as_u16(),
+                body: bytes.to_vec(),
+                content_type,
+            }),
+            ..claim
         };
-        let _ = state.cache.set_json(&cache_key, &cached, ttl).await;
+        if let Err(e) = state.cache.set_json(&cache_key, &completed, ttl).await {
+            tracing::warn!(error = %e, "failed to store idempotent response");
+        }
+    } else if let Err(e) = state.cache.del(&cache_key).await {
+        tracing::warn!(error = %e, "failed to release idempotency key");
     }
 
     let mut resp = Response::from_parts(parts, Body::from(bytes));
@@ -134,6 +214,56 @@ pub async fn idempotency_middleware(
     resp
 }
 
+/// The response for a request whose key is already claimed.
+fn replay(existing: Option<IdempotencyRecord>, user_id: &str, fingerprint: &str) -> Response {
+    let record = match existing {
+        Some(record) => record,
+        // Released or expired between the claim attempt and the read.
+        None => return in_progress(),
+    };
+    if record.owner != user_id {
+        // Cross-user collision: same scoped key with different owner (should not happen
+        // with scoped keys, but defensive check against key-format changes)
+        return ApiError::unprocessable(
+            "IDEMPOTENCY_KEY_COLLISION",
+            "This Idempotency-Key belongs to another client.",
+        )
+        .into_response();
+    }
+    if record.fingerprint != fingerprint {
+        return ApiError::new(
+            ApiErrorKind::Conflict,
+            "IDEMPOTENCY_KEY_REUSED",
+            "This Idempotency-Key was already used with a different request.",
+        )
+        .into_response();
+    }
+    let Some(cached) = record.response else {
+        return in_progress();
+    };
+
+    let status = StatusCode::from_u16(cached.status).unwrap_or(StatusCode::OK);
+    let mut resp = Response::builder().status(status);
+    if let Some(ct) = cached.content_type {
+        resp = resp.header(axum::http::header::CONTENT_TYPE, ct);
+    }
+    resp = resp.header("Idempotency-Replayed", "true");
+    resp.body(Body::from(cached.body))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+fn in_progress() -> Response {
+    (
+        [("Retry-After", "1")],
+        ApiError::new(
+            ApiErrorKind::Conflict,
+            "IDEMPOTENCY_REQUEST_IN_PROGRESS",
+            "A request with this Idempotency-Key is still being processed; retry shortly.",
+        ),
+    )
+        .into_response()
+}
+
 // ── Standalone testable IdempotencyStore ─────────────────────────────────────
 
 /// Cached response stored against a scoped idempotency key.
@@ -247,7 +377,7 @@ mod tests {
         store.set("user_a", "key1", make_response(201));
         let victim_scoped = IdempotencyStore::scoped_key("user_a", "key1");
         let err = store.check_cross_user("user_b", &victim_scoped);
-        assert_eq!(err, Err(IdempotencyError::CrossUserCollision));
+        assert!(matches!(err, Err(IdempotencyError::CrossUserCollision)));
     }
 
     #[test]
@@ -264,4 +394,68 @@ mod tests {
         let store = IdempotencyStore::new(Duration::from_secs(60));
         assert!(store.get("user_a", "nonexistent").unwrap().is_none());
     }
+
+    // ── Middleware replay decisions ──────────────────────────────────────────
+
+    fn record(owner: &str, body: &[u8], response: Option<CachedResponse>) -> IdempotencyRecord {
+        IdempotencyRecord {
+            owner: owner.to_string(),
+            fingerprint: request_fingerprint(&Method::POST, "/api/v1/waitlist", body),
+            response,
+        }
+    }
+
+    async fn error_code(response: Response) -> String {
+        use http_body_util::BodyExt;
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        body["code"].as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn fingerprint_covers_method_path_and_body() {
+        let base = request_fingerprint(&Method::POST, "/a", b"{}");
+        assert_eq!(base, request_fingerprint(&Method::POST, "/a", b"{}"));
+        assert_ne!(base, request_fingerprint(&Method::PUT, "/a", b"{}"));
+        assert_ne!(base, request_fingerprint(&Method::POST, "/b", b"{}"));
+        assert_ne!(base, request_fingerprint(&Method::POST, "/a", b"{ }"));
+    }
+
+    #[tokio::test]
+    async fn completed_matching_request_is_replayed() {
+        let stored = CachedResponse {
+            status: 201,
+            body: br#"{"ok":true}"#.to_vec(),
+            content_type: Some("application/json".to_string()),
+        };
+        let fingerprint = request_fingerprint(&Method::POST, "/api/v1/waitlist", b"{}");
+        let response = replay(Some(record("user_a", b"{}", Some(stored))), "user_a", &fingerprint);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["idempotency-replayed"], "true");
+        assert_eq!(response.headers()["content-type"], "application/json");
+    }
+
+    #[tokio::test]
+    async fn different_body_under_same_key_is_409() {
+        let fingerprint = request_fingerprint(&Method::POST, "/api/v1/waitlist", b"{\"email\":\"b\"}");
+        let response = replay(Some(record("user_a", b"{}", None)), "user_a", &fingerprint);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(error_code(response).await, "IDEMPOTENCY_KEY_REUSED");
+    }
+
+    #[tokio::test]
+    async fn in_flight_request_is_409_with_retry_after() {
+        let fingerprint = request_fingerprint(&Method::POST, "/api/v1/waitlist", b"{}");
+        let response = replay(Some(record("user_a", b"{}", None)), "user_a", &fingerprint);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(response.headers()["retry-after"], "1");
+        assert_eq!(error_code(response).await, "IDEMPOTENCY_REQUEST_IN_PROGRESS");
+    }
+
+    #[tokio::test]
+    async fn other_owner_is_422() {
+        let fingerprint = request_fingerprint(&Method::POST, "/api/v1/waitlist", b"{}");
+        let response = replay(Some(record("user_a", b"{}", None)), "user_b", &fingerprint);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
 }
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod idempotency_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        middleware,
+        routing::post,
+        Json, Router,
+    };
+    use http_body_util::BodyExt;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::{handlers::ApiError, idempotency::idempotency_middleware};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// A slow handler that counts its side effects, and one that always fails,
+    /// behind the idempotency middleware.
+    fn app(state: Arc<crate::AppState>, effects: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/effect",
+                post(move || {
+                    let effects = effects.clone();
+                    async move {
+                        let n = effects.fetch_add(1, Ordering::SeqCst) + 1;
+                        tokio::time::sleep(Duration::from_millis(200)).await;
+                        Json(serde_json::json!({ "effect": n }))
+                    }
+                }),
+            )
+            .route("/broken", post(|| async { ApiError::bad_request("nope") }))
+            .layer(middleware::from_fn_with_state(state.clone(), idempotency_middleware))
+            .with_state(state)
+    }
+
+    fn post_json(uri: &str, key: &str, body: serde_json::Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("idempotency-key", key)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn send(router: Router, req: Request<Body>) -> (StatusCode, Option<String>, serde_json::Value) {
+        let response = router.oneshot(req).await.unwrap();
+        let status = response.status();
+        let replayed = response
+            .headers()
+            .get("idempotency-replayed")
+            .map(|v| v.to_str().unwrap().to_string());
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, replayed, serde_json::from_slice(&bytes).unwrap_or(serde_json::Value::Null))
+    }
+
+    fn key() -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Two simultaneous identical requests run the handler once; the loser is
+    /// told to retry, and a later retry gets the winner's response.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_concurrent_duplicates_have_one_side_effect() {
+        let state = build_test_state().await;
+        let effects = Arc::new(AtomicUsize::new(0));
+        let router = app(state, effects.clone());
+        let key = key();
+        let body = serde_json::json!({ "email": "a@example.com" });
+
+        let (a, b) = tokio::join!(
+            send(router.clone(), post_json("/effect", &key, body.clone())),
+            send(router.clone(), post_json("/effect", &key, body.clone())),
+        );
+        assert_eq!(effects.load(Ordering::SeqCst), 1);
+        let mut statuses = [a.0, b.0];
+        statuses.sort();
+        assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT]);
+        let loser = if a.0 == StatusCode::CONFLICT { &a } else { &b };
+        assert_eq!(loser.2["code"], "IDEMPOTENCY_REQUEST_IN_PROGRESS");
+
+        let (status, replayed, json) = send(router, post_json("/effect", &key, body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replayed.as_deref(), Some("true"));
+        assert_eq!(json["effect"], 1);
+        assert_eq!(effects.load(Ordering::SeqCst), 1);
+    }
+
+    /// Reusing a key with a different body is rejected without running the
+    /// handler.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_key_reused_with_different_body_is_409() {
+        let state = build_test_state().await;
+        let effects = Arc::new(AtomicUsize::new(0));
+        let router = app(state, effects.clone());
+        let key = key();
+
+        let (status, replayed, _) =
+            send(router.clone(), post_json("/effect", &key, serde_json::json!({ "n": 1 }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replayed.as_deref(), Some("false"));
+
+        let (status, _, json) = send(router, post_json("/effect", &key, serde_json::json!({ "n": 2 }))).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "IDEMPOTENCY_KEY_REUSED");
+        assert_eq!(effects.load(Ordering::SeqCst), 1);
+    }
+
+    /// A failed response is not stored, so the same key can be retried.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_failed_request_releases_key() {
+        let state = build_test_state().await;
+        let router = app(state, Arc::new(AtomicUsize::new(0)));
+        let key = key();
+
+        for _ in 0..2 {
+            let (status, replayed, _) =
+                send(router.clone(), post_json("/broken", &key, serde_json::json!({}))).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert_eq!(replayed.as_deref(), Some("false"));
+        }
+    }
+
+    /// Anonymous callers at different addresses do not share a key space, so
+    /// one cannot replay another's response by guessing its key.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_anonymous_callers_are_scoped_by_client_ip() {
+        let state = build_test_state().await;
+        let effects = Arc::new(AtomicUsize::new(0));
+        let router = app(state, effects.clone());
+        let key = key();
+        let body = serde_json::json!({ "email": "a@example.com" });
+
+        for peer in ["203.0.113.1:4000", "203.0.113.2:4000"] {
+            let mut req = post_json("/effect", &key, body.clone());
+            let addr: std::net::SocketAddr = peer.parse().unwrap();
+            req.extensions_mut().insert(axum::extract::ConnectInfo(addr));
+            let (status, replayed, _) = send(router.clone(), req).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(replayed.as_deref(), Some("false"));
+        }
+        assert_eq!(effects.load(Ordering::SeqCst), 2);
+    }
+
+}
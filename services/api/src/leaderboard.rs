@@ -0,0 +1,178 @@
+//! Weekly and all-time leaderboards.
+//!
+//! Boards are not computed per request. A supervised task ([`run`]) calls
+//! [`refresh_all`] every `LEADERBOARD_REFRESH_INTERVAL_SECS`,
+//! which rebuilds every (period, metric) snapshot in `leaderboard_snapshots`
+//! from `chain_events` and then drops the cached API documents.
+//!
+//! Scores assume a single stake token platform-wide: profit and volume are
+//! summed across markets without grouping by token.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{keys, RedisCache},
+    config::Config,
+    db::Database,
+    AppState,
+};
+
+const WORKER_NAME: &str = "leaderboard_aggregation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardPeriod {
+    /// Activity in the trailing seven days.
+    #[default]
+    Weekly,
+    All,
+}
+
+impl LeaderboardPeriod {
+    pub const ALL: [Self; 2] = [Self::Weekly, Self::All];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::All => "all",
+        }
+    }
+
+    /// Lower bound on settlement / bet time, or `None` for all-time boards.
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Weekly => Some(now - ChronoDuration::days(7)),
+            Self::All => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Realized profit on resolved markets, in stroops.
+    #[default]
+    Profit,
+    /// Total amount staked, in stroops.
+    Volume,
+    /// Share of resolved markets where the address backed the winning
+    /// outcome. Requires `LEADERBOARD_MIN_MARKETS` resolved markets.
+    Winrate,
+}
+
+impl LeaderboardMetric {
+    pub const ALL: [Self; 3] = [Self::Profit, Self::Volume, Self::Winrate];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Profit => "profit",
+            Self::Volume => "volume",
+            Self::Winrate => "winrate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LeaderboardEntry {
+    pub rank: i32,
+    pub address: String,
+    /// Integer stroops for profit/volume, a decimal fraction for win rate.
+    pub score: String,
+    /// Markets counted towards the score.
+    pub markets: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Leaderboard {
+    pub period: LeaderboardPeriod,
+    pub metric: LeaderboardMetric,
+    /// When the snapshot was built; `None` before the first run.
+    pub computed_at: Option<DateTime<Utc>>,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// Rebuild every board. A failing board is logged and skipped so one bad
+/// query does not freeze the others; the first error is returned afterwards.
+pub async fn refresh_all(db: &Database, cache: &RedisCache, config: &Config) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let mut first_err = None;
+
+    for period in LeaderboardPeriod::ALL {
+        for metric in LeaderboardMetric::ALL {
+            let result = db
+                .leaderboard_rebuild(
+                    config.network_name(),
+                    period,
+                    metric,
+                    period.since(now),
+                    &config.leaderboard_excluded_addresses,
+                    config.leaderboard_min_markets,
+                    config.leaderboard_size,
+                )
+                .await;
+            match result {
+                Ok(rows) => {
+                    tracing::debug!(period = period.label(), metric = metric.label(), rows, "leaderboard rebuilt");
+                    let _ = cache
+                        .del(&keys::api_leaderboard(period.label(), metric.label()))
+                        .await;
+                }
+                Err(e) => {
+                    tracing::warn!(period = period.label(), metric = metric.label(), error = %e, "leaderboard rebuild failed");
+                    first_err.get_or_insert(e);
+                }
+            }
+        }
+    }
+
+    match first_err {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Supervised loop: rebuilds the boards right away, then every
+/// `LEADERBOARD_REFRESH_INTERVAL_SECS` until `shutdown` fires.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+
+    let mut interval = tokio::time::interval(state.config.leaderboard_refresh_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = refresh_all(&state.db, &state.cache, &state.config).await {
+            tracing::warn!("[leaderboard] aggregation error: {e}");
+        }
+        state.metrics.set_worker_status(WORKER_NAME, true);
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weekly_period_looks_back_seven_days() {
+        let now = Utc::now();
+        assert_eq!(LeaderboardPeriod::Weekly.since(now), Some(now - ChronoDuration::days(7)));
+        assert_eq!(LeaderboardPeriod::All.since(now), None);
+    }
+
+    #[test]
+    fn labels_match_serde_names() {
+        for metric in LeaderboardMetric::ALL {
+            assert_eq!(serde_json::to_value(metric).unwrap(), metric.label());
+        }
+        for period in LeaderboardPeriod::ALL {
+            assert_eq!(serde_json::to_value(period).unwrap(), period.label());
+        }
+    }
+}
@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod leaderboard_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        blockchain::{IndexedEvent, EVENT_BET_PLACED, EVENT_REWARD_CLAIMED},
+        handlers::leaderboard,
+        leaderboard::{LeaderboardMetric, LeaderboardPeriod},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 3] = [9201, 9202, 9203];
+    const SEED_ADDRESSES: [&str; 4] = ["GLBTEST_A", "GLBTEST_B", "GLBTEST_C", "GLBTEST_D"];
+    const EXCLUDED: &str = "GLBTEST_D";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/leaderboard", get(leaderboard))
+            .with_state(state)
+    }
+
+    async fn get_json(router: Router, uri: &str) -> (StatusCode, Value) {
+        let response = router
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn event(
+        n: u32,
+        kind: &str,
+        market_id: i64,
+        address: &str,
+        outcome: Option<u32>,
+        amount: i128,
+    ) -> IndexedEvent {
+        IndexedEvent {
+            id: format!("lbtest-{n}"),
+            ledger: 1_000 + n,
+            tx_hash: None,
+            kind: kind.to_string(),
+            market_id: Some(market_id),
+            address: Some(address.to_string()),
+            outcome,
+            amount: Some(amount.to_string()),
+            token: None,
+            is_refund: false,
+        }
+    }
+
+    /// 9201 resolved to outcome 0 yesterday, 9202 resolved to outcome 1 a
+    /// month ago, 9203 still open.
+    ///
+    /// A: +1000 over one market. B: +1000 over two markets (wins the tie).
+    /// C: -700 settled, plus a large open stake. D: excluded big winner.
+    async fn seed(state: &crate::AppState) {
+        cleanup(state).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, outcome_index, total_volume, ends_at, created_at, resolved_at) \
+             VALUES \
+                (9201, 'LB 1', 'resolved', 0,    0, NOW() - INTERVAL '2 day',  NOW() - INTERVAL '40 day', NOW() - INTERVAL '1 day'), \
+                (9202, 'LB 2', 'resolved', 1,    0, NOW() - INTERVAL '31 day', NOW() - INTERVAL '40 day', NOW() - INTERVAL '30 day'), \
+                (9203, 'LB 3', 'active',   NULL, 0, NOW() + INTERVAL '5 day',  NOW() - INTERVAL '1 day',  NULL)",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+
+        let network = state.config.network_name();
+        let events = [
+            event(1, EVENT_BET_PLACED, 9201, "GLBTEST_A", Some(0), 1000),
+            event(2, EVENT_REWARD_CLAIMED, 9201, "GLBTEST_A", None, 2000),
+            event(3, EVENT_BET_PLACED, 9201, "GLBTEST_B", Some(0), 500),
+            event(4, EVENT_REWARD_CLAIMED, 9201, "GLBTEST_B", None, 1000),
+            event(5, EVENT_BET_PLACED, 9202, "GLBTEST_B", Some(1), 500),
+            event(6, EVENT_REWARD_CLAIMED, 9202, "GLBTEST_B", None, 1000),
+            event(7, EVENT_BET_PLACED, 9202, "GLBTEST_C", Some(0), 700),
+            event(8, EVENT_BET_PLACED, 9203, "GLBTEST_C", Some(1), 5000),
+            event(9, EVENT_BET_PLACED, 9201, "GLBTEST_D", Some(0), 100),
+            event(10, EVENT_REWARD_CLAIMED, 9201, "GLBTEST_D", None, 10_000),
+        ];
+        for e in &events {
+            state.db.chain_event_insert(network, e).await.unwrap();
+        }
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE 'lbtest-%'")
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    async fn rebuild(state: &crate::AppState, period: LeaderboardPeriod, metric: LeaderboardMetric) {
+        let now = chrono::Utc::now();
+        state
+            .db
+            .leaderboard_rebuild(
+                state.config.network_name(),
+                period,
+                metric,
+                period.since(now),
+                &[EXCLUDED.to_string()],
+                2,
+                1000,
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Seeded addresses in board order, with their scores.
+    async fn board(
+        state: &crate::AppState,
+        period: LeaderboardPeriod,
+        metric: LeaderboardMetric,
+    ) -> Vec<(String, String)> {
+        state
+            .db
+            .leaderboard(period, metric, 1000)
+            .await
+            .unwrap()
+            .entries
+            .into_iter()
+            .filter(|e| SEED_ADDRESSES.contains(&e.address.as_str()))
+            .map(|e| (e.address, e.score))
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(a, s)| (a.to_string(), s.to_string()))
+            .collect()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Equal profit is broken by market count; excluded addresses never rank
+    /// and open markets do not count towards profit.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_profit_board_breaks_ties_and_excludes_addresses() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        rebuild(&state, LeaderboardPeriod::All, LeaderboardMetric::Profit).await;
+        assert_eq!(
+            board(&state, LeaderboardPeriod::All, LeaderboardMetric::Profit).await,
+            pairs(&[("GLBTEST_B", "1000"), ("GLBTEST_A", "1000"), ("GLBTEST_C", "-700")])
+        );
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_weekly_profit_only_counts_recent_resolutions() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        rebuild(&state, LeaderboardPeriod::Weekly, LeaderboardMetric::Profit).await;
+        assert_eq!(
+            board(&state, LeaderboardPeriod::Weekly, LeaderboardMetric::Profit).await,
+            pairs(&[("GLBTEST_A", "1000"), ("GLBTEST_B", "500")])
+        );
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_volume_board_includes_open_stakes() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        rebuild(&state, LeaderboardPeriod::All, LeaderboardMetric::Volume).await;
+        assert_eq!(
+            board(&state, LeaderboardPeriod::All, LeaderboardMetric::Volume).await,
+            pairs(&[("GLBTEST_C", "5700"), ("GLBTEST_B", "1000"), ("GLBTEST_A", "1000")])
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Only B has the two resolved markets the test rebuild requires.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_winrate_board_applies_minimum_market_count() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        rebuild(&state, LeaderboardPeriod::All, LeaderboardMetric::Winrate).await;
+        assert_eq!(
+            board(&state, LeaderboardPeriod::All, LeaderboardMetric::Winrate).await,
+            pairs(&[("GLBTEST_B", "1.000000")])
+        );
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_leaderboard_handler_serves_snapshot() {
+        let state = build_test_state().await;
+        seed(&state).await;
+        rebuild(&state, LeaderboardPeriod::All, LeaderboardMetric::Volume).await;
+        let _ = state
+            .cache
+            .del(&crate::cache::keys::api_leaderboard("all", "volume"))
+            .await;
+
+        let (status, body) = get_json(
+            app(Arc::clone(&state)),
+            "/leaderboard?period=all&metric=volume&limit=100",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["period"], "all");
+        assert_eq!(body["metric"], "volume");
+        assert!(body["computed_at"].is_string());
+        let entries = body["entries"].as_array().unwrap();
+        let ranks: Vec<i64> = entries.iter().map(|e| e["rank"].as_i64().unwrap()).collect();
+        assert_eq!(ranks, (1..=entries.len() as i64).collect::<Vec<_>>());
+        assert!(entries.iter().all(|e| e["address"] != EXCLUDED));
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Pure-logic unit tests (no I/O)
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_leaderboard_query_defaults_and_parsing() {
+        let uri: axum::http::Uri = "/leaderboard?period=all&metric=winrate&limit=5".parse().unwrap();
+        let axum::extract::Query(query) =
+            axum::extract::Query::<crate::handlers::LeaderboardQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.period, Some(LeaderboardPeriod::All));
+        assert_eq!(query.metric, Some(LeaderboardMetric::Winrate));
+        assert_eq!(query.limit, Some(5));
+
+        let uri: axum::http::Uri = "/leaderboard".parse().unwrap();
+        let axum::extract::Query(query) =
+            axum::extract::Query::<crate::handlers::LeaderboardQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.period.unwrap_or_default(), LeaderboardPeriod::Weekly);
+        assert_eq!(query.metric.unwrap_or_default(), LeaderboardMetric::Profit);
+    }
+
+    #[test]
+    fn test_leaderboard_query_rejects_unknown_metric() {
+        let uri: axum::http::Uri = "/leaderboard?metric=streak".parse().unwrap();
+        assert!(
+            axum::extract::Query::<crate::handlers::LeaderboardQuery>::try_from_uri(&uri).is_err()
+        );
+    }
+
+}
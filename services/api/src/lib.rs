@@ -1,30 +1,170 @@
+// Test files wrap their tests in a module named after the file.
+#![cfg_attr(test, allow(clippy::module_inception))]
+
+pub mod acquisition;
+#[cfg(test)]
+mod acquisition_tests;
+pub mod activity;
+#[cfg(test)]
+mod activity_tests;
+pub mod analytics;
+#[cfg(test)]
+mod analytics_tests;
+pub mod alerting;
+pub mod api_key_usage;
+#[cfg(test)]
+mod api_key_usage_tests;
 pub mod audit;
 pub mod audit_middleware;
+#[cfg(test)]
+mod audit_middleware_tests;
+pub mod backfill;
+#[cfg(test)]
+mod backfill_tests;
 pub mod body_redact;
-pub mod client_ip;
+#[cfg(test)]
+mod cache_admin_tests;
+#[cfg(test)]
+mod cache_ttl_tests;
+pub mod campaign;
+#[cfg(test)]
+mod campaign_tests;
+pub mod category;
+#[cfg(test)]
+mod category_tests;
+pub mod contact;
+#[cfg(test)]
+mod contact_tests;
+pub mod content;
+#[cfg(test)]
+mod content_tests;
 pub mod content_type;
+pub mod contract_read;
+#[cfg(test)]
+mod contract_read_tests;
+pub mod creators;
+#[cfg(test)]
+mod creators_tests;
 pub mod csrf;
 #[cfg(test)]
+mod digest_tests;
+#[cfg(test)]
+mod email_queue_tests;
+#[cfg(test)]
+mod etag_tests;
+#[cfg(test)]
+mod event_invalidation_tests;
+#[cfg(test)]
+mod export_tests;
+#[cfg(test)]
+mod gdpr_tests;
+#[cfg(test)]
+mod health_tests;
+#[cfg(test)]
+mod idempotency_tests;
+#[cfg(test)]
+mod leaderboard_tests;
+#[cfg(test)]
+mod market_ids_tests;
+#[cfg(test)]
+mod market_image_tests;
+#[cfg(test)]
+mod market_list_tests;
+#[cfg(test)]
+mod market_skeleton_tests;
+#[cfg(test)]
+mod market_watch_tests;
+#[cfg(test)]
+mod newsletter_tests;
+#[cfg(test)]
+mod oracle_keeper_tests;
+#[cfg(test)]
+mod platform_stats_tests;
+#[cfg(test)]
+mod price_history_tests;
+#[cfg(test)]
+mod protocol_state_tests;
+#[cfg(test)]
+mod resolution_reminder_tests;
+#[cfg(test)]
 mod resolve_market_tests;
+#[cfg(test)]
+mod retention_tests;
+#[cfg(test)]
+mod risk_tests;
+#[cfg(test)]
+mod signup_guard_tests;
+#[cfg(test)]
+mod stats_history_tests;
+#[cfg(test)]
+mod sync_watch_tests;
+#[cfg(test)]
+mod tx_watch_tests;
+#[cfg(test)]
+mod user_notifications_tests;
+#[cfg(test)]
+mod waitlist_tests;
+#[cfg(test)]
+mod wallet_auth_tests;
+#[cfg(test)]
+mod watchlist_tests;
 pub mod blockchain;
+#[cfg(test)]
+mod blockchain_batch_tests;
 pub mod cache;
 pub mod compression;
 pub mod config;
 pub mod correlation;
 pub mod db;
+#[cfg(test)]
+mod db_pool_metrics_tests;
+pub mod digest;
 pub mod email;
+pub mod etag;
+pub mod export;
+pub mod field_mask;
+pub mod gdpr;
 pub mod handlers;
 pub mod idempotency;
+pub mod leaderboard;
+pub mod log_redact;
+pub mod market_ids;
+pub mod market_image;
+pub mod market_watch;
 pub mod metrics;
 pub mod migrations;
 pub mod newsletter;
+pub mod oracle_keeper;
 pub mod pagination;
+pub mod portfolio;
+pub mod price;
+pub mod platform_stats;
+pub mod price_history;
+pub mod protocol_state;
 pub mod rate_limit;
+pub mod readiness;
+pub mod resolution_reminder;
+pub mod retention;
+pub mod risk;
+pub mod rpc;
 pub mod security;
 pub mod shutdown;
+pub mod signer;
+pub mod signup_guard;
+pub mod stats_history;
+pub mod storage;
+pub mod supervisor;
+pub mod sync_refresh;
+#[cfg(test)]
+mod test_support;
 pub mod tracing_config;
+pub mod tx_watch;
+pub mod user_notifications;
 pub mod validation;
 pub mod versioning;
+pub mod waitlist;
+pub mod wallet_auth;
+pub mod watchlist;
 pub mod openapi_spec;
 
 // Re-export AppState so integration tests can construct it.
@@ -33,26 +173,38 @@ pub use crate::app_state::AppState;
 mod app_state {
     use crate::{
         audit::AuditLogger,
-        blockchain::BlockchainClient,
+        blockchain::NetworkClients,
         cache::RedisCache,
         config::Config,
         db::Database,
         email::{queue::EmailQueue, service::EmailService, webhook::WebhookHandler},
         metrics::Metrics,
         newsletter::IpRateLimiter,
+        readiness::ReadinessCache,
+        supervisor::TaskSupervisor,
     };
+    use tokio_util::sync::CancellationToken;
 
     #[derive(Clone)]
     pub struct AppState {
         pub config: Config,
         pub cache: RedisCache,
         pub db: Database,
-        pub blockchain: BlockchainClient,
+        /// Blockchain client per served network; see [`NetworkClients`].
+        pub networks: NetworkClients,
         pub metrics: Metrics,
         pub newsletter_rate_limiter: IpRateLimiter,
         pub email_service: EmailService,
         pub email_queue: EmailQueue,
         pub webhook_handler: WebhookHandler,
         pub audit_logger: AuditLogger,
+        /// Last `/health/ready` report; see [`crate::readiness`].
+        pub readiness: ReadinessCache,
+        /// Cancelled when the process starts shutting down. Long-running work
+        /// should select on it; see [`crate::shutdown`].
+        pub shutdown: CancellationToken,
+        /// Restart supervision for background loops; its snapshot is part of
+        /// the `/health/ready` report.
+        pub tasks: TaskSupervisor,
     }
 }
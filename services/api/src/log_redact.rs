@@ -0,0 +1,88 @@
+//! log_redact.rs — Masking for personal data and credentials in log fields.
+//!
+//! Log lines are shipped to the aggregator verbatim, so call sites that would
+//! otherwise log an email address or an API key pass it through one of these
+//! helpers first. Masked values stay recognisable enough to correlate with a
+//! support ticket (`a***@example.com`) without identifying anyone.
+
+/// Written in place of a value that has nothing safe to keep.
+pub const MASK: &str = "***";
+
+/// `alice@example.com` → `a***@example.com`. Only the first character of the
+/// local part survives; anything that is not shaped like an address is masked
+/// entirely.
+pub fn mask_email(raw: &str) -> String {
+    match raw.trim().rsplit_once('@') {
+        Some((local, domain)) if !domain.is_empty() => match local.chars().next() {
+            Some(first) => format!("{first}{MASK}@{domain}"),
+            None => MASK.to_string(),
+        },
+        _ => MASK.to_string(),
+    }
+}
+
+/// Mask every email address inside free text, such as a rate-limiter key
+/// (`gdpr_export:email:alice@example.com`) or a nonce built from one.
+pub fn mask_emails_in(text: &str) -> String {
+    const DELIMITERS: &[char] = &[' ', ':', ',', ';', '<', '>', '"', '\'', '(', ')', '='];
+    let mut out = String::with_capacity(text.len());
+    for segment in text.split_inclusive(DELIMITERS) {
+        let (word, delimiter) = match segment.char_indices().last() {
+            Some((i, c)) if DELIMITERS.contains(&c) => segment.split_at(i),
+            _ => (segment, ""),
+        };
+        if word.contains('@') {
+            out.push_str(&mask_email(word));
+        } else {
+            out.push_str(word);
+        }
+        out.push_str(delimiter);
+    }
+    out
+}
+
+/// API keys and tokens keep their first four characters, enough to tell keys
+/// apart in a log without weakening them: `sk-live-abc123` → `sk-l****`.
+pub fn mask_secret(raw: &str) -> String {
+    let prefix: String = raw.chars().take(4).collect();
+    format!("{prefix}****")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn email_keeps_first_character_and_domain() {
+        assert_eq!(mask_email("alice@example.com"), "a***@example.com");
+        assert_eq!(mask_email("  Bob.Smith+news@mail.example.org "), "B***@mail.example.org");
+    }
+
+    #[test]
+    fn malformed_email_is_masked_entirely() {
+        assert_eq!(mask_email("not-an-email"), MASK);
+        assert_eq!(mask_email("@example.com"), MASK);
+        assert_eq!(mask_email("alice@"), MASK);
+        assert_eq!(mask_email(""), MASK);
+    }
+
+    #[test]
+    fn embedded_emails_are_masked_in_place() {
+        assert_eq!(
+            mask_emails_in("gdpr_export:email:alice@example.com"),
+            "gdpr_export:email:a***@example.com"
+        );
+        assert_eq!(
+            mask_emails_in("msg-1 bounce bob@example.org, carol@example.net"),
+            "msg-1 bounce b***@example.org, c***@example.net"
+        );
+        assert_eq!(mask_emails_in("contact:ip:203.0.113.7"), "contact:ip:203.0.113.7");
+    }
+
+    #[test]
+    fn secret_keeps_only_a_short_prefix() {
+        assert_eq!(mask_secret("sk-live-abc123"), "sk-l****");
+        assert_eq!(mask_secret("ab"), "ab****");
+        assert_eq!(mask_secret(""), "****");
+    }
+}
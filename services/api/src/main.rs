@@ -1,17 +1,34 @@
 use predictiq_api::{
     audit::AuditLogger,
-    blockchain::BlockchainClient,
-    cache::RedisCache,
+    blockchain::{BlockchainClient, NetworkClients},
+    cache::{warming, RedisCache},
     config::{Config, CorsConfig},
     csrf::{CsrfConfig, csrf_protection_middleware},
     db::Database,
-    email::{queue::EmailQueue, service::EmailService, webhook::WebhookHandler},
+    email::{queue::EmailQueue, service::EmailService, webhook::{self, WebhookHandler}},
+    alerting,
+    analytics,
+    api_key_usage::{self, UsageMeter},
+    backfill,
+    campaign,
+    digest,
     handlers,
+    leaderboard,
+    market_ids,
+    price,
+    price_history,
+    platform_stats,
+    resolution_reminder,
+    retention,
+    risk,
+    stats_history,
     idempotency, correlation, versioning, validation, rate_limit, audit_middleware,
-    metrics::Metrics,
-    newsletter::IpRateLimiter,
-    security::{self, ApiKeyAuth, IpWhitelist, MetricsAuthConfig, RateLimiter, RequireHttps},
+    metrics::{self, Metrics},
+    newsletter::{self, IpRateLimiter},
+    openapi_spec,
+    security::{self, ApiKeyAuth, IpWhitelist, MetricsAuthConfig, RequireHttps},
     shutdown::{self as shutdown, wait_for_signal, ShutdownCoordinator},
+    supervisor::TaskSupervisor,
     tracing_config, compression,
     AppState,
 };
@@ -19,12 +36,15 @@ use predictiq_api::{
 use std::{sync::Arc, time::Duration};
 
 use axum::{
+    extract::DefaultBodyLimit,
     http::{HeaderName, HeaderValue, Method},
     middleware,
     routing::{get, post},
     Router,
 };
+use secrecy::ExposeSecret;
 use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 /// Read `SHUTDOWN_TIMEOUT_SECS` from the environment; default 30 s.
@@ -36,6 +56,43 @@ fn shutdown_timeout() -> Duration {
     Duration::from_secs(secs)
 }
 
+/// Build the client for one network, probe its RPC endpoint and validate the
+/// passphrase. Runs once for the primary and once per `ADDITIONAL_NETWORKS`
+/// entry, so every network gets the same startup checks.
+async fn connect_network(
+    config: &Config,
+    cache: RedisCache,
+    db: Database,
+    metrics: Metrics,
+) -> anyhow::Result<BlockchainClient> {
+    let blockchain = BlockchainClient::new(config, cache, db, metrics)?;
+
+    // ── RPC reachability probe (issue #918) ───────────────────────────────────
+    match blockchain.probe_rpc_reachability().await {
+        Ok(()) => tracing::info!(
+            network = config.network_name(),
+            url = %config.blockchain_rpc_url,
+            "RPC endpoint is reachable"
+        ),
+        Err(e) => {
+            if config.is_production() {
+                return Err(anyhow::anyhow!(
+                    "RPC endpoint for {} is unreachable in production: {e}",
+                    config.network_name()
+                ));
+            }
+            tracing::warn!(
+                network = config.network_name(),
+                url = %config.blockchain_rpc_url,
+                error = %e,
+                "RPC endpoint unreachable — blockchain features will be unavailable"
+            );
+        }
+    }
+    blockchain.validate_network_passphrase().await?;
+    Ok(blockchain)
+}
+
 /// Build a [`CorsLayer`] from the application's [`CorsConfig`].
 ///
 /// When `dev_mode` is `true` the layer is fully permissive and a warning is
@@ -85,11 +142,32 @@ fn build_cors_layer(cfg: &CorsConfig) -> CorsLayer {
 async fn main() -> anyhow::Result<()> {
     let config = Config::from_env();
 
+    // `--check-config` (or CHECK_CONFIG=true) validates the environment and
+    // exits without connecting to anything, so CI can gate a deploy on it.
+    let check_only = std::env::args().any(|arg| arg == "--check-config")
+        || std::env::var("CHECK_CONFIG")
+            .ok()
+            .and_then(|s| s.parse::<bool>().ok())
+            .unwrap_or(false);
+    if check_only {
+        match config.validate() {
+            Ok(()) => {
+                println!("Configuration OK");
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+    }
+
     tracing_config::init_tracing(
         "predictiq-api",
         env!("CARGO_PKG_VERSION"),
         config.otlp_endpoint.clone(),
         config.trace_sample_rate,
+        config.log_format,
     )?;
 
     // Validate required configuration before proceeding
@@ -101,6 +179,8 @@ async fn main() -> anyhow::Result<()> {
     // Warn at startup if the SendGrid API key is older than 90 days.
     config.warn_if_sendgrid_key_stale();
 
+    tracing::info!(cache_ttls = %config.cache_ttls.summary(), "effective cache TTLs");
+
     let metrics = Metrics::new()?;
 
     // Warn at startup if the OTLP endpoint is unreachable so operators know
@@ -111,48 +191,43 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
-    let cache = RedisCache::new(&config.redis_url).await?;
-    let db = Database::new(&config.database_url, cache.clone(), metrics.clone(), &config.db_pool).await?;
+    let cache = RedisCache::new_with_metrics(&config.redis_url, metrics.clone()).await?;
+    let db = Database::new(config.db_credentials.to_connection_string().expose_secret(), cache.clone(), metrics.clone(), &config.db_pool)
+        .await?
+        .with_cache_ttls(config.cache_ttls.clone());
     let db_arc = Arc::new(db.clone());
-    let blockchain = BlockchainClient::new(&config, cache.clone(), db.clone(), metrics.clone())?;
-
-    // ── RPC reachability probe (issue #918) ───────────────────────────────────
-    match blockchain.probe_rpc_reachability().await {
-        Ok(()) => tracing::info!(
-            url = %config.blockchain_rpc_url,
-            "RPC endpoint is reachable"
-        ),
-        Err(e) => {
-            if config.is_production() {
-                return Err(anyhow::anyhow!(
-                    "BLOCKCHAIN_RPC_URL is unreachable in production: {e}"
-                ));
-            }
-            tracing::warn!(
-                url = %config.blockchain_rpc_url,
-                error = %e,
-                "RPC endpoint unreachable — blockchain features will be unavailable"
-            );
-        }
+    let mut networks = NetworkClients::single(
+        connect_network(&config, cache.clone(), db.clone(), metrics.clone()).await?,
+    );
+    for endpoint in &config.additional_networks {
+        let network_config = config.for_network(endpoint);
+        networks = networks.with(
+            connect_network(&network_config, cache.clone(), db.clone(), metrics.clone()).await?,
+        )?;
     }
-    blockchain.validate_network_passphrase().await?;
+    tracing::info!(
+        primary = networks.primary_name(),
+        served = ?networks.names(),
+        "blockchain networks configured"
+    );
 
     let email_service = EmailService::new(config.clone())?;
-    let email_queue = EmailQueue::new(cache.clone(), db.clone());
+    let email_queue = EmailQueue::new(db.clone());
     let webhook_handler = WebhookHandler::new(db.clone(), cache.clone(), config.webhook_replay_window_secs);
     let audit_logger = AuditLogger::new(db.pool());
 
     let bind_addr = config.bind_addr;
     let require_https = config.require_https;
 
-    let rate_limiter = Arc::new(RateLimiter::new());
     // Use DB-backed ApiKeyAuth for zero-downtime key rotation (issue #892).
-    let api_key_auth = Arc::new(ApiKeyAuth::new_with_db(
-        config.api_keys.clone(),
-        db_arc.clone(),
-    ));
+    // Requests made with database keys are metered against their monthly quota.
+    let api_key_auth = Arc::new(
+        ApiKeyAuth::new_with_db(config.api_keys.clone(), db_arc.clone())
+            .with_usage(UsageMeter::new(cache.clone(), metrics.clone())),
+    );
     let ip_whitelist = Arc::new(IpWhitelist::new(config.admin_whitelist_ips.clone()));
-    let config_trust_proxy = config.trust_proxy;
+    let trusted_proxies =
+        security::TrustedProxies::new(config.trust_proxy, config.trusted_proxy_cidrs.clone());
 
     // CSRF config: derive allowed origins from the CORS config so the two
     // lists stay in sync.
@@ -166,56 +241,81 @@ async fn main() -> anyhow::Result<()> {
     // worker shutdown timeout.  Losing in-flight emails is more costly than
     // delaying exit, so the email drain timeout defaults to 60 s.
     //
-    // Blockchain workers (sync + tx-monitor) use the global coordinator.
+    // Blockchain workers (sync + tx-monitor per network) use the global coordinator.
     // The newsletter cleanup task is fire-and-forget (low-risk) so it is not tracked.
-    let email_coordinator = ShutdownCoordinator::new(1);
-    let coordinator = ShutdownCoordinator::new(2);
-
-    // ── Rate-limiter cleanup (fire-and-forget) ────────────────────────────────
-    let rate_limiter_cleanup = rate_limiter.clone();
-    let metrics_rate_limiter = metrics.clone();
-    tokio::spawn(async move {
-        const WORKER_NAME: &str = "rate_limiter_cleanup";
-
-        metrics_rate_limiter.set_worker_status(WORKER_NAME, true);
-
-        let mut interval = tokio::time::interval(Duration::from_secs(300));
-        let mut heartbeat_interval = tokio::time::interval(Duration::from_secs(30));
-        heartbeat_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    rate_limiter_cleanup.cleanup().await;
-                }
-                _ = heartbeat_interval.tick() => {
-                    metrics_rate_limiter.set_worker_status(WORKER_NAME, true);
-                }
-            }
-        }
-    });
+    //
+    // Both coordinators hang off the root token, so a signal stops workers from
+    // picking up new work while in-flight HTTP requests drain.
+    let shutdown_token = CancellationToken::new();
+    let email_coordinator = ShutdownCoordinator::with_parent(&shutdown_token, 1);
+    let coordinator = ShutdownCoordinator::with_parent(&shutdown_token, 2 * networks.names().len());
 
+    let tasks = TaskSupervisor::new(metrics.clone());
 
     let state = Arc::new(AppState {
         config,
         cache: cache.clone(),
         db,
-        blockchain,
+        networks,
         metrics,
         newsletter_rate_limiter: IpRateLimiter::new(cache.clone()),
         email_service: email_service.clone(),
         email_queue: email_queue.clone(),
         webhook_handler: webhook_handler.clone(),
         audit_logger,
+        readiness: Default::default(),
+        shutdown: shutdown_token.clone(),
+        tasks,
     });
 
     // ── Blockchain background workers ─────────────────────────────────────────
-    // Restore watched transactions from the database before workers start polling.
-    if let Err(e) = state.blockchain.load_watched_transactions().await {
-        tracing::warn!(error = %e, "failed to restore watched transactions from database; starting with empty watch list");
+    // Seed the Redis watch set from the database before workers start polling.
+    // Each network gets its own sync and tx-monitor workers.
+    let mut _blockchain_handles = Vec::new();
+    for client in state.networks.iter() {
+        let include_unscoped = client.network() == state.networks.primary_name();
+        if let Err(e) = client.load_watched_transactions(include_unscoped).await {
+            tracing::warn!(network = client.network(), error = %e, "failed to restore watched transactions from database; relying on the Redis watch set");
+        }
+        // Fatal, unlike the restore above: the mappings are only seeded while
+        // the network has none, which stops holding once the worker runs.
+        if include_unscoped {
+            let added = market_ids::map_indexed_markets(&state.db, client)
+                .await
+                .map_err(|e| anyhow::anyhow!("mapping indexed markets to their public ids: {e}"))?;
+            if added > 0 {
+                tracing::info!(added, "mapped indexed markets to their public ids");
+            }
+        }
+        _blockchain_handles.extend(Arc::new(client.clone()).start_background_tasks(&coordinator, &state.tasks));
     }
-    let _blockchain_handles = Arc::new(state.blockchain.clone())
-        .start_background_tasks(&coordinator);
+
+    // ── Leaderboard aggregation (supervised) ──────────────────────────────────
+    // Rebuilds the leaderboard_snapshots boards from indexed chain events.
+    // The first tick fires immediately so boards exist shortly after deploy.
+    let leaderboard_state = state.clone();
+    let leaderboard_token = state.shutdown.clone();
+    state.tasks.spawn("leaderboard_aggregation", leaderboard_token.clone(), move || {
+        leaderboard::run(leaderboard_state.clone(), leaderboard_token.clone())
+    });
+
+    // ── Price history retention (supervised) ──────────────────────────────────
+    // Rolls raw price points older than 30 days into hourly rows. Runs every
+    // hour; a failed run leaves the raw rows for the next tick.
+    let price_retention_state = state.clone();
+    let price_retention_token = state.shutdown.clone();
+    state.tasks.spawn("price_history_retention", price_retention_token.clone(), move || {
+        price_history::run_retention(price_retention_state.clone(), price_retention_token.clone())
+    });
+
+    // ── Analytics rollup (supervised) ─────────────────────────────────────────
+    // Recomputes analytics_daily for the last few days. Each run overwrites the
+    // days it covers, so a failed run is simply repaired by the next tick.
+    let analytics_state = state.clone();
+    let analytics_token = state.shutdown.clone();
+    state.tasks.spawn("analytics_rollup", analytics_token.clone(), move || {
+        analytics::run_rollup(analytics_state.clone(), analytics_token.clone())
+    });
 
     // ── Newsletter cleanup (fire-and-forget) ──────────────────────────────────
     let db_cleanup = state.clone();
@@ -233,9 +333,13 @@ async fn main() -> anyhow::Result<()> {
         loop {
             tokio::select! {
                 _ = interval.tick() => {
-                    let ttl = db_cleanup.config.newsletter_token_ttl_secs;
+                    // Keep expired rows around long enough to answer "expired"
+                    // (and allow a resend) instead of "not found".
+                    let retention = newsletter::PENDING_RETENTION
+                        .as_secs()
+                        .max(db_cleanup.config.newsletter_token_ttl_secs);
                     let batch = db_cleanup.config.newsletter_cleanup_batch_size;
-                    match db_cleanup.db.newsletter_delete_expired_pending(ttl, batch).await {
+                    match db_cleanup.db.newsletter_delete_expired_pending(retention, batch).await {
                         Ok(n) if n > 0 => tracing::info!("[newsletter] cleaned up {n} expired pending subscriptions"),
                         Err(e) => tracing::warn!("[newsletter] cleanup error: {e}"),
                         _ => {}
@@ -328,8 +432,142 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
-    if let Err(err) = handlers::warm_critical_caches(state.clone()).await {
-        tracing::warn!("cache warming skipped: {err}");
+    // ── Cache warming (supervised) ────────────────────────────────────────────
+    // Refreshes hot entries shortly before they expire; the first tick warms
+    // everything at boot.
+    if state.config.cache_warm_targets.is_empty() {
+        tracing::info!("cache warming disabled: CACHE_WARM_TARGETS is empty");
+    } else {
+        let warm_state = state.clone();
+        let warm_token = state.shutdown.clone();
+        state.tasks.spawn("cache_warming", warm_token.clone(), move || {
+            warming::run(warm_state.clone(), warm_token.clone())
+        });
+    }
+
+    // ── USD prices (supervised) ───────────────────────────────────────────────
+    // Keeps the PRICE_TOKENS quotes fresh for volume_usd fields. Without a
+    // source every volume_usd is null.
+    if state.config.price_source_url.is_none() || state.config.price_tokens.is_empty() {
+        tracing::info!("price refresh disabled: PRICE_SOURCE_URL or PRICE_TOKENS is unset");
+    } else {
+        let price_state = state.clone();
+        let price_token = state.shutdown.clone();
+        state.tasks.spawn("price_refresh", price_token.clone(), move || {
+            price::run(price_state.clone(), price_token.clone())
+        });
+    }
+
+    // ── Platform stats history (supervised) ───────────────────────────────────
+    // Rolls up platform_stats_daily at startup (backfilling an empty table)
+    // and again shortly after each UTC midnight.
+    let stats_state = state.clone();
+    let stats_token = state.shutdown.clone();
+    state.tasks.spawn("stats_history_rollup", stats_token.clone(), move || {
+        stats_history::run(stats_state.clone(), stats_token.clone())
+    });
+
+    // ── Platform stats snapshots (supervised) ─────────────────────────────────
+    // Derives platform statistics from indexed data hourly, for the fallback
+    // when the contract read fails and for the divergence check.
+    let snap_state = state.clone();
+    let snap_token = state.shutdown.clone();
+    state.tasks.spawn("platform_stats_snapshot", snap_token.clone(), move || {
+        platform_stats::run(snap_state.clone(), snap_token.clone())
+    });
+
+    // ── DB pool sampler (supervised) ──────────────────────────────────────────
+    // Refreshes the pool gauges and acquire-wait histogram every
+    // DB_POOL_SAMPLE_INTERVAL_SECS.
+    let pool_db = state.db.clone();
+    let pool_token = state.shutdown.clone();
+    state.tasks.spawn("db_pool_sampler", pool_token.clone(), move || {
+        pool_db.clone().run_pool_sampler(pool_token.clone())
+    });
+
+    // ── API key usage flush (supervised) ──────────────────────────────────────
+    // Copies the per-key daily request counters from Redis into api_key_usage
+    // every hour, and once more on shutdown.
+    let usage_state = state.clone();
+    let usage_token = state.shutdown.clone();
+    state.tasks.spawn("api_key_usage_flush", usage_token.clone(), move || {
+        api_key_usage::run(usage_state.clone(), usage_token.clone())
+    });
+
+    // ── Newsletter campaigns (supervised) ─────────────────────────────────────
+    // Enqueues launched campaigns in throttled batches; idles when none is
+    // sending.
+    let campaign_state = state.clone();
+    let campaign_token = state.shutdown.clone();
+    state.tasks.spawn("campaign_sender", campaign_token.clone(), move || {
+        campaign::run(campaign_state.clone(), campaign_token.clone())
+    });
+
+    // ── Weekly digest (supervised) ────────────────────────────────────────────
+    // Enqueues the featured-markets digest at each DIGEST_SCHEDULE slot, and
+    // at startup when this week's slot passed without a send.
+    match state.config.digest_schedule {
+        None => tracing::info!("weekly digest disabled: DIGEST_SCHEDULE is unset"),
+        Some(schedule) => {
+            let digest_state = state.clone();
+            let digest_token = state.shutdown.clone();
+            state.tasks.spawn("digest_sender", digest_token.clone(), move || {
+                digest::run(digest_state.clone(), schedule, digest_token.clone())
+            });
+        }
+    }
+
+    // ── Resolution reminders (supervised) ─────────────────────────────────────
+    // Emails creators of unresolved markets as each resolution deadline comes
+    // within RESOLUTION_REMINDER_LEAD_HOURS, once per lead time.
+    let reminder_state = state.clone();
+    let reminder_token = state.shutdown.clone();
+    state.tasks.spawn("resolution_reminder", reminder_token.clone(), move || {
+        resolution_reminder::run(reminder_state.clone(), reminder_token.clone())
+    });
+
+    // ── Retention (supervised) ────────────────────────────────────────────────
+    // Deletes or anonymizes rows past their RETENTION_POLICIES age nightly, in
+    // bounded batches.
+    if state.config.retention_policies.is_empty() {
+        tracing::info!("retention disabled: RETENTION_POLICIES is empty");
+    } else {
+        let retention_state = state.clone();
+        let retention_token = state.shutdown.clone();
+        state.tasks.spawn("retention", retention_token.clone(), move || {
+            retention::run(retention_state.clone(), retention_token.clone())
+        });
+    }
+
+    // ── Risk monitoring (supervised) ──────────────────────────────────────────
+    // Opens and clears concentration / inflow alerts for the primary network
+    // every RISK_CHECK_INTERVAL_SECS and pushes new ones to ALERT_WEBHOOK_URL.
+    let risk_state = state.clone();
+    let risk_token = state.shutdown.clone();
+    state.tasks.spawn("risk_monitor", risk_token.clone(), move || {
+        risk::run(risk_state.clone(), risk_token.clone())
+    });
+
+    // ── Indexer backfill (supervised) ─────────────────────────────────────────
+    // Works through jobs queued via POST /api/v1/admin/indexer/backfill,
+    // resuming any a previous instance left unfinished.
+    let backfill_state = state.clone();
+    let backfill_token = state.shutdown.clone();
+    state.tasks.spawn("indexer_backfill", backfill_token.clone(), move || {
+        backfill::run(backfill_state.clone(), backfill_token.clone())
+    });
+
+    // ── Blockchain alerts (supervised) ────────────────────────────────────────
+    // Posts to ALERT_WEBHOOK_URL when a network's RPC turns unhealthy, the
+    // indexer stalls or RPC errors spike, and again when each recovers.
+    if state.config.alert_webhook_url.is_none() {
+        tracing::info!("blockchain alerts disabled: ALERT_WEBHOOK_URL is unset");
+    } else {
+        let alert_state = state.clone();
+        let alert_token = state.shutdown.clone();
+        state.tasks.spawn("blockchain_alerts", alert_token.clone(), move || {
+            alerting::run(alert_state.clone(), alert_token.clone())
+        });
     }
 
     // ── CORS ──────────────────────────────────────────────────────────────────
@@ -347,16 +585,72 @@ async fn main() -> anyhow::Result<()> {
         .route("/health/dependencies", get(handlers::health_dependencies))
         .with_state(state.clone());
 
+    // The OpenAPI document is generated once, here, and served from memory.
+    // The Swagger UI that renders it is for non-production environments.
+    let spec_bytes = openapi_spec::openapi_json().len();
+    tracing::info!(bytes = spec_bytes, "OpenAPI document generated");
+    let mut docs_routes = Router::new()
+        .route("/api/v1/openapi.json", get(handlers::openapi_document));
+    if state.config.swagger_ui_enabled {
+        docs_routes = docs_routes
+            .route("/api/v1/docs", get(handlers::swagger_ui))
+            .route(
+                "/api/v1/docs/swagger-initializer.js",
+                get(handlers::swagger_ui_initializer),
+            );
+        tracing::info!("Swagger UI enabled at /api/v1/docs");
+    }
+    let docs_routes = docs_routes
+        .layer(middleware::from_fn(correlation::correlation_id_middleware))
+        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::global_rate_limit_middleware,
+        ));
+
     let public_routes = Router::new()
         .route("/api/v1/blockchain/health", get(handlers::blockchain_health))
+        .route("/api/v1/blockchain/protocol-state", get(handlers::blockchain_protocol_state))
+        .route("/api/v1/blockchain/call/:fn_name", get(handlers::blockchain_contract_call))
         .route("/api/v1/blockchain/markets/:market_id", get(handlers::blockchain_market_data))
         .route("/api/v1/blockchain/stats", get(handlers::blockchain_platform_stats))
         .route("/api/v1/blockchain/users/:user/bets", get(handlers::blockchain_user_bets))
         .route("/api/v1/blockchain/oracle/:market_id", get(handlers::blockchain_oracle_result))
         .route("/api/v1/blockchain/tx/:tx_hash", get(handlers::blockchain_tx_status))
+        .route("/api/v1/blockchain/batch", post(handlers::blockchain_batch))
         .route("/api/v1/statistics", get(handlers::statistics))
+        .route("/api/v1/statistics/history", get(handlers::statistics_history))
+        .route("/api/v1/markets", get(handlers::list_markets))
         .route("/api/v1/markets/featured", get(handlers::featured_markets))
+        .route("/api/v1/categories", get(handlers::categories))
+        .route("/api/v1/markets/:market_id", get(handlers::market_detail))
+        .route("/api/v1/markets/:market_id/history", get(handlers::market_history))
+        .route("/api/v1/markets/:market_id/image", get(handlers::market_image))
+        .route("/api/v1/users/:address/portfolio", get(handlers::user_portfolio))
+        .route("/api/v1/users/:address/watchlist", get(handlers::watchlist_get))
+        .route(
+            "/api/v1/users/:address/watchlist/:market_id",
+            axum::routing::put(handlers::watchlist_put).delete(handlers::watchlist_delete),
+        )
+        .route(
+            "/api/v1/users/:address/notifications",
+            get(handlers::notification_settings_get).put(handlers::notification_settings_put),
+        )
+        .route("/api/v1/auth/challenge", get(handlers::auth_challenge))
+        .route("/api/v1/auth/verify", post(handlers::auth_verify))
+        .route("/api/v1/auth/refresh", post(handlers::auth_refresh))
+        .route("/api/v1/leaderboard", get(handlers::leaderboard))
+        .route("/api/v1/creators/top", get(handlers::top_creators))
+        .route("/api/v1/creators/:address", get(handlers::creator_profile))
+        .route("/api/v1/activity", get(handlers::activity_feed))
         .route("/api/v1/content", get(handlers::content))
+        .route("/api/v1/content/:slug", get(handlers::content_page))
+        // Per wallet / market limit across all IPs; a route layer so it sees
+        // the matched path parameters.
+        .route_layer(middleware::from_fn_with_state(
+            rate_limit::ResourceRateLimitState::from_app_state(&state),
+            rate_limit::resource_rate_limit_middleware,
+        ))
         .layer(middleware::from_fn(correlation::correlation_id_middleware))
         .layer(TraceLayer::new_for_http())
         .layer(middleware::from_fn_with_state(
@@ -369,6 +663,24 @@ async fn main() -> anyhow::Result<()> {
         ))
         .with_state(state.clone());
 
+    // ── Transaction relay (partner API keys) ──────────────────────────────────
+    // These proxy straight to the Soroban RPC, so they require an API key, are
+    // rate limited per key, and accept at most one envelope-sized body. No
+    // signing happens here: callers submit envelopes they signed themselves.
+    let tx_routes = Router::new()
+        .route("/api/v1/tx/simulate", post(handlers::tx_simulate))
+        .route("/api/v1/tx/submit", post(handlers::tx_submit))
+        .layer(DefaultBodyLimit::max(handlers::TX_BODY_LIMIT_BYTES))
+        .layer(middleware::from_fn(validation::content_type_validation_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::tx_rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(api_key_auth.clone(), security::api_key_middleware))
+        .layer(middleware::from_fn(correlation::correlation_id_middleware))
+        .layer(TraceLayer::new_for_http())
+        .with_state(state.clone());
+
     let metrics_auth_config = Arc::new(MetricsAuthConfig::new(
         state.config.metrics_public,
         state.config.metrics_allowlist_ips.clone(),
@@ -387,22 +699,66 @@ async fn main() -> anyhow::Result<()> {
     let newsletter_routes = Router::new()
         .route("/api/v1/newsletter/subscribe", post(handlers::newsletter_subscribe))
         .route("/api/v1/newsletter/confirm", get(handlers::newsletter_confirm))
+        .route(
+            "/api/v1/newsletter/resend-confirmation",
+            post(handlers::newsletter_resend_confirmation),
+        )
         .route("/api/v1/newsletter/unsubscribe", get(handlers::newsletter_unsubscribe))
+        .route(
+            "/api/v1/newsletter/preferences",
+            get(handlers::newsletter_preferences_get).put(handlers::newsletter_preferences_update),
+        )
         .route("/api/v1/newsletter/gdpr/export", get(handlers::newsletter_gdpr_export))
         .route("/api/v1/newsletter/gdpr/delete", axum::routing::delete(handlers::newsletter_gdpr_delete))
+        // Market watches, transaction watches, and the waitlist collect email
+        // addresses too, so they share the newsletter group's CSRF and per-IP
+        // abuse controls.
+        .route("/api/v1/markets/:market_id/watches", post(handlers::market_watch_create))
+        .route("/api/v1/blockchain/tx/:tx_hash/watch", post(handlers::blockchain_tx_watch))
+        .route("/api/v1/watches/unsubscribe", get(handlers::market_watch_unsubscribe))
+        .route("/api/v1/watches/:token", axum::routing::delete(handlers::market_watch_delete))
+        .route("/api/v1/notifications/confirm", get(handlers::notification_email_confirm))
+        .route("/api/v1/notifications/unsubscribe", get(handlers::notification_unsubscribe))
+        .route("/api/v1/waitlist", post(handlers::waitlist_join))
+        .route("/api/v1/waitlist/status", get(handlers::waitlist_status))
         .layer(middleware::from_fn(correlation::correlation_id_middleware))
         .layer(TraceLayer::new_for_http())
         .layer(middleware::from_fn_with_state(state.clone(), idempotency::idempotency_middleware))
         .layer(middleware::from_fn(validation::content_type_validation_middleware))
         .layer(middleware::from_fn(validation::request_size_validation_middleware))
         // CSRF defense-in-depth: validate Origin/Referer on state-changing requests.
-        .layer(middleware::from_fn_with_state(csrf_config, csrf_protection_middleware))
+        .layer(middleware::from_fn_with_state(csrf_config.clone(), csrf_protection_middleware))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             rate_limit::newsletter_rate_limit_middleware,
         ))
         .with_state(state.clone());
 
+    // The contact form applies its own, stricter per-IP limit in the handler
+    // (CONTACT_RATE_LIMIT_MAX), so it stays out of the newsletter group.
+    let contact_routes = Router::new()
+        .route("/api/v1/contact", post(handlers::contact_submit))
+        .layer(middleware::from_fn(correlation::correlation_id_middleware))
+        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn_with_state(state.clone(), idempotency::idempotency_middleware))
+        .layer(middleware::from_fn(validation::content_type_validation_middleware))
+        .layer(middleware::from_fn(validation::request_size_validation_middleware))
+        .layer(middleware::from_fn_with_state(csrf_config.clone(), csrf_protection_middleware))
+        .with_state(state.clone());
+
+    // Analytics batches are capped twice: the route body limit bounds what is
+    // buffered, and the handler bounds events per batch and per-IP batches
+    // per window (ANALYTICS_RATE_LIMIT_MAX).
+    let analytics_routes = Router::new()
+        .route("/api/v1/analytics/events", post(handlers::analytics_ingest))
+        .layer(DefaultBodyLimit::max(analytics::BODY_LIMIT_BYTES))
+        .layer(middleware::from_fn(correlation::correlation_id_middleware))
+        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn(validation::content_type_validation_middleware))
+        .layer(middleware::from_fn(validation::request_size_validation_middleware))
+        .layer(middleware::from_fn_with_state(csrf_config, csrf_protection_middleware))
+        .with_state(state.clone());
+
     // ── Webhook routes (provider-signed, no admin auth required) ──────────────┐
     // Provider webhooks like SendGrid are authenticated via cryptographic       │
     // signatures in request headers, NOT via API keys. This is the correct      │
@@ -410,7 +766,7 @@ async fn main() -> anyhow::Result<()> {
     // and verifies the signature matches known credentials.                     │
     //                                                                           │
     // Middleware stack (order matters — applied inside-out):                    │
-    // 1. sendgrid_webhook_middleware: verify provider signature                │
+    // 1. webhook::signature_middleware: verify ECDSA P-256 signature            │
     // 2. request_size_validation_middleware: prevent payload bombs              │
     // 3. security_headers_middleware: add security headers                      │
     // 4. correlation_id_middleware: request tracing                             │
@@ -426,11 +782,16 @@ async fn main() -> anyhow::Result<()> {
         .layer(middleware::from_fn(validation::request_size_validation_middleware))
         .layer(middleware::from_fn(security::security_headers_middleware))
         .layer(middleware::from_fn_with_state(
-            security::WebhookConfig {
-                secret: state.config.sendgrid_webhook_secret.clone(),
-                replay_window_secs: state.config.webhook_replay_window_secs,
+            webhook::WebhookSignatureConfig {
+                public_key: state
+                    .config
+                    .sendgrid_webhook_public_key
+                    .as_deref()
+                    .map(webhook::parse_public_key)
+                    .transpose()?,
+                allow_unsigned: state.config.sendgrid_webhook_allow_unsigned,
             },
-            security::sendgrid_webhook_middleware,
+            webhook::signature_middleware,
         ))
         .layer(middleware::from_fn(correlation::correlation_id_middleware))
         .layer(TraceLayer::new_for_http())
@@ -462,12 +823,132 @@ async fn main() -> anyhow::Result<()> {
             get(handlers::email_queue_stats),
         )
         .route(
-            "/api/v1/email/queue/dead-letter",
+            "/api/v1/admin/email/dead-letter",
             get(handlers::email_dead_letter_list),
         )
         .route(
-            "/api/v1/email/queue/dead-letter/:job_id/requeue",
-            post(handlers::email_dead_letter_requeue),
+            "/api/v1/admin/email/dead-letter/:job_id/retry",
+            post(handlers::email_dead_letter_retry),
+        )
+        .route(
+            "/api/v1/admin/email/blocked-domains",
+            get(handlers::blocked_domains_list).post(handlers::blocked_domain_create),
+        )
+        .route(
+            "/api/v1/admin/email/blocked-domains/:id",
+            axum::routing::delete(handlers::blocked_domain_delete),
+        )
+        .route(
+            "/api/v1/gdpr/export",
+            get(handlers::gdpr_export),
+        )
+        .route(
+            "/api/v1/gdpr/delete",
+            axum::routing::delete(handlers::gdpr_delete),
+        )
+        .route(
+            "/api/v1/admin/campaigns",
+            post(handlers::campaign_create),
+        )
+        .route(
+            "/api/v1/admin/campaigns/:id",
+            get(handlers::campaign_get),
+        )
+        .route(
+            "/api/v1/admin/campaigns/:id/launch",
+            post(handlers::campaign_launch),
+        )
+        .route(
+            "/api/v1/admin/campaigns/:id/cancel",
+            post(handlers::campaign_cancel),
+        )
+        .route(
+            "/api/v1/admin/digest/preview",
+            get(handlers::digest_preview),
+        )
+        .route(
+            "/api/v1/admin/contact",
+            get(handlers::contact_list),
+        )
+        .route(
+            "/api/v1/admin/contact/:id/status",
+            post(handlers::contact_set_status),
+        )
+        .route(
+            "/api/v1/admin/analytics/summary",
+            get(handlers::analytics_summary),
+        )
+        .route(
+            "/api/v1/admin/analytics/acquisition",
+            get(handlers::analytics_acquisition),
+        )
+        .route(
+            "/api/v1/admin/analytics/acquisition.csv",
+            get(handlers::analytics_acquisition_csv),
+        )
+        .route(
+            "/api/v1/admin/waitlist/invite",
+            post(handlers::waitlist_invite),
+        )
+        .route(
+            "/api/v1/admin/waitlist/stats",
+            get(handlers::waitlist_stats),
+        )
+        .route(
+            "/api/v1/admin/waitlist/export.csv",
+            get(handlers::waitlist_export_csv),
+        )
+        .route(
+            "/api/v1/admin/newsletter/export.csv",
+            get(handlers::newsletter_export_csv),
+        )
+        .route(
+            "/api/v1/admin/events/export.csv",
+            get(handlers::chain_events_export_csv),
+        )
+        .route(
+            "/api/v1/admin/indexer/backfill",
+            post(handlers::backfill_create),
+        )
+        .route(
+            "/api/v1/admin/indexer/backfill/:id",
+            get(handlers::backfill_get),
+        )
+        .route(
+            "/api/v1/admin/categories",
+            post(handlers::category_create),
+        )
+        .route(
+            "/api/v1/admin/categories/:slug",
+            axum::routing::patch(handlers::category_update).delete(handlers::category_delete),
+        )
+        .route(
+            "/api/v1/admin/content",
+            post(handlers::content_create),
+        )
+        .route(
+            "/api/v1/admin/content/:id",
+            axum::routing::put(handlers::content_update).delete(handlers::content_delete),
+        )
+        .route(
+            "/api/v1/admin/content/:id/publish",
+            post(handlers::content_publish),
+        )
+        .route(
+            "/api/v1/admin/cache/keys",
+            get(handlers::cache_keys),
+        )
+        .route(
+            "/api/v1/admin/cache/entry",
+            get(handlers::cache_entry),
+        )
+        .route(
+            "/api/v1/admin/cache",
+            axum::routing::delete(handlers::cache_invalidate),
+        )
+        .route(
+            "/api/v1/admin/audit",
+            get(handlers::admin_audit_list),
         )
         .route(
             "/api/v1/audit/logs",
@@ -477,6 +958,10 @@ async fn main() -> anyhow::Result<()> {
             "/api/v1/audit/statistics",
             get(handlers::audit_statistics),
         )
+        .route(
+            "/api/v1/admin/oracle/:market_id/result",
+            post(handlers::oracle_submit_result),
+        )
         // ── API key rotation endpoints (issue #892) ────────────────────────────
         .route(
             "/api/v1/admin/api-keys",
@@ -486,6 +971,16 @@ async fn main() -> anyhow::Result<()> {
             "/api/v1/admin/api-keys/rotate",
             post(handlers::rotate_api_key),
         )
+        .route("/api/v1/admin/keys/:id/usage", get(handlers::api_key_usage))
+        .route(
+            "/api/v1/admin/markets/:market_id/chain-ids/:network",
+            axum::routing::put(handlers::market_id_bind).delete(handlers::market_id_unbind),
+        )
+        .route(
+            "/api/v1/admin/risk/markets/:market_id/exposure",
+            get(handlers::risk_market_exposure),
+        )
+        .route("/api/v1/admin/risk/alerts", get(handlers::risk_alerts))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             idempotency::idempotency_middleware,
@@ -493,7 +988,31 @@ async fn main() -> anyhow::Result<()> {
         .layer(middleware::from_fn(validation::content_type_validation_middleware))
         .layer(middleware::from_fn(validation::request_size_validation_middleware))
         .layer(middleware::from_fn_with_state(
-            (ip_whitelist.clone(), security::TrustProxy(config_trust_proxy)),
+            (ip_whitelist.clone(), trusted_proxies.clone()),
+            security::ip_whitelist_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(api_key_auth.clone(), security::api_key_middleware))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::admin_rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(state.clone(), audit_middleware::audit_logging_middleware))
+        .layer(middleware::from_fn(correlation::correlation_id_middleware))
+        .layer(TraceLayer::new_for_http())
+        .with_state(state.clone());
+
+    // Image uploads are multipart, so they skip the admin group's JSON
+    // content-type check and idempotency replay but keep its auth, rate
+    // limit and audit layers. Size is bounded by the app-wide
+    // request_size_validation_middleware and MARKET_IMAGE_MAX_BYTES.
+    let admin_upload_routes = Router::new()
+        .route(
+            "/api/v1/admin/markets/:market_id/image",
+            post(handlers::market_image_upload),
+        )
+        .layer(middleware::from_fn(validation::request_size_validation_middleware))
+        .layer(middleware::from_fn_with_state(
+            (ip_whitelist, trusted_proxies),
             security::ip_whitelist_middleware,
         ))
         .layer(middleware::from_fn_with_state(api_key_auth.clone(), security::api_key_middleware))
@@ -508,15 +1027,29 @@ async fn main() -> anyhow::Result<()> {
 
     let app = Router::new()
         .merge(health_routes)
+        .merge(docs_routes)
         .merge(public_routes)
+        .merge(tx_routes)
         .merge(metrics_routes)
         .merge(newsletter_routes)
+        .merge(contact_routes)
+        .merge(analytics_routes)
         .merge(webhook_routes)
         .merge(admin_routes)
+        .merge(admin_upload_routes)
+        // Innermost app-wide layer: it sees each route's MatchedPath and the
+        // status its group's middleware produced (429s, 401s, ...).
+        .layer(middleware::from_fn_with_state(
+            state.metrics.clone(),
+            metrics::http_metrics_middleware,
+        ))
         .layer(middleware::from_fn(validation::request_validation_middleware))
         .layer(middleware::from_fn(validation::request_size_validation_middleware))
         .layer(middleware::from_fn(security::security_headers_middleware))
-        .layer(compression::compression_layer())
+        .layer(compression::compression_layer(
+            state.config.compression_min_size,
+            &state.config.compression_content_types,
+        ))
         .layer(cors_layer)
         // HTTPS redirect is the outermost layer: it runs before any other
         // middleware so plain-HTTP requests are bounced before touching app logic.
@@ -529,43 +1062,41 @@ async fn main() -> anyhow::Result<()> {
     let listener = TcpListener::bind(bind_addr).await?;
     tracing::info!("API listening on {bind_addr}");
 
-    // Axum's built-in graceful shutdown drains in-flight HTTP requests.
-    // After the server stops accepting connections we then drain background workers.
-    let axum_shutdown = {
-        let coord = coordinator.clone();
-        let email_coord = email_coordinator.clone();
-        async move {
-            wait_for_signal().await;
-            tracing::info!("Shutdown signal received — stopping HTTP server");
-
-            // Drain email queue first with its own timeout to avoid losing in-flight emails.
-            let email_drain = shutdown::email_queue_drain_timeout();
-            tracing::info!(
-                timeout_secs = email_drain.as_secs(),
-                "Draining email queue worker"
-            );
-            match email_coord.shutdown(email_drain).await {
-                Ok(_) => tracing::info!("Email queue worker drained cleanly"),
-                Err(e) => tracing::warn!("Email queue drain timeout — in-flight email may be lost: {e}"),
-            }
-
-            // Then drain remaining background workers (blockchain sync, tx-monitor).
-            let timeout_dur = shutdown_timeout();
-            tracing::info!(
-                timeout_secs = timeout_dur.as_secs(),
-                "Waiting for remaining background workers to drain"
-            );
-            match coord.shutdown(timeout_dur).await {
-                Ok(_) => tracing::info!("All background workers stopped cleanly"),
-                Err(e) => tracing::warn!("Shutdown timeout — forcing exit: {e}"),
-            }
-            tracing_config::shutdown_tracing();
-        }
-    };
+    // On SIGTERM/SIGINT: stop accepting connections, drain in-flight requests
+    // (HTTP_DRAIN_TIMEOUT_SECS), then wait for the background workers, which
+    // were cancelled by the same token and are finishing their current task.
+    let signal_token = shutdown_token.clone();
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        tracing::info!("Shutdown signal received — stopping HTTP server");
+        signal_token.cancel();
+    });
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(axum_shutdown)
+    shutdown::serve_until_cancelled(listener, app, shutdown_token, shutdown::http_drain_timeout())
         .await?;
 
+    // Drain email queue first with its own timeout to avoid losing in-flight emails.
+    let email_drain = shutdown::email_queue_drain_timeout();
+    tracing::info!(
+        timeout_secs = email_drain.as_secs(),
+        "Draining email queue worker"
+    );
+    match email_coordinator.shutdown(email_drain).await {
+        Ok(_) => tracing::info!("Email queue worker drained cleanly"),
+        Err(e) => tracing::warn!("Email queue drain timeout — in-flight email may be lost: {e}"),
+    }
+
+    // Then drain remaining background workers (blockchain sync, tx-monitor).
+    let timeout_dur = shutdown_timeout();
+    tracing::info!(
+        timeout_secs = timeout_dur.as_secs(),
+        "Waiting for remaining background workers to drain"
+    );
+    match coordinator.shutdown(timeout_dur).await {
+        Ok(_) => tracing::info!("All background workers stopped cleanly"),
+        Err(e) => tracing::warn!("Shutdown timeout — forcing exit: {e}"),
+    }
+    tracing_config::shutdown_tracing();
+
     Ok(())
 }
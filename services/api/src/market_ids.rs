@@ -0,0 +1,118 @@
+//! Public market ids and the chain ids they stand for on each network.
+//!
+//! Every network's contract numbers its markets on its own, and a testnet
+//! reset starts over from 1, so one market rarely has the same chain id
+//! everywhere. The API and the CMS refer to a market by its public id, its
+//! `markets.id` on the primary network, and `market_id_mappings` binds that
+//! id to a chain id per network. A binding belongs to the contract it was
+//! made under: after a redeploy the old bindings stop resolving instead of
+//! pointing at whichever new market reuses their chain ids.
+//!
+//! Blockchain reads in the handlers go through [`resolve`] (or
+//! [`resolve_many`]); a public id without a binding on the requested network
+//! is an [`UnmappedMarket`], which the handlers answer with a 404. Markets
+//! created on the primary network are mapped to themselves from their
+//! `mkt_creat` events, by the sync worker as it indexes them and by
+//! [`map_indexed_markets`] at startup for those indexed before. Admins bind
+//! the other networks.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{blockchain::BlockchainClient, db::Database};
+
+/// One row of `market_id_mappings`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketIdMapping {
+    /// Public market id.
+    pub internal_id: i64,
+    pub network: String,
+    /// Market id in the network's contract.
+    pub chain_market_id: i64,
+    /// Contract the chain id belongs to.
+    pub contract_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A public market id with no binding on a network's current contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedMarket {
+    pub market_id: i64,
+    pub network: String,
+}
+
+impl std::fmt::Display for UnmappedMarket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "market {} is not mapped on network {}",
+            self.market_id, self.network
+        )
+    }
+}
+
+impl std::error::Error for UnmappedMarket {}
+
+/// The chain id public market `market_id` stands for on `client`'s network.
+/// Fails with [`UnmappedMarket`] when it has none.
+pub async fn resolve(
+    db: &Database,
+    client: &BlockchainClient,
+    market_id: i64,
+) -> anyhow::Result<i64> {
+    db.market_id_mapping_get(client.network(), client.contract_id(), market_id)
+        .await?
+        .ok_or_else(|| unmapped(client, market_id))
+}
+
+/// [`resolve`] for many public ids with one query. Results are in
+/// `market_ids` order; unmapped ids are `None`.
+pub async fn resolve_many(
+    db: &Database,
+    client: &BlockchainClient,
+    market_ids: &[i64],
+) -> anyhow::Result<Vec<Option<i64>>> {
+    if market_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mapped: HashMap<i64, i64> = db
+        .market_id_mappings_get_many(client.network(), client.contract_id(), market_ids)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(market_ids
+        .iter()
+        .map(|id| mapped.get(id).copied())
+        .collect())
+}
+
+/// The error [`resolve`] returns for `market_id` on `client`'s network.
+pub fn unmapped(client: &BlockchainClient, market_id: i64) -> anyhow::Error {
+    UnmappedMarket {
+        market_id,
+        network: client.network().to_string(),
+    }
+    .into()
+}
+
+/// Map every market with an indexed creation event on `client`'s network to
+/// itself, when the network has no mappings yet. Run for the primary network
+/// at startup, so markets indexed before the mappings existed keep
+/// resolving. Returns the mappings added.
+pub async fn map_indexed_markets(db: &Database, client: &BlockchainClient) -> anyhow::Result<u64> {
+    db.market_id_mappings_seed(client.network(), client.contract_id())
+        .await
+}
+
+/// Body of `PUT /api/v1/admin/markets/:market_id/chain-ids/:network`.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct MarketIdBindRequest {
+    /// Market id in the network's contract.
+    pub chain_market_id: i64,
+    /// Contract the chain id belongs to; defaults to the contract the API
+    /// serves on the network. Name a new deployment to bind ahead of a
+    /// switch to it.
+    pub contract_id: Option<String>,
+}
@@ -0,0 +1,396 @@
+#[cfg(test)]
+mod market_ids_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post, put},
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use tower::ServiceExt;
+
+    use crate::{
+        blockchain::{ChainMarketData, DataSource},
+        cache::keys,
+        handlers::{
+            blockchain_market_data, blockchain_oracle_result, market_id_bind, market_id_unbind,
+        },
+        market_ids,
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    const CONTRACT_ID: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+    const OTHER_CONTRACT_ID: &str = "CAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQC526";
+    const LATEST_LEDGER: u32 = 1_000;
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route(
+                "/blockchain/markets/:market_id",
+                get(blockchain_market_data),
+            )
+            .route(
+                "/blockchain/oracle/:market_id",
+                get(blockchain_oracle_result),
+            )
+            .route(
+                "/admin/markets/:market_id/chain-ids/:network",
+                put(market_id_bind).delete(market_id_unbind),
+            )
+            .with_state(state)
+    }
+
+    async fn call(
+        state: &Arc<crate::AppState>,
+        method: &str,
+        uri: &str,
+        network: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(network) = network {
+            request = request.header("x-network", network);
+        }
+        let body = match body {
+            Some(body) => {
+                request = request.header("content-type", "application/json");
+                Body::from(serde_json::to_vec(&body).unwrap())
+            }
+            None => Body::empty(),
+        };
+        let response = app(Arc::clone(state))
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    fn unique_market_id() -> i64 {
+        (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 5_000_000_000
+    }
+
+    /// Cache `network`'s chain market `chain_market_id` under `title`, so it
+    /// is read without an RPC node.
+    async fn seed_chain_market(
+        state: &crate::AppState,
+        network: &str,
+        chain_market_id: i64,
+        title: &str,
+    ) {
+        let data = ChainMarketData {
+            market_id: chain_market_id,
+            title: Some(title.to_string()),
+            options: None,
+            status: Some("active".to_string()),
+            onchain_volume: "0".to_string(),
+            resolved_outcome: None,
+            ledger: LATEST_LEDGER,
+            source: DataSource::Live,
+        };
+        state
+            .cache
+            .set_json(
+                &keys::chain_market(network, chain_market_id),
+                &data,
+                Duration::from_secs(300),
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Answers `getLatestLedger` with [`LATEST_LEDGER`] and `getEvents` with
+    /// `events`; every other method is a JSON-RPC error.
+    async fn start_mock_rpc(events: Vec<Value>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let events = events.clone();
+                async move {
+                    let resp = match body["method"].as_str().unwrap_or_default() {
+                        "getLatestLedger" => {
+                            json!({ "result": { "latestLedger": { "sequence": LATEST_LEDGER } } })
+                        }
+                        "getEvents" => {
+                            json!({ "result": { "events": events, "latestLedger": LATEST_LEDGER } })
+                        }
+                        _ => json!({ "error": { "code": -32601, "message": "not mocked" } }),
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// A URL nothing listens on; reads that reach the node fail.
+    fn dead_rpc() -> String {
+        "http://127.0.0.1:1".to_string()
+    }
+
+    async fn cleanup(state: &crate::AppState, market_ids: &[i64]) {
+        let networks: Vec<String> = state
+            .networks
+            .names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        for table in ["chain_events", "market_id_mappings"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE network = ANY($1)"))
+                .bind(&networks)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(market_ids)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// One public id reads a different chain market on each network, and
+    /// answers with the public id either way.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_public_id_maps_to_a_chain_id_per_network() {
+        let state = build_state(dead_rpc()).await;
+        let (primary, secondary) = network_names(&state);
+        let market_id = unique_market_id();
+
+        for (network, chain_market_id) in [(&primary, 3), (&secondary, 11)] {
+            let (status, body) = call(
+                &state,
+                "PUT",
+                &format!("/admin/markets/{market_id}/chain-ids/{network}"),
+                None,
+                Some(json!({ "chain_market_id": chain_market_id })),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["internal_id"], market_id);
+            assert_eq!(body["chain_market_id"], chain_market_id);
+            assert_eq!(body["contract_id"], CONTRACT_ID);
+        }
+        seed_chain_market(&state, &primary, 3, "Primary market 3").await;
+        seed_chain_market(&state, &secondary, 11, "Secondary market 11").await;
+        // The public id as a chain id on the secondary network is some other
+        // market, which the mapping must not read.
+        seed_chain_market(&state, &secondary, market_id, "Wrong market").await;
+
+        let uri = format!("/blockchain/markets/{market_id}");
+        let (status, body) = call(&state, "GET", &uri, None, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["market_id"], market_id);
+        assert_eq!(body["title"], "Primary market 3");
+
+        let (status, body) = call(&state, "GET", &uri, Some(&secondary), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["market_id"], market_id);
+        assert_eq!(body["title"], "Secondary market 11");
+
+        let primary_client = state.networks.primary();
+        let secondary_client = state.networks.get(&secondary).unwrap();
+        assert_eq!(
+            market_ids::resolve_many(&state.db, secondary_client, &[market_id, market_id + 1])
+                .await
+                .unwrap(),
+            vec![Some(11), None]
+        );
+        assert_eq!(
+            market_ids::resolve(&state.db, primary_client, market_id)
+                .await
+                .unwrap(),
+            3
+        );
+
+        cleanup(&state, &[market_id]).await;
+    }
+
+    /// A market bound on one network only is a 404 on the others, on every
+    /// market read, without reaching the node.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_unmapped_network_is_404() {
+        let state = build_state(dead_rpc()).await;
+        let (primary, secondary) = network_names(&state);
+        let market_id = unique_market_id();
+        state
+            .db
+            .market_id_mapping_bind(market_id, &primary, 4, CONTRACT_ID)
+            .await
+            .unwrap();
+
+        for route in ["markets", "oracle"] {
+            let uri = format!("/blockchain/{route}/{market_id}");
+            let (status, body) = call(&state, "GET", &uri, Some(&secondary), None).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "{route}");
+            assert_eq!(body["code"], "MARKET_NOT_MAPPED");
+            assert_eq!(body["details"]["market_id"], market_id);
+            assert_eq!(body["details"]["network"], secondary.as_str());
+        }
+
+        let uri = format!("/blockchain/markets/{}", market_id + 1);
+        let (status, body) = call(&state, "GET", &uri, None, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["details"]["network"], primary.as_str());
+
+        cleanup(&state, &[market_id]).await;
+    }
+
+    /// A binding made under another contract does not resolve, a chain
+    /// market stands for one public id, and an unbound market is a 404.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_bind_and_unbind() {
+        let state = build_state(dead_rpc()).await;
+        let (_, secondary) = network_names(&state);
+        let (market_id, other_id) = (unique_market_id(), unique_market_id());
+        let path = |id: i64| format!("/admin/markets/{id}/chain-ids/{secondary}");
+        seed_chain_market(&state, &secondary, 8, "Secondary market 8").await;
+
+        let (status, _) = call(
+            &state,
+            "PUT",
+            &path(market_id),
+            None,
+            Some(json!({ "chain_market_id": 8, "contract_id": OTHER_CONTRACT_ID })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let uri = format!("/blockchain/markets/{market_id}");
+        let (status, _) = call(&state, "GET", &uri, Some(&secondary), None).await;
+        assert_eq!(
+            status,
+            StatusCode::NOT_FOUND,
+            "bound under another contract"
+        );
+
+        let bind = json!({ "chain_market_id": 8 });
+        let (status, _) = call(&state, "PUT", &path(market_id), None, Some(bind.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = call(&state, "GET", &uri, Some(&secondary), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["title"], "Secondary market 8");
+
+        let (status, _) = call(&state, "PUT", &path(other_id), None, Some(bind)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = call(
+            &state,
+            "PUT",
+            &path(other_id),
+            None,
+            Some(json!({ "chain_market_id": -1 })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(
+            &state,
+            "PUT",
+            &format!("/admin/markets/{other_id}/chain-ids/nowhere"),
+            None,
+            Some(json!({ "chain_market_id": 9 })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = call(&state, "DELETE", &path(market_id), None, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, body) = call(&state, "GET", &uri, Some(&secondary), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "MARKET_NOT_MAPPED");
+        let (status, _) = call(&state, "DELETE", &path(market_id), None, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        cleanup(&state, &[market_id, other_id]).await;
+    }
+
+    /// The sync worker maps a market created on the primary network to
+    /// itself; the same event on another network maps nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_creation_event_maps_primary_market() {
+        let market_id = unique_market_id();
+        let event = json!({
+            "id": format!("{:019}-{:010}", LATEST_LEDGER - 10, market_id % 1_000_000_000),
+            "ledger": LATEST_LEDGER - 10,
+            "topic": ["mkt_creat", market_id, "GCREATOR"],
+            "value": [2, "Will it snow in Nairobi?", 2, 1_900_000_000, null, []],
+        });
+        let state = build_state(start_mock_rpc(vec![event]).await).await;
+        let (_, secondary) = network_names(&state);
+        let primary_client = state.networks.primary();
+        let secondary_client = state.networks.get(&secondary).unwrap();
+
+        primary_client.sync_once(LATEST_LEDGER - 100).await.unwrap();
+        secondary_client
+            .sync_once(LATEST_LEDGER - 100)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            market_ids::resolve(&state.db, primary_client, market_id)
+                .await
+                .unwrap(),
+            market_id
+        );
+        let unmapped = market_ids::resolve(&state.db, secondary_client, market_id)
+            .await
+            .unwrap_err();
+        assert!(unmapped
+            .downcast_ref::<market_ids::UnmappedMarket>()
+            .is_some());
+
+        cleanup(&state, &[market_id]).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    /// The primary and secondary network names of a [`build_test_state`].
+    fn network_names(state: &crate::AppState) -> (String, String) {
+        let primary = state.networks.primary_name().to_string();
+        let secondary = state
+            .networks
+            .names()
+            .into_iter()
+            .find(|name| *name != primary)
+            .unwrap()
+            .to_string();
+        (primary, secondary)
+    }
+
+    /// Two networks with names unique to this run, both on `rpc_url`.
+    async fn build_state(rpc_url: String) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.contract_id = CONTRACT_ID.to_string();
+        config.contract_call_timeout = Duration::from_secs(1);
+        config.retry_attempts = 1;
+        let run = uuid::Uuid::new_v4().simple().to_string();
+        build_test_state_with(config, &[&format!("idsprimary{run}"), &format!("idssecondary{run}")])
+            .await
+    }
+}
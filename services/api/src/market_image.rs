@@ -0,0 +1,139 @@
+//! Market cover images.
+//!
+//! `POST /api/v1/admin/markets/:id/image` takes a multipart upload, decides
+//! the format from the file's leading bytes (the declared content type and
+//! file name are ignored), writes it to [`crate::storage`] under a
+//! content-addressed key, and records the key and a versioned URL on the
+//! market row. `GET /api/v1/markets/:id/image` serves the stored bytes with a
+//! year-long `Cache-Control` and a strong ETag: a new upload changes the URL,
+//! so cached copies never go stale.
+
+use serde::{Deserialize, Serialize};
+
+use crate::etag::strong_etag;
+use crate::security::integrity_hash;
+
+/// Multipart field carrying the image.
+pub const UPLOAD_FIELD: &str = "image";
+
+/// `Cache-Control` for served images.
+pub const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// The image formats markets may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl ImageFormat {
+    /// The format `bytes` start with, or `None` for anything else.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(b"\xff\xd8\xff") {
+            Some(Self::Jpeg)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Webp => "image/webp",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpg",
+            Self::Webp => "webp",
+        }
+    }
+}
+
+/// Where a market's image is stored and how clients fetch it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketImage {
+    pub market_id: i64,
+    /// Storage key; not exposed beyond the admin response.
+    pub key: String,
+    pub content_type: String,
+    /// Strong ETag of the bytes, quoted.
+    pub etag: String,
+    /// Versioned URL for `<img src>`.
+    pub url: String,
+}
+
+impl MarketImage {
+    /// Describe `bytes` of `format` as the image of `market_id`, served from
+    /// `base_url`.
+    pub fn new(market_id: i64, format: ImageFormat, bytes: &[u8], base_url: &str) -> Self {
+        let digest = integrity_hash(bytes);
+        Self {
+            market_id,
+            key: format!("markets/{market_id}/{digest}.{}", format.extension()),
+            content_type: format.content_type().to_string(),
+            etag: strong_etag(bytes),
+            url: format!(
+                "{}/api/v1/markets/{market_id}/image?v={}",
+                base_url.trim_end_matches('/'),
+                &digest[..16]
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+    const JPEG: &[u8] = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00";
+    const WEBP: &[u8] = b"RIFF\x24\x00\x00\x00WEBPVP8 ";
+
+    #[test]
+    fn sniffs_allowed_formats_by_magic_bytes() {
+        assert_eq!(ImageFormat::sniff(PNG), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::sniff(JPEG), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::sniff(WEBP), Some(ImageFormat::Webp));
+    }
+
+    #[test]
+    fn rejects_executables_and_other_formats() {
+        // A Windows executable, an ELF binary, a script, a GIF, an SVG, and a
+        // RIFF container that is not WebP.
+        for bytes in [
+            &b"MZ\x90\x00\x03\x00\x00\x00\x04\x00"[..],
+            b"\x7fELF\x02\x01\x01\x00",
+            b"#!/bin/sh\nrm -rf /\n",
+            b"GIF89a\x01\x00\x01\x00",
+            b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+            b"RIFF\x24\x00\x00\x00WAVEfmt ",
+            b"\x89PN",
+            b"",
+        ] {
+            assert_eq!(ImageFormat::sniff(bytes), None, "{bytes:?}");
+        }
+    }
+
+    #[test]
+    fn key_and_url_are_content_addressed() {
+        let a = MarketImage::new(7, ImageFormat::Png, PNG, "https://api.example.com/");
+        assert!(a.key.starts_with("markets/7/") && a.key.ends_with(".png"));
+        assert!(a.url.starts_with("https://api.example.com/api/v1/markets/7/image?v="));
+        assert_eq!(a.content_type, "image/png");
+        assert_eq!(a, MarketImage::new(7, ImageFormat::Png, PNG, "https://api.example.com"));
+
+        let b = MarketImage::new(7, ImageFormat::Jpeg, JPEG, "https://api.example.com");
+        assert_ne!(a.key, b.key);
+        assert_ne!(a.url, b.url);
+        assert_ne!(a.etag, b.etag);
+    }
+}
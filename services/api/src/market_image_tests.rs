@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod market_image_tests {
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use std::{path::PathBuf, sync::Arc};
+    use tower::ServiceExt;
+
+    use crate::{
+        config::MediaStorage,
+        handlers::{market_image, market_image_upload},
+        market_image::MarketImage,
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 2] = [9621, 9622];
+
+    const BOUNDARY: &str = "predictiq-test-boundary";
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01";
+    const JPEG: &[u8] = b"\xff\xd8\xff\xe0\x00\x10JFIF\x00\x01\x01";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/admin/markets/:market_id/image", post(market_image_upload))
+            .route("/markets/:market_id/image", get(market_image))
+            .with_state(state)
+    }
+
+    /// A multipart body with one file `field` named `filename`, declared as
+    /// `content_type`.
+    fn form(field: &str, filename: &str, content_type: &str, bytes: &[u8]) -> Vec<u8> {
+        let mut body = format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{field}\"; filename=\"{filename}\"\r\n\
+             Content-Type: {content_type}\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+        body
+    }
+
+    async fn upload(
+        state: &Arc<crate::AppState>,
+        market_id: i64,
+        body: Vec<u8>,
+    ) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/admin/markets/{market_id}/image"))
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={BOUNDARY}"))
+            .body(Body::from(body))
+            .unwrap();
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, outcome_options) \
+             VALUES ($1, 'Image test', 'active', 0, NOW() + INTERVAL '1 day', NOW(), ARRAY['Yes', 'No'])",
+        )
+        .bind(market_id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    fn media_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("predictiq-media-{}", uuid::Uuid::new_v4()))
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// An executable named and declared as a PNG is rejected on its magic
+    /// bytes, and nothing is recorded or written.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_disguised_executable_is_rejected() {
+        let dir = media_dir();
+        let state = build_state(&dir).await;
+        cleanup(&state).await;
+        seed_market(&state, 9621).await;
+
+        let executable = b"MZ\x90\x00\x03\x00\x00\x00\x04\x00\x00\x00\xff\xff\x00\x00";
+        let (status, body) =
+            upload(&state, 9621, form("image", "cover.png", "image/png", executable)).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(body["code"], "UNSUPPORTED_IMAGE_TYPE");
+
+        assert_eq!(state.db.market_image(9621).await.unwrap(), None);
+        assert!(!dir.exists());
+        cleanup(&state).await;
+    }
+
+    /// Uploads land in the filesystem backend and are served with a strong
+    /// ETag and immutable caching; a replacement changes the URL and removes
+    /// the old object.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_upload_is_stored_and_served() {
+        let dir = media_dir();
+        let state = build_state(&dir).await;
+        cleanup(&state).await;
+        seed_market(&state, 9622).await;
+
+        let (status, _) = upload(&state, 9699, form("image", "a.png", "image/png", PNG)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, body) = upload(&state, 9622, form("cover", "a.png", "image/png", PNG)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+
+        let (status, body) = upload(&state, 9622, form("image", "a.bin", "text/plain", PNG)).await;
+        assert_eq!(status, StatusCode::OK);
+        let first: MarketImage = serde_json::from_value(body).unwrap();
+        assert_eq!(first.content_type, "image/png");
+        assert!(dir.join(&first.key).is_file());
+        assert_eq!(state.db.market_image(9622).await.unwrap().as_ref(), Some(&first));
+
+        let response = app(Arc::clone(&state))
+            .oneshot(Request::builder().uri("/markets/9622/image").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(response.headers()[header::ETAG], first.etag.as_str());
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            crate::market_image::CACHE_CONTROL
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], PNG);
+
+        let response = app(Arc::clone(&state))
+            .oneshot(
+                Request::builder()
+                    .uri("/markets/9622/image")
+                    .header(header::IF_NONE_MATCH, &first.etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let (status, body) = upload(&state, 9622, form("image", "b.jpg", "image/jpeg", JPEG)).await;
+        assert_eq!(status, StatusCode::OK);
+        let second: MarketImage = serde_json::from_value(body).unwrap();
+        assert_ne!(second.url, first.url);
+        assert!(dir.join(&second.key).is_file());
+        assert!(!dir.join(&first.key).exists());
+        let detail = state.db.market_detail(9622).await.unwrap().unwrap();
+        assert_eq!(detail.image_url.as_deref(), Some(second.url.as_str()));
+
+        cleanup(&state).await;
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state(media_dir: &std::path::Path) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.media_storage = MediaStorage::Local { dir: media_dir.to_path_buf() };
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod market_list_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::handlers::list_markets;
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_IDS: [i64; 5] = [9101, 9102, 9103, 9104, 9105];
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/markets", get(list_markets))
+            .with_state(state)
+    }
+
+    async fn get_json(router: Router, uri: &str) -> (StatusCode, Value) {
+        let response = router
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn ids(body: &Value) -> Vec<i64> {
+        body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["id"].as_i64().unwrap())
+            .filter(|id| SEED_IDS.contains(id))
+            .collect()
+    }
+
+    async fn seed(state: &crate::AppState) {
+        cleanup(state).await;
+        // The category filter only accepts slugs present in `categories`.
+        sqlx::query(
+            "INSERT INTO categories (slug, name) \
+             VALUES ('sports', 'Sports'), ('politics', 'Politics'), ('crypto', 'Crypto') \
+             ON CONFLICT (slug) DO NOTHING",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        state
+            .cache
+            .invalidate_tag(&crate::cache::InvalidationTag::CategoryChanged)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO markets \
+                (id, title, status, outcome_index, total_volume, ends_at, created_at, \
+                 category, creator, participant_count) \
+             VALUES \
+                (9101, 'Seed A', 'active',   NULL, 500, NOW() + INTERVAL '1 day',  NOW() - INTERVAL '5 day', 'sports',   'GCREATOR1', 10), \
+                (9102, 'Seed B', 'active',   NULL, 900, NOW() + INTERVAL '3 day',  NOW() - INTERVAL '4 day', 'sports',   'GCREATOR2', 40), \
+                (9103, 'Seed C', 'active',   NULL, 100, NOW() + INTERVAL '2 day',  NOW() - INTERVAL '3 day', 'politics', 'GCREATOR1', 25), \
+                (9104, 'Seed D', 'resolved', 1,    700, NOW() - INTERVAL '1 day',  NOW() - INTERVAL '2 day', 'sports',   'GCREATOR2', 5), \
+                (9105, 'Seed E', 'active',   NULL, 900, NOW() + INTERVAL '10 day', NOW() - INTERVAL '1 day', 'crypto',   'GCREATOR3', 1)",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_IDS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Default sort is volume DESC with id DESC as the tie-breaker.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_default_sort_is_volume_desc() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (status, body) = get_json(app(Arc::clone(&state)), "/markets?category=sports&limit=100").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids(&body), vec![9102, 9104, 9101]);
+        assert!(body["items"][0].get("onchain_volume").is_none(), "chain data must be opt-in");
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_filters_combine() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (status, body) = get_json(
+            app(Arc::clone(&state)),
+            "/markets?status=active&creator=GCREATOR1&sort=ends_at",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids(&body), vec![9101, 9103]);
+
+        cleanup(&state).await;
+    }
+
+    /// Walking the cursor with limit=1 must visit every matching row exactly
+    /// once, in order, including rows that tie on the sort key.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_cursor_walk_visits_each_row_once() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let mut seen = Vec::new();
+        let mut uri = "/markets?status=active&sort=volume&limit=1".to_string();
+        loop {
+            let (status, body) = get_json(app(Arc::clone(&state)), &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            seen.extend(ids(&body));
+            match body["next_cursor"].as_str() {
+                Some(cursor) => {
+                    uri = format!("/markets?status=active&sort=volume&limit=1&cursor={cursor}")
+                }
+                None => break,
+            }
+        }
+        assert_eq!(seen, vec![9105, 9102, 9101, 9103]);
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_sort_by_participant_count() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (status, body) =
+            get_json(app(Arc::clone(&state)), "/markets?sort=participant_count&limit=100").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(ids(&body), vec![9102, 9103, 9101, 9104, 9105]);
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_rejects_unknown_status() {
+        let state = build_test_state().await;
+        let (status, body) = get_json(app(state), "/markets?status=open").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+    }
+
+    /// `fields` trims each item but leaves the page envelope alone.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_fields_mask_each_item() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (status, body) = get_json(
+            app(Arc::clone(&state)),
+            "/markets?category=politics&fields=id,title,unknown&limit=100",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["items"][0], serde_json::json!({ "id": 9103, "title": "Seed C" }));
+        assert!(body.get("has_more").is_some());
+
+        cleanup(&state).await;
+    }
+
+    /// A cursor issued under one sort order must not be replayed under another.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_list_markets_rejects_cursor_from_other_sort() {
+        let state = build_test_state().await;
+        seed(&state).await;
+
+        let (_, first) = get_json(app(Arc::clone(&state)), "/markets?sort=volume&limit=1").await;
+        let cursor = first["next_cursor"].as_str().unwrap().to_string();
+        let (status, _) = get_json(
+            app(Arc::clone(&state)),
+            &format!("/markets?sort=created_at&cursor={cursor}"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Pure-logic unit tests (no I/O)
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_market_list_query_deserialises_sort_and_flags() {
+        let uri: axum::http::Uri = "/markets?sort=participant_count&include_chain=true&limit=5"
+            .parse()
+            .unwrap();
+        let axum::extract::Query(query) =
+            axum::extract::Query::<crate::handlers::MarketListQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.sort, Some(crate::db::MarketSort::ParticipantCount));
+        assert!(query.include_chain);
+        assert_eq!(query.limit, Some(5));
+    }
+
+    #[test]
+    fn test_market_list_view_omits_onchain_volume_when_absent() {
+        let view = crate::handlers::MarketListView {
+            id: 1,
+            title: "t".to_string(),
+            volume: 1.0,
+            ends_at: chrono::Utc::now(),
+            onchain_volume: None,
+            resolved_outcome: None,
+        };
+        let json = serde_json::to_value(&view).unwrap();
+        assert!(json.get("onchain_volume").is_none());
+        assert!(json.get("resolved_outcome").is_some());
+    }
+
+}
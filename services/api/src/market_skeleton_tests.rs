@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod market_skeleton_tests {
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use sqlx::Row;
+
+    use crate::{
+        blockchain::{BlockchainClient, NetworkClients},
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    const LATEST_LEDGER: u32 = 1_000;
+    const DEADLINE: i64 = 1_900_000_000;
+
+    /// Answers `getLatestLedger` with [`LATEST_LEDGER`] and `getEvents` with
+    /// `events`; every other method is a JSON-RPC error.
+    async fn start_mock_rpc(events: Vec<Value>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let events = events.clone();
+                async move {
+                    let resp = match body["method"].as_str().unwrap_or_default() {
+                        "getLatestLedger" => json!({ "result": { "latestLedger": { "sequence": LATEST_LEDGER } } }),
+                        "getEvents" => json!({ "result": { "events": events, "latestLedger": LATEST_LEDGER } }),
+                        _ => json!({ "error": { "code": -32601, "message": "not mocked" } }),
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// A client on a network name unique to this run.
+    async fn build_client(rpc_url: &str) -> (BlockchainClient, Database, String) {
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url.to_string();
+        config.retry_attempts = 1;
+        let network = format!("mkttest{}", uuid::Uuid::new_v4().simple());
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(config.db_credentials.to_connection_string().expose_secret(), cache.clone(), metrics.clone(), &config.db_pool)
+            .await
+            .expect("db");
+        let client = BlockchainClient::new(&config, cache, db.clone(), metrics)
+            .expect("blockchain")
+            .with_network(&network);
+        (client, db, network)
+    }
+
+    fn unique_market_id() -> i64 {
+        (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 4_000_000_000
+    }
+
+    fn creation_event(market_id: i64, category: Value) -> Value {
+        json!({
+            "id": format!("{:019}-{:010}", LATEST_LEDGER - 10, market_id % 1_000_000_000),
+            "ledger": LATEST_LEDGER - 10,
+            "topic": ["mkt_creat", market_id, "GCREATOR"],
+            "value": [2, "Will it rain in Lagos?", 2, DEADLINE, category, ["rain"]],
+        })
+    }
+
+    async fn market_row(db: &Database, market_id: i64) -> Option<sqlx::postgres::PgRow> {
+        sqlx::query(
+            "SELECT title, category, creator, status, EXTRACT(EPOCH FROM ends_at)::BIGINT AS ends_at
+             FROM markets WHERE id = $1",
+        )
+        .bind(market_id)
+        .fetch_optional(&db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(db: &Database, network: &str, market_id: i64) {
+        for table in ["chain_events", "market_id_mappings"] {
+            sqlx::query(&format!("DELETE FROM {table} WHERE network = $1"))
+                .bind(network)
+                .execute(&db.pool())
+                .await
+                .unwrap();
+        }
+        sqlx::query("DELETE FROM markets WHERE id = $1")
+            .bind(market_id)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A market created on chain gets a row built from its creation event.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_creation_event_inserts_a_skeleton_market() {
+        let market_id = unique_market_id();
+        let rpc = start_mock_rpc(vec![creation_event(market_id, json!("weather"))]).await;
+        let (client, db, network) = build_client(&rpc).await;
+
+        client.sync_once(LATEST_LEDGER - 100).await.unwrap();
+
+        let row = market_row(&db, market_id).await.expect("skeleton row");
+        assert_eq!(row.get::<String, _>("title"), "Will it rain in Lagos?");
+        assert_eq!(row.get::<Option<String>, _>("category").as_deref(), Some("weather"));
+        assert_eq!(row.get::<Option<String>, _>("creator").as_deref(), Some("GCREATOR"));
+        assert_eq!(row.get::<String, _>("status"), "active");
+        assert_eq!(row.get::<i64, _>("ends_at"), DEADLINE);
+
+        cleanup(&db, &network, market_id).await;
+    }
+
+    /// A market the database already knows keeps its curated fields and only
+    /// gains the category it lacked.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_creation_event_fills_in_an_existing_market() {
+        let market_id = unique_market_id();
+        let rpc = start_mock_rpc(vec![creation_event(market_id, json!("weather"))]).await;
+        let (client, db, network) = build_client(&rpc).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at)
+             VALUES ($1, 'Curated title', 'active', 12.5, NOW() + INTERVAL '1 day')",
+        )
+        .bind(market_id)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+
+        client.sync_once(LATEST_LEDGER - 100).await.unwrap();
+
+        let row = market_row(&db, market_id).await.unwrap();
+        assert_eq!(row.get::<String, _>("title"), "Curated title");
+        assert_eq!(row.get::<Option<String>, _>("category").as_deref(), Some("weather"));
+        assert_ne!(row.get::<i64, _>("ends_at"), DEADLINE);
+
+        cleanup(&db, &network, market_id).await;
+    }
+
+    /// Only the primary network's market ids belong in `markets`.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_secondary_network_creates_no_markets() {
+        let market_id = unique_market_id();
+        let rpc = start_mock_rpc(vec![creation_event(market_id, json!(null))]).await;
+        let (primary, db, _) = build_client(&rpc).await;
+        let (secondary, _, secondary_network) = build_client(&rpc).await;
+        let networks = NetworkClients::single(primary).with(secondary).unwrap();
+
+        let client = networks.get(&secondary_network).unwrap();
+        client.sync_once(LATEST_LEDGER - 100).await.unwrap();
+        assert!(market_row(&db, market_id).await.is_none());
+
+        cleanup(&db, &secondary_network, market_id).await;
+    }
+}
@@ -0,0 +1,249 @@
+//! Email notifications for markets a user is watching.
+//!
+//! `POST /api/v1/markets/:market_id/watches` records a [`MarketWatch`] for one
+//! email address on one network. The sync worker hands every indexed event to
+//! [`notify_watchers`], which enqueues a `market_resolved` email per matching
+//! watcher when the event is a resolution or a dispute. Each (watch, event)
+//! pair is claimed in `market_watch_notifications` before the job is enqueued,
+//! so replays and re-syncs never email a watcher twice. Suppressed addresses
+//! (bounces, complaints, unsubscribes) are skipped at claim time and again by
+//! the queue worker at send time.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    blockchain::{IndexedEvent, EVENT_DISPUTE_FILED, EVENT_MARKET_RESOLVED},
+    db::Database,
+    email::{queue::EmailQueue, types::EmailJobType},
+};
+
+/// Template used for both resolution and dispute notifications.
+pub const MARKET_RESOLVED_TEMPLATE: &str = "market_resolved";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchTrigger {
+    /// The market's resolution was finalized (`resolv_fx`).
+    Resolve,
+    /// A dispute was filed against the market (`disp_file`).
+    Dispute,
+    /// The market's betting deadline is approaching.
+    Deadline,
+}
+
+impl WatchTrigger {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Resolve => "resolve",
+            Self::Dispute => "dispute",
+            Self::Deadline => "deadline",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "resolve" => Some(Self::Resolve),
+            "dispute" => Some(Self::Dispute),
+            "deadline" => Some(Self::Deadline),
+            _ => None,
+        }
+    }
+
+    /// The trigger an indexed contract event fires, if any.
+    pub fn for_event_kind(kind: &str) -> Option<Self> {
+        match kind {
+            EVENT_MARKET_RESOLVED => Some(Self::Resolve),
+            EVENT_DISPUTE_FILED => Some(Self::Dispute),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketWatch {
+    pub id: i64,
+    pub network: String,
+    pub market_id: i64,
+    pub email: String,
+    /// Optional Stellar account (`G...`) the watcher bets from.
+    pub address: Option<String>,
+    pub notify_on: Vec<WatchTrigger>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A watcher whose notification for one event has just been claimed.
+#[derive(Debug, Clone)]
+pub struct WatchRecipient {
+    pub watch_id: i64,
+    pub email: String,
+    pub unsubscribe_token: String,
+    pub market_title: String,
+    pub outcome_options: Vec<String>,
+}
+
+/// A fresh opaque unsubscribe token.
+pub fn new_unsubscribe_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Template data for the `market_resolved` email. `event_id` scopes the
+/// queue's idempotency key to this event.
+pub fn notification_data(
+    base_url: &str,
+    event: &IndexedEvent,
+    market_id: i64,
+    recipient: &WatchRecipient,
+) -> Value {
+    let base_url = base_url.trim_end_matches('/');
+    let is_dispute = event.kind == EVENT_DISPUTE_FILED;
+    let outcome_label = match event.outcome {
+        Some(index) if !is_dispute => recipient
+            .outcome_options
+            .get(index as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("Outcome {index}")),
+        _ => String::new(),
+    };
+    json!({
+        "market_id": market_id,
+        "market_title": recipient.market_title,
+        "event": if is_dispute { "disputed" } else { "resolved" },
+        "is_dispute": is_dispute,
+        "outcome_label": outcome_label,
+        "payout_url": format!("{base_url}/markets/{market_id}"),
+        "unsubscribe_url": format!(
+            "{base_url}/api/v1/watches/unsubscribe?token={}",
+            recipient.unsubscribe_token
+        ),
+        "event_id": event.id,
+    })
+}
+
+/// Enqueue one notification per watcher of `event`'s market and return how
+/// many were enqueued. Events that are not watch triggers, or that carry no
+/// market id, enqueue nothing. A claim whose enqueue fails is released so a
+/// replay of the event retries it.
+pub async fn notify_watchers(
+    db: &Database,
+    queue: &EmailQueue,
+    base_url: &str,
+    network: &str,
+    event: &IndexedEvent,
+) -> anyhow::Result<usize> {
+    let (Some(trigger), Some(market_id)) = (WatchTrigger::for_event_kind(&event.kind), event.market_id) else {
+        return Ok(0);
+    };
+
+    let recipients = db
+        .market_watch_claim_notifications(network, market_id, trigger, &event.id)
+        .await?;
+
+    let mut enqueued = 0;
+    for recipient in recipients {
+        let data = notification_data(base_url, event, market_id, &recipient);
+        match queue
+            .enqueue(
+                EmailJobType::MarketNotification,
+                &recipient.email,
+                MARKET_RESOLVED_TEMPLATE,
+                data,
+                0,
+            )
+            .await
+        {
+            Ok(job_id) => {
+                enqueued += 1;
+                if let Err(e) = db
+                    .market_watch_set_notification_job(recipient.watch_id, &event.id, job_id)
+                    .await
+                {
+                    tracing::warn!(watch_id = recipient.watch_id, error = %e, "failed to record notification job");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    watch_id = recipient.watch_id,
+                    event_id = %event.id,
+                    error = %e,
+                    "failed to enqueue watcher notification"
+                );
+                db.market_watch_release_notification(recipient.watch_id, &event.id)
+                    .await?;
+            }
+        }
+    }
+    Ok(enqueued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: &str, outcome: Option<u32>) -> IndexedEvent {
+        IndexedEvent {
+            id: "0000001-0001".to_string(),
+            ledger: 1,
+            tx_hash: None,
+            kind: kind.to_string(),
+            market_id: Some(7),
+            address: None,
+            outcome,
+            amount: None,
+            token: None,
+            is_refund: false,
+        }
+    }
+
+    fn recipient() -> WatchRecipient {
+        WatchRecipient {
+            watch_id: 1,
+            email: "watcher@example.com".to_string(),
+            unsubscribe_token: "tok".to_string(),
+            market_title: "Will it rain?".to_string(),
+            outcome_options: vec!["Yes".to_string(), "No".to_string()],
+        }
+    }
+
+    #[test]
+    fn only_resolution_and_dispute_events_trigger() {
+        assert_eq!(WatchTrigger::for_event_kind("resolv_fx"), Some(WatchTrigger::Resolve));
+        assert_eq!(WatchTrigger::for_event_kind("disp_file"), Some(WatchTrigger::Dispute));
+        assert_eq!(WatchTrigger::for_event_kind("bet_place"), None);
+    }
+
+    #[test]
+    fn trigger_labels_round_trip() {
+        for trigger in [WatchTrigger::Resolve, WatchTrigger::Dispute, WatchTrigger::Deadline] {
+            assert_eq!(WatchTrigger::parse(trigger.label()), Some(trigger));
+            assert_eq!(serde_json::to_value(trigger).unwrap(), trigger.label());
+        }
+        assert_eq!(WatchTrigger::parse("close"), None);
+    }
+
+    #[test]
+    fn resolution_data_names_the_winning_outcome() {
+        let data = notification_data("https://predictiq.io/", &event("resolv_fx", Some(1)), 7, &recipient());
+        assert_eq!(data["event"], "resolved");
+        assert_eq!(data["outcome_label"], "No");
+        assert_eq!(data["payout_url"], "https://predictiq.io/markets/7");
+        assert_eq!(
+            data["unsubscribe_url"],
+            "https://predictiq.io/api/v1/watches/unsubscribe?token=tok"
+        );
+        assert_eq!(data["event_id"], "0000001-0001");
+    }
+
+    #[test]
+    fn dispute_data_has_no_outcome_and_unknown_indexes_fall_back() {
+        let data = notification_data("http://x", &event("disp_file", None), 7, &recipient());
+        assert_eq!(data["is_dispute"], true);
+        assert_eq!(data["outcome_label"], "");
+
+        let data = notification_data("http://x", &event("resolv_fx", Some(5)), 7, &recipient());
+        assert_eq!(data["outcome_label"], "Outcome 5");
+    }
+}
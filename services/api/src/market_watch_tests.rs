@@ -0,0 +1,259 @@
+#[cfg(test)]
+mod market_watch_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{delete, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        blockchain::IndexedEvent,
+        handlers::{market_watch_create, market_watch_delete, MarketWatchResponse},
+        market_watch::{notify_watchers, WatchTrigger},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 3] = [9501, 9502, 9503];
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/markets/:market_id/watches", post(market_watch_create))
+            .route("/watches/:token", delete(market_watch_delete))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    async fn post_watch(state: &Arc<crate::AppState>, market_id: i64, body: Value) -> (StatusCode, Value) {
+        send(
+            state,
+            Request::builder()
+                .method("POST")
+                .uri(format!("/markets/{market_id}/watches"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, outcome_options) \
+             VALUES ($1, 'Watch test', 'active', 0, NOW() + INTERVAL '1 day', NOW(), ARRAY['Yes', 'No'])",
+        )
+        .bind(market_id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn watch(state: &crate::AppState, market_id: i64, email: &str, notify_on: &[WatchTrigger]) {
+        state
+            .db
+            .market_watch_upsert(
+                state.networks.primary().network(),
+                market_id,
+                email,
+                None,
+                notify_on,
+                &crate::market_watch::new_unsubscribe_token(),
+            )
+            .await
+            .unwrap()
+            .expect("market exists");
+    }
+
+    fn event(id: &str, kind: &str, market_id: i64) -> IndexedEvent {
+        IndexedEvent {
+            id: id.to_string(),
+            ledger: 100,
+            tx_hash: None,
+            kind: kind.to_string(),
+            market_id: Some(market_id),
+            address: None,
+            outcome: Some(0),
+            amount: None,
+            token: None,
+            is_refund: false,
+        }
+    }
+
+    async fn notify(state: &crate::AppState, event: &IndexedEvent) -> usize {
+        notify_watchers(
+            &state.db,
+            &state.email_queue,
+            &state.config.base_url,
+            state.networks.primary().network(),
+            event,
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Email jobs per recipient for the seeded markets, sorted by email.
+    async fn jobs(state: &crate::AppState, market_id: i64) -> Vec<(String, i64)> {
+        sqlx::query_as(
+            "SELECT recipient_email, COUNT(*) FROM email_jobs \
+             WHERE template_name = 'market_resolved' AND (template_data->>'market_id')::BIGINT = $1 \
+             GROUP BY recipient_email ORDER BY recipient_email",
+        )
+        .bind(market_id)
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pool = state.db.pool();
+        sqlx::query(
+            "DELETE FROM email_jobs WHERE template_name = 'market_resolved' \
+             AND (template_data->>'market_id')::BIGINT = ANY($1)",
+        )
+        .bind(&SEED_MARKETS[..])
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("DELETE FROM email_suppressions WHERE email LIKE 'watch-95__-%@example.com'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Cascades to market_watches and market_watch_notifications.
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Replaying the same event never enqueues a second email, and watchers
+    /// who did not ask for resolutions get none.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_each_watcher_notified_once_per_event() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9501).await;
+        watch(&state, 9501, "watch-9501-a@example.com", &[WatchTrigger::Resolve]).await;
+        watch(&state, 9501, "watch-9501-b@example.com", &[WatchTrigger::Resolve, WatchTrigger::Dispute]).await;
+        watch(&state, 9501, "watch-9501-c@example.com", &[WatchTrigger::Dispute]).await;
+
+        let resolved = event("0000000100-0001", "resolv_fx", 9501);
+        assert_eq!(notify(&state, &resolved).await, 2);
+        assert_eq!(notify(&state, &resolved).await, 0);
+
+        assert_eq!(
+            jobs(&state, 9501).await,
+            vec![
+                ("watch-9501-a@example.com".to_string(), 1),
+                ("watch-9501-b@example.com".to_string(), 1),
+            ]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Distinct events for the same market each notify once.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_distinct_events_each_notify() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9502).await;
+        watch(&state, 9502, "watch-9502-a@example.com", &[WatchTrigger::Resolve, WatchTrigger::Dispute]).await;
+
+        assert_eq!(notify(&state, &event("0000000100-0002", "disp_file", 9502)).await, 1);
+        assert_eq!(notify(&state, &event("0000000200-0002", "resolv_fx", 9502)).await, 1);
+        // Events that are not watch triggers enqueue nothing.
+        assert_eq!(notify(&state, &event("0000000300-0002", "bet_place", 9502)).await, 0);
+
+        assert_eq!(jobs(&state, 9502).await, vec![("watch-9502-a@example.com".to_string(), 2)]);
+
+        cleanup(&state).await;
+    }
+
+    /// Suppressed addresses are never claimed; unsubscribing removes the watch.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_suppressed_and_unsubscribed_watchers_skipped() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9503).await;
+
+        let (status, body) = post_watch(&state, 9503, json!({ "email": "watch-9503-a@example.com" })).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let created: MarketWatchResponse = serde_json::from_value(body).unwrap();
+        assert_eq!(created.watch.notify_on, vec![WatchTrigger::Resolve]);
+
+        watch(&state, 9503, "watch-9503-b@example.com", &[WatchTrigger::Resolve]).await;
+        state
+            .db
+            .email_add_suppression("watch-9503-b@example.com", "bounce", Some("test"), Some("hard"))
+            .await
+            .unwrap();
+
+        let (status, _) = send(
+            &state,
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/watches/{}", created.unsubscribe_token))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        assert_eq!(notify(&state, &event("0000000100-0003", "resolv_fx", 9503)).await, 0);
+        assert!(jobs(&state, 9503).await.is_empty());
+
+        cleanup(&state).await;
+    }
+
+    /// Unknown markets are a 404 and invalid emails a 400.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_watch_create_validation() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let (status, _) = post_watch(&state, 9501, json!({ "email": "watch-9501-a@example.com" })).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        seed_market(&state, 9501).await;
+        let (status, body) = post_watch(&state, 9501, json!({ "email": "not-an-email" })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+
+        let (status, _) = post_watch(&state, 9501, json!({ "email": "a@example.com", "notify_on": [] })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        cleanup(&state).await;
+    }
+
+}
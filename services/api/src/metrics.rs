@@ -1,15 +1,40 @@
-use std::time::Duration;
+use std::{
+    pin::Pin,
+    task::Poll,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, State},
+    http::Request,
+    middleware::Next,
+    response::Response,
+};
+use http_body::Body as HttpBody;
 use prometheus::{Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry, TextEncoder};
 
 const MAX_LABEL_VALUE_LEN: usize = 48;
 
+/// Lowercases `value` for use as a label: anything outside `[a-z0-9]`
+/// becomes `_`, a `V<digits>` version segment glued to a word (`ApiV1`) is
+/// split off with `_`, and the result is capped at [`MAX_LABEL_VALUE_LEN`].
 fn normalize_label(value: &str) -> String {
-    let sanitized: String = value
-        .chars()
-        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
-        .collect();
+    let chars: Vec<char> = value.chars().collect();
+    let mut sanitized = String::with_capacity(value.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+        let version_start = c == 'V'
+            && prev.is_some_and(|p| p.is_ascii_lowercase())
+            && next.is_some_and(|n| n.is_ascii_digit());
+        let after_version = c.is_ascii_uppercase() && prev.is_some_and(|p| p.is_ascii_digit());
+        if version_start || after_version {
+            sanitized.push('_');
+        }
+        sanitized.push(if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' });
+    }
     let sanitized = sanitized.trim_matches('_').to_string();
     if sanitized.len() > MAX_LABEL_VALUE_LEN {
         let head = &sanitized[..(MAX_LABEL_VALUE_LEN - 8)];
@@ -32,16 +57,22 @@ pub struct Metrics {
     request_latency: HistogramVec,
     rpc_errors: IntCounterVec,
     rpc_fallbacks: IntCounterVec,
+    rpc_calls: IntCounterVec,
     db_query_duration: HistogramVec,
     db_timeouts: IntCounterVec,
     db_pool_exhaustion: IntCounterVec,
     ledger_gaps: IntCounterVec,
     email_dlq_size: IntGauge,
     email_queue_depth: IntGauge,
+    email_dead_lettered: IntCounterVec,
+    db_pool_size: IntGaugeVec,
     db_pool_connections_active: IntGaugeVec,
     db_pool_connections_idle: IntGaugeVec,
     db_pool_acquire_duration: HistogramVec,
+    slow_queries: IntCounterVec,
     rate_limit_rejections: IntCounterVec,
+    resource_rate_limit_rejections: IntCounterVec,
+    abuse_blocks: IntCounterVec,
     deprecated_api_calls: IntCounterVec,
     /// Counts Redis errors encountered by security-critical rate limiters.
     /// A non-zero rate here means the rate limiter is running in fail-closed
@@ -49,6 +80,21 @@ pub struct Metrics {
     /// Metric: `rate_limiter_redis_errors_total{limiter="<name>"}`
     rate_limiter_redis_errors: IntCounterVec,
     watched_tx_count: IntGauge,
+    tx_watch_expired: IntCounterVec,
+    endpoint_latency: HistogramVec,
+    endpoint_responses: IntCounterVec,
+    endpoint_response_size: HistogramVec,
+    sync_lag_ledgers: IntGaugeVec,
+    sync_watch_markets: IntGaugeVec,
+    sync_refreshes_skipped: IntCounterVec,
+    background_task_restarts: IntCounterVec,
+    cache_warms: IntCounterVec,
+    retention_rows: IntCounterVec,
+    retention_dry_run_rows: IntGaugeVec,
+    event_invalidations: IntCounterVec,
+    redis_command_timeouts: prometheus::IntCounter,
+    api_key_quota_rejections: prometheus::IntCounter,
+    pending_override_hits: IntCounterVec,
     /// Counts authentication failures by failure reason.
     /// Labels: `reason` — one of: "invalid_api_key", "expired_token", "missing_credentials".
     auth_failures: IntCounterVec,
@@ -98,20 +144,29 @@ impl Metrics {
         .context("request_latency metric")?;
 
         let rpc_errors = IntCounterVec::new(
-            prometheus::Opts::new("rpc_errors_total", "RPC errors by method"),
-            &["method"],
+            prometheus::Opts::new("rpc_errors_total", "RPC errors by network and method"),
+            &["network", "method"],
         )
         .context("rpc_errors metric")?;
 
         let rpc_fallbacks = IntCounterVec::new(
             prometheus::Opts::new(
                 "rpc_fallbacks_total",
-                "RPC calls that fell back to zero/default payload, by endpoint",
+                "RPC calls that fell back to zero/default payload, by network and endpoint",
             ),
-            &["endpoint"],
+            &["network", "endpoint"],
         )
         .context("rpc_fallbacks metric")?;
 
+        let rpc_calls = IntCounterVec::new(
+            prometheus::Opts::new(
+                "rpc_calls_total",
+                "Soroban RPC calls, counted once after retries, by network and outcome (ok|error)",
+            ),
+            &["network", "outcome"],
+        )
+        .context("rpc_calls metric")?;
+
         let db_query_duration = HistogramVec::new(
             prometheus::HistogramOpts::new(
                 "db_query_duration_seconds",
@@ -139,18 +194,45 @@ impl Metrics {
         )
         .context("db_pool_exhaustion metric")?;
 
+        let ledger_gaps = IntCounterVec::new(
+            prometheus::Opts::new(
+                "ledger_gaps_total",
+                "Ledgers skipped between sync passes, by network",
+            ),
+            &["network"],
+        )
+        .context("ledger_gaps metric")?;
+
         let email_dlq_size = IntGauge::new(
             "email_dlq_size",
             "Number of email jobs currently in the dead-letter queue",
         )
         .context("email_dlq_size metric")?;
 
+        let email_dead_lettered = IntCounterVec::new(
+            prometheus::Opts::new(
+                "email_dead_letter_total",
+                "Email jobs moved to dead_letter after exhausting their attempts, by template",
+            ),
+            &["template"],
+        )
+        .context("email_dead_letter_total metric")?;
+
         let email_queue_depth = IntGauge::new(
             "email_queue_depth",
             "Number of email jobs currently in the main queue",
         )
         .context("email_queue_depth metric")?;
 
+        let db_pool_size = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "db_pool_size",
+                "Connections open in the pool, idle and checked out",
+            ),
+            &["pool"],
+        )
+        .context("db_pool_size metric")?;
+
         let db_pool_connections_active = IntGaugeVec::new(
             prometheus::Opts::new(
                 "db_pool_connections_active",
@@ -181,6 +263,15 @@ impl Metrics {
         )
         .context("db_pool_acquire_duration metric")?;
 
+        let slow_queries = IntCounterVec::new(
+            prometheus::Opts::new(
+                "slow_queries_total",
+                "DB queries slower than DB_SLOW_QUERY_THRESHOLD_MS, by query name",
+            ),
+            &["query"],
+        )
+        .context("slow_queries metric")?;
+
         let rate_limit_rejections = IntCounterVec::new(
             prometheus::Opts::new(
                 "rate_limit_rejections_total",
@@ -190,6 +281,24 @@ impl Metrics {
         )
         .context("rate_limit_rejections metric")?;
 
+        let resource_rate_limit_rejections = IntCounterVec::new(
+            prometheus::Opts::new(
+                "rate_limit_resource_rejections_total",
+                "Requests rejected by the per-resource limiter, by resource kind (wallet, market)",
+            ),
+            &["kind"],
+        )
+        .context("resource_rate_limit_rejections metric")?;
+
+        let abuse_blocks = IntCounterVec::new(
+            prometheus::Opts::new(
+                "rate_limit_abuse_blocks_total",
+                "Client IPs blocked after repeated 429s, by the limiter that issued the last one",
+            ),
+            &["limiter"],
+        )
+        .context("abuse_blocks metric")?;
+
         let deprecated_api_calls = IntCounterVec::new(
             prometheus::Opts::new(
                 "deprecated_api_calls_total",
@@ -279,22 +388,161 @@ impl Metrics {
         )
         .context("watched_tx_count metric")?;
 
+        let tx_watch_expired = IntCounterVec::new(
+            prometheus::Opts::new(
+                "tx_watch_expired_total",
+                "Watched transactions dropped after WATCHED_TX_TTL_SECS without reaching a final status",
+            ),
+            &["network"],
+        )
+        .context("tx_watch_expired metric")?;
+
+        let endpoint_latency = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "http_endpoint_request_duration_seconds",
+                "HTTP request latency in seconds by matched route template",
+            )
+            .buckets(vec![
+                0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+            ]),
+            &["endpoint"],
+        )
+        .context("endpoint_latency metric")?;
+
+        let endpoint_responses = IntCounterVec::new(
+            prometheus::Opts::new(
+                "http_endpoint_responses_total",
+                "HTTP responses by matched route template and status class (2xx, 4xx, ...)",
+            ),
+            &["endpoint", "status_class"],
+        )
+        .context("endpoint_responses metric")?;
+
+        let endpoint_response_size = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "http_endpoint_response_size_bytes",
+                "HTTP response body size in bytes, before compression, by matched route template",
+            )
+            .buckets(prometheus::exponential_buckets(256.0, 4.0, 10).expect("valid buckets")),
+            &["endpoint"],
+        )
+        .context("endpoint_response_size metric")?;
+
+        let sync_lag_ledgers = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "blockchain_sync_lag_ledgers",
+                "Ledgers between the chain head and the sync cursor, by network",
+            ),
+            &["network"],
+        )
+        .context("sync_lag_ledgers metric")?;
+
+        let sync_watch_markets = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "blockchain_sync_watch_markets",
+                "Markets whose chain data the sync worker refreshes each pass, by network",
+            ),
+            &["network"],
+        )
+        .context("sync_watch_markets metric")?;
+
+        let sync_refreshes_skipped = IntCounterVec::new(
+            prometheus::Opts::new(
+                "blockchain_sync_refreshes_skipped_total",
+                "Chain data refreshes the sync worker put off, by network and reason (budget, backoff)",
+            ),
+            &["network", "reason"],
+        )
+        .context("sync_refreshes_skipped metric")?;
+
+        let background_task_restarts = IntCounterVec::new(
+            prometheus::Opts::new(
+                "background_task_restarts_total",
+                "Times a supervised background task was restarted after a panic or early exit, by task",
+            ),
+            &["task"],
+        )
+        .context("background_task_restarts metric")?;
+
+        let cache_warms = IntCounterVec::new(
+            prometheus::Opts::new(
+                "cache_warm_total",
+                "Scheduled cache warming attempts by target and outcome (success, failure)",
+            ),
+            &["target", "outcome"],
+        )
+        .context("cache_warms metric")?;
+
+        let retention_rows = IntCounterVec::new(
+            prometheus::Opts::new(
+                "retention_rows_total",
+                "Rows the retention task deleted or anonymized, by table and action",
+            ),
+            &["table", "action"],
+        )
+        .context("retention_rows metric")?;
+
+        let retention_dry_run_rows = IntGaugeVec::new(
+            prometheus::Opts::new(
+                "retention_dry_run_rows",
+                "Rows the last dry run of the retention task would have changed, by table",
+            ),
+            &["table"],
+        )
+        .context("retention_dry_run_rows metric")?;
+
+        let event_invalidations = IntCounterVec::new(
+            prometheus::Opts::new(
+                "cache_event_invalidations_total",
+                "Cache keys invalidated by the sync worker, by network and contract event topic",
+            ),
+            &["network", "topic"],
+        )
+        .context("event_invalidations metric")?;
+
+        let redis_command_timeouts = prometheus::IntCounter::new(
+            "redis_command_timeouts_total",
+            "Redis commands abandoned after REDIS_COMMAND_TIMEOUT_MS; the caller fell back to its source",
+        )
+        .context("redis_command_timeouts metric")?;
+
+        let api_key_quota_rejections = prometheus::IntCounter::new(
+            "api_key_quota_rejections_total",
+            "Requests rejected because their API key had used its monthly quota",
+        )
+        .context("api_key_quota_rejections metric")?;
+
+        let pending_override_hits = IntCounterVec::new(
+            prometheus::Opts::new(
+                "pending_override_hits_total",
+                "Market reads answered from a pending override because the chain had not caught up with an admin write, by network",
+            ),
+            &["network"],
+        )
+        .context("pending_override_hits metric")?;
+
         registry.register(Box::new(cache_hits.clone()))?;
         registry.register(Box::new(cache_misses.clone()))?;
         registry.register(Box::new(invalidations.clone()))?;
         registry.register(Box::new(request_latency.clone()))?;
         registry.register(Box::new(rpc_errors.clone()))?;
         registry.register(Box::new(rpc_fallbacks.clone()))?;
+        registry.register(Box::new(rpc_calls.clone()))?;
         registry.register(Box::new(db_query_duration.clone()))?;
         registry.register(Box::new(db_timeouts.clone()))?;
         registry.register(Box::new(db_pool_exhaustion.clone()))?;
         registry.register(Box::new(ledger_gaps.clone()))?;
         registry.register(Box::new(email_dlq_size.clone()))?;
+        registry.register(Box::new(email_dead_lettered.clone()))?;
         registry.register(Box::new(email_queue_depth.clone()))?;
+        registry.register(Box::new(db_pool_size.clone()))?;
         registry.register(Box::new(db_pool_connections_active.clone()))?;
         registry.register(Box::new(db_pool_connections_idle.clone()))?;
         registry.register(Box::new(db_pool_acquire_duration.clone()))?;
+        registry.register(Box::new(slow_queries.clone()))?;
         registry.register(Box::new(rate_limit_rejections.clone()))?;
+        registry.register(Box::new(resource_rate_limit_rejections.clone()))?;
+        registry.register(Box::new(abuse_blocks.clone()))?;
         registry.register(Box::new(deprecated_api_calls.clone()))?;
         registry.register(Box::new(rate_limiter_redis_errors.clone()))?;
         registry.register(Box::new(auth_failures.clone()))?;
@@ -306,6 +554,21 @@ impl Metrics {
         registry.register(Box::new(worker_status.clone()))?;
         registry.register(Box::new(cache_circuit_breaker_state.clone()))?;
         registry.register(Box::new(watched_tx_count.clone()))?;
+        registry.register(Box::new(tx_watch_expired.clone()))?;
+        registry.register(Box::new(endpoint_latency.clone()))?;
+        registry.register(Box::new(endpoint_responses.clone()))?;
+        registry.register(Box::new(endpoint_response_size.clone()))?;
+        registry.register(Box::new(sync_lag_ledgers.clone()))?;
+        registry.register(Box::new(sync_watch_markets.clone()))?;
+        registry.register(Box::new(sync_refreshes_skipped.clone()))?;
+        registry.register(Box::new(background_task_restarts.clone()))?;
+        registry.register(Box::new(cache_warms.clone()))?;
+        registry.register(Box::new(retention_rows.clone()))?;
+        registry.register(Box::new(retention_dry_run_rows.clone()))?;
+        registry.register(Box::new(event_invalidations.clone()))?;
+        registry.register(Box::new(redis_command_timeouts.clone()))?;
+        registry.register(Box::new(api_key_quota_rejections.clone()))?;
+        registry.register(Box::new(pending_override_hits.clone()))?;
 
         Ok(Self {
             registry,
@@ -315,16 +578,22 @@ impl Metrics {
             request_latency,
             rpc_errors,
             rpc_fallbacks,
+            rpc_calls,
             db_query_duration,
             db_timeouts,
             db_pool_exhaustion,
             ledger_gaps,
             email_dlq_size,
+            email_dead_lettered,
             email_queue_depth,
+            db_pool_size,
             db_pool_connections_active,
             db_pool_connections_idle,
             db_pool_acquire_duration,
+            slow_queries,
             rate_limit_rejections,
+            resource_rate_limit_rejections,
+            abuse_blocks,
             deprecated_api_calls,
             rate_limiter_redis_errors,
             auth_failures,
@@ -336,6 +605,21 @@ impl Metrics {
             worker_status,
             cache_circuit_breaker_state,
             watched_tx_count,
+            tx_watch_expired,
+            endpoint_latency,
+            endpoint_responses,
+            endpoint_response_size,
+            sync_lag_ledgers,
+            sync_watch_markets,
+            sync_refreshes_skipped,
+            background_task_restarts,
+            cache_warms,
+            retention_rows,
+            retention_dry_run_rows,
+            event_invalidations,
+            redis_command_timeouts,
+            api_key_quota_rejections,
+            pending_override_hits,
         })
     }
 
@@ -367,14 +651,31 @@ impl Metrics {
             .observe(duration);
     }
 
-    pub fn observe_rpc_error(&self, method: &str) {
-        let labels = normalize_label_values(&[method]);
-        self.rpc_errors.with_label_values(&[&labels[0]]).inc();
+    pub fn observe_rpc_error(&self, network: &str, method: &str) {
+        let labels = normalize_label_values(&[network, method]);
+        self.rpc_errors.with_label_values(&[&labels[0], &labels[1]]).inc();
     }
 
-    pub fn observe_rpc_fallback(&self, endpoint: &str) {
-        let labels = normalize_label_values(&[endpoint]);
-        self.rpc_fallbacks.with_label_values(&[&labels[0]]).inc();
+    /// Count one RPC call on `network`, after any retries.
+    pub fn observe_rpc_call(&self, network: &str, ok: bool) {
+        let outcome = if ok { "ok" } else { "error" };
+        self.rpc_calls
+            .with_label_values(&[&normalize_label(network), outcome])
+            .inc();
+    }
+
+    /// Running `(calls, failed)` totals for `network` since startup; see
+    /// [`crate::alerting`] for the sliding-window rate built on them.
+    pub fn rpc_call_totals(&self, network: &str) -> (u64, u64) {
+        let network = normalize_label(network);
+        let ok = self.rpc_calls.with_label_values(&[&network, "ok"]).get();
+        let failed = self.rpc_calls.with_label_values(&[&network, "error"]).get();
+        (ok + failed, failed)
+    }
+
+    pub fn observe_rpc_fallback(&self, network: &str, endpoint: &str) {
+        let labels = normalize_label_values(&[network, endpoint]);
+        self.rpc_fallbacks.with_label_values(&[&labels[0], &labels[1]]).inc();
     }
 
     pub fn observe_db_query_duration(&self, query_name: &str, duration: Duration) {
@@ -388,6 +689,20 @@ impl Metrics {
         self.db_timeouts.with_label_values(&[&labels[0]]).inc();
     }
 
+    /// Count a query slower than the slow-query threshold.
+    pub fn observe_slow_query(&self, query_name: &str) {
+        self.slow_queries
+            .with_label_values(&[&normalize_label(query_name)])
+            .inc();
+    }
+
+    /// Slow queries counted for `query_name` since startup.
+    pub fn slow_query_count(&self, query_name: &str) -> u64 {
+        self.slow_queries
+            .with_label_values(&[&normalize_label(query_name)])
+            .get()
+    }
+
     pub fn observe_db_pool_exhaustion(&self, pool: &str) {
         self.db_pool_exhaustion
             .with_label_values(&[pool])
@@ -407,6 +722,12 @@ impl Metrics {
         self.email_dlq_size.set(n);
     }
 
+    pub fn observe_email_dead_lettered(&self, template: &str) {
+        self.email_dead_lettered
+            .with_label_values(&[&normalize_label(template)])
+            .inc();
+    }
+
     pub fn set_email_queue_depth(&self, n: i64) {
         self.email_queue_depth.set(n);
     }
@@ -419,30 +740,19 @@ impl Metrics {
         }
     }
 
-    /// Update connection pool utilisation gauges.
-    /// Call this on each pool event (connection acquired, released, opened, closed).
-    pub fn observe_pool_connections(&self, pool_label: &str, active: i64, idle: i64) {
-        let labels = normalize_label_values(&[pool_label]);
+    /// Update the pool gauges from a snapshot of its open (`size`) and idle
+    /// connections; the rest are checked out.
+    pub fn set_pool_state(&self, pool: &str, size: i64, idle: i64) {
+        let labels = normalize_label_values(&[pool]);
+        self.db_pool_size.with_label_values(&[&labels[0]]).set(size);
         self.db_pool_connections_active
             .with_label_values(&[&labels[0]])
-            .set(active);
+            .set(size.saturating_sub(idle));
         self.db_pool_connections_idle
             .with_label_values(&[&labels[0]])
             .set(idle);
     }
 
-    /// Snapshot the current pool size and idle count into Prometheus gauges.
-    /// Call this before rendering `/metrics` so the values are current.
-    pub fn record_pool_metrics(&self, max: u32, idle: i64) {
-        let pool_label = format!("pool_{}", max);
-        self.db_pool_connections_active
-            .with_label_values(&[&pool_label])
-            .set((max as i64).saturating_sub(idle));
-        self.db_pool_connections_idle
-            .with_label_values(&[&pool_label])
-            .set(idle);
-    }
-
     /// Record how long the caller waited to acquire a connection from the pool.
     pub fn observe_pool_acquire(&self, pool: &str, duration: Duration) {
         let labels = normalize_label_values(&[pool]);
@@ -460,6 +770,19 @@ impl Metrics {
             .inc();
     }
 
+    /// Count a 429 from the per-resource limiter; `kind` is `wallet` or `market`.
+    pub fn observe_resource_rate_limit_rejection(&self, kind: &str) {
+        self.resource_rate_limit_rejections
+            .with_label_values(&[kind])
+            .inc();
+    }
+
+    /// Count a client IP being blocked (or its block extended) after repeated
+    /// 429s from `limiter`.
+    pub fn observe_abuse_block(&self, limiter: &str) {
+        self.abuse_blocks.with_label_values(&[limiter]).inc();
+    }
+
     pub fn observe_deprecated_api_call(&self, version: &str) {
         self.deprecated_api_calls
             .with_label_values(&[version])
@@ -557,6 +880,107 @@ impl Metrics {
         self.watched_tx_count.set(n);
     }
 
+    /// Count watched transactions on `network` that aged out of the watch set.
+    pub fn observe_tx_watch_expired(&self, network: &str, count: u64) {
+        if count > 0 {
+            self.tx_watch_expired.with_label_values(&[network]).inc_by(count);
+        }
+    }
+
+    /// Record one response for `endpoint`, a route template such as
+    /// `/api/v1/markets/:market_id`. Templates are bounded by the router, so
+    /// they are used as-is rather than normalised.
+    pub fn observe_endpoint(&self, endpoint: &str, status_code: u16, duration: Duration) {
+        self.endpoint_latency
+            .with_label_values(&[endpoint])
+            .observe(duration.as_secs_f64());
+        self.endpoint_responses
+            .with_label_values(&[endpoint, status_class(status_code)])
+            .inc();
+    }
+
+    /// Record the body size of one response under its route template.
+    pub fn observe_response_size(&self, endpoint: &str, bytes: u64) {
+        self.endpoint_response_size
+            .with_label_values(&[endpoint])
+            .observe(bytes as f64);
+    }
+
+    /// Update the gap between the chain head and the sync cursor on `network`.
+    pub fn set_sync_lag(&self, network: &str, ledgers: u32) {
+        self.sync_lag_ledgers
+            .with_label_values(&[&normalize_label(network)])
+            .set(i64::from(ledgers));
+    }
+
+    /// Record how many markets the sync worker on `network` is refreshing.
+    pub fn set_sync_watch_size(&self, network: &str, markets: usize) {
+        self.sync_watch_markets
+            .with_label_values(&[&normalize_label(network)])
+            .set(markets as i64);
+    }
+
+    /// Count `count` refreshes the sync worker on `network` put off this
+    /// pass: `reason` is `budget` when the call budget ran out, `backoff`
+    /// for dormant markets not yet due.
+    pub fn observe_sync_refreshes_skipped(&self, network: &str, reason: &str, count: usize) {
+        if count > 0 {
+            self.sync_refreshes_skipped
+                .with_label_values(&[&normalize_label(network), reason])
+                .inc_by(count as u64);
+        }
+    }
+
+    /// Count one restart of the supervised task `task`; see [`crate::supervisor`].
+    pub fn observe_background_task_restart(&self, task: &str) {
+        self.background_task_restarts.with_label_values(&[task]).inc();
+    }
+
+    pub fn observe_cache_warm(&self, target: &str, success: bool) {
+        let outcome = if success { "success" } else { "failure" };
+        self.cache_warms.with_label_values(&[target, outcome]).inc();
+    }
+
+    /// Count rows the retention task changed in `table`; see
+    /// [`crate::retention`].
+    pub fn observe_retention_rows(&self, table: &str, action: &str, rows: u64) {
+        self.retention_rows.with_label_values(&[table, action]).inc_by(rows);
+    }
+
+    pub fn set_retention_dry_run_rows(&self, table: &str, rows: u64) {
+        self.retention_dry_run_rows
+            .with_label_values(&[table])
+            .set(i64::try_from(rows).unwrap_or(i64::MAX));
+    }
+
+    pub fn observe_redis_command_timeout(&self) {
+        self.redis_command_timeouts.inc();
+    }
+
+    pub fn observe_api_key_quota_rejection(&self) {
+        self.api_key_quota_rejections.inc();
+    }
+
+    pub fn observe_pending_override_hit(&self, network: &str) {
+        self.pending_override_hits
+            .with_label_values(&[&normalize_label(network)])
+            .inc();
+    }
+
+    pub fn pending_override_hit_count(&self, network: &str) -> u64 {
+        self.pending_override_hits
+            .with_label_values(&[&normalize_label(network)])
+            .get()
+    }
+
+    pub fn observe_event_invalidation(&self, network: &str, topic: &str, count: usize) {
+        if count > 0 {
+            self.event_invalidations
+                .with_label_values(&[network, topic])
+                .inc_by(count as u64);
+        }
+    }
+
     pub fn render(&self) -> anyhow::Result<String> {
         let mut buffer = vec![];
         let encoder = TextEncoder::new();
@@ -566,6 +990,107 @@ impl Metrics {
     }
 }
 
+fn status_class(status_code: u16) -> &'static str {
+    match status_code {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        500..=599 => "5xx",
+        _ => "other",
+    }
+}
+
+/// Endpoint label for requests that matched no route, so probes for random
+/// paths cannot grow the label set.
+pub const UNMATCHED_ENDPOINT: &str = "unmatched";
+
+/// Response body that counts the bytes it yields and records the total in
+/// `http_endpoint_response_size_bytes` when the stream ends. A body dropped
+/// early (client gone) records what was sent.
+struct MeteredBody {
+    inner: Body,
+    bytes: u64,
+    /// Taken once the size has been recorded.
+    observer: Option<(Metrics, String)>,
+}
+
+impl MeteredBody {
+    fn finish(&mut self) {
+        if let Some((metrics, endpoint)) = self.observer.take() {
+            metrics.observe_response_size(&endpoint, self.bytes);
+        }
+    }
+}
+
+impl HttpBody for MeteredBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Bytes>, axum::Error>>> {
+        let polled = Pin::new(&mut self.inner).poll_frame(cx);
+        match &polled {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.bytes += data.len() as u64;
+                }
+            }
+            Poll::Ready(None) => self.finish(),
+            _ => {}
+        }
+        polled
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for MeteredBody {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Records latency, status class and body size for every request under the
+/// matched route template, so handlers need no instrumentation of their own.
+/// A body of known length is measured up front; a streamed one as it is
+/// sent. Must be added with `Router::layer` for [`MatchedPath`] to be
+/// available.
+pub async fn http_metrics_middleware(
+    State(metrics): State<Metrics>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let endpoint = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned())
+        .unwrap_or_else(|| UNMATCHED_ENDPOINT.to_owned());
+    let start = Instant::now();
+    let response = next.run(req).await;
+    metrics.observe_endpoint(&endpoint, response.status().as_u16(), start.elapsed());
+
+    if let Some(len) = response.body().size_hint().exact() {
+        metrics.observe_response_size(&endpoint, len);
+        return response;
+    }
+    response.map(|inner| {
+        Body::new(MeteredBody {
+            inner,
+            bytes: 0,
+            observer: Some((metrics, endpoint)),
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -595,6 +1120,7 @@ mod tests {
     fn normalize_label_lowercases_and_sanitises() {
         assert_eq!(normalize_label("ApiV1_Statistics"), "api_v1_statistics");
         assert_eq!(normalize_label("featured-markets"), "featured_markets");
+        assert_eq!(normalize_label("ApiV2Markets"), "api_v2_markets");
     }
 
     #[test]
@@ -628,43 +1154,65 @@ mod tests {
         m.observe_miss("api", "featured_markets");
         m.observe_invalidation("market_resolve", 5);
         m.observe_request("statistics", 200, 0.05);
-        m.observe_rpc_error("getContractData");
-        m.observe_rpc_fallback("market_data");
+        m.observe_rpc_error("testnet", "getContractData");
+        m.observe_rpc_fallback("testnet", "market_data");
+        m.observe_rpc_call("testnet", true);
+        m.observe_rpc_call("testnet", false);
+        assert_eq!(m.rpc_call_totals("testnet"), (2, 1));
         m.observe_db_timeout("statistics");
-        m.record_pool_metrics(10, 4);
-        m.observe_pool_acquire("pool_10", Duration::from_millis(2));
+        m.set_pool_state("primary", 10, 4);
+        m.observe_pool_acquire("primary", Duration::from_millis(2));
+        m.observe_slow_query("statistics");
+        assert_eq!(m.slow_query_count("statistics"), 1);
         m.observe_rate_limit_rejection("ratelimit");
         m.observe_tx_eviction(3);
+        m.observe_event_invalidation("testnet", "resolv_fx", 7);
+        m.observe_redis_command_timeout();
+        m.observe_api_key_quota_rejection();
+        m.observe_pending_override_hit("testnet");
+        assert_eq!(m.pending_override_hit_count("testnet"), 1);
         m.set_dlq_size(7);
         m.set_email_queue_depth(12);
         m.observe_auth_failure("invalid_api_key");
         m.set_circuit_breaker_state(0);
         m.set_watched_tx_count(42);
+        m.observe_tx_watch_expired("testnet", 2);
+        m.set_sync_watch_size("testnet", 17);
         m.set_worker_status("test_worker", true);
+        m.observe_retention_rows("analytics_events", "delete", 12);
+        m.set_retention_dry_run_rows("email_events", 5);
         let rendered = m.render().expect("render must not fail");
         assert!(rendered.contains("cache_hits_total"));
         assert!(rendered.contains("http_request_duration_seconds"));
         assert!(rendered.contains("watched_tx_count 42"));
+        assert!(rendered.contains("db_pool_size{pool=\"primary\"} 10"));
+        assert!(rendered.contains("db_pool_connections_active{pool=\"primary\"} 6"));
+        assert!(rendered.contains("slow_queries_total{query=\"statistics\"} 1"));
+        assert!(rendered.contains("tx_watch_expired_total{network=\"testnet\"} 2"));
+        assert!(rendered.contains("blockchain_sync_watch_markets{network=\"testnet\"} 17"));
+        assert!(rendered.contains("retention_rows_total{action=\"delete\",table=\"analytics_events\"} 12"));
+        assert!(rendered.contains("retention_dry_run_rows{table=\"email_events\"} 5"));
     }
 
-    // ── record_pool_metrics ────────────────────────────────────────────────────
+    // ── set_pool_state ─────────────────────────────────────────────────────────
 
     #[test]
-    fn record_pool_metrics_sets_active_and_idle_gauges() {
+    fn set_pool_state_sets_size_active_and_idle_gauges() {
         let m = Metrics::new().unwrap();
-        m.record_pool_metrics(10, 3);
+        m.set_pool_state("primary", 10, 3);
         let rendered = m.render().unwrap();
-        assert!(rendered.contains("db_pool_connections_active{pool=\"pool_10\"} 7"));
-        assert!(rendered.contains("db_pool_connections_idle{pool=\"pool_10\"} 3"));
+        assert!(rendered.contains("db_pool_size{pool=\"primary\"} 10"));
+        assert!(rendered.contains("db_pool_connections_active{pool=\"primary\"} 7"));
+        assert!(rendered.contains("db_pool_connections_idle{pool=\"primary\"} 3"));
     }
 
     #[test]
-    fn record_pool_metrics_with_zero_active() {
+    fn set_pool_state_with_zero_idle() {
         let m = Metrics::new().unwrap();
-        m.record_pool_metrics(20, 0);
+        m.set_pool_state("primary", 20, 0);
         let rendered = m.render().unwrap();
-        assert!(rendered.contains("db_pool_connections_active{pool=\"pool_20\"} 20"));
-        assert!(rendered.contains("db_pool_connections_idle{pool=\"pool_20\"} 0"));
+        assert!(rendered.contains("db_pool_connections_active{pool=\"primary\"} 20"));
+        assert!(rendered.contains("db_pool_connections_idle{pool=\"primary\"} 0"));
     }
 
     // ── Cardinality guard: observe_request normalises labels ───────────────────
@@ -701,4 +1249,97 @@ mod tests {
         assert!(rendered.contains("layer=\"chain\""));
         assert!(rendered.contains("endpoint=\"oracle_result\""));
     }
+
+    #[test]
+    fn email_dead_letter_counter_is_labelled_by_template() {
+        let m = Metrics::new().unwrap();
+        m.observe_email_dead_lettered("market_resolved");
+        m.observe_email_dead_lettered("market_resolved");
+        let rendered = m.render().unwrap();
+        assert!(rendered.contains("email_dead_letter_total{template=\"market_resolved\"} 2"));
+    }
+
+    #[test]
+    fn status_class_buckets_by_hundreds() {
+        assert_eq!(status_class(204), "2xx");
+        assert_eq!(status_class(429), "4xx");
+        assert_eq!(status_class(503), "5xx");
+        assert_eq!(status_class(42), "other");
+    }
+
+    #[test]
+    fn sync_lag_is_labelled_by_network() {
+        let m = Metrics::new().unwrap();
+        m.set_sync_lag("testnet", 12);
+        let rendered = m.render().unwrap();
+        assert!(rendered.contains("blockchain_sync_lag_ledgers{network=\"testnet\"} 12"));
+    }
+
+    #[tokio::test]
+    async fn middleware_records_endpoint_template_and_status_class() {
+        use axum::{middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let m = Metrics::new().unwrap();
+        let app = Router::new()
+            .route("/api/v1/markets/:market_id", get(|| async { "ok" }))
+            .route(
+                "/api/v1/broken",
+                get(|| async { axum::http::StatusCode::BAD_GATEWAY }),
+            )
+            .layer(middleware::from_fn_with_state(m.clone(), http_metrics_middleware));
+
+        for uri in ["/api/v1/markets/1", "/api/v1/markets/2", "/api/v1/broken", "/nope"] {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(req).await.unwrap();
+        }
+
+        let rendered = m.render().unwrap();
+        assert!(rendered.contains("# TYPE http_endpoint_request_duration_seconds histogram"));
+        assert!(rendered.contains("# TYPE http_endpoint_responses_total counter"));
+        assert!(rendered.contains(
+            "http_endpoint_request_duration_seconds_count{endpoint=\"/api/v1/markets/:market_id\"} 2"
+        ));
+        assert!(rendered.contains(
+            "http_endpoint_responses_total{endpoint=\"/api/v1/markets/:market_id\",status_class=\"2xx\"} 2"
+        ));
+        assert!(rendered.contains(
+            "http_endpoint_responses_total{endpoint=\"/api/v1/broken\",status_class=\"5xx\"} 1"
+        ));
+        assert!(rendered.contains(
+            "http_endpoint_responses_total{endpoint=\"unmatched\",status_class=\"4xx\"} 1"
+        ));
+        assert!(!rendered.contains("/api/v1/markets/1"));
+    }
+
+    #[tokio::test]
+    async fn middleware_records_response_size_of_fixed_and_streamed_bodies() {
+        use axum::{middleware, routing::get, Router};
+        use http_body_util::BodyExt;
+        use tower::ServiceExt;
+
+        let m = Metrics::new().unwrap();
+        let app = Router::new()
+            .route("/fixed", get(|| async { "x".repeat(300) }))
+            .route(
+                "/stream",
+                get(|| async {
+                    let chunks = (0..10).map(|_| Ok::<_, std::io::Error>(Bytes::from(vec![b'y'; 100])));
+                    Body::from_stream(futures::stream::iter(chunks))
+                }),
+            )
+            .layer(middleware::from_fn_with_state(m.clone(), http_metrics_middleware));
+
+        for uri in ["/fixed", "/stream"] {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(req).await.unwrap();
+            response.into_body().collect().await.unwrap();
+        }
+
+        let rendered = m.render().unwrap();
+        assert!(rendered.contains("# TYPE http_endpoint_response_size_bytes histogram"));
+        assert!(rendered.contains("http_endpoint_response_size_bytes_sum{endpoint=\"/fixed\"} 300"));
+        assert!(rendered.contains("http_endpoint_response_size_bytes_sum{endpoint=\"/stream\"} 1000"));
+        assert!(rendered.contains("http_endpoint_response_size_bytes_count{endpoint=\"/stream\"} 1"));
+    }
 }
@@ -4,18 +4,26 @@
 //! time, skips already-applied versions, and records each applied migration
 //! in the `schema_migrations` table.
 //!
+//! [`crate::db::Database::new`] runs this at startup when `RUN_MIGRATIONS=true`
+//! and otherwise refuses to start while migrations are pending.
+//!
+//! Several files share a numeric prefix. Versions are the prefix plus a
+//! letter for the second and later files with that prefix, in filename order
+//! (`017`, `017b`, `017c`, ...), matching `scripts/run_migrations.sh`.
+//!
 //! # Usage
 //!
-//! ```rust
+//! ```ignore
 //! let runner = MigrationRunner::new(&pool);
 //! runner.run().await?;          // apply pending migrations
+//! runner.pending().await?;      // versions not yet applied
 //! runner.status().await?;       // print applied / pending state
 //! ```
 
 use anyhow::{bail, Context};
 use sha2::{Digest, Sha256};
 use sqlx::PgPool;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Returns true when the `MIGRATE_DRY_RUN` environment variable is set to `"true"`.
 fn is_dry_run() -> bool {
@@ -35,6 +43,14 @@ pub struct Migration {
     pub sql: &'static str,
 }
 
+impl Migration {
+    /// `CREATE INDEX CONCURRENTLY` cannot run inside a transaction, so these
+    /// migrations are applied one statement at a time instead.
+    fn is_transactional(&self) -> bool {
+        !self.sql.contains("CONCURRENTLY")
+    }
+}
+
 /// Applied migration record stored in `schema_migrations`.
 #[derive(Debug, sqlx::FromRow)]
 pub struct AppliedMigration {
@@ -94,15 +110,55 @@ const MIGRATIONS: &[Migration] = &[
         name: "008_create_email_tracking",
         sql: include_str!("../database/migrations/008_create_email_tracking.sql"),
     },
+    Migration {
+        version: "009",
+        name: "009_add_newsletter_indexes",
+        sql: include_str!("../database/migrations/009_add_newsletter_indexes.sql"),
+    },
+    Migration {
+        version: "010",
+        name: "010_add_soft_delete_newsletter",
+        sql: include_str!("../database/migrations/010_add_soft_delete_newsletter.sql"),
+    },
+    Migration {
+        version: "010b",
+        name: "010_create_audit_log",
+        sql: include_str!("../database/migrations/010_create_audit_log.sql"),
+    },
     Migration {
         version: "011",
         name: "011_create_markets",
         sql: include_str!("../database/migrations/011_create_markets.sql"),
     },
+    Migration {
+        version: "012",
+        name: "012_add_performance_indexes",
+        sql: include_str!("../database/migrations/012_add_performance_indexes.sql"),
+    },
+    Migration {
+        version: "013",
+        name: "013_add_email_queue_composite_index",
+        sql: include_str!("../database/migrations/013_add_email_queue_composite_index.sql"),
+    },
+    Migration {
+        version: "014",
+        name: "014_add_email_events_composite_index",
+        sql: include_str!("../database/migrations/014_add_email_events_composite_index.sql"),
+    },
+    Migration {
+        version: "015",
+        name: "015_add_newsletter_cleanup_index",
+        sql: include_str!("../database/migrations/015_add_newsletter_cleanup_index.sql"),
+    },
+    Migration {
+        version: "016",
+        name: "016_add_markets_deadline_index",
+        sql: include_str!("../database/migrations/016_add_markets_deadline_index.sql"),
+    },
     Migration {
         version: "017",
-        name: "017_create_email_dead_letter_jobs",
-        sql: include_str!("../database/migrations/017_create_email_dead_letter_jobs.sql"),
+        name: "017_add_analytics_events_market_id",
+        sql: include_str!("../database/migrations/017_add_analytics_events_market_id.sql"),
     },
     Migration {
         version: "017b",
@@ -111,8 +167,23 @@ const MIGRATIONS: &[Migration] = &[
     },
     Migration {
         version: "017c",
-        name: "017_add_analytics_events_market_id",
-        sql: include_str!("../database/migrations/017_add_analytics_events_market_id.sql"),
+        name: "017_create_api_keys",
+        sql: include_str!("../database/migrations/017_create_api_keys.sql"),
+    },
+    Migration {
+        version: "017d",
+        name: "017_create_email_dead_letter_jobs",
+        sql: include_str!("../database/migrations/017_create_email_dead_letter_jobs.sql"),
+    },
+    Migration {
+        version: "017e",
+        name: "017_create_unsubscribe_tokens",
+        sql: include_str!("../database/migrations/017_create_unsubscribe_tokens.sql"),
+    },
+    Migration {
+        version: "017f",
+        name: "017_create_watched_transactions",
+        sql: include_str!("../database/migrations/017_create_watched_transactions.sql"),
     },
     Migration {
         version: "018",
@@ -129,11 +200,201 @@ const MIGRATIONS: &[Migration] = &[
         name: "019_add_newsletter_confirmed_at_index",
         sql: include_str!("../database/migrations/019_add_newsletter_confirmed_at_index.sql"),
     },
+    Migration {
+        version: "019b",
+        name: "019_create_watched_transactions",
+        sql: include_str!("../database/migrations/019_create_watched_transactions.sql"),
+    },
     Migration {
         version: "020",
         name: "020_add_audit_log_actor_time_index",
         sql: include_str!("../database/migrations/020_add_audit_log_actor_time_index.sql"),
     },
+    Migration {
+        version: "021",
+        name: "021_add_markets_list_filters",
+        sql: include_str!("../database/migrations/021_add_markets_list_filters.sql"),
+    },
+    Migration {
+        version: "022",
+        name: "022_add_markets_detail_fields",
+        sql: include_str!("../database/migrations/022_add_markets_detail_fields.sql"),
+    },
+    Migration {
+        version: "023",
+        name: "023_create_chain_events",
+        sql: include_str!("../database/migrations/023_create_chain_events.sql"),
+    },
+    Migration {
+        version: "024",
+        name: "024_create_leaderboard_snapshots",
+        sql: include_str!("../database/migrations/024_create_leaderboard_snapshots.sql"),
+    },
+    Migration {
+        version: "025",
+        name: "025_create_market_price_points",
+        sql: include_str!("../database/migrations/025_create_market_price_points.sql"),
+    },
+    Migration {
+        version: "026",
+        name: "026_create_sync_state",
+        sql: include_str!("../database/migrations/026_create_sync_state.sql"),
+    },
+    Migration {
+        version: "027",
+        name: "027_create_oracle_submissions",
+        sql: include_str!("../database/migrations/027_create_oracle_submissions.sql"),
+    },
+    Migration {
+        version: "028",
+        name: "028_add_network_to_watched_transactions",
+        sql: include_str!("../database/migrations/028_add_network_to_watched_transactions.sql"),
+    },
+    Migration {
+        version: "029",
+        name: "029_create_market_watches",
+        sql: include_str!("../database/migrations/029_create_market_watches.sql"),
+    },
+    Migration {
+        version: "030",
+        name: "030_email_jobs_dead_letter_status",
+        sql: include_str!("../database/migrations/030_email_jobs_dead_letter_status.sql"),
+    },
+    Migration {
+        version: "031",
+        name: "031_contact_form_status_workflow",
+        sql: include_str!("../database/migrations/031_contact_form_status_workflow.sql"),
+    },
+    Migration {
+        version: "032",
+        name: "032_waitlist_referrals",
+        sql: include_str!("../database/migrations/032_waitlist_referrals.sql"),
+    },
+    Migration {
+        version: "033",
+        name: "033_create_analytics_daily",
+        sql: include_str!("../database/migrations/033_create_analytics_daily.sql"),
+    },
+    Migration {
+        version: "034",
+        name: "034_audit_log_request_fields",
+        sql: include_str!("../database/migrations/034_audit_log_request_fields.sql"),
+    },
+    Migration {
+        version: "035",
+        name: "035_create_content",
+        sql: include_str!("../database/migrations/035_create_content.sql"),
+    },
+    Migration {
+        version: "036",
+        name: "036_create_categories",
+        sql: include_str!("../database/migrations/036_create_categories.sql"),
+    },
+    Migration {
+        version: "037",
+        name: "037_create_platform_stats_daily",
+        sql: include_str!("../database/migrations/037_create_platform_stats_daily.sql"),
+    },
+    Migration {
+        version: "038",
+        name: "038_newsletter_confirmation_sent_at",
+        sql: include_str!("../database/migrations/038_newsletter_confirmation_sent_at.sql"),
+    },
+    Migration {
+        version: "039",
+        name: "039_create_newsletter_preferences",
+        sql: include_str!("../database/migrations/039_create_newsletter_preferences.sql"),
+    },
+    Migration {
+        version: "040",
+        name: "040_create_campaigns",
+        sql: include_str!("../database/migrations/040_create_campaigns.sql"),
+    },
+    Migration {
+        version: "041",
+        name: "041_content_slug_and_body",
+        sql: include_str!("../database/migrations/041_content_slug_and_body.sql"),
+    },
+    Migration {
+        version: "042",
+        name: "042_api_key_usage",
+        sql: include_str!("../database/migrations/042_api_key_usage.sql"),
+    },
+    Migration {
+        version: "043",
+        name: "043_create_watchlists",
+        sql: include_str!("../database/migrations/043_create_watchlists.sql"),
+    },
+    Migration {
+        version: "044",
+        name: "044_market_images",
+        sql: include_str!("../database/migrations/044_market_images.sql"),
+    },
+    Migration {
+        version: "045",
+        name: "045_digest_sends",
+        sql: include_str!("../database/migrations/045_digest_sends.sql"),
+    },
+    Migration {
+        version: "046",
+        name: "046_user_notification_settings",
+        sql: include_str!("../database/migrations/046_user_notification_settings.sql"),
+    },
+    Migration {
+        version: "047",
+        name: "047_protocol_state_changes",
+        sql: include_str!("../database/migrations/047_protocol_state_changes.sql"),
+    },
+    Migration {
+        version: "048",
+        name: "048_tx_watch_subscriptions",
+        sql: include_str!("../database/migrations/048_tx_watch_subscriptions.sql"),
+    },
+    Migration {
+        version: "049",
+        name: "049_platform_stats_snapshots",
+        sql: include_str!("../database/migrations/049_platform_stats_snapshots.sql"),
+    },
+    Migration {
+        version: "050",
+        name: "050_sent_reminders",
+        sql: include_str!("../database/migrations/050_sent_reminders.sql"),
+    },
+    Migration {
+        version: "051",
+        name: "051_backfill_jobs",
+        sql: include_str!("../database/migrations/051_backfill_jobs.sql"),
+    },
+    Migration {
+        version: "052",
+        name: "052_signup_source_attribution",
+        sql: include_str!("../database/migrations/052_signup_source_attribution.sql"),
+    },
+    Migration {
+        version: "053",
+        name: "053_blocked_email_domains",
+        sql: include_str!("../database/migrations/053_blocked_email_domains.sql"),
+    },
+    Migration {
+        version: "054",
+        name: "054_retention_indexes",
+        sql: include_str!("../database/migrations/054_retention_indexes.sql"),
+    },
+    Migration {
+        version: "055",
+        name: "055_create_risk_alerts",
+        sql: include_str!("../database/migrations/055_create_risk_alerts.sql"),
+    },
+    Migration {
+        version: "056",
+        name: "056_hide_creator_profile",
+        sql: include_str!("../database/migrations/056_hide_creator_profile.sql"),
+    },
+    Migration {
+        version: "057",
+        name: "057_create_market_id_mappings",
+        sql: include_str!("../database/migrations/057_create_market_id_mappings.sql"),
+    },
 ];
 
 // ---------------------------------------------------------------------------
@@ -204,7 +465,7 @@ impl<'a> MigrationRunner<'a> {
         self.ensure_tracking_table().await?;
 
         let mut pending = 0usize;
-        for migration in MIGRATIONS {
+        for migration in tracked_migrations() {
             if self.is_applied(migration.version).await? {
                 info!(version = migration.version, "migration already applied — skipping");
                 continue;
@@ -229,7 +490,7 @@ impl<'a> MigrationRunner<'a> {
 
         let mut applied = 0usize;
 
-        for migration in MIGRATIONS {
+        for migration in tracked_migrations() {
             if self.is_applied(migration.version).await? {
                 info!(version = migration.version, "migration already applied — skipping");
                 continue;
@@ -298,6 +559,22 @@ impl<'a> MigrationRunner<'a> {
         Ok(())
     }
 
+    /// Versions of embedded migrations not yet recorded in `schema_migrations`,
+    /// in the order they would be applied.
+    pub async fn pending(&self) -> anyhow::Result<Vec<&'static str>> {
+        self.ensure_tracking_table().await?;
+
+        let applied: Vec<String> = sqlx::query_scalar("SELECT version FROM schema_migrations")
+            .fetch_all(self.pool)
+            .await
+            .context("failed to query schema_migrations")?;
+
+        Ok(tracked_migrations()
+            .filter(|m| !applied.iter().any(|v| v == m.version))
+            .map(|m| m.version)
+            .collect())
+    }
+
     /// Return the list of applied migrations from the tracking table.
     pub async fn status(&self) -> anyhow::Result<Vec<AppliedMigration>> {
         self.ensure_tracking_table().await?;
@@ -357,31 +634,46 @@ impl<'a> MigrationRunner<'a> {
             "applying migration"
         );
 
-        let mut tx = self.pool.begin().await.context("begin transaction")?;
+        let record = sqlx::query(
+            "INSERT INTO schema_migrations (version, name, applied_at, checksum)
+             VALUES ($1, $2, NOW(), $3)
+             ON CONFLICT (version) DO NOTHING",
+        )
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(&checksum);
+
+        if migration.is_transactional() {
+            let mut tx = self.pool.begin().await.context("begin transaction")?;
+
+            // Execute the migration SQL
+            sqlx::raw_sql(migration.sql)
+                .execute(&mut *tx)
+                .await
+                .with_context(|| format!("SQL error in migration {}", migration.name))?;
+
+            record
+                .execute(&mut *tx)
+                .await
+                .context("failed to record migration in schema_migrations")?;
+
+            tx.commit().await.context("commit transaction")?;
+        } else {
+            // Each statement autocommits; they all use IF NOT EXISTS, so a
+            // run interrupted part-way through is safe to repeat.
+            for statement in split_statements(migration.sql) {
+                sqlx::raw_sql(&statement)
+                    .execute(self.pool)
+                    .await
+                    .with_context(|| format!("SQL error in migration {}", migration.name))?;
+            }
 
-        // Execute the migration SQL
-        sqlx::raw_sql(migration.sql)
-            .execute(&mut *tx)
-            .await
-            .with_context(|| format!("SQL error in migration {}", migration.name))?;
-
-        // Record in tracking table (skip version 000 — it IS the tracking table)
-        if migration.version != "000" {
-            sqlx::query(
-                "INSERT INTO schema_migrations (version, name, applied_at, checksum)
-                 VALUES ($1, $2, NOW(), $3)
-                 ON CONFLICT (version) DO NOTHING",
-            )
-            .bind(migration.version)
-            .bind(migration.name)
-            .bind(&checksum)
-            .execute(&mut *tx)
-            .await
-            .context("failed to record migration in schema_migrations")?;
+            record
+                .execute(self.pool)
+                .await
+                .context("failed to record migration in schema_migrations")?;
         }
 
-        tx.commit().await.context("commit transaction")?;
-
         info!(
             version = migration.version,
             checksum = &checksum[..12],
@@ -391,3 +683,63 @@ impl<'a> MigrationRunner<'a> {
         Ok(())
     }
 }
+
+/// Every embedded migration except `000`, which [`MigrationRunner`] applies
+/// before anything else and never records.
+fn tracked_migrations() -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(|m| m.version != "000")
+}
+
+/// Split a non-transactional migration into statements. Only used for
+/// index-only files, which contain no string literals or function bodies, so
+/// splitting on `;` after dropping `--` comments is sufficient.
+fn split_statements(sql: &str) -> Vec<String> {
+    let uncommented: String = sql
+        .lines()
+        .map(|line| line.split("--").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+    uncommented
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_migration_file_is_embedded_in_order() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/database/migrations");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .filter(|f| f.ends_with(".sql"))
+            .collect();
+        files.sort();
+        let embedded: Vec<String> = MIGRATIONS.iter().map(|m| format!("{}.sql", m.name)).collect();
+        assert_eq!(embedded, files);
+    }
+
+    #[test]
+    fn versions_are_unique_and_follow_the_filename_prefix() {
+        let mut seen = std::collections::HashSet::new();
+        for m in MIGRATIONS {
+            assert!(seen.insert(m.version), "duplicate version {}", m.version);
+            assert!(m.version.starts_with(m.name.split('_').next().unwrap()), "{}", m.name);
+        }
+        assert!(!tracked_migrations().any(|m| m.version == "000"));
+    }
+
+    #[test]
+    fn concurrent_index_migrations_split_into_statements() {
+        let m = MIGRATIONS.iter().find(|m| m.version == "018").unwrap();
+        assert!(!m.is_transactional());
+        let statements = split_statements(m.sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements.iter().all(|s| s.starts_with("CREATE INDEX CONCURRENTLY")));
+    }
+}
@@ -4,6 +4,7 @@ use crate::cache::RedisCache;
 use crate::metrics::Metrics;
 
 use anyhow::Context;
+use hmac::{Hmac, Mac};
 use rand::RngCore;
 use sha2::{Digest, Sha256};
 use serde_json::json;
@@ -11,6 +12,99 @@ use uuid::Uuid;
 
 use crate::config::Config;
 
+// ── Double opt-in confirmation ───────────────────────────────────────────────
+
+/// Minimum gap between confirmation emails for one address; enforced by
+/// `newsletter_resend_confirmation` in `db.rs`.
+pub const RESEND_COOLDOWN: Duration = Duration::from_secs(15 * 60);
+
+/// How long an unconfirmed subscription is kept after its last confirmation
+/// email before the hourly cleanup purges it. Deliberately longer than the
+/// token TTL so an expired link can still be answered with "expired" and a
+/// resend for a while.
+pub const PENDING_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// ── Subscription preferences ────────────────────────────────────────────────
+
+/// Kinds of mail a subscriber can turn off individually. Stored as
+/// [`EmailCategory::as_str`] in `newsletter_preferences.category`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmailCategory {
+    /// Product announcements and marketing campaigns.
+    Product,
+    /// Market resolution and dispute alerts.
+    Markets,
+    /// Periodic digest of market activity.
+    Digest,
+}
+
+impl EmailCategory {
+    pub const ALL: [Self; 3] = [Self::Product, Self::Markets, Self::Digest];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Product => "product",
+            Self::Markets => "markets",
+            Self::Digest => "digest",
+        }
+    }
+}
+
+/// Domain-separates preference tokens from every other HMAC made with the
+/// same secret.
+const PREFERENCES_TOKEN_CONTEXT: &str = "newsletter-preferences:v1:";
+
+fn preferences_mac(subscriber_id: Uuid, secret: &str) -> Option<Hmac<Sha256>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(PREFERENCES_TOKEN_CONTEXT.as_bytes());
+    mac.update(subscriber_id.as_bytes());
+    Some(mac)
+}
+
+/// Sign a preferences token for `subscriber_id`: `<uuid>.<hex hmac>`. The
+/// token names the subscriber row, not the email address, and does not
+/// expire — it is embedded in every newsletter footer. Returns `None` for an
+/// empty secret.
+pub fn sign_preferences_token(subscriber_id: Uuid, secret: &str) -> Option<String> {
+    if secret.is_empty() {
+        return None;
+    }
+    let mac = preferences_mac(subscriber_id, secret)?;
+    Some(format!(
+        "{subscriber_id}.{}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+/// Check a token from [`sign_preferences_token`] and return the subscriber
+/// id it was issued for. The signature is compared in constant time.
+pub fn verify_preferences_token(token: &str, secret: &str) -> Option<Uuid> {
+    if secret.is_empty() {
+        return None;
+    }
+    let (id, signature) = token.split_once('.')?;
+    let subscriber_id = Uuid::parse_str(id).ok()?;
+    let signature = hex::decode(signature).ok()?;
+    preferences_mac(subscriber_id, secret)?
+        .verify_slice(&signature)
+        .ok()?;
+    Some(subscriber_id)
+}
+
+/// Footer link to the preferences page for `subscriber_id`, or an empty
+/// string when no signing secret is configured.
+pub fn preferences_url(base_url: &str, subscriber_id: Uuid, secret: Option<&str>) -> String {
+    secret
+        .and_then(|secret| sign_preferences_token(subscriber_id, secret))
+        .map(|token| {
+            format!(
+                "{}/api/v1/newsletter/preferences?token={token}",
+                base_url.trim_end_matches('/')
+            )
+        })
+        .unwrap_or_default()
+}
+
 // ── Opaque unsubscribe tokens ────────────────────────────────────────────────
 //
 // Token format (issue #896)
@@ -106,7 +200,7 @@ impl IpRateLimiter {
                 tracing::warn!(
                     error = %e,
                     limiter = %self.name,
-                    key,
+                    key = %crate::log_redact::mask_emails_in(key),
                     "rate limiter Redis error — failing CLOSED (429) to prevent abuse during outage"
                 );
                 // Fail-closed: deny the request.
@@ -123,7 +217,7 @@ const TOKEN_BYTES: usize = 32;
 ///
 /// Returns `(raw_token, token_hash)` where:
 /// - `raw_token`  — 64-character lowercase hex string sent to the subscriber.
-///                  Store nowhere; embed once in the unsubscribe URL.
+///   Store nowhere; embed once in the unsubscribe URL.
 /// - `token_hash` — SHA-256 hex of `raw_token`. Persist this in the DB.
 pub fn generate_opaque_unsubscribe_token() -> (String, String) {
     let mut bytes = [0u8; TOKEN_BYTES];
@@ -343,6 +437,7 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[cfg(feature = "redis-integration")]
     async fn make_limiter() -> IpRateLimiter {
         use testcontainers::runners::AsyncRunner;
         use testcontainers_modules::redis::Redis;
@@ -356,6 +451,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn limiter_blocks_when_max_requests_reached() {
         let limiter = make_limiter().await;
         let key = "203.0.113.1";
@@ -367,6 +463,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[cfg(feature = "redis-integration")]
     async fn limiter_allows_after_window_expires() {
         let limiter = make_limiter().await;
         let key = "198.51.100.42";
@@ -530,4 +627,41 @@ mod tests {
         assert_eq!(s.confirm("tok-old"), ConfirmResult::InvalidOrExpired);
         assert_eq!(s.confirm("tok-new"), ConfirmResult::Confirmed);
     }
+
+    // ── Preference tokens ────────────────────────────────────────────────────
+
+    const SECRET: &str = "preferences-test-secret";
+
+    #[test]
+    fn preferences_token_round_trips() {
+        let id = Uuid::new_v4();
+        let token = sign_preferences_token(id, SECRET).unwrap();
+        assert!(token.starts_with(&id.to_string()));
+        assert_eq!(verify_preferences_token(&token, SECRET), Some(id));
+    }
+
+    #[test]
+    fn preferences_token_rejects_tampering_and_wrong_secret() {
+        let id = Uuid::new_v4();
+        let token = sign_preferences_token(id, SECRET).unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+
+        // Another subscriber's id under this signature.
+        let forged = format!("{}.{signature}", Uuid::new_v4());
+        assert_eq!(verify_preferences_token(&forged, SECRET), None);
+        assert_eq!(verify_preferences_token(&token, "other-secret"), None);
+        assert_eq!(verify_preferences_token(&token, ""), None);
+        for malformed in ["", ".", "not-a-uuid.abcd", &id.to_string(), &format!("{id}.zz")] {
+            assert_eq!(verify_preferences_token(malformed, SECRET), None, "{malformed}");
+        }
+    }
+
+    #[test]
+    fn preferences_url_is_empty_without_a_secret() {
+        let id = Uuid::new_v4();
+        assert_eq!(preferences_url("https://example.com", id, None), "");
+        assert_eq!(preferences_url("https://example.com", id, Some("")), "");
+        let url = preferences_url("https://example.com/", id, Some(SECRET));
+        assert!(url.starts_with("https://example.com/api/v1/newsletter/preferences?token="));
+    }
 }
@@ -0,0 +1,377 @@
+#[cfg(test)]
+mod newsletter_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use sqlx::Row;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::email::types::{EmailJobType, SuppressionType};
+    use crate::handlers::{
+        newsletter_confirm, newsletter_preferences_get, newsletter_preferences_update,
+        newsletter_resend_confirmation,
+    };
+    use crate::newsletter::{
+        sign_preferences_token, EmailCategory, PENDING_RETENTION, RESEND_COOLDOWN,
+    };
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Subscribers live in a reserved range so cleanup never touches rows
+    /// created by other tests or the seed data.
+    const EMAIL_PREFIX: &str = "newsletter-634";
+
+    fn email(n: usize) -> String {
+        format!("{EMAIL_PREFIX}-{n:02}@example.com")
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/newsletter/confirm", get(newsletter_confirm))
+            .route(
+                "/newsletter/resend-confirmation",
+                post(newsletter_resend_confirmation),
+            )
+            .route(
+                "/newsletter/preferences",
+                get(newsletter_preferences_get).put(newsletter_preferences_update),
+            )
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    async fn confirm(state: &Arc<crate::AppState>, token: &str) -> StatusCode {
+        let request = Request::builder()
+            .uri(format!("/newsletter/confirm?token={token}"))
+            .body(Body::empty())
+            .unwrap();
+        send(state, request).await.0
+    }
+
+    async fn resend(state: &Arc<crate::AppState>, address: &str) -> StatusCode {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/newsletter/resend-confirmation")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "email": address }).to_string()))
+            .unwrap();
+        send(state, request).await.0
+    }
+
+    async fn preferences(
+        state: &Arc<crate::AppState>,
+        token: &str,
+        update: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let builder = Request::builder().uri(format!("/newsletter/preferences?token={token}"));
+        let request = match update {
+            Some(body) => builder
+                .method("PUT")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        };
+        send(state, request.unwrap()).await
+    }
+
+    /// Insert a pending subscription whose confirmation email went out
+    /// `age_secs` ago and return its id.
+    async fn seed_pending(
+        state: &crate::AppState,
+        address: &str,
+        token: &str,
+        age_secs: i64,
+    ) -> uuid::Uuid {
+        let id = state
+            .db
+            .newsletter_upsert_pending(address, "test", token)
+            .await
+            .unwrap();
+        backdate(state, address, age_secs).await;
+        id
+    }
+
+    async fn backdate(state: &crate::AppState, address: &str, age_secs: i64) {
+        sqlx::query(
+            "UPDATE newsletter_subscribers
+             SET confirmation_sent_at = NOW() - ($2 || ' seconds')::INTERVAL,
+                 created_at = NOW() - ($2 || ' seconds')::INTERVAL
+             WHERE email = $1",
+        )
+        .bind(address)
+        .bind(age_secs)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// `(confirmed, confirmation_token)`, or `None` when the row is gone.
+    async fn row(state: &crate::AppState, address: &str) -> Option<(bool, Option<String>)> {
+        sqlx::query(
+            "SELECT confirmed, confirmation_token FROM newsletter_subscribers WHERE email = $1",
+        )
+        .bind(address)
+        .fetch_optional(&state.db.pool())
+        .await
+        .unwrap()
+        .map(|r| (r.get("confirmed"), r.get("confirmation_token")))
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        for table_and_column in [
+            "newsletter_subscribers WHERE email",
+            "newsletter_preferences WHERE email",
+            "email_jobs WHERE recipient_email",
+        ] {
+            sqlx::query(&format!("DELETE FROM {table_and_column} LIKE $1"))
+                .bind(format!("{EMAIL_PREFIX}-%"))
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A token just inside the window confirms; one just outside it is a 410
+    /// that leaves the row pending (so it can be resent), and an unknown
+    /// token is a 404.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_confirmation_expiry_boundary() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        let ttl = state.config.newsletter_token_ttl_secs as i64;
+
+        seed_pending(&state, &email(1), "newsletter-634-fresh", ttl - 60).await;
+        seed_pending(&state, &email(2), "newsletter-634-stale", ttl + 60).await;
+
+        assert_eq!(
+            confirm(&state, "newsletter-634-fresh").await,
+            StatusCode::OK
+        );
+        assert_eq!(row(&state, &email(1)).await, Some((true, None)));
+
+        assert_eq!(
+            confirm(&state, "newsletter-634-stale").await,
+            StatusCode::GONE
+        );
+        assert_eq!(
+            confirm(&state, "newsletter-634-stale").await,
+            StatusCode::GONE
+        );
+        assert_eq!(
+            row(&state, &email(2)).await,
+            Some((false, Some("newsletter-634-stale".to_string())))
+        );
+
+        assert_eq!(
+            confirm(&state, "newsletter-634-unknown").await,
+            StatusCode::NOT_FOUND
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Resending within the cooldown keeps the current token; once the
+    /// cooldown has passed a fresh token replaces it and confirms. Unknown
+    /// addresses get the same 202.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_resend_is_rate_limited_per_email() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        let ttl = state.config.newsletter_token_ttl_secs as i64;
+        let cooldown = RESEND_COOLDOWN.as_secs() as i64;
+
+        seed_pending(&state, &email(3), "newsletter-634-first", cooldown - 60).await;
+        assert_eq!(resend(&state, &email(3)).await, StatusCode::ACCEPTED);
+        assert_eq!(
+            row(&state, &email(3)).await,
+            Some((false, Some("newsletter-634-first".to_string())))
+        );
+
+        // Past the cooldown and past the TTL: the old link is dead, a resend
+        // issues a new one that works.
+        backdate(&state, &email(3), ttl + 60).await;
+        assert_eq!(resend(&state, &email(3)).await, StatusCode::ACCEPTED);
+        let (_, token) = row(&state, &email(3)).await.unwrap();
+        let token = token.expect("pending token");
+        assert_ne!(token, "newsletter-634-first");
+        assert_eq!(
+            confirm(&state, "newsletter-634-first").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(confirm(&state, &token).await, StatusCode::OK);
+
+        // Nothing to resend for a confirmed or unknown address.
+        assert_eq!(resend(&state, &email(3)).await, StatusCode::ACCEPTED);
+        assert_eq!(row(&state, &email(3)).await, Some((true, None)));
+        assert_eq!(resend(&state, &email(4)).await, StatusCode::ACCEPTED);
+        assert_eq!(row(&state, &email(4)).await, None);
+
+        cleanup(&state).await;
+    }
+
+    /// The cleanup job purges pending rows past the retention period only;
+    /// recently expired pending rows and unsubscribed subscribers stay.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_cleanup_purges_only_old_pending_rows() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        let retention = PENDING_RETENTION.as_secs() as i64;
+
+        seed_pending(&state, &email(5), "newsletter-634-old", retention + 3600).await;
+        seed_pending(&state, &email(6), "newsletter-634-expired", 3 * 24 * 3600).await;
+        seed_pending(&state, &email(7), "newsletter-634-unsub", 60).await;
+        assert_eq!(
+            confirm(&state, "newsletter-634-unsub").await,
+            StatusCode::OK
+        );
+        state.db.newsletter_unsubscribe(&email(7)).await.unwrap();
+        backdate(&state, &email(7), retention + 3600).await;
+
+        state
+            .db
+            .newsletter_delete_expired_pending(PENDING_RETENTION.as_secs(), 1000)
+            .await
+            .unwrap();
+
+        assert_eq!(row(&state, &email(5)).await, None);
+        assert!(row(&state, &email(6)).await.is_some());
+        assert_eq!(row(&state, &email(7)).await, Some((false, None)));
+
+        cleanup(&state).await;
+    }
+
+    /// Confirming turns every category on; the signed token reads and edits
+    /// them, a token for another subscriber id is a 404, and unsubscribe-all
+    /// turns every category off.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_preferences_follow_token_and_unsubscribe_all() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        let secret = state.config.unsubscribe_signing_secret.clone().unwrap();
+
+        let id = seed_pending(&state, &email(8), "newsletter-634-prefs", 60).await;
+        assert_eq!(
+            confirm(&state, "newsletter-634-prefs").await,
+            StatusCode::OK
+        );
+        let token = sign_preferences_token(id, &secret).unwrap();
+
+        let all_on = json!({ "product": true, "markets": true, "digest": true });
+        assert_eq!(
+            preferences(&state, &token, None).await,
+            (StatusCode::OK, all_on)
+        );
+
+        let (status, body) = preferences(&state, &token, Some(json!({ "product": false }))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!({ "product": false, "markets": true, "digest": true })
+        );
+
+        let forged = sign_preferences_token(uuid::Uuid::new_v4(), &secret).unwrap();
+        let (status, body) = preferences(&state, &forged, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "INVALID_PREFERENCES_TOKEN");
+        let (status, _) = preferences(&state, &format!("{token}0"), None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        assert!(state.db.newsletter_unsubscribe(&email(8)).await.unwrap());
+        let all_off = json!({ "product": false, "markets": false, "digest": false });
+        assert_eq!(
+            preferences(&state, &token, None).await,
+            (StatusCode::OK, all_off)
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Category mail is skipped for an address that turned the category off
+    /// or is suppressed, and still enqueued for every other category.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_enqueue_skips_disabled_category() {
+        let state = build_state().await;
+        cleanup(&state).await;
+        let address = email(9);
+        state
+            .db
+            .newsletter_set_preferences(&address, &[(EmailCategory::Digest, false)])
+            .await
+            .unwrap();
+
+        let enqueue = |category| {
+            state.email_queue.enqueue_for_category(
+                category,
+                EmailJobType::Custom("newsletter_test".to_string()),
+                &address,
+                "newsletter_test",
+                json!({}),
+                0,
+            )
+        };
+        assert_eq!(enqueue(EmailCategory::Digest).await.unwrap(), None);
+        assert!(enqueue(EmailCategory::Markets).await.unwrap().is_some());
+
+        // No preference row at all still means "send"; suppression wins.
+        let other = email(10);
+        assert!(state
+            .db
+            .email_allowed_for_category(&other, EmailCategory::Product)
+            .await
+            .unwrap());
+        state
+            .db
+            .email_add_suppression(&other, SuppressionType::Manual.as_str(), None, None)
+            .await
+            .unwrap();
+        assert!(!state
+            .db
+            .email_allowed_for_category(&other, EmailCategory::Product)
+            .await
+            .unwrap());
+
+        state.db.email_remove_suppression(&other).await.unwrap();
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state() -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config
+            .unsubscribe_signing_secret
+            .get_or_insert_with(|| "newsletter-test-signing-secret".to_string());
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -1,12 +1,62 @@
-use utoipa::OpenApi;
+use std::sync::OnceLock;
+
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 use crate::handlers::{
-    ApiError, AuditLogsQuery, AuditStatisticsQuery, EmailAnalyticsQuery, EmailTestRequest,
-    FeaturedMarketView, InvalidationResult, NewsletterEmailRequest, NewsletterExportResponse,
-    NewsletterResponse, NewsletterSubscribeRequest, ResolveMarketRequest,
-    NewsletterConfirmQuery, NewsletterUnsubscribeQuery, NewsletterExportQuery,
+    ApiError, BlockchainBatchItem, BlockchainBatchRequest, BlockchainBatchResponse,
+    BlockchainQuery, EmailTestRequest,
+    FeaturedMarketView, InvalidationResult, MarketChainView, MarketDetailSources, MarketDetailView,
+    MarketListView, PartSource, NewsletterEmailRequest, NewsletterExportResponse,
+    NewsletterResponse, NewsletterSubscribeRequest, ResolveMarketRequest, ResolveMarketResult, OracleResultRequest, OracleResultResponse, TxEnvelopeRequest,
+    NewsletterPreferences, NewsletterPreferencesUpdate,
+    MarketWatchRequest, MarketWatchResponse, TxWatchRequest, TxWatchResponse, ContactRequest,
+    ContactStatusRequest,
+    WaitlistJoinRequest, WaitlistStatusResponse, WaitlistInviteRequest, WaitlistInviteResponse,
+    AnalyticsEventInput, AnalyticsEventsRequest, AnalyticsIngestResponse,
+    CategoryCreateRequest, CategoryUpdateRequest, CampaignCreateRequest, GdprSubjectRequest,
+    ContentWriteRequest, AuthVerifyRequest, AuthRefreshRequest, DigestPreview, BackfillRequest,
+    BlockedDomainCreateRequest,
+};
+use crate::acquisition::{AcquisitionGroupBy, AcquisitionReport, AcquisitionRow};
+use crate::analytics::{AnalyticsDailyCount, AnalyticsSummary, AnalyticsTypeTotal};
+use crate::api_key_usage::{ApiKeyUsage, DailyUsage};
+use crate::risk::{
+    LargestPosition, MarketExposure, OutcomeExposure, RiskAlert, RiskAlertKind, RiskAlertList,
+    RiskThresholds,
+};
+use crate::backfill::{BackfillJob, BackfillStatus};
+use crate::cache::admin::{CacheDeleteResult, CacheEntry, CacheKeyList};
+use crate::campaign::{Campaign, CampaignStatus};
+use crate::category::Category;
+use crate::gdpr::{GdprDeleteReport, GdprExport};
+use crate::contact::{ContactStatus, ContactSubmission};
+use crate::content::{ContentEntry, RenderedContent};
+use crate::blockchain::{TxSimulation, TxSimulationResult, TxSubmission};
+use crate::db::{MarketDetail, MarketSort};
+use crate::signup_guard::BlockedDomain;
+use crate::market_watch::{MarketWatch, WatchTrigger};
+use crate::oracle_keeper::{OracleSubmission, OracleSubmissionStatus};
+use crate::market_ids::{MarketIdBindRequest, MarketIdMapping};
+use crate::market_image::MarketImage;
+use crate::activity::{Activity, ActivityDetails, ActivityFeed, ActivityType};
+use crate::leaderboard::{Leaderboard, LeaderboardEntry, LeaderboardMetric, LeaderboardPeriod};
+use crate::stats_history::{StatsHistory, StatsMetric, StatsPoint};
+use crate::contract_read::ContractRead;
+use crate::creators::{
+    ActiveMarket, CreatorProfile, CreatorReputation, CreatorStats, TopCreator, TopCreators, TopCreatorsMetric,
+};
+use crate::protocol_state::{
+    BreakerState, GuardianRemoval, MarketBettingState, PendingUpgrade, ProtocolState,
+    ProtocolStateChange, ProtocolStateView,
 };
-use crate::pagination::PaginationQuery;
+use crate::price_history::{HistoryResolution, OutcomeSeries, PriceCandle, PriceHistory};
+use crate::portfolio::{MarketPosition, Portfolio, PositionStatus, TokenTotals};
+use crate::waitlist::{WaitlistInvitee, WaitlistStats, WaitlistStatus};
+use crate::tx_watch::{TxFinalized, TxSubscription};
+use crate::user_notifications::{NotificationSettings, NotificationSettingsUpdate};
+use crate::wallet_auth::{Challenge, SessionToken};
+use crate::watchlist::{Watchlist, WatchlistEntry};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -26,54 +76,346 @@ use crate::pagination::PaginationQuery;
         crate::handlers::health,
         crate::handlers::newsletter_subscribe,
         crate::handlers::newsletter_confirm,
+        crate::handlers::newsletter_resend_confirmation,
         crate::handlers::newsletter_unsubscribe,
+        crate::handlers::newsletter_preferences_get,
+        crate::handlers::newsletter_preferences_update,
         crate::handlers::newsletter_gdpr_export,
         crate::handlers::newsletter_gdpr_delete,
+        crate::handlers::gdpr_export,
+        crate::handlers::gdpr_delete,
+        crate::handlers::contact_submit,
+        crate::handlers::contact_list,
+        crate::handlers::contact_set_status,
+        crate::handlers::waitlist_join,
+        crate::handlers::waitlist_status,
+        crate::handlers::waitlist_invite,
+        crate::handlers::waitlist_stats,
+        crate::handlers::waitlist_export_csv,
+        crate::handlers::analytics_ingest,
+        crate::handlers::analytics_summary,
+        crate::handlers::analytics_acquisition,
+        crate::handlers::analytics_acquisition_csv,
+        crate::handlers::newsletter_export_csv,
+        crate::handlers::chain_events_export_csv,
+        crate::handlers::backfill_create,
+        crate::handlers::backfill_get,
         crate::handlers::statistics,
+        crate::handlers::statistics_history,
+        crate::handlers::list_markets,
         crate::handlers::featured_markets,
+        crate::handlers::categories,
+        crate::handlers::category_create,
+        crate::handlers::category_update,
+        crate::handlers::category_delete,
+        crate::handlers::market_id_bind,
+        crate::handlers::market_id_unbind,
+        crate::handlers::market_detail,
+        crate::handlers::user_portfolio,
+        crate::handlers::watchlist_get,
+        crate::handlers::watchlist_put,
+        crate::handlers::watchlist_delete,
+        crate::handlers::notification_settings_get,
+        crate::handlers::notification_settings_put,
+        crate::handlers::notification_email_confirm,
+        crate::handlers::notification_unsubscribe,
+        crate::handlers::auth_challenge,
+        crate::handlers::auth_verify,
+        crate::handlers::auth_refresh,
+        crate::handlers::leaderboard,
+        crate::handlers::top_creators,
+        crate::handlers::creator_profile,
+        crate::handlers::activity_feed,
+        crate::handlers::market_history,
+        crate::handlers::market_image,
+        crate::handlers::market_image_upload,
+        crate::handlers::market_watch_create,
+        crate::handlers::market_watch_delete,
+        crate::handlers::market_watch_unsubscribe,
         crate::handlers::content,
+        crate::handlers::content_page,
+        crate::handlers::content_create,
+        crate::handlers::content_update,
+        crate::handlers::content_publish,
+        crate::handlers::content_delete,
         crate::handlers::resolve_market,
         crate::handlers::blockchain_health,
+        crate::handlers::blockchain_protocol_state,
+        crate::handlers::blockchain_contract_call,
         crate::handlers::blockchain_market_data,
         crate::handlers::blockchain_platform_stats,
         crate::handlers::blockchain_user_bets,
         crate::handlers::blockchain_oracle_result,
         crate::handlers::blockchain_tx_status,
+        crate::handlers::blockchain_tx_watch,
+        crate::handlers::blockchain_batch,
         crate::handlers::blockchain_replay,
+        crate::handlers::tx_simulate,
+        crate::handlers::tx_submit,
+        crate::handlers::oracle_submit_result,
         crate::handlers::email_preview,
         crate::handlers::email_send_test,
         crate::handlers::email_analytics,
         crate::handlers::email_queue_stats,
         crate::handlers::email_dead_letter_list,
-        crate::handlers::email_dead_letter_requeue,
+        crate::handlers::email_dead_letter_retry,
+        crate::handlers::blocked_domains_list,
+        crate::handlers::blocked_domain_create,
+        crate::handlers::blocked_domain_delete,
+        crate::handlers::campaign_create,
+        crate::handlers::campaign_get,
+        crate::handlers::campaign_launch,
+        crate::handlers::campaign_cancel,
+        crate::handlers::digest_preview,
         crate::handlers::sendgrid_webhook,
         crate::handlers::audit_logs,
+        crate::handlers::admin_audit_list,
         crate::handlers::audit_statistics,
+        crate::handlers::cache_keys,
+        crate::handlers::cache_entry,
+        crate::handlers::cache_invalidate,
+        crate::handlers::api_key_usage,
+        crate::handlers::risk_market_exposure,
+        crate::handlers::risk_alerts,
     ),
     components(
         schemas(
             ApiError,
+            BlockchainQuery,
+            BlockchainBatchRequest,
+            BlockchainBatchItem,
+            BlockchainBatchResponse,
             FeaturedMarketView,
+            MarketListView,
+            MarketSort,
+            Category,
+            CategoryCreateRequest,
+            CategoryUpdateRequest,
+            ContentEntry,
+            ContentWriteRequest,
+            RenderedContent,
+            MarketDetail,
+            MarketIdBindRequest,
+            MarketIdMapping,
+            MarketImage,
+            Watchlist,
+            WatchlistEntry,
+            NotificationSettings,
+            NotificationSettingsUpdate,
+            Challenge,
+            SessionToken,
+            AuthVerifyRequest,
+            AuthRefreshRequest,
+            MarketDetailView,
+            MarketDetailSources,
+            MarketChainView,
+            PartSource,
+            Portfolio,
+            MarketPosition,
+            PositionStatus,
+            TokenTotals,
+            Leaderboard,
+            ActivityFeed,
+            Activity,
+            ActivityDetails,
+            ActivityType,
+            LeaderboardEntry,
+            LeaderboardPeriod,
+            LeaderboardMetric,
+            TopCreators,
+            TopCreator,
+            TopCreatorsMetric,
+            CreatorProfile,
+            CreatorReputation,
+            CreatorStats,
+            ActiveMarket,
+            StatsHistory,
+            StatsPoint,
+            StatsMetric,
+            PriceHistory,
+            OutcomeSeries,
+            PriceCandle,
+            HistoryResolution,
+            MarketWatchRequest,
+            MarketWatchResponse,
+            MarketWatch,
+            WatchTrigger,
+            TxEnvelopeRequest,
+            TxWatchRequest,
+            TxWatchResponse,
+            TxSubscription,
+            TxFinalized,
+            ProtocolStateView,
+            ProtocolState,
+            ContractRead,
+            BreakerState,
+            MarketBettingState,
+            GuardianRemoval,
+            PendingUpgrade,
+            ProtocolStateChange,
+            TxSimulation,
+            TxSimulationResult,
+            TxSubmission,
             InvalidationResult,
             NewsletterSubscribeRequest,
             NewsletterEmailRequest,
             NewsletterResponse,
+            NewsletterPreferences,
+            NewsletterPreferencesUpdate,
             NewsletterExportResponse,
+            GdprSubjectRequest,
+            GdprExport,
+            GdprDeleteReport,
+            CampaignCreateRequest,
+            Campaign,
+            CampaignStatus,
+            DigestPreview,
+            BlockedDomain,
+            BlockedDomainCreateRequest,
+            ContactRequest,
+            ContactStatusRequest,
+            ContactSubmission,
+            ContactStatus,
+            WaitlistJoinRequest,
+            WaitlistStatusResponse,
+            WaitlistStatus,
+            WaitlistInviteRequest,
+            WaitlistInviteResponse,
+            WaitlistInvitee,
+            WaitlistStats,
+            AnalyticsEventInput,
+            AnalyticsEventsRequest,
+            AnalyticsIngestResponse,
+            AnalyticsSummary,
+            AnalyticsTypeTotal,
+            AnalyticsDailyCount,
+            AcquisitionReport,
+            AcquisitionRow,
+            AcquisitionGroupBy,
             ResolveMarketRequest,
+            ResolveMarketResult,
+            OracleResultRequest,
+            OracleResultResponse,
+            OracleSubmission,
+            OracleSubmissionStatus,
+            BackfillRequest,
+            BackfillJob,
+            BackfillStatus,
             EmailTestRequest,
+            CacheKeyList,
+            CacheEntry,
+            CacheDeleteResult,
+            ApiKeyUsage,
+            DailyUsage,
+            MarketExposure,
+            OutcomeExposure,
+            LargestPosition,
+            RiskAlert,
+            RiskAlertKind,
+            RiskAlertList,
+            RiskThresholds,
         )
     ),
     tags(
         (name = "health", description = "Health check"),
         (name = "newsletter", description = "Newsletter subscription management"),
+        (name = "gdpr", description = "Data-subject export and erasure across all tables (admin)"),
+        (name = "contact", description = "Contact form submissions"),
+        (name = "waitlist", description = "Launch waitlist and referrals"),
+        (name = "analytics", description = "Product analytics ingestion and rollups"),
         (name = "markets", description = "Market data and resolution"),
+        (name = "auth", description = "Signed wallet challenges and session tokens"),
+        (name = "notifications", description = "Per-wallet emails about the wallet's own positions"),
+        (name = "content", description = "Editorial content and its admin workflow"),
         (name = "blockchain", description = "Stellar blockchain integration"),
         (name = "email", description = "Email service management (admin)"),
         (name = "webhooks", description = "Incoming provider webhooks"),
         (name = "audit", description = "Audit log access (admin)"),
+        (name = "cache", description = "Cache inspection and invalidation (admin)"),
+        (name = "api-keys", description = "API key usage and quotas (admin)"),
+        (name = "risk", description = "Bet concentration and inflow monitoring (admin)"),
     ),
-    security(
-        ("api_key" = [])
-    )
+    modifiers(&SecuritySchemes),
 )]
 pub struct ApiDoc;
+
+/// Declares the schemes handlers name in their `security(...)`: the admin
+/// API key and the wallet session token. Routes that name neither are
+/// public.
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                "X-API-Key",
+                "Admin or partner API key",
+            ))),
+        );
+        components.add_security_scheme(
+            "wallet_session",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .description(Some("Session token from `POST /api/v1/auth/verify`"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// The generated document as JSON, built on first use and served as is by
+/// `GET /api/v1/openapi.json`.
+pub fn openapi_json() -> &'static str {
+    static JSON: OnceLock<String> = OnceLock::new();
+    JSON.get_or_init(|| {
+        ApiDoc::openapi()
+            .to_json()
+            .expect("OpenAPI document serializes")
+    })
+}
+
+/// Swagger UI release loaded by the docs page.
+const SWAGGER_UI_DIST: &str = "https://unpkg.com/swagger-ui-dist@5.17.14";
+
+/// The Swagger UI page served at `/api/v1/docs` when `SWAGGER_UI_ENABLED`
+/// is set. Its assets come from [`SWAGGER_UI_DIST`]; the bootstrap script is
+/// served alongside so the page needs no inline script.
+pub fn swagger_ui_html() -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>PredictIQ API</title>
+  <link rel="stylesheet" href="{SWAGGER_UI_DIST}/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{SWAGGER_UI_DIST}/swagger-ui-bundle.js"></script>
+  <script src="/api/v1/docs/swagger-initializer.js"></script>
+</body>
+</html>
+"#
+    )
+}
+
+/// Points Swagger UI at the generated document.
+pub const SWAGGER_UI_INITIALIZER: &str = r##"window.onload = () => {
+  window.ui = SwaggerUIBundle({
+    url: "/api/v1/openapi.json",
+    dom_id: "#swagger-ui",
+  });
+};
+"##;
+
+/// Replaces the API's deny-all CSP on the docs page: scripts from this
+/// origin and [`SWAGGER_UI_DIST`], inline styles (Swagger UI sets them) and
+/// requests only back to this origin.
+pub const SWAGGER_UI_CSP: &str = "default-src 'none'; \
+    script-src 'self' https://unpkg.com; style-src 'unsafe-inline' https://unpkg.com; \
+    img-src 'self' data: https://unpkg.com; connect-src 'self'; \
+    frame-ancestors 'none'; base-uri 'none'; form-action 'none'; object-src 'none';";
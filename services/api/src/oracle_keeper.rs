@@ -0,0 +1,124 @@
+//! Oracle results pushed on-chain by the off-chain keeper.
+//!
+//! `POST /api/v1/admin/oracle/:market_id/result` records every submission in
+//! `oracle_submissions` before calling the contract's `set_oracle_result`, so
+//! the audit row exists even if the process dies mid-call. The live row for a
+//! (market, oracle) pair is the idempotency key: resubmitting the same outcome
+//! is a no-op and a different outcome is a conflict. See
+//! [`crate::db::Database::oracle_submission_claim`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleSubmissionStatus {
+    /// Recorded; the contract call is in flight.
+    Pending,
+    /// `set_oracle_result` succeeded on-chain.
+    Confirmed,
+    /// The contract call failed; a new submission may be made.
+    Failed,
+}
+
+impl OracleSubmissionStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Confirmed => "confirmed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "pending" => Some(Self::Pending),
+            "confirmed" => Some(Self::Confirmed),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OracleSubmission {
+    pub id: i64,
+    pub market_id: i64,
+    pub oracle_id: i32,
+    pub outcome: i32,
+    /// Free-form provenance supplied by the keeper (feed name, URL, ...).
+    pub source: String,
+    pub confidence_bps: i32,
+    pub status: OracleSubmissionStatus,
+    pub tx_hash: Option<String>,
+    pub error: Option<String>,
+    /// `attempt_oracle_resolution` transaction, when it was triggered.
+    pub resolution_tx_hash: Option<String>,
+    pub resolution_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What claiming the live slot for a (market, oracle) pair found.
+#[derive(Debug, Clone)]
+pub enum SubmissionClaim {
+    /// A new pending row was recorded; the caller now owns the contract call.
+    New(OracleSubmission),
+    /// A live row already carries this outcome.
+    Duplicate(OracleSubmission),
+    /// A live row carries a different outcome.
+    Conflict(OracleSubmission),
+}
+
+impl SubmissionClaim {
+    /// Classify an existing live row against the outcome being submitted.
+    pub fn against(existing: OracleSubmission, outcome: i32) -> Self {
+        if existing.outcome == outcome {
+            Self::Duplicate(existing)
+        } else {
+            Self::Conflict(existing)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(outcome: i32) -> OracleSubmission {
+        OracleSubmission {
+            id: 1,
+            market_id: 7,
+            oracle_id: 0,
+            outcome,
+            source: "feed".to_string(),
+            confidence_bps: 9_500,
+            status: OracleSubmissionStatus::Confirmed,
+            tx_hash: Some("ab".to_string()),
+            error: None,
+            resolution_tx_hash: None,
+            resolution_error: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn same_outcome_is_duplicate_and_different_outcome_conflicts() {
+        assert!(matches!(SubmissionClaim::against(live(1), 1), SubmissionClaim::Duplicate(_)));
+        assert!(matches!(SubmissionClaim::against(live(1), 0), SubmissionClaim::Conflict(_)));
+    }
+
+    #[test]
+    fn status_labels_round_trip() {
+        for status in [
+            OracleSubmissionStatus::Pending,
+            OracleSubmissionStatus::Confirmed,
+            OracleSubmissionStatus::Failed,
+        ] {
+            assert_eq!(OracleSubmissionStatus::parse(status.label()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.label());
+        }
+        assert_eq!(OracleSubmissionStatus::parse("done"), None);
+    }
+}
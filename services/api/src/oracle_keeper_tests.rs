@@ -0,0 +1,312 @@
+#[cfg(test)]
+mod oracle_keeper_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use secrecy::SecretString;
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    use crate::handlers::{oracle_submit_result, OracleResultRequest, OracleResultResponse};
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 4] = [9401, 9402, 9403, 9404];
+    const ORACLE_SEED: &str = "SAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSBF5K";
+    const CONTRACT_ID: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/admin/oracle/:market_id/result", post(oracle_submit_result))
+            .with_state(state)
+    }
+
+    async fn post_result(
+        state: &Arc<crate::AppState>,
+        market_id: i64,
+        outcome: u32,
+    ) -> (StatusCode, Value) {
+        let body = json!({
+            "outcome": outcome,
+            "source": "sportsfeed:match-42",
+            "confidence_bps": 9_800
+        });
+        let response = app(Arc::clone(state))
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/admin/oracle/{market_id}/result"))
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    /// Serve `responses` in order as JSON-RPC replies.
+    async fn start_mock_rpc(responses: Vec<Value>) -> String {
+        let queue = Arc::new(Mutex::new(responses));
+        let app = Router::new().route(
+            "/",
+            post(move |Json(_body): Json<Value>| {
+                let queue = queue.clone();
+                async move {
+                    let mut q = queue.lock().await;
+                    let resp = if q.is_empty() {
+                        json!({ "result": { "status": "NOT_FOUND" } })
+                    } else {
+                        q.remove(0)
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    /// RPC replies for one contract call that ends in `final_status`.
+    fn contract_call(hash: &str, final_status: &str) -> Vec<Value> {
+        use stellar_xdr::curr::{self as xdr, WriteXdr};
+        let public_key = crate::signer::TxSigner::from_secret(&SecretString::new(ORACLE_SEED.to_string()))
+            .unwrap()
+            .public_key();
+        let account = xdr::LedgerEntryData::Account(xdr::AccountEntry {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(public_key))),
+            balance: 100_000_000,
+            seq_num: xdr::SequenceNumber(41),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: xdr::String32::default(),
+            thresholds: xdr::Thresholds([1, 0, 0, 0]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        });
+        let data = xdr::SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: xdr::VecM::default(),
+                },
+                instructions: 1_000,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        };
+        vec![
+            json!({ "result": {
+                "entries": [{ "xdr": account.to_xdr_base64(xdr::Limits::none()).unwrap() }],
+                "latestLedger": 900
+            } }),
+            json!({ "result": {
+                "latestLedger": 900,
+                "minResourceFee": "5000",
+                "transactionData": data.to_xdr_base64(xdr::Limits::none()).unwrap(),
+                "results": [{ "xdr": "AAAAAQ==", "auth": [] }]
+            } }),
+            json!({ "result": { "hash": hash, "status": "PENDING", "latestLedger": 900 } }),
+            json!({ "result": { "status": final_status, "ledger": 902 } }),
+        ]
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64, ends_in: &str) {
+        sqlx::query(&format!(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at) \
+             VALUES ($1, 'Oracle test', 'active', 0, NOW() + INTERVAL '{ends_in}', NOW())"
+        ))
+        .bind(market_id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// (status, outcome, tx_hash, resolution_tx_hash) per audit row, oldest first.
+    async fn audit_rows(
+        state: &crate::AppState,
+        market_id: i64,
+    ) -> Vec<(String, i32, Option<String>, Option<String>)> {
+        sqlx::query_as(
+            "SELECT status, outcome, tx_hash, resolution_tx_hash FROM oracle_submissions \
+             WHERE market_id = $1 ORDER BY id",
+        )
+        .bind(market_id)
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM oracle_submissions WHERE market_id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis; the RPC node is mocked
+    // ---------------------------------------------------------------------------
+
+    /// An open market gets the result on-chain but is not resolved yet.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_oracle_result_recorded_before_deadline() {
+        let state = build_state(start_mock_rpc(contract_call("aa01", "SUCCESS")).await).await;
+        cleanup(&state).await;
+        seed_market(&state, 9401, "1 day").await;
+
+        let (status, body) = post_result(&state, 9401, 1).await;
+        assert_eq!(status, StatusCode::OK);
+        let body: OracleResultResponse = serde_json::from_value(body).unwrap();
+        assert!(body.submitted);
+        assert!(!body.resolution_triggered);
+        assert_eq!(
+            audit_rows(&state, 9401).await,
+            vec![("confirmed".to_string(), 1, Some("aa01".to_string()), None)]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Same outcome again is a no-op; a different outcome is a 409. Neither
+    /// reaches the RPC node (the mock has nothing queued) or adds a row.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_oracle_result_idempotent_and_conflicting() {
+        let state = build_state(start_mock_rpc(contract_call("aa02", "SUCCESS")).await).await;
+        cleanup(&state).await;
+        seed_market(&state, 9402, "1 day").await;
+
+        assert_eq!(post_result(&state, 9402, 0).await.0, StatusCode::OK);
+
+        let (status, body) = post_result(&state, 9402, 0).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["submitted"], false);
+        assert_eq!(body["submission"]["tx_hash"], "aa02");
+
+        let (status, body) = post_result(&state, 9402, 1).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["code"], "CONFLICT");
+
+        assert_eq!(audit_rows(&state, 9402).await.len(), 1);
+
+        cleanup(&state).await;
+    }
+
+    /// A closed market is moved to resolution right after the result lands.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_oracle_result_triggers_resolution_after_deadline() {
+        let mut rpc = contract_call("aa03", "SUCCESS");
+        rpc.extend(contract_call("bb03", "SUCCESS"));
+        let state = build_state(start_mock_rpc(rpc).await).await;
+        cleanup(&state).await;
+        seed_market(&state, 9403, "-1 hour").await;
+
+        let (status, body) = post_result(&state, 9403, 2).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["resolution_triggered"], true);
+        assert_eq!(
+            audit_rows(&state, 9403).await,
+            vec![(
+                "confirmed".to_string(),
+                2,
+                Some("aa03".to_string()),
+                Some("bb03".to_string())
+            )]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// A failed write is audited as failed and does not block a retry.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_oracle_result_failure_is_audited_and_retryable() {
+        let state = build_state(start_mock_rpc(contract_call("aa04", "FAILED")).await).await;
+        cleanup(&state).await;
+        seed_market(&state, 9404, "1 day").await;
+
+        let (status, body) = post_result(&state, 9404, 1).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "SUBMISSION_REJECTED");
+
+        let retry = build_state(start_mock_rpc(contract_call("cc04", "SUCCESS")).await).await;
+        let (status, _) = post_result(&retry, 9404, 1).await;
+        assert_eq!(status, StatusCode::OK);
+
+        assert_eq!(
+            audit_rows(&state, 9404).await,
+            vec![
+                ("failed".to_string(), 1, Some("aa04".to_string()), None),
+                ("confirmed".to_string(), 1, Some("cc04".to_string()), None),
+            ]
+        );
+
+        cleanup(&state).await;
+    }
+
+    // ---------------------------------------------------------------------------
+    // Pure-logic unit tests (no I/O)
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_oracle_result_request_deserialises() {
+        let req: OracleResultRequest = serde_json::from_value(json!({
+            "outcome": 1,
+            "source": "feed",
+            "confidence_bps": 10_000
+        }))
+        .unwrap();
+        assert_eq!((req.outcome, req.confidence_bps), (1, 10_000));
+        let negative = json!({ "outcome": -1, "source": "x", "confidence_bps": 0 });
+        assert!(serde_json::from_value::<OracleResultRequest>(negative).is_err());
+    }
+
+    // ---------------------------------------------------------------------------
+    // Helper — builds AppState from env (used by #[ignore] integration tests)
+    // ---------------------------------------------------------------------------
+
+    async fn build_state(rpc_url: String) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
+
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.contract_id = CONTRACT_ID.to_string();
+        config.network_passphrase = "Test SDF Network ; September 2015".to_string();
+        config.admin_secret_key = None;
+        config.oracle_secret_key = Some(SecretString::new(ORACLE_SEED.to_string()));
+        config.oracle_id = 0;
+        config.contract_call_timeout = Duration::from_secs(1);
+        config.tx_poll_interval = Duration::from_millis(50);
+        config.retry_attempts = 1;
+        build_test_state_with(config, &[]).await
+    }
+}
@@ -12,9 +12,12 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /// Hard cap on the number of rows a client may request in a single page.
 pub const MAX_PAGE_LIMIT: u32 = 100;
+/// [`MAX_PAGE_LIMIT`] as the type of [`PaginationQuery::limit`].
+const MAX_LIMIT: i64 = MAX_PAGE_LIMIT as i64;
 /// Default rows returned when the client omits `limit`.
 pub const DEFAULT_LIMIT: u32 = 20;
 
@@ -118,15 +121,20 @@ impl<T: Serialize> PageResponse<T> {
 pub struct ValidatedPaginationQuery(pub ValidatedPagination);
 
 /// Lightweight raw pagination query used by handlers that do their own
-/// bounds-checking or in-memory slicing.  Axum extracts this directly from
-/// the query string; call `.limit()` / `.cursor()` for the clamped values.
-#[derive(Debug, Clone, Deserialize, Default, utoipa::IntoParams)]
+/// cursor decoding or in-memory slicing. Extract it with
+/// [`crate::validation::ValidatedQuery`]: a `limit` outside
+/// `1..=MAX_PAGE_LIMIT` is a `422 INVALID_FIELDS`. Call `.limit()` /
+/// `.cursor()` for the values with defaults applied.
+#[derive(Debug, Clone, Deserialize, Default, Validate, utoipa::IntoParams)]
 pub struct PaginationQuery {
+    #[validate(range(min = 1, max = MAX_LIMIT))]
     pub limit: Option<i64>,
     pub cursor: Option<String>,
 }
 
 impl PaginationQuery {
+    /// `limit`, or [`DEFAULT_LIMIT`]. Still clamped for queries built in
+    /// code rather than extracted.
     pub fn limit(&self) -> i64 {
         self.limit.unwrap_or(DEFAULT_LIMIT as i64).max(1).min(MAX_PAGE_LIMIT as i64)
     }
@@ -136,20 +144,9 @@ impl PaginationQuery {
     }
 }
 
-/// A single page of results returned by paginated endpoints.
-#[derive(Debug, Serialize)]
-pub struct PaginatedResponse<T: Serialize> {
-    pub items: Vec<T>,
-    pub next_cursor: Option<String>,
-    pub limit: u32,
-    pub has_more: bool,
-}
-
-impl<T: Serialize> PaginatedResponse<T> {
-    pub fn new(items: Vec<T>, next_cursor: Option<String>, limit: u32, has_more: bool) -> Self {
-        Self { items, next_cursor, limit, has_more }
-    }
-}
+/// A single page of results returned by paginated endpoints; shared with
+/// `predictiq-api-client`.
+pub use predictiq_api_types::PaginatedResponse;
 
 
 #[axum::async_trait]
@@ -0,0 +1,372 @@
+//! Platform statistics derived from indexed data.
+//!
+//! The contract's own counters (read by
+//! [`BlockchainClient::platform_statistics_cached`]) are the primary source,
+//! but they can be missing or unreadable and cannot be audited. An hourly
+//! task (spawned in `main.rs`) runs [`refresh`], which derives the same
+//! numbers from `markets` and the primary network's `chain_events` and
+//! stores them in `platform_stats_snapshots` with the ledger the indexer had
+//! confirmed. A failed chain read falls back to the newest snapshot, marked
+//! [`DataSource::StaleFallback`].
+//!
+//! After each refresh, a live chain read is compared with the snapshot; any
+//! counter or the total volume differing by more than
+//! `STATS_DIVERGENCE_THRESHOLD` (relative) is logged, and alerted through
+//! `ALERT_WEBHOOK_URL` when it is set. As with network alerts, a divergence
+//! alerts when it starts and not again until it has cleared.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    alerting::AlertWebhook,
+    blockchain::{BlockchainClient, DataSource, PlatformStatistics},
+    db::Database,
+    AppState,
+};
+
+/// Time between two snapshots.
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Snapshots older than this are deleted after each refresh.
+pub const SNAPSHOT_RETENTION_DAYS: i64 = 90;
+
+const WORKER_NAME: &str = "platform_stats_snapshot";
+
+/// Bet volume of one stake token, in stroops.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenVolume {
+    /// `None` for markets without a recorded token.
+    pub token: Option<String>,
+    pub volume: String,
+}
+
+/// Counts and volumes computed from the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivedStats {
+    pub total_markets: u64,
+    pub active_markets: u64,
+    pub resolved_markets: u64,
+    /// Every indexed bet, in stroops, summed across tokens like the
+    /// contract's counter.
+    pub total_volume: String,
+    /// Volume per token of bets on markets in the `markets` table.
+    pub token_volumes: Vec<TokenVolume>,
+}
+
+/// A stored [`DerivedStats`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsSnapshot {
+    pub network: String,
+    /// Last ledger the indexer had confirmed when the snapshot was taken.
+    pub ledger: u32,
+    pub stats: DerivedStats,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StatsSnapshot {
+    /// The snapshot as a stale stand-in for the contract's statistics.
+    pub fn into_statistics(self) -> PlatformStatistics {
+        PlatformStatistics {
+            total_markets: self.stats.total_markets,
+            active_markets: self.stats.active_markets,
+            resolved_markets: self.stats.resolved_markets,
+            total_volume: self.stats.total_volume,
+            ledger: self.ledger,
+            source: DataSource::StaleFallback,
+        }
+    }
+}
+
+/// One statistic on which the chain and the database disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub field: &'static str,
+    pub chain: String,
+    pub derived: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: chain {}, derived {}",
+            self.field, self.chain, self.derived
+        )
+    }
+}
+
+/// `|a - b|` relative to the larger of the two; zero when both are zero.
+fn relative_difference(a: f64, b: f64) -> f64 {
+    let larger = a.abs().max(b.abs());
+    if larger == 0.0 {
+        0.0
+    } else {
+        (a - b).abs() / larger
+    }
+}
+
+/// The statistics of `chain` and `derived` whose relative difference is
+/// above `threshold`. A chain volume that is not a number compares as zero.
+pub fn divergences(
+    chain: &PlatformStatistics,
+    derived: &DerivedStats,
+    threshold: f64,
+) -> Vec<Divergence> {
+    let volume = |v: &str| v.parse::<f64>().unwrap_or(0.0);
+    let count = |field: &'static str, a: u64, b: u64| {
+        (field, a.to_string(), b.to_string(), a as f64, b as f64)
+    };
+    [
+        count("total_markets", chain.total_markets, derived.total_markets),
+        count(
+            "active_markets",
+            chain.active_markets,
+            derived.active_markets,
+        ),
+        count(
+            "resolved_markets",
+            chain.resolved_markets,
+            derived.resolved_markets,
+        ),
+        (
+            "total_volume",
+            chain.total_volume.clone(),
+            derived.total_volume.clone(),
+            volume(&chain.total_volume),
+            volume(&derived.total_volume),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, _, _, a, b)| relative_difference(*a, *b) > threshold)
+    .map(|(field, chain, derived, _, _)| Divergence {
+        field,
+        chain,
+        derived,
+    })
+    .collect()
+}
+
+/// Compares chain statistics with snapshots and alerts when they start to
+/// diverge.
+pub struct DivergenceCheck {
+    threshold: f64,
+    webhook: Option<AlertWebhook>,
+    /// The previous check found a divergence.
+    diverged: bool,
+}
+
+impl DivergenceCheck {
+    pub fn new(threshold: f64, webhook: Option<AlertWebhook>) -> Self {
+        Self {
+            threshold,
+            webhook,
+            diverged: false,
+        }
+    }
+
+    /// Compare `chain` with `snapshot`, log every divergence, and send one
+    /// alert when they start to diverge. Returns the divergences found.
+    pub async fn check(
+        &mut self,
+        chain: &PlatformStatistics,
+        snapshot: &StatsSnapshot,
+    ) -> Vec<Divergence> {
+        let found = divergences(chain, &snapshot.stats, self.threshold);
+        for d in &found {
+            tracing::warn!(
+                network = %snapshot.network,
+                field = d.field,
+                chain = %d.chain,
+                derived = %d.derived,
+                chain_ledger = chain.ledger,
+                snapshot_ledger = snapshot.ledger,
+                "[platform-stats] chain and derived statistics diverge"
+            );
+        }
+
+        let started = !found.is_empty() && !self.diverged;
+        if found.is_empty() && self.diverged {
+            tracing::info!(network = %snapshot.network, "[platform-stats] statistics agree again");
+        }
+        self.diverged = !found.is_empty();
+
+        if let (true, Some(webhook)) = (started, &self.webhook) {
+            let details: Vec<String> = found.iter().map(ToString::to_string).collect();
+            let text = format!(
+                ":warning: [{}] Platform statistics diverge by more than {:.0}% \
+                 (chain ledger {}, snapshot ledger {}): {}",
+                snapshot.network,
+                self.threshold * 100.0,
+                chain.ledger,
+                snapshot.ledger,
+                details.join("; ")
+            );
+            if let Err(e) = webhook.send(&text).await {
+                tracing::warn!("[platform-stats] divergence alert failed: {e:#}");
+                // Retried by the next check.
+                self.diverged = false;
+            }
+        }
+        found
+    }
+}
+
+/// Derive the statistics of `network`, store them as a snapshot, and drop
+/// snapshots past [`SNAPSHOT_RETENTION_DAYS`].
+pub async fn refresh(db: &Database, network: &str) -> anyhow::Result<StatsSnapshot> {
+    let ledger = db.sync_state_get(network).await?.unwrap_or(0);
+    let stats = db.platform_stats_derive(network).await?;
+    let snapshot = db
+        .platform_stats_snapshot_insert(network, ledger, &stats)
+        .await?;
+    let cutoff = snapshot.created_at - chrono::Duration::days(SNAPSHOT_RETENTION_DAYS);
+    db.platform_stats_snapshots_delete_before(network, cutoff)
+        .await?;
+    Ok(snapshot)
+}
+
+/// Compare a live chain read with `snapshot`. Skipped when the chain read
+/// fails or is itself a snapshot.
+async fn compare(check: &mut DivergenceCheck, chain: &BlockchainClient, snapshot: &StatsSnapshot) {
+    match chain.platform_statistics_cached().await {
+        Ok(stats) if stats.source == DataSource::Live => {
+            check.check(&stats, snapshot).await;
+        }
+        Ok(_) => tracing::debug!("[platform-stats] chain read fell back; comparison skipped"),
+        Err(e) => tracing::debug!("[platform-stats] chain statistics unavailable: {e}"),
+    }
+}
+
+/// Take a snapshot at startup and every [`SNAPSHOT_INTERVAL`] until
+/// `shutdown` fires, comparing each with the chain's statistics. Only the
+/// primary network has a `markets` table to derive from.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    let url = state.config.alert_webhook_url.as_deref();
+    let webhook = match url.map(AlertWebhook::new) {
+        Some(Ok(webhook)) => Some(webhook),
+        Some(Err(e)) => {
+            tracing::warn!("[platform-stats] alert webhook unavailable: {e:#}");
+            None
+        }
+        None => None,
+    };
+    let mut check = DivergenceCheck::new(state.config.stats_divergence_threshold, webhook);
+    let network = state.config.network_name();
+
+    loop {
+        match refresh(&state.db, network).await {
+            Ok(snapshot) => {
+                tracing::info!(
+                    "[platform-stats] snapshot at ledger {}: {} markets",
+                    snapshot.ledger,
+                    snapshot.stats.total_markets
+                );
+                compare(&mut check, state.networks.primary(), &snapshot).await;
+            }
+            Err(e) => tracing::warn!("[platform-stats] snapshot error: {e}"),
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(SNAPSHOT_INTERVAL) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(total: u64, active: u64, resolved: u64, volume: &str) -> PlatformStatistics {
+        PlatformStatistics {
+            total_markets: total,
+            active_markets: active,
+            resolved_markets: resolved,
+            total_volume: volume.to_string(),
+            ledger: 900,
+            source: DataSource::Live,
+        }
+    }
+
+    fn derived(total: u64, active: u64, resolved: u64, volume: &str) -> DerivedStats {
+        DerivedStats {
+            total_markets: total,
+            active_markets: active,
+            resolved_markets: resolved,
+            total_volume: volume.to_string(),
+            token_volumes: vec![TokenVolume {
+                token: Some("CUSDC".to_string()),
+                volume: volume.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn differences_within_the_threshold_are_ignored() {
+        let found = divergences(
+            &chain(100, 60, 40, "10000"),
+            &derived(96, 58, 38, "9600"),
+            0.05,
+        );
+        assert!(found.is_empty(), "{found:?}");
+        assert!(divergences(&chain(0, 0, 0, "0"), &derived(0, 0, 0, "0"), 0.0).is_empty());
+    }
+
+    #[test]
+    fn differences_above_the_threshold_are_reported() {
+        let found = divergences(
+            &chain(100, 60, 40, "10000"),
+            &derived(100, 50, 50, "20000"),
+            0.05,
+        );
+        let fields: Vec<_> = found.iter().map(|d| d.field).collect();
+        assert_eq!(
+            fields,
+            ["active_markets", "resolved_markets", "total_volume"]
+        );
+        assert_eq!(found[2].chain, "10000");
+        assert_eq!(found[2].derived, "20000");
+        assert_eq!(found[0].to_string(), "active_markets: chain 60, derived 50");
+    }
+
+    #[test]
+    fn zero_on_one_side_is_a_full_divergence() {
+        let found = divergences(&chain(0, 0, 0, "0"), &derived(3, 1, 2, "150"), 0.99);
+        assert_eq!(found.len(), 4);
+
+        let found = divergences(
+            &chain(3, 1, 2, "not a number"),
+            &derived(3, 1, 2, "150"),
+            0.5,
+        );
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "total_volume");
+    }
+
+    #[test]
+    fn snapshot_is_served_as_a_stale_fallback() {
+        let snapshot = StatsSnapshot {
+            network: "testnet".to_string(),
+            ledger: 4321,
+            stats: derived(3, 1, 2, "150"),
+            created_at: Utc::now(),
+        };
+        let stats = snapshot.into_statistics();
+        assert_eq!(stats.source, DataSource::StaleFallback);
+        assert_eq!(stats.ledger, 4321);
+        assert_eq!(
+            (
+                stats.total_markets,
+                stats.active_markets,
+                stats.resolved_markets
+            ),
+            (3, 1, 2)
+        );
+        assert_eq!(stats.total_volume, "150");
+    }
+}
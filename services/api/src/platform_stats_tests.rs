@@ -0,0 +1,290 @@
+#[cfg(test)]
+mod platform_stats_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+    use tokio::sync::Mutex;
+
+    use crate::{
+        alerting::AlertWebhook,
+        blockchain::{BlockchainClient, DataSource, PlatformStatistics},
+        cache::{keys, RedisCache},
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+        platform_stats::{self, DerivedStats, DivergenceCheck, StatsSnapshot, TokenVolume},
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Events are written on a network no real monitor uses, and markets and
+    /// the token use reserved ids, so cleanup only touches rows created here.
+    /// The markets table has no network, so derived counts are compared as
+    /// deltas around the seed.
+    const NETWORK: &str = "platform-stats-test";
+    const SEED_MARKET_IDS: [i64; 4] = [9401, 9402, 9403, 9404];
+    const SEED_EVENT_PREFIX: &str = "platform-stats-test-";
+    const SEED_TOKEN: &str = "CPLATFORMSTATSTEST";
+
+    /// A ledger no real sync reaches, marking snapshots written here.
+    const SEED_LEDGER: u32 = u32::MAX - 7;
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    /// - 9401: a stored active market with two bets in [`SEED_TOKEN`].
+    /// - 9402: a stored active market with a resolve event.
+    /// - 9403: known only from its create event, with one bet.
+    /// - 9404: known only from its create event, then cancelled.
+    async fn seed(db: &Database) {
+        cleanup(db).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, token, ends_at) \
+             VALUES \
+                (9401, 'Stats A', 'active', $1, NOW() + INTERVAL '1 day'), \
+                (9402, 'Stats B', 'active', $1, NOW() + INTERVAL '1 day')",
+        )
+        .bind(SEED_TOKEN)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id, address, outcome, amount) \
+             VALUES \
+                ($1 || '1', $2, 1, 'bet_place', 9401, 'GPLATFORMSTATSA', 0, 250), \
+                ($1 || '2', $2, 2, 'bet_place', 9401, 'GPLATFORMSTATSB', 1, 50), \
+                ($1 || '3', $2, 3, 'resolv_fx', 9402, NULL, 0, NULL), \
+                ($1 || '4', $2, 4, 'mkt_creat', 9403, 'GPLATFORMSTATSA', NULL, NULL), \
+                ($1 || '5', $2, 5, 'bet_place', 9403, 'GPLATFORMSTATSB', 0, 100), \
+                ($1 || '6', $2, 6, 'mkt_creat', 9404, 'GPLATFORMSTATSA', NULL, NULL), \
+                ($1 || '7', $2, 7, 'mkt_cncl', 9404, NULL, NULL, NULL)",
+        )
+        .bind(SEED_EVENT_PREFIX)
+        .bind(NETWORK)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(db: &Database) {
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(SEED_EVENT_PREFIX)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKET_IDS[..])
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM platform_stats_snapshots WHERE network = $1 OR ledger = $2")
+            .bind(NETWORK)
+            .bind(i64::from(SEED_LEDGER))
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    fn volume(stats: &DerivedStats) -> u128 {
+        stats.total_volume.parse().unwrap()
+    }
+
+    /// A JSON-RPC endpoint answering every call with `result`, or with an
+    /// error when `result` is `None`.
+    async fn start_mock_rpc(result: Option<Value>) -> String {
+        let app = Router::new().route(
+            "/",
+            post(move |Json(_): Json<Value>| {
+                let result = result.clone();
+                async move {
+                    Json(match result {
+                        Some(result) => json!({ "result": result }),
+                        None => {
+                            json!({ "error": { "code": -32600, "message": "node unavailable" } })
+                        }
+                    })
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    async fn build_client(rpc_url: String) -> (BlockchainClient, RedisCache, Database) {
+        let mut config = Config::from_env();
+        config.blockchain_rpc_url = rpc_url;
+        config.contract_call_timeout = Duration::from_secs(1);
+        config.retry_attempts = 1;
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(
+            config.db_credentials.to_connection_string().expose_secret(),
+            cache.clone(),
+            metrics.clone(),
+            &config.db_pool,
+        )
+        .await
+        .expect("db");
+        let client =
+            BlockchainClient::new(&config, cache.clone(), db.clone(), metrics).expect("blockchain");
+        (client, cache, db)
+    }
+
+    /// A local webhook recording each alert text.
+    async fn start_webhook() -> (String, Arc<Mutex<Vec<String>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let sink = sink.clone();
+                async move {
+                    let text = body["text"].as_str().unwrap_or_default().to_string();
+                    sink.lock().await.push(text);
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, received)
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Stored markets and create events both count; resolve and cancel
+    /// events end a market; every bet counts toward the total volume, and
+    /// bets on stored markets toward their token.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_derivation_counts_seeded_events() {
+        let db = build_db().await;
+        cleanup(&db).await;
+        let before = db.platform_stats_derive(NETWORK).await.unwrap();
+
+        seed(&db).await;
+        let after = db.platform_stats_derive(NETWORK).await.unwrap();
+
+        assert_eq!(after.total_markets - before.total_markets, 4);
+        assert_eq!(after.active_markets - before.active_markets, 2);
+        assert_eq!(after.resolved_markets - before.resolved_markets, 1);
+        assert_eq!(volume(&after) - volume(&before), 400);
+        assert!(after.token_volumes.contains(&TokenVolume {
+            token: Some(SEED_TOKEN.to_string()),
+            volume: "300".to_string(),
+        }));
+
+        // A snapshot stores the same numbers and reads back as the newest.
+        let snapshot = platform_stats::refresh(&db, NETWORK).await.unwrap();
+        assert_eq!(snapshot.stats, after);
+        let latest = db
+            .platform_stats_snapshot_latest(NETWORK)
+            .await
+            .unwrap()
+            .expect("snapshot stored");
+        assert_eq!(latest.stats, after);
+        assert_eq!(latest.ledger, snapshot.ledger);
+
+        cleanup(&db).await;
+    }
+
+    /// A failed chain read, or one without counters, serves the newest
+    /// snapshot as a stale fallback and caches nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_failed_chain_read_serves_latest_snapshot() {
+        for response in [None, Some(json!({}))] {
+            let (client, cache, db) = build_client(start_mock_rpc(response).await).await;
+            let key = keys::chain_platform_stats(client.network());
+            cache.del(&key).await.unwrap();
+            cleanup(&db).await;
+
+            let stats = DerivedStats {
+                total_markets: 12,
+                active_markets: 7,
+                resolved_markets: 4,
+                total_volume: "98765".to_string(),
+                token_volumes: vec![],
+            };
+            db.platform_stats_snapshot_insert(client.network(), SEED_LEDGER, &stats)
+                .await
+                .unwrap();
+
+            let served = client.platform_statistics_cached().await.unwrap();
+            assert_eq!(served.source, DataSource::StaleFallback);
+            assert_eq!(served.ledger, SEED_LEDGER);
+            assert_eq!(
+                (
+                    served.total_markets,
+                    served.active_markets,
+                    served.resolved_markets
+                ),
+                (12, 7, 4)
+            );
+            assert_eq!(served.total_volume, "98765");
+            assert!(cache.get_json::<Value>(&key).await.unwrap().is_none());
+
+            cleanup(&db).await;
+        }
+    }
+
+    /// A chain read that disagrees with a derived snapshot alerts once, and
+    /// again only after the two have agreed in between.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_divergence_logs_and_alerts_once() {
+        let db = build_db().await;
+        seed(&db).await;
+        let snapshot: StatsSnapshot = platform_stats::refresh(&db, NETWORK).await.unwrap();
+        let (url, received) = start_webhook().await;
+        let mut check = DivergenceCheck::new(0.05, Some(AlertWebhook::new(url).unwrap()));
+
+        let agreeing = PlatformStatistics {
+            total_markets: snapshot.stats.total_markets,
+            active_markets: snapshot.stats.active_markets,
+            resolved_markets: snapshot.stats.resolved_markets,
+            total_volume: snapshot.stats.total_volume.clone(),
+            ledger: 1000,
+            source: DataSource::Live,
+        };
+        let diverging = PlatformStatistics {
+            total_markets: snapshot.stats.total_markets * 2 + 10,
+            ..agreeing.clone()
+        };
+
+        let found = check.check(&diverging, &snapshot).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].field, "total_markets");
+        assert_eq!(check.check(&diverging, &snapshot).await.len(), 1);
+        {
+            let texts = received.lock().await;
+            assert_eq!(texts.len(), 1, "a held divergence alerts once");
+            assert!(texts[0].contains(&format!("[{NETWORK}]")), "{}", texts[0]);
+            assert!(texts[0].contains("total_markets"), "{}", texts[0]);
+        }
+
+        assert!(check.check(&agreeing, &snapshot).await.is_empty());
+        check.check(&diverging, &snapshot).await;
+        assert_eq!(received.lock().await.len(), 2);
+
+        cleanup(&db).await;
+    }
+}
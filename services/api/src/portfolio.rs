@@ -0,0 +1,215 @@
+//! Per-address position and P&L computation over indexed chain events.
+//!
+//! Amounts are integer token units (stroops) carried as decimal strings end to
+//! end: Postgres sums them as `NUMERIC`, this module does the arithmetic in
+//! `i128` (the contract's amount type) and serialises the results back to
+//! strings. Nothing here goes through `f64`.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+
+/// The response types, shared with `predictiq-api-client`.
+pub use predictiq_api_types::{MarketPosition, Portfolio, PositionStatus, TokenTotals};
+
+/// One (market, token) aggregate for an address, as returned by
+/// [`crate::db::Database::user_portfolio`]'s query.
+#[derive(Debug, Clone)]
+pub struct PositionRow {
+    pub market_id: i64,
+    pub token: Option<String>,
+    /// `markets.status`; `None` when the market has no off-chain row yet.
+    pub market_status: Option<String>,
+    pub resolved_outcome: Option<i32>,
+    pub staked: String,
+    pub staked_on_winner: String,
+    pub claimed: String,
+    pub refunded: String,
+}
+
+fn parse_amount(field: &str, raw: &str) -> anyhow::Result<i128> {
+    raw.parse::<i128>()
+        .with_context(|| format!("portfolio: {field} is not an integer amount: {raw:?}"))
+}
+
+/// Classify one (market, token) aggregate and compute its P&L.
+pub fn position_from_row(row: PositionRow) -> anyhow::Result<MarketPosition> {
+    let staked = parse_amount("staked", &row.staked)?;
+    let staked_on_winner = parse_amount("staked_on_winner", &row.staked_on_winner)?;
+    let claimed = parse_amount("claimed", &row.claimed)?;
+    let refunded = parse_amount("refunded", &row.refunded)?;
+    let returned = claimed.saturating_add(refunded);
+
+    let status = match (row.market_status.as_deref(), row.resolved_outcome) {
+        (Some("cancelled"), _) => PositionStatus::Refunded,
+        (Some("resolved"), Some(_)) if staked_on_winner > 0 && claimed > 0 => PositionStatus::Won,
+        (Some("resolved"), Some(_)) if staked_on_winner > 0 => PositionStatus::Unclaimed,
+        (Some("resolved"), Some(_)) => PositionStatus::Lost,
+        _ => PositionStatus::Open,
+    };
+
+    let (realized, exposure) = match status {
+        PositionStatus::Open | PositionStatus::Unclaimed => {
+            (0, staked.saturating_sub(refunded).max(0))
+        }
+        PositionStatus::Won | PositionStatus::Lost | PositionStatus::Refunded => {
+            (returned.saturating_sub(staked), 0)
+        }
+    };
+
+    Ok(MarketPosition {
+        market_id: row.market_id,
+        token: row.token,
+        status,
+        market_status: row.market_status,
+        resolved_outcome: row.resolved_outcome,
+        staked: staked.to_string(),
+        claimed: claimed.to_string(),
+        refunded: refunded.to_string(),
+        realized_pnl: realized.to_string(),
+        open_exposure: exposure.to_string(),
+    })
+}
+
+/// Build `address`'s portfolio: its positions plus per-token totals.
+pub fn portfolio_from_rows(address: &str, rows: Vec<PositionRow>) -> anyhow::Result<Portfolio> {
+    let positions = rows
+        .into_iter()
+        .map(position_from_row)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    // (staked, realized, exposure) per token; BTreeMap keeps output order stable.
+    let mut sums: BTreeMap<Option<String>, (i128, i128, i128)> = BTreeMap::new();
+    for p in &positions {
+        let entry = sums.entry(p.token.clone()).or_default();
+        entry.0 = entry.0.saturating_add(parse_amount("staked", &p.staked)?);
+        entry.1 = entry.1.saturating_add(parse_amount("realized_pnl", &p.realized_pnl)?);
+        entry.2 = entry.2.saturating_add(parse_amount("open_exposure", &p.open_exposure)?);
+    }
+
+    let totals = sums
+        .into_iter()
+        .map(|(token, (staked, realized, exposure))| TokenTotals {
+            token,
+            staked: staked.to_string(),
+            realized_pnl: realized.to_string(),
+            open_exposure: exposure.to_string(),
+        })
+        .collect();
+
+    Ok(Portfolio {
+        address: address.to_string(),
+        positions,
+        totals,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(market_id: i64, status: Option<&str>, outcome: Option<i32>) -> PositionRow {
+        PositionRow {
+            market_id,
+            token: Some("CUSDC".to_string()),
+            market_status: status.map(str::to_string),
+            resolved_outcome: outcome,
+            staked: "0".to_string(),
+            staked_on_winner: "0".to_string(),
+            claimed: "0".to_string(),
+            refunded: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn win_realizes_payout_minus_stake() {
+        let mut r = row(1, Some("resolved"), Some(0));
+        r.staked = "1000".to_string();
+        r.staked_on_winner = "1000".to_string();
+        r.claimed = "1800".to_string();
+
+        let p = position_from_row(r).unwrap();
+        assert_eq!(p.status, PositionStatus::Won);
+        assert_eq!(p.realized_pnl, "800");
+        assert_eq!(p.open_exposure, "0");
+    }
+
+    #[test]
+    fn loss_realizes_full_stake() {
+        let mut r = row(2, Some("resolved"), Some(1));
+        r.staked = "500".to_string();
+
+        let p = position_from_row(r).unwrap();
+        assert_eq!(p.status, PositionStatus::Lost);
+        assert_eq!(p.realized_pnl, "-500");
+        assert_eq!(p.open_exposure, "0");
+    }
+
+    #[test]
+    fn open_position_is_exposure_not_pnl() {
+        let mut r = row(3, Some("active"), None);
+        r.staked = "250".to_string();
+
+        let p = position_from_row(r).unwrap();
+        assert_eq!(p.status, PositionStatus::Open);
+        assert_eq!(p.realized_pnl, "0");
+        assert_eq!(p.open_exposure, "250");
+    }
+
+    #[test]
+    fn refunded_market_nets_to_zero_once_refund_is_claimed() {
+        let mut r = row(4, Some("cancelled"), None);
+        r.staked = "300".to_string();
+        r.refunded = "300".to_string();
+
+        let p = position_from_row(r).unwrap();
+        assert_eq!(p.status, PositionStatus::Refunded);
+        assert_eq!(p.realized_pnl, "0");
+        assert_eq!(p.open_exposure, "0");
+    }
+
+    #[test]
+    fn winner_without_claim_stays_exposed() {
+        let mut r = row(5, Some("resolved"), Some(0));
+        r.staked = "100".to_string();
+        r.staked_on_winner = "100".to_string();
+
+        let p = position_from_row(r).unwrap();
+        assert_eq!(p.status, PositionStatus::Unclaimed);
+        assert_eq!(p.realized_pnl, "0");
+        assert_eq!(p.open_exposure, "100");
+    }
+
+    #[test]
+    fn totals_are_grouped_by_token_and_exceed_f64_precision() {
+        let mut a = row(1, Some("resolved"), Some(0));
+        a.staked = "9007199254740993".to_string();
+        a.staked_on_winner = a.staked.clone();
+        a.claimed = "9007199254740995".to_string();
+        let mut b = row(2, Some("active"), None);
+        b.token = Some("CXLM".to_string());
+        b.staked = "7".to_string();
+
+        let portfolio = portfolio_from_rows("GADDR", vec![a, b]).unwrap();
+        assert_eq!(portfolio.totals.len(), 2);
+        let usdc = portfolio
+            .totals
+            .iter()
+            .find(|t| t.token.as_deref() == Some("CUSDC"))
+            .unwrap();
+        assert_eq!(usdc.realized_pnl, "2");
+        let xlm = portfolio
+            .totals
+            .iter()
+            .find(|t| t.token.as_deref() == Some("CXLM"))
+            .unwrap();
+        assert_eq!(xlm.open_exposure, "7");
+    }
+
+    #[test]
+    fn rejects_non_integer_amounts() {
+        let mut r = row(1, Some("active"), None);
+        r.staked = "1.5".to_string();
+        assert!(position_from_row(r).is_err());
+    }
+}
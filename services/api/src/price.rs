@@ -0,0 +1,416 @@
+//! USD conversion for token-denominated volumes.
+//!
+//! Volumes are integer token units in whatever token a market stakes, so
+//! `"30000000000"` is 3,000 XLM (7 decimals) but 30,000 USDC (6 decimals).
+//! The price task ([`run`], spawned in `main.rs`) fetches the USD price of
+//! every asset in `PRICE_TOKENS` from `PRICE_SOURCE_URL` each
+//! `price_refresh_interval` and stores it under [`keys::price_usd`]. Request
+//! paths read a [`PriceQuotes`] snapshot through [`PriceService::quotes`] and
+//! convert with it. An unset source, an unpriced token, or a price older than
+//! `price_max_age` gives `None`, serialized as `null`: a missing price is
+//! never shown as $0.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{keys, RedisCache},
+    config::{Config, PricedToken},
+    AppState,
+};
+
+const WORKER_NAME: &str = "price_refresh";
+
+/// Prices are held as integer units of 10^-8 USD so conversion is exact
+/// integer arithmetic once the quote is read.
+const PRICE_SCALE_DIGITS: u32 = 8;
+
+/// One stored USD price.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Quote {
+    pub usd: f64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// HTTP client for a CoinGecko-compatible `simple/price` endpoint:
+/// `GET {url}?ids=a,b&vs_currencies=usd` answering `{"a": {"usd": 0.12}}`.
+pub struct PriceSource {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl PriceSource {
+    pub fn new(url: impl Into<String>) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(3))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .context("failed to construct price source http client")?;
+        Ok(Self {
+            http,
+            url: url.into(),
+        })
+    }
+
+    /// USD price per asset id. Assets the source does not quote are absent.
+    pub async fn fetch(&self, assets: &[&str]) -> anyhow::Result<HashMap<String, f64>> {
+        let body: HashMap<String, HashMap<String, f64>> = self
+            .http
+            .get(&self.url)
+            .query(&[("ids", assets.join(",").as_str()), ("vs_currencies", "usd")])
+            .send()
+            .await
+            .context("price source request failed")?
+            .error_for_status()
+            .context("price source returned an error")?
+            .json()
+            .await
+            .context("price source returned malformed JSON")?;
+
+        Ok(body
+            .into_iter()
+            .filter_map(|(asset, quotes)| {
+                let usd = *quotes.get("usd")?;
+                (usd.is_finite() && usd >= 0.0).then_some((asset, usd))
+            })
+            .collect())
+    }
+}
+
+/// Reads and writes the cached quotes for the configured tokens.
+#[derive(Clone)]
+pub struct PriceService {
+    cache: RedisCache,
+    enabled: bool,
+    tokens: Arc<Vec<PricedToken>>,
+    max_age: Duration,
+}
+
+impl PriceService {
+    pub fn new(cache: RedisCache, config: &Config) -> Self {
+        Self {
+            cache,
+            enabled: config.price_source_url.is_some(),
+            tokens: Arc::new(config.price_tokens.clone()),
+            max_age: config.price_max_age,
+        }
+    }
+
+    /// Distinct price-source asset ids across the configured tokens.
+    fn assets(&self) -> Vec<&str> {
+        let mut assets: Vec<&str> = self.tokens.iter().map(|t| t.asset.as_str()).collect();
+        assets.sort_unstable();
+        assets.dedup();
+        assets
+    }
+
+    /// Fetch every asset from `source` and store the quotes. Returns how many
+    /// were stored; an asset the source left out keeps its previous quote
+    /// until that ages out.
+    pub async fn refresh(&self, source: &PriceSource, now: DateTime<Utc>) -> anyhow::Result<usize> {
+        let assets = self.assets();
+        if assets.is_empty() {
+            return Ok(0);
+        }
+        let prices = source.fetch(&assets).await?;
+        for asset in &assets {
+            if !prices.contains_key(*asset) {
+                tracing::warn!(asset, "price source returned no USD price");
+            }
+        }
+        for (asset, usd) in &prices {
+            let quote = Quote {
+                usd: *usd,
+                fetched_at: now,
+            };
+            self.cache
+                .set_json(&keys::price_usd(asset), &quote, self.max_age)
+                .await?;
+        }
+        Ok(prices.len())
+    }
+
+    /// Snapshot of the stored quotes. A quote that cannot be read leaves its
+    /// asset unpriced rather than failing the request.
+    pub async fn quotes(&self) -> PriceQuotes {
+        let mut quotes = HashMap::new();
+        if self.enabled {
+            for asset in self.assets() {
+                match self.cache.get_json::<Quote>(&keys::price_usd(asset)).await {
+                    Ok(Some(quote)) => {
+                        quotes.insert(asset.to_string(), quote);
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(asset, error = %e, "price quote read failed"),
+                }
+            }
+        }
+        PriceQuotes::new(&self.tokens, quotes, self.max_age, Utc::now())
+    }
+}
+
+/// Quotes usable at one instant, keyed by token.
+#[derive(Debug, Clone, Default)]
+pub struct PriceQuotes {
+    /// token → (decimals, USD per whole token), fresh quotes only.
+    prices: HashMap<String, (u32, f64)>,
+}
+
+impl PriceQuotes {
+    /// Keep the quotes of `tokens` no older than `max_age` at `now`.
+    pub fn new(
+        tokens: &[PricedToken],
+        quotes: HashMap<String, Quote>,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let max_age =
+            chrono::Duration::from_std(max_age).unwrap_or_else(|_| chrono::Duration::weeks(52));
+        let prices = tokens
+            .iter()
+            .filter_map(|t| {
+                let quote = quotes.get(&t.asset)?;
+                (now - quote.fetched_at <= max_age)
+                    .then(|| (t.token.clone(), (t.decimals, quote.usd)))
+            })
+            .collect();
+        Self { prices }
+    }
+
+    /// USD value of `amount` integer units of `token`, to the cent.
+    pub fn volume_usd(&self, token: Option<&str>, amount: &str) -> Option<String> {
+        self.usd_cents(token, amount).map(format_usd)
+    }
+
+    /// Summed USD value of per-token amounts. `None` when any non-zero amount
+    /// cannot be priced, so a partial sum is never reported as the total.
+    pub fn total_usd(&self, volumes: &[(Option<String>, String)]) -> Option<String> {
+        let mut cents: i128 = 0;
+        for (token, amount) in volumes {
+            if amount.trim_start_matches('0').is_empty() {
+                continue;
+            }
+            cents = cents.checked_add(self.usd_cents(token.as_deref(), amount)?)?;
+        }
+        Some(format_usd(cents))
+    }
+
+    fn usd_cents(&self, token: Option<&str>, amount: &str) -> Option<i128> {
+        let (decimals, usd) = *self.prices.get(token?)?;
+        usd_cents(amount, decimals, usd)
+    }
+}
+
+/// USD cents for `amount` integer units of a token with `decimals` decimals
+/// priced at `usd` per whole token, rounded half up. `None` for a malformed
+/// or negative amount, or one too large to convert.
+pub fn usd_cents(amount: &str, decimals: u32, usd: f64) -> Option<i128> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: i128 = amount.parse().ok()?;
+    let price = (usd * 10f64.powi(PRICE_SCALE_DIGITS as i32)).round();
+    if !price.is_finite() || price < 0.0 || price >= i128::MAX as f64 {
+        return None;
+    }
+    // amount / 10^decimals tokens × price / 10^8 USD × 100 cents.
+    let divisor = 10i128.checked_pow(decimals + PRICE_SCALE_DIGITS - 2)?;
+    let scaled = amount.checked_mul(price as i128)?;
+    Some((scaled + divisor / 2) / divisor)
+}
+
+/// `cents` as a decimal dollar string, e.g. `"1234.50"`.
+pub fn format_usd(cents: i128) -> String {
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+/// Refresh prices every `price_refresh_interval` until `shutdown` fires. A
+/// failed fetch is logged; the previous quotes keep serving until they age
+/// past `price_max_age`.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    let Some(url) = state.config.price_source_url.clone() else {
+        return;
+    };
+    let source = match PriceSource::new(url) {
+        Ok(source) => source,
+        Err(e) => {
+            tracing::error!("[price] {e}");
+            return;
+        }
+    };
+    let service = PriceService::new(state.cache.clone(), &state.config);
+    let mut interval = tokio::time::interval(state.config.price_refresh_interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    state.metrics.set_worker_status(WORKER_NAME, true);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        if let Err(e) = service.refresh(&source, Utc::now()).await {
+            tracing::warn!("[price] refresh error: {e:#}");
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens() -> Vec<PricedToken> {
+        PricedToken::parse_list("CXLM:7:stellar,CUSDC:6:usd-coin")
+    }
+
+    fn quotes(prices: &[(&str, f64)], age: chrono::Duration) -> PriceQuotes {
+        let now = Utc::now();
+        let quotes = prices
+            .iter()
+            .map(|(asset, usd)| {
+                (
+                    asset.to_string(),
+                    Quote {
+                        usd: *usd,
+                        fetched_at: now - age,
+                    },
+                )
+            })
+            .collect();
+        PriceQuotes::new(&tokens(), quotes, Duration::from_secs(600), now)
+    }
+
+    #[test]
+    fn converts_six_and_seven_decimal_tokens() {
+        // 3,000 XLM at $0.12 and 30,000 USDC at $1.
+        assert_eq!(usd_cents("30000000000", 7, 0.12), Some(36_000));
+        assert_eq!(usd_cents("30000000000", 6, 1.0), Some(3_000_000));
+        // One stroop and one micro-USDC are fractions of a cent.
+        assert_eq!(usd_cents("1", 7, 0.12), Some(0));
+        assert_eq!(usd_cents("1", 6, 1.0), Some(0));
+        // 0.05 USDC and 0.5 XLM at $0.13 (6.5 cents) round half up.
+        assert_eq!(usd_cents("50000", 6, 1.0), Some(5));
+        assert_eq!(usd_cents("5000000", 7, 0.13), Some(7));
+        assert_eq!(format_usd(36_000), "360.00");
+        assert_eq!(format_usd(7), "0.07");
+    }
+
+    #[test]
+    fn rejects_malformed_and_oversized_amounts() {
+        for amount in ["", "-5", "1.5", "12a", " 1"] {
+            assert_eq!(usd_cents(amount, 7, 1.0), None, "{amount:?}");
+        }
+        // Past i128 once multiplied by the scaled price.
+        assert_eq!(usd_cents(&i128::MAX.to_string(), 7, 1.0), None);
+        assert_eq!(usd_cents("1", 7, f64::INFINITY), None);
+    }
+
+    #[test]
+    fn stale_or_unknown_prices_are_null_not_zero() {
+        let fresh = quotes(&[("stellar", 0.12)], chrono::Duration::seconds(30));
+        assert_eq!(
+            fresh.volume_usd(Some("CXLM"), "30000000000").as_deref(),
+            Some("360.00")
+        );
+        assert_eq!(
+            fresh.volume_usd(Some("CUSDC"), "30000000000"),
+            None,
+            "no quote"
+        );
+        assert_eq!(
+            fresh.volume_usd(Some("COTHER"), "1"),
+            None,
+            "unpriced token"
+        );
+        assert_eq!(fresh.volume_usd(None, "1"), None, "unknown token");
+
+        let stale = quotes(&[("stellar", 0.12)], chrono::Duration::seconds(601));
+        assert_eq!(stale.volume_usd(Some("CXLM"), "30000000000"), None);
+    }
+
+    #[test]
+    fn total_is_null_when_any_volume_is_unpriced() {
+        let q = quotes(
+            &[("stellar", 0.12), ("usd-coin", 1.0)],
+            chrono::Duration::zero(),
+        );
+        let volumes = vec![
+            (Some("CXLM".to_string()), "30000000000".to_string()),
+            (Some("CUSDC".to_string()), "30000000000".to_string()),
+            (None, "0".to_string()),
+        ];
+        assert_eq!(q.total_usd(&volumes).as_deref(), Some("30360.00"));
+
+        let mut with_unpriced = volumes.clone();
+        with_unpriced.push((None, "10".to_string()));
+        assert_eq!(q.total_usd(&with_unpriced), None);
+        assert_eq!(q.total_usd(&[]).as_deref(), Some("0.00"));
+    }
+
+    /// Prices from a mocked source convert each token by its own decimals.
+    #[tokio::test]
+    async fn fetches_from_source_and_converts_by_token_decimals() {
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("ids", "stellar,usd-coin"))
+            .and(query_param("vs_currencies", "usd"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "stellar": { "usd": 0.1234 },
+                "usd-coin": { "usd": 0.9998 },
+                "unasked": { "eur": 1.0 },
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let source = PriceSource::new(mock_server.uri()).unwrap();
+        let prices = source.fetch(&["stellar", "usd-coin"]).await.unwrap();
+        assert_eq!(prices.len(), 2);
+
+        let now = Utc::now();
+        let quotes = prices
+            .into_iter()
+            .map(|(asset, usd)| {
+                (
+                    asset,
+                    Quote {
+                        usd,
+                        fetched_at: now,
+                    },
+                )
+            })
+            .collect();
+        let q = PriceQuotes::new(&tokens(), quotes, Duration::from_secs(600), now);
+
+        // 12.5 XLM at $0.1234 = $1.5425; 12.5 USDC at $0.9998 = $12.4975.
+        assert_eq!(
+            q.volume_usd(Some("CXLM"), "125000000").as_deref(),
+            Some("1.54")
+        );
+        assert_eq!(
+            q.volume_usd(Some("CUSDC"), "12500000").as_deref(),
+            Some("12.50")
+        );
+    }
+
+    #[tokio::test]
+    async fn source_error_is_reported() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&mock_server)
+            .await;
+
+        let source = PriceSource::new(mock_server.uri()).unwrap();
+        assert!(source.fetch(&["stellar"]).await.is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Implied-probability history for market outcomes.
+//!
+//! The sync worker samples stake-weighted odds into `market_price_points`
+//! ([`crate::db::Database::price_points_sample`]); the history endpoint buckets
+//! those rows into OHLC candles in SQL. Raw samples older than
+//! [`RAW_RETENTION_DAYS`] are rolled up into hourly rows by a supervised task
+//! ([`run_retention`]), so sub-hour resolutions are only meaningful inside the
+//! retention window.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Duration as ChronoDuration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::AppState;
+
+/// Raw per-minute samples are kept this long before hourly roll-up.
+pub const RAW_RETENTION_DAYS: i64 = 30;
+
+/// Upper bound on candles per outcome in one response.
+pub const MAX_BUCKETS: i64 = 2_000;
+
+/// How often the retention task rolls up expired raw samples.
+const RETENTION_INTERVAL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub enum HistoryResolution {
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[default]
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl HistoryResolution {
+    pub fn seconds(self) -> i64 {
+        match self {
+            Self::FiveMinutes => 5 * 60,
+            Self::OneHour => 60 * 60,
+            Self::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Window used when the request omits `from`.
+    pub fn default_window(self) -> ChronoDuration {
+        match self {
+            Self::FiveMinutes => ChronoDuration::days(1),
+            Self::OneHour => ChronoDuration::days(7),
+            Self::OneDay => ChronoDuration::days(90),
+        }
+    }
+
+    /// Number of buckets `[from, to)` spans, counting partial buckets.
+    pub fn bucket_count(self, from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+        let secs = (to - from).num_seconds().max(0);
+        (secs + self.seconds() - 1) / self.seconds()
+    }
+}
+
+/// Start of the raw-retention cutoff hour. Rolling up whole hours only means
+/// an hour is never split between a roll-up row and raw rows.
+pub fn retention_cutoff(now: DateTime<Utc>) -> DateTime<Utc> {
+    let cutoff = now - ChronoDuration::days(RAW_RETENTION_DAYS);
+    cutoff.duration_trunc(ChronoDuration::hours(1)).unwrap_or(cutoff)
+}
+
+/// Supervised loop: rolls raw points past [`retention_cutoff`] into hourly
+/// rows every [`RETENTION_INTERVAL`] until `shutdown` fires. A failed run
+/// leaves the raw rows for the next tick.
+pub async fn run_retention(state: Arc<AppState>, shutdown: CancellationToken) {
+    let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        match state.db.price_points_downsample(retention_cutoff(Utc::now())).await {
+            Ok(n) if n > 0 => tracing::info!("[price-history] rolled up {n} hourly price points"),
+            Err(e) => tracing::warn!("[price-history] downsample error: {e}"),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PriceCandle {
+    /// Bucket start, aligned to the Unix epoch (UTC midnight for `1d`).
+    pub bucket_start: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct OutcomeSeries {
+    pub outcome: i32,
+    pub candles: Vec<PriceCandle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PriceHistory {
+    pub market_id: i64,
+    pub resolution: HistoryResolution,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub series: Vec<OutcomeSeries>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn resolution_uses_short_labels() {
+        assert_eq!(serde_json::to_value(HistoryResolution::FiveMinutes).unwrap(), "5m");
+        assert_eq!(
+            serde_json::from_value::<HistoryResolution>(serde_json::json!("1d")).unwrap(),
+            HistoryResolution::OneDay
+        );
+        assert!(serde_json::from_value::<HistoryResolution>(serde_json::json!("15m")).is_err());
+    }
+
+    #[test]
+    fn bucket_count_rounds_partial_buckets_up() {
+        let from = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let r = HistoryResolution::OneHour;
+        assert_eq!(r.bucket_count(from, from), 0);
+        assert_eq!(r.bucket_count(from, from + ChronoDuration::minutes(1)), 1);
+        assert_eq!(r.bucket_count(from, from + ChronoDuration::hours(2)), 2);
+        assert_eq!(r.bucket_count(from, from - ChronoDuration::hours(2)), 0);
+    }
+
+    #[test]
+    fn retention_cutoff_is_hour_aligned() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 12, 34, 56).unwrap();
+        assert_eq!(
+            retention_cutoff(now),
+            Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,279 @@
+#[cfg(test)]
+mod price_history_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use chrono::{TimeZone, Utc};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        blockchain::{IndexedEvent, EVENT_BET_PLACED},
+        handlers::market_history,
+        price_history::HistoryResolution,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 3] = [9301, 9302, 9303];
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/markets/:market_id/history", get(market_history))
+            .with_state(state)
+    }
+
+    async fn get_json(router: Router, uri: &str) -> (StatusCode, Value) {
+        let response = router
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    async fn insert_point(state: &crate::AppState, market_id: i64, ts: &str, p: f64) {
+        sqlx::query(
+            "INSERT INTO market_price_points (market_id, outcome, ts, open, high, low, close) \
+             VALUES ($1, 0, $2::TIMESTAMPTZ, $3, $3, $3, $3)",
+        )
+        .bind(market_id)
+        .bind(ts)
+        .bind(p)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM market_price_points WHERE market_id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE 'phtest-%'")
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    fn candles(body: &Value) -> Vec<(String, f64, f64, f64, f64)> {
+        body["series"][0]["candles"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| {
+                (
+                    c["bucket_start"].as_str().unwrap().to_string(),
+                    c["open"].as_f64().unwrap(),
+                    c["high"].as_f64().unwrap(),
+                    c["low"].as_f64().unwrap(),
+                    c["close"].as_f64().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A point exactly on a boundary opens the next bucket; `to` is exclusive.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_history_hourly_bucket_boundaries() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        insert_point(&state, 9301, "2026-01-01T10:00:00Z", 0.40).await;
+        insert_point(&state, 9301, "2026-01-01T10:30:00Z", 0.55).await;
+        insert_point(&state, 9301, "2026-01-01T10:59:00Z", 0.45).await;
+        insert_point(&state, 9301, "2026-01-01T11:00:00Z", 0.50).await;
+        insert_point(&state, 9301, "2026-01-01T12:00:00Z", 0.60).await;
+
+        let (status, body) = get_json(
+            app(Arc::clone(&state)),
+            "/markets/9301/history?resolution=1h&from=2026-01-01T10:00:00Z&to=2026-01-01T12:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            candles(&body),
+            vec![
+                ("2026-01-01T10:00:00Z".to_string(), 0.40, 0.55, 0.40, 0.45),
+                ("2026-01-01T11:00:00Z".to_string(), 0.50, 0.50, 0.50, 0.50),
+            ]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Daily candles start at UTC midnight even when `from` does not.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_history_daily_buckets_align_to_midnight() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        insert_point(&state, 9301, "2026-01-01T10:00:00Z", 0.40).await;
+        insert_point(&state, 9301, "2026-01-01T23:59:59Z", 0.70).await;
+        insert_point(&state, 9301, "2026-01-02T00:00:00Z", 0.20).await;
+
+        let (status, body) = get_json(
+            app(Arc::clone(&state)),
+            "/markets/9301/history?resolution=1d&from=2026-01-01T06:00:00Z&to=2026-01-03T00:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            candles(&body),
+            vec![
+                ("2026-01-01T00:00:00Z".to_string(), 0.40, 0.70, 0.40, 0.70),
+                ("2026-01-02T00:00:00Z".to_string(), 0.20, 0.20, 0.20, 0.20),
+            ]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Raw rows before the cutoff collapse into one hourly row with the same
+    /// OHLC; rows after the cutoff stay raw; a second run is a no-op.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_downsample_rolls_up_whole_hours() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        insert_point(&state, 9302, "2020-01-01T10:00:00Z", 0.30).await;
+        insert_point(&state, 9302, "2020-01-01T10:20:00Z", 0.10).await;
+        insert_point(&state, 9302, "2020-01-01T10:40:00Z", 0.35).await;
+        insert_point(&state, 9302, "2020-01-01T11:05:00Z", 0.50).await;
+
+        let cutoff = Utc.with_ymd_and_hms(2020, 1, 1, 11, 0, 0).unwrap();
+        assert_eq!(state.db.price_points_downsample(cutoff).await.unwrap(), 1);
+        assert_eq!(state.db.price_points_downsample(cutoff).await.unwrap(), 0);
+
+        let (raw, rollups): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*) FILTER (WHERE NOT is_rollup), COUNT(*) FILTER (WHERE is_rollup) \
+             FROM market_price_points WHERE market_id = 9302",
+        )
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!((raw, rollups), (1, 1));
+
+        let series = state
+            .db
+            .price_history(
+                9302,
+                Some(0),
+                HistoryResolution::OneHour,
+                Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap(),
+            )
+            .await
+            .unwrap();
+        let c = &series[0].candles[0];
+        assert_eq!(c.bucket_start, Utc.with_ymd_and_hms(2020, 1, 1, 10, 0, 0).unwrap());
+        assert_eq!((c.open, c.high, c.low, c.close), (0.30, 0.35, 0.10, 0.35));
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_sample_records_stake_weighted_odds() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at) \
+             VALUES (9303, 'PH', 'active', 0, NOW() + INTERVAL '1 day', NOW())",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        let network = state.config.network_name();
+        for (n, outcome, amount) in [(1u32, 0u32, 200i128), (2, 0, 100), (3, 1, 100)] {
+            let event = IndexedEvent {
+                id: format!("phtest-{n}"),
+                ledger: 2_000 + n,
+                tx_hash: None,
+                kind: EVENT_BET_PLACED.to_string(),
+                market_id: Some(9303),
+                address: Some(format!("GPHTEST_{n}")),
+                outcome: Some(outcome),
+                amount: Some(amount.to_string()),
+                token: None,
+                is_refund: false,
+            };
+            state.db.chain_event_insert(network, &event).await.unwrap();
+        }
+
+        state.db.price_points_sample(network).await.unwrap();
+
+        let rows: Vec<(i32, f64)> = sqlx::query_as(
+            "SELECT outcome, close FROM market_price_points \
+             WHERE market_id = 9303 ORDER BY outcome",
+        )
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(rows, vec![(0, 0.75), (1, 0.25)]);
+
+        cleanup(&state).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_history_rejects_inverted_range() {
+        let state = build_test_state().await;
+        let (status, body) = get_json(
+            app(state),
+            "/markets/9301/history?from=2026-01-02T00:00:00Z&to=2026-01-01T00:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "BAD_REQUEST");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_history_rejects_too_many_buckets() {
+        let state = build_test_state().await;
+        let (status, _) = get_json(
+            app(state),
+            "/markets/9301/history?resolution=5m&from=2025-01-01T00:00:00Z&to=2026-01-01T00:00:00Z",
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // ---------------------------------------------------------------------------
+    // Pure-logic unit tests (no I/O)
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_price_history_query_parses_resolution_labels() {
+        let uri: axum::http::Uri = "/history?resolution=5m&outcome=1".parse().unwrap();
+        let axum::extract::Query(query) =
+            axum::extract::Query::<crate::handlers::PriceHistoryQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.resolution, Some(HistoryResolution::FiveMinutes));
+        assert_eq!(query.outcome, Some(1));
+        assert!(query.from.is_none());
+    }
+
+}
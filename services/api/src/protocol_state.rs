@@ -0,0 +1,470 @@
+//! Protocol-wide state for `GET /api/v1/blockchain/protocol-state`.
+//!
+//! Support uses this to answer "why can't I bet" without a block explorer.
+//! [`ProtocolState`] is simulated from the contract's getters
+//! (`get_circuit_breaker_state`, `get_pending_guardian_removal`,
+//! `get_pending_upgrade`) and cached for `CACHE_TTL_PROTOCOL_STATE_SECS`.
+//! The contract has no per-market pause: a market takes bets when the breaker
+//! is not paused and the market itself is active, which is what
+//! [`MarketBettingState`] reports.
+//!
+//! Every breaker transition emits a `cb_state` event. The sync worker turns
+//! those into [`ProtocolStateChange`] rows in `protocol_state_changes`, which
+//! back `?include_history=true`.
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use stellar_xdr::curr::{self as xdr, ReadXdr};
+
+use crate::blockchain::ContractEvent;
+
+/// Event published on every circuit breaker transition.
+pub const EVENT_CIRCUIT_BREAKER: &str = "cb_state";
+
+/// Default and maximum number of transitions `?include_history=true` returns.
+pub const DEFAULT_HISTORY_LIMIT: i64 = 20;
+pub const MAX_HISTORY_LIMIT: i64 = 100;
+
+/// Most market ids one request may ask about.
+pub const MAX_MARKETS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    Closed,
+    /// Tripped. Disputes and votes are blocked; recovers to `half_open`
+    /// after the contract's cool-down.
+    Open,
+    /// Probing: a limited number of guarded calls go through.
+    HalfOpen,
+    /// Emergency pause by the admin, guardian, or guardian majority. Blocks
+    /// bets, market creation, and disputes until unpaused.
+    Paused,
+}
+
+impl BreakerState {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::Open => "open",
+            Self::HalfOpen => "half_open",
+            Self::Paused => "paused",
+        }
+    }
+
+    /// Parse a label as the `cb_state` event and the database carry it.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "closed" => Some(Self::Closed),
+            "open" => Some(Self::Open),
+            "half_open" => Some(Self::HalfOpen),
+            "paused" => Some(Self::Paused),
+            _ => None,
+        }
+    }
+
+    /// Whether `place_bet` fails with `ContractPaused` in this state.
+    pub fn blocks_bets(self) -> bool {
+        self == Self::Paused
+    }
+
+    /// Whether `file_dispute` and `cast_vote` fail with `ContractPaused`.
+    /// Half-open is not counted: it lets a few calls through.
+    pub fn blocks_disputes(self) -> bool {
+        matches!(self, Self::Open | Self::Paused)
+    }
+}
+
+/// A guardian removal that is being voted on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GuardianRemoval {
+    pub target_guardian: String,
+    /// Unix seconds.
+    pub initiated_at: u64,
+    pub votes_for: u32,
+}
+
+/// A contract upgrade that has been initiated but not executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PendingUpgrade {
+    /// Hex-encoded WASM hash.
+    pub wasm_hash: String,
+    /// Unix seconds.
+    pub initiated_at: u64,
+    pub votes_for: u32,
+    pub votes_against: u32,
+}
+
+/// Contract-wide state, as simulated and cached.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProtocolState {
+    pub circuit_breaker: BreakerState,
+    pub accepts_bets: bool,
+    pub accepts_disputes: bool,
+    pub guardian_removal: Option<GuardianRemoval>,
+    pub pending_upgrade: Option<PendingUpgrade>,
+    /// Ledger the simulations ran against.
+    pub ledger: u32,
+    pub read_at: DateTime<Utc>,
+}
+
+impl ProtocolState {
+    pub fn new(
+        circuit_breaker: BreakerState,
+        guardian_removal: Option<GuardianRemoval>,
+        pending_upgrade: Option<PendingUpgrade>,
+        ledger: u32,
+        read_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            accepts_bets: !circuit_breaker.blocks_bets(),
+            accepts_disputes: !circuit_breaker.blocks_disputes(),
+            circuit_breaker,
+            guardian_removal,
+            pending_upgrade,
+            ledger,
+            read_at,
+        }
+    }
+}
+
+/// Whether one market takes bets right now.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MarketBettingState {
+    pub market_id: i64,
+    /// On-chain status; `null` when the read failed.
+    pub status: Option<String>,
+    pub accepts_bets: bool,
+}
+
+impl MarketBettingState {
+    pub fn new(market_id: i64, status: Option<String>, breaker: BreakerState) -> Self {
+        let active = status
+            .as_deref()
+            .is_some_and(|status| status.eq_ignore_ascii_case("active"));
+        Self {
+            market_id,
+            accepts_bets: active && !breaker.blocks_bets(),
+            status,
+        }
+    }
+}
+
+/// One recorded circuit breaker transition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProtocolStateChange {
+    pub event_id: String,
+    pub ledger: u32,
+    pub state: BreakerState,
+    /// Close time of the ledger the transition happened in.
+    pub changed_at: DateTime<Utc>,
+}
+
+/// `GET /api/v1/blockchain/protocol-state` response.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ProtocolStateView {
+    pub state: ProtocolState,
+    /// One entry per requested market id, in request order.
+    pub markets: Vec<MarketBettingState>,
+    /// Latest transitions first; only present with `include_history=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<ProtocolStateChange>>,
+}
+
+/// The transition `event` records, if it is a `cb_state` event. The event
+/// carries `(version, state)`; `changed_at` is the ledger close time, or now
+/// when the RPC did not report one.
+pub fn breaker_transition(event: &ContractEvent) -> Option<ProtocolStateChange> {
+    let topic = event.value.get("topic").and_then(Value::as_array)?;
+    if topic.first().and_then(Value::as_str)? != EVENT_CIRCUIT_BREAKER {
+        return None;
+    }
+    let state = event
+        .value
+        .get("value")
+        .and_then(Value::as_array)
+        .and_then(|data| data.get(1))
+        .and_then(Value::as_str)
+        .and_then(BreakerState::parse)?;
+    let changed_at = event
+        .value
+        .get("ledgerClosedAt")
+        .and_then(Value::as_str)
+        .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        .map_or_else(Utc::now, |at| at.with_timezone(&Utc));
+
+    Some(ProtocolStateChange {
+        event_id: event.id.clone(),
+        ledger: event.ledger,
+        state,
+        changed_at,
+    })
+}
+
+/// Decode `get_circuit_breaker_state`'s result: a unit enum variant, which
+/// the host encodes as a one-symbol vec.
+pub fn decode_breaker_state(result_xdr: &str) -> anyhow::Result<BreakerState> {
+    let val = xdr::ScVal::from_xdr_base64(result_xdr, xdr::Limits::none())?;
+    let variant = match &val {
+        xdr::ScVal::Vec(Some(items)) => match items.0.first() {
+            Some(xdr::ScVal::Symbol(symbol)) => symbol.0.to_utf8_string_lossy(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    };
+    match variant.as_str() {
+        "Closed" => Ok(BreakerState::Closed),
+        "Open" => Ok(BreakerState::Open),
+        "HalfOpen" => Ok(BreakerState::HalfOpen),
+        "Paused" => Ok(BreakerState::Paused),
+        _ => Err(anyhow!(
+            "unexpected circuit breaker state {:?}",
+            val.discriminant()
+        )),
+    }
+}
+
+/// Decode `get_pending_guardian_removal`'s `Option<PendingGuardianRemoval>`.
+pub fn decode_guardian_removal(result_xdr: &str) -> anyhow::Result<Option<GuardianRemoval>> {
+    let Some(map) = optional_struct(result_xdr)? else {
+        return Ok(None);
+    };
+    Ok(Some(GuardianRemoval {
+        target_guardian: address(field(&map, "target_guardian")?)?,
+        initiated_at: u64_value(field(&map, "initiated_at")?)?,
+        votes_for: vec_len(field(&map, "votes_for")?)?,
+    }))
+}
+
+/// Decode `get_pending_upgrade`'s `Option<PendingUpgrade>`.
+pub fn decode_pending_upgrade(result_xdr: &str) -> anyhow::Result<Option<PendingUpgrade>> {
+    let Some(map) = optional_struct(result_xdr)? else {
+        return Ok(None);
+    };
+    let wasm_hash = match field(&map, "wasm_hash")? {
+        xdr::ScVal::Bytes(bytes) => hex::encode(bytes.0.as_slice()),
+        other => {
+            return Err(anyhow!(
+                "wasm_hash: expected bytes, got {:?}",
+                other.discriminant()
+            ))
+        }
+    };
+    Ok(Some(PendingUpgrade {
+        wasm_hash,
+        initiated_at: u64_value(field(&map, "initiated_at")?)?,
+        votes_for: vec_len(field(&map, "votes_for")?)?,
+        votes_against: vec_len(field(&map, "votes_against")?)?,
+    }))
+}
+
+/// A contract struct is a map keyed by field-name symbols; `None` is void.
+fn optional_struct(result_xdr: &str) -> anyhow::Result<Option<xdr::ScMap>> {
+    match xdr::ScVal::from_xdr_base64(result_xdr, xdr::Limits::none())? {
+        xdr::ScVal::Void => Ok(None),
+        xdr::ScVal::Map(Some(map)) => Ok(Some(map)),
+        other => Err(anyhow!("expected a struct, got {:?}", other.discriminant())),
+    }
+}
+
+fn field<'a>(map: &'a xdr::ScMap, name: &str) -> anyhow::Result<&'a xdr::ScVal> {
+    map.0
+        .iter()
+        .find(|entry| matches!(&entry.key, xdr::ScVal::Symbol(symbol) if symbol.0.to_utf8_string_lossy() == name))
+        .map(|entry| &entry.val)
+        .ok_or_else(|| anyhow!("missing field {name}"))
+}
+
+fn u64_value(val: &xdr::ScVal) -> anyhow::Result<u64> {
+    match val {
+        xdr::ScVal::U64(n) => Ok(*n),
+        other => Err(anyhow!("expected a u64, got {:?}", other.discriminant())),
+    }
+}
+
+fn vec_len(val: &xdr::ScVal) -> anyhow::Result<u32> {
+    match val {
+        xdr::ScVal::Vec(Some(items)) => Ok(items.0.len() as u32),
+        xdr::ScVal::Vec(None) => Ok(0),
+        other => Err(anyhow!("expected a vec, got {:?}", other.discriminant())),
+    }
+}
+
+fn address(val: &xdr::ScVal) -> anyhow::Result<String> {
+    match val {
+        xdr::ScVal::Address(xdr::ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(key)),
+        ))) => Ok(stellar_strkey::ed25519::PublicKey(*key).to_string()),
+        xdr::ScVal::Address(xdr::ScAddress::Contract(xdr::Hash(hash))) => {
+            Ok(stellar_strkey::Contract(*hash).to_string())
+        }
+        other => Err(anyhow!(
+            "expected an address, got {:?}",
+            other.discriminant()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::WriteXdr;
+
+    /// Simulation result XDR for `val`, as `simulateTransaction` returns it.
+    fn result_xdr(val: xdr::ScVal) -> String {
+        val.to_xdr_base64(xdr::Limits::none()).unwrap()
+    }
+
+    fn symbol(name: &str) -> xdr::ScVal {
+        xdr::ScVal::Symbol(xdr::ScSymbol(name.try_into().unwrap()))
+    }
+
+    fn struct_val(fields: Vec<(&str, xdr::ScVal)>) -> xdr::ScVal {
+        let entries: Vec<xdr::ScMapEntry> = fields
+            .into_iter()
+            .map(|(key, val)| xdr::ScMapEntry {
+                key: symbol(key),
+                val,
+            })
+            .collect();
+        xdr::ScVal::Map(Some(xdr::ScMap(entries.try_into().unwrap())))
+    }
+
+    fn accounts(n: u8) -> xdr::ScVal {
+        let items: Vec<xdr::ScVal> = (0..n)
+            .map(|i| {
+                xdr::ScVal::Address(xdr::ScAddress::Account(xdr::AccountId(
+                    xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256([i; 32])),
+                )))
+            })
+            .collect();
+        xdr::ScVal::Vec(Some(xdr::ScVec(items.try_into().unwrap())))
+    }
+
+    fn event(value: Value) -> ContractEvent {
+        ContractEvent {
+            id: "0000000200-0001".to_string(),
+            ledger: 200,
+            topic: value["topic"].to_string(),
+            tx_hash: None,
+            value,
+        }
+    }
+
+    #[test]
+    fn every_breaker_state_decodes_from_simulation_result() {
+        for (variant, state) in [
+            ("Closed", BreakerState::Closed),
+            ("Open", BreakerState::Open),
+            ("HalfOpen", BreakerState::HalfOpen),
+            ("Paused", BreakerState::Paused),
+        ] {
+            let xdr = result_xdr(xdr::ScVal::Vec(Some(xdr::ScVec(
+                vec![symbol(variant)].try_into().unwrap(),
+            ))));
+            assert_eq!(decode_breaker_state(&xdr).unwrap(), state, "{variant}");
+            assert_eq!(BreakerState::parse(state.label()), Some(state));
+        }
+        assert!(decode_breaker_state(&result_xdr(xdr::ScVal::U32(0))).is_err());
+    }
+
+    #[test]
+    fn only_a_pause_blocks_bets() {
+        let paused = ProtocolState::new(BreakerState::Paused, None, None, 1, Utc::now());
+        assert!(!paused.accepts_bets && !paused.accepts_disputes);
+
+        let open = ProtocolState::new(BreakerState::Open, None, None, 1, Utc::now());
+        assert!(open.accepts_bets && !open.accepts_disputes);
+
+        let half_open = ProtocolState::new(BreakerState::HalfOpen, None, None, 1, Utc::now());
+        assert!(half_open.accepts_bets && half_open.accepts_disputes);
+
+        assert!(
+            MarketBettingState::new(7, Some("Active".into()), BreakerState::Closed).accepts_bets
+        );
+        assert!(
+            !MarketBettingState::new(7, Some("active".into()), BreakerState::Paused).accepts_bets
+        );
+        assert!(
+            !MarketBettingState::new(7, Some("resolved".into()), BreakerState::Closed).accepts_bets
+        );
+        assert!(!MarketBettingState::new(7, None, BreakerState::Closed).accepts_bets);
+    }
+
+    #[test]
+    fn guardian_removal_decodes_or_is_absent() {
+        let target = xdr::ScVal::Address(xdr::ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256([0; 32])),
+        )));
+        let xdr = result_xdr(struct_val(vec![
+            ("initiated_at", xdr::ScVal::U64(1_000)),
+            ("target_guardian", target),
+            ("votes_for", accounts(2)),
+        ]));
+        let removal = decode_guardian_removal(&xdr).unwrap().unwrap();
+        assert_eq!(
+            removal.target_guardian,
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+        );
+        assert_eq!(removal.initiated_at, 1_000);
+        assert_eq!(removal.votes_for, 2);
+
+        assert_eq!(
+            decode_guardian_removal(&result_xdr(xdr::ScVal::Void)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn pending_upgrade_decodes_or_is_absent() {
+        let xdr = result_xdr(struct_val(vec![
+            ("initiated_at", xdr::ScVal::U64(5_000)),
+            ("votes_against", accounts(1)),
+            ("votes_for", accounts(3)),
+            (
+                "wasm_hash",
+                xdr::ScVal::Bytes(xdr::ScBytes(vec![0xab; 32].try_into().unwrap())),
+            ),
+        ]));
+        let upgrade = decode_pending_upgrade(&xdr).unwrap().unwrap();
+        assert_eq!(upgrade.wasm_hash, "ab".repeat(32));
+        assert_eq!(upgrade.initiated_at, 5_000);
+        assert_eq!((upgrade.votes_for, upgrade.votes_against), (3, 1));
+
+        assert_eq!(
+            decode_pending_upgrade(&result_xdr(xdr::ScVal::Void)).unwrap(),
+            None
+        );
+        assert!(decode_pending_upgrade(&result_xdr(struct_val(vec![]))).is_err());
+    }
+
+    #[test]
+    fn breaker_events_become_transitions() {
+        let change = breaker_transition(&event(serde_json::json!({
+            "topic": ["cb_state", 0, "CCONTRACT"],
+            "value": [1, "paused"],
+            "ledgerClosedAt": "2026-03-01T12:00:00Z",
+        })))
+        .unwrap();
+        assert_eq!(change.state, BreakerState::Paused);
+        assert_eq!(change.ledger, 200);
+        assert_eq!(change.changed_at.to_rfc3339(), "2026-03-01T12:00:00+00:00");
+
+        assert_eq!(
+            breaker_transition(&event(serde_json::json!({
+                "topic": ["bet_place", 7, "GBETTOR"],
+                "value": [1, 0, "100"],
+            }))),
+            None
+        );
+        assert_eq!(
+            breaker_transition(&event(serde_json::json!({
+                "topic": ["cb_state", 0, "CCONTRACT"],
+                "value": [1, "melted"],
+            }))),
+            None
+        );
+    }
+}
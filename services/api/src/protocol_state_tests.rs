@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod protocol_state_tests {
+    use serde_json::json;
+
+    use crate::{
+        blockchain::ContractEvent,
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+        protocol_state::{breaker_transition, BreakerState},
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// A network name no real sync writes to, so cleanup only touches rows
+    /// created here.
+    const NETWORK: &str = "protocol-state-test";
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    fn breaker_event(id: &str, ledger: u32, state: &str) -> ContractEvent {
+        let value = json!({
+            "id": id,
+            "ledger": ledger,
+            "ledgerClosedAt": "2026-03-01T12:00:00Z",
+            "topic": ["cb_state", 0, "CCONTRACT"],
+            "value": [1, state],
+        });
+        ContractEvent {
+            id: id.to_string(),
+            ledger,
+            topic: value["topic"].to_string(),
+            tx_hash: None,
+            value,
+        }
+    }
+
+    async fn cleanup(db: &Database) {
+        db.protocol_state_changes_delete_above(NETWORK, 0)
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Tests
+    // ---------------------------------------------------------------------------
+
+    /// A synthetic `cb_state` event becomes one history row, replays do not
+    /// add a second, and a rewind removes it.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_breaker_event_records_one_history_row() {
+        let db = build_db().await;
+        cleanup(&db).await;
+
+        let paused = breaker_transition(&breaker_event("0000000300-0001", 300, "paused")).unwrap();
+        db.protocol_state_change_insert(NETWORK, &paused)
+            .await
+            .unwrap();
+        db.protocol_state_change_insert(NETWORK, &paused)
+            .await
+            .unwrap();
+        let closed = breaker_transition(&breaker_event("0000000310-0001", 310, "closed")).unwrap();
+        db.protocol_state_change_insert(NETWORK, &closed)
+            .await
+            .unwrap();
+
+        let history = db.protocol_state_changes_recent(NETWORK, 10).await.unwrap();
+        assert_eq!(history, vec![closed.clone(), paused.clone()]);
+        assert_eq!(history[1].state, BreakerState::Paused);
+        assert_eq!(
+            db.protocol_state_changes_recent(NETWORK, 1).await.unwrap(),
+            vec![closed]
+        );
+
+        assert_eq!(
+            db.protocol_state_changes_delete_above(NETWORK, 305)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            db.protocol_state_changes_recent(NETWORK, 10).await.unwrap(),
+            vec![paused]
+        );
+
+        cleanup(&db).await;
+    }
+}
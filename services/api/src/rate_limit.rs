@@ -11,19 +11,29 @@
 //!  4. EXPIRE to align Redis TTL with the window
 //!
 //! All four commands execute atomically via a Lua script.
+//!
+//! ## Per-resource limits and abuse blocks
+//! Public routes that name a wallet address or market id are also limited
+//! per resource ([`resource_rate_limit_middleware`]), across all client IPs,
+//! so a botnet spread over many IPs cannot hammer one address's RPC lookups.
+//! Every 429 from the global or per-resource limiter adds one to the client
+//! IP's abuse score; once the score reaches [`AbusePolicy::threshold`] the IP
+//! is blocked from the public routes for a period that doubles with each
+//! further 429 ([`AbusePolicy::block_secs`]). All counters live in Redis.
 
 use axum::{
-    extract::{ConnectInfo, State},
-    http::{HeaderMap, StatusCode},
+    extract::{ConnectInfo, RawPathParams, State},
+    http::HeaderMap,
     middleware::Next,
     response::{IntoResponse, Response},
-    Json,
 };
 use deadpool_redis::Pool as RedisPool;
-use serde::Serialize;
+use serde_json::json;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::sync::Arc;
 
+use crate::handlers::ApiError;
+
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
     pub max_requests:   u64,
@@ -48,13 +58,8 @@ pub struct RateLimitState {
     /// Optional metrics sink. When present, rejections are counted under
     /// the `rate_limit_rejections_total` Prometheus counter.
     pub metrics: Option<crate::metrics::Metrics>,
-}
-
-#[derive(Serialize)]
-struct RateLimitError {
-    error:       &'static str,
-    message:     String,
-    retry_after: u64,
+    /// Peers whose forwarding headers name the client.
+    pub proxies: crate::security::TrustedProxies,
 }
 
 // KEYS[1] = rate limit key
@@ -62,6 +67,7 @@ struct RateLimitError {
 // ARGV[2] = window start (ms)
 // ARGV[3] = window TTL (seconds)
 // ARGV[4] = unique member
+// ARGV[5] = number of requests to record
 // Returns request count within the current window.
 const SLIDING_WINDOW_SCRIPT: &str = r#"
 local key          = KEYS[1]
@@ -69,8 +75,11 @@ local now          = tonumber(ARGV[1])
 local window_start = tonumber(ARGV[2])
 local ttl          = tonumber(ARGV[3])
 local member       = ARGV[4]
+local cost         = tonumber(ARGV[5])
 
-redis.call('ZADD', key, now, member)
+for i = 1, cost do
+    redis.call('ZADD', key, now, member .. ':' .. i)
+end
 redis.call('ZREMRANGEBYSCORE', key, '-inf', window_start)
 local count = redis.call('ZCARD', key)
 redis.call('EXPIRE', key, ttl)
@@ -81,6 +90,17 @@ pub async fn check_rate_limit(
     redis: &RedisPool,
     config: &RateLimitConfig,
     client_key: &str,
+) -> Result<u64, u64> {
+    check_rate_limit_weighted(redis, config, client_key, 1).await
+}
+
+/// [`check_rate_limit`] for a request that does the work of `cost` requests,
+/// such as a batch. All `cost` are recorded even when the call is rejected.
+pub async fn check_rate_limit_weighted(
+    redis: &RedisPool,
+    config: &RateLimitConfig,
+    client_key: &str,
+    cost: u64,
 ) -> Result<u64, u64> {
     let mut conn = redis
         .get()
@@ -103,6 +123,7 @@ pub async fn check_rate_limit(
         .arg(window_start_ms)
         .arg(config.window_seconds + 1)
         .arg(&member)
+        .arg(cost.max(1))
         .invoke_async(&mut conn)
         .await
         .map_err(|_| config.window_seconds)?;
@@ -117,10 +138,11 @@ pub async fn check_rate_limit(
 pub async fn rate_limit_middleware(
     State(state): State<RateLimitState>,
     headers: HeaderMap,
+    connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
     req: axum::extract::Request,
     next: Next,
 ) -> Response {
-    let client_key = client_key_from_headers(&headers);
+    let client_key = state.proxies.client_ip(&headers, connect_info.as_ref());
 
     match check_rate_limit(&state.redis, &state.config, &client_key).await {
         Ok(_count) => next.run(req).await,
@@ -134,20 +156,7 @@ pub async fn rate_limit_middleware(
                 retry_after,
                 "rate limit exceeded"
             );
-            let body = RateLimitError {
-                error:   "rate_limit_exceeded",
-                message: format!(
-                    "Rate limit of {} requests per {}s exceeded. Retry after {} seconds.",
-                    state.config.max_requests, state.config.window_seconds, retry_after
-                ),
-                retry_after,
-            };
-            (
-                StatusCode::TOO_MANY_REQUESTS,
-                [("Retry-After", retry_after.to_string())],
-                Json(body),
-            )
-                .into_response()
+            rate_limit_response(retry_after, &state.config)
         }
     }
 }
@@ -183,65 +192,49 @@ pub async fn newsletter_rate_limit_middleware(
         tracing::warn!(client_ip = %ip, "newsletter rate limit exceeded");
         state.metrics.observe_rate_limit_rejection("newsletter");
         let retry_after = state.config.newsletter_rate_limit_window_secs;
-        let body = RateLimitError {
-            error: "rate_limit_exceeded",
-            message: "Too many newsletter requests. Please try again later.".to_string(),
-            retry_after,
-        };
-        return (
-            StatusCode::TOO_MANY_REQUESTS,
-            [("Retry-After", retry_after.to_string())],
-            Json(body),
-        )
-            .into_response();
+        return too_many_requests("Too many newsletter requests. Please try again later.", retry_after);
     }
     next.run(req).await
 }
 
-/// Rate-limit middleware for admin routes using the in-memory limiter.
-///
-/// Admin routes see far lower traffic than public routes, so the per-instance
-/// in-memory limiter is sufficient here — it still protects individual nodes
-/// from local abuse without requiring a Redis round-trip on every admin call.
-pub async fn admin_rate_limit_middleware(
-    State(limiter): State<std::sync::Arc<crate::security::RateLimiter>>,
-    headers: HeaderMap,
-    connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
-    req: axum::extract::Request,
-    next: Next,
-) -> Response {
-    use crate::security::{extract_client_ip, RateLimitConfig};
-    let ip = extract_client_ip(&headers, connect_info.as_ref(), true);
-    let config = RateLimitConfig::new(30, std::time::Duration::from_secs(60));
-    if !limiter.check(&format!("admin:{ip}"), &config).await {
-        tracing::warn!(client_ip = %ip, "admin rate limit exceeded");
-        let body = RateLimitError {
-            error: "rate_limit_exceeded",
-            message: "Too many admin requests. Please try again later.".to_string(),
-            retry_after: 60,
-        };
-        return (
-            StatusCode::TOO_MANY_REQUESTS,
-            [("Retry-After", "60".to_string())],
-            Json(body),
-        )
-            .into_response();
+/// The per-IP budget [`global_rate_limit_middleware`] applies to public routes.
+pub fn global_rate_limit_config() -> RateLimitConfig {
+    RateLimitConfig {
+        max_requests:   100,
+        window_seconds: 60,
+        key_prefix:     "global".to_string(),
     }
-    next.run(req).await
 }
 
-fn client_key_from_headers(headers: &HeaderMap) -> String {
-    headers
-        .get("x-forwarded-for")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.split(',').next())
-        .map(|s| s.trim().to_string())
-        .unwrap_or_else(|| "unknown".to_string())
+/// Charge `extra` more requests to `ip`'s global budget, on top of the one
+/// the middleware already counted, for routes where one HTTP request does the
+/// work of several (`POST /api/v1/blockchain/batch`). Returns the 429 to send
+/// when the budget is exhausted.
+pub async fn charge_global_rate_limit(
+    state: &crate::AppState,
+    ip: &str,
+    extra: u64,
+) -> Result<(), Response> {
+    if extra == 0 {
+        return Ok(());
+    }
+    let config = global_rate_limit_config();
+    let pool = state.cache.redis_pool();
+    match check_rate_limit_weighted(&pool, &config, ip, extra).await {
+        Ok(_) => Ok(()),
+        Err(retry_after) => {
+            state.metrics.observe_rate_limit_rejection("global");
+            Err(rate_limit_response(retry_after, &config))
+        }
+    }
 }
 
 /// Redis-backed global rate limit middleware (100 req/min per IP).
 /// Replaces the former in-memory `global_rate_limit_middleware` from `security.rs`
 /// so limits are shared across all API instances.
+///
+/// Also enforces abuse blocks: an IP that is currently blocked is rejected
+/// before its request is counted, and each 429 raises its abuse score.
 pub async fn global_rate_limit_middleware(
     State(state): State<Arc<crate::AppState>>,
     headers: HeaderMap,
@@ -256,36 +249,220 @@ pub async fn global_rate_limit_middleware(
         state.config.trust_proxy,
         &state.config.trusted_proxy_cidrs,
     );
-    let config = RateLimitConfig {
-        max_requests:   100,
-        window_seconds: 60,
-        key_prefix:     "global".to_string(),
-    };
+    let config = global_rate_limit_config();
     let pool = Arc::new(state.cache.redis_pool());
+    if let Some(remaining) = abuse_block_remaining(&pool, &ip).await {
+        state.metrics.observe_rate_limit_rejection("abuse_block");
+        return abuse_block_response(remaining);
+    }
     match check_rate_limit(&pool, &config, &ip).await {
         Ok(_) => next.run(req).await,
-        Err(retry_after) => rate_limit_response(retry_after, &config),
+        Err(retry_after) => {
+            state.metrics.observe_rate_limit_rejection("global");
+            let policy = AbusePolicy::from_config(&state.config);
+            match record_abuse(&pool, &policy, &ip).await {
+                Some(block_secs) => {
+                    state.metrics.observe_abuse_block("global");
+                    tracing::warn!(
+                        client_ip = %ip,
+                        block_secs,
+                        "client blocked after repeated 429s"
+                    );
+                    abuse_block_response(block_secs)
+                }
+                None => rate_limit_response(retry_after, &config),
+            }
+        }
     }
 }
 
-/// Redis-backed newsletter route rate limit middleware.
-/// Applied per-IP using the newsletter-specific limits from config.
-pub async fn newsletter_rate_limit_middleware(
-    State(state): State<Arc<crate::AppState>>,
+/// How repeated 429s from one IP escalate into a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbusePolicy {
+    /// Score at which the first block starts; 0 disables blocking.
+    pub threshold:         u64,
+    /// Seconds a 429 keeps counting towards the score.
+    pub score_window_secs: u64,
+    pub base_block_secs:   u64,
+    pub max_block_secs:    u64,
+}
+
+impl AbusePolicy {
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            threshold:         config.abuse_threshold,
+            score_window_secs: config.abuse_score_window_secs,
+            base_block_secs:   config.abuse_block_base_secs,
+            max_block_secs:    config.abuse_block_max_secs,
+        }
+    }
+
+    /// Block length for an IP whose score has just reached `score`: none
+    /// below the threshold, then the base length doubling per point above
+    /// it, capped at `max_block_secs`.
+    pub fn block_secs(&self, score: u64) -> Option<u64> {
+        if self.threshold == 0 || score < self.threshold {
+            return None;
+        }
+        let doublings = (score - self.threshold).min(63) as u32;
+        Some(
+            self.base_block_secs
+                .saturating_mul(1u64 << doublings)
+                .min(self.max_block_secs),
+        )
+    }
+}
+
+fn abuse_score_key(ip: &str) -> String {
+    format!("abuse:score:{ip}")
+}
+
+fn abuse_block_key(ip: &str) -> String {
+    format!("abuse:block:{ip}")
+}
+
+// KEYS[1] = abuse score key
+// ARGV[1] = score TTL (seconds), set when the score is first created
+// Returns the new score.
+const ABUSE_SCORE_SCRIPT: &str = r#"
+local score = redis.call('INCR', KEYS[1])
+if score == 1 then
+    redis.call('EXPIRE', KEYS[1], tonumber(ARGV[1]))
+end
+return score
+"#;
+
+/// Add a 429 to `ip`'s abuse score and, once it reaches the policy's
+/// threshold, (re)start its block. Returns the block length when one was
+/// set. Fails open: a Redis error never blocks anyone.
+pub async fn record_abuse(redis: &RedisPool, policy: &AbusePolicy, ip: &str) -> Option<u64> {
+    if policy.threshold == 0 {
+        return None;
+    }
+    let mut conn = redis.get().await.ok()?;
+    let score: u64 = deadpool_redis::redis::Script::new(ABUSE_SCORE_SCRIPT)
+        .key(abuse_score_key(ip))
+        .arg(policy.score_window_secs)
+        .invoke_async(&mut conn)
+        .await
+        .ok()?;
+    let block_secs = policy.block_secs(score)?;
+    deadpool_redis::redis::cmd("SET")
+        .arg(abuse_block_key(ip))
+        .arg(score)
+        .arg("EX")
+        .arg(block_secs)
+        .query_async::<_, ()>(&mut conn)
+        .await
+        .ok()?;
+    Some(block_secs)
+}
+
+/// Seconds left on `ip`'s block, or `None` when it is not blocked (or Redis
+/// is unreachable).
+pub async fn abuse_block_remaining(redis: &RedisPool, ip: &str) -> Option<u64> {
+    let mut conn = redis.get().await.ok()?;
+    let ttl: i64 = deadpool_redis::redis::cmd("TTL")
+        .arg(abuse_block_key(ip))
+        .query_async(&mut conn)
+        .await
+        .ok()?;
+    u64::try_from(ttl).ok().filter(|secs| *secs > 0)
+}
+
+/// The resource a public route names, as `(kind, id)`: the wallet address
+/// of `/blockchain/users/:user/bets` or the id of any `:market_id` route.
+/// Wallet addresses are upper-cased so case variants share one counter.
+pub fn resource_key<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<(&'static str, String)> {
+    params.into_iter().find_map(|(name, value)| match name {
+        "user" => Some(("wallet", value.to_ascii_uppercase())),
+        "market_id" => Some(("market", value.to_string())),
+        _ => None,
+    })
+}
+
+/// State for [`resource_rate_limit_middleware`]. Built from `AppState` in
+/// `main.rs`; kept separate so the limiter can be exercised against a bare
+/// Redis pool.
+#[derive(Clone)]
+pub struct ResourceRateLimitState {
+    pub redis:               Arc<RedisPool>,
+    pub config:              RateLimitConfig,
+    pub abuse:               AbusePolicy,
+    pub trust_proxy:         bool,
+    pub trusted_proxy_cidrs: Vec<ipnet::IpNet>,
+    pub metrics:             Option<crate::metrics::Metrics>,
+}
+
+impl ResourceRateLimitState {
+    pub fn from_app_state(state: &crate::AppState) -> Self {
+        Self {
+            redis:               Arc::new(state.cache.redis_pool()),
+            config:              RateLimitConfig {
+                max_requests:   state.config.resource_rate_limit_max,
+                window_seconds: state.config.resource_rate_limit_window_secs,
+                key_prefix:     "resource".to_string(),
+            },
+            abuse:               AbusePolicy::from_config(&state.config),
+            trust_proxy:         state.config.trust_proxy,
+            trusted_proxy_cidrs: state.config.trusted_proxy_cidrs.clone(),
+            metrics:             Some(state.metrics.clone()),
+        }
+    }
+}
+
+/// Redis-backed limit per wallet address or market id, summed over every
+/// client IP. Applied with `route_layer` so the matched path parameters are
+/// available; requests to routes without one pass straight through.
+pub async fn resource_rate_limit_middleware(
+    State(state): State<ResourceRateLimitState>,
     headers: HeaderMap,
+    connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
+    params: Option<RawPathParams>,
     req: axum::extract::Request,
     next: Next,
 ) -> Response {
-    let client_key = client_key_from_headers(&headers);
-    let config = RateLimitConfig {
-        max_requests:   state.config.newsletter_rate_limit_max as u64,
-        window_seconds: state.config.newsletter_rate_limit_window_secs,
-        key_prefix:     "newsletter".to_string(),
+    use crate::security::extract_client_ip_cidrs;
+    let Some((kind, id)) = params.as_ref().and_then(|p| resource_key(p.iter())) else {
+        return next.run(req).await;
     };
-    let pool = Arc::new(state.cache.redis_pool());
-    match check_rate_limit(&pool, &config, &client_key).await {
+    let client_key = format!("{kind}:{id}");
+    match check_rate_limit(&state.redis, &state.config, &client_key).await {
         Ok(_) => next.run(req).await,
-        Err(retry_after) => rate_limit_response(retry_after, &config),
+        Err(retry_after) => {
+            let ip = extract_client_ip_cidrs(
+                &headers,
+                connect_info.as_ref(),
+                state.trust_proxy,
+                &state.trusted_proxy_cidrs,
+            );
+            if let Some(m) = &state.metrics {
+                m.observe_rate_limit_rejection("resource");
+                m.observe_resource_rate_limit_rejection(kind);
+            }
+            tracing::warn!(
+                client_ip = %ip,
+                resource = %client_key,
+                retry_after,
+                "resource rate limit exceeded"
+            );
+            match record_abuse(&state.redis, &state.abuse, &ip).await {
+                Some(block_secs) => {
+                    if let Some(m) = &state.metrics {
+                        m.observe_abuse_block("resource");
+                    }
+                    tracing::warn!(
+                        client_ip = %ip,
+                        block_secs,
+                        "client blocked after repeated 429s"
+                    );
+                    abuse_block_response(block_secs)
+                }
+                None => rate_limit_response(retry_after, &state.config),
+            }
+        }
     }
 }
 
@@ -293,10 +470,17 @@ pub async fn newsletter_rate_limit_middleware(
 pub async fn admin_rate_limit_middleware(
     State(state): State<Arc<crate::AppState>>,
     headers: HeaderMap,
+    connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
     req: axum::extract::Request,
     next: Next,
 ) -> Response {
-    let client_key = client_key_from_headers(&headers);
+    use crate::security::extract_client_ip_cidrs;
+    let client_key = extract_client_ip_cidrs(
+        &headers,
+        connect_info.as_ref(),
+        state.config.trust_proxy,
+        &state.config.trusted_proxy_cidrs,
+    );
     let config = RateLimitConfig {
         max_requests:   60,
         window_seconds: 60,
@@ -309,40 +493,64 @@ pub async fn admin_rate_limit_middleware(
     }
 }
 
+/// Redis-backed per-API-key limit for the transaction relay routes.
+/// Runs after `api_key_middleware`, so the key is already known to be valid;
+/// only a hash of it is used in the Redis key name.
+pub async fn tx_rate_limit_middleware(
+    State(state): State<Arc<crate::AppState>>,
+    headers: HeaderMap,
+    req: axum::extract::Request,
+    next: Next,
+) -> Response {
+    use sha2::{Digest, Sha256};
+    let api_key = headers
+        .get("x-api-key")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let client_key = hex::encode(&Sha256::digest(api_key.as_bytes())[..16]);
+    let config = RateLimitConfig {
+        max_requests:   state.config.tx_rate_limit_max,
+        window_seconds: state.config.tx_rate_limit_window_secs,
+        key_prefix:     "tx".to_string(),
+    };
+    let pool = Arc::new(state.cache.redis_pool());
+    match check_rate_limit(&pool, &config, &client_key).await {
+        Ok(_) => next.run(req).await,
+        Err(retry_after) => rate_limit_response(retry_after, &config),
+    }
+}
+
 fn rate_limit_response(retry_after: u64, config: &RateLimitConfig) -> Response {
-    let body = RateLimitError {
-        error:   "rate_limit_exceeded",
-        message: format!(
+    too_many_requests(
+        format!(
             "Rate limit of {} requests per {}s exceeded. Retry after {} seconds.",
             config.max_requests, config.window_seconds, retry_after
         ),
         retry_after,
-    };
-    (
-        StatusCode::TOO_MANY_REQUESTS,
-        [("Retry-After", retry_after.to_string())],
-        Json(body),
     )
-        .into_response()
+}
+
+fn abuse_block_response(block_secs: u64) -> Response {
+    too_many_requests(
+        format!(
+            "Too many rate-limited requests from this client. Retry after {block_secs} seconds."
+        ),
+        block_secs,
+    )
+}
+
+/// A `429 RATE_LIMITED` envelope with `retry_after` in both the
+/// `Retry-After` header and `details`.
+fn too_many_requests(message: impl Into<String>, retry_after: u64) -> Response {
+    let mut error = ApiError::rate_limited().with_details(json!({ "retry_after": retry_after }));
+    error.message = message.into();
+    ([("Retry-After", retry_after.to_string())], error).into_response()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn client_key_from_x_forwarded_for_picks_first_ip() {
-        let mut headers = HeaderMap::new();
-        headers.insert("x-forwarded-for", "1.2.3.4, 5.6.7.8".parse().unwrap());
-        assert_eq!(client_key_from_headers(&headers), "1.2.3.4");
-    }
-
-    #[test]
-    fn client_key_falls_back_to_unknown() {
-        let headers = HeaderMap::new();
-        assert_eq!(client_key_from_headers(&headers), "unknown");
-    }
-
     #[test]
     fn config_defaults_are_sensible() {
         let cfg = RateLimitConfig::default();
@@ -358,7 +566,66 @@ mod tests {
                 .create_pool(Some(deadpool_redis::Runtime::Tokio1)).unwrap()),
             config:  RateLimitConfig::default(),
             metrics: None,
+            proxies: Default::default(),
         };
         assert!(state.metrics.is_none());
     }
+
+    #[tokio::test]
+    async fn rejection_uses_error_envelope_with_retry_after() {
+        use axum::http::StatusCode;
+        use http_body_util::BodyExt;
+
+        let response = rate_limit_response(17, &RateLimitConfig::default());
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "17");
+
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["code"], "RATE_LIMITED");
+        assert_eq!(body["details"]["retry_after"], 17);
+        assert!(body["message"].as_str().unwrap().contains("100 requests per 60s"));
+    }
+
+    fn policy() -> AbusePolicy {
+        AbusePolicy {
+            threshold:         3,
+            score_window_secs: 3600,
+            base_block_secs:   60,
+            max_block_secs:    600,
+        }
+    }
+
+    #[test]
+    fn abuse_block_starts_at_threshold_and_doubles_up_to_cap() {
+        let p = policy();
+        assert_eq!(p.block_secs(1), None);
+        assert_eq!(p.block_secs(2), None);
+        assert_eq!(p.block_secs(3), Some(60));
+        assert_eq!(p.block_secs(4), Some(120));
+        assert_eq!(p.block_secs(6), Some(480));
+        assert_eq!(p.block_secs(7), Some(600));
+        assert_eq!(p.block_secs(u64::MAX), Some(600));
+    }
+
+    #[test]
+    fn zero_abuse_threshold_never_blocks() {
+        let p = AbusePolicy { threshold: 0, ..policy() };
+        assert_eq!(p.block_secs(0), None);
+        assert_eq!(p.block_secs(1_000), None);
+    }
+
+    #[test]
+    fn resource_key_names_wallets_and_markets() {
+        assert_eq!(
+            resource_key([("user", "gabc")]),
+            Some(("wallet", "GABC".to_string()))
+        );
+        assert_eq!(
+            resource_key([("market_id", "42")]),
+            Some(("market", "42".to_string()))
+        );
+        assert_eq!(resource_key([("tx_hash", "ff")]), None);
+        assert_eq!(resource_key([]), None);
+    }
 }
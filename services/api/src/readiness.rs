@@ -0,0 +1,287 @@
+//! Readiness probe behind `GET /health/ready`.
+//!
+//! Postgres (`SELECT 1`), Redis (`PING`), and the cached blockchain health
+//! snapshot are checked concurrently, each under [`CHECK_TIMEOUT`] so a hung
+//! dependency cannot hang the probe. The instance is ready when Postgres and
+//! Redis are both reachable; the blockchain RPC is reported but not required,
+//! since reads fall back to cached data without it.
+//!
+//! The report also carries the restart history of supervised background
+//! tasks (see [`crate::supervisor`]). Restarts do not affect readiness; they
+//! are there so a crash-looping worker shows up next to the dependencies.
+//!
+//! Reports are cached for [`CACHE_TTL`]. Probes that arrive while a check is
+//! running wait for it instead of starting their own, so a probe storm costs
+//! the dependencies one check per TTL.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::{supervisor::TaskStatus, AppState};
+
+/// Upper bound on each dependency check.
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long a report is reused before the dependencies are checked again.
+pub const CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Redis round trips slower than this are reported as `degraded`.
+const REDIS_DEGRADED_MS: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Ok,
+    /// Reachable but slow or partially failing.
+    Degraded,
+    Unavailable,
+    /// The check timed out before producing an answer.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ComponentCheck {
+    pub status: ComponentStatus,
+    pub latency_ms: u64,
+    /// Why the check failed; absent when it passed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ComponentCheck {
+    fn ok(status: ComponentStatus, latency: Duration) -> Self {
+        Self {
+            status,
+            latency_ms: latency.as_millis() as u64,
+            error: None,
+        }
+    }
+
+    fn failed(status: ComponentStatus, latency: Duration, error: impl ToString) -> Self {
+        Self {
+            status,
+            latency_ms: latency.as_millis() as u64,
+            error: Some(error.to_string()),
+        }
+    }
+
+    fn is_up(&self) -> bool {
+        matches!(self.status, ComponentStatus::Ok | ComponentStatus::Degraded)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct BlockchainCheck {
+    #[serde(flatten)]
+    pub check: ComponentCheck,
+    pub latest_ledger: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ReadinessDependencies {
+    pub database: ComponentCheck,
+    pub redis: ComponentCheck,
+    pub blockchain_rpc: BlockchainCheck,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ReadinessReport {
+    /// Whether every required dependency is reachable.
+    pub ready: bool,
+    /// `ok`, `degraded` (ready, but something is slow or unhealthy), or
+    /// `unavailable`.
+    pub status: &'static str,
+    /// When the dependencies were last checked; up to [`CACHE_TTL`] old.
+    pub checked_at: DateTime<Utc>,
+    pub dependencies: ReadinessDependencies,
+    /// Restart history of supervised background tasks, by task name.
+    pub background_tasks: BTreeMap<String, TaskStatus>,
+}
+
+impl ReadinessReport {
+    fn new(database: ComponentCheck, redis: ComponentCheck, blockchain_rpc: BlockchainCheck) -> Self {
+        let ready = database.is_up() && redis.is_up();
+        let degraded = [&database, &redis, &blockchain_rpc.check]
+            .iter()
+            .any(|c| c.status != ComponentStatus::Ok);
+        let status = match (ready, degraded) {
+            (false, _) => "unavailable",
+            (true, true) => "degraded",
+            (true, false) => "ok",
+        };
+        Self {
+            ready,
+            status,
+            checked_at: Utc::now(),
+            dependencies: ReadinessDependencies { database, redis, blockchain_rpc },
+            background_tasks: BTreeMap::new(),
+        }
+    }
+}
+
+/// The last readiness report, shared across requests.
+#[derive(Clone, Default)]
+pub struct ReadinessCache {
+    last: Arc<Mutex<Option<(Instant, ReadinessReport)>>>,
+}
+
+impl ReadinessCache {
+    /// The cached report if it is younger than `ttl`, otherwise the result of
+    /// `check`. The lock is held across `check`, which is what makes
+    /// concurrent callers share one run.
+    pub async fn get_or_check<F, Fut>(&self, ttl: Duration, check: F) -> ReadinessReport
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ReadinessReport>,
+    {
+        let mut last = self.last.lock().await;
+        if let Some((at, report)) = last.as_ref() {
+            if at.elapsed() < ttl {
+                return report.clone();
+            }
+        }
+        let report = check().await;
+        *last = Some((Instant::now(), report.clone()));
+        report
+    }
+}
+
+/// Check every dependency now, bypassing the cache.
+pub async fn check(state: &AppState) -> ReadinessReport {
+    let (database, redis, blockchain_rpc) =
+        tokio::join!(check_database(state), check_redis(state), check_blockchain(state));
+    ReadinessReport {
+        background_tasks: state.tasks.snapshot(),
+        ..ReadinessReport::new(database, redis, blockchain_rpc)
+    }
+}
+
+async fn check_database(state: &AppState) -> ComponentCheck {
+    let start = Instant::now();
+    match tokio::time::timeout(CHECK_TIMEOUT, state.db.ping()).await {
+        Ok(Ok(())) => ComponentCheck::ok(ComponentStatus::Ok, start.elapsed()),
+        Ok(Err(e)) => ComponentCheck::failed(ComponentStatus::Unavailable, start.elapsed(), format!("{e:#}")),
+        Err(_) => ComponentCheck::failed(ComponentStatus::Unavailable, start.elapsed(), "timed out"),
+    }
+}
+
+async fn check_redis(state: &AppState) -> ComponentCheck {
+    let start = Instant::now();
+    // Direct PING, bypassing the circuit breaker, so an open breaker does not
+    // hide a recovered Redis.
+    match tokio::time::timeout(CHECK_TIMEOUT, state.cache.ping_direct_ms()).await {
+        Ok(Ok(ms)) if ms as u64 > REDIS_DEGRADED_MS => {
+            ComponentCheck::ok(ComponentStatus::Degraded, start.elapsed())
+        }
+        Ok(Ok(_)) => ComponentCheck::ok(ComponentStatus::Ok, start.elapsed()),
+        Ok(Err(e)) => ComponentCheck::failed(ComponentStatus::Unavailable, start.elapsed(), format!("{e:#}")),
+        Err(_) => ComponentCheck::failed(ComponentStatus::Unavailable, start.elapsed(), "timed out"),
+    }
+}
+
+async fn check_blockchain(state: &AppState) -> BlockchainCheck {
+    let start = Instant::now();
+    let result = tokio::time::timeout(
+        CHECK_TIMEOUT,
+        state.networks.primary().health_check_cached(),
+    )
+    .await;
+    match result {
+        Ok(Ok(health)) => BlockchainCheck {
+            check: ComponentCheck::ok(
+                if health.is_healthy {
+                    ComponentStatus::Ok
+                } else {
+                    ComponentStatus::Degraded
+                },
+                start.elapsed(),
+            ),
+            latest_ledger: Some(health.latest_ledger),
+        },
+        Ok(Err(e)) => BlockchainCheck {
+            check: ComponentCheck::failed(ComponentStatus::Unavailable, start.elapsed(), format!("{e:#}")),
+            latest_ledger: None,
+        },
+        Err(_) => BlockchainCheck {
+            check: ComponentCheck::failed(ComponentStatus::Unknown, start.elapsed(), "timed out"),
+            latest_ledger: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn up() -> ComponentCheck {
+        ComponentCheck::ok(ComponentStatus::Ok, Duration::ZERO)
+    }
+
+    fn down() -> ComponentCheck {
+        ComponentCheck::failed(ComponentStatus::Unavailable, Duration::ZERO, "connection refused")
+    }
+
+    fn rpc(status: ComponentStatus) -> BlockchainCheck {
+        BlockchainCheck {
+            check: ComponentCheck { status, latency_ms: 0, error: None },
+            latest_ledger: None,
+        }
+    }
+
+    #[test]
+    fn ready_requires_database_and_redis() {
+        assert!(ReadinessReport::new(up(), up(), rpc(ComponentStatus::Ok)).ready);
+        assert!(!ReadinessReport::new(down(), up(), rpc(ComponentStatus::Ok)).ready);
+        let report = ReadinessReport::new(up(), down(), rpc(ComponentStatus::Ok));
+        assert!(!report.ready);
+        assert_eq!(report.status, "unavailable");
+    }
+
+    #[test]
+    fn blockchain_trouble_degrades_but_stays_ready() {
+        let report = ReadinessReport::new(up(), up(), rpc(ComponentStatus::Unknown));
+        assert!(report.ready);
+        assert_eq!(report.status, "degraded");
+    }
+
+    #[test]
+    fn report_serializes_component_breakdown() {
+        let json = serde_json::to_value(ReadinessReport::new(up(), down(), rpc(ComponentStatus::Ok))).unwrap();
+        assert_eq!(json["ready"], false);
+        assert_eq!(json["dependencies"]["redis"]["status"], "unavailable");
+        assert_eq!(json["dependencies"]["redis"]["error"], "connection refused");
+        assert!(json["dependencies"]["database"].get("error").is_none());
+        assert_eq!(json["dependencies"]["blockchain_rpc"]["status"], "ok");
+    }
+
+    #[tokio::test]
+    async fn cache_reuses_report_within_ttl_and_coalesces_callers() {
+        let cache = ReadinessCache::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let check = || {
+            let runs = runs.clone();
+            || async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                ReadinessReport::new(up(), up(), rpc(ComponentStatus::Ok))
+            }
+        };
+
+        let ttl = Duration::from_secs(60);
+        let reports = futures::future::join_all((0..10).map(|_| cache.get_or_check(ttl, check()))).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(reports.iter().all(|r| r.checked_at == reports[0].checked_at));
+
+        cache.get_or_check(Duration::ZERO, check()).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
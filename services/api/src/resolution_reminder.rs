@@ -0,0 +1,286 @@
+//! Resolution deadline reminders for market creators.
+//!
+//! Once a market's betting deadline (`ends_at`) has passed, its creator has
+//! `RESOLUTION_WINDOW_HOURS` to get the oracle result in before the
+//! resolution deadline lapses. Every [`CHECK_INTERVAL`] the supervised
+//! [`run`] loop looks for markets still awaiting resolution whose deadline
+//! falls within one of the configured lead times
+//! (`RESOLUTION_REMINDER_LEAD_HOURS`, 48h and 6h by default) and enqueues a
+//! `resolution_reminder` email to the creator's verified notification email.
+//!
+//! A market gets at most one reminder per lead-time bucket: each (market,
+//! lead time) pair is claimed in `sent_reminders` before its job is
+//! enqueued (see [`Database::resolution_reminder_claim`]). The claim is only
+//! taken while the market is still unresolved, and the queue worker checks
+//! again with [`still_due`] before sending, so a market resolved or
+//! cancelled after it was picked up is skipped rather than reminded.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::Config,
+    db::Database,
+    email::{queue::EmailQueue, types::EmailJobType},
+    AppState,
+};
+
+/// Email template the reminder is rendered with.
+pub const TEMPLATE: &str = "resolution_reminder";
+
+/// Lead times used when `RESOLUTION_REMINDER_LEAD_HOURS` is unset.
+pub const DEFAULT_LEAD_HOURS: &[u32] = &[48, 6];
+
+/// How often due reminders are looked for. Well below the shortest useful
+/// lead time, so a bucket is never skipped over.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+const WORKER_NAME: &str = "resolution_reminder";
+
+/// An unresolved market past its betting deadline whose creator has a
+/// verified email.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderCandidate {
+    pub market_id: i64,
+    pub title: String,
+    pub email: String,
+    pub resolution_deadline: DateTime<Utc>,
+}
+
+/// A reminder due now, in the bucket of `lead_hours`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DueReminder {
+    pub candidate: ReminderCandidate,
+    pub lead_hours: u32,
+}
+
+/// Parse comma-separated lead times in hours, longest first. Zero and
+/// unparsable entries are skipped.
+pub fn parse_lead_hours(raw: &str) -> Vec<u32> {
+    let mut hours: Vec<u32> = raw
+        .split(',')
+        .filter_map(|h| h.trim().parse().ok())
+        .filter(|&h| h > 0)
+        .collect();
+    hours.sort_unstable_by(|a, b| b.cmp(a));
+    hours.dedup();
+    hours
+}
+
+/// The tightest lead time that `deadline` is within at `now`, or `None`
+/// when it is further off than every lead time or already past. A market
+/// first seen 5h before its deadline is reminded in the 6h bucket only.
+pub fn lead_bucket(lead_hours: &[u32], deadline: DateTime<Utc>, now: DateTime<Utc>) -> Option<u32> {
+    if deadline <= now {
+        return None;
+    }
+    let remaining = deadline - now;
+    lead_hours
+        .iter()
+        .copied()
+        .filter(|&h| remaining <= chrono::Duration::hours(i64::from(h)))
+        .min()
+}
+
+/// Template data for one reminder. `event_id` scopes the queue's
+/// idempotency key to the (market, lead time) pair.
+pub fn template_data(base_url: &str, reminder: &DueReminder) -> Value {
+    let candidate = &reminder.candidate;
+    json!({
+        "market_id": candidate.market_id,
+        "market_title": candidate.title,
+        "market_url": format!("{}/markets/{}", base_url.trim_end_matches('/'), candidate.market_id),
+        "lead_hours": reminder.lead_hours,
+        "deadline": candidate.resolution_deadline.format("%-d %B %Y, %H:%M UTC").to_string(),
+        "event_id": format!("resolution-reminder-{}-{}h", candidate.market_id, reminder.lead_hours),
+    })
+}
+
+/// Reminders due at `now`: candidates whose resolution deadline is within
+/// the longest lead time, each placed in its bucket. Buckets already sent
+/// are still listed; [`send_reminders`] skips them.
+pub async fn due_reminders(
+    db: &Database,
+    window: Duration,
+    lead_hours: &[u32],
+    now: DateTime<Utc>,
+) -> anyhow::Result<Vec<DueReminder>> {
+    let Some(&longest) = lead_hours.iter().max() else {
+        return Ok(Vec::new());
+    };
+    let horizon = Duration::from_secs(u64::from(longest) * 3600);
+    let candidates = db
+        .resolution_reminder_candidates(window, horizon, now)
+        .await?;
+    Ok(candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            lead_bucket(lead_hours, candidate.resolution_deadline, now).map(|lead_hours| {
+                DueReminder {
+                    candidate,
+                    lead_hours,
+                }
+            })
+        })
+        .collect())
+}
+
+/// Claim and enqueue each reminder, returning how many were enqueued. A
+/// reminder whose bucket was already sent, or whose market has been
+/// resolved or cancelled since it was listed, is not claimed. A claim whose
+/// enqueue fails is released so the next pass retries it.
+pub async fn send_reminders(
+    db: &Database,
+    queue: &EmailQueue,
+    base_url: &str,
+    reminders: &[DueReminder],
+) -> anyhow::Result<usize> {
+    let mut enqueued = 0;
+    for reminder in reminders {
+        let candidate = &reminder.candidate;
+        if !db
+            .resolution_reminder_claim(candidate.market_id, reminder.lead_hours)
+            .await?
+        {
+            continue;
+        }
+        let data = template_data(base_url, reminder);
+        match queue
+            .enqueue(
+                EmailJobType::ResolutionReminder,
+                &candidate.email,
+                TEMPLATE,
+                data,
+                0,
+            )
+            .await
+        {
+            Ok(job_id) => {
+                enqueued += 1;
+                if let Err(e) = db
+                    .resolution_reminder_set_job(candidate.market_id, reminder.lead_hours, job_id)
+                    .await
+                {
+                    tracing::warn!(market_id = candidate.market_id, error = %e, "failed to record reminder job");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    market_id = candidate.market_id,
+                    lead_hours = reminder.lead_hours,
+                    error = %e,
+                    "failed to enqueue resolution reminder"
+                );
+                db.resolution_reminder_release(candidate.market_id, reminder.lead_hours)
+                    .await?;
+            }
+        }
+    }
+    Ok(enqueued)
+}
+
+/// Enqueue every reminder due at `now` under `config`.
+pub async fn remind_due(
+    db: &Database,
+    queue: &EmailQueue,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> anyhow::Result<usize> {
+    let reminders = due_reminders(
+        db,
+        config.resolution_window,
+        &config.resolution_reminder_lead_hours,
+        now,
+    )
+    .await?;
+    send_reminders(db, queue, &config.base_url, &reminders).await
+}
+
+/// Whether the market a queued reminder is about still awaits resolution.
+/// Called by the queue worker right before sending.
+pub async fn still_due(db: &Database, template_data: &Value) -> anyhow::Result<bool> {
+    match template_data.get("market_id").and_then(Value::as_i64) {
+        Some(market_id) => db.market_awaiting_resolution(market_id).await,
+        None => Ok(false),
+    }
+}
+
+/// Enqueue due reminders every [`CHECK_INTERVAL`] until `shutdown` fires.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    loop {
+        match remind_due(&state.db, &state.email_queue, &state.config, Utc::now()).await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!(enqueued = n, "[resolution-reminder] reminders enqueued"),
+            Err(e) => tracing::warn!("[resolution-reminder] pass failed: {e}"),
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(CHECK_INTERVAL) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 16, h, 0, 0).unwrap()
+    }
+
+    fn deadline() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn lead_hours_are_parsed_longest_first() {
+        assert_eq!(parse_lead_hours("6, 48,,x,0,6"), vec![48, 6]);
+        assert_eq!(parse_lead_hours(""), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn deadlines_fall_into_the_tightest_bucket() {
+        let leads = [48, 6];
+        // 52h out: not yet.
+        assert_eq!(lead_bucket(&leads, deadline(), at(8)), None);
+        // Exactly 48h out, then 30h out.
+        assert_eq!(lead_bucket(&leads, deadline(), at(12)), Some(48));
+        assert_eq!(
+            lead_bucket(&leads, deadline(), at(12) + chrono::Duration::hours(18)),
+            Some(48)
+        );
+        // 5h out skips straight to the 6h bucket.
+        assert_eq!(
+            lead_bucket(&leads, deadline(), deadline() - chrono::Duration::hours(5)),
+            Some(6)
+        );
+        // Lapsed deadlines are not reminded.
+        assert_eq!(lead_bucket(&leads, deadline(), deadline()), None);
+        assert_eq!(lead_bucket(&[], deadline(), at(12)), None);
+    }
+
+    #[test]
+    fn template_data_scopes_the_idempotency_key_per_bucket() {
+        let reminder = DueReminder {
+            candidate: ReminderCandidate {
+                market_id: 7,
+                title: "Will it rain?".to_string(),
+                email: "creator@example.com".to_string(),
+                resolution_deadline: deadline(),
+            },
+            lead_hours: 6,
+        };
+        let data = template_data("https://predictiq.io/", &reminder);
+        assert_eq!(data["market_url"], "https://predictiq.io/markets/7");
+        assert_eq!(data["deadline"], "18 October 2026, 12:00 UTC");
+        assert_eq!(data["lead_hours"], 6);
+        assert_eq!(data["event_id"], "resolution-reminder-7-6h");
+        assert!(!data.to_string().contains("creator@example.com"));
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod resolution_reminder_tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+
+    use crate::{
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        email::queue::EmailQueue,
+        metrics::Metrics,
+        resolution_reminder::{self, DueReminder},
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range, and the creator and its
+    /// email are used nowhere else, so cleanup only touches rows created here.
+    const SEED_MARKETS: [i64; 3] = [9801, 9802, 9803];
+    const CREATOR: &str = "GCRESREMINDERTESTXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+    const EMAIL: &str = "resolution-reminder-test@example.com";
+    const EVENT_PREFIX: &str = "resolution-reminder-test-";
+
+    const WINDOW: Duration = Duration::from_secs(72 * 3600);
+    const LEAD_HOURS: [u32; 2] = [48, 6];
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    /// A verified creator and, for each id, an active market whose betting
+    /// closed 42h before `now`, putting its resolution deadline 30h out.
+    async fn seed(db: &Database, market_ids: &[i64], now: DateTime<Utc>) {
+        cleanup(db).await;
+        sqlx::query(
+            "INSERT INTO user_notification_settings (address, email, email_verified_at, unsubscribe_token) \
+             VALUES ($1, $2, NOW(), $3)",
+        )
+        .bind(CREATOR)
+        .bind(EMAIL)
+        .bind(crate::user_notifications::new_token())
+        .execute(&db.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, creator, ends_at) \
+             SELECT id, 'Reminder test ' || id, 'active', $2, $3 - INTERVAL '42 hours' \
+             FROM UNNEST($1::BIGINT[]) AS id",
+        )
+        .bind(market_ids)
+        .bind(CREATOR)
+        .bind(now)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(db: &Database) {
+        sqlx::query("DELETE FROM email_jobs WHERE recipient_email = $1")
+            .bind(EMAIL)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(EVENT_PREFIX)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        // sent_reminders rows go with their market.
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM user_notification_settings WHERE address = $1")
+            .bind(CREATOR)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    /// Reminders due at `now` for seeded markets only.
+    async fn due(db: &Database, now: DateTime<Utc>) -> Vec<DueReminder> {
+        resolution_reminder::due_reminders(db, WINDOW, &LEAD_HOURS, now)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| SEED_MARKETS.contains(&r.candidate.market_id))
+            .collect()
+    }
+
+    async fn send(db: &Database, reminders: &[DueReminder]) -> usize {
+        let queue = EmailQueue::new(db.clone());
+        resolution_reminder::send_reminders(db, &queue, "https://predictiq.test", reminders)
+            .await
+            .unwrap()
+    }
+
+    async fn sent_buckets(db: &Database, market_id: i64) -> Vec<i32> {
+        sqlx::query_scalar(
+            "SELECT lead_hours FROM sent_reminders \
+             WHERE market_id = $1 AND email_job_id IS NOT NULL \
+             ORDER BY lead_hours",
+        )
+        .bind(market_id)
+        .fetch_all(&db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn reminder_jobs(db: &Database) -> i64 {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM email_jobs \
+             WHERE recipient_email = $1 AND template_name = $2",
+        )
+        .bind(EMAIL)
+        .bind(resolution_reminder::TEMPLATE)
+        .fetch_one(&db.pool())
+        .await
+        .unwrap()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Each lead-time bucket is reminded once however many passes see it;
+    /// the 6h bucket still sends after the 48h one.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_each_bucket_is_reminded_once() {
+        let db = build_db().await;
+        let now = Utc::now();
+        seed(&db, &[9801], now).await;
+
+        let first = due(&db, now).await;
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].lead_hours, 48);
+        assert_eq!(first[0].candidate.email, EMAIL);
+        assert_eq!(send(&db, &first).await, 1);
+        assert_eq!(
+            send(&db, &first).await,
+            0,
+            "a claimed bucket is not sent again"
+        );
+        assert_eq!(
+            send(&db, &due(&db, now + chrono::Duration::hours(1)).await).await,
+            0
+        );
+
+        // 5h before the deadline the 6h bucket is due.
+        let later = due(&db, now + chrono::Duration::hours(25)).await;
+        assert_eq!(later.len(), 1);
+        assert_eq!(later[0].lead_hours, 6);
+        assert_eq!(send(&db, &later).await, 1);
+        assert_eq!(send(&db, &later).await, 0);
+
+        assert_eq!(sent_buckets(&db, 9801).await, vec![6, 48]);
+        assert_eq!(reminder_jobs(&db).await, 2);
+
+        cleanup(&db).await;
+    }
+
+    /// Markets resolved in the table or cancelled by an indexed event after
+    /// the pass listed them are not claimed, and a queued reminder whose
+    /// market resolves before it is sent is no longer due.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_markets_resolved_before_send_are_skipped() {
+        let db = build_db().await;
+        let now = Utc::now();
+        seed(&db, &SEED_MARKETS, now).await;
+
+        let listed = due(&db, now).await;
+        assert_eq!(listed.len(), 3);
+        let (pending, raced): (Vec<_>, Vec<_>) = listed
+            .into_iter()
+            .partition(|r| r.candidate.market_id == 9801);
+
+        // Between listing and claiming: 9802 resolves, 9803 is cancelled.
+        sqlx::query("UPDATE markets SET status = 'resolved', resolved_at = NOW() WHERE id = 9802")
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id) \
+             VALUES ($1 || '1', 'resolution-reminder-test', 1, 'mkt_cncl', 9803)",
+        )
+        .bind(EVENT_PREFIX)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+
+        assert_eq!(send(&db, &raced).await, 0);
+        assert!(sent_buckets(&db, 9802).await.is_empty());
+        assert!(sent_buckets(&db, 9803).await.is_empty());
+        assert_eq!(reminder_jobs(&db).await, 0);
+        assert_eq!(due(&db, now).await.len(), 1, "only 9801 is still listed");
+
+        // Between enqueueing and sending: the worker's check turns false.
+        assert_eq!(send(&db, &pending).await, 1);
+        let data = resolution_reminder::template_data("https://predictiq.test", &pending[0]);
+        assert!(resolution_reminder::still_due(&db, &data).await.unwrap());
+        sqlx::query("UPDATE markets SET status = 'resolved', resolved_at = NOW() WHERE id = 9801")
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        assert!(!resolution_reminder::still_due(&db, &data).await.unwrap());
+
+        cleanup(&db).await;
+    }
+}
@@ -4,18 +4,27 @@ mod resolve_market_tests {
         body::Body,
         http::{Request, StatusCode},
         routing::post,
-        Router,
+        Json, Router,
     };
-    use serde_json::json;
-    use std::sync::Arc;
+    use secrecy::SecretString;
+    use serde_json::{json, Value};
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::Mutex;
     use tower::ServiceExt;
 
-    use crate::handlers::{resolve_market, InvalidationResult};
+    use crate::{
+        blockchain::PendingOverride,
+        cache::keys,
+        handlers::{resolve_market, InvalidationResult, ResolveMarketResult},
+    };
 
     // ---------------------------------------------------------------------------
     // Helpers
     // ---------------------------------------------------------------------------
 
+    const ADMIN_SEED: &str = "SAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSBF5K";
+    const CONTRACT_ID: &str = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+
     /// Build a minimal router wired to `resolve_market` for handler-level tests.
     fn app(state: Arc<crate::AppState>) -> Router {
         Router::new()
@@ -26,9 +35,9 @@ mod resolve_market_tests {
     async fn post_resolve(
         router: Router,
         market_id: i64,
-        outcome_index: u32,
+        winning_outcome: u32,
     ) -> axum::response::Response {
-        let body = serde_json::to_vec(&json!({ "outcome_index": outcome_index })).unwrap();
+        let body = serde_json::to_vec(&json!({ "winning_outcome": winning_outcome })).unwrap();
         router
             .oneshot(
                 Request::builder()
@@ -42,82 +51,384 @@ mod resolve_market_tests {
             .unwrap()
     }
 
-    // ---------------------------------------------------------------------------
-    // Unit tests — no real DB/Redis required
-    // ---------------------------------------------------------------------------
+    /// Serve `responses` in order as JSON-RPC replies.
+    async fn start_mock_rpc(responses: Vec<Value>) -> String {
+        let queue = Arc::new(Mutex::new(responses));
+        let app = Router::new().route(
+            "/",
+            post(move |Json(_body): Json<Value>| {
+                let queue = queue.clone();
+                async move {
+                    let mut q = queue.lock().await;
+                    let resp = if q.is_empty() {
+                        json!({ "result": { "status": "NOT_FOUND" } })
+                    } else {
+                        q.remove(0)
+                    };
+                    Json(resp)
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
 
-    /// Verifies that a successful DB write returns 200 and a non-zero
-    /// `invalidated_keys` count.
-    #[tokio::test]
-    #[ignore] // Requires PostgreSQL + Redis
-    async fn test_resolve_market_success_returns_200_and_invalidation_count() {
-        let state = build_test_state().await;
-        // Insert a test market first so the UPDATE finds a row.
+    fn admin_public_key() -> [u8; 32] {
+        crate::signer::TxSigner::from_secret(&SecretString::new(ADMIN_SEED.to_string()))
+            .unwrap()
+            .public_key()
+    }
+
+    /// RPC replies up to and including an accepted submission of `feed01`.
+    fn submitted() -> Vec<Value> {
+        use stellar_xdr::curr::{self as xdr, WriteXdr};
+        let account = xdr::LedgerEntryData::Account(xdr::AccountEntry {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+                admin_public_key(),
+            ))),
+            balance: 100_000_000,
+            seq_num: xdr::SequenceNumber(41),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: xdr::String32::default(),
+            thresholds: xdr::Thresholds([1, 0, 0, 0]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        });
+        let data = xdr::SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: xdr::VecM::default(),
+                },
+                instructions: 1_000,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        };
+        vec![
+            json!({ "result": {
+                "entries": [{ "xdr": account.to_xdr_base64(xdr::Limits::none()).unwrap() }],
+                "latestLedger": 900
+            } }),
+            json!({ "result": {
+                "latestLedger": 900,
+                "minResourceFee": "5000",
+                "transactionData": data.to_xdr_base64(xdr::Limits::none()).unwrap(),
+                "results": [{ "xdr": "AAAAAQ==", "auth": [] }]
+            } }),
+            json!({ "result": { "hash": "feed01", "status": "PENDING", "latestLedger": 900 } }),
+        ]
+    }
+
+    fn tx_status(status: &str) -> Value {
+        json!({ "result": { "status": status, "ledger": 902 } })
+    }
+
+    /// RPC replies to one chain market read as a node sees the market. A
+    /// read makes two calls (latest ledger, then the entry) and the reply
+    /// answers either, so concurrent reads may take them in any order.
+    fn market_read(status: &str, resolved_outcome: Option<u32>) -> [Value; 2] {
+        let reply = json!({ "result": {
+            "latestLedger": { "sequence": 903 },
+            "status": status,
+            "onchain_volume": "0",
+            "resolved_outcome": resolved_outcome
+        } });
+        [reply.clone(), reply]
+    }
+
+    async fn pending_override(state: &crate::AppState, market_id: i64) -> Option<PendingOverride> {
+        state
+            .cache
+            .get_json(&keys::pending_override_market(market_id))
+            .await
+            .unwrap()
+    }
+
+    /// Drop the chain entry, as its TTL would, so the next read asks the node.
+    async fn expire_chain_entry(state: &crate::AppState, market_id: i64) {
+        let key = keys::chain_market(state.networks.primary().network(), market_id);
+        state.cache.del(&key).await.unwrap();
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64) {
         sqlx::query(
             "INSERT INTO markets (id, title, status, total_volume, ends_at) \
-             VALUES (9001, 'Test Market', 'active', 0, NOW() + INTERVAL '1 day')",
+             VALUES ($1, 'Test Market', 'active', 0, NOW() + INTERVAL '1 day')",
         )
-        .execute(state.db.pool())
+        .bind(market_id)
+        .execute(&state.db.pool())
         .await
         .unwrap();
+        state
+            .cache
+            .set_json(
+                &format!("api:v1:market_detail:{market_id}"),
+                &json!({ "stale": true }),
+                Duration::from_secs(300),
+            )
+            .await
+            .unwrap();
+    }
 
-        let response = post_resolve(app(Arc::clone(&state)), 9001, 0).await;
-        assert_eq!(response.status(), StatusCode::OK);
+    async fn market_status(state: &crate::AppState, market_id: i64) -> String {
+        sqlx::query_scalar("SELECT status FROM markets WHERE id = $1")
+            .bind(market_id)
+            .fetch_one(&state.db.pool())
+            .await
+            .unwrap()
+    }
 
-        let body: InvalidationResult =
-            serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap())
-                .unwrap();
-        assert!(body.invalidated_keys > 0);
+    async fn detail_cached(state: &crate::AppState, market_id: i64) -> bool {
+        state
+            .cache
+            .get_json::<Value>(&format!("api:v1:market_detail:{market_id}"))
+            .await
+            .unwrap()
+            .is_some()
+    }
 
-        // Cleanup
-        sqlx::query("DELETE FROM markets WHERE id = 9001")
-            .execute(state.db.pool())
+    async fn cleanup(state: &crate::AppState, market_id: i64) {
+        sqlx::query("DELETE FROM markets WHERE id = $1")
+            .bind(market_id)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        state
+            .cache
+            .del(&keys::pending_override_market(market_id))
             .await
             .unwrap();
+        expire_chain_entry(state, market_id).await;
     }
 
-    /// Verifies that resolving a non-existent market returns 500.
+    async fn body_json(response: axum::response::Response) -> Value {
+        serde_json::from_slice(&axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap())
+            .unwrap()
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis; the RPC node is mocked
+    // ---------------------------------------------------------------------------
+
+    /// The DB row and caches change only after the node reports SUCCESS, and
+    /// the response carries the transaction hash.
     #[tokio::test]
     #[ignore] // Requires PostgreSQL + Redis
-    async fn test_resolve_market_not_found_returns_500() {
-        let state = build_test_state().await;
-        let response = post_resolve(app(Arc::clone(&state)), 999_999_999, 0).await;
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    async fn test_resolve_market_success_invalidates_after_confirmation() {
+        let mut rpc = submitted();
+        rpc.extend([tx_status("NOT_FOUND"), tx_status("SUCCESS")]);
+        let state = build_state(Some(start_mock_rpc(rpc).await)).await;
+        seed_market(&state, 9001).await;
+
+        let response = post_resolve(app(Arc::clone(&state)), 9001, 0).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: ResolveMarketResult = serde_json::from_value(body_json(response).await).unwrap();
+        assert_eq!(body.tx_hash, "feed01");
+        assert_eq!(body.ledger, Some(902));
+        assert!(body.invalidated_keys > 0);
+
+        assert_eq!(market_status(&state, 9001).await, "resolved");
+        assert!(!detail_cached(&state, 9001).await);
+
+        cleanup(&state, 9001).await;
     }
 
-    /// Verifies that resolving an already-resolved market returns 500.
+    /// A transaction that fails on-chain leaves the database and cache alone.
     #[tokio::test]
     #[ignore] // Requires PostgreSQL + Redis
-    async fn test_resolve_market_already_resolved_returns_500() {
-        let state = build_test_state().await;
-        sqlx::query(
-            "INSERT INTO markets (id, title, status, outcome_index, total_volume, ends_at, resolved_at) \
-             VALUES (9002, 'Resolved Market', 'resolved', 1, 0, NOW() - INTERVAL '1 day', NOW())",
-        )
-        .execute(state.db.pool())
-        .await
-        .unwrap();
+    async fn test_resolve_market_failed_tx_does_not_invalidate() {
+        let mut rpc = submitted();
+        rpc.push(tx_status("FAILED"));
+        let state = build_state(Some(start_mock_rpc(rpc).await)).await;
+        seed_market(&state, 9002).await;
 
         let response = post_resolve(app(Arc::clone(&state)), 9002, 0).await;
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body_json(response).await["code"], "SUBMISSION_REJECTED");
+
+        assert_eq!(market_status(&state, 9002).await, "active");
+        assert!(detail_cached(&state, 9002).await);
+
+        cleanup(&state, 9002).await;
+    }
+
+    /// Still pending at the deadline → 504 with the hash; nothing invalidated.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_resolve_market_timeout_does_not_invalidate() {
+        let state = build_state(Some(start_mock_rpc(submitted()).await)).await;
+        seed_market(&state, 9003).await;
+
+        let response = post_resolve(app(Arc::clone(&state)), 9003, 0).await;
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "CONFIRMATION_TIMEOUT");
+        assert!(body["message"].as_str().unwrap().contains("feed01"));
+
+        assert_eq!(market_status(&state, 9003).await, "active");
+        assert!(detail_cached(&state, 9003).await);
 
-        // Cleanup
-        sqlx::query("DELETE FROM markets WHERE id = 9002")
-            .execute(state.db.pool())
+        cleanup(&state, 9003).await;
+    }
+
+    /// Reads from a node that has not seen the resolution yet are answered
+    /// with the resolved state, cached or not; once a read shows it, the
+    /// override is dropped and the chain is served as is.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_lagging_rpc_is_overridden_until_chain_confirms() {
+        let mut rpc = submitted();
+        rpc.push(tx_status("SUCCESS"));
+        rpc.extend(market_read("active", None));
+        rpc.extend(market_read("resolved", Some(1)));
+        let state = build_state(Some(start_mock_rpc(rpc).await)).await;
+        let chain = state.networks.primary();
+        let network = chain.network().to_string();
+        cleanup(&state, 9004).await;
+        seed_market(&state, 9004).await;
+
+        let response = post_resolve(app(Arc::clone(&state)), 9004, 1).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let pending = pending_override(&state, 9004)
+            .await
+            .expect("override written");
+        assert_eq!(
+            (pending.status.as_str(), pending.resolved_outcome),
+            ("resolved", 1)
+        );
+        assert_eq!(pending.tx_hash, "feed01");
+
+        // The node still says active: once fetched, once from the cache.
+        let hits = state.metrics.pending_override_hit_count(&network);
+        for _ in 0..2 {
+            let data = chain.market_data_cached(9004).await.unwrap();
+            assert_eq!(data.status.as_deref(), Some("resolved"));
+            assert_eq!(data.resolved_outcome, Some(1));
+        }
+        assert_eq!(state.metrics.pending_override_hit_count(&network), hits + 2);
+
+        // The stale entry expires and the node has caught up.
+        expire_chain_entry(&state, 9004).await;
+        let data = chain.market_data_cached(9004).await.unwrap();
+        assert_eq!(data.status.as_deref(), Some("resolved"));
+        assert_eq!(data.resolved_outcome, Some(1));
+        assert!(pending_override(&state, 9004).await.is_none());
+        assert_eq!(state.metrics.pending_override_hit_count(&network), hits + 2);
+
+        cleanup(&state, 9004).await;
+    }
+
+    /// Batched reads layer the override too, an override for another
+    /// network is ignored, and an expired one no longer masks the chain.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_pending_override_covers_batched_reads_until_it_expires() {
+        let mut rpc = Vec::new();
+        for _ in 0..3 {
+            rpc.extend(market_read("active", None));
+        }
+        let state = build_state(Some(start_mock_rpc(rpc).await)).await;
+        let chain = state.networks.primary();
+        for market_id in [9005, 9006] {
+            cleanup(&state, market_id).await;
+        }
+        let pending = PendingOverride {
+            network: chain.network().to_string(),
+            status: "resolved".to_string(),
+            resolved_outcome: 0,
+            tx_hash: "feed02".to_string(),
+        };
+        let elsewhere = PendingOverride {
+            network: format!("{}-other", chain.network()),
+            ..pending.clone()
+        };
+        state
+            .cache
+            .set_json(
+                &keys::pending_override_market(9005),
+                &pending,
+                Duration::from_secs(1),
+            )
+            .await
+            .unwrap();
+        state
+            .cache
+            .set_json(
+                &keys::pending_override_market(9006),
+                &elsewhere,
+                Duration::from_secs(60),
+            )
             .await
             .unwrap();
+
+        let batch = chain.market_data_many(&[9005, 9006]).await;
+        let statuses: Vec<_> = batch
+            .iter()
+            .map(|r| r.as_ref().unwrap().status.clone())
+            .collect();
+        assert_eq!(
+            statuses,
+            [Some("resolved".to_string()), Some("active".to_string())]
+        );
+
+        tokio::time::sleep(Duration::from_millis(1_100)).await;
+        expire_chain_entry(&state, 9005).await;
+        let data = chain.market_data_cached(9005).await.unwrap();
+        assert_eq!(data.status.as_deref(), Some("active"));
+        assert_eq!(data.resolved_outcome, None);
+
+        for market_id in [9005, 9006] {
+            cleanup(&state, market_id).await;
+        }
+    }
+
+    /// A contract error during simulation (e.g. unknown market) is passed
+    /// through and nothing is submitted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_resolve_market_simulation_error_returns_422() {
+        let mut rpc = submitted();
+        rpc[1] = json!({ "result": { "latestLedger": 900, "error": "Error(Contract, #2)" } });
+        let state = build_state(Some(start_mock_rpc(rpc).await)).await;
+
+        let response = post_resolve(app(Arc::clone(&state)), 999_999_999, 0).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = body_json(response).await;
+        assert_eq!(body["code"], "SIMULATION_FAILED");
+        assert_eq!(body["message"], "Error(Contract, #2)");
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_resolve_market_without_signer_returns_503() {
+        let state = build_state(None).await;
+        let response = post_resolve(app(Arc::clone(&state)), 9001, 0).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
     // ---------------------------------------------------------------------------
     // Pure-logic unit tests (no I/O)
     // ---------------------------------------------------------------------------
 
-    /// Verifies that `ResolveMarketRequest` deserialises correctly.
+    /// Verifies that `ResolveMarketRequest` deserialises correctly, including
+    /// the legacy `outcome_index` field name.
     #[test]
     fn test_resolve_market_request_deserialises() {
-        let json = r#"{"outcome_index": 2}"#;
+        let json = r#"{"winning_outcome": 2}"#;
         let req: crate::handlers::ResolveMarketRequest = serde_json::from_str(json).unwrap();
-        assert_eq!(req.outcome_index, 2);
+        assert_eq!(req.winning_outcome, 2);
+        let legacy = r#"{"outcome_index": 1}"#;
+        let req: crate::handlers::ResolveMarketRequest = serde_json::from_str(legacy).unwrap();
+        assert_eq!(req.winning_outcome, 1);
     }
 
     /// Verifies that `InvalidationResult` serialises correctly.
@@ -132,43 +443,23 @@ mod resolve_market_tests {
     // Helper — builds AppState from env (used by #[ignore] integration tests)
     // ---------------------------------------------------------------------------
 
+    /// With `rpc_url`, points the client at a mock node and configures the
+    /// test admin key; with `None`, no signer is configured.
     #[cfg(test)]
-    async fn build_test_state() -> Arc<crate::AppState> {
-        use crate::{
-            audit::AuditLogger,
-            blockchain::BlockchainClient,
-            cache::RedisCache,
-            config::Config,
-            db::Database,
-            email::{queue::EmailQueue, service::EmailService, webhook::WebhookHandler},
-            metrics::Metrics,
-            newsletter::IpRateLimiter,
-        };
+    async fn build_state(rpc_url: Option<String>) -> Arc<crate::AppState> {
+        use crate::{config::Config, test_support::build_test_state_with};
 
-        let config = Config::from_env();
-        let metrics = Metrics::new().expect("metrics");
-        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
-        let db = Database::new(&config.database_url, cache.clone(), metrics.clone(), &config.db_pool)
-            .await
-            .expect("db");
-        let blockchain = BlockchainClient::new(&config, cache.clone(), metrics.clone())
-            .expect("blockchain");
-        let email_service = EmailService::new(config.clone()).expect("email_service");
-        let email_queue = EmailQueue::new(cache.clone(), db.clone());
-        let webhook_handler = WebhookHandler::new(db.clone());
-        let audit_logger = AuditLogger::new(db.pool());
-
-        Arc::new(crate::AppState {
-            config,
-            cache: cache.clone(),
-            db,
-            blockchain,
-            metrics,
-            newsletter_rate_limiter: IpRateLimiter::new(cache),
-            email_service,
-            email_queue,
-            webhook_handler,
-            audit_logger,
-        })
+        let mut config = Config::from_env();
+        config.admin_secret_key = None;
+        if let Some(rpc_url) = rpc_url {
+            config.blockchain_rpc_url = rpc_url;
+            config.contract_id = CONTRACT_ID.to_string();
+            config.network_passphrase = "Test SDF Network ; September 2015".to_string();
+            config.admin_secret_key = Some(SecretString::new(ADMIN_SEED.to_string()));
+            config.contract_call_timeout = Duration::from_secs(1);
+            config.tx_poll_interval = Duration::from_millis(50);
+            config.retry_attempts = 1;
+        }
+        build_test_state_with(config, &[]).await
     }
 }
@@ -0,0 +1,468 @@
+//! Retention: purging rows past their useful life from tables that would
+//! otherwise grow without bound.
+//!
+//! Each [`RetentionPolicy`] names a table, the timestamp column that dates
+//! its rows, a maximum age in days and what happens to older rows:
+//! [`RetentionAction::Delete`] removes them and [`RetentionAction::Anonymize`]
+//! applies the table's GDPR anonymizer ([`crate::gdpr::anonymize_assignments`]),
+//! so only tables [`crate::gdpr::GDPR_TABLES`] anonymizes can use it.
+//! Policies come from `RETENTION_POLICIES` ([`parse_policies`]), defaulting
+//! to [`DEFAULT_POLICIES`].
+//!
+//! A nightly task (spawned in `main.rs`) calls [`run_once`] at [`RUN_TIME`].
+//! Each table is worked through in batches of `RETENTION_BATCH_SIZE` rows,
+//! each its own short statement, so no lock is held for long; a run stops a
+//! table after `RETENTION_MAX_BATCHES` batches and the next night carries on.
+//! With `RETENTION_DRY_RUN=true` a run only counts the rows it would change.
+//! Rows changed are counted in `retention_rows_total{table,action}` and
+//! dry-run counts are set on `retention_dry_run_rows{table}`.
+//!
+//! `chain_events` is not in the defaults: portfolios and leaderboards are
+//! derived from it, so dropping old events changes them. Operators who accept
+//! that can add a policy for it.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    db::Database,
+    gdpr::{self, Erasure, ANONYMIZED_RECIPIENT},
+    metrics::Metrics,
+    AppState,
+};
+
+/// `RETENTION_POLICIES` when unset: contact submissions after 24 months (a
+/// legal requirement), analytics events after 13 months, email events
+/// anonymized after a year and watched transactions after 30 days.
+pub const DEFAULT_POLICIES: &str = "contact_form_submissions:created_at:730:delete;\
+     analytics_events:created_at:395:delete;\
+     email_events:created_at:365:anonymize;\
+     watched_transactions:created_at:30:delete";
+
+/// Default `RETENTION_BATCH_SIZE`.
+pub const DEFAULT_BATCH_SIZE: u64 = 5_000;
+
+/// Default `RETENTION_MAX_BATCHES`: up to a million rows per table a night.
+pub const DEFAULT_MAX_BATCHES: u32 = 200;
+
+/// Time after UTC midnight at which the nightly run starts, clear of the
+/// stats rollup at 00:10.
+pub const RUN_TIME: NaiveTime = match NaiveTime::from_hms_opt(3, 30, 0) {
+    Some(t) => t,
+    None => panic!("valid retention time"),
+};
+
+/// Pause between batches, so replicas and autovacuum keep up.
+const BATCH_PAUSE: Duration = Duration::from_millis(100);
+
+const WORKER_NAME: &str = "retention";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionAction {
+    Delete,
+    Anonymize,
+}
+
+impl RetentionAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Anonymize => "anonymize",
+        }
+    }
+}
+
+/// Rows of `table` whose `age_column` is more than `max_age_days` old get
+/// `action`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub table: String,
+    pub age_column: String,
+    pub max_age_days: u32,
+    pub action: RetentionAction,
+    /// For [`RetentionAction::Anonymize`], the email column and the `SET`
+    /// list of the table's GDPR anonymizer.
+    anonymizer: Option<(&'static str, String)>,
+}
+
+impl RetentionPolicy {
+    pub fn new(
+        table: &str,
+        age_column: &str,
+        max_age_days: u32,
+        action: RetentionAction,
+    ) -> Result<Self, String> {
+        for (what, name) in [("table", table), ("age column", age_column)] {
+            if !is_identifier(name) {
+                return Err(format!("{what} '{name}' is not a plain SQL identifier"));
+            }
+        }
+        if max_age_days == 0 {
+            return Err(format!("{table}: max age must be at least 1 day"));
+        }
+        let anonymizer = match action {
+            RetentionAction::Delete => None,
+            RetentionAction::Anonymize => match gdpr::erasure_for(table) {
+                Some(Erasure::Anonymize {
+                    email_column,
+                    json_column,
+                }) => Some((
+                    email_column,
+                    gdpr::anonymize_assignments(email_column, json_column),
+                )),
+                _ => return Err(format!("{table}: has no GDPR anonymizer; use delete")),
+            },
+        };
+        Ok(Self {
+            table: table.to_string(),
+            age_column: age_column.to_string(),
+            max_age_days,
+            action,
+            anonymizer,
+        })
+    }
+
+    /// Rows dated before this are past retention.
+    pub fn cutoff(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono::Duration::days(i64::from(self.max_age_days))
+    }
+
+    /// Rows still to process, with the cutoff bound as `$1`. Anonymized rows
+    /// are left out, so a pass over them ends.
+    fn pending(&self) -> String {
+        match &self.anonymizer {
+            Some((email_column, _)) => format!(
+                "{} < $1 AND {email_column} <> '{ANONYMIZED_RECIPIENT}'",
+                self.age_column
+            ),
+            None => format!("{} < $1", self.age_column),
+        }
+    }
+
+    /// Counts the rows a run would change.
+    pub(crate) fn count_sql(&self) -> String {
+        format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            self.table,
+            self.pending()
+        )
+    }
+
+    /// Processes at most `$2` pending rows. Rows are picked by `ctid`, so it
+    /// works on any table whatever its key.
+    pub(crate) fn batch_sql(&self) -> String {
+        let batch = format!(
+            "ctid = ANY(ARRAY(SELECT ctid FROM {} WHERE {} LIMIT $2))",
+            self.table,
+            self.pending()
+        );
+        match &self.anonymizer {
+            Some((_, assignments)) => {
+                format!("UPDATE {} SET {assignments} WHERE {batch}", self.table)
+            }
+            None => format!("DELETE FROM {} WHERE {batch}", self.table),
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Parse `table:age_column:max_age_days:action` entries separated by `;`,
+/// e.g. `analytics_events:created_at:395:delete`. An empty string is no
+/// policies.
+pub fn parse_policies(raw: &str) -> Result<Vec<RetentionPolicy>, String> {
+    let mut policies: Vec<RetentionPolicy> = Vec::new();
+    for entry in raw.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let [table, age_column, days, action] = entry
+            .split(':')
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| format!("'{entry}': expected table:age_column:max_age_days:action"))?;
+        let days = days
+            .parse()
+            .map_err(|_| format!("'{entry}': max age '{days}' is not a number of days"))?;
+        let action = match action {
+            "delete" => RetentionAction::Delete,
+            "anonymize" => RetentionAction::Anonymize,
+            other => {
+                return Err(format!(
+                    "'{entry}': unknown action '{other}', expected delete or anonymize"
+                ))
+            }
+        };
+        let policy = RetentionPolicy::new(table, age_column, days, action)?;
+        if policies.iter().any(|p| p.table == policy.table) {
+            return Err(format!("{table}: more than one policy"));
+        }
+        policies.push(policy);
+    }
+    Ok(policies)
+}
+
+/// What one run did to one table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableOutcome {
+    pub table: String,
+    pub action: RetentionAction,
+    pub cutoff: DateTime<Utc>,
+    /// Rows changed, or in a dry run the rows that would be.
+    pub rows: u64,
+    pub batches: u32,
+    pub dry_run: bool,
+    /// False when the run stopped at the batch limit or on shutdown with
+    /// rows left.
+    pub complete: bool,
+}
+
+/// Run limits, from `RETENTION_*` in [`crate::config::Config`].
+#[derive(Debug, Clone, Copy)]
+pub struct RunLimits {
+    pub batch_size: u64,
+    pub max_batches: u32,
+    pub dry_run: bool,
+}
+
+/// Apply one policy, batch by batch, until no rows are left, the batch limit
+/// is reached or `shutdown` fires.
+pub async fn apply_policy(
+    db: &Database,
+    policy: &RetentionPolicy,
+    limits: RunLimits,
+    now: DateTime<Utc>,
+    shutdown: &CancellationToken,
+) -> anyhow::Result<TableOutcome> {
+    let cutoff = policy.cutoff(now);
+    let mut outcome = TableOutcome {
+        table: policy.table.clone(),
+        action: policy.action,
+        cutoff,
+        rows: 0,
+        batches: 0,
+        dry_run: limits.dry_run,
+        complete: false,
+    };
+    if limits.dry_run {
+        outcome.rows = db.retention_count(policy, cutoff).await?;
+        outcome.complete = true;
+        return Ok(outcome);
+    }
+
+    while outcome.batches < limits.max_batches && !shutdown.is_cancelled() {
+        let rows = db
+            .retention_batch(policy, cutoff, limits.batch_size)
+            .await?;
+        outcome.rows += rows;
+        outcome.batches += 1;
+        if rows < limits.batch_size {
+            outcome.complete = true;
+            break;
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => {}
+            _ = tokio::time::sleep(BATCH_PAUSE) => {}
+        }
+    }
+    Ok(outcome)
+}
+
+/// Apply every policy in turn. A table that fails is logged and skipped;
+/// the rest still run.
+pub async fn run_once(
+    db: &Database,
+    metrics: &Metrics,
+    policies: &[RetentionPolicy],
+    limits: RunLimits,
+    now: DateTime<Utc>,
+    shutdown: &CancellationToken,
+) -> Vec<TableOutcome> {
+    let mut outcomes = Vec::with_capacity(policies.len());
+    for policy in policies {
+        match apply_policy(db, policy, limits, now, shutdown).await {
+            Ok(outcome) => {
+                if outcome.dry_run {
+                    metrics.set_retention_dry_run_rows(&outcome.table, outcome.rows);
+                } else {
+                    metrics.observe_retention_rows(
+                        &outcome.table,
+                        outcome.action.label(),
+                        outcome.rows,
+                    );
+                }
+                tracing::info!(
+                    table = %outcome.table,
+                    action = outcome.action.label(),
+                    rows = outcome.rows,
+                    batches = outcome.batches,
+                    dry_run = outcome.dry_run,
+                    complete = outcome.complete,
+                    "[retention] table processed"
+                );
+                outcomes.push(outcome);
+            }
+            Err(e) => tracing::warn!(table = %policy.table, "[retention] error: {e}"),
+        }
+    }
+    outcomes
+}
+
+/// Time from `now` until the next [`RUN_TIME`].
+pub fn next_run_delay(now: DateTime<Utc>) -> Duration {
+    let today = now.date_naive().and_time(RUN_TIME).and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// Run [`run_once`] nightly at [`RUN_TIME`] until `shutdown` fires.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    let config = &state.config;
+    let limits = RunLimits {
+        batch_size: config.retention_batch_size,
+        max_batches: config.retention_max_batches,
+        dry_run: config.retention_dry_run,
+    };
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(next_run_delay(Utc::now())) => {}
+        }
+        run_once(
+            &state.db,
+            &state.metrics,
+            &config.retention_policies,
+            limits,
+            Utc::now(),
+            &shutdown,
+        )
+        .await;
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn default_policies_parse() {
+        let policies = parse_policies(DEFAULT_POLICIES).unwrap();
+        let tables: Vec<&str> = policies.iter().map(|p| p.table.as_str()).collect();
+        assert_eq!(
+            tables,
+            [
+                "contact_form_submissions",
+                "analytics_events",
+                "email_events",
+                "watched_transactions"
+            ]
+        );
+        assert_eq!(policies[0].max_age_days, 730);
+        assert_eq!(policies[2].action, RetentionAction::Anonymize);
+        assert!(parse_policies("").unwrap().is_empty());
+        assert!(parse_policies(" ; ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed_policies_are_rejected() {
+        for (raw, expected) in [
+            ("analytics_events:created_at:395", "expected table"),
+            ("analytics_events:created_at:a year:delete", "not a number"),
+            ("analytics_events:created_at:0:delete", "at least 1 day"),
+            ("analytics_events:created_at:30:truncate", "unknown action"),
+            ("analytics events:created_at:30:delete", "identifier"),
+            ("analytics_events:created_at;--:30:delete", "expected table"),
+            ("analytics_events:Created_At:30:delete", "identifier"),
+            (
+                "contact_form_submissions:created_at:30:anonymize",
+                "no GDPR anonymizer",
+            ),
+            (
+                "analytics_events:created_at:30:delete;analytics_events:occurred_at:60:delete",
+                "more than one",
+            ),
+        ] {
+            let err = parse_policies(raw).unwrap_err();
+            assert!(err.contains(expected), "{raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn cutoff_is_max_age_before_now() {
+        let policy = RetentionPolicy::new(
+            "analytics_events",
+            "created_at",
+            30,
+            RetentionAction::Delete,
+        )
+        .unwrap();
+        let now = Utc.with_ymd_and_hms(2026, 3, 31, 3, 30, 0).unwrap();
+        assert_eq!(
+            policy.cutoff(now),
+            Utc.with_ymd_and_hms(2026, 3, 1, 3, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn deletes_are_batched_by_ctid() {
+        let policy = RetentionPolicy::new(
+            "analytics_events",
+            "created_at",
+            30,
+            RetentionAction::Delete,
+        )
+        .unwrap();
+        assert_eq!(
+            policy.batch_sql(),
+            "DELETE FROM analytics_events WHERE ctid = ANY(ARRAY(\
+             SELECT ctid FROM analytics_events WHERE created_at < $1 LIMIT $2))"
+        );
+        assert_eq!(
+            policy.count_sql(),
+            "SELECT COUNT(*) FROM analytics_events WHERE created_at < $1"
+        );
+    }
+
+    #[test]
+    fn anonymization_reuses_the_gdpr_anonymizer_and_skips_done_rows() {
+        let policy = RetentionPolicy::new(
+            "email_events",
+            "created_at",
+            365,
+            RetentionAction::Anonymize,
+        )
+        .unwrap();
+        let sql = policy.batch_sql();
+        assert!(sql.starts_with(&format!(
+            "UPDATE email_events SET {} WHERE",
+            gdpr::anonymize_assignments("recipient_email", "metadata")
+        )));
+        assert!(sql.contains("recipient_email <> 'erased@gdpr.invalid'"));
+        assert!(policy
+            .count_sql()
+            .contains("recipient_email <> 'erased@gdpr.invalid'"));
+    }
+
+    #[test]
+    fn runs_nightly_at_run_time() {
+        let before = Utc.with_ymd_and_hms(2026, 3, 1, 2, 0, 0).unwrap();
+        assert_eq!(next_run_delay(before), Duration::from_secs(90 * 60));
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 3, 30, 0).unwrap();
+        assert_eq!(next_run_delay(at), Duration::from_secs(24 * 60 * 60));
+    }
+}
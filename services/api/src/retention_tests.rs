@@ -0,0 +1,338 @@
+#[cfg(test)]
+mod retention_tests {
+    use chrono::{DateTime, TimeZone, Utc};
+    use tokio::sync::Mutex;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::{
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        gdpr::ANONYMIZED_RECIPIENT,
+        metrics::Metrics,
+        retention::{
+            self, parse_policies, RetentionAction, RetentionPolicy, RunLimits, TableOutcome,
+            DEFAULT_POLICIES,
+        },
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded rows are dated around 2000, long before any real row, and carry
+    /// this marker so cleanup finds them after anonymization too.
+    const MARKER: &str = "retention-98";
+
+    /// Any run purges every row older than its cutoff, including other
+    /// tests' seeds, so the tests here take turns.
+    static SERIAL: Mutex<()> = Mutex::const_new(());
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2002, 6, 1, 3, 30, 0).unwrap()
+    }
+
+    async fn build_db() -> (Database, Metrics) {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(
+            config.db_credentials.to_connection_string().expose_secret(),
+            cache,
+            metrics.clone(),
+            &config.db_pool,
+        )
+        .await
+        .expect("db");
+        (db, metrics)
+    }
+
+    fn policy(table: &str) -> RetentionPolicy {
+        parse_policies(DEFAULT_POLICIES)
+            .unwrap()
+            .into_iter()
+            .find(|p| p.table == table)
+            .unwrap()
+    }
+
+    /// Insert `n` marked rows into `table` dated `at`.
+    async fn seed(db: &Database, table: &str, at: DateTime<Utc>, n: usize) {
+        let sql = match table {
+            "contact_form_submissions" => {
+                "INSERT INTO contact_form_submissions (name, email, subject, message, created_at) \
+                 SELECT $1, $1 || '-' || i || '@example.com', 'Hello', 'Old message', $2 \
+                 FROM generate_series(1, $3) AS i"
+            }
+            "analytics_events" => {
+                "INSERT INTO analytics_events (event_name, session_id, created_at) \
+                 SELECT 'page_view', $1, $2 FROM generate_series(1, $3)"
+            }
+            "email_events" => {
+                "INSERT INTO email_events (message_id, event_type, recipient_email, metadata, created_at) \
+                 SELECT $1, 'delivered', $1 || '-' || i || '@example.com', \
+                        jsonb_build_object('email', $1 || '-' || i || '@example.com'), $2 \
+                 FROM generate_series(1, $3) AS i"
+            }
+            "watched_transactions" => {
+                "INSERT INTO watched_transactions (tx_hash, expires_at, status, created_at) \
+                 SELECT $1 || '-' || gen_random_uuid(), $2 + INTERVAL '30 minutes', 'expired', $2 \
+                 FROM generate_series(1, $3)"
+            }
+            other => panic!("no seed for {other}"),
+        };
+        sqlx::query(sql)
+            .bind(MARKER)
+            .bind(at)
+            .bind(n as i32)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    /// Marked rows in `table` dated before `before`.
+    async fn count(db: &Database, table: &str, before: DateTime<Utc>) -> i64 {
+        let (column, matches) = marker_match(table);
+        sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM {table} WHERE {matches} AND {column} < $2"
+        ))
+        .bind(MARKER)
+        .bind(before)
+        .fetch_one(&db.pool())
+        .await
+        .unwrap()
+    }
+
+    fn marker_match(table: &str) -> (&'static str, &'static str) {
+        match table {
+            "contact_form_submissions" => ("created_at", "email LIKE $1 || '-%'"),
+            "analytics_events" => ("created_at", "session_id = $1"),
+            "email_events" => ("created_at", "message_id = $1"),
+            "watched_transactions" => ("created_at", "tx_hash LIKE $1 || '-%'"),
+            other => panic!("no marker for {other}"),
+        }
+    }
+
+    async fn cleanup(db: &Database) {
+        for table in [
+            "contact_form_submissions",
+            "analytics_events",
+            "email_events",
+            "watched_transactions",
+        ] {
+            let (_, matches) = marker_match(table);
+            sqlx::query(&format!("DELETE FROM {table} WHERE {matches}"))
+                .bind(MARKER)
+                .execute(&db.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    fn outcome<'a>(outcomes: &'a [TableOutcome], table: &str) -> &'a TableOutcome {
+        outcomes
+            .iter()
+            .find(|o| o.table == table)
+            .unwrap_or_else(|| panic!("{table} failed"))
+    }
+
+    fn limits(batch_size: u64, max_batches: u32, dry_run: bool) -> RunLimits {
+        RunLimits {
+            batch_size,
+            max_batches,
+            dry_run,
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Under each default policy, rows a second past the maximum age go and
+    /// rows exactly at it stay; email events are anonymized, not deleted.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_default_policies_stop_at_the_cutoff() {
+        let _serial = SERIAL.lock().await;
+        let (db, metrics) = build_db().await;
+        cleanup(&db).await;
+        let policies = parse_policies(DEFAULT_POLICIES).unwrap();
+        for policy in &policies {
+            let cutoff = policy.cutoff(now());
+            seed(&db, &policy.table, cutoff - chrono::Duration::seconds(1), 3).await;
+            seed(&db, &policy.table, cutoff, 2).await;
+        }
+
+        let outcomes = retention::run_once(
+            &db,
+            &metrics,
+            &policies,
+            limits(5_000, 10, false),
+            now(),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        for policy in &policies {
+            let cutoff = policy.cutoff(now());
+            let done = outcome(&outcomes, &policy.table);
+            assert_eq!(done.rows, 3, "{}", policy.table);
+            assert!(done.complete);
+            assert_eq!(
+                count(&db, &policy.table, cutoff + chrono::Duration::seconds(1)).await,
+                match policy.action {
+                    RetentionAction::Delete => 2,
+                    RetentionAction::Anonymize => 5,
+                },
+                "{}",
+                policy.table
+            );
+        }
+
+        let anonymized: Vec<(String, String)> = sqlx::query_as(
+            "SELECT recipient_email, metadata::TEXT FROM email_events \
+             WHERE message_id = $1 AND created_at < $2",
+        )
+        .bind(MARKER)
+        .bind(policy("email_events").cutoff(now()))
+        .fetch_all(&db.pool())
+        .await
+        .unwrap();
+        assert_eq!(anonymized.len(), 3);
+        for (recipient, metadata) in &anonymized {
+            assert_eq!(recipient, ANONYMIZED_RECIPIENT);
+            assert_eq!(metadata, "{}");
+        }
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains(
+            "retention_rows_total{action=\"delete\",table=\"contact_form_submissions\"} 3"
+        ));
+        assert!(rendered
+            .contains("retention_rows_total{action=\"anonymize\",table=\"email_events\"} 3"));
+
+        cleanup(&db).await;
+    }
+
+    /// A dry run reports what a real run would change and changes nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_dry_run_only_counts() {
+        let _serial = SERIAL.lock().await;
+        let (db, metrics) = build_db().await;
+        cleanup(&db).await;
+        let policies = vec![policy("analytics_events"), policy("email_events")];
+        for policy in &policies {
+            seed(
+                &db,
+                &policy.table,
+                policy.cutoff(now()) - chrono::Duration::days(1),
+                4,
+            )
+            .await;
+        }
+
+        let outcomes = retention::run_once(
+            &db,
+            &metrics,
+            &policies,
+            limits(2, 10, true),
+            now(),
+            &CancellationToken::new(),
+        )
+        .await;
+
+        for policy in &policies {
+            let dry = outcome(&outcomes, &policy.table);
+            assert!(dry.dry_run);
+            assert_eq!(dry.rows, 4, "{}", policy.table);
+            assert_eq!(dry.batches, 0);
+            assert_eq!(count(&db, &policy.table, policy.cutoff(now())).await, 4);
+        }
+        let untouched: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM email_events WHERE message_id = $1 AND recipient_email <> $2",
+        )
+        .bind(MARKER)
+        .bind(ANONYMIZED_RECIPIENT)
+        .fetch_one(&db.pool())
+        .await
+        .unwrap();
+        assert_eq!(untouched, 4);
+
+        let rendered = metrics.render().unwrap();
+        assert!(rendered.contains("retention_dry_run_rows{table=\"analytics_events\"} 4"));
+        assert!(!rendered.contains("retention_rows_total{"));
+
+        cleanup(&db).await;
+    }
+
+    /// A run stops at the batch limit and the next one carries on where it
+    /// left off, for deletes and for anonymization alike.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_batches_continue_across_runs() {
+        let _serial = SERIAL.lock().await;
+        let (db, _) = build_db().await;
+        cleanup(&db).await;
+        let shutdown = CancellationToken::new();
+        let analytics = policy("analytics_events");
+        let email = policy("email_events");
+        seed(
+            &db,
+            "analytics_events",
+            analytics.cutoff(now()) - chrono::Duration::days(1),
+            7,
+        )
+        .await;
+        seed(
+            &db,
+            "email_events",
+            email.cutoff(now()) - chrono::Duration::days(1),
+            3,
+        )
+        .await;
+
+        let run = |policy: RetentionPolicy, max_batches: u32| {
+            let (db, shutdown) = (&db, &shutdown);
+            async move {
+                retention::apply_policy(db, &policy, limits(2, max_batches, false), now(), shutdown)
+                    .await
+            }
+        };
+        let progress = |o: TableOutcome| (o.rows, o.batches, o.complete);
+
+        assert_eq!(progress(run(analytics.clone(), 2).await.unwrap()), (4, 2, false));
+        assert_eq!(
+            count(&db, "analytics_events", analytics.cutoff(now())).await,
+            3
+        );
+        assert_eq!(progress(run(analytics.clone(), 2).await.unwrap()), (3, 2, true));
+        assert_eq!(progress(run(analytics.clone(), 2).await.unwrap()), (0, 1, true));
+        assert_eq!(
+            count(&db, "analytics_events", analytics.cutoff(now())).await,
+            0
+        );
+
+        // Anonymized rows no longer match, so each run moves on to new ones.
+        assert_eq!(progress(run(email.clone(), 1).await.unwrap()), (2, 1, false));
+        assert_eq!(progress(run(email.clone(), 1).await.unwrap()), (1, 1, true));
+        assert_eq!(progress(run(email.clone(), 1).await.unwrap()), (0, 1, true));
+
+        // Shutdown ends a run before its next batch.
+        seed(
+            &db,
+            "analytics_events",
+            analytics.cutoff(now()) - chrono::Duration::days(1),
+            2,
+        )
+        .await;
+        shutdown.cancel();
+        assert_eq!(progress(run(analytics.clone(), 2).await.unwrap()), (0, 0, false));
+        assert_eq!(
+            count(&db, "analytics_events", analytics.cutoff(now())).await,
+            2
+        );
+
+        cleanup(&db).await;
+    }
+}
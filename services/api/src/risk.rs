@@ -0,0 +1,563 @@
+//! Bet exposure per market, for risk monitoring.
+//!
+//! [`exposure`] derives a market's exposure from the primary network's
+//! indexed `bet_place` events: the stake on each outcome, the largest single
+//! position, the top-five bettors' share of the stake ("concentration") and
+//! the stake placed in the last hour, with each outcome's implied
+//! probability now and an hour ago from `market_price_points`. A position is
+//! everything one address has staked on the market, across outcomes. The
+//! contract has no withdrawal event; an address refunded on the market
+//! (`reward_fx` with `is_refund`) has no stake left and is left out.
+//!
+//! A background task ([`run`], spawned in `main.rs`) evaluates every active
+//! market holding at least `RISK_MIN_STAKE` each `RISK_CHECK_INTERVAL_SECS`
+//! and keeps one open row in `risk_alerts` per market whose concentration or
+//! hourly inflow is above its threshold. New alerts are pushed to
+//! `ALERT_WEBHOOK_URL` when it is set; an alert is cleared once its
+//! condition stops holding. Amounts are stroop strings, computed in `i128`.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use tokio_util::sync::CancellationToken;
+
+use crate::{alerting::AlertWebhook, config::Config, db::Database, AppState};
+
+const WORKER_NAME: &str = "risk_monitor";
+
+/// Bettors counted in the concentration share.
+pub const TOP_BETTORS: usize = 5;
+
+/// One address's stake on one outcome, as returned by
+/// [`Database::risk_positions`].
+#[derive(Debug, Clone)]
+pub struct PositionRow {
+    pub address: String,
+    pub outcome: i32,
+    pub staked: String,
+    /// The part of `staked` placed in the last hour.
+    pub last_hour: String,
+}
+
+/// An outcome's implied probability now and an hour ago, as returned by
+/// [`Database::risk_probabilities`].
+#[derive(Debug, Clone)]
+pub struct ProbabilityRow {
+    pub outcome: i32,
+    pub latest: f64,
+    /// `None` when the outcome has no sample that old.
+    pub hour_ago: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct LargestPosition {
+    pub address: String,
+    pub staked: String,
+    /// Fraction of the market's stake.
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct OutcomeExposure {
+    pub outcome: u32,
+    pub staked: String,
+    /// Fraction of the market's stake on this outcome.
+    pub share: f64,
+    pub bettors: u64,
+    /// Largest stake one address has on this outcome.
+    pub largest_stake: String,
+    pub staked_last_hour: String,
+    /// Latest implied probability, when sampled.
+    pub probability: Option<f64>,
+    /// Change in implied probability over the last hour.
+    pub probability_change: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct MarketExposure {
+    pub market_id: i64,
+    pub network: String,
+    pub total_staked: String,
+    pub bettors: u64,
+    pub largest_position: Option<LargestPosition>,
+    /// Fraction of the stake held by the [`TOP_BETTORS`] largest positions.
+    pub top5_share: f64,
+    pub staked_last_hour: String,
+    /// `staked_last_hour` as a fraction of `total_staked`.
+    pub inflow_share: f64,
+    pub outcomes: Vec<OutcomeExposure>,
+    pub computed_at: DateTime<Utc>,
+    #[serde(skip)]
+    #[schema(ignore)]
+    pub total: i128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskAlertKind {
+    /// The top five bettors hold too much of the stake.
+    Concentration,
+    /// Too much of the stake arrived in the last hour.
+    Inflow,
+}
+
+impl RiskAlertKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Concentration => "concentration",
+            Self::Inflow => "inflow",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "concentration" => Some(Self::Concentration),
+            "inflow" => Some(Self::Inflow),
+            _ => None,
+        }
+    }
+}
+
+fn amount_string<S: Serializer>(amount: &i128, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(amount)
+}
+
+/// Limits above which a market alerts. Markets with less than `min_stake`
+/// staked are not evaluated: with a handful of bettors, five of them always
+/// hold everything.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct RiskThresholds {
+    /// Largest acceptable top-five share.
+    pub concentration: f64,
+    /// Largest acceptable share of the stake placed in the last hour.
+    pub inflow: f64,
+    #[serde(serialize_with = "amount_string")]
+    #[schema(value_type = String)]
+    pub min_stake: i128,
+}
+
+impl RiskThresholds {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            concentration: config.risk_concentration_threshold,
+            inflow: config.risk_inflow_threshold,
+            min_stake: i128::from(config.risk_min_stake),
+        }
+    }
+}
+
+/// A threshold a market's exposure exceeds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskSignal {
+    pub kind: RiskAlertKind,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+/// A stored alert.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RiskAlert {
+    pub id: i64,
+    pub network: String,
+    pub market_id: i64,
+    pub kind: RiskAlertKind,
+    /// The concentration or inflow share when last evaluated.
+    pub value: f64,
+    pub threshold: f64,
+    pub total_staked: String,
+    pub opened_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// When the alert was pushed to the alert webhook.
+    pub notified_at: Option<DateTime<Utc>>,
+}
+
+/// `GET /api/v1/admin/risk/alerts`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RiskAlertList {
+    pub alerts: Vec<RiskAlert>,
+    pub thresholds: RiskThresholds,
+}
+
+fn parse_amount(field: &str, raw: &str) -> anyhow::Result<i128> {
+    raw.parse::<i128>()
+        .with_context(|| format!("risk: {field} is not an integer amount: {raw:?}"))
+}
+
+/// `part / total`, or 0 for an empty market.
+fn share(part: i128, total: i128) -> f64 {
+    if total <= 0 {
+        return 0.0;
+    }
+    part as f64 / total as f64
+}
+
+#[derive(Default)]
+struct OutcomeTotals {
+    staked: i128,
+    last_hour: i128,
+    bettors: u64,
+    largest: i128,
+}
+
+/// Compute a market's exposure from its position and probability rows.
+pub fn market_exposure(
+    network: &str,
+    market_id: i64,
+    positions: Vec<PositionRow>,
+    probabilities: Vec<ProbabilityRow>,
+    computed_at: DateTime<Utc>,
+) -> anyhow::Result<MarketExposure> {
+    let mut by_address: BTreeMap<String, i128> = BTreeMap::new();
+    let mut by_outcome: BTreeMap<u32, OutcomeTotals> = BTreeMap::new();
+    for row in positions {
+        let staked = parse_amount("staked", &row.staked)?;
+        let last_hour = parse_amount("last_hour", &row.last_hour)?;
+        let outcome = u32::try_from(row.outcome)
+            .with_context(|| format!("risk: negative outcome {}", row.outcome))?;
+        *by_address.entry(row.address).or_default() += staked;
+        let totals = by_outcome.entry(outcome).or_default();
+        totals.staked += staked;
+        totals.last_hour += last_hour;
+        totals.bettors += 1;
+        totals.largest = totals.largest.max(staked);
+    }
+    let total: i128 = by_outcome.values().map(|o| o.staked).sum();
+    let last_hour: i128 = by_outcome.values().map(|o| o.last_hour).sum();
+
+    let mut ranked: Vec<(String, i128)> = by_address.into_iter().collect();
+    // Largest first; ties by address so the response is deterministic.
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let top: i128 = ranked.iter().take(TOP_BETTORS).map(|(_, s)| *s).sum();
+    let largest_position = ranked.first().map(|(address, staked)| LargestPosition {
+        address: address.clone(),
+        staked: staked.to_string(),
+        share: share(*staked, total),
+    });
+
+    let mut probabilities: BTreeMap<i32, ProbabilityRow> = probabilities
+        .into_iter()
+        .map(|row| (row.outcome, row))
+        .collect();
+    let outcomes = by_outcome
+        .into_iter()
+        .map(|(outcome, totals)| {
+            let probability = probabilities.remove(&(outcome as i32));
+            OutcomeExposure {
+                outcome,
+                staked: totals.staked.to_string(),
+                share: share(totals.staked, total),
+                bettors: totals.bettors,
+                largest_stake: totals.largest.to_string(),
+                staked_last_hour: totals.last_hour.to_string(),
+                probability: probability.as_ref().map(|p| p.latest),
+                probability_change: probability
+                    .as_ref()
+                    .and_then(|p| p.hour_ago.map(|before| p.latest - before)),
+            }
+        })
+        .collect();
+
+    Ok(MarketExposure {
+        market_id,
+        network: network.to_string(),
+        total_staked: total.to_string(),
+        bettors: ranked.len() as u64,
+        largest_position,
+        top5_share: share(top, total),
+        staked_last_hour: last_hour.to_string(),
+        inflow_share: share(last_hour, total),
+        outcomes,
+        computed_at,
+        total,
+    })
+}
+
+/// The thresholds `exposure` exceeds. Nothing for markets under the
+/// minimum stake.
+pub fn evaluate(exposure: &MarketExposure, thresholds: &RiskThresholds) -> Vec<RiskSignal> {
+    if exposure.total < thresholds.min_stake || exposure.total <= 0 {
+        return Vec::new();
+    }
+    [
+        (
+            RiskAlertKind::Concentration,
+            exposure.top5_share,
+            thresholds.concentration,
+        ),
+        (
+            RiskAlertKind::Inflow,
+            exposure.inflow_share,
+            thresholds.inflow,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, value, threshold)| value > threshold)
+    .map(|(kind, value, threshold)| RiskSignal {
+        kind,
+        value,
+        threshold,
+    })
+    .collect()
+}
+
+/// `market_id`'s exposure on `network` as of `now`.
+pub async fn exposure(
+    db: &Database,
+    network: &str,
+    market_id: i64,
+    now: DateTime<Utc>,
+) -> anyhow::Result<MarketExposure> {
+    let hour_ago = now - chrono::Duration::hours(1);
+    let positions = db.risk_positions(network, market_id, hour_ago).await?;
+    let probabilities = db.risk_probabilities(market_id, hour_ago).await?;
+    market_exposure(network, market_id, positions, probabilities, now)
+}
+
+fn alert_text(alert: &RiskAlert) -> String {
+    let what = match alert.kind {
+        RiskAlertKind::Concentration => "the top 5 bettors hold",
+        RiskAlertKind::Inflow => "the last hour brought in",
+    };
+    format!(
+        ":rotating_light: [{}] Market {}: {what} {:.0}% of {} staked (threshold {:.0}%)",
+        alert.network,
+        alert.market_id,
+        alert.value * 100.0,
+        alert.total_staked,
+        alert.threshold * 100.0
+    )
+}
+
+/// Push alerts not yet sent to `webhook`. An alert whose push fails is
+/// retried on the next check. Returns how many were sent.
+pub async fn notify(db: &Database, network: &str, webhook: &AlertWebhook) -> anyhow::Result<usize> {
+    let mut sent = 0;
+    for alert in db.risk_alerts_claim_unnotified(network).await? {
+        match webhook.send(&alert_text(&alert)).await {
+            Ok(()) => sent += 1,
+            Err(e) => {
+                tracing::warn!(
+                    market_id = alert.market_id,
+                    "[risk] alert push failed: {e:#}"
+                );
+                db.risk_alert_release(alert.id).await?;
+            }
+        }
+    }
+    Ok(sent)
+}
+
+/// Evaluate every active market on `network` holding at least the minimum
+/// stake, open or refresh an alert for each threshold exceeded, clear the
+/// rest and push new alerts to `webhook`. A market that fails to evaluate
+/// is logged and keeps its alerts. Returns the signals found per market.
+pub async fn check(
+    db: &Database,
+    network: &str,
+    thresholds: &RiskThresholds,
+    webhook: Option<&AlertWebhook>,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Vec<(i64, Vec<RiskSignal>)>> {
+    let markets = db
+        .risk_candidate_markets(network, thresholds.min_stake)
+        .await?;
+    let mut found = Vec::new();
+    for &market_id in &markets {
+        let exposure = match exposure(db, network, market_id, now).await {
+            Ok(exposure) => exposure,
+            Err(e) => {
+                tracing::warn!(market_id, "[risk] exposure error: {e:#}");
+                continue;
+            }
+        };
+        let signals = evaluate(&exposure, thresholds);
+        for signal in &signals {
+            db.risk_alert_upsert(network, market_id, signal, &exposure.total_staked)
+                .await?;
+        }
+        let holding: Vec<&str> = signals.iter().map(|s| s.kind.as_str()).collect();
+        db.risk_alerts_clear(network, market_id, &holding).await?;
+        if !signals.is_empty() {
+            found.push((market_id, signals));
+        }
+    }
+    db.risk_alerts_clear_other_markets(network, &markets)
+        .await?;
+
+    if let Some(webhook) = webhook {
+        notify(db, network, webhook).await?;
+    }
+    Ok(found)
+}
+
+/// Check the primary network at startup and every `risk_check_interval`
+/// until `shutdown` fires. Only the primary network has price points.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    let url = state.config.alert_webhook_url.as_deref();
+    let webhook = match url.map(AlertWebhook::new) {
+        Some(Ok(webhook)) => Some(webhook),
+        Some(Err(e)) => {
+            tracing::warn!("[risk] alert webhook unavailable: {e:#}");
+            None
+        }
+        None => None,
+    };
+    let thresholds = RiskThresholds::from_config(&state.config);
+    let network = state.config.network_name();
+
+    loop {
+        match check(
+            &state.db,
+            network,
+            &thresholds,
+            webhook.as_ref(),
+            Utc::now(),
+        )
+        .await
+        {
+            Ok(found) if !found.is_empty() => {
+                tracing::info!("[risk] {} market(s) over a threshold", found.len())
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[risk] check error: {e:#}"),
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(state.config.risk_check_interval) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(address: &str, outcome: i32, staked: i128, last_hour: i128) -> PositionRow {
+        PositionRow {
+            address: address.to_string(),
+            outcome,
+            staked: staked.to_string(),
+            last_hour: last_hour.to_string(),
+        }
+    }
+
+    fn thresholds(min_stake: i128) -> RiskThresholds {
+        RiskThresholds {
+            concentration: 0.8,
+            inflow: 0.25,
+            min_stake,
+        }
+    }
+
+    #[test]
+    fn concentration_sums_the_five_largest_addresses() {
+        // GA holds 400 across both outcomes, then 100, 90, 80, 70, 60, 50.
+        let rows = vec![
+            position("GA", 0, 300, 0),
+            position("GA", 1, 100, 0),
+            position("GB", 0, 100, 0),
+            position("GC", 1, 90, 0),
+            position("GD", 1, 80, 0),
+            position("GE", 0, 70, 0),
+            position("GF", 0, 60, 0),
+            position("GG", 1, 50, 50),
+        ];
+        let exposure = market_exposure("testnet", 7, rows, Vec::new(), Utc::now()).unwrap();
+
+        assert_eq!(exposure.total_staked, "850");
+        assert_eq!(exposure.bettors, 7);
+        let largest = exposure.largest_position.unwrap();
+        assert_eq!(
+            (largest.address.as_str(), largest.staked.as_str()),
+            ("GA", "400")
+        );
+        assert!((largest.share - 400.0 / 850.0).abs() < 1e-12);
+        assert!((exposure.top5_share - 740.0 / 850.0).abs() < 1e-12);
+        assert_eq!(exposure.staked_last_hour, "50");
+        assert!((exposure.inflow_share - 50.0 / 850.0).abs() < 1e-12);
+
+        let outcome_0 = &exposure.outcomes[0];
+        assert_eq!(outcome_0.staked, "530");
+        assert_eq!(outcome_0.bettors, 4);
+        assert_eq!(outcome_0.largest_stake, "300");
+        let outcome_1 = &exposure.outcomes[1];
+        assert_eq!(outcome_1.staked, "320");
+        assert_eq!(outcome_1.staked_last_hour, "50");
+        assert!((outcome_0.share + outcome_1.share - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn probability_change_needs_an_hour_old_sample() {
+        let rows = vec![position("GA", 0, 10, 0), position("GB", 1, 10, 0)];
+        let probabilities = vec![
+            ProbabilityRow {
+                outcome: 0,
+                latest: 0.5,
+                hour_ago: Some(0.25),
+            },
+            ProbabilityRow {
+                outcome: 1,
+                latest: 0.5,
+                hour_ago: None,
+            },
+        ];
+        let exposure = market_exposure("testnet", 7, rows, probabilities, Utc::now()).unwrap();
+        assert_eq!(exposure.outcomes[0].probability_change, Some(0.25));
+        assert_eq!(exposure.outcomes[1].probability, Some(0.5));
+        assert_eq!(exposure.outcomes[1].probability_change, None);
+    }
+
+    #[test]
+    fn an_empty_market_has_no_exposure() {
+        let exposure = market_exposure("testnet", 7, Vec::new(), Vec::new(), Utc::now()).unwrap();
+        assert_eq!(exposure.total_staked, "0");
+        assert_eq!(exposure.top5_share, 0.0);
+        assert!(exposure.largest_position.is_none());
+        assert!(evaluate(&exposure, &thresholds(0)).is_empty());
+    }
+
+    #[test]
+    fn thresholds_are_exclusive_and_need_the_minimum_stake() {
+        // Five bettors hold 90 of 100; 30 of it arrived in the last hour.
+        let mut rows: Vec<_> = ["GA", "GB", "GC", "GD", "GE"]
+            .iter()
+            .map(|a| position(a, 0, 18, 6))
+            .collect();
+        rows.extend(["GF", "GG"].iter().map(|a| position(a, 1, 5, 0)));
+        let exposure = market_exposure("testnet", 7, rows, Vec::new(), Utc::now()).unwrap();
+
+        let kinds = |t: RiskThresholds| -> Vec<RiskAlertKind> {
+            evaluate(&exposure, &t)
+                .into_iter()
+                .map(|s| s.kind)
+                .collect()
+        };
+        assert_eq!(
+            kinds(thresholds(100)),
+            [RiskAlertKind::Concentration, RiskAlertKind::Inflow]
+        );
+        assert!(kinds(thresholds(101)).is_empty(), "below the minimum stake");
+        let at_the_limit = RiskThresholds {
+            concentration: 0.9,
+            inflow: 0.3,
+            min_stake: 0,
+        };
+        assert!(kinds(at_the_limit).is_empty(), "equal is not over");
+    }
+
+    #[test]
+    fn bad_amounts_are_errors() {
+        let rows = vec![
+            position("GA", 0, 1, 0),
+            PositionRow {
+                staked: "1.5".to_string(),
+                ..position("GB", 0, 0, 0)
+            },
+        ];
+        assert!(market_exposure("testnet", 7, rows, Vec::new(), Utc::now()).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+#[cfg(test)]
+mod risk_tests {
+    use std::sync::Arc;
+
+    use axum::{routing::post, Json, Router};
+    use chrono::{DateTime, Utc};
+    use serde_json::Value;
+    use tokio::sync::Mutex;
+
+    use crate::{
+        alerting::AlertWebhook,
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+        risk::{self, RiskAlertKind, RiskThresholds},
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Events and alerts are written on a network no real monitor uses, and
+    /// markets use reserved ids, so cleanup only touches rows created here.
+    const NETWORK: &str = "risk-test";
+    const SEED_MARKET_IDS: [i64; 3] = [9501, 9502, 9503];
+    const SEED_EVENT_PREFIX: &str = "risk-test-";
+
+    /// The check and the cleanup of one test would clear the other's
+    /// alerts, so the tests here take turns.
+    static SERIAL: Mutex<()> = Mutex::const_new(());
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    fn thresholds() -> RiskThresholds {
+        RiskThresholds {
+            concentration: 0.8,
+            inflow: 0.25,
+            min_stake: 5_000,
+        }
+    }
+
+    async fn seed_markets(db: &Database) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, token, ends_at) \
+             SELECT id, 'Risk ' || id, 'active', 'CRISKTEST', NOW() + INTERVAL '1 day' \
+             FROM UNNEST($1::BIGINT[]) AS id",
+        )
+        .bind(&SEED_MARKET_IDS[..])
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// Insert a bet of `amount` by `address` on `outcome`, indexed
+    /// `minutes_ago`.
+    async fn bet(
+        db: &Database,
+        market_id: i64,
+        address: &str,
+        outcome: i32,
+        amount: i64,
+        minutes_ago: i64,
+    ) {
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id, address, outcome, amount, indexed_at) \
+             VALUES ($1 || gen_random_uuid(), $2, 1, 'bet_place', $3, $4, $5, $6, \
+                     NOW() - make_interval(mins => $7))",
+        )
+        .bind(SEED_EVENT_PREFIX)
+        .bind(NETWORK)
+        .bind(market_id)
+        .bind(address)
+        .bind(outcome)
+        .bind(amount)
+        .bind(minutes_ago as i32)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn refund(db: &Database, market_id: i64, address: &str) {
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id, address, amount, is_refund) \
+             VALUES ($1 || gen_random_uuid(), $2, 2, 'reward_fx', $3, $4, 0, TRUE)",
+        )
+        .bind(SEED_EVENT_PREFIX)
+        .bind(NETWORK)
+        .bind(market_id)
+        .bind(address)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn price(db: &Database, market_id: i64, outcome: i32, close: f64, minutes_ago: i64) {
+        sqlx::query(
+            "INSERT INTO market_price_points (market_id, outcome, ts, open, high, low, close) \
+             VALUES ($1, $2, NOW() - make_interval(mins => $3), $4, $4, $4, $4)",
+        )
+        .bind(market_id)
+        .bind(outcome)
+        .bind(minutes_ago as i32)
+        .bind(close)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    /// - 9501: one whale holds 9000 of 10200, 1000 of it placed in the last
+    ///   hour; six small bettors hold 200 each; a refunded bettor's 5000 is
+    ///   left out.
+    /// - 9502: ten even positions of 1000, four of them placed in the last
+    ///   hour.
+    /// - 9503: one bettor holding everything, under the minimum stake.
+    async fn seed(db: &Database) {
+        cleanup(db).await;
+        seed_markets(db).await;
+        bet(db, 9501, "GRISKWHALE", 0, 8_000, 120).await;
+        bet(db, 9501, "GRISKWHALE", 1, 1_000, 10).await;
+        for i in 0..6 {
+            bet(db, 9501, &format!("GRISKSMALL{i}"), 1, 200, 120).await;
+        }
+        bet(db, 9501, "GRISKREFUNDED", 0, 5_000, 120).await;
+        refund(db, 9501, "GRISKREFUNDED").await;
+        price(db, 9501, 0, 0.6, 120).await;
+        price(db, 9501, 0, 0.7, 10).await;
+
+        for i in 0..10 {
+            let minutes_ago = if i < 4 { 5 } else { 180 };
+            bet(
+                db,
+                9502,
+                &format!("GRISKEVEN{i}"),
+                i % 2,
+                1_000,
+                minutes_ago,
+            )
+            .await;
+        }
+
+        bet(db, 9503, "GRISKLONE", 0, 500, 120).await;
+    }
+
+    async fn cleanup(db: &Database) {
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(SEED_EVENT_PREFIX)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM market_price_points WHERE market_id = ANY($1)")
+            .bind(&SEED_MARKET_IDS[..])
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKET_IDS[..])
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM risk_alerts WHERE network = $1")
+            .bind(NETWORK)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    /// A local webhook recording each alert text.
+    async fn start_webhook() -> (String, Arc<Mutex<Vec<String>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let sink = sink.clone();
+                async move {
+                    let text = body["text"].as_str().unwrap_or_default().to_string();
+                    sink.lock().await.push(text);
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, received)
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    async fn open_kinds(db: &Database) -> Vec<(i64, RiskAlertKind)> {
+        let mut open: Vec<(i64, RiskAlertKind)> = db
+            .risk_alerts_open(NETWORK)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|a| (a.market_id, a.kind))
+            .collect();
+        open.sort_by_key(|(market_id, _)| *market_id);
+        open
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Positions are summed per address across outcomes, refunded addresses
+    /// are left out, and the hourly deltas come from recent bets and price
+    /// points.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_exposure_of_a_skewed_market() {
+        let _serial = SERIAL.lock().await;
+        let db = build_db().await;
+        seed(&db).await;
+        let now: DateTime<Utc> = Utc::now();
+
+        let exposure = risk::exposure(&db, NETWORK, 9501, now).await.unwrap();
+
+        assert_eq!(exposure.total_staked, "10200");
+        assert_eq!(exposure.bettors, 7);
+        let largest = exposure.largest_position.as_ref().unwrap();
+        assert_eq!(largest.address, "GRISKWHALE");
+        assert_eq!(largest.staked, "9000");
+        assert!(close(largest.share, 9_000.0 / 10_200.0));
+        // The whale and four of the six 200s.
+        assert!(close(exposure.top5_share, 9_800.0 / 10_200.0));
+        assert_eq!(exposure.staked_last_hour, "1000");
+        assert!(close(exposure.inflow_share, 1_000.0 / 10_200.0));
+
+        assert_eq!(exposure.outcomes.len(), 2);
+        let yes = &exposure.outcomes[0];
+        assert_eq!((yes.outcome, yes.staked.as_str()), (0, "8000"));
+        assert_eq!((yes.bettors, yes.largest_stake.as_str()), (1, "8000"));
+        assert_eq!(yes.staked_last_hour, "0");
+        assert_eq!(yes.probability, Some(0.7));
+        assert!(close(yes.probability_change.unwrap(), 0.1));
+        let no = &exposure.outcomes[1];
+        assert_eq!((no.outcome, no.staked.as_str()), (1, "2200"));
+        assert_eq!((no.bettors, no.largest_stake.as_str()), (7, "1000"));
+        assert_eq!(no.staked_last_hour, "1000");
+        assert_eq!((no.probability, no.probability_change), (None, None));
+
+        // No bets on the network at all.
+        let empty = risk::exposure(&db, NETWORK, 9599, now).await.unwrap();
+        assert_eq!(empty.total_staked, "0");
+        assert!(empty.largest_position.is_none());
+        assert_eq!(empty.top5_share, 0.0);
+
+        cleanup(&db).await;
+    }
+
+    /// Each threshold opens one alert, pushed once however many checks see
+    /// it; markets under the minimum stake never alert, and an alert clears
+    /// once its condition stops holding.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_check_opens_notifies_and_clears_alerts() {
+        let _serial = SERIAL.lock().await;
+        let db = build_db().await;
+        seed(&db).await;
+        let (url, received) = start_webhook().await;
+        let webhook = AlertWebhook::new(url).unwrap();
+
+        let found = risk::check(&db, NETWORK, &thresholds(), Some(&webhook), Utc::now())
+            .await
+            .unwrap();
+        let found: Vec<(i64, Vec<RiskAlertKind>)> = found
+            .into_iter()
+            .map(|(id, signals)| (id, signals.iter().map(|s| s.kind).collect()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (9501, vec![RiskAlertKind::Concentration]),
+                (9502, vec![RiskAlertKind::Inflow]),
+            ]
+        );
+        assert_eq!(
+            open_kinds(&db).await,
+            vec![
+                (9501, RiskAlertKind::Concentration),
+                (9502, RiskAlertKind::Inflow),
+            ]
+        );
+        let alerts = db.risk_alerts_open(NETWORK).await.unwrap();
+        let inflow = alerts.iter().find(|a| a.market_id == 9502).unwrap();
+        assert!(close(inflow.value, 0.4));
+        assert_eq!(inflow.threshold, 0.25);
+        assert_eq!(inflow.total_staked, "10000");
+        assert!(alerts.iter().all(|a| a.notified_at.is_some()));
+        {
+            let texts = received.lock().await;
+            assert_eq!(texts.len(), 2);
+            assert!(texts
+                .iter()
+                .any(|t| t.contains("Market 9501") && t.contains("96%")));
+            assert!(texts
+                .iter()
+                .any(|t| t.contains("Market 9502") && t.contains("40%")));
+        }
+
+        // Still over: the alerts are refreshed, not pushed again.
+        risk::check(&db, NETWORK, &thresholds(), Some(&webhook), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(received.lock().await.len(), 2);
+        assert_eq!(open_kinds(&db).await.len(), 2);
+
+        // Ten more 2000 positions bring 9501's top five to 17000 of 30200.
+        for i in 0..10 {
+            bet(&db, 9501, &format!("GRISKLATE{i}"), 1, 2_000, 120).await;
+        }
+        risk::check(&db, NETWORK, &thresholds(), Some(&webhook), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(open_kinds(&db).await, vec![(9502, RiskAlertKind::Inflow)]);
+
+        // A resolved market leaves the candidates and its alert clears.
+        sqlx::query("UPDATE markets SET status = 'resolved' WHERE id = 9502")
+            .execute(&db.pool())
+            .await
+            .unwrap();
+        risk::check(&db, NETWORK, &thresholds(), Some(&webhook), Utc::now())
+            .await
+            .unwrap();
+        assert!(open_kinds(&db).await.is_empty());
+        assert_eq!(received.lock().await.len(), 2);
+
+        cleanup(&db).await;
+    }
+}
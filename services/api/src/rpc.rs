@@ -0,0 +1,494 @@
+//! JSON-RPC transport to the Stellar RPC node.
+//!
+//! [`RpcTransport::call`] retries a call until it succeeds, fails in a way a
+//! retry cannot fix, runs out of attempts, or runs out of the time budget the
+//! caller passed. Between attempts it sleeps for an exponential backoff with
+//! jitter, capped at [`RetryPolicy::max_delay`]. Every failure comes back as
+//! an [`RpcError`] carrying the attempt count and an [`RpcErrorKind`], which
+//! handlers use to pick between 502 and 500.
+
+use std::time::{Duration, Instant};
+
+use rand::Rng as _;
+use reqwest::{Client, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use tokio::time::sleep;
+
+/// Why an RPC call failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcErrorKind {
+    /// The node was unreachable or overloaded (transport errors, 5xx, 429,
+    /// server-side JSON-RPC errors, empty results) on every attempt.
+    Transient,
+    /// The node refused the request itself (4xx, malformed request, unknown
+    /// method, invalid params) or answered with a body we cannot decode.
+    /// Never retried: the same request gets the same answer.
+    Terminal,
+    /// The caller's time budget ran out before an attempt succeeded.
+    Timeout,
+}
+
+impl RpcErrorKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Transient => "transient",
+            Self::Terminal => "terminal",
+            Self::Timeout => "timeout",
+        }
+    }
+}
+
+/// An RPC call that failed after its retries. Handlers map
+/// [`RpcErrorKind::Terminal`] to `500` and the rest to
+/// `502 UPSTREAM_UNAVAILABLE`.
+#[derive(Debug)]
+pub struct RpcError {
+    pub method: String,
+    pub message: String,
+    pub kind: RpcErrorKind,
+    /// Attempts made, including the last one.
+    pub attempts: u32,
+}
+
+impl RpcError {
+    /// A transient failure after a single attempt.
+    pub fn new(method: &str, message: impl Into<String>) -> Self {
+        Self {
+            method: method.to_string(),
+            message: message.into(),
+            kind: RpcErrorKind::Transient,
+            attempts: 1,
+        }
+    }
+
+    /// Whether the node, rather than the request, is at fault.
+    pub fn is_upstream(&self) -> bool {
+        self.kind != RpcErrorKind::Terminal
+    }
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rpc {} {} ({} after {} attempt(s))",
+            self.method,
+            self.message,
+            self.kind.label(),
+            self.attempts
+        )
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+/// How often and how patiently a call is retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Most attempts per call, including the first. At least 1.
+    pub attempts: u32,
+    /// Backoff before the second attempt; doubles for each one after.
+    pub base_delay: Duration,
+    /// Ceiling on any single backoff.
+    pub max_delay: Duration,
+    /// Share of each backoff that is randomised: 1.0 is full jitter, 0.0
+    /// none.
+    pub jitter_factor: f64,
+}
+
+impl RetryPolicy {
+    /// Backoff after failed attempt number `attempt` (1-based):
+    /// `min(max_delay, base_delay * 2^(attempt - 1))`, of which
+    /// `jitter_factor` is drawn uniformly at random.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(16))
+            .min(self.max_delay);
+        let jitter = ceiling.mul_f64(self.jitter_factor.clamp(0.0, 1.0));
+        if jitter.is_zero() {
+            return ceiling;
+        }
+        ceiling - jitter + jitter.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcEnvelope<T> {
+    result: Option<T>,
+    error: Option<RpcErrorBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorBody {
+    code: i64,
+    message: String,
+}
+
+/// Standard JSON-RPC error codes that indicate a client-side mistake and
+/// should never be retried (retrying will produce the same error).
+fn is_non_retryable_rpc_error(code: i64) -> bool {
+    matches!(
+        code,
+        -32700  // Parse error
+        | -32600  // Invalid request
+        | -32601  // Method not found
+        | -32602 // Invalid params
+    )
+}
+
+/// One failed attempt.
+struct AttemptError {
+    kind: RpcErrorKind,
+    message: String,
+}
+
+impl AttemptError {
+    fn transient(message: impl Into<String>) -> Self {
+        Self {
+            kind: RpcErrorKind::Transient,
+            message: message.into(),
+        }
+    }
+
+    fn terminal(message: impl Into<String>) -> Self {
+        Self {
+            kind: RpcErrorKind::Terminal,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RpcTransport {
+    http: Client,
+    url: String,
+    retry: RetryPolicy,
+}
+
+impl RpcTransport {
+    pub fn new(http: Client, url: impl Into<String>, retry: RetryPolicy) -> Self {
+        Self {
+            http,
+            url: url.into(),
+            retry,
+        }
+    }
+
+    /// Call `method`, retrying transient failures until one succeeds, the
+    /// attempts run out, or `budget` has elapsed. Each attempt's HTTP request
+    /// is cut off at whatever is left of the budget, and no backoff is slept
+    /// that would end past it.
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &Value,
+        budget: Duration,
+        correlation_id: Option<&str>,
+    ) -> Result<T, RpcError> {
+        let deadline = Instant::now() + budget;
+        let max_attempts = self.retry.attempts.max(1);
+        let mut attempt: u32 = 0;
+
+        loop {
+            attempt += 1;
+            let fail = |kind, message: String| RpcError {
+                method: method.to_string(),
+                message,
+                kind,
+                attempts: attempt,
+            };
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(fail(
+                    RpcErrorKind::Timeout,
+                    format!("exceeded its {}ms budget", budget.as_millis()),
+                ));
+            }
+
+            let error = match self.attempt(method, params, attempt, remaining).await {
+                Ok(result) => return Ok(result),
+                Err(error) => error,
+            };
+
+            if error.kind == RpcErrorKind::Terminal {
+                return Err(fail(error.kind, error.message));
+            }
+            if attempt >= max_attempts && Instant::now() < deadline {
+                return Err(fail(error.kind, error.message));
+            }
+            // Give up now rather than sleep into a deadline the next attempt
+            // could not meet anyway.
+            let backoff = self.retry.backoff(attempt);
+            if Instant::now() + backoff >= deadline {
+                let message = format!(
+                    "exceeded its {}ms budget: {}",
+                    budget.as_millis(),
+                    error.message
+                );
+                return Err(fail(RpcErrorKind::Timeout, message));
+            }
+            tracing::warn!(
+                method,
+                attempt,
+                correlation_id,
+                backoff_ms = backoff.as_millis() as u64,
+                error = %error.message,
+                "rpc attempt failed, retrying"
+            );
+            sleep(backoff).await;
+        }
+    }
+
+    async fn attempt<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: &Value,
+        attempt: u32,
+        timeout: Duration,
+    ) -> Result<T, AttemptError> {
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": format!("{method}-{attempt}"),
+            "method": method,
+            "params": params,
+        });
+
+        let resp = self
+            .http
+            .post(&self.url)
+            .timeout(timeout)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| AttemptError::transient(format!("transport failed: {e}")))?;
+
+        let status = resp.status();
+        // 4xx (except 429 Too Many Requests) are non-retryable client errors.
+        if status.is_client_error() && status != StatusCode::TOO_MANY_REQUESTS {
+            return Err(AttemptError::terminal(format!(
+                "non-retryable client error: {status}"
+            )));
+        }
+        if !status.is_success() {
+            return Err(AttemptError::transient(format!("http error: {status}")));
+        }
+
+        let parsed = resp
+            .json::<RpcEnvelope<T>>()
+            .await
+            .map_err(|e| AttemptError::terminal(format!("parse error: {e}")))?;
+        match (parsed.error, parsed.result) {
+            (Some(err), _) if is_non_retryable_rpc_error(err.code) => Err(AttemptError::terminal(
+                format!("non-retryable error: {} ({})", err.message, err.code),
+            )),
+            (Some(err), _) => Err(AttemptError::transient(format!(
+                "failed: {} ({})",
+                err.message, err.code
+            ))),
+            (None, Some(result)) => Ok(result),
+            (None, None) => Err(AttemptError::transient("returned empty result")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn policy(attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            attempts,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(40),
+            jitter_factor: 1.0,
+        }
+    }
+
+    fn transport(server: &MockServer, attempts: u32) -> RpcTransport {
+        RpcTransport::new(Client::new(), server.uri(), policy(attempts))
+    }
+
+    async fn call(transport: &RpcTransport, budget: Duration) -> Result<Value, RpcError> {
+        transport
+            .call::<Value>("getLatestLedger", &json!({}), budget, None)
+            .await
+    }
+
+    fn rpc_error(code: i64) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "jsonrpc": "2.0",
+            "id": "1",
+            "error": { "code": code, "message": "boom" },
+        }))
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            jitter_factor: 0.0,
+            ..policy(10)
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(9), Duration::from_millis(40));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn full_jitter_stays_under_the_ceiling() {
+        let policy = policy(10);
+        for attempt in 1..8 {
+            let ceiling = Duration::from_millis(10 << (attempt - 1)).min(policy.max_delay);
+            assert!(policy.backoff(attempt) <= ceiling);
+        }
+    }
+
+    #[tokio::test]
+    async fn success_after_transient_failures() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "result": { "sequence": 7 } })),
+            )
+            .mount(&server)
+            .await;
+
+        let result = call(&transport(&server, 3), Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(result["sequence"], 7);
+    }
+
+    #[tokio::test]
+    async fn transient_failures_exhaust_attempts() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(rpc_error(-32603))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let err = call(&transport(&server, 3), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Transient);
+        assert_eq!(err.attempts, 3);
+        assert!(err.is_upstream());
+    }
+
+    #[tokio::test]
+    async fn invalid_params_are_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(rpc_error(-32602))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = call(&transport(&server, 5), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Terminal);
+        assert_eq!(err.attempts, 1);
+        assert!(!err.is_upstream());
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_terminal_but_429_is_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let err = call(&transport(&server, 3), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Terminal);
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .expect(3)
+            .mount(&server)
+            .await;
+        let err = call(&transport(&server, 3), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Transient);
+        assert_eq!(err.attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn undecodable_body_is_terminal() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>gateway</html>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = call(&transport(&server, 3), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Terminal);
+    }
+
+    #[tokio::test]
+    async fn slow_node_is_cut_off_at_the_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "result": {} }))
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let started = Instant::now();
+        let err = call(&transport(&server, 10), Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Timeout);
+        assert_eq!(err.attempts, 1);
+        assert!(err.is_upstream());
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn backoff_never_sleeps_past_the_budget() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&server)
+            .await;
+        let transport = RpcTransport::new(
+            Client::new(),
+            server.uri(),
+            RetryPolicy {
+                base_delay: Duration::from_secs(10),
+                max_delay: Duration::from_secs(10),
+                jitter_factor: 0.0,
+                ..policy(5)
+            },
+        );
+
+        let started = Instant::now();
+        let err = call(&transport, Duration::from_secs(1)).await.unwrap_err();
+        assert_eq!(err.kind, RpcErrorKind::Timeout);
+        assert_eq!(err.attempts, 1);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -4,7 +4,6 @@ use std::{
 };
 
 use axum::{
-    body::Body,
     extract::{ConnectInfo, Request, State},
     http::{HeaderMap, HeaderValue, StatusCode},
     middleware::Next,
@@ -14,15 +13,42 @@ use axum::{
 use ipnet::IpNet;
 use serde::Serialize;
 
+use crate::api_key_usage::QuotaDecision;
+
 /// Newtype wrapper so `trust_proxy: bool` can be injected as Axum `State`.
 #[derive(Clone, Copy, Debug)]
 pub struct TrustProxy(pub bool);
 
-/// Webhook signature verification config
-#[derive(Clone)]
-pub struct WebhookConfig {
-    pub secret: Option<String>,
-    pub replay_window_secs: u64,
+/// Which peers may set forwarding headers, for middleware that runs without
+/// `AppState`. Built from `TRUST_PROXY` and `TRUSTED_PROXY_CIDRS`; resolves
+/// addresses with [`extract_client_ip_cidrs`].
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies {
+    pub trust_proxy: bool,
+    pub cidrs: Arc<Vec<IpNet>>,
+}
+
+impl TrustedProxies {
+    pub fn new(trust_proxy: bool, cidrs: Vec<IpNet>) -> Self {
+        Self {
+            trust_proxy,
+            cidrs: Arc::new(cidrs),
+        }
+    }
+
+    pub fn client_ip(
+        &self,
+        headers: &HeaderMap,
+        connect_info: Option<&ConnectInfo<std::net::SocketAddr>>,
+    ) -> String {
+        extract_client_ip_cidrs(headers, connect_info, self.trust_proxy, &self.cidrs)
+    }
+}
+
+impl From<TrustProxy> for TrustedProxies {
+    fn from(TrustProxy(trust_proxy): TrustProxy) -> Self {
+        Self::new(trust_proxy, Vec::new())
+    }
 }
 
 /// Extract client IP with trusted proxy CIDR validation.
@@ -42,9 +68,21 @@ pub fn extract_client_ip(
     extract_client_ip_cidrs(headers, connect_info, trust_proxy, &[])
 }
 
-/// CIDR-aware variant. When `trusted_cidrs` is non-empty the connecting IP
-/// must be contained in one of the CIDRs before proxy headers are trusted.
-/// When `trusted_cidrs` is empty, falls back to the `trust_proxy` boolean.
+/// CIDR-aware variant, and the one extractor every rate limiter, audit record
+/// and IP allowlist goes through. When `trusted_cidrs` is non-empty the
+/// connecting IP must be contained in one of the CIDRs before proxy headers
+/// are trusted. When `trusted_cidrs` is empty, falls back to the
+/// `trust_proxy` boolean.
+///
+/// # Walking `X-Forwarded-For`
+/// Each proxy appends the address it received the request from, so only the
+/// right-hand end of the chain was written by infrastructure we trust; a
+/// client can put anything on the left. Entries are read right-to-left,
+/// skipping hops that are themselves trusted proxies, and the first address
+/// outside `trusted_cidrs` is the client. If every hop is trusted (an internal
+/// caller) the leftmost is used. In legacy `trust_proxy` mode every address
+/// counts as trusted, so this is always the leftmost entry — only safe when
+/// the edge proxy overwrites the header rather than appending to it.
 ///
 /// # IPv6 handling
 /// Each candidate IP string from a proxy header is parsed via
@@ -64,11 +102,17 @@ pub fn extract_client_ip_cidrs(
     trust_proxy: bool,
     trusted_cidrs: &[IpNet],
 ) -> String {
+    let is_trusted = |ip: &IpAddr| {
+        if trusted_cidrs.is_empty() {
+            trust_proxy
+        } else {
+            trusted_cidrs.iter().any(|cidr| cidr.contains(ip))
+        }
+    };
     let proxy_trusted = if !trusted_cidrs.is_empty() {
         // Only trust headers if the connecting IP is in a trusted CIDR.
         connect_info
-            .and_then(|ci| ci.0.ip().to_string().parse::<IpAddr>().ok())
-            .map(|connecting_ip| trusted_cidrs.iter().any(|cidr| cidr.contains(&connecting_ip)))
+            .map(|ci| is_trusted(&ci.0.ip()))
             .unwrap_or(false)
     } else {
         trust_proxy
@@ -76,19 +120,19 @@ pub fn extract_client_ip_cidrs(
 
     if proxy_trusted {
         // 1. Check X-Forwarded-For header (may contain a comma-separated list).
-        //    Iterate from left to right; return the first well-formed address.
+        //    Walk from right to left past trusted proxy hops; see above.
         //    Malformed entries are logged as warnings and skipped.
         if let Some(forwarded_for) = headers.get("x-forwarded-for").and_then(|h| h.to_str().ok()) {
-            for raw in forwarded_for.split(',') {
+            let mut leftmost = None;
+            for raw in forwarded_for.rsplit(',') {
                 let candidate = raw.trim();
                 if candidate.is_empty() {
                     continue;
                 }
                 match candidate.parse::<IpAddr>() {
-                    Ok(addr) => {
-                        // Return canonical form — critical for IPv6 rate-limit keys.
-                        return addr.to_string();
-                    }
+                    // Return canonical form — critical for IPv6 rate-limit keys.
+                    Ok(addr) if !is_trusted(&addr) => return addr.to_string(),
+                    Ok(addr) => leftmost = Some(addr),
                     Err(_) => {
                         tracing::warn!(
                             header = "x-forwarded-for",
@@ -98,6 +142,9 @@ pub fn extract_client_ip_cidrs(
                     }
                 }
             }
+            if let Some(addr) = leftmost {
+                return addr.to_string();
+            }
         }
 
         // 2. Check X-Real-IP header (single value expected).
@@ -134,12 +181,15 @@ pub async fn security_headers_middleware(request: Request, next: Next) -> Respon
     // Content Security Policy
     // The API serves JSON only — a "null" CSP prevents browsers from rendering
     // API responses as HTML pages, blocking any injected script execution.
-    headers.insert(
-        "content-security-policy",
-        HeaderValue::from_static(
-            "default-src 'none'; script-src 'none'; style-src 'none'; img-src 'none'; font-src 'none'; connect-src 'none'; frame-ancestors 'none'; base-uri 'none'; form-action 'none'; object-src 'none';"
-        ),
-    );
+    // The one HTML page, the non-production Swagger UI, sets its own.
+    if !headers.contains_key("content-security-policy") {
+        headers.insert(
+            "content-security-policy",
+            HeaderValue::from_static(
+                "default-src 'none'; script-src 'none'; style-src 'none'; img-src 'none'; font-src 'none'; connect-src 'none'; frame-ancestors 'none'; base-uri 'none'; form-action 'none'; object-src 'none';"
+            ),
+        );
+    }
 
     // X-Frame-Options
     headers.insert("x-frame-options", HeaderValue::from_static("DENY"));
@@ -285,10 +335,14 @@ pub async fn https_redirect_middleware(
 ///
 /// This dual-store design allows zero-downtime migration from static env-var
 /// keys to fully DB-managed keys with rotation support (issue #892).
+///
+/// With a [`UsageMeter`](crate::api_key_usage::UsageMeter) attached, requests
+/// made with database keys are counted and held to each key's monthly quota.
 #[derive(Clone)]
 pub struct ApiKeyAuth {
     valid_keys: Arc<Vec<String>>,
     db: Option<Arc<crate::db::Database>>,
+    usage: Option<crate::api_key_usage::UsageMeter>,
 }
 
 impl ApiKeyAuth {
@@ -297,6 +351,7 @@ impl ApiKeyAuth {
         Self {
             valid_keys: Arc::new(keys),
             db: None,
+            usage: None,
         }
     }
 
@@ -305,9 +360,16 @@ impl ApiKeyAuth {
         Self {
             valid_keys: Arc::new(keys),
             db: Some(db),
+            usage: None,
         }
     }
 
+    /// Meter requests made with database keys and enforce their quotas.
+    pub fn with_usage(mut self, usage: crate::api_key_usage::UsageMeter) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
     /// Synchronous check against static env-var keys only.
     pub fn verify(&self, key: &str) -> bool {
         !self.valid_keys.is_empty() && self.valid_keys.iter().any(|k| k == key)
@@ -320,9 +382,22 @@ impl ApiKeyAuth {
     /// hash is looked up in the `api_keys` table.  Keys that are revoked or
     /// past their `expires_at` overlap window are rejected by the query.
     pub async fn verify_async(&self, key: &str) -> bool {
+        self.identify(key).await.is_some()
+    }
+
+    /// Like [`verify_async`](Self::verify_async), but returns the key's name:
+    /// its `api_keys.label` for database keys, or `static:<n>` for the n-th
+    /// (1-based) entry of `API_KEYS`, which have no label.
+    pub async fn identify(&self, key: &str) -> Option<ApiKeyIdentity> {
+        self.lookup(key).await.map(|(identity, _)| identity)
+    }
+
+    /// Like [`identify`](Self::identify), also returning the `api_keys` row
+    /// for database keys.
+    async fn lookup(&self, key: &str) -> Option<(ApiKeyIdentity, Option<crate::db::ApiKeyRecord>)> {
         // Fast path: static env-var keys.
-        if self.verify(key) {
-            return true;
+        if let Some(index) = self.valid_keys.iter().position(|k| k == key) {
+            return Some((ApiKeyIdentity(format!("static:{}", index + 1)), None));
         }
 
         // Slow path: database-backed keys.
@@ -330,7 +405,9 @@ impl ApiKeyAuth {
             use sha2::{Digest, Sha256};
             let hash = hex::encode(Sha256::digest(key.as_bytes()));
             match db.api_key_validate(&hash).await {
-                Ok(Some(_)) => return true,
+                Ok(Some(record)) => {
+                    return Some((ApiKeyIdentity(record.label.clone()), Some(record)))
+                }
                 Ok(None) => {}
                 Err(e) => {
                     tracing::warn!(error = %e, "api_key_validate db error");
@@ -338,10 +415,17 @@ impl ApiKeyAuth {
             }
         }
 
-        false
+        None
     }
 }
 
+/// Name of the API key that authenticated a request. Inserted by
+/// [`api_key_middleware`] into both the request and the response extensions,
+/// so outer middleware such as the audit logger can attribute the request
+/// without seeing the key itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyIdentity(pub String);
+
 #[derive(Serialize)]
 struct ApiKeyErrorBody {
     error: &'static str,
@@ -351,6 +435,9 @@ struct ApiKeyErrorBody {
 ///
 /// Accepts keys validated by [`ApiKeyAuth::verify_async`], which checks both
 /// the static `API_KEYS` env-var list and the database-backed `api_keys` table.
+/// When the auth instance meters usage, a database key that has used its
+/// monthly quota gets `429` with code `QUOTA_EXCEEDED`. Metering fails open:
+/// if Redis is unavailable the request proceeds uncounted.
 pub async fn api_key_middleware(
     State(auth): State<Arc<ApiKeyAuth>>,
     headers: HeaderMap,
@@ -362,7 +449,7 @@ pub async fn api_key_middleware(
         .and_then(|h| h.to_str().ok())
         .unwrap_or("");
 
-    if !auth.verify_async(api_key).await {
+    let Some((identity, record)) = auth.lookup(api_key).await else {
         let mut resp = (
             StatusCode::UNAUTHORIZED,
             Json(ApiKeyErrorBody {
@@ -375,9 +462,49 @@ pub async fn api_key_middleware(
             HeaderValue::from_static("ApiKey realm=\"predictiq\""),
         );
         return resp;
+    };
+
+    if let (Some(usage), Some(record)) = (&auth.usage, record) {
+        match usage.record(record.id, record.monthly_quota, chrono::Utc::now()).await {
+            Ok(QuotaDecision::Allowed { .. }) => {}
+            Ok(QuotaDecision::Exceeded { quota }) => {
+                tracing::info!(key = %identity.0, quota, "API key monthly quota exhausted");
+                let mut resp = crate::handlers::ApiError::new(
+                    crate::handlers::ApiErrorKind::RateLimited,
+                    "QUOTA_EXCEEDED",
+                    format!("This API key has used its monthly quota of {quota} requests."),
+                )
+                .into_response();
+                let retry_after = seconds_until_next_month(chrono::Utc::now());
+                resp.headers_mut().insert("Retry-After", HeaderValue::from(retry_after));
+                return resp;
+            }
+            Err(e) => {
+                tracing::warn!(key = %identity.0, error = %e, "API key usage metering failed; allowing request");
+            }
+        }
     }
 
-    next.run(request).await
+    let mut request = request;
+    request.extensions_mut().insert(identity.clone());
+    let mut response = next.run(request).await;
+    response.extensions_mut().insert(identity);
+    response
+}
+
+/// Seconds from `now` until the next calendar month (UTC) starts and monthly
+/// quotas reset.
+fn seconds_until_next_month(now: chrono::DateTime<chrono::Utc>) -> i64 {
+    use chrono::{Datelike, NaiveDate};
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|start| (start.and_utc() - now).num_seconds().max(1))
+        .unwrap_or(1)
 }
 
 /// IP whitelist for admin endpoints
@@ -409,14 +536,16 @@ impl IpWhitelist {
 /// Allows all IPs when `ADMIN_WHITELIST_IPS` is empty (open-by-default for
 /// local/dev). When the env var is set to a comma-separated list of IPs, only
 /// those addresses may reach admin endpoints; all others receive `403 Forbidden`.
+/// The caller's address is resolved exactly as for rate limiting, so a
+/// forwarding header only counts when it arrives from a trusted proxy.
 pub async fn ip_whitelist_middleware(
-    State((whitelist, TrustProxy(trust_proxy))): State<(Arc<IpWhitelist>, TrustProxy)>,
+    State((whitelist, proxies)): State<(Arc<IpWhitelist>, TrustedProxies)>,
     headers: HeaderMap,
     connect_info: Option<ConnectInfo<std::net::SocketAddr>>,
     request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let ip = extract_client_ip(&headers, connect_info.as_ref(), trust_proxy);
+    let ip = proxies.client_ip(&headers, connect_info.as_ref());
 
     if !whitelist.is_allowed(&ip) {
         return Err(StatusCode::FORBIDDEN);
@@ -487,84 +616,6 @@ pub async fn metrics_auth_middleware(
     Ok(next.run(request).await)
 }
 
-/// SendGrid webhook signature verification middleware.
-///
-/// Verifies the `X-Twilio-Email-Event-Webhook-Signature` header using HMAC-SHA256
-/// against the raw request body. The `SENDGRID_WEBHOOK_SECRET` must be configured
-/// for webhook security, except in development environment where it passes through.
-///
-/// Replay protection: rejects requests whose `X-Twilio-Email-Event-Webhook-Timestamp`
-/// is more than `WEBHOOK_REPLAY_WINDOW_SECS` seconds old relative to the server clock
-/// (default: 300 seconds).
-///
-/// # OpenAPI policy
-/// Route: `POST /webhooks/sendgrid`
-/// Auth: provider-signed (SendGrid HMAC) — no API key required.
-pub async fn sendgrid_webhook_middleware(
-    State(config): State<WebhookConfig>,
-    headers: HeaderMap,
-    request: Request,
-    next: Next,
-) -> Result<Response, StatusCode> {
-    let is_dev = std::env::var("ENVIRONMENT")
-        .map(|e| e == "development")
-        .unwrap_or(false); // default to non-dev so signature verification is enforced
-
-    if config.secret.is_none() && !is_dev {
-        return Err(StatusCode::UNAUTHORIZED);
-    }
-
-    if let Some(ref secret) = config.secret {
-        let sig = headers
-            .get("x-twilio-email-event-webhook-signature")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("");
-
-        // Replay protection: reject stale AND future-dated timestamps.
-        //
-        // The previous check used .abs() which accepted future-dated timestamps
-        // within the replay window. An attacker could pre-sign a request with
-        // timestamp = now + window - 1 and replay it for up to 2 * window seconds.
-        // The fix rejects any timestamp that is in the future at all, and any that
-        // is more than replay_window_secs old.
-        let ts_str = headers
-            .get("x-twilio-email-event-webhook-timestamp")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("");
-        let ts: i64 = ts_str.parse().unwrap_or(0);
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0);
-        let age_secs = now - ts;
-        if age_secs < 0 || age_secs > config.replay_window_secs as i64 {
-            tracing::warn!(
-                ts,
-                now,
-                age_secs,
-                "sendgrid webhook rejected: timestamp out of bounds"
-            );
-            return Err(StatusCode::UNAUTHORIZED);
-        }
-
-        let (parts, body) = request.into_parts();
-        let bytes = axum::body::to_bytes(body, usize::MAX)
-            .await
-            .map_err(|_| StatusCode::BAD_REQUEST)?;
-
-        // Signature covers timestamp + payload per SendGrid spec
-        let signed_payload = format!("{}{}", ts_str, String::from_utf8_lossy(&bytes));
-        if !signing::verify_signature(signed_payload.as_bytes(), sig, secret) {
-            return Err(StatusCode::UNAUTHORIZED);
-        }
-
-        let request = Request::from_parts(parts, Body::from(bytes));
-        return Ok(next.run(request).await);
-    }
-
-    Ok(next.run(request).await)
-}
-
 pub mod signing {
     use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
     use hmac::{Hmac, Mac};
@@ -635,6 +686,9 @@ pub mod signing {
     }
 
     pub fn generate_signature(payload: &[u8], secret: &str) -> Result<String, SigningError> {
+        if secret.is_empty() {
+            return Err(SigningError::InvalidKey);
+        }
         let mut mac =
             HmacSha256::new_from_slice(secret.as_bytes()).map_err(|_| SigningError::InvalidKey)?;
         mac.update(payload);
@@ -645,7 +699,7 @@ pub mod signing {
     /// Error type for fallible signing operations.
     #[derive(Debug, PartialEq)]
     pub enum SigningError {
-        /// The secret key was rejected by the HMAC constructor (empty key).
+        /// The secret key is empty or was rejected by the HMAC constructor.
         InvalidKey,
     }
 
@@ -672,6 +726,71 @@ impl IntoResponse for SecurityError {
     }
 }
 
+// ── Password hashing (Argon2id) ───────────────────────────────────────────────
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+
+/// Hash a user password or API secret using Argon2id with a random salt.
+pub fn hash_password(plaintext: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    let argon2 = Argon2::default();
+    Ok(argon2.hash_password(plaintext.as_bytes(), &salt)?.to_string())
+}
+
+/// Verify a plaintext password against a stored Argon2 PHC hash string.
+/// Returns `false` on any error — prevents oracle leaks.
+pub fn verify_password(plaintext: &str, hash: &str) -> bool {
+    let parsed = match PasswordHash::new(hash) {
+        Ok(h) => h,
+        Err(_) => return false,
+    };
+    Argon2::default()
+        .verify_password(plaintext.as_bytes(), &parsed)
+        .is_ok()
+}
+
+/// SHA-256 integrity hash for non-secret data: cache keys, ETags, content deduplication.
+/// Do NOT use for passwords or API secrets — use `hash_password` instead.
+pub fn integrity_hash(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Middleware to propagate trace context from incoming requests
+pub async fn trace_propagation_middleware(
+    headers: HeaderMap,
+    request: Request,
+    next: Next,
+) -> Response {
+    use opentelemetry::global;
+    use std::collections::HashMap;
+
+    // Extract trace context from headers
+    let propagator = global::get_text_map_propagator(|propagator| {
+        let mut carrier = HashMap::new();
+        for (key, value) in headers.iter() {
+            if let Ok(v) = value.to_str() {
+                carrier.insert(key.as_str().to_string(), v.to_string());
+            }
+        }
+        
+        
+        propagator.extract(&carrier)
+    });
+
+    // Attach context to current span
+    let span = tracing::Span::current();
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    span.set_parent(propagator);
+
+    next.run(request).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -744,6 +863,28 @@ mod tests {
         assert_eq!(headers.get_all("referrer-policy").iter().count(), 1);
     }
 
+    #[tokio::test]
+    async fn security_headers_middleware_keeps_a_route_csp() {
+        use axum::{body::Body, http::Request, middleware, routing::get, Router};
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/docs", get(crate::handlers::swagger_ui))
+            .layer(middleware::from_fn(super::security_headers_middleware));
+
+        let response = app
+            .oneshot(Request::builder().uri("/docs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let headers = response.headers();
+        assert_eq!(headers.get_all("content-security-policy").iter().count(), 1);
+        assert_eq!(
+            headers["content-security-policy"],
+            crate::openapi_spec::SWAGGER_UI_CSP
+        );
+    }
+
     #[test]
     fn test_extract_client_ip_precedence() {
         let mut headers = HeaderMap::new();
@@ -961,6 +1102,63 @@ mod tests {
         assert_eq!(extract_client_ip_cidrs(&headers, Some(&ci), false, &[]), "1.2.3.4");
     }
 
+    // ── right-to-left X-Forwarded-For walking ────────────────────────────
+
+    fn proxies() -> Vec<IpNet> {
+        vec!["10.0.0.0/8".parse().unwrap(), "fd00::/8".parse().unwrap()]
+    }
+
+    /// A client-supplied entry on the left of the chain cannot displace the
+    /// address our own proxy appended.
+    #[test]
+    fn xff_chain_resolves_to_first_untrusted_hop_from_the_right() {
+        let headers = xff("6.6.6.6, 203.0.113.7, 10.0.0.5");
+        let ci = addr("10.0.0.1:443");
+        assert_eq!(
+            extract_client_ip_cidrs(&headers, Some(&ci), false, &proxies()),
+            "203.0.113.7"
+        );
+    }
+
+    #[test]
+    fn xff_chain_skips_malformed_and_ipv6_proxy_hops() {
+        let headers = xff("2001:db8::7, garbage, fd00::2");
+        let ci = addr("[fd00::1]:443");
+        assert_eq!(
+            extract_client_ip_cidrs(&headers, Some(&ci), false, &proxies()),
+            "2001:db8::7"
+        );
+    }
+
+    /// Every hop is internal: the originating (leftmost) address is the client.
+    #[test]
+    fn xff_chain_of_trusted_hops_resolves_to_leftmost() {
+        let headers = xff("10.1.2.3, 10.0.0.5");
+        let ci = addr("10.0.0.1:443");
+        assert_eq!(
+            extract_client_ip_cidrs(&headers, Some(&ci), false, &proxies()),
+            "10.1.2.3"
+        );
+    }
+
+    /// A full chain sent straight from the internet is ignored, even with
+    /// `TRUST_PROXY` on: configured CIDRs take precedence.
+    #[test]
+    fn spoofed_chain_from_untrusted_peer_ignored_despite_trust_proxy() {
+        let mut headers = xff("10.0.0.9, 203.0.113.7");
+        headers.insert("x-real-ip", "10.0.0.9".parse().unwrap());
+        let ci = addr("198.51.100.4:5555");
+        assert_eq!(
+            extract_client_ip_cidrs(&headers, Some(&ci), true, &proxies()),
+            "198.51.100.4"
+        );
+        // No socket address → nothing to vouch for the headers.
+        assert_eq!(
+            extract_client_ip_cidrs(&headers, None, true, &proxies()),
+            "unknown"
+        );
+    }
+
     // ── api_key_middleware ────────────────────────────────────────────────
 
     #[tokio::test]
@@ -1029,6 +1227,45 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[tokio::test]
+    async fn api_key_middleware_exposes_key_name_on_response() {
+        use axum::{body::Body, http::Request, middleware, routing::get, Extension, Router};
+        use tower::ServiceExt;
+
+        let auth = Arc::new(ApiKeyAuth::new(vec!["first".to_string(), "second".to_string()]));
+        let app = Router::new()
+            .route("/", get(|Extension(identity): Extension<ApiKeyIdentity>| async move { identity.0 }))
+            .layer(middleware::from_fn_with_state(auth, super::api_key_middleware));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header("x-api-key", "second")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.extensions().get::<ApiKeyIdentity>(),
+            Some(&ApiKeyIdentity("static:2".to_string()))
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"static:2");
+    }
+
+    #[test]
+    fn quota_retry_after_counts_down_to_the_next_month() {
+        use chrono::TimeZone;
+        let month_end = chrono::Utc.with_ymd_and_hms(2026, 3, 31, 23, 0, 0).unwrap();
+        assert_eq!(seconds_until_next_month(month_end), 3600);
+        let new_year = chrono::Utc.with_ymd_and_hms(2026, 12, 31, 23, 59, 30).unwrap();
+        assert_eq!(seconds_until_next_month(new_year), 30);
+    }
+
     // ── ip_whitelist_middleware ───────────────────────────────────────────
 
     #[tokio::test]
@@ -1037,7 +1274,7 @@ mod tests {
         use tower::ServiceExt;
 
         let whitelist = Arc::new(IpWhitelist::new(vec!["127.0.0.1".parse().unwrap()]));
-        let state = (whitelist, TrustProxy(false));
+        let state = (whitelist, TrustedProxies::from(TrustProxy(false)));
         let app = Router::new()
             .route("/", get(|| async { "ok" }))
             .layer(middleware::from_fn_with_state(state, super::ip_whitelist_middleware));
@@ -1058,7 +1295,7 @@ mod tests {
         use tower::ServiceExt;
 
         let whitelist = Arc::new(IpWhitelist::new(vec![])); // empty = allow all
-        let state = (whitelist, TrustProxy(false));
+        let state = (whitelist, TrustedProxies::from(TrustProxy(false)));
         let app = Router::new()
             .route("/", get(|| async { "ok" }))
             .layer(middleware::from_fn_with_state(state, super::ip_whitelist_middleware));
@@ -1077,7 +1314,7 @@ mod tests {
         use tower::ServiceExt;
 
         let whitelist = Arc::new(IpWhitelist::new(vec!["10.0.0.1".parse().unwrap()]));
-        let state = (whitelist, TrustProxy(true));
+        let state = (whitelist, TrustedProxies::from(TrustProxy(true)));
         let app = Router::new()
             .route("/", get(|| async { "ok" }))
             .layer(middleware::from_fn_with_state(state, super::ip_whitelist_middleware));
@@ -1102,7 +1339,7 @@ mod tests {
         use tower::ServiceExt;
 
         let whitelist = Arc::new(IpWhitelist::new(vec!["10.0.0.1".parse().unwrap()]));
-        let state = (whitelist, TrustProxy(true));
+        let state = (whitelist, TrustedProxies::from(TrustProxy(true)));
         let app = Router::new()
             .route("/", get(|| async { "ok" }))
             .layer(middleware::from_fn_with_state(state, super::ip_whitelist_middleware));
@@ -1121,55 +1358,33 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
-    // ── Rate Limiter tests (issue #473) ──────────────────────────────────
-
-    /// Test that rate limiter correctly enforces limits.
-    #[tokio::test]
-    async fn rate_limiter_allows_under_limit() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(3, Duration::from_secs(1));
-
-        assert!(limiter.check("test:1", &config).await); // 1/3
-        assert!(limiter.check("test:1", &config).await); // 2/3
-        assert!(limiter.check("test:1", &config).await); // 3/3
-    }
-
-    /// Test that rate limiter blocks over limit.
+    /// The whitelist resolves the caller the same way as the rate limiters:
+    /// a whitelisted address in a header only counts from a trusted proxy.
     #[tokio::test]
-    async fn rate_limiter_blocks_over_limit() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(2, Duration::from_secs(1));
-
-        assert!(limiter.check("test:2", &config).await); // 1/2
-        assert!(limiter.check("test:2", &config).await); // 2/2
-        assert!(!limiter.check("test:2", &config).await); // BLOCKED
-    }
-
-    /// Test that rate limiter uses separate buckets per key.
-    #[tokio::test]
-    async fn rate_limiter_separate_buckets_per_key() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(1, Duration::from_secs(1));
-
-        assert!(limiter.check("key1", &config).await); // 1/1 for key1
-        assert!(limiter.check("key2", &config).await); // 1/1 for key2
-        assert!(!limiter.check("key1", &config).await); // BLOCKED (key1 at limit)
-        assert!(!limiter.check("key2", &config).await); // BLOCKED (key2 at limit)
-    }
+    async fn ip_whitelist_honours_headers_only_from_trusted_proxies() {
+        use axum::{body::Body, http::Request, middleware, routing::get, Router};
+        use tower::ServiceExt;
 
-    /// Test that rate limiter resets window after expiry.
-    #[tokio::test]
-    async fn rate_limiter_resets_window_after_expiry() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(1, Duration::from_millis(100));
+        let whitelist = Arc::new(IpWhitelist::new(vec!["203.0.113.7".parse().unwrap()]));
+        let state = (whitelist, TrustedProxies::new(false, proxies()));
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(state, super::ip_whitelist_middleware));
 
-        assert!(limiter.check("test:3", &config).await); // 1/1
-        assert!(!limiter.check("test:3", &config).await); // BLOCKED
+        let request = |peer: &str| {
+            Request::builder()
+                .uri("/")
+                .header("x-forwarded-for", "203.0.113.7")
+                .extension(addr(peer))
+                .body(Body::empty())
+                .unwrap()
+        };
 
-        // Wait for window to expire
-        tokio::time::sleep(Duration::from_millis(150)).await;
+        let via_proxy = app.clone().oneshot(request("10.0.0.1:443")).await.unwrap();
+        assert_eq!(via_proxy.status(), StatusCode::OK);
 
-        assert!(limiter.check("test:3", &config).await); // 1/1 (window reset)
+        let spoofed = app.oneshot(request("198.51.100.4:5555")).await.unwrap();
+        assert_eq!(spoofed.status(), StatusCode::FORBIDDEN);
     }
 
     // ── Argon2 password hashing tests (issue #664) ───────────────────────────
@@ -1213,23 +1428,6 @@ mod tests {
         assert_ne!(integrity_hash(b"a"), integrity_hash(b"b"));
     }
 
-    /// Test cleanup removes expired entries.
-    #[tokio::test]
-    async fn rate_limiter_cleanup_removes_expired_entries() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(1, Duration::from_millis(100));
-
-        assert!(limiter.check("test:cleanup", &config).await);
-
-        // Wait for entry to expire (>1 hour window by default)
-        // In real scenarios, cleanup runs periodically (300s)
-        // For testing, we just verify cleanup doesn't error
-        limiter.cleanup().await;
-
-        // Entry should still exist if not removed, or be re-creatable
-        assert!(limiter.check("test:cleanup", &config).await);
-    }
-
     // ── Rate Limiter no-leak tests (issue #473) ───────────────────────────
 
     /// Test analytics rate limiter key generation (no Box::leak used).
@@ -1274,7 +1472,7 @@ mod tests {
             payload,
             &signature,
             current_key,
-            previous_key.as_deref(),
+            previous_key,
             token_timestamp,
             grace_period
         ));
@@ -1357,109 +1555,4 @@ mod tests {
             grace_period
         ));
     }
-
-    // ── webhook timestamp validation tests ───────────────────────────────────
-
-    fn check_age(now: i64, ts: i64, window: u64) -> bool {
-        let age_secs = now - ts;
-        !(age_secs < 0 || age_secs > window as i64)
-    }
-
-    #[test]
-    fn webhook_timestamp_within_window_accepted() {
-        let now = 1_700_000_000i64;
-        assert!(check_age(now, now - 100, 300));
-    }
-
-    #[test]
-    fn webhook_timestamp_exactly_at_window_edge_accepted() {
-        let now = 1_700_000_000i64;
-        assert!(check_age(now, now - 300, 300));
-    }
-
-    #[test]
-    fn webhook_timestamp_beyond_window_rejected() {
-        let now = 1_700_000_000i64;
-        assert!(!check_age(now, now - 301, 300));
-    }
-
-    #[test]
-    fn webhook_future_timestamp_rejected() {
-        let now = 1_700_000_000i64;
-        assert!(!check_age(now, now + 1, 300));
-    }
-
-    #[test]
-    fn webhook_future_timestamp_within_old_window_rejected() {
-        // Under the old .abs() logic, now + 299 would have been accepted
-        // because abs(now - (now+299)) = 299 < 300. The new logic rejects it.
-        let now = 1_700_000_000i64;
-        assert!(!check_age(now, now + 299, 300));
-    }
-}
-
-// ── Password hashing (Argon2id) ───────────────────────────────────────────────
-
-use argon2::{
-    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
-};
-
-/// Hash a user password or API secret using Argon2id with a random salt.
-pub fn hash_password(plaintext: &str) -> Result<String, argon2::password_hash::Error> {
-    let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
-    Ok(argon2.hash_password(plaintext.as_bytes(), &salt)?.to_string())
-}
-
-/// Verify a plaintext password against a stored Argon2 PHC hash string.
-/// Returns `false` on any error — prevents oracle leaks.
-pub fn verify_password(plaintext: &str, hash: &str) -> bool {
-    let parsed = match PasswordHash::new(hash) {
-        Ok(h) => h,
-        Err(_) => return false,
-    };
-    Argon2::default()
-        .verify_password(plaintext.as_bytes(), &parsed)
-        .is_ok()
-}
-
-/// SHA-256 integrity hash for non-secret data: cache keys, ETags, content deduplication.
-/// Do NOT use for passwords or API secrets — use `hash_password` instead.
-pub fn integrity_hash(data: &[u8]) -> String {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    format!("{:x}", hasher.finalize())
-}
-
-/// Middleware to propagate trace context from incoming requests
-pub async fn trace_propagation_middleware(
-    headers: HeaderMap,
-    mut request: Request,
-    next: Next,
-) -> Response {
-    use opentelemetry::global;
-    use opentelemetry::propagation::TextMapPropagator;
-    use std::collections::HashMap;
-
-    // Extract trace context from headers
-    let propagator = global::get_text_map_propagator(|propagator| {
-        let mut carrier = HashMap::new();
-        for (key, value) in headers.iter() {
-            if let Ok(v) = value.to_str() {
-                carrier.insert(key.as_str().to_string(), v.to_string());
-            }
-        }
-        
-        let context = propagator.extract(&carrier);
-        context
-    });
-
-    // Attach context to current span
-    let span = tracing::Span::current();
-    use tracing_opentelemetry::OpenTelemetrySpanExt;
-    span.set_parent(propagator);
-
-    next.run(request).await
 }
@@ -1,4 +1,7 @@
+use std::future::IntoFuture;
+use std::net::SocketAddr;
 use std::time::Duration;
+use tokio::net::TcpListener;
 use tokio::sync::watch;
 use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
@@ -15,6 +18,54 @@ pub fn email_queue_drain_timeout() -> Duration {
     Duration::from_secs(secs)
 }
 
+/// Read `HTTP_DRAIN_TIMEOUT_SECS` from the environment.
+/// Defaults to 20 s: long enough for slow exports to finish, short enough to
+/// leave room for the worker drains inside a 30 s+ termination grace period.
+pub fn http_drain_timeout() -> Duration {
+    let secs = std::env::var("HTTP_DRAIN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(20);
+    Duration::from_secs(secs)
+}
+
+/// Serve `app` until `token` is cancelled, then stop accepting connections
+/// and give in-flight requests up to `drain_timeout` to finish. Connections
+/// still open after that are abandoned and die with the runtime.
+///
+/// Connections carry their peer address as `ConnectInfo<SocketAddr>`, which
+/// client-IP extraction falls back to (and checks against
+/// `TRUSTED_PROXY_CIDRS`) before trusting any forwarding header.
+pub async fn serve_until_cancelled(
+    listener: TcpListener,
+    app: axum::Router,
+    token: CancellationToken,
+    drain_timeout: Duration,
+) -> anyhow::Result<()> {
+    let stop = token.clone();
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move { stop.cancelled().await })
+        .into_future();
+    tokio::pin!(server);
+
+    tokio::select! {
+        result = &mut server => return result.map_err(Into::into),
+        _ = token.cancelled() => {}
+    }
+
+    info!(timeout_secs = drain_timeout.as_secs(), "Draining in-flight HTTP requests");
+    match timeout(drain_timeout, server).await {
+        Ok(result) => {
+            info!("HTTP server drained cleanly");
+            result.map_err(Into::into)
+        }
+        Err(_) => {
+            warn!("HTTP drain timeout exceeded — abandoning open connections");
+            Ok(())
+        }
+    }
+}
+
 /// Coordinates graceful shutdown across all background workers.
 ///
 /// Workers receive a [`CancellationToken`] they poll on each iteration.
@@ -42,6 +93,15 @@ impl ShutdownCoordinator {
         }
     }
 
+    /// Like [`ShutdownCoordinator::new`], but workers are also cancelled as
+    /// soon as `parent` is, without waiting for [`ShutdownCoordinator::shutdown`].
+    pub fn with_parent(parent: &CancellationToken, total_workers: usize) -> Self {
+        Self {
+            token: parent.child_token(),
+            ..Self::new(total_workers)
+        }
+    }
+
     /// Returns a child token that workers should poll with `.is_cancelled()`.
     pub fn token(&self) -> CancellationToken {
         self.token.child_token()
@@ -175,6 +235,16 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_parent_cancellation_reaches_workers() {
+        let parent = CancellationToken::new();
+        let coord = ShutdownCoordinator::with_parent(&parent, 1);
+        let token = coord.token();
+        assert!(!token.is_cancelled());
+        parent.cancel();
+        assert!(token.is_cancelled());
+    }
+
     #[tokio::test]
     async fn test_shutdown_timeout_forces_exit() {
         let coord = ShutdownCoordinator::new(1);
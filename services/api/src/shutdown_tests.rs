@@ -69,7 +69,7 @@ async fn test_email_queue_worker_graceful_shutdown() {
     
     let email_service = EmailService::new(config.clone())
         .expect("Failed to create email service");
-    let email_queue = EmailQueue::new(cache, db);
+    let email_queue = EmailQueue::new(db);
     
     let shutdown_coordinator = ShutdownCoordinator::new(1);
     let _shutdown_rx = shutdown_coordinator.subscribe();
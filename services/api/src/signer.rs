@@ -0,0 +1,143 @@
+//! Ed25519 signing for transactions the API submits on its own behalf.
+//!
+//! Partner traffic through `/api/v1/tx/*` is signed client-side; this module
+//! is only for privileged calls the service makes itself (e.g. the admin
+//! `resolve_market`). The seed never leaves [`TxSigner`]: it is not
+//! serialisable and its `Debug` output shows the public account only.
+
+use std::fmt;
+
+use anyhow::{anyhow, bail};
+use ed25519_dalek::{Signer as _, SigningKey};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{self as xdr, WriteXdr};
+
+pub struct TxSigner {
+    key: SigningKey,
+}
+
+impl TxSigner {
+    /// Load a signer from a Stellar secret seed (`S...`). The error never
+    /// echoes the input.
+    pub fn from_secret(secret: &SecretString) -> anyhow::Result<Self> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret.expose_secret().trim())
+            .map_err(|_| anyhow!("signing key is not a valid Stellar secret seed"))?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed.0),
+        })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    /// The signer's account address (`G...`).
+    pub fn account_id(&self) -> String {
+        stellar_strkey::ed25519::PublicKey(self.public_key()).to_string()
+    }
+
+    /// Sign `tx` for the network identified by `network_passphrase` and wrap
+    /// it in a v1 envelope ready for `sendTransaction`.
+    pub fn sign(
+        &self,
+        tx: xdr::Transaction,
+        network_passphrase: &str,
+    ) -> anyhow::Result<xdr::TransactionEnvelope> {
+        let hash = transaction_hash(&tx, network_passphrase)?;
+        let public = self.public_key();
+        let signature = xdr::DecoratedSignature {
+            hint: xdr::SignatureHint(public[28..].try_into()?),
+            signature: xdr::Signature(self.key.sign(&hash).to_bytes().to_vec().try_into()?),
+        };
+        Ok(xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx,
+            signatures: vec![signature].try_into()?,
+        }))
+    }
+}
+
+impl fmt::Debug for TxSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TxSigner")
+            .field("account_id", &self.account_id())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The hash signers commit to: SHA-256 of the signature payload, which binds
+/// the transaction to one network.
+pub fn transaction_hash(tx: &xdr::Transaction, network_passphrase: &str) -> anyhow::Result<[u8; 32]> {
+    if network_passphrase.is_empty() {
+        bail!("STELLAR_NETWORK_PASSPHRASE is required to sign transactions");
+    }
+    let payload = xdr::TransactionSignaturePayload {
+        network_id: xdr::Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        tagged_transaction: xdr::TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(xdr::Limits::none())?).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, VerifyingKey};
+
+    const SEED: &str = "SAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSBF5K";
+    const TESTNET: &str = "Test SDF Network ; September 2015";
+
+    fn signer() -> TxSigner {
+        TxSigner::from_secret(&SecretString::new(SEED.to_string())).unwrap()
+    }
+
+    fn empty_tx(source: [u8; 32]) -> xdr::Transaction {
+        xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(source)),
+            fee: 100,
+            seq_num: xdr::SequenceNumber(1),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: xdr::VecM::default(),
+            ext: xdr::TransactionExt::V0,
+        }
+    }
+
+    #[test]
+    fn signature_verifies_against_account_key_and_network() {
+        let signer = signer();
+        let tx = empty_tx(signer.public_key());
+        let xdr::TransactionEnvelope::Tx(env) = signer.sign(tx.clone(), TESTNET).unwrap() else {
+            panic!("expected a v1 envelope");
+        };
+        let decorated = &env.signatures[0];
+        assert_eq!(decorated.hint.0, signer.public_key()[28..]);
+
+        let key = VerifyingKey::from_bytes(&signer.public_key()).unwrap();
+        let sig = Signature::from_slice(decorated.signature.0.as_slice()).unwrap();
+        assert!(key.verify_strict(&transaction_hash(&tx, TESTNET).unwrap(), &sig).is_ok());
+        // The same signature is not valid for a different network.
+        let other = transaction_hash(&tx, "Public Global Stellar Network ; September 2015").unwrap();
+        assert!(key.verify_strict(&other, &sig).is_err());
+    }
+
+    #[test]
+    fn debug_output_never_contains_the_seed() {
+        let rendered = format!("{:?}", signer());
+        assert!(rendered.contains(&signer().account_id()));
+        assert!(!rendered.contains(SEED));
+    }
+
+    #[test]
+    fn rejects_public_keys_and_garbage_without_echoing_them() {
+        let public = signer().account_id();
+        let err = TxSigner::from_secret(&SecretString::new(public.clone())).unwrap_err();
+        assert!(!err.to_string().contains(&public));
+        assert!(TxSigner::from_secret(&SecretString::new("not-a-key".to_string())).is_err());
+    }
+
+    #[test]
+    fn signing_requires_a_passphrase() {
+        let signer = signer();
+        assert!(signer.sign(empty_tx(signer.public_key()), "").is_err());
+    }
+}
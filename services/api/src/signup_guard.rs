@@ -0,0 +1,237 @@
+//! Abuse checks for the endpoints that take an email address from anyone:
+//! newsletter, waitlist and contact signups, and the notification email
+//! fields.
+//!
+//! An address is refused when its domain matches a pattern in the
+//! `blocked_email_domains` table ([`is_blocked`]). Admins manage the table
+//! through `/api/v1/admin/email/blocked-domains`, and signups read it
+//! through the cache ([`crate::db::Database::blocked_email_patterns_cached`]).
+//! With `SIGNUP_MX_CHECK` on, the domain must also be able to receive mail
+//! ([`has_mail_host`]); a DNS lookup that fails or times out lets the
+//! address through.
+//!
+//! The newsletter, waitlist and contact forms also share a honeypot field,
+//! `website`, which the forms hide from people. A request that fills it in
+//! gets the endpoint's usual success response, but nothing is stored or sent
+//! ([`is_honeypot_hit`]).
+
+use std::{sync::OnceLock, time::Duration};
+
+use chrono::{DateTime, Utc};
+use hickory_resolver::{
+    config::{ResolverConfig, ResolverOpts},
+    error::{ResolveError, ResolveErrorKind},
+    TokioAsyncResolver,
+};
+use serde::{Deserialize, Serialize};
+
+/// Longest pattern accepted, the longest a domain name can be.
+pub const MAX_PATTERN_LEN: usize = 253;
+
+/// A row of the blocklist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BlockedDomain {
+    pub id: i64,
+    /// A domain in which `*` matches any run of characters, e.g.
+    /// `mailinator.com` or `*.tempmail.*`.
+    pub pattern: String,
+    /// Why the domain was added, for other admins.
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `raw` as stored: trimmed, lowercased, without a leading `@` and with runs
+/// of `*` collapsed. Rejects characters a domain cannot hold and patterns
+/// such as `*.*` that would block every address.
+pub fn normalize_pattern(raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim().trim_start_matches('@').to_lowercase();
+    if trimmed.is_empty() {
+        return Err("pattern must not be empty".into());
+    }
+    if trimmed.len() > MAX_PATTERN_LEN {
+        return Err(format!(
+            "pattern must be at most {MAX_PATTERN_LEN} characters"
+        ));
+    }
+    if !trimmed
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '*'))
+    {
+        return Err("pattern may only contain letters, digits, '.', '-' and '*'".into());
+    }
+    if !trimmed.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return Err("pattern must name part of a domain".into());
+    }
+
+    let mut pattern = String::with_capacity(trimmed.len());
+    for c in trimmed.chars() {
+        if !(c == '*' && pattern.ends_with('*')) {
+            pattern.push(c);
+        }
+    }
+    Ok(pattern)
+}
+
+/// Whether `domain` matches `pattern`, where `*` matches any run of
+/// characters, dots included, as `%` does in SQL `LIKE`. Everything else
+/// must match exactly, so `mailinator.com` does not cover its subdomains.
+pub fn pattern_matches(pattern: &str, domain: &str) -> bool {
+    let (pattern, domain) = (pattern.as_bytes(), domain.as_bytes());
+    let (mut p, mut d) = (0, 0);
+    // Where the last `*` was, and how much of the domain it has taken.
+    let mut star: Option<(usize, usize)> = None;
+
+    while d < domain.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, d));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == domain[d] {
+            p += 1;
+            d += 1;
+        } else if let Some((star_p, star_d)) = star {
+            // Let the `*` take one more character and retry from there.
+            star = Some((star_p, star_d + 1));
+            p = star_p + 1;
+            d = star_d + 1;
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// The part of a normalized `email` after the last `@`.
+pub fn email_domain(email: &str) -> Option<&str> {
+    email.rsplit_once('@').map(|(_, domain)| domain)
+}
+
+/// Whether the domain of a normalized `email` matches any of `patterns`.
+pub fn is_blocked(patterns: &[String], email: &str) -> bool {
+    email_domain(email).is_some_and(|domain| {
+        patterns
+            .iter()
+            .any(|pattern| pattern_matches(pattern, domain))
+    })
+}
+
+/// True when the hidden honeypot field was filled in.
+pub fn is_honeypot_hit(field: Option<&str>) -> bool {
+    field.is_some_and(|v| !v.trim().is_empty())
+}
+
+fn resolver() -> &'static TokioAsyncResolver {
+    static RESOLVER: OnceLock<TokioAsyncResolver> = OnceLock::new();
+    RESOLVER.get_or_init(|| {
+        TokioAsyncResolver::tokio_from_system_conf().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "no system DNS configuration; using public resolvers");
+            TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())
+        })
+    })
+}
+
+/// Whether `domain` can receive mail, giving up after `timeout`. Fails
+/// open: only a lookup that completes and finds nowhere to deliver refuses
+/// the domain.
+pub async fn has_mail_host(domain: &str, timeout: Duration) -> bool {
+    match tokio::time::timeout(timeout, lookup_mail_host(domain)).await {
+        Ok(Ok(found)) => found,
+        Ok(Err(e)) => {
+            tracing::debug!(domain, error = %e, "mail host lookup failed; allowing");
+            true
+        }
+        Err(_) => {
+            tracing::debug!(domain, "mail host lookup timed out; allowing");
+            true
+        }
+    }
+}
+
+/// An MX record other than the RFC 7505 null MX, or, without any MX, an
+/// address record (RFC 5321 §5.1). `Err` only when a lookup failed, not
+/// when it found nothing.
+async fn lookup_mail_host(domain: &str) -> Result<bool, ResolveError> {
+    // Fully qualified, so the system's search domains are not tried.
+    let fqdn = format!("{}.", domain.trim_end_matches('.'));
+    match resolver().mx_lookup(fqdn.as_str()).await {
+        Ok(mx) => return Ok(mx.iter().any(|mx| !mx.exchange().is_root())),
+        Err(e) if !is_no_records(&e) => return Err(e),
+        Err(_) => {}
+    }
+    match resolver().lookup_ip(fqdn.as_str()).await {
+        Ok(_) => Ok(true),
+        Err(e) if is_no_records(&e) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+fn is_no_records(e: &ResolveError) -> bool {
+    matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_patterns_match_only_that_domain() {
+        assert!(pattern_matches("mailinator.com", "mailinator.com"));
+        assert!(!pattern_matches("mailinator.com", "eu.mailinator.com"));
+        assert!(!pattern_matches("mailinator.com", "mailinator.co"));
+        assert!(!pattern_matches("mailinator.com", "notmailinator.com"));
+    }
+
+    #[test]
+    fn wildcards_match_any_run_including_dots() {
+        let pattern = "*.tempmail.*";
+        assert!(pattern_matches(pattern, "x.tempmail.org"));
+        assert!(pattern_matches(pattern, "a.b.tempmail.co.uk"));
+        assert!(
+            !pattern_matches(pattern, "tempmail.org"),
+            "the dot is literal"
+        );
+        assert!(!pattern_matches(pattern, "x.tempmailer.org"));
+
+        assert!(pattern_matches("guerrillamail.*", "guerrillamail.net"));
+        assert!(
+            pattern_matches("*mail.*", "mail.com"),
+            "a star may match nothing"
+        );
+        assert!(pattern_matches("temp*mail.com", "temp-fast-mail.com"));
+        assert!(
+            pattern_matches("*a*b", "aaab"),
+            "backtracks past a false start"
+        );
+        assert!(!pattern_matches("*a*b", "aaba"));
+    }
+
+    #[test]
+    fn patterns_are_normalized_and_checked() {
+        assert_eq!(
+            normalize_pattern("  @Mailinator.COM ").unwrap(),
+            "mailinator.com"
+        );
+        assert_eq!(normalize_pattern("**.tempmail.**").unwrap(), "*.tempmail.*");
+        assert!(normalize_pattern("   ").is_err());
+        assert!(normalize_pattern("*.*").is_err(), "would block everything");
+        assert!(normalize_pattern("temp_mail.com").is_err());
+        assert!(normalize_pattern("tempmail.com/x").is_err());
+        assert!(normalize_pattern(&"a".repeat(MAX_PATTERN_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn emails_are_blocked_by_domain() {
+        let patterns = vec!["mailinator.com".to_string(), "*.tempmail.*".to_string()];
+        assert!(is_blocked(&patterns, "bot@mailinator.com"));
+        assert!(is_blocked(&patterns, "bot@inbox.tempmail.net"));
+        assert!(!is_blocked(&patterns, "mailinator.com@example.com"));
+        assert!(!is_blocked(&patterns, "no-at-sign"));
+        assert!(!is_blocked(&[], "bot@mailinator.com"));
+    }
+
+    #[test]
+    fn honeypot_counts_only_non_blank_values() {
+        assert!(!is_honeypot_hit(None));
+        assert!(!is_honeypot_hit(Some("  ")));
+        assert!(is_honeypot_hit(Some("https://spam.example")));
+    }
+}
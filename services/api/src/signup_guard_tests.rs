@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod signup_guard_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{delete, get, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::handlers::{
+        blocked_domain_create, blocked_domain_delete, blocked_domains_list, newsletter_subscribe,
+        waitlist_join, waitlist_status,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Honeypot signups use a reserved range so cleanup never touches rows
+    /// created by other tests or the seed data.
+    const EMAIL_PREFIX: &str = "signup-guard-96";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/newsletter/subscribe", post(newsletter_subscribe))
+            .route("/waitlist", post(waitlist_join))
+            .route("/waitlist/status", get(waitlist_status))
+            .route(
+                "/admin/email/blocked-domains",
+                get(blocked_domains_list).post(blocked_domain_create),
+            )
+            .route(
+                "/admin/email/blocked-domains/:id",
+                delete(blocked_domain_delete),
+            )
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    fn post_json(uri: &str, body: Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn join(state: &Arc<crate::AppState>, email: &str) -> StatusCode {
+        send(state, post_json("/waitlist", json!({ "email": email })))
+            .await
+            .0
+    }
+
+    /// A domain unique to this run, so the pattern blocks nothing else.
+    fn test_domain() -> String {
+        format!(
+            "sg{}.example.org",
+            &uuid::Uuid::new_v4().simple().to_string()[..12]
+        )
+    }
+
+    async fn count(state: &crate::AppState, sql: &str, email_like: &str) -> i64 {
+        sqlx::query_scalar(sql)
+            .bind(email_like)
+            .fetch_one(&state.db.pool())
+            .await
+            .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState, email_like: &str) {
+        for sql in [
+            "DELETE FROM email_jobs WHERE recipient_email LIKE $1",
+            "DELETE FROM newsletter_subscribers WHERE email LIKE $1",
+            "DELETE FROM waitlist_entries WHERE email LIKE $1",
+        ] {
+            sqlx::query(sql)
+                .bind(email_like)
+                .execute(&state.db.pool())
+                .await
+                .unwrap();
+        }
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Signups read the blocklist through the cache, and an admin add or
+    /// delete takes effect on the next signup rather than after the TTL.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_add_refreshes_cached_blocklist() {
+        let state = build_test_state().await;
+        let domain = test_domain();
+        let email_like = format!("%@%{domain}");
+
+        // Caches a blocklist without the pattern.
+        assert_eq!(
+            join(&state, &format!("a@mx.{domain}")).await,
+            StatusCode::CREATED
+        );
+        assert!(state
+            .cache
+            .get_json::<Vec<String>>(&crate::cache::keys::dbq_blocked_email_domains())
+            .await
+            .unwrap()
+            .is_some());
+
+        let (status, created) = send(
+            &state,
+            post_json(
+                "/admin/email/blocked-domains",
+                json!({ "pattern": format!("  *.{}", domain.to_uppercase()), "note": "test" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(
+            created["pattern"],
+            format!("*.{domain}"),
+            "stored normalized"
+        );
+        let id = created["id"].as_i64().unwrap();
+
+        let (status, _) = send(
+            &state,
+            post_json(
+                "/admin/email/blocked-domains",
+                json!({ "pattern": format!("*.{domain}") }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send(
+            &state,
+            post_json("/admin/email/blocked-domains", json!({ "pattern": "*.*" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        assert_eq!(
+            join(&state, &format!("b@mx.{domain}")).await,
+            StatusCode::BAD_REQUEST
+        );
+        let (status, body) = send(
+            &state,
+            post_json(
+                "/newsletter/subscribe",
+                json!({ "email": format!("b@mx.{domain}") }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        assert_eq!(
+            join(&state, &format!("c@{domain}")).await,
+            StatusCode::CREATED,
+            "*. needs a subdomain"
+        );
+
+        let (status, listed) = send(
+            &state,
+            Request::builder()
+                .uri("/admin/email/blocked-domains")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(listed
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|d| d["id"] == id && d["note"] == "test"));
+
+        let delete_request = || {
+            Request::builder()
+                .method("DELETE")
+                .uri(format!("/admin/email/blocked-domains/{id}"))
+                .body(Body::empty())
+                .unwrap()
+        };
+        assert_eq!(
+            send(&state, delete_request()).await.0,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            send(&state, delete_request()).await.0,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            join(&state, &format!("d@mx.{domain}")).await,
+            StatusCode::CREATED
+        );
+
+        cleanup(&state, &email_like).await;
+    }
+
+    /// A filled honeypot gets the endpoint's usual success response, but
+    /// nothing is stored or queued.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_honeypot_hits_look_accepted_but_store_nothing() {
+        let state = build_test_state().await;
+        let email_like = format!("{EMAIL_PREFIX}%@example.com");
+        cleanup(&state, &email_like).await;
+        let email = format!("{EMAIL_PREFIX}01@example.com");
+
+        let (status, body) = send(
+            &state,
+            post_json(
+                "/newsletter/subscribe",
+                json!({ "email": email, "website": "https://spam.example" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body["success"], true);
+        assert_eq!(
+            body["message"],
+            "Please check your email to confirm your subscription."
+        );
+
+        let (status, body) = send(
+            &state,
+            post_json(
+                "/waitlist",
+                json!({ "email": email, "website": "https://spam.example" }),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body["email"], email);
+        assert_eq!(body["status"], "pending");
+        assert!(body["referral_code"]
+            .as_str()
+            .is_some_and(|c| !c.is_empty()));
+
+        assert_eq!(
+            count(
+                &state,
+                "SELECT COUNT(*) FROM newsletter_subscribers WHERE email LIKE $1",
+                &email_like
+            )
+            .await,
+            0
+        );
+        assert_eq!(
+            count(
+                &state,
+                "SELECT COUNT(*) FROM waitlist_entries WHERE email LIKE $1",
+                &email_like
+            )
+            .await,
+            0
+        );
+        assert_eq!(
+            count(
+                &state,
+                "SELECT COUNT(*) FROM email_jobs WHERE recipient_email LIKE $1",
+                &email_like
+            )
+            .await,
+            0,
+            "no confirmation queued"
+        );
+        let (status, _) = send(
+            &state,
+            Request::builder()
+                .uri(format!("/waitlist/status?email={email}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        // A blank honeypot is a normal signup.
+        let (status, _) = send(
+            &state,
+            post_json("/waitlist", json!({ "email": email, "website": " " })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(
+            count(
+                &state,
+                "SELECT COUNT(*) FROM waitlist_entries WHERE email LIKE $1",
+                &email_like
+            )
+            .await,
+            1
+        );
+
+        cleanup(&state, &email_like).await;
+    }
+}
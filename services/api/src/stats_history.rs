@@ -0,0 +1,288 @@
+//! Daily platform statistics for the landing-page growth chart.
+//!
+//! `platform_stats_daily` holds one row per UTC day: markets created (from
+//! `markets.created_at`), bet volume and first-time bettors (from
+//! `chain_events` on the primary network, dated by `indexed_at`). A nightly
+//! task (spawned in `main.rs`) calls [`refresh`]. Its first run, against an
+//! empty table, backfills every day since the earliest market or event; later
+//! runs recompute the last [`ROLLUP_LOOKBACK_DAYS`] days. Rows are upserted on
+//! `day` and overwritten, so a rerun never double-counts.
+//!
+//! `GET /api/v1/statistics/history` serves the last `days` days of one
+//! [`StatsMetric`], with any day missing from the table returned as zero. Each
+//! metric's full [`MAX_HISTORY_DAYS`] series is cached for
+//! [`HISTORY_CACHE_TTL`] and sliced per request, so [`refresh`] only has three
+//! keys to drop.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    cache::{keys, RedisCache},
+    db::Database,
+    AppState,
+};
+
+/// Default and maximum `days` for the history endpoint.
+pub const DEFAULT_HISTORY_DAYS: u32 = 30;
+pub const MAX_HISTORY_DAYS: u32 = 365;
+
+/// Lifetime of each cached metric series.
+pub const HISTORY_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Days before today that each nightly run recomputes. Covers yesterday in
+/// full even when the run starts late, plus a day of slack for events the
+/// sync worker indexed after midnight.
+pub const ROLLUP_LOOKBACK_DAYS: u64 = 2;
+
+/// Time after UTC midnight at which the nightly run starts.
+pub const ROLLUP_TIME: NaiveTime = match NaiveTime::from_hms_opt(0, 10, 0) {
+    Some(t) => t,
+    None => panic!("valid rollup time"),
+};
+
+const WORKER_NAME: &str = "stats_history_rollup";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsMetric {
+    /// Markets created that day.
+    #[default]
+    MarketsCreated,
+    /// Total staked that day, in stroops.
+    Volume,
+    /// Addresses that placed their first bet that day.
+    NewParticipants,
+}
+
+impl StatsMetric {
+    pub const ALL: [Self; 3] = [Self::MarketsCreated, Self::Volume, Self::NewParticipants];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MarketsCreated => "markets_created",
+            Self::Volume => "volume",
+            Self::NewParticipants => "new_participants",
+        }
+    }
+
+    /// `platform_stats_daily` column holding this metric. The label and
+    /// column are the same; kept separate so renaming one is deliberate.
+    pub(crate) fn column(self) -> &'static str {
+        match self {
+            Self::MarketsCreated => "markets_created",
+            Self::Volume => "volume",
+            Self::NewParticipants => "new_participants",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct StatsPoint {
+    pub day: NaiveDate,
+    /// Integer count, or integer stroops for `volume`.
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct StatsHistory {
+    pub metric: StatsMetric,
+    pub days: u32,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One point per day from `from` to `to`, oldest first.
+    pub points: Vec<StatsPoint>,
+    /// When `platform_stats_daily` was last written; `None` before the first
+    /// run.
+    pub rolled_up_at: Option<DateTime<Utc>>,
+}
+
+impl StatsHistory {
+    /// Build the series for `[from, to]` from stored rows, filling any day
+    /// without a row with an explicit zero.
+    pub fn new(
+        metric: StatsMetric,
+        (from, to): (NaiveDate, NaiveDate),
+        rows: Vec<(NaiveDate, String)>,
+        rolled_up_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let mut by_day: HashMap<NaiveDate, String> = rows.into_iter().collect();
+        let points: Vec<StatsPoint> = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|day| StatsPoint {
+                day,
+                value: by_day.remove(&day).unwrap_or_else(|| "0".to_string()),
+            })
+            .collect();
+        Self {
+            metric,
+            days: points.len() as u32,
+            from,
+            to,
+            points,
+            rolled_up_at,
+        }
+    }
+
+    /// The trailing `days` points.
+    pub fn last_days(mut self, days: u32) -> Self {
+        let skip = self.points.len().saturating_sub(days as usize);
+        self.points.drain(..skip);
+        if let Some(first) = self.points.first() {
+            self.from = first.day;
+        }
+        self.days = self.points.len() as u32;
+        self
+    }
+}
+
+/// Recompute `platform_stats_daily` and drop the cached series. Returns the
+/// number of day rows written.
+pub async fn refresh(
+    db: &Database,
+    cache: &RedisCache,
+    network: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<u64> {
+    let today = now.date_naive();
+    let recent = today - chrono::Days::new(ROLLUP_LOOKBACK_DAYS);
+    let from = if db.platform_stats_has_rows().await? {
+        recent
+    } else {
+        db.platform_stats_first_day(network)
+            .await?
+            .map_or(recent, |first| first.min(recent))
+    };
+
+    let rows = db
+        .platform_stats_rollup(network, from, today + chrono::Days::new(1))
+        .await?;
+    for metric in StatsMetric::ALL {
+        let _ = cache
+            .del(&keys::api_statistics_history(metric.label()))
+            .await;
+    }
+    Ok(rows)
+}
+
+/// Time from `now` until the next [`ROLLUP_TIME`].
+pub fn next_run_delay(now: DateTime<Utc>) -> Duration {
+    let today = now.date_naive().and_time(ROLLUP_TIME).and_utc();
+    let next = if today > now {
+        today
+    } else {
+        today + chrono::Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// Run [`refresh`] once at startup (which backfills an empty table), then
+/// nightly at [`ROLLUP_TIME`] until `shutdown` fires. A failed run is logged
+/// and repaired by the next one, since each run overwrites its days.
+pub async fn run(state: Arc<AppState>, shutdown: CancellationToken) {
+    state.metrics.set_worker_status(WORKER_NAME, true);
+    loop {
+        match refresh(
+            &state.db,
+            &state.cache,
+            state.config.network_name(),
+            Utc::now(),
+        )
+        .await
+        {
+            Ok(n) => tracing::info!("[stats-history] rolled up {n} days"),
+            Err(e) => tracing::warn!("[stats-history] rollup error: {e}"),
+        }
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = tokio::time::sleep(next_run_delay(Utc::now())) => {}
+        }
+    }
+    state.metrics.set_worker_status(WORKER_NAME, false);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn values(history: &StatsHistory) -> Vec<(NaiveDate, &str)> {
+        history
+            .points
+            .iter()
+            .map(|p| (p.day, p.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn missing_days_are_explicit_zeros() {
+        let history = StatsHistory::new(
+            StatsMetric::Volume,
+            (day(2026, 2, 27), day(2026, 3, 2)),
+            vec![
+                (day(2026, 2, 28), "500".to_string()),
+                (day(2026, 3, 2), "7".to_string()),
+            ],
+            None,
+        );
+        assert_eq!(history.days, 4);
+        assert_eq!(
+            values(&history),
+            vec![
+                (day(2026, 2, 27), "0"),
+                (day(2026, 2, 28), "500"),
+                (day(2026, 3, 1), "0"),
+                (day(2026, 3, 2), "7"),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_days_keeps_the_newest_points() {
+        let history = StatsHistory::new(
+            StatsMetric::MarketsCreated,
+            (day(2026, 3, 1), day(2026, 3, 10)),
+            vec![(day(2026, 3, 10), "3".to_string())],
+            None,
+        )
+        .last_days(3);
+        assert_eq!(
+            (history.from, history.to, history.days),
+            (day(2026, 3, 8), day(2026, 3, 10), 3)
+        );
+        assert_eq!(history.points.last().unwrap().value, "3");
+
+        let all = StatsHistory::new(
+            StatsMetric::MarketsCreated,
+            (day(2026, 3, 1), day(2026, 3, 2)),
+            vec![],
+            None,
+        )
+        .last_days(MAX_HISTORY_DAYS);
+        assert_eq!(all.days, 2);
+    }
+
+    #[test]
+    fn next_run_is_the_coming_rollup_time() {
+        let at = |d, h, m| Utc.with_ymd_and_hms(2026, 3, d, h, m, 0).unwrap();
+        assert_eq!(next_run_delay(at(2, 0, 0)), Duration::from_secs(10 * 60));
+        assert_eq!(next_run_delay(at(2, 0, 10)), Duration::from_secs(24 * 3600));
+        assert_eq!(next_run_delay(at(2, 23, 0)), Duration::from_secs(70 * 60));
+    }
+
+    #[test]
+    fn metric_labels_round_trip_through_serde() {
+        for metric in StatsMetric::ALL {
+            assert_eq!(serde_json::to_value(metric).unwrap(), metric.label());
+            assert_eq!(metric.column(), metric.label());
+        }
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod stats_history_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use chrono::{NaiveDate, Utc};
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        cache::keys,
+        handlers::statistics_history,
+        stats_history::{StatsHistory, StatsMetric, MAX_HISTORY_DAYS},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded rows sit on days long before any real activity, and use
+    /// reserved market ids, event ids and addresses, so the rollup of those
+    /// days only sees what the test wrote.
+    const SEED_MARKET_IDS: [i64; 2] = [9301, 9302];
+    const SEED_EVENT_PREFIX: &str = "stats-history-test-";
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2001, 3, d).unwrap()
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/statistics/history", get(statistics_history))
+            .with_state(state)
+    }
+
+    async fn get_json(state: &Arc<crate::AppState>, uri: &str) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state))
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (
+            status,
+            serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Day 1: one market, bets of 100 and 50 from two new addresses.
+    /// Day 2: nothing.
+    /// Day 3: one market, a 25 bet from an address that bet on day 1.
+    async fn seed(state: &crate::AppState) {
+        cleanup(state).await;
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at) \
+             VALUES \
+                (9301, 'History A', 'resolved', 150, '2001-03-10', '2001-03-01 08:00:00+00'), \
+                (9302, 'History B', 'resolved', 25,  '2001-03-10', '2001-03-03 23:59:00+00')",
+        )
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO chain_events (id, network, ledger, kind, market_id, address, outcome, amount, indexed_at) \
+             VALUES \
+                ($1 || '1', $2, 1, 'bet_place', 9301, 'GSTATSHISTORYA', 0, 100, '2001-03-01 09:00:00+00'), \
+                ($1 || '2', $2, 2, 'bet_place', 9301, 'GSTATSHISTORYB', 1, 50,  '2001-03-01 10:00:00+00'), \
+                ($1 || '3', $2, 3, 'bet_place', 9302, 'GSTATSHISTORYA', 0, 25,  '2001-03-03 12:00:00+00')",
+        )
+        .bind(SEED_EVENT_PREFIX)
+        .bind(state.config.network_name())
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE $1 || '%'")
+            .bind(SEED_EVENT_PREFIX)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKET_IDS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM platform_stats_daily WHERE day BETWEEN $1 AND $2")
+            .bind(day(1))
+            .bind(day(3))
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    async fn series(state: &crate::AppState, metric: StatsMetric) -> Vec<(NaiveDate, String)> {
+        let (rows, _) = state
+            .db
+            .platform_stats_daily(metric, day(1), day(3))
+            .await
+            .unwrap();
+        rows
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Rolling the same days up twice overwrites them with the same values
+    /// instead of adding to them.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_rollup_is_idempotent() {
+        let state = build_test_state().await;
+        let network = state.config.network_name();
+        seed(&state).await;
+
+        let written = state
+            .db
+            .platform_stats_rollup(network, day(1), day(4))
+            .await
+            .unwrap();
+        assert_eq!(written, 3);
+        let markets = series(&state, StatsMetric::MarketsCreated).await;
+        let volume = series(&state, StatsMetric::Volume).await;
+        let participants = series(&state, StatsMetric::NewParticipants).await;
+
+        let written = state
+            .db
+            .platform_stats_rollup(network, day(1), day(4))
+            .await
+            .unwrap();
+        assert_eq!(written, 3);
+        assert_eq!(series(&state, StatsMetric::MarketsCreated).await, markets);
+        assert_eq!(series(&state, StatsMetric::Volume).await, volume);
+        assert_eq!(
+            series(&state, StatsMetric::NewParticipants).await,
+            participants
+        );
+
+        let values =
+            |rows: &[(NaiveDate, String)]| rows.iter().map(|(_, v)| v.clone()).collect::<Vec<_>>();
+        assert_eq!(values(&markets), ["1", "0", "1"]);
+        assert_eq!(values(&volume), ["150", "0", "25"]);
+        assert_eq!(
+            values(&participants),
+            ["2", "0", "0"],
+            "a repeat bettor is not new"
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// A day without activity is stored as an explicit zero row, and a day
+    /// missing from the table is still served as zero.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_gap_days_are_zero_rows() {
+        let state = build_test_state().await;
+        seed(&state).await;
+        state
+            .db
+            .platform_stats_rollup(state.config.network_name(), day(1), day(4))
+            .await
+            .unwrap();
+
+        let rows = series(&state, StatsMetric::Volume).await;
+        assert_eq!(rows[1], (day(2), "0".to_string()));
+
+        sqlx::query("DELETE FROM platform_stats_daily WHERE day = $1")
+            .bind(day(2))
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        let (rows, rolled_up_at) = state
+            .db
+            .platform_stats_daily(StatsMetric::Volume, day(1), day(3))
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        let history = StatsHistory::new(StatsMetric::Volume, (day(1), day(3)), rows, rolled_up_at);
+        let points: Vec<_> = history
+            .points
+            .iter()
+            .map(|p| (p.day, p.value.as_str()))
+            .collect();
+        assert_eq!(points, vec![(day(1), "150"), (day(2), "0"), (day(3), "25")]);
+
+        cleanup(&state).await;
+    }
+
+    /// `days` is capped at 365 and the series is one consecutive point per
+    /// day ending today; an unknown metric is a 400.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_endpoint_clamps_days_and_fills_every_day() {
+        let state = build_test_state().await;
+        state
+            .cache
+            .del(&keys::api_statistics_history(
+                StatsMetric::NewParticipants.label(),
+            ))
+            .await
+            .unwrap();
+
+        let (status, body) = get_json(
+            &state,
+            "/statistics/history?metric=new_participants&days=1000",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["metric"], "new_participants");
+        assert_eq!(body["days"], MAX_HISTORY_DAYS);
+        let points = body["points"].as_array().unwrap();
+        assert_eq!(points.len(), MAX_HISTORY_DAYS as usize);
+        assert_eq!(
+            points.last().unwrap()["day"],
+            Utc::now().date_naive().to_string()
+        );
+        let days: Vec<NaiveDate> = points
+            .iter()
+            .map(|p| p["day"].as_str().unwrap().parse().unwrap())
+            .collect();
+        assert!(days.windows(2).all(|w| w[1] == w[0].succ_opt().unwrap()));
+
+        let (status, body) =
+            get_json(&state, "/statistics/history?metric=new_participants&days=7").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["points"].as_array().unwrap().len(), 7);
+
+        let (status, _) = get_json(&state, "/statistics/history?metric=bogus").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+}
@@ -0,0 +1,245 @@
+//! Restart supervision for long-running background loops.
+//!
+//! [`TaskSupervisor::spawn`] runs a loop in its own task and restarts it if
+//! it panics or returns before its cancellation token fires, waiting a capped
+//! exponential backoff between attempts. Every restart is logged with the task
+//! name, counted in `background_task_restarts_total{task}`, and recorded in
+//! [`TaskSupervisor::snapshot`], which `/health/ready` reports so a
+//! crash-looping worker is visible without digging through logs.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::metrics::Metrics;
+
+/// Delay before the first restart; doubled for each consecutive failure.
+pub const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound on the delay between restarts. A run that lasts at least this
+/// long counts as healthy and resets the backoff.
+pub const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Restart history of one supervised task.
+#[derive(Debug, Clone, Default, Serialize, utoipa::ToSchema)]
+pub struct TaskStatus {
+    /// Restarts since the process started.
+    pub restarts: u64,
+    pub last_restart_at: Option<DateTime<Utc>>,
+    /// Panic message, or a note that the task returned early.
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Default)]
+pub struct TaskSupervisor {
+    metrics: Option<Metrics>,
+    status: Arc<Mutex<BTreeMap<String, TaskStatus>>>,
+    initial_backoff: Option<Duration>,
+    max_backoff: Option<Duration>,
+}
+
+impl TaskSupervisor {
+    pub fn new(metrics: Metrics) -> Self {
+        Self {
+            metrics: Some(metrics),
+            ..Self::default()
+        }
+    }
+
+    /// Override [`INITIAL_BACKOFF`] and [`MAX_BACKOFF`]; tests use this to
+    /// restart immediately.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = Some(initial);
+        self.max_backoff = Some(max);
+        self
+    }
+
+    /// Restart history of every task spawned so far, by name.
+    pub fn snapshot(&self) -> BTreeMap<String, TaskStatus> {
+        self.status.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Run `task` under supervision until `shutdown` is cancelled and the
+    /// current run has returned. `task` is called once per attempt and should
+    /// itself return promptly once `shutdown` fires.
+    pub fn spawn<F, Fut>(&self, name: impl Into<String>, shutdown: CancellationToken, mut task: F) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let name = name.into();
+        let supervisor = self.clone();
+        supervisor.record(&name, None);
+
+        tokio::spawn(async move {
+            let mut failures: u32 = 0;
+            loop {
+                let started = Instant::now();
+                let error = match tokio::spawn(task()).await {
+                    Ok(()) if shutdown.is_cancelled() => break,
+                    Ok(()) => "exited before shutdown".to_string(),
+                    Err(e) if e.is_panic() => format!("panicked: {}", panic_message(e.into_panic())),
+                    Err(e) => e.to_string(),
+                };
+
+                if started.elapsed() >= supervisor.max_backoff() {
+                    failures = 0;
+                }
+                failures += 1;
+                let backoff = supervisor.backoff(failures);
+                tracing::error!(
+                    task = %name,
+                    error = %error,
+                    consecutive_failures = failures,
+                    backoff_ms = backoff.as_millis() as u64,
+                    "background task failed — restarting"
+                );
+                supervisor.record(&name, Some(error));
+
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = shutdown.cancelled() => break,
+                }
+            }
+            tracing::info!(task = %name, "background task stopped");
+        })
+    }
+
+    fn max_backoff(&self) -> Duration {
+        self.max_backoff.unwrap_or(MAX_BACKOFF)
+    }
+
+    /// Delay before restart number `failures` (1-based) in a row.
+    fn backoff(&self, failures: u32) -> Duration {
+        let initial = self.initial_backoff.unwrap_or(INITIAL_BACKOFF);
+        initial
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max_backoff())
+    }
+
+    /// Register `name`, and count a restart when `error` is set.
+    fn record(&self, name: &str, error: Option<String>) {
+        let mut status = self.status.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = status.entry(name.to_string()).or_default();
+        if let Some(error) = error {
+            entry.restarts += 1;
+            entry.last_restart_at = Some(Utc::now());
+            entry.last_error = Some(error);
+            if let Some(metrics) = &self.metrics {
+                metrics.observe_background_task_restart(name);
+            }
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "non-string panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast(metrics: Metrics) -> TaskSupervisor {
+        TaskSupervisor::new(metrics).with_backoff(Duration::from_millis(1), Duration::from_millis(20))
+    }
+
+    #[tokio::test]
+    async fn panicking_task_is_restarted_and_counted() {
+        let metrics = Metrics::new().unwrap();
+        let supervisor = fast(metrics.clone());
+        let shutdown = CancellationToken::new();
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let handle = supervisor.spawn("flaky", shutdown.clone(), {
+            let attempts = attempts.clone();
+            let shutdown = shutdown.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                let shutdown = shutdown.clone();
+                async move {
+                    if attempt <= 2 {
+                        panic!("malformed event {attempt}");
+                    }
+                    shutdown.cancelled().await;
+                }
+            }
+        });
+
+        while attempts.load(Ordering::SeqCst) < 3 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        shutdown.cancel();
+        tokio::time::timeout(Duration::from_secs(1), handle).await.unwrap().unwrap();
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        let status = &supervisor.snapshot()["flaky"];
+        assert_eq!(status.restarts, 2);
+        assert!(status.last_restart_at.is_some());
+        assert_eq!(status.last_error.as_deref(), Some("panicked: malformed event 2"));
+        assert!(metrics
+            .render()
+            .unwrap()
+            .contains("background_task_restarts_total{task=\"flaky\"} 2"));
+    }
+
+    #[tokio::test]
+    async fn early_return_is_restarted_but_shutdown_return_is_not() {
+        let supervisor = fast(Metrics::new().unwrap());
+        let shutdown = CancellationToken::new();
+        let attempts = Arc::new(AtomicU32::new(0));
+
+        let handle = supervisor.spawn("returns", shutdown.clone(), {
+            let attempts = attempts.clone();
+            let shutdown = shutdown.clone();
+            move || {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                let shutdown = shutdown.clone();
+                async move {
+                    if attempt == 2 {
+                        shutdown.cancel();
+                    }
+                }
+            }
+        });
+
+        tokio::time::timeout(Duration::from_secs(1), handle).await.unwrap().unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(supervisor.snapshot()["returns"].restarts, 1);
+    }
+
+    #[tokio::test]
+    async fn registered_task_reports_zero_restarts() {
+        let supervisor = TaskSupervisor::default();
+        let shutdown = CancellationToken::new();
+        shutdown.cancel();
+        supervisor.spawn("idle", shutdown, || async {}).await.unwrap();
+        let status = &supervisor.snapshot()["idle"];
+        assert_eq!(status.restarts, 0);
+        assert!(status.last_restart_at.is_none());
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let supervisor = TaskSupervisor::default();
+        assert_eq!(supervisor.backoff(1), INITIAL_BACKOFF);
+        assert_eq!(supervisor.backoff(2), INITIAL_BACKOFF * 2);
+        assert_eq!(supervisor.backoff(4), INITIAL_BACKOFF * 8);
+        assert_eq!(supervisor.backoff(40), MAX_BACKOFF);
+    }
+}
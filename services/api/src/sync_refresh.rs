@@ -0,0 +1,366 @@
+//! Which watched markets the sync worker refreshes on a pass.
+//!
+//! Refreshing every watched market on every pass costs
+//! [`CALLS_PER_MARKET`] RPC calls each, whether anything happened to the
+//! market or not. Instead each market gets a [`RefreshTier`] from its
+//! [`MarketProfile`]: markets with recent events or close to their deadline
+//! are hot, other open markets active, and quiet resolved markets dormant.
+//! Hot and active markets are due every pass; dormant ones every
+//! `dormant_every` passes. Due markets then go through a priority queue —
+//! hot markets first, then the rest by how many passes they are overdue —
+//! until the per-pass call budget runs out. Whatever does not fit rolls over
+//! to the next pass, where it is further overdue and so ranks higher; a
+//! dormant market is not starved by open ones that are always due.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+
+/// RPC calls one market refresh makes: market data and oracle result.
+pub const CALLS_PER_MARKET: usize = 2;
+
+/// RPC calls the platform statistics refresh makes.
+pub const PLATFORM_STATS_CALLS: usize = 1;
+
+/// What the scheduler knows about a watched market, loaded with the watch
+/// set and updated as the sync worker indexes events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarketProfile {
+    pub market_id: i64,
+    pub resolved: bool,
+    pub ends_at: Option<DateTime<Utc>>,
+    /// When the latest event for the market was indexed on this network.
+    pub last_event_at: Option<DateTime<Utc>>,
+}
+
+impl MarketProfile {
+    /// A market with no row in `markets`, e.g. a `SYNC_MARKET_IDS` entry
+    /// created elsewhere. Treated as open, so it is refreshed every pass.
+    pub fn unknown(market_id: i64) -> Self {
+        Self {
+            market_id,
+            resolved: false,
+            ends_at: None,
+            last_event_at: None,
+        }
+    }
+}
+
+/// How urgently a market needs fresh chain data. Ordered so the most urgent
+/// tier is the greatest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RefreshTier {
+    /// Resolved with no recent events; refreshed every `dormant_every` passes.
+    Dormant,
+    /// Open but quiet; refreshed every pass the budget allows.
+    Active,
+    /// Recent events, or an open market near its deadline; refreshed first.
+    Hot,
+}
+
+/// Limits the sync worker's refreshes. Built from `SYNC_HOT_WINDOW_SECS`,
+/// `SYNC_DORMANT_REFRESH_EVERY` and `SYNC_CALL_BUDGET`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshPolicy {
+    /// How recent an event, or how close a deadline, makes a market hot.
+    pub hot_window: Duration,
+    /// Passes between refreshes of a dormant market.
+    pub dormant_every: u32,
+    /// Most RPC calls a pass may make; `0` means no limit.
+    pub call_budget: usize,
+}
+
+impl RefreshPolicy {
+    pub fn tier(&self, profile: &MarketProfile, now: DateTime<Utc>) -> RefreshTier {
+        let window = i64::try_from(self.hot_window.as_secs()).unwrap_or(i64::MAX);
+        let recent_event = profile
+            .last_event_at
+            .is_some_and(|at| (now - at).num_seconds() <= window);
+        if recent_event {
+            return RefreshTier::Hot;
+        }
+        if profile.resolved {
+            return RefreshTier::Dormant;
+        }
+        let near_deadline = profile
+            .ends_at
+            .is_some_and(|ends| (ends - now).num_seconds().abs() <= window);
+        if near_deadline {
+            RefreshTier::Hot
+        } else {
+            RefreshTier::Active
+        }
+    }
+}
+
+/// The refreshes chosen for one pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshPlan {
+    /// Markets to refresh, most urgent first.
+    pub markets: Vec<i64>,
+    pub platform_stats: bool,
+    /// RPC calls the plan makes, at most the budget.
+    pub calls: usize,
+    /// Due refreshes left for a later pass because the budget ran out.
+    pub skipped_budget: usize,
+    /// Dormant markets not yet due.
+    pub skipped_backoff: usize,
+}
+
+/// Remembers the pass each market was last refreshed on. One per network,
+/// kept by the sync worker across passes.
+#[derive(Debug, Default)]
+pub struct RefreshSchedule {
+    pass: u64,
+    last_refreshed: HashMap<i64, u64>,
+}
+
+impl RefreshSchedule {
+    /// Choose this pass's refreshes from the watch set `profiles` and count
+    /// them as done. The platform statistics go first; among markets, ties
+    /// go to the higher tier, then keep the watch set order.
+    pub fn plan(
+        &mut self,
+        profiles: &[MarketProfile],
+        policy: &RefreshPolicy,
+        now: DateTime<Utc>,
+    ) -> RefreshPlan {
+        let pass = self.pass;
+        self.pass += 1;
+        self.last_refreshed
+            .retain(|id, _| profiles.iter().any(|p| p.market_id == *id));
+
+        let mut plan = RefreshPlan::default();
+        let mut queue = BinaryHeap::with_capacity(profiles.len());
+        for (position, profile) in profiles.iter().enumerate() {
+            let tier = policy.tier(profile, now);
+            let interval = match tier {
+                RefreshTier::Dormant => u64::from(policy.dormant_every.max(1)),
+                RefreshTier::Active | RefreshTier::Hot => 1,
+            };
+            // Never refreshed counts as most overdue.
+            let staleness = self
+                .last_refreshed
+                .get(&profile.market_id)
+                .map_or(u64::MAX, |last| pass - last);
+            if staleness < interval {
+                plan.skipped_backoff += 1;
+                continue;
+            }
+            let overdue = staleness - interval;
+            queue.push((tier == RefreshTier::Hot, overdue, tier, Reverse(position)));
+        }
+
+        let mut remaining = match policy.call_budget {
+            0 => usize::MAX,
+            budget => budget,
+        };
+        if remaining >= PLATFORM_STATS_CALLS {
+            plan.platform_stats = true;
+            plan.calls += PLATFORM_STATS_CALLS;
+            remaining -= PLATFORM_STATS_CALLS;
+        } else {
+            plan.skipped_budget += 1;
+        }
+        while let Some((_, _, _, Reverse(position))) = queue.pop() {
+            if remaining < CALLS_PER_MARKET {
+                plan.skipped_budget += queue.len() + 1;
+                break;
+            }
+            let market_id = profiles[position].market_id;
+            plan.markets.push(market_id);
+            plan.calls += CALLS_PER_MARKET;
+            remaining -= CALLS_PER_MARKET;
+            self.last_refreshed.insert(market_id, pass);
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(call_budget: usize) -> RefreshPolicy {
+        RefreshPolicy {
+            hot_window: Duration::from_secs(3600),
+            dormant_every: 6,
+            call_budget,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn profile(
+        market_id: i64,
+        resolved: bool,
+        ends_in_hours: i64,
+        event_hours_ago: Option<i64>,
+    ) -> MarketProfile {
+        MarketProfile {
+            market_id,
+            resolved,
+            ends_at: Some(now() + chrono::Duration::hours(ends_in_hours)),
+            last_event_at: event_hours_ago.map(|h| now() - chrono::Duration::hours(h)),
+        }
+    }
+
+    #[test]
+    fn tiers_follow_events_deadlines_and_resolution() {
+        let p = policy(0);
+        assert_eq!(
+            p.tier(&profile(1, false, 48, Some(0)), now()),
+            RefreshTier::Hot
+        );
+        assert_eq!(
+            p.tier(&profile(2, false, 0, None), now()),
+            RefreshTier::Hot,
+            "deadline now"
+        );
+        assert_eq!(
+            p.tier(&profile(3, false, 48, Some(5)), now()),
+            RefreshTier::Active
+        );
+        assert_eq!(
+            p.tier(&profile(4, true, -48, Some(0)), now()),
+            RefreshTier::Hot,
+            "just resolved"
+        );
+        assert_eq!(
+            p.tier(&profile(5, true, 0, Some(5)), now()),
+            RefreshTier::Dormant,
+            "a resolved deadline is not hot"
+        );
+        assert_eq!(
+            p.tier(&MarketProfile::unknown(6), now()),
+            RefreshTier::Active
+        );
+    }
+
+    #[test]
+    fn no_budget_refreshes_everything_due() {
+        let profiles = vec![profile(1, false, 48, None), profile(2, true, -48, None)];
+        let mut schedule = RefreshSchedule::default();
+
+        let first = schedule.plan(&profiles, &policy(0), now());
+        assert_eq!(first.markets, vec![1, 2]);
+        assert!(first.platform_stats);
+        assert_eq!(first.calls, 5);
+
+        let second = schedule.plan(&profiles, &policy(0), now());
+        assert_eq!(second.markets, vec![1], "the resolved market backs off");
+        assert_eq!(second.skipped_backoff, 1);
+        assert_eq!(second.skipped_budget, 0);
+    }
+
+    #[test]
+    fn dormant_markets_refresh_every_n_passes() {
+        let profiles = vec![profile(1, true, -48, None)];
+        let mut schedule = RefreshSchedule::default();
+
+        let refreshed: Vec<bool> = (0..13)
+            .map(|_| {
+                !schedule
+                    .plan(&profiles, &policy(0), now())
+                    .markets
+                    .is_empty()
+            })
+            .collect();
+        let passes: Vec<usize> = refreshed
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| **r)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(passes, vec![0, 6, 12]);
+    }
+
+    #[test]
+    fn budget_rolls_over_to_the_stalest_markets() {
+        let profiles: Vec<MarketProfile> = (1..=4).map(|id| profile(id, false, 48, None)).collect();
+        let mut schedule = RefreshSchedule::default();
+
+        // Room for the platform stats and two markets.
+        let first = schedule.plan(&profiles, &policy(5), now());
+        assert_eq!(first.markets, vec![1, 2]);
+        assert_eq!(first.calls, 5);
+        assert_eq!(first.skipped_budget, 2);
+
+        let second = schedule.plan(&profiles, &policy(5), now());
+        assert_eq!(
+            second.markets,
+            vec![3, 4],
+            "skipped markets go first next pass"
+        );
+    }
+
+    #[test]
+    fn dropped_markets_are_forgotten() {
+        let mut schedule = RefreshSchedule::default();
+        schedule.plan(&[profile(1, true, -48, None)], &policy(0), now());
+        schedule.plan(&[], &policy(0), now());
+
+        let back = schedule.plan(&[profile(1, true, -48, None)], &policy(0), now());
+        assert_eq!(
+            back.markets,
+            vec![1],
+            "a market back in the watch set starts stale"
+        );
+    }
+
+    /// 50 markets: 5 with fresh events, 5 near their deadline, 20 quiet and
+    /// open, 20 resolved long ago. Every pass stays within the budget, hot
+    /// markets are refreshed on every pass, and the rest all get a turn.
+    #[test]
+    fn fifty_markets_respect_the_budget_and_keep_hot_markets_fresh() {
+        let mut profiles = Vec::new();
+        for id in 0..50 {
+            profiles.push(match id {
+                0..=4 => profile(id, false, 72, Some(0)),
+                5..=9 => profile(id, false, 0, None),
+                10..=29 => profile(id, false, 72, Some(24)),
+                _ => profile(id, true, -72, Some(48)),
+            });
+        }
+        let hot: Vec<i64> = (0..10).collect();
+        let budget = 41;
+        let mut schedule = RefreshSchedule::default();
+        let mut refreshes: HashMap<i64, usize> = HashMap::new();
+
+        for pass in 0..24 {
+            let plan = schedule.plan(&profiles, &policy(budget), now());
+            assert!(plan.calls <= budget, "pass {pass}: {} calls", plan.calls);
+            assert!(plan.platform_stats);
+            assert_eq!(
+                &plan.markets[..hot.len()],
+                hot.as_slice(),
+                "pass {pass}: hot markets first"
+            );
+            for id in plan.markets {
+                *refreshes.entry(id).or_default() += 1;
+            }
+        }
+
+        assert!(hot.iter().all(|id| refreshes[id] == 24));
+        for id in 10..50 {
+            assert!(
+                refreshes.get(&id).copied().unwrap_or(0) >= 2,
+                "market {id} starved"
+            );
+        }
+        let unlimited = RefreshSchedule::default().plan(&profiles, &policy(0), now());
+        assert_eq!(
+            unlimited.calls,
+            1 + 50 * CALLS_PER_MARKET,
+            "the first pass without a budget refreshes all"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod sync_watch_tests {
+    use std::time::Duration;
+
+    use crate::{
+        blockchain::{BlockchainClient, IndexedEvent},
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        metrics::Metrics,
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Larger than any volume a dev database is likely to hold, so seeded
+    /// markets sort first.
+    const TOP_VOLUME: f64 = 1e15;
+
+    async fn build_client(
+        configure: impl FnOnce(&mut Config),
+    ) -> (BlockchainClient, Database, Metrics) {
+        let mut config = Config::from_env();
+        configure(&mut config);
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        let db = Database::new(
+            config.db_credentials.to_connection_string().expose_secret(),
+            cache.clone(),
+            metrics.clone(),
+            &config.db_pool,
+        )
+        .await
+        .expect("db");
+        let client =
+            BlockchainClient::new(&config, cache, db.clone(), metrics.clone()).expect("blockchain");
+        (client, db, metrics)
+    }
+
+    fn unique_market_id() -> i64 {
+        (uuid::Uuid::new_v4().as_u128() % 1_000_000_000) as i64 + 2_000_000_000
+    }
+
+    async fn seed_market(db: &Database, market_id: i64, volume: f64) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at) \
+             VALUES ($1, 'Watch Test Market', 'active', $2, NOW() + INTERVAL '1 day')",
+        )
+        .bind(market_id)
+        .bind(volume)
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn resolve_market_at(db: &Database, market_id: i64, ago: Duration) {
+        sqlx::query(
+            "UPDATE markets SET status = 'resolved', \
+                    resolved_at = NOW() - make_interval(secs => $2) \
+             WHERE id = $1",
+        )
+        .bind(market_id)
+        .bind(ago.as_secs_f64())
+        .execute(&db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn delete_markets(db: &Database, ids: &[i64]) {
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Tests
+    // ---------------------------------------------------------------------------
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_resolved_market_drops_off_after_retention() {
+        let retention = Duration::from_secs(3600);
+        let (client, db, _) = build_client(|c| {
+            c.sync_market_ids = vec![];
+            c.sync_watch_max_markets = 1_000;
+            c.sync_watch_resolved_retention = retention;
+        })
+        .await;
+        let market_id = unique_market_id();
+        seed_market(&db, market_id, TOP_VOLUME).await;
+
+        let watched = client.refresh_market_watch_set().await.unwrap();
+        assert!(watched.contains(&market_id), "an active market is watched");
+
+        resolve_market_at(&db, market_id, Duration::from_secs(60)).await;
+        let watched = client.refresh_market_watch_set().await.unwrap();
+        assert!(
+            watched.contains(&market_id),
+            "a market resolved within the retention window is still watched"
+        );
+
+        resolve_market_at(&db, market_id, retention + Duration::from_secs(60)).await;
+        let watched = client.refresh_market_watch_set().await.unwrap();
+        assert!(
+            !watched.contains(&market_id),
+            "a market resolved before the retention window is dropped"
+        );
+
+        delete_markets(&db, &[market_id]).await;
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_watch_set_is_capped_by_volume_after_static_ids() {
+        let static_id = unique_market_id();
+        let (high, low) = (unique_market_id(), unique_market_id());
+        let (client, db, metrics) = build_client(|c| {
+            c.sync_market_ids = vec![static_id];
+            c.sync_watch_max_markets = 2;
+        })
+        .await;
+        seed_market(&db, high, TOP_VOLUME * 2.0).await;
+        seed_market(&db, low, TOP_VOLUME).await;
+
+        let watched = client.refresh_market_watch_set().await.unwrap();
+        assert_eq!(
+            watched,
+            vec![static_id, high],
+            "static ids come first, then the highest-volume markets up to the cap"
+        );
+
+        let rendered = metrics.render().unwrap();
+        assert!(
+            rendered.contains(&format!(
+                "blockchain_sync_watch_markets{{network=\"{}\"}} 2",
+                client.network()
+            )),
+            "watch set size is exported"
+        );
+
+        delete_markets(&db, &[high, low]).await;
+    }
+
+    /// Profiles carry status and deadline from `markets` and the latest
+    /// event indexed on the asking network only.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_market_profiles_track_latest_event_per_network() {
+        let (client, db, _) = build_client(|_| {}).await;
+        let (quiet, busy) = (unique_market_id(), unique_market_id());
+        seed_market(&db, quiet, 1.0).await;
+        seed_market(&db, busy, 1.0).await;
+        resolve_market_at(&db, quiet, Duration::from_secs(60)).await;
+        let network = format!("watchtest{}", uuid::Uuid::new_v4().simple());
+        let event = IndexedEvent {
+            id: format!("{busy}-{network}"),
+            ledger: 100,
+            tx_hash: None,
+            kind: "bet_place".into(),
+            market_id: Some(busy),
+            address: None,
+            outcome: Some(0),
+            amount: Some("10".into()),
+            token: None,
+            is_refund: false,
+        };
+        assert!(db
+            .chain_event_insert_at(&network, &event, None)
+            .await
+            .unwrap());
+
+        let profiles = db
+            .sync_market_profiles(&network, &[quiet, busy, unique_market_id()])
+            .await
+            .unwrap();
+        assert_eq!(profiles.len(), 2, "ids without a market row are left out");
+        let quiet_profile = profiles.iter().find(|p| p.market_id == quiet).unwrap();
+        assert!(quiet_profile.resolved);
+        assert_eq!(quiet_profile.last_event_at, None);
+        let busy_profile = profiles.iter().find(|p| p.market_id == busy).unwrap();
+        assert!(!busy_profile.resolved);
+        assert!(busy_profile.ends_at.is_some());
+        assert!(busy_profile.last_event_at.is_some());
+
+        let elsewhere = db
+            .sync_market_profiles(client.network(), &[busy])
+            .await
+            .unwrap();
+        assert_eq!(elsewhere[0].last_event_at, None, "events are per network");
+
+        db.chain_events_delete_above(&network, 0).await.unwrap();
+        delete_markets(&db, &[quiet, busy]).await;
+    }
+}
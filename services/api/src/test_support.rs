@@ -0,0 +1,75 @@
+//! Shared fixtures for the `*_tests.rs` modules.
+
+use std::sync::Arc;
+
+use secrecy::ExposeSecret;
+
+use crate::{
+    audit::AuditLogger,
+    blockchain::{BlockchainClient, NetworkClients},
+    cache::RedisCache,
+    config::Config,
+    db::Database,
+    email::{queue::EmailQueue, service::EmailService, webhook::WebhookHandler},
+    metrics::Metrics,
+    newsletter::IpRateLimiter,
+    AppState,
+};
+
+/// Builds an [`AppState`] from the environment, wired the way `main` wires
+/// it. Needs the PostgreSQL and Redis the environment points at.
+pub(crate) async fn build_test_state() -> Arc<AppState> {
+    build_test_state_with(Config::from_env(), &[]).await
+}
+
+/// [`build_test_state`] with a caller-adjusted `config`. Each name in
+/// `networks` gets its own blockchain client, the first being primary; with
+/// none, the single client keeps the configured network name.
+pub(crate) async fn build_test_state_with(config: Config, networks: &[&str]) -> Arc<AppState> {
+    let metrics = Metrics::new().expect("metrics");
+    let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+    let database_url = config.db_credentials.to_connection_string();
+    let db = Database::new(
+        database_url.expose_secret(),
+        cache.clone(),
+        metrics.clone(),
+        &config.db_pool,
+    )
+    .await
+    .expect("db")
+    .with_cache_ttls(config.cache_ttls.clone());
+
+    let client = || {
+        BlockchainClient::new(&config, cache.clone(), db.clone(), metrics.clone())
+            .expect("blockchain")
+    };
+    let networks = match networks.split_first() {
+        None => NetworkClients::single(client()),
+        Some((primary, rest)) => rest.iter().fold(
+            NetworkClients::single(client().with_network(primary)),
+            |clients, name| clients.with(client().with_network(name)).expect("networks"),
+        ),
+    };
+
+    let email_service = EmailService::new(config.clone()).expect("email_service");
+    let email_queue = EmailQueue::new(db.clone());
+    let webhook_handler =
+        WebhookHandler::new(db.clone(), cache.clone(), config.webhook_replay_window_secs);
+    let audit_logger = AuditLogger::new(db.pool());
+
+    Arc::new(AppState {
+        config,
+        cache: cache.clone(),
+        db,
+        networks,
+        metrics,
+        newsletter_rate_limiter: IpRateLimiter::new(cache),
+        email_service,
+        email_queue,
+        webhook_handler,
+        audit_logger,
+        readiness: Default::default(),
+        shutdown: Default::default(),
+        tasks: Default::default(),
+    })
+}
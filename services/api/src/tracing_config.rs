@@ -10,9 +10,23 @@ use opentelemetry_sdk::{
     Resource,
 };
 use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, SERVICE_VERSION};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{
+    fmt::{
+        format::Writer, FmtContext, FormatEvent, FormatFields, FormattedFields, MakeWriter,
+    },
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+    EnvFilter, Layer,
+};
 use url::Url;
 
+use crate::config::LogFormat;
+
 /// Validates that `raw` is a float in the closed interval [0.0, 1.0].
 /// Returns `Err` with a human-readable reason when the value is malformed or out of range.
 pub(crate) fn validate_sampler_arg(raw: &str) -> Result<f64, String> {
@@ -106,16 +120,118 @@ pub async fn check_otlp_connectivity(endpoint: &str) -> bool {
     }
 }
 
+/// JSON-lines log layer writing to `writer`: one object per event holding
+/// `timestamp`, `level`, `target`, the fields of every enclosing span
+/// (outermost first, so an inner span wins a name clash) and then the event's
+/// own fields. The built-in JSON format nests span fields under `span` /
+/// `spans`; flattening them puts the correlation `request_id` at the top level
+/// where the aggregator indexes it.
+pub fn json_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .event_format(FlattenedJson)
+        .with_writer(writer)
+}
+
+/// Event format for [`json_layer`]. Relies on the layer's `JsonFields`
+/// formatter having stored each span's fields as a JSON object.
+struct FlattenedJson;
+
+impl<S, N> FormatEvent<S, N> for FlattenedJson
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let meta = event.metadata();
+        let mut line = serde_json::Map::new();
+        line.insert(
+            "timestamp".into(),
+            chrono::Utc::now()
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+                .into(),
+        );
+        line.insert("level".into(), meta.level().as_str().into());
+        line.insert("target".into(), meta.target().into());
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                    if let Ok(serde_json::Value::Object(fields)) =
+                        serde_json::from_str::<serde_json::Value>(&fields.fields)
+                    {
+                        line.extend(fields);
+                    }
+                }
+            }
+        }
+        event.record(&mut JsonVisitor(&mut line));
+
+        // Work spawned off a request loses its span but not always the task
+        // local; fall back to it so the line can still be correlated.
+        if !line.contains_key("request_id") {
+            if let Some(id) = crate::correlation::current_request_id() {
+                line.insert("request_id".into(), id.into());
+            }
+        }
+
+        writeln!(writer, "{}", serde_json::Value::Object(line))
+    }
+}
+
+/// Records event fields into a JSON object, keeping numbers and booleans
+/// typed and formatting everything else as a string.
+struct JsonVisitor<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
 /// Initialize distributed tracing with OpenTelemetry.
 ///
 /// Validates `otlp_endpoint` as a parseable URL; returns an error immediately
 /// if the value is set but cannot be parsed (prevents silent misconfiguration).
 /// A separate TCP reachability check is available via [`check_otlp_connectivity`].
+/// Log lines go to stdout as text or, with [`LogFormat::Json`], through
+/// [`json_layer`].
 pub fn init_tracing(
     service_name: &str,
     service_version: &str,
     otlp_endpoint: Option<String>,
     sample_rate: f64,
+    log_format: LogFormat,
 ) -> anyhow::Result<()> {
     // Fail fast on an unparseable endpoint URL so the error surfaces at startup
     // rather than silently at the first export attempt.
@@ -129,10 +245,6 @@ pub fn init_tracing(
         })?;
     }
 
-    // OTel standard env vars take precedence over the passed-in rate.
-    // OTEL_TRACES_SAMPLER / OTEL_TRACES_SAMPLER_ARG default to 10 % for production.
-    let sample_rate = sample_rate_from_env(sample_rate);
-
     // Create resource with service information
     let resource = Resource::new(vec![
         KeyValue::new(SERVICE_NAME, service_name.to_string()),
@@ -143,14 +255,7 @@ pub fn init_tracing(
     // OTEL_TRACES_SAMPLER_ARG overrides the configured rate when present and valid.
     let sample_rate = resolve_sampler_rate(sample_rate);
 
-    // Configure sampler based on sample rate
-    let sampler = if sample_rate >= 1.0 {
-        Sampler::AlwaysOn
-    } else if sample_rate <= 0.0 {
-        Sampler::AlwaysOff
-    } else {
-        Sampler::TraceIdRatioBased(sample_rate)
-    };
+    let sampler = sampler_for(sample_rate);
 
     // Build tracer provider
     let tracer_provider = if let Some(ref endpoint) = otlp_endpoint {
@@ -200,20 +305,28 @@ pub fn init_tracing(
     let telemetry_layer = tracing_opentelemetry::layer()
         .with_tracer(tracer_provider.tracer(service_name.to_string()));
 
+    let (text_logs, json_logs) = match log_format {
+        LogFormat::Text => (Some(tracing_subscriber::fmt::layer()), None),
+        LogFormat::Json => (None, Some(json_layer(std::io::stdout))),
+    };
+
     // Initialize tracing subscriber with OpenTelemetry layer
     tracing_subscriber::registry()
         .with(EnvFilter::new(
             std::env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
         ))
-        .with(tracing_subscriber::fmt::layer())
+        .with(text_logs)
+        .with(json_logs)
         .with(telemetry_layer)
-        .init();
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("tracing subscriber already installed: {e}"))?;
 
     tracing::info!(
         service_name = service_name,
         service_version = service_version,
         sample_rate = sample_rate,
         otlp_endpoint = otlp_endpoint.as_deref().unwrap_or("none"),
+        log_format = ?log_format,
         "Distributed tracing initialized"
     );
 
@@ -221,6 +334,18 @@ pub fn init_tracing(
 }
 
 
+/// Sampler for a resolved `sample_rate`: always on at 1.0 or above, always
+/// off at 0.0 or below, trace-id ratio in between.
+fn sampler_for(sample_rate: f64) -> Sampler {
+    if sample_rate >= 1.0 {
+        Sampler::AlwaysOn
+    } else if sample_rate <= 0.0 {
+        Sampler::AlwaysOff
+    } else {
+        Sampler::TraceIdRatioBased(sample_rate)
+    }
+}
+
 /// Shutdown tracing and flush remaining spans
 pub fn shutdown_tracing() {
     tracing::info!("Shutting down tracing");
@@ -281,19 +406,16 @@ mod tests {
 
     #[test]
     fn test_sampler_configuration() {
-        // Test always on
-        let result = init_tracing("test-service", "0.1.0", None, 1.0);
-        assert!(result.is_ok());
-        shutdown_tracing();
-
-        // Test always off
-        let result = init_tracing("test-service", "0.1.0", None, 0.0);
-        assert!(result.is_ok());
-        shutdown_tracing();
+        assert!(matches!(sampler_for(1.0), Sampler::AlwaysOn));
+        assert!(matches!(sampler_for(0.0), Sampler::AlwaysOff));
+        assert!(matches!(sampler_for(0.5), Sampler::TraceIdRatioBased(r) if r == 0.5));
+    }
 
-        // Test ratio-based
-        let result = init_tracing("test-service", "0.1.0", None, 0.5);
-        assert!(result.is_ok());
+    /// A second install reports an error instead of panicking.
+    #[test]
+    fn repeated_init_does_not_panic() {
+        let _ = init_tracing("test-service", "0.1.0", None, 1.0, LogFormat::Text);
+        assert!(init_tracing("test-service", "0.1.0", None, 1.0, LogFormat::Json).is_err());
         shutdown_tracing();
     }
 
@@ -304,6 +426,7 @@ mod tests {
             "0.1.0",
             Some("not a valid url :::".to_string()),
             0.1,
+            LogFormat::Text,
         );
         assert!(result.is_err());
         let msg = format!("{}", result.unwrap_err());
@@ -313,14 +436,17 @@ mod tests {
         );
     }
 
-    #[test]
-    fn valid_otlp_url_passes_parse_check() {
+    // The batch exporter's shutdown blocks on its own task, which a
+    // current-thread runtime can never poll.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn valid_otlp_url_passes_parse_check() {
         // URL parsing only — no TCP connection is attempted in init_tracing
         let result = init_tracing(
             "test-service",
             "0.1.0",
             Some("http://localhost:4317".to_string()),
             0.0,
+            LogFormat::Text,
         );
         // init may fail later (e.g. already-initialized subscriber) but must not
         // fail at URL validation, so we only check it's NOT a URL-parse error.
@@ -334,6 +460,56 @@ mod tests {
         shutdown_tracing();
     }
 
+    /// `MakeWriter` collecting everything the layer writes, for assertions.
+    #[derive(Clone, Default)]
+    struct Captured(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for Captured {
+        type Writer = Captured;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn json_mode_writes_parseable_lines_with_masked_email_and_flattened_span() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry().with(json_layer(captured.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "req-42", method = "POST");
+            let _entered = span.enter();
+            tracing::info!(
+                email = %crate::log_redact::mask_email("alice@example.com"),
+                attempts = 3,
+                "newsletter subscription attempt"
+            );
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.contains("alice@"), "raw email leaked into logs: {output}");
+        let line: serde_json::Value = serde_json::from_str(output.trim()).expect("one JSON line");
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["message"], "newsletter subscription attempt");
+        assert_eq!(line["email"], "a***@example.com");
+        assert_eq!(line["attempts"], 3);
+        assert_eq!(line["request_id"], "req-42");
+        assert_eq!(line["method"], "POST");
+        assert!(line.get("span").is_none() && line.get("spans").is_none());
+    }
+
     // ── validate_sampler_arg ──────────────────────────────────────────────────
 
     #[test]
@@ -411,5 +587,4 @@ mod tests {
         std::env::remove_var("OTEL_TRACES_SAMPLER_ARG");
         assert_eq!(rate, 0.5);
     }
-    }
 }
@@ -0,0 +1,398 @@
+//! Notifications when a watched transaction finalizes.
+//!
+//! `POST /api/v1/blockchain/tx/:hash/watch` stores a [`TxSubscription`] with a
+//! callback URL, an email address, or both, and registers the hash with the
+//! transaction monitor. However many subscriptions point at a hash, the
+//! monitor polls it once. When it sees a final status it calls
+//! [`notify_subscribers`], which claims every open subscription for the hash
+//! and, for each one, POSTs a signed [`TxFinalized`] payload to the callback
+//! URL and/or enqueues a `tx_finalized` email. A claim is a single attempt: a
+//! failed delivery is logged, not retried. Subscriptions expire
+//! [`SUBSCRIPTION_TTL_HOURS`] after creation and are deleted on the monitor's
+//! heartbeat; a hash is only polled for `WATCHED_TX_TTL_SECS`, so a
+//! transaction that never lands in that window notifies no one.
+//!
+//! Each subscription has its own secret, returned once when it is created.
+//! [`SIGNATURE_HEADER`] carries the base64 HMAC-SHA256 of the raw webhook
+//! body under that secret.
+
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    alerting::WebhookDispatcher,
+    blockchain::TransactionStatus,
+    db::Database,
+    email::{queue::EmailQueue, types::EmailJobType},
+    security::signing,
+};
+
+/// How long a subscription waits for its transaction to finalize.
+pub const SUBSCRIPTION_TTL_HOURS: i64 = 24;
+
+/// Template of the finalization email.
+pub const TX_FINALIZED_TEMPLATE: &str = "tx_finalized";
+
+/// Header carrying the webhook body's signature.
+pub const SIGNATURE_HEADER: &str = "X-PredictIQ-Signature";
+
+/// Longest accepted callback URL.
+pub const MAX_CALLBACK_URL_LEN: usize = 2048;
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TxSubscription {
+    pub id: Uuid,
+    pub network: String,
+    pub tx_hash: String,
+    pub callback_url: Option<String>,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// An open subscription claimed for delivery by the monitor.
+#[derive(Debug, Clone)]
+pub struct ClaimedSubscription {
+    pub id: Uuid,
+    pub callback_url: Option<String>,
+    pub email: Option<String>,
+    pub secret: String,
+}
+
+/// Webhook body sent when a watched transaction finalizes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TxFinalized {
+    pub subscription_id: Uuid,
+    pub tx_hash: String,
+    pub network: String,
+    /// Final RPC status: `SUCCESS` or `FAILED`.
+    pub status: String,
+    /// Ledger the transaction was applied in, when the RPC reported one.
+    pub ledger: Option<u32>,
+    pub finalized_at: DateTime<Utc>,
+}
+
+impl TxFinalized {
+    pub fn new(
+        subscription_id: Uuid,
+        network: &str,
+        status: &TransactionStatus,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            subscription_id,
+            tx_hash: status.hash.clone(),
+            network: network.to_string(),
+            status: status.status.clone(),
+            ledger: status.ledger,
+            finalized_at: now,
+        }
+    }
+}
+
+/// A fresh webhook signing secret: 64 hex characters.
+pub fn new_secret() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// The normalised callback URL, or why it is refused. Callbacks are fetched
+/// from inside the deployment, so only `https` is accepted and hosts that are
+/// loopback, private, or link-local address literals are refused.
+pub fn check_callback_url(raw: &str) -> Result<String, &'static str> {
+    if raw.len() > MAX_CALLBACK_URL_LEN {
+        return Err("callback_url is too long");
+    }
+    let url = url::Url::parse(raw.trim()).map_err(|_| "callback_url must be a valid URL")?;
+    if url.scheme() != "https" {
+        return Err("callback_url must use https");
+    }
+    let internal = match url.host() {
+        None => return Err("callback_url must have a host"),
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Some(url::Host::Ipv4(ip)) => is_internal_ip(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => is_internal_ip(IpAddr::V6(ip)),
+    };
+    if internal {
+        return Err("callback_url must not point at a local or private address");
+    }
+    Ok(url.to_string())
+}
+
+fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_internal_ip(IpAddr::V4(v4)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // Unique local (fc00::/7) and link-local (fe80::/10).
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+/// The serialized webhook body and its signature under `secret`.
+pub fn signed_body(payload: &TxFinalized, secret: &str) -> anyhow::Result<(Vec<u8>, String)> {
+    let body = serde_json::to_vec(payload)?;
+    let signature = signing::generate_signature(&body, secret)?;
+    Ok((body, signature))
+}
+
+/// Template data for the `tx_finalized` email.
+pub fn email_data(payload: &TxFinalized) -> Value {
+    json!({
+        "tx_hash": payload.tx_hash,
+        "network": payload.network,
+        "status": payload.status,
+        "is_success": payload.status == "SUCCESS",
+        "ledger": payload.ledger.map(|ledger| ledger.to_string()).unwrap_or_default(),
+        "subscription_id": payload.subscription_id,
+    })
+}
+
+/// POST `payload` to `url`, signed with the subscription's `secret`.
+pub async fn deliver_webhook(
+    webhooks: &WebhookDispatcher,
+    url: &str,
+    payload: &TxFinalized,
+    secret: &str,
+) -> anyhow::Result<()> {
+    let (body, signature) = signed_body(payload, secret)?;
+    webhooks
+        .post(url, body, &[(SIGNATURE_HEADER, &signature)])
+        .await
+}
+
+/// Claim every open subscription to `status.hash` and notify each one;
+/// returns how many webhooks were delivered and emails enqueued. Without a
+/// `webhooks` dispatcher, callback subscriptions are claimed but not called.
+pub async fn notify_subscribers(
+    db: &Database,
+    queue: &EmailQueue,
+    webhooks: Option<&WebhookDispatcher>,
+    network: &str,
+    status: &TransactionStatus,
+) -> anyhow::Result<usize> {
+    let claimed = db
+        .tx_watch_subscriptions_claim(network, &status.hash)
+        .await?;
+    let now = Utc::now();
+
+    let mut sent = 0;
+    for subscription in claimed {
+        let payload = TxFinalized::new(subscription.id, network, status, now);
+        if let Some(url) = &subscription.callback_url {
+            let delivered = match webhooks {
+                Some(webhooks) => {
+                    deliver_webhook(webhooks, url, &payload, &subscription.secret).await
+                }
+                None => Err(anyhow::anyhow!("webhook dispatcher unavailable")),
+            };
+            match delivered {
+                Ok(()) => sent += 1,
+                Err(e) => tracing::warn!(
+                    subscription_id = %subscription.id,
+                    tx_hash = %status.hash,
+                    error = %e,
+                    "failed to deliver transaction webhook"
+                ),
+            }
+        }
+        if let Some(email) = &subscription.email {
+            match queue
+                .enqueue(
+                    EmailJobType::TxFinalized,
+                    email,
+                    TX_FINALIZED_TEMPLATE,
+                    email_data(&payload),
+                    0,
+                )
+                .await
+            {
+                Ok(_) => sent += 1,
+                Err(e) => tracing::warn!(
+                    subscription_id = %subscription.id,
+                    tx_hash = %status.hash,
+                    error = %e,
+                    "failed to enqueue transaction email"
+                ),
+            }
+        }
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+    use tokio::sync::Mutex;
+
+    use super::*;
+    use crate::blockchain::DataSource;
+
+    fn status(status: &str, ledger: Option<u32>) -> TransactionStatus {
+        TransactionStatus {
+            hash: "ab".repeat(32),
+            status: status.to_string(),
+            ledger,
+            error: None,
+            source: DataSource::Live,
+        }
+    }
+
+    /// A local endpoint recording each body with its signature header.
+    async fn start_receiver() -> (String, Arc<Mutex<Vec<(Bytes, Option<String>)>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let sink = sink.clone();
+                async move {
+                    let signature = headers
+                        .get(SIGNATURE_HEADER)
+                        .and_then(|v| v.to_str().ok())
+                        .map(str::to_string);
+                    sink.lock().await.push((body, signature));
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, received)
+    }
+
+    #[tokio::test]
+    async fn webhook_body_is_signed_with_the_subscription_secret() {
+        let (url, received) = start_receiver().await;
+        let secret = new_secret();
+        let payload = TxFinalized::new(
+            Uuid::new_v4(),
+            "testnet",
+            &status("SUCCESS", Some(51234)),
+            Utc::now(),
+        );
+
+        deliver_webhook(&WebhookDispatcher::new().unwrap(), &url, &payload, &secret)
+            .await
+            .unwrap();
+
+        let received = received.lock().await;
+        assert_eq!(received.len(), 1);
+        let (body, signature) = &received[0];
+        let signature = signature.as_deref().expect("signature header");
+        assert!(signing::verify_signature(body, signature, &secret));
+        assert!(!signing::verify_signature(body, signature, &new_secret()));
+
+        let sent: TxFinalized = serde_json::from_slice(body).unwrap();
+        assert_eq!(sent, payload);
+        assert_eq!(sent.ledger, Some(51234));
+    }
+
+    #[test]
+    fn tampered_body_fails_verification() {
+        let secret = new_secret();
+        let payload = TxFinalized::new(
+            Uuid::new_v4(),
+            "testnet",
+            &status("FAILED", Some(7)),
+            Utc::now(),
+        );
+        let (body, signature) = signed_body(&payload, &secret).unwrap();
+        assert!(signing::verify_signature(&body, &signature, &secret));
+
+        let tampered = String::from_utf8(body)
+            .unwrap()
+            .replace("FAILED", "SUCCESS");
+        assert!(!signing::verify_signature(
+            tampered.as_bytes(),
+            &signature,
+            &secret
+        ));
+    }
+
+    #[test]
+    fn secrets_are_unique_and_long() {
+        let (a, b) = (new_secret(), new_secret());
+        assert_eq!(a.len(), 64);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn email_data_carries_status_and_ledger() {
+        let payload = TxFinalized::new(
+            Uuid::new_v4(),
+            "mainnet",
+            &status("SUCCESS", Some(99)),
+            Utc::now(),
+        );
+        let data = email_data(&payload);
+        assert_eq!(data["status"], "SUCCESS");
+        assert_eq!(data["is_success"], true);
+        assert_eq!(data["ledger"], "99");
+        assert_eq!(data["network"], "mainnet");
+
+        let payload = TxFinalized::new(
+            Uuid::new_v4(),
+            "mainnet",
+            &status("FAILED", None),
+            Utc::now(),
+        );
+        let data = email_data(&payload);
+        assert_eq!(data["is_success"], false);
+        assert_eq!(data["ledger"], "");
+    }
+
+    #[test]
+    fn callback_urls_must_be_public_https() {
+        assert_eq!(
+            check_callback_url("https://hooks.example.com/tx?id=1").as_deref(),
+            Ok("https://hooks.example.com/tx?id=1")
+        );
+        for refused in [
+            "http://hooks.example.com/tx",
+            "ftp://hooks.example.com/tx",
+            "not a url",
+            "https://localhost/tx",
+            "https://api.localhost./tx",
+            "https://127.0.0.1/tx",
+            "https://10.1.2.3/tx",
+            "https://192.168.0.10/tx",
+            "https://169.254.169.254/latest/meta-data",
+            "https://0.0.0.0/tx",
+            "https://[::1]/tx",
+            "https://[fd00::1]/tx",
+            "https://[fe80::1]/tx",
+            "https://[::ffff:10.0.0.1]/tx",
+        ] {
+            assert!(check_callback_url(refused).is_err(), "{refused}");
+        }
+        let long = format!(
+            "https://hooks.example.com/{}",
+            "a".repeat(MAX_CALLBACK_URL_LEN)
+        );
+        assert!(check_callback_url(&long).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tx_watch_tests {
+    use std::sync::Arc;
+
+    use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+    use chrono::{Duration, Utc};
+    use tokio::sync::Mutex;
+
+    use crate::{
+        alerting::WebhookDispatcher,
+        blockchain::{DataSource, TransactionStatus},
+        cache::RedisCache,
+        config::Config,
+        db::Database,
+        email::queue::EmailQueue,
+        metrics::Metrics,
+        security::signing,
+        tx_watch::{self, TxFinalized, SIGNATURE_HEADER},
+    };
+    use secrecy::ExposeSecret;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// A network name no real monitor uses, so cleanup only touches rows
+    /// created here.
+    const NETWORK: &str = "tx-watch-test";
+
+    async fn build_db() -> Database {
+        let config = Config::from_env();
+        let metrics = Metrics::new().expect("metrics");
+        let cache = RedisCache::new(&config.redis_url).await.expect("redis");
+        Database::new(config.db_credentials.to_connection_string().expose_secret(), cache, metrics, &config.db_pool)
+            .await
+            .expect("db")
+    }
+
+    async fn cleanup(db: &Database) {
+        sqlx::query("DELETE FROM tx_watch_subscriptions WHERE network = $1")
+            .bind(NETWORK)
+            .execute(&db.pool())
+            .await
+            .unwrap();
+    }
+
+    fn finalized(hash: &str) -> TransactionStatus {
+        TransactionStatus {
+            hash: hash.to_string(),
+            status: "SUCCESS".to_string(),
+            ledger: Some(4242),
+            error: None,
+            source: DataSource::Live,
+        }
+    }
+
+    /// A local endpoint recording each body with its signature header.
+    async fn start_receiver() -> (String, Arc<Mutex<Vec<(Bytes, String)>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let sink = sink.clone();
+                async move {
+                    let signature = headers
+                        .get(SIGNATURE_HEADER)
+                        .and_then(|v| v.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    sink.lock().await.push((body, signature));
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, received)
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require a migrated PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Three subscribers to one hash get one signed webhook each from a single
+    /// finalization, and a second finalization of the hash notifies no one.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_each_subscriber_is_notified_once() {
+        let db = build_db().await;
+        cleanup(&db).await;
+        let (url, received) = start_receiver().await;
+        let hash = "a1".repeat(32);
+        let expires_at = Utc::now() + Duration::hours(tx_watch::SUBSCRIPTION_TTL_HOURS);
+
+        let mut secrets = Vec::new();
+        for _ in 0..3 {
+            let secret = tx_watch::new_secret();
+            db.tx_watch_subscription_insert(NETWORK, &hash, Some(&url), None, &secret, expires_at)
+                .await
+                .unwrap();
+            secrets.push(secret);
+        }
+
+        let queue = EmailQueue::new(db.clone());
+        let webhooks = WebhookDispatcher::new().unwrap();
+        let sent =
+            tx_watch::notify_subscribers(&db, &queue, Some(&webhooks), NETWORK, &finalized(&hash))
+                .await
+                .unwrap();
+        assert_eq!(sent, 3);
+
+        let received = received.lock().await.clone();
+        assert_eq!(received.len(), 3);
+        for (body, signature) in &received {
+            let payload: TxFinalized = serde_json::from_slice(body).unwrap();
+            assert_eq!(payload.tx_hash, hash);
+            assert_eq!(payload.status, "SUCCESS");
+            assert_eq!(payload.ledger, Some(4242));
+            assert!(
+                secrets
+                    .iter()
+                    .any(|secret| signing::verify_signature(body, signature, secret)),
+                "signed with a subscription secret"
+            );
+        }
+
+        let again =
+            tx_watch::notify_subscribers(&db, &queue, Some(&webhooks), NETWORK, &finalized(&hash))
+                .await
+                .unwrap();
+        assert_eq!(again, 0);
+
+        cleanup(&db).await;
+    }
+
+    /// Expired subscriptions are never claimed and are removed by cleanup;
+    /// live ones stay.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_expired_subscriptions_are_cleaned_up() {
+        let db = build_db().await;
+        cleanup(&db).await;
+        let hash = "b2".repeat(32);
+        let email = Some("tx-watch-test@example.com");
+
+        let expired = db
+            .tx_watch_subscription_insert(
+                NETWORK,
+                &hash,
+                None,
+                email,
+                &tx_watch::new_secret(),
+                Utc::now() - Duration::minutes(1),
+            )
+            .await
+            .unwrap();
+        let live = db
+            .tx_watch_subscription_insert(
+                NETWORK,
+                &hash,
+                None,
+                email,
+                &tx_watch::new_secret(),
+                Utc::now() + Duration::hours(1),
+            )
+            .await
+            .unwrap();
+
+        let removed = db.tx_watch_subscriptions_delete_expired().await.unwrap();
+        assert!(removed >= 1);
+
+        let ids: Vec<uuid::Uuid> =
+            sqlx::query_scalar("SELECT id FROM tx_watch_subscriptions WHERE network = $1")
+                .bind(NETWORK)
+                .fetch_all(&db.pool())
+                .await
+                .unwrap();
+        assert_eq!(ids, vec![live.id]);
+        assert_ne!(expired.id, live.id);
+
+        let claimed = db
+            .tx_watch_subscriptions_claim(NETWORK, &hash)
+            .await
+            .unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].id, live.id);
+
+        cleanup(&db).await;
+    }
+}
@@ -0,0 +1,349 @@
+//! Email notifications about a wallet's own positions.
+//!
+//! `PUT /api/v1/users/:address/notifications` stores one
+//! [`NotificationSettings`] row per Stellar account: an email address and a
+//! toggle per [`NotificationKind`]. The email is not used until it has been
+//! verified through a confirmation link, issued and redeemed the same way as
+//! a newsletter confirmation. The sync worker hands every indexed event to
+//! [`notify_position_holders`], which looks up the addresses holding
+//! positions in the event's market and enqueues one `position_update` email
+//! per opted-in, verified holder. Each (address, event) pair is claimed in
+//! `user_notification_sends` before the job is enqueued, so a holder gets at
+//! most one email per event and replays never send a second.
+//!
+//! Notifications only describe the recipient's own market: the template data
+//! carries no other participant's address, stake, or email.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    blockchain::{IndexedEvent, EVENT_DISPUTE_FILED, EVENT_MARKET_RESOLVED},
+    db::Database,
+    email::{queue::EmailQueue, types::EmailJobType},
+};
+
+/// Template used for every position notification.
+pub const POSITION_UPDATE_TEMPLATE: &str = "position_update";
+
+/// Template carrying the email verification link.
+pub const EMAIL_CONFIRMATION_TEMPLATE: &str = "notification_email_confirmation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// A market the address bet on resolved.
+    MarketResolved,
+    /// A market the address bet on resolved in its favour; sent instead of
+    /// `market_resolved` to holders of the winning outcome.
+    ClaimAvailable,
+    /// A dispute was filed against a market the address bet on.
+    DisputeFiled,
+}
+
+impl NotificationKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MarketResolved => "market_resolved",
+            Self::ClaimAvailable => "claim_available",
+            Self::DisputeFiled => "dispute_filed",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "market_resolved" => Some(Self::MarketResolved),
+            "claim_available" => Some(Self::ClaimAvailable),
+            "dispute_filed" => Some(Self::DisputeFiled),
+            _ => None,
+        }
+    }
+}
+
+/// What an indexed event means for the holders of its market.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionEvent {
+    Resolved { outcome: u32 },
+    Disputed,
+}
+
+impl PositionEvent {
+    /// The position event `event` is, if any.
+    pub fn for_event(event: &IndexedEvent) -> Option<Self> {
+        match (event.kind.as_str(), event.outcome) {
+            (EVENT_MARKET_RESOLVED, Some(outcome)) => Some(Self::Resolved { outcome }),
+            (EVENT_DISPUTE_FILED, _) => Some(Self::Disputed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct NotificationSettings {
+    /// Stellar account (`G...`) the settings belong to.
+    pub address: String,
+    pub email: Option<String>,
+    /// Whether `email` has been confirmed. Nothing is sent until it has.
+    pub email_verified: bool,
+    pub market_resolved: bool,
+    pub claim_available: bool,
+    pub dispute_filed: bool,
+    /// Hide the address's creator profile: `GET /api/v1/creators/:address`
+    /// answers 404 and the address is left out of the top creators.
+    pub hide_creator_profile: bool,
+    /// `None` until the address first saves its settings.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl NotificationSettings {
+    /// Settings of an address that has never saved any: no email, every
+    /// notification off.
+    pub fn defaults(address: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            email: None,
+            email_verified: false,
+            market_resolved: false,
+            claim_available: false,
+            dispute_filed: false,
+            hide_creator_profile: false,
+            updated_at: None,
+        }
+    }
+}
+
+/// Replacement settings. Changing `email` (or clearing it) resets
+/// verification.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct NotificationSettingsUpdate {
+    pub email: Option<String>,
+    #[serde(default)]
+    pub market_resolved: bool,
+    #[serde(default)]
+    pub claim_available: bool,
+    #[serde(default)]
+    pub dispute_filed: bool,
+    #[serde(default)]
+    pub hide_creator_profile: bool,
+}
+
+/// A holder whose notification for one event has just been claimed.
+#[derive(Debug, Clone)]
+pub struct PositionRecipient {
+    pub address: String,
+    pub email: String,
+    pub kind: NotificationKind,
+    pub unsubscribe_token: String,
+    pub market_title: String,
+    pub outcome_options: Vec<String>,
+}
+
+/// A fresh opaque token, used both for email confirmation and unsubscribe.
+pub fn new_token() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// Link that verifies a notification email.
+pub fn confirm_url(base_url: &str, token: &str) -> String {
+    format!(
+        "{}/api/v1/notifications/confirm?token={token}",
+        base_url.trim_end_matches('/')
+    )
+}
+
+/// Template data for the `position_update` email. Only the recipient's own
+/// market is described. `event_id` scopes the queue's idempotency key to this
+/// event.
+pub fn notification_data(
+    base_url: &str,
+    event: &IndexedEvent,
+    market_id: i64,
+    recipient: &PositionRecipient,
+) -> Value {
+    let base_url = base_url.trim_end_matches('/');
+    let outcome_label = match (recipient.kind, event.outcome) {
+        (NotificationKind::DisputeFiled, _) | (_, None) => String::new(),
+        (_, Some(index)) => recipient
+            .outcome_options
+            .get(index as usize)
+            .cloned()
+            .unwrap_or_else(|| format!("Outcome {index}")),
+    };
+    json!({
+        "market_id": market_id,
+        "market_title": recipient.market_title,
+        "kind": recipient.kind.label(),
+        "is_claim": recipient.kind == NotificationKind::ClaimAvailable,
+        "is_dispute": recipient.kind == NotificationKind::DisputeFiled,
+        "outcome_label": outcome_label,
+        "market_url": format!("{base_url}/markets/{market_id}"),
+        "unsubscribe_url": format!(
+            "{base_url}/api/v1/notifications/unsubscribe?token={}",
+            recipient.unsubscribe_token
+        ),
+        "event_id": event.id,
+    })
+}
+
+/// Enqueue one notification per opted-in holder of `event`'s market and
+/// return how many were enqueued. Winners who want `claim_available` get that
+/// instead of `market_resolved`. Unverified and suppressed emails are never
+/// claimed. A claim whose enqueue fails is released so a replay of the event
+/// retries it.
+pub async fn notify_position_holders(
+    db: &Database,
+    queue: &EmailQueue,
+    base_url: &str,
+    network: &str,
+    event: &IndexedEvent,
+) -> anyhow::Result<usize> {
+    let (Some(position_event), Some(market_id)) = (PositionEvent::for_event(event), event.market_id) else {
+        return Ok(0);
+    };
+
+    let recipients = db
+        .notification_claim_sends(network, market_id, &event.id, position_event)
+        .await?;
+
+    let mut enqueued = 0;
+    for recipient in recipients {
+        let data = notification_data(base_url, event, market_id, &recipient);
+        match queue
+            .enqueue(
+                EmailJobType::MarketNotification,
+                &recipient.email,
+                POSITION_UPDATE_TEMPLATE,
+                data,
+                0,
+            )
+            .await
+        {
+            Ok(job_id) => {
+                enqueued += 1;
+                if let Err(e) = db
+                    .notification_set_send_job(&recipient.address, &event.id, job_id)
+                    .await
+                {
+                    tracing::warn!(address = %recipient.address, error = %e, "failed to record notification job");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    address = %recipient.address,
+                    event_id = %event.id,
+                    error = %e,
+                    "failed to enqueue position notification"
+                );
+                db.notification_release_send(&recipient.address, &event.id)
+                    .await?;
+            }
+        }
+    }
+    Ok(enqueued)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: &str, outcome: Option<u32>) -> IndexedEvent {
+        IndexedEvent {
+            id: "0000001-0001".to_string(),
+            ledger: 1,
+            tx_hash: None,
+            kind: kind.to_string(),
+            market_id: Some(7),
+            address: Some("GDISPUTER".to_string()),
+            outcome,
+            amount: Some("5000".to_string()),
+            token: None,
+            is_refund: false,
+        }
+    }
+
+    fn recipient(kind: NotificationKind) -> PositionRecipient {
+        PositionRecipient {
+            address: "GHOLDER".to_string(),
+            email: "holder@example.com".to_string(),
+            kind,
+            unsubscribe_token: "tok".to_string(),
+            market_title: "Will it rain?".to_string(),
+            outcome_options: vec!["Yes".to_string(), "No".to_string()],
+        }
+    }
+
+    #[test]
+    fn only_resolution_and_dispute_events_are_position_events() {
+        assert_eq!(
+            PositionEvent::for_event(&event("resolv_fx", Some(1))),
+            Some(PositionEvent::Resolved { outcome: 1 })
+        );
+        assert_eq!(PositionEvent::for_event(&event("disp_file", None)), Some(PositionEvent::Disputed));
+        // A resolution without a winning outcome cannot tell winners apart.
+        assert_eq!(PositionEvent::for_event(&event("resolv_fx", None)), None);
+        assert_eq!(PositionEvent::for_event(&event("bet_place", Some(0))), None);
+    }
+
+    #[test]
+    fn kind_labels_round_trip() {
+        for kind in [
+            NotificationKind::MarketResolved,
+            NotificationKind::ClaimAvailable,
+            NotificationKind::DisputeFiled,
+        ] {
+            assert_eq!(NotificationKind::parse(kind.label()), Some(kind));
+            assert_eq!(serde_json::to_value(kind).unwrap(), kind.label());
+        }
+        assert_eq!(NotificationKind::parse("resolve"), None);
+    }
+
+    #[test]
+    fn claim_data_names_the_winning_outcome() {
+        let data = notification_data(
+            "https://predictiq.io/",
+            &event("resolv_fx", Some(1)),
+            7,
+            &recipient(NotificationKind::ClaimAvailable),
+        );
+        assert_eq!(data["kind"], "claim_available");
+        assert_eq!(data["is_claim"], true);
+        assert_eq!(data["outcome_label"], "No");
+        assert_eq!(data["market_url"], "https://predictiq.io/markets/7");
+        assert_eq!(
+            data["unsubscribe_url"],
+            "https://predictiq.io/api/v1/notifications/unsubscribe?token=tok"
+        );
+    }
+
+    #[test]
+    fn data_never_describes_other_participants() {
+        let data = notification_data(
+            "http://x",
+            &event("disp_file", None),
+            7,
+            &recipient(NotificationKind::DisputeFiled),
+        );
+        let mut keys: Vec<&str> = data.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec![
+                "event_id",
+                "is_claim",
+                "is_dispute",
+                "kind",
+                "market_id",
+                "market_title",
+                "market_url",
+                "outcome_label",
+                "unsubscribe_url",
+            ]
+        );
+        let rendered = data.to_string();
+        assert!(!rendered.contains("GDISPUTER"));
+        assert!(!rendered.contains("5000"));
+        assert_eq!(data["outcome_label"], "");
+    }
+}
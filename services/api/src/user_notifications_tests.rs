@@ -0,0 +1,388 @@
+#[cfg(test)]
+mod user_notifications_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        blockchain::IndexedEvent,
+        handlers::{notification_email_confirm, notification_settings_get, notification_settings_put},
+        user_notifications::{new_token, notify_position_holders, NotificationSettingsUpdate},
+        wallet_auth::SessionKeys,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 3] = [9701, 9702, 9703];
+
+    const WINNER: &str = "GCLQCBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQPW36";
+    const LOSER: &str = "GCLQEDQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4F7H";
+    const OPTED_OUT: &str = "GCLQGFIVCUKRKFIVCUKRKFIVCUKRKFIVCUKRKFIVCUKRKFIVCUKRLBCF";
+    const UNVERIFIED: &str = "GCLQIHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBZLWE";
+    const SEED_ADDRESSES: [&str; 4] = [WINNER, LOSER, OPTED_OUT, UNVERIFIED];
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route(
+                "/users/:address/notifications",
+                get(notification_settings_get).put(notification_settings_put),
+            )
+            .route("/notifications/confirm", get(notification_email_confirm))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    /// A session token for `address`, as `POST /auth/verify` would issue.
+    fn token(state: &crate::AppState, address: &str) -> String {
+        let now = chrono::Utc::now().timestamp();
+        SessionKeys::from_config(&state.config)
+            .issue(address, now, now)
+            .unwrap()
+            .token
+    }
+
+    async fn put_settings(
+        state: &Arc<crate::AppState>,
+        address: &str,
+        bearer: Option<&str>,
+        body: Value,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method("PUT")
+            .uri(format!("/users/{address}/notifications"))
+            .header("content-type", "application/json");
+        if let Some(bearer) = bearer {
+            request = request.header("authorization", format!("Bearer {bearer}"));
+        }
+        send(state, request.body(Body::from(serde_json::to_vec(&body).unwrap())).unwrap()).await
+    }
+
+    fn email(address: &str) -> String {
+        format!("notify-{}@example.com", &address[..8].to_lowercase())
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, outcome_options) \
+             VALUES ($1, 'Notification test', 'active', 0, NOW() + INTERVAL '1 day', NOW(), ARRAY['Yes', 'No'])",
+        )
+        .bind(market_id)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn bet(state: &crate::AppState, market_id: i64, address: &str, outcome: u32) {
+        let event = IndexedEvent {
+            id: format!("notify-test-{market_id}-{address}"),
+            ledger: 50,
+            tx_hash: None,
+            kind: "bet_place".to_string(),
+            market_id: Some(market_id),
+            address: Some(address.to_string()),
+            outcome: Some(outcome),
+            amount: Some("1000".to_string()),
+            token: None,
+            is_refund: false,
+        };
+        state
+            .db
+            .chain_event_insert(state.networks.primary().network(), &event)
+            .await
+            .unwrap();
+    }
+
+    /// Save settings for `address` with all three toggles set to `on`.
+    async fn opt_in(state: &crate::AppState, address: &str, on: bool) {
+        let update = NotificationSettingsUpdate {
+            email: Some(email(address)),
+            market_resolved: on,
+            claim_available: on,
+            dispute_filed: on,
+            hide_creator_profile: false,
+        };
+        state
+            .db
+            .notification_settings_update(address, &update, &new_token())
+            .await
+            .unwrap();
+    }
+
+    /// Verify `address`'s email through the token flow.
+    async fn verify(state: &crate::AppState, address: &str) {
+        let token = new_token();
+        state
+            .db
+            .notification_issue_confirmation(address, &token, 0)
+            .await
+            .unwrap()
+            .expect("unverified email on file");
+        let outcome = state
+            .db
+            .notification_confirm_by_token(&token, state.config.newsletter_token_ttl_secs)
+            .await
+            .unwrap();
+        assert_eq!(outcome, crate::db::NewsletterConfirmation::Confirmed);
+    }
+
+    fn event(id: &str, kind: &str, market_id: i64, outcome: Option<u32>) -> IndexedEvent {
+        IndexedEvent {
+            id: id.to_string(),
+            ledger: 100,
+            tx_hash: None,
+            kind: kind.to_string(),
+            market_id: Some(market_id),
+            address: None,
+            outcome,
+            amount: None,
+            token: None,
+            is_refund: false,
+        }
+    }
+
+    async fn notify(state: &crate::AppState, event: &IndexedEvent) -> usize {
+        notify_position_holders(
+            &state.db,
+            &state.email_queue,
+            &state.config.base_url,
+            state.networks.primary().network(),
+            event,
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Position emails per (recipient, kind) for a seeded market.
+    async fn jobs(state: &crate::AppState, market_id: i64) -> Vec<(String, String, i64)> {
+        sqlx::query_as(
+            "SELECT recipient_email, template_data->>'kind', COUNT(*) FROM email_jobs \
+             WHERE template_name = 'position_update' AND (template_data->>'market_id')::BIGINT = $1 \
+             GROUP BY 1, 2 ORDER BY 1, 2",
+        )
+        .bind(market_id)
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pool = state.db.pool();
+        let emails: Vec<String> = SEED_ADDRESSES.iter().map(|a| email(a)).collect();
+        sqlx::query(
+            "DELETE FROM email_jobs WHERE template_name IN ('position_update', 'notification_email_confirmation') \
+             AND recipient_email = ANY($1)",
+        )
+        .bind(&emails)
+        .execute(&pool)
+        .await
+        .unwrap();
+        // Cascades to user_notification_sends.
+        sqlx::query("DELETE FROM user_notification_settings WHERE address = ANY($1)")
+            .bind(&SEED_ADDRESSES[..])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM chain_events WHERE id LIKE 'notify-test-%'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// A winner who opted in gets exactly one `claim_available` email, even
+    /// when the event is replayed; a loser gets `market_resolved` instead.
+    /// Neither email mentions the other holder.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_opted_in_winner_gets_one_claim_email() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9701).await;
+        for (address, outcome) in [(WINNER, 0), (LOSER, 1)] {
+            bet(&state, 9701, address, outcome).await;
+            opt_in(&state, address, true).await;
+            verify(&state, address).await;
+        }
+
+        let resolved = event("0000000100-0001", "resolv_fx", 9701, Some(0));
+        assert_eq!(notify(&state, &resolved).await, 2);
+        assert_eq!(notify(&state, &resolved).await, 0);
+
+        let mut expected = vec![
+            (email(WINNER), "claim_available".to_string(), 1),
+            (email(LOSER), "market_resolved".to_string(), 1),
+        ];
+        expected.sort();
+        assert_eq!(jobs(&state, 9701).await, expected);
+
+        let data: Vec<(String,)> = sqlx::query_as(
+            "SELECT template_data::TEXT FROM email_jobs WHERE template_name = 'position_update' \
+             AND (template_data->>'market_id')::BIGINT = 9701",
+        )
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap();
+        for (rendered,) in data {
+            for address in [WINNER, LOSER] {
+                assert!(!rendered.contains(address));
+                assert!(!rendered.contains(&email(address)));
+            }
+        }
+
+        cleanup(&state).await;
+    }
+
+    /// A verified holder with every toggle off gets nothing, for resolutions
+    /// or disputes.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_opted_out_holder_gets_nothing() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9702).await;
+        bet(&state, 9702, OPTED_OUT, 0).await;
+        opt_in(&state, OPTED_OUT, false).await;
+        verify(&state, OPTED_OUT).await;
+
+        assert_eq!(notify(&state, &event("0000000100-0002", "disp_file", 9702, None)).await, 0);
+        assert_eq!(notify(&state, &event("0000000200-0002", "resolv_fx", 9702, Some(0))).await, 0);
+        assert!(jobs(&state, 9702).await.is_empty());
+
+        cleanup(&state).await;
+    }
+
+    /// An opted-in holder whose email was never confirmed gets nothing; once
+    /// the confirmation link is followed, later events are delivered.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_unverified_email_gets_nothing() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9703).await;
+        bet(&state, 9703, UNVERIFIED, 0).await;
+
+        let bearer = token(&state, UNVERIFIED);
+        let (status, body) = put_settings(
+            &state,
+            UNVERIFIED,
+            Some(&bearer),
+            json!({ "email": email(UNVERIFIED), "claim_available": true, "dispute_filed": true }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["email_verified"], false);
+
+        assert_eq!(notify(&state, &event("0000000100-0003", "disp_file", 9703, None)).await, 0);
+        assert!(jobs(&state, 9703).await.is_empty());
+
+        let (confirm_url,): (String,) = sqlx::query_as(
+            "SELECT template_data->>'confirm_url' FROM email_jobs \
+             WHERE template_name = 'notification_email_confirmation' AND recipient_email = $1",
+        )
+        .bind(email(UNVERIFIED))
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        let token = confirm_url.rsplit("token=").next().unwrap();
+        let (status, _) = send(
+            &state,
+            Request::builder()
+                .uri(format!("/notifications/confirm?token={token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        assert_eq!(notify(&state, &event("0000000200-0003", "resolv_fx", 9703, Some(0))).await, 1);
+        assert_eq!(
+            jobs(&state, 9703).await,
+            vec![(email(UNVERIFIED), "claim_available".to_string(), 1)]
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Settings need a bearer token issued to the path's address, and saving
+    /// the same unverified email again within the cooldown sends no second
+    /// confirmation.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_settings_require_session_and_throttle_confirmation() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        let body = json!({ "email": email(WINNER), "market_resolved": true });
+
+        let (status, _) = put_settings(&state, WINNER, None, body.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = put_settings(&state, WINNER, Some(&token(&state, LOSER)), body.clone()).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let bearer = token(&state, WINNER);
+        let (status, _) = put_settings(&state, WINNER, Some(&bearer), json!({ "email": "not-an-email" })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        for _ in 0..2 {
+            let (status, _) = put_settings(&state, WINNER, Some(&bearer), body.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let (sent,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM email_jobs \
+             WHERE template_name = 'notification_email_confirmation' AND recipient_email = $1",
+        )
+        .bind(email(WINNER))
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(sent, 1);
+
+        let (status, body) = send(
+            &state,
+            Request::builder()
+                .uri(format!("/users/{WINNER}/notifications"))
+                .header("authorization", format!("Bearer {bearer}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["email"], email(WINNER));
+        assert_eq!(body["market_resolved"], true);
+        assert_eq!(body["claim_available"], false);
+
+        cleanup(&state).await;
+    }
+
+}
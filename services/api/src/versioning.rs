@@ -29,6 +29,12 @@ pub struct DeprecationSampler {
     last_logged: Arc<Mutex<HashMap<String, Instant>>>,
 }
 
+impl Default for DeprecationSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DeprecationSampler {
     pub fn new() -> Self {
         Self {
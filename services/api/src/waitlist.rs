@@ -0,0 +1,153 @@
+//! Launch waitlist with referrals.
+//!
+//! `POST /api/v1/waitlist` adds a [`WaitlistEntry`] with its own referral
+//! code. Joining with someone else's code records the referral and adds
+//! [`REFERRAL_BONUS`] to the referrer's `priority_score`, moving them up the
+//! queue. Pending entries are ordered by `priority_score DESC, joined_at, id`;
+//! an entry's position is its rank in that order. Admins invite entries off
+//! the front of the queue, which moves them to `invited` and enqueues a
+//! `waitlist_invitation` email.
+//!
+//! Positions are derived at read time rather than stored, so concurrent joins
+//! cannot be handed the same one, and the referrer's bonus is applied by the
+//! same statement that inserts the entry: a join that fails or conflicts
+//! credits nobody.
+
+use chrono::{DateTime, Utc};
+use rand::Rng as _;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Sent when an entry joins.
+pub const CONFIRMATION_TEMPLATE: &str = "waitlist_confirmation";
+
+/// Sent when an admin invites an entry.
+pub const INVITATION_TEMPLATE: &str = "waitlist_invitation";
+
+/// Priority points a referrer gains for each entry that joins with their code.
+pub const REFERRAL_BONUS: i32 = 10;
+
+/// Upper bound on entries invited by one admin request.
+pub const MAX_INVITE_BATCH: usize = 500;
+
+/// Length of generated referral codes.
+pub const REFERRAL_CODE_LEN: usize = 8;
+
+/// Generated codes avoid 0/O and 1/I so they survive being read aloud.
+const REFERRAL_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitlistStatus {
+    /// Queued; has a position.
+    Pending,
+    /// Invited off the front of the queue.
+    Invited,
+    /// Signed up after being invited.
+    Converted,
+}
+
+impl WaitlistStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Invited => "invited",
+            Self::Converted => "converted",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "pending" => Some(Self::Pending),
+            "invited" => Some(Self::Invited),
+            "converted" => Some(Self::Converted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WaitlistEntry {
+    pub id: Uuid,
+    pub email: String,
+    pub status: WaitlistStatus,
+    pub referral_code: String,
+    /// Entries that joined with this entry's referral code.
+    pub referral_count: i64,
+    /// 1-based rank among pending entries; `None` once invited.
+    pub position: Option<i64>,
+    pub joined_at: DateTime<Utc>,
+    pub invited_at: Option<DateTime<Utc>>,
+}
+
+/// An entry moved to `invited` by an admin, with the position it held.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WaitlistInvitee {
+    pub id: Uuid,
+    pub email: String,
+    pub position: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WaitlistStats {
+    pub total: i64,
+    pub pending: i64,
+    pub invited: i64,
+    pub converted: i64,
+    /// Entries that joined with a valid referral code.
+    pub referred: i64,
+    pub joined_last_24h: i64,
+}
+
+/// Outcome of a join; joining again with the same email is a no-op.
+#[derive(Debug, Clone)]
+pub enum WaitlistJoin {
+    Joined(WaitlistEntry),
+    Existing(WaitlistEntry),
+}
+
+pub fn generate_referral_code() -> String {
+    let mut rng = rand::thread_rng();
+    (0..REFERRAL_CODE_LEN)
+        .map(|_| REFERRAL_CODE_ALPHABET[rng.gen_range(0..REFERRAL_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Upper-cased code, or `None` when it cannot be a referral code. Codes
+/// backfilled by migration 032 are hex, so any alphanumeric code is accepted.
+pub fn normalize_referral_code(raw: &str) -> Option<String> {
+    let code = raw.trim().to_ascii_uppercase();
+    let valid = (6..=16).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then_some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_codes_use_the_unambiguous_alphabet() {
+        for _ in 0..100 {
+            let code = generate_referral_code();
+            assert_eq!(code.len(), REFERRAL_CODE_LEN);
+            assert!(code.bytes().all(|b| REFERRAL_CODE_ALPHABET.contains(&b)), "{code}");
+            assert_eq!(normalize_referral_code(&code), Some(code));
+        }
+    }
+
+    #[test]
+    fn referral_codes_are_normalized() {
+        assert_eq!(normalize_referral_code("  abcd2345 "), Some("ABCD2345".to_string()));
+        assert_eq!(normalize_referral_code("ab12"), None);
+        assert_eq!(normalize_referral_code("ABCD-2345"), None);
+        assert_eq!(normalize_referral_code(""), None);
+    }
+
+    #[test]
+    fn status_labels_round_trip() {
+        for status in [WaitlistStatus::Pending, WaitlistStatus::Invited, WaitlistStatus::Converted] {
+            assert_eq!(WaitlistStatus::parse(status.label()), Some(status));
+            assert_eq!(serde_json::to_value(status).unwrap(), status.label());
+        }
+    }
+}
@@ -0,0 +1,325 @@
+#[cfg(test)]
+mod waitlist_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post},
+        Router,
+    };
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::handlers::{waitlist_invite, waitlist_join, waitlist_stats, waitlist_status};
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Entries live in a reserved range so cleanup never touches rows created
+    /// by other tests or the seed data.
+    const EMAIL_PREFIX: &str = "waitlist-98";
+
+    /// Above any real priority_score, so entries bumped to it sit at the front
+    /// of the queue in a shared test database.
+    const FRONT_OF_QUEUE: i32 = 1_000_000;
+
+    fn email(n: usize) -> String {
+        format!("{EMAIL_PREFIX}{n:02}@example.com")
+    }
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/waitlist", post(waitlist_join))
+            .route("/waitlist/status", get(waitlist_status))
+            .route("/admin/waitlist/invite", post(waitlist_invite))
+            .route("/admin/waitlist/stats", get(waitlist_stats))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    fn post_json(uri: &str, body: Value) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    async fn join(state: &Arc<crate::AppState>, n: usize, referral_code: Option<&str>) -> (StatusCode, Value) {
+        send(
+            state,
+            post_json("/waitlist", json!({ "email": email(n), "referral_code": referral_code })),
+        )
+        .await
+    }
+
+    async fn status(state: &Arc<crate::AppState>, n: usize) -> (StatusCode, Value) {
+        send(
+            state,
+            Request::builder()
+                .uri(format!("/waitlist/status?email={}", email(n)))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn move_to_front(state: &crate::AppState, n: usize, bonus: i32) {
+        sqlx::query("UPDATE waitlist_entries SET priority_score = $2 WHERE email = $1")
+            .bind(email(n))
+            .bind(FRONT_OF_QUEUE + bonus)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    async fn invitations(state: &crate::AppState) -> Vec<String> {
+        sqlx::query_scalar(
+            "SELECT recipient_email FROM email_jobs \
+             WHERE template_name = 'waitlist_invitation' AND recipient_email LIKE $1 \
+             ORDER BY recipient_email",
+        )
+        .bind(format!("{EMAIL_PREFIX}%@example.com"))
+        .fetch_all(&state.db.pool())
+        .await
+        .unwrap()
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        let pattern = format!("{EMAIL_PREFIX}%@example.com");
+        sqlx::query("DELETE FROM email_jobs WHERE recipient_email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        // Referrals point at other test entries; clear them before deleting.
+        sqlx::query("UPDATE waitlist_entries SET referred_by = NULL WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM waitlist_entries WHERE email LIKE $1")
+            .bind(&pattern)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Joining twice returns the same entry with a 200 and keeps its position.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_joining_again_conflicts_and_keeps_the_entry() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let (code, first) = join(&state, 0, None).await;
+        assert_eq!(code, StatusCode::CREATED);
+        assert_eq!(first["status"], "pending");
+        assert_eq!(first["referral_count"], 0);
+        assert!(first["position"].as_i64().unwrap() >= 1);
+        assert_eq!(first["referral_code"].as_str().unwrap().len(), 8);
+
+        let (code, second) = join(&state, 0, None).await;
+        assert_eq!(code, StatusCode::CONFLICT);
+        assert_eq!(second["code"], "ALREADY_ON_WAITLIST");
+        assert_eq!(second["details"]["status"], "pending");
+        assert_eq!(second["details"]["position"], first["position"]);
+
+        let confirmations: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM email_jobs \
+             WHERE recipient_email = $1 AND template_name = 'waitlist_confirmation'",
+        )
+        .bind(email(0))
+        .fetch_one(&state.db.pool())
+        .await
+        .unwrap();
+        assert_eq!(confirmations, 1, "only the first join is confirmed");
+
+        let (code, body) = status(&state, 0).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["referral_code"], first["referral_code"]);
+        assert_eq!(body["joined_at"], first["joined_at"]);
+
+        let (code, _) = status(&state, 99).await;
+        assert_eq!(code, StatusCode::NOT_FOUND);
+
+        cleanup(&state).await;
+    }
+
+    /// A valid referral code moves the referrer up; an unknown one is a 422
+    /// and stores nothing.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_referral_code_is_validated_before_bonus() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let (_, early) = join(&state, 1, None).await;
+        let (_, late) = join(&state, 2, None).await;
+        assert!(early["position"].as_i64() < late["position"].as_i64());
+
+        let (code, body) = join(&state, 3, Some("NOSUCHCODE")).await;
+        assert_eq!(code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["code"], "INVALID_REFERRAL_CODE");
+        assert_eq!(status(&state, 3).await.0, StatusCode::NOT_FOUND);
+
+        let late_code = late["referral_code"].as_str().unwrap().to_lowercase();
+        let (code, _) = join(&state, 3, Some(&late_code)).await;
+        assert_eq!(code, StatusCode::CREATED);
+
+        let (_, early) = status(&state, 1).await;
+        let (_, late) = status(&state, 2).await;
+        assert_eq!(late["referral_count"], 1);
+        assert!(
+            late["position"].as_i64() < early["position"].as_i64(),
+            "the referral bonus moves the referrer ahead"
+        );
+
+        // Re-joining with a code grants no second bonus.
+        join(&state, 3, Some(&late_code)).await;
+        assert_eq!(status(&state, 2).await.1["referral_count"], 1);
+
+        cleanup(&state).await;
+    }
+
+    /// Admins invite the front of the queue or explicit positions; each
+    /// invitee gets one invitation email and leaves the queue.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_admin_invites_and_stats() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        for n in 0..4 {
+            join(&state, n, None).await;
+            move_to_front(&state, n, 10 - n as i32).await;
+        }
+
+        let (code, _) = send(&state, post_json("/admin/waitlist/invite", json!({}))).await;
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+        let (code, _) = send(
+            &state,
+            post_json("/admin/waitlist/invite", json!({ "count": 1, "positions": [1] })),
+        )
+        .await;
+        assert_eq!(code, StatusCode::BAD_REQUEST);
+
+        let (code, body) = send(&state, post_json("/admin/waitlist/invite", json!({ "count": 2 }))).await;
+        assert_eq!(code, StatusCode::OK);
+        let invited: Vec<_> = body["invited"].as_array().unwrap().iter().map(|i| i["email"].clone()).collect();
+        assert_eq!(invited, vec![json!(email(0)), json!(email(1))]);
+        assert_eq!(body["emails_queued"], 2);
+
+        let (_, entry) = status(&state, 0).await;
+        assert_eq!(entry["status"], "invited");
+        assert!(entry["position"].is_null());
+        assert_eq!(status(&state, 2).await.1["position"], 1, "the queue closes up");
+
+        // Position 2 is now the fourth entry.
+        let (code, body) = send(&state, post_json("/admin/waitlist/invite", json!({ "positions": [2] }))).await;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(body["invited"][0]["email"], email(3));
+        assert_eq!(body["invited"][0]["position"], 2);
+
+        assert_eq!(invitations(&state).await, vec![email(0), email(1), email(3)]);
+
+        let (code, stats) = send(
+            &state,
+            Request::builder().uri("/admin/waitlist/stats").body(Body::empty()).unwrap(),
+        )
+        .await;
+        assert_eq!(code, StatusCode::OK);
+        assert!(stats["invited"].as_i64().unwrap() >= 3);
+        assert!(stats["joined_last_24h"].as_i64().unwrap() >= 4);
+        assert_eq!(
+            stats["total"].as_i64().unwrap(),
+            stats["pending"].as_i64().unwrap()
+                + stats["invited"].as_i64().unwrap()
+                + stats["converted"].as_i64().unwrap()
+        );
+
+        cleanup(&state).await;
+    }
+
+    /// Concurrent signups get dense, unique positions; racing joins for one
+    /// email create a single entry; and every referral is credited once.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_concurrent_signups_get_dense_unique_positions() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+
+        let (_, referrer) = join(&state, 0, None).await;
+        let code = referrer["referral_code"].as_str().unwrap().to_string();
+
+        let signups: Vec<_> = (1..=50)
+            .map(|n| {
+                let state = Arc::clone(&state);
+                let code = code.clone();
+                tokio::spawn(async move { join(&state, n, Some(&code)).await })
+            })
+            .collect();
+        let duplicates: Vec<_> = (0..10)
+            .map(|_| {
+                let state = Arc::clone(&state);
+                tokio::spawn(async move { join(&state, 51, None).await })
+            })
+            .collect();
+
+        for signup in signups {
+            assert_eq!(signup.await.unwrap().0, StatusCode::CREATED);
+        }
+        let mut created = 0;
+        for duplicate in duplicates {
+            match duplicate.await.unwrap().0 {
+                StatusCode::CREATED => created += 1,
+                StatusCode::CONFLICT => {}
+                other => panic!("unexpected status {other}"),
+            }
+        }
+        assert_eq!(created, 1, "one entry per email");
+
+        let score: i32 = sqlx::query_scalar("SELECT priority_score FROM waitlist_entries WHERE email = $1")
+            .bind(email(0))
+            .fetch_one(&state.db.pool())
+            .await
+            .unwrap();
+        assert_eq!(score, 50 * crate::waitlist::REFERRAL_BONUS, "each referral credited once");
+        assert_eq!(status(&state, 0).await.1["referral_count"], 50);
+
+        // Our entries are the only ones at the front of the queue, so their
+        // positions must be exactly 1..=52.
+        for n in 0..=51 {
+            move_to_front(&state, n, 0).await;
+        }
+        let mut positions = Vec::new();
+        for n in 0..=51 {
+            positions.push(status(&state, n).await.1["position"].as_i64().unwrap());
+        }
+        positions.sort_unstable();
+        assert_eq!(positions, (1..=52).collect::<Vec<_>>());
+
+        cleanup(&state).await;
+    }
+
+}
@@ -0,0 +1,398 @@
+//! Proof that a caller controls a Stellar account, by signed challenge, and
+//! the short-lived session tokens issued for it.
+//!
+//! 1. `GET /api/v1/auth/challenge?address=G...` issues a random nonce for the
+//!    address and stores it in Redis for [`CHALLENGE_TTL`].
+//! 2. The wallet signs [`challenge_message`] with the account's ed25519 key
+//!    and the client posts the nonce and base64 signature to
+//!    `POST /api/v1/auth/verify`. [`verify_challenge`] takes the nonce out of
+//!    Redis before checking the signature, so each challenge is answered at
+//!    most once whether or not the signature turns out to be valid.
+//! 3. A valid answer is exchanged for an HS256 session token carrying the
+//!    address. User-scoped routes take an [`AuthedAddress`], which validates
+//!    the `Authorization: Bearer` token.
+//! 4. `POST /api/v1/auth/refresh` trades a still-valid token for a fresh one
+//!    until `JWT_MAX_SESSION_SECS` after the original signature; after that
+//!    the wallet signs a new challenge.
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{extract::FromRequestParts, http::request::Parts, http::HeaderMap};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, VerifyingKey};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache::{keys, RedisCache},
+    config::Config,
+    handlers::ApiError,
+    AppState,
+};
+
+/// How long an issued challenge can be answered.
+pub const CHALLENGE_TTL: Duration = Duration::from_secs(300);
+
+/// Clock skew tolerated between this instance and whichever one issued a
+/// token, applied to both `iat` and `exp`.
+pub const TOKEN_LEEWAY_SECS: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Challenge {
+    pub address: String,
+    pub nonce: String,
+    /// The exact text to sign with the account's key.
+    pub message: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A session token for one address.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SessionToken {
+    pub token: String,
+    /// Always `Bearer`.
+    pub token_type: String,
+    pub address: String,
+    pub expires_at: DateTime<Utc>,
+    /// Last moment `POST /api/v1/auth/refresh` will extend this session.
+    pub refresh_until: DateTime<Utc>,
+}
+
+/// Claims carried by a session token. Times are Unix seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionClaims {
+    /// The Stellar account the bearer proved control of.
+    pub address: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// When the wallet signed the challenge; refreshes keep it.
+    pub auth_time: i64,
+}
+
+/// Why a wallet proof or session token was rejected. Every variant but
+/// `Cache` is the caller's fault.
+#[derive(Debug)]
+pub enum WalletAuthError {
+    /// The address is not a `G...` account strkey.
+    InvalidAddress,
+    /// The nonce or signature is absent.
+    Missing,
+    /// The nonce was never issued for this address, has expired, or has
+    /// already been used.
+    UnknownChallenge,
+    /// The signature does not verify against the account's key.
+    BadSignature,
+    /// No `Authorization: Bearer` token was sent.
+    MissingToken,
+    /// The token is malformed, tampered with, or expired.
+    InvalidToken,
+    /// The token is valid but its session is too old to refresh.
+    SessionExpired,
+    /// Redis failed while issuing or consuming the challenge.
+    Cache(anyhow::Error),
+}
+
+impl std::fmt::Display for WalletAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidAddress => write!(f, "address must be a Stellar account (G...)"),
+            Self::Missing => write!(f, "nonce and signature are required"),
+            Self::UnknownChallenge => write!(f, "unknown, expired, or already used challenge"),
+            Self::BadSignature => write!(f, "signature does not verify for this address"),
+            Self::MissingToken => write!(f, "missing bearer token"),
+            Self::InvalidToken => write!(f, "invalid or expired bearer token"),
+            Self::SessionExpired => write!(f, "session can no longer be refreshed; sign a new challenge"),
+            Self::Cache(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for WalletAuthError {}
+
+impl From<anyhow::Error> for WalletAuthError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Cache(e)
+    }
+}
+
+impl From<WalletAuthError> for ApiError {
+    fn from(err: WalletAuthError) -> Self {
+        let code = match err {
+            WalletAuthError::InvalidAddress | WalletAuthError::Missing => {
+                return ApiError::bad_request(err.to_string())
+            }
+            WalletAuthError::Cache(e) => {
+                tracing::warn!(error = %e, "wallet challenge store unavailable");
+                return ApiError::service_unavailable("Wallet challenges are temporarily unavailable.");
+            }
+            WalletAuthError::UnknownChallenge => "CHALLENGE_INVALID",
+            WalletAuthError::BadSignature => "SIGNATURE_INVALID",
+            WalletAuthError::MissingToken => "AUTH_REQUIRED",
+            WalletAuthError::InvalidToken => "TOKEN_INVALID",
+            WalletAuthError::SessionExpired => "SESSION_EXPIRED",
+        };
+        ApiError::unauthorized(code, err.to_string())
+    }
+}
+
+// ── challenges ────────────────────────────────────────────────────────────────
+
+/// The text a wallet signs to answer `nonce`. Naming the service and the
+/// address keeps a signature from being replayed against another site or
+/// account.
+pub fn challenge_message(address: &str, nonce: &str) -> String {
+    format!("PredictIQ wants you to prove control of {address}.\nNonce: {nonce}")
+}
+
+/// The ed25519 key behind a `G...` account strkey.
+pub fn account_key(address: &str) -> Result<VerifyingKey, WalletAuthError> {
+    let key = stellar_strkey::ed25519::PublicKey::from_string(address.trim())
+        .map_err(|_| WalletAuthError::InvalidAddress)?;
+    VerifyingKey::from_bytes(&key.0).map_err(|_| WalletAuthError::InvalidAddress)
+}
+
+/// Check a base64 `signature` of `message` by the account at `address`.
+pub fn verify_signature(address: &str, message: &str, signature: &str) -> Result<(), WalletAuthError> {
+    let key = account_key(address)?;
+    let signature = BASE64
+        .decode(signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .ok_or(WalletAuthError::BadSignature)?;
+    key.verify_strict(message.as_bytes(), &signature)
+        .map_err(|_| WalletAuthError::BadSignature)
+}
+
+/// Issue a fresh challenge for `address`.
+pub async fn issue_challenge(cache: &RedisCache, address: &str) -> Result<Challenge, WalletAuthError> {
+    account_key(address)?;
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let nonce = hex::encode(bytes);
+
+    cache
+        .set_json(&keys::auth_challenge(&nonce), &address, CHALLENGE_TTL)
+        .await?;
+
+    Ok(Challenge {
+        address: address.to_string(),
+        message: challenge_message(address, &nonce),
+        nonce,
+        expires_at: Utc::now() + chrono::Duration::seconds(CHALLENGE_TTL.as_secs() as i64),
+    })
+}
+
+/// Consume the challenge `nonce` and check it was issued to `address` and
+/// signed by it.
+pub async fn verify_challenge(
+    cache: &RedisCache,
+    address: &str,
+    nonce: &str,
+    signature: &str,
+) -> Result<(), WalletAuthError> {
+    if nonce.is_empty() || signature.is_empty() {
+        return Err(WalletAuthError::Missing);
+    }
+    account_key(address)?;
+    let issued_to: Option<String> = cache.take_json(&keys::auth_challenge(nonce)).await?;
+    if issued_to.as_deref() != Some(address) {
+        return Err(WalletAuthError::UnknownChallenge);
+    }
+    verify_signature(address, &challenge_message(address, nonce), signature)
+}
+
+// ── session tokens ────────────────────────────────────────────────────────────
+
+/// Signs and checks session tokens. Built per request from the config; the
+/// keys are just the secret's bytes.
+pub struct SessionKeys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl_secs: i64,
+    max_session_secs: i64,
+}
+
+impl SessionKeys {
+    pub fn new(secret: &str, ttl_secs: u64, max_session_secs: u64) -> Self {
+        Self {
+            encoding: EncodingKey::from_secret(secret.as_bytes()),
+            decoding: DecodingKey::from_secret(secret.as_bytes()),
+            ttl_secs: ttl_secs as i64,
+            max_session_secs: max_session_secs as i64,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(&config.jwt_secret, config.jwt_ttl_secs, config.jwt_max_session_secs)
+    }
+
+    /// A token for `address`, whose wallet signed at `auth_time`. It never
+    /// outlives the session.
+    pub fn issue(&self, address: &str, auth_time: i64, now: i64) -> anyhow::Result<SessionToken> {
+        let refresh_until = auth_time + self.max_session_secs;
+        let claims = SessionClaims {
+            address: address.to_string(),
+            iat: now,
+            exp: (now + self.ttl_secs).min(refresh_until),
+            auth_time,
+        };
+        let token = jsonwebtoken::encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)?;
+        Ok(SessionToken {
+            token,
+            token_type: "Bearer".to_string(),
+            address: claims.address,
+            expires_at: timestamp(claims.exp),
+            refresh_until: timestamp(refresh_until),
+        })
+    }
+
+    /// The claims of `token` if it is signed with this secret and current at
+    /// `now`, give or take [`TOKEN_LEEWAY_SECS`].
+    pub fn validate(&self, token: &str, now: i64) -> Result<SessionClaims, WalletAuthError> {
+        // Expiry is checked here against `now` rather than by the library
+        // against the system clock, so it is testable and uses one leeway.
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = false;
+        validation.set_required_spec_claims(&["exp"]);
+        let claims = jsonwebtoken::decode::<SessionClaims>(token, &self.decoding, &validation)
+            .map_err(|_| WalletAuthError::InvalidToken)?
+            .claims;
+        if claims.exp + TOKEN_LEEWAY_SECS < now || claims.iat - TOKEN_LEEWAY_SECS > now {
+            return Err(WalletAuthError::InvalidToken);
+        }
+        Ok(claims)
+    }
+
+    /// Trade a current token for a fresh one with the same `auth_time`.
+    pub fn refresh(&self, token: &str, now: i64) -> Result<SessionToken, WalletAuthError> {
+        let claims = self.validate(token, now)?;
+        if now >= claims.auth_time + self.max_session_secs {
+            return Err(WalletAuthError::SessionExpired);
+        }
+        Ok(self.issue(&claims.address, claims.auth_time, now)?)
+    }
+}
+
+fn timestamp(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(secs, 0).unwrap_or_default()
+}
+
+/// The token in an `Authorization: Bearer <token>` header.
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let value = headers.get(axum::http::header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.trim().split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+/// The Stellar account a request's bearer token was issued to. Rejects with
+/// 401 when the token is missing, tampered with, or expired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthedAddress(pub String);
+
+#[axum::async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthedAddress {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let token = bearer_token(&parts.headers).ok_or(WalletAuthError::MissingToken)?;
+        let claims = SessionKeys::from_config(&state.config).validate(token, Utc::now().timestamp())?;
+        Ok(Self(claims.address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+    const NOW: i64 = 1_700_000_000;
+
+    fn keys() -> SessionKeys {
+        SessionKeys::new("test-jwt-secret-of-at-least-32-bytes", 3600, 86_400)
+    }
+
+    #[test]
+    fn issued_token_validates_to_its_address() {
+        let session = keys().issue(ADDRESS, NOW, NOW).unwrap();
+        assert_eq!(session.token_type, "Bearer");
+        assert_eq!(session.expires_at.timestamp(), NOW + 3600);
+        assert_eq!(session.refresh_until.timestamp(), NOW + 86_400);
+        let claims = keys().validate(&session.token, NOW + 10).unwrap();
+        assert_eq!(claims.address, ADDRESS);
+        assert_eq!(claims.auth_time, NOW);
+    }
+
+    #[test]
+    fn tampered_token_is_rejected() {
+        let token = keys().issue(ADDRESS, NOW, NOW).unwrap().token;
+        let mut parts: Vec<String> = token.split('.').map(str::to_string).collect();
+
+        // Swap the payload for one naming another address.
+        let forged = SessionClaims {
+            address: "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H".to_string(),
+            iat: NOW,
+            exp: NOW + 3600,
+            auth_time: NOW,
+        };
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        parts[1] = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&forged).unwrap());
+        assert!(matches!(keys().validate(&parts.join("."), NOW), Err(WalletAuthError::InvalidToken)));
+
+        let other_secret = SessionKeys::new("another-secret-that-is-also-32-bytes", 3600, 86_400);
+        assert!(matches!(other_secret.validate(&token, NOW), Err(WalletAuthError::InvalidToken)));
+        assert!(matches!(keys().validate("not.a.jwt", NOW), Err(WalletAuthError::InvalidToken)));
+    }
+
+    #[test]
+    fn expiry_and_issue_time_allow_clock_skew() {
+        let token = keys().issue(ADDRESS, NOW, NOW).unwrap().token;
+        let exp = NOW + 3600;
+        assert!(keys().validate(&token, exp + TOKEN_LEEWAY_SECS).is_ok());
+        assert!(keys().validate(&token, exp + TOKEN_LEEWAY_SECS + 1).is_err());
+        // Issued by an instance whose clock runs slightly ahead.
+        assert!(keys().validate(&token, NOW - TOKEN_LEEWAY_SECS).is_ok());
+        assert!(keys().validate(&token, NOW - TOKEN_LEEWAY_SECS - 1).is_err());
+    }
+
+    #[test]
+    fn refresh_keeps_auth_time_and_stops_at_session_end() {
+        let first = keys().issue(ADDRESS, NOW, NOW).unwrap();
+        let later = NOW + 3000;
+        let refreshed = keys().refresh(&first.token, later).unwrap();
+        assert_eq!(refreshed.expires_at.timestamp(), later + 3600);
+        assert_eq!(keys().validate(&refreshed.token, later).unwrap().auth_time, NOW);
+
+        // Near the end of the session the token is capped at it ...
+        let near_end = NOW + 86_400 - 600;
+        let token = keys().issue(ADDRESS, NOW, near_end).unwrap();
+        assert_eq!(token.expires_at.timestamp(), NOW + 86_400);
+        // ... and once it is over, refreshing requires a new signature.
+        let at_end = NOW + 86_400;
+        assert!(matches!(keys().refresh(&token.token, at_end), Err(WalletAuthError::SessionExpired)));
+        // Expired tokens cannot be refreshed at all.
+        assert!(matches!(
+            keys().refresh(&first.token, NOW + 3600 + TOKEN_LEEWAY_SECS + 1),
+            Err(WalletAuthError::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn bearer_token_requires_the_scheme() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(bearer_token(&headers), None);
+        headers.insert("authorization", "Bearer abc.def.ghi".parse().unwrap());
+        assert_eq!(bearer_token(&headers), Some("abc.def.ghi"));
+        headers.insert("authorization", "bearer  abc".parse().unwrap());
+        assert_eq!(bearer_token(&headers), Some("abc"));
+        headers.insert("authorization", "Basic abc".parse().unwrap());
+        assert_eq!(bearer_token(&headers), None);
+        headers.insert("authorization", "Bearer ".parse().unwrap());
+        assert_eq!(bearer_token(&headers), None);
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod wallet_auth_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, post, put},
+        Router,
+    };
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        handlers::{auth_challenge, auth_refresh, auth_verify, watchlist_put},
+        wallet_auth::{self, Challenge, SessionToken, WalletAuthError},
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKET: i64 = 9611;
+
+    fn wallet(seed: u8) -> (SigningKey, String) {
+        let key = SigningKey::from_bytes(&[seed; 32]);
+        let address = stellar_strkey::ed25519::PublicKey(key.verifying_key().to_bytes()).to_string();
+        (key, address)
+    }
+
+    fn sign(key: &SigningKey, message: &str) -> String {
+        BASE64.encode(key.sign(message.as_bytes()).to_bytes())
+    }
+
+    // ---------------------------------------------------------------------------
+    // Unit tests — signature verification
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn signature_by_the_account_verifies() {
+        let (key, address) = wallet(1);
+        let message = wallet_auth::challenge_message(&address, "abc123");
+        assert!(wallet_auth::verify_signature(&address, &message, &sign(&key, &message)).is_ok());
+    }
+
+    #[test]
+    fn signature_by_another_account_is_rejected() {
+        let (_, address) = wallet(1);
+        let (other, _) = wallet(2);
+        let message = wallet_auth::challenge_message(&address, "abc123");
+        assert!(matches!(
+            wallet_auth::verify_signature(&address, &message, &sign(&other, &message)),
+            Err(WalletAuthError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn signature_over_another_nonce_is_rejected() {
+        let (key, address) = wallet(1);
+        let signed = wallet_auth::challenge_message(&address, "nonce-a");
+        let expected = wallet_auth::challenge_message(&address, "nonce-b");
+        assert!(matches!(
+            wallet_auth::verify_signature(&address, &expected, &sign(&key, &signed)),
+            Err(WalletAuthError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn malformed_signature_and_address_are_rejected() {
+        let (_, address) = wallet(1);
+        let message = wallet_auth::challenge_message(&address, "abc123");
+        assert!(matches!(
+            wallet_auth::verify_signature(&address, &message, "not base64!"),
+            Err(WalletAuthError::BadSignature)
+        ));
+        assert!(matches!(
+            wallet_auth::verify_signature(&address, &message, &BASE64.encode([0u8; 12])),
+            Err(WalletAuthError::BadSignature)
+        ));
+        assert!(matches!(
+            wallet_auth::verify_signature("GNOTANADDRESS", &message, ""),
+            Err(WalletAuthError::InvalidAddress)
+        ));
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration helpers
+    // ---------------------------------------------------------------------------
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/auth/challenge", get(auth_challenge))
+            .route("/auth/verify", post(auth_verify))
+            .route("/auth/refresh", post(auth_refresh))
+            .route("/users/:address/watchlist/:market_id", put(watchlist_put))
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    async fn post_json(state: &Arc<crate::AppState>, uri: &str, body: Value) -> (StatusCode, Value) {
+        send(
+            state,
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn challenge(state: &Arc<crate::AppState>, address: &str) -> Challenge {
+        let (status, body) = send(
+            state,
+            Request::builder()
+                .uri(format!("/auth/challenge?address={address}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_value(body).unwrap()
+    }
+
+    async fn verify(
+        state: &Arc<crate::AppState>,
+        address: &str,
+        challenge: &Challenge,
+        signature: &str,
+    ) -> (StatusCode, Value) {
+        post_json(
+            state,
+            "/auth/verify",
+            json!({ "address": address, "nonce": challenge.nonce, "signature": signature }),
+        )
+        .await
+    }
+
+    async fn star(state: &Arc<crate::AppState>, address: &str, bearer: &str) -> (StatusCode, Value) {
+        send(
+            state,
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/users/{address}/watchlist/{SEED_MARKET}"))
+                .header("authorization", format!("Bearer {bearer}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+    }
+
+    async fn seed_market(state: &crate::AppState) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, outcome_options) \
+             VALUES ($1, 'Wallet auth', 'active', 0, NOW() + INTERVAL '1 day', NOW(), ARRAY['Yes', 'No'])",
+        )
+        .bind(SEED_MARKET)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        // Cascades to watchlists.
+        sqlx::query("DELETE FROM markets WHERE id = $1")
+            .bind(SEED_MARKET)
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Challenge, sign, verify, then use the token on a protected route and
+    /// refresh it. The answered challenge cannot be verified again.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_challenge_verify_and_protected_route() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state).await;
+        let (key, address) = wallet(21);
+
+        let issued = challenge(&state, &address).await;
+        let signature = sign(&key, &issued.message);
+        let (status, body) = verify(&state, &address, &issued, &signature).await;
+        assert_eq!(status, StatusCode::OK);
+        let session: SessionToken = serde_json::from_value(body).unwrap();
+        assert_eq!(session.address, address);
+        assert_eq!(session.token_type, "Bearer");
+
+        let (status, _) = star(&state, &address, &session.token).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, body) = verify(&state, &address, &issued, &signature).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "CHALLENGE_INVALID");
+
+        let (status, body) = post_json(&state, "/auth/refresh", json!({ "token": session.token })).await;
+        assert_eq!(status, StatusCode::OK);
+        let refreshed: SessionToken = serde_json::from_value(body).unwrap();
+        assert_eq!(refreshed.refresh_until, session.refresh_until);
+        let (status, _) = star(&state, &address, &refreshed.token).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        cleanup(&state).await;
+    }
+
+    /// A bad signature is a 401 and still burns the challenge.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_bad_signature_rejected_and_consumes_challenge() {
+        let state = build_test_state().await;
+        let (key, address) = wallet(22);
+        let (impostor, _) = wallet(23);
+
+        let issued = challenge(&state, &address).await;
+        let (status, body) = verify(&state, &address, &issued, &sign(&impostor, &issued.message)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "SIGNATURE_INVALID");
+
+        let (status, body) = verify(&state, &address, &issued, &sign(&key, &issued.message)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "CHALLENGE_INVALID");
+
+        // A challenge issued to another address does not verify for this one.
+        let (other_key, other_address) = wallet(24);
+        let foreign = challenge(&state, &other_address).await;
+        let (status, body) = verify(&state, &address, &foreign, &sign(&other_key, &foreign.message)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "CHALLENGE_INVALID");
+    }
+
+    /// Altering any part of a token makes the protected route a 401.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_tampered_token_rejected_by_protected_route() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state).await;
+        let (key, address) = wallet(25);
+
+        let issued = challenge(&state, &address).await;
+        let (_, body) = verify(&state, &address, &issued, &sign(&key, &issued.message)).await;
+        let session: SessionToken = serde_json::from_value(body).unwrap();
+
+        // Flip the first character of the signature.
+        let (signed, signature) = session.token.rsplit_once('.').unwrap();
+        let flipped = if signature.starts_with('A') { 'B' } else { 'A' };
+        let tampered = format!("{signed}.{flipped}{}", &signature[1..]);
+        let (status, body) = star(&state, &address, &tampered).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "TOKEN_INVALID");
+
+        let (status, body) = post_json(&state, "/auth/refresh", json!({ "token": tampered })).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "TOKEN_INVALID");
+
+        cleanup(&state).await;
+    }
+
+}
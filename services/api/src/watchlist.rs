@@ -0,0 +1,28 @@
+//! Markets a wallet has starred.
+//!
+//! A watchlist is keyed by Stellar account rather than email, so changing it
+//! requires proof of control of the account (see [`crate::wallet_auth`]);
+//! reading it does not. Unlike [`crate::market_watch`], starring a market
+//! sends no notifications.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::MarketDetail;
+
+/// Most entries `GET /api/v1/users/:address/watchlist` returns.
+pub const WATCHLIST_MAX_ENTRIES: i64 = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WatchlistEntry {
+    /// When the address first starred the market.
+    pub added_at: DateTime<Utc>,
+    pub market: MarketDetail,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Watchlist {
+    pub address: String,
+    /// Most recently starred first.
+    pub entries: Vec<WatchlistEntry>,
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod watchlist_tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::{get, put},
+        Router,
+    };
+    use serde_json::Value;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        handlers::{watchlist_delete, watchlist_get, watchlist_put},
+        wallet_auth::SessionKeys,
+        watchlist::Watchlist,
+    };
+    use crate::test_support::build_test_state;
+
+    // ---------------------------------------------------------------------------
+    // Helpers
+    // ---------------------------------------------------------------------------
+
+    /// Seeded market ids live in a reserved range so cleanup never touches
+    /// rows created by other tests.
+    const SEED_MARKETS: [i64; 3] = [9601, 9602, 9603];
+
+    const ADDRESS: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+    const OTHER_ADDRESS: &str = "GBRPYHIL2CI3FNQ4BXLFMNDLFJUNPU2HY3ZMFSHONUCEOASW7QC7OX2H";
+
+    fn app(state: Arc<crate::AppState>) -> Router {
+        Router::new()
+            .route("/users/:address/watchlist", get(watchlist_get))
+            .route(
+                "/users/:address/watchlist/:market_id",
+                put(watchlist_put).delete(watchlist_delete),
+            )
+            .with_state(state)
+    }
+
+    async fn send(state: &Arc<crate::AppState>, request: Request<Body>) -> (StatusCode, Value) {
+        let response = app(Arc::clone(state)).oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, body)
+    }
+
+    /// A session token for `address`, as `POST /auth/verify` would issue.
+    fn token(state: &crate::AppState, address: &str) -> String {
+        let now = chrono::Utc::now().timestamp();
+        SessionKeys::from_config(&state.config)
+            .issue(address, now, now)
+            .unwrap()
+            .token
+    }
+
+    /// `method` the watchlist entry with `bearer` as the session token.
+    async fn change(
+        state: &Arc<crate::AppState>,
+        method: &str,
+        address: &str,
+        market_id: i64,
+        bearer: Option<&str>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("/users/{address}/watchlist/{market_id}"));
+        if let Some(bearer) = bearer {
+            request = request.header("authorization", format!("Bearer {bearer}"));
+        }
+        send(state, request.body(Body::empty()).unwrap()).await
+    }
+
+    async fn list(state: &Arc<crate::AppState>, address: &str) -> Watchlist {
+        let (status, body) = send(
+            state,
+            Request::builder()
+                .uri(format!("/users/{address}/watchlist"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        serde_json::from_value(body).unwrap()
+    }
+
+    async fn seed_market(state: &crate::AppState, market_id: i64, title: &str) {
+        sqlx::query(
+            "INSERT INTO markets (id, title, status, total_volume, ends_at, created_at, outcome_options) \
+             VALUES ($1, $2, 'active', 0, NOW() + INTERVAL '1 day', NOW(), ARRAY['Yes', 'No'])",
+        )
+        .bind(market_id)
+        .bind(title)
+        .execute(&state.db.pool())
+        .await
+        .unwrap();
+    }
+
+    async fn cleanup(state: &crate::AppState) {
+        // Cascades to watchlists.
+        sqlx::query("DELETE FROM markets WHERE id = ANY($1)")
+            .bind(&SEED_MARKETS[..])
+            .execute(&state.db.pool())
+            .await
+            .unwrap();
+    }
+
+    // ---------------------------------------------------------------------------
+    // Integration tests — require PostgreSQL + Redis
+    // ---------------------------------------------------------------------------
+
+    /// Writes need a bearer token issued to the path's address: none is a
+    /// 401 and another address's token is a 403.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_writes_require_a_token_for_the_address() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9601, "Watchlist auth").await;
+
+        let (status, body) = change(&state, "PUT", ADDRESS, 9601, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["code"], "AUTH_REQUIRED");
+
+        let foreign = token(&state, OTHER_ADDRESS);
+        let (status, body) = change(&state, "PUT", ADDRESS, 9601, Some(&foreign)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["code"], "ADDRESS_MISMATCH");
+
+        assert!(list(&state, ADDRESS).await.entries.is_empty());
+        cleanup(&state).await;
+    }
+
+    /// The listing joins market details, newest star first; unknown markets
+    /// are a 404 and unstarring removes the entry.
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL + Redis
+    async fn test_listing_joins_market_details() {
+        let state = build_test_state().await;
+        cleanup(&state).await;
+        seed_market(&state, 9601, "First starred").await;
+        seed_market(&state, 9602, "Second starred").await;
+        seed_market(&state, 9603, "Never starred").await;
+        let bearer = token(&state, ADDRESS);
+        let star = |method, market_id| change(&state, method, ADDRESS, market_id, Some(&bearer));
+
+        assert_eq!(star("PUT", 9601).await.0, StatusCode::NO_CONTENT);
+        assert_eq!(star("PUT", 9602).await.0, StatusCode::NO_CONTENT);
+        // Starring twice is idempotent.
+        assert_eq!(star("PUT", 9601).await.0, StatusCode::NO_CONTENT);
+        assert_eq!(star("PUT", 9699).await.0, StatusCode::NOT_FOUND);
+
+        let watchlist = list(&state, ADDRESS).await;
+        assert_eq!(watchlist.address, ADDRESS);
+        let titles: Vec<&str> = watchlist.entries.iter().map(|e| e.market.title.as_str()).collect();
+        assert_eq!(titles, vec!["Second starred", "First starred"]);
+        assert_eq!(watchlist.entries[0].market.outcome_options, vec!["Yes", "No"]);
+
+        assert_eq!(star("DELETE", 9602).await.0, StatusCode::NO_CONTENT);
+        assert_eq!(star("DELETE", 9602).await.0, StatusCode::NOT_FOUND);
+        let ids: Vec<i64> = list(&state, ADDRESS).await.entries.iter().map(|e| e.market.id).collect();
+        assert_eq!(ids, vec![9601]);
+
+        cleanup(&state).await;
+    }
+
+}
@@ -11,7 +11,7 @@ mod tests {
 
     use axum::{
         body::Body,
-        extract::ConnectInfo,
+        extract::{ConnectInfo, State},
         http::{Request, StatusCode},
         middleware,
         routing::get,
@@ -53,15 +53,18 @@ mod tests {
         });
         let auth = Arc::new(ApiKeyAuth::new(vec!["valid-key".to_string()]));
 
+        // The audit layer is outermost so it also sees requests the key check
+        // rejects.
         let router = Router::new()
             .route("/api/v1/admin/markets/42/resolve", get(|| async { "resolved" }))
             .route("/api/v1/audit/logs", get(|| async { "audit logs" }))
+            .layer(middleware::from_fn_with_state(auth, security::api_key_middleware))
             .layer(middleware::from_fn_with_state(
                 state.clone(),
-                |state: Arc<TestState>,
+                |State(state): State<Arc<TestState>>,
                  addr: ConnectInfo<std::net::SocketAddr>,
                  headers: axum::http::HeaderMap,
-                 request: Request,
+                 request: Request<Body>,
                  next: axum::middleware::Next| async move {
                     let actor = headers
                         .get("x-api-key")
@@ -101,12 +104,15 @@ mod tests {
                         error_message,
                         request_id: None,
                         user_agent,
+                        route: Some(path.to_string()),
+                        method: Some(method.to_string()),
+                        response_status: Some(response.status().as_u16()),
+                        request_summary: None,
                     };
                     let _ = state.audit_logger.log(entry).await;
                     response
                 },
-            ))
-            .layer(middleware::from_fn_with_state(auth, security::api_key_middleware));
+            ));
 
         (router, logger)
     }
@@ -131,6 +137,7 @@ mod tests {
             .header("x-api-key", key)
             .header("user-agent", user_agent)
             .header("x-forwarded-for", "10.0.0.1")
+            .extension(ConnectInfo(std::net::SocketAddr::from(([10, 0, 0, 1], 443))))
             .body(Body::empty())
             .unwrap()
     }
@@ -4,8 +4,14 @@
 ///  - RPC timeout → worker retries and resumes from the correct ledger
 ///  - Connection reset → worker retries without crashing
 ///  - Ledger gap detection → warning metric is incremented
+///  - Sync planning → cursor behind, cursor ahead, and a small reorg
+///  - Admin contract calls → success only after a SUCCESS status, rejection, timeout
+///  - Multiple networks → clients sharing one Redis never see each other's data
+///  - Watched transactions → a restarted client resumes polling from Redis;
+///    final and over-age entries leave the watch set
 ///
-/// All tests require a live Redis instance (started via testcontainers).
+/// All tests require a live Redis instance (started via testcontainers) and
+/// a PostgreSQL database at `TEST_DATABASE_URL`.
 /// Run with: cargo test --features redis-integration
 #[cfg(feature = "redis-integration")]
 mod tests {
@@ -18,7 +24,15 @@ mod tests {
     };
 
     use axum::{routing::post, Json, Router};
-    use predictiq_api::{blockchain::BlockchainClient, cache::RedisCache, metrics::Metrics};
+    use predictiq_api::{
+        blockchain::{BlockchainClient, ContractCallError, NetworkClients, SyncPlan},
+        cache::{keys, RedisCache},
+        config::DbPoolConfig,
+        db::Database,
+        metrics::Metrics,
+        shutdown::ShutdownCoordinator,
+        signer::TxSigner,
+    };
     use reqwest::Client;
     use serde_json::{json, Value};
     use testcontainers::runners::AsyncRunner;
@@ -44,6 +58,36 @@ mod tests {
         Metrics::new().expect("Metrics::new")
     }
 
+    /// [`BlockchainClient::new_for_test`] backed by the database at
+    /// `TEST_DATABASE_URL`, migrated on connect.
+    async fn make_client(
+        rpc_url: String,
+        cache: RedisCache,
+        metrics: Metrics,
+        http: Client,
+        retry_attempts: u32,
+    ) -> BlockchainClient {
+        let url = std::env::var("TEST_DATABASE_URL")
+            .expect("TEST_DATABASE_URL must be set to run the sync worker tests");
+        let pool = DbPoolConfig {
+            min_connections: 1,
+            max_connections: 5,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: None,
+            max_lifetime: None,
+            query_timeout: Duration::from_secs(30),
+            statement_timeout_ms: 30_000,
+            lock_timeout_ms: 10_000,
+            run_migrations: true,
+            slow_query_threshold: Duration::from_millis(500),
+            sample_interval: Duration::from_secs(15),
+        };
+        let db = Database::new(&url, cache.clone(), metrics.clone(), &pool)
+            .await
+            .expect("Database::new");
+        BlockchainClient::new_for_test(rpc_url, cache, db, metrics, http, retry_attempts)
+    }
+
     /// Start an axum mock RPC server that returns `responses` in sequence.
     /// Each response is a complete JSON-RPC result envelope.
     async fn start_mock_rpc(responses: Vec<Value>) -> String {
@@ -137,7 +181,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let client = BlockchainClient::new_for_test(rpc_url, cache, metrics, http, 3);
+        let client = make_client(rpc_url, cache, metrics, http, 3).await;
 
         // health_check_cached() calls latest_ledger() which will time out.
         // After `retry_attempts` failures the call must return Err, not panic.
@@ -164,8 +208,7 @@ mod tests {
             .unwrap();
 
         let retry_attempts = 3_u32;
-        let client =
-            BlockchainClient::new_for_test(rpc_url, cache, metrics, http, retry_attempts);
+        let client = make_client(rpc_url, cache, metrics, http, retry_attempts).await;
 
         let result = client.health_check_cached().await;
         assert!(result.is_err(), "should fail after connection resets");
@@ -212,9 +255,7 @@ mod tests {
             .build()
             .unwrap();
 
-        let client = Arc::new(BlockchainClient::new_for_test(
-            rpc_url, cache, metrics, http, 2,
-        ));
+        let client = Arc::new(make_client(rpc_url, cache, metrics, http, 2).await);
 
         // sync_once(cursor=490) — confirmed tip = 500 - 1 = 499.
         // The worker should advance the cursor to 499 (confirmed_tip).
@@ -291,4 +332,476 @@ mod tests {
             "zero-size gap must not create a metric sample:\n{output}"
         );
     }
+
+    fn latest_ledger(sequence: u32) -> Value {
+        json!({ "result": { "latestLedger": { "sequence": sequence } } })
+    }
+
+    async fn planning_client(redis_url: &str, latest: Vec<u32>) -> BlockchainClient {
+        let rpc_url = start_mock_rpc(latest.into_iter().map(latest_ledger).collect()).await;
+        let http = Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        make_client(rpc_url, make_cache(redis_url).await, make_metrics(), http, 1).await
+    }
+
+    /// A cursor behind the confirmed tip catches up without rewinding.
+    #[tokio::test]
+    async fn plan_sync_cursor_behind_catches_up() {
+        let (redis_url, _container) = start_redis().await;
+        let client = planning_client(&redis_url, vec![500]).await;
+
+        let plan = client.plan_sync(490).await.unwrap();
+        assert_eq!(
+            plan,
+            SyncPlan { cursor: 490, confirmed_tip: 499, rewind_to: None }
+        );
+    }
+
+    /// A cursor past the chain head (e.g. left over from another network or a
+    /// reset) is rewound to the confirmed tip instead of skipping ahead.
+    #[tokio::test]
+    async fn plan_sync_cursor_ahead_rewinds_to_tip() {
+        let (redis_url, _container) = start_redis().await;
+        let client = planning_client(&redis_url, vec![500]).await;
+
+        let plan = client.plan_sync(800).await.unwrap();
+        assert_eq!(
+            plan,
+            SyncPlan { cursor: 499, confirmed_tip: 499, rewind_to: Some(499) }
+        );
+    }
+
+    /// The head moving back by more than the confirmation lag between two
+    /// passes rewinds indexing to the new confirmed tip.
+    #[tokio::test]
+    async fn plan_sync_small_reorg_rewinds_to_fork() {
+        let (redis_url, _container) = start_redis().await;
+        let client = planning_client(&redis_url, vec![500, 495]).await;
+
+        let first = client.plan_sync(490).await.unwrap();
+        assert_eq!(first.rewind_to, None);
+
+        let second = client.plan_sync(499).await.unwrap();
+        assert_eq!(
+            second,
+            SyncPlan { cursor: 494, confirmed_tip: 494, rewind_to: Some(494) }
+        );
+    }
+
+    /// An unreachable RPC node holds the cursor rather than treating the
+    /// missing head as a reorg.
+    #[tokio::test]
+    async fn plan_sync_holds_cursor_when_rpc_fails() {
+        let (redis_url, _container) = start_redis().await;
+        let rpc_url = start_timeout_rpc().await;
+        let http = Client::builder()
+            .timeout(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let client =
+            make_client(rpc_url, make_cache(&redis_url).await, make_metrics(), http, 1).await;
+
+        let plan = client.plan_sync(490).await.unwrap();
+        assert_eq!(plan, SyncPlan::hold(490));
+    }
+
+    async fn relay_client(redis_url: &str, response: Value) -> BlockchainClient {
+        let rpc_url = start_mock_rpc(vec![response]).await;
+        let http = Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        make_client(rpc_url, make_cache(redis_url).await, make_metrics(), http, 1).await
+    }
+
+    /// A failed simulation is a result, not a transport error, and the node's
+    /// message reaches the caller unmodified.
+    #[tokio::test]
+    async fn simulate_transaction_passes_error_through_verbatim() {
+        let (redis_url, _container) = start_redis().await;
+        let message = "HostError: Error(Contract, #7)\nDebugInfo not available";
+        let client = relay_client(
+            &redis_url,
+            json!({ "result": { "latestLedger": 900, "error": message } }),
+        )
+        .await;
+
+        let sim = client.simulate_transaction("AAAA").await.unwrap();
+        assert_eq!(sim.error.as_deref(), Some(message));
+        assert!(sim.results.is_empty());
+    }
+
+    /// An accepted submission is registered with the transaction monitor.
+    #[tokio::test]
+    async fn submit_transaction_pending_is_watched() {
+        let (redis_url, _container) = start_redis().await;
+        let client = relay_client(
+            &redis_url,
+            json!({ "result": { "hash": "ab12", "status": "PENDING", "latestLedger": 900 } }),
+        )
+        .await;
+
+        let sub = client.submit_transaction("AAAA").await.unwrap();
+        assert!(sub.is_accepted());
+        assert!(sub.watched);
+        assert!(client.is_watched("ab12").await);
+    }
+
+    /// A rejected submission carries the result XDR and is not watched.
+    #[tokio::test]
+    async fn submit_transaction_error_is_not_watched() {
+        let (redis_url, _container) = start_redis().await;
+        let client = relay_client(
+            &redis_url,
+            json!({ "result": {
+                "hash": "cd34",
+                "status": "ERROR",
+                "latestLedger": 900,
+                "errorResultXdr": "AAAAAAAAAGT////7AAAAAA=="
+            } }),
+        )
+        .await;
+
+        let sub = client.submit_transaction("AAAA").await.unwrap();
+        assert!(!sub.is_accepted());
+        assert!(!sub.watched);
+        assert_eq!(sub.error_result_xdr.as_deref(), Some("AAAAAAAAAGT////7AAAAAA=="));
+        assert!(!client.is_watched("cd34").await);
+    }
+
+    // ── admin contract calls ──────────────────────────────────────────────────
+
+    const ADMIN_SEED: &str = "SAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYPSBF5K";
+
+    fn admin_signer() -> TxSigner {
+        TxSigner::from_secret(&secrecy::SecretString::new(ADMIN_SEED.to_string())).unwrap()
+    }
+
+    /// `getLedgerEntries` answer for the admin account at sequence 41.
+    fn account_entry() -> Value {
+        use stellar_xdr::curr::{self as xdr, WriteXdr};
+        let entry = xdr::LedgerEntryData::Account(xdr::AccountEntry {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(
+                admin_signer().public_key(),
+            ))),
+            balance: 100_000_000,
+            seq_num: xdr::SequenceNumber(41),
+            num_sub_entries: 0,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: xdr::String32::default(),
+            thresholds: xdr::Thresholds([1, 0, 0, 0]),
+            signers: xdr::VecM::default(),
+            ext: xdr::AccountEntryExt::V0,
+        });
+        json!({ "result": {
+            "entries": [{ "xdr": entry.to_xdr_base64(xdr::Limits::none()).unwrap() }],
+            "latestLedger": 900
+        } })
+    }
+
+    fn simulation_ok() -> Value {
+        use stellar_xdr::curr::{self as xdr, WriteXdr};
+        let data = xdr::SorobanTransactionData {
+            ext: xdr::ExtensionPoint::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: xdr::VecM::default(),
+                },
+                instructions: 1_000,
+                read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 5_000,
+        };
+        json!({ "result": {
+            "latestLedger": 900,
+            "minResourceFee": "5000",
+            "transactionData": data.to_xdr_base64(xdr::Limits::none()).unwrap(),
+            "results": [{ "xdr": "AAAAAQ==", "auth": [] }]
+        } })
+    }
+
+    fn sent(status: &str) -> Value {
+        json!({ "result": { "hash": "feed01", "status": status, "latestLedger": 900 } })
+    }
+
+    fn tx_status(status: &str) -> Value {
+        json!({ "result": { "status": status, "ledger": 902 } })
+    }
+
+    async fn admin_client(redis_url: &str, responses: Vec<Value>) -> BlockchainClient {
+        let rpc_url = start_mock_rpc(responses).await;
+        let http = Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        make_client(rpc_url, make_cache(redis_url).await, make_metrics(), http, 1).await
+            .with_admin_signer(admin_signer())
+    }
+
+    /// A pending status is polled past; the call returns only once the node
+    /// reports SUCCESS.
+    #[tokio::test]
+    async fn resolve_market_onchain_waits_for_success() {
+        let (redis_url, _container) = start_redis().await;
+        let client = admin_client(
+            &redis_url,
+            vec![
+                account_entry(),
+                simulation_ok(),
+                sent("PENDING"),
+                tx_status("NOT_FOUND"),
+                tx_status("SUCCESS"),
+            ],
+        )
+        .await;
+
+        let status = client.resolve_market_onchain(7, 1).await.unwrap();
+        assert_eq!(status.hash, "feed01");
+        assert_eq!(status.status, "SUCCESS");
+        assert_eq!(status.ledger, Some(902));
+    }
+
+    #[tokio::test]
+    async fn resolve_market_onchain_failed_transaction_is_rejected() {
+        let (redis_url, _container) = start_redis().await;
+        let client = admin_client(
+            &redis_url,
+            vec![account_entry(), simulation_ok(), sent("PENDING"), tx_status("FAILED")],
+        )
+        .await;
+
+        let err = client.resolve_market_onchain(7, 1).await.unwrap_err();
+        assert!(
+            matches!(&err, ContractCallError::Rejected { hash, status, .. } if hash == "feed01" && status == "FAILED"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_market_onchain_simulation_error_is_not_submitted() {
+        let (redis_url, _container) = start_redis().await;
+        let client = admin_client(
+            &redis_url,
+            vec![
+                account_entry(),
+                json!({ "result": { "latestLedger": 900, "error": "Error(Contract, #2)" } }),
+            ],
+        )
+        .await;
+
+        let err = client.resolve_market_onchain(7, 9).await.unwrap_err();
+        assert!(matches!(&err, ContractCallError::Simulation(msg) if msg == "Error(Contract, #2)"));
+    }
+
+    /// A transaction that never becomes final times out with its hash so the
+    /// caller can check on it later.
+    #[tokio::test]
+    async fn resolve_market_onchain_times_out_while_pending() {
+        let (redis_url, _container) = start_redis().await;
+        let mut responses = vec![account_entry(), simulation_ok(), sent("PENDING")];
+        responses.extend(std::iter::repeat_with(|| tx_status("NOT_FOUND")).take(200));
+        let client = admin_client(&redis_url, responses).await;
+
+        let err = client.resolve_market_onchain(7, 1).await.unwrap_err();
+        assert!(matches!(&err, ContractCallError::Timeout { hash } if hash == "feed01"));
+    }
+
+    #[tokio::test]
+    async fn resolve_market_onchain_without_signer_is_not_configured() {
+        let (redis_url, _container) = start_redis().await;
+        let client = planning_client(&redis_url, vec![]).await;
+
+        let err = client.resolve_market_onchain(7, 1).await.unwrap_err();
+        assert!(matches!(err, ContractCallError::NotConfigured));
+    }
+
+    // ── multiple networks ─────────────────────────────────────────────────────
+
+    /// A node that answers every call with the same result, carrying both a
+    /// ledger sequence and market fields.
+    async fn network_client(redis_url: &str, ledger: u32, title: &str) -> BlockchainClient {
+        let response = json!({
+            "result": {
+                "latestLedger": { "sequence": ledger },
+                "title": title,
+                "onchain_volume": ledger.to_string(),
+            }
+        });
+        let rpc_url = start_mock_rpc(std::iter::repeat(response).take(16).collect()).await;
+        let http = Client::builder()
+            .timeout(Duration::from_secs(2))
+            .build()
+            .unwrap();
+        make_client(rpc_url, make_cache(redis_url).await, make_metrics(), http, 1).await
+    }
+
+    /// The same market id on two networks resolves to each network's own
+    /// node, both on the first fetch and when served from the shared cache.
+    #[tokio::test]
+    async fn networks_sharing_a_cache_do_not_cross_contaminate() {
+        let (redis_url, _container) = start_redis().await;
+        let testnet = network_client(&redis_url, 100, "testnet market").await;
+        let mainnet = network_client(&redis_url, 200, "mainnet market")
+            .await
+            .with_network("mainnet");
+        let networks = NetworkClients::single(testnet).with(mainnet).unwrap();
+
+        for _pass in 0..2 {
+            let test = networks.resolve(None).unwrap().market_data_cached(7).await.unwrap();
+            let main = networks.resolve(Some("mainnet")).unwrap().market_data_cached(7).await.unwrap();
+            assert_eq!(test.title.as_deref(), Some("testnet market"));
+            assert_eq!(test.onchain_volume, "100");
+            assert_eq!(main.title.as_deref(), Some("mainnet market"));
+            assert_eq!(main.onchain_volume, "200");
+
+            let test = networks.get("testnet").unwrap().health_check_cached().await.unwrap();
+            let main = networks.get("mainnet").unwrap().health_check_cached().await.unwrap();
+            assert_eq!((test.network.as_str(), test.latest_ledger), ("testnet", 100));
+            assert_eq!((main.network.as_str(), main.latest_ledger), ("mainnet", 200));
+        }
+    }
+
+    #[tokio::test]
+    async fn network_resolution_defaults_to_primary_and_rejects_unknown_names() {
+        let (redis_url, _container) = start_redis().await;
+        let networks = NetworkClients::single(network_client(&redis_url, 100, "t").await)
+            .with(network_client(&redis_url, 200, "m").await.with_network("mainnet"))
+            .unwrap();
+
+        assert_eq!(networks.resolve(None).unwrap().network(), "testnet");
+        assert_eq!(networks.resolve(Some("  ")).unwrap().network(), "testnet");
+        assert_eq!(networks.resolve(Some("MAINNET")).unwrap().network(), "mainnet");
+        assert!(networks.resolve(Some("futurenet")).is_none());
+        assert_eq!(networks.names(), vec!["mainnet", "testnet"]);
+
+        // The primary's name cannot be claimed by a second client.
+        let duplicate = network_client(&redis_url, 300, "x").await;
+        assert!(networks.with(duplicate).is_err());
+    }
+
+    // ── watched transactions ──────────────────────────────────────────────────
+
+    /// Mock RPC answering `getTransaction` with the status listed for the
+    /// hash (`NOT_FOUND` otherwise) and recording every hash it was asked about.
+    async fn start_tx_status_rpc(statuses: Vec<(&'static str, &'static str)>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let polled = Arc::new(Mutex::new(Vec::new()));
+        let seen = polled.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(body): Json<Value>| {
+                let (statuses, seen) = (statuses.clone(), seen.clone());
+                async move {
+                    let hash = body["params"]["hash"].as_str().unwrap_or_default().to_string();
+                    let status = statuses
+                        .iter()
+                        .find(|(h, _)| *h == hash)
+                        .map_or("NOT_FOUND", |(_, s)| *s);
+                    seen.lock().await.push(hash);
+                    Json(json!({ "result": { "status": status, "ledger": 10 } }))
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, polled)
+    }
+
+    async fn tx_client(rpc_url: &str, redis_url: &str, metrics: Metrics) -> BlockchainClient {
+        let http = Client::builder().timeout(Duration::from_secs(2)).build().unwrap();
+        make_client(rpc_url.to_string(), make_cache(redis_url).await, metrics, http, 1).await
+    }
+
+    /// Run `client`'s transaction monitor for `duration`, then stop it.
+    async fn run_monitor_for(client: Arc<BlockchainClient>, duration: Duration) {
+        let coordinator = ShutdownCoordinator::new(1);
+        let token = coordinator.token();
+        let monitor = tokio::spawn(client.run_transaction_monitor(token.clone(), coordinator.clone()));
+        tokio::time::sleep(duration).await;
+        token.cancel();
+        monitor.await.unwrap();
+    }
+
+    /// A client started after another one watched a hash — the restart case —
+    /// polls it without being told, and drops it from Redis once it is final.
+    #[tokio::test]
+    async fn restarted_client_resumes_polling_watched_transactions() {
+        let (redis_url, _container) = start_redis().await;
+        let (rpc_url, polled) = start_tx_status_rpc(vec![("confirmed-tx", "SUCCESS")]).await;
+
+        let before = tx_client(&rpc_url, &redis_url, make_metrics()).await;
+        before.watch_transaction("pending-tx").await.unwrap();
+        before.watch_transaction("confirmed-tx").await.unwrap();
+        drop(before);
+
+        let after = Arc::new(tx_client(&rpc_url, &redis_url, make_metrics()).await);
+        assert!(!after.is_watched("pending-tx").await);
+        run_monitor_for(after.clone(), Duration::from_millis(300)).await;
+
+        let polled = polled.lock().await.clone();
+        assert!(polled.iter().any(|h| h == "pending-tx"), "polled: {polled:?}");
+        assert!(polled.iter().any(|h| h == "confirmed-tx"), "polled: {polled:?}");
+        assert!(after.is_watched("pending-tx").await);
+        assert!(!after.is_watched("confirmed-tx").await);
+
+        let persisted = make_cache(&redis_url)
+            .await
+            .zset_members(&keys::tx_watch_set("testnet"))
+            .await
+            .unwrap();
+        let hashes: Vec<_> = persisted.iter().map(|(h, _)| h.as_str()).collect();
+        assert_eq!(hashes, vec!["pending-tx"]);
+    }
+
+    /// Entries older than the max age are expired and counted, not polled.
+    #[tokio::test]
+    async fn watched_transactions_past_max_age_expire() {
+        let (redis_url, _container) = start_redis().await;
+        let (rpc_url, polled) = start_tx_status_rpc(vec![]).await;
+
+        tx_client(&rpc_url, &redis_url, make_metrics())
+            .await
+            .watch_transaction("stale-tx")
+            .await
+            .unwrap();
+
+        let metrics = make_metrics();
+        let client = tx_client(&rpc_url, &redis_url, metrics.clone())
+            .await
+            .with_watched_tx_ttl(Duration::ZERO);
+        run_monitor_for(Arc::new(client), Duration::from_millis(200)).await;
+
+        assert!(polled.lock().await.is_empty());
+        let persisted = make_cache(&redis_url)
+            .await
+            .zset_members(&keys::tx_watch_set("testnet"))
+            .await
+            .unwrap();
+        assert!(persisted.is_empty());
+        assert!(metrics
+            .render()
+            .unwrap()
+            .contains("tx_watch_expired_total{network=\"testnet\"} 1"));
+    }
+
+    /// Watch sets are namespaced per network.
+    #[tokio::test]
+    async fn watch_sets_are_scoped_per_network() {
+        let (redis_url, _container) = start_redis().await;
+        let (rpc_url, _) = start_tx_status_rpc(vec![]).await;
+
+        let mainnet = tx_client(&rpc_url, &redis_url, make_metrics()).await.with_network("mainnet");
+        mainnet.watch_transaction("mainnet-tx").await.unwrap();
+
+        let cache = make_cache(&redis_url).await;
+        assert!(cache.zset_members(&keys::tx_watch_set("testnet")).await.unwrap().is_empty());
+        assert_eq!(cache.zset_members(&keys::tx_watch_set("mainnet")).await.unwrap().len(), 1);
+    }
 }
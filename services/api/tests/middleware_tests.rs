@@ -1,149 +0,0 @@
-/// End-to-end HTTP tests for rate limiting middleware behavior.
-///
-/// Covers:
-/// - 200 → 429 transitions through the actual middleware stack
-/// - Per-IP key isolation (different IPs don't share quotas)
-/// - Cleanup bounds map size under high-cardinality load
-#[cfg(test)]
-mod tests {
-    use std::{sync::Arc, time::Duration};
-
-    use axum::{
-        body::Body,
-        http::{Request, StatusCode},
-        middleware,
-        routing::get,
-        Router,
-    };
-    use predictiq_api::security::{global_rate_limit_middleware, RateLimitConfig, RateLimiter};
-    use tower::ServiceExt; // for `oneshot`
-
-    /// Build a minimal router with global rate limiting applied.
-    fn app(limiter: Arc<RateLimiter>) -> Router {
-        Router::new()
-            .route("/ping", get(|| async { "ok" }))
-            .layer(middleware::from_fn_with_state(
-                limiter,
-                global_rate_limit_middleware,
-            ))
-    }
-
-    fn get_request(ip: &str) -> Request<Body> {
-        Request::builder()
-            .uri("/ping")
-            .header("x-forwarded-for", ip)
-            .body(Body::empty())
-            .unwrap()
-    }
-
-    // ── 200 / 429 transition ─────────────────────────────────────────────────
-
-    #[tokio::test]
-    async fn test_middleware_allows_requests_within_limit() {
-        // Global limit is 100/min; a single request must return 200.
-        let limiter = Arc::new(RateLimiter::new());
-        let response = app(limiter)
-            .oneshot(get_request("1.2.3.4"))
-            .await
-            .unwrap();
-
-        assert_eq!(response.status(), StatusCode::OK);
-    }
-
-    #[tokio::test]
-    async fn test_middleware_returns_429_after_limit_exceeded() {
-        // Use a tight config via the limiter directly, then hit the middleware.
-        // We exhaust the global limit (100) by calling check() 100 times, then
-        // the next HTTP request through the middleware must get 429.
-        let limiter = Arc::new(RateLimiter::new());
-        let config = RateLimitConfig::new(100, Duration::from_secs(60));
-
-        for _ in 0..100 {
-            limiter.check("global:1.2.3.4", &config).await;
-        }
-
-        let response = app(limiter)
-            .oneshot(get_request("1.2.3.4"))
-            .await
-            .unwrap();
-
-        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
-    }
-
-    // ── Per-IP isolation ─────────────────────────────────────────────────────
-
-    #[tokio::test]
-    async fn test_middleware_isolates_keys_per_ip() {
-        let limiter = Arc::new(RateLimiter::new());
-        let config = RateLimitConfig::new(100, Duration::from_secs(60));
-
-        // Exhaust quota for IP A.
-        for _ in 0..100 {
-            limiter.check("global:10.0.0.1", &config).await;
-        }
-
-        // IP A is now rate-limited.
-        let resp_a = app(limiter.clone())
-            .oneshot(get_request("10.0.0.1"))
-            .await
-            .unwrap();
-        assert_eq!(resp_a.status(), StatusCode::TOO_MANY_REQUESTS);
-
-        // IP B has a fresh quota and must still get 200.
-        let resp_b = app(limiter)
-            .oneshot(get_request("10.0.0.2"))
-            .await
-            .unwrap();
-        assert_eq!(resp_b.status(), StatusCode::OK);
-    }
-
-    // ── Cleanup / map-size bound ─────────────────────────────────────────────
-
-    #[tokio::test]
-    async fn test_cleanup_removes_expired_entries() {
-        // Insert many unique keys with a very short window so they expire fast.
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(1, Duration::from_millis(50));
-
-        for i in 0..500 {
-            limiter.check(&format!("ip:{i}"), &config).await;
-        }
-
-        // Wait for all windows to expire (cleanup retains entries < 1 hour old
-        // by default, so we use the public check() to age them out via window
-        // reset, then verify cleanup reduces the internal count indirectly by
-        // asserting subsequent checks still work — the real assertion is that
-        // cleanup() doesn't panic and the limiter remains functional).
-        tokio::time::sleep(Duration::from_millis(100)).await;
-        limiter.cleanup().await;
-
-        // After cleanup the limiter must still accept new keys correctly.
-        let config2 = RateLimitConfig::new(2, Duration::from_secs(60));
-        assert!(limiter.check("fresh-key", &config2).await);
-        assert!(limiter.check("fresh-key", &config2).await);
-        assert!(!limiter.check("fresh-key", &config2).await);
-    }
-
-    #[tokio::test]
-    async fn test_cleanup_bounds_map_under_high_cardinality() {
-        // Simulate a long-running process: insert 10_000 unique keys, run
-        // cleanup, then insert another batch. The limiter must remain correct.
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(1, Duration::from_millis(1));
-
-        for i in 0..10_000 {
-            limiter.check(&format!("stress:{i}"), &config).await;
-        }
-
-        // Let all windows expire.
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        limiter.cleanup().await;
-
-        // New keys must still be tracked correctly after cleanup.
-        let fresh_config = RateLimitConfig::new(3, Duration::from_secs(60));
-        for i in 0..100 {
-            let key = format!("post-cleanup:{i}");
-            assert!(limiter.check(&key, &fresh_config).await);
-        }
-    }
-}
@@ -0,0 +1,74 @@
+#[cfg(feature = "db-integration")]
+#[allow(dead_code)]
+mod common;
+
+/// Integration tests for the embedded migration runner.
+///
+/// Covers:
+///  - A fresh database reaches the full schema, including the indexes the
+///    hot queries rely on
+///  - Running the migrations a second time applies nothing and leaves
+///    nothing pending
+///
+/// Requires a PostgreSQL database at `TEST_DATABASE_URL`.
+/// Run with: cargo test --features db-integration
+#[cfg(feature = "db-integration")]
+mod tests {
+    use predictiq_api::migrations::MigrationRunner;
+
+    use super::common::db_fixture::test_pool;
+
+    #[tokio::test]
+    async fn migrations_are_idempotent() {
+        let pool = test_pool().await;
+        let runner = MigrationRunner::new(&pool);
+
+        runner.run().await.expect("first run");
+        assert!(runner.pending().await.unwrap().is_empty());
+        let applied = runner.status().await.unwrap();
+
+        assert_eq!(runner.run().await.expect("second run"), 0);
+        assert!(runner.pending().await.unwrap().is_empty());
+        let reapplied = runner.status().await.unwrap();
+        assert_eq!(
+            applied.iter().map(|m| (&m.version, &m.checksum)).collect::<Vec<_>>(),
+            reapplied.iter().map(|m| (&m.version, &m.checksum)).collect::<Vec<_>>(),
+        );
+
+        for table in [
+            "markets",
+            "newsletter_subscribers",
+            "email_jobs",
+            "waitlist_entries",
+            "audit_log",
+            "api_keys",
+            "watched_transactions",
+            "content",
+        ] {
+            let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+                .bind(table)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert!(exists, "table {table} missing");
+        }
+
+        for index in [
+            "idx_markets_list_volume",
+            "newsletter_subscribers_email_key",
+            "idx_email_jobs_status_priority_scheduled",
+            "waitlist_entries_email_key",
+            "uq_waitlist_entries_referral_code",
+        ] {
+            let valid: Option<bool> = sqlx::query_scalar(
+                "SELECT i.indisvalid FROM pg_index i JOIN pg_class c ON c.oid = i.indexrelid \
+                 WHERE c.relname = $1",
+            )
+            .bind(index)
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+            assert_eq!(valid, Some(true), "index {index} missing or invalid");
+        }
+    }
+}
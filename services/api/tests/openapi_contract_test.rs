@@ -5,58 +5,360 @@
 ///
 /// The SPEC_ROUTES table is the authoritative mirror of openapi.yaml paths.
 /// The yaml_paths_match_spec_routes test parses the YAML at test-time and
-/// fails if the two diverge, preventing silent spec drift.
+/// fails if the two diverge, preventing silent spec drift. The document
+/// generated from the handler annotations (served at /api/v1/openapi.json)
+/// is held to the same table, and its schema names to SCHEMA_NAMES.
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::{BTreeSet, HashSet};
+
+    use predictiq_api::openapi_spec::ApiDoc;
+    use serde_json::Value;
+    use utoipa::OpenApi;
 
     /// All (METHOD, path) pairs declared in openapi.yaml.
     /// Must stay in sync — the yaml_paths_match_spec_routes test enforces this.
     const SPEC_ROUTES: &[(&str, &str)] = &[
         ("GET", "/health"),
         ("GET", "/api/v1/statistics"),
+        ("GET", "/api/v1/statistics/history"),
+        ("GET", "/api/v1/markets"),
         ("GET", "/api/v1/markets/featured"),
+        ("GET", "/api/v1/categories"),
+        ("GET", "/api/v1/markets/{market_id}"),
+        ("GET", "/api/v1/markets/{market_id}/history"),
+        ("GET", "/api/v1/markets/{market_id}/image"),
+        ("POST", "/api/v1/markets/{market_id}/watches"),
+        ("DELETE", "/api/v1/watches/{token}"),
+        ("GET", "/api/v1/watches/unsubscribe"),
+        ("GET", "/api/v1/users/{address}/portfolio"),
+        ("GET", "/api/v1/users/{address}/watchlist"),
+        ("PUT", "/api/v1/users/{address}/watchlist/{market_id}"),
+        ("DELETE", "/api/v1/users/{address}/watchlist/{market_id}"),
+        ("GET", "/api/v1/users/{address}/notifications"),
+        ("PUT", "/api/v1/users/{address}/notifications"),
+        ("GET", "/api/v1/notifications/confirm"),
+        ("GET", "/api/v1/notifications/unsubscribe"),
+        ("GET", "/api/v1/auth/challenge"),
+        ("POST", "/api/v1/auth/verify"),
+        ("POST", "/api/v1/auth/refresh"),
+        ("GET", "/api/v1/leaderboard"),
+        ("GET", "/api/v1/creators/top"),
+        ("GET", "/api/v1/creators/{address}"),
+        ("GET", "/api/v1/activity"),
         ("GET", "/api/v1/content"),
+        ("GET", "/api/v1/content/{slug}"),
         ("POST", "/api/v1/markets/{market_id}/resolve"),
+        ("POST", "/api/v1/admin/oracle/{market_id}/result"),
         ("GET", "/api/v1/blockchain/health"),
+        ("GET", "/api/v1/blockchain/protocol-state"),
+        ("GET", "/api/v1/blockchain/call/{fn_name}"),
         ("GET", "/api/v1/blockchain/markets/{market_id}"),
         ("GET", "/api/v1/blockchain/stats"),
         ("GET", "/api/v1/blockchain/users/{user}/bets"),
         ("GET", "/api/v1/blockchain/oracle/{market_id}"),
         ("GET", "/api/v1/blockchain/tx/{tx_hash}"),
+        ("POST", "/api/v1/blockchain/tx/{tx_hash}/watch"),
+        ("POST", "/api/v1/blockchain/batch"),
+        ("POST", "/api/v1/tx/simulate"),
+        ("POST", "/api/v1/tx/submit"),
         ("POST", "/api/v1/newsletter/subscribe"),
         ("GET", "/api/v1/newsletter/confirm"),
-        ("DELETE", "/api/v1/newsletter/unsubscribe"),
+        ("POST", "/api/v1/newsletter/resend-confirmation"),
+        ("GET", "/api/v1/newsletter/unsubscribe"),
+        ("GET", "/api/v1/newsletter/preferences"),
+        ("PUT", "/api/v1/newsletter/preferences"),
         ("GET", "/api/v1/newsletter/gdpr/export"),
         ("DELETE", "/api/v1/newsletter/gdpr/delete"),
+        ("POST", "/api/v1/contact"),
+        ("GET", "/api/v1/admin/contact"),
+        ("POST", "/api/v1/admin/contact/{id}/status"),
+        ("PUT", "/api/v1/admin/markets/{market_id}/chain-ids/{network}"),
+        ("DELETE", "/api/v1/admin/markets/{market_id}/chain-ids/{network}"),
+        ("POST", "/api/v1/admin/markets/{market_id}/image"),
+        ("POST", "/api/v1/admin/categories"),
+        ("PATCH", "/api/v1/admin/categories/{slug}"),
+        ("DELETE", "/api/v1/admin/categories/{slug}"),
+        ("POST", "/api/v1/admin/content"),
+        ("PUT", "/api/v1/admin/content/{id}"),
+        ("DELETE", "/api/v1/admin/content/{id}"),
+        ("POST", "/api/v1/admin/content/{id}/publish"),
+        ("POST", "/api/v1/waitlist"),
+        ("GET", "/api/v1/waitlist/status"),
+        ("POST", "/api/v1/admin/waitlist/invite"),
+        ("GET", "/api/v1/admin/waitlist/stats"),
+        ("GET", "/api/v1/admin/waitlist/export.csv"),
+        ("GET", "/api/v1/admin/newsletter/export.csv"),
+        ("GET", "/api/v1/admin/events/export.csv"),
+        ("POST", "/api/v1/admin/indexer/backfill"),
+        ("GET", "/api/v1/admin/indexer/backfill/{id}"),
+        ("POST", "/api/v1/analytics/events"),
+        ("GET", "/api/v1/admin/analytics/summary"),
+        ("GET", "/api/v1/admin/analytics/acquisition"),
+        ("GET", "/api/v1/admin/analytics/acquisition.csv"),
         ("GET", "/api/v1/email/preview/{template_name}"),
         ("POST", "/api/v1/email/test"),
         ("GET", "/api/v1/email/analytics"),
         ("GET", "/api/v1/email/queue/stats"),
         ("POST", "/api/blockchain/replay"),
-        ("GET", "/api/v1/email/queue/dead-letter"),
-        ("POST", "/api/v1/email/queue/dead-letter/{job_id}/requeue"),
+        ("GET", "/api/v1/admin/email/dead-letter"),
+        ("POST", "/api/v1/admin/email/dead-letter/{job_id}/retry"),
+        ("GET", "/api/v1/admin/email/blocked-domains"),
+        ("POST", "/api/v1/admin/email/blocked-domains"),
+        ("DELETE", "/api/v1/admin/email/blocked-domains/{id}"),
+        ("GET", "/api/v1/gdpr/export"),
+        ("DELETE", "/api/v1/gdpr/delete"),
+        ("POST", "/api/v1/admin/campaigns"),
+        ("GET", "/api/v1/admin/campaigns/{id}"),
+        ("POST", "/api/v1/admin/campaigns/{id}/launch"),
+        ("POST", "/api/v1/admin/campaigns/{id}/cancel"),
+        ("GET", "/api/v1/admin/digest/preview"),
+        ("GET", "/api/v1/admin/audit"),
         ("GET", "/api/v1/audit/logs"),
         ("GET", "/api/v1/audit/statistics"),
+        ("GET", "/api/v1/admin/cache/keys"),
+        ("GET", "/api/v1/admin/cache/entry"),
+        ("DELETE", "/api/v1/admin/cache"),
+        ("GET", "/api/v1/admin/keys/{id}/usage"),
+        ("GET", "/api/v1/admin/risk/markets/{market_id}/exposure"),
+        ("GET", "/api/v1/admin/risk/alerts"),
         ("POST", "/webhooks/sendgrid"),
     ];
 
     /// Admin routes that must declare ApiKeyAuth security in the spec.
     const ADMIN_ROUTES: &[(&str, &str)] = &[
         ("POST", "/api/v1/markets/{market_id}/resolve"),
+        ("GET", "/api/v1/admin/contact"),
+        ("POST", "/api/v1/admin/contact/{id}/status"),
+        ("PUT", "/api/v1/admin/markets/{market_id}/chain-ids/{network}"),
+        ("DELETE", "/api/v1/admin/markets/{market_id}/chain-ids/{network}"),
+        ("POST", "/api/v1/admin/markets/{market_id}/image"),
+        ("POST", "/api/v1/admin/categories"),
+        ("PATCH", "/api/v1/admin/categories/{slug}"),
+        ("DELETE", "/api/v1/admin/categories/{slug}"),
+        ("POST", "/api/v1/admin/content"),
+        ("PUT", "/api/v1/admin/content/{id}"),
+        ("DELETE", "/api/v1/admin/content/{id}"),
+        ("POST", "/api/v1/admin/content/{id}/publish"),
+        ("POST", "/api/v1/admin/waitlist/invite"),
+        ("GET", "/api/v1/admin/waitlist/stats"),
+        ("GET", "/api/v1/admin/waitlist/export.csv"),
+        ("GET", "/api/v1/admin/newsletter/export.csv"),
+        ("GET", "/api/v1/admin/events/export.csv"),
+        ("POST", "/api/v1/admin/indexer/backfill"),
+        ("GET", "/api/v1/admin/indexer/backfill/{id}"),
+        ("GET", "/api/v1/admin/analytics/summary"),
+        ("GET", "/api/v1/admin/analytics/acquisition"),
+        ("GET", "/api/v1/admin/analytics/acquisition.csv"),
         ("GET", "/api/v1/email/preview/{template_name}"),
         ("POST", "/api/v1/email/test"),
         ("GET", "/api/v1/email/analytics"),
         ("GET", "/api/v1/email/queue/stats"),
         ("POST", "/api/blockchain/replay"),
-        ("GET", "/api/v1/email/queue/dead-letter"),
-        ("POST", "/api/v1/email/queue/dead-letter/{job_id}/requeue"),
+        ("GET", "/api/v1/admin/email/dead-letter"),
+        ("POST", "/api/v1/admin/email/dead-letter/{job_id}/retry"),
+        ("GET", "/api/v1/admin/email/blocked-domains"),
+        ("POST", "/api/v1/admin/email/blocked-domains"),
+        ("DELETE", "/api/v1/admin/email/blocked-domains/{id}"),
+        ("GET", "/api/v1/gdpr/export"),
+        ("DELETE", "/api/v1/gdpr/delete"),
+        ("POST", "/api/v1/admin/campaigns"),
+        ("GET", "/api/v1/admin/campaigns/{id}"),
+        ("POST", "/api/v1/admin/campaigns/{id}/launch"),
+        ("POST", "/api/v1/admin/campaigns/{id}/cancel"),
+        ("GET", "/api/v1/admin/digest/preview"),
+        ("GET", "/api/v1/admin/audit"),
         ("GET", "/api/v1/audit/logs"),
         ("GET", "/api/v1/audit/statistics"),
+        ("GET", "/api/v1/admin/cache/keys"),
+        ("GET", "/api/v1/admin/cache/entry"),
+        ("DELETE", "/api/v1/admin/cache"),
+        ("GET", "/api/v1/admin/keys/{id}/usage"),
+        ("GET", "/api/v1/admin/risk/markets/{market_id}/exposure"),
+        ("GET", "/api/v1/admin/risk/alerts"),
+    ];
+
+    /// Every schema in the generated document. Client generators name types
+    /// after these, so renaming one is a breaking change for integrators:
+    /// update this list only on purpose.
+    const SCHEMA_NAMES: &[&str] = &[
+        "AcquisitionGroupBy",
+        "AcquisitionReport",
+        "AcquisitionRow",
+        "ActiveMarket",
+        "Activity",
+        "ActivityDetails",
+        "ActivityFeed",
+        "ActivityType",
+        "AnalyticsDailyCount",
+        "AnalyticsEventInput",
+        "AnalyticsEventsRequest",
+        "AnalyticsIngestResponse",
+        "AnalyticsSummary",
+        "AnalyticsTypeTotal",
+        "ApiError",
+        "ApiKeyUsage",
+        "AuthRefreshRequest",
+        "AuthVerifyRequest",
+        "BackfillJob",
+        "BackfillRequest",
+        "BackfillStatus",
+        "BlockchainBatchItem",
+        "BlockchainBatchRequest",
+        "BlockchainBatchResponse",
+        "BlockchainQuery",
+        "BlockedDomain",
+        "BlockedDomainCreateRequest",
+        "BreakerState",
+        "CacheDeleteResult",
+        "CacheEntry",
+        "CacheKeyList",
+        "Campaign",
+        "CampaignCreateRequest",
+        "CampaignStatus",
+        "Category",
+        "CategoryCreateRequest",
+        "CategoryUpdateRequest",
+        "Challenge",
+        "ContactRequest",
+        "ContactStatus",
+        "ContactStatusRequest",
+        "ContactSubmission",
+        "ContentEntry",
+        "ContentWriteRequest",
+        "ContractRead",
+        "CreatorProfile",
+        "CreatorReputation",
+        "CreatorStats",
+        "DailyUsage",
+        "DigestPreview",
+        "EmailTestRequest",
+        "FeaturedMarketView",
+        "GdprDeleteReport",
+        "GdprExport",
+        "GdprSubjectRequest",
+        "GuardianRemoval",
+        "HistoryResolution",
+        "InvalidationResult",
+        "LargestPosition",
+        "Leaderboard",
+        "LeaderboardEntry",
+        "LeaderboardMetric",
+        "LeaderboardPeriod",
+        "MarketBettingState",
+        "MarketChainView",
+        "MarketDetail",
+        "MarketDetailSources",
+        "MarketDetailView",
+        "MarketExposure",
+        "MarketIdBindRequest",
+        "MarketIdMapping",
+        "MarketImage",
+        "MarketListView",
+        "MarketPosition",
+        "MarketSort",
+        "MarketWatch",
+        "MarketWatchRequest",
+        "MarketWatchResponse",
+        "NewsletterEmailRequest",
+        "NewsletterExportResponse",
+        "NewsletterPreferences",
+        "NewsletterPreferencesUpdate",
+        "NewsletterResponse",
+        "NewsletterSubscribeRequest",
+        "NotificationSettings",
+        "NotificationSettingsUpdate",
+        "OracleResultRequest",
+        "OracleResultResponse",
+        "OracleSubmission",
+        "OracleSubmissionStatus",
+        "OutcomeExposure",
+        "OutcomeSeries",
+        "PartSource",
+        "PendingUpgrade",
+        "Portfolio",
+        "PositionStatus",
+        "PriceCandle",
+        "PriceHistory",
+        "ProtocolState",
+        "ProtocolStateChange",
+        "ProtocolStateView",
+        "RenderedContent",
+        "ResolveMarketRequest",
+        "ResolveMarketResult",
+        "RiskAlert",
+        "RiskAlertKind",
+        "RiskAlertList",
+        "RiskThresholds",
+        "SessionToken",
+        "StatsHistory",
+        "StatsMetric",
+        "StatsPoint",
+        "TokenTotals",
+        "TopCreator",
+        "TopCreators",
+        "TopCreatorsMetric",
+        "TxEnvelopeRequest",
+        "TxFinalized",
+        "TxSimulation",
+        "TxSimulationResult",
+        "TxSubmission",
+        "TxSubscription",
+        "TxWatchRequest",
+        "TxWatchResponse",
+        "WaitlistInviteRequest",
+        "WaitlistInviteResponse",
+        "WaitlistInvitee",
+        "WaitlistJoinRequest",
+        "WaitlistStats",
+        "WaitlistStatus",
+        "WaitlistStatusResponse",
+        "WatchTrigger",
+        "Watchlist",
+        "WatchlistEntry",
     ];
 
     const OPENAPI_YAML: &str = include_str!("../openapi.yaml");
 
+    /// The generated document, round-tripped through JSON as a client would
+    /// read it from /api/v1/openapi.json.
+    fn generated() -> Value {
+        let json = ApiDoc::openapi().to_json().expect("spec serializes");
+        serde_json::from_str(&json).expect("spec is valid JSON")
+    }
+
+    /// (METHOD, path, operation) for every operation in the generated document.
+    fn generated_operations(doc: &Value) -> Vec<(String, String, Value)> {
+        let methods = ["get", "post", "put", "delete", "patch"];
+        let mut operations = Vec::new();
+        for (path, item) in doc["paths"].as_object().expect("paths object") {
+            for method in methods {
+                if let Some(operation) = item.get(method) {
+                    operations.push((method.to_uppercase(), path.clone(), operation.clone()));
+                }
+            }
+        }
+        operations
+    }
+
+    /// Every `$ref` target anywhere under `value`.
+    fn refs(value: &Value, out: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(target)) => {
+                            out.insert(target.clone());
+                        }
+                        _ => refs(value, out),
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| refs(item, out)),
+            _ => {}
+        }
+    }
+
     /// Parse (METHOD, path) pairs directly from openapi.yaml and return them.
     /// Uses line-by-line parsing so no YAML library is required in dev-deps.
     fn yaml_routes() -> Vec<(String, String)> {
@@ -170,15 +472,42 @@ mod tests {
     fn admin_routes_have_security_in_spec() {
         let admin_operation_ids = [
             "resolveMarket",
+            "listContactSubmissions",
+            "setContactSubmissionStatus",
+            "bindMarketChainId",
+            "unbindMarketChainId",
+            "uploadMarketImage",
+            "createCategory",
+            "updateCategory",
+            "deleteCategory",
+            "createContent",
+            "updateContent",
+            "publishContent",
+            "deleteContent",
+            "inviteWaitlistEntries",
+            "getWaitlistStats",
+            "exportWaitlistCsv",
+            "exportNewsletterCsv",
+            "getAnalyticsSummary",
+            "getAcquisitionReport",
+            "exportAcquisitionCsv",
             "emailPreview",
             "emailSendTest",
             "getEmailAnalytics",
             "getEmailQueueStats",
             "blockchainReplay",
             "getEmailDeadLetterList",
-            "requeueEmailDeadLetterJob",
+            "retryEmailDeadLetterJob",
+            "listBlockedEmailDomains",
+            "createBlockedEmailDomain",
+            "deleteBlockedEmailDomain",
+            "previewDigest",
+            "listAdminAuditLog",
             "getAuditLogs",
             "getAuditStatistics",
+            "getApiKeyUsage",
+            "getMarketRiskExposure",
+            "listRiskAlerts",
         ];
         for op_id in admin_operation_ids {
             assert!(
@@ -216,10 +545,11 @@ mod tests {
     /// Every path in the spec must declare at least one success (2xx) response.
     #[test]
     fn every_route_has_success_response() {
-        // Count "200:", "201:", "204:" response codes; there must be at least
-        // one per route to satisfy the acceptance criterion.
+        // Count "200:", "201:", "202:", "204:" response codes; there must be
+        // at least one per route to satisfy the acceptance criterion.
         let success_count = OPENAPI_YAML.matches("\"200\"").count()
             + OPENAPI_YAML.matches("\"201\"").count()
+            + OPENAPI_YAML.matches("\"202\"").count()
             + OPENAPI_YAML.matches("\"204\"").count();
         let route_count = yaml_routes().len();
         assert!(
@@ -228,4 +558,132 @@ mod tests {
              every endpoint must document at least one 2xx response"
         );
     }
+
+    /// The generated document covers exactly the routes in SPEC_ROUTES.
+    #[test]
+    fn generated_paths_match_spec_routes() {
+        let generated: HashSet<(String, String)> = generated_operations(&generated())
+            .into_iter()
+            .map(|(method, path, _)| (method, path))
+            .collect();
+        let spec: HashSet<(String, String)> = SPEC_ROUTES
+            .iter()
+            .map(|(m, p)| (m.to_string(), p.to_string()))
+            .collect();
+        let mut missing: Vec<_> = spec.difference(&generated).collect();
+        let mut extra: Vec<_> = generated.difference(&spec).collect();
+        missing.sort();
+        extra.sort();
+        assert!(
+            missing.is_empty() && extra.is_empty(),
+            "generated spec drifted from SPEC_ROUTES\n  missing: {missing:?}\n  extra: {extra:?}"
+        );
+    }
+
+    #[test]
+    fn generated_operations_have_ids_and_success_responses() {
+        for (method, path, operation) in generated_operations(&generated()) {
+            assert!(
+                operation["operationId"].is_string(),
+                "{method} {path} has no operationId"
+            );
+            let responses = operation["responses"].as_object().expect("responses");
+            assert!(
+                responses.keys().any(|status| status.starts_with('2')),
+                "{method} {path} documents no 2xx response"
+            );
+        }
+    }
+
+    /// Admin routes require the API key; routes that name no scheme are
+    /// public, not covered by a document-wide default.
+    #[test]
+    fn generated_admin_routes_require_api_key() {
+        let doc = generated();
+        assert!(doc.get("security").is_none(), "no document-wide security");
+        let admin: HashSet<(String, String)> = ADMIN_ROUTES
+            .iter()
+            .map(|(m, p)| (m.to_string(), p.to_string()))
+            .collect();
+        for (method, path, operation) in generated_operations(&doc) {
+            let schemes: Vec<&str> = operation["security"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|requirement| requirement.as_object())
+                .flat_map(|requirement| requirement.keys().map(String::as_str))
+                .collect();
+            if admin.contains(&(method.clone(), path.clone())) {
+                assert!(
+                    schemes.contains(&"api_key"),
+                    "admin route {method} {path} does not require api_key"
+                );
+            }
+            for scheme in schemes {
+                assert!(
+                    doc["components"]["securitySchemes"].get(scheme).is_some(),
+                    "{method} {path} names undeclared security scheme {scheme}"
+                );
+            }
+        }
+        let api_key = &doc["components"]["securitySchemes"]["api_key"];
+        assert_eq!(api_key["type"], "apiKey");
+        assert_eq!(api_key["in"], "header");
+        assert_eq!(api_key["name"], "X-API-Key");
+        let session = &doc["components"]["securitySchemes"]["wallet_session"];
+        assert_eq!(session["type"], "http");
+        assert_eq!(session["scheme"], "bearer");
+    }
+
+    /// Schema names are what generated clients call their types.
+    #[test]
+    fn generated_schema_names_are_stable() {
+        let doc = generated();
+        let generated: BTreeSet<&str> = doc["components"]["schemas"]
+            .as_object()
+            .expect("schemas object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let pinned: BTreeSet<&str> = SCHEMA_NAMES.iter().copied().collect();
+        assert_eq!(
+            pinned.len(),
+            SCHEMA_NAMES.len(),
+            "duplicate in SCHEMA_NAMES"
+        );
+        let added: Vec<_> = generated.difference(&pinned).collect();
+        let removed: Vec<_> = pinned.difference(&generated).collect();
+        assert!(
+            added.is_empty() && removed.is_empty(),
+            "schema names changed — update SCHEMA_NAMES only if this is intended\n  \
+             added: {added:?}\n  removed: {removed:?}"
+        );
+    }
+
+    /// The error envelope is a schema of its own, and every reference in the
+    /// document resolves.
+    #[test]
+    fn generated_refs_resolve() {
+        let doc = generated();
+        let envelope = &doc["components"]["schemas"]["ApiError"];
+        let required: Vec<&str> = envelope["required"]
+            .as_array()
+            .expect("ApiError required fields")
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert!(required.contains(&"code") && required.contains(&"message"));
+
+        let mut targets = BTreeSet::new();
+        refs(&doc, &mut targets);
+        for target in targets {
+            let name = target
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected $ref {target}"));
+            assert!(
+                doc["components"]["schemas"].get(name).is_some(),
+                "$ref to unregistered schema {name}"
+            );
+        }
+    }
 }
@@ -44,7 +44,7 @@ mod tests {
         let response = app()
             .oneshot(
                 Request::builder()
-                    .uri("/safe?q=' OR 1=1")
+                    .uri("/safe?q='%20OR%201=1")
                     .body(Body::empty())
                     .unwrap(),
             )
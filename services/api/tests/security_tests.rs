@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use std::{net::IpAddr, sync::Arc, time::Duration};
+    use std::{net::IpAddr, sync::Arc};
 
     use axum::{
         body::Body,
@@ -10,7 +10,8 @@ mod tests {
         Router,
     };
     use predictiq_api::security::{
-        ip_whitelist_middleware, sanitize, signing, ApiKeyAuth, IpWhitelist, MetricsAuthConfig, RateLimitConfig, RateLimiter, TrustProxy,
+        ip_whitelist_middleware, sanitize, signing, ApiKeyAuth, IpWhitelist, MetricsAuthConfig, TrustProxy,
+        TrustedProxies,
     };
     use tower::ServiceExt;
 
@@ -53,40 +54,6 @@ mod tests {
         assert!(sanitize::contains_sql_injection("onerror=alert(1)"));
     }
 
-    #[tokio::test]
-    async fn test_rate_limiter() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(3, Duration::from_secs(60));
-
-        // First 3 requests should succeed
-        assert!(limiter.check("test-key", &config).await);
-        assert!(limiter.check("test-key", &config).await);
-        assert!(limiter.check("test-key", &config).await);
-
-        // 4th request should fail
-        assert!(!limiter.check("test-key", &config).await);
-
-        // Different key should succeed
-        assert!(limiter.check("other-key", &config).await);
-    }
-
-    #[tokio::test]
-    async fn test_rate_limiter_window_reset() {
-        let limiter = RateLimiter::new();
-        let config = RateLimitConfig::new(2, Duration::from_millis(100));
-
-        // Use up the limit
-        assert!(limiter.check("test-key", &config).await);
-        assert!(limiter.check("test-key", &config).await);
-        assert!(!limiter.check("test-key", &config).await);
-
-        // Wait for window to reset
-        tokio::time::sleep(Duration::from_millis(150)).await;
-
-        // Should work again
-        assert!(limiter.check("test-key", &config).await);
-    }
-
     #[test]
     fn test_request_signing() {
         let payload = b"test payload";
@@ -211,8 +178,8 @@ mod tests {
 
     #[test]
     fn generate_signature_empty_secret_returns_err() {
-        // HMAC rejects a zero-length key; previously this would have panicked
-        // via .expect(). Now it must surface as Err(SigningError::InvalidKey).
+        // HMAC itself accepts a zero-length key, so an empty secret is
+        // rejected up front as Err(SigningError::InvalidKey).
         let result = signing::generate_signature(b"payload", "");
         assert_eq!(result, Err(signing::SigningError::InvalidKey));
     }
@@ -252,10 +219,11 @@ mod tests {
     }
 
     #[test]
-    fn ip_whitelist_empty_list_denies_all() {
+    fn ip_whitelist_empty_list_allows_all() {
+        // Open by default, matching `middleware_allows_when_whitelist_empty`.
         let wl = IpWhitelist::new(vec![]);
-        assert!(!wl.is_allowed("127.0.0.1"));
-        assert!(!wl.is_allowed("::1"));
+        assert!(wl.is_allowed("127.0.0.1"));
+        assert!(wl.is_allowed("::1"));
     }
 
     // ── IpWhitelist middleware — HTTP-level tests ────────────────────────────
@@ -264,7 +232,7 @@ mod tests {
         Router::new()
             .route("/admin", get(|| async { "ok" }))
             .layer(middleware::from_fn_with_state(
-                (wl, TrustProxy(true)),
+                (wl, TrustedProxies::from(TrustProxy(true))),
                 ip_whitelist_middleware,
             ))
     }
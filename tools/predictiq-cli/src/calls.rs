@@ -0,0 +1,282 @@
+//! Argument encoding for each contract entrypoint the CLI drives.
+//!
+//! Each builder mirrors one `PredictIQ` entrypoint's parameter list, so a
+//! signature change in `contracts/predict-iq/src/lib.rs` needs the matching
+//! change here.
+
+use stellar_xdr::curr as xdr;
+
+use crate::scval;
+use crate::spec::MarketSpec;
+
+/// One contract invocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub function: &'static str,
+    pub args: Vec<xdr::ScVal>,
+    /// Read-only calls are simulated and never submitted.
+    pub read_only: bool,
+}
+
+impl Call {
+    fn write(function: &'static str, args: Vec<xdr::ScVal>) -> Self {
+        Self {
+            function,
+            args,
+            read_only: false,
+        }
+    }
+
+    fn read(function: &'static str, args: Vec<xdr::ScVal>) -> Self {
+        Self {
+            function,
+            args,
+            read_only: true,
+        }
+    }
+}
+
+/// `create_market(creator, description, options, deadline,
+/// resolution_deadline, oracle_config, tier, native_token, parent_id,
+/// parent_outcome_idx)`. `creator` is used when the spec names none.
+pub fn create_market(spec: &MarketSpec, creator: &str) -> anyhow::Result<Call> {
+    let options = spec
+        .options
+        .iter()
+        .map(|option| scval::string(option))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let oracle = &spec.oracle;
+    let oracle_config = scval::structure(vec![
+        ("oracle_address", scval::address(&oracle.address)?),
+        ("feed_id", scval::string(&oracle.feed_id)?),
+        ("min_responses", scval::option(oracle.min_responses.map(scval::u32))),
+        ("max_staleness_seconds", scval::u64(oracle.max_staleness_seconds)),
+        ("max_confidence_bps", scval::u64(oracle.max_confidence_bps)),
+        ("strike_price", scval::option(oracle.strike_price.map(scval::i64))),
+    ])?;
+
+    Ok(Call::write(
+        "create_market",
+        vec![
+            scval::address(spec.creator.as_deref().unwrap_or(creator))?,
+            scval::string(&spec.description)?,
+            scval::vec(options)?,
+            scval::u64(spec.deadline),
+            scval::u64(spec.resolution_deadline),
+            oracle_config,
+            scval::unit_variant(spec.tier.variant())?,
+            scval::address(&spec.token)?,
+            scval::u64(spec.parent_id),
+            scval::u32(spec.parent_outcome),
+        ],
+    ))
+}
+
+/// `resolve_market(market_id, winning_outcome)`.
+pub fn resolve(market_id: u64, outcome: u32) -> Call {
+    Call::write("resolve_market", vec![scval::u64(market_id), scval::u32(outcome)])
+}
+
+/// `set_oracle_result(market_id, oracle_id, outcome)`.
+pub fn set_oracle_result(market_id: u64, oracle_id: u32, outcome: u32) -> Call {
+    Call::write(
+        "set_oracle_result",
+        vec![scval::u64(market_id), scval::u32(oracle_id), scval::u32(outcome)],
+    )
+}
+
+pub fn pause() -> Call {
+    Call::write("pause", Vec::new())
+}
+
+pub fn unpause() -> Call {
+    Call::write("unpause", Vec::new())
+}
+
+/// `claim_winnings(bettor, market_id, token)`.
+pub fn claim(bettor: &str, market_id: u64, token: &str) -> anyhow::Result<Call> {
+    Ok(Call::write(
+        "claim_winnings",
+        vec![scval::address(bettor)?, scval::u64(market_id), scval::address(token)?],
+    ))
+}
+
+/// `get_market(id)`.
+pub fn get_market(market_id: u64) -> Call {
+    Call::read("get_market", vec![scval::u64(market_id)])
+}
+
+/// The getters `stats` reports, keyed by the name they appear under in its
+/// output. `get_revenue(token)` is included when a token is given.
+pub fn stats(token: Option<&str>) -> anyhow::Result<Vec<(&'static str, Call)>> {
+    let mut calls = vec![
+        ("admin", Call::read("get_admin", Vec::new())),
+        ("base_fee_bps", Call::read("get_base_fee", Vec::new())),
+        ("creation_fee", Call::read("get_creation_fee", Vec::new())),
+        ("creation_deposit", Call::read("get_creation_deposit", Vec::new())),
+        (
+            "circuit_breaker_threshold",
+            Call::read("get_circuit_breaker_threshold", Vec::new()),
+        ),
+    ];
+    if let Some(token) = token {
+        calls.push(("revenue", Call::read("get_revenue", vec![scval::address(token)?])));
+    }
+    Ok(calls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn account(byte: u8) -> String {
+        stellar_strkey::ed25519::PublicKey([byte; 32]).to_string()
+    }
+
+    fn contract(byte: u8) -> String {
+        stellar_strkey::Contract([byte; 32]).to_string()
+    }
+
+    fn spec(creator: Option<String>) -> MarketSpec {
+        MarketSpec::parse(&format!(
+            r#"
+            {creator}
+            description = "Will it rain?"
+            options = ["Yes", "No", "Maybe"]
+            deadline = 1000
+            resolution_deadline = 2000
+            tier = "pro"
+            token = "{token}"
+            parent_id = 4
+            parent_outcome = 1
+
+            [oracle]
+            address = "{oracle}"
+            feed_id = "rain"
+            min_responses = 2
+            strike_price = -5
+            "#,
+            creator = creator.map(|c| format!("creator = \"{c}\"")).unwrap_or_default(),
+            token = contract(1),
+            oracle = contract(2),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_create_market_args_follow_entrypoint_order() {
+        let call = create_market(&spec(None), &account(3)).unwrap();
+
+        assert_eq!(call.function, "create_market");
+        assert!(!call.read_only);
+        let args: Vec<_> = call.args.iter().map(|a| scval::to_json(a).unwrap()).collect();
+        assert_eq!(
+            args,
+            vec![
+                json!(account(3)),
+                json!("Will it rain?"),
+                json!(["Yes", "No", "Maybe"]),
+                json!(1000),
+                json!(2000),
+                json!({
+                    "feed_id": "rain",
+                    "max_confidence_bps": 200,
+                    "max_staleness_seconds": 3600,
+                    "min_responses": 2,
+                    "oracle_address": contract(2),
+                    "strike_price": -5,
+                }),
+                json!("Pro"),
+                json!(contract(1)),
+                json!(4),
+                json!(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_market_prefers_spec_creator() {
+        let call = create_market(&spec(Some(account(5))), &account(3)).unwrap();
+        assert_eq!(call.args[0], scval::address(&account(5)).unwrap());
+    }
+
+    #[test]
+    fn test_create_market_encodes_absent_options_as_void() {
+        let mut spec = spec(None);
+        spec.oracle.min_responses = None;
+        spec.oracle.strike_price = None;
+
+        let call = create_market(&spec, &account(3)).unwrap();
+
+        let xdr::ScVal::Map(Some(config)) = &call.args[5] else {
+            panic!("oracle_config should be a map")
+        };
+        let void_fields: Vec<_> = config
+            .iter()
+            .filter(|entry| entry.val == xdr::ScVal::Void)
+            .map(|entry| scval::to_json(&entry.key).unwrap())
+            .collect();
+        assert_eq!(void_fields, vec![json!("min_responses"), json!("strike_price")]);
+    }
+
+    #[test]
+    fn test_resolve_args() {
+        let call = resolve(9, 2);
+        assert_eq!(call.function, "resolve_market");
+        assert_eq!(call.args, vec![xdr::ScVal::U64(9), xdr::ScVal::U32(2)]);
+    }
+
+    #[test]
+    fn test_set_oracle_result_args() {
+        let call = set_oracle_result(9, 0, 1);
+        assert_eq!(call.function, "set_oracle_result");
+        assert_eq!(
+            call.args,
+            vec![xdr::ScVal::U64(9), xdr::ScVal::U32(0), xdr::ScVal::U32(1)]
+        );
+    }
+
+    #[test]
+    fn test_pause_and_unpause_take_no_args() {
+        for (call, function) in [(pause(), "pause"), (unpause(), "unpause")] {
+            assert_eq!(call.function, function);
+            assert!(call.args.is_empty());
+            assert!(!call.read_only);
+        }
+    }
+
+    #[test]
+    fn test_claim_args() {
+        let call = claim(&account(3), 9, &contract(1)).unwrap();
+        assert_eq!(call.function, "claim_winnings");
+        assert_eq!(
+            call.args,
+            vec![
+                scval::address(&account(3)).unwrap(),
+                xdr::ScVal::U64(9),
+                scval::address(&contract(1)).unwrap(),
+            ]
+        );
+        assert!(claim("nonsense", 9, &contract(1)).is_err());
+    }
+
+    #[test]
+    fn test_get_market_is_read_only() {
+        let call = get_market(9);
+        assert_eq!(call.function, "get_market");
+        assert_eq!(call.args, vec![xdr::ScVal::U64(9)]);
+        assert!(call.read_only);
+    }
+
+    #[test]
+    fn test_stats_adds_revenue_only_with_token() {
+        let without = stats(None).unwrap();
+        assert!(without.iter().all(|(name, call)| *name != "revenue" && call.read_only));
+
+        let with = stats(Some(&contract(1))).unwrap();
+        let (_, revenue) = with.iter().find(|(name, _)| *name == "revenue").unwrap();
+        assert_eq!(revenue.function, "get_revenue");
+        assert_eq!(revenue.args, vec![scval::address(&contract(1)).unwrap()]);
+    }
+}
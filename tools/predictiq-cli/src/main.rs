@@ -0,0 +1,328 @@
+//! `predictiq`: operate a deployed PredictIQ contract from the command line.
+//!
+//! Every subcommand prints one JSON document to stdout. State-changing
+//! subcommands are simulated, assembled, signed with `PREDICTIQ_SECRET_KEY`,
+//! and submitted; with `--dry-run` they stop after simulation and print the
+//! footprint and fee instead. Getters are only ever simulated. Failures
+//! print `{"error": "..."}` and exit non-zero.
+
+mod calls;
+mod rpc;
+mod scval;
+mod spec;
+mod tx;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde_json::{json, Value};
+
+use crate::calls::Call;
+use crate::rpc::{RpcClient, Simulation};
+use crate::spec::MarketSpec;
+use crate::tx::Signer;
+
+/// How long to wait for a submitted transaction to be included.
+const SUBMIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Parser)]
+#[command(name = "predictiq", version, about)]
+struct Cli {
+    #[command(flatten)]
+    network: NetworkArgs,
+
+    /// Simulate state-changing calls and print the footprint and fee
+    /// instead of submitting.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct NetworkArgs {
+    /// Network preset supplying the RPC URL and passphrase defaults.
+    #[arg(long, global = true, value_enum, env = "PREDICTIQ_NETWORK", default_value = "testnet")]
+    network: Network,
+
+    /// Soroban RPC endpoint; overrides the preset's.
+    #[arg(long, global = true, env = "PREDICTIQ_RPC_URL")]
+    rpc_url: Option<String>,
+
+    /// Network passphrase; overrides the preset's.
+    #[arg(long, global = true, env = "PREDICTIQ_NETWORK_PASSPHRASE")]
+    network_passphrase: Option<String>,
+
+    /// PredictIQ contract address (`C...`).
+    #[arg(long, global = true, env = "PREDICTIQ_CONTRACT_ID")]
+    contract_id: Option<String>,
+
+    /// Secret seed (`S...`) of the source account. Only read from the
+    /// environment so it stays out of shell history and process listings.
+    #[arg(long, env = "PREDICTIQ_SECRET_KEY", hide = true, hide_env_values = true)]
+    secret_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Network {
+    /// A local quickstart container.
+    Local,
+    Testnet,
+    Mainnet,
+}
+
+impl Network {
+    fn rpc_url(self) -> Option<&'static str> {
+        match self {
+            Network::Local => Some("http://localhost:8000/soroban/rpc"),
+            Network::Testnet => Some("https://soroban-testnet.stellar.org"),
+            // No public default; pick a provider explicitly.
+            Network::Mainnet => None,
+        }
+    }
+
+    fn passphrase(self) -> &'static str {
+        match self {
+            Network::Local => "Standalone Network ; February 2017",
+            Network::Testnet => "Test SDF Network ; September 2015",
+            Network::Mainnet => "Public Global Stellar Network ; September 2015",
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Create a market from a TOML spec.
+    CreateMarket {
+        /// Path to the market spec.
+        spec: PathBuf,
+    },
+    /// Resolve a market to a winning outcome (admin).
+    Resolve {
+        #[arg(long)]
+        market_id: u64,
+        #[arg(long)]
+        outcome: u32,
+    },
+    /// Push an oracle result for a market (admin).
+    SetOracleResult {
+        #[arg(long)]
+        market_id: u64,
+        #[arg(long, default_value_t = 0)]
+        oracle_id: u32,
+        #[arg(long)]
+        outcome: u32,
+    },
+    /// Pause the contract.
+    Pause,
+    /// Unpause the contract.
+    Unpause,
+    /// Print a market.
+    GetMarket {
+        #[arg(long)]
+        market_id: u64,
+    },
+    /// Print contract-wide configuration and, with `--token`, revenue.
+    Stats {
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Claim winnings from a resolved market.
+    Claim {
+        #[arg(long)]
+        market_id: u64,
+        /// Token the market settles in.
+        #[arg(long)]
+        token: String,
+        /// Bettor to claim for; defaults to the source account.
+        #[arg(long)]
+        bettor: Option<String>,
+    },
+}
+
+struct Context {
+    rpc: RpcClient,
+    contract_id: String,
+    passphrase: String,
+    signer: Option<Signer>,
+    dry_run: bool,
+}
+
+impl Context {
+    fn from_args(args: NetworkArgs, dry_run: bool) -> anyhow::Result<Self> {
+        let rpc_url = args
+            .rpc_url
+            .or_else(|| args.network.rpc_url().map(str::to_string))
+            .ok_or_else(|| anyhow!("--rpc-url is required for this network"))?;
+        let contract_id = args
+            .contract_id
+            .ok_or_else(|| anyhow!("--contract-id (or PREDICTIQ_CONTRACT_ID) is required"))?;
+        let signer = args.secret_key.as_deref().map(Signer::from_secret).transpose()?;
+        Ok(Self {
+            rpc: RpcClient::new(&rpc_url)?,
+            contract_id,
+            passphrase: args
+                .network_passphrase
+                .unwrap_or_else(|| args.network.passphrase().to_string()),
+            signer,
+            dry_run,
+        })
+    }
+
+    fn signer(&self) -> anyhow::Result<&Signer> {
+        self.signer
+            .as_ref()
+            .ok_or_else(|| anyhow!("PREDICTIQ_SECRET_KEY is required for this command"))
+    }
+
+    /// Simulate a getter and return its value.
+    fn read(&self, call: &Call) -> anyhow::Result<Value> {
+        // Simulation does not check the source's sequence number, and a
+        // getter needs no signer, so any key will do when none is set.
+        let source = self.signer.as_ref().map_or([0; 32], Signer::public_key);
+        let tx = tx::build(&self.contract_id, source, 0, call)?;
+        let simulation = self.rpc.simulate(&tx::unsigned(tx))?;
+        scval::to_json(&simulation.return_value()?)
+    }
+
+    /// Simulate a state-changing call, then submit it unless `--dry-run`.
+    fn write(&self, call: &Call) -> anyhow::Result<Value> {
+        let signer = self.signer()?;
+        let sequence = self.rpc.account_sequence(signer.public_key())?;
+        let tx = tx::build(&self.contract_id, signer.public_key(), sequence + 1, call)?;
+        let simulation = self.rpc.simulate(&tx::unsigned(tx.clone()))?;
+
+        if self.dry_run {
+            return simulation_report(call, &simulation);
+        }
+
+        let assembled = tx::assemble(tx, &simulation)?;
+        let fee = assembled.fee;
+        let submission = self.rpc.send(&signer.sign(assembled, &self.passphrase)?)?;
+        if !matches!(submission.status.as_str(), "PENDING" | "DUPLICATE") {
+            bail!(
+                "transaction {} rejected with status {}: {}",
+                submission.hash,
+                submission.status,
+                submission.error_result_xdr.as_deref().unwrap_or("no result")
+            );
+        }
+
+        let status = self.rpc.wait_for_transaction(&submission.hash, SUBMIT_TIMEOUT)?;
+        if status.status != "SUCCESS" {
+            bail!(
+                "transaction {} finished with status {}: {}",
+                submission.hash,
+                status.status,
+                status.result_xdr.as_deref().unwrap_or("no result")
+            );
+        }
+        let result = status.return_value()?.as_ref().map(scval::to_json).transpose()?;
+
+        Ok(json!({
+            "function": call.function,
+            "hash": submission.hash,
+            "status": status.status,
+            "ledger": status.ledger,
+            "fee": fee,
+            "result": result,
+        }))
+    }
+
+    fn invoke(&self, call: &Call) -> anyhow::Result<Value> {
+        if call.read_only {
+            self.read(call)
+        } else {
+            self.write(call)
+        }
+    }
+}
+
+/// What `--dry-run` prints: the fee the transaction would be submitted with,
+/// the resources it declares, and the simulated return value.
+fn simulation_report(call: &Call, simulation: &Simulation) -> anyhow::Result<Value> {
+    let data = simulation.transaction_data()?;
+    let resources = &data.resources;
+    let min_resource_fee = simulation.min_resource_fee()?;
+    let auth_entries = simulation.results.first().map_or(0, |r| r.auth.len());
+
+    Ok(json!({
+        "function": call.function,
+        "dry_run": true,
+        "latest_ledger": simulation.latest_ledger,
+        "min_resource_fee": min_resource_fee,
+        "fee": tx::BASE_FEE.saturating_add(min_resource_fee),
+        "footprint": {
+            "read_only": serde_json::to_value(resources.footprint.read_only.as_slice())?,
+            "read_write": serde_json::to_value(resources.footprint.read_write.as_slice())?,
+        },
+        "resources": {
+            "instructions": resources.instructions,
+            "read_bytes": resources.read_bytes,
+            "write_bytes": resources.write_bytes,
+        },
+        "auth_entries": auth_entries,
+        "result": scval::to_json(&simulation.return_value()?)?,
+    }))
+}
+
+fn run(cli: Cli) -> anyhow::Result<Value> {
+    let ctx = Context::from_args(cli.network, cli.dry_run)?;
+
+    let call = match cli.command {
+        Command::CreateMarket { spec } => {
+            let spec = MarketSpec::load(&spec)?;
+            let creator = match &spec.creator {
+                Some(creator) => creator.clone(),
+                None => ctx.signer()?.account_id(),
+            };
+            calls::create_market(&spec, &creator)?
+        }
+        Command::Resolve { market_id, outcome } => calls::resolve(market_id, outcome),
+        Command::SetOracleResult {
+            market_id,
+            oracle_id,
+            outcome,
+        } => calls::set_oracle_result(market_id, oracle_id, outcome),
+        Command::Pause => calls::pause(),
+        Command::Unpause => calls::unpause(),
+        Command::GetMarket { market_id } => calls::get_market(market_id),
+        Command::Stats { token } => {
+            let mut stats = serde_json::Map::new();
+            for (name, call) in calls::stats(token.as_deref())? {
+                stats.insert(name.to_string(), ctx.read(&call)?);
+            }
+            return Ok(Value::Object(stats));
+        }
+        Command::Claim {
+            market_id,
+            token,
+            bettor,
+        } => {
+            let bettor = match bettor {
+                Some(bettor) => bettor,
+                None => ctx.signer()?.account_id(),
+            };
+            calls::claim(&bettor, market_id, &token)?
+        }
+    };
+
+    ctx.invoke(&call)
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(output) => {
+            println!("{}", serde_json::to_string_pretty(&output).expect("JSON values serialize"));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{}", json!({ "error": format!("{error:#}") }));
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Minimal blocking Soroban JSON-RPC client: the four methods a contract
+//! call needs. Mirrors the request shapes in `services/api/src/blockchain.rs`.
+
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+
+/// How often `wait_for_transaction` polls `getTransaction`.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct RpcClient {
+    http: reqwest::blocking::Client,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Simulation {
+    pub latest_ledger: u32,
+    pub min_resource_fee: Option<String>,
+    /// Base64 `SorobanTransactionData`, including the read/write footprint.
+    pub transaction_data: Option<String>,
+    #[serde(default)]
+    pub results: Vec<SimulationResult>,
+    pub error: Option<String>,
+    pub restore_preamble: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimulationResult {
+    /// Base64 `ScVal` return value.
+    pub xdr: String,
+    /// Base64 `SorobanAuthorizationEntry` values the signer must cover.
+    #[serde(default)]
+    pub auth: Vec<String>,
+}
+
+impl Simulation {
+    /// The simulated return value.
+    pub fn return_value(&self) -> anyhow::Result<xdr::ScVal> {
+        let result = self
+            .results
+            .first()
+            .ok_or_else(|| anyhow!("simulation returned no result"))?;
+        Ok(xdr::ScVal::from_xdr_base64(&result.xdr, xdr::Limits::none())?)
+    }
+
+    pub fn transaction_data(&self) -> anyhow::Result<xdr::SorobanTransactionData> {
+        let data = self
+            .transaction_data
+            .as_deref()
+            .ok_or_else(|| anyhow!("simulation returned no transaction data"))?;
+        Ok(xdr::SorobanTransactionData::from_xdr_base64(data, xdr::Limits::none())?)
+    }
+
+    pub fn min_resource_fee(&self) -> anyhow::Result<u32> {
+        self.min_resource_fee
+            .as_deref()
+            .unwrap_or("0")
+            .parse()
+            .context("simulation: minResourceFee is not a u32")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Submission {
+    pub hash: String,
+    pub status: String,
+    pub error_result_xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionStatus {
+    pub status: String,
+    pub ledger: Option<u32>,
+    pub result_meta_xdr: Option<String>,
+    pub result_xdr: Option<String>,
+}
+
+impl TransactionStatus {
+    /// The contract's return value, from the Soroban transaction meta.
+    pub fn return_value(&self) -> anyhow::Result<Option<xdr::ScVal>> {
+        let Some(meta) = self.result_meta_xdr.as_deref() else {
+            return Ok(None);
+        };
+        match xdr::TransactionMeta::from_xdr_base64(meta, xdr::Limits::none())? {
+            xdr::TransactionMeta::V3(v3) => Ok(v3.soroban_meta.map(|m| m.return_value)),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            http: reqwest::blocking::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()?,
+            url: url.to_string(),
+        })
+    }
+
+    fn call<T: for<'de> Deserialize<'de>>(&self, method: &str, params: Value) -> anyhow::Result<T> {
+        #[derive(Deserialize)]
+        struct Response<T> {
+            result: Option<T>,
+            error: Option<Value>,
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params,
+        });
+        let response: Response<T> = self
+            .http
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .with_context(|| format!("{method}: request to {} failed", self.url))?
+            .error_for_status()?
+            .json()
+            .with_context(|| format!("{method}: malformed response"))?;
+
+        match (response.result, response.error) {
+            (_, Some(error)) => bail!("{method}: {error}"),
+            (Some(result), None) => Ok(result),
+            (None, None) => bail!("{method}: response has neither result nor error"),
+        }
+    }
+
+    pub fn simulate(&self, envelope: &xdr::TransactionEnvelope) -> anyhow::Result<Simulation> {
+        let simulation: Simulation = self.call(
+            "simulateTransaction",
+            json!({ "transaction": envelope.to_xdr_base64(xdr::Limits::none())? }),
+        )?;
+        if let Some(error) = &simulation.error {
+            bail!("simulation failed: {error}");
+        }
+        if simulation.restore_preamble.is_some() {
+            bail!("simulation needs archived ledger entries restored first");
+        }
+        Ok(simulation)
+    }
+
+    pub fn send(&self, envelope: &xdr::TransactionEnvelope) -> anyhow::Result<Submission> {
+        self.call(
+            "sendTransaction",
+            json!({ "transaction": envelope.to_xdr_base64(xdr::Limits::none())? }),
+        )
+    }
+
+    /// Poll `getTransaction` until the transaction leaves `NOT_FOUND` or
+    /// `timeout` elapses.
+    pub fn wait_for_transaction(&self, hash: &str, timeout: Duration) -> anyhow::Result<TransactionStatus> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status: TransactionStatus = self.call("getTransaction", json!({ "hash": hash }))?;
+            if status.status != "NOT_FOUND" {
+                return Ok(status);
+            }
+            if Instant::now() >= deadline {
+                bail!("transaction {hash} not final after {}s", timeout.as_secs());
+            }
+            sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Current sequence number of `account`.
+    pub fn account_sequence(&self, account: [u8; 32]) -> anyhow::Result<i64> {
+        #[derive(Deserialize)]
+        struct Entry {
+            xdr: String,
+        }
+
+        #[derive(Deserialize)]
+        struct Entries {
+            #[serde(default)]
+            entries: Vec<Entry>,
+        }
+
+        let key = xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+            account_id: xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(account))),
+        });
+        let raw: Entries = self.call(
+            "getLedgerEntries",
+            json!({ "keys": [key.to_xdr_base64(xdr::Limits::none())?] }),
+        )?;
+
+        let entry = raw
+            .entries
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("source account does not exist on this network"))?;
+        match xdr::LedgerEntryData::from_xdr_base64(&entry.xdr, xdr::Limits::none())
+            .context("getLedgerEntries: bad account entry")?
+        {
+            xdr::LedgerEntryData::Account(account) => Ok(account.seq_num.0),
+            _ => bail!("getLedgerEntries: entry is not an account"),
+        }
+    }
+}
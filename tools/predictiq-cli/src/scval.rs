@@ -0,0 +1,193 @@
+//! Conversions between CLI values and `ScVal`.
+//!
+//! Encoding follows the layout `#[contracttype]` gives each Rust type:
+//! structs are maps keyed by field name in sorted order, unit enum variants
+//! are a one-element vector holding the variant symbol, and `None` is
+//! `Void`. Decoding goes the other way for printing results as JSON.
+
+use anyhow::bail;
+use serde_json::{json, Value};
+use stellar_xdr::curr as xdr;
+
+pub fn u32(value: u32) -> xdr::ScVal {
+    xdr::ScVal::U32(value)
+}
+
+pub fn u64(value: u64) -> xdr::ScVal {
+    xdr::ScVal::U64(value)
+}
+
+pub fn i64(value: i64) -> xdr::ScVal {
+    xdr::ScVal::I64(value)
+}
+
+pub fn string(value: &str) -> anyhow::Result<xdr::ScVal> {
+    Ok(xdr::ScVal::String(xdr::ScString(value.try_into()?)))
+}
+
+pub fn symbol(value: &str) -> anyhow::Result<xdr::ScVal> {
+    Ok(xdr::ScVal::Symbol(xdr::ScSymbol(value.try_into()?)))
+}
+
+pub fn vec(items: Vec<xdr::ScVal>) -> anyhow::Result<xdr::ScVal> {
+    Ok(xdr::ScVal::Vec(Some(xdr::ScVec(items.try_into()?))))
+}
+
+pub fn option(value: Option<xdr::ScVal>) -> xdr::ScVal {
+    value.unwrap_or(xdr::ScVal::Void)
+}
+
+/// A unit variant of a `#[contracttype]` enum.
+pub fn unit_variant(name: &str) -> anyhow::Result<xdr::ScVal> {
+    vec(vec![symbol(name)?])
+}
+
+/// A `#[contracttype]` struct. Fields may be given in any order; the host
+/// requires map keys sorted, so they are sorted here.
+pub fn structure(mut fields: Vec<(&str, xdr::ScVal)>) -> anyhow::Result<xdr::ScVal> {
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+    let entries = fields
+        .into_iter()
+        .map(|(key, val)| Ok(xdr::ScMapEntry { key: symbol(key)?, val }))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(xdr::ScVal::Map(Some(xdr::ScMap(entries.try_into()?))))
+}
+
+/// An account (`G...`) or contract (`C...`) address.
+pub fn address(strkey: &str) -> anyhow::Result<xdr::ScVal> {
+    Ok(xdr::ScVal::Address(sc_address(strkey)?))
+}
+
+pub fn sc_address(strkey: &str) -> anyhow::Result<xdr::ScAddress> {
+    let strkey = strkey.trim();
+    if let Ok(account) = stellar_strkey::ed25519::PublicKey::from_string(strkey) {
+        return Ok(xdr::ScAddress::Account(xdr::AccountId(
+            xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(account.0)),
+        )));
+    }
+    if let Ok(contract) = stellar_strkey::Contract::from_string(strkey) {
+        return Ok(xdr::ScAddress::Contract(xdr::Hash(contract.0)));
+    }
+    bail!("not an account or contract address: {strkey}")
+}
+
+/// Render a contract return value as JSON.
+///
+/// 64-bit integers stay numbers; 128-bit integers become decimal strings so
+/// nothing is rounded by a consumer that parses numbers as doubles. A
+/// one-element vector holding a symbol is taken to be a unit enum variant
+/// and printed as the bare variant name.
+pub fn to_json(value: &xdr::ScVal) -> anyhow::Result<Value> {
+    Ok(match value {
+        xdr::ScVal::Void => Value::Null,
+        xdr::ScVal::Bool(b) => json!(b),
+        xdr::ScVal::U32(n) => json!(n),
+        xdr::ScVal::I32(n) => json!(n),
+        xdr::ScVal::U64(n) => json!(n),
+        xdr::ScVal::I64(n) => json!(n),
+        xdr::ScVal::Timepoint(t) => json!(t.0),
+        xdr::ScVal::Duration(d) => json!(d.0),
+        xdr::ScVal::U128(parts) => {
+            json!((((parts.hi as u128) << 64) | parts.lo as u128).to_string())
+        }
+        xdr::ScVal::I128(parts) => {
+            json!((((parts.hi as i128) << 64) | parts.lo as i128).to_string())
+        }
+        xdr::ScVal::Bytes(bytes) => json!(hex(bytes.as_slice())),
+        xdr::ScVal::String(s) => json!(s.0.to_utf8_string_lossy()),
+        xdr::ScVal::Symbol(s) => json!(s.0.to_utf8_string_lossy()),
+        xdr::ScVal::Address(address) => json!(address_to_string(address)),
+        xdr::ScVal::Vec(None) => json!([]),
+        xdr::ScVal::Vec(Some(items)) => match items.as_slice() {
+            [xdr::ScVal::Symbol(variant)] => json!(variant.0.to_utf8_string_lossy()),
+            items => Value::Array(items.iter().map(to_json).collect::<anyhow::Result<_>>()?),
+        },
+        xdr::ScVal::Map(None) => json!({}),
+        xdr::ScVal::Map(Some(map)) => map_to_json(map)?,
+        xdr::ScVal::Error(error) => json!({ "error": format!("{error:?}") }),
+        other => bail!("cannot render {:?} as JSON", other.discriminant()),
+    })
+}
+
+/// Maps keyed by symbols or strings become objects; anything else becomes
+/// a list of `[key, value]` pairs.
+fn map_to_json(map: &xdr::ScMap) -> anyhow::Result<Value> {
+    let named = map
+        .iter()
+        .all(|entry| matches!(entry.key, xdr::ScVal::Symbol(_) | xdr::ScVal::String(_)));
+    if named {
+        let mut object = serde_json::Map::new();
+        for entry in map.iter() {
+            let key = match &entry.key {
+                xdr::ScVal::Symbol(s) => s.0.to_utf8_string_lossy(),
+                xdr::ScVal::String(s) => s.0.to_utf8_string_lossy(),
+                _ => unreachable!(),
+            };
+            object.insert(key, to_json(&entry.val)?);
+        }
+        Ok(Value::Object(object))
+    } else {
+        map.iter()
+            .map(|entry| Ok(json!([to_json(&entry.key)?, to_json(&entry.val)?])))
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(Value::Array)
+    }
+}
+
+pub fn address_to_string(address: &xdr::ScAddress) -> String {
+    match address {
+        xdr::ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(key))) => {
+            stellar_strkey::ed25519::PublicKey(key.0).to_string()
+        }
+        xdr::ScAddress::Contract(hash) => stellar_strkey::Contract(hash.0).to_string(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structure_sorts_fields() {
+        let value = structure(vec![("zeta", u32(1)), ("alpha", u32(2))]).unwrap();
+        let xdr::ScVal::Map(Some(map)) = value else {
+            panic!("expected a map")
+        };
+        let keys: Vec<_> = map.iter().map(|e| e.key.clone()).collect();
+        assert_eq!(keys, vec![symbol("alpha").unwrap(), symbol("zeta").unwrap()]);
+    }
+
+    #[test]
+    fn test_addresses_round_trip() {
+        let account = stellar_strkey::ed25519::PublicKey([7; 32]).to_string();
+        let contract = stellar_strkey::Contract([9; 32]).to_string();
+        for strkey in [account, contract] {
+            let xdr::ScVal::Address(parsed) = address(&strkey).unwrap() else {
+                panic!("expected an address")
+            };
+            assert_eq!(address_to_string(&parsed), strkey);
+        }
+        assert!(address("not-an-address").is_err());
+    }
+
+    #[test]
+    fn test_unit_variant_renders_as_name() {
+        assert_eq!(to_json(&unit_variant("Active").unwrap()).unwrap(), json!("Active"));
+    }
+
+    #[test]
+    fn test_i128_renders_as_string() {
+        let value = xdr::ScVal::I128(xdr::Int128Parts { hi: -1, lo: u64::MAX - 41 });
+        assert_eq!(to_json(&value).unwrap(), json!("-42"));
+    }
+
+    #[test]
+    fn test_struct_renders_as_object() {
+        let value = structure(vec![("id", u64(7)), ("tier", unit_variant("Pro").unwrap())]).unwrap();
+        assert_eq!(to_json(&value).unwrap(), json!({ "id": 7, "tier": "Pro" }));
+    }
+}
@@ -0,0 +1,159 @@
+//! TOML market specs read by `create-market`.
+//!
+//! ```toml
+//! description = "Will BTC close above $100k on 2026-12-31?"
+//! options = ["Yes", "No"]
+//! deadline = 1798675200
+//! resolution_deadline = 1798761600
+//! tier = "basic"
+//! token = "C..."
+//!
+//! [oracle]
+//! address = "C..."
+//! feed_id = "btc_above_100k"
+//! ```
+//!
+//! `creator` defaults to the signing account; `parent_id`/`parent_outcome`
+//! default to zero (no parent market).
+
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketSpec {
+    pub creator: Option<String>,
+    pub description: String,
+    pub options: Vec<String>,
+    pub deadline: u64,
+    pub resolution_deadline: u64,
+    #[serde(default)]
+    pub tier: Tier,
+    pub token: String,
+    #[serde(default)]
+    pub parent_id: u64,
+    #[serde(default)]
+    pub parent_outcome: u32,
+    pub oracle: OracleSpec,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OracleSpec {
+    pub address: String,
+    pub feed_id: String,
+    pub min_responses: Option<u32>,
+    #[serde(default = "default_max_staleness_seconds")]
+    pub max_staleness_seconds: u64,
+    #[serde(default = "default_max_confidence_bps")]
+    pub max_confidence_bps: u64,
+    pub strike_price: Option<i64>,
+}
+
+fn default_max_staleness_seconds() -> u64 {
+    3600
+}
+
+fn default_max_confidence_bps() -> u64 {
+    200
+}
+
+/// Mirrors the contract's `MarketTier`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tier {
+    #[default]
+    Basic,
+    Pro,
+    Institutional,
+}
+
+impl Tier {
+    /// Variant name as the contract spells it.
+    pub fn variant(self) -> &'static str {
+        match self {
+            Tier::Basic => "Basic",
+            Tier::Pro => "Pro",
+            Tier::Institutional => "Institutional",
+        }
+    }
+}
+
+impl MarketSpec {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading market spec {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("in market spec {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> anyhow::Result<Self> {
+        let spec: Self = toml::from_str(text)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Checks the contract would otherwise reject only after simulation.
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.options.len() < 2 {
+            bail!("a market needs at least two options");
+        }
+        if self.deadline >= self.resolution_deadline {
+            bail!("deadline must be before resolution_deadline");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"
+        description = "Will it rain?"
+        options = ["Yes", "No"]
+        deadline = 1000
+        resolution_deadline = 2000
+        token = "CTOKEN"
+
+        [oracle]
+        address = "CORACLE"
+        feed_id = "rain"
+    "#;
+
+    #[test]
+    fn test_defaults_apply() {
+        let spec = MarketSpec::parse(MINIMAL).unwrap();
+
+        assert_eq!(spec.creator, None);
+        assert_eq!(spec.tier, Tier::Basic);
+        assert_eq!((spec.parent_id, spec.parent_outcome), (0, 0));
+        assert_eq!(spec.oracle.min_responses, None);
+        assert_eq!(spec.oracle.max_staleness_seconds, 3600);
+        assert_eq!(spec.oracle.max_confidence_bps, 200);
+    }
+
+    #[test]
+    fn test_tier_is_lowercase() {
+        let spec = MarketSpec::parse(&format!("tier = \"institutional\"\n{MINIMAL}")).unwrap();
+        assert_eq!(spec.tier.variant(), "Institutional");
+    }
+
+    #[test]
+    fn test_single_option_is_rejected() {
+        let text = MINIMAL.replace(r#"["Yes", "No"]"#, r#"["Yes"]"#);
+        assert!(MarketSpec::parse(&text).is_err());
+    }
+
+    #[test]
+    fn test_inverted_deadlines_are_rejected() {
+        let text = MINIMAL.replace("deadline = 1000", "deadline = 3000");
+        assert!(MarketSpec::parse(&text).is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(MarketSpec::parse(&format!("fee = 5\n{MINIMAL}")).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Building, assembling, and signing contract invocations.
+
+use anyhow::anyhow;
+use ed25519_dalek::{Signer as _, SigningKey};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{self as xdr, ReadXdr, WriteXdr};
+
+use crate::calls::Call;
+use crate::rpc::Simulation;
+
+/// Inclusion fee added on top of the simulated resource fee, in stroops.
+pub const BASE_FEE: u32 = 100;
+
+/// An unsigned transaction invoking `call` on `contract_id` from `source`.
+pub fn build(
+    contract_id: &str,
+    source: [u8; 32],
+    seq_num: i64,
+    call: &Call,
+) -> anyhow::Result<xdr::Transaction> {
+    let contract = stellar_strkey::Contract::from_string(contract_id)
+        .map_err(|_| anyhow!("contract id is not a contract address: {contract_id}"))?;
+
+    let operation = xdr::Operation {
+        source_account: None,
+        body: xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+            host_function: xdr::HostFunction::InvokeContract(xdr::InvokeContractArgs {
+                contract_address: xdr::ScAddress::Contract(xdr::Hash(contract.0)),
+                function_name: xdr::ScSymbol(call.function.try_into()?),
+                args: call.args.clone().try_into()?,
+            }),
+            auth: xdr::VecM::default(),
+        }),
+    };
+
+    Ok(xdr::Transaction {
+        source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(source)),
+        fee: BASE_FEE,
+        seq_num: xdr::SequenceNumber(seq_num),
+        cond: xdr::Preconditions::None,
+        memo: xdr::Memo::None,
+        operations: vec![operation].try_into()?,
+        ext: xdr::TransactionExt::V0,
+    })
+}
+
+/// Wrap `tx` in an envelope with no signatures, for simulation.
+pub fn unsigned(tx: xdr::Transaction) -> xdr::TransactionEnvelope {
+    xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+        tx,
+        signatures: xdr::VecM::default(),
+    })
+}
+
+/// Apply the simulation's auth entries, footprint, and resource fee to `tx`.
+pub fn assemble(mut tx: xdr::Transaction, simulation: &Simulation) -> anyhow::Result<xdr::Transaction> {
+    let auth = match simulation.results.first() {
+        Some(result) => result
+            .auth
+            .iter()
+            .map(|entry| xdr::SorobanAuthorizationEntry::from_xdr_base64(entry, xdr::Limits::none()))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let mut operations = tx.operations.to_vec();
+    for op in &mut operations {
+        if let xdr::OperationBody::InvokeHostFunction(invoke) = &mut op.body {
+            invoke.auth = auth.clone().try_into()?;
+        }
+    }
+
+    tx.operations = operations.try_into()?;
+    tx.fee = tx.fee.saturating_add(simulation.min_resource_fee()?);
+    tx.ext = xdr::TransactionExt::V1(simulation.transaction_data()?);
+    Ok(tx)
+}
+
+pub struct Signer {
+    key: SigningKey,
+}
+
+impl Signer {
+    /// Load a signer from a Stellar secret seed (`S...`). The error never
+    /// echoes the input.
+    pub fn from_secret(secret: &str) -> anyhow::Result<Self> {
+        let seed = stellar_strkey::ed25519::PrivateKey::from_string(secret.trim())
+            .map_err(|_| anyhow!("signing key is not a valid Stellar secret seed"))?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&seed.0),
+        })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.key.verifying_key().to_bytes()
+    }
+
+    /// The signer's account address (`G...`).
+    pub fn account_id(&self) -> String {
+        stellar_strkey::ed25519::PublicKey(self.public_key()).to_string()
+    }
+
+    /// Sign `tx` for the network identified by `network_passphrase`.
+    pub fn sign(
+        &self,
+        tx: xdr::Transaction,
+        network_passphrase: &str,
+    ) -> anyhow::Result<xdr::TransactionEnvelope> {
+        let hash = transaction_hash(&tx, network_passphrase)?;
+        let public = self.public_key();
+        let signature = xdr::DecoratedSignature {
+            hint: xdr::SignatureHint(public[28..].try_into()?),
+            signature: xdr::Signature(self.key.sign(&hash).to_bytes().to_vec().try_into()?),
+        };
+        Ok(xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx,
+            signatures: vec![signature].try_into()?,
+        }))
+    }
+}
+
+/// SHA-256 of the signature payload, which binds the transaction to one
+/// network.
+pub fn transaction_hash(tx: &xdr::Transaction, network_passphrase: &str) -> anyhow::Result<[u8; 32]> {
+    let payload = xdr::TransactionSignaturePayload {
+        network_id: xdr::Hash(Sha256::digest(network_passphrase.as_bytes()).into()),
+        tagged_transaction: xdr::TransactionSignaturePayloadTaggedTransaction::Tx(tx.clone()),
+    };
+    Ok(Sha256::digest(payload.to_xdr(xdr::Limits::none())?).into())
+}